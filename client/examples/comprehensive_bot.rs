@@ -12,7 +12,7 @@ use commonware_runtime::ThreadPool;
 use nullspace_execution::mocks::{create_account_keypair, create_network_keypair, create_seed};
 use nullspace_execution::{Layer, Memory, State};
 use nullspace_types::{
-    casino::{GameSession, GameType, SuperModeState},
+    casino::{derive_session_id, GameSession, GameType, SuperModeState, FAUCET_AMOUNT},
     execution::{Event, Instruction, Key, Transaction, Value},
     NAMESPACE,
 };
@@ -56,9 +56,11 @@ impl Context {
         })
         .await;
 
-        // Give player lots of chips
-        ctx.execute(Instruction::CasinoDeposit { amount: 1_000_000 })
-            .await;
+        // Claim the faucet
+        ctx.execute(Instruction::CasinoDeposit {
+            amount: FAUCET_AMOUNT,
+        })
+        .await;
 
         ctx
     }
@@ -66,7 +68,7 @@ impl Context {
     /// Execute a single instruction as a transaction/block
     async fn execute(&mut self, instruction: Instruction) -> Vec<Event> {
         let seed = create_seed(&self.network_secret, self.view);
-        let mut layer = Layer::new(&self.state, self.network_public.clone(), NAMESPACE, seed);
+        let mut layer = Layer::new(&self.state, self.network_public.clone(), NAMESPACE, seed).await;
 
         let tx = Transaction::sign(&self.player_secret, self.nonce, instruction);
         let (outputs, _) = layer.execute(self.pool.clone(), vec![tx]).await;
@@ -100,7 +102,8 @@ impl Context {
     }
 
     fn get_session_id(&self) -> u64 {
-        self.view // Simple unique ID
+        // The ID a session started by the next instruction must use
+        derive_session_id(&self.player_public, self.nonce)
     }
 }
 
@@ -117,6 +120,7 @@ async fn test_baccarat(ctx: &mut Context) {
                 game_type: GameType::Baccarat,
                 bet: 100,
                 session_id,
+                commitment: None,
             })
             .await;
         assert!(matches!(events[0], Event::CasinoGameStarted { .. }));
@@ -145,6 +149,7 @@ async fn test_blackjack(ctx: &mut Context) {
                 game_type: GameType::Blackjack,
                 bet: 100,
                 session_id,
+                commitment: None,
             })
             .await;
 
@@ -172,6 +177,7 @@ async fn test_blackjack(ctx: &mut Context) {
                 game_type: GameType::Blackjack,
                 bet: 100,
                 session_id,
+                commitment: None,
             })
             .await;
 
@@ -198,6 +204,7 @@ async fn test_casino_war(ctx: &mut Context) {
             game_type: GameType::CasinoWar,
             bet: 100,
             session_id,
+            commitment: None,
         })
         .await;
 
@@ -226,6 +233,9 @@ async fn test_casino_war(ctx: &mut Context) {
             super_mode: SuperModeState::default(),
             is_tournament: false,
             tournament_id: None,
+            last_move_at: 0,
+            wagered: 100,
+            entropy: None,
         };
         ctx.inject_session(session).await;
 
@@ -253,6 +263,9 @@ async fn test_casino_war(ctx: &mut Context) {
             super_mode: SuperModeState::default(),
             is_tournament: false,
             tournament_id: None,
+            last_move_at: 0,
+            wagered: 100,
+            entropy: None,
         };
         ctx.inject_session(session).await;
 
@@ -274,6 +287,7 @@ async fn test_craps(ctx: &mut Context) {
         game_type: GameType::Craps,
         bet: 1, // Craps starts with >0 bet to pass check
         session_id,
+        commitment: None,
     })
     .await;
 
@@ -330,6 +344,7 @@ async fn test_video_poker(ctx: &mut Context) {
         game_type: GameType::VideoPoker,
         bet: 100,
         session_id,
+        commitment: None,
     })
     .await;
 
@@ -349,6 +364,7 @@ async fn test_hilo(ctx: &mut Context) {
         game_type: GameType::HiLo,
         bet: 100,
         session_id,
+        commitment: None,
     })
     .await;
 
@@ -384,6 +400,7 @@ async fn test_roulette(ctx: &mut Context) {
         game_type: GameType::Roulette,
         bet: 100,
         session_id,
+        commitment: None,
     })
     .await;
 
@@ -403,6 +420,7 @@ async fn test_sic_bo(ctx: &mut Context) {
         game_type: GameType::SicBo,
         bet: 100,
         session_id,
+        commitment: None,
     })
     .await;
 
@@ -422,6 +440,7 @@ async fn test_three_card(ctx: &mut Context) {
         game_type: GameType::ThreeCard,
         bet: 100,
         session_id,
+        commitment: None,
     })
     .await;
 
@@ -444,6 +463,7 @@ async fn test_ultimate_holdem(ctx: &mut Context) {
             game_type: GameType::UltimateHoldem,
             bet: 100,
             session_id,
+            commitment: None,
         })
         .await;
 
@@ -15,7 +15,7 @@ use commonware_cryptography::{
 };
 use nullspace_client::Client;
 use nullspace_types::{
-    casino::GameType,
+    casino::{derive_session_id, GameType},
     execution::{Instruction, Key, Transaction, Value},
     Identity,
 };
@@ -103,22 +103,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     time::sleep(Duration::from_secs(2)).await;
 
     // 3. Game Loop (High Stakes Baccarat - Banker Bet)
-    let mut session_id = 900000;
+    let mut rounds = 0u64;
     let duration = Duration::from_secs(60);
     let start = Instant::now();
 
     while start.elapsed() < duration {
         let bet = 1000; // High bet
-        session_id += 1;
+        rounds += 1;
 
-        // Start Baccarat
+        // Start Baccarat (its session ID is derived from the nonce)
+        let nonce = bot.next_nonce();
+        let session_id = derive_session_id(&bot.keypair.public_key(), nonce);
         let tx = Transaction::sign(
             &bot.keypair,
-            bot.next_nonce(),
+            nonce,
             Instruction::CasinoStartGame {
                 game_type: GameType::Baccarat,
                 bet,
                 session_id,
+                commitment: None,
             },
         );
         flush_tx(&client, tx).await;
@@ -152,13 +155,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         time::sleep(Duration::from_millis(500)).await;
 
         // Check PnL
-        if session_id % 10 == 0 {
+        if rounds % 10 == 0 {
             if let Some(lookup) = client
                 .query_state(&Key::CasinoPlayer(bot.keypair.public_key()))
                 .await?
             {
                 if let Some(Value::CasinoPlayer(p)) = lookup.operation.value() {
-                    info!("Current Chips: {}", p.chips);
+                    info!("Current Chips: {}", p.chips.0);
                 }
             }
         }
@@ -18,10 +18,9 @@ use nullspace_client::{Client, Error};
 use nullspace_execution::mocks::{create_account_keypair, create_network_keypair};
 use nullspace_types::{
     api::{Update, UpdatesFilter},
-    casino::GameType,
+    casino::{derive_session_id, GameType},
     execution::{Event, Instruction, Output, Transaction},
 };
-use std::time::{SystemTime, UNIX_EPOCH};
 
 const NODE_URL: &str = "http://localhost:3000";
 
@@ -93,7 +92,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Deposited 1000 chips.");
 
     // 4. Start HiLo Game
-    let session_id = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos() as u64;
+    let session_id = derive_session_id(&public_key, nonce);
     println!("Starting HiLo Game (Session ID: {})...", session_id);
 
     submit_tx(
@@ -104,6 +103,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             game_type: GameType::HiLo,
             bet: 10,
             session_id,
+            commitment: None,
         },
     )
     .await?;
@@ -123,7 +123,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             for (_, keyless_output) in events.events_proof_ops {
                 // Keyless is an enum
                 if let Keyless::Append(output) = keyless_output {
-                    if let Output::Event(event) = output {
+                    if let Output::Event(event) | Output::VersionedEvent { event, .. } = output {
                         match event {
                             Event::CasinoGameStarted {
                                 session_id: sid,
@@ -19,7 +19,7 @@ use commonware_storage::store::operation::Keyless;
 use nullspace_client::Client;
 use nullspace_types::{
     api::{Update, UpdatesFilter},
-    casino::{AmmPool, GameType, HouseState},
+    casino::{derive_session_id, AmmPool, CasinoErrorCode, GameType, HouseState, PairId},
     execution::{Event, Instruction, Key, Output, Transaction, Value}, // Added Output/Event
     Identity,
 };
@@ -237,9 +237,9 @@ async fn run_keeper(client: Arc<Client>, bot: Arc<Bot>, duration: Duration) {
 
 // === Bootstrap AMM Liquidity (Central Bank style) ===
 async fn bootstrap_amm(client: Arc<Client>, bot: Arc<Bot>) {
-    let seeded = match client.query_state(&Key::AmmPool).await {
+    let seeded = match client.query_state(&Key::AmmPool(PairId::RNG_VUSDT)).await {
         Ok(Some(lookup)) => {
-            matches!(lookup.operation.value(), Some(Value::AmmPool(p)) if p.reserve_rng > 0 && p.reserve_vusdt > 0)
+            matches!(lookup.operation.value(), Some(Value::AmmPool(p)) if p.reserve_rng.0 > 0 && p.reserve_vusdt.0 > 0)
         }
         _ => false,
     };
@@ -310,6 +310,7 @@ async fn bootstrap_amm(client: Arc<Client>, bot: Arc<Bot>) {
             &bot.keypair,
             bot.next_nonce(),
             Instruction::AddLiquidity {
+                pair: PairId::RNG_VUSDT,
                 rng_amount: INITIAL_POOL_RNG,
                 usdt_amount: INITIAL_POOL_VUSD,
             },
@@ -320,13 +321,13 @@ async fn bootstrap_amm(client: Arc<Client>, bot: Arc<Bot>) {
     // Wait for block inclusion
     let mut seeded = false;
     for _ in 0..20 {
-        if let Ok(Some(lookup)) = client.query_state(&Key::AmmPool).await {
+        if let Ok(Some(lookup)) = client.query_state(&Key::AmmPool(PairId::RNG_VUSDT)).await {
             if let Some(Value::AmmPool(p)) = lookup.operation.value() {
                 info!(
                     "AMM seeded: reserves {} RNG / {} vUSD, shares {}",
-                    p.reserve_rng, p.reserve_vusdt, p.total_shares
+                    p.reserve_rng.0, p.reserve_vusdt.0, p.total_shares.0
                 );
-                seeded = p.reserve_rng > 0 && p.reserve_vusdt > 0;
+                seeded = p.reserve_rng.0 > 0 && p.reserve_vusdt.0 > 0;
                 break;
             }
         }
@@ -396,6 +397,7 @@ async fn run_whale(client: Arc<Client>, bot: Arc<Bot>, duration: Duration) {
             &bot.keypair,
             bot.next_nonce(),
             Instruction::AddLiquidity {
+                pair: PairId::RNG_VUSDT,
                 rng_amount: 5_000_000,
                 usdt_amount: 5_000_000,
             },
@@ -422,6 +424,7 @@ async fn run_whale(client: Arc<Client>, bot: Arc<Bot>, duration: Duration) {
                         &bot.keypair,
                         bot.next_nonce(),
                         Instruction::Swap {
+                            pair: PairId::RNG_VUSDT,
                             amount_in: amount,
                             min_amount_out: 0,
                             is_buying_rng: true,
@@ -444,6 +447,7 @@ async fn run_whale(client: Arc<Client>, bot: Arc<Bot>, duration: Duration) {
                             &bot.keypair,
                             bot.next_nonce(),
                             Instruction::Swap {
+                                pair: PairId::RNG_VUSDT,
                                 amount_in: amount,
                                 min_amount_out: 0,
                                 is_buying_rng: false,
@@ -515,6 +519,7 @@ async fn run_retail(client: Arc<Client>, bot: Arc<Bot>, duration: Duration) {
                 &bot.keypair,
                 bot.next_nonce(),
                 Instruction::Swap {
+                    pair: PairId::RNG_VUSDT,
                     amount_in: amount / 2,
                     min_amount_out: 0,
                     is_buying_rng: true,
@@ -531,6 +536,7 @@ async fn run_retail(client: Arc<Client>, bot: Arc<Bot>, duration: Duration) {
                     &bot.keypair,
                     bot.next_nonce(),
                     Instruction::Swap {
+                        pair: PairId::RNG_VUSDT,
                         amount_in: amount,
                         min_amount_out: 0,
                         is_buying_rng: buy,
@@ -540,16 +546,18 @@ async fn run_retail(client: Arc<Client>, bot: Arc<Bot>, duration: Duration) {
             .await;
         } else {
             // Play Game
-            let session_id = rng.gen::<u64>();
+            let nonce = bot.next_nonce();
+            let session_id = derive_session_id(&bot.public_key(), nonce);
             flush_tx(
                 &client,
                 Transaction::sign(
                     &bot.keypair,
-                    bot.next_nonce(),
+                    nonce,
                     Instruction::CasinoStartGame {
                         game_type: GameType::Blackjack,
                         bet: 500,
                         session_id,
+                        commitment: None,
                     },
                 ),
             )
@@ -573,7 +581,6 @@ async fn run_retail(client: Arc<Client>, bot: Arc<Bot>, duration: Duration) {
 // === Maximizer Bot ===
 async fn run_maximizer(client: Arc<Client>, bot: Arc<Bot>, duration: Duration) {
     let start = Instant::now();
-    let mut rng = StdRng::from_entropy();
     info!("Maximizer: Started.");
 
     flush_tx(
@@ -603,17 +610,19 @@ async fn run_maximizer(client: Arc<Client>, bot: Arc<Bot>, duration: Duration) {
         // Strategy: High volume Baccarat Banker bets to farm House Edge distribution (via Staking)
         // Also stake frequently.
 
-        let session_id = rng.gen::<u64>();
+        let nonce = bot.next_nonce();
+        let session_id = derive_session_id(&bot.public_key(), nonce);
         let mut txs = Vec::new();
 
         // 1. Play
         txs.push(Transaction::sign(
             &bot.keypair,
-            bot.next_nonce(),
+            nonce,
             Instruction::CasinoStartGame {
                 game_type: GameType::Baccarat,
                 bet: 2000,
                 session_id,
+                commitment: None,
             },
         ));
         txs.push(Transaction::sign(
@@ -681,15 +690,17 @@ async fn run_tournament_grinder(client: Arc<Client>, bot: Arc<Bot>, duration: Du
 
         // Play to accumulate chips
         if rng.gen_bool(0.5) {
-            let session_id = rng.gen::<u64>();
+            let nonce = bot.next_nonce();
+            let session_id = derive_session_id(&bot.public_key(), nonce);
             let mut txs = Vec::new();
             txs.push(Transaction::sign(
                 &bot.keypair,
-                bot.next_nonce(),
+                nonce,
                 Instruction::CasinoStartGame {
                     game_type: GameType::Baccarat,
                     bet: 1000,
                     session_id,
+                    commitment: None,
                 },
             ));
             txs.push(Transaction::sign(
@@ -890,7 +901,7 @@ async fn run_monitor(
             let mut tx_count = 0;
 
             // Fetch current AMM state for price conversion
-            let amm = match client.query_state(&Key::AmmPool).await {
+            let amm = match client.query_state(&Key::AmmPool(PairId::RNG_VUSDT)).await {
                 Ok(Some(lookup)) => {
                     if let Some(Value::AmmPool(p)) = lookup.operation.value() {
                         Some(p.clone())
@@ -901,20 +912,20 @@ async fn run_monitor(
                 _ => None,
             };
             let a = amm.clone().unwrap_or_default();
-            let price = if a.reserve_rng > 0 && a.reserve_vusdt > 0 {
-                let p = a.reserve_vusdt as f64 / a.reserve_rng as f64;
+            let price = if a.reserve_rng.0 > 0 && a.reserve_vusdt.0 > 0 {
+                let p = a.reserve_vusdt.0 as f64 / a.reserve_rng.0 as f64;
                 last_price = p;
                 p
             } else {
                 last_price
             };
-            let pool_tvl_vusdt = a.reserve_vusdt as f64 + a.reserve_rng as f64 * price;
-            let lp_share_price_vusdt = if a.total_shares > 0 {
-                pool_tvl_vusdt / a.total_shares as f64
+            let pool_tvl_vusdt = a.reserve_vusdt.0 as f64 + a.reserve_rng.0 as f64 * price;
+            let lp_share_price_vusdt = if a.total_shares.0 > 0 {
+                pool_tvl_vusdt / a.total_shares.0 as f64
             } else {
                 0.0
             };
-            let amm_invariant_k = a.reserve_rng as u128 * a.reserve_vusdt as u128;
+            let amm_invariant_k = a.reserve_rng.0 as u128 * a.reserve_vusdt.0 as u128;
             let submit_failures_delta = SUBMIT_FAILURES
                 .load(Ordering::Relaxed)
                 .saturating_sub(last_submit_failures);
@@ -923,28 +934,29 @@ async fn run_monitor(
             let mut liquidity_shares_removed = metrics.liquidity_shares_removed;
 
             if let Some(prev) = &last_amm {
-                if a.total_shares > prev.total_shares {
-                    if prev.total_shares == 0 {
-                        liquidity_rng_added = liquidity_rng_added.saturating_add(a.reserve_rng);
-                        liquidity_vusd_added = liquidity_vusd_added.saturating_add(a.reserve_vusdt);
+                if a.total_shares.0 > prev.total_shares.0 {
+                    if prev.total_shares.0 == 0 {
+                        liquidity_rng_added = liquidity_rng_added.saturating_add(a.reserve_rng.0);
+                        liquidity_vusd_added =
+                            liquidity_vusd_added.saturating_add(a.reserve_vusdt.0);
                     } else {
-                        let share_delta = a.total_shares - prev.total_shares;
-                        let add_rng = ((share_delta as u128 * prev.reserve_rng as u128)
-                            / prev.total_shares as u128)
+                        let share_delta = a.total_shares.0 - prev.total_shares.0;
+                        let add_rng = ((share_delta as u128 * prev.reserve_rng.0 as u128)
+                            / prev.total_shares.0 as u128)
                             as u64;
-                        let add_vusd = ((share_delta as u128 * prev.reserve_vusdt as u128)
-                            / prev.total_shares as u128)
+                        let add_vusd = ((share_delta as u128 * prev.reserve_vusdt.0 as u128)
+                            / prev.total_shares.0 as u128)
                             as u64;
                         liquidity_rng_added = liquidity_rng_added.saturating_add(add_rng);
                         liquidity_vusd_added = liquidity_vusd_added.saturating_add(add_vusd);
                     }
-                } else if a.total_shares < prev.total_shares {
-                    liquidity_shares_removed =
-                        liquidity_shares_removed.saturating_add(prev.total_shares - a.total_shares);
+                } else if a.total_shares.0 < prev.total_shares.0 {
+                    liquidity_shares_removed = liquidity_shares_removed
+                        .saturating_add(prev.total_shares.0 - a.total_shares.0);
                 }
-            } else if a.total_shares > 0 {
-                liquidity_rng_added = liquidity_rng_added.saturating_add(a.reserve_rng);
-                liquidity_vusd_added = liquidity_vusd_added.saturating_add(a.reserve_vusdt);
+            } else if a.total_shares.0 > 0 {
+                liquidity_rng_added = liquidity_rng_added.saturating_add(a.reserve_rng.0);
+                liquidity_vusd_added = liquidity_vusd_added.saturating_add(a.reserve_vusdt.0);
             }
 
             for op in events.events_proof_ops {
@@ -982,7 +994,7 @@ async fn run_monitor(
                                     }
                                 }
                                 Instruction::Stake { amount, .. } => metrics.stakes_in += *amount,
-                                Instruction::Unstake => metrics.unstake_actions += 1,
+                                Instruction::Unstake { .. } => metrics.unstake_actions += 1,
                                 Instruction::ClaimRewards => metrics.claim_actions += 1,
                                 Instruction::DepositCollateral { amount } => {
                                     metrics.vault_collateral += *amount
@@ -994,17 +1006,19 @@ async fn run_monitor(
                                 Instruction::AddLiquidity {
                                     rng_amount,
                                     usdt_amount,
+                                    ..
                                 } => {
                                     metrics.liquidity_rng_added += *rng_amount;
                                     metrics.liquidity_vusd_added += *usdt_amount;
                                 }
-                                Instruction::RemoveLiquidity { shares } => {
+                                Instruction::RemoveLiquidity { shares, .. } => {
                                     metrics.liquidity_shares_removed += *shares
                                 }
                                 Instruction::CasinoStartGame {
                                     bet,
                                     game_type,
                                     session_id,
+                                    ..
                                 } => {
                                     // Determine if this is a freeroll tournament session
                                     let is_tournament = match client
@@ -1072,7 +1086,7 @@ async fn run_monitor(
                                 _ => {}
                             }
                         }
-                        Output::Event(ev) => match ev {
+                        Output::Event(ev) | Output::VersionedEvent { event: ev, .. } => match ev {
                             Event::CasinoGameCompleted {
                                 session_id,
                                 payout,
@@ -1118,40 +1132,40 @@ async fn run_monitor(
                             Event::CasinoError { error_code, .. } => {
                                 metrics.errors += 1;
                                 match error_code {
-                                    nullspace_types::casino::ERROR_INVALID_MOVE => {
+                                    CasinoErrorCode::InvalidMove => {
                                         metrics.errors_invalid_move += 1;
                                     }
-                                    nullspace_types::casino::ERROR_INVALID_BET => {
+                                    CasinoErrorCode::InvalidBet => {
                                         metrics.errors_invalid_bet += 1;
                                     }
-                                    nullspace_types::casino::ERROR_INSUFFICIENT_FUNDS => {
+                                    CasinoErrorCode::InsufficientFunds => {
                                         metrics.errors_insufficient += 1;
                                     }
-                                    nullspace_types::casino::ERROR_PLAYER_NOT_FOUND => {
+                                    CasinoErrorCode::PlayerNotFound => {
                                         metrics.errors_player_not_found += 1;
                                     }
-                                    nullspace_types::casino::ERROR_SESSION_EXISTS => {
+                                    CasinoErrorCode::SessionExists => {
                                         metrics.errors_session_exists += 1;
                                     }
-                                    nullspace_types::casino::ERROR_SESSION_NOT_FOUND => {
+                                    CasinoErrorCode::SessionNotFound => {
                                         metrics.errors_session_not_found += 1;
                                     }
-                                    nullspace_types::casino::ERROR_SESSION_NOT_OWNED => {
+                                    CasinoErrorCode::SessionNotOwned => {
                                         metrics.errors_session_not_owned += 1;
                                     }
-                                    nullspace_types::casino::ERROR_SESSION_COMPLETE => {
+                                    CasinoErrorCode::SessionComplete => {
                                         metrics.errors_session_complete += 1;
                                     }
-                                    nullspace_types::casino::ERROR_TOURNAMENT_NOT_REGISTERING => {
+                                    CasinoErrorCode::TournamentNotRegistering => {
                                         metrics.errors_tournament_not_registering += 1;
                                     }
-                                    nullspace_types::casino::ERROR_ALREADY_IN_TOURNAMENT => {
+                                    CasinoErrorCode::AlreadyInTournament => {
                                         metrics.errors_already_in_tournament += 1;
                                     }
-                                    nullspace_types::casino::ERROR_TOURNAMENT_LIMIT_REACHED => {
+                                    CasinoErrorCode::TournamentLimitReached => {
                                         metrics.errors_tournament_limit_reached += 1;
                                     }
-                                    nullspace_types::casino::ERROR_RATE_LIMITED => {
+                                    CasinoErrorCode::RateLimited => {
                                         metrics.errors_rate_limited += 1;
                                     }
                                     _ => metrics.errors_other += 1,
@@ -1246,11 +1260,11 @@ async fn run_monitor(
                         .await
                     {
                         if let Some(Value::Vault(v)) = vault_lookup.operation.value() {
-                            max_debt = v.debt_vusdt;
+                            max_debt = v.debt_vusdt.0;
                         }
                     }
-                    let vusdt_val = p.vusdt_balance as f64;
-                    let rng_val = (p.chips as f64) * price;
+                    let vusdt_val = p.vusdt_balance.0 as f64;
+                    let rng_val = (p.chips.0 as f64) * price;
                     max_nw = (rng_val + vusdt_val - max_debt as f64).round() as i64;
                 }
             }
@@ -1266,8 +1280,8 @@ async fn run_monitor(
                 rng_price: price,
                 total_burned: current_house.total_burned,
                 total_issuance: current_house.total_issuance,
-                amm_rng: a.reserve_rng,
-                amm_vusdt: a.reserve_vusdt,
+                amm_rng: a.reserve_rng.0,
+                amm_vusdt: a.reserve_vusdt.0,
                 maximizer_nw: max_nw,
                 tx_count,
                 volume_vusdt,
@@ -1275,7 +1289,7 @@ async fn run_monitor(
                 burn_rng: burn_delta,
                 mint_rng: mint_delta,
                 epoch: current_house.current_epoch,
-                lp_shares: a.total_shares,
+                lp_shares: a.total_shares.0,
                 total_staked: current_house.total_staked_amount,
                 swap_count: metrics.swap_count,
                 buy_volume_vusdt: metrics.buy_volume_vusdt,
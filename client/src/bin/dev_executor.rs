@@ -13,8 +13,12 @@ use commonware_runtime::{tokio as cw_tokio, Runner};
 use futures_util::StreamExt;
 use nullspace_client::Client;
 use nullspace_execution::mocks::{create_adbs, create_network_keypair, execute_block};
-use nullspace_types::{api, execution::Transaction, Identity};
-use std::time::Duration;
+use nullspace_types::{
+    api,
+    execution::{instruction_catalog, Transaction},
+    Identity,
+};
+use std::{collections::BTreeMap, time::Duration};
 use tokio_tungstenite::tungstenite::Message;
 use tracing::{info, warn};
 
@@ -34,6 +38,19 @@ struct Args {
     block_interval_ms: u64,
 }
 
+/// The number of transactions of each instruction kind (e.g. `CasinoGameMove=3, Swap=1`).
+fn instruction_counts(txs: &[Transaction]) -> String {
+    let mut counts = BTreeMap::new();
+    for tx in txs {
+        *counts.entry(tx.instruction.info().kind).or_insert(0usize) += 1;
+    }
+    counts
+        .into_iter()
+        .map(|(kind, count)| format!("{}={count}", instruction_catalog()[kind as usize].name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Parse args
     let args = Args::parse();
@@ -193,7 +210,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         last_block_time = std::time::Instant::now();
                     } else {
                         let txs = std::mem::take(&mut pending_txs);
-                        info!(count = txs.len(), view, elapsed_ms = elapsed.as_millis(), "Executing block");
+                        info!(count = txs.len(), view, elapsed_ms = elapsed.as_millis(), instructions = %instruction_counts(&txs), "Executing block");
 
                         // Execute block
                         let (seed, summary) = execute_block(
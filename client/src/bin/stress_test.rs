@@ -18,7 +18,7 @@ use commonware_cryptography::{
 };
 use nullspace_client::Client;
 use nullspace_types::{
-    casino::GameType,
+    casino::{derive_session_id, GameType},
     execution::{Instruction, Key, Transaction, Value},
     Identity,
 };
@@ -57,7 +57,6 @@ struct BotState {
     keypair: PrivateKey,
     name: String,
     nonce: AtomicU64,
-    session_counter: AtomicU64,
     games_played: AtomicU64,
 }
 
@@ -68,7 +67,6 @@ impl BotState {
             keypair,
             name: format!("Bot{:04}", id),
             nonce: AtomicU64::new(0),
-            session_counter: AtomicU64::new(id as u64 * 1_000_000),
             games_played: AtomicU64::new(0),
         }
     }
@@ -77,10 +75,6 @@ impl BotState {
         self.nonce.fetch_add(1, Ordering::Relaxed)
     }
 
-    fn next_session_id(&self) -> u64 {
-        self.session_counter.fetch_add(1, Ordering::Relaxed)
-    }
-
     fn public_key(&self) -> PublicKey {
         self.keypair.public_key()
     }
@@ -218,6 +212,70 @@ fn generate_move_payload(game_type: GameType, rng: &mut StdRng, move_number: u32
                 vec![]
             }
         }
+        GameType::FourCard => {
+            // Deal, play 1x, then reveal: [2] / [0, 1] / [4]
+            match move_number {
+                0 => vec![2],
+                1 => vec![0, 1],
+                2 => vec![4],
+                _ => vec![],
+            }
+        }
+        GameType::Mines => {
+            match move_number {
+                // 5x5 grid with 3 mines: [0, 5, 3]
+                0 => vec![0, 5, 3],
+                // Reveal a few cells, then cash out: [1, cell] / [2]
+                1..=3 => vec![1, rng.gen_range(0..25u8)],
+                4 => vec![2],
+                _ => vec![],
+            }
+        }
+        GameType::CaribbeanStud => {
+            // Deal, raise, then reveal: [2] / [0] / [3]
+            match move_number {
+                0 => vec![2],
+                1 => vec![0],
+                2 => vec![3],
+                _ => vec![],
+            }
+        }
+        GameType::MississippiStud => {
+            // Bet 1x on each street, then reveal: [0, 1] / [2]
+            match move_number {
+                0..=2 => vec![0, 1],
+                3 => vec![2],
+                _ => vec![],
+            }
+        }
+        GameType::MoneyWheel => {
+            match move_number {
+                // Bet 1 on $1, then spin: [0, 0, amount] / [1]
+                0 => {
+                    let mut payload = vec![0, 0];
+                    payload.extend_from_slice(&1u64.to_be_bytes());
+                    payload
+                }
+                1 => vec![1],
+                _ => vec![],
+            }
+        }
+        GameType::PaiGow => {
+            if move_number == 0 {
+                // Set the hand the house way: [1]
+                vec![1]
+            } else {
+                vec![]
+            }
+        }
+        GameType::Slots => {
+            if move_number == 0 {
+                // Spin all paylines: [0, 20]
+                vec![0, 20]
+            } else {
+                vec![]
+            }
+        }
     }
 }
 
@@ -289,7 +347,7 @@ async fn run_bot(
         interval.tick().await;
 
         // Pick a random game type
-        let game_type = match rng.gen_range(0..10u8) {
+        let game_type = match rng.gen_range(0..17u8) {
             0 => GameType::Baccarat,
             1 => GameType::Blackjack,
             2 => GameType::CasinoWar,
@@ -299,20 +357,29 @@ async fn run_bot(
             6 => GameType::Roulette,
             7 => GameType::SicBo,
             8 => GameType::ThreeCard,
-            _ => GameType::UltimateHoldem,
+            9 => GameType::UltimateHoldem,
+            10 => GameType::Slots,
+            11 => GameType::Mines,
+            12 => GameType::PaiGow,
+            13 => GameType::CaribbeanStud,
+            14 => GameType::MississippiStud,
+            15 => GameType::MoneyWheel,
+            _ => GameType::FourCard,
         };
 
-        let session_id = bot.next_session_id();
         let bet = 10;
 
-        // Start game
+        // Start game (its session ID is derived from the nonce)
+        let nonce = bot.next_nonce();
+        let session_id = derive_session_id(&bot.public_key(), nonce);
         let start_tx = Transaction::sign(
             &bot.keypair,
-            bot.next_nonce(),
+            nonce,
             Instruction::CasinoStartGame {
                 game_type,
                 bet,
                 session_id,
+                commitment: None,
             },
         );
         pending_txs.push(start_tx);
@@ -358,7 +425,7 @@ async fn monitor_logic(client: Arc<Client>, bots: Vec<Arc<BotState>>, duration:
                 if let Some(Value::CasinoLeaderboard(lb)) = value {
                     info!("Leaderboard Update ({} entries):", lb.entries.len());
                     for (i, entry) in lb.entries.iter().enumerate() {
-                        info!("  #{}: {} - {} chips", i + 1, entry.name, entry.chips);
+                        info!("  #{}: {} - {} chips", i + 1, entry.name, entry.chips.0);
                     }
 
                     if lb.entries.is_empty() {
@@ -509,7 +576,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Ok(Some(lookup)) => {
             if let Some(Value::CasinoLeaderboard(lb)) = lookup.operation.value() {
                 for (i, entry) in lb.entries.iter().enumerate() {
-                    info!("  #{}: {} - {} chips", i + 1, entry.name, entry.chips);
+                    info!("  #{}: {} - {} chips", i + 1, entry.name, entry.chips.0);
                 }
             }
         }
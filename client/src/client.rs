@@ -4,7 +4,8 @@ use commonware_cryptography::{Hasher, Sha256};
 use commonware_utils::hex;
 use nullspace_types::{
     api::{
-        Lookup, Pending, Submission, Summary, Update, UpdatesFilter, MAX_SUBMISSION_TRANSACTIONS,
+        Lookup, Pending, Rejection, Submission, Summary, Update, UpdatesFilter,
+        MAX_SUBMISSION_SUMMARIES, MAX_SUBMISSION_TRANSACTIONS,
     },
     execution::{Key, Seed, Transaction},
     Identity,
@@ -183,6 +184,30 @@ impl Client {
         self.submit(submission).await
     }
 
+    /// Submit the summaries of multiple heights in a single request
+    pub async fn submit_summaries(&self, summaries: Vec<Summary>) -> Result<()> {
+        if summaries.len() > MAX_SUBMISSION_SUMMARIES {
+            return Err(Error::TooManySummaries {
+                max: MAX_SUBMISSION_SUMMARIES,
+                got: summaries.len(),
+            });
+        }
+        let submission = Submission::Summaries(summaries);
+        self.submit(submission).await
+    }
+
+    /// Report pending transactions that were refused by a validator's mempool
+    pub async fn submit_rejections(&self, rejections: Vec<Rejection>) -> Result<()> {
+        if rejections.len() > MAX_SUBMISSION_TRANSACTIONS {
+            return Err(Error::TooManyRejections {
+                max: MAX_SUBMISSION_TRANSACTIONS,
+                got: rejections.len(),
+            });
+        }
+        let submission = Submission::Rejections(rejections);
+        self.submit(submission).await
+    }
+
     pub async fn submit_seed(&self, seed: Seed) -> Result<()> {
         let submission = Submission::Seed(seed);
         self.submit(submission).await
@@ -222,6 +247,40 @@ impl Client {
         }
     }
 
+    /// Query state by key as of a specific height.
+    ///
+    /// Returns the last version of `key` written at or before `height`. Only
+    /// indexers that retain every uploaded key version can answer this.
+    pub async fn query_state_at(&self, key: &Key, height: u64) -> Result<Option<Lookup>> {
+        // Make request
+        let key_hash = Sha256::hash(&key.encode());
+        let url = self
+            .base_url
+            .join(&format!("state/{}/{height}", hex(&key_hash.encode())))?;
+        let response = self.get_with_retry(url).await?;
+
+        // Parse response
+        match response.status() {
+            reqwest::StatusCode::OK => {
+                let buf = response.bytes().await?.to_vec();
+                let lookup = Lookup::decode(&mut buf.as_slice())?;
+
+                // Verify the lookup
+                if let Err(err) = lookup.verify(&self.identity) {
+                    debug!(?err, "Lookup verification failed");
+                    return Err(Error::InvalidSignature);
+                }
+                if lookup.progress.height > height {
+                    return Err(Error::UnexpectedResponse);
+                }
+
+                Ok(Some(lookup))
+            }
+            reqwest::StatusCode::NOT_FOUND => Ok(None),
+            _ => Err(Error::Failed(response.status())),
+        }
+    }
+
     /// Connect to the updates stream with the specified filter
     pub async fn connect_updates(&self, filter: UpdatesFilter) -> Result<Stream<Update>> {
         let encoded_filter = hex(&filter.encode());
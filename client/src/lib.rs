@@ -18,6 +18,10 @@ pub enum Error {
     Failed(reqwest::StatusCode),
     #[error("too many transactions in one submission: {got} (max {max})")]
     TooManyTransactions { max: usize, got: usize },
+    #[error("too many summaries in one submission: {got} (max {max})")]
+    TooManySummaries { max: usize, got: usize },
+    #[error("too many rejections in one submission: {got} (max {max})")]
+    TooManyRejections { max: usize, got: usize },
     #[error("invalid data: {0}")]
     InvalidData(#[from] commonware_codec::Error),
     #[error("invalid signature")]
@@ -262,6 +266,12 @@ mod tests {
         };
         assert_eq!(account.nonce, 1);
 
+        // Query for account state at a specific height
+        let lookup = client.query_state_at(&account_key, 1).await.unwrap();
+        assert_eq!(lookup.unwrap().progress.height, 1);
+        let result = client.query_state_at(&account_key, 0).await.unwrap();
+        assert!(result.is_none());
+
         // Query for non-existent account
         let (_, other_public) = create_account_keypair(2);
         let other_key = Key::Account(other_public);
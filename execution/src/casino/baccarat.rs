@@ -2,14 +2,42 @@
 //!
 //! State blob format:
 //! [bet_count:u8] [bets:BaccaratBet×count] [playerHandLen:u8] [playerCards:u8×n] [bankerHandLen:u8] [bankerCards:u8×n]
+//! [historyLen:u8] [history:RoundRecord×n] [betReturns:u64 BE×count] [shoe:Shoe] [squeeze:u8]
 //!
 //! Each BaccaratBet (9 bytes):
 //! [bet_type:u8] [amount:u64 BE]
 //!
+//! Each RoundRecord (2 bytes), oldest first (at most the last 72 rounds of the session):
+//! [flags:u8] [totals:u8]
+//! flags: bit 0 = player pair, bit 1 = banker pair, bit 2 = natural
+//! totals: player total << 4 | banker total
+//!
+//! The history is the scoreboard the Bead Plate and Big Road are derived from. Once the cards are
+//! dealt, `betReturns` holds what each bet returned (stake plus winnings, in bet order, before any
+//! super multiplier): 0 is a loss and the bet amount is a push. Blobs written before the history
+//! and returns were recorded simply end after the banker cards.
+//!
+//! Tables configured with a persistent shoe keep it at the end of the blob, and deal every round
+//! of the session from it (see [super::shoe]):
+//! [decks:u8] [penetration:u8] [counts:u8×52] (copies of each card left in the shoe)
+//!
+//! A round dealt with a squeeze ([5]) reveals its third cards over several moves, and `squeeze`
+//! (present only until they are all revealed) counts the squeeze steps taken so far. Each third
+//! card, the player's first, takes three steps: peek at one corner, peek at the other, flip it.
+//! The squeeze only stages the reveal: every card is in the state from the deal (so the result is
+//! fixed there), and it is up to clients to keep the third cards face down until they are flipped.
+//!
 //! Payload format:
-//! [0, bet_type, amount_bytes...] - Place bet (adds to pending bets)
-//! [1] - Deal cards and resolve all bets
+//! [0, bet_type, amount_bytes...] - Place bet (adds to pending bets; after [3], starts the next round)
+//! [1] - Deal cards and resolve all bets (ends the session)
 //! [2] - Clear all pending bets
+//! [3] - Deal cards and resolve all bets, crediting the return and keeping the session open for
+//!       another round
+//! [4, rounds] - Deal `rounds` rounds (auto-play) with the bets placed, each round staking them
+//!               again, and settle all the rounds together (ends the session)
+//! [5] - Deal cards with a squeeze: the bets are resolved as the last third card is flipped (or
+//!       at once if neither hand draws), ending the session
+//! [6] - Take the next step of the squeeze
 //!
 //! Bet types:
 //! 0 = Player (1:1)
@@ -18,28 +46,45 @@
 //! 3 = Player Pair (11:1)
 //! 4 = Banker Pair (11:1)
 //! 5 = Lucky 6 (banker wins with total 6)
-
+//! 6 = Perfect Pair (25:1 for a suited pair in either hand, 200:1 in both)
+//! 7 = Player Dragon Bonus (player wins: 1:1 natural, up to 30:1 by margin; natural tie pushes)
+//! 8 = Banker Dragon Bonus (same pay table for the banker hand)
+
+use super::shoe::{CardSource, Shoe};
+use super::state_blob::{
+    impl_blob_field_enum, BlobField, BlobReader, BlobWriter, StateBlob, UNVERSIONED,
+};
 use super::super_mode::apply_super_multiplier_cards;
-use super::{CasinoGame, GameError, GameResult, GameRng};
-use nullspace_types::casino::GameSession;
+use super::{CasinoGame, GameError, GameResult, GameRng, TableEvent};
+use nullspace_types::casino::{
+    GameConfig, GameSession, Seat, BACCARAT_DRAGON_BONUS_PAYTABLE, BACCARAT_DRAGON_NATURAL_PAYOUT,
+    BACCARAT_PAIR_PAYOUT, BACCARAT_PERFECT_PAIR_BOTH_PAYOUT, BACCARAT_PERFECT_PAIR_PAYOUT,
+};
 
 /// Maximum cards in a Baccarat hand (2-3 cards per hand).
 const MAX_HAND_SIZE: usize = 3;
 /// Maximum number of bets per session (one of each type).
-const MAX_BETS: usize = 6;
+const MAX_BETS: usize = 9;
+/// Rounds kept on the scoreboard (a full 6×12 Bead Plate).
+const MAX_HISTORY: usize = 72;
 /// WoO notes Baccarat is usually dealt from eight decks.
 const BACCARAT_DECKS: u8 = 8;
+/// Squeeze steps to reveal a third card (peek at one corner, peek at the other, flip).
+const SQUEEZE_STEPS: u8 = 3;
 
 /// Bet types in Baccarat.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BetType {
-    Player = 0,     // 1:1
-    Banker = 1,     // 0.95:1 (5% commission)
-    Tie = 2,        // 8:1
-    PlayerPair = 3, // 11:1
-    BankerPair = 4, // 11:1
-    Lucky6 = 5,     // 12:1 (2-card), 23:1 (3-card)
+    Player = 0,       // 1:1
+    Banker = 1,       // 0.95:1 (5% commission)
+    Tie = 2,          // 8:1
+    PlayerPair = 3,   // 11:1
+    BankerPair = 4,   // 11:1
+    Lucky6 = 5,       // 12:1 (2-card), 23:1 (3-card)
+    PerfectPair = 6,  // 25:1 (either hand), 200:1 (both)
+    PlayerDragon = 7, // 1:1 (natural), up to 30:1 (by margin)
+    BankerDragon = 8, // 1:1 (natural), up to 30:1 (by margin)
 }
 
 impl TryFrom<u8> for BetType {
@@ -53,11 +98,16 @@ impl TryFrom<u8> for BetType {
             3 => Ok(BetType::PlayerPair),
             4 => Ok(BetType::BankerPair),
             5 => Ok(BetType::Lucky6),
+            6 => Ok(BetType::PerfectPair),
+            7 => Ok(BetType::PlayerDragon),
+            8 => Ok(BetType::BankerDragon),
             _ => Err(GameError::InvalidPayload),
         }
     }
 }
 
+impl_blob_field_enum!(BetType);
+
 /// Get card value for Baccarat (0-9).
 /// Face cards and 10s = 0, Ace = 1, others = face value.
 fn card_value(card: u8) -> u8 {
@@ -84,6 +134,33 @@ fn is_pair(cards: &[u8]) -> bool {
     cards.len() >= 2 && card_rank(cards[0]) == card_rank(cards[1])
 }
 
+/// Check if first two cards are a perfect pair (the same card, which the shoe allows).
+fn is_perfect_pair(cards: &[u8]) -> bool {
+    cards.len() >= 2 && cards[0] == cards[1]
+}
+
+/// Check if a hand is a natural (8 or 9 on the first two cards).
+fn is_natural(cards: &[u8]) -> bool {
+    cards.len() == 2 && hand_total(cards) >= 8
+}
+
+/// Dragon Bonus winnings multiplier for a side (`None` for a push, `Some(0)` for a loss).
+fn dragon_multiplier(own_cards: &[u8], other_cards: &[u8]) -> Option<u64> {
+    let own_total = hand_total(own_cards);
+    let other_total = hand_total(other_cards);
+    if is_natural(own_cards) {
+        return match own_total.cmp(&other_total) {
+            std::cmp::Ordering::Greater => Some(BACCARAT_DRAGON_NATURAL_PAYOUT),
+            std::cmp::Ordering::Equal => None,
+            std::cmp::Ordering::Less => Some(0),
+        };
+    }
+    if own_total <= other_total || is_natural(other_cards) {
+        return Some(0);
+    }
+    Some(BACCARAT_DRAGON_BONUS_PAYTABLE[(own_total - other_total) as usize])
+}
+
 /// Individual bet in baccarat.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BaccaratBet {
@@ -91,31 +168,78 @@ pub struct BaccaratBet {
     pub amount: u64,
 }
 
-impl BaccaratBet {
-    /// Serialize to 9 bytes: [bet_type:u8] [amount:u64 BE]
-    fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::with_capacity(9);
-        bytes.push(self.bet_type as u8);
-        bytes.extend_from_slice(&self.amount.to_be_bytes());
-        bytes
+impl BlobField for BaccaratBet {
+    fn write(&self, writer: &mut BlobWriter) {
+        writer.put(&self.bet_type).put(&self.amount);
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        Some(BaccaratBet {
+            bet_type: reader.get()?,
+            amount: reader.get()?,
+        })
+    }
+}
+
+/// One dealt round on the scoreboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct RoundRecord {
+    player_total: u8,
+    banker_total: u8,
+    player_pair: bool,
+    banker_pair: bool,
+    natural: bool,
+}
+
+impl RoundRecord {
+    fn new(player_cards: &[u8], banker_cards: &[u8]) -> Self {
+        RoundRecord {
+            player_total: hand_total(player_cards),
+            banker_total: hand_total(banker_cards),
+            player_pair: is_pair(player_cards),
+            banker_pair: is_pair(banker_cards),
+            natural: is_natural(player_cards) || is_natural(banker_cards),
+        }
+    }
+
+    /// Serialize to 2 bytes: [flags:u8] [totals:u8]
+    fn to_bytes(self) -> [u8; 2] {
+        let flags =
+            self.player_pair as u8 | (self.banker_pair as u8) << 1 | (self.natural as u8) << 2;
+        [flags, self.player_total << 4 | self.banker_total]
     }
 
-    /// Deserialize from 9 bytes
-    fn from_bytes(bytes: &[u8]) -> Option<Self> {
-        if bytes.len() < 9 {
+    /// Deserialize from 2 bytes
+    fn from_bytes(bytes: [u8; 2]) -> Option<Self> {
+        let [flags, totals] = bytes;
+        let (player_total, banker_total) = (totals >> 4, totals & 0x0F);
+        if flags > 0b111 || player_total > 9 || banker_total > 9 {
             return None;
         }
-        let bet_type = BetType::try_from(bytes[0]).ok()?;
-        let amount = u64::from_be_bytes(bytes[1..9].try_into().ok()?);
-        Some(BaccaratBet { bet_type, amount })
+        Some(RoundRecord {
+            player_total,
+            banker_total,
+            player_pair: flags & 1 != 0,
+            banker_pair: flags & 2 != 0,
+            natural: flags & 4 != 0,
+        })
     }
 }
 
 /// Game state for multi-bet baccarat.
+#[derive(Clone, Debug, PartialEq, Eq)]
 struct BaccaratState {
     bets: Vec<BaccaratBet>,
     player_cards: Vec<u8>,
     banker_cards: Vec<u8>,
+    /// Rounds dealt so far this session (oldest first, bounded by `MAX_HISTORY`).
+    history: Vec<RoundRecord>,
+    /// What each bet returned (in bet order), once the cards are dealt.
+    bet_returns: Vec<u64>,
+    /// The table's persistent shoe, if it keeps one.
+    shoe: Option<Shoe>,
+    /// Squeeze steps taken, while a squeeze is under way.
+    squeeze: Option<u8>,
 }
 
 impl BaccaratState {
@@ -124,89 +248,113 @@ impl BaccaratState {
             bets: Vec::new(),
             player_cards: Vec::new(),
             banker_cards: Vec::new(),
+            history: Vec::new(),
+            bet_returns: Vec::new(),
+            shoe: None,
+            squeeze: None,
+        }
+    }
+
+    /// Third cards dealt this round (the player's, then the banker's).
+    fn third_cards(&self) -> Vec<(u8, Seat)> {
+        let player = self
+            .player_cards
+            .get(2)
+            .map(|&card| (card, Seat::Player(0)));
+        let banker = self.banker_cards.get(2).map(|&card| (card, Seat::Dealer));
+        player.into_iter().chain(banker).collect()
+    }
+
+    /// Squeeze steps to reveal every third card.
+    fn squeeze_steps(&self) -> u8 {
+        SQUEEZE_STEPS * self.third_cards().len() as u8
+    }
+
+    /// Add a dealt round to the scoreboard, dropping the oldest once it is full.
+    fn record_round(&mut self) {
+        if self.history.len() >= MAX_HISTORY {
+            self.history.remove(0);
         }
+        self.history
+            .push(RoundRecord::new(&self.player_cards, &self.banker_cards));
+    }
+}
+
+impl BlobField for RoundRecord {
+    fn write(&self, writer: &mut BlobWriter) {
+        writer.put(&self.to_bytes());
     }
 
-    /// Serialize state to blob
-    fn to_blob(&self) -> Vec<u8> {
-        // Capacity: 1 (bet count) + bets (9 bytes each) + 1 (player len) + player cards + 1 (banker len) + banker cards
-        let capacity =
-            1 + (self.bets.len() * 9) + 1 + self.player_cards.len() + 1 + self.banker_cards.len();
-        let mut blob = Vec::with_capacity(capacity);
-        blob.push(self.bets.len() as u8);
-        for bet in &self.bets {
-            blob.extend_from_slice(&bet.to_bytes());
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        Self::from_bytes(reader.get()?)
+    }
+}
+
+impl StateBlob for BaccaratState {
+    const VERSION: u8 = UNVERSIONED;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put_list(&self.bets)
+            .put_list(&self.player_cards)
+            .put_list(&self.banker_cards)
+            .put_list(&self.history);
+        for bet_return in &self.bet_returns {
+            writer.put(bet_return);
+        }
+        if let Some(shoe) = &self.shoe {
+            writer.put(shoe);
+        }
+        if let Some(squeeze) = &self.squeeze {
+            writer.put(squeeze);
         }
-        blob.push(self.player_cards.len() as u8);
-        blob.extend_from_slice(&self.player_cards);
-        blob.push(self.banker_cards.len() as u8);
-        blob.extend_from_slice(&self.banker_cards);
-        blob
     }
 
-    /// Deserialize state from blob
-    fn from_blob(blob: &[u8]) -> Option<Self> {
-        if blob.is_empty() {
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        if reader.remaining() == 0 {
             return Some(BaccaratState::new());
         }
 
-        let mut offset = 0;
+        let mut state = BaccaratState::new();
+        state.bets = reader.get_list(MAX_BETS)?;
 
-        // Parse bets
-        if offset >= blob.len() {
-            return None;
+        // No cards yet - just bets
+        if reader.remaining() == 0 {
+            return Some(state);
         }
-        let bet_count = blob[offset] as usize;
-        offset += 1;
+        state.player_cards = reader.get_list(MAX_HAND_SIZE)?;
+        state.banker_cards = reader.get_list(MAX_HAND_SIZE)?;
 
-        // Validate bet count against maximum to prevent DoS via large allocations
-        if bet_count > MAX_BETS {
-            return None;
+        // History (absent in older blobs, along with bet returns)
+        if reader.remaining() > 0 {
+            state.history = reader.get_list(MAX_HISTORY)?;
         }
 
-        let mut bets = Vec::with_capacity(bet_count);
-        for _ in 0..bet_count {
-            if offset + 9 > blob.len() {
-                return None;
-            }
-            let bet = BaccaratBet::from_bytes(&blob[offset..offset + 9])?;
-            bets.push(bet);
-            offset += 9;
-        }
-
-        // Parse player cards
-        if offset >= blob.len() {
-            // No cards yet - just bets
-            return Some(BaccaratState {
-                bets,
-                player_cards: Vec::new(),
-                banker_cards: Vec::new(),
-            });
-        }
-        let player_len = blob[offset] as usize;
-        offset += 1;
-        if player_len > MAX_HAND_SIZE || offset + player_len > blob.len() {
-            return None;
+        // Bet returns (absent before the deal), the shoe (absent unless the table keeps one) and
+        // the squeeze steps (absent unless a squeeze is under way). A shoe is never a whole
+        // number of returns, so the length tells them apart.
+        let returns_len = 8 * state.bets.len();
+        let trailing = [0, 1, Shoe::BLOB_SIZE, Shoe::BLOB_SIZE + 1];
+        if reader
+            .remaining()
+            .checked_sub(returns_len)
+            .is_some_and(|rest| trailing.contains(&rest))
+        {
+            state.bet_returns = (0..state.bets.len())
+                .map(|_| reader.get())
+                .collect::<Option<_>>()?;
         }
-        let player_cards = blob[offset..offset + player_len].to_vec();
-        offset += player_len;
-
-        // Parse banker cards
-        if offset >= blob.len() {
-            return None;
+        if reader.remaining() >= Shoe::BLOB_SIZE {
+            state.shoe = Some(reader.get()?);
         }
-        let banker_len = blob[offset] as usize;
-        offset += 1;
-        if banker_len > MAX_HAND_SIZE || offset + banker_len > blob.len() {
-            return None;
+        if reader.remaining() > 0 {
+            let squeeze = reader.get()?;
+            if state.bet_returns.is_empty() || squeeze >= state.squeeze_steps() {
+                return None;
+            }
+            state.squeeze = Some(squeeze);
         }
-        let banker_cards = blob[offset..offset + banker_len].to_vec();
-
-        Some(BaccaratState {
-            bets,
-            player_cards,
-            banker_cards,
-        })
+        Some(state)
     }
 }
 
@@ -254,31 +402,60 @@ fn banker_draws(banker_total: u8, player_third_card: Option<u8>) -> bool {
 /// Returns total payout (stake + winnings) for wins, 0 for losses, stake for push.
 fn calculate_bet_payout(
     bet: &BaccaratBet,
-    player_total: u8,
-    banker_total: u8,
-    player_has_pair: bool,
-    banker_has_pair: bool,
-    banker_cards_len: usize,
+    player_cards: &[u8],
+    banker_cards: &[u8],
 ) -> (i64, bool) {
     // Returns (payout_delta, is_push)
     // payout_delta: positive for win (winnings only), negative for loss (amount lost), 0 for push
+    let player_total = hand_total(player_cards);
+    let banker_total = hand_total(banker_cards);
     match bet.bet_type {
         BetType::PlayerPair => {
-            if player_has_pair {
+            if is_pair(player_cards) {
                 // 11:1 payout = winnings of 11x
-                (bet.amount.saturating_mul(11) as i64, false)
+                (
+                    bet.amount.saturating_mul(BACCARAT_PAIR_PAYOUT) as i64,
+                    false,
+                )
             } else {
                 (-(bet.amount as i64), false)
             }
         }
         BetType::BankerPair => {
-            if banker_has_pair {
+            if is_pair(banker_cards) {
                 // 11:1 payout = winnings of 11x
-                (bet.amount.saturating_mul(11) as i64, false)
+                (
+                    bet.amount.saturating_mul(BACCARAT_PAIR_PAYOUT) as i64,
+                    false,
+                )
+            } else {
+                (-(bet.amount as i64), false)
+            }
+        }
+        BetType::PerfectPair => {
+            let multiplier = match (is_perfect_pair(player_cards), is_perfect_pair(banker_cards)) {
+                (true, true) => BACCARAT_PERFECT_PAIR_BOTH_PAYOUT,
+                (true, false) | (false, true) => BACCARAT_PERFECT_PAIR_PAYOUT,
+                (false, false) => 0,
+            };
+            if multiplier > 0 {
+                (bet.amount.saturating_mul(multiplier) as i64, false)
             } else {
                 (-(bet.amount as i64), false)
             }
         }
+        BetType::PlayerDragon | BetType::BankerDragon => {
+            let (own_cards, other_cards) = if bet.bet_type == BetType::PlayerDragon {
+                (player_cards, banker_cards)
+            } else {
+                (banker_cards, player_cards)
+            };
+            match dragon_multiplier(own_cards, other_cards) {
+                None => (0, true), // Natural tie
+                Some(0) => (-(bet.amount as i64), false),
+                Some(multiplier) => (bet.amount.saturating_mul(multiplier) as i64, false),
+            }
+        }
         BetType::Tie => {
             if player_total == banker_total {
                 // 8:1 payout = winnings of 8x
@@ -316,7 +493,7 @@ fn calculate_bet_payout(
             // Lucky 6 wins when Banker wins with a final total of 6.
             if banker_total == 6 && banker_total > player_total {
                 // WoO "liberal pay table": 2-card 6 pays 12:1, 3-card 6 pays 23:1 (to 1).
-                let winnings_multiplier = match banker_cards_len {
+                let winnings_multiplier = match banker_cards.len() {
                     2 => 12u64,
                     3 => 23u64,
                     _ => 0u64,
@@ -329,9 +506,120 @@ fn calculate_bet_payout(
     }
 }
 
+/// Deal a round for `state`'s bets, recording the cards, what each bet returned and the round's
+/// scoreboard entry, and return what the bets returned (after any super multiplier).
+fn deal_round(state: &mut BaccaratState, session: &GameSession, rng: &mut GameRng) -> u64 {
+    // Deal initial cards (from the table's shoe, reshuffled once its cut card is out)
+    if let Some(shoe) = &mut state.shoe {
+        shoe.start_round();
+    }
+    let mut deck = CardSource::new(state.shoe.as_mut(), rng, BACCARAT_DECKS, &[]);
+
+    // Deal 2 cards each: Player, Banker, Player, Banker
+    state.player_cards = vec![deck.draw(rng).unwrap_or(0), deck.draw(rng).unwrap_or(1)];
+    state.banker_cards = vec![deck.draw(rng).unwrap_or(2), deck.draw(rng).unwrap_or(3)];
+
+    let player_total = hand_total(&state.player_cards);
+    let banker_total = hand_total(&state.banker_cards);
+
+    // Natural check (8 or 9 on first two cards)
+    let natural = player_total >= 8 || banker_total >= 8;
+
+    let mut player_third_card: Option<u8> = None;
+
+    if !natural {
+        // Player draws?
+        if player_draws(player_total) {
+            let card = deck.draw(rng).unwrap_or(4);
+            state.player_cards.push(card);
+            player_third_card = Some(card);
+        }
+
+        // Banker draws?
+        if banker_draws(banker_total, player_third_card) {
+            let card = deck.draw(rng).unwrap_or(5);
+            state.banker_cards.push(card);
+        }
+    }
+
+    // Record what each bet returned (stake plus winnings)
+    state.bet_returns = state
+        .bets
+        .iter()
+        .map(|bet| {
+            let (payout_delta, _) =
+                calculate_bet_payout(bet, &state.player_cards, &state.banker_cards);
+            if payout_delta >= 0 {
+                bet.amount.saturating_add(payout_delta.unsigned_abs())
+            } else {
+                bet.amount.saturating_sub(payout_delta.unsigned_abs())
+            }
+        })
+        .collect();
+    state.record_round();
+    round_return(state, session)
+}
+
+/// What the bets returned in the round dealt (after any super multiplier).
+fn round_return(state: &BaccaratState, session: &GameSession) -> u64 {
+    let total_return = state
+        .bet_returns
+        .iter()
+        .fold(0u64, |total, &ret| total.saturating_add(ret));
+    if session.super_mode.is_active && total_return > 0 {
+        // Aura Cards: combine player and banker cards for multiplier check
+        let all_cards: Vec<u8> = state
+            .player_cards
+            .iter()
+            .chain(state.banker_cards.iter())
+            .cloned()
+            .collect();
+        apply_super_multiplier_cards(&all_cards, &session.super_mode.multipliers, total_return)
+    } else {
+        total_return
+    }
+}
+
+/// Settle a round that ends the session.
+fn settle_round(state: &BaccaratState, total_return: u64) -> GameResult {
+    // Wagers were deducted via ContinueWithUpdate at bet time, so a round returning anything
+    // (pushes included) credits it back; GameResult::Push would only refund `session.bet`
+    if total_return > 0 {
+        GameResult::Win(total_return)
+    } else {
+        let total_wagered = state
+            .bets
+            .iter()
+            .fold(0u64, |total, bet| total.saturating_add(bet.amount));
+        GameResult::LossPreDeducted(total_wagered)
+    }
+}
+
+/// The first `per_hand` cards of each hand, in the order they are dealt (alternately, starting
+/// with the player).
+fn dealt_cards(state: &BaccaratState, per_hand: usize) -> Vec<TableEvent> {
+    (0..per_hand)
+        .flat_map(|i| {
+            [
+                state
+                    .player_cards
+                    .get(i)
+                    .map(|&card| (card, Seat::Player(0))),
+                state.banker_cards.get(i).map(|&card| (card, Seat::Dealer)),
+            ]
+        })
+        .flatten()
+        .map(|(card, to)| TableEvent::CardDealt { card, to })
+        .collect()
+}
+
 pub struct Baccarat;
 
 impl CasinoGame for Baccarat {
+    fn bet_count(session: &GameSession) -> usize {
+        BaccaratState::from_blob(&session.state_blob).map_or(0, |state| state.bets.len())
+    }
+
     fn init(session: &mut GameSession, _rng: &mut GameRng) -> GameResult {
         // Initialize with empty state
         let state = BaccaratState::new();
@@ -339,6 +627,43 @@ impl CasinoGame for Baccarat {
         GameResult::Continue
     }
 
+    fn configure(session: &mut GameSession, config: &GameConfig) {
+        if let Some(mut state) = BaccaratState::from_blob(&session.state_blob) {
+            state.shoe = Shoe::from_config(config);
+            session.state_blob = state.to_blob();
+        }
+    }
+
+    fn table_events(before: &[u8], session: &GameSession, payload: &[u8]) -> Vec<TableEvent> {
+        // Only a deal puts cards on the table (auto-play's rounds are left to the scoreboard)
+        match payload.first() {
+            Some(1 | 3) => BaccaratState::from_blob(&session.state_blob)
+                .map_or_else(Vec::new, |state| dealt_cards(&state, MAX_HAND_SIZE)),
+            // A squeeze shows the first two cards of each hand, then each third card as it is
+            // flipped
+            Some(5) => BaccaratState::from_blob(&session.state_blob)
+                .map_or_else(Vec::new, |state| dealt_cards(&state, 2)),
+            Some(6) => {
+                let Some(state) = BaccaratState::from_blob(before) else {
+                    return Vec::new();
+                };
+                let Some(taken) = state.squeeze.map(|steps| steps + 1) else {
+                    return Vec::new();
+                };
+                if taken % SQUEEZE_STEPS != 0 {
+                    return Vec::new();
+                }
+                state
+                    .third_cards()
+                    .get(usize::from(taken / SQUEEZE_STEPS - 1))
+                    .map(|&(card, to)| TableEvent::CardDealt { card, to })
+                    .into_iter()
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
     fn process_move(
         session: &mut GameSession,
         payload: &[u8],
@@ -356,6 +681,11 @@ impl CasinoGame for Baccarat {
         let mut state =
             BaccaratState::from_blob(&session.state_blob).ok_or(GameError::InvalidPayload)?;
 
+        // A squeeze must be seen through before anything else happens at the table
+        if state.squeeze.is_some() && payload[0] != 6 {
+            return Err(GameError::InvalidMove);
+        }
+
         match payload[0] {
             // [0, bet_type, amount_bytes...] - Place bet
             0 => {
@@ -363,11 +693,6 @@ impl CasinoGame for Baccarat {
                     return Err(GameError::InvalidPayload);
                 }
 
-                // Cards already dealt - can't place more bets
-                if !state.player_cards.is_empty() {
-                    return Err(GameError::InvalidMove);
-                }
-
                 let bet_type = BetType::try_from(payload[1])?;
                 let amount = u64::from_be_bytes(
                     payload[2..10]
@@ -379,6 +704,14 @@ impl CasinoGame for Baccarat {
                     return Err(GameError::InvalidPayload);
                 }
 
+                // Cards already dealt (and the session kept open) - start the next round
+                if !state.player_cards.is_empty() {
+                    state.bets.clear();
+                    state.player_cards.clear();
+                    state.banker_cards.clear();
+                    state.bet_returns.clear();
+                }
+
                 // Check if bet type already exists - if so, add to it
                 if let Some(existing) = state.bets.iter_mut().find(|b| b.bet_type == bet_type) {
                     existing.amount = existing.amount.saturating_add(amount);
@@ -397,7 +730,8 @@ impl CasinoGame for Baccarat {
             }
 
             // [1] - Deal cards and resolve all bets
-            1 => {
+            // [3] - Same, keeping the session open for another round
+            1 | 3 => {
                 // Must have at least one bet
                 if state.bets.is_empty() {
                     return Err(GameError::InvalidMove);
@@ -408,128 +742,24 @@ impl CasinoGame for Baccarat {
                     return Err(GameError::InvalidMove);
                 }
 
-                // Deal initial cards
-                let mut deck = rng.create_shoe(BACCARAT_DECKS);
-
-                // Deal 2 cards each: Player, Banker, Player, Banker
-                state.player_cards = vec![
-                    rng.draw_card(&mut deck).unwrap_or(0),
-                    rng.draw_card(&mut deck).unwrap_or(1),
-                ];
-                state.banker_cards = vec![
-                    rng.draw_card(&mut deck).unwrap_or(2),
-                    rng.draw_card(&mut deck).unwrap_or(3),
-                ];
-
-                let mut player_total = hand_total(&state.player_cards);
-                let mut banker_total = hand_total(&state.banker_cards);
-
-                // Natural check (8 or 9 on first two cards)
-                let natural = player_total >= 8 || banker_total >= 8;
-
-                let mut player_third_card: Option<u8> = None;
-
-                if !natural {
-                    // Player draws?
-                    if player_draws(player_total) {
-                        let card = rng.draw_card(&mut deck).unwrap_or(4);
-                        state.player_cards.push(card);
-                        player_third_card = Some(card);
-                        player_total = hand_total(&state.player_cards);
-                    }
-
-                    // Banker draws?
-                    if banker_draws(banker_total, player_third_card) {
-                        let card = rng.draw_card(&mut deck).unwrap_or(5);
-                        state.banker_cards.push(card);
-                        banker_total = hand_total(&state.banker_cards);
-                    }
-                }
-
-                // Check for pairs
-                let player_has_pair = is_pair(&state.player_cards);
-                let banker_has_pair = is_pair(&state.banker_cards);
-
-                // Calculate total payout across all bets
-                let mut total_wagered: u64 = 0;
-                let mut net_payout: i64 = 0;
-                let mut all_push = true;
-
-                for bet in &state.bets {
-                    total_wagered = total_wagered.saturating_add(bet.amount);
-                    let (payout_delta, is_push) = calculate_bet_payout(
-                        bet,
-                        player_total,
-                        banker_total,
-                        player_has_pair,
-                        banker_has_pair,
-                        state.banker_cards.len(),
-                    );
-                    net_payout = net_payout.saturating_add(payout_delta);
-                    if !is_push {
-                        all_push = false;
-                    }
-                }
+                let total_return = deal_round(&mut state, session, rng);
 
+                let keep_playing = payload[0] == 3;
                 session.state_blob = state.to_blob();
                 session.move_count += 1;
-                session.is_complete = true;
-
-                // Determine final result
-                let base_result = if all_push && net_payout == 0 {
-                    // All wagers push; since wagers were deducted via ContinueWithUpdate at bet time,
-                    // return the full wagered amount here (do not use GameResult::Push, which only
-                    // refunds `session.bet`).
-                    GameResult::Win(total_wagered)
-                } else if net_payout > 0 {
-                    // Net win: return total wagered + net winnings
-                    // Safe cast: positive i64 fits in u64
-                    let winnings = u64::try_from(net_payout).unwrap_or(0);
-                    let total_return = total_wagered.saturating_add(winnings);
-                    GameResult::Win(total_return)
-                } else if net_payout < 0 {
-                    // Net loss
-                    // Use checked_neg to safely convert negative i64 to positive value
-                    let loss_amount = net_payout
-                        .checked_neg()
-                        .and_then(|v| u64::try_from(v).ok())
-                        .unwrap_or(0);
-                    if loss_amount == 0 {
-                        // Overflow case - treat as total loss
-                        // Use LossPreDeducted to report actual loss (chips already deducted via ContinueWithUpdate)
-                        GameResult::LossPreDeducted(total_wagered)
-                    } else if loss_amount >= total_wagered {
-                        // Total loss - use LossPreDeducted to report actual amount
-                        GameResult::LossPreDeducted(total_wagered)
+                session.is_complete = !keep_playing;
+
+                if keep_playing {
+                    // Credit the round's return mid-session (losses were deducted at bet time)
+                    return Ok(if total_return > 0 {
+                        GameResult::ContinueWithUpdate {
+                            payout: i64::try_from(total_return).unwrap_or(i64::MAX),
+                        }
                     } else {
-                        // Partial loss - return remaining stake
-                        let remaining = total_wagered.saturating_sub(loss_amount);
-                        GameResult::Win(remaining)
-                    }
-                } else {
-                    // Net zero but not all push - mixed results
-                    GameResult::Win(total_wagered)
-                };
-
-                // Apply super mode multipliers if active and player won
-                if session.super_mode.is_active {
-                    if let GameResult::Win(base_payout) = base_result {
-                        // Aura Cards: combine player and banker cards for multiplier check
-                        let all_cards: Vec<u8> = state
-                            .player_cards
-                            .iter()
-                            .chain(state.banker_cards.iter())
-                            .cloned()
-                            .collect();
-                        let boosted_payout = apply_super_multiplier_cards(
-                            &all_cards,
-                            &session.super_mode.multipliers,
-                            base_payout,
-                        );
-                        return Ok(GameResult::Win(boosted_payout));
-                    }
+                        GameResult::Continue
+                    });
                 }
-                Ok(base_result)
+                Ok(settle_round(&state, total_return))
             }
 
             // [2] - Clear all pending bets
@@ -544,6 +774,72 @@ impl CasinoGame for Baccarat {
                 Ok(GameResult::Continue)
             }
 
+            // [4, rounds] - Deal several rounds with the bets placed
+            4 => {
+                if payload.len() != 2 {
+                    return Err(GameError::InvalidPayload);
+                }
+                if state.bets.is_empty() || !state.player_cards.is_empty() {
+                    return Err(GameError::InvalidMove);
+                }
+
+                let stake = state
+                    .bets
+                    .iter()
+                    .fold(0u64, |total, bet| total.saturating_add(bet.amount));
+                let result = super::auto_play(rng, payload[1], stake, |rng| {
+                    deal_round(&mut state, session, rng)
+                })?;
+
+                session.state_blob = state.to_blob();
+                session.move_count += 1;
+                session.is_complete = true;
+                Ok(result)
+            }
+
+            // [5] - Deal cards with a squeeze
+            5 => {
+                if state.bets.is_empty() || !state.player_cards.is_empty() {
+                    return Err(GameError::InvalidMove);
+                }
+
+                // The round is decided here; the squeeze only stages the reveal of its third
+                // cards
+                let total_return = deal_round(&mut state, session, rng);
+                session.move_count += 1;
+                if !state.third_cards().is_empty() {
+                    state.squeeze = Some(0);
+                    session.state_blob = state.to_blob();
+                    return Ok(GameResult::Continue);
+                }
+
+                // Neither hand draws, so there is nothing to squeeze
+                session.state_blob = state.to_blob();
+                session.is_complete = true;
+                Ok(settle_round(&state, total_return))
+            }
+
+            // [6] - Take the next squeeze step
+            6 => {
+                let Some(steps) = state.squeeze else {
+                    return Err(GameError::InvalidMove);
+                };
+
+                let taken = steps + 1;
+                session.move_count += 1;
+                if taken < state.squeeze_steps() {
+                    state.squeeze = Some(taken);
+                    session.state_blob = state.to_blob();
+                    return Ok(GameResult::Continue);
+                }
+
+                // The last third card is flipped: settle the round dealt by [5]
+                state.squeeze = None;
+                session.state_blob = state.to_blob();
+                session.is_complete = true;
+                Ok(settle_round(&state, round_return(&state, session)))
+            }
+
             _ => Err(GameError::InvalidPayload),
         }
     }
@@ -574,6 +870,9 @@ mod tests {
             super_mode: nullspace_types::casino::SuperModeState::default(),
             is_tournament: false,
             tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
         }
     }
 
@@ -653,6 +952,13 @@ mod tests {
             ],
             player_cards: vec![1, 2, 3],
             banker_cards: vec![4, 5],
+            history: vec![
+                RoundRecord::new(&[7, 9], &[6, 19]),
+                RoundRecord::new(&[1, 2, 3], &[4, 5]),
+            ],
+            bet_returns: vec![200, 0],
+            shoe: None,
+            squeeze: None,
         };
 
         let blob = state.to_blob();
@@ -665,6 +971,93 @@ mod tests {
         assert_eq!(parsed.bets[1].amount, 50);
         assert_eq!(parsed.player_cards, vec![1, 2, 3]);
         assert_eq!(parsed.banker_cards, vec![4, 5]);
+        assert_eq!(parsed.history, state.history);
+        assert_eq!(parsed.bet_returns, vec![200, 0]);
+
+        // Blobs from before the history and bet returns were recorded still parse
+        let cards_end = 1 + 2 * 9 + 1 + 3 + 1 + 2;
+        let parsed =
+            BaccaratState::from_blob(&blob[..cards_end]).expect("Failed to parse legacy state");
+        assert_eq!(parsed.banker_cards, vec![4, 5]);
+        assert!(parsed.history.is_empty());
+        assert!(parsed.bet_returns.is_empty());
+
+        // But partial history or returns don't
+        assert!(BaccaratState::from_blob(&blob[..cards_end + 2]).is_none());
+        assert!(BaccaratState::from_blob(&blob[..blob.len() - 8]).is_none());
+
+        // A table's shoe follows the returns, or the history before the deal
+        let shoe = Some(Shoe::new(8, 75));
+        let dealt = BaccaratState {
+            shoe: shoe.clone(),
+            ..state
+        };
+        assert_eq!(
+            BaccaratState::from_blob(&dealt.to_blob()).as_ref(),
+            Some(&dealt)
+        );
+        let undealt = BaccaratState {
+            bets: dealt.bets.clone(),
+            shoe,
+            ..BaccaratState::new()
+        };
+        assert_eq!(
+            BaccaratState::from_blob(&undealt.to_blob()).as_ref(),
+            Some(&undealt)
+        );
+
+        // As do the squeeze steps taken, while a squeeze is under way
+        let squeezed = BaccaratState {
+            squeeze: Some(1),
+            ..dealt.clone()
+        };
+        assert_eq!(
+            BaccaratState::from_blob(&squeezed.to_blob()).as_ref(),
+            Some(&squeezed)
+        );
+        let squeezed_without_shoe = BaccaratState {
+            shoe: None,
+            ..squeezed.clone()
+        };
+        assert_eq!(
+            BaccaratState::from_blob(&squeezed_without_shoe.to_blob()).as_ref(),
+            Some(&squeezed_without_shoe)
+        );
+
+        // A squeeze can't have revealed more than the third cards dealt
+        let overrun = BaccaratState {
+            squeeze: Some(SQUEEZE_STEPS),
+            ..dealt.clone()
+        };
+        assert!(BaccaratState::from_blob(&overrun.to_blob()).is_none());
+
+        crate::casino::state_blob::fuzz::roundtrip(&[
+            dealt,
+            undealt,
+            squeezed,
+            squeezed_without_shoe,
+            BaccaratState::new(),
+        ]);
+    }
+
+    #[test]
+    fn test_round_record() {
+        // Natural 8 (pair of 4s) against a pair of 7s
+        let record = RoundRecord::new(&[3, 16], &[6, 19]);
+        assert_eq!(record.player_total, 8);
+        assert_eq!(record.banker_total, 4);
+        assert!(record.player_pair && record.banker_pair && record.natural);
+        assert_eq!(record.to_bytes(), [0b111, 0x84]);
+        assert_eq!(RoundRecord::from_bytes(record.to_bytes()), Some(record));
+
+        // Three-card 7 against 6, no pairs
+        let record = RoundRecord::new(&[0, 9, 5], &[5, 9]);
+        assert_eq!(record.to_bytes(), [0, 0x76]);
+        assert_eq!(RoundRecord::from_bytes(record.to_bytes()), Some(record));
+
+        // Totals above 9 and unknown flags are rejected
+        assert!(RoundRecord::from_bytes([0, 0xA0]).is_none());
+        assert!(RoundRecord::from_bytes([0b1000, 0]).is_none());
     }
 
     /// Helper to create place bet payload
@@ -790,8 +1183,8 @@ mod tests {
         Baccarat::init(&mut session, &mut rng);
 
         let mut rng = GameRng::new(&seed, session.id, 1);
-        // Invalid bet type (5 is valid now - BankerPair, use 6 for invalid)
-        let mut payload = vec![0, 6]; // Invalid bet type
+        // Invalid bet type (8 is valid now - BankerDragon, use 9 for invalid)
+        let mut payload = vec![0, 9]; // Invalid bet type
         payload.extend_from_slice(&100u64.to_be_bytes());
         let result = Baccarat::process_move(&mut session, &payload, &mut rng);
 
@@ -844,7 +1237,7 @@ mod tests {
             amount: 100,
         };
         // Banker wins with 9 vs 4
-        let (payout, is_push) = calculate_bet_payout(&bet, 4, 9, false, false, 2);
+        let (payout, is_push) = calculate_bet_payout(&bet, &[3, 9], &[8, 22]);
         assert!(!is_push);
         assert_eq!(payout, 95); // 95% of 100
     }
@@ -856,7 +1249,8 @@ mod tests {
             bet_type: BetType::Tie,
             amount: 100,
         };
-        let (payout, is_push) = calculate_bet_payout(&bet, 5, 5, false, false, 2);
+        // 5 vs 5
+        let (payout, is_push) = calculate_bet_payout(&bet, &[4, 9], &[17, 22]);
         assert!(!is_push);
         assert_eq!(payout, 800); // 8x winnings
     }
@@ -868,7 +1262,8 @@ mod tests {
             bet_type: BetType::PlayerPair,
             amount: 100,
         };
-        let (payout, _) = calculate_bet_payout(&bet, 5, 7, true, false, 2);
+        // Pair of 2s
+        let (payout, _) = calculate_bet_payout(&bet, &[1, 14], &[6, 9]);
         assert_eq!(payout, 1100); // 11x winnings
     }
 
@@ -880,18 +1275,441 @@ mod tests {
         };
 
         // Banker wins with a 2-card 6
-        let (payout, is_push) = calculate_bet_payout(&bet, 1, 6, false, false, 2);
+        let (payout, is_push) = calculate_bet_payout(&bet, &[0, 9], &[5, 9]);
         assert!(!is_push);
         assert_eq!(payout, 1200); // 12x winnings
 
         // Banker wins with a 3-card 6
-        let (payout, is_push) = calculate_bet_payout(&bet, 1, 6, false, false, 3);
+        let (payout, is_push) = calculate_bet_payout(&bet, &[0, 9], &[1, 9, 29]);
         assert!(!is_push);
         assert_eq!(payout, 2300); // 23x winnings
 
         // Banker loses (no payout)
-        let (payout, _) = calculate_bet_payout(&bet, 7, 6, false, false, 2);
+        let (payout, _) = calculate_bet_payout(&bet, &[6, 9], &[5, 9]);
+        assert_eq!(payout, -100);
+    }
+
+    #[test]
+    fn test_perfect_pair_payout() {
+        let bet = BaccaratBet {
+            bet_type: BetType::PerfectPair,
+            amount: 100,
+        };
+
+        // Suited pair of 6s in one hand
+        let (payout, _) = calculate_bet_payout(&bet, &[5, 5], &[6, 9]);
+        assert_eq!(payout, 2500);
+        let (payout, _) = calculate_bet_payout(&bet, &[6, 9], &[5, 5]);
+        assert_eq!(payout, 2500);
+
+        // In both hands
+        let (payout, _) = calculate_bet_payout(&bet, &[5, 5], &[18, 18]);
+        assert_eq!(payout, 20000);
+
+        // An unsuited pair only wins the ordinary pair bet
+        let (payout, _) = calculate_bet_payout(&bet, &[5, 18], &[6, 9]);
         assert_eq!(payout, -100);
+        let pair = BaccaratBet {
+            bet_type: BetType::PlayerPair,
+            amount: 100,
+        };
+        let (payout, _) = calculate_bet_payout(&pair, &[5, 18], &[6, 9]);
+        assert_eq!(payout, 1100);
+    }
+
+    #[test]
+    fn test_dragon_bonus_payout() {
+        let player = BaccaratBet {
+            bet_type: BetType::PlayerDragon,
+            amount: 100,
+        };
+        let banker = BaccaratBet {
+            bet_type: BetType::BankerDragon,
+            amount: 100,
+        };
+
+        // Natural 8 beats 7: even money
+        assert_eq!(
+            calculate_bet_payout(&player, &[7, 9], &[6, 9]),
+            (100, false)
+        );
+        assert_eq!(
+            calculate_bet_payout(&banker, &[7, 9], &[6, 9]),
+            (-100, false)
+        );
+        assert_eq!(
+            calculate_bet_payout(&banker, &[6, 9], &[7, 9]),
+            (100, false)
+        );
+
+        // Natural tie pushes both sides
+        assert_eq!(calculate_bet_payout(&player, &[7, 9], &[20, 22]), (0, true));
+        assert_eq!(calculate_bet_payout(&banker, &[7, 9], &[20, 22]), (0, true));
+
+        // Non-natural wins pay by margin
+        assert_eq!(
+            calculate_bet_payout(&player, &[4, 9, 3], &[9, 22, 35]),
+            (3000, false)
+        ); // 9 vs 0
+        assert_eq!(
+            calculate_bet_payout(&player, &[6, 9, 22], &[2, 9, 22]),
+            (100, false)
+        ); // 7 vs 3
+        assert_eq!(
+            calculate_bet_payout(&banker, &[2, 9, 22], &[6, 9, 22]),
+            (100, false)
+        );
+
+        // Winning by 3 or less, or a non-natural tie, loses
+        assert_eq!(
+            calculate_bet_payout(&player, &[4, 9, 0], &[2, 9, 22]),
+            (-100, false)
+        ); // 6 vs 3
+        assert_eq!(
+            calculate_bet_payout(&player, &[4, 9, 0], &[4, 9, 13]),
+            (-100, false)
+        ); // 6 vs 6
+    }
+
+    #[test]
+    fn test_deal_records_bet_returns() {
+        let seed = create_test_seed();
+
+        for session_id in 1..20 {
+            let mut session = create_test_session(100);
+            session.id = session_id;
+
+            let mut rng = GameRng::new(&seed, session_id, 0);
+            Baccarat::init(&mut session, &mut rng);
+
+            // One of every bet type
+            for bet_type in 0..MAX_BETS as u8 {
+                let mut rng = GameRng::new(&seed, session_id, 1);
+                let payload = place_bet_payload(BetType::try_from(bet_type).unwrap(), 10);
+                Baccarat::process_move(&mut session, &payload, &mut rng)
+                    .expect("Failed to place bet");
+            }
+            let state =
+                BaccaratState::from_blob(&session.state_blob).expect("Failed to parse state");
+            assert!(state.bet_returns.is_empty());
+
+            let mut rng = GameRng::new(&seed, session_id, 2);
+            let result = Baccarat::process_move(&mut session, &[1], &mut rng)
+                .expect("Failed to process move");
+
+            // Each bet's return matches its own resolution, and they add up to the result
+            let state =
+                BaccaratState::from_blob(&session.state_blob).expect("Failed to parse state");
+            assert_eq!(state.bet_returns.len(), MAX_BETS);
+            for (bet, &bet_return) in state.bets.iter().zip(&state.bet_returns) {
+                let (payout_delta, _) =
+                    calculate_bet_payout(bet, &state.player_cards, &state.banker_cards);
+                assert_eq!(bet_return as i64, bet.amount as i64 + payout_delta);
+            }
+            let total_return: u64 = state.bet_returns.iter().sum();
+            match result {
+                GameResult::Win(payout) => assert_eq!(payout, total_return),
+                GameResult::LossPreDeducted(total) => {
+                    assert_eq!(total_return, 0);
+                    assert_eq!(total, 10 * MAX_BETS as u64);
+                }
+                _ => panic!("Unexpected baccarat result"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_multi_round_history() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(0);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        Baccarat::init(&mut session, &mut rng);
+
+        let rounds = MAX_HISTORY as u32 + 3;
+        let mut records = Vec::new();
+        for round in 0..rounds {
+            let mut rng = GameRng::new(&seed, session.id, 2 * round + 1);
+            let payload = place_bet_payload(BetType::Banker, 100);
+            let result = Baccarat::process_move(&mut session, &payload, &mut rng)
+                .expect("Failed to place bet");
+            assert!(matches!(
+                result,
+                GameResult::ContinueWithUpdate { payout: -100 }
+            ));
+
+            // Placing a bet clears the previous round, but not the scoreboard
+            let state =
+                BaccaratState::from_blob(&session.state_blob).expect("Failed to parse state");
+            assert_eq!(state.bets.len(), 1);
+            assert!(state.player_cards.is_empty() && state.bet_returns.is_empty());
+            assert_eq!(state.history.len(), records.len().min(MAX_HISTORY));
+
+            // Deal and keep playing: the round's return is credited mid-session
+            let mut rng = GameRng::new(&seed, session.id, 2 * round + 2);
+            let result =
+                Baccarat::process_move(&mut session, &[3], &mut rng).expect("Failed to deal");
+            assert!(!session.is_complete);
+            let state =
+                BaccaratState::from_blob(&session.state_blob).expect("Failed to parse state");
+            match result {
+                GameResult::ContinueWithUpdate { payout } => {
+                    assert_eq!(payout, state.bet_returns[0] as i64)
+                }
+                GameResult::Continue => assert_eq!(state.bet_returns[0], 0),
+                _ => panic!("Unexpected baccarat result"),
+            }
+            records.push(RoundRecord::new(&state.player_cards, &state.banker_cards));
+
+            // Can't deal the same round twice
+            assert!(matches!(
+                Baccarat::process_move(&mut session, &[3], &mut rng),
+                Err(GameError::InvalidMove)
+            ));
+        }
+
+        // The scoreboard keeps the most recent rounds, oldest first
+        let state = BaccaratState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.history, records[records.len() - MAX_HISTORY..]);
+
+        // A final deal ends the session and still records the round
+        let mut rng = GameRng::new(&seed, session.id, 2 * rounds + 1);
+        let payload = place_bet_payload(BetType::Player, 100);
+        Baccarat::process_move(&mut session, &payload, &mut rng).expect("Failed to place bet");
+        let mut rng = GameRng::new(&seed, session.id, 2 * rounds + 2);
+        let result = Baccarat::process_move(&mut session, &[1], &mut rng).expect("Failed to deal");
+        assert!(matches!(
+            result,
+            GameResult::Win(_) | GameResult::LossPreDeducted(_)
+        ));
+        assert!(session.is_complete);
+        let state = BaccaratState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.history.len(), MAX_HISTORY);
+        assert_eq!(
+            state.history.last(),
+            Some(&RoundRecord::new(&state.player_cards, &state.banker_cards))
+        );
+    }
+
+    #[test]
+    fn test_persistent_shoe() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(0);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        Baccarat::init(&mut session, &mut rng);
+        Baccarat::configure(
+            &mut session,
+            &GameConfig {
+                shoe_decks: 6,
+                shoe_penetration: 50,
+                ..GameConfig::default()
+            },
+        );
+
+        // Each round is dealt from what is left of the shoe, until the cut card comes out
+        let mut remaining = 6 * 52;
+        let mut reshuffled = false;
+        for round in 0..100 {
+            let mut rng = GameRng::new(&seed, session.id, 2 * round + 1);
+            let payload = place_bet_payload(BetType::Player, 100);
+            Baccarat::process_move(&mut session, &payload, &mut rng).expect("Failed to place bet");
+            let mut rng = GameRng::new(&seed, session.id, 2 * round + 2);
+            Baccarat::process_move(&mut session, &[3], &mut rng).expect("Failed to deal");
+
+            let state =
+                BaccaratState::from_blob(&session.state_blob).expect("Failed to parse state");
+            let shoe = state.shoe.expect("Shoe not kept");
+            let dealt = state.player_cards.len() + state.banker_cards.len();
+            if remaining * 2 <= 6 * 52 {
+                remaining = 6 * 52;
+                reshuffled = true;
+            }
+            remaining -= dealt;
+            assert_eq!(shoe.remaining(), remaining);
+        }
+        assert!(reshuffled);
+    }
+
+    #[test]
+    fn test_auto_play() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(0);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        Baccarat::init(&mut session, &mut rng);
+
+        // Dealing needs bets
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        assert!(matches!(
+            Baccarat::process_move(&mut session, &[4, 20], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+        let payload = place_bet_payload(BetType::Banker, 100);
+        Baccarat::process_move(&mut session, &payload, &mut rng).expect("Failed to place bet");
+
+        // Every round is dealt from its own RNG and goes on the scoreboard
+        let mut expected =
+            BaccaratState::from_blob(&session.state_blob).expect("Failed to parse state");
+        let total_return: u64 = (0..20)
+            .map(|round| {
+                let mut rng = GameRng::new(&seed, session.id, 2).for_round(round);
+                deal_round(&mut expected, &session, &mut rng)
+            })
+            .sum();
+        let mut rng = GameRng::new(&seed, session.id, 2);
+        let result = Baccarat::process_move(&mut session, &[4, 20], &mut rng).expect("Deal failed");
+        assert!(matches!(
+            result,
+            GameResult::WinWithExtraDeduction { payout, extra_deduction: 1900 }
+                if payout == total_return
+        ));
+        let state = BaccaratState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.history.len(), 20);
+        assert_eq!(state, expected);
+        assert!(session.is_complete);
+
+        // The rounds' cards are left to the scoreboard
+        assert!(Baccarat::table_events(&[], &session, &[4, 20]).is_empty());
+    }
+
+    #[test]
+    fn test_table_events() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(0);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        Baccarat::init(&mut session, &mut rng);
+
+        // Placing a bet deals nothing
+        let before = session.state_blob.clone();
+        let payload = place_bet_payload(BetType::Banker, 100);
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        Baccarat::process_move(&mut session, &payload, &mut rng).expect("Failed to place bet");
+        assert!(Baccarat::table_events(&before, &session, &payload).is_empty());
+
+        // The deal alternates between player and banker
+        let before = session.state_blob.clone();
+        let mut rng = GameRng::new(&seed, session.id, 2);
+        Baccarat::process_move(&mut session, &[3], &mut rng).expect("Failed to deal");
+        let state = BaccaratState::from_blob(&session.state_blob).expect("Failed to parse state");
+        let events = Baccarat::table_events(&before, &session, &[3]);
+        assert_eq!(
+            events.len(),
+            state.player_cards.len() + state.banker_cards.len()
+        );
+        assert_eq!(
+            events[..4],
+            [
+                TableEvent::CardDealt {
+                    card: state.player_cards[0],
+                    to: Seat::Player(0),
+                },
+                TableEvent::CardDealt {
+                    card: state.banker_cards[0],
+                    to: Seat::Dealer,
+                },
+                TableEvent::CardDealt {
+                    card: state.player_cards[1],
+                    to: Seat::Player(0),
+                },
+                TableEvent::CardDealt {
+                    card: state.banker_cards[1],
+                    to: Seat::Dealer,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_squeeze() {
+        let seed = create_test_seed();
+        let mut squeezed_rounds = 0;
+        for session_id in 1..20 {
+            // The same round dealt at once and with a squeeze
+            let mut plain = create_test_session(0);
+            plain.id = session_id;
+            let mut rng = GameRng::new(&seed, session_id, 0);
+            Baccarat::init(&mut plain, &mut rng);
+            let payload = place_bet_payload(BetType::Player, 100);
+            let mut rng = GameRng::new(&seed, session_id, 1);
+            Baccarat::process_move(&mut plain, &payload, &mut rng).expect("Failed to place bet");
+            let mut session = plain.clone();
+            let mut rng = GameRng::new(&seed, session_id, 2);
+            let plain_result =
+                Baccarat::process_move(&mut plain, &[1], &mut rng).expect("Failed to deal");
+            let mut rng = GameRng::new(&seed, session_id, 2);
+            let mut result =
+                Baccarat::process_move(&mut session, &[5], &mut rng).expect("Failed to deal");
+
+            // The deal shows the first two cards of each hand and decides the round
+            let dealt = BaccaratState::from_blob(&plain.state_blob).expect("Failed to parse");
+            let state = BaccaratState::from_blob(&session.state_blob).expect("Failed to parse");
+            assert_eq!(
+                Baccarat::table_events(&[], &session, &[5]),
+                dealt_cards(&dealt, 2)
+            );
+            assert_eq!(state.player_cards, dealt.player_cards);
+            assert_eq!(state.banker_cards, dealt.banker_cards);
+            assert_eq!(state.bet_returns, dealt.bet_returns);
+
+            let third_cards = dealt.third_cards();
+            if third_cards.is_empty() {
+                // Nothing to squeeze
+                assert_eq!(state.squeeze, None);
+            } else {
+                squeezed_rounds += 1;
+                assert_eq!(state.squeeze, Some(0));
+                assert!(matches!(result, GameResult::Continue));
+
+                // Nothing else happens at the table until the squeeze is over
+                for payload in [place_bet_payload(BetType::Tie, 10), vec![2], vec![3]] {
+                    assert!(matches!(
+                        Baccarat::process_move(&mut session, &payload, &mut rng),
+                        Err(GameError::InvalidMove)
+                    ));
+                }
+
+                // Each third card is flipped on its last step
+                let mut flipped = Vec::new();
+                for step in 1..=SQUEEZE_STEPS * third_cards.len() as u8 {
+                    assert!(!session.is_complete);
+                    let before = session.state_blob.clone();
+                    let mut rng = GameRng::new(&seed, session_id, 2 + u32::from(step));
+                    result = Baccarat::process_move(&mut session, &[6], &mut rng)
+                        .expect("Failed to squeeze");
+                    let events = Baccarat::table_events(&before, &session, &[6]);
+                    assert_eq!(events.is_empty(), step % SQUEEZE_STEPS != 0);
+                    flipped.extend(events);
+                }
+                let expected: Vec<_> = third_cards
+                    .iter()
+                    .map(|&(card, to)| TableEvent::CardDealt { card, to })
+                    .collect();
+                assert_eq!(flipped, expected);
+            }
+
+            // Settled just as the plain deal was
+            assert!(session.is_complete);
+            assert_eq!(
+                session.move_count,
+                plain.move_count + third_cards.len() as u32 * 3
+            );
+            assert_eq!(
+                BaccaratState::from_blob(&session.state_blob).as_ref(),
+                Some(&dealt)
+            );
+            match (result, plain_result) {
+                (GameResult::Win(payout), GameResult::Win(expected)) => {
+                    assert_eq!(payout, expected)
+                }
+                (GameResult::LossPreDeducted(loss), GameResult::LossPreDeducted(expected)) => {
+                    assert_eq!(loss, expected)
+                }
+                _ => panic!("Squeeze settled differently from the plain deal"),
+            }
+            assert!(matches!(
+                Baccarat::process_move(&mut session, &[6], &mut rng),
+                Err(GameError::GameAlreadyComplete)
+            ));
+        }
+        assert!(squeezed_rounds > 0);
     }
 
     #[test]
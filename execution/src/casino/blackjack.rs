@@ -2,16 +2,29 @@
 //!
 //! This implementation supports:
 //! - Standard blackjack main wager (`session.bet`, deducted by `CasinoStartGame`)
-//! - Splits (up to 4 hands) + doubles (deducted via `ContinueWithUpdate`)
+//! - Splits (re-splitting up to the configured hand limit, 4 by default) + doubles (deducted
+//!   via `ContinueWithUpdate`); split Aces receive one card each
 //! - 21+3 side bet (optional, placed before deal)
+//! - Insurance (half the bet, paying 2:1 on a dealer blackjack) when the dealer shows an Ace
+//! - Even money (a blackjack paid 1:1 at once) when the dealer shows an Ace, if the table offers it
+//! - Late surrender (half the bet returned, unless the dealer has blackjack)
+//! - Multiple rounds per session (dealing with `DealAndContinue` keeps the session open once the
+//!   round resolves; the next round's side bet or deal charges its main bet)
 //!
 //! House rules (executor):
-//! - 8-deck shoe, dealer hits soft 17 (H17)
-//! - No surrender, no on-chain insurance
-//! - No dealer peek (dealer hole card is drawn at `Reveal` for hidden-info safety)
+//! - 8-deck shoe, dealer hits soft 17 (H17) unless the game config has it stand (S17)
+//! - Each round is dealt from a fresh shoe, unless the table keeps a persistent shoe across the
+//!   rounds of a session (see [super::shoe])
+//! - No dealer peek by default (dealer hole card is drawn at `Reveal` for hidden-info safety), so
+//!   insurance and surrender resolve at `Reveal`
+//! - With dealer peek, the dealer checks for blackjack when showing a ten-value card (at the
+//!   deal) or an Ace (once insurance and even money have been offered, i.e. with an insurance
+//!   bet or the player's first other action). A dealer blackjack is turned over and ends the
+//!   round at once. Otherwise the card looked at goes back, and the hole card is drawn at
+//!   `Reveal` from the cards that don't make a blackjack, so it stays hidden until then.
 //!
-//! State blob format (v2):
-//! [version:u8=2]
+//! State blob format (v6):
+//! [version:u8=6]
 //! [stage:u8]
 //! [sideBet21Plus3Amount:u64 BE]
 //! [initialPlayerCard1:u8] [initialPlayerCard2:u8]   (0xFF if not dealt yet)
@@ -19,17 +32,28 @@
 //! [hand_count:u8]
 //! ... per hand:
 //!   [bet_mult:u8] (1=base, 2=doubled)
-//!   [status:u8] (0=playing, 1=stand, 2=bust, 3=blackjack)
+//!   [status:u8] (0=playing, 1=stand, 2=bust, 3=blackjack, 4=surrendered)
 //!   [was_split:u8] (0/1; split hands cannot be a natural blackjack)
 //!   [card_count:u8]
 //!   [cards...]
 //! [dealer_count:u8] [dealer_cards...]
+//! [dealer_stands_soft_17:u8]
+//! [max_hands:u8] (split limit)
+//! [insuranceAmount:u64 BE]
+//! [keep_playing:u8] (0/1; the session stays open once the round resolves)
+//! [dealer_peeks:u8] [even_money:u8] (0/1 table rules)
+//! [peeked:u8] (0/1; the dealer peeked and doesn't have blackjack)
+//! [shoe] (only if the table keeps one: [decks:u8] [penetration:u8] [counts:u8×52])
+//!
+//! v5 blobs have no peek or even money rules. v4 blobs end after the insurance amount, and v3 blobs have no insurance amount. v2 blobs share the layout up to the dealer cards, followed
+//! by an optional [dealer_stands_soft_17:u8] (sessions without it use H17) and no split limit
+//! (4 hands).
 //!
 //! Stages:
 //! 0 = Betting (optional 21+3, then Deal)
 //! 1 = PlayerTurn
 //! 2 = AwaitingReveal (player done; Reveal resolves)
-//! 3 = Complete
+//! 3 = Complete (with the session kept open, a side bet or deal starts the next round)
 //!
 //! Payload format:
 //! [move:u8] [optional amount:u64 BE]
@@ -40,16 +64,24 @@
 //! 4 = Deal
 //! 5 = Set 21+3 side bet (u64)
 //! 6 = Reveal
+//! 7 = Insurance (dealer shows an Ace; before acting on the initial hand)
+//! 8 = Surrender (the initial two-card hand, before any other action)
+//! 9 = Deal, keeping the session open for another round once this one resolves
+//! 10 = Even money (a natural blackjack against a dealer Ace, before the reveal)
 
+use super::shoe::{CardSource, Shoe};
+use super::state_blob::{impl_blob_field_enum, BlobField, BlobReader, BlobWriter, StateBlob};
 use super::super_mode::apply_super_multiplier_cards;
-use super::{CasinoGame, GameError, GameResult, GameRng};
-use nullspace_types::casino::GameSession;
+use super::{CasinoGame, GameError, GameResult, GameRng, TableEvent};
+use nullspace_types::casino::{GameConfig, GameSession, Seat, BLACKJACK_MAX_HANDS};
 
 /// Maximum cards in a blackjack hand.
 const MAX_HAND_SIZE: usize = 11;
-/// Maximum number of hands allowed (splits).
-const MAX_HANDS: usize = 4;
-const STATE_VERSION: u8 = 2;
+const STATE_VERSION_V2: u8 = 2;
+const STATE_VERSION_V3: u8 = 3;
+const STATE_VERSION_V4: u8 = 4;
+const STATE_VERSION_V5: u8 = 5;
+const STATE_VERSION_V6: u8 = 6;
 const CARD_UNKNOWN: u8 = 0xFF;
 /// WoO notes blackjack is commonly dealt from multi-deck shoes; we use 8 decks.
 const BLACKJACK_DECKS: u8 = 8;
@@ -78,6 +110,8 @@ impl TryFrom<u8> for Stage {
     }
 }
 
+impl_blob_field_enum!(Stage);
+
 /// Blackjack move types
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -89,6 +123,10 @@ pub enum Move {
     Deal = 4,
     Set21Plus3 = 5,
     Reveal = 6,
+    Insurance = 7,
+    Surrender = 8,
+    DealAndContinue = 9,
+    EvenMoney = 10,
 }
 
 impl TryFrom<u8> for Move {
@@ -103,6 +141,10 @@ impl TryFrom<u8> for Move {
             4 => Ok(Move::Deal),
             5 => Ok(Move::Set21Plus3),
             6 => Ok(Move::Reveal),
+            7 => Ok(Move::Insurance),
+            8 => Ok(Move::Surrender),
+            9 => Ok(Move::DealAndContinue),
+            10 => Ok(Move::EvenMoney),
             _ => Err(GameError::InvalidPayload),
         }
     }
@@ -114,6 +156,7 @@ pub enum HandStatus {
     Standing = 1,
     Busted = 2,
     Blackjack = 3,
+    Surrendered = 4,
 }
 
 impl TryFrom<u8> for HandStatus {
@@ -124,12 +167,15 @@ impl TryFrom<u8> for HandStatus {
             1 => Ok(HandStatus::Standing),
             2 => Ok(HandStatus::Busted),
             3 => Ok(HandStatus::Blackjack),
+            4 => Ok(HandStatus::Surrendered),
             _ => Err(GameError::InvalidPayload),
         }
     }
 }
 
-#[derive(Clone, Debug)]
+impl_blob_field_enum!(HandStatus);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HandState {
     pub cards: Vec<u8>,
     pub bet_mult: u8,
@@ -138,6 +184,7 @@ pub struct HandState {
 }
 
 /// Game state structure
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BlackjackState {
     pub stage: Stage,
     pub side_bet_21plus3: u64,
@@ -145,6 +192,20 @@ pub struct BlackjackState {
     pub active_hand_idx: usize,
     pub hands: Vec<HandState>,
     pub dealer_cards: Vec<u8>,
+    pub dealer_hits_soft_17: bool,
+    /// Most hands the player can split into.
+    pub max_hands: u8,
+    pub insurance_bet: u64,
+    /// The session stays open for another round once this one resolves.
+    pub keep_playing: bool,
+    /// The dealer peeks for blackjack when showing an Ace or ten-value card.
+    pub dealer_peeks: bool,
+    /// Even money is offered on a blackjack against a dealer Ace.
+    pub even_money: bool,
+    /// The dealer peeked at the hole card this round, and doesn't have blackjack.
+    pub peeked: bool,
+    /// The table's persistent shoe, if it keeps one.
+    pub shoe: Option<Shoe>,
 }
 
 /// Calculate the value of a blackjack hand.
@@ -182,6 +243,33 @@ fn is_natural_blackjack(hand: &HandState) -> bool {
     !hand.was_split && is_blackjack(&hand.cards)
 }
 
+/// Peek at the dealer's hole card. A blackjack is turned over (returning `true`); otherwise the
+/// card goes back and the round is marked as peeked, so the hole card is drawn at `Reveal` from
+/// the cards that don't make a blackjack.
+fn peek(
+    dealer_cards: &mut Vec<u8>,
+    peeked: &mut bool,
+    deck: &mut CardSource<'_>,
+    rng: &mut GameRng,
+) -> Result<bool, GameError> {
+    let up = *dealer_cards.first().ok_or(GameError::InvalidState)?;
+    let hole = deck.draw(rng).ok_or(GameError::DeckExhausted)?;
+    if is_blackjack(&[up, hole]) {
+        dealer_cards.push(hole);
+        return Ok(true);
+    }
+    deck.put_back(hole);
+    *peeked = true;
+    Ok(false)
+}
+
+/// Whether the dealer stands on `cards` (always on hard 17, and on soft 17 unless the dealer
+/// hits soft 17).
+fn dealer_stands(cards: &[u8], hits_soft_17: bool) -> bool {
+    let (value, is_soft) = hand_value(cards);
+    value > 17 || (value == 17 && !(is_soft && hits_soft_17))
+}
+
 /// Get card rank (0-12).
 fn card_rank(card: u8) -> u8 {
     card % 13
@@ -286,106 +374,89 @@ fn apply_21plus3_update(state: &mut BlackjackState, new_bet: u64) -> Result<i64,
     Ok(-(delta as i64))
 }
 
-/// Serialize state to blob.
-fn serialize_state(state: &BlackjackState) -> Vec<u8> {
-    let mut blob = Vec::new();
-    blob.push(STATE_VERSION);
-    blob.push(state.stage as u8);
-    blob.extend_from_slice(&state.side_bet_21plus3.to_be_bytes());
-    blob.push(state.initial_player_cards[0]);
-    blob.push(state.initial_player_cards[1]);
-    blob.push(state.active_hand_idx as u8);
-    blob.push(state.hands.len() as u8);
-
-    for hand in &state.hands {
-        blob.push(hand.bet_mult);
-        blob.push(hand.status as u8);
-        blob.push(hand.was_split as u8);
-        blob.push(hand.cards.len() as u8);
-        blob.extend_from_slice(&hand.cards);
+impl BlobField for HandState {
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.bet_mult)
+            .put(&self.status)
+            .put(&self.was_split)
+            .put_list(&self.cards);
     }
 
-    blob.push(state.dealer_cards.len() as u8);
-    blob.extend_from_slice(&state.dealer_cards);
-    blob
-}
-
-/// Parse state from blob.
-fn parse_state(blob: &[u8]) -> Option<BlackjackState> {
-    if blob.len() < 14 {
-        return None;
-    }
-
-    if blob[0] != STATE_VERSION {
-        return None;
-    }
-
-    let stage = Stage::try_from(blob[1]).ok()?;
-    let mut idx = 2;
-    let side_bet_21plus3 = u64::from_be_bytes(blob[idx..idx + 8].try_into().ok()?);
-    idx += 8;
-
-    let initial_player_cards = [blob[idx], blob[idx + 1]];
-    idx += 2;
-
-    let active_hand_idx = blob[idx] as usize;
-    idx += 1;
-
-    let hand_count = blob[idx] as usize;
-    idx += 1;
-    if hand_count > MAX_HANDS {
-        return None;
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        Some(Self {
+            bet_mult: reader.get()?,
+            status: reader.get()?,
+            was_split: reader.get()?,
+            cards: reader.get_list(MAX_HAND_SIZE)?,
+        })
     }
+}
 
-    let mut hands = Vec::with_capacity(hand_count);
-    for _ in 0..hand_count {
-        if idx + 4 > blob.len() {
-            return None;
+impl StateBlob for BlackjackState {
+    const VERSION: u8 = STATE_VERSION_V6;
+    const MIN_VERSION: u8 = STATE_VERSION_V2;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.stage)
+            .put(&self.side_bet_21plus3)
+            .put(&self.initial_player_cards)
+            .put(&(self.active_hand_idx as u8))
+            .put_list(&self.hands)
+            .put_list(&self.dealer_cards)
+            .put(&!self.dealer_hits_soft_17)
+            .put(&self.max_hands)
+            .put(&self.insurance_bet)
+            .put(&self.keep_playing)
+            .put(&self.dealer_peeks)
+            .put(&self.even_money)
+            .put(&self.peeked);
+        if let Some(shoe) = &self.shoe {
+            writer.put(shoe);
         }
-        let bet_mult = blob[idx];
-        let status = HandStatus::try_from(blob[idx + 1]).ok()?;
-        let was_split = blob[idx + 2] != 0;
-        let c_len = blob[idx + 3] as usize;
-        idx += 4;
+    }
 
-        if c_len > MAX_HAND_SIZE || idx + c_len > blob.len() {
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        let stage = reader.get()?;
+        let side_bet_21plus3 = reader.get()?;
+        let initial_player_cards = reader.get()?;
+        let active_hand_idx = reader.get::<u8>()? as usize;
+        let hands = reader.get_list(BLACKJACK_MAX_HANDS as usize)?;
+        let dealer_cards = reader.get_list(MAX_HAND_SIZE)?;
+
+        // The rules are recorded as "dealer stands on soft 17" and the split limit. v2 blobs
+        // may end before the soft 17 rule (H17) and always split to the most hands.
+        let (dealer_hits_soft_17, max_hands) = if reader.version() == STATE_VERSION_V2 {
+            (!reader.get_or(false)?, BLACKJACK_MAX_HANDS)
+        } else {
+            (!reader.get::<bool>()?, reader.get()?)
+        };
+        if !(1..=BLACKJACK_MAX_HANDS).contains(&max_hands) {
             return None;
         }
-        let cards = blob[idx..idx + c_len].to_vec();
-        idx += c_len;
-
-        hands.push(HandState {
-            cards,
-            bet_mult,
-            status,
-            was_split,
-        });
-    }
-
-    if idx >= blob.len() {
-        return None;
-    }
-    let d_len = blob[idx] as usize;
-    idx += 1;
-
-    if d_len > MAX_HAND_SIZE || idx + d_len > blob.len() {
-        return None;
-    }
-    let dealer_cards = blob[idx..idx + d_len].to_vec();
-    idx += d_len;
 
-    if idx != blob.len() {
-        return None;
+        Some(Self {
+            stage,
+            side_bet_21plus3,
+            initial_player_cards,
+            active_hand_idx,
+            hands,
+            dealer_cards,
+            dealer_hits_soft_17,
+            max_hands,
+            insurance_bet: reader.since(STATE_VERSION_V4, 0)?,
+            keep_playing: reader.since(STATE_VERSION_V5, false)?,
+            dealer_peeks: reader.since(STATE_VERSION_V6, false)?,
+            even_money: reader.since(STATE_VERSION_V6, false)?,
+            peeked: reader.since(STATE_VERSION_V6, false)?,
+            shoe: if reader.remaining() > 0 {
+                Some(reader.get()?)
+            } else {
+                None
+            },
+        })
     }
-
-    Some(BlackjackState {
-        stage,
-        side_bet_21plus3,
-        initial_player_cards,
-        active_hand_idx,
-        hands,
-        dealer_cards,
-    })
 }
 
 pub struct Blackjack;
@@ -400,11 +471,73 @@ impl CasinoGame for Blackjack {
             active_hand_idx: 0,
             hands: Vec::new(),
             dealer_cards: Vec::new(),
+            dealer_hits_soft_17: true,
+            max_hands: BLACKJACK_MAX_HANDS,
+            insurance_bet: 0,
+            keep_playing: false,
+            dealer_peeks: false,
+            even_money: false,
+            peeked: false,
+            shoe: None,
         };
-        session.state_blob = serialize_state(&state);
+        session.state_blob = state.to_blob();
         GameResult::Continue
     }
 
+    fn configure(session: &mut GameSession, config: &GameConfig) {
+        if let Some(mut state) = BlackjackState::from_blob(&session.state_blob) {
+            state.dealer_hits_soft_17 = config.dealer_hits_soft_17;
+            state.max_hands = config.max_split_hands;
+            state.dealer_peeks = config.dealer_peeks;
+            state.even_money = config.even_money;
+            state.shoe = Shoe::from_config(config);
+            session.state_blob = state.to_blob();
+        }
+    }
+
+    fn table_events(before: &[u8], session: &GameSession, _payload: &[u8]) -> Vec<TableEvent> {
+        let (Some(before), Some(state)) = (
+            BlackjackState::from_blob(before),
+            BlackjackState::from_blob(&session.state_blob),
+        ) else {
+            return Vec::new();
+        };
+        let player = |hand: usize| Seat::Player(hand as u8);
+        let mut dealt = Vec::new();
+
+        let deal = matches!(before.stage, Stage::Betting | Stage::Complete);
+        if deal {
+            // The deal: the player's two cards, then the dealer's up card
+            if let Some(hand) = state.hands.first() {
+                dealt.extend(hand.cards.iter().map(|&card| (card, player(0))));
+            }
+        } else if state.hands.len() > before.hands.len() {
+            // A split: each of the two hands is dealt its second card
+            let split = before.active_hand_idx;
+            for idx in [split, split + 1] {
+                if let Some(&card) = state.hands.get(idx).and_then(|hand| hand.cards.get(1)) {
+                    dealt.push((card, player(idx)));
+                }
+            }
+        } else {
+            for (idx, (hand, prior)) in state.hands.iter().zip(&before.hands).enumerate() {
+                let new_cards = hand.cards.get(prior.cards.len()..).unwrap_or_default();
+                dealt.extend(new_cards.iter().map(|&card| (card, player(idx))));
+            }
+        }
+        let prior_dealer_cards = if deal { 0 } else { before.dealer_cards.len() };
+        let dealer_cards = state
+            .dealer_cards
+            .get(prior_dealer_cards..)
+            .unwrap_or_default();
+        dealt.extend(dealer_cards.iter().map(|&card| (card, Seat::Dealer)));
+
+        dealt
+            .into_iter()
+            .map(|(card, to)| TableEvent::CardDealt { card, to })
+            .collect()
+    }
+
     fn process_move(
         session: &mut GameSession,
         payload: &[u8],
@@ -418,36 +551,49 @@ impl CasinoGame for Blackjack {
         }
 
         let mv = Move::try_from(payload[0])?;
-        let mut state = parse_state(&session.state_blob).ok_or(GameError::InvalidPayload)?;
+        let mut state =
+            BlackjackState::from_blob(&session.state_blob).ok_or(GameError::InvalidPayload)?;
 
+        // A round resolved with the session kept open: a side bet or deal starts the next one,
+        // charging its main bet.
+        let mut round_bet = 0;
         if state.stage == Stage::Complete {
-            return Err(GameError::GameAlreadyComplete);
+            if !matches!(mv, Move::Set21Plus3 | Move::Deal | Move::DealAndContinue) {
+                return Err(GameError::GameAlreadyComplete);
+            }
+            start_next_round(&mut state);
+            round_bet = session.bet;
         }
 
         match state.stage {
             Stage::Betting => match mv {
                 Move::Set21Plus3 => {
                     let new_bet = parse_u64_be(payload, 1)?;
-                    let payout = apply_21plus3_update(&mut state, new_bet)?;
-                    session.state_blob = serialize_state(&state);
+                    let payout =
+                        apply_21plus3_update(&mut state, new_bet)?.saturating_sub(round_bet as i64);
+                    session.state_blob = state.to_blob();
                     Ok(if payout == 0 {
                         GameResult::Continue
                     } else {
                         GameResult::ContinueWithUpdate { payout }
                     })
                 }
-                Move::Deal => {
+                Move::Deal | Move::DealAndContinue => {
                     if payload.len() != 1 {
                         return Err(GameError::InvalidPayload);
                     }
                     if !state.hands.is_empty() || !state.dealer_cards.is_empty() {
                         return Err(GameError::InvalidMove);
                     }
+                    state.keep_playing = mv == Move::DealAndContinue;
 
-                    let mut deck = rng.create_shoe(BLACKJACK_DECKS);
-                    let p1 = rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
-                    let p2 = rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
-                    let dealer_up = rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
+                    if let Some(shoe) = &mut state.shoe {
+                        shoe.start_round();
+                    }
+                    let mut deck = CardSource::new(state.shoe.as_mut(), rng, BLACKJACK_DECKS, &[]);
+                    let p1 = deck.draw(rng).ok_or(GameError::DeckExhausted)?;
+                    let p2 = deck.draw(rng).ok_or(GameError::DeckExhausted)?;
+                    let dealer_up = deck.draw(rng).ok_or(GameError::DeckExhausted)?;
 
                     state.initial_player_cards = [p1, p2];
                     let player_cards = vec![p1, p2];
@@ -465,6 +611,17 @@ impl CasinoGame for Blackjack {
                     }];
                     state.dealer_cards = vec![dealer_up];
                     state.active_hand_idx = 0;
+
+                    // A dealer showing a ten-value card peeks at once (an Ace waits until
+                    // insurance and even money have been offered)
+                    if state.dealer_peeks
+                        && card_rank(dealer_up) >= 9
+                        && peek(&mut state.dealer_cards, &mut state.peeked, &mut deck, rng)?
+                    {
+                        let total_return = resolve_hands_return(session.bet, &state)
+                            .saturating_add(resolve_21plus3_return(&state));
+                        return Ok(resolve_round(session, &mut state, total_return, round_bet));
+                    }
                     state.stage = if player_bj {
                         Stage::AwaitingReveal
                     } else {
@@ -477,8 +634,14 @@ impl CasinoGame for Blackjack {
                         state.stage = Stage::AwaitingReveal;
                     }
 
-                    session.state_blob = serialize_state(&state);
-                    Ok(GameResult::Continue)
+                    session.state_blob = state.to_blob();
+                    Ok(if round_bet == 0 {
+                        GameResult::Continue
+                    } else {
+                        GameResult::ContinueWithUpdate {
+                            payout: -(round_bet as i64),
+                        }
+                    })
                 }
                 _ => Err(GameError::InvalidMove),
             },
@@ -489,7 +652,23 @@ impl CasinoGame for Blackjack {
                     all_cards.extend_from_slice(&h.cards);
                 }
                 all_cards.extend_from_slice(&state.dealer_cards);
-                let mut deck = rng.create_shoe_excluding(&all_cards, BLACKJACK_DECKS);
+                // The dealer peeks under an Ace once the player acts on the hand (insurance
+                // peeks as it is placed)
+                let acting = matches!(
+                    mv,
+                    Move::Hit | Move::Stand | Move::Double | Move::Split | Move::Surrender
+                );
+                let peek_first = acting
+                    && state.dealer_peeks
+                    && !state.peeked
+                    && dealer_shows_ace(&state.dealer_cards);
+                let mut deck =
+                    CardSource::new(state.shoe.as_mut(), rng, BLACKJACK_DECKS, &all_cards);
+                if peek_first && peek(&mut state.dealer_cards, &mut state.peeked, &mut deck, rng)? {
+                    let total_return = resolve_hands_return(session.bet, &state)
+                        .saturating_add(resolve_21plus3_return(&state));
+                    return Ok(resolve_round(session, &mut state, total_return, 0));
+                }
 
                 match mv {
                     Move::Hit => {
@@ -501,7 +680,7 @@ impl CasinoGame for Blackjack {
                             return Err(GameError::InvalidMove);
                         }
 
-                        let card = rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
+                        let card = deck.draw(rng).ok_or(GameError::DeckExhausted)?;
                         hand.cards.push(card);
                         session.move_count = session.move_count.saturating_add(1);
 
@@ -509,17 +688,13 @@ impl CasinoGame for Blackjack {
                         if val > 21 {
                             hand.status = HandStatus::Busted;
                             if !advance_turn(&mut state) {
-                                // If all hands are busted, dealer play/reveal is irrelevant.
+                                // If all hands are busted, dealer play/reveal is irrelevant (unless
+                                // insurance still needs the hole card).
                                 let all_busted =
                                     state.hands.iter().all(|h| h.status == HandStatus::Busted);
-                                if all_busted {
+                                if all_busted && state.insurance_bet == 0 {
                                     let total_return = resolve_21plus3_return(&state);
-
-                                    state.stage = Stage::Complete;
-                                    session.is_complete = true;
-                                    session.state_blob = serialize_state(&state);
-
-                                    return Ok(finalize_game_result(session, &state, total_return));
+                                    return Ok(resolve_round(session, &mut state, total_return, 0));
                                 }
 
                                 state.stage = Stage::AwaitingReveal;
@@ -531,7 +706,7 @@ impl CasinoGame for Blackjack {
                             }
                         }
 
-                        session.state_blob = serialize_state(&state);
+                        session.state_blob = state.to_blob();
                         Ok(GameResult::Continue)
                     }
                     Move::Stand => {
@@ -549,7 +724,7 @@ impl CasinoGame for Blackjack {
                             state.stage = Stage::AwaitingReveal;
                         }
 
-                        session.state_blob = serialize_state(&state);
+                        session.state_blob = state.to_blob();
                         Ok(GameResult::Continue)
                     }
                     Move::Double => {
@@ -567,7 +742,7 @@ impl CasinoGame for Blackjack {
                         let extra_bet = session.bet;
                         hand.bet_mult = 2;
 
-                        let card = rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
+                        let card = deck.draw(rng).ok_or(GameError::DeckExhausted)?;
                         hand.cards.push(card);
                         session.move_count = session.move_count.saturating_add(1);
 
@@ -579,23 +754,27 @@ impl CasinoGame for Blackjack {
                         };
 
                         if !advance_turn(&mut state) {
-                            // If all hands are busted, dealer play/reveal is irrelevant.
+                            // If all hands are busted, dealer play/reveal is irrelevant (unless
+                            // insurance still needs the hole card).
                             let all_busted =
                                 state.hands.iter().all(|h| h.status == HandStatus::Busted);
-                            if all_busted {
+                            if all_busted && state.insurance_bet == 0 {
                                 let total_return = resolve_21plus3_return(&state);
 
-                                state.stage = Stage::Complete;
-                                session.is_complete = true;
-                                session.state_blob = serialize_state(&state);
-
-                                return Ok(finalize_game_result(session, &state, total_return));
+                                // The double is charged with the round's result, as there is no
+                                // further move to deduct it.
+                                return Ok(resolve_round(
+                                    session,
+                                    &mut state,
+                                    total_return,
+                                    extra_bet,
+                                ));
                             }
 
                             state.stage = Stage::AwaitingReveal;
                         }
 
-                        session.state_blob = serialize_state(&state);
+                        session.state_blob = state.to_blob();
                         Ok(GameResult::ContinueWithUpdate {
                             payout: -(extra_bet as i64),
                         })
@@ -604,7 +783,7 @@ impl CasinoGame for Blackjack {
                         if state.active_hand_idx >= state.hands.len() {
                             return Err(GameError::InvalidState);
                         }
-                        if state.hands.len() >= MAX_HANDS {
+                        if state.hands.len() >= state.max_hands as usize {
                             return Err(GameError::InvalidMove);
                         }
 
@@ -628,10 +807,10 @@ impl CasinoGame for Blackjack {
                         current_hand.was_split = true;
 
                         // Deal a card to each split hand
-                        let c1 = rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
+                        let c1 = deck.draw(rng).ok_or(GameError::DeckExhausted)?;
                         current_hand.cards.push(c1);
 
-                        let c2 = rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
+                        let c2 = deck.draw(rng).ok_or(GameError::DeckExhausted)?;
                         let new_hand = HandState {
                             cards: vec![split_card, c2],
                             bet_mult: 1,
@@ -641,12 +820,82 @@ impl CasinoGame for Blackjack {
 
                         state.hands.insert(state.active_hand_idx + 1, new_hand);
 
+                        // Split Aces receive one card each and stand.
+                        if r1 == 0 {
+                            let idx = state.active_hand_idx;
+                            for hand in &mut state.hands[idx..=idx + 1] {
+                                hand.status = HandStatus::Standing;
+                            }
+                            if !advance_turn(&mut state) {
+                                state.stage = Stage::AwaitingReveal;
+                            }
+                        }
+
                         session.move_count = session.move_count.saturating_add(1);
-                        session.state_blob = serialize_state(&state);
+                        session.state_blob = state.to_blob();
                         Ok(GameResult::ContinueWithUpdate {
                             payout: -(split_bet as i64),
                         })
                     }
+                    Move::Insurance => {
+                        if payload.len() != 1 {
+                            return Err(GameError::InvalidPayload);
+                        }
+                        if !dealer_shows_ace(&state.dealer_cards)
+                            || state.insurance_bet != 0
+                            || state.peeked
+                            || !is_initial_decision(&state.hands)
+                        {
+                            return Err(GameError::InvalidMove);
+                        }
+
+                        let insurance_bet = session.bet / 2;
+                        if insurance_bet == 0 {
+                            return Err(GameError::InvalidMove);
+                        }
+                        state.insurance_bet = insurance_bet;
+                        session.move_count = session.move_count.saturating_add(1);
+
+                        // With insurance placed, the dealer peeks (charging the insurance with
+                        // the result if that ends the round)
+                        if state.dealer_peeks
+                            && peek(&mut state.dealer_cards, &mut state.peeked, &mut deck, rng)?
+                        {
+                            let total_return = resolve_hands_return(session.bet, &state)
+                                .saturating_add(resolve_insurance_return(&state))
+                                .saturating_add(resolve_21plus3_return(&state));
+                            return Ok(resolve_round(
+                                session,
+                                &mut state,
+                                total_return,
+                                insurance_bet,
+                            ));
+                        }
+
+                        session.state_blob = state.to_blob();
+                        Ok(GameResult::ContinueWithUpdate {
+                            payout: -(insurance_bet as i64),
+                        })
+                    }
+                    Move::Surrender => {
+                        if payload.len() != 1 {
+                            return Err(GameError::InvalidPayload);
+                        }
+                        if !is_initial_decision(&state.hands) {
+                            return Err(GameError::InvalidMove);
+                        }
+
+                        // Half the bet comes back at Reveal, once the dealer is known not to have
+                        // blackjack.
+                        state.hands[0].status = HandStatus::Surrendered;
+                        if !advance_turn(&mut state) {
+                            state.stage = Stage::AwaitingReveal;
+                        }
+
+                        session.move_count = session.move_count.saturating_add(1);
+                        session.state_blob = state.to_blob();
+                        Ok(GameResult::Continue)
+                    }
                     _ => Err(GameError::InvalidMove),
                 }
             }
@@ -662,59 +911,59 @@ impl CasinoGame for Blackjack {
                         all_cards.extend_from_slice(&h.cards);
                     }
                     all_cards.extend_from_slice(&state.dealer_cards);
-                    let mut deck = rng.create_shoe_excluding(&all_cards, BLACKJACK_DECKS);
-
-                    // Reveal dealer hole card.
-                    let hole = rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
+                    let mut deck =
+                        CardSource::new(state.shoe.as_mut(), rng, BLACKJACK_DECKS, &all_cards);
+
+                    // Reveal dealer hole card (which can't make a blackjack once the dealer
+                    // has peeked).
+                    let up = *state.dealer_cards.first().ok_or(GameError::InvalidState)?;
+                    let hole = if state.peeked {
+                        deck.draw_where(rng, |card| !is_blackjack(&[up, card]))
+                    } else {
+                        deck.draw(rng)
+                    }
+                    .ok_or(GameError::DeckExhausted)?;
                     state.dealer_cards.push(hole);
 
-                    let any_live = state.hands.iter().any(|h| h.status != HandStatus::Busted);
+                    let any_live = state
+                        .hands
+                        .iter()
+                        .any(|h| !matches!(h.status, HandStatus::Busted | HandStatus::Surrendered));
                     if any_live {
-                        loop {
-                            let (val, is_soft) = hand_value(&state.dealer_cards);
-                            if val > 17 || (val == 17 && !is_soft) {
-                                break;
-                            }
-                            let c = rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
+                        while !dealer_stands(&state.dealer_cards, state.dealer_hits_soft_17) {
+                            let c = deck.draw(rng).ok_or(GameError::DeckExhausted)?;
                             state.dealer_cards.push(c);
                         }
                     }
 
-                    let (d_val, _) = hand_value(&state.dealer_cards);
-                    let d_bj = is_blackjack(&state.dealer_cards);
-
-                    let mut total_return: u64 = 0;
-                    for hand in &state.hands {
-                        let bet = session.bet.saturating_mul(hand.bet_mult as u64);
-                        if hand.status == HandStatus::Busted {
-                            continue;
-                        }
-
-                        let (p_val, _) = hand_value(&hand.cards);
-                        let p_bj = is_natural_blackjack(hand);
-
-                        if p_bj && d_bj {
-                            total_return = total_return.saturating_add(bet);
-                        } else if p_bj {
-                            total_return = total_return.saturating_add(bet.saturating_mul(5) / 2);
-                        } else if d_bj {
-                            // Lose.
-                        } else if d_val > 21 {
-                            total_return = total_return.saturating_add(bet.saturating_mul(2));
-                        } else if p_val > d_val {
-                            total_return = total_return.saturating_add(bet.saturating_mul(2));
-                        } else if p_val == d_val {
-                            total_return = total_return.saturating_add(bet);
-                        }
+                    let total_return = resolve_hands_return(session.bet, &state)
+                        .saturating_add(resolve_insurance_return(&state))
+                        .saturating_add(resolve_21plus3_return(&state));
+                    Ok(resolve_round(session, &mut state, total_return, 0))
+                }
+                Move::EvenMoney => {
+                    if payload.len() != 1 {
+                        return Err(GameError::InvalidPayload);
+                    }
+                    let natural =
+                        matches!(state.hands.as_slice(), [hand] if is_natural_blackjack(hand));
+                    if !state.even_money
+                        || !natural
+                        || !dealer_shows_ace(&state.dealer_cards)
+                        || state.dealer_cards.len() != 1
+                        || state.insurance_bet != 0
+                    {
+                        return Err(GameError::InvalidMove);
                     }
 
-                    total_return = total_return.saturating_add(resolve_21plus3_return(&state));
-
-                    state.stage = Stage::Complete;
-                    session.is_complete = true;
-                    session.state_blob = serialize_state(&state);
-
-                    Ok(finalize_game_result(session, &state, total_return))
+                    // The blackjack is paid 1:1 whatever the dealer holds, so the hole card is
+                    // never drawn.
+                    session.move_count = session.move_count.saturating_add(1);
+                    let total_return = session
+                        .bet
+                        .saturating_mul(2)
+                        .saturating_add(resolve_21plus3_return(&state));
+                    Ok(resolve_round(session, &mut state, total_return, 0))
                 }
                 _ => Err(GameError::InvalidMove),
             },
@@ -723,6 +972,101 @@ impl CasinoGame for Blackjack {
     }
 }
 
+/// Clear a resolved round, keeping the table rules and shoe, so the next one can be bet and dealt.
+fn start_next_round(state: &mut BlackjackState) {
+    state.stage = Stage::Betting;
+    state.side_bet_21plus3 = 0;
+    state.initial_player_cards = [CARD_UNKNOWN; 2];
+    state.active_hand_idx = 0;
+    state.hands.clear();
+    state.dealer_cards.clear();
+    state.insurance_bet = 0;
+    state.keep_playing = false;
+    state.peeked = false;
+}
+
+/// Complete the round. Unless it was dealt to keep the session open, this ends the session with
+/// its final result; otherwise the round's return (less any `extra_deduction` still owed) is
+/// settled mid-session.
+fn resolve_round(
+    session: &mut GameSession,
+    state: &mut BlackjackState,
+    total_return: u64,
+    extra_deduction: u64,
+) -> GameResult {
+    state.stage = Stage::Complete;
+    session.state_blob = state.to_blob();
+    if !state.keep_playing {
+        session.is_complete = true;
+        return finalize_game_result(session, state, total_return, extra_deduction);
+    }
+
+    let total_return = apply_super_multiplier(session, state, total_return);
+    let payout = i64::try_from(total_return)
+        .unwrap_or(i64::MAX)
+        .saturating_sub(i64::try_from(extra_deduction).unwrap_or(i64::MAX));
+    if payout == 0 {
+        GameResult::Continue
+    } else {
+        GameResult::ContinueWithUpdate { payout }
+    }
+}
+
+/// Whether the dealer's up card is an Ace.
+fn dealer_shows_ace(dealer_cards: &[u8]) -> bool {
+    dealer_cards.first().is_some_and(|&c| card_rank(c) == 0)
+}
+
+/// Whether the player is still on their first decision (a single, untouched two-card hand).
+fn is_initial_decision(hands: &[HandState]) -> bool {
+    match hands {
+        [hand] => {
+            hand.status == HandStatus::Playing
+                && hand.cards.len() == 2
+                && hand.bet_mult == 1
+                && !hand.was_split
+        }
+        _ => false,
+    }
+}
+
+/// Return (stake plus winnings) of the player's hands against the dealer's final hand.
+fn resolve_hands_return(base_bet: u64, state: &BlackjackState) -> u64 {
+    let (d_val, _) = hand_value(&state.dealer_cards);
+    let d_bj = is_blackjack(&state.dealer_cards);
+
+    let mut total_return: u64 = 0;
+    for hand in &state.hands {
+        let bet = base_bet.saturating_mul(hand.bet_mult as u64);
+        let (p_val, _) = hand_value(&hand.cards);
+        let p_bj = is_natural_blackjack(hand);
+
+        let hand_return = match hand.status {
+            HandStatus::Busted => 0,
+            // Late surrender: forfeited to a dealer blackjack.
+            HandStatus::Surrendered if d_bj => 0,
+            HandStatus::Surrendered => bet / 2,
+            _ if p_bj && d_bj => bet,
+            _ if p_bj => bet.saturating_mul(5) / 2,
+            _ if d_bj => 0,
+            _ if d_val > 21 || p_val > d_val => bet.saturating_mul(2),
+            _ if p_val == d_val => bet,
+            _ => 0,
+        };
+        total_return = total_return.saturating_add(hand_return);
+    }
+    total_return
+}
+
+/// Return of the insurance bet (2:1 when the dealer has blackjack).
+fn resolve_insurance_return(state: &BlackjackState) -> u64 {
+    if is_blackjack(&state.dealer_cards) {
+        state.insurance_bet.saturating_mul(3)
+    } else {
+        0
+    }
+}
+
 /// Advance active turn to next playing hand. Returns true if there is a hand to play.
 fn advance_turn(state: &mut BlackjackState) -> bool {
     while state.active_hand_idx < state.hands.len() {
@@ -740,7 +1084,9 @@ fn total_wagered(session: &GameSession, state: &BlackjackState) -> u64 {
         .iter()
         .map(|h| session.bet.saturating_mul(h.bet_mult as u64))
         .sum();
-    main_wagered.saturating_add(state.side_bet_21plus3)
+    main_wagered
+        .saturating_add(state.side_bet_21plus3)
+        .saturating_add(state.insurance_bet)
 }
 
 fn apply_super_multiplier(session: &GameSession, state: &BlackjackState, total_return: u64) -> u64 {
@@ -753,17 +1099,27 @@ fn apply_super_multiplier(session: &GameSession, state: &BlackjackState, total_r
     apply_super_multiplier_cards(&hand.cards, &session.super_mode.multipliers, total_return)
 }
 
+/// Final result of a completed hand. `extra_deduction` is any wager that still has to be
+/// charged (a double that ends the game immediately).
 fn finalize_game_result(
     session: &GameSession,
     state: &BlackjackState,
     total_return: u64,
+    extra_deduction: u64,
 ) -> GameResult {
     let total_wagered = total_wagered(session, state);
     let total_return = apply_super_multiplier(session, state, total_return);
-    if total_return == 0 {
-        GameResult::LossPreDeducted(total_wagered)
-    } else {
-        GameResult::Win(total_return)
+    match (total_return, extra_deduction) {
+        (0, 0) => GameResult::LossPreDeducted(total_wagered),
+        (0, extra_deduction) => GameResult::LossPreDeductedWithExtraDeduction {
+            total_loss: total_wagered,
+            extra_deduction,
+        },
+        (payout, 0) => GameResult::Win(payout),
+        (payout, extra_deduction) => GameResult::WinWithExtraDeduction {
+            payout,
+            extra_deduction,
+        },
     }
 }
 
@@ -802,6 +1158,507 @@ mod tests {
         assert!(!is_natural_blackjack(&hand));
     }
 
+    #[test]
+    fn test_dealer_soft_17_rule() {
+        // A + 6 is a soft 17, 10 + 7 a hard 17
+        assert!(!dealer_stands(&[0, 5], true));
+        assert!(dealer_stands(&[0, 5], false));
+        assert!(dealer_stands(&[9, 6], true));
+        assert!(!dealer_stands(&[9, 5], false));
+    }
+
+    #[test]
+    fn test_configure() {
+        let (network_secret, _) = crate::mocks::create_network_keypair();
+        let seed = crate::mocks::create_seed(&network_secret, 1);
+        let (_, public) = crate::mocks::create_account_keypair(1);
+        let mut session = GameSession {
+            id: 1,
+            player: public,
+            game_type: GameType::Blackjack,
+            bet: 100,
+            state_blob: vec![],
+            move_count: 0,
+            created_at: 0,
+            is_complete: false,
+            super_mode: SuperModeState::default(),
+            is_tournament: false,
+            tournament_id: None,
+            last_move_at: 0,
+            wagered: 100,
+            entropy: None,
+        };
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        Blackjack::init(&mut session, &mut rng);
+        assert!(
+            BlackjackState::from_blob(&session.state_blob)
+                .unwrap()
+                .dealer_hits_soft_17
+        );
+
+        // The configured rule is recorded in the session's state
+        Blackjack::configure(
+            &mut session,
+            &GameConfig {
+                dealer_hits_soft_17: false,
+                ..GameConfig::default()
+            },
+        );
+        assert!(
+            !BlackjackState::from_blob(&session.state_blob)
+                .unwrap()
+                .dealer_hits_soft_17
+        );
+
+        // The configured split limit is recorded too
+        Blackjack::configure(
+            &mut session,
+            &GameConfig {
+                max_split_hands: 2,
+                ..GameConfig::default()
+            },
+        );
+        assert_eq!(
+            BlackjackState::from_blob(&session.state_blob)
+                .unwrap()
+                .max_hands,
+            2
+        );
+
+        // The peek and even money rules are recorded too
+        Blackjack::configure(
+            &mut session,
+            &GameConfig {
+                max_split_hands: 2,
+                dealer_peeks: true,
+                even_money: true,
+                ..GameConfig::default()
+            },
+        );
+        let state = BlackjackState::from_blob(&session.state_blob).unwrap();
+        assert!(state.dealer_peeks && state.even_money && !state.peeked);
+
+        // v5 sessions have neither
+        let mut v5 = session.state_blob[..session.state_blob.len() - 3].to_vec();
+        v5[0] = STATE_VERSION_V5;
+        let state = BlackjackState::from_blob(&v5).unwrap();
+        assert!(!state.dealer_peeks && !state.even_money);
+
+        // v4 sessions play a single round
+        let mut v4 = session.state_blob[..session.state_blob.len() - 4].to_vec();
+        v4[0] = STATE_VERSION_V4;
+        let state = BlackjackState::from_blob(&v4).unwrap();
+        assert!(!state.keep_playing);
+        assert_eq!(state.shoe, None);
+
+        // v2 sessions keep their soft 17 rule (H17 if it wasn't recorded) and split to 4 hands
+        let mut legacy = session.state_blob[..session.state_blob.len() - 13].to_vec();
+        legacy[0] = STATE_VERSION_V2;
+        let state = BlackjackState::from_blob(&legacy).unwrap();
+        assert!(state.dealer_hits_soft_17);
+        assert_eq!(state.max_hands, BLACKJACK_MAX_HANDS);
+        *legacy.last_mut().unwrap() = 1;
+        assert!(
+            !BlackjackState::from_blob(&legacy)
+                .unwrap()
+                .dealer_hits_soft_17
+        );
+        legacy.pop();
+        assert!(
+            BlackjackState::from_blob(&legacy)
+                .unwrap()
+                .dealer_hits_soft_17
+        );
+
+        // v3 sessions have no insurance, and must record a usable split limit
+        let mut v3 = session.state_blob[..session.state_blob.len() - 12].to_vec();
+        v3[0] = STATE_VERSION_V3;
+        let state = BlackjackState::from_blob(&v3).unwrap();
+        assert_eq!(state.max_hands, 2);
+        assert_eq!(state.insurance_bet, 0);
+        *v3.last_mut().unwrap() = 0;
+        assert!(BlackjackState::from_blob(&v3).is_none());
+        v3.pop();
+        assert!(BlackjackState::from_blob(&v3).is_none());
+
+        // A table's persistent shoe is kept at the end of the state
+        Blackjack::configure(
+            &mut session,
+            &GameConfig {
+                shoe_decks: 6,
+                ..GameConfig::default()
+            },
+        );
+        assert_eq!(
+            BlackjackState::from_blob(&session.state_blob).unwrap().shoe,
+            Some(Shoe::new(6, 75))
+        );
+    }
+
+    #[test]
+    fn test_state_blob_fuzz() {
+        let hand = |cards: &[u8], status, was_split| HandState {
+            cards: cards.to_vec(),
+            bet_mult: 1,
+            status,
+            was_split,
+        };
+        let state = BlackjackState {
+            stage: Stage::PlayerTurn,
+            side_bet_21plus3: 5,
+            initial_player_cards: [8, 21],
+            active_hand_idx: 1,
+            hands: vec![
+                hand(&[8, 3, 9], HandStatus::Standing, true),
+                hand(&[21], HandStatus::Playing, true),
+            ],
+            dealer_cards: vec![12, 0xFF],
+            dealer_hits_soft_17: false,
+            max_hands: 2,
+            insurance_bet: 50,
+            keep_playing: false,
+            dealer_peeks: false,
+            even_money: false,
+            peeked: false,
+            shoe: None,
+        };
+        let states = [
+            BlackjackState {
+                stage: Stage::Betting,
+                active_hand_idx: 0,
+                hands: Vec::new(),
+                dealer_cards: Vec::new(),
+                ..state.clone()
+            },
+            BlackjackState {
+                stage: Stage::Complete,
+                hands: vec![hand(&[0, 12], HandStatus::Blackjack, false)],
+                dealer_hits_soft_17: true,
+                max_hands: BLACKJACK_MAX_HANDS,
+                ..state.clone()
+            },
+            BlackjackState {
+                keep_playing: true,
+                dealer_peeks: true,
+                even_money: true,
+                peeked: true,
+                shoe: Some(Shoe::new(8, 90)),
+                ..state.clone()
+            },
+            state,
+        ];
+        crate::casino::state_blob::fuzz::roundtrip(&states);
+    }
+
+    fn split_test_session(state: &BlackjackState) -> GameSession {
+        let (_, public) = crate::mocks::create_account_keypair(1);
+        GameSession {
+            id: 0,
+            player: public,
+            game_type: GameType::Blackjack,
+            bet: 100,
+            state_blob: state.to_blob(),
+            move_count: 1,
+            created_at: 0,
+            is_complete: false,
+            super_mode: SuperModeState::default(),
+            is_tournament: false,
+            tournament_id: None,
+            last_move_at: 0,
+            wagered: 100,
+            entropy: None,
+        }
+    }
+
+    fn pair_hand(cards: Vec<u8>, was_split: bool) -> HandState {
+        HandState {
+            cards,
+            bet_mult: 1,
+            status: HandStatus::Playing,
+            was_split,
+        }
+    }
+
+    #[test]
+    fn test_resplit_limit() {
+        let (network_secret, _) = crate::mocks::create_network_keypair();
+        let seed = crate::mocks::create_seed(&network_secret, 1);
+
+        // Eights were split, and the first hand drew another eight
+        let mut state = BlackjackState {
+            stage: Stage::PlayerTurn,
+            side_bet_21plus3: 0,
+            initial_player_cards: [7, 20],
+            active_hand_idx: 0,
+            hands: vec![pair_hand(vec![7, 33], true), pair_hand(vec![20, 4], true)],
+            dealer_cards: vec![5],
+            dealer_hits_soft_17: true,
+            max_hands: 2,
+            insurance_bet: 0,
+            keep_playing: false,
+            dealer_peeks: false,
+            even_money: false,
+            peeked: false,
+            shoe: None,
+        };
+
+        let mut session = split_test_session(&state);
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        assert!(matches!(
+            Blackjack::process_move(&mut session, &[Move::Split as u8], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+
+        // Re-splitting is allowed up to the limit
+        state.max_hands = 3;
+        let mut session = split_test_session(&state);
+        let result = Blackjack::process_move(&mut session, &[Move::Split as u8], &mut rng).unwrap();
+        assert!(matches!(
+            result,
+            GameResult::ContinueWithUpdate { payout: -100 }
+        ));
+        let state = BlackjackState::from_blob(&session.state_blob).unwrap();
+        assert_eq!(state.hands.len(), 3);
+        assert_eq!(state.hands[1].cards[0], 33);
+        assert!(state
+            .hands
+            .iter()
+            .all(|h| h.was_split && h.cards.len() == 2));
+        assert_eq!(state.stage, Stage::PlayerTurn);
+        assert!(matches!(
+            Blackjack::process_move(&mut session, &[Move::Split as u8], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+    }
+
+    #[test]
+    fn test_split_aces_receive_one_card() {
+        let (network_secret, _) = crate::mocks::create_network_keypair();
+        let seed = crate::mocks::create_seed(&network_secret, 1);
+
+        let state = BlackjackState {
+            stage: Stage::PlayerTurn,
+            side_bet_21plus3: 0,
+            initial_player_cards: [0, 13],
+            active_hand_idx: 0,
+            hands: vec![pair_hand(vec![0, 13], false)],
+            dealer_cards: vec![5],
+            dealer_hits_soft_17: true,
+            max_hands: BLACKJACK_MAX_HANDS,
+            insurance_bet: 0,
+            keep_playing: false,
+            dealer_peeks: false,
+            even_money: false,
+            peeked: false,
+            shoe: None,
+        };
+
+        let mut session = split_test_session(&state);
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        let result = Blackjack::process_move(&mut session, &[Move::Split as u8], &mut rng).unwrap();
+        assert!(matches!(
+            result,
+            GameResult::ContinueWithUpdate { payout: -100 }
+        ));
+
+        // Both hands stand on their second card, so the dealer plays next
+        let state = BlackjackState::from_blob(&session.state_blob).unwrap();
+        assert_eq!(state.stage, Stage::AwaitingReveal);
+        assert_eq!(state.hands.len(), 2);
+        for hand in &state.hands {
+            assert_eq!(hand.cards.len(), 2);
+            assert_eq!(hand.status, HandStatus::Standing);
+            assert!(!is_natural_blackjack(hand));
+        }
+        assert!(matches!(
+            Blackjack::process_move(&mut session, &[Move::Hit as u8], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+    }
+
+    #[test]
+    fn test_table_events() {
+        let (network_secret, _) = crate::mocks::create_network_keypair();
+        let seed = crate::mocks::create_seed(&network_secret, 1);
+        let dealt = |cards: &[u8], to: Seat| {
+            cards
+                .iter()
+                .map(|&card| TableEvent::CardDealt { card, to })
+                .collect::<Vec<_>>()
+        };
+
+        // The deal reports the player's cards, then the dealer's
+        let mut session = split_test_session(&BlackjackState {
+            stage: Stage::Betting,
+            side_bet_21plus3: 0,
+            initial_player_cards: [CARD_UNKNOWN; 2],
+            active_hand_idx: 0,
+            hands: Vec::new(),
+            dealer_cards: Vec::new(),
+            dealer_hits_soft_17: true,
+            max_hands: BLACKJACK_MAX_HANDS,
+            insurance_bet: 0,
+            keep_playing: false,
+            dealer_peeks: false,
+            even_money: false,
+            peeked: false,
+            shoe: None,
+        });
+        let before = session.state_blob.clone();
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        Blackjack::process_move(&mut session, &[Move::Deal as u8], &mut rng).unwrap();
+        let state = BlackjackState::from_blob(&session.state_blob).unwrap();
+        let mut expected = dealt(&state.hands[0].cards, Seat::Player(0));
+        expected.extend(dealt(&state.dealer_cards, Seat::Dealer));
+        assert_eq!(expected.len(), 3);
+        assert_eq!(
+            Blackjack::table_events(&before, &session, &[Move::Deal as u8]),
+            expected
+        );
+
+        // A split deals each hand its second card
+        let mut session = split_test_session(&BlackjackState {
+            stage: Stage::PlayerTurn,
+            side_bet_21plus3: 0,
+            initial_player_cards: [0, 13],
+            active_hand_idx: 0,
+            hands: vec![pair_hand(vec![0, 13], false)],
+            dealer_cards: vec![5],
+            dealer_hits_soft_17: true,
+            max_hands: BLACKJACK_MAX_HANDS,
+            insurance_bet: 0,
+            keep_playing: false,
+            dealer_peeks: false,
+            even_money: false,
+            peeked: false,
+            shoe: None,
+        });
+        let before = session.state_blob.clone();
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        Blackjack::process_move(&mut session, &[Move::Split as u8], &mut rng).unwrap();
+        let state = BlackjackState::from_blob(&session.state_blob).unwrap();
+        assert_eq!(
+            Blackjack::table_events(&before, &session, &[Move::Split as u8]),
+            vec![
+                TableEvent::CardDealt {
+                    card: state.hands[0].cards[1],
+                    to: Seat::Player(0),
+                },
+                TableEvent::CardDealt {
+                    card: state.hands[1].cards[1],
+                    to: Seat::Player(1),
+                },
+            ]
+        );
+
+        // The reveal reports the dealer's hole card and any hits
+        let before = session.state_blob.clone();
+        let mut rng = GameRng::new(&seed, session.id, 2);
+        Blackjack::process_move(&mut session, &[Move::Reveal as u8], &mut rng).unwrap();
+        let state = BlackjackState::from_blob(&session.state_blob).unwrap();
+        assert!(state.dealer_cards.len() >= 2);
+        assert_eq!(
+            Blackjack::table_events(&before, &session, &[Move::Reveal as u8]),
+            dealt(&state.dealer_cards[1..], Seat::Dealer)
+        );
+    }
+
+    #[test]
+    fn test_double_busting_last_split_hand_charges_extra() {
+        let (network_secret, _) = crate::mocks::create_network_keypair();
+        let seed = crate::mocks::create_seed(&network_secret, 1);
+
+        // The first split hand busted; the second (10 + 2) doubles
+        let state = BlackjackState {
+            stage: Stage::PlayerTurn,
+            side_bet_21plus3: 0,
+            initial_player_cards: [9, 22],
+            active_hand_idx: 1,
+            hands: vec![
+                HandState {
+                    cards: vec![9, 11, 24],
+                    bet_mult: 1,
+                    status: HandStatus::Busted,
+                    was_split: true,
+                },
+                pair_hand(vec![22, 1], true),
+            ],
+            dealer_cards: vec![5],
+            dealer_hits_soft_17: true,
+            max_hands: BLACKJACK_MAX_HANDS,
+            insurance_bet: 0,
+            keep_playing: false,
+            dealer_peeks: false,
+            even_money: false,
+            peeked: false,
+            shoe: None,
+        };
+
+        let mut found = None;
+        for session_id in 0u64..64 {
+            let mut session = split_test_session(&state);
+            session.id = session_id;
+            let mut rng = GameRng::new(&seed, session_id, 1);
+            match Blackjack::process_move(&mut session, &[Move::Double as u8], &mut rng).unwrap() {
+                GameResult::ContinueWithUpdate { payout } => assert_eq!(payout, -100),
+                result => {
+                    assert!(session.is_complete);
+                    found = Some(result);
+                    break;
+                }
+            }
+        }
+
+        // Both split hands lose: 300 in total, of which the double's 100 is still owed
+        assert!(matches!(
+            found,
+            Some(GameResult::LossPreDeductedWithExtraDeduction {
+                total_loss: 300,
+                extra_deduction: 100,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_finalize_game_result_extra_deduction() {
+        let state = BlackjackState {
+            stage: Stage::Complete,
+            side_bet_21plus3: 10,
+            initial_player_cards: [9, 22],
+            active_hand_idx: 2,
+            hands: vec![pair_hand(vec![9, 22], true), pair_hand(vec![22, 9], true)],
+            dealer_cards: vec![5],
+            dealer_hits_soft_17: true,
+            max_hands: BLACKJACK_MAX_HANDS,
+            insurance_bet: 0,
+            keep_playing: false,
+            dealer_peeks: false,
+            even_money: false,
+            peeked: false,
+            shoe: None,
+        };
+        let session = split_test_session(&state);
+
+        assert!(matches!(
+            finalize_game_result(&session, &state, 0, 0),
+            GameResult::LossPreDeducted(210)
+        ));
+        assert!(matches!(
+            finalize_game_result(&session, &state, 0, 100),
+            GameResult::LossPreDeductedWithExtraDeduction {
+                total_loss: 210,
+                extra_deduction: 100,
+            }
+        ));
+        assert!(matches!(
+            finalize_game_result(&session, &state, 110, 100),
+            GameResult::WinWithExtraDeduction {
+                payout: 110,
+                extra_deduction: 100,
+            }
+        ));
+    }
+
     #[test]
     fn test_hit_all_busted_returns_loss_prededucted() {
         let (network_secret, _) = crate::mocks::create_network_keypair();
@@ -820,6 +1677,14 @@ mod tests {
                 was_split: false,
             }],
             dealer_cards: vec![0],
+            dealer_hits_soft_17: true,
+            max_hands: BLACKJACK_MAX_HANDS,
+            insurance_bet: 0,
+            keep_playing: false,
+            dealer_peeks: false,
+            even_money: false,
+            peeked: false,
+            shoe: None,
         };
 
         let base_session = GameSession {
@@ -827,13 +1692,16 @@ mod tests {
             player: public,
             game_type: GameType::Blackjack,
             bet: 100,
-            state_blob: serialize_state(&state),
+            state_blob: state.to_blob(),
             move_count: 1,
             created_at: 0,
             is_complete: false,
             super_mode: SuperModeState::default(),
             is_tournament: false,
             tournament_id: None,
+            last_move_at: 0,
+            wagered: 100,
+            entropy: None,
         };
 
         let mut found = None;
@@ -871,6 +1739,14 @@ mod tests {
                 was_split: false,
             }],
             dealer_cards: vec![3],
+            dealer_hits_soft_17: true,
+            max_hands: BLACKJACK_MAX_HANDS,
+            insurance_bet: 0,
+            keep_playing: false,
+            dealer_peeks: false,
+            even_money: false,
+            peeked: false,
+            shoe: None,
         };
 
         let base_session = GameSession {
@@ -878,13 +1754,16 @@ mod tests {
             player: public,
             game_type: GameType::Blackjack,
             bet: 100,
-            state_blob: serialize_state(&state),
+            state_blob: state.to_blob(),
             move_count: 1,
             created_at: 0,
             is_complete: false,
             super_mode: SuperModeState::default(),
             is_tournament: false,
             tournament_id: None,
+            last_move_at: 0,
+            wagered: 100,
+            entropy: None,
         };
 
         let mut found = None;
@@ -903,4 +1782,472 @@ mod tests {
 
         assert_eq!(found, Some(310));
     }
+
+    fn initial_state(player: [u8; 2], dealer_up: u8) -> BlackjackState {
+        BlackjackState {
+            stage: Stage::PlayerTurn,
+            side_bet_21plus3: 0,
+            initial_player_cards: player,
+            active_hand_idx: 0,
+            hands: vec![pair_hand(player.to_vec(), false)],
+            dealer_cards: vec![dealer_up],
+            dealer_hits_soft_17: true,
+            max_hands: BLACKJACK_MAX_HANDS,
+            insurance_bet: 0,
+            keep_playing: false,
+            dealer_peeks: false,
+            even_money: false,
+            peeked: false,
+            shoe: None,
+        }
+    }
+
+    #[test]
+    fn test_insurance_and_surrender_moves() {
+        let (network_secret, _) = crate::mocks::create_network_keypair();
+        let seed = crate::mocks::create_seed(&network_secret, 1);
+
+        // Insurance needs the dealer to show an Ace
+        let mut session = split_test_session(&initial_state([9, 7], 5));
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        assert!(matches!(
+            Blackjack::process_move(&mut session, &[Move::Insurance as u8], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+
+        // Half the bet, taken once
+        let mut session = split_test_session(&initial_state([9, 7], 13));
+        let result =
+            Blackjack::process_move(&mut session, &[Move::Insurance as u8], &mut rng).unwrap();
+        assert!(matches!(
+            result,
+            GameResult::ContinueWithUpdate { payout: -50 }
+        ));
+        assert_eq!(
+            BlackjackState::from_blob(&session.state_blob)
+                .unwrap()
+                .insurance_bet,
+            50
+        );
+        assert!(matches!(
+            Blackjack::process_move(&mut session, &[Move::Insurance as u8], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+
+        // Surrender ends the player's turn (and may follow insurance)
+        let result =
+            Blackjack::process_move(&mut session, &[Move::Surrender as u8], &mut rng).unwrap();
+        assert!(matches!(result, GameResult::Continue));
+        let state = BlackjackState::from_blob(&session.state_blob).unwrap();
+        assert_eq!(state.stage, Stage::AwaitingReveal);
+        assert_eq!(state.hands[0].status, HandStatus::Surrendered);
+
+        // Neither is offered once the initial hand has been played
+        let mut state = initial_state([1, 2], 0);
+        state.hands[0].cards.push(3);
+        let mut session = split_test_session(&state);
+        for mv in [Move::Insurance, Move::Surrender] {
+            assert!(matches!(
+                Blackjack::process_move(&mut session, &[mv as u8], &mut rng),
+                Err(GameError::InvalidMove)
+            ));
+        }
+        let mut state = initial_state([7, 20], 0);
+        state.hands = vec![pair_hand(vec![7, 1], true), pair_hand(vec![20, 2], true)];
+        let mut session = split_test_session(&state);
+        for mv in [Move::Insurance, Move::Surrender] {
+            assert!(matches!(
+                Blackjack::process_move(&mut session, &[mv as u8], &mut rng),
+                Err(GameError::InvalidMove)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_dealer_blackjack_combinations() {
+        let dealer_blackjack = vec![0, 9]; // A + 10
+        let dealer_twenty = vec![9, 22]; // 10 + 10
+
+        // (player cards, status, insured, return against dealer blackjack, return against 20)
+        let cases = [
+            // Naturals push a dealer blackjack and otherwise pay 3:2
+            (vec![0, 12], HandStatus::Blackjack, false, 100, 250),
+            (vec![0, 12], HandStatus::Blackjack, true, 250, 250),
+            // A standing 18 loses either way, and insurance pays 2:1 on the dealer blackjack
+            (vec![9, 7], HandStatus::Standing, false, 0, 0),
+            (vec![9, 7], HandStatus::Standing, true, 150, 0),
+            // Late surrender returns half the bet, unless the dealer has blackjack
+            (vec![9, 5], HandStatus::Surrendered, false, 0, 50),
+            (vec![9, 5], HandStatus::Surrendered, true, 150, 50),
+            // A standing 20 pushes the dealer's 20
+            (vec![9, 11], HandStatus::Standing, true, 150, 100),
+        ];
+
+        for (cards, status, insured, vs_blackjack, vs_twenty) in cases {
+            for (dealer_cards, expected) in [
+                (&dealer_blackjack, vs_blackjack),
+                (&dealer_twenty, vs_twenty),
+            ] {
+                let state = BlackjackState {
+                    stage: Stage::Complete,
+                    side_bet_21plus3: 0,
+                    initial_player_cards: [cards[0], cards[1]],
+                    active_hand_idx: 1,
+                    hands: vec![HandState {
+                        cards: cards.clone(),
+                        bet_mult: 1,
+                        status,
+                        was_split: false,
+                    }],
+                    dealer_cards: dealer_cards.clone(),
+                    dealer_hits_soft_17: true,
+                    max_hands: BLACKJACK_MAX_HANDS,
+                    insurance_bet: if insured { 50 } else { 0 },
+                    keep_playing: false,
+                    dealer_peeks: false,
+                    even_money: false,
+                    peeked: false,
+                    shoe: None,
+                };
+                let total_return = resolve_hands_return(100, &state)
+                    .saturating_add(resolve_insurance_return(&state));
+                assert_eq!(
+                    total_return, expected,
+                    "{cards:?} {status:?} insured={insured}"
+                );
+
+                // Insurance is part of the wager
+                let session = split_test_session(&state);
+                let wagered = if insured { 150 } else { 100 };
+                match finalize_game_result(&session, &state, total_return, 0) {
+                    GameResult::LossPreDeducted(total) => {
+                        assert_eq!(expected, 0);
+                        assert_eq!(total, wagered);
+                    }
+                    GameResult::Win(payout) => assert_eq!(payout, expected),
+                    _ => panic!("unexpected result"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_insured_bust_waits_for_reveal() {
+        let (network_secret, _) = crate::mocks::create_network_keypair();
+        let seed = crate::mocks::create_seed(&network_secret, 1);
+
+        let mut state = initial_state([9, 11], 0); // 20 against an Ace
+        state.insurance_bet = 50;
+
+        let mut found = false;
+        for session_id in 0u64..64 {
+            let mut session = split_test_session(&state);
+            session.id = session_id;
+            let mut rng = GameRng::new(&seed, session_id, 1);
+            let result =
+                Blackjack::process_move(&mut session, &[Move::Hit as u8], &mut rng).unwrap();
+            assert!(matches!(result, GameResult::Continue));
+            let hit = BlackjackState::from_blob(&session.state_blob).unwrap();
+            if hit.hands[0].status != HandStatus::Busted {
+                continue;
+            }
+
+            // The busted hand still needs the hole card to settle the insurance
+            assert_eq!(hit.stage, Stage::AwaitingReveal);
+            assert!(!session.is_complete);
+            let mut rng = GameRng::new(&seed, session_id, 2);
+            let result =
+                Blackjack::process_move(&mut session, &[Move::Reveal as u8], &mut rng).unwrap();
+            let revealed = BlackjackState::from_blob(&session.state_blob).unwrap();
+            assert_eq!(revealed.dealer_cards.len(), 2);
+            if is_blackjack(&revealed.dealer_cards) {
+                assert!(matches!(result, GameResult::Win(150)));
+            } else {
+                assert!(matches!(result, GameResult::LossPreDeducted(150)));
+            }
+            found = true;
+            break;
+        }
+        assert!(found);
+    }
+
+    #[test]
+    fn test_dealer_peek() {
+        let (network_secret, _) = crate::mocks::create_network_keypair();
+        let seed = crate::mocks::create_seed(&network_secret, 1);
+        let is_ten = |card: u8| card_rank(card) >= 9;
+
+        // A ten-value up card is peeked at on the deal
+        let mut betting = initial_state([CARD_UNKNOWN; 2], 0);
+        betting.stage = Stage::Betting;
+        betting.hands.clear();
+        betting.dealer_cards.clear();
+        betting.dealer_peeks = true;
+        let mut outcomes = [false; 2];
+        for session_id in 0u64..400 {
+            let mut session = split_test_session(&betting);
+            session.id = session_id;
+            let mut rng = GameRng::new(&seed, session_id, 1);
+            let result =
+                Blackjack::process_move(&mut session, &[Move::Deal as u8], &mut rng).unwrap();
+            let state = BlackjackState::from_blob(&session.state_blob).unwrap();
+            if !is_ten(state.dealer_cards[0]) {
+                assert!(!state.peeked);
+                continue;
+            }
+            if state.dealer_cards.len() == 2 {
+                // A dealer blackjack is turned over and settles the round
+                outcomes[0] = true;
+                assert!(is_blackjack(&state.dealer_cards));
+                assert!(session.is_complete);
+                assert_eq!(state.stage, Stage::Complete);
+                let expected = if state.hands[0].status == HandStatus::Blackjack {
+                    matches!(result, GameResult::Win(100))
+                } else {
+                    matches!(result, GameResult::LossPreDeducted(100))
+                };
+                assert!(expected);
+            } else {
+                // Otherwise the hole card drawn at the reveal can't make one
+                outcomes[1] = true;
+                assert!(state.peeked);
+                assert!(matches!(result, GameResult::Continue));
+                if state.stage == Stage::PlayerTurn {
+                    let mut rng = GameRng::new(&seed, session_id, 2);
+                    Blackjack::process_move(&mut session, &[Move::Stand as u8], &mut rng).unwrap();
+                }
+                let mut rng = GameRng::new(&seed, session_id, 3);
+                Blackjack::process_move(&mut session, &[Move::Reveal as u8], &mut rng).unwrap();
+                let state = BlackjackState::from_blob(&session.state_blob).unwrap();
+                assert_ne!(card_rank(state.dealer_cards[1]), 0);
+            }
+        }
+        assert_eq!(outcomes, [true; 2]);
+
+        // An Ace is peeked at once the player acts (after insurance has been offered)
+        let mut state = initial_state([9, 7], 13);
+        state.dealer_peeks = true;
+        let mut outcomes = [false; 2];
+        for session_id in 0u64..100 {
+            let mut session = split_test_session(&state);
+            session.id = session_id;
+            let mut rng = GameRng::new(&seed, session_id, 1);
+            let result =
+                Blackjack::process_move(&mut session, &[Move::Stand as u8], &mut rng).unwrap();
+            let stood = BlackjackState::from_blob(&session.state_blob).unwrap();
+            if stood.dealer_cards.len() == 2 {
+                // The stand never happens: the round is over
+                outcomes[0] = true;
+                assert!(is_blackjack(&stood.dealer_cards));
+                assert_eq!(stood.hands[0].status, HandStatus::Playing);
+                assert!(matches!(result, GameResult::LossPreDeducted(100)));
+                assert!(session.is_complete);
+            } else {
+                outcomes[1] = true;
+                assert!(stood.peeked);
+                assert_eq!(stood.stage, Stage::AwaitingReveal);
+                let mut rng = GameRng::new(&seed, session_id, 2);
+                Blackjack::process_move(&mut session, &[Move::Reveal as u8], &mut rng).unwrap();
+                let revealed = BlackjackState::from_blob(&session.state_blob).unwrap();
+                assert!(!is_ten(revealed.dealer_cards[1]));
+            }
+        }
+        assert_eq!(outcomes, [true; 2]);
+
+        // Insurance peeks as it is placed, and isn't offered once the dealer has peeked
+        let mut outcomes = [false; 2];
+        for session_id in 0u64..100 {
+            let mut session = split_test_session(&state);
+            session.id = session_id;
+            let mut rng = GameRng::new(&seed, session_id, 1);
+            let result =
+                Blackjack::process_move(&mut session, &[Move::Insurance as u8], &mut rng).unwrap();
+            let insured = BlackjackState::from_blob(&session.state_blob).unwrap();
+            if insured.dealer_cards.len() == 2 {
+                outcomes[0] = true;
+                assert!(matches!(
+                    result,
+                    GameResult::WinWithExtraDeduction {
+                        payout: 150,
+                        extra_deduction: 50
+                    }
+                ));
+            } else {
+                outcomes[1] = true;
+                assert!(matches!(
+                    result,
+                    GameResult::ContinueWithUpdate { payout: -50 }
+                ));
+                assert!(insured.peeked);
+            }
+        }
+        assert_eq!(outcomes, [true; 2]);
+        let mut peeked = state.clone();
+        peeked.peeked = true;
+        let mut session = split_test_session(&peeked);
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        assert!(matches!(
+            Blackjack::process_move(&mut session, &[Move::Insurance as u8], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+
+        // Without the rule the dealer never peeks
+        state.dealer_peeks = false;
+        let mut session = split_test_session(&state);
+        Blackjack::process_move(&mut session, &[Move::Stand as u8], &mut rng).unwrap();
+        let stood = BlackjackState::from_blob(&session.state_blob).unwrap();
+        assert_eq!(stood.dealer_cards.len(), 1);
+        assert!(!stood.peeked);
+    }
+
+    #[test]
+    fn test_even_money() {
+        let (network_secret, _) = crate::mocks::create_network_keypair();
+        let seed = crate::mocks::create_seed(&network_secret, 1);
+        let mut rng = GameRng::new(&seed, 0, 1);
+        let natural = |dealer_up| {
+            let mut state = initial_state([0, 12], dealer_up);
+            state.stage = Stage::AwaitingReveal;
+            state.hands[0].status = HandStatus::Blackjack;
+            state.even_money = true;
+            state
+        };
+
+        // A blackjack against an Ace is paid 1:1 without drawing the hole card
+        let mut session = split_test_session(&natural(13));
+        let result =
+            Blackjack::process_move(&mut session, &[Move::EvenMoney as u8], &mut rng).unwrap();
+        assert!(matches!(result, GameResult::Win(200)));
+        assert!(session.is_complete);
+        let state = BlackjackState::from_blob(&session.state_blob).unwrap();
+        assert_eq!(state.stage, Stage::Complete);
+        assert_eq!(state.dealer_cards, vec![13]);
+
+        // Only offered on a blackjack against an Ace, at tables offering it, and not with
+        // insurance
+        let mut no_rule = natural(13);
+        no_rule.even_money = false;
+        let mut insured = natural(13);
+        insured.insurance_bet = 50;
+        let mut no_blackjack = initial_state([9, 7], 13);
+        no_blackjack.stage = Stage::AwaitingReveal;
+        no_blackjack.hands[0].status = HandStatus::Standing;
+        no_blackjack.even_money = true;
+        for state in [natural(9), no_rule, insured, no_blackjack] {
+            let mut session = split_test_session(&state);
+            assert!(matches!(
+                Blackjack::process_move(&mut session, &[Move::EvenMoney as u8], &mut rng),
+                Err(GameError::InvalidMove)
+            ));
+        }
+        let mut session = split_test_session(&natural(13));
+        assert!(matches!(
+            Blackjack::process_move(&mut session, &[Move::EvenMoney as u8, 0], &mut rng),
+            Err(GameError::InvalidPayload)
+        ));
+    }
+
+    #[test]
+    fn test_rounds_from_persistent_shoe() {
+        let (network_secret, _) = crate::mocks::create_network_keypair();
+        let seed = crate::mocks::create_seed(&network_secret, 1);
+        let mut session = split_test_session(&BlackjackState {
+            stage: Stage::Betting,
+            side_bet_21plus3: 0,
+            initial_player_cards: [CARD_UNKNOWN; 2],
+            active_hand_idx: 0,
+            hands: Vec::new(),
+            dealer_cards: Vec::new(),
+            dealer_hits_soft_17: true,
+            max_hands: BLACKJACK_MAX_HANDS,
+            insurance_bet: 0,
+            keep_playing: false,
+            dealer_peeks: false,
+            even_money: false,
+            peeked: false,
+            shoe: None,
+        });
+        Blackjack::configure(
+            &mut session,
+            &GameConfig {
+                shoe_decks: 6,
+                shoe_penetration: 50,
+                ..GameConfig::default()
+            },
+        );
+
+        let mut move_number = 0;
+        let mut play = |session: &mut GameSession, mv: Move| {
+            move_number += 1;
+            let mut rng = GameRng::new(&seed, session.id, move_number);
+            Blackjack::process_move(session, &[mv as u8], &mut rng)
+        };
+
+        // Each round is dealt from what is left of the shoe, until the cut card comes out
+        let mut remaining = 6 * 52;
+        let mut reshuffled = false;
+        for round in 0..40 {
+            let result = play(&mut session, Move::DealAndContinue).unwrap();
+            if round == 0 {
+                // The first round's bet was charged as the session started
+                assert!(matches!(result, GameResult::Continue));
+            } else {
+                assert!(matches!(
+                    result,
+                    GameResult::ContinueWithUpdate { payout: -100 }
+                ));
+            }
+            while BlackjackState::from_blob(&session.state_blob)
+                .unwrap()
+                .stage
+                == Stage::PlayerTurn
+            {
+                play(&mut session, Move::Stand).unwrap();
+            }
+            let result = play(&mut session, Move::Reveal).unwrap();
+            let state = BlackjackState::from_blob(&session.state_blob).unwrap();
+            assert_eq!(state.stage, Stage::Complete);
+            assert!(!session.is_complete);
+            let total_return = resolve_hands_return(session.bet, &state);
+            match result {
+                GameResult::ContinueWithUpdate { payout } => {
+                    assert_eq!(payout, total_return as i64)
+                }
+                GameResult::Continue => assert_eq!(total_return, 0),
+                _ => panic!("Unexpected blackjack result"),
+            }
+
+            let dealt = state.hands[0].cards.len() + state.dealer_cards.len();
+            if remaining * 2 <= 6 * 52 {
+                remaining = 6 * 52;
+                reshuffled = true;
+            }
+            remaining -= dealt;
+            assert_eq!(state.shoe.unwrap().remaining(), remaining);
+
+            // Only a new round can follow
+            assert!(matches!(
+                play(&mut session, Move::Hit),
+                Err(GameError::GameAlreadyComplete)
+            ));
+        }
+        assert!(reshuffled);
+
+        // A plain deal makes the next round the last
+        assert!(matches!(
+            play(&mut session, Move::Deal).unwrap(),
+            GameResult::ContinueWithUpdate { payout: -100 }
+        ));
+        while !session.is_complete {
+            let stage = BlackjackState::from_blob(&session.state_blob)
+                .unwrap()
+                .stage;
+            let mv = if stage == Stage::PlayerTurn {
+                Move::Stand
+            } else {
+                Move::Reveal
+            };
+            play(&mut session, mv).unwrap();
+        }
+    }
 }
@@ -0,0 +1,606 @@
+//! Caribbean Stud Poker implementation.
+//!
+//! This implementation supports:
+//! - Ante (`session.bet`, deducted by CasinoStartGame)
+//! - Optional Progressive side bet (placed before deal; for-one, royal flush hits the meter)
+//! - Raise/Fold decision (Raise bet is twice the Ante; charged before reveal)
+//! - Dealer qualification: A-K or better
+//! - Raise pay table: Pair 1, Two Pair 2, Trips 3, Straight 4, Flush 5, Full House 7,
+//!   Quads 20, Straight Flush 50, Royal Flush 100 (to 1)
+//!
+//! If the dealer doesn't qualify, the Ante wins 1:1 and the Raise pushes. Otherwise the better
+//! hand wins both bets (Ante 1:1, Raise per the pay table) and ties push.
+//!
+//! State blob format (19 bytes):
+//! [stage:u8]
+//! [playerCard1..5:u8]   (0xFF if not dealt yet)
+//! [dealerCard1..5:u8]   (0xFF if unrevealed)
+//! [progressiveBetAmount:u64 BE]
+//!
+//! Stages:
+//! 0 = Betting (optional Progressive, then Deal)
+//! 1 = Decision (player cards dealt; Raise/Fold)
+//! 2 = AwaitingReveal (Raise bet deducted; Reveal resolves)
+//! 3 = Complete
+//!
+//! Payload format:
+//! [move:u8] [optional amount:u64 BE]
+//! 0 = Raise
+//! 1 = Fold
+//! 2 = Deal
+//! 3 = Reveal
+//! 4 = Set Progressive bet (u64)
+
+use super::poker::{evaluate_five, HandRank, HandValue};
+use super::side_bet::{self, JackpotTier, Progressive, SideBet};
+use super::state_blob::{impl_blob_field_enum, BlobReader, BlobWriter, StateBlob, UNVERSIONED};
+use super::{CasinoGame, GameError, GameResult, GameRng};
+use nullspace_types::casino::{GameSession, CARIBBEAN_STUD_PROGRESSIVE_BASE_JACKPOT};
+
+const CARD_UNKNOWN: u8 = 0xFF;
+
+const PROGRESSIVE_BET_UNIT: u64 = 1;
+
+/// Side bet identifier (reported by [side_bet::SideBet::id]).
+pub const SIDE_BET_PROGRESSIVE: u8 = 0;
+
+/// Caribbean Stud stages.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Betting = 0,
+    Decision = 1,
+    AwaitingReveal = 2,
+    Complete = 3,
+}
+
+impl TryFrom<u8> for Stage {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Stage::Betting),
+            1 => Ok(Stage::Decision),
+            2 => Ok(Stage::AwaitingReveal),
+            3 => Ok(Stage::Complete),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+impl_blob_field_enum!(Stage);
+
+/// Player moves.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Move {
+    Raise = 0,
+    Fold = 1,
+    Deal = 2,
+    Reveal = 3,
+    SetProgressive = 4,
+}
+
+impl TryFrom<u8> for Move {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Move::Raise),
+            1 => Ok(Move::Fold),
+            2 => Ok(Move::Deal),
+            3 => Ok(Move::Reveal),
+            4 => Ok(Move::SetProgressive),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct CsState {
+    stage: Stage,
+    player: [u8; 5],
+    dealer: [u8; 5],
+    progressive_bet: u64,
+}
+
+impl StateBlob for CsState {
+    const VERSION: u8 = UNVERSIONED;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.stage)
+            .put(&self.player)
+            .put(&self.dealer)
+            .put(&self.progressive_bet);
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        Some(Self {
+            stage: reader.get()?,
+            player: reader.get()?,
+            dealer: reader.get()?,
+            progressive_bet: reader.get()?,
+        })
+    }
+}
+
+fn parse_u64_be(payload: &[u8], offset: usize) -> Result<u64, GameError> {
+    let end = offset.saturating_add(8);
+    if payload.len() != end {
+        return Err(GameError::InvalidPayload);
+    }
+    Ok(u64::from_be_bytes(
+        payload[offset..end]
+            .try_into()
+            .map_err(|_| GameError::InvalidPayload)?,
+    ))
+}
+
+fn draw_hand(rng: &mut GameRng, deck: &mut Vec<u8>) -> Result<[u8; 5], GameError> {
+    let mut cards = [CARD_UNKNOWN; 5];
+    for card in cards.iter_mut() {
+        *card = rng.draw_card(deck).ok_or(GameError::DeckExhausted)?;
+    }
+    Ok(cards)
+}
+
+/// Whether the dealer's hand qualifies (A-K or better).
+fn dealer_qualifies(dealer: &HandValue) -> bool {
+    dealer.rank > HandRank::HighCard || dealer.tiebreak[..2] == [14, 13]
+}
+
+/// Raise bet payout multiplier (to 1) for the player's hand.
+fn raise_multiplier(rank: HandRank) -> u64 {
+    match rank {
+        HandRank::HighCard | HandRank::Pair => 1,
+        HandRank::TwoPair => 2,
+        HandRank::ThreeOfAKind => 3,
+        HandRank::Straight => 4,
+        HandRank::Flush => 5,
+        HandRank::FullHouse => 7,
+        HandRank::FourOfAKind => 20,
+        HandRank::StraightFlush => 50,
+        HandRank::RoyalFlush | HandRank::FiveAces => 100,
+    }
+}
+
+/// Progressive side bet return (for-one). The royal flush pays the base jackpot and the straight
+/// flush a tenth of it; the progressive meter adds its growth over the base on top.
+fn resolve_progressive_return(player_cards: &[u8; 5], progressive_bet: u64) -> u64 {
+    if progressive_bet == 0 {
+        return 0;
+    }
+    let mult = match evaluate_five(player_cards).rank {
+        HandRank::RoyalFlush | HandRank::FiveAces => CARIBBEAN_STUD_PROGRESSIVE_BASE_JACKPOT,
+        HandRank::StraightFlush => CARIBBEAN_STUD_PROGRESSIVE_BASE_JACKPOT / 10,
+        HandRank::FourOfAKind => 500,
+        HandRank::FullHouse => 100,
+        HandRank::Flush => 50,
+        _ => 0,
+    };
+    progressive_bet.saturating_mul(mult)
+}
+
+/// The side bets held, with their returns once the hand is complete.
+fn resolve_side_bets(state: &CsState) -> Vec<SideBet> {
+    if state.progressive_bet == 0 {
+        return Vec::new();
+    }
+    // Nothing is returned until the hand is complete
+    let (returned, tier) = if state.stage == Stage::Complete {
+        let tier = match evaluate_five(&state.player).rank {
+            HandRank::RoyalFlush => JackpotTier::RoyalFlush,
+            HandRank::StraightFlush => JackpotTier::StraightFlush,
+            _ => JackpotTier::None,
+        };
+        let returned = resolve_progressive_return(&state.player, state.progressive_bet);
+        (returned, tier)
+    } else {
+        (0, JackpotTier::None)
+    };
+    vec![SideBet::progressive(
+        SIDE_BET_PROGRESSIVE,
+        state.progressive_bet,
+        returned,
+        Progressive::CaribbeanStud,
+        tier,
+    )]
+}
+
+/// Return of the Ante and Raise bets once the dealer's hand is revealed.
+fn resolve_main_return(ante: u64, player: &HandValue, dealer: &HandValue) -> u64 {
+    let raise = ante.saturating_mul(2);
+    if !dealer_qualifies(dealer) {
+        // Ante wins 1:1, Raise pushes.
+        return ante.saturating_mul(2).saturating_add(raise);
+    }
+    match player.cmp(dealer) {
+        std::cmp::Ordering::Greater => ante
+            .saturating_mul(2)
+            .saturating_add(raise.saturating_mul(raise_multiplier(player.rank).saturating_add(1))),
+        std::cmp::Ordering::Equal => ante.saturating_add(raise),
+        std::cmp::Ordering::Less => 0,
+    }
+}
+
+pub struct CaribbeanStud;
+
+impl CasinoGame for CaribbeanStud {
+    fn init(session: &mut GameSession, _rng: &mut GameRng) -> GameResult {
+        // Start in a betting stage so the Progressive bet can be placed before any cards are dealt.
+        let state = CsState {
+            stage: Stage::Betting,
+            player: [CARD_UNKNOWN; 5],
+            dealer: [CARD_UNKNOWN; 5],
+            progressive_bet: 0,
+        };
+        session.state_blob = state.to_blob();
+        GameResult::Continue
+    }
+
+    fn process_move(
+        session: &mut GameSession,
+        payload: &[u8],
+        rng: &mut GameRng,
+    ) -> Result<GameResult, GameError> {
+        if session.is_complete {
+            return Err(GameError::GameAlreadyComplete);
+        }
+        if payload.is_empty() {
+            return Err(GameError::InvalidPayload);
+        }
+
+        let mv = Move::try_from(payload[0])?;
+        let mut state = CsState::from_blob(&session.state_blob).ok_or(GameError::InvalidPayload)?;
+
+        match state.stage {
+            Stage::Betting => match mv {
+                Move::SetProgressive => {
+                    let new_bet = parse_u64_be(payload, 1)?;
+                    if new_bet != 0 && new_bet != PROGRESSIVE_BET_UNIT {
+                        return Err(GameError::InvalidMove);
+                    }
+                    let payout = state.progressive_bet as i64 - new_bet as i64;
+                    state.progressive_bet = new_bet;
+                    session.state_blob = state.to_blob();
+                    Ok(if payout == 0 {
+                        GameResult::Continue
+                    } else {
+                        GameResult::ContinueWithUpdate { payout }
+                    })
+                }
+                Move::Deal => {
+                    if payload.len() != 1 {
+                        return Err(GameError::InvalidPayload);
+                    }
+                    let mut deck = rng.create_deck();
+                    state.player = draw_hand(rng, &mut deck)?;
+                    state.stage = Stage::Decision;
+                    session.state_blob = state.to_blob();
+                    Ok(GameResult::Continue)
+                }
+                _ => Err(GameError::InvalidMove),
+            },
+            Stage::Decision => match mv {
+                Move::Fold => {
+                    // Fold: lose the Ante, the Progressive still resolves.
+                    // Reveal dealer cards for display.
+                    let mut deck = rng.create_deck_excluding(&state.player);
+                    state.dealer = draw_hand(rng, &mut deck)?;
+                    state.stage = Stage::Complete;
+                    session.is_complete = true;
+                    session.state_blob = state.to_blob();
+
+                    let total_return = side_bet::total_returned(&resolve_side_bets(&state));
+                    let total_wagered = session.bet.saturating_add(state.progressive_bet);
+                    if total_return == 0 {
+                        Ok(GameResult::LossPreDeducted(total_wagered))
+                    } else {
+                        Ok(GameResult::Win(total_return))
+                    }
+                }
+                Move::Raise => {
+                    // Charge the Raise bet (twice the Ante) now; resolve on Reveal.
+                    let raise = session.bet.saturating_mul(2);
+                    let payout = i64::try_from(raise).map_err(|_| GameError::InvalidMove)?;
+                    state.stage = Stage::AwaitingReveal;
+                    session.state_blob = state.to_blob();
+                    Ok(GameResult::ContinueWithUpdate { payout: -payout })
+                }
+                _ => Err(GameError::InvalidMove),
+            },
+            Stage::AwaitingReveal => match mv {
+                Move::Reveal => {
+                    // Reveal dealer cards and resolve all bets.
+                    let mut deck = rng.create_deck_excluding(&state.player);
+                    state.dealer = draw_hand(rng, &mut deck)?;
+                    state.stage = Stage::Complete;
+                    session.is_complete = true;
+                    session.state_blob = state.to_blob();
+
+                    let player_hand = evaluate_five(&state.player);
+                    let dealer_hand = evaluate_five(&state.dealer);
+                    let total_return = resolve_main_return(session.bet, &player_hand, &dealer_hand)
+                        .saturating_add(side_bet::total_returned(&resolve_side_bets(&state)));
+                    let total_wagered = session
+                        .bet
+                        .saturating_mul(3)
+                        .saturating_add(state.progressive_bet);
+                    if total_return == 0 {
+                        Ok(GameResult::LossPreDeducted(total_wagered))
+                    } else {
+                        Ok(GameResult::Win(total_return))
+                    }
+                }
+                _ => Err(GameError::InvalidMove),
+            },
+            Stage::Complete => Err(GameError::GameAlreadyComplete),
+        }
+    }
+
+    fn side_bets(session: &GameSession) -> Vec<SideBet> {
+        CsState::from_blob(&session.state_blob)
+            .map(|state| resolve_side_bets(&state))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::{create_account_keypair, create_network_keypair, create_seed};
+    use nullspace_types::casino::GameType;
+
+    fn create_test_seed() -> nullspace_types::Seed {
+        let (network_secret, _) = create_network_keypair();
+        create_seed(&network_secret, 1)
+    }
+
+    fn create_test_session(bet: u64) -> GameSession {
+        let (_, pk) = create_account_keypair(1);
+        GameSession {
+            id: 1,
+            player: pk,
+            game_type: GameType::CaribbeanStud,
+            bet,
+            state_blob: vec![],
+            move_count: 0,
+            created_at: 0,
+            is_complete: false,
+            super_mode: nullspace_types::casino::SuperModeState::default(),
+            is_tournament: false,
+            tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
+        }
+    }
+
+    /// Card with `rank` (2-14) and `suit` (0-3).
+    fn card(rank: u8, suit: u8) -> u8 {
+        suit * 13 + if rank == 14 { 0 } else { rank - 1 }
+    }
+
+    fn hand(cards: [(u8, u8); 5]) -> HandValue {
+        evaluate_five(&cards.map(|(rank, suit)| card(rank, suit)))
+    }
+
+    fn progressive_payload(amount: u64) -> Vec<u8> {
+        let mut payload = vec![Move::SetProgressive as u8];
+        payload.extend_from_slice(&amount.to_be_bytes());
+        payload
+    }
+
+    #[test]
+    fn test_dealer_qualifies() {
+        assert!(dealer_qualifies(&hand([
+            (14, 0),
+            (13, 1),
+            (4, 2),
+            (3, 3),
+            (2, 0)
+        ])));
+        assert!(dealer_qualifies(&hand([
+            (2, 0),
+            (2, 1),
+            (7, 2),
+            (5, 3),
+            (3, 0)
+        ])));
+        assert!(!dealer_qualifies(&hand([
+            (14, 0),
+            (12, 1),
+            (11, 2),
+            (9, 3),
+            (8, 0)
+        ])));
+        assert!(!dealer_qualifies(&hand([
+            (13, 0),
+            (12, 1),
+            (11, 2),
+            (9, 3),
+            (8, 0)
+        ])));
+    }
+
+    #[test]
+    fn test_main_return() {
+        let ak = hand([(14, 0), (13, 1), (4, 2), (3, 3), (2, 0)]);
+        let no_qualify = hand([(13, 0), (12, 1), (11, 2), (9, 3), (8, 0)]);
+        let flush = hand([(13, 2), (9, 2), (7, 2), (5, 2), (3, 2)]);
+        let two_pair = hand([(9, 0), (9, 1), (4, 2), (4, 3), (2, 0)]);
+
+        // Dealer doesn't qualify: Ante 1:1, Raise pushes
+        assert_eq!(resolve_main_return(10, &flush, &no_qualify), 20 + 20);
+        // Player wins: Ante 1:1, Raise per the pay table
+        assert_eq!(resolve_main_return(10, &flush, &ak), 20 + 20 * 6);
+        assert_eq!(resolve_main_return(10, &two_pair, &ak), 20 + 20 * 3);
+        // Tie pushes, loss loses both
+        assert_eq!(resolve_main_return(10, &ak, &ak), 10 + 20);
+        assert_eq!(resolve_main_return(10, &ak, &two_pair), 0);
+    }
+
+    #[test]
+    fn test_progressive_return() {
+        let royal = [
+            card(14, 3),
+            card(13, 3),
+            card(12, 3),
+            card(11, 3),
+            card(10, 3),
+        ];
+        assert_eq!(
+            resolve_progressive_return(&royal, 1),
+            CARIBBEAN_STUD_PROGRESSIVE_BASE_JACKPOT
+        );
+        assert_eq!(resolve_progressive_return(&royal, 0), 0);
+        let straight_flush = [card(9, 1), card(8, 1), card(7, 1), card(6, 1), card(5, 1)];
+        assert_eq!(
+            resolve_progressive_return(&straight_flush, 1),
+            CARIBBEAN_STUD_PROGRESSIVE_BASE_JACKPOT / 10
+        );
+        let quads = [card(9, 0), card(9, 1), card(9, 2), card(9, 3), card(5, 1)];
+        assert_eq!(resolve_progressive_return(&quads, 1), 500);
+        let straight = [card(9, 0), card(8, 1), card(7, 2), card(6, 3), card(5, 1)];
+        assert_eq!(resolve_progressive_return(&straight, 1), 0);
+    }
+
+    #[test]
+    fn test_side_bets() {
+        let royal = [
+            card(14, 3),
+            card(13, 3),
+            card(12, 3),
+            card(11, 3),
+            card(10, 3),
+        ];
+        let mut state = CsState {
+            stage: Stage::AwaitingReveal,
+            player: royal,
+            dealer: [CARD_UNKNOWN; 5],
+            progressive_bet: 1,
+        };
+
+        // Nothing is returned (or hit) before the reveal
+        assert_eq!(
+            resolve_side_bets(&state),
+            vec![SideBet::progressive(
+                SIDE_BET_PROGRESSIVE,
+                1,
+                0,
+                Progressive::CaribbeanStud,
+                JackpotTier::None
+            )]
+        );
+
+        state.stage = Stage::Complete;
+        assert_eq!(
+            resolve_side_bets(&state),
+            vec![SideBet::progressive(
+                SIDE_BET_PROGRESSIVE,
+                1,
+                CARIBBEAN_STUD_PROGRESSIVE_BASE_JACKPOT,
+                Progressive::CaribbeanStud,
+                JackpotTier::RoyalFlush
+            )]
+        );
+
+        state.progressive_bet = 0;
+        assert!(resolve_side_bets(&state).is_empty());
+    }
+
+    #[test]
+    fn test_state_roundtrip() {
+        let state = CsState {
+            stage: Stage::AwaitingReveal,
+            player: [0, 1, 2, 3, 4],
+            dealer: [CARD_UNKNOWN; 5],
+            progressive_bet: 1,
+        };
+        let blob = state.to_blob();
+        assert_eq!(blob.len(), 19);
+        assert_eq!(CsState::from_blob(&blob), Some(state));
+        assert_eq!(CsState::from_blob(&blob[1..]), None);
+        crate::casino::state_blob::fuzz::roundtrip(&[state]);
+    }
+
+    #[test]
+    fn test_game_flow() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        CaribbeanStud::init(&mut session, &mut rng);
+
+        // Raising before the deal is rejected
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        assert!(matches!(
+            CaribbeanStud::process_move(&mut session, &[Move::Raise as u8], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+
+        // Only a one-unit progressive bet is accepted
+        assert!(matches!(
+            CaribbeanStud::process_move(&mut session, &progressive_payload(2), &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+        let result =
+            CaribbeanStud::process_move(&mut session, &progressive_payload(1), &mut rng).unwrap();
+        assert!(matches!(
+            result,
+            GameResult::ContinueWithUpdate { payout: -1 }
+        ));
+
+        let result =
+            CaribbeanStud::process_move(&mut session, &[Move::Deal as u8], &mut rng).unwrap();
+        assert!(matches!(result, GameResult::Continue));
+        let state = CsState::from_blob(&session.state_blob).unwrap();
+        assert_eq!(state.stage, Stage::Decision);
+        assert!(state.player.iter().all(|&card| card < 52));
+        assert_eq!(state.dealer, [CARD_UNKNOWN; 5]);
+
+        let mut rng = GameRng::new(&seed, session.id, 2);
+        let result =
+            CaribbeanStud::process_move(&mut session, &[Move::Raise as u8], &mut rng).unwrap();
+        assert!(matches!(
+            result,
+            GameResult::ContinueWithUpdate { payout: -200 }
+        ));
+
+        let mut rng = GameRng::new(&seed, session.id, 3);
+        let result =
+            CaribbeanStud::process_move(&mut session, &[Move::Reveal as u8], &mut rng).unwrap();
+        assert!(matches!(
+            result,
+            GameResult::Win(_) | GameResult::LossPreDeducted(301)
+        ));
+        assert!(session.is_complete);
+
+        let state = CsState::from_blob(&session.state_blob).unwrap();
+        assert_eq!(state.stage, Stage::Complete);
+        let mut cards = [state.player, state.dealer].concat();
+        cards.sort_unstable();
+        cards.dedup();
+        assert_eq!(cards.len(), 10);
+    }
+
+    #[test]
+    fn test_fold() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        CaribbeanStud::init(&mut session, &mut rng);
+
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        CaribbeanStud::process_move(&mut session, &[Move::Deal as u8], &mut rng).unwrap();
+
+        // Folding forfeits the Ante (there is no progressive bet to resolve)
+        let mut rng = GameRng::new(&seed, session.id, 2);
+        let result =
+            CaribbeanStud::process_move(&mut session, &[Move::Fold as u8], &mut rng).unwrap();
+        assert!(matches!(result, GameResult::LossPreDeducted(100)));
+        assert!(session.is_complete);
+        let state = CsState::from_blob(&session.state_blob).unwrap();
+        assert!(state.dealer.iter().all(|&card| card < 52));
+    }
+}
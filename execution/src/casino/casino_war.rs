@@ -2,8 +2,12 @@
 //!
 //! State blob format:
 //! v1: [version:u8=1] [stage:u8] [playerCard:u8] [dealerCard:u8] [tie_bet:u64 BE]
+//!     [war_tie_bonus:u8]? (absent: a bonus equal to the ante)
 //! legacy: [playerCard:u8] [dealerCard:u8] [stage:u8]
 //!
+//! The war tie bonus (0 = bonus equal to the ante, 1 = no bonus) is chosen from the game
+//! config's paytable when the session starts.
+//!
 //! v1 Stage: 0 = Betting (pre-deal), 1 = War (after tie), 2 = Complete
 //! legacy Stage: 0 = Initial, 1 = War (after tie)
 //!
@@ -13,14 +17,14 @@
 //! [2] = Surrender (after tie, forfeit half bet)
 //! [3, tie_bet:u64 BE] = Set tie bet (v1 Betting only)
 
+use super::state_blob::{impl_blob_field_enum, BlobReader, BlobWriter, StateBlob};
 use super::super_mode::apply_super_multiplier_cards;
 use super::{CasinoGame, GameError, GameResult, GameRng};
-use nullspace_types::casino::GameSession;
+use nullspace_types::casino::{GameConfig, GameSession};
 
 const STATE_VERSION_V1: u8 = 1;
 const HIDDEN_CARD: u8 = 0xFF;
 const TIE_BET_PAYOUT_TO_1: u64 = 10;
-const TIE_AFTER_TIE_BONUS_MULTIPLIER: u64 = 1;
 /// WoO: Casino War is played with six decks.
 const CASINO_WAR_DECKS: u8 = 6;
 
@@ -34,6 +38,49 @@ fn card_rank(card: u8) -> u8 {
     } // Ace is high
 }
 
+/// Bonus paid when the war itself ends in a tie (the game config's paytable).
+///
+/// WoO (six decks, always going to war): house edge 2.33% with the bonus, 2.88% without.
+/// https://wizardofodds.com/games/casino-war/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum WarTieBonus {
+    /// A tie in the war pays a bonus equal to the ante.
+    #[default]
+    Ante = 0,
+    /// A tie in the war pays even money on the raise only.
+    NoBonus = 1,
+}
+
+impl TryFrom<u8> for WarTieBonus {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(WarTieBonus::Ante),
+            1 => Ok(WarTieBonus::NoBonus),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+impl_blob_field_enum!(WarTieBonus);
+
+/// Return credited for a war (the ante was deducted at start and the raise is a contingent
+/// loss), or `None` if the player loses the war.
+fn war_return(bet: u64, player_rank: u8, dealer_rank: u8, bonus: WarTieBonus) -> Option<u64> {
+    if player_rank < dealer_rank {
+        return None;
+    }
+    // The ante pushes and the raise wins even money.
+    let base = bet.saturating_mul(2);
+    if player_rank == dealer_rank && bonus == WarTieBonus::Ante {
+        Some(base.saturating_add(bet))
+    } else {
+        Some(base)
+    }
+}
+
 /// Casino War stages.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
@@ -56,6 +103,8 @@ impl TryFrom<u8> for StageV1 {
     }
 }
 
+impl_blob_field_enum!(StageV1);
+
 /// Legacy stages (pre-versioned state).
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
@@ -100,27 +149,43 @@ impl TryFrom<u8> for Move {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 struct CasinoWarStateV1 {
     player_card: u8,
     dealer_card: u8,
     stage: StageV1,
     tie_bet: u64,
+    war_tie_bonus: WarTieBonus,
+}
+
+impl StateBlob for CasinoWarStateV1 {
+    const VERSION: u8 = STATE_VERSION_V1;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.stage)
+            .put(&self.player_card)
+            .put(&self.dealer_card)
+            .put(&self.tie_bet)
+            .put(&self.war_tie_bonus);
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        Some(Self {
+            stage: reader.get()?,
+            player_card: reader.get()?,
+            dealer_card: reader.get()?,
+            tie_bet: reader.get()?,
+            // Appended after v1 shipped (absent bytes pay the bonus)
+            war_tie_bonus: reader.get_or(WarTieBonus::default())?,
+        })
+    }
 }
 
 fn parse_state(state: &[u8]) -> Option<Result<CasinoWarStateV1, (u8, u8, StageV0)>> {
-    // v1
+    // v1 (legacy blobs are 3 bytes, so a first byte of 1 there is a card)
     if state.len() >= 12 && state[0] == STATE_VERSION_V1 {
-        let stage = StageV1::try_from(state[1]).ok()?;
-        let player_card = state[2];
-        let dealer_card = state[3];
-        let tie_bet = u64::from_be_bytes(state[4..12].try_into().ok()?);
-        return Some(Ok(CasinoWarStateV1 {
-            player_card,
-            dealer_card,
-            stage,
-            tie_bet,
-        }));
+        return CasinoWarStateV1::from_blob(state).map(Ok);
     }
 
     // legacy
@@ -131,16 +196,6 @@ fn parse_state(state: &[u8]) -> Option<Result<CasinoWarStateV1, (u8, u8, StageV0
     Some(Err((state[0], state[1], stage)))
 }
 
-fn serialize_state_v1(state: &CasinoWarStateV1) -> Vec<u8> {
-    let mut out = Vec::with_capacity(12);
-    out.push(STATE_VERSION_V1);
-    out.push(state.stage as u8);
-    out.push(state.player_card);
-    out.push(state.dealer_card);
-    out.extend_from_slice(&state.tie_bet.to_be_bytes());
-    out
-}
-
 fn serialize_state_legacy(player_card: u8, dealer_card: u8, stage: StageV0) -> Vec<u8> {
     vec![player_card, dealer_card, stage as u8]
 }
@@ -155,11 +210,19 @@ impl CasinoGame for CasinoWar {
             dealer_card: HIDDEN_CARD,
             stage: StageV1::Betting,
             tie_bet: 0,
+            war_tie_bonus: WarTieBonus::default(),
         };
-        session.state_blob = serialize_state_v1(&state);
+        session.state_blob = state.to_blob();
         GameResult::Continue
     }
 
+    fn configure(session: &mut GameSession, config: &GameConfig) {
+        if let Some(Ok(mut state)) = parse_state(&session.state_blob) {
+            state.war_tie_bonus = WarTieBonus::try_from(config.paytable).unwrap_or_default();
+            session.state_blob = state.to_blob();
+        }
+    }
+
     fn process_move(
         session: &mut GameSession,
         payload: &[u8],
@@ -208,7 +271,7 @@ impl CasinoGame for CasinoWar {
                         };
 
                         state.tie_bet = new_tie_bet;
-                        session.state_blob = serialize_state_v1(&state);
+                        session.state_blob = state.to_blob();
                         Ok(GameResult::ContinueWithUpdate { payout })
                     }
                     Move::Play => {
@@ -240,7 +303,7 @@ impl CasinoGame for CasinoWar {
                             state.stage = StageV1::Complete;
                             state.player_card = player_card;
                             state.dealer_card = dealer_card;
-                            session.state_blob = serialize_state_v1(&state);
+                            session.state_blob = state.to_blob();
                             session.is_complete = true;
 
                             let base_winnings = session.bet.saturating_mul(2);
@@ -259,7 +322,7 @@ impl CasinoGame for CasinoWar {
                             state.stage = StageV1::Complete;
                             state.player_card = player_card;
                             state.dealer_card = dealer_card;
-                            session.state_blob = serialize_state_v1(&state);
+                            session.state_blob = state.to_blob();
                             session.is_complete = true;
                             Ok(GameResult::Loss)
                         } else {
@@ -267,7 +330,7 @@ impl CasinoGame for CasinoWar {
                             state.stage = StageV1::War;
                             state.player_card = player_card;
                             state.dealer_card = dealer_card;
-                            session.state_blob = serialize_state_v1(&state);
+                            session.state_blob = state.to_blob();
 
                             if tie_bet_return != 0 {
                                 Ok(GameResult::ContinueWithUpdate {
@@ -283,7 +346,7 @@ impl CasinoGame for CasinoWar {
                 StageV1::War => match mv {
                     Move::Surrender => {
                         state.stage = StageV1::Complete;
-                        session.state_blob = serialize_state_v1(&state);
+                        session.state_blob = state.to_blob();
                         session.is_complete = true;
                         // CasinoStartGame already deducted the ante, so refund half to realize a
                         // half-loss outcome.
@@ -312,22 +375,17 @@ impl CasinoGame for CasinoWar {
                         state.stage = StageV1::Complete;
                         state.player_card = new_player_card;
                         state.dealer_card = new_dealer_card;
-                        session.state_blob = serialize_state_v1(&state);
+                        session.state_blob = state.to_blob();
                         session.is_complete = true;
 
-                        if new_player_rank >= new_dealer_rank {
-                            // WoO "bonus" variant: tie-after-tie awards a bonus equal to the ante.
-                            // https://wizardofodds.com/games/casino-war/
-                            //
-                            // Note: We model the raise as a contingent loss (`LossWithExtraDeduction`)
-                            // instead of a pre-deducted bet, so we express the bonus via the credited return.
-                            let base_winnings = if new_player_rank == new_dealer_rank {
-                                session.bet.saturating_mul(2).saturating_add(
-                                    session.bet.saturating_mul(TIE_AFTER_TIE_BONUS_MULTIPLIER),
-                                )
-                            } else {
-                                session.bet.saturating_mul(2)
-                            };
+                        // Note: We model the raise as a contingent loss (`LossWithExtraDeduction`)
+                        // instead of a pre-deducted bet, so we express the bonus via the credited return.
+                        if let Some(base_winnings) = war_return(
+                            session.bet,
+                            new_player_rank,
+                            new_dealer_rank,
+                            state.war_tie_bonus,
+                        ) {
                             let final_winnings = if session.super_mode.is_active {
                                 apply_super_multiplier_cards(
                                     &[new_player_card],
@@ -410,8 +468,13 @@ impl CasinoGame for CasinoWar {
                             );
                             session.is_complete = true;
 
-                            if new_player_rank >= new_dealer_rank {
-                                let base_winnings = session.bet.saturating_mul(2);
+                            // Legacy sessions predate the war tie bonus.
+                            if let Some(base_winnings) = war_return(
+                                session.bet,
+                                new_player_rank,
+                                new_dealer_rank,
+                                WarTieBonus::NoBonus,
+                            ) {
                                 let final_winnings = if session.super_mode.is_active {
                                     apply_super_multiplier_cards(
                                         &[new_player_card],
@@ -459,6 +522,9 @@ mod tests {
             super_mode: nullspace_types::casino::SuperModeState::default(),
             is_tournament: false,
             tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
         }
     }
 
@@ -714,4 +780,75 @@ mod tests {
 
         panic!("failed to find a tie-after-tie in 10,000 trials");
     }
+
+    #[test]
+    fn test_configure_war_tie_bonus() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+
+        CasinoWar::init(&mut session, &mut rng);
+        CasinoWar::configure(
+            &mut session,
+            &GameConfig {
+                paytable: 1,
+                ..GameConfig::default()
+            },
+        );
+        let Some(Ok(state)) = parse_state(&session.state_blob) else {
+            panic!("expected v1 state");
+        };
+        assert_eq!(state.war_tie_bonus, WarTieBonus::NoBonus);
+
+        // States without the bonus byte pay the bonus.
+        let Some(Ok(state)) = parse_state(&session.state_blob[..12]) else {
+            panic!("expected v1 state");
+        };
+        assert_eq!(state.war_tie_bonus, WarTieBonus::Ante);
+
+        crate::casino::state_blob::fuzz::roundtrip(&[
+            state,
+            CasinoWarStateV1 {
+                player_card: 12,
+                dealer_card: 25,
+                stage: StageV1::War,
+                tie_bet: 10,
+                war_tie_bonus: WarTieBonus::NoBonus,
+            },
+        ]);
+    }
+
+    #[test]
+    fn test_war_house_edge() {
+        // Exact expected value per unit ante of always going to war from a six-deck shoe,
+        // against WoO: 2.33% with the tie bonus and 2.88% without. The first two cards tie
+        // with probability 23/311; win and loss are otherwise equally likely.
+        for (bonus, edge) in [(WarTieBonus::Ante, 0.0233), (WarTieBonus::NoBonus, 0.0288)] {
+            let mut ev = 0.0;
+            for player_rank in 2..=14u8 {
+                for dealer_rank in 2..=14u8 {
+                    // The tied rank has 22 cards left in the 310-card shoe.
+                    let player_cards = if player_rank == 14 { 22.0 } else { 24.0 };
+                    let dealer_cards = match (player_rank == dealer_rank, dealer_rank == 14) {
+                        (true, _) => player_cards - 1.0,
+                        (false, true) => 22.0,
+                        (false, false) => 24.0,
+                    };
+                    let p = player_cards / 310.0 * dealer_cards / 309.0;
+                    // Net of the ante and the raise.
+                    let net = match war_return(1, player_rank, dealer_rank, bonus) {
+                        Some(credited) => credited as f64 - 1.0,
+                        None => -2.0,
+                    };
+                    ev += p * net;
+                }
+            }
+            let ev = ev * 23.0 / 311.0;
+            assert!((-ev - edge).abs() < 0.00005, "{bonus:?}: {ev}");
+        }
+
+        // The tie bet pays 10:1 on a 23/311 tie: house edge 18.65%.
+        let ev = (TIE_BET_PAYOUT_TO_1 + 1) as f64 * 23.0 / 311.0 - 1.0;
+        assert!((-ev - 0.1865).abs() < 0.00005);
+    }
 }
@@ -1,7 +1,7 @@
 //! Enhanced Craps game implementation with a multi-bet menu.
 //!
 //! State blob format:
-//! [version:u8=2]
+//! [version:u8=3]
 //! [phase:u8]
 //! [main_point:u8]
 //! [d1:u8] [d2:u8]
@@ -11,8 +11,12 @@
 //! [bets:CrapsBetEntry×count]
 //! [field_paytable:u8]? [buy_commission_timing:u8]? (optional, post-bets rules bytes)
 //!
-//! Each CrapsBetEntry (19 bytes):
-//! [bet_type:u8] [target:u8] [status:u8] [amount:u64 BE] [odds_amount:u64 BE]
+//! Each CrapsBetEntry (20 bytes; 19 bytes without `working` in v1/v2 states):
+//! [bet_type:u8] [target:u8] [status:u8] [amount:u64 BE] [odds_amount:u64 BE] [working:u8]
+//!
+//! Place-style bets (YES/NO/BUY) and the odds behind contract bets can be turned off. A bet
+//! that is off neither wins nor loses; odds that are off are returned when the contract bet
+//! resolves (the flat bet always works).
 //!
 //! Phases:
 //! 0 = Come out (initial roll)
@@ -23,14 +27,18 @@
 //! [1, amount_bytes...] - Add odds to last contract bet
 //! [2] - Roll dice
 //! [3] - Clear all bets (only before first roll)
+//! [4, bet_index, working] - Turn a place bet or contract bet's odds on (1) or off (0)
+//! [5, bet_index, amount_bytes...?] - Take down a bet, or reduce it by amount
 
+use super::state_blob::{impl_blob_field_enum, BlobField, BlobReader, BlobWriter, StateBlob};
 use super::super_mode::apply_super_multiplier_total;
-use super::{CasinoGame, GameError, GameResult, GameRng};
-use nullspace_types::casino::GameSession;
+use super::{CasinoGame, GameError, GameResult, GameRng, TableEvent};
+use nullspace_types::casino::{GameConfig, GameSession, ResolvedBet, MAX_CRAPS_BETS};
 
 const STATE_VERSION_V1: u8 = 1;
-const STATE_VERSION: u8 = 2;
-const MAX_BETS: usize = 20;
+const STATE_VERSION_V2: u8 = 2;
+const STATE_VERSION: u8 = 3;
+const MAX_BETS: usize = MAX_CRAPS_BETS;
 const BUY_COMMISSION_BPS: u64 = 500; // 5.00%
 const BUY_COMMISSION_DENOM: u64 = 10_000;
 
@@ -61,6 +69,8 @@ impl TryFrom<u8> for FieldPaytable {
     }
 }
 
+impl_blob_field_enum!(FieldPaytable);
+
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum BuyCommissionTiming {
@@ -88,6 +98,12 @@ impl TryFrom<u8> for BuyCommissionTiming {
     }
 }
 
+impl_blob_field_enum!(BuyCommissionTiming);
+
+// Hop bet pay table ("to 1"): a hopping hardway has one way to roll, an easy hop two.
+const HOP_HARD_PAYOUT_TO_1: u64 = 30;
+const HOP_EASY_PAYOUT_TO_1: u64 = 15;
+
 // All Tall Small (ATS) pay table ("to 1").
 const ATS_SMALL_PAYOUT_TO_1: u64 = 34;
 const ATS_TALL_PAYOUT_TO_1: u64 = 34;
@@ -123,6 +139,8 @@ impl TryFrom<u8> for Phase {
     }
 }
 
+impl_blob_field_enum!(Phase);
+
 /// Supported bet types in craps.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
@@ -144,6 +162,7 @@ pub enum BetType {
     AtsSmall = 15,  // All Tall Small: Small (2-6) before seven-out
     AtsTall = 16,   // All Tall Small: Tall (8-12) before seven-out
     AtsAll = 17,    // All Tall Small: All (Small + Tall) before seven-out
+    Hop = 18,       // Hop bet: exact dice pair on next roll (target = low << 4 | high)
 }
 
 impl TryFrom<u8> for BetType {
@@ -168,11 +187,14 @@ impl TryFrom<u8> for BetType {
             15 => Ok(BetType::AtsSmall),
             16 => Ok(BetType::AtsTall),
             17 => Ok(BetType::AtsAll),
+            18 => Ok(BetType::Hop),
             _ => Err(()),
         }
     }
 }
 
+impl_blob_field_enum!(BetType);
+
 /// Bet status for contract bets.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
@@ -193,6 +215,8 @@ impl TryFrom<u8> for BetStatus {
     }
 }
 
+impl_blob_field_enum!(BetStatus);
+
 /// Individual bet in craps.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CrapsBet {
@@ -201,36 +225,29 @@ pub struct CrapsBet {
     pub status: BetStatus, // ON or PENDING
     pub amount: u64,
     pub odds_amount: u64, // Free odds behind contract bets
+    pub working: bool,    // Off bets (and odds) neither win nor lose
 }
 
-impl CrapsBet {
-    /// Serialize to 19 bytes
-    fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::with_capacity(19);
-        bytes.push(self.bet_type as u8);
-        bytes.push(self.target);
-        bytes.push(self.status as u8);
-        bytes.extend_from_slice(&self.amount.to_be_bytes());
-        bytes.extend_from_slice(&self.odds_amount.to_be_bytes());
-        bytes
-    }
-
-    /// Deserialize from 19 bytes
-    fn from_bytes(bytes: &[u8]) -> Option<Self> {
-        if bytes.len() < 19 {
-            return None;
-        }
-        let bet_type = BetType::try_from(bytes[0]).ok()?;
-        let target = bytes[1];
-        let status = BetStatus::try_from(bytes[2]).ok()?;
-        let amount = u64::from_be_bytes(bytes[3..11].try_into().ok()?);
-        let odds_amount = u64::from_be_bytes(bytes[11..19].try_into().ok()?);
+impl BlobField for CrapsBet {
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.bet_type)
+            .put(&self.target)
+            .put(&self.status)
+            .put(&self.amount)
+            .put(&self.odds_amount)
+            .put(&self.working);
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
         Some(CrapsBet {
-            bet_type,
-            target,
-            status,
-            amount,
-            odds_amount,
+            bet_type: reader.get()?,
+            target: reader.get()?,
+            status: reader.get()?,
+            amount: reader.get()?,
+            odds_amount: reader.get()?,
+            // v1/v2 bets are always working
+            working: reader.since(STATE_VERSION, true)?,
         })
     }
 }
@@ -247,6 +264,7 @@ struct BetResult {
 }
 
 /// Game state.
+#[derive(Debug, PartialEq, Eq)]
 struct CrapsState {
     phase: Phase,
     main_point: u8,
@@ -259,128 +277,36 @@ struct CrapsState {
     bets: Vec<CrapsBet>,
 }
 
-impl CrapsState {
-    /// Serialize state to blob
-    fn to_blob(&self) -> Vec<u8> {
-        // Capacity: 8 (header) + bets (19 bytes each) + 2 (optional rules bytes)
-        let capacity = 8 + (self.bets.len() * 19) + 2;
-        let mut blob = Vec::with_capacity(capacity);
-        blob.push(STATE_VERSION);
-        blob.push(self.phase as u8);
-        blob.push(self.main_point);
-        blob.push(self.d1);
-        blob.push(self.d2);
-        blob.push(self.made_points_mask);
-        blob.push(self.epoch_point_established as u8);
-        blob.push(self.bets.len() as u8);
-
-        for bet in &self.bets {
-            blob.extend_from_slice(&bet.to_bytes());
-        }
-
-        // Post-bets optional rules bytes (kept at the end so legacy parsers remain compatible).
-        blob.push(self.field_paytable as u8);
-        blob.push(self.buy_commission_timing as u8);
-
-        blob
+impl StateBlob for CrapsState {
+    const VERSION: u8 = STATE_VERSION;
+    const MIN_VERSION: u8 = STATE_VERSION_V1;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.phase)
+            .put(&self.main_point)
+            .put(&self.d1)
+            .put(&self.d2)
+            .put(&self.made_points_mask)
+            .put(&self.epoch_point_established)
+            .put_list(&self.bets)
+            // Rules follow the bets (older blobs end before them)
+            .put(&self.field_paytable)
+            .put(&self.buy_commission_timing);
     }
 
-    /// Deserialize state from blob
-    fn from_blob(blob: &[u8]) -> Option<Self> {
-        if blob.len() < 7 {
-            return None;
-        }
-
-        let version = blob[0];
-
-        let (
-            phase,
-            main_point,
-            d1,
-            d2,
-            made_points_mask,
-            epoch_point_established,
-            bet_count,
-            header_len,
-        ) = if version == STATE_VERSION {
-            if blob.len() < 8 {
-                return None;
-            }
-            let phase = Phase::try_from(blob[1]).ok()?;
-            let main_point = blob[2];
-            let d1 = blob[3];
-            let d2 = blob[4];
-            let made_points_mask = blob[5];
-            let epoch_point_established = blob[6] != 0;
-            let bet_count = blob[7] as usize;
-            (
-                phase,
-                main_point,
-                d1,
-                d2,
-                made_points_mask,
-                epoch_point_established,
-                bet_count,
-                8usize,
-            )
-        } else if version == STATE_VERSION_V1 {
-            // v1 header (7 bytes): [v=1][phase][main_point][d1][d2][made_points_mask][bet_count]
-            let phase = Phase::try_from(blob[1]).ok()?;
-            let main_point = blob[2];
-            let d1 = blob[3];
-            let d2 = blob[4];
-            let made_points_mask = blob[5];
-            let bet_count = blob[6] as usize;
-
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        let phase = reader.get()?;
+        let main_point = reader.get()?;
+        let d1 = reader.get()?;
+        let d2 = reader.get()?;
+        let made_points_mask = reader.get()?;
+        let epoch_point_established = if reader.version() == STATE_VERSION_V1 {
             // Best-effort derivation for legacy states: if we're currently in Point phase
             // or have ever made a point, treat the epoch as having established a point.
-            let epoch_point_established =
-                phase == Phase::Point || main_point != 0 || made_points_mask != 0;
-
-            (
-                phase,
-                main_point,
-                d1,
-                d2,
-                made_points_mask,
-                epoch_point_established,
-                bet_count,
-                7usize,
-            )
-        } else {
-            return None;
-        };
-
-        // Validate bet count against maximum to prevent DoS via large allocations
-        if bet_count > MAX_BETS {
-            return None;
-        }
-
-        // Validate we have enough bytes for all bets before allocating
-        let required_len = header_len + (bet_count * 19);
-        if blob.len() < required_len {
-            return None;
-        }
-
-        let mut bets = Vec::with_capacity(bet_count);
-        let mut offset = header_len;
-
-        for _ in 0..bet_count {
-            if offset + 19 > blob.len() {
-                return None;
-            }
-            let bet = CrapsBet::from_bytes(&blob[offset..offset + 19])?;
-            bets.push(bet);
-            offset += 19;
-        }
-
-        let (field_paytable, buy_commission_timing) = if blob.len() >= offset + 2 {
-            (
-                FieldPaytable::try_from(blob[offset]).ok()?,
-                BuyCommissionTiming::try_from(blob[offset + 1]).ok()?,
-            )
+            phase == Phase::Point || main_point != 0 || made_points_mask != 0
         } else {
-            (FieldPaytable::default(), BuyCommissionTiming::default())
+            reader.get()?
         };
 
         Some(CrapsState {
@@ -390,9 +316,9 @@ impl CrapsState {
             d2,
             made_points_mask,
             epoch_point_established,
-            field_paytable,
-            buy_commission_timing,
-            bets,
+            bets: reader.get_list(MAX_BETS)?,
+            field_paytable: reader.get_or(FieldPaytable::default())?,
+            buy_commission_timing: reader.get_or(BuyCommissionTiming::default())?,
         })
     }
 }
@@ -401,9 +327,14 @@ impl CrapsState {
 // Payout Calculations
 // ============================================================================
 
-/// Calculate pass/don't pass return (TOTAL RETURN: stake + winnings).
-/// Stake is assumed already deducted at bet placement.
+/// Calculate pass/don't pass (and come/don't come) return (TOTAL RETURN: stake + winnings).
+/// Stake is assumed already deducted at bet placement. Odds that are off are returned
+/// whether the flat bet wins or loses.
 fn calculate_pass_return(bet: &CrapsBet, won: bool, is_pass: bool) -> u64 {
+    if !bet.working {
+        let flat_return = if won { bet.amount.saturating_mul(2) } else { 0 };
+        return flat_return.saturating_add(bet.odds_amount);
+    }
     if !won {
         return 0;
     }
@@ -512,6 +443,16 @@ fn calculate_buy_commission(amount: u64) -> u64 {
         .saturating_div(BUY_COMMISSION_DENOM)
 }
 
+/// Amount deducted from the player when placing a bet (BUY bets may include the commission).
+fn placement_cost(bet_type: BetType, amount: u64, timing: BuyCommissionTiming) -> u64 {
+    match (bet_type, timing) {
+        (BetType::Buy, BuyCommissionTiming::AtPlacement) => {
+            amount.saturating_add(calculate_buy_commission(amount))
+        }
+        _ => amount,
+    }
+}
+
 /// Calculate BUY bet return (TOTAL RETURN: stake + winnings). Commission is charged separately.
 fn calculate_buy_payout(target: u8, amount: u64, hit: bool) -> u64 {
     if !hit {
@@ -553,6 +494,32 @@ fn calculate_next_payout(target: u8, total: u8, amount: u64) -> u64 {
     amount.saturating_add(winnings)
 }
 
+/// Decode a HOP target into its dice pair (low, high), or `None` if it isn't a valid pair.
+fn hop_dice(target: u8) -> Option<(u8, u8)> {
+    let (low, high) = (target >> 4, target & 0x0F);
+    if low >= 1 && low <= high && high <= 6 {
+        Some((low, high))
+    } else {
+        None
+    }
+}
+
+/// Calculate HOP bet return (TOTAL RETURN: stake + winnings). The dice match in either order.
+fn calculate_hop_payout(target: u8, d1: u8, d2: u8, amount: u64) -> u64 {
+    let Some((low, high)) = hop_dice(target) else {
+        return 0;
+    };
+    if (d1.min(d2), d1.max(d2)) != (low, high) {
+        return 0;
+    }
+    let mult = if low == high {
+        HOP_HARD_PAYOUT_TO_1
+    } else {
+        HOP_EASY_PAYOUT_TO_1
+    };
+    amount.saturating_mul(mult.saturating_add(1))
+}
+
 fn ats_bit_for_total(total: u8) -> u64 {
     match total {
         2 => 1u64 << 0,
@@ -620,7 +587,7 @@ fn process_roll(state: &mut CrapsState, d1: u8, d2: u8) -> Vec<BetResult> {
     let total = d1.saturating_add(d2);
     let mut results = Vec::with_capacity(state.bets.len());
 
-    // 1. Single-roll bets (FIELD, NEXT) - always resolve
+    // 1. Single-roll bets (FIELD, NEXT, HOP) - always resolve
     for (idx, bet) in state.bets.iter().enumerate() {
         if bet.bet_type == BetType::Field {
             results.push(BetResult {
@@ -638,6 +605,14 @@ fn process_roll(state: &mut CrapsState, d1: u8, d2: u8) -> Vec<BetResult> {
                 resolved: true,
             });
         }
+        if bet.bet_type == BetType::Hop {
+            results.push(BetResult {
+                bet_idx: idx,
+                return_amount: calculate_hop_payout(bet.target, d1, d2, bet.amount),
+                wagered: bet.amount,
+                resolved: true,
+            });
+        }
     }
 
     // 2. HARDWAY bets (check for 7 or easy way)
@@ -666,7 +641,7 @@ fn process_roll(state: &mut CrapsState, d1: u8, d2: u8) -> Vec<BetResult> {
 
     // 3. YES/NO/BUY bets (working bets only)
     for (idx, bet) in state.bets.iter().enumerate() {
-        if bet.status != BetStatus::On {
+        if bet.status != BetStatus::On || !bet.working {
             continue;
         }
 
@@ -803,15 +778,9 @@ fn process_roll(state: &mut CrapsState, d1: u8, d2: u8) -> Vec<BetResult> {
             (BetType::Come, BetStatus::On) => {
                 if total == bet.target {
                     // Win!
-                    let odds_payout = calculate_odds_payout(bet.target, bet.odds_amount, true);
-                    let total_payout = bet
-                        .amount
-                        .saturating_mul(2)
-                        .saturating_add(bet.odds_amount)
-                        .saturating_add(odds_payout);
                     results.push(BetResult {
                         bet_idx: idx,
-                        return_amount: total_payout,
+                        return_amount: calculate_pass_return(bet, true, true),
                         wagered: bet.amount.saturating_add(bet.odds_amount),
                         resolved: true,
                     });
@@ -819,7 +788,7 @@ fn process_roll(state: &mut CrapsState, d1: u8, d2: u8) -> Vec<BetResult> {
                     // Lose
                     results.push(BetResult {
                         bet_idx: idx,
-                        return_amount: 0,
+                        return_amount: calculate_pass_return(bet, false, true),
                         wagered: bet.amount.saturating_add(bet.odds_amount),
                         resolved: true,
                     });
@@ -861,22 +830,16 @@ fn process_roll(state: &mut CrapsState, d1: u8, d2: u8) -> Vec<BetResult> {
             (BetType::DontCome, BetStatus::On) => {
                 if total == 7 {
                     // Win!
-                    let odds_payout = calculate_odds_payout(bet.target, bet.odds_amount, false);
-                    let total_payout = bet
-                        .amount
-                        .saturating_mul(2)
-                        .saturating_add(bet.odds_amount)
-                        .saturating_add(odds_payout);
                     results.push(BetResult {
                         bet_idx: idx,
-                        return_amount: total_payout,
+                        return_amount: calculate_pass_return(bet, true, false),
                         wagered: bet.amount.saturating_add(bet.odds_amount),
                         resolved: true,
                     });
                 } else if total == bet.target {
                     results.push(BetResult {
                         bet_idx: idx,
-                        return_amount: 0,
+                        return_amount: calculate_pass_return(bet, false, false),
                         wagered: bet.amount.saturating_add(bet.odds_amount),
                         resolved: true,
                     });
@@ -996,7 +959,7 @@ fn process_pass_bets(state: &CrapsState, total: u8, results: &mut Vec<BetResult>
                     // Seven out - lose
                     results.push(BetResult {
                         bet_idx: idx,
-                        return_amount: 0,
+                        return_amount: calculate_pass_return(bet, false, true),
                         wagered: bet.amount.saturating_add(bet.odds_amount),
                         resolved: true,
                     });
@@ -1050,7 +1013,7 @@ fn process_pass_bets(state: &CrapsState, total: u8, results: &mut Vec<BetResult>
                     // Hit the point - lose for don't pass
                     results.push(BetResult {
                         bet_idx: idx,
-                        return_amount: 0,
+                        return_amount: calculate_pass_return(bet, false, false),
                         wagered: bet.amount.saturating_add(bet.odds_amount),
                         resolved: true,
                     });
@@ -1130,6 +1093,10 @@ fn update_phase(state: &mut CrapsState, total: u8) -> PhaseEvent {
 pub struct Craps;
 
 impl CasinoGame for Craps {
+    fn bet_count(session: &GameSession) -> usize {
+        CrapsState::from_blob(&session.state_blob).map_or(0, |state| state.bets.len())
+    }
+
     fn init(session: &mut GameSession, _rng: &mut GameRng) -> GameResult {
         let state = CrapsState {
             phase: Phase::ComeOut,
@@ -1146,6 +1113,53 @@ impl CasinoGame for Craps {
         GameResult::Continue
     }
 
+    fn configure(session: &mut GameSession, config: &GameConfig) {
+        if let Some(mut state) = CrapsState::from_blob(&session.state_blob) {
+            state.field_paytable = FieldPaytable::try_from(config.paytable).unwrap_or_default();
+            state.buy_commission_timing = if config.commission_on_win {
+                BuyCommissionTiming::OnWin
+            } else {
+                BuyCommissionTiming::AtPlacement
+            };
+            session.state_blob = state.to_blob();
+        }
+    }
+
+    fn table_events(before: &[u8], session: &GameSession, payload: &[u8]) -> Vec<TableEvent> {
+        // [2] - Roll dice
+        if payload.first() != Some(&2) {
+            return Vec::new();
+        }
+        let (Some(mut before), Some(state)) = (
+            CrapsState::from_blob(before),
+            CrapsState::from_blob(&session.state_blob),
+        ) else {
+            return Vec::new();
+        };
+
+        // Replay the roll on the bets it was made against to see what it resolved
+        let mut results = process_roll(&mut before, state.d1, state.d2);
+        results.sort_by_key(|result| result.bet_idx);
+        let resolved_bets = results
+            .into_iter()
+            .filter(|result| result.resolved)
+            .map(|result| {
+                let bet = &before.bets[result.bet_idx];
+                ResolvedBet {
+                    bet_type: bet.bet_type as u8,
+                    target: bet.target,
+                    amount: result.wagered,
+                    payout: result.return_amount,
+                }
+            })
+            .collect();
+        vec![TableEvent::CrapsRolled {
+            d1: state.d1,
+            d2: state.d2,
+            resolved_bets,
+        }]
+    }
+
     fn process_move(
         session: &mut GameSession,
         payload: &[u8],
@@ -1231,6 +1245,11 @@ impl CasinoGame for Craps {
                             return Err(GameError::InvalidPayload);
                         }
                     }
+                    BetType::Hop => {
+                        if hop_dice(target).is_none() {
+                            return Err(GameError::InvalidPayload);
+                        }
+                    }
                     BetType::Hardway4
                     | BetType::Hardway6
                     | BetType::Hardway8
@@ -1279,20 +1298,12 @@ impl CasinoGame for Craps {
                     status,
                     amount,
                     odds_amount: 0,
+                    working: true,
                 });
 
                 session.state_blob = state.to_blob();
 
-                let deduction = if bet_type == BetType::Buy {
-                    match state.buy_commission_timing {
-                        BuyCommissionTiming::AtPlacement => {
-                            amount.saturating_add(calculate_buy_commission(amount))
-                        }
-                        BuyCommissionTiming::OnWin => amount,
-                    }
-                } else {
-                    amount
-                };
+                let deduction = placement_cost(bet_type, amount, state.buy_commission_timing);
                 let deduction_i64 =
                     i64::try_from(deduction).map_err(|_| GameError::InvalidPayload)?;
                 Ok(GameResult::ContinueWithUpdate {
@@ -1420,6 +1431,88 @@ impl CasinoGame for Craps {
                 Ok(GameResult::Continue)
             }
 
+            // [4, bet_index, working] - Turn a place bet (or contract bet odds) on/off
+            4 => {
+                if payload.len() != 3 {
+                    return Err(GameError::InvalidPayload);
+                }
+                let working = match payload[2] {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(GameError::InvalidPayload),
+                };
+                let bet = state
+                    .bets
+                    .get_mut(payload[1] as usize)
+                    .ok_or(GameError::InvalidMove)?;
+                let can_toggle = match bet.bet_type {
+                    BetType::Yes | BetType::No | BetType::Buy => true,
+                    // Only the odds of a contract bet can be off; pending come bets have none.
+                    BetType::Pass | BetType::DontPass | BetType::Come | BetType::DontCome => {
+                        bet.status == BetStatus::On
+                    }
+                    _ => false,
+                };
+                if !can_toggle {
+                    return Err(GameError::InvalidMove);
+                }
+                bet.working = working;
+                session.state_blob = state.to_blob();
+                Ok(GameResult::Continue)
+            }
+
+            // [5, bet_index, amount_bytes...?] - Take down a bet (or reduce it by amount)
+            5 => {
+                if payload.len() != 2 && payload.len() != 10 {
+                    return Err(GameError::InvalidPayload);
+                }
+                let idx = payload[1] as usize;
+                let bet = state.bets.get(idx).ok_or(GameError::InvalidMove)?;
+                let reduction = if payload.len() == 10 {
+                    let amount = u64::from_be_bytes(
+                        payload[2..10]
+                            .try_into()
+                            .map_err(|_| GameError::InvalidPayload)?,
+                    );
+                    if amount == 0 {
+                        return Err(GameError::InvalidPayload);
+                    }
+                    if amount > bet.amount {
+                        return Err(GameError::InvalidMove);
+                    }
+                    amount
+                } else {
+                    bet.amount
+                };
+
+                let removable = match bet.bet_type {
+                    // Contract bets are committed once their point is established.
+                    BetType::Pass | BetType::DontPass => state.phase == Phase::ComeOut,
+                    BetType::Come | BetType::DontCome => bet.status == BetStatus::Pending,
+                    // Fire and ATS bets track the whole shooter epoch.
+                    BetType::Fire | BetType::AtsSmall | BetType::AtsTall | BetType::AtsAll => false,
+                    _ => true,
+                };
+                if !removable {
+                    return Err(GameError::InvalidMove);
+                }
+
+                // Refund the difference in placement cost (including any up-front commission).
+                let timing = state.buy_commission_timing;
+                let remaining = bet.amount - reduction;
+                let refund = placement_cost(bet.bet_type, bet.amount, timing)
+                    .saturating_sub(placement_cost(bet.bet_type, remaining, timing));
+                if remaining == 0 {
+                    state.bets.remove(idx);
+                } else {
+                    state.bets[idx].amount = remaining;
+                }
+                session.state_blob = state.to_blob();
+
+                let refund = i64::try_from(refund).map_err(|_| GameError::InvalidMove)?;
+                Ok(GameResult::ContinueWithUpdate { payout: refund })
+            }
+
             _ => Err(GameError::InvalidPayload),
         }
     }
@@ -1428,6 +1521,7 @@ impl CasinoGame for Craps {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::casino::state_blob::UNVERSIONED;
     use crate::mocks::{create_account_keypair, create_network_keypair, create_seed};
     use nullspace_types::casino::GameType;
 
@@ -1450,9 +1544,27 @@ mod tests {
             super_mode: nullspace_types::casino::SuperModeState::default(),
             is_tournament: false,
             tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
         }
     }
 
+    fn bet_entry(bet: &CrapsBet) -> Vec<u8> {
+        let mut writer = BlobWriter::new(UNVERSIONED);
+        writer.put(bet);
+        writer.finish()
+    }
+
+    /// Parse a bet entry as laid out by state `version`.
+    fn parse_bet(version: u8, bytes: &[u8]) -> Option<CrapsBet> {
+        let blob = [&[version], bytes].concat();
+        let mut reader = BlobReader::new(&blob)?;
+        let bet = reader.get()?;
+        reader.finish()?;
+        Some(bet)
+    }
+
     #[test]
     fn test_bet_serialization() {
         let bet = CrapsBet {
@@ -1461,13 +1573,26 @@ mod tests {
             status: BetStatus::On,
             amount: 100,
             odds_amount: 50,
+            working: true,
         };
 
-        let bytes = bet.to_bytes();
-        assert_eq!(bytes.len(), 19);
+        let bytes = bet_entry(&bet);
+        assert_eq!(bytes.len(), 20);
 
-        let deserialized = CrapsBet::from_bytes(&bytes).expect("Failed to parse bet");
+        let deserialized = parse_bet(STATE_VERSION, &bytes).expect("Failed to parse bet");
         assert_eq!(deserialized, bet);
+
+        // Legacy 19-byte entries are always working.
+        let off = CrapsBet {
+            working: false,
+            ..bet.clone()
+        };
+        let bytes = bet_entry(&off);
+        assert_eq!(parse_bet(STATE_VERSION, &bytes), Some(off));
+        assert_eq!(parse_bet(STATE_VERSION_V2, &bytes[..19]), Some(bet.clone()));
+        let mut bad = bytes.clone();
+        bad[19] = 2;
+        assert_eq!(parse_bet(STATE_VERSION, &bad), None);
     }
 
     #[test]
@@ -1488,6 +1613,7 @@ mod tests {
                     status: BetStatus::On,
                     amount: 100,
                     odds_amount: 50,
+                    working: true,
                 },
                 CrapsBet {
                     bet_type: BetType::Field,
@@ -1495,6 +1621,7 @@ mod tests {
                     status: BetStatus::On,
                     amount: 25,
                     odds_amount: 0,
+                    working: true,
                 },
             ],
         };
@@ -1516,6 +1643,46 @@ mod tests {
             state.epoch_point_established
         );
         assert_eq!(deserialized.bets.len(), 2);
+
+        // v2 states (19-byte entries) still parse.
+        let mut legacy = blob[..8].to_vec();
+        legacy[0] = STATE_VERSION_V2;
+        for bet in &state.bets {
+            legacy.extend_from_slice(&bet_entry(bet)[..19]);
+        }
+        let deserialized = CrapsState::from_blob(&legacy).expect("Failed to parse v2 state");
+        assert_eq!(deserialized.bets, state.bets);
+
+        crate::casino::state_blob::fuzz::roundtrip(&[state]);
+    }
+
+    #[test]
+    fn test_configure() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(0);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        Craps::init(&mut session, &mut rng);
+
+        // The configured rules are recorded in the session's state
+        Craps::configure(
+            &mut session,
+            &GameConfig {
+                paytable: 1,
+                commission_on_win: true,
+                ..GameConfig::default()
+            },
+        );
+        let state = CrapsState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.field_paytable, FieldPaytable::Double2Triple12);
+        assert_eq!(state.buy_commission_timing, BuyCommissionTiming::OnWin);
+
+        Craps::configure(&mut session, &GameConfig::default());
+        let state = CrapsState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.field_paytable, FieldPaytable::Double2And12);
+        assert_eq!(
+            state.buy_commission_timing,
+            BuyCommissionTiming::AtPlacement
+        );
     }
 
     #[test]
@@ -1561,6 +1728,66 @@ mod tests {
         assert_eq!(calculate_next_payout(7, 6, 100), 0);
     }
 
+    #[test]
+    fn test_hop_payout() {
+        // Every hop wins on exactly its own dice pair.
+        for target in 0..=u8::MAX {
+            let Some((low, high)) = hop_dice(target) else {
+                continue;
+            };
+            let (hard, easy) = (100 * 31, 100 * 16);
+            for d1 in 1..=6u8 {
+                for d2 in 1..=6u8 {
+                    let expected = if (d1.min(d2), d1.max(d2)) != (low, high) {
+                        0
+                    } else if low == high {
+                        hard
+                    } else {
+                        easy
+                    };
+                    assert_eq!(calculate_hop_payout(target, d1, d2, 100), expected);
+                }
+            }
+        }
+        assert_eq!((1..=u8::MAX).filter(|&t| hop_dice(t).is_some()).count(), 21);
+
+        // Hard hop (3,3) pays 30:1; easy hop (2,4) pays 15:1 in either order.
+        assert_eq!(calculate_hop_payout(0x33, 3, 3, 10), 310);
+        assert_eq!(calculate_hop_payout(0x24, 4, 2, 10), 160);
+        assert_eq!(calculate_hop_payout(0x24, 3, 3, 10), 0);
+
+        // Pairs must be in low-high order with faces 1-6.
+        for target in [0x00, 0x42, 0x07, 0x70, 0x17] {
+            assert_eq!(hop_dice(target), None);
+        }
+    }
+
+    #[test]
+    fn test_place_hop_bet() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+
+        Craps::init(&mut session, &mut rng);
+
+        let mut payload = vec![0, BetType::Hop as u8, 0x42];
+        payload.extend_from_slice(&100u64.to_be_bytes());
+        assert!(matches!(
+            Craps::process_move(&mut session, &payload, &mut rng),
+            Err(GameError::InvalidPayload)
+        ));
+
+        payload[2] = 0x24;
+        Craps::process_move(&mut session, &payload, &mut rng).expect("Failed to place hop");
+
+        // Hops are single-roll bets.
+        let mut state = CrapsState::from_blob(&session.state_blob).expect("Failed to parse state");
+        let results = process_roll(&mut state, 1, 5);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].return_amount, 0);
+        assert!(results[0].resolved);
+    }
+
     #[test]
     fn test_buy_commission_rounds_up() {
         assert_eq!(calculate_buy_commission(100), 5);
@@ -1593,6 +1820,7 @@ mod tests {
                 status: BetStatus::On,
                 amount: 10,
                 odds_amount: 0,
+                working: true,
             }],
         };
 
@@ -1627,6 +1855,7 @@ mod tests {
                 status: BetStatus::On,
                 amount: 10,
                 odds_amount: 0,
+                working: true,
             }],
         };
 
@@ -1698,6 +1927,42 @@ mod tests {
         assert!(session.is_complete);
     }
 
+    #[test]
+    fn test_table_events() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        Craps::init(&mut session, &mut rng);
+
+        // Placing a bet rolls nothing
+        let before = session.state_blob.clone();
+        let mut payload = vec![0, BetType::Field as u8, 0];
+        payload.extend_from_slice(&100u64.to_be_bytes());
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        Craps::process_move(&mut session, &payload, &mut rng).expect("Failed to process move");
+        assert!(Craps::table_events(&before, &session, &payload).is_empty());
+
+        // The roll reports the dice and the field bet it settled
+        let before = session.state_blob.clone();
+        let mut rng = GameRng::new(&seed, session.id, 2);
+        Craps::process_move(&mut session, &[2], &mut rng).expect("Failed to roll");
+        let state = CrapsState::from_blob(&session.state_blob).expect("Failed to parse state");
+        let payout = calculate_field_payout(state.d1 + state.d2, 100, state.field_paytable);
+        assert_eq!(
+            Craps::table_events(&before, &session, &[2]),
+            vec![TableEvent::CrapsRolled {
+                d1: state.d1,
+                d2: state.d2,
+                resolved_bets: vec![ResolvedBet {
+                    bet_type: BetType::Field as u8,
+                    target: 0,
+                    amount: 100,
+                    payout,
+                }],
+            }]
+        );
+    }
+
     #[test]
     fn test_pass_line_flow() {
         let seed = create_test_seed();
@@ -1791,6 +2056,193 @@ mod tests {
         assert_eq!(state.bets[0].target, 6);
     }
 
+    #[test]
+    fn test_working_toggle() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+
+        Craps::init(&mut session, &mut rng);
+
+        let mut payload = vec![0, BetType::Yes as u8, 6];
+        payload.extend_from_slice(&100u64.to_be_bytes());
+        Craps::process_move(&mut session, &payload, &mut rng).expect("Failed to place bet");
+        let mut payload = vec![0, BetType::Field as u8, 0];
+        payload.extend_from_slice(&100u64.to_be_bytes());
+        Craps::process_move(&mut session, &payload, &mut rng).expect("Failed to place bet");
+
+        // Only place-style bets and contract bets can be toggled.
+        assert!(matches!(
+            Craps::process_move(&mut session, &[4, 1, 0], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+        assert!(matches!(
+            Craps::process_move(&mut session, &[4, 2, 0], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+        assert!(matches!(
+            Craps::process_move(&mut session, &[4, 0, 2], &mut rng),
+            Err(GameError::InvalidPayload)
+        ));
+        assert!(matches!(
+            Craps::process_move(&mut session, &[4, 0], &mut rng),
+            Err(GameError::InvalidPayload)
+        ));
+
+        let result = Craps::process_move(&mut session, &[4, 0, 0], &mut rng)
+            .expect("Failed to turn bet off");
+        assert!(matches!(result, GameResult::Continue));
+        let mut state = CrapsState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(session.state_blob[0], STATE_VERSION);
+        assert!(!state.bets[0].working);
+        assert!(state.bets[1].working);
+
+        // An off place bet stays up through its number and the seven.
+        let results = process_roll(&mut state, 3, 3);
+        assert!(results.iter().all(|r| r.bet_idx != 0));
+        let results = process_roll(&mut state, 3, 4);
+        assert!(results.iter().all(|r| r.bet_idx != 0));
+
+        // Turned back on, it wins.
+        state.bets[0].working = true;
+        let results = process_roll(&mut state, 2, 4);
+        assert!(results
+            .iter()
+            .any(|r| r.bet_idx == 0 && r.return_amount > 100));
+    }
+
+    #[test]
+    fn test_odds_off_are_returned() {
+        let bet = CrapsBet {
+            bet_type: BetType::Come,
+            target: 6,
+            status: BetStatus::On,
+            amount: 100,
+            odds_amount: 50,
+            working: false,
+        };
+        let state = || CrapsState {
+            phase: Phase::ComeOut,
+            main_point: 0,
+            d1: 0,
+            d2: 0,
+            made_points_mask: 0,
+            epoch_point_established: false,
+            field_paytable: FieldPaytable::default(),
+            buy_commission_timing: BuyCommissionTiming::default(),
+            bets: vec![bet.clone()],
+        };
+
+        // The flat bet loses to the seven; the odds come back.
+        let results = process_roll(&mut state(), 3, 4);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].return_amount, 50);
+
+        // The flat bet wins even money; the odds are returned without winnings.
+        let results = process_roll(&mut state(), 3, 3);
+        assert_eq!(results[0].return_amount, 250);
+
+        // Working odds are paid at true odds (6:5).
+        assert_eq!(
+            calculate_pass_return(
+                &CrapsBet {
+                    working: true,
+                    ..bet
+                },
+                true,
+                true
+            ),
+            310
+        );
+    }
+
+    #[test]
+    fn test_take_down_bets() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+
+        Craps::init(&mut session, &mut rng);
+        let mut state = CrapsState::from_blob(&session.state_blob).expect("Failed to parse state");
+        state.buy_commission_timing = BuyCommissionTiming::AtPlacement;
+        session.state_blob = state.to_blob();
+
+        for (bet_type, target) in [
+            (BetType::Pass, 0),
+            (BetType::Buy, 4),
+            (BetType::Field, 0),
+            (BetType::Fire, 0),
+        ] {
+            let mut payload = vec![0, bet_type as u8, target];
+            payload.extend_from_slice(&100u64.to_be_bytes());
+            Craps::process_move(&mut session, &payload, &mut rng).expect("Failed to place bet");
+        }
+
+        // Reduce the buy bet from 100 to 60: refunds 40 plus the commission difference (5 -> 3).
+        let mut payload = vec![5, 1];
+        payload.extend_from_slice(&40u64.to_be_bytes());
+        let result = Craps::process_move(&mut session, &payload, &mut rng).expect("reduce");
+        assert!(matches!(
+            result,
+            GameResult::ContinueWithUpdate { payout: 42 }
+        ));
+        let state = CrapsState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.bets[1].amount, 60);
+
+        // Reductions can't exceed the bet, and can't be zero.
+        let mut payload = vec![5, 1];
+        payload.extend_from_slice(&61u64.to_be_bytes());
+        assert!(matches!(
+            Craps::process_move(&mut session, &payload, &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+        let mut payload = vec![5, 1];
+        payload.extend_from_slice(&0u64.to_be_bytes());
+        assert!(matches!(
+            Craps::process_move(&mut session, &payload, &mut rng),
+            Err(GameError::InvalidPayload)
+        ));
+
+        // Take down the field bet entirely.
+        let result = Craps::process_move(&mut session, &[5, 2], &mut rng).expect("take down");
+        assert!(matches!(
+            result,
+            GameResult::ContinueWithUpdate { payout: 100 }
+        ));
+        let state = CrapsState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.bets.len(), 3);
+        assert_eq!(state.bets[2].bet_type, BetType::Fire);
+
+        // Fire bets stay up, as do missing bets.
+        assert!(matches!(
+            Craps::process_move(&mut session, &[5, 2], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+        assert!(matches!(
+            Craps::process_move(&mut session, &[5, 3], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+
+        // The pass line can come down on the come-out roll, but not once a point is set.
+        let mut state = CrapsState::from_blob(&session.state_blob).expect("Failed to parse state");
+        state.phase = Phase::Point;
+        state.main_point = 6;
+        session.state_blob = state.to_blob();
+        assert!(matches!(
+            Craps::process_move(&mut session, &[5, 0], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+        let mut state = CrapsState::from_blob(&session.state_blob).expect("Failed to parse state");
+        state.phase = Phase::ComeOut;
+        state.main_point = 0;
+        session.state_blob = state.to_blob();
+        let result = Craps::process_move(&mut session, &[5, 0], &mut rng).expect("take down");
+        assert!(matches!(
+            result,
+            GameResult::ContinueWithUpdate { payout: 100 }
+        ));
+    }
+
     #[test]
     fn test_clear_bets() {
         let seed = create_test_seed();
@@ -1899,6 +2351,7 @@ mod tests {
                 status: BetStatus::On,
                 amount: 10,
                 odds_amount: 0,
+                working: true,
             }],
         };
 
@@ -1942,6 +2395,7 @@ mod tests {
                 status: BetStatus::On,
                 amount: 10,
                 odds_amount: 0,
+                working: true,
             }],
         };
 
@@ -0,0 +1,688 @@
+//! Four Card Poker implementation.
+//!
+//! This implementation supports:
+//! - Ante (`session.bet`, deducted by CasinoStartGame)
+//! - Optional Aces Up side bet (placed before deal)
+//! - Play/Fold decision (Play bet of 1-3x the Ante; charged before reveal)
+//! - The player makes the best four-card hand of five cards, the dealer of six
+//! - No dealer qualification, and the player wins ties
+//! - Ante bonus (Quads 25, Straight Flush 40, Trips 2), paid when the player plays
+//!
+//! Pay tables live in `nullspace_types::casino` (`FOUR_CARD_*`).
+//!
+//! State blob format:
+//! v1 (22 bytes):
+//! [version:u8=1]
+//! [stage:u8]
+//! [playerCard1..5:u8]    (0xFF if not dealt yet)
+//! [dealerCard1..6:u8]    (0xFF if unrevealed)
+//! [acesUpBetAmount:u64 BE]
+//! [playMultiplier:u8]    (0 until the player plays)
+//!
+//! Stages:
+//! 0 = Betting (optional Aces Up, then Deal)
+//! 1 = Decision (player cards dealt; Play/Fold)
+//! 2 = AwaitingReveal (Play bet deducted; Reveal resolves)
+//! 3 = Complete
+//!
+//! Payload format:
+//! [move:u8] [optional operand]
+//! 0 = Play ([0, multiplier] with multiplier 1-3)
+//! 1 = Fold
+//! 2 = Deal (optional u64 = Aces Up bet)
+//! 3 = Set Aces Up bet (u64)
+//! 4 = Reveal
+
+use super::poker::{card_rank, card_suit};
+use super::state_blob::{impl_blob_field_enum, BlobReader, BlobWriter, StateBlob};
+use super::{CasinoGame, GameError, GameResult, GameRng};
+use nullspace_types::casino::{
+    GameSession, FOUR_CARD_ACES_UP_PAYTABLE, FOUR_CARD_ANTE_BONUS_PAYTABLE,
+    FOUR_CARD_MAX_PLAY_MULTIPLIER,
+};
+use std::cmp::Ordering;
+
+const STATE_VERSION_V1: u8 = 1;
+const CARD_UNKNOWN: u8 = 0xFF;
+
+/// Four Card Poker stages.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Betting = 0,
+    Decision = 1,
+    AwaitingReveal = 2,
+    Complete = 3,
+}
+
+impl TryFrom<u8> for Stage {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Stage::Betting),
+            1 => Ok(Stage::Decision),
+            2 => Ok(Stage::AwaitingReveal),
+            3 => Ok(Stage::Complete),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+impl_blob_field_enum!(Stage);
+
+/// Player moves.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Move {
+    Play = 0,
+    Fold = 1,
+    Deal = 2,
+    SetAcesUp = 3,
+    Reveal = 4,
+}
+
+impl TryFrom<u8> for Move {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Move::Play),
+            1 => Ok(Move::Fold),
+            2 => Ok(Move::Deal),
+            3 => Ok(Move::SetAcesUp),
+            4 => Ok(Move::Reveal),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+/// Four card hand rankings (higher is better; also the index into the pay tables).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandRank {
+    HighCard = 0,
+    Pair = 1,
+    TwoPair = 2,
+    Straight = 3,
+    Flush = 4,
+    ThreeOfAKind = 5,
+    StraightFlush = 6,
+    FourOfAKind = 7,
+}
+
+/// Evaluate a 4-card hand, returns (HandRank, ranks for tiebreaker).
+///
+/// Tiebreak ranks are ordered by how many of each the hand holds, then by rank (straights
+/// order from their top card, so A-2-3-4 is the lowest straight).
+pub fn evaluate_hand(cards: &[u8; 4]) -> (HandRank, [u8; 4]) {
+    let mut counts = [0u8; 15];
+    for &card in cards {
+        counts[card_rank(card) as usize] += 1;
+    }
+
+    // Group by count, then rank (both descending)
+    let mut groups: Vec<(u8, u8)> = (2..=14u8)
+        .filter(|&rank| counts[rank as usize] > 0)
+        .map(|rank| (counts[rank as usize], rank))
+        .collect();
+    groups.sort_unstable_by(|a, b| b.cmp(a));
+    let mut ranks = [0u8; 4];
+    let mut i = 0;
+    for &(count, rank) in &groups {
+        for _ in 0..count {
+            ranks[i] = rank;
+            i += 1;
+        }
+    }
+
+    let is_flush = cards.iter().all(|&c| card_suit(c) == card_suit(cards[0]));
+    let is_straight = if groups.len() < 4 {
+        false
+    } else if ranks[0] - ranks[3] == 3 {
+        true
+    } else if ranks == [14, 4, 3, 2] {
+        ranks = [4, 3, 2, 1];
+        true
+    } else {
+        false
+    };
+
+    let hand_rank = match (groups[0].0, groups.get(1).map(|group| group.0)) {
+        (4, _) => HandRank::FourOfAKind,
+        _ if is_straight && is_flush => HandRank::StraightFlush,
+        (3, _) => HandRank::ThreeOfAKind,
+        _ if is_flush => HandRank::Flush,
+        _ if is_straight => HandRank::Straight,
+        (2, Some(2)) => HandRank::TwoPair,
+        (2, _) => HandRank::Pair,
+        _ => HandRank::HighCard,
+    };
+
+    (hand_rank, ranks)
+}
+
+/// Evaluate the best 4-card hand that can be made from `cards` (at least four).
+pub fn evaluate_best_hand(cards: &[u8]) -> (HandRank, [u8; 4]) {
+    let n = cards.len();
+    let mut best = None;
+    for a in 0..n {
+        for b in (a + 1)..n {
+            for c in (b + 1)..n {
+                for d in (c + 1)..n {
+                    let hand = evaluate_hand(&[cards[a], cards[b], cards[c], cards[d]]);
+                    if best.is_none_or(|best| compare_hands(&hand, &best) == Ordering::Greater) {
+                        best = Some(hand);
+                    }
+                }
+            }
+        }
+    }
+    best.expect("at least four cards")
+}
+
+fn compare_hands(h1: &(HandRank, [u8; 4]), h2: &(HandRank, [u8; 4])) -> Ordering {
+    match h1.0.cmp(&h2.0) {
+        Ordering::Equal => h1.1.cmp(&h2.1),
+        other => other,
+    }
+}
+
+fn ante_bonus_multiplier(hand_rank: HandRank) -> u64 {
+    FOUR_CARD_ANTE_BONUS_PAYTABLE[hand_rank as usize]
+}
+
+fn aces_up_multiplier(hand: &(HandRank, [u8; 4])) -> u64 {
+    if hand.0 == HandRank::Pair && hand.1[0] != 14 {
+        return 0;
+    }
+    FOUR_CARD_ACES_UP_PAYTABLE[hand.0 as usize]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FcState {
+    stage: Stage,
+    player: [u8; 5],
+    dealer: [u8; 6],
+    aces_up_bet: u64,
+    play_multiplier: u8,
+}
+
+impl StateBlob for FcState {
+    const VERSION: u8 = STATE_VERSION_V1;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.stage)
+            .put(&self.player)
+            .put(&self.dealer)
+            .put(&self.aces_up_bet)
+            .put(&self.play_multiplier);
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        Some(Self {
+            stage: reader.get()?,
+            player: reader.get()?,
+            dealer: reader.get()?,
+            aces_up_bet: reader.get()?,
+            play_multiplier: reader
+                .get()
+                .filter(|&multiplier| multiplier <= FOUR_CARD_MAX_PLAY_MULTIPLIER)?,
+        })
+    }
+}
+
+fn parse_u64_be(payload: &[u8], offset: usize) -> Result<u64, GameError> {
+    let end = offset.saturating_add(8);
+    if payload.len() < end {
+        return Err(GameError::InvalidPayload);
+    }
+    Ok(u64::from_be_bytes(
+        payload[offset..end]
+            .try_into()
+            .map_err(|_| GameError::InvalidPayload)?,
+    ))
+}
+
+fn apply_aces_up_update(state: &mut FcState, new_bet: u64) -> Result<i64, GameError> {
+    let old = state.aces_up_bet as i128;
+    let new = new_bet as i128;
+    let delta = new - old;
+    if delta > i64::MAX as i128 || delta < i64::MIN as i128 {
+        return Err(GameError::InvalidMove);
+    }
+    state.aces_up_bet = new_bet;
+    Ok(-(delta as i64))
+}
+
+fn resolve_aces_up_return(player_cards: &[u8; 5], aces_up_bet: u64) -> u64 {
+    if aces_up_bet == 0 {
+        return 0;
+    }
+    let mult = aces_up_multiplier(&evaluate_best_hand(player_cards));
+    if mult == 0 {
+        0
+    } else {
+        aces_up_bet.saturating_mul(mult.saturating_add(1))
+    }
+}
+
+/// Deal the dealer's six cards from the cards the player doesn't hold.
+fn deal_dealer(state: &mut FcState, rng: &mut GameRng) -> Result<(), GameError> {
+    let mut deck = rng.create_deck_excluding(&state.player);
+    for card in state.dealer.iter_mut() {
+        *card = rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
+    }
+    Ok(())
+}
+
+/// Total return of the Ante, Play, ante bonus and Aces Up bets once the dealer is revealed.
+fn resolve_return(state: &FcState, ante: u64) -> u64 {
+    let player_hand = evaluate_best_hand(&state.player);
+    let dealer_hand = evaluate_best_hand(&state.dealer);
+    let play = ante.saturating_mul(state.play_multiplier as u64);
+
+    // Ante and Play pay 1:1, and the player wins ties.
+    let main_return = if compare_hands(&player_hand, &dealer_hand) != Ordering::Less {
+        ante.saturating_add(play).saturating_mul(2)
+    } else {
+        0
+    };
+
+    // Ante bonus is paid when the player plays, regardless of the outcome.
+    let ante_bonus = ante.saturating_mul(ante_bonus_multiplier(player_hand.0));
+
+    main_return
+        .saturating_add(ante_bonus)
+        .saturating_add(resolve_aces_up_return(&state.player, state.aces_up_bet))
+}
+
+pub struct FourCardPoker;
+
+impl CasinoGame for FourCardPoker {
+    fn init(session: &mut GameSession, _rng: &mut GameRng) -> GameResult {
+        // Start in a betting stage so Aces Up can be placed before any cards are dealt.
+        let state = FcState {
+            stage: Stage::Betting,
+            player: [CARD_UNKNOWN; 5],
+            dealer: [CARD_UNKNOWN; 6],
+            aces_up_bet: 0,
+            play_multiplier: 0,
+        };
+        session.state_blob = state.to_blob();
+        GameResult::Continue
+    }
+
+    fn process_move(
+        session: &mut GameSession,
+        payload: &[u8],
+        rng: &mut GameRng,
+    ) -> Result<GameResult, GameError> {
+        if session.is_complete {
+            return Err(GameError::GameAlreadyComplete);
+        }
+        if payload.is_empty() {
+            return Err(GameError::InvalidPayload);
+        }
+
+        let mv = Move::try_from(payload[0])?;
+        let mut state = FcState::from_blob(&session.state_blob).ok_or(GameError::InvalidPayload)?;
+
+        match state.stage {
+            Stage::Betting => match mv {
+                Move::SetAcesUp => {
+                    let new_bet = parse_u64_be(payload, 1)?;
+                    let payout = apply_aces_up_update(&mut state, new_bet)?;
+                    session.state_blob = state.to_blob();
+                    Ok(if payout == 0 {
+                        GameResult::Continue
+                    } else {
+                        GameResult::ContinueWithUpdate { payout }
+                    })
+                }
+                Move::Deal => {
+                    let mut payout_update: i64 = 0;
+                    if payload.len() == 9 {
+                        let new_bet = parse_u64_be(payload, 1)?;
+                        payout_update = apply_aces_up_update(&mut state, new_bet)?;
+                    } else if payload.len() != 1 {
+                        return Err(GameError::InvalidPayload);
+                    }
+
+                    let mut deck = rng.create_deck();
+                    for card in state.player.iter_mut() {
+                        *card = rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
+                    }
+                    state.stage = Stage::Decision;
+
+                    session.state_blob = state.to_blob();
+                    Ok(if payout_update == 0 {
+                        GameResult::Continue
+                    } else {
+                        GameResult::ContinueWithUpdate {
+                            payout: payout_update,
+                        }
+                    })
+                }
+                _ => Err(GameError::InvalidMove),
+            },
+            Stage::Decision => match mv {
+                Move::Fold => {
+                    // Fold: lose ante, Aces Up still resolves.
+                    // Reveal dealer cards for display.
+                    deal_dealer(&mut state, rng)?;
+                    state.stage = Stage::Complete;
+                    session.is_complete = true;
+
+                    let total_return = resolve_aces_up_return(&state.player, state.aces_up_bet);
+                    let total_wagered = session.bet.saturating_add(state.aces_up_bet);
+
+                    session.state_blob = state.to_blob();
+
+                    if total_return == 0 {
+                        Ok(GameResult::LossPreDeducted(total_wagered))
+                    } else {
+                        Ok(GameResult::Win(total_return))
+                    }
+                }
+                Move::Play => {
+                    if payload.len() != 2 {
+                        return Err(GameError::InvalidPayload);
+                    }
+                    let multiplier = payload[1];
+                    if multiplier == 0 || multiplier > FOUR_CARD_MAX_PLAY_MULTIPLIER {
+                        return Err(GameError::InvalidMove);
+                    }
+                    let play = session
+                        .bet
+                        .checked_mul(multiplier as u64)
+                        .and_then(|play| i64::try_from(play).ok())
+                        .ok_or(GameError::InvalidMove)?;
+
+                    // Charge Play bet now; resolve on Reveal.
+                    state.play_multiplier = multiplier;
+                    state.stage = Stage::AwaitingReveal;
+                    session.state_blob = state.to_blob();
+                    Ok(GameResult::ContinueWithUpdate { payout: -play })
+                }
+                _ => Err(GameError::InvalidMove),
+            },
+            Stage::AwaitingReveal => match mv {
+                Move::Reveal => {
+                    // Reveal dealer cards and resolve all bets.
+                    deal_dealer(&mut state, rng)?;
+                    state.stage = Stage::Complete;
+                    session.is_complete = true;
+
+                    let total_return = resolve_return(&state, session.bet);
+                    let total_wagered = session
+                        .bet
+                        .saturating_mul(1 + state.play_multiplier as u64)
+                        .saturating_add(state.aces_up_bet);
+
+                    session.state_blob = state.to_blob();
+
+                    if total_return == 0 {
+                        Ok(GameResult::LossPreDeducted(total_wagered))
+                    } else {
+                        Ok(GameResult::Win(total_return))
+                    }
+                }
+                _ => Err(GameError::InvalidMove),
+            },
+            Stage::Complete => Err(GameError::GameAlreadyComplete),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::{create_account_keypair, create_network_keypair, create_seed};
+    use nullspace_types::casino::GameType;
+
+    fn create_test_seed() -> nullspace_types::Seed {
+        let (network_secret, _) = create_network_keypair();
+        create_seed(&network_secret, 1)
+    }
+
+    fn create_test_session(id: u64, bet: u64) -> GameSession {
+        let (_, pk) = create_account_keypair(1);
+        GameSession {
+            id,
+            player: pk,
+            game_type: GameType::FourCard,
+            bet,
+            state_blob: vec![],
+            move_count: 0,
+            created_at: 0,
+            is_complete: false,
+            super_mode: nullspace_types::casino::SuperModeState::default(),
+            is_tournament: false,
+            tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
+        }
+    }
+
+    /// Card with `rank` (2-14) and `suit` (0-3).
+    fn card(rank: u8, suit: u8) -> u8 {
+        suit * 13 + if rank == 14 { 0 } else { rank - 1 }
+    }
+
+    fn hand(cards: [(u8, u8); 4]) -> (HandRank, [u8; 4]) {
+        evaluate_hand(&cards.map(|(r, s)| card(r, s)))
+    }
+
+    #[test]
+    fn test_evaluate_hand() {
+        assert_eq!(
+            hand([(9, 0), (9, 1), (9, 2), (9, 3)]).0,
+            HandRank::FourOfAKind
+        );
+        assert_eq!(
+            hand([(9, 0), (10, 0), (11, 0), (12, 0)]).0,
+            HandRank::StraightFlush
+        );
+        assert_eq!(
+            hand([(2, 0), (2, 1), (2, 2), (14, 3)]),
+            (HandRank::ThreeOfAKind, [2, 2, 2, 14])
+        );
+        assert_eq!(hand([(2, 0), (5, 0), (9, 0), (13, 0)]).0, HandRank::Flush);
+        let wheel = hand([(14, 0), (2, 1), (3, 2), (4, 3)]);
+        assert_eq!(wheel, (HandRank::Straight, [4, 3, 2, 1]));
+        assert_eq!(
+            hand([(14, 0), (13, 1), (12, 2), (11, 3)]),
+            (HandRank::Straight, [14, 13, 12, 11])
+        );
+        assert_eq!(
+            hand([(5, 0), (5, 1), (3, 2), (3, 3)]),
+            (HandRank::TwoPair, [5, 5, 3, 3])
+        );
+        assert_eq!(
+            hand([(5, 0), (5, 1), (3, 2), (14, 3)]),
+            (HandRank::Pair, [5, 5, 14, 3])
+        );
+        assert_eq!(
+            hand([(5, 0), (7, 1), (3, 2), (14, 3)]),
+            (HandRank::HighCard, [14, 7, 5, 3])
+        );
+
+        // Trips beat a flush and a straight in four-card poker
+        assert!(HandRank::ThreeOfAKind > HandRank::Flush);
+        assert!(HandRank::Flush > HandRank::Straight);
+    }
+
+    #[test]
+    fn test_evaluate_best_hand() {
+        let cards = [
+            card(14, 0),
+            card(14, 1),
+            card(9, 2),
+            card(9, 3),
+            card(9, 0),
+            card(4, 1),
+        ];
+        assert_eq!(
+            evaluate_best_hand(&cards),
+            (HandRank::ThreeOfAKind, [9, 9, 9, 14])
+        );
+    }
+
+    #[test]
+    fn test_paytables() {
+        assert_eq!(
+            ante_bonus_multiplier(HandRank::StraightFlush),
+            FOUR_CARD_ANTE_BONUS_PAYTABLE[6]
+        );
+        assert_eq!(ante_bonus_multiplier(HandRank::Flush), 0);
+
+        // Aces Up pays a pair only when it is a pair of Aces
+        assert_eq!(
+            aces_up_multiplier(&hand([(14, 0), (14, 1), (3, 2), (5, 3)])),
+            1
+        );
+        assert_eq!(
+            aces_up_multiplier(&hand([(13, 0), (13, 1), (3, 2), (5, 3)])),
+            0
+        );
+        assert_eq!(
+            aces_up_multiplier(&hand([(13, 0), (13, 1), (3, 2), (3, 3)])),
+            2
+        );
+        assert_eq!(
+            aces_up_multiplier(&hand([(9, 0), (9, 1), (9, 2), (9, 3)])),
+            50
+        );
+    }
+
+    #[test]
+    fn test_state_roundtrip() {
+        let state = FcState {
+            stage: Stage::AwaitingReveal,
+            player: [1, 2, 3, 4, 5],
+            dealer: [CARD_UNKNOWN; 6],
+            aces_up_bet: 25,
+            play_multiplier: 3,
+        };
+        let blob = state.to_blob();
+        assert_eq!(blob.len(), 22);
+        assert_eq!(FcState::from_blob(&blob), Some(state));
+
+        let mut bad = blob.clone();
+        bad[21] = FOUR_CARD_MAX_PLAY_MULTIPLIER + 1;
+        assert_eq!(FcState::from_blob(&bad), None);
+        assert_eq!(FcState::from_blob(&blob[..21]), None);
+        crate::casino::state_blob::fuzz::roundtrip(&[state]);
+    }
+
+    #[test]
+    fn test_play_multiplier_bounds() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(1, 10);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        FourCardPoker::init(&mut session, &mut rng);
+        FourCardPoker::process_move(&mut session, &[Move::Deal as u8], &mut rng).unwrap();
+
+        for payload in [vec![0], vec![0, 0], vec![0, 4]] {
+            assert!(FourCardPoker::process_move(&mut session, &payload, &mut rng).is_err());
+        }
+        assert!(matches!(
+            FourCardPoker::process_move(&mut session, &[Move::Reveal as u8], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+
+        let result = FourCardPoker::process_move(&mut session, &[0, 3], &mut rng).unwrap();
+        assert!(matches!(
+            result,
+            GameResult::ContinueWithUpdate { payout: -30 }
+        ));
+    }
+
+    #[test]
+    fn test_payout_properties() {
+        // Over many deals: the Ante and Play win together exactly when the player's hand is at
+        // least as good as the dealer's, the ante bonus and Aces Up pay on top, and the total
+        // never exceeds the best possible hand.
+        let seed = create_test_seed();
+        let ante = 10u64;
+        let aces_up = 5u64;
+        for id in 0..200u64 {
+            let multiplier = (id % 3) as u8 + 1;
+            let mut session = create_test_session(id, ante);
+            let mut rng = GameRng::new(&seed, id, 0);
+            FourCardPoker::init(&mut session, &mut rng);
+
+            let mut deal = vec![Move::Deal as u8];
+            deal.extend_from_slice(&aces_up.to_be_bytes());
+            let mut rng = GameRng::new(&seed, id, 1);
+            let result = FourCardPoker::process_move(&mut session, &deal, &mut rng).unwrap();
+            assert!(matches!(
+                result,
+                GameResult::ContinueWithUpdate { payout: -5 }
+            ));
+
+            let mut rng = GameRng::new(&seed, id, 2);
+            FourCardPoker::process_move(&mut session, &[0, multiplier], &mut rng).unwrap();
+            let mut rng = GameRng::new(&seed, id, 3);
+            let result =
+                FourCardPoker::process_move(&mut session, &[Move::Reveal as u8], &mut rng).unwrap();
+            assert!(session.is_complete);
+
+            let state = FcState::from_blob(&session.state_blob).unwrap();
+            let mut cards = state.player.to_vec();
+            cards.extend_from_slice(&state.dealer);
+            cards.sort_unstable();
+            cards.dedup();
+            assert_eq!(cards.len(), 11);
+
+            let player_hand = evaluate_best_hand(&state.player);
+            let dealer_hand = evaluate_best_hand(&state.dealer);
+            let staked = ante * (1 + multiplier as u64);
+            let main = if player_hand >= dealer_hand {
+                2 * staked
+            } else {
+                0
+            };
+            let expected = main
+                + ante * ante_bonus_multiplier(player_hand.0)
+                + match aces_up_multiplier(&player_hand) {
+                    0 => 0,
+                    mult => aces_up * (mult + 1),
+                };
+            let max = 2 * staked + ante * 40 + aces_up * 51;
+            assert!(expected <= max);
+            match result {
+                GameResult::Win(total) => assert_eq!(total, expected),
+                GameResult::LossPreDeducted(total) => {
+                    assert_eq!(expected, 0);
+                    assert_eq!(total, staked + aces_up);
+                }
+                _ => panic!("unexpected result"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_fold_resolves_aces_up() {
+        let seed = create_test_seed();
+        for id in 0..50u64 {
+            let mut session = create_test_session(id, 10);
+            let mut rng = GameRng::new(&seed, id, 0);
+            FourCardPoker::init(&mut session, &mut rng);
+            let mut deal = vec![Move::Deal as u8];
+            deal.extend_from_slice(&4u64.to_be_bytes());
+            FourCardPoker::process_move(&mut session, &deal, &mut rng).unwrap();
+            let result =
+                FourCardPoker::process_move(&mut session, &[Move::Fold as u8], &mut rng).unwrap();
+
+            let state = FcState::from_blob(&session.state_blob).unwrap();
+            assert!(state.dealer.iter().all(|&c| c < 52));
+            match aces_up_multiplier(&evaluate_best_hand(&state.player)) {
+                0 => assert!(matches!(result, GameResult::LossPreDeducted(14))),
+                mult => assert!(matches!(result, GameResult::Win(x) if x == 4 * (mult + 1))),
+            }
+        }
+    }
+}
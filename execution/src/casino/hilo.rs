@@ -1,16 +1,39 @@
 //! HiLo game implementation.
 //!
 //! State blob format:
-//! [currentCard:u8] [accumulator:i64 BE]
+//! [currentCard:u8] [accumulator:i64 BE] [rung:u8]?
+//! [sideBet:u8] [sideTarget:u8] [sideAmount:u64 BE] [sideReturn:u64 BE] (only after a call
+//!                                                                      with a side bet)
 //!
 //! The accumulator stores the current pot multiplier in basis points (1/10000).
 //! For example, 15000 = 1.5x multiplier.
+//! The rung counts the correct calls made so far (blobs without it are read from the start of
+//! the ladder).
+//!
+//! Each correct call climbs the ladder, multiplying the accumulator by the step for the call in
+//! `MULTIPLIERS`. A call loses on a card of the same rank.
+//!
+//! The side bet block reports the last call's side bet and what it returned (stake included).
 //!
 //! Payload format:
 //! [0] = Higher - guess next card is higher
 //! [1] = Lower - guess next card is lower
 //! [2] = Cashout - take current pot
+//!
+//! Higher and Lower may carry a side bet on the card they draw:
+//! [0|1, side_bet, target, amount_bytes...]
+//!
+//! Side bets:
+//! 0 = Exact (10:1) - target = rank 1-13 (Ace = 1)
+//! 1 = Color (1:1) - target = 0 (red) or 1 (black)
+//! 2 = Suit (3:1) - target = suit 0-3
+//!
+//! Side bets are charged and settled with the call; a losing call still pays a winning side
+//! bet.
 
+use super::state_blob::{
+    impl_blob_field_enum, BlobField, BlobReader, BlobWriter, StateBlob, UNVERSIONED,
+};
 use super::super_mode::apply_hilo_streak_multiplier;
 use super::{CasinoGame, GameError, GameResult, GameRng};
 use nullspace_types::casino::GameSession;
@@ -40,54 +63,172 @@ impl TryFrom<u8> for Move {
     }
 }
 
+/// Side bets on the next card.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SideBetType {
+    Exact = 0,
+    Color = 1,
+    Suit = 2,
+}
+
+impl TryFrom<u8> for SideBetType {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(SideBetType::Exact),
+            1 => Ok(SideBetType::Color),
+            2 => Ok(SideBetType::Suit),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+/// A side bet placed with a call, and what it returned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SideBet {
+    bet_type: SideBetType,
+    target: u8,
+    amount: u64,
+    /// Total return (stake + winnings, 0 on a loss).
+    returned: u64,
+}
+
+/// [type:u8] [target:u8] [amount:u64] [return:u64]
+impl BlobField for SideBet {
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.bet_type)
+            .put(&self.target)
+            .put(&self.amount)
+            .put(&self.returned);
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        Some(SideBet {
+            bet_type: reader.get()?,
+            target: reader.get()?,
+            amount: reader.get()?,
+            returned: reader.get()?,
+        })
+    }
+}
+
+impl_blob_field_enum!(SideBetType);
+
+impl SideBet {
+    /// Whether `target` is valid for `bet_type`.
+    fn is_valid_target(bet_type: SideBetType, target: u8) -> bool {
+        match bet_type {
+            SideBetType::Exact => (1..=13).contains(&target),
+            SideBetType::Color => target <= 1,
+            SideBetType::Suit => target <= 3,
+        }
+    }
+
+    /// Payout multiplier ("to 1") of a side bet type.
+    fn payout_multiplier(bet_type: SideBetType) -> u64 {
+        match bet_type {
+            SideBetType::Exact => 10,
+            SideBetType::Color => 1,
+            SideBetType::Suit => 3,
+        }
+    }
+
+    /// Whether the side bet wins on `card`.
+    fn wins(&self, card: u8) -> bool {
+        match self.bet_type {
+            SideBetType::Exact => card_rank(card) == self.target,
+            // Hearts and diamonds are red
+            SideBetType::Color => u8::from(!matches!(card / 13, 1 | 2)) == self.target,
+            SideBetType::Suit => card / 13 == self.target,
+        }
+    }
+}
+
 /// Get the rank of a card for HiLo comparison (1-13).
 /// Ace = 1, 2 = 2, ..., K = 13
 pub fn card_rank(card: u8) -> u8 {
     (card % 13) + 1
 }
 
-/// Calculate the multiplier for a correct guess based on probability.
-/// Returns multiplier in basis points.
-fn calculate_multiplier(current_rank: u8, guess_higher: bool) -> i64 {
-    // Cards left that would be wins
-    let wins = if guess_higher {
-        // Higher: cards with rank > current
-        13 - current_rank as i64
-    } else {
-        // Lower: cards with rank < current
-        current_rank as i64 - 1
-    };
-
-    if wins <= 0 {
-        // No possible wins (e.g., guessing higher than King)
-        return 0;
+/// Build the ladder steps: the fair multiplier (in basis points, rounded down) for a correct
+/// call from each rank, given the 51 cards left after the current one.
+const fn build_multipliers() -> [[i64; 2]; 13] {
+    let mut table = [[0; 2]; 13];
+    let mut rank = 1;
+    while rank <= 13 {
+        // Four cards of each rank strictly above (Higher) or below (Lower) the current one win
+        let higher = 4 * (13 - rank);
+        let lower = 4 * (rank - 1);
+        if higher > 0 {
+            table[rank as usize - 1][0] = 51 * BASE_MULTIPLIER / higher;
+        }
+        if lower > 0 {
+            table[rank as usize - 1][1] = 51 * BASE_MULTIPLIER / lower;
+        }
+        rank += 1;
     }
-
-    // Multiplier = 13 / wins (approximate fair odds)
-    // Stored in basis points for precision
-    (13 * BASE_MULTIPLIER) / wins
+    table
 }
 
-/// Parse state blob into current card and accumulator.
-fn parse_state(state: &[u8]) -> Option<(u8, i64)> {
-    if state.len() < 9 {
-        return None;
-    }
+/// Published ladder steps in basis points, as `[higher, lower]` for current ranks Ace (1)
+/// through King (13). A step of 0 means the call is impossible.
+pub const MULTIPLIERS: [[i64; 2]; 13] = build_multipliers();
 
-    let current_card = state[0];
-    let accumulator = i64::from_be_bytes([
-        state[1], state[2], state[3], state[4], state[5], state[6], state[7], state[8],
-    ]);
+/// Get the ladder step for a correct guess from `current_rank` (in basis points, 0 when the
+/// guess can't win).
+fn calculate_multiplier(current_rank: u8, guess_higher: bool) -> i64 {
+    MULTIPLIERS
+        .get((current_rank as usize).wrapping_sub(1))
+        .map_or(0, |steps| steps[usize::from(!guess_higher)])
+}
 
-    Some((current_card, accumulator))
+/// Game state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct HiLoState {
+    current_card: u8,
+    /// Pot multiplier in basis points.
+    accumulator: i64,
+    /// Correct calls made so far.
+    rung: u8,
+    /// The last call's side bet, if it had one.
+    side_bet: Option<SideBet>,
 }
 
-/// Serialize state to blob.
-fn serialize_state(current_card: u8, accumulator: i64) -> Vec<u8> {
-    let mut state = Vec::with_capacity(9);
-    state.push(current_card);
-    state.extend_from_slice(&accumulator.to_be_bytes());
-    state
+/// [currentCard:u8] [accumulator:i64] [rung:u8] [sideBet?]
+impl StateBlob for HiLoState {
+    const VERSION: u8 = UNVERSIONED;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.current_card)
+            .put(&self.accumulator)
+            .put(&self.rung);
+        if let Some(side_bet) = &self.side_bet {
+            writer.put(side_bet);
+        }
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        let current_card = reader.get()?;
+        let accumulator = reader.get()?;
+        // Blobs without a rung start from the bottom of the ladder
+        let rung = reader.get_or(0)?;
+        let side_bet = if reader.remaining() > 0 {
+            Some(reader.get()?)
+        } else {
+            None
+        };
+
+        Some(HiLoState {
+            current_card,
+            accumulator,
+            rung,
+            side_bet,
+        })
+    }
 }
 
 pub struct HiLo;
@@ -100,9 +241,13 @@ impl CasinoGame for HiLo {
         let card = rng.draw_card(&mut deck).unwrap_or(0);
 
         // Initial accumulator = bet amount in basis points (1x)
-        let accumulator = BASE_MULTIPLIER;
-
-        session.state_blob = serialize_state(card, accumulator);
+        session.state_blob = HiLoState {
+            current_card: card,
+            accumulator: BASE_MULTIPLIER,
+            rung: 0,
+            side_bet: None,
+        }
+        .to_blob();
         GameResult::Continue
     }
 
@@ -120,8 +265,9 @@ impl CasinoGame for HiLo {
         }
 
         let mv = Move::try_from(payload[0])?;
-        let (current_card, accumulator) =
-            parse_state(&session.state_blob).ok_or(GameError::InvalidPayload)?;
+        let mut state =
+            HiLoState::from_blob(&session.state_blob).ok_or(GameError::InvalidPayload)?;
+        let current_card = state.current_card;
 
         match mv {
             Move::Cashout => {
@@ -131,7 +277,7 @@ impl CasinoGame for HiLo {
                 // Calculate actual payout from accumulator with overflow protection
                 // accumulator is in basis points, so divide by BASE_MULTIPLIER
                 let base_payout = (session.bet as i64)
-                    .checked_mul(accumulator)
+                    .checked_mul(state.accumulator)
                     .and_then(|v| v.checked_div(BASE_MULTIPLIER))
                     .ok_or(GameError::InvalidState)?;
 
@@ -167,6 +313,37 @@ impl CasinoGame for HiLo {
                     return Err(GameError::InvalidMove);
                 }
 
+                // Optional side bet: [call, side_bet, target, amount_bytes...]
+                let mut side_bet = match payload.len() {
+                    1 => None,
+                    11 => {
+                        let bet_type = SideBetType::try_from(payload[1])?;
+                        let target = payload[2];
+                        let amount = u64::from_be_bytes(
+                            payload[3..11]
+                                .try_into()
+                                .map_err(|_| GameError::InvalidPayload)?,
+                        );
+                        // The whole return must fit a balance update
+                        let max_return = amount
+                            .checked_mul(SideBet::payout_multiplier(bet_type) + 1)
+                            .and_then(|max_return| i64::try_from(max_return).ok());
+                        if amount == 0
+                            || max_return.is_none()
+                            || !SideBet::is_valid_target(bet_type, target)
+                        {
+                            return Err(GameError::InvalidPayload);
+                        }
+                        Some(SideBet {
+                            bet_type,
+                            target,
+                            amount,
+                            returned: 0,
+                        })
+                    }
+                    _ => return Err(GameError::InvalidPayload),
+                };
+
                 // Draw new card (recreate deck without current card)
                 let mut deck = rng.create_deck_excluding(&[current_card]);
                 let new_card = rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
@@ -174,6 +351,19 @@ impl CasinoGame for HiLo {
 
                 session.move_count += 1;
 
+                // Settle the side bet on the same card
+                if let Some(side_bet) = side_bet.as_mut() {
+                    if side_bet.wins(new_card) {
+                        side_bet.returned = side_bet.amount.saturating_mul(
+                            SideBet::payout_multiplier(side_bet.bet_type).saturating_add(1),
+                        );
+                    }
+                }
+                let side = side_bet
+                    .as_ref()
+                    .map_or((0, 0), |side_bet| (side_bet.amount, side_bet.returned));
+                state.side_bet = side_bet;
+
                 // Check if guess was correct
                 let correct = if guess_higher {
                     new_rank > current_rank
@@ -184,18 +374,35 @@ impl CasinoGame for HiLo {
                 if correct {
                     // Calculate new accumulator with overflow protection
                     let multiplier = calculate_multiplier(current_rank, guess_higher);
-                    let new_accumulator = accumulator
+                    state.accumulator = state
+                        .accumulator
                         .checked_mul(multiplier)
                         .and_then(|v| v.checked_div(BASE_MULTIPLIER))
                         .ok_or(GameError::InvalidState)?;
-
-                    session.state_blob = serialize_state(new_card, new_accumulator);
-                    Ok(GameResult::Continue)
+                    state.rung = state.rung.saturating_add(1);
+                    state.current_card = new_card;
+
+                    session.state_blob = state.to_blob();
+                    Ok(match side {
+                        (0, _) => GameResult::Continue,
+                        (amount, returned) => GameResult::ContinueWithUpdate {
+                            payout: (returned as i64).saturating_sub(amount as i64),
+                        },
+                    })
                 } else {
                     // Wrong guess - lose everything
-                    session.state_blob = serialize_state(new_card, 0);
+                    state.current_card = new_card;
+                    state.accumulator = 0;
+                    session.state_blob = state.to_blob();
                     session.is_complete = true;
-                    Ok(GameResult::Loss)
+                    Ok(match side {
+                        (0, _) => GameResult::Loss,
+                        (amount, 0) => GameResult::LossWithExtraDeduction(amount),
+                        (amount, returned) => GameResult::WinWithExtraDeduction {
+                            payout: returned,
+                            extra_deduction: amount,
+                        },
+                    })
                 }
             }
         }
@@ -227,9 +434,23 @@ mod tests {
             super_mode: nullspace_types::casino::SuperModeState::default(),
             is_tournament: false,
             tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
         }
     }
 
+    /// State blob at the bottom of the ladder showing `card`.
+    fn start_state(card: u8) -> Vec<u8> {
+        HiLoState {
+            current_card: card,
+            accumulator: BASE_MULTIPLIER,
+            rung: 0,
+            side_bet: None,
+        }
+        .to_blob()
+    }
+
     #[test]
     fn test_card_rank() {
         // Ace = 1
@@ -247,21 +468,33 @@ mod tests {
 
     #[test]
     fn test_calculate_multiplier() {
-        // From Ace (rank 1), guessing higher: 12 wins possible
+        // From Ace (rank 1), guessing higher: 48 of 51 cards win
         let mult = calculate_multiplier(1, true);
-        assert_eq!(mult, 13 * BASE_MULTIPLIER / 12); // ~1.08x
+        assert_eq!(mult, 10_625); // 1.0625x
 
-        // From King (rank 13), guessing lower: 12 wins possible
+        // From King (rank 13), guessing lower: 48 of 51 cards win
         let mult = calculate_multiplier(13, false);
-        assert_eq!(mult, 13 * BASE_MULTIPLIER / 12); // ~1.08x
+        assert_eq!(mult, 10_625);
 
-        // From 7 (middle), guessing higher: 6 wins possible
+        // From 7 (middle), guessing higher: 24 of 51 cards win
         let mult = calculate_multiplier(7, true);
-        assert_eq!(mult, 13 * BASE_MULTIPLIER / 6); // ~2.16x
+        assert_eq!(mult, 21_250); // 2.125x
 
-        // From 2, guessing lower: only 1 win possible (Ace)
+        // From 2, guessing lower: only the 4 Aces win
         let mult = calculate_multiplier(2, false);
-        assert_eq!(mult, 13 * BASE_MULTIPLIER / 1); // 13x
+        assert_eq!(mult, 127_500); // 12.75x
+
+        // The published table is symmetric, and every step is fair on the remaining cards
+        for rank in 1..=13u8 {
+            let [higher, lower] = MULTIPLIERS[rank as usize - 1];
+            assert_eq!(higher, MULTIPLIERS[13 - rank as usize][1]);
+            assert_eq!(lower, MULTIPLIERS[13 - rank as usize][0]);
+            let wins = 4 * (13 - rank as i64);
+            if wins > 0 {
+                assert!(higher * wins <= 51 * BASE_MULTIPLIER);
+                assert!((higher + 1) * wins > 51 * BASE_MULTIPLIER);
+            }
+        }
     }
 
     #[test]
@@ -271,18 +504,29 @@ mod tests {
 
         // Cannot guess lower than Ace
         assert_eq!(calculate_multiplier(1, false), 0);
+
+        // Ranks off the table never win
+        assert_eq!(calculate_multiplier(0, true), 0);
+        assert_eq!(calculate_multiplier(14, false), 0);
     }
 
     #[test]
     fn test_parse_serialize_roundtrip() {
-        let card = 25; // Queen of diamonds
-        let accumulator = 15_000; // 1.5x
-
-        let state = serialize_state(card, accumulator);
-        let (c, a) = parse_state(&state).expect("Failed to parse state");
-
-        assert_eq!(c, card);
-        assert_eq!(a, accumulator);
+        let state = HiLoState {
+            current_card: 25,    // Queen of diamonds
+            accumulator: 15_000, // 1.5x
+            rung: 2,
+            side_bet: None,
+        };
+
+        let blob = state.to_blob();
+        assert_eq!(HiLoState::from_blob(&blob), Some(state));
+
+        // Blobs without a rung start from the bottom of the ladder
+        let legacy = HiLoState::from_blob(&blob[..9]).expect("Failed to parse state");
+        assert_eq!(legacy.accumulator, 15_000);
+        assert_eq!(legacy.rung, 0);
+        assert!(HiLoState::from_blob(&blob[..8]).is_none());
     }
 
     #[test]
@@ -293,10 +537,11 @@ mod tests {
 
         HiLo::init(&mut session, &mut rng);
 
-        let (card, accumulator) = parse_state(&session.state_blob).expect("Failed to parse state");
+        let state = HiLoState::from_blob(&session.state_blob).expect("Failed to parse state");
 
-        assert!(card < 52);
-        assert_eq!(accumulator, BASE_MULTIPLIER);
+        assert!(state.current_card < 52);
+        assert_eq!(state.accumulator, BASE_MULTIPLIER);
+        assert_eq!(state.rung, 0);
         assert!(!session.is_complete);
     }
 
@@ -327,7 +572,7 @@ mod tests {
         let mut session = create_test_session(100);
 
         // Force a King in state
-        session.state_blob = serialize_state(12, BASE_MULTIPLIER); // King
+        session.state_blob = start_state(12); // King
 
         let mut rng = GameRng::new(&seed, session.id, 1);
         let result = HiLo::process_move(&mut session, &[0], &mut rng); // Higher
@@ -341,7 +586,7 @@ mod tests {
         let mut session = create_test_session(100);
 
         // Force an Ace in state
-        session.state_blob = serialize_state(0, BASE_MULTIPLIER); // Ace
+        session.state_blob = start_state(0); // Ace
 
         let mut rng = GameRng::new(&seed, session.id, 1);
         let result = HiLo::process_move(&mut session, &[1], &mut rng); // Lower
@@ -355,7 +600,7 @@ mod tests {
         let mut session = create_test_session(100);
 
         // Start with a 2 (lots of room to go higher)
-        session.state_blob = serialize_state(1, BASE_MULTIPLIER); // 2 of spades
+        session.state_blob = start_state(1); // 2 of spades
 
         let mut move_num = 1;
         let mut streak = 0;
@@ -368,9 +613,11 @@ mod tests {
             match result {
                 Ok(GameResult::Continue) => {
                     streak += 1;
-                    let (_, acc) = parse_state(&session.state_blob).expect("Failed to parse state");
-                    // Accumulator should be growing
-                    assert!(acc > BASE_MULTIPLIER);
+                    let state =
+                        HiLoState::from_blob(&session.state_blob).expect("Failed to parse state");
+                    // Accumulator should be growing, one rung per correct call
+                    assert!(state.accumulator > BASE_MULTIPLIER);
+                    assert_eq!(state.rung, streak);
                 }
                 Ok(GameResult::Loss) => {
                     break;
@@ -384,4 +631,189 @@ mod tests {
             move_num += 1;
         }
     }
+
+    #[test]
+    fn test_ladder_steps() {
+        let seed = create_test_seed();
+
+        // Cashing out banks the pot
+        let mut session = create_test_session(100);
+        session.state_blob = HiLoState {
+            current_card: 6,
+            accumulator: 21_250,
+            rung: 1,
+            side_bet: None,
+        }
+        .to_blob();
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        let result = HiLo::process_move(&mut session, &[2], &mut rng).expect("Failed to cash out");
+        assert!(matches!(result, GameResult::Win(212)));
+
+        // Climb from a 2 until a call fails, checking each step against the table
+        let mut session = create_test_session(100);
+        session.state_blob = start_state(1);
+        for move_num in 1..20 {
+            let before = HiLoState::from_blob(&session.state_blob).expect("Failed to parse state");
+            let rank = card_rank(before.current_card);
+            let guess_higher = rank <= 7;
+            let mut rng = GameRng::new(&seed, session.id, move_num);
+            let result = HiLo::process_move(&mut session, &[u8::from(!guess_higher)], &mut rng)
+                .expect("Failed to process move");
+            let after = HiLoState::from_blob(&session.state_blob).expect("Failed to parse state");
+            match result {
+                GameResult::Continue => {
+                    let step = MULTIPLIERS[rank as usize - 1][usize::from(!guess_higher)];
+                    assert_eq!(
+                        after.accumulator,
+                        before.accumulator * step / BASE_MULTIPLIER
+                    );
+                    assert_eq!(after.rung, before.rung + 1);
+                }
+                GameResult::Loss => {
+                    assert_eq!(after.accumulator, 0);
+                    assert_eq!(after.rung, before.rung);
+                    return;
+                }
+                _ => panic!("Unexpected result"),
+            }
+        }
+    }
+
+    /// Call payload carrying a side bet.
+    fn side_bet_payload(call: Move, bet_type: SideBetType, target: u8, amount: u64) -> Vec<u8> {
+        let mut payload = vec![call as u8, bet_type as u8, target];
+        payload.extend_from_slice(&amount.to_be_bytes());
+        payload
+    }
+
+    #[test]
+    fn test_side_bet_wins() {
+        let side_bet = |bet_type, target| SideBet {
+            bet_type,
+            target,
+            amount: 10,
+            returned: 0,
+        };
+
+        // Ace of hearts (13), Ace of clubs (39), King of diamonds (38)
+        assert!(side_bet(SideBetType::Exact, 1).wins(13));
+        assert!(side_bet(SideBetType::Exact, 1).wins(39));
+        assert!(!side_bet(SideBetType::Exact, 1).wins(38));
+        assert!(side_bet(SideBetType::Color, 0).wins(13));
+        assert!(side_bet(SideBetType::Color, 0).wins(38));
+        assert!(side_bet(SideBetType::Color, 1).wins(39));
+        assert!(!side_bet(SideBetType::Color, 1).wins(13));
+        assert!(side_bet(SideBetType::Suit, 3).wins(39));
+        assert!(!side_bet(SideBetType::Suit, 3).wins(38));
+
+        assert!(SideBet::is_valid_target(SideBetType::Exact, 13));
+        assert!(!SideBet::is_valid_target(SideBetType::Exact, 0));
+        assert!(!SideBet::is_valid_target(SideBetType::Color, 2));
+        assert!(!SideBet::is_valid_target(SideBetType::Suit, 4));
+    }
+
+    #[test]
+    fn test_side_bet_state() {
+        let state = HiLoState {
+            current_card: 25,
+            accumulator: 15_000,
+            rung: 2,
+            side_bet: Some(SideBet {
+                bet_type: SideBetType::Suit,
+                target: 1,
+                amount: 50,
+                returned: 200,
+            }),
+        };
+        let blob = state.to_blob();
+        assert_eq!(blob.len(), 28);
+        assert_eq!(HiLoState::from_blob(&blob), Some(state.clone()));
+        assert!(HiLoState::from_blob(&blob[..blob.len() - 1]).is_none());
+        let mut bad_type = blob.clone();
+        bad_type[10] = 3;
+        assert!(HiLoState::from_blob(&bad_type).is_none());
+
+        crate::casino::state_blob::fuzz::roundtrip(&[state]);
+    }
+
+    #[test]
+    fn test_side_bet_moves() {
+        let seed = create_test_seed();
+
+        // Invalid side bets are rejected before drawing
+        let mut session = create_test_session(100);
+        session.state_blob = start_state(6); // 7 of spades
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        for payload in [
+            side_bet_payload(Move::Higher, SideBetType::Exact, 14, 10),
+            side_bet_payload(Move::Higher, SideBetType::Color, 0, 0),
+            side_bet_payload(Move::Higher, SideBetType::Exact, 1, u64::MAX / 4),
+            vec![0, 1, 0],
+            vec![0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 10],
+        ] {
+            assert!(matches!(
+                HiLo::process_move(&mut session, &payload, &mut rng),
+                Err(GameError::InvalidPayload)
+            ));
+        }
+        assert_eq!(session.move_count, 0);
+
+        // Each call settles its side bet on the card it draws
+        for move_num in 1..40 {
+            let mut session = create_test_session(100);
+            session.state_blob = start_state(6);
+            let mut rng = GameRng::new(&seed, session.id, move_num);
+            let result = HiLo::process_move(
+                &mut session,
+                &side_bet_payload(Move::Higher, SideBetType::Suit, 2, 10),
+                &mut rng,
+            )
+            .expect("Failed to process move");
+
+            let state = HiLoState::from_blob(&session.state_blob).expect("Failed to parse state");
+            let side_bet = state.side_bet.expect("Side bet should be reported");
+            let diamond = state.current_card / 13 == 2;
+            assert_eq!(side_bet.returned, if diamond { 40 } else { 0 });
+            let call_won = card_rank(state.current_card) > 7;
+            match (call_won, diamond) {
+                (true, true) => assert!(matches!(
+                    result,
+                    GameResult::ContinueWithUpdate { payout: 30 }
+                )),
+                (true, false) => assert!(matches!(
+                    result,
+                    GameResult::ContinueWithUpdate { payout: -10 }
+                )),
+                (false, true) => assert!(matches!(
+                    result,
+                    GameResult::WinWithExtraDeduction {
+                        payout: 40,
+                        extra_deduction: 10
+                    }
+                )),
+                (false, false) => {
+                    assert!(matches!(result, GameResult::LossWithExtraDeduction(10)))
+                }
+            }
+        }
+
+        // A call without a side bet clears the last report
+        let mut session = create_test_session(100);
+        session.state_blob = HiLoState {
+            current_card: 0,
+            accumulator: BASE_MULTIPLIER,
+            rung: 0,
+            side_bet: Some(SideBet {
+                bet_type: SideBetType::Color,
+                target: 0,
+                amount: 10,
+                returned: 20,
+            }),
+        }
+        .to_blob();
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        HiLo::process_move(&mut session, &[0], &mut rng).expect("Failed to process move");
+        let state = HiLoState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.side_bet, None);
+    }
 }
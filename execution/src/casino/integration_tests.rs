@@ -28,6 +28,9 @@ mod tests {
             super_mode: nullspace_types::casino::SuperModeState::default(),
             is_tournament: false,
             tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
         }
     }
 
@@ -39,11 +42,18 @@ mod tests {
         for (i, game_type) in [
             GameType::Baccarat,
             GameType::Blackjack,
+            GameType::CaribbeanStud,
             GameType::CasinoWar,
             GameType::Craps,
+            GameType::FourCard,
             GameType::HiLo,
+            GameType::Mines,
+            GameType::MississippiStud,
+            GameType::MoneyWheel,
+            GameType::PaiGow,
             GameType::Roulette,
             GameType::SicBo,
+            GameType::Slots,
             GameType::ThreeCard,
             GameType::UltimateHoldem,
             GameType::VideoPoker,
@@ -200,7 +210,8 @@ mod tests {
         init_game(&mut session, &mut rng);
 
         // Verify 5 cards dealt
-        assert_eq!(session.state_blob.len(), 6); // stage + 5 cards
+        // version + stage + variant + 5 cards + hand count + winnings + gamble count
+        assert_eq!(session.state_blob.len(), 18);
 
         let mut rng = GameRng::new(&seed, session.id, 1);
         // Hold all cards (0b11111)
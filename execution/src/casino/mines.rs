@@ -0,0 +1,501 @@
+//! Mines game implementation.
+//!
+//! The player picks a square grid and how many mines it hides, then reveals cells one at a
+//! time. Every safe cell grows the multiplier, a mine loses the bet, and the player may cash
+//! out at the current multiplier after any safe reveal.
+//!
+//! Whether a revealed cell is a mine is drawn from that move's `GameRng` (with the odds of the
+//! mines left among the unrevealed cells), so the board can't be read from the public seed of
+//! an earlier block. When the round ends, the remaining mines are placed with the final move's
+//! `GameRng` and the whole board is recorded, so replaying the session's moves reproduces it.
+//!
+//! State blob format:
+//! [stage:u8] [side:u8] [mines:u8] [revealed:u32 BE] [mineMask:u32 BE] [multiplier:u64 BE]
+//!
+//! Stage: 0 = Setup, 1 = Playing, 2 = Complete
+//!
+//! Cells are numbered row by row (`row * side + column`); bit `i` of `revealed` and
+//! `mineMask` is cell `i`. The multiplier is in basis points (10000 = 1x).
+//!
+//! Payload format:
+//! [0, side, mines] - Pick the grid (2-5 cells a side) and mine count (at least one safe cell)
+//! [1, cell] - Reveal a cell
+//! [2] - Cash out (after at least one safe reveal)
+
+use super::state_blob::{impl_blob_field_enum, BlobReader, BlobWriter, StateBlob, UNVERSIONED};
+use super::{CasinoGame, GameError, GameResult, GameRng};
+use nullspace_types::casino::{GameSession, RngDraw};
+
+/// Base multiplier in basis points (1.0 = 10000)
+pub const BASE_MULTIPLIER: u64 = 10_000;
+
+/// House edge taken from the fair multiplier (in basis points).
+pub const HOUSE_EDGE_BPS: u64 = 100;
+
+/// Maximum multiplier (10,000x) in basis points.
+pub const MAX_MULTIPLIER: u64 = 10_000 * BASE_MULTIPLIER;
+
+/// Smallest grid side.
+pub const MIN_SIDE: u8 = 2;
+
+/// Largest grid side.
+pub const MAX_SIDE: u8 = 5;
+
+/// Mines stages.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Setup = 0,
+    Playing = 1,
+    Complete = 2,
+}
+
+impl TryFrom<u8> for Stage {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Stage::Setup),
+            1 => Ok(Stage::Playing),
+            2 => Ok(Stage::Complete),
+            _ => Err(GameError::InvalidState),
+        }
+    }
+}
+
+/// Mines move types.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Move {
+    Setup = 0,
+    Reveal = 1,
+    CashOut = 2,
+}
+
+impl TryFrom<u8> for Move {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Move::Setup),
+            1 => Ok(Move::Reveal),
+            2 => Ok(Move::CashOut),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+/// Game state for mines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinesState {
+    pub stage: Stage,
+    pub side: u8,
+    pub mines: u8,
+    pub revealed: u32,
+    pub mine_mask: u32,
+    pub multiplier: u64,
+}
+
+impl MinesState {
+    fn new() -> Self {
+        MinesState {
+            stage: Stage::Setup,
+            side: 0,
+            mines: 0,
+            revealed: 0,
+            mine_mask: 0,
+            multiplier: BASE_MULTIPLIER,
+        }
+    }
+
+    /// Number of cells on the grid.
+    pub fn cells(&self) -> u8 {
+        self.side * self.side
+    }
+
+    /// Number of safe cells revealed.
+    pub fn revealed_count(&self) -> u8 {
+        self.revealed.count_ones() as u8
+    }
+
+    /// Place `count` more mines among the cells that are neither revealed nor mined.
+    fn place_mines(&mut self, count: u8, rng: &mut GameRng) {
+        let taken = self.revealed | self.mine_mask;
+        let mut free: Vec<u8> = (0..self.cells())
+            .filter(|&cell| taken & (1 << cell) == 0)
+            .collect();
+        rng.shuffle(&mut free);
+        for cell in free.into_iter().take(count as usize) {
+            rng.record(RngDraw::Value(cell));
+            self.mine_mask |= 1 << cell;
+        }
+    }
+}
+
+/// [stage:u8] [side:u8] [mines:u8] [revealed:u32] [mineMask:u32] [multiplier:u64]
+impl StateBlob for MinesState {
+    const VERSION: u8 = UNVERSIONED;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.stage)
+            .put(&self.side)
+            .put(&self.mines)
+            .put(&self.revealed)
+            .put(&self.mine_mask)
+            .put(&self.multiplier);
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        let stage = reader.get()?;
+        let side: u8 = reader.get()?;
+        let mines: u8 = reader.get()?;
+        if side > MAX_SIDE {
+            return None;
+        }
+        if stage != Stage::Setup && (side < MIN_SIDE || mines == 0 || mines >= side * side) {
+            return None;
+        }
+        Some(MinesState {
+            stage,
+            side,
+            mines,
+            revealed: reader.get()?,
+            mine_mask: reader.get()?,
+            multiplier: reader.get()?,
+        })
+    }
+}
+
+impl_blob_field_enum!(Stage);
+
+/// Number of ways to choose `k` of `n` items.
+fn binomial(n: u8, k: u8) -> u64 {
+    let mut result: u64 = 1;
+    for i in 0..k as u64 {
+        result = result * (n as u64 - i) / (i + 1);
+    }
+    result
+}
+
+/// Multiplier (in basis points) after revealing `revealed` safe cells of a grid of `cells`
+/// hiding `mines`.
+///
+/// The fair multiplier is the inverse of the chance of surviving every reveal
+/// (`C(cells, revealed) / C(cells - mines, revealed)`), less the house edge.
+pub fn multiplier(cells: u8, mines: u8, revealed: u8) -> u64 {
+    if revealed == 0 {
+        return BASE_MULTIPLIER;
+    }
+    let safe = cells.saturating_sub(mines);
+    if revealed > safe {
+        return 0;
+    }
+    let fair = binomial(cells, revealed) as u128 * BASE_MULTIPLIER as u128
+        / binomial(safe, revealed) as u128;
+    let multiplier = fair * (BASE_MULTIPLIER - HOUSE_EDGE_BPS) as u128 / BASE_MULTIPLIER as u128;
+    multiplier.min(MAX_MULTIPLIER as u128) as u64
+}
+
+/// Return (stake + profit) of cashing out `bet` at `multiplier`.
+fn cash_out(bet: u64, multiplier: u64) -> u64 {
+    let payout = bet as u128 * multiplier as u128 / BASE_MULTIPLIER as u128;
+    u64::try_from(payout).unwrap_or(u64::MAX)
+}
+
+pub struct Mines;
+
+impl CasinoGame for Mines {
+    fn init(session: &mut GameSession, _rng: &mut GameRng) -> GameResult {
+        // The grid is picked (and mines drawn) by later moves
+        session.state_blob = MinesState::new().to_blob();
+        GameResult::Continue
+    }
+
+    fn process_move(
+        session: &mut GameSession,
+        payload: &[u8],
+        rng: &mut GameRng,
+    ) -> Result<GameResult, GameError> {
+        if session.is_complete {
+            return Err(GameError::GameAlreadyComplete);
+        }
+
+        if payload.is_empty() {
+            return Err(GameError::InvalidPayload);
+        }
+
+        let mv = Move::try_from(payload[0])?;
+        let mut state =
+            MinesState::from_blob(&session.state_blob).ok_or(GameError::InvalidState)?;
+
+        match mv {
+            Move::Setup => {
+                if state.stage != Stage::Setup {
+                    return Err(GameError::InvalidMove);
+                }
+                let [_, side, mines] = payload else {
+                    return Err(GameError::InvalidPayload);
+                };
+                if !(MIN_SIDE..=MAX_SIDE).contains(side) || *mines == 0 || *mines >= side * side {
+                    return Err(GameError::InvalidPayload);
+                }
+                state.side = *side;
+                state.mines = *mines;
+                state.stage = Stage::Playing;
+                session.state_blob = state.to_blob();
+                Ok(GameResult::Continue)
+            }
+            Move::Reveal => {
+                if state.stage != Stage::Playing {
+                    return Err(GameError::InvalidMove);
+                }
+                let [_, cell] = payload else {
+                    return Err(GameError::InvalidPayload);
+                };
+                if *cell >= state.cells() {
+                    return Err(GameError::InvalidPayload);
+                }
+                if state.revealed & (1 << cell) != 0 {
+                    return Err(GameError::InvalidMove);
+                }
+
+                // Every mine is still among the unrevealed cells
+                let unrevealed = state.cells() - state.revealed_count();
+                if rng.next_bounded(unrevealed) < state.mines {
+                    state.mine_mask = 1 << cell;
+                    state.place_mines(state.mines - 1, rng);
+                    state.multiplier = 0;
+                    state.stage = Stage::Complete;
+                    session.state_blob = state.to_blob();
+                    session.is_complete = true;
+                    return Ok(GameResult::Loss);
+                }
+
+                state.revealed |= 1 << cell;
+                state.multiplier = multiplier(state.cells(), state.mines, state.revealed_count());
+                if state.revealed_count() < state.cells() - state.mines {
+                    session.state_blob = state.to_blob();
+                    return Ok(GameResult::Continue);
+                }
+
+                // Every safe cell has been found, so the rest are mines
+                state.place_mines(state.mines, rng);
+                state.stage = Stage::Complete;
+                session.state_blob = state.to_blob();
+                session.is_complete = true;
+                Ok(GameResult::Win(cash_out(session.bet, state.multiplier)))
+            }
+            Move::CashOut => {
+                if state.stage != Stage::Playing || state.revealed == 0 {
+                    return Err(GameError::InvalidMove);
+                }
+                state.place_mines(state.mines, rng);
+                state.stage = Stage::Complete;
+                session.state_blob = state.to_blob();
+                session.is_complete = true;
+                Ok(GameResult::Win(cash_out(session.bet, state.multiplier)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::{create_account_keypair, create_network_keypair, create_seed};
+    use nullspace_types::casino::GameType;
+
+    fn create_test_seed() -> nullspace_types::Seed {
+        let (network_secret, _) = create_network_keypair();
+        create_seed(&network_secret, 1)
+    }
+
+    fn create_test_session(id: u64, bet: u64) -> GameSession {
+        let (_, pk) = create_account_keypair(1);
+        GameSession {
+            id,
+            player: pk,
+            game_type: GameType::Mines,
+            bet,
+            state_blob: vec![],
+            move_count: 0,
+            created_at: 0,
+            is_complete: false,
+            super_mode: nullspace_types::casino::SuperModeState::default(),
+            is_tournament: false,
+            tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
+        }
+    }
+
+    /// Start a session on a `side` grid hiding `mines`.
+    fn setup(seed: &nullspace_types::Seed, id: u64, side: u8, mines: u8) -> GameSession {
+        let mut session = create_test_session(id, 100);
+        let mut rng = GameRng::new(seed, id, 0);
+        Mines::init(&mut session, &mut rng);
+        let mut rng = GameRng::new(seed, id, 1);
+        let result = Mines::process_move(&mut session, &[0, side, mines], &mut rng);
+        assert!(matches!(result, Ok(GameResult::Continue)));
+        session
+    }
+
+    #[test]
+    fn test_multiplier() {
+        // 25 cells, 1 mine: 25/24 less 1%
+        assert_eq!(multiplier(25, 1, 0), BASE_MULTIPLIER);
+        assert_eq!(multiplier(25, 1, 1), 10_311);
+
+        // 25 cells, 3 mines, 2 reveals: (25*24)/(22*21) less 1%
+        assert_eq!(multiplier(25, 3, 2), 12_857);
+
+        // 25 cells, 24 mines: 25x less 1%
+        assert_eq!(multiplier(25, 24, 1), 247_500);
+
+        // Capped at 10,000x (and impossible reveals pay nothing)
+        assert_eq!(multiplier(25, 20, 5), MAX_MULTIPLIER);
+        assert_eq!(multiplier(25, 20, 6), 0);
+    }
+
+    #[test]
+    fn test_state_roundtrip() {
+        let state = MinesState {
+            stage: Stage::Playing,
+            side: 5,
+            mines: 3,
+            revealed: 0b1011,
+            mine_mask: 0,
+            multiplier: 12_857,
+        };
+        let blob = state.to_blob();
+        assert_eq!(blob.len(), 19);
+        assert_eq!(MinesState::from_blob(&blob), Some(state.clone()));
+        assert_eq!(MinesState::from_blob(&blob[1..]), None);
+
+        crate::casino::state_blob::fuzz::roundtrip(&[MinesState::new(), state]);
+    }
+
+    #[test]
+    fn test_invalid_setup() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(1, 100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        Mines::init(&mut session, &mut rng);
+
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        let payloads: [&[u8]; 6] = [
+            &[0],
+            &[0, 5],
+            &[0, 1, 1],
+            &[0, 6, 1],
+            &[0, 5, 0],
+            &[0, 5, 25],
+        ];
+        for payload in payloads {
+            let result = Mines::process_move(&mut session, payload, &mut rng);
+            assert!(matches!(result, Err(GameError::InvalidPayload)));
+        }
+
+        // Nothing to reveal or cash out before the grid is picked
+        let result = Mines::process_move(&mut session, &[1, 0], &mut rng);
+        assert!(matches!(result, Err(GameError::InvalidMove)));
+        let result = Mines::process_move(&mut session, &[2], &mut rng);
+        assert!(matches!(result, Err(GameError::InvalidMove)));
+    }
+
+    #[test]
+    fn test_invalid_reveal() {
+        let seed = create_test_seed();
+        let mut session = setup(&seed, 1, 3, 1);
+        let mut rng = GameRng::new(&seed, session.id, 2);
+
+        // Out of the grid
+        let result = Mines::process_move(&mut session, &[1, 9], &mut rng);
+        assert!(matches!(result, Err(GameError::InvalidPayload)));
+
+        // The grid can't be picked twice, and cashing out needs a safe reveal
+        let result = Mines::process_move(&mut session, &[0, 5, 1], &mut rng);
+        assert!(matches!(result, Err(GameError::InvalidMove)));
+        let result = Mines::process_move(&mut session, &[2], &mut rng);
+        assert!(matches!(result, Err(GameError::InvalidMove)));
+
+        // A revealed cell can't be revealed again
+        let mut state = MinesState::from_blob(&session.state_blob).unwrap();
+        state.revealed = 1;
+        session.state_blob = state.to_blob();
+        let result = Mines::process_move(&mut session, &[1, 0], &mut rng);
+        assert!(matches!(result, Err(GameError::InvalidMove)));
+    }
+
+    #[test]
+    fn test_reveal_and_cash_out() {
+        let seed = create_test_seed();
+        let mut cashed_out = false;
+        let mut hit_mine = false;
+
+        for id in 1..50 {
+            let mut session = setup(&seed, id, 5, 3);
+            let mut move_number = 2;
+            let mut cell = 0;
+            let result = loop {
+                let mut rng = GameRng::new(&seed, id, move_number);
+                let payload = if cell < 3 { vec![1, cell] } else { vec![2] };
+                let result = Mines::process_move(&mut session, &payload, &mut rng).unwrap();
+                if !matches!(result, GameResult::Continue) {
+                    break result;
+                }
+                let state = MinesState::from_blob(&session.state_blob).unwrap();
+                assert_eq!(state.revealed_count(), cell + 1);
+                assert_eq!(state.multiplier, multiplier(25, 3, cell + 1));
+                cell += 1;
+                move_number += 1;
+            };
+
+            // The finished board hides exactly the mines picked, none under a safe reveal
+            assert!(session.is_complete);
+            let state = MinesState::from_blob(&session.state_blob).unwrap();
+            assert_eq!(state.stage, Stage::Complete);
+            assert_eq!(state.mine_mask.count_ones(), 3);
+            assert_eq!(state.mine_mask & state.revealed, 0);
+            assert!(state.mine_mask < 1 << 25);
+            match result {
+                GameResult::Win(amount) => {
+                    assert_eq!(state.revealed_count(), 3);
+                    assert_eq!(amount, cash_out(100, multiplier(25, 3, 3)));
+                    cashed_out = true;
+                }
+                GameResult::Loss => {
+                    assert_ne!(state.mine_mask & (1 << cell), 0);
+                    hit_mine = true;
+                }
+                _ => panic!("unexpected result"),
+            }
+        }
+        assert!(cashed_out && hit_mine);
+    }
+
+    #[test]
+    fn test_clearing_the_board() {
+        let seed = create_test_seed();
+
+        // With a single safe cell, surviving the first reveal clears the board
+        for id in 1..50 {
+            let mut session = setup(&seed, id, 2, 3);
+            let mut rng = GameRng::new(&seed, id, 2);
+            let result = Mines::process_move(&mut session, &[1, 0], &mut rng).unwrap();
+            let state = MinesState::from_blob(&session.state_blob).unwrap();
+            assert!(session.is_complete);
+            assert_eq!(state.mine_mask.count_ones(), 3);
+            match result {
+                GameResult::Win(amount) => {
+                    assert_eq!(state.mine_mask, 0b1110);
+                    assert_eq!(amount, 396);
+                }
+                GameResult::Loss => assert_ne!(state.mine_mask & 1, 0),
+                _ => panic!("unexpected result"),
+            }
+        }
+    }
+}
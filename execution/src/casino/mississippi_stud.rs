@@ -0,0 +1,423 @@
+//! Mississippi Stud implementation.
+//!
+//! The player antes (`session.bet`, deducted by CasinoStartGame) and is dealt two cards. Before
+//! each of the three community cards (3rd, 4th and 5th street) is revealed, the player bets 1x-3x
+//! the ante or folds (forfeiting everything wagered so far). The final five-card hand pays every
+//! bet by the pay table:
+//! Royal Flush 500, Straight Flush 100, Quads 40, Full House 10, Flush 6, Straight 4, Trips 3,
+//! Two Pair 2, Jacks or Better 1 (to 1); a pair of 6s through 10s pushes, anything else loses.
+//!
+//! State blob format (9 bytes):
+//! [stage:u8]
+//! [holeCard1:u8] [holeCard2:u8]
+//! [community1:u8] [community2:u8] [community3:u8]   (0xFF if unrevealed)
+//! [thirdBet:u8] [fourthBet:u8] [fifthBet:u8]        (multiple of the ante; 0 = not placed)
+//!
+//! Stages:
+//! 0 = Third (bet before the 1st community card)
+//! 1 = Fourth (bet before the 2nd community card)
+//! 2 = Fifth (bet before the 3rd community card)
+//! 3 = AwaitingReveal (all bets placed; Reveal resolves)
+//! 4 = Complete
+//!
+//! Payload format:
+//! [0, multiple] - Bet 1x-3x the ante (and reveal the next community card)
+//! [1] - Fold
+//! [2] - Reveal (resolve the hand)
+
+use super::poker::{evaluate_five, HandRank, HandValue};
+use super::state_blob::{impl_blob_field_enum, BlobReader, BlobWriter, StateBlob, UNVERSIONED};
+use super::{CasinoGame, GameError, GameResult, GameRng};
+use nullspace_types::casino::GameSession;
+
+const CARD_UNKNOWN: u8 = 0xFF;
+
+/// Largest street bet (as a multiple of the ante).
+const MAX_STREET_MULTIPLE: u8 = 3;
+
+/// Game stages.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Third = 0,
+    Fourth = 1,
+    Fifth = 2,
+    AwaitingReveal = 3,
+    Complete = 4,
+}
+
+impl TryFrom<u8> for Stage {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Stage::Third),
+            1 => Ok(Stage::Fourth),
+            2 => Ok(Stage::Fifth),
+            3 => Ok(Stage::AwaitingReveal),
+            4 => Ok(Stage::Complete),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+impl_blob_field_enum!(Stage);
+
+/// Player moves.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Move {
+    Bet = 0,
+    Fold = 1,
+    Reveal = 2,
+}
+
+impl TryFrom<u8> for Move {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Move::Bet),
+            1 => Ok(Move::Fold),
+            2 => Ok(Move::Reveal),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct MsState {
+    stage: Stage,
+    hole: [u8; 2],
+    community: [u8; 3],
+    street_bets: [u8; 3],
+}
+
+impl MsState {
+    /// Cards dealt so far.
+    fn known_cards(&self) -> Vec<u8> {
+        self.hole
+            .iter()
+            .chain(self.community.iter())
+            .copied()
+            .filter(|&card| card != CARD_UNKNOWN)
+            .collect()
+    }
+
+    /// Total wagered (ante and street bets).
+    fn total_wagered(&self, ante: u64) -> u64 {
+        let multiple: u64 = self.street_bets.iter().map(|&bet| bet as u64).sum();
+        ante.saturating_mul(multiple.saturating_add(1))
+    }
+}
+
+impl StateBlob for MsState {
+    const VERSION: u8 = UNVERSIONED;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.stage)
+            .put(&self.hole)
+            .put(&self.community)
+            .put(&self.street_bets);
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        Some(Self {
+            stage: reader.get()?,
+            hole: reader.get()?,
+            community: reader.get()?,
+            street_bets: reader.get()?,
+        })
+    }
+}
+
+/// Pay table multiplier (to 1) for the final hand: `None` loses and `Some(0)` pushes.
+pub fn pay_multiplier(hand: &HandValue) -> Option<u64> {
+    match hand.rank {
+        HandRank::RoyalFlush | HandRank::FiveAces => Some(500),
+        HandRank::StraightFlush => Some(100),
+        HandRank::FourOfAKind => Some(40),
+        HandRank::FullHouse => Some(10),
+        HandRank::Flush => Some(6),
+        HandRank::Straight => Some(4),
+        HandRank::ThreeOfAKind => Some(3),
+        HandRank::TwoPair => Some(2),
+        HandRank::Pair => match hand.tiebreak[0] {
+            11.. => Some(1),
+            6..=10 => Some(0),
+            _ => None,
+        },
+        HandRank::HighCard => None,
+    }
+}
+
+pub struct MississippiStud;
+
+impl CasinoGame for MississippiStud {
+    fn init(session: &mut GameSession, rng: &mut GameRng) -> GameResult {
+        // Deal the player's two cards (community cards are drawn as they are revealed)
+        let mut deck = rng.create_deck();
+        let state = MsState {
+            stage: Stage::Third,
+            hole: [
+                rng.draw_card(&mut deck).unwrap_or(0),
+                rng.draw_card(&mut deck).unwrap_or(1),
+            ],
+            community: [CARD_UNKNOWN; 3],
+            street_bets: [0; 3],
+        };
+        session.state_blob = state.to_blob();
+        GameResult::Continue
+    }
+
+    fn process_move(
+        session: &mut GameSession,
+        payload: &[u8],
+        rng: &mut GameRng,
+    ) -> Result<GameResult, GameError> {
+        if session.is_complete {
+            return Err(GameError::GameAlreadyComplete);
+        }
+        if payload.is_empty() {
+            return Err(GameError::InvalidPayload);
+        }
+
+        let mv = Move::try_from(payload[0])?;
+        let mut state = MsState::from_blob(&session.state_blob).ok_or(GameError::InvalidPayload)?;
+
+        match (state.stage, mv) {
+            (Stage::Complete, _) => Err(GameError::GameAlreadyComplete),
+            (Stage::Third | Stage::Fourth | Stage::Fifth, Move::Bet) => {
+                if payload.len() != 2 {
+                    return Err(GameError::InvalidPayload);
+                }
+                let multiple = payload[1];
+                if multiple == 0 || multiple > MAX_STREET_MULTIPLE {
+                    return Err(GameError::InvalidPayload);
+                }
+                let bet = session.bet.saturating_mul(multiple as u64);
+                let payout = i64::try_from(bet).map_err(|_| GameError::InvalidMove)?;
+
+                // Place the bet, then reveal the next community card (the last one is revealed
+                // by Reveal once the bet has been charged)
+                let street = state.stage as usize;
+                state.street_bets[street] = multiple;
+                state.stage = match state.stage {
+                    Stage::Third => Stage::Fourth,
+                    Stage::Fourth => Stage::Fifth,
+                    _ => Stage::AwaitingReveal,
+                };
+                if state.stage != Stage::AwaitingReveal {
+                    let mut deck = rng.create_deck_excluding(&state.known_cards());
+                    state.community[street] =
+                        rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
+                }
+                session.state_blob = state.to_blob();
+                Ok(GameResult::ContinueWithUpdate { payout: -payout })
+            }
+            (Stage::Third | Stage::Fourth | Stage::Fifth, Move::Fold) => {
+                if payload.len() != 1 {
+                    return Err(GameError::InvalidPayload);
+                }
+                state.stage = Stage::Complete;
+                session.is_complete = true;
+                session.state_blob = state.to_blob();
+                Ok(GameResult::LossPreDeducted(
+                    state.total_wagered(session.bet),
+                ))
+            }
+            (Stage::AwaitingReveal, Move::Reveal) => {
+                if payload.len() != 1 {
+                    return Err(GameError::InvalidPayload);
+                }
+                let mut deck = rng.create_deck_excluding(&state.known_cards());
+                state.community[2] = rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
+                state.stage = Stage::Complete;
+                session.is_complete = true;
+                session.state_blob = state.to_blob();
+
+                let hand = evaluate_five(&[
+                    state.hole[0],
+                    state.hole[1],
+                    state.community[0],
+                    state.community[1],
+                    state.community[2],
+                ]);
+                let total_wagered = state.total_wagered(session.bet);
+                match pay_multiplier(&hand) {
+                    Some(multiplier) => Ok(GameResult::Win(
+                        total_wagered.saturating_mul(multiplier.saturating_add(1)),
+                    )),
+                    None => Ok(GameResult::LossPreDeducted(total_wagered)),
+                }
+            }
+            _ => Err(GameError::InvalidMove),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::{create_account_keypair, create_network_keypair, create_seed};
+    use nullspace_types::casino::GameType;
+
+    fn create_test_seed() -> nullspace_types::Seed {
+        let (network_secret, _) = create_network_keypair();
+        create_seed(&network_secret, 1)
+    }
+
+    fn create_test_session(bet: u64) -> GameSession {
+        let (_, pk) = create_account_keypair(1);
+        GameSession {
+            id: 1,
+            player: pk,
+            game_type: GameType::MississippiStud,
+            bet,
+            state_blob: vec![],
+            move_count: 0,
+            created_at: 0,
+            is_complete: false,
+            super_mode: nullspace_types::casino::SuperModeState::default(),
+            is_tournament: false,
+            tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
+        }
+    }
+
+    /// Card with `rank` (2-14) and `suit` (0-3).
+    fn card(rank: u8, suit: u8) -> u8 {
+        suit * 13 + if rank == 14 { 0 } else { rank - 1 }
+    }
+
+    fn hand(cards: [(u8, u8); 5]) -> HandValue {
+        evaluate_five(&cards.map(|(rank, suit)| card(rank, suit)))
+    }
+
+    #[test]
+    fn test_pay_multiplier() {
+        let royal = hand([(14, 0), (13, 0), (12, 0), (11, 0), (10, 0)]);
+        assert_eq!(pay_multiplier(&royal), Some(500));
+        let two_pair = hand([(9, 0), (9, 1), (4, 2), (4, 3), (2, 0)]);
+        assert_eq!(pay_multiplier(&two_pair), Some(2));
+        let jacks = hand([(11, 0), (11, 1), (7, 2), (4, 3), (2, 0)]);
+        assert_eq!(pay_multiplier(&jacks), Some(1));
+        let sixes = hand([(6, 0), (6, 1), (14, 2), (4, 3), (2, 0)]);
+        assert_eq!(pay_multiplier(&sixes), Some(0));
+        let tens = hand([(10, 0), (10, 1), (14, 2), (4, 3), (2, 0)]);
+        assert_eq!(pay_multiplier(&tens), Some(0));
+        let fives = hand([(5, 0), (5, 1), (14, 2), (4, 3), (2, 0)]);
+        assert_eq!(pay_multiplier(&fives), None);
+        let high = hand([(14, 0), (13, 1), (7, 2), (4, 3), (2, 0)]);
+        assert_eq!(pay_multiplier(&high), None);
+    }
+
+    #[test]
+    fn test_state_roundtrip() {
+        let state = MsState {
+            stage: Stage::Fifth,
+            hole: [0, 1],
+            community: [2, 3, CARD_UNKNOWN],
+            street_bets: [3, 1, 0],
+        };
+        let blob = state.to_blob();
+        assert_eq!(blob.len(), 9);
+        assert_eq!(MsState::from_blob(&blob), Some(state));
+        assert_eq!(MsState::from_blob(&blob[1..]), None);
+        crate::casino::state_blob::fuzz::roundtrip(&[state]);
+        assert_eq!(state.known_cards(), vec![0, 1, 2, 3]);
+        assert_eq!(state.total_wagered(10), 50);
+    }
+
+    #[test]
+    fn test_game_flow() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(10);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        MississippiStud::init(&mut session, &mut rng);
+        let state = MsState::from_blob(&session.state_blob).unwrap();
+        assert_eq!(state.stage, Stage::Third);
+        assert!(state.hole.iter().all(|&card| card < 52));
+        assert_eq!(state.community, [CARD_UNKNOWN; 3]);
+
+        // Invalid bets and early reveals are rejected
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        for payload in [vec![0], vec![0, 0], vec![0, 4], vec![3]] {
+            assert!(matches!(
+                MississippiStud::process_move(&mut session, &payload, &mut rng),
+                Err(GameError::InvalidPayload)
+            ));
+        }
+        assert!(matches!(
+            MississippiStud::process_move(&mut session, &[2], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+
+        // Each street bet is charged and reveals a community card
+        for (i, multiple) in [3u8, 2, 1].into_iter().enumerate() {
+            let mut rng = GameRng::new(&seed, session.id, i as u32 + 1);
+            let result =
+                MississippiStud::process_move(&mut session, &[0, multiple], &mut rng).unwrap();
+            let expected = -10 * multiple as i64;
+            assert!(matches!(
+                result,
+                GameResult::ContinueWithUpdate { payout } if payout == expected
+            ));
+            let state = MsState::from_blob(&session.state_blob).unwrap();
+            let revealed = state
+                .community
+                .iter()
+                .filter(|&&card| card != CARD_UNKNOWN)
+                .count();
+            assert_eq!(revealed, (i + 1).min(2));
+        }
+        let state = MsState::from_blob(&session.state_blob).unwrap();
+        assert_eq!(state.stage, Stage::AwaitingReveal);
+        assert_eq!(state.street_bets, [3, 2, 1]);
+
+        let mut rng = GameRng::new(&seed, session.id, 4);
+        let result = MississippiStud::process_move(&mut session, &[2], &mut rng).unwrap();
+        let state = MsState::from_blob(&session.state_blob).unwrap();
+        let mut cards = state.known_cards();
+        assert_eq!(cards.len(), 5);
+        cards.sort_unstable();
+        cards.dedup();
+        assert_eq!(cards.len(), 5);
+
+        let final_hand = evaluate_five(&[
+            state.hole[0],
+            state.hole[1],
+            state.community[0],
+            state.community[1],
+            state.community[2],
+        ]);
+        match pay_multiplier(&final_hand) {
+            Some(multiplier) => {
+                assert!(matches!(result, GameResult::Win(x) if x == 70 * (multiplier + 1)))
+            }
+            None => assert!(matches!(result, GameResult::LossPreDeducted(70))),
+        }
+        assert!(session.is_complete);
+    }
+
+    #[test]
+    fn test_fold() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(10);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        MississippiStud::init(&mut session, &mut rng);
+
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        MississippiStud::process_move(&mut session, &[0, 2], &mut rng).unwrap();
+        let result = MississippiStud::process_move(&mut session, &[1], &mut rng).unwrap();
+        assert!(matches!(result, GameResult::LossPreDeducted(30)));
+        assert!(session.is_complete);
+        assert!(matches!(
+            MississippiStud::process_move(&mut session, &[1], &mut rng),
+            Err(GameError::GameAlreadyComplete)
+        ));
+    }
+}
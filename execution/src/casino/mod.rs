@@ -6,31 +6,52 @@
 //! - Baccarat
 //! - Video Poker
 //! - Three Card Poker
+//! - Four Card Poker
 //! - Ultimate Texas Hold'em
 //! - Roulette
 //! - Sic Bo
 //! - Craps
 //! - Casino War
+//! - Slots
+//! - Mines
+//! - Pai Gow Poker
+//! - Caribbean Stud Poker
+//! - Mississippi Stud
+//! - Money Wheel
 
 pub mod baccarat;
 pub mod blackjack;
+pub mod caribbean_stud;
 pub mod casino_war;
 pub mod craps;
+pub mod four_card;
 pub mod hilo;
 #[cfg(test)]
 mod integration_tests;
+pub mod mines;
+pub mod mississippi_stud;
+pub mod money_wheel;
+pub mod pai_gow;
+pub mod poker;
 pub mod roulette;
+pub mod shoe;
 pub mod sic_bo;
+pub mod side_bet;
+pub mod slots;
+pub mod state_blob;
 pub mod super_mode;
 pub mod three_card;
 pub mod ultimate_holdem;
 pub mod video_poker;
 
 use commonware_codec::Encode;
-use commonware_cryptography::sha256::Sha256;
+use commonware_cryptography::sha256::{Digest, Sha256};
 use commonware_cryptography::Hasher;
-use nullspace_types::casino::{GameSession, GameType, Player};
+use nullspace_types::casino::{
+    GameConfig, GameSession, GameType, Player, ResolvedBet, RngDraw, Seat, MAX_AUTO_PLAY_ROUNDS,
+};
 use nullspace_types::Seed;
+use side_bet::SideBet;
 
 /// Deterministic random number generator seeded from consensus.
 ///
@@ -40,6 +61,8 @@ use nullspace_types::Seed;
 pub struct GameRng {
     state: [u8; 32],
     index: usize,
+    /// Outcomes drawn so far (if recording).
+    transcript: Option<Vec<RngDraw>>,
 }
 
 impl GameRng {
@@ -52,6 +75,54 @@ impl GameRng {
         Self {
             state: hasher.finalize().0,
             index: 0,
+            transcript: None,
+        }
+    }
+
+    /// Create a new RNG that also mixes in entropy revealed by the player (so neither the
+    /// network nor the player alone chooses the outcome).
+    pub fn with_entropy(seed: &Seed, session_id: u64, move_number: u32, entropy: &Digest) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(seed.encode().as_ref());
+        hasher.update(&session_id.to_be_bytes());
+        hasher.update(&move_number.to_be_bytes());
+        hasher.update(entropy.as_ref());
+        Self {
+            state: hasher.finalize().0,
+            index: 0,
+            transcript: None,
+        }
+    }
+
+    /// Record every outcome drawn from now on (cards dealt, dice rolled, wheels spun and other
+    /// values games draw directly).
+    pub fn record_transcript(&mut self) {
+        self.transcript.get_or_insert_with(Vec::new);
+    }
+
+    /// Record an outcome a game drew without a recorded draw (e.g. cards dealt from the top of
+    /// a deck it shuffled).
+    pub fn record(&mut self, draw: RngDraw) {
+        if let Some(transcript) = &mut self.transcript {
+            transcript.push(draw);
+        }
+    }
+
+    /// Take the outcomes recorded so far (`None` if not recording).
+    pub fn take_transcript(&mut self) -> Option<Vec<RngDraw>> {
+        self.transcript.take()
+    }
+
+    /// An RNG for one of the rounds a move plays, keyed by the round (recording its outcomes if
+    /// this RNG records them).
+    pub fn for_round(&self, round: u8) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(&self.state);
+        hasher.update(&[round]);
+        Self {
+            state: hasher.finalize().0,
+            index: 0,
+            transcript: self.transcript.as_ref().map(|_| Vec::new()),
         }
     }
 
@@ -95,8 +166,15 @@ impl GameRng {
         f32::from(self.next_u8()) / 256.0
     }
 
-    /// Get a random value in range [0, max).
+    /// Get a random value in range [0, max) (recorded as a drawn value).
     pub fn next_bounded(&mut self, max: u8) -> u8 {
+        let value = self.bounded(max);
+        self.record(RngDraw::Value(value));
+        value
+    }
+
+    /// Get a random value in range [0, max) without recording it.
+    fn bounded(&mut self, max: u8) -> u8 {
         if max == 0 {
             return 0;
         }
@@ -130,7 +208,7 @@ impl GameRng {
             return 0;
         }
         if max <= u8::MAX as usize {
-            return self.next_bounded(max as u8) as usize;
+            return self.bounded(max as u8) as usize;
         }
         if max <= u32::MAX as usize {
             return self.next_bounded_u32(max as u32) as usize;
@@ -146,11 +224,13 @@ impl GameRng {
             return None;
         }
         let idx = if deck.len() <= u8::MAX as usize {
-            self.next_bounded(deck.len() as u8) as usize
+            self.bounded(deck.len() as u8) as usize
         } else {
             self.next_bounded_usize(deck.len())
         };
-        Some(deck.swap_remove(idx))
+        let card = deck.swap_remove(idx);
+        self.record(RngDraw::Card(card));
+        Some(card)
     }
 
     /// Create a shuffled deck of 52 cards.
@@ -177,7 +257,7 @@ impl GameRng {
     pub fn shuffle<T>(&mut self, slice: &mut [T]) {
         if slice.len() <= u8::MAX as usize {
             for i in (1..slice.len()).rev() {
-                let j = self.next_bounded((i + 1) as u8) as usize;
+                let j = self.bounded((i + 1) as u8) as usize;
                 slice.swap(i, j);
             }
             return;
@@ -191,7 +271,9 @@ impl GameRng {
 
     /// Roll a single die (1-6).
     pub fn roll_die(&mut self) -> u8 {
-        self.next_bounded(6) + 1
+        let die = self.bounded(6) + 1;
+        self.record(RngDraw::Die(die));
+        die
     }
 
     /// Roll multiple dice.
@@ -201,7 +283,16 @@ impl GameRng {
 
     /// Spin roulette wheel (0-36).
     pub fn spin_roulette(&mut self) -> u8 {
-        self.next_bounded(37)
+        let pocket = self.bounded(37);
+        self.record(RngDraw::Wheel(pocket));
+        pocket
+    }
+
+    /// Spin a double-zero roulette wheel (0-37, with 37 standing for 00).
+    pub fn spin_american_roulette(&mut self) -> u8 {
+        let pocket = self.bounded(38);
+        self.record(RngDraw::Wheel(pocket));
+        pocket
     }
 
     /// Create a shuffled deck excluding specific cards.
@@ -313,6 +404,21 @@ pub enum GameResult {
     Push,
 }
 
+/// Something a move did at the table, reported alongside the session's new state so spectators
+/// can follow the game without decoding its state blob.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TableEvent {
+    CardDealt {
+        card: u8,
+        to: Seat,
+    },
+    CrapsRolled {
+        d1: u8,
+        d2: u8,
+        resolved_bets: Vec<ResolvedBet>,
+    },
+}
+
 /// Error during game execution.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum GameError {
@@ -341,6 +447,26 @@ pub trait CasinoGame {
         payload: &[u8],
         rng: &mut GameRng,
     ) -> Result<GameResult, GameError>;
+
+    /// Number of bets held by the session (which a move may have to resolve).
+    fn bet_count(_session: &GameSession) -> usize {
+        1
+    }
+
+    /// Record the table rules in the state of a newly initialized session (games without
+    /// configurable rules ignore them).
+    fn configure(_session: &mut GameSession, _config: &GameConfig) {}
+
+    /// Side bets held by the session (settled by the layer as the session completes).
+    fn side_bets(_session: &GameSession) -> Vec<SideBet> {
+        Vec::new()
+    }
+
+    /// What a move (`payload`, which took the session from the `before` state blob to its
+    /// current state) did at the table, in the order it happened.
+    fn table_events(_before: &[u8], _session: &GameSession, _payload: &[u8]) -> Vec<TableEvent> {
+        Vec::new()
+    }
 }
 
 /// Dispatch game initialization to the appropriate game module.
@@ -348,17 +474,38 @@ pub fn init_game(session: &mut GameSession, rng: &mut GameRng) -> GameResult {
     match session.game_type {
         GameType::Baccarat => baccarat::Baccarat::init(session, rng),
         GameType::Blackjack => blackjack::Blackjack::init(session, rng),
+        GameType::CaribbeanStud => caribbean_stud::CaribbeanStud::init(session, rng),
         GameType::CasinoWar => casino_war::CasinoWar::init(session, rng),
         GameType::Craps => craps::Craps::init(session, rng),
+        GameType::FourCard => four_card::FourCardPoker::init(session, rng),
         GameType::HiLo => hilo::HiLo::init(session, rng),
+        GameType::Mines => mines::Mines::init(session, rng),
+        GameType::MississippiStud => mississippi_stud::MississippiStud::init(session, rng),
+        GameType::MoneyWheel => money_wheel::MoneyWheel::init(session, rng),
+        GameType::PaiGow => pai_gow::PaiGow::init(session, rng),
         GameType::Roulette => roulette::Roulette::init(session, rng),
         GameType::SicBo => sic_bo::SicBo::init(session, rng),
+        GameType::Slots => slots::Slots::init(session, rng),
         GameType::ThreeCard => three_card::ThreeCardPoker::init(session, rng),
         GameType::UltimateHoldem => ultimate_holdem::UltimateHoldem::init(session, rng),
         GameType::VideoPoker => video_poker::VideoPoker::init(session, rng),
     }
 }
 
+/// Dispatch recording the table rules to the appropriate game module.
+pub fn configure_game(session: &mut GameSession, config: &GameConfig) {
+    match session.game_type {
+        GameType::Baccarat => baccarat::Baccarat::configure(session, config),
+        GameType::Blackjack => blackjack::Blackjack::configure(session, config),
+        GameType::CasinoWar => casino_war::CasinoWar::configure(session, config),
+        GameType::Craps => craps::Craps::configure(session, config),
+        GameType::Roulette => roulette::Roulette::configure(session, config),
+        GameType::SicBo => sic_bo::SicBo::configure(session, config),
+        GameType::VideoPoker => video_poker::VideoPoker::configure(session, config),
+        _ => {}
+    }
+}
+
 /// Dispatch game move processing to the appropriate game module.
 pub fn process_game_move(
     session: &mut GameSession,
@@ -368,11 +515,22 @@ pub fn process_game_move(
     match session.game_type {
         GameType::Baccarat => baccarat::Baccarat::process_move(session, payload, rng),
         GameType::Blackjack => blackjack::Blackjack::process_move(session, payload, rng),
+        GameType::CaribbeanStud => {
+            caribbean_stud::CaribbeanStud::process_move(session, payload, rng)
+        }
         GameType::CasinoWar => casino_war::CasinoWar::process_move(session, payload, rng),
         GameType::Craps => craps::Craps::process_move(session, payload, rng),
+        GameType::FourCard => four_card::FourCardPoker::process_move(session, payload, rng),
         GameType::HiLo => hilo::HiLo::process_move(session, payload, rng),
+        GameType::Mines => mines::Mines::process_move(session, payload, rng),
+        GameType::MississippiStud => {
+            mississippi_stud::MississippiStud::process_move(session, payload, rng)
+        }
+        GameType::MoneyWheel => money_wheel::MoneyWheel::process_move(session, payload, rng),
+        GameType::PaiGow => pai_gow::PaiGow::process_move(session, payload, rng),
         GameType::Roulette => roulette::Roulette::process_move(session, payload, rng),
         GameType::SicBo => sic_bo::SicBo::process_move(session, payload, rng),
+        GameType::Slots => slots::Slots::process_move(session, payload, rng),
         GameType::ThreeCard => three_card::ThreeCardPoker::process_move(session, payload, rng),
         GameType::UltimateHoldem => {
             ultimate_holdem::UltimateHoldem::process_move(session, payload, rng)
@@ -381,6 +539,72 @@ pub fn process_game_move(
     }
 }
 
+/// Dispatch bet counting to the appropriate game module.
+pub fn bet_count(session: &GameSession) -> usize {
+    match session.game_type {
+        GameType::Baccarat => baccarat::Baccarat::bet_count(session),
+        GameType::Blackjack => blackjack::Blackjack::bet_count(session),
+        GameType::CaribbeanStud => caribbean_stud::CaribbeanStud::bet_count(session),
+        GameType::CasinoWar => casino_war::CasinoWar::bet_count(session),
+        GameType::Craps => craps::Craps::bet_count(session),
+        GameType::FourCard => four_card::FourCardPoker::bet_count(session),
+        GameType::HiLo => hilo::HiLo::bet_count(session),
+        GameType::Mines => mines::Mines::bet_count(session),
+        GameType::MississippiStud => mississippi_stud::MississippiStud::bet_count(session),
+        GameType::MoneyWheel => money_wheel::MoneyWheel::bet_count(session),
+        GameType::PaiGow => pai_gow::PaiGow::bet_count(session),
+        GameType::Roulette => roulette::Roulette::bet_count(session),
+        GameType::SicBo => sic_bo::SicBo::bet_count(session),
+        GameType::Slots => slots::Slots::bet_count(session),
+        GameType::ThreeCard => three_card::ThreeCardPoker::bet_count(session),
+        GameType::UltimateHoldem => ultimate_holdem::UltimateHoldem::bet_count(session),
+        GameType::VideoPoker => video_poker::VideoPoker::bet_count(session),
+    }
+}
+
+/// Dispatch side bet reporting to the games that offer side bets.
+pub fn side_bets(session: &GameSession) -> Vec<SideBet> {
+    match session.game_type {
+        GameType::CaribbeanStud => caribbean_stud::CaribbeanStud::side_bets(session),
+        GameType::ThreeCard => three_card::ThreeCardPoker::side_bets(session),
+        GameType::UltimateHoldem => ultimate_holdem::UltimateHoldem::side_bets(session),
+        _ => Vec::new(),
+    }
+}
+
+/// Dispatch table event reporting to the games that report them.
+pub fn table_events(before: &[u8], session: &GameSession, payload: &[u8]) -> Vec<TableEvent> {
+    match session.game_type {
+        GameType::Baccarat => baccarat::Baccarat::table_events(before, session, payload),
+        GameType::Blackjack => blackjack::Blackjack::table_events(before, session, payload),
+        GameType::Craps => craps::Craps::table_events(before, session, payload),
+        _ => Vec::new(),
+    }
+}
+
+/// Chips returned when an expired session is abandoned.
+///
+/// Table games (and slots, mines and the money wheel) don't reveal anything until a move is made, so they are
+/// refunded if abandoned before the first move. Otherwise, the wager is forfeited (as when walking away
+/// mid-hand).
+pub fn abandon_refund(session: &GameSession) -> u64 {
+    let is_table_game = matches!(
+        session.game_type,
+        GameType::Baccarat
+            | GameType::Craps
+            | GameType::Roulette
+            | GameType::SicBo
+            | GameType::Slots
+            | GameType::Mines
+            | GameType::MoneyWheel
+    );
+    if is_table_game && session.move_count == 0 {
+        session.bet
+    } else {
+        0
+    }
+}
+
 /// Apply modifiers (shield/double) to a game outcome.
 pub fn apply_modifiers(player: &mut Player, payout: i64) -> (i64, bool, bool) {
     let mut final_payout = payout;
@@ -410,9 +634,46 @@ pub fn apply_modifiers(player: &mut Player, payout: i64) -> (i64, bool, bool) {
     (final_payout, was_shielded, was_doubled)
 }
 
-/// Calculate super mode fee (20% of bet)
-pub fn get_super_mode_fee(bet: u64) -> u64 {
-    bet / 5 // 20%
+/// Play `rounds` identical rounds in a single move (auto-play), each drawing from its own RNG
+/// (see [GameRng::for_round]), and settle them together.
+///
+/// `play_round` plays one round and returns what it returned (stake plus winnings, after any
+/// super multiplier). `stake` is what a round wagers: the first round's was charged as its bets
+/// were placed (or the session started), and the other rounds' are deducted with the result.
+pub fn auto_play(
+    rng: &mut GameRng,
+    rounds: u8,
+    stake: u64,
+    mut play_round: impl FnMut(&mut GameRng) -> u64,
+) -> Result<GameResult, GameError> {
+    if rounds == 0 || rounds > MAX_AUTO_PLAY_ROUNDS {
+        return Err(GameError::InvalidPayload);
+    }
+    let extra_deduction = stake
+        .checked_mul(u64::from(rounds - 1))
+        .ok_or(GameError::InvalidPayload)?;
+
+    let mut total_return: u64 = 0;
+    for round in 0..rounds {
+        let mut round_rng = rng.for_round(round);
+        total_return = total_return.saturating_add(play_round(&mut round_rng));
+        if let (Some(transcript), Some(draws)) = (&mut rng.transcript, round_rng.take_transcript())
+        {
+            transcript.extend(draws);
+        }
+    }
+
+    Ok(if total_return > 0 {
+        GameResult::WinWithExtraDeduction {
+            payout: total_return,
+            extra_deduction,
+        }
+    } else {
+        GameResult::LossPreDeductedWithExtraDeduction {
+            total_loss: stake.saturating_add(extra_deduction),
+            extra_deduction,
+        }
+    })
 }
 
 /// Generate super mode multipliers for a game type
@@ -430,7 +691,13 @@ pub fn generate_super_multipliers(
         GameType::ThreeCard => super_mode::generate_three_card_multipliers(rng),
         GameType::UltimateHoldem => super_mode::generate_uth_multipliers(rng),
         GameType::CasinoWar => super_mode::generate_casino_war_multipliers(rng),
-        GameType::HiLo => Vec::new(), // HiLo uses streak-based system
+        GameType::Slots => super_mode::generate_slots_multipliers(rng),
+        GameType::MoneyWheel => super_mode::generate_money_wheel_multipliers(rng),
+        GameType::HiLo => Vec::new(),   // HiLo uses streak-based system
+        GameType::Mines => Vec::new(),  // Mines pays through its own multiplier
+        GameType::PaiGow => Vec::new(), // Pai Gow pays even money (less commission)
+        GameType::CaribbeanStud | GameType::MississippiStud => Vec::new(), // Stud games have none
+        GameType::FourCard => Vec::new(), // Four Card Poker pays from its own tables
     }
 }
 
@@ -470,6 +737,23 @@ mod tests {
         assert_ne!(seq1, seq2);
     }
 
+    #[test]
+    fn test_game_rng_entropy() {
+        let seed = create_test_seed();
+        let entropy = Sha256::hash(b"player");
+
+        let mut rng1 = GameRng::new(&seed, 1, 1);
+        let mut rng2 = GameRng::with_entropy(&seed, 1, 1, &entropy);
+        let mut rng3 = GameRng::with_entropy(&seed, 1, 1, &entropy);
+
+        // Player entropy changes the sequence (deterministically)
+        let seq1: Vec<u8> = (0..10).map(|_| rng1.next_u8()).collect();
+        let seq2: Vec<u8> = (0..10).map(|_| rng2.next_u8()).collect();
+        let seq3: Vec<u8> = (0..10).map(|_| rng3.next_u8()).collect();
+        assert_ne!(seq1, seq2);
+        assert_eq!(seq2, seq3);
+    }
+
     #[test]
     fn test_game_rng_bounded() {
         let seed = create_test_seed();
@@ -528,6 +812,102 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_game_rng_transcript() {
+        let seed = create_test_seed();
+        let draw = |rng: &mut GameRng| {
+            let mut deck = rng.create_deck();
+            let card = rng.draw_card(&mut deck).unwrap();
+            (
+                card,
+                rng.roll_die(),
+                rng.spin_roulette(),
+                rng.next_bounded(20),
+            )
+        };
+
+        // Recording doesn't change what is drawn
+        let mut rng = GameRng::new(&seed, 1, 0);
+        let expected = draw(&mut rng);
+        assert!(rng.take_transcript().is_none());
+        let mut rng = GameRng::new(&seed, 1, 0);
+        rng.record_transcript();
+        let (card, die, pocket, value) = draw(&mut rng);
+        assert_eq!((card, die, pocket, value), expected);
+
+        // Only the outcomes are recorded (not the shuffle), in the order they were drawn
+        assert_eq!(
+            rng.take_transcript().unwrap(),
+            vec![
+                RngDraw::Card(card),
+                RngDraw::Die(die),
+                RngDraw::Wheel(pocket),
+                RngDraw::Value(value)
+            ]
+        );
+        assert!(rng.take_transcript().is_none());
+    }
+
+    #[test]
+    fn test_auto_play() {
+        let seed = create_test_seed();
+
+        // Each round draws from its own RNG, and the move records every round's outcomes
+        let mut rng = GameRng::new(&seed, 1, 0);
+        rng.record_transcript();
+        let mut dice = Vec::new();
+        let result = auto_play(&mut rng, 3, 10, |rng| {
+            let die = rng.roll_die();
+            dice.push(die);
+            u64::from(die == 6) * 60
+        })
+        .unwrap();
+        let expected: Vec<u8> = (0..3)
+            .map(|round| GameRng::new(&seed, 1, 0).for_round(round).roll_die())
+            .collect();
+        assert_eq!(dice, expected);
+        assert_eq!(
+            rng.take_transcript().unwrap(),
+            dice.iter()
+                .map(|&die| RngDraw::Die(die))
+                .collect::<Vec<_>>()
+        );
+
+        // The first round's stake was already charged, the others are deducted with the result
+        let sixes = dice.iter().filter(|&&die| die == 6).count() as u64;
+        if sixes > 0 {
+            assert!(matches!(
+                result,
+                GameResult::WinWithExtraDeduction { payout, extra_deduction: 20 }
+                    if payout == sixes * 60
+            ));
+        } else {
+            assert!(matches!(
+                result,
+                GameResult::LossPreDeductedWithExtraDeduction {
+                    total_loss: 30,
+                    extra_deduction: 20
+                }
+            ));
+        }
+        assert!(matches!(
+            auto_play(&mut rng, 2, 10, |_| 0),
+            Ok(GameResult::LossPreDeductedWithExtraDeduction {
+                total_loss: 20,
+                extra_deduction: 10
+            })
+        ));
+
+        // Between one and the most rounds a move may play
+        for rounds in [0, MAX_AUTO_PLAY_ROUNDS + 1] {
+            assert!(matches!(
+                auto_play(&mut rng, rounds, 10, |_| 0),
+                Err(GameError::InvalidPayload)
+            ));
+        }
+        assert!(auto_play(&mut rng, MAX_AUTO_PLAY_ROUNDS, 10, |_| 0).is_ok());
+    }
+
     #[test]
     fn test_game_rng_roulette() {
         let seed = create_test_seed();
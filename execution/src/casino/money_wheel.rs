@@ -0,0 +1,483 @@
+//! Big Six (Money Wheel) implementation with multi-bet support.
+//!
+//! The wheel has 54 segments (`SEGMENTS`): 24 × $1, 15 × $2, 7 × $5, 4 × $10, 2 × $20, one
+//! Joker and one Logo. A bet on a symbol pays its face value to 1 (Joker and Logo pay 40:1).
+//!
+//! State blob format:
+//! [bet_count:u8] [bets:MoneyWheelBet×count] [segment:u8]?
+//!
+//! Each MoneyWheelBet (9 bytes):
+//! [bet_type:u8] [amount:u64 BE]
+//!
+//! Payload format:
+//! [0, bet_type, amount_bytes...] - Place bet (adds to any bet already on that symbol)
+//! [1] - Spin the wheel and resolve all bets
+//! [2] - Clear all bets (refunding them)
+//!
+//! Bet types:
+//! 0 = $1 (1:1)
+//! 1 = $2 (2:1)
+//! 2 = $5 (5:1)
+//! 3 = $10 (10:1)
+//! 4 = $20 (20:1)
+//! 5 = Joker (40:1)
+//! 6 = Logo (40:1)
+
+use super::state_blob::{
+    impl_blob_field_enum, BlobField, BlobReader, BlobWriter, StateBlob, UNVERSIONED,
+};
+use super::super_mode::apply_super_multiplier_number;
+use super::{CasinoGame, GameError, GameResult, GameRng};
+use nullspace_types::casino::GameSession;
+
+/// Number of wheel segments.
+pub const SEGMENT_COUNT: usize = 54;
+
+/// Number of distinct wheel symbols (one bet type each).
+const SYMBOL_COUNT: usize = 7;
+
+/// Symbol (bet type) of each wheel segment, in wheel order.
+pub const SEGMENTS: [u8; SEGMENT_COUNT] = [
+    5, 0, 1, 0, 2, 1, 0, 3, 0, 1, 0, 0, 2, 1, 4, 0, 1, 0, 0, 2, 3, 1, 0, 1, 0, 0, 1, 6, 2, 0, 0, 1,
+    0, 1, 3, 2, 0, 0, 1, 0, 4, 1, 2, 0, 0, 1, 0, 3, 0, 1, 2, 0, 1, 0,
+];
+
+/// Money Wheel bet types (one per wheel symbol).
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BetType {
+    One = 0,    // $1 (1:1)
+    Two = 1,    // $2 (2:1)
+    Five = 2,   // $5 (5:1)
+    Ten = 3,    // $10 (10:1)
+    Twenty = 4, // $20 (20:1)
+    Joker = 5,  // Joker (40:1)
+    Logo = 6,   // Logo (40:1)
+}
+
+impl TryFrom<u8> for BetType {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(BetType::One),
+            1 => Ok(BetType::Two),
+            2 => Ok(BetType::Five),
+            3 => Ok(BetType::Ten),
+            4 => Ok(BetType::Twenty),
+            5 => Ok(BetType::Joker),
+            6 => Ok(BetType::Logo),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+impl BetType {
+    /// Payout (to 1) when the wheel stops on this symbol.
+    pub fn payout(self) -> u64 {
+        match self {
+            BetType::One => 1,
+            BetType::Two => 2,
+            BetType::Five => 5,
+            BetType::Ten => 10,
+            BetType::Twenty => 20,
+            BetType::Joker | BetType::Logo => 40,
+        }
+    }
+}
+
+/// A single bet in Money Wheel (9 bytes).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MoneyWheelBet {
+    pub bet_type: BetType,
+    pub amount: u64,
+}
+
+impl BlobField for MoneyWheelBet {
+    fn write(&self, writer: &mut BlobWriter) {
+        writer.put(&self.bet_type).put(&self.amount);
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        Some(Self {
+            bet_type: reader.get()?,
+            amount: reader.get()?,
+        })
+    }
+}
+
+/// Money Wheel game state.
+#[derive(Debug, PartialEq, Eq)]
+struct MoneyWheelState {
+    bets: Vec<MoneyWheelBet>,
+    segment: Option<u8>,
+}
+
+impl MoneyWheelState {
+    fn new() -> Self {
+        Self {
+            bets: Vec::new(),
+            segment: None,
+        }
+    }
+}
+
+impl StateBlob for MoneyWheelState {
+    const VERSION: u8 = UNVERSIONED;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer.put_list(&self.bets);
+        if let Some(segment) = &self.segment {
+            writer.put(segment);
+        }
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        if reader.remaining() == 0 {
+            return Some(Self::new());
+        }
+
+        // Bets on the same symbol are merged, so there is at most one bet per symbol
+        let bets = reader.get_list(SYMBOL_COUNT)?;
+
+        // Optional spin result
+        let segment = if reader.remaining() > 0 {
+            Some(
+                reader
+                    .get::<u8>()
+                    .filter(|&segment| (segment as usize) < SEGMENT_COUNT)?,
+            )
+        } else {
+            None
+        };
+
+        Some(Self { bets, segment })
+    }
+}
+
+impl_blob_field_enum!(BetType);
+
+/// Return (stake plus winnings) of a bet when the wheel stops on `segment`.
+fn calculate_bet_return(bet: &MoneyWheelBet, segment: u8) -> u64 {
+    if SEGMENTS[segment as usize] != bet.bet_type as u8 {
+        return 0;
+    }
+    bet.amount
+        .saturating_mul(bet.bet_type.payout().saturating_add(1))
+}
+
+pub struct MoneyWheel;
+
+impl CasinoGame for MoneyWheel {
+    fn bet_count(session: &GameSession) -> usize {
+        MoneyWheelState::from_blob(&session.state_blob).map_or(0, |state| state.bets.len())
+    }
+
+    fn init(session: &mut GameSession, _rng: &mut GameRng) -> GameResult {
+        let state = MoneyWheelState::new();
+        session.state_blob = state.to_blob();
+        GameResult::Continue
+    }
+
+    fn process_move(
+        session: &mut GameSession,
+        payload: &[u8],
+        rng: &mut GameRng,
+    ) -> Result<GameResult, GameError> {
+        if session.is_complete {
+            return Err(GameError::GameAlreadyComplete);
+        }
+
+        if payload.is_empty() {
+            return Err(GameError::InvalidPayload);
+        }
+
+        let mut state =
+            MoneyWheelState::from_blob(&session.state_blob).ok_or(GameError::InvalidState)?;
+
+        match payload[0] {
+            // [0, bet_type, amount] - Place bet
+            0 => {
+                if payload.len() != 10 {
+                    return Err(GameError::InvalidPayload);
+                }
+                let bet_type = BetType::try_from(payload[1])?;
+                let amount = u64::from_be_bytes(
+                    payload[2..10]
+                        .try_into()
+                        .map_err(|_| GameError::InvalidPayload)?,
+                );
+                if amount == 0 {
+                    return Err(GameError::InvalidPayload);
+                }
+                let payout = i64::try_from(amount).map_err(|_| GameError::InvalidPayload)?;
+
+                match state.bets.iter_mut().find(|bet| bet.bet_type == bet_type) {
+                    Some(bet) => bet.amount = bet.amount.saturating_add(amount),
+                    None => state.bets.push(MoneyWheelBet { bet_type, amount }),
+                }
+                session.state_blob = state.to_blob();
+                Ok(GameResult::ContinueWithUpdate { payout: -payout })
+            }
+
+            // [1] - Spin and resolve all bets
+            1 => {
+                if state.bets.is_empty() {
+                    return Err(GameError::InvalidPayload); // Must have at least one bet
+                }
+
+                let segment = rng.next_bounded(SEGMENT_COUNT as u8);
+                state.segment = Some(segment);
+
+                let total_bet: u64 = state.bets.iter().map(|bet| bet.amount).sum();
+                let total_return: u64 = state
+                    .bets
+                    .iter()
+                    .map(|bet| calculate_bet_return(bet, segment))
+                    .sum();
+
+                session.state_blob = state.to_blob();
+                session.is_complete = true;
+
+                if total_return > 0 {
+                    // Super mode boosts winnings when the wheel stops on a supercharged segment
+                    let final_return = if session.super_mode.is_active {
+                        apply_super_multiplier_number(
+                            segment,
+                            &session.super_mode.multipliers,
+                            total_return,
+                        )
+                    } else {
+                        total_return
+                    };
+                    Ok(GameResult::Win(final_return))
+                } else {
+                    Ok(GameResult::LossPreDeducted(total_bet))
+                }
+            }
+
+            // [2] - Clear all bets
+            2 => {
+                if payload.len() != 1 {
+                    return Err(GameError::InvalidPayload);
+                }
+                let total_bet: u64 = state.bets.iter().map(|bet| bet.amount).sum();
+                state.bets.clear();
+                session.state_blob = state.to_blob();
+                if total_bet == 0 {
+                    return Ok(GameResult::Continue);
+                }
+                let payout = i64::try_from(total_bet).map_err(|_| GameError::InvalidState)?;
+                Ok(GameResult::ContinueWithUpdate { payout })
+            }
+
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::{create_account_keypair, create_network_keypair, create_seed};
+    use nullspace_types::casino::{GameType, SuperModeState, SuperMultiplier, SuperType};
+
+    fn create_test_seed() -> nullspace_types::Seed {
+        let (network_secret, _) = create_network_keypair();
+        create_seed(&network_secret, 1)
+    }
+
+    fn create_test_session(bet: u64) -> GameSession {
+        let (_, pk) = create_account_keypair(1);
+        GameSession {
+            id: 1,
+            player: pk,
+            game_type: GameType::MoneyWheel,
+            bet,
+            state_blob: vec![],
+            move_count: 0,
+            created_at: 0,
+            is_complete: false,
+            super_mode: SuperModeState::default(),
+            is_tournament: false,
+            tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
+        }
+    }
+
+    fn place_bet_payload(bet_type: u8, amount: u64) -> Vec<u8> {
+        let mut payload = vec![0, bet_type];
+        payload.extend_from_slice(&amount.to_be_bytes());
+        payload
+    }
+
+    #[test]
+    fn test_segments() {
+        let mut counts = [0usize; SYMBOL_COUNT];
+        for &symbol in &SEGMENTS {
+            counts[symbol as usize] += 1;
+        }
+        assert_eq!(counts, [24, 15, 7, 4, 2, 1, 1]);
+
+        // Every bet keeps a house edge
+        for (symbol, &count) in counts.iter().enumerate() {
+            let bet_type = BetType::try_from(symbol as u8).unwrap();
+            let return_per_spin = count as u64 * (bet_type.payout() + 1);
+            assert!(return_per_spin < SEGMENT_COUNT as u64);
+        }
+    }
+
+    #[test]
+    fn test_bet_return() {
+        let bet = MoneyWheelBet {
+            bet_type: BetType::Five,
+            amount: 10,
+        };
+        assert_eq!(calculate_bet_return(&bet, 4), 60);
+        assert_eq!(calculate_bet_return(&bet, 0), 0);
+        let joker = MoneyWheelBet {
+            bet_type: BetType::Joker,
+            amount: 10,
+        };
+        assert_eq!(calculate_bet_return(&joker, 0), 410);
+    }
+
+    #[test]
+    fn test_state_roundtrip() {
+        let state = MoneyWheelState {
+            bets: vec![
+                MoneyWheelBet {
+                    bet_type: BetType::One,
+                    amount: 5,
+                },
+                MoneyWheelBet {
+                    bet_type: BetType::Logo,
+                    amount: 1,
+                },
+            ],
+            segment: Some(27),
+        };
+        let bytes = state.to_blob();
+        assert_eq!(bytes.len(), 1 + 2 * 9 + 1);
+        let decoded = MoneyWheelState::from_blob(&bytes).unwrap();
+        assert_eq!(decoded.bets, state.bets);
+        assert_eq!(decoded.segment, Some(27));
+
+        // Out-of-range segments and truncated bets are rejected
+        let mut bad = bytes.clone();
+        *bad.last_mut().unwrap() = SEGMENT_COUNT as u8;
+        assert!(MoneyWheelState::from_blob(&bad).is_none());
+        assert!(MoneyWheelState::from_blob(&bytes[..10]).is_none());
+
+        crate::casino::state_blob::fuzz::roundtrip(&[MoneyWheelState::new(), state]);
+    }
+
+    #[test]
+    fn test_place_and_clear_bets() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(0);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        MoneyWheel::init(&mut session, &mut rng);
+
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        assert!(matches!(
+            MoneyWheel::process_move(&mut session, &place_bet_payload(7, 10), &mut rng),
+            Err(GameError::InvalidPayload)
+        ));
+        assert!(matches!(
+            MoneyWheel::process_move(&mut session, &place_bet_payload(0, 0), &mut rng),
+            Err(GameError::InvalidPayload)
+        ));
+        assert!(matches!(
+            MoneyWheel::process_move(&mut session, &[1], &mut rng),
+            Err(GameError::InvalidPayload)
+        ));
+
+        // Bets on the same symbol are merged
+        for amount in [10, 15] {
+            let result =
+                MoneyWheel::process_move(&mut session, &place_bet_payload(0, amount), &mut rng)
+                    .unwrap();
+            assert!(matches!(
+                result,
+                GameResult::ContinueWithUpdate { payout } if payout == -(amount as i64)
+            ));
+        }
+        MoneyWheel::process_move(&mut session, &place_bet_payload(5, 1), &mut rng).unwrap();
+        assert_eq!(MoneyWheel::bet_count(&session), 2);
+
+        // Clearing refunds every bet
+        let result = MoneyWheel::process_move(&mut session, &[2], &mut rng).unwrap();
+        assert!(matches!(
+            result,
+            GameResult::ContinueWithUpdate { payout: 26 }
+        ));
+        assert_eq!(MoneyWheel::bet_count(&session), 0);
+        assert!(!session.is_complete);
+    }
+
+    #[test]
+    fn test_spin() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(0);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        MoneyWheel::init(&mut session, &mut rng);
+
+        // Cover every symbol
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        for bet_type in 0..SYMBOL_COUNT as u8 {
+            MoneyWheel::process_move(&mut session, &place_bet_payload(bet_type, 10), &mut rng)
+                .unwrap();
+        }
+
+        let mut rng = GameRng::new(&seed, session.id, 2);
+        let result = MoneyWheel::process_move(&mut session, &[1], &mut rng).unwrap();
+        let state = MoneyWheelState::from_blob(&session.state_blob).unwrap();
+        let segment = state.segment.unwrap();
+        let payout = BetType::try_from(SEGMENTS[segment as usize])
+            .unwrap()
+            .payout();
+        assert!(matches!(result, GameResult::Win(x) if x == 10 * (payout + 1)));
+        assert!(session.is_complete);
+        assert!(matches!(
+            MoneyWheel::process_move(&mut session, &[1], &mut rng),
+            Err(GameError::GameAlreadyComplete)
+        ));
+    }
+
+    #[test]
+    fn test_super_segment() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(0);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        MoneyWheel::init(&mut session, &mut rng);
+
+        // Supercharge every segment
+        session.super_mode = SuperModeState {
+            is_active: true,
+            multipliers: (0..SEGMENT_COUNT as u8)
+                .map(|id| SuperMultiplier {
+                    id,
+                    multiplier: 3,
+                    super_type: SuperType::Number,
+                })
+                .collect(),
+            ..SuperModeState::default()
+        };
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        for bet_type in 0..SYMBOL_COUNT as u8 {
+            MoneyWheel::process_move(&mut session, &place_bet_payload(bet_type, 10), &mut rng)
+                .unwrap();
+        }
+
+        let mut rng = GameRng::new(&seed, session.id, 2);
+        let result = MoneyWheel::process_move(&mut session, &[1], &mut rng).unwrap();
+        let state = MoneyWheelState::from_blob(&session.state_blob).unwrap();
+        let payout = BetType::try_from(SEGMENTS[state.segment.unwrap() as usize])
+            .unwrap()
+            .payout();
+        assert!(matches!(result, GameResult::Win(x) if x == 30 * (payout + 1)));
+    }
+}
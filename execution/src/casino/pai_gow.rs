@@ -0,0 +1,486 @@
+//! Pai Gow Poker implementation.
+//!
+//! Played with a 53-card deck (the joker is semi-wild, see `poker`). The player is dealt seven
+//! cards and sets them into a two-card front hand and a five-card back hand, which must outrank
+//! the front. The dealer sets its seven cards the house way. Winning both hands pays even money
+//! less the banker's commission, losing both loses the bet, and anything else pushes (the
+//! dealer wins copies).
+//!
+//! State blob format:
+//! [stage:u8] [playerCards:u8×7] [frontMask:u8] [dealerCards:u8×7]
+//!
+//! Stage: 0 = Setting (player cards dealt), 1 = Complete
+//! The front mask and dealer cards are 0 until the hand completes (the dealer's cards are drawn
+//! when the player sets their hand, so nothing about them is known beforehand).
+//!
+//! Payload format:
+//! [0, frontMask] - Set the hand (bits 0-6 pick the two player cards that form the front hand)
+//! [1] - Set the hand the house way
+
+use super::poker::{evaluate_five, evaluate_two, HandRank, HandValue, JOKER};
+use super::state_blob::{impl_blob_field_enum, BlobReader, BlobWriter, StateBlob, UNVERSIONED};
+use super::{CasinoGame, GameError, GameResult, GameRng};
+use nullspace_types::casino::{GameSession, RngDraw};
+
+/// Commission taken from winning hands (in basis points).
+pub const COMMISSION_BPS: u64 = 500;
+
+/// Pai Gow stages.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Setting = 0,
+    Complete = 1,
+}
+
+impl TryFrom<u8> for Stage {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Stage::Setting),
+            1 => Ok(Stage::Complete),
+            _ => Err(GameError::InvalidState),
+        }
+    }
+}
+
+/// Player moves.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Move {
+    SetHand = 0,
+    HouseWay = 1,
+}
+
+impl TryFrom<u8> for Move {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Move::SetHand),
+            1 => Ok(Move::HouseWay),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+/// Pai Gow state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaiGowState {
+    pub stage: Stage,
+    pub player: [u8; 7],
+    pub front_mask: u8,
+    pub dealer: [u8; 7],
+}
+
+/// [stage:u8] [player:7] [frontMask:u8] [dealer:7]
+impl StateBlob for PaiGowState {
+    const VERSION: u8 = UNVERSIONED;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.stage)
+            .put(&self.player)
+            .put(&self.front_mask)
+            .put(&self.dealer);
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        let stage = reader.get()?;
+        let player: [u8; 7] = reader.get()?;
+        if player.iter().any(|&card| card > JOKER) {
+            return None;
+        }
+        Some(Self {
+            stage,
+            player,
+            front_mask: reader.get()?,
+            dealer: reader.get()?,
+        })
+    }
+}
+
+impl_blob_field_enum!(Stage);
+
+/// Split seven cards into the front (the cards picked by `front_mask`) and back hands.
+///
+/// Returns `None` unless the mask picks exactly two of the seven cards.
+pub fn split(cards: &[u8; 7], front_mask: u8) -> Option<([u8; 2], [u8; 5])> {
+    if front_mask >> 7 != 0 || front_mask.count_ones() != 2 {
+        return None;
+    }
+    let mut front = [0u8; 2];
+    let mut back = [0u8; 5];
+    let (mut f, mut b) = (0, 0);
+    for (i, &card) in cards.iter().enumerate() {
+        if front_mask & (1 << i) != 0 {
+            front[f] = card;
+            f += 1;
+        } else {
+            back[b] = card;
+            b += 1;
+        }
+    }
+    Some((front, back))
+}
+
+/// Rank a split, or `None` if it is fouled (the back hand doesn't outrank the front).
+fn rank_split(front: &[u8; 2], back: &[u8; 5]) -> Option<(HandValue, HandValue)> {
+    let front = evaluate_two(front);
+    let back = evaluate_five(back);
+    (back > front).then_some((front, back))
+}
+
+/// The front mask the house sets seven cards with.
+///
+/// The house puts a pair in front when the back hand can still beat it, then keeps the strongest
+/// back hand, then plays the strongest front hand (so two pair and full houses are split, and
+/// without a pair the highest card stays in back with the next two in front).
+pub fn house_way(cards: &[u8; 7]) -> u8 {
+    let mut best: Option<((bool, HandRank, HandValue, HandValue), u8)> = None;
+    for a in 0..7 {
+        for b in (a + 1)..7 {
+            let mask = (1u8 << a) | (1u8 << b);
+            let Some((front, back)) = split(cards, mask) else {
+                continue;
+            };
+            let Some((front, back)) = rank_split(&front, &back) else {
+                continue;
+            };
+            let key = (front.rank == HandRank::Pair, back.rank, front, back);
+            if best.is_none_or(|(best, _)| key > best) {
+                best = Some((key, mask));
+            }
+        }
+    }
+    // Five cards always outrank two of the remaining ones for some split
+    best.map(|(_, mask)| mask).unwrap_or(0b11)
+}
+
+/// Settle the player's set hands against the dealer's cards.
+fn settle(bet: u64, front: HandValue, back: HandValue, dealer: &[u8; 7]) -> GameResult {
+    let (dealer_front, dealer_back) = split(dealer, house_way(dealer))
+        .and_then(|(front, back)| rank_split(&front, &back))
+        .expect("the house way is never fouled");
+
+    // The dealer wins copies
+    match (front > dealer_front, back > dealer_back) {
+        (true, true) => {
+            let commission = bet.saturating_mul(COMMISSION_BPS) / 10_000;
+            let winnings = bet.saturating_sub(commission);
+            GameResult::Win(bet.saturating_add(winnings))
+        }
+        (false, false) => GameResult::Loss,
+        _ => GameResult::Push,
+    }
+}
+
+pub struct PaiGow;
+
+impl CasinoGame for PaiGow {
+    fn init(session: &mut GameSession, rng: &mut GameRng) -> GameResult {
+        // Deal 7 cards from the 53-card deck
+        let mut deck: Vec<u8> = (0..=JOKER).collect();
+        rng.shuffle(&mut deck);
+        let mut player = [0u8; 7];
+        player.copy_from_slice(&deck[..7]);
+        for &card in &player {
+            rng.record(RngDraw::Card(card));
+        }
+
+        let state = PaiGowState {
+            stage: Stage::Setting,
+            player,
+            front_mask: 0,
+            dealer: [0; 7],
+        };
+        session.state_blob = state.to_blob();
+        GameResult::Continue
+    }
+
+    fn process_move(
+        session: &mut GameSession,
+        payload: &[u8],
+        rng: &mut GameRng,
+    ) -> Result<GameResult, GameError> {
+        if session.is_complete {
+            return Err(GameError::GameAlreadyComplete);
+        }
+        let mut state =
+            PaiGowState::from_blob(&session.state_blob).ok_or(GameError::InvalidState)?;
+        if state.stage != Stage::Setting {
+            return Err(GameError::GameAlreadyComplete);
+        }
+
+        let front_mask = match Move::try_from(*payload.first().ok_or(GameError::InvalidPayload)?)? {
+            Move::SetHand => {
+                if payload.len() != 2 {
+                    return Err(GameError::InvalidPayload);
+                }
+                payload[1]
+            }
+            Move::HouseWay => {
+                if payload.len() != 1 {
+                    return Err(GameError::InvalidPayload);
+                }
+                house_way(&state.player)
+            }
+        };
+        let (front, back) = split(&state.player, front_mask).ok_or(GameError::InvalidPayload)?;
+        let (front, back) = rank_split(&front, &back).ok_or(GameError::InvalidMove)?;
+
+        // Deal the dealer's cards from the rest of the deck
+        let mut deck: Vec<u8> = (0..=JOKER)
+            .filter(|card| !state.player.contains(card))
+            .collect();
+        rng.shuffle(&mut deck);
+        state.dealer.copy_from_slice(&deck[..7]);
+        for &card in &state.dealer {
+            rng.record(RngDraw::Card(card));
+        }
+
+        state.stage = Stage::Complete;
+        state.front_mask = front_mask;
+        session.state_blob = state.to_blob();
+        session.move_count += 1;
+        session.is_complete = true;
+
+        Ok(settle(session.bet, front, back, &state.dealer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::casino::poker::card_rank;
+    use crate::mocks::{create_account_keypair, create_network_keypair, create_seed};
+    use nullspace_types::casino::GameType;
+
+    fn create_test_seed() -> nullspace_types::Seed {
+        let (network_secret, _) = create_network_keypair();
+        create_seed(&network_secret, 1)
+    }
+
+    fn create_test_session(bet: u64) -> GameSession {
+        let (_, pk) = create_account_keypair(1);
+        GameSession {
+            id: 1,
+            player: pk,
+            game_type: GameType::PaiGow,
+            bet,
+            state_blob: vec![],
+            move_count: 0,
+            created_at: 0,
+            is_complete: false,
+            super_mode: nullspace_types::casino::SuperModeState::default(),
+            is_tournament: false,
+            tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
+        }
+    }
+
+    /// Card with `rank` (2-14) and `suit` (0-3).
+    fn card(rank: u8, suit: u8) -> u8 {
+        suit * 13 + if rank == 14 { 0 } else { rank - 1 }
+    }
+
+    fn hand(cards: [(u8, u8); 7]) -> [u8; 7] {
+        cards.map(|(rank, suit)| card(rank, suit))
+    }
+
+    /// Ranks of the front hand the house way sets.
+    fn house_front(cards: &[u8; 7]) -> [u8; 2] {
+        let (front, _) = split(cards, house_way(cards)).unwrap();
+        let mut ranks = front.map(card_rank);
+        ranks.sort_unstable();
+        ranks
+    }
+
+    #[test]
+    fn test_split() {
+        let cards = [0, 1, 2, 3, 4, 5, 6];
+        assert_eq!(split(&cards, 0b1000001), Some(([0, 6], [1, 2, 3, 4, 5])));
+        assert_eq!(split(&cards, 0b1), None);
+        assert_eq!(split(&cards, 0b111), None);
+        assert_eq!(split(&cards, 0b10000001), None);
+
+        // The back hand must outrank the front
+        let (front, back) = split(
+            &hand([(14, 0), (14, 1), (9, 2), (7, 3), (5, 0), (4, 1), (2, 2)]),
+            0b11,
+        )
+        .unwrap();
+        assert!(rank_split(&front, &back).is_none());
+    }
+
+    #[test]
+    fn test_house_way() {
+        // No pair: highest card in back, next two in front
+        let high = hand([(14, 0), (13, 1), (12, 2), (9, 3), (7, 0), (5, 1), (3, 2)]);
+        assert_eq!(house_front(&high), [12, 13]);
+
+        // Two pair: split them
+        let two_pair = hand([(13, 0), (13, 1), (5, 2), (5, 3), (14, 0), (9, 1), (3, 2)]);
+        assert_eq!(house_front(&two_pair), [5, 5]);
+
+        // Full house: pair in front, trips in back
+        let full_house = hand([(13, 0), (13, 1), (13, 2), (5, 3), (5, 0), (14, 1), (3, 2)]);
+        assert_eq!(house_front(&full_house), [5, 5]);
+
+        // One pair: keep it in back
+        let pair = hand([(14, 0), (14, 1), (13, 2), (12, 3), (9, 0), (5, 1), (3, 2)]);
+        assert_eq!(house_front(&pair), [12, 13]);
+
+        // Keep the flush together
+        let flush = hand([(14, 0), (13, 0), (9, 0), (5, 0), (3, 0), (8, 1), (7, 2)]);
+        assert_eq!(house_front(&flush), [7, 8]);
+
+        // The joker completes the straight and the pair goes in front
+        let mut straight = hand([(9, 0), (8, 1), (6, 2), (5, 3), (13, 0), (13, 1), (2, 2)]);
+        straight[6] = JOKER;
+        assert_eq!(house_front(&straight), [13, 13]);
+    }
+
+    #[test]
+    fn test_settle() {
+        let dealer = hand([(14, 0), (13, 1), (12, 2), (9, 3), (7, 0), (5, 1), (3, 2)]);
+        let value = |cards: [(u8, u8); 7], mask| {
+            let (front, back) = split(&hand(cards), mask).unwrap();
+            rank_split(&front, &back).unwrap()
+        };
+
+        // Both hands win (less commission)
+        let (front, back) = value(
+            [(13, 0), (13, 1), (5, 2), (5, 3), (14, 0), (9, 1), (3, 2)],
+            0b1100,
+        );
+        assert!(matches!(
+            settle(100, front, back, &dealer),
+            GameResult::Win(195)
+        ));
+
+        // One each
+        let (front, back) = value(
+            [(3, 1), (2, 1), (5, 2), (5, 3), (14, 2), (9, 1), (3, 2)],
+            0b11,
+        );
+        assert!(matches!(
+            settle(100, front, back, &dealer),
+            GameResult::Push
+        ));
+
+        // Copies go to the dealer
+        let (front, back) = value(
+            [(14, 1), (13, 2), (12, 3), (9, 0), (7, 1), (5, 2), (3, 3)],
+            0b110,
+        );
+        assert!(matches!(
+            settle(100, front, back, &dealer),
+            GameResult::Loss
+        ));
+    }
+
+    #[test]
+    fn test_state_roundtrip() {
+        let state = PaiGowState {
+            stage: Stage::Complete,
+            player: [0, 13, 26, 39, 52, 1, 2],
+            front_mask: 0b11,
+            dealer: [3, 4, 5, 6, 7, 8, 9],
+        };
+        let blob = state.to_blob();
+        assert_eq!(blob.len(), 16);
+        assert_eq!(PaiGowState::from_blob(&blob).unwrap(), state);
+
+        assert_eq!(PaiGowState::from_blob(&blob[1..]), None);
+        let mut bad = blob.clone();
+        bad[0] = 2;
+        assert_eq!(PaiGowState::from_blob(&bad), None);
+        bad = blob;
+        bad[1] = 53;
+        assert_eq!(PaiGowState::from_blob(&bad), None);
+
+        crate::casino::state_blob::fuzz::roundtrip(&[state]);
+    }
+
+    #[test]
+    fn test_deal() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        PaiGow::init(&mut session, &mut rng);
+
+        let state = PaiGowState::from_blob(&session.state_blob).unwrap();
+        assert_eq!(state.stage, Stage::Setting);
+        let mut cards = state.player.to_vec();
+        cards.sort_unstable();
+        cards.dedup();
+        assert_eq!(cards.len(), 7);
+        assert_eq!(state.dealer, [0; 7]);
+    }
+
+    #[test]
+    fn test_invalid_moves() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        PaiGow::init(&mut session, &mut rng);
+
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        let payloads: [&[u8]; 6] = [&[], &[2], &[0], &[0, 0b1], &[0, 0b111], &[1, 0]];
+        for payload in payloads {
+            assert!(matches!(
+                PaiGow::process_move(&mut session, payload, &mut rng),
+                Err(GameError::InvalidPayload)
+            ));
+        }
+
+        // A fouled hand is rejected
+        let state = PaiGowState::from_blob(&session.state_blob).unwrap();
+        let fouled = (0..7)
+            .flat_map(|a| ((a + 1)..7).map(move |b| (1u8 << a) | (1u8 << b)))
+            .find(|&mask| {
+                let (front, back) = split(&state.player, mask).unwrap();
+                rank_split(&front, &back).is_none()
+            });
+        if let Some(mask) = fouled {
+            assert!(matches!(
+                PaiGow::process_move(&mut session, &[0, mask], &mut rng),
+                Err(GameError::InvalidMove)
+            ));
+        }
+        assert!(!session.is_complete);
+    }
+
+    #[test]
+    fn test_house_way_move() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        PaiGow::init(&mut session, &mut rng);
+
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        let result = PaiGow::process_move(&mut session, &[1], &mut rng).unwrap();
+        assert!(matches!(
+            result,
+            GameResult::Win(195) | GameResult::Push | GameResult::Loss
+        ));
+        assert!(session.is_complete);
+
+        let state = PaiGowState::from_blob(&session.state_blob).unwrap();
+        assert_eq!(state.stage, Stage::Complete);
+        assert_eq!(state.front_mask, house_way(&state.player));
+        let mut cards = [state.player, state.dealer].concat();
+        cards.sort_unstable();
+        cards.dedup();
+        assert_eq!(cards.len(), 14);
+
+        assert!(matches!(
+            PaiGow::process_move(&mut session, &[1], &mut rng),
+            Err(GameError::GameAlreadyComplete)
+        ));
+    }
+}
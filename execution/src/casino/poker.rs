@@ -0,0 +1,299 @@
+//! Poker hand ranking shared by the poker games.
+//!
+//! Cards are 0-51 (suit = card/13, rank = card%13 with 0 = Ace), and `JOKER` (52) is the joker
+//! of a 53-card deck. The joker is semi-wild: it plays as an Ace, or as any card that completes
+//! a straight or a flush.
+//!
+//! Hands compare by `HandValue`: the rank first, then the tiebreak (ranks 2-14 ordered by how
+//! many of each the hand holds, then by rank; straights order from their top card, so the
+//! wheel A-2-3-4-5 is the lowest straight).
+
+/// The joker of a 53-card deck.
+pub const JOKER: u8 = 52;
+
+/// Poker hand rankings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandRank {
+    HighCard = 0,
+    Pair = 1,
+    TwoPair = 2,
+    ThreeOfAKind = 3,
+    Straight = 4,
+    Flush = 5,
+    FullHouse = 6,
+    FourOfAKind = 7,
+    StraightFlush = 8,
+    RoyalFlush = 9,
+    /// Four Aces and the joker.
+    FiveAces = 10,
+}
+
+/// The strength of a hand (ordered from weakest to strongest).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HandValue {
+    pub rank: HandRank,
+    /// Ranks (2-14) in the order they break ties (unused places are 0).
+    pub tiebreak: [u8; 5],
+}
+
+/// Get card rank (2-14, Ace high). The joker plays as an Ace.
+pub fn card_rank(card: u8) -> u8 {
+    if card == JOKER {
+        return 14;
+    }
+    match card % 13 {
+        0 => 14,
+        r => r + 1,
+    }
+}
+
+/// Get card suit (0-3).
+pub fn card_suit(card: u8) -> u8 {
+    card / 13
+}
+
+/// Rank five cards given as ranks (2-14) and suits.
+fn evaluate(ranks: [u8; 5], suits: [u8; 5]) -> HandValue {
+    let mut counts = [0u8; 15];
+    for &rank in &ranks {
+        counts[rank as usize] += 1;
+    }
+
+    // Group by count, then rank (both descending)
+    let mut groups: Vec<(u8, u8)> = (2..=14u8)
+        .filter(|&rank| counts[rank as usize] > 0)
+        .map(|rank| (counts[rank as usize], rank))
+        .collect();
+    groups.sort_unstable_by(|a, b| b.cmp(a));
+    let mut tiebreak = [0u8; 5];
+    let mut i = 0;
+    for &(count, rank) in &groups {
+        for _ in 0..count {
+            tiebreak[i] = rank;
+            i += 1;
+        }
+    }
+
+    let is_flush = suits.iter().all(|&suit| suit == suits[0]);
+    let straight_high = if groups.len() < 5 {
+        None
+    } else if tiebreak[0] - tiebreak[4] == 4 {
+        Some(tiebreak[0])
+    } else if tiebreak == [14, 5, 4, 3, 2] {
+        Some(5)
+    } else {
+        None
+    };
+    if let Some(high) = straight_high {
+        tiebreak = [high, high - 1, high - 2, high - 3, high - 4];
+    }
+
+    let rank = match (groups[0].0, groups.get(1).map(|group| group.0)) {
+        (5, _) => HandRank::FiveAces,
+        _ if straight_high == Some(14) && is_flush => HandRank::RoyalFlush,
+        _ if straight_high.is_some() && is_flush => HandRank::StraightFlush,
+        (4, _) => HandRank::FourOfAKind,
+        (3, Some(2)) => HandRank::FullHouse,
+        _ if is_flush => HandRank::Flush,
+        _ if straight_high.is_some() => HandRank::Straight,
+        (3, _) => HandRank::ThreeOfAKind,
+        (2, Some(2)) => HandRank::TwoPair,
+        (2, _) => HandRank::Pair,
+        _ => HandRank::HighCard,
+    };
+    HandValue { rank, tiebreak }
+}
+
+/// Rank a five-card hand (which may hold the joker).
+pub fn evaluate_five(cards: &[u8; 5]) -> HandValue {
+    let mut ranks = [0u8; 5];
+    let mut suits = [0u8; 5];
+    for (i, &card) in cards.iter().enumerate() {
+        ranks[i] = card_rank(card);
+        suits[i] = card_suit(card);
+    }
+    let Some(joker) = cards.iter().position(|&card| card == JOKER) else {
+        return evaluate(ranks, suits);
+    };
+
+    // Try the joker as every card it may stand for
+    let mut best = None;
+    for rank in 2..=14u8 {
+        for suit in 0..4u8 {
+            ranks[joker] = rank;
+            suits[joker] = suit;
+            let value = evaluate(ranks, suits);
+            let allowed = rank == 14
+                || matches!(
+                    value.rank,
+                    HandRank::Straight
+                        | HandRank::Flush
+                        | HandRank::StraightFlush
+                        | HandRank::RoyalFlush
+                );
+            if allowed && best.is_none_or(|best| value > best) {
+                best = Some(value);
+            }
+        }
+    }
+    best.expect("the joker can always play as an Ace")
+}
+
+/// Rank a two-card hand (a pair or high cards; the joker plays as an Ace).
+pub fn evaluate_two(cards: &[u8; 2]) -> HandValue {
+    let (high, low) = {
+        let (a, b) = (card_rank(cards[0]), card_rank(cards[1]));
+        (a.max(b), a.min(b))
+    };
+    let rank = if high == low {
+        HandRank::Pair
+    } else {
+        HandRank::HighCard
+    };
+    HandValue {
+        rank,
+        tiebreak: [high, low, 0, 0, 0],
+    }
+}
+
+/// Rank the best five-card hand that can be made from `cards` (at least five).
+pub fn best_five(cards: &[u8]) -> HandValue {
+    let n = cards.len();
+    let mut best = None;
+    for a in 0..n {
+        for b in (a + 1)..n {
+            for c in (b + 1)..n {
+                for d in (c + 1)..n {
+                    for e in (d + 1)..n {
+                        let value =
+                            evaluate_five(&[cards[a], cards[b], cards[c], cards[d], cards[e]]);
+                        if best.is_none_or(|best| value > best) {
+                            best = Some(value);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    best.expect("at least five cards")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Card with `rank` (2-14) and `suit` (0-3).
+    fn card(rank: u8, suit: u8) -> u8 {
+        suit * 13 + if rank == 14 { 0 } else { rank - 1 }
+    }
+
+    #[test]
+    fn test_card_rank() {
+        assert_eq!(card_rank(0), 14);
+        assert_eq!(card_rank(1), 2);
+        assert_eq!(card_rank(12), 13);
+        assert_eq!(card_rank(JOKER), 14);
+        assert_eq!(card(14, 2), 26);
+        assert_eq!(card_rank(card(10, 3)), 10);
+    }
+
+    #[test]
+    fn test_evaluate_five() {
+        let hand = |cards: [(u8, u8); 5]| evaluate_five(&cards.map(|(r, s)| card(r, s)));
+
+        let royal = hand([(14, 0), (13, 0), (12, 0), (11, 0), (10, 0)]);
+        assert_eq!(royal.rank, HandRank::RoyalFlush);
+        let wheel = hand([(14, 0), (2, 1), (3, 2), (4, 3), (5, 0)]);
+        assert_eq!(wheel.rank, HandRank::Straight);
+        assert_eq!(wheel.tiebreak, [5, 4, 3, 2, 1]);
+        let six_high = hand([(6, 0), (2, 1), (3, 2), (4, 3), (5, 0)]);
+        assert!(six_high > wheel);
+
+        let full_house = hand([(3, 0), (3, 1), (3, 2), (14, 3), (14, 0)]);
+        assert_eq!(full_house.rank, HandRank::FullHouse);
+        assert_eq!(full_house.tiebreak, [3, 3, 3, 14, 14]);
+
+        // Pairs beat high kickers, and kickers break ties
+        let two_pair = hand([(9, 0), (9, 1), (4, 2), (4, 3), (2, 0)]);
+        let better_kicker = hand([(9, 2), (9, 3), (4, 0), (4, 1), (3, 0)]);
+        assert_eq!(two_pair.rank, HandRank::TwoPair);
+        assert!(better_kicker > two_pair);
+        let pair = hand([(2, 0), (2, 1), (14, 2), (13, 3), (12, 0)]);
+        assert_eq!(pair.rank, HandRank::Pair);
+        assert!(two_pair > pair);
+
+        let high = hand([(14, 0), (9, 1), (7, 2), (4, 3), (2, 0)]);
+        assert_eq!(high.rank, HandRank::HighCard);
+        assert_eq!(high.tiebreak, [14, 9, 7, 4, 2]);
+    }
+
+    #[test]
+    fn test_joker() {
+        let hand = |cards: [(u8, u8); 4]| {
+            let [a, b, c, d] = cards.map(|(r, s)| card(r, s));
+            evaluate_five(&[a, b, c, d, JOKER])
+        };
+
+        // Plays as an Ace
+        assert_eq!(hand([(14, 0), (9, 1), (7, 2), (4, 3)]).rank, HandRank::Pair);
+        assert_eq!(
+            hand([(14, 0), (9, 1), (7, 2), (4, 3)]).tiebreak,
+            [14, 14, 9, 7, 4]
+        );
+        assert_eq!(
+            hand([(13, 0), (9, 1), (7, 2), (4, 3)]).tiebreak,
+            [14, 13, 9, 7, 4]
+        );
+        assert_eq!(
+            hand([(14, 0), (14, 1), (14, 2), (14, 3)]).rank,
+            HandRank::FiveAces
+        );
+
+        // Completes straights and flushes
+        let straight = hand([(9, 0), (8, 1), (6, 2), (5, 3)]);
+        assert_eq!(straight.rank, HandRank::Straight);
+        assert_eq!(straight.tiebreak[0], 9);
+        assert_eq!(
+            hand([(13, 0), (9, 0), (7, 0), (4, 0)]).rank,
+            HandRank::Flush
+        );
+        assert_eq!(
+            hand([(9, 2), (8, 2), (7, 2), (6, 2)]).rank,
+            HandRank::StraightFlush
+        );
+
+        // But isn't otherwise wild
+        assert_eq!(hand([(9, 0), (9, 1), (7, 2), (4, 3)]).rank, HandRank::Pair);
+        assert_eq!(
+            hand([(9, 0), (9, 1), (7, 2), (7, 3)]).rank,
+            HandRank::TwoPair
+        );
+    }
+
+    #[test]
+    fn test_evaluate_two() {
+        let aces = evaluate_two(&[card(14, 0), JOKER]);
+        assert_eq!(aces.rank, HandRank::Pair);
+        let ace_king = evaluate_two(&[card(13, 1), card(14, 0)]);
+        assert_eq!(ace_king.rank, HandRank::HighCard);
+        assert_eq!(ace_king.tiebreak, [14, 13, 0, 0, 0]);
+        assert!(aces > ace_king);
+        assert!(evaluate_two(&[card(2, 0), card(2, 1)]) > ace_king);
+    }
+
+    #[test]
+    fn test_best_five() {
+        let cards = [
+            card(14, 0),
+            card(14, 1),
+            card(9, 2),
+            card(9, 3),
+            card(9, 0),
+            card(4, 1),
+            card(2, 2),
+        ];
+        let value = best_five(&cards);
+        assert_eq!(value.rank, HandRank::FullHouse);
+        assert_eq!(value.tiebreak, [9, 9, 9, 14, 14]);
+    }
+}
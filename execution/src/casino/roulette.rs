@@ -1,7 +1,17 @@
 //! Roulette game implementation with multi-bet support.
 //!
 //! State blob format:
-//! v2:
+//! v3:
+//! [bet_count:u8]
+//! [zero_rule:u8]
+//! [phase:u8]
+//! [wheel:u8]                     (0=European single zero, 1=American double zero)
+//! [totalWagered:u64 BE]
+//! [pendingReturn:u64 BE]
+//! [bets:RouletteBet×count]
+//! [result:u8]                    (last spin result, or 0xFF before the first spin)
+//!
+//! v2 (European wheel):
 //! [bet_count:u8]
 //! [zero_rule:u8]                 (0=Standard, 1=La Partage, 2=En Prison, 3=En Prison (Double))
 //! [phase:u8]                     (0=Betting, 1=Prison)
@@ -10,9 +20,13 @@
 //! [bets:RouletteBet×count]
 //! [result:u8]?                   (last spin result, if any)
 //!
-//! Legacy:
+//! Legacy (European wheel):
 //! [bet_count:u8] [bets:RouletteBet×count] [result:u8]?
 //!
+//! The wheel is chosen from the game config's paytable when the session starts. Results are the
+//! pocket numbers, with 37 standing for the American wheel's 00 (which, like 0, loses every bet
+//! but a straight bet on it; the zero rules treat both zeros alike).
+//!
 //! Each RouletteBet (10 bytes):
 //! [bet_type:u8] [number:u8] [amount:u64 BE]
 //!
@@ -21,9 +35,15 @@
 //! [1] - Spin wheel and resolve all bets
 //! [2] - Clear all pending bets
 //! [3, zero_rule] - Set even-money-on-zero rule
+//! [4, call_bet, number, unit_bytes...] - Place a call bet (European wheel only), expanded into
+//!                                        the chips below at `unit` per chip
+//! [5, version, count, bets:RouletteBet×count] - Place several bets at once (version 1); the
+//!                                               batch is placed whole or rejected whole
+//! [6, rounds] - Spin `rounds` times (auto-play) with the bets placed, each spin staking them
+//!               again, and settle all the spins together (not under En Prison)
 //!
 //! Bet types:
-//! 0 = Straight (single number, 35:1)
+//! 0 = Straight (single number, 35:1) - number = 0-36, or 37 for 00 on the American wheel
 //! 1 = Red (1:1)
 //! 2 = Black (1:1)
 //! 3 = Even (1:1)
@@ -37,16 +57,38 @@
 //! 11 = Street (3 numbers in a row, 11:1) - number = row start (1,4,...,34)
 //! 12 = Corner (4-number corner, 8:1) - number = top-left (1-32, not multiple of 3)
 //! 13 = SixLine (6 numbers, 5:1) - number = row start (1,4,...,31)
-
+//! 14 = Trio (0 with two numbers, 11:1) - number = 1 (0-1-2) or 2 (0-2-3); European wheel only
+//!
+//! Call bets (named after sections of the single-zero wheel):
+//! 0 = Voisins du Zero (9 chips: 0-2-3 trio ×2, 4/7, 12/15, 18/21, 19/22, 32/35, 25-29 corner ×2)
+//! 1 = Tiers du Cylindre (6 chips: 5/8, 10/11, 13/16, 23/24, 27/30, 33/36)
+//! 2 = Orphelins (5 chips: 1, 6/9, 14/17, 17/20, 31/34)
+//! 3 = Neighbors (5 chips: straight up on `number` and the two pockets either side of it)
+
+use super::state_blob::{
+    impl_blob_field_enum, BlobField, BlobReader, BlobWriter, StateBlob, UNVERSIONED,
+};
 use super::super_mode::apply_super_multiplier_number;
 use super::{CasinoGame, GameError, GameResult, GameRng};
-use nullspace_types::casino::GameSession;
+use nullspace_types::casino::{GameConfig, GameSession};
 
 /// Maximum number of bets per session.
 const MAX_BETS: usize = 20;
 
-/// v2 header length: bet_count(1) + zero_rule(1) + phase(1) + totalWagered(8) + pendingReturn(8).
-const STATE_HEADER_V2_LEN: usize = 19;
+/// Current version of the batch bet payload.
+const BATCH_VERSION: u8 = 1;
+
+/// The result byte before the first spin.
+const NO_RESULT: u8 = 0xFF;
+
+/// The American wheel's 00 pocket.
+const DOUBLE_ZERO: u8 = 37;
+
+/// Pocket order around the single-zero wheel (clockwise from 0).
+const EUROPEAN_WHEEL_ORDER: [u8; 37] = [
+    0, 32, 15, 19, 4, 21, 2, 25, 17, 34, 6, 27, 13, 36, 11, 30, 8, 23, 10, 5, 24, 16, 33, 1, 20,
+    14, 31, 9, 22, 18, 29, 7, 28, 12, 35, 3, 26,
+];
 
 /// Red numbers on a roulette wheel.
 const RED_NUMBERS: [u8; 18] = [
@@ -62,6 +104,13 @@ enum ZeroRule {
     EnPrisonDouble = 3,
 }
 
+impl ZeroRule {
+    /// Whether a zero imprisons the even-money bets (rather than settling every bet).
+    fn imprisons(self) -> bool {
+        matches!(self, ZeroRule::EnPrison | ZeroRule::EnPrisonDouble)
+    }
+}
+
 impl TryFrom<u8> for ZeroRule {
     type Error = GameError;
 
@@ -76,6 +125,45 @@ impl TryFrom<u8> for ZeroRule {
     }
 }
 
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Wheel {
+    /// Single zero (37 pockets).
+    #[default]
+    European = 0,
+    /// Double zero (38 pockets).
+    American = 1,
+}
+
+impl TryFrom<u8> for Wheel {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Wheel::European),
+            1 => Ok(Wheel::American),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+impl Wheel {
+    /// Highest pocket number on the wheel.
+    fn max_number(self) -> u8 {
+        match self {
+            Wheel::European => 36,
+            Wheel::American => DOUBLE_ZERO,
+        }
+    }
+
+    fn spin(self, rng: &mut GameRng) -> u8 {
+        match self {
+            Wheel::European => rng.spin_roulette(),
+            Wheel::American => rng.spin_american_roulette(),
+        }
+    }
+}
+
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Phase {
@@ -113,6 +201,7 @@ pub enum BetType {
     Street = 11,  // 3-number row (11:1) - number is row start
     Corner = 12,  // 4-number corner (8:1) - number is top-left cell
     SixLine = 13, // 6-number (5:1) - number is row start of top row
+    Trio = 14,    // 0 and two numbers (11:1) - number is 1 (0-1-2) or 2 (0-2-3)
 }
 
 impl TryFrom<u8> for BetType {
@@ -134,21 +223,99 @@ impl TryFrom<u8> for BetType {
             11 => Ok(BetType::Street),
             12 => Ok(BetType::Corner),
             13 => Ok(BetType::SixLine),
+            14 => Ok(BetType::Trio),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+/// Call (announced) bets, placed as a fixed pattern of chips.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallBet {
+    Voisins = 0,
+    Tiers = 1,
+    Orphelins = 2,
+    Neighbors = 3,
+}
+
+impl TryFrom<u8> for CallBet {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(CallBet::Voisins),
+            1 => Ok(CallBet::Tiers),
+            2 => Ok(CallBet::Orphelins),
+            3 => Ok(CallBet::Neighbors),
             _ => Err(GameError::InvalidPayload),
         }
     }
 }
 
+/// Expand a call bet into its chips as (bet type, number, chip count).
+fn call_bet_chips(call_bet: CallBet, number: u8) -> Result<Vec<(BetType, u8, u64)>, GameError> {
+    let chips = match call_bet {
+        CallBet::Voisins => vec![
+            (BetType::Trio, 2, 2),
+            (BetType::SplitV, 4, 1),
+            (BetType::SplitV, 12, 1),
+            (BetType::SplitV, 18, 1),
+            (BetType::SplitV, 19, 1),
+            (BetType::SplitV, 32, 1),
+            (BetType::Corner, 25, 2),
+        ],
+        CallBet::Tiers => vec![
+            (BetType::SplitV, 5, 1),
+            (BetType::SplitH, 10, 1),
+            (BetType::SplitV, 13, 1),
+            (BetType::SplitH, 23, 1),
+            (BetType::SplitV, 27, 1),
+            (BetType::SplitV, 33, 1),
+        ],
+        CallBet::Orphelins => vec![
+            (BetType::Straight, 1, 1),
+            (BetType::SplitV, 6, 1),
+            (BetType::SplitV, 14, 1),
+            (BetType::SplitV, 17, 1),
+            (BetType::SplitV, 31, 1),
+        ],
+        CallBet::Neighbors => {
+            let position = EUROPEAN_WHEEL_ORDER
+                .iter()
+                .position(|&pocket| pocket == number)
+                .ok_or(GameError::InvalidPayload)?;
+            let len = EUROPEAN_WHEEL_ORDER.len();
+            (0..5)
+                .map(|offset| {
+                    let pocket = EUROPEAN_WHEEL_ORDER[(position + len + offset - 2) % len];
+                    (BetType::Straight, pocket, 1)
+                })
+                .collect()
+        }
+    };
+    Ok(chips)
+}
+
 /// Check if a number is red.
 fn is_red(number: u8) -> bool {
     RED_NUMBERS.contains(&number)
 }
 
+/// Check if a result is a zero pocket (0, or 00 on the American wheel).
+fn is_zero(result: u8) -> bool {
+    result == 0 || result == DOUBLE_ZERO
+}
+
 /// Check if a bet wins for a given result.
 fn bet_wins(bet_type: BetType, bet_number: u8, result: u8) -> bool {
-    // Zero loses all except straight bet on 0
-    if result == 0 {
-        return bet_type == BetType::Straight && bet_number == 0;
+    // Zeros lose all except a straight bet on that zero (or a trio, on 0)
+    if is_zero(result) {
+        return match bet_type {
+            BetType::Straight => bet_number == result,
+            BetType::Trio => result == 0,
+            _ => false,
+        };
     }
 
     match bet_type {
@@ -184,7 +351,62 @@ fn bet_wins(bet_type: BetType, bet_number: u8, result: u8) -> bool {
                 || result == bet_number.saturating_add(4)
         }
         BetType::SixLine => result >= bet_number && result <= bet_number.saturating_add(5),
+        BetType::Trio => result == bet_number || result == bet_number.saturating_add(1),
+    }
+}
+
+/// Check that `number` is a valid target for `bet_type` on `wheel`.
+fn validate_bet(bet_type: BetType, number: u8, wheel: Wheel) -> Result<(), GameError> {
+    match bet_type {
+        BetType::Straight => {
+            if number > wheel.max_number() {
+                return Err(GameError::InvalidPayload);
+            }
+        }
+        BetType::Dozen | BetType::Column => {
+            if number > 2 {
+                return Err(GameError::InvalidPayload);
+            }
+        }
+        BetType::SplitH => {
+            // Horizontal split: (n, n+1) within a row -> n is 1-35 and not rightmost.
+            if number < 1 || number > 35 || number % 3 == 0 {
+                return Err(GameError::InvalidPayload);
+            }
+        }
+        BetType::SplitV => {
+            // Vertical split: (n, n+3) within a column -> n is 1-33.
+            if number < 1 || number > 33 {
+                return Err(GameError::InvalidPayload);
+            }
+        }
+        BetType::Street => {
+            // Street: (n, n+1, n+2) row -> n is 1,4,...,34.
+            if number < 1 || number > 34 || (number - 1) % 3 != 0 {
+                return Err(GameError::InvalidPayload);
+            }
+        }
+        BetType::Corner => {
+            // Corner: (n, n+1, n+3, n+4) -> n is 1-32 and not rightmost.
+            if number < 1 || number > 32 || number % 3 == 0 {
+                return Err(GameError::InvalidPayload);
+            }
+        }
+        BetType::SixLine => {
+            // Six-line: (n..n+5) two adjacent rows -> n is 1,4,...,31.
+            if number < 1 || number > 31 || (number - 1) % 3 != 0 {
+                return Err(GameError::InvalidPayload);
+            }
+        }
+        BetType::Trio => {
+            // Trio: (0, n, n+1) -> n is 1 or 2, on the single-zero layout.
+            if !(1..=2).contains(&number) || wheel != Wheel::European {
+                return Err(GameError::InvalidPayload);
+            }
+        }
+        _ => {} // No number needed for other bets
     }
+    Ok(())
 }
 
 /// Get the payout multiplier for a bet type (excludes original bet).
@@ -199,7 +421,7 @@ fn payout_multiplier(bet_type: BetType) -> u64 {
         | BetType::High => 1,
         BetType::Dozen | BetType::Column => 2,
         BetType::SplitH | BetType::SplitV => 17,
-        BetType::Street => 11,
+        BetType::Street | BetType::Trio => 11,
         BetType::Corner => 8,
         BetType::SixLine => 5,
     }
@@ -212,6 +434,26 @@ fn is_even_money_bet(bet_type: BetType) -> bool {
     )
 }
 
+/// Return (stake plus winnings) of the bets on a spin that settles them all: any spin but a zero
+/// under En Prison, which imprisons the even-money bets instead.
+fn settled_return(bets: &[RouletteBet], zero_rule: ZeroRule, result: u8) -> u64 {
+    bets.iter().fold(0u64, |total, bet| {
+        let ret = if bet_wins(bet.bet_type, bet.number, result) {
+            let multiplier = payout_multiplier(bet.bet_type).saturating_add(1);
+            bet.amount.saturating_mul(multiplier)
+        } else if is_zero(result)
+            && zero_rule == ZeroRule::LaPartage
+            && is_even_money_bet(bet.bet_type)
+        {
+            // Half-back on even-money bets.
+            bet.amount / 2
+        } else {
+            0
+        };
+        total.saturating_add(ret)
+    })
+}
+
 /// Individual bet in roulette.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RouletteBet {
@@ -220,36 +462,30 @@ pub struct RouletteBet {
     pub amount: u64,
 }
 
-impl RouletteBet {
-    /// Serialize to 10 bytes: [bet_type:u8] [number:u8] [amount:u64 BE]
-    fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::with_capacity(10);
-        bytes.push(self.bet_type as u8);
-        bytes.push(self.number);
-        bytes.extend_from_slice(&self.amount.to_be_bytes());
-        bytes
+/// 10 bytes: [bet_type:u8] [number:u8] [amount:u64 BE]
+impl BlobField for RouletteBet {
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.bet_type)
+            .put(&self.number)
+            .put(&self.amount);
     }
 
-    /// Deserialize from 10 bytes
-    fn from_bytes(bytes: &[u8]) -> Option<Self> {
-        if bytes.len() < 10 {
-            return None;
-        }
-        let bet_type = BetType::try_from(bytes[0]).ok()?;
-        let number = bytes[1];
-        let amount = u64::from_be_bytes(bytes[2..10].try_into().ok()?);
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
         Some(RouletteBet {
-            bet_type,
-            number,
-            amount,
+            bet_type: reader.get()?,
+            number: reader.get()?,
+            amount: reader.get()?,
         })
     }
 }
 
 /// Game state for multi-bet roulette.
+#[derive(Debug, PartialEq, Eq)]
 struct RouletteState {
     zero_rule: ZeroRule,
     phase: Phase,
+    wheel: Wheel,
     total_wagered: u64,
     pending_return: u64,
     bets: Vec<RouletteBet>,
@@ -261,112 +497,105 @@ impl RouletteState {
         RouletteState {
             zero_rule: ZeroRule::Standard,
             phase: Phase::Betting,
+            wheel: Wheel::European,
             total_wagered: 0,
             pending_return: 0,
             bets: Vec::new(),
             result: None,
         }
     }
+}
 
-    /// Serialize state to blob
-    fn to_blob(&self) -> Vec<u8> {
-        // Capacity: header + bets (10 bytes each) + 1 (optional result)
-        let capacity = STATE_HEADER_V2_LEN
-            + (self.bets.len() * 10)
-            + if self.result.is_some() { 1 } else { 0 };
-        let mut blob = Vec::with_capacity(capacity);
-        blob.push(self.bets.len() as u8);
-        blob.push(self.zero_rule as u8);
-        blob.push(self.phase as u8);
-        blob.extend_from_slice(&self.total_wagered.to_be_bytes());
-        blob.extend_from_slice(&self.pending_return.to_be_bytes());
+/// Roulette blobs carry no version byte, so the layouts are told apart by their length once the
+/// bet count is known:
+/// - legacy: [bet_count] [bets] [result?]
+/// - v2: [bet_count] [zero_rule] [phase] [totalWagered:u64] [pendingReturn:u64] [bets] [result?]
+/// - v3: the v2 header plus [wheel] after the phase, and the result byte is always present (it
+///   can't be mistaken for v2)
+impl StateBlob for RouletteState {
+    const VERSION: u8 = UNVERSIONED;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&(self.bets.len() as u8))
+            .put(&self.zero_rule)
+            .put(&self.phase)
+            .put(&self.wheel)
+            .put(&self.total_wagered)
+            .put(&self.pending_return);
         for bet in &self.bets {
-            blob.extend_from_slice(&bet.to_bytes());
-        }
-        if let Some(result) = self.result {
-            blob.push(result);
+            writer.put(bet);
         }
-        blob
+        writer.put(&self.result.unwrap_or(NO_RESULT));
     }
 
-    /// Deserialize state from blob
-    fn from_blob(blob: &[u8]) -> Option<Self> {
-        if blob.is_empty() {
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        if reader.remaining() == 0 {
             return Some(RouletteState::new());
         }
 
-        let bet_count = blob[0] as usize;
+        let bet_count = reader.get::<u8>()? as usize;
         if bet_count > MAX_BETS {
             return None;
         }
 
-        let legacy_no_result_len = 1 + (bet_count * 10);
-        let legacy_with_result_len = legacy_no_result_len + 1;
-
-        let v2_no_result_len = STATE_HEADER_V2_LEN + (bet_count * 10);
-        let v2_with_result_len = v2_no_result_len + 1;
-
-        if blob.len() == legacy_no_result_len || blob.len() == legacy_with_result_len {
-            let mut offset = 1;
-            let mut bets = Vec::with_capacity(bet_count);
-            let mut total_wagered: u64 = 0;
-            for _ in 0..bet_count {
-                let bet = RouletteBet::from_bytes(&blob[offset..offset + 10])?;
-                total_wagered = total_wagered.saturating_add(bet.amount);
-                bets.push(bet);
-                offset += 10;
-            }
-            let result = if blob.len() == legacy_with_result_len {
-                Some(blob[offset])
-            } else {
-                None
-            };
-            return Some(RouletteState {
-                zero_rule: ZeroRule::Standard,
-                phase: Phase::Betting,
-                total_wagered,
-                pending_return: 0,
-                bets,
-                result,
-            });
-        }
-
-        if blob.len() == v2_no_result_len || blob.len() == v2_with_result_len {
-            let zero_rule = ZeroRule::try_from(blob[1]).ok()?;
-            let phase = Phase::try_from(blob[2]).ok()?;
-            let total_wagered = u64::from_be_bytes(blob[3..11].try_into().ok()?);
-            let pending_return = u64::from_be_bytes(blob[11..19].try_into().ok()?);
-
-            let mut offset = STATE_HEADER_V2_LEN;
-            let mut bets = Vec::with_capacity(bet_count);
-            for _ in 0..bet_count {
-                let bet = RouletteBet::from_bytes(&blob[offset..offset + 10])?;
-                bets.push(bet);
-                offset += 10;
-            }
-            let result = if blob.len() == v2_with_result_len {
-                Some(blob[offset])
-            } else {
-                None
-            };
-
-            return Some(RouletteState {
-                zero_rule,
-                phase,
-                total_wagered,
-                pending_return,
-                bets,
-                result,
-            });
-        }
-
-        None
+        // Header bytes (and result byte) beyond the bets
+        let extra = reader.remaining().checked_sub(bet_count * 10)?;
+        let (zero_rule, phase, wheel, totals) = match extra {
+            0 | 1 => (ZeroRule::Standard, Phase::Betting, Wheel::European, None),
+            18 | 19 => (
+                reader.get()?,
+                reader.get()?,
+                Wheel::European,
+                Some((reader.get()?, reader.get()?)),
+            ),
+            20 => (
+                reader.get()?,
+                reader.get()?,
+                reader.get()?,
+                Some((reader.get()?, reader.get()?)),
+            ),
+            _ => return None,
+        };
+
+        let bets = (0..bet_count)
+            .map(|_| reader.get())
+            .collect::<Option<Vec<RouletteBet>>>()?;
+        // Legacy blobs don't record the totals
+        let (total_wagered, pending_return) = totals.unwrap_or_else(|| {
+            let total = bets
+                .iter()
+                .fold(0u64, |total, bet| total.saturating_add(bet.amount));
+            (total, 0)
+        });
+
+        let result = match reader.get_or(NO_RESULT)? {
+            NO_RESULT => None,
+            result if result <= wheel.max_number() => Some(result),
+            _ => return None,
+        };
+
+        Some(RouletteState {
+            zero_rule,
+            phase,
+            wheel,
+            total_wagered,
+            pending_return,
+            bets,
+            result,
+        })
     }
 }
 
+impl_blob_field_enum!(ZeroRule, Wheel, Phase, BetType);
+
 pub struct Roulette;
 
 impl CasinoGame for Roulette {
+    fn bet_count(session: &GameSession) -> usize {
+        RouletteState::from_blob(&session.state_blob).map_or(0, |state| state.bets.len())
+    }
+
     fn init(session: &mut GameSession, _rng: &mut GameRng) -> GameResult {
         // Initialize with empty state
         let state = RouletteState::new();
@@ -374,6 +603,13 @@ impl CasinoGame for Roulette {
         GameResult::Continue
     }
 
+    fn configure(session: &mut GameSession, config: &GameConfig) {
+        if let Some(mut state) = RouletteState::from_blob(&session.state_blob) {
+            state.wheel = Wheel::try_from(config.paytable).unwrap_or_default();
+            session.state_blob = state.to_blob();
+        }
+    }
+
     fn process_move(
         session: &mut GameSession,
         payload: &[u8],
@@ -415,50 +651,7 @@ impl CasinoGame for Roulette {
                     return Err(GameError::InvalidPayload);
                 }
 
-                // Validate bet number
-                match bet_type {
-                    BetType::Straight => {
-                        if number > 36 {
-                            return Err(GameError::InvalidPayload);
-                        }
-                    }
-                    BetType::Dozen | BetType::Column => {
-                        if number > 2 {
-                            return Err(GameError::InvalidPayload);
-                        }
-                    }
-                    BetType::SplitH => {
-                        // Horizontal split: (n, n+1) within a row -> n is 1-35 and not rightmost.
-                        if number < 1 || number > 35 || number % 3 == 0 {
-                            return Err(GameError::InvalidPayload);
-                        }
-                    }
-                    BetType::SplitV => {
-                        // Vertical split: (n, n+3) within a column -> n is 1-33.
-                        if number < 1 || number > 33 {
-                            return Err(GameError::InvalidPayload);
-                        }
-                    }
-                    BetType::Street => {
-                        // Street: (n, n+1, n+2) row -> n is 1,4,...,34.
-                        if number < 1 || number > 34 || (number - 1) % 3 != 0 {
-                            return Err(GameError::InvalidPayload);
-                        }
-                    }
-                    BetType::Corner => {
-                        // Corner: (n, n+1, n+3, n+4) -> n is 1-32 and not rightmost.
-                        if number < 1 || number > 32 || number % 3 == 0 {
-                            return Err(GameError::InvalidPayload);
-                        }
-                    }
-                    BetType::SixLine => {
-                        // Six-line: (n..n+5) two adjacent rows -> n is 1,4,...,31.
-                        if number < 1 || number > 31 || (number - 1) % 3 != 0 {
-                            return Err(GameError::InvalidPayload);
-                        }
-                    }
-                    _ => {} // No number needed for other bets
-                }
+                validate_bet(bet_type, number, state.wheel)?;
 
                 // Check max bets limit
                 if state.bets.len() >= MAX_BETS {
@@ -493,92 +686,47 @@ impl CasinoGame for Roulette {
                             return Err(GameError::InvalidMove);
                         }
 
-                        let result = rng.spin_roulette();
+                        let result = state.wheel.spin(rng);
                         state.result = Some(result);
 
                         // Standard single-spin settlement unless En Prison triggers.
-                        let mut total_return: u64 = 0;
-
-                        if result == 0 {
-                            match state.zero_rule {
-                                ZeroRule::Standard => {
-                                    for bet in &state.bets {
-                                        if bet_wins(bet.bet_type, bet.number, result) {
-                                            let multiplier =
-                                                payout_multiplier(bet.bet_type).saturating_add(1);
-                                            total_return = total_return.saturating_add(
-                                                bet.amount.saturating_mul(multiplier),
-                                            );
-                                        }
-                                    }
-                                }
-                                ZeroRule::LaPartage => {
-                                    for bet in &state.bets {
-                                        if bet_wins(bet.bet_type, bet.number, result) {
-                                            let multiplier =
-                                                payout_multiplier(bet.bet_type).saturating_add(1);
-                                            total_return = total_return.saturating_add(
-                                                bet.amount.saturating_mul(multiplier),
-                                            );
-                                        } else if is_even_money_bet(bet.bet_type) {
-                                            // Half-back on even-money bets.
-                                            total_return =
-                                                total_return.saturating_add(bet.amount / 2);
-                                        }
-                                    }
-                                }
-                                ZeroRule::EnPrison | ZeroRule::EnPrisonDouble => {
-                                    let mut imprisoned: Vec<RouletteBet> = Vec::new();
-
-                                    for bet in &state.bets {
-                                        if bet_wins(bet.bet_type, bet.number, result) {
-                                            let multiplier =
-                                                payout_multiplier(bet.bet_type).saturating_add(1);
-                                            let mut ret = bet.amount.saturating_mul(multiplier);
-                                            if session.super_mode.is_active && ret > 0 {
-                                                ret = apply_super_multiplier_number(
-                                                    result,
-                                                    &session.super_mode.multipliers,
-                                                    ret,
-                                                );
-                                            }
-                                            state.pending_return =
-                                                state.pending_return.saturating_add(ret);
-                                        } else if is_even_money_bet(bet.bet_type) {
-                                            imprisoned.push(bet.clone());
-                                        }
-                                    }
-
-                                    if imprisoned.is_empty() {
-                                        total_return = state.pending_return;
-                                    } else {
-                                        state.bets = imprisoned;
-                                        state.phase = Phase::Prison;
+                        let mut total_return = if is_zero(result) && state.zero_rule.imprisons() {
+                            let mut imprisoned: Vec<RouletteBet> = Vec::new();
 
-                                        session.state_blob = state.to_blob();
-                                        session.move_count += 1;
-                                        return Ok(GameResult::Continue);
-                                    }
-                                }
-                            }
-                        } else {
                             for bet in &state.bets {
                                 if bet_wins(bet.bet_type, bet.number, result) {
                                     let multiplier =
                                         payout_multiplier(bet.bet_type).saturating_add(1);
-                                    total_return = total_return
-                                        .saturating_add(bet.amount.saturating_mul(multiplier));
+                                    let mut ret = bet.amount.saturating_mul(multiplier);
+                                    if session.super_mode.is_active && ret > 0 {
+                                        ret = apply_super_multiplier_number(
+                                            result,
+                                            &session.super_mode.multipliers,
+                                            ret,
+                                        );
+                                    }
+                                    state.pending_return = state.pending_return.saturating_add(ret);
+                                } else if is_even_money_bet(bet.bet_type) {
+                                    imprisoned.push(bet.clone());
                                 }
                             }
-                        }
+
+                            if !imprisoned.is_empty() {
+                                state.bets = imprisoned;
+                                state.phase = Phase::Prison;
+
+                                session.state_blob = state.to_blob();
+                                session.move_count += 1;
+                                return Ok(GameResult::Continue);
+                            }
+                            state.pending_return
+                        } else {
+                            settled_return(&state.bets, state.zero_rule, result)
+                        };
 
                         if session.super_mode.is_active && total_return > 0 {
                             // In En Prison on a zero result, pending_return already includes the super multiplier (if any).
-                            if !(matches!(
-                                state.zero_rule,
-                                ZeroRule::EnPrison | ZeroRule::EnPrisonDouble
-                            ) && result == 0)
-                            {
+                            if !(state.zero_rule.imprisons() && is_zero(result)) {
                                 total_return = apply_super_multiplier_number(
                                     result,
                                     &session.super_mode.multipliers,
@@ -603,10 +751,10 @@ impl CasinoGame for Roulette {
                             return Err(GameError::InvalidMove);
                         }
 
-                        let result = rng.spin_roulette();
+                        let result = state.wheel.spin(rng);
                         state.result = Some(result);
 
-                        if result == 0 && state.zero_rule == ZeroRule::EnPrisonDouble {
+                        if is_zero(result) && state.zero_rule == ZeroRule::EnPrisonDouble {
                             // Double-imprisonment variant: a second 0 re-imprisons the bets.
                             session.state_blob = state.to_blob();
                             session.move_count += 1;
@@ -614,7 +762,7 @@ impl CasinoGame for Roulette {
                         }
 
                         let mut push_return: u64 = 0;
-                        if result != 0 {
+                        if !is_zero(result) {
                             for bet in &state.bets {
                                 if bet_wins(bet.bet_type, bet.number, result) {
                                     // Winning imprisoned bets push (stake returned, no winnings).
@@ -671,6 +819,132 @@ impl CasinoGame for Roulette {
                 Ok(GameResult::Continue)
             }
 
+            // [4, call_bet, number, unit_bytes...] - Place a call bet
+            4 => {
+                if payload.len() < 11 {
+                    return Err(GameError::InvalidPayload);
+                }
+                if state.phase != Phase::Betting || state.result.is_some() {
+                    return Err(GameError::InvalidMove);
+                }
+                // Call bets follow the single-zero wheel
+                if state.wheel != Wheel::European {
+                    return Err(GameError::InvalidMove);
+                }
+
+                let call_bet = CallBet::try_from(payload[1])?;
+                let number = payload[2];
+                let unit = u64::from_be_bytes(
+                    payload[3..11]
+                        .try_into()
+                        .map_err(|_| GameError::InvalidPayload)?,
+                );
+                if unit == 0 {
+                    return Err(GameError::InvalidPayload);
+                }
+
+                let chips = call_bet_chips(call_bet, number)?;
+                if state.bets.len() + chips.len() > MAX_BETS {
+                    return Err(GameError::InvalidMove);
+                }
+
+                // Price every chip before placing any
+                let mut cost: u64 = 0;
+                let mut bets = Vec::with_capacity(chips.len());
+                for (bet_type, number, count) in chips {
+                    let amount = unit.checked_mul(count).ok_or(GameError::InvalidPayload)?;
+                    cost = cost.checked_add(amount).ok_or(GameError::InvalidPayload)?;
+                    bets.push(RouletteBet {
+                        bet_type,
+                        number,
+                        amount,
+                    });
+                }
+                let payout = i64::try_from(cost).map_err(|_| GameError::InvalidPayload)?;
+
+                state.bets.extend(bets);
+                state.total_wagered = state.total_wagered.saturating_add(cost);
+
+                session.state_blob = state.to_blob();
+                Ok(GameResult::ContinueWithUpdate { payout: -payout })
+            }
+
+            // [5, version, count, bets...] - Place a batch of bets
+            5 => {
+                if payload.len() < 3 || payload[1] != BATCH_VERSION {
+                    return Err(GameError::InvalidPayload);
+                }
+                let count = payload[2] as usize;
+                if count == 0 || payload.len() != 3 + count * 10 {
+                    return Err(GameError::InvalidPayload);
+                }
+                if state.phase != Phase::Betting || state.result.is_some() {
+                    return Err(GameError::InvalidMove);
+                }
+                if state.bets.len() + count > MAX_BETS {
+                    return Err(GameError::InvalidMove);
+                }
+
+                // Validate every bet before placing any
+                let mut cost: u64 = 0;
+                let mut bets = Vec::with_capacity(count);
+                for bytes in payload[3..].chunks_exact(10) {
+                    let bet = BlobReader::unversioned(bytes)
+                        .get::<RouletteBet>()
+                        .ok_or(GameError::InvalidPayload)?;
+                    if bet.amount == 0 {
+                        return Err(GameError::InvalidPayload);
+                    }
+                    validate_bet(bet.bet_type, bet.number, state.wheel)?;
+                    cost = cost
+                        .checked_add(bet.amount)
+                        .ok_or(GameError::InvalidPayload)?;
+                    bets.push(bet);
+                }
+                let payout = i64::try_from(cost).map_err(|_| GameError::InvalidPayload)?;
+
+                state.bets.extend(bets);
+                state.total_wagered = state.total_wagered.saturating_add(cost);
+
+                session.state_blob = state.to_blob();
+                Ok(GameResult::ContinueWithUpdate { payout: -payout })
+            }
+
+            // [6, rounds] - Spin `rounds` times with the bets placed
+            6 => {
+                if payload.len() != 2 {
+                    return Err(GameError::InvalidPayload);
+                }
+                if state.phase != Phase::Betting || state.result.is_some() || state.bets.is_empty()
+                {
+                    return Err(GameError::InvalidMove);
+                }
+                // Every spin must settle on its own
+                if state.zero_rule.imprisons() {
+                    return Err(GameError::InvalidMove);
+                }
+
+                let stake = state
+                    .bets
+                    .iter()
+                    .fold(0u64, |total, bet| total.saturating_add(bet.amount));
+                let result = super::auto_play(rng, payload[1], stake, |rng| {
+                    let pocket = state.wheel.spin(rng);
+                    state.result = Some(pocket);
+                    let ret = settled_return(&state.bets, state.zero_rule, pocket);
+                    if session.super_mode.is_active && ret > 0 {
+                        apply_super_multiplier_number(pocket, &session.super_mode.multipliers, ret)
+                    } else {
+                        ret
+                    }
+                })?;
+
+                session.state_blob = state.to_blob();
+                session.move_count += 1;
+                session.is_complete = true;
+                Ok(result)
+            }
+
             _ => Err(GameError::InvalidPayload),
         }
     }
@@ -701,6 +975,9 @@ mod tests {
             super_mode: nullspace_types::casino::SuperModeState::default(),
             is_tournament: false,
             tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
         }
     }
 
@@ -1177,6 +1454,62 @@ mod tests {
         panic!("did not find a session that landed on 0 twice with En Prison Double");
     }
 
+    #[test]
+    fn test_auto_play() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(0);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        Roulette::init(&mut session, &mut rng);
+        let payload = place_bet_payload(BetType::Red, 0, 100);
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        Roulette::process_move(&mut session, &payload, &mut rng).expect("Failed to place bet");
+
+        // Between one and the most rounds a move may play
+        let mut rng = GameRng::new(&seed, session.id, 2);
+        assert!(matches!(
+            Roulette::process_move(&mut session, &[6, 0], &mut rng),
+            Err(GameError::InvalidPayload)
+        ));
+
+        // Each spin pays the red bet on its own
+        let result = Roulette::process_move(&mut session, &[6, 10], &mut rng).expect("Spin failed");
+        let spins: Vec<u8> = (0..10)
+            .map(|round| {
+                let mut rng = GameRng::new(&seed, session.id, 2).for_round(round);
+                Wheel::European.spin(&mut rng)
+            })
+            .collect();
+        let reds = spins.iter().filter(|&&result| is_red(result)).count() as u64;
+        if reds > 0 {
+            assert!(matches!(
+                result,
+                GameResult::WinWithExtraDeduction { payout, extra_deduction: 900 }
+                    if payout == reds * 200
+            ));
+        } else {
+            assert!(matches!(
+                result,
+                GameResult::LossPreDeductedWithExtraDeduction {
+                    total_loss: 1000,
+                    extra_deduction: 900
+                }
+            ));
+        }
+        let state = RouletteState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.result, spins.last().copied());
+        assert!(session.is_complete);
+
+        // En Prison zeros can't be settled spin by spin
+        let mut session = create_test_session(0);
+        Roulette::init(&mut session, &mut rng);
+        Roulette::process_move(&mut session, &[3, 2], &mut rng).expect("Failed to set rule");
+        Roulette::process_move(&mut session, &payload, &mut rng).expect("Failed to place bet");
+        assert!(matches!(
+            Roulette::process_move(&mut session, &[6, 10], &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+    }
+
     #[test]
     fn test_straight_win_payout() {
         let seed = create_test_seed();
@@ -1207,4 +1540,435 @@ mod tests {
         // Note: It's statistically unlikely to hit 0 in 100 tries (expected ~2-3 times)
         // but not guaranteed. This test just verifies the logic works.
     }
+
+    /// Helper to create a session on the American wheel
+    fn create_american_session(session_id: u64, seed: &nullspace_types::Seed) -> GameSession {
+        let mut session = create_test_session(100);
+        session.id = session_id;
+        let mut rng = GameRng::new(seed, session_id, 0);
+        Roulette::init(&mut session, &mut rng);
+        let config = GameConfig {
+            paytable: 1,
+            ..GameConfig::default()
+        };
+        Roulette::configure(&mut session, &config);
+        session
+    }
+
+    fn bet_bytes(bet: &RouletteBet) -> Vec<u8> {
+        let mut writer = BlobWriter::new(UNVERSIONED);
+        writer.put(bet);
+        writer.finish()
+    }
+
+    #[test]
+    fn test_state_versions() {
+        let bet = RouletteBet {
+            bet_type: BetType::Straight,
+            number: 17,
+            amount: 100,
+        };
+
+        // Legacy and v2 blobs are European
+        let mut legacy = vec![1];
+        legacy.extend_from_slice(&bet_bytes(&bet));
+        legacy.push(17);
+        let state = RouletteState::from_blob(&legacy).expect("Failed to parse legacy state");
+        assert_eq!(state.wheel, Wheel::European);
+        assert_eq!(state.result, Some(17));
+
+        let mut v2 = vec![1, ZeroRule::LaPartage as u8, Phase::Betting as u8];
+        v2.extend_from_slice(&100u64.to_be_bytes());
+        v2.extend_from_slice(&0u64.to_be_bytes());
+        v2.extend_from_slice(&bet_bytes(&bet));
+        let state = RouletteState::from_blob(&v2).expect("Failed to parse v2 state");
+        assert_eq!(state.wheel, Wheel::European);
+        assert_eq!(state.zero_rule, ZeroRule::LaPartage);
+        assert_eq!(state.result, None);
+
+        // v3 records the wheel, and the result byte is always present
+        let mut state = RouletteState::new();
+        state.wheel = Wheel::American;
+        state.bets.push(bet);
+        let blob = state.to_blob();
+        assert_eq!(blob.len(), 20 + 10 + 1);
+        let parsed = RouletteState::from_blob(&blob).expect("Failed to parse v3 state");
+        assert_eq!(parsed.wheel, Wheel::American);
+        assert_eq!(parsed.result, None);
+
+        state.result = Some(DOUBLE_ZERO);
+        let parsed = RouletteState::from_blob(&state.to_blob()).expect("Failed to parse v3 state");
+        assert_eq!(parsed.result, Some(DOUBLE_ZERO));
+
+        // 00 isn't on the European wheel
+        state.wheel = Wheel::European;
+        assert!(RouletteState::from_blob(&state.to_blob()).is_none());
+
+        state.wheel = Wheel::American;
+        state.zero_rule = ZeroRule::EnPrison;
+        state.phase = Phase::Prison;
+        crate::casino::state_blob::fuzz::roundtrip(&[RouletteState::new(), state]);
+    }
+
+    #[test]
+    fn test_american_wheel_bets() {
+        let seed = create_test_seed();
+
+        // 00 can only be backed on the American wheel
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        Roulette::init(&mut session, &mut rng);
+        let payload = place_bet_payload(BetType::Straight, DOUBLE_ZERO, 100);
+        assert!(matches!(
+            Roulette::process_move(&mut session, &payload, &mut rng),
+            Err(GameError::InvalidPayload)
+        ));
+
+        let mut session = create_american_session(1, &seed);
+        let state = RouletteState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.wheel, Wheel::American);
+        Roulette::process_move(&mut session, &payload, &mut rng).expect("Failed to place bet");
+        let payload = place_bet_payload(BetType::Straight, DOUBLE_ZERO + 1, 100);
+        assert!(matches!(
+            Roulette::process_move(&mut session, &payload, &mut rng),
+            Err(GameError::InvalidPayload)
+        ));
+
+        // 00 loses everything but a straight bet on it
+        assert!(bet_wins(BetType::Straight, DOUBLE_ZERO, DOUBLE_ZERO));
+        assert!(!bet_wins(BetType::Straight, 0, DOUBLE_ZERO));
+        assert!(!bet_wins(BetType::Straight, DOUBLE_ZERO, 0));
+        for bet_type in [
+            BetType::Red,
+            BetType::Black,
+            BetType::Even,
+            BetType::Odd,
+            BetType::Low,
+            BetType::High,
+        ] {
+            assert!(!bet_wins(bet_type, 0, DOUBLE_ZERO));
+        }
+        assert!(!bet_wins(BetType::Dozen, 0, DOUBLE_ZERO));
+        assert!(!bet_wins(BetType::Column, 0, DOUBLE_ZERO));
+        assert!(!bet_wins(BetType::SixLine, 31, DOUBLE_ZERO));
+    }
+
+    #[test]
+    fn test_american_wheel_spins() {
+        let seed = create_test_seed();
+
+        for session_id in 1..10_000 {
+            let mut session = create_american_session(session_id, &seed);
+
+            // La Partage applies to 00 too
+            let mut rng = GameRng::new(&seed, session_id, 1);
+            Roulette::process_move(&mut session, &[3, 1], &mut rng).expect("Failed to set rule");
+            let mut rng = GameRng::new(&seed, session_id, 2);
+            let payload = place_bet_payload(BetType::Straight, DOUBLE_ZERO, 100);
+            Roulette::process_move(&mut session, &payload, &mut rng).expect("Failed to place bet");
+            let payload = place_bet_payload(BetType::Red, 0, 100);
+            Roulette::process_move(&mut session, &payload, &mut rng).expect("Failed to place bet");
+
+            let mut rng = GameRng::new(&seed, session_id, 3);
+            let result = Roulette::process_move(&mut session, &[1], &mut rng).expect("Spin failed");
+            let state =
+                RouletteState::from_blob(&session.state_blob).expect("Failed to parse state");
+            let number = state.result.expect("Result should be set");
+            assert!(number <= DOUBLE_ZERO);
+
+            if number == DOUBLE_ZERO {
+                // 35:1 on the straight bet, half back on red
+                assert!(matches!(result, GameResult::Win(3650)));
+                return;
+            }
+        }
+
+        panic!("did not find a session that landed on 00");
+    }
+
+    /// Helper to create call bet payload
+    fn call_bet_payload(call_bet: CallBet, number: u8, unit: u64) -> Vec<u8> {
+        let mut payload = vec![4, call_bet as u8, number];
+        payload.extend_from_slice(&unit.to_be_bytes());
+        payload
+    }
+
+    /// Chip units a call bet returns when `result` comes up.
+    fn call_bet_return(call_bet: CallBet, number: u8, result: u8) -> u64 {
+        call_bet_chips(call_bet, number)
+            .unwrap()
+            .into_iter()
+            .filter(|&(bet_type, number, _)| bet_wins(bet_type, number, result))
+            .map(|(bet_type, _, count)| count * (payout_multiplier(bet_type) + 1))
+            .sum()
+    }
+
+    #[test]
+    fn test_call_bet_chips() {
+        let covered = |call_bet: CallBet| -> Vec<u8> {
+            (0..=36)
+                .filter(|&result| call_bet_return(call_bet, 0, result) > 0)
+                .collect()
+        };
+        let chip_count = |call_bet: CallBet, number: u8| -> u64 {
+            call_bet_chips(call_bet, number)
+                .unwrap()
+                .iter()
+                .map(|&(_, _, count)| count)
+                .sum()
+        };
+
+        // Each bet covers its section of the wheel, and together they cover it exactly once
+        let mut voisins = vec![
+            22, 18, 29, 7, 28, 12, 35, 3, 26, 0, 32, 15, 19, 4, 21, 2, 25,
+        ];
+        let mut tiers = vec![27, 13, 36, 11, 30, 8, 23, 10, 5, 24, 16, 33];
+        let mut orphelins = vec![1, 20, 14, 31, 9, 17, 34, 6];
+        voisins.sort_unstable();
+        tiers.sort_unstable();
+        orphelins.sort_unstable();
+        assert_eq!(covered(CallBet::Voisins), voisins);
+        assert_eq!(covered(CallBet::Tiers), tiers);
+        assert_eq!(covered(CallBet::Orphelins), orphelins);
+        assert_eq!(voisins.len() + tiers.len() + orphelins.len(), 37);
+        assert_eq!(chip_count(CallBet::Voisins, 0), 9);
+        assert_eq!(chip_count(CallBet::Tiers, 0), 6);
+        assert_eq!(chip_count(CallBet::Orphelins, 0), 5);
+
+        // Doubled chips pay double
+        assert_eq!(call_bet_return(CallBet::Voisins, 0, 0), 24); // 0-2-3 trio ×2
+        assert_eq!(call_bet_return(CallBet::Voisins, 0, 26), 18); // 25-29 corner ×2
+        assert_eq!(call_bet_return(CallBet::Voisins, 0, 4), 18); // 4/7 split
+        assert_eq!(call_bet_return(CallBet::Orphelins, 0, 17), 36); // 14/17 and 17/20
+
+        // Neighbors wrap around the wheel
+        let pockets = |number: u8| -> Vec<u8> {
+            call_bet_chips(CallBet::Neighbors, number)
+                .unwrap()
+                .iter()
+                .map(|&(_, pocket, _)| pocket)
+                .collect()
+        };
+        assert_eq!(pockets(0), vec![3, 26, 0, 32, 15]);
+        assert_eq!(pockets(26), vec![35, 3, 26, 0, 32]);
+        assert_eq!(pockets(17), vec![2, 25, 17, 34, 6]);
+        assert_eq!(chip_count(CallBet::Neighbors, 17), 5);
+        assert!(matches!(
+            call_bet_chips(CallBet::Neighbors, DOUBLE_ZERO),
+            Err(GameError::InvalidPayload)
+        ));
+    }
+
+    #[test]
+    fn test_call_bet_move() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(0);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        Roulette::init(&mut session, &mut rng);
+
+        // Charged for every chip at once
+        let result = Roulette::process_move(
+            &mut session,
+            &call_bet_payload(CallBet::Voisins, 0, 10),
+            &mut rng,
+        )
+        .expect("Failed to place call bet");
+        assert!(matches!(
+            result,
+            GameResult::ContinueWithUpdate { payout: -90 }
+        ));
+        let state = RouletteState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.bets.len(), 7);
+        assert_eq!(state.total_wagered, 90);
+        assert!(state.bets.contains(&RouletteBet {
+            bet_type: BetType::Trio,
+            number: 2,
+            amount: 20,
+        }));
+
+        // Bad amounts and overflowing costs are rejected without placing anything
+        for unit in [0, u64::MAX / 2] {
+            assert!(matches!(
+                Roulette::process_move(
+                    &mut session,
+                    &call_bet_payload(CallBet::Voisins, 0, unit),
+                    &mut rng
+                ),
+                Err(GameError::InvalidPayload)
+            ));
+        }
+
+        // The expansion must fit within the bet limit
+        Roulette::process_move(
+            &mut session,
+            &call_bet_payload(CallBet::Voisins, 0, 10),
+            &mut rng,
+        )
+        .expect("Failed to place call bet");
+        assert!(matches!(
+            Roulette::process_move(
+                &mut session,
+                &call_bet_payload(CallBet::Voisins, 0, 10),
+                &mut rng
+            ),
+            Err(GameError::InvalidMove)
+        ));
+        Roulette::process_move(
+            &mut session,
+            &call_bet_payload(CallBet::Tiers, 0, 10),
+            &mut rng,
+        )
+        .expect("Failed to place call bet");
+        let state = RouletteState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.bets.len(), MAX_BETS);
+        assert_eq!(state.total_wagered, 240);
+
+        // Settles like the individual chips
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        let result = Roulette::process_move(&mut session, &[1], &mut rng).expect("Spin failed");
+        let state = RouletteState::from_blob(&session.state_blob).expect("Failed to parse state");
+        let number = state.result.expect("Result should be set");
+        let expected = 10
+            * (2 * call_bet_return(CallBet::Voisins, 0, number)
+                + call_bet_return(CallBet::Tiers, 0, number));
+        if expected > 0 {
+            assert!(matches!(result, GameResult::Win(payout) if payout == expected));
+        } else {
+            assert!(matches!(result, GameResult::LossPreDeducted(240)));
+        }
+    }
+
+    #[test]
+    fn test_call_bets_need_european_wheel() {
+        let seed = create_test_seed();
+        let mut session = create_american_session(1, &seed);
+        let mut rng = GameRng::new(&seed, session.id, 1);
+
+        assert!(matches!(
+            Roulette::process_move(
+                &mut session,
+                &call_bet_payload(CallBet::Tiers, 0, 10),
+                &mut rng
+            ),
+            Err(GameError::InvalidMove)
+        ));
+        assert!(matches!(
+            Roulette::process_move(
+                &mut session,
+                &place_bet_payload(BetType::Trio, 1, 10),
+                &mut rng
+            ),
+            Err(GameError::InvalidPayload)
+        ));
+
+        // Trios win on 0 and their two numbers
+        assert!(bet_wins(BetType::Trio, 1, 0));
+        assert!(bet_wins(BetType::Trio, 1, 2));
+        assert!(!bet_wins(BetType::Trio, 1, 3));
+        assert!(bet_wins(BetType::Trio, 2, 3));
+        assert!(!bet_wins(BetType::Trio, 2, DOUBLE_ZERO));
+    }
+
+    /// Helper to create a batch bet payload
+    fn batch_payload(bets: &[(BetType, u8, u64)]) -> Vec<u8> {
+        let mut payload = vec![5, BATCH_VERSION, bets.len() as u8];
+        for &(bet_type, number, amount) in bets {
+            payload.extend(bet_bytes(&RouletteBet {
+                bet_type,
+                number,
+                amount,
+            }));
+        }
+        payload
+    }
+
+    #[test]
+    fn test_batch_bets() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(0);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        Roulette::init(&mut session, &mut rng);
+
+        let bets = [
+            (BetType::Straight, 17, 10),
+            (BetType::Red, 0, 50),
+            (BetType::Corner, 25, 20),
+        ];
+        let result = Roulette::process_move(&mut session, &batch_payload(&bets), &mut rng)
+            .expect("Failed to place batch");
+        assert!(matches!(
+            result,
+            GameResult::ContinueWithUpdate { payout: -80 }
+        ));
+        let state = RouletteState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.bets.len(), 3);
+        assert_eq!(state.total_wagered, 80);
+
+        // Malformed batches
+        let mut wrong_version = batch_payload(&bets);
+        wrong_version[1] = 2;
+        let mut truncated = batch_payload(&bets);
+        truncated.pop();
+        let mut miscounted = batch_payload(&bets);
+        miscounted[2] = 4;
+        for payload in [
+            wrong_version,
+            truncated,
+            miscounted,
+            batch_payload(&[]),
+            vec![5, BATCH_VERSION],
+        ] {
+            assert!(matches!(
+                Roulette::process_move(&mut session, &payload, &mut rng),
+                Err(GameError::InvalidPayload)
+            ));
+        }
+
+        // One bad bet rejects the whole batch
+        for bad in [
+            (BetType::Straight, 37, 10),
+            (BetType::Street, 2, 10),
+            (BetType::Red, 0, 0),
+        ] {
+            assert!(matches!(
+                Roulette::process_move(
+                    &mut session,
+                    &batch_payload(&[(BetType::Black, 0, 10), bad]),
+                    &mut rng
+                ),
+                Err(GameError::InvalidPayload)
+            ));
+        }
+        assert!(matches!(
+            Roulette::process_move(
+                &mut session,
+                &batch_payload(&[(BetType::Red, 0, u64::MAX), (BetType::Black, 0, 1)]),
+                &mut rng
+            ),
+            Err(GameError::InvalidPayload)
+        ));
+        let state = RouletteState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.bets.len(), 3);
+        assert_eq!(state.total_wagered, 80);
+
+        // The batch must fit within the bet limit
+        let fill = vec![(BetType::Odd, 0, 1); MAX_BETS - 2];
+        assert!(matches!(
+            Roulette::process_move(&mut session, &batch_payload(&fill), &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+        Roulette::process_move(&mut session, &batch_payload(&fill[1..]), &mut rng)
+            .expect("Failed to place batch");
+        let state = RouletteState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.bets.len(), MAX_BETS);
+
+        // No bets after the spin
+        Roulette::process_move(&mut session, &[1], &mut rng).expect("Spin failed");
+        assert!(Roulette::process_move(
+            &mut session,
+            &batch_payload(&[(BetType::Red, 0, 10)]),
+            &mut rng
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,271 @@
+//! Persistent multi-deck shoes.
+//!
+//! By default each blackjack or baccarat round is dealt from a freshly shuffled shoe. A table
+//! configured with a persistent shoe (`GameConfig::shoe_decks`) instead keeps its shoe in the
+//! session state and deals every round from what is left of it, reshuffling once the cut card
+//! comes out (`GameConfig::shoe_penetration` percent of the way through the shoe).
+//!
+//! The shoe is kept as the number of each card left in it rather than as an ordered deck, so the
+//! (public) session state reveals nothing beyond the cards already dealt: each card is drawn at
+//! random from the rest of the shoe when it is dealt.
+
+use super::state_blob::{BlobField, BlobReader, BlobWriter};
+use super::GameRng;
+use nullspace_types::casino::{
+    GameConfig, RngDraw, MAX_SHOE_DECKS, MAX_SHOE_PENETRATION, MIN_SHOE_DECKS, MIN_SHOE_PENETRATION,
+};
+
+/// Distinct cards in a deck.
+const DECK_SIZE: usize = 52;
+
+/// A multi-deck shoe kept across the rounds of a session.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Shoe {
+    decks: u8,
+    /// Percent of the shoe dealt before it is reshuffled.
+    penetration: u8,
+    /// Copies of each card (0-51) left in the shoe.
+    counts: [u8; DECK_SIZE],
+}
+
+impl Shoe {
+    /// Bytes a shoe takes in a state blob.
+    pub const BLOB_SIZE: usize = 2 + DECK_SIZE;
+
+    /// A freshly shuffled shoe.
+    pub fn new(decks: u8, penetration: u8) -> Self {
+        Self {
+            decks,
+            penetration,
+            counts: [decks; DECK_SIZE],
+        }
+    }
+
+    /// The shoe the table rules ask for, if the table keeps one.
+    pub fn from_config(config: &GameConfig) -> Option<Self> {
+        (config.shoe_decks != 0).then(|| Self::new(config.shoe_decks, config.shoe_penetration))
+    }
+
+    /// Cards left in the shoe.
+    pub fn remaining(&self) -> usize {
+        self.counts.iter().map(|&count| count as usize).sum()
+    }
+
+    /// Whether the cut card has come out.
+    pub fn cut_card_reached(&self) -> bool {
+        let size = DECK_SIZE * self.decks as usize;
+        (size - self.remaining()) * 100 >= size * self.penetration as usize
+    }
+
+    /// Start a round, reshuffling the shoe if the cut card came out during the last one.
+    /// Returns whether the shoe was reshuffled.
+    pub fn start_round(&mut self) -> bool {
+        if !self.cut_card_reached() {
+            return false;
+        }
+        self.counts = [self.decks; DECK_SIZE];
+        true
+    }
+
+    /// Deal a card from the shoe.
+    pub fn draw(&mut self, rng: &mut GameRng) -> Option<u8> {
+        self.draw_where(rng, |_| true)
+    }
+
+    /// Deal a card from those left in the shoe that `keep` accepts.
+    pub fn draw_where(&mut self, rng: &mut GameRng, keep: impl Fn(u8) -> bool) -> Option<u8> {
+        let counts: [u8; DECK_SIZE] = std::array::from_fn(|card| {
+            if keep(card as u8) {
+                self.counts[card]
+            } else {
+                0
+            }
+        });
+        let total = counts.iter().map(|&count| count as usize).sum();
+        let mut idx = rng.next_bounded_usize(total);
+        for (card, &count) in counts.iter().enumerate() {
+            if idx < count as usize {
+                self.counts[card] -= 1;
+                rng.record(RngDraw::Card(card as u8));
+                return Some(card as u8);
+            }
+            idx -= count as usize;
+        }
+        None
+    }
+
+    /// Return a card drawn (but not dealt) to the shoe.
+    pub fn put_back(&mut self, card: u8) {
+        if let Some(count) = self.counts.get_mut(card as usize) {
+            *count = count.saturating_add(1).min(self.decks);
+        }
+    }
+}
+
+impl BlobField for Shoe {
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.decks)
+            .put(&self.penetration)
+            .put(&self.counts);
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        let shoe = Self {
+            decks: reader.get()?,
+            penetration: reader.get()?,
+            counts: reader.get()?,
+        };
+        let valid = (MIN_SHOE_DECKS..=MAX_SHOE_DECKS).contains(&shoe.decks)
+            && (MIN_SHOE_PENETRATION..=MAX_SHOE_PENETRATION).contains(&shoe.penetration)
+            && shoe.counts.iter().all(|&count| count <= shoe.decks);
+        valid.then_some(shoe)
+    }
+}
+
+/// Where a move's cards come from: the session's persistent shoe, or a freshly shuffled one.
+pub enum CardSource<'a> {
+    Shoe(&'a mut Shoe),
+    Fresh(Vec<u8>),
+}
+
+impl<'a> CardSource<'a> {
+    /// Deal from `shoe` if the table keeps one, otherwise from a fresh shoe of `decks` decks
+    /// without the cards already in play.
+    pub fn new(shoe: Option<&'a mut Shoe>, rng: &mut GameRng, decks: u8, in_play: &[u8]) -> Self {
+        match shoe {
+            Some(shoe) => Self::Shoe(shoe),
+            None if in_play.is_empty() => Self::Fresh(rng.create_shoe(decks)),
+            None => Self::Fresh(rng.create_shoe_excluding(in_play, decks)),
+        }
+    }
+
+    pub fn draw(&mut self, rng: &mut GameRng) -> Option<u8> {
+        match self {
+            Self::Shoe(shoe) => shoe.draw(rng),
+            Self::Fresh(deck) => rng.draw_card(deck),
+        }
+    }
+
+    /// Draw a card from those `keep` accepts.
+    pub fn draw_where(&mut self, rng: &mut GameRng, keep: impl Fn(u8) -> bool) -> Option<u8> {
+        match self {
+            Self::Shoe(shoe) => shoe.draw_where(rng, keep),
+            Self::Fresh(deck) => {
+                let eligible: Vec<usize> = (0..deck.len()).filter(|&i| keep(deck[i])).collect();
+                if eligible.is_empty() {
+                    return None;
+                }
+                let card = deck.swap_remove(eligible[rng.next_bounded_usize(eligible.len())]);
+                rng.record(RngDraw::Card(card));
+                Some(card)
+            }
+        }
+    }
+
+    /// Return a card drawn (but not dealt).
+    pub fn put_back(&mut self, card: u8) {
+        match self {
+            Self::Shoe(shoe) => shoe.put_back(card),
+            Self::Fresh(deck) => deck.push(card),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::{create_network_keypair, create_seed};
+
+    fn test_rng() -> GameRng {
+        let (network_secret, _) = create_network_keypair();
+        GameRng::new(&create_seed(&network_secret, 1), 1, 0)
+    }
+
+    #[test]
+    fn test_draw_and_reshuffle() {
+        let mut rng = test_rng();
+        let mut shoe = Shoe::new(MIN_SHOE_DECKS, MIN_SHOE_PENETRATION);
+        let size = DECK_SIZE * MIN_SHOE_DECKS as usize;
+        assert_eq!(shoe.remaining(), size);
+        assert!(!shoe.start_round());
+
+        // Cards dealt are gone from the shoe until it is reshuffled
+        let mut dealt = [0u8; DECK_SIZE];
+        for _ in 0..size / 2 - 1 {
+            let card = shoe.draw(&mut rng).unwrap();
+            dealt[card as usize] += 1;
+            assert!(!shoe.cut_card_reached());
+        }
+        for (card, &count) in dealt.iter().enumerate() {
+            assert_eq!(shoe.counts[card], MIN_SHOE_DECKS - count);
+        }
+
+        // The cut card comes out half way through, and the next round reshuffles
+        shoe.draw(&mut rng).unwrap();
+        assert!(shoe.cut_card_reached());
+        assert!(shoe.start_round());
+        assert_eq!(shoe, Shoe::new(MIN_SHOE_DECKS, MIN_SHOE_PENETRATION));
+
+        // An empty shoe deals nothing
+        shoe.counts = [0; DECK_SIZE];
+        assert_eq!(shoe.draw(&mut rng), None);
+    }
+
+    #[test]
+    fn test_draw_where() {
+        let mut rng = test_rng();
+        let mut shoe = Shoe::new(MIN_SHOE_DECKS, MIN_SHOE_PENETRATION);
+
+        // Only the cards accepted are dealt, until there are none left
+        for _ in 0..MIN_SHOE_DECKS {
+            assert_eq!(shoe.draw_where(&mut rng, |card| card == 7), Some(7));
+        }
+        assert_eq!(shoe.draw_where(&mut rng, |card| card == 7), None);
+        assert_eq!(shoe.counts[7], 0);
+
+        // A card put back can be dealt again
+        shoe.put_back(7);
+        assert_eq!(shoe.draw_where(&mut rng, |card| card == 7), Some(7));
+
+        let mut deck = CardSource::Fresh(vec![3, 7, 20]);
+        assert_eq!(deck.draw_where(&mut rng, |card| card > 5), Some(7));
+        assert_eq!(deck.draw_where(&mut rng, |card| card > 5), Some(20));
+        assert_eq!(deck.draw_where(&mut rng, |card| card > 5), None);
+        deck.put_back(20);
+        assert_eq!(deck.draw_where(&mut rng, |card| card > 5), Some(20));
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert_eq!(Shoe::from_config(&GameConfig::default()), None);
+        let config = GameConfig {
+            shoe_decks: MAX_SHOE_DECKS,
+            shoe_penetration: MAX_SHOE_PENETRATION,
+            ..GameConfig::default()
+        };
+        assert_eq!(
+            Shoe::from_config(&config),
+            Some(Shoe::new(MAX_SHOE_DECKS, MAX_SHOE_PENETRATION))
+        );
+    }
+
+    #[test]
+    fn test_blob() {
+        let mut shoe = Shoe::new(MAX_SHOE_DECKS, MAX_SHOE_PENETRATION);
+        shoe.counts[0] = 0;
+        let mut writer = BlobWriter::new(1);
+        writer.put(&shoe);
+        let blob = writer.finish();
+        assert_eq!(blob.len(), 1 + Shoe::BLOB_SIZE);
+        let mut reader = BlobReader::new(&blob).unwrap();
+        assert_eq!(reader.get(), Some(shoe.clone()));
+
+        // Counts beyond the shoe's decks are rejected
+        shoe.counts[0] = MAX_SHOE_DECKS + 1;
+        let mut writer = BlobWriter::new(1);
+        writer.put(&shoe);
+        let blob = writer.finish();
+        assert_eq!(BlobReader::new(&blob).unwrap().get::<Shoe>(), None);
+    }
+}
@@ -1,7 +1,10 @@
 //! Sic Bo game implementation with multi-bet support.
 //!
 //! State blob format:
-//! [bet_count:u8] [bets:SicBoBet×count] [die1:u8]? [die2:u8]? [die3:u8]?
+//! [bet_count:u8] [bets:SicBoBet×count] [die1:u8]? [die2:u8]? [die3:u8]? [paytable:u8]?
+//!
+//! The paytable (0 = Macau, 1 = Atlantic City) is chosen from the game config when the session
+//! starts; blobs without it use the Macau table.
 //!
 //! Each SicBoBet (10 bytes):
 //! [bet_type:u8] [number:u8] [amount:u64 BE]
@@ -10,25 +13,87 @@
 //! Action 0: Place bet - [0, bet_type, number, amount_bytes...]
 //! Action 1: Roll dice and resolve - [1]
 //! Action 2: Clear bets - [2]
+//! Action 3: Place several bets at once - [3, version, count, bets:SicBoBet×count] (version 1);
+//!           the batch is placed whole or rejected whole
+//! Action 4: Roll `rounds` times and resolve the bets on each roll (auto-play) - [4, rounds];
+//!           each roll stakes the bets again, and the rolls are settled together
 //!
 //! Bet types:
 //! 0 = Small (4-10, 1:1) - loses on triple
 //! 1 = Big (11-17, 1:1) - loses on triple
 //! 2 = Odd total (1:1)
 //! 3 = Even total (1:1)
-//! 4 = Specific triple (150:1) - number = 1-6
-//! 5 = Any triple (24:1)
-//! 6 = Specific double (8:1) - number = 1-6
-//! 7 = Total of N (various payouts) - number = 3-18
+//! 4 = Specific triple (150:1, Atlantic City 180:1) - number = 1-6
+//! 5 = Any triple (24:1, Atlantic City 30:1)
+//! 6 = Specific double (8:1, Atlantic City 11:1) - number = 1-6
+//! 7 = Total of N (various payouts, see `total_payout`) - number = 3-18
 //! 8 = Single number appears (1:1 to 3:1) - number = 1-6
 //! 9 = Domino (two faces) (5:1) - number = (min<<4)|max, min/max in 1-6 and min<max
 //! 10 = Three-Number Easy Hop (30:1) - number = 6-bit mask of chosen numbers (exactly 3 bits set)
 //! 11 = Three-Number Hard Hop (50:1) - number = (double<<4)|single, both 1-6 and distinct
 //! 12 = Four-Number Easy Hop (7:1) - number = 6-bit mask of chosen numbers (exactly 4 bits set)
 
+use super::state_blob::{
+    impl_blob_field_enum, BlobField, BlobReader, BlobWriter, StateBlob, UNVERSIONED,
+};
 use super::super_mode::apply_super_multiplier_total;
 use super::{CasinoGame, GameError, GameResult, GameRng};
-use nullspace_types::casino::GameSession;
+use nullspace_types::casino::{GameConfig, GameSession};
+
+/// Maximum number of bets per session.
+const MAX_BETS: usize = 20;
+
+/// Current version of the batch bet payload.
+const BATCH_VERSION: u8 = 1;
+
+/// Sic Bo paytables (the game config's paytable).
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Paytable {
+    /// The standard table.
+    #[default]
+    Macau = 0,
+    /// Pays more on small totals, specific doubles and triples.
+    AtlanticCity = 1,
+}
+
+impl TryFrom<u8> for Paytable {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Paytable::Macau),
+            1 => Ok(Paytable::AtlanticCity),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+impl Paytable {
+    /// Specific triple payout ("to 1").
+    fn specific_triple(self) -> u64 {
+        match self {
+            Paytable::Macau => 150,
+            Paytable::AtlanticCity => 180,
+        }
+    }
+
+    /// Any triple payout ("to 1").
+    fn any_triple(self) -> u64 {
+        match self {
+            Paytable::Macau => 24,
+            Paytable::AtlanticCity => 30,
+        }
+    }
+
+    /// Specific double payout ("to 1").
+    fn specific_double(self) -> u64 {
+        match self {
+            Paytable::Macau => 8,
+            Paytable::AtlanticCity => 11,
+        }
+    }
+}
 
 /// Sic Bo bet types.
 #[repr(u8)]
@@ -80,34 +145,29 @@ pub struct SicBoBet {
     pub amount: u64,
 }
 
-impl SicBoBet {
-    pub fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::with_capacity(10);
-        bytes.push(self.bet_type as u8);
-        bytes.push(self.number);
-        bytes.extend_from_slice(&self.amount.to_be_bytes());
-        bytes
+impl BlobField for SicBoBet {
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.bet_type)
+            .put(&self.number)
+            .put(&self.amount);
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
-        if bytes.len() < 10 {
-            return None;
-        }
-        let bet_type = BetType::try_from(bytes[0]).ok()?;
-        let number = bytes[1];
-        let amount = u64::from_be_bytes(bytes[2..10].try_into().ok()?);
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
         Some(Self {
-            bet_type,
-            number,
-            amount,
+            bet_type: reader.get()?,
+            number: reader.get()?,
+            amount: reader.get()?,
         })
     }
 }
 
 /// Sic Bo game state.
+#[derive(Debug, PartialEq, Eq)]
 struct SicBoState {
     bets: Vec<SicBoBet>,
     dice: Option<[u8; 3]>,
+    paytable: Paytable,
 }
 
 impl SicBoState {
@@ -115,72 +175,102 @@ impl SicBoState {
         Self {
             bets: Vec::new(),
             dice: None,
+            paytable: Paytable::default(),
         }
     }
+}
 
-    fn from_bytes(bytes: &[u8]) -> Option<Self> {
-        if bytes.is_empty() {
-            return Some(Self::new());
-        }
-
-        let bet_count = bytes[0] as usize;
-
-        // Validate bet count against maximum to prevent DoS via large allocations
-        const MAX_BETS: usize = 20;
-        if bet_count > MAX_BETS {
-            return None;
-        }
-
-        let expected_bet_bytes = bet_count * 10;
+impl StateBlob for SicBoState {
+    const VERSION: u8 = UNVERSIONED;
 
-        if bytes.len() < 1 + expected_bet_bytes {
-            return None;
+    fn write(&self, writer: &mut BlobWriter) {
+        writer.put_list(&self.bets);
+        if let Some(dice) = &self.dice {
+            writer.put(dice);
         }
+        writer.put(&self.paytable);
+    }
 
-        let mut bets = Vec::with_capacity(bet_count);
-        let mut offset = 1;
-        for _ in 0..bet_count {
-            let bet = SicBoBet::from_bytes(&bytes[offset..])?;
-            bets.push(bet);
-            offset += 10;
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        if reader.remaining() == 0 {
+            return Some(Self::new());
         }
 
-        // Optional dice result (3 bytes)
-        let dice = if bytes.len() >= offset + 3 {
-            Some([bytes[offset], bytes[offset + 1], bytes[offset + 2]])
-        } else {
-            None
+        let bets = reader.get_list(MAX_BETS)?;
+        // Optional dice result (3 bytes) and paytable (1 byte), told apart by what remains
+        let dice = match reader.remaining() {
+            0 | 1 => None,
+            3 | 4 => Some(reader.get()?),
+            _ => return None,
         };
+        let paytable = reader.get_or(Paytable::default())?;
 
-        Some(Self { bets, dice })
+        Some(Self {
+            bets,
+            dice,
+            paytable,
+        })
     }
+}
+
+impl_blob_field_enum!(Paytable, BetType);
 
-    fn to_bytes(&self) -> Vec<u8> {
-        // Capacity: 1 (bet count) + bets (10 bytes each) + 3 (optional dice)
-        let capacity = 1 + (self.bets.len() * 10) + if self.dice.is_some() { 3 } else { 0 };
-        let mut bytes = Vec::with_capacity(capacity);
-        bytes.push(self.bets.len() as u8);
-        for bet in &self.bets {
-            bytes.extend(bet.to_bytes());
+/// Check that `number` is a valid target for `bet_type`.
+fn validate_bet(bet_type: BetType, number: u8) -> Result<(), GameError> {
+    match bet_type {
+        BetType::SpecificTriple | BetType::SpecificDouble | BetType::Single => {
+            if number < 1 || number > 6 {
+                return Err(GameError::InvalidPayload);
+            }
+        }
+        BetType::Total => {
+            if number < 3 || number > 18 {
+                return Err(GameError::InvalidPayload);
+            }
+        }
+        BetType::Domino => {
+            let min = (number >> 4) & 0x0f;
+            let max = number & 0x0f;
+            if min < 1 || min > 6 || max < 1 || max > 6 || min >= max {
+                return Err(GameError::InvalidPayload);
+            }
+        }
+        BetType::ThreeNumberEasyHop => {
+            if number & !0x3F != 0 || number.count_ones() != 3 {
+                return Err(GameError::InvalidPayload);
+            }
+        }
+        BetType::ThreeNumberHardHop => {
+            let double = (number >> 4) & 0x0f;
+            let single = number & 0x0f;
+            if double < 1 || double > 6 || single < 1 || single > 6 || double == single {
+                return Err(GameError::InvalidPayload);
+            }
         }
-        if let Some(dice) = self.dice {
-            bytes.extend_from_slice(&dice);
+        BetType::FourNumberEasyHop => {
+            if number & !0x3F != 0 || number.count_ones() != 4 {
+                return Err(GameError::InvalidPayload);
+            }
         }
-        bytes
+        _ => {}
     }
+    Ok(())
 }
 
-/// Payout table for total bets.
-fn total_payout(total: u8) -> u64 {
-    match total {
-        3 | 18 => 180,
-        4 | 17 => 50,
-        5 | 16 => 18,
-        6 | 15 => 14,
-        7 | 14 => 12,
-        8 | 13 => 8,
-        9 | 12 => 6,
-        10 | 11 => 6,
+/// Payout table for total bets ("to 1").
+fn total_payout(total: u8, paytable: Paytable) -> u64 {
+    match (total, paytable) {
+        (3 | 18, _) => 180,
+        (4 | 17, Paytable::Macau) => 50,
+        (4 | 17, Paytable::AtlanticCity) => 60,
+        (5 | 16, Paytable::Macau) => 18,
+        (5 | 16, Paytable::AtlanticCity) => 30,
+        (6 | 15, Paytable::Macau) => 14,
+        (6 | 15, Paytable::AtlanticCity) => 17,
+        (7 | 14, _) => 12,
+        (8 | 13, _) => 8,
+        (9 | 12, _) => 6,
+        (10 | 11, _) => 6,
         _ => 0,
     }
 }
@@ -209,8 +299,27 @@ fn dice_mask(dice: &[u8; 3]) -> u8 {
     mask
 }
 
+/// Roll the dice for `state`'s bets and return what they returned (after any super multiplier).
+fn roll(state: &mut SicBoState, session: &GameSession, rng: &mut GameRng) -> u64 {
+    // Roll three dice
+    let dice: [u8; 3] = [rng.roll_die(), rng.roll_die(), rng.roll_die()];
+    state.dice = Some(dice);
+
+    let total_winnings: u64 = state
+        .bets
+        .iter()
+        .map(|bet| calculate_bet_payout(bet, &dice, state.paytable))
+        .sum();
+    if session.super_mode.is_active && total_winnings > 0 {
+        let dice_total = dice.iter().sum::<u8>();
+        apply_super_multiplier_total(dice_total, &session.super_mode.multipliers, total_winnings)
+    } else {
+        total_winnings
+    }
+}
+
 /// Calculate payout for a single bet given the dice result.
-fn calculate_bet_payout(bet: &SicBoBet, dice: &[u8; 3]) -> u64 {
+fn calculate_bet_payout(bet: &SicBoBet, dice: &[u8; 3], paytable: Paytable) -> u64 {
     let total: u8 = dice.iter().sum();
     let triple = is_triple(dice);
 
@@ -245,28 +354,32 @@ fn calculate_bet_payout(bet: &SicBoBet, dice: &[u8; 3]) -> u64 {
         }
         BetType::SpecificTriple => {
             if triple && dice[0] == bet.number {
-                bet.amount.saturating_mul(151) // 150:1 -> 151x
+                bet.amount
+                    .saturating_mul(paytable.specific_triple().saturating_add(1))
             } else {
                 0
             }
         }
         BetType::AnyTriple => {
             if triple {
-                bet.amount.saturating_mul(25) // 24:1 -> 25x
+                bet.amount
+                    .saturating_mul(paytable.any_triple().saturating_add(1))
             } else {
                 0
             }
         }
         BetType::SpecificDouble => {
             if count_number(dice, bet.number) >= 2 {
-                bet.amount.saturating_mul(9) // 8:1 -> 9x
+                bet.amount
+                    .saturating_mul(paytable.specific_double().saturating_add(1))
             } else {
                 0
             }
         }
         BetType::Total => {
             if total == bet.number {
-                bet.amount.saturating_mul(total_payout(bet.number) + 1)
+                bet.amount
+                    .saturating_mul(total_payout(bet.number, paytable) + 1)
             } else {
                 0
             }
@@ -339,12 +452,23 @@ fn calculate_bet_payout(bet: &SicBoBet, dice: &[u8; 3]) -> u64 {
 pub struct SicBo;
 
 impl CasinoGame for SicBo {
+    fn bet_count(session: &GameSession) -> usize {
+        SicBoState::from_blob(&session.state_blob).map_or(0, |state| state.bets.len())
+    }
+
     fn init(session: &mut GameSession, _rng: &mut GameRng) -> GameResult {
         let state = SicBoState::new();
-        session.state_blob = state.to_bytes();
+        session.state_blob = state.to_blob();
         GameResult::Continue
     }
 
+    fn configure(session: &mut GameSession, config: &GameConfig) {
+        if let Some(mut state) = SicBoState::from_blob(&session.state_blob) {
+            state.paytable = Paytable::try_from(config.paytable).unwrap_or_default();
+            session.state_blob = state.to_blob();
+        }
+    }
+
     fn process_move(
         session: &mut GameSession,
         payload: &[u8],
@@ -359,8 +483,7 @@ impl CasinoGame for SicBo {
         }
 
         let action = payload[0];
-        let mut state =
-            SicBoState::from_bytes(&session.state_blob).ok_or(GameError::InvalidMove)?;
+        let mut state = SicBoState::from_blob(&session.state_blob).ok_or(GameError::InvalidMove)?;
 
         match action {
             // Action 0: Place bet
@@ -377,56 +500,20 @@ impl CasinoGame for SicBo {
                         .map_err(|_| GameError::InvalidPayload)?,
                 );
 
-                // Validate number for bet types that need it
-                match bet_type {
-                    BetType::SpecificTriple | BetType::SpecificDouble | BetType::Single => {
-                        if number < 1 || number > 6 {
-                            return Err(GameError::InvalidPayload);
-                        }
-                    }
-                    BetType::Total => {
-                        if number < 3 || number > 18 {
-                            return Err(GameError::InvalidPayload);
-                        }
-                    }
-                    BetType::Domino => {
-                        let min = (number >> 4) & 0x0f;
-                        let max = number & 0x0f;
-                        if min < 1 || min > 6 || max < 1 || max > 6 || min >= max {
-                            return Err(GameError::InvalidPayload);
-                        }
-                    }
-                    BetType::ThreeNumberEasyHop => {
-                        if number & !0x3F != 0 || number.count_ones() != 3 {
-                            return Err(GameError::InvalidPayload);
-                        }
-                    }
-                    BetType::ThreeNumberHardHop => {
-                        let double = (number >> 4) & 0x0f;
-                        let single = number & 0x0f;
-                        if double < 1 || double > 6 || single < 1 || single > 6 || double == single
-                        {
-                            return Err(GameError::InvalidPayload);
-                        }
-                    }
-                    BetType::FourNumberEasyHop => {
-                        if number & !0x3F != 0 || number.count_ones() != 4 {
-                            return Err(GameError::InvalidPayload);
-                        }
-                    }
-                    _ => {}
-                }
-
+                validate_bet(bet_type, number)?;
                 if amount == 0 {
                     return Err(GameError::InvalidPayload);
                 }
+                if state.bets.len() >= MAX_BETS {
+                    return Err(GameError::InvalidMove);
+                }
 
                 state.bets.push(SicBoBet {
                     bet_type,
                     number,
                     amount,
                 });
-                session.state_blob = state.to_bytes();
+                session.state_blob = state.to_blob();
                 session.move_count += 1;
                 Ok(GameResult::ContinueWithUpdate {
                     payout: -(amount as i64),
@@ -439,36 +526,18 @@ impl CasinoGame for SicBo {
                     return Err(GameError::InvalidPayload); // Must have at least one bet
                 }
 
-                // Roll three dice
-                let dice: [u8; 3] = [rng.roll_die(), rng.roll_die(), rng.roll_die()];
-                state.dice = Some(dice);
-
                 // Calculate total winnings and losses
                 let total_bet: u64 = state.bets.iter().map(|b| b.amount).sum();
-                let total_winnings: u64 = state
-                    .bets
-                    .iter()
-                    .map(|bet| calculate_bet_payout(bet, &dice))
-                    .sum();
+                let final_winnings = roll(&mut state, session, rng);
 
-                session.state_blob = state.to_bytes();
+                session.state_blob = state.to_blob();
                 session.move_count += 1;
                 session.is_complete = true;
 
                 // Determine overall result.
                 // All wagers were deducted via ContinueWithUpdate at bet time, so the completion
                 // result should return the total amount to credit back (if any).
-                if total_winnings > 0 {
-                    let final_winnings = if session.super_mode.is_active {
-                        let dice_total = dice.iter().sum::<u8>();
-                        apply_super_multiplier_total(
-                            dice_total,
-                            &session.super_mode.multipliers,
-                            total_winnings,
-                        )
-                    } else {
-                        total_winnings
-                    };
+                if final_winnings > 0 {
                     Ok(GameResult::Win(final_winnings))
                 } else {
                     Ok(GameResult::LossPreDeducted(total_bet))
@@ -478,10 +547,67 @@ impl CasinoGame for SicBo {
             // Action 2: Clear all bets
             2 => {
                 state.bets.clear();
-                session.state_blob = state.to_bytes();
+                session.state_blob = state.to_blob();
                 Ok(GameResult::Continue)
             }
 
+            // Action 3: Place a batch of bets
+            3 => {
+                if payload.len() < 3 || payload[1] != BATCH_VERSION {
+                    return Err(GameError::InvalidPayload);
+                }
+                let count = payload[2] as usize;
+                if count == 0 || payload.len() != 3 + count * 10 {
+                    return Err(GameError::InvalidPayload);
+                }
+                if state.bets.len() + count > MAX_BETS {
+                    return Err(GameError::InvalidMove);
+                }
+
+                // Validate every bet before placing any
+                let mut cost: u64 = 0;
+                let mut bets = Vec::with_capacity(count);
+                for bytes in payload[3..].chunks_exact(10) {
+                    let bet = BlobReader::unversioned(bytes)
+                        .get::<SicBoBet>()
+                        .ok_or(GameError::InvalidPayload)?;
+                    if bet.amount == 0 {
+                        return Err(GameError::InvalidPayload);
+                    }
+                    validate_bet(bet.bet_type, bet.number)?;
+                    cost = cost
+                        .checked_add(bet.amount)
+                        .ok_or(GameError::InvalidPayload)?;
+                    bets.push(bet);
+                }
+                let payout = i64::try_from(cost).map_err(|_| GameError::InvalidPayload)?;
+
+                state.bets.extend(bets);
+                session.state_blob = state.to_blob();
+                session.move_count += 1;
+                Ok(GameResult::ContinueWithUpdate { payout: -payout })
+            }
+
+            // Action 4: Roll several times, resolving the bets on each roll
+            4 => {
+                if payload.len() != 2 {
+                    return Err(GameError::InvalidPayload);
+                }
+                if state.bets.is_empty() {
+                    return Err(GameError::InvalidPayload); // Must have at least one bet
+                }
+
+                let total_bet: u64 = state.bets.iter().map(|b| b.amount).sum();
+                let result = super::auto_play(rng, payload[1], total_bet, |rng| {
+                    roll(&mut state, session, rng)
+                })?;
+
+                session.state_blob = state.to_blob();
+                session.move_count += 1;
+                session.is_complete = true;
+                Ok(result)
+            }
+
             _ => Err(GameError::InvalidPayload),
         }
     }
@@ -512,6 +638,9 @@ mod tests {
             super_mode: nullspace_types::casino::SuperModeState::default(),
             is_tournament: false,
             tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
         }
     }
 
@@ -540,13 +669,101 @@ mod tests {
 
     #[test]
     fn test_total_payout() {
-        assert_eq!(total_payout(3), 180);
-        assert_eq!(total_payout(4), 50);
-        assert_eq!(total_payout(17), 50);
-        assert_eq!(total_payout(18), 180);
-        assert_eq!(total_payout(5), 18);
-        assert_eq!(total_payout(10), 6);
-        assert_eq!(total_payout(11), 6);
+        assert_eq!(total_payout(3, Paytable::Macau), 180);
+        assert_eq!(total_payout(4, Paytable::Macau), 50);
+        assert_eq!(total_payout(17, Paytable::Macau), 50);
+        assert_eq!(total_payout(18, Paytable::Macau), 180);
+        assert_eq!(total_payout(5, Paytable::Macau), 18);
+        assert_eq!(total_payout(10, Paytable::Macau), 6);
+        assert_eq!(total_payout(11, Paytable::Macau), 6);
+    }
+
+    #[test]
+    fn test_paytable_returns() {
+        // Total return over all 216 rolls of a 1-unit bet (Wizard of Odds Macau and Atlantic
+        // City tables), e.g. Small returns 210/216 for a 2.78% house edge.
+        let cases: [(BetType, u8, u64, u64); 20] = [
+            (BetType::Small, 0, 210, 210),
+            (BetType::Big, 0, 210, 210),
+            (BetType::Odd, 0, 210, 210),
+            (BetType::Even, 0, 210, 210),
+            (BetType::SpecificTriple, 1, 151, 181),
+            (BetType::AnyTriple, 0, 150, 186),
+            (BetType::SpecificDouble, 1, 144, 192),
+            (BetType::Total, 4, 153, 183),
+            (BetType::Total, 5, 114, 186),
+            (BetType::Total, 6, 150, 180),
+            (BetType::Total, 7, 195, 195),
+            (BetType::Total, 8, 189, 189),
+            (BetType::Total, 9, 175, 175),
+            (BetType::Total, 10, 189, 189),
+            (BetType::Total, 17, 153, 183),
+            (BetType::Single, 1, 199, 199),
+            (BetType::Domino, 0x12, 180, 180),
+            (BetType::ThreeNumberEasyHop, 0b000111, 186, 186),
+            (BetType::ThreeNumberHardHop, 0x12, 153, 153),
+            (BetType::FourNumberEasyHop, 0b001111, 192, 192),
+        ];
+        for (bet_type, number, macau, atlantic_city) in cases {
+            let bet = SicBoBet {
+                bet_type,
+                number,
+                amount: 1,
+            };
+            for (paytable, expected) in [
+                (Paytable::Macau, macau),
+                (Paytable::AtlanticCity, atlantic_city),
+            ] {
+                let mut total = 0;
+                for d1 in 1..=6 {
+                    for d2 in 1..=6 {
+                        for d3 in 1..=6 {
+                            total += calculate_bet_payout(&bet, &[d1, d2, d3], paytable);
+                        }
+                    }
+                }
+                assert_eq!(total, expected, "{bet_type:?} {number} {paytable:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_configure_paytable() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+
+        SicBo::init(&mut session, &mut rng);
+        SicBo::configure(
+            &mut session,
+            &GameConfig {
+                paytable: 1,
+                ..GameConfig::default()
+            },
+        );
+        let state = SicBoState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.paytable, Paytable::AtlanticCity);
+
+        // The paytable survives bets and the roll.
+        let payload = place_bet_payload(BetType::AnyTriple as u8, 0, 100);
+        SicBo::process_move(&mut session, &payload, &mut rng).expect("Failed to place bet");
+        SicBo::process_move(&mut session, &[1], &mut rng).expect("Failed to roll");
+        let state = SicBoState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert!(state.dice.is_some());
+        assert_eq!(state.paytable, Paytable::AtlanticCity);
+
+        // Legacy blobs (with or without dice) use the Macau table.
+        let legacy = &session.state_blob[..session.state_blob.len() - 1];
+        let state = SicBoState::from_blob(legacy).expect("Failed to parse legacy state");
+        assert!(state.dice.is_some());
+        assert_eq!(state.paytable, Paytable::Macau);
+        let state = SicBoState::from_blob(&legacy[..legacy.len() - 3]).expect("legacy state");
+        assert!(state.dice.is_none());
+        assert_eq!(state.paytable, Paytable::Macau);
+        assert!(SicBoState::from_blob(&legacy[..legacy.len() - 1]).is_none());
+
+        let state = SicBoState::from_blob(&session.state_blob).expect("Failed to parse state");
+        crate::casino::state_blob::fuzz::roundtrip(&[SicBoState::new(), state]);
     }
 
     #[test]
@@ -559,14 +776,23 @@ mod tests {
             amount: 10,
         };
 
-        assert_eq!(calculate_bet_payout(&bet, &[1, 2, 5]), 10 * 6);
-        assert_eq!(calculate_bet_payout(&bet, &[2, 2, 5]), 10 * 6);
-        assert_eq!(calculate_bet_payout(&bet, &[2, 5, 5]), 10 * 6);
+        assert_eq!(
+            calculate_bet_payout(&bet, &[1, 2, 5], Paytable::Macau),
+            10 * 6
+        );
+        assert_eq!(
+            calculate_bet_payout(&bet, &[2, 2, 5], Paytable::Macau),
+            10 * 6
+        );
+        assert_eq!(
+            calculate_bet_payout(&bet, &[2, 5, 5], Paytable::Macau),
+            10 * 6
+        );
 
         // Missing one of the faces loses.
-        assert_eq!(calculate_bet_payout(&bet, &[2, 2, 2]), 0);
-        assert_eq!(calculate_bet_payout(&bet, &[5, 5, 5]), 0);
-        assert_eq!(calculate_bet_payout(&bet, &[1, 3, 4]), 0);
+        assert_eq!(calculate_bet_payout(&bet, &[2, 2, 2], Paytable::Macau), 0);
+        assert_eq!(calculate_bet_payout(&bet, &[5, 5, 5], Paytable::Macau), 0);
+        assert_eq!(calculate_bet_payout(&bet, &[1, 3, 4], Paytable::Macau), 0);
     }
 
     #[test]
@@ -577,10 +803,16 @@ mod tests {
             amount: 10,
         };
 
-        assert_eq!(calculate_bet_payout(&bet, &[1, 3, 5]), 10 * 31);
-        assert_eq!(calculate_bet_payout(&bet, &[5, 1, 3]), 10 * 31);
-        assert_eq!(calculate_bet_payout(&bet, &[1, 1, 5]), 0);
-        assert_eq!(calculate_bet_payout(&bet, &[1, 3, 6]), 0);
+        assert_eq!(
+            calculate_bet_payout(&bet, &[1, 3, 5], Paytable::Macau),
+            10 * 31
+        );
+        assert_eq!(
+            calculate_bet_payout(&bet, &[5, 1, 3], Paytable::Macau),
+            10 * 31
+        );
+        assert_eq!(calculate_bet_payout(&bet, &[1, 1, 5], Paytable::Macau), 0);
+        assert_eq!(calculate_bet_payout(&bet, &[1, 3, 6], Paytable::Macau), 0);
     }
 
     #[test]
@@ -591,10 +823,16 @@ mod tests {
             amount: 10,
         };
 
-        assert_eq!(calculate_bet_payout(&bet, &[2, 2, 4]), 10 * 51);
-        assert_eq!(calculate_bet_payout(&bet, &[2, 4, 2]), 10 * 51);
-        assert_eq!(calculate_bet_payout(&bet, &[2, 4, 4]), 0);
-        assert_eq!(calculate_bet_payout(&bet, &[2, 2, 2]), 0);
+        assert_eq!(
+            calculate_bet_payout(&bet, &[2, 2, 4], Paytable::Macau),
+            10 * 51
+        );
+        assert_eq!(
+            calculate_bet_payout(&bet, &[2, 4, 2], Paytable::Macau),
+            10 * 51
+        );
+        assert_eq!(calculate_bet_payout(&bet, &[2, 4, 4], Paytable::Macau), 0);
+        assert_eq!(calculate_bet_payout(&bet, &[2, 2, 2], Paytable::Macau), 0);
     }
 
     #[test]
@@ -605,10 +843,16 @@ mod tests {
             amount: 10,
         };
 
-        assert_eq!(calculate_bet_payout(&bet, &[1, 3, 4]), 10 * 8);
-        assert_eq!(calculate_bet_payout(&bet, &[6, 4, 3]), 10 * 8);
-        assert_eq!(calculate_bet_payout(&bet, &[1, 3, 5]), 0);
-        assert_eq!(calculate_bet_payout(&bet, &[1, 1, 4]), 0);
+        assert_eq!(
+            calculate_bet_payout(&bet, &[1, 3, 4], Paytable::Macau),
+            10 * 8
+        );
+        assert_eq!(
+            calculate_bet_payout(&bet, &[6, 4, 3], Paytable::Macau),
+            10 * 8
+        );
+        assert_eq!(calculate_bet_payout(&bet, &[1, 3, 5], Paytable::Macau), 0);
+        assert_eq!(calculate_bet_payout(&bet, &[1, 1, 4], Paytable::Macau), 0);
     }
 
     #[test]
@@ -654,7 +898,7 @@ mod tests {
         ));
 
         // Verify bet was stored
-        let state = SicBoState::from_bytes(&session.state_blob).expect("Failed to parse state");
+        let state = SicBoState::from_blob(&session.state_blob).expect("Failed to parse state");
         assert_eq!(state.bets.len(), 1);
         assert_eq!(state.bets[0].bet_type, BetType::Small);
         assert_eq!(state.bets[0].amount, 100);
@@ -684,7 +928,7 @@ mod tests {
         assert!(session.is_complete);
 
         // Verify dice were rolled and stored
-        let state = SicBoState::from_bytes(&session.state_blob).expect("Failed to parse state");
+        let state = SicBoState::from_blob(&session.state_blob).expect("Failed to parse state");
         assert!(state.dice.is_some());
         let dice = state.dice.expect("Dice should be rolled");
         for die in dice.iter() {
@@ -692,6 +936,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_auto_play() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        SicBo::init(&mut session, &mut rng);
+
+        // Rolling needs bets
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        assert!(matches!(
+            SicBo::process_move(&mut session, &[4, 5], &mut rng),
+            Err(GameError::InvalidPayload)
+        ));
+
+        // Place a Small bet and roll it five times
+        let payload = place_bet_payload(0, 0, 100);
+        SicBo::process_move(&mut session, &payload, &mut rng).expect("Failed to place bet");
+        let mut rng = GameRng::new(&seed, session.id, 2);
+        let result = SicBo::process_move(&mut session, &[4, 5], &mut rng).expect("Roll failed");
+        assert!(session.is_complete);
+
+        // Each roll pays the bet on its own
+        let rolls: Vec<[u8; 3]> = (0..5)
+            .map(|round| {
+                let mut rng = GameRng::new(&seed, session.id, 2).for_round(round);
+                [rng.roll_die(), rng.roll_die(), rng.roll_die()]
+            })
+            .collect();
+        let bet = SicBoBet {
+            bet_type: BetType::Small,
+            number: 0,
+            amount: 100,
+        };
+        let total_return: u64 = rolls
+            .iter()
+            .map(|dice| calculate_bet_payout(&bet, dice, Paytable::Macau))
+            .sum();
+        if total_return > 0 {
+            assert!(matches!(
+                result,
+                GameResult::WinWithExtraDeduction { payout, extra_deduction: 400 }
+                    if payout == total_return
+            ));
+        } else {
+            assert!(matches!(
+                result,
+                GameResult::LossPreDeductedWithExtraDeduction {
+                    total_loss: 500,
+                    extra_deduction: 400
+                }
+            ));
+        }
+        let state = SicBoState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.dice, rolls.last().copied());
+    }
+
     #[test]
     fn test_invalid_number() {
         let seed = create_test_seed();
@@ -757,7 +1057,7 @@ mod tests {
         SicBo::process_move(&mut session, &payload, &mut rng).expect("Failed to process move");
 
         // Verify bet was placed
-        let state = SicBoState::from_bytes(&session.state_blob).expect("Failed to parse state");
+        let state = SicBoState::from_blob(&session.state_blob).expect("Failed to parse state");
         assert_eq!(state.bets.len(), 1);
 
         // Clear bets
@@ -766,7 +1066,7 @@ mod tests {
         assert!(result.is_ok());
 
         // Verify bets were cleared
-        let state = SicBoState::from_bytes(&session.state_blob).expect("Failed to parse state");
+        let state = SicBoState::from_blob(&session.state_blob).expect("Failed to parse state");
         assert_eq!(state.bets.len(), 0);
     }
 
@@ -789,7 +1089,7 @@ mod tests {
         SicBo::process_move(&mut session, &payload, &mut rng).expect("Failed to process move");
 
         // Verify both bets were placed
-        let state = SicBoState::from_bytes(&session.state_blob).expect("Failed to parse state");
+        let state = SicBoState::from_blob(&session.state_blob).expect("Failed to parse state");
         assert_eq!(state.bets.len(), 2);
         assert_eq!(state.bets[0].bet_type, BetType::Small);
         assert_eq!(state.bets[1].bet_type, BetType::Big);
@@ -831,4 +1131,86 @@ mod tests {
             }
         }
     }
+
+    /// Helper to create a batch bet payload.
+    fn batch_payload(bets: &[(u8, u8, u64)]) -> Vec<u8> {
+        let mut payload = vec![3, BATCH_VERSION, bets.len() as u8];
+        for &(bet_type, number, amount) in bets {
+            payload.push(bet_type);
+            payload.push(number);
+            payload.extend_from_slice(&amount.to_be_bytes());
+        }
+        payload
+    }
+
+    #[test]
+    fn test_batch_bets() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(0);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        SicBo::init(&mut session, &mut rng);
+
+        // Small, Total of 10, and a 1-2 domino
+        let bets = [(0, 0, 50), (7, 10, 20), (9, 0x12, 5)];
+        let result = SicBo::process_move(&mut session, &batch_payload(&bets), &mut rng)
+            .expect("Failed to place batch");
+        assert!(matches!(
+            result,
+            GameResult::ContinueWithUpdate { payout: -75 }
+        ));
+        let state = SicBoState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.bets.len(), 3);
+        assert_eq!(state.bets[1].bet_type, BetType::Total);
+        assert_eq!(state.bets[2].number, 0x12);
+
+        // Malformed batches
+        let mut wrong_version = batch_payload(&bets);
+        wrong_version[1] = 0;
+        let mut truncated = batch_payload(&bets);
+        truncated.pop();
+        for payload in [wrong_version, truncated, batch_payload(&[]), vec![3]] {
+            assert!(matches!(
+                SicBo::process_move(&mut session, &payload, &mut rng),
+                Err(GameError::InvalidPayload)
+            ));
+        }
+
+        // One bad bet rejects the whole batch
+        for bad in [(13, 0, 10), (7, 2, 10), (9, 0x21, 10), (2, 0, 0)] {
+            assert!(matches!(
+                SicBo::process_move(&mut session, &batch_payload(&[(3, 0, 10), bad]), &mut rng),
+                Err(GameError::InvalidPayload)
+            ));
+        }
+        assert!(matches!(
+            SicBo::process_move(
+                &mut session,
+                &batch_payload(&[(2, 0, u64::MAX), (3, 0, 1)]),
+                &mut rng
+            ),
+            Err(GameError::InvalidPayload)
+        ));
+        let state = SicBoState::from_blob(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.bets.len(), 3);
+
+        // The batch must fit within the bet limit, as must single bets after it
+        let fill = vec![(2, 0, 1); MAX_BETS - 2];
+        assert!(matches!(
+            SicBo::process_move(&mut session, &batch_payload(&fill), &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+        SicBo::process_move(&mut session, &batch_payload(&fill[1..]), &mut rng)
+            .expect("Failed to place batch");
+        assert!(matches!(
+            SicBo::process_move(&mut session, &place_bet_payload(3, 0, 10), &mut rng),
+            Err(GameError::InvalidMove)
+        ));
+        assert_eq!(SicBo::bet_count(&session), MAX_BETS);
+
+        let result = SicBo::process_move(&mut session, &[1], &mut rng).expect("Roll failed");
+        assert!(matches!(
+            result,
+            GameResult::Win(_) | GameResult::LossPreDeducted(_)
+        ));
+    }
 }
@@ -0,0 +1,172 @@
+//! Side bets shared by the table games.
+//!
+//! Games describe the side bets held by a session as [SideBet]s (see `CasinoGame::side_bets`):
+//! what was staked, what it returns and, for progressives, the meter it feeds and the jackpot
+//! its hand hit. Settling a completed session (the progressive meters and the events reporting
+//! each side bet) then works from the descriptors rather than from each game's state layout.
+
+use nullspace_types::casino::{
+    HouseState, CARIBBEAN_STUD_PROGRESSIVE_BASE_JACKPOT, THREE_CARD_PROGRESSIVE_BASE_JACKPOT,
+    UTH_PROGRESSIVE_BASE_JACKPOT,
+};
+
+/// Progressive jackpot hit by a hand (a royal flush takes the meter, a straight flush a tenth).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JackpotTier {
+    None,
+    StraightFlush,
+    RoyalFlush,
+}
+
+/// A progressive jackpot meter kept by the house.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Progressive {
+    ThreeCard,
+    UltimateHoldem,
+    CaribbeanStud,
+}
+
+impl Progressive {
+    /// The jackpot the meter resets to (which the game's paytable pays).
+    pub fn base_jackpot(self) -> u64 {
+        match self {
+            Self::ThreeCard => THREE_CARD_PROGRESSIVE_BASE_JACKPOT,
+            Self::UltimateHoldem => UTH_PROGRESSIVE_BASE_JACKPOT,
+            Self::CaribbeanStud => CARIBBEAN_STUD_PROGRESSIVE_BASE_JACKPOT,
+        }
+    }
+
+    /// The meter in the house state.
+    pub fn meter(self, house: &mut HouseState) -> &mut u64 {
+        match self {
+            Self::ThreeCard => &mut house.three_card_progressive_jackpot,
+            Self::UltimateHoldem => &mut house.uth_progressive_jackpot,
+            Self::CaribbeanStud => &mut house.caribbean_stud_progressive_jackpot,
+        }
+    }
+}
+
+/// A side bet held by a session.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SideBet {
+    /// Identifies the bet within its game (e.g. `three_card::SIDE_BET_PAIR_PLUS`).
+    pub id: u8,
+    pub amount: u64,
+    /// What the bet returns, stake included (by the game's paytable, before any super mode
+    /// multiplier; only final once the session is complete).
+    pub returned: u64,
+    /// The progressive meter the bet feeds, and the jackpot its hand hit.
+    pub progressive: Option<(Progressive, JackpotTier)>,
+}
+
+impl SideBet {
+    /// A side bet paid by the game's paytable alone.
+    pub fn new(id: u8, amount: u64, returned: u64) -> Self {
+        Self {
+            id,
+            amount,
+            returned,
+            progressive: None,
+        }
+    }
+
+    /// A side bet that also feeds a progressive meter.
+    pub fn progressive(
+        id: u8,
+        amount: u64,
+        returned: u64,
+        progressive: Progressive,
+        tier: JackpotTier,
+    ) -> Self {
+        Self {
+            progressive: Some((progressive, tier)),
+            ..Self::new(id, amount, returned)
+        }
+    }
+}
+
+/// Total returned by the side bets.
+pub fn total_returned(bets: &[SideBet]) -> u64 {
+    bets.iter()
+        .fold(0u64, |total, bet| total.saturating_add(bet.returned))
+}
+
+/// Add a completed side bet to a progressive meter, returning what the meter pays on top of the
+/// game's own return (its growth over the base jackpot, which the game already pays).
+pub fn settle_progressive_meter(
+    meter: &mut u64,
+    base: u64,
+    progressive_bet: u64,
+    tier: JackpotTier,
+) -> u64 {
+    let jackpot = (*meter).max(base).saturating_add(progressive_bet);
+    let delta = match tier {
+        JackpotTier::RoyalFlush => progressive_bet.saturating_mul(jackpot.saturating_sub(base)),
+        JackpotTier::StraightFlush => {
+            progressive_bet.saturating_mul((jackpot / 10).saturating_sub(base / 10))
+        }
+        JackpotTier::None => 0,
+    };
+
+    *meter = if tier == JackpotTier::RoyalFlush {
+        base
+    } else {
+        jackpot
+    };
+    delta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settle_progressive_meter() {
+        let base = 10_000;
+
+        // Misses grow the meter by the side bet
+        let mut meter = 0;
+        assert_eq!(
+            settle_progressive_meter(&mut meter, base, 1, JackpotTier::None),
+            0
+        );
+        assert_eq!(meter, base + 1);
+
+        // A straight flush pays a tenth of the growth and leaves the meter running
+        let mut meter = base + 999;
+        assert_eq!(
+            settle_progressive_meter(&mut meter, base, 1, JackpotTier::StraightFlush),
+            100
+        );
+        assert_eq!(meter, base + 1_000);
+
+        // A royal flush pays the growth and resets the meter
+        assert_eq!(
+            settle_progressive_meter(&mut meter, base, 1, JackpotTier::RoyalFlush),
+            1_001
+        );
+        assert_eq!(meter, base);
+    }
+
+    #[test]
+    fn test_progressive_meters() {
+        let mut house = HouseState::new(0);
+        for progressive in [
+            Progressive::ThreeCard,
+            Progressive::UltimateHoldem,
+            Progressive::CaribbeanStud,
+        ] {
+            *progressive.meter(&mut house) += 1;
+            assert_eq!(
+                *progressive.meter(&mut house),
+                progressive.base_jackpot() + 1
+            );
+        }
+
+        let bets = [
+            SideBet::new(0, 5, 30),
+            SideBet::progressive(1, 1, 0, Progressive::ThreeCard, JackpotTier::None),
+        ];
+        assert_eq!(total_returned(&bets), 30);
+    }
+}
@@ -0,0 +1,815 @@
+//! Slots game implementation.
+//!
+//! Five reels of three rows stop at positions drawn from fixed reel strips. The session's bet
+//! is the total wager, split evenly across the paylines the player activates.
+//!
+//! State blob format:
+//! [stage:u8] [lines:u8] [freeSpins:u8] [lastWin:u64 BE] [totalWon:u64 BE] [grid:u8×15]
+//!
+//! Stage: 0 = Ready (awaiting the paid spin), 1 = FreeSpins (bonus in progress), 2 = Complete
+//!
+//! The grid holds the symbols of the last spin reel by reel (top row first) and is all zeros
+//! before the first spin. `lastWin` is the return of the last spin and `totalWon` the return
+//! of the whole session.
+//!
+//! Payload format:
+//! [0, lines] - Spin with the first `lines` paylines active (1-20)
+//! [1] - Play the next free spin
+//! [2, lines, rounds] - Spin `rounds` times (auto-play), each spin paid at the session's bet and
+//!                      playing out any free spins it awards, and settle the spins together
+//!
+//! Symbols:
+//! 0 = Cherry, 1 = Lemon, 2 = Orange, 3 = Plum, 4 = Bell, 5 = Bar, 6 = Seven,
+//! 7 = Wild (substitutes for any paying symbol), 8 = Scatter
+//!
+//! Three or more Scatters anywhere award free spins, played at the same wager (and retriggered
+//! by Scatters landing during the bonus). The return of the spin that triggers the bonus, and
+//! of every free spin but the last, is credited as it lands (via `ContinueWithUpdate`); the
+//! last free spin completes the session.
+
+use super::state_blob::{impl_blob_field_enum, BlobReader, BlobWriter, StateBlob, UNVERSIONED};
+use super::super_mode::apply_super_multiplier_number;
+use super::{CasinoGame, GameError, GameResult, GameRng};
+use nullspace_types::casino::{GameSession, SuperMultiplier};
+
+/// Number of reels.
+pub const REELS: usize = 5;
+
+/// Number of visible rows on each reel.
+pub const ROWS: usize = 3;
+
+/// Substitutes for any paying symbol.
+pub const WILD: u8 = 7;
+
+/// Awards free spins when three or more land anywhere.
+pub const SCATTER: u8 = 8;
+
+/// Symbol names (indexed by symbol).
+pub const SYMBOLS: [&str; 9] = [
+    "Cherry", "Lemon", "Orange", "Plum", "Bell", "Bar", "Seven", "Wild", "Scatter",
+];
+
+/// Stops on each reel strip.
+const STRIP_LEN: usize = 32;
+
+/// Reel strips (each has 6 Cherries, 6 Lemons, 5 Oranges, 4 Plums, 4 Bells, 2 Bars, 2 Sevens,
+/// 2 Wilds and 1 Scatter).
+pub const REEL_STRIPS: [[u8; STRIP_LEN]; REELS] = [
+    [
+        1, 4, 2, 1, 6, 0, 0, 0, 4, 4, 2, 2, 4, 2, 6, 1, 1, 7, 1, 5, 0, 5, 0, 7, 3, 8, 3, 1, 0, 3,
+        3, 2,
+    ],
+    [
+        0, 4, 2, 6, 4, 3, 0, 5, 7, 1, 3, 5, 6, 4, 1, 1, 8, 1, 4, 2, 0, 0, 3, 1, 7, 2, 1, 0, 3, 2,
+        2, 0,
+    ],
+    [
+        2, 4, 3, 2, 6, 6, 0, 3, 0, 1, 1, 8, 3, 2, 0, 2, 2, 1, 0, 0, 1, 4, 3, 7, 4, 4, 0, 7, 1, 1,
+        5, 5,
+    ],
+    [
+        2, 2, 5, 2, 3, 2, 8, 0, 1, 3, 1, 1, 4, 1, 7, 0, 3, 2, 0, 1, 0, 0, 4, 4, 7, 6, 6, 1, 0, 3,
+        5, 4,
+    ],
+    [
+        6, 7, 0, 0, 1, 1, 8, 2, 2, 1, 2, 3, 1, 1, 4, 3, 5, 6, 3, 4, 1, 3, 7, 5, 0, 4, 0, 2, 4, 0,
+        2, 0,
+    ],
+];
+
+/// Paylines (the row on each reel), activated in order.
+pub const PAYLINES: [[u8; REELS]; 20] = [
+    [1, 1, 1, 1, 1],
+    [0, 0, 0, 0, 0],
+    [2, 2, 2, 2, 2],
+    [0, 1, 2, 1, 0],
+    [2, 1, 0, 1, 2],
+    [0, 0, 1, 2, 2],
+    [2, 2, 1, 0, 0],
+    [1, 0, 0, 0, 1],
+    [1, 2, 2, 2, 1],
+    [0, 1, 1, 1, 0],
+    [2, 1, 1, 1, 2],
+    [1, 0, 1, 2, 1],
+    [1, 2, 1, 0, 1],
+    [0, 1, 0, 1, 0],
+    [2, 1, 2, 1, 2],
+    [1, 1, 0, 1, 1],
+    [1, 1, 2, 1, 1],
+    [0, 0, 2, 0, 0],
+    [2, 2, 0, 2, 2],
+    [0, 2, 0, 2, 0],
+];
+
+/// Pays for 3, 4 and 5 of a kind from the leftmost reel, in multiples of the line bet (indexed
+/// by symbol).
+///
+/// Lines return about 90% of their wager, and the free spins bring the game to about 96%
+/// (before super mode).
+pub const PAYTABLE: [[u64; 3]; 8] = [
+    [5, 15, 60],     // Cherry
+    [5, 15, 60],     // Lemon
+    [10, 30, 100],   // Orange
+    [10, 30, 100],   // Plum
+    [15, 40, 150],   // Bell
+    [20, 75, 300],   // Bar
+    [30, 120, 500],  // Seven
+    [50, 250, 1000], // Wild
+];
+
+/// Free spins awarded for 3, 4 and 5 (or more) Scatters.
+pub const FREE_SPINS: [u8; 3] = [8, 12, 20];
+
+/// Maximum free spins a bonus can have pending.
+pub const MAX_FREE_SPINS: u8 = 50;
+
+/// Symbols showing after a spin (indexed by reel, then row).
+pub type Grid = [[u8; ROWS]; REELS];
+
+/// Slots stages.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Ready = 0,
+    FreeSpins = 1,
+    Complete = 2,
+}
+
+impl TryFrom<u8> for Stage {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Stage::Ready),
+            1 => Ok(Stage::FreeSpins),
+            2 => Ok(Stage::Complete),
+            _ => Err(GameError::InvalidState),
+        }
+    }
+}
+
+/// Slots move types.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Move {
+    Spin = 0,
+    FreeSpin = 1,
+    AutoSpin = 2,
+}
+
+impl TryFrom<u8> for Move {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Move::Spin),
+            1 => Ok(Move::FreeSpin),
+            2 => Ok(Move::AutoSpin),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+/// Game state for slots.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlotsState {
+    pub stage: Stage,
+    pub lines: u8,
+    pub free_spins: u8,
+    pub last_win: u64,
+    pub total_won: u64,
+    pub grid: Grid,
+}
+
+impl SlotsState {
+    fn new() -> Self {
+        SlotsState {
+            stage: Stage::Ready,
+            lines: 0,
+            free_spins: 0,
+            last_win: 0,
+            total_won: 0,
+            grid: [[0; ROWS]; REELS],
+        }
+    }
+}
+
+/// [stage:u8] [lines:u8] [freeSpins:u8] [lastWin:u64] [totalWon:u64] [grid:15]
+impl StateBlob for SlotsState {
+    const VERSION: u8 = UNVERSIONED;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.stage)
+            .put(&self.lines)
+            .put(&self.free_spins)
+            .put(&self.last_win)
+            .put(&self.total_won);
+        for column in &self.grid {
+            writer.put(column);
+        }
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        let stage = reader.get()?;
+        let lines: u8 = reader.get()?;
+        if lines as usize > PAYLINES.len() {
+            return None;
+        }
+        let free_spins = reader.get()?;
+        let last_win = reader.get()?;
+        let total_won = reader.get()?;
+        let mut grid = [[0; ROWS]; REELS];
+        for column in grid.iter_mut() {
+            *column = reader.get()?;
+        }
+        if grid.iter().flatten().any(|&symbol| symbol > SCATTER) {
+            return None;
+        }
+
+        Some(SlotsState {
+            stage,
+            lines,
+            free_spins,
+            last_win,
+            total_won,
+            grid,
+        })
+    }
+}
+
+impl_blob_field_enum!(Stage);
+
+/// Spin the reels.
+fn spin(rng: &mut GameRng) -> Grid {
+    let mut grid = [[0; ROWS]; REELS];
+    for (strip, column) in REEL_STRIPS.iter().zip(grid.iter_mut()) {
+        let stop = rng.next_bounded(STRIP_LEN as u8) as usize;
+        for (row, symbol) in column.iter_mut().enumerate() {
+            *symbol = strip[(stop + row) % STRIP_LEN];
+        }
+    }
+    grid
+}
+
+/// The symbols on a payline.
+fn line_symbols(grid: &Grid, line: &[u8; REELS]) -> [u8; REELS] {
+    let mut symbols = [0; REELS];
+    for (reel, &row) in line.iter().enumerate() {
+        symbols[reel] = grid[reel][row as usize];
+    }
+    symbols
+}
+
+/// The paying symbol and pay (in line bets) of a line, if it pays.
+///
+/// Wilds substitute for the first paying symbol on the line, unless a leading run of Wilds
+/// pays more on its own.
+pub fn line_win(symbols: &[u8; REELS]) -> Option<(u8, u64)> {
+    let pay = |symbol: u8, count: usize| {
+        (count >= 3).then(|| (symbol, PAYTABLE[symbol as usize][count - 3]))
+    };
+    let wilds = symbols.iter().take_while(|&&s| s == WILD).count();
+    let wild_win = pay(WILD, wilds);
+
+    let symbol = match symbols.iter().find(|&&s| s != WILD) {
+        Some(&symbol) if symbol != SCATTER => symbol,
+        _ => return wild_win,
+    };
+    let count = symbols
+        .iter()
+        .take_while(|&&s| s == symbol || s == WILD)
+        .count();
+    match (wild_win, pay(symbol, count)) {
+        (Some(wild), Some(win)) if wild.1 >= win.1 => Some(wild),
+        (wild, None) => wild,
+        (_, win) => win,
+    }
+}
+
+/// Number of Scatters showing.
+fn scatter_count(grid: &Grid) -> usize {
+    grid.iter()
+        .flatten()
+        .filter(|&&symbol| symbol == SCATTER)
+        .count()
+}
+
+/// Free spins awarded for `scatters` Scatters.
+pub fn free_spins_awarded(scatters: usize) -> u8 {
+    match scatters {
+        0..=2 => 0,
+        3 => FREE_SPINS[0],
+        4 => FREE_SPINS[1],
+        _ => FREE_SPINS[2],
+    }
+}
+
+/// Return (stake + profit) of a spin of `bet` across the first `lines` paylines, boosting the
+/// lines of any Super Symbols.
+fn spin_return(grid: &Grid, lines: u8, bet: u64, multipliers: &[SuperMultiplier]) -> u64 {
+    if lines == 0 {
+        return 0;
+    }
+    let mut pays: u128 = 0;
+    for line in PAYLINES.iter().take(lines as usize) {
+        if let Some((symbol, pay)) = line_win(&line_symbols(grid, line)) {
+            pays += apply_super_multiplier_number(symbol, multipliers, pay) as u128;
+        }
+    }
+
+    // Each line carries an equal share of the wager
+    u64::try_from(pays * bet as u128 / lines as u128).unwrap_or(u64::MAX)
+}
+
+/// Spin the reels for `state`, recording the result (and any free spins it awards), and
+/// return the spin's return.
+fn play(
+    state: &mut SlotsState,
+    bet: u64,
+    multipliers: &[SuperMultiplier],
+    rng: &mut GameRng,
+) -> u64 {
+    state.grid = spin(rng);
+    let win = spin_return(&state.grid, state.lines, bet, multipliers);
+    let awarded = free_spins_awarded(scatter_count(&state.grid));
+    state.free_spins = state.free_spins.saturating_add(awarded).min(MAX_FREE_SPINS);
+    state.last_win = win;
+    state.total_won = state.total_won.saturating_add(win);
+    win
+}
+
+/// Credit the return of a spin that leaves the bonus running.
+fn credit(win: u64) -> Result<GameResult, GameError> {
+    if win == 0 {
+        return Ok(GameResult::Continue);
+    }
+    let payout = i64::try_from(win).map_err(|_| GameError::InvalidState)?;
+    Ok(GameResult::ContinueWithUpdate { payout })
+}
+
+pub struct Slots;
+
+impl CasinoGame for Slots {
+    fn init(session: &mut GameSession, _rng: &mut GameRng) -> GameResult {
+        // Nothing is revealed until the player spins
+        session.state_blob = SlotsState::new().to_blob();
+        GameResult::Continue
+    }
+
+    fn process_move(
+        session: &mut GameSession,
+        payload: &[u8],
+        rng: &mut GameRng,
+    ) -> Result<GameResult, GameError> {
+        if session.is_complete {
+            return Err(GameError::GameAlreadyComplete);
+        }
+
+        if payload.is_empty() {
+            return Err(GameError::InvalidPayload);
+        }
+
+        let mv = Move::try_from(payload[0])?;
+        let mut state =
+            SlotsState::from_blob(&session.state_blob).ok_or(GameError::InvalidState)?;
+        let multipliers: &[SuperMultiplier] = if session.super_mode.is_active {
+            &session.super_mode.multipliers
+        } else {
+            &[]
+        };
+
+        match mv {
+            Move::Spin => {
+                if state.stage != Stage::Ready {
+                    return Err(GameError::InvalidMove);
+                }
+                let lines = *payload.get(1).ok_or(GameError::InvalidPayload)?;
+                if lines == 0 || lines as usize > PAYLINES.len() {
+                    return Err(GameError::InvalidPayload);
+                }
+                state.lines = lines;
+
+                let win = play(&mut state, session.bet, multipliers, rng);
+                if state.free_spins > 0 {
+                    state.stage = Stage::FreeSpins;
+                    session.state_blob = state.to_blob();
+                    return credit(win);
+                }
+
+                state.stage = Stage::Complete;
+                session.state_blob = state.to_blob();
+                session.is_complete = true;
+                if win > 0 {
+                    Ok(GameResult::Win(win))
+                } else {
+                    Ok(GameResult::Loss)
+                }
+            }
+            Move::FreeSpin => {
+                if state.stage != Stage::FreeSpins || state.free_spins == 0 {
+                    return Err(GameError::InvalidMove);
+                }
+                state.free_spins -= 1;
+
+                let win = play(&mut state, session.bet, multipliers, rng);
+                if state.free_spins > 0 {
+                    session.state_blob = state.to_blob();
+                    return credit(win);
+                }
+
+                state.stage = Stage::Complete;
+                session.state_blob = state.to_blob();
+                session.is_complete = true;
+                if win > 0 {
+                    Ok(GameResult::Win(win))
+                } else {
+                    // The stake was settled by the paid spin and earlier wins were already
+                    // credited, so there is nothing left to deduct
+                    Ok(GameResult::LossPreDeducted(0))
+                }
+            }
+            Move::AutoSpin => {
+                if state.stage != Stage::Ready {
+                    return Err(GameError::InvalidMove);
+                }
+                let &[_, lines, rounds] = payload else {
+                    return Err(GameError::InvalidPayload);
+                };
+                if lines == 0 || lines as usize > PAYLINES.len() {
+                    return Err(GameError::InvalidPayload);
+                }
+                state.lines = lines;
+
+                let result = super::auto_play(rng, rounds, session.bet, |rng| {
+                    let mut won = play(&mut state, session.bet, multipliers, rng);
+                    while state.free_spins > 0 {
+                        state.free_spins -= 1;
+                        won = won.saturating_add(play(&mut state, session.bet, multipliers, rng));
+                    }
+                    won
+                })?;
+
+                state.stage = Stage::Complete;
+                session.state_blob = state.to_blob();
+                session.is_complete = true;
+                Ok(result)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::{create_account_keypair, create_network_keypair, create_seed};
+    use nullspace_types::casino::{GameType, SuperType};
+
+    fn create_test_seed() -> nullspace_types::Seed {
+        let (network_secret, _) = create_network_keypair();
+        create_seed(&network_secret, 1)
+    }
+
+    fn create_test_session(id: u64, bet: u64) -> GameSession {
+        let (_, pk) = create_account_keypair(1);
+        GameSession {
+            id,
+            player: pk,
+            game_type: GameType::Slots,
+            bet,
+            state_blob: vec![],
+            move_count: 0,
+            created_at: 0,
+            is_complete: false,
+            super_mode: nullspace_types::casino::SuperModeState::default(),
+            is_tournament: false,
+            tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
+        }
+    }
+
+    /// Sevens across the middle row (and nothing else paying), with Scatters on the first
+    /// `scatters` reels of the top row.
+    fn create_test_grid(scatters: usize) -> Grid {
+        let mut grid = [[0; ROWS]; REELS];
+        for (reel, column) in grid.iter_mut().enumerate() {
+            *column = [reel as u8, 6, reel as u8 + 1];
+            if reel < scatters {
+                column[0] = SCATTER;
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn test_reel_strips() {
+        for strip in &REEL_STRIPS {
+            let mut counts = [0; 9];
+            for &symbol in strip {
+                counts[symbol as usize] += 1;
+            }
+            assert_eq!(counts, [6, 6, 5, 4, 4, 2, 2, 2, 1]);
+        }
+    }
+
+    #[test]
+    fn test_line_return() {
+        // Lines pay the same on every payline (each shows one stop per reel)
+        let mut frequencies = [[0u64; 9]; REELS];
+        for (reel, strip) in REEL_STRIPS.iter().enumerate() {
+            for &symbol in strip {
+                frequencies[reel][symbol as usize] += 1;
+            }
+        }
+        let mut returned: u128 = 0;
+        let mut total: u128 = 0;
+        for index in 0..9usize.pow(REELS as u32) {
+            let mut symbols = [0u8; REELS];
+            let mut weight: u128 = 1;
+            let mut rest = index;
+            for (reel, symbol) in symbols.iter_mut().enumerate() {
+                *symbol = (rest % 9) as u8;
+                rest /= 9;
+                weight *= frequencies[reel][*symbol as usize] as u128;
+            }
+            if let Some((_, pay)) = line_win(&symbols) {
+                returned += weight * pay as u128;
+            }
+            total += weight;
+        }
+        let rtp_bps = returned * 10_000 / total;
+        assert!((8_900..9_100).contains(&rtp_bps), "line rtp {rtp_bps}");
+    }
+
+    #[test]
+    fn test_line_win() {
+        // Runs pay from the leftmost reel
+        assert_eq!(line_win(&[0, 0, 0, 1, 2]), Some((0, 5)));
+        assert_eq!(line_win(&[5, 5, 5, 5, 5]), Some((5, 300)));
+        assert_eq!(line_win(&[1, 0, 0, 0, 0]), None);
+
+        // Wilds substitute
+        assert_eq!(line_win(&[7, 0, 7, 0, 3]), Some((0, 15)));
+        assert_eq!(line_win(&[0, 7, 7, 7, 7]), Some((0, 60)));
+
+        // Leading Wilds pay on their own when that pays more
+        assert_eq!(line_win(&[7, 7, 7, 0, 1]), Some((7, 50)));
+        assert_eq!(line_win(&[7, 7, 7, 6, 1]), Some((6, 120)));
+        assert_eq!(line_win(&[7, 7, 7, 7, 7]), Some((7, 1000)));
+
+        // Scatters never pay on a line (or take a Wild's place)
+        assert_eq!(line_win(&[8, 8, 8, 8, 8]), None);
+        assert_eq!(line_win(&[7, 7, 8, 0, 0]), None);
+        assert_eq!(line_win(&[7, 7, 7, 8, 0]), Some((7, 50)));
+    }
+
+    #[test]
+    fn test_free_spins_awarded() {
+        assert_eq!(free_spins_awarded(0), 0);
+        assert_eq!(free_spins_awarded(2), 0);
+        assert_eq!(free_spins_awarded(3), 8);
+        assert_eq!(free_spins_awarded(4), 12);
+        assert_eq!(free_spins_awarded(5), 20);
+        assert_eq!(free_spins_awarded(7), 20);
+        assert_eq!(scatter_count(&create_test_grid(4)), 4);
+    }
+
+    #[test]
+    fn test_spin_return() {
+        let grid = create_test_grid(0);
+
+        // Only the middle row pays: 500x the line bet
+        assert_eq!(spin_return(&grid, 1, 100, &[]), 50_000);
+        assert_eq!(spin_return(&grid, 2, 100, &[]), 25_000);
+        assert_eq!(spin_return(&grid, 20, 100, &[]), 2_500);
+        assert_eq!(spin_return(&grid, 0, 100, &[]), 0);
+
+        // Super Symbols boost their lines
+        let multipliers = [
+            SuperMultiplier {
+                id: 6,
+                multiplier: 3,
+                super_type: SuperType::Number,
+            },
+            SuperMultiplier {
+                id: 0,
+                multiplier: 5,
+                super_type: SuperType::Number,
+            },
+        ];
+        assert_eq!(spin_return(&grid, 1, 100, &multipliers), 150_000);
+    }
+
+    #[test]
+    fn test_state_roundtrip() {
+        let state = SlotsState {
+            stage: Stage::FreeSpins,
+            lines: 20,
+            free_spins: 12,
+            last_win: 250,
+            total_won: 1_000,
+            grid: create_test_grid(3),
+        };
+        let blob = state.to_blob();
+        assert_eq!(blob.len(), 19 + REELS * ROWS);
+        assert_eq!(SlotsState::from_blob(&blob), Some(state.clone()));
+
+        // Malformed blobs are rejected
+        assert_eq!(SlotsState::from_blob(&blob[1..]), None);
+        let mut bad = blob.clone();
+        bad[0] = 3;
+        assert_eq!(SlotsState::from_blob(&bad), None);
+        let mut bad = blob.clone();
+        *bad.last_mut().unwrap() = 9;
+        assert_eq!(SlotsState::from_blob(&bad), None);
+
+        crate::casino::state_blob::fuzz::roundtrip(&[SlotsState::new(), state]);
+    }
+
+    #[test]
+    fn test_invalid_moves() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(1, 100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        Slots::init(&mut session, &mut rng);
+
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        let payloads: [&[u8]; 5] = [&[], &[0], &[0, 0], &[0, 21], &[2]];
+        for payload in payloads {
+            let result = Slots::process_move(&mut session, payload, &mut rng);
+            assert!(matches!(result, Err(GameError::InvalidPayload)));
+        }
+
+        // No bonus is running
+        let result = Slots::process_move(&mut session, &[1], &mut rng);
+        assert!(matches!(result, Err(GameError::InvalidMove)));
+        assert!(!session.is_complete);
+    }
+
+    #[test]
+    fn test_paid_spin() {
+        let seed = create_test_seed();
+        for id in 1..50 {
+            let mut session = create_test_session(id, 100);
+            let mut rng = GameRng::new(&seed, session.id, 0);
+            Slots::init(&mut session, &mut rng);
+
+            let mut rng = GameRng::new(&seed, session.id, 1);
+            let result =
+                Slots::process_move(&mut session, &[0, 20], &mut rng).expect("spin failed");
+            let state = SlotsState::from_blob(&session.state_blob).expect("invalid state");
+            assert_eq!(state.lines, 20);
+            assert_eq!(state.last_win, state.total_won);
+            assert_eq!(
+                state.last_win,
+                spin_return(&state.grid, 20, session.bet, &[])
+            );
+            match result {
+                GameResult::Win(amount) => {
+                    assert!(session.is_complete);
+                    assert_eq!(amount, state.last_win);
+                }
+                GameResult::Loss => {
+                    assert!(session.is_complete);
+                    assert_eq!(state.last_win, 0);
+                }
+                GameResult::Continue | GameResult::ContinueWithUpdate { .. } => {
+                    assert_eq!(state.stage, Stage::FreeSpins);
+                    assert!(state.free_spins > 0);
+                }
+                _ => panic!("unexpected result"),
+            }
+
+            // A session only has one paid spin
+            if session.is_complete {
+                let result = Slots::process_move(&mut session, &[0, 20], &mut rng);
+                assert!(matches!(result, Err(GameError::GameAlreadyComplete)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_free_spins() {
+        let seed = create_test_seed();
+
+        // Find a paid spin that triggers the bonus
+        let mut session = (1..5_000)
+            .map(|id| {
+                let mut session = create_test_session(id, 100);
+                let mut rng = GameRng::new(&seed, id, 0);
+                Slots::init(&mut session, &mut rng);
+                let mut rng = GameRng::new(&seed, id, 1);
+                Slots::process_move(&mut session, &[0, 20], &mut rng).expect("spin failed");
+                session
+            })
+            .find(|session| !session.is_complete)
+            .expect("no bonus triggered");
+        let state = SlotsState::from_blob(&session.state_blob).expect("invalid state");
+        assert!(state.free_spins >= FREE_SPINS[0]);
+
+        // Play the bonus out, tracking the return credited along the way
+        let mut credited = state.total_won;
+        let mut move_number = 2;
+        while !session.is_complete {
+            let before = SlotsState::from_blob(&session.state_blob).expect("invalid state");
+            let mut rng = GameRng::new(&seed, session.id, move_number);
+            let result = Slots::process_move(&mut session, &[1], &mut rng).expect("spin failed");
+            let after = SlotsState::from_blob(&session.state_blob).expect("invalid state");
+            assert_eq!(after.total_won - before.total_won, after.last_win);
+            match result {
+                GameResult::Continue => assert_eq!(after.last_win, 0),
+                GameResult::ContinueWithUpdate { payout } => {
+                    assert_eq!(payout as u64, after.last_win)
+                }
+                GameResult::Win(amount) => assert_eq!(amount, after.last_win),
+                GameResult::LossPreDeducted(0) => assert_eq!(after.last_win, 0),
+                _ => panic!("unexpected result"),
+            }
+            credited += after.last_win;
+            move_number += 1;
+        }
+
+        let state = SlotsState::from_blob(&session.state_blob).expect("invalid state");
+        assert_eq!(state.stage, Stage::Complete);
+        assert_eq!(state.free_spins, 0);
+        assert_eq!(credited, state.total_won);
+    }
+
+    #[test]
+    fn test_auto_spin() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(1, 100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        Slots::init(&mut session, &mut rng);
+
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        for payload in [&[2, 20][..], &[2, 21, 10], &[2, 20, 0]] {
+            let result = Slots::process_move(&mut session, payload, &mut rng);
+            assert!(matches!(result, Err(GameError::InvalidPayload)));
+        }
+
+        // Fifty paid spins, with any bonuses they trigger played out along the way
+        let result =
+            Slots::process_move(&mut session, &[2, 20, 50], &mut rng).expect("spin failed");
+        let state = SlotsState::from_blob(&session.state_blob).expect("invalid state");
+        assert!(session.is_complete);
+        assert_eq!(state.stage, Stage::Complete);
+        assert_eq!(state.free_spins, 0);
+        match result {
+            GameResult::WinWithExtraDeduction {
+                payout,
+                extra_deduction,
+            } => {
+                assert_eq!(payout, state.total_won);
+                assert_eq!(extra_deduction, 49 * 100);
+            }
+            GameResult::LossPreDeductedWithExtraDeduction {
+                total_loss,
+                extra_deduction,
+            } => {
+                assert_eq!(state.total_won, 0);
+                assert_eq!((total_loss, extra_deduction), (50 * 100, 49 * 100));
+            }
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_last_free_spin() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(1, 100);
+        session.state_blob = SlotsState {
+            stage: Stage::FreeSpins,
+            lines: 1,
+            free_spins: 1,
+            last_win: 0,
+            total_won: 0,
+            grid: create_test_grid(0),
+        }
+        .to_blob();
+
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        let result = Slots::process_move(&mut session, &[1], &mut rng).expect("spin failed");
+        let state = SlotsState::from_blob(&session.state_blob).expect("invalid state");
+
+        // The bonus ends unless the spin retriggers it
+        if state.free_spins == 0 {
+            assert!(session.is_complete);
+            assert_eq!(state.stage, Stage::Complete);
+            match result {
+                GameResult::Win(amount) => assert_eq!(amount, state.last_win),
+                GameResult::LossPreDeducted(0) => assert_eq!(state.last_win, 0),
+                _ => panic!("unexpected result"),
+            }
+        } else {
+            assert!(!session.is_complete);
+            assert!(state.free_spins >= FREE_SPINS[0]);
+        }
+
+        // Spins can't be paid for during (or after) the bonus
+        let result = Slots::process_move(&mut session, &[0, 1], &mut rng);
+        assert!(result.is_err());
+    }
+}
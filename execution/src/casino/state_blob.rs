@@ -0,0 +1,390 @@
+//! Versioned encoding of game state blobs.
+//!
+//! Game states implement [StateBlob] by writing their fields, in order, to a [BlobWriter] and
+//! reading them back from a [BlobReader]. The writer stamps the blob with the state's current
+//! version and the reader exposes the version of the blob being decoded, so a field added by a
+//! later layout is read with [BlobReader::since] (which falls back to a default for older blobs)
+//! instead of each game matching on lengths and byte offsets.
+//!
+//! Integers are big-endian and lists are prefixed with a `u8` count, matching the layouts the
+//! games (and the frontend) already use.
+
+/// Version of layouts that predate versioning and have no version byte.
+pub const UNVERSIONED: u8 = 0;
+
+/// A value that can be written to and read from a state blob.
+pub trait BlobField: Sized {
+    fn write(&self, writer: &mut BlobWriter);
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self>;
+}
+
+impl BlobField for u8 {
+    fn write(&self, writer: &mut BlobWriter) {
+        writer.blob.push(*self);
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        let (&value, rest) = reader.blob.split_first()?;
+        reader.blob = rest;
+        Some(value)
+    }
+}
+
+impl BlobField for bool {
+    fn write(&self, writer: &mut BlobWriter) {
+        writer.blob.push(u8::from(*self));
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        match u8::read(reader)? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl<const N: usize> BlobField for [u8; N] {
+    fn write(&self, writer: &mut BlobWriter) {
+        writer.blob.extend_from_slice(self);
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        reader.get_bytes(N)?.try_into().ok()
+    }
+}
+
+macro_rules! impl_blob_field_int {
+    ($($int:ty),*) => {$(
+        impl BlobField for $int {
+            fn write(&self, writer: &mut BlobWriter) {
+                writer.blob.extend_from_slice(&self.to_be_bytes());
+            }
+
+            fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+                Some(<$int>::from_be_bytes(BlobField::read(reader)?))
+            }
+        }
+    )*};
+}
+
+impl_blob_field_int!(u16, u32, u64, i64);
+
+impl<A: BlobField, B: BlobField> BlobField for (A, B) {
+    fn write(&self, writer: &mut BlobWriter) {
+        self.0.write(writer);
+        self.1.write(writer);
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        Some((A::read(reader)?, B::read(reader)?))
+    }
+}
+
+/// Builds a state blob.
+pub struct BlobWriter {
+    blob: Vec<u8>,
+}
+
+impl BlobWriter {
+    /// Start a blob of the given layout version (`UNVERSIONED` writes no version byte).
+    pub fn new(version: u8) -> Self {
+        let mut blob = Vec::new();
+        if version != UNVERSIONED {
+            blob.push(version);
+        }
+        Self { blob }
+    }
+
+    pub fn put<T: BlobField>(&mut self, value: &T) -> &mut Self {
+        value.write(self);
+        self
+    }
+
+    pub fn put_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.blob.extend_from_slice(bytes);
+        self
+    }
+
+    /// Write a list prefixed by its length (lists are bounded well below 256 entries).
+    pub fn put_list<T: BlobField>(&mut self, items: &[T]) -> &mut Self {
+        self.blob.push(items.len() as u8);
+        for item in items {
+            item.write(self);
+        }
+        self
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.blob
+    }
+}
+
+/// Reads a state blob.
+pub struct BlobReader<'a> {
+    version: u8,
+    blob: &'a [u8],
+}
+
+impl<'a> BlobReader<'a> {
+    /// Read a blob that starts with its version byte.
+    pub fn new(blob: &'a [u8]) -> Option<Self> {
+        let (&version, blob) = blob.split_first()?;
+        (version != UNVERSIONED).then_some(Self { version, blob })
+    }
+
+    /// Read a blob without a version byte.
+    pub fn unversioned(blob: &'a [u8]) -> Self {
+        Self {
+            version: UNVERSIONED,
+            blob,
+        }
+    }
+
+    /// Layout version of the blob being read.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.blob.len()
+    }
+
+    pub fn get<T: BlobField>(&mut self) -> Option<T> {
+        T::read(self)
+    }
+
+    /// Read a field added by layout `version`, or take `default` for older blobs.
+    pub fn since<T: BlobField>(&mut self, version: u8, default: T) -> Option<T> {
+        if self.version >= version {
+            self.get()
+        } else {
+            Some(default)
+        }
+    }
+
+    /// Read a field appended without a version bump, or take `default` if the blob ends first.
+    pub fn get_or<T: BlobField>(&mut self, default: T) -> Option<T> {
+        if self.blob.is_empty() {
+            Some(default)
+        } else {
+            self.get()
+        }
+    }
+
+    pub fn get_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.blob.len() < len {
+            return None;
+        }
+        let (bytes, rest) = self.blob.split_at(len);
+        self.blob = rest;
+        Some(bytes)
+    }
+
+    /// Read a list prefixed by its length, rejecting lists longer than `max`.
+    pub fn get_list<T: BlobField>(&mut self, max: usize) -> Option<Vec<T>> {
+        let len = self.get::<u8>()? as usize;
+        if len > max {
+            return None;
+        }
+        (0..len).map(|_| self.get()).collect()
+    }
+
+    /// Finish reading, rejecting any trailing bytes.
+    pub fn finish(self) -> Option<()> {
+        self.blob.is_empty().then_some(())
+    }
+}
+
+/// A game state stored in `GameSession::state_blob`.
+pub trait StateBlob: Sized {
+    /// Layout version written by [StateBlob::to_blob].
+    const VERSION: u8;
+    /// Oldest layout version [StateBlob::from_blob] still decodes.
+    const MIN_VERSION: u8 = Self::VERSION;
+
+    fn write(&self, writer: &mut BlobWriter);
+
+    /// Read the state's fields (the reader's version is in `MIN_VERSION..=VERSION`).
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self>;
+
+    fn to_blob(&self) -> Vec<u8> {
+        let mut writer = BlobWriter::new(Self::VERSION);
+        self.write(&mut writer);
+        writer.finish()
+    }
+
+    fn from_blob(blob: &[u8]) -> Option<Self> {
+        let mut reader = if Self::VERSION == UNVERSIONED {
+            BlobReader::unversioned(blob)
+        } else {
+            BlobReader::new(blob)?
+        };
+        if !(Self::MIN_VERSION..=Self::VERSION).contains(&reader.version()) {
+            return None;
+        }
+        let state = Self::read(&mut reader)?;
+        reader.finish()?;
+        Some(state)
+    }
+}
+
+/// Implement [BlobField] for a `#[repr(u8)]` enum with a `TryFrom<u8>` conversion.
+macro_rules! impl_blob_field_enum {
+    ($($ty:ty),*) => {$(
+        impl $crate::casino::state_blob::BlobField for $ty {
+            fn write(&self, writer: &mut $crate::casino::state_blob::BlobWriter) {
+                writer.put(&(*self as u8));
+            }
+
+            fn read(reader: &mut $crate::casino::state_blob::BlobReader<'_>) -> Option<Self> {
+                Self::try_from(reader.get::<u8>()?).ok()
+            }
+        }
+    )*};
+}
+pub(crate) use impl_blob_field_enum;
+
+/// Round-trip fuzzing shared by the games' state tests.
+#[cfg(test)]
+pub(crate) mod fuzz {
+    use super::StateBlob;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::fmt::Debug;
+
+    /// Check that each state survives a round trip, and that mutations of its blob (flipped,
+    /// truncated or extended bytes) are either rejected or decode to a state that round trips
+    /// in turn.
+    pub fn roundtrip<T: StateBlob + PartialEq + Debug>(states: &[T]) {
+        let mut rng = StdRng::seed_from_u64(0);
+        for state in states {
+            let blob = state.to_blob();
+            assert_eq!(T::from_blob(&blob).as_ref(), Some(state));
+
+            for _ in 0..64 {
+                let mut mutated = blob.clone();
+                match rng.gen_range(0..3) {
+                    0 if !mutated.is_empty() => {
+                        let idx = rng.gen_range(0..mutated.len());
+                        mutated[idx] = rng.gen();
+                    }
+                    1 => mutated.truncate(rng.gen_range(0..=blob.len())),
+                    _ => mutated.push(rng.gen()),
+                }
+                if let Some(decoded) = T::from_blob(&mutated) {
+                    assert_eq!(T::from_blob(&decoded.to_blob()), Some(decoded));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Example {
+        stage: u8,
+        cards: [u8; 2],
+        bet: u64,
+        hands: Vec<u64>,
+        insured: bool,
+    }
+
+    impl StateBlob for Example {
+        const VERSION: u8 = 3;
+        const MIN_VERSION: u8 = 2;
+
+        fn write(&self, writer: &mut BlobWriter) {
+            writer
+                .put(&self.stage)
+                .put(&self.cards)
+                .put(&self.bet)
+                .put_list(&self.hands)
+                .put(&self.insured);
+        }
+
+        fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+            Some(Self {
+                stage: reader.get()?,
+                cards: reader.get()?,
+                bet: reader.get()?,
+                hands: reader.get_list(4)?,
+                insured: reader.since(3, false)?,
+            })
+        }
+    }
+
+    #[test]
+    fn test_layout() {
+        let state = Example {
+            stage: 1,
+            cards: [7, 8],
+            bet: 0x0102,
+            hands: vec![5],
+            insured: true,
+        };
+        let blob = state.to_blob();
+        assert_eq!(
+            blob,
+            vec![3, 1, 7, 8, 0, 0, 0, 0, 0, 0, 1, 2, 1, 0, 0, 0, 0, 0, 0, 0, 5, 1]
+        );
+        assert_eq!(Example::from_blob(&blob), Some(state.clone()));
+
+        // Version 2 blobs predate the trailing flag
+        let mut v2 = blob[..blob.len() - 1].to_vec();
+        v2[0] = 2;
+        assert_eq!(
+            Example::from_blob(&v2),
+            Some(Example {
+                insured: false,
+                ..state
+            })
+        );
+
+        // Unknown versions, trailing bytes, short blobs and oversized lists are rejected
+        let mut v1 = blob.clone();
+        v1[0] = 1;
+        assert_eq!(Example::from_blob(&v1), None);
+        let mut v4 = blob.clone();
+        v4[0] = 4;
+        assert_eq!(Example::from_blob(&v4), None);
+        assert_eq!(Example::from_blob(&[blob.clone(), vec![0]].concat()), None);
+        assert_eq!(Example::from_blob(&blob[..blob.len() - 2]), None);
+        assert_eq!(Example::from_blob(&[]), None);
+        let mut long = blob;
+        long[12] = 5;
+        assert_eq!(Example::from_blob(&long), None);
+    }
+
+    #[test]
+    fn test_reader() {
+        let mut reader = BlobReader::unversioned(&[2, 0, 1]);
+        assert_eq!(reader.version(), UNVERSIONED);
+        assert_eq!(reader.get::<bool>(), None);
+        assert_eq!(reader.get::<bool>(), Some(false));
+        assert_eq!(reader.remaining(), 1);
+        assert_eq!(reader.get_or(7u8), Some(1));
+        assert_eq!(reader.get_or(7u8), Some(7));
+        assert_eq!(reader.get::<u8>(), None);
+        assert_eq!(reader.finish(), Some(()));
+
+        assert!(BlobReader::new(&[]).is_none());
+        assert!(BlobReader::new(&[UNVERSIONED]).is_none());
+    }
+
+    #[test]
+    fn test_fuzz_roundtrip() {
+        let states: Vec<Example> = (0..32u8)
+            .map(|i| Example {
+                stage: i,
+                cards: [i, i.wrapping_mul(7)],
+                bet: u64::from(i) << 40,
+                hands: (0..i % 5).map(u64::from).collect(),
+                insured: i % 2 == 0,
+            })
+            .collect();
+        fuzz::roundtrip(&states);
+    }
+}
@@ -564,6 +564,83 @@ pub fn apply_casino_war_strike_multiplier(
     base_payout.saturating_mul(multiplier)
 }
 
+/// Generate Super Slots multipliers (2 Super Symbols, 2-5x)
+///
+/// Distribution:
+/// - 2 distinct paying symbols (Cherry through Seven; Wild and Scatter are never chosen)
+/// - Multipliers: 60% 2x, 30% 3x, 10% 5x
+/// - Each winning line of a Super Symbol is boosted (apply with
+///   `apply_super_multiplier_number()`, keyed by the line's symbol)
+pub fn generate_slots_multipliers(rng: &mut GameRng) -> Vec<SuperMultiplier> {
+    let mut mults = Vec::with_capacity(2);
+    let mut used = 0u8;
+
+    for _ in 0..2 {
+        let symbol = loop {
+            let s = rng.next_bounded(7);
+            if (used & (1 << s)) == 0 {
+                used |= 1 << s;
+                break s;
+            }
+        };
+
+        let roll = rng.next_f32();
+        let multiplier = if roll < 0.6 {
+            2
+        } else if roll < 0.9 {
+            3
+        } else {
+            5
+        };
+
+        mults.push(SuperMultiplier {
+            id: symbol,
+            multiplier,
+            super_type: SuperType::Number,
+        });
+    }
+    mults
+}
+
+/// Generate Super Money Wheel multipliers (1-3 Super Segments, 2-5x)
+///
+/// Distribution:
+/// - 1-3 distinct wheel segments (0-53), so a symbol is only boosted on some of its segments
+/// - Multipliers: 60% 2x, 30% 3x, 10% 5x
+/// - All winnings are boosted when the wheel stops on a Super Segment (apply with
+///   `apply_super_multiplier_number()`, keyed by the segment)
+pub fn generate_money_wheel_multipliers(rng: &mut GameRng) -> Vec<SuperMultiplier> {
+    let count = 1 + rng.next_bounded(3) as usize;
+    let mut mults = Vec::with_capacity(count);
+    let mut used = 0u64;
+
+    for _ in 0..count {
+        let segment = loop {
+            let s = rng.next_bounded(54);
+            if (used & (1 << s)) == 0 {
+                used |= 1 << s;
+                break s;
+            }
+        };
+
+        let roll = rng.next_f32();
+        let multiplier = if roll < 0.6 {
+            2
+        } else if roll < 0.9 {
+            3
+        } else {
+            5
+        };
+
+        mults.push(SuperMultiplier {
+            id: segment,
+            multiplier,
+            super_type: SuperType::Number,
+        });
+    }
+    mults
+}
+
 /// Generate Super HiLo state (streak-based progressive multipliers)
 ///
 /// Distribution per plan (STREAK-BASED multipliers):
@@ -744,6 +821,15 @@ pub fn enhance_multipliers_for_aura_round(multipliers: &mut [SuperMultiplier]) {
     }
 }
 
+/// Boost multipliers by `boost_bps` basis points (rounded down), e.g. for tournaments with
+/// boosted super mode odds.
+pub fn boost_multipliers(multipliers: &mut [SuperMultiplier], boost_bps: u16) {
+    for m in multipliers {
+        let boosted = m.multiplier as u32 * (10_000 + boost_bps as u32) / 10_000;
+        m.multiplier = boosted.min(u16::MAX as u32) as u16;
+    }
+}
+
 /// Check if any of the outcome elements match Aura elements.
 ///
 /// Used to determine if the round qualifies as a "near-miss" for meter purposes.
@@ -932,6 +1018,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_generate_slots_multipliers() {
+        let mut rng = create_test_rng(10);
+        let mults = generate_slots_multipliers(&mut rng);
+
+        assert_eq!(mults.len(), 2);
+        assert_ne!(mults[0].id, mults[1].id);
+        for m in &mults {
+            // Never Wild (7) or Scatter (8)
+            assert!(m.id < 7);
+            assert!([2, 3, 5].contains(&m.multiplier));
+            assert_eq!(m.super_type, SuperType::Number);
+        }
+    }
+
+    #[test]
+    fn test_generate_money_wheel_multipliers() {
+        let mut rng = create_test_rng(11);
+        let mults = generate_money_wheel_multipliers(&mut rng);
+
+        assert!((1..=3).contains(&mults.len()));
+        for (i, m) in mults.iter().enumerate() {
+            assert!(m.id < 54);
+            assert!(mults[..i].iter().all(|other| other.id != m.id));
+            assert!([2, 3, 5].contains(&m.multiplier));
+            assert_eq!(m.super_type, SuperType::Number);
+        }
+    }
+
     #[test]
     fn test_generate_hilo_state() {
         let state0 = generate_hilo_state(0);
@@ -1071,6 +1186,27 @@ mod tests {
         assert_eq!(mults[1].multiplier, 12); // 8 * 1.5 = 12
     }
 
+    #[test]
+    fn test_boost_multipliers() {
+        let mut mults = vec![
+            SuperMultiplier {
+                id: 0,
+                multiplier: 3,
+                super_type: SuperType::Card,
+            },
+            SuperMultiplier {
+                id: 1,
+                multiplier: u16::MAX,
+                super_type: SuperType::Card,
+            },
+        ];
+        boost_multipliers(&mut mults, 2_500);
+        assert_eq!(mults[0].multiplier, 3); // 3 * 1.25 = 3.75, rounded down
+        assert_eq!(mults[1].multiplier, u16::MAX);
+        boost_multipliers(&mut mults, 10_000);
+        assert_eq!(mults[0].multiplier, 6);
+    }
+
     #[test]
     fn test_check_aura_element_presence_cards() {
         let multipliers = vec![SuperMultiplier {
@@ -4,12 +4,24 @@
 //! - Ante (`session.bet`, deducted by CasinoStartGame)
 //! - Optional Pairplus side bet (placed before deal)
 //! - Optional 6-card bonus side bet (placed before deal)
-//! - Optional Progressive side bet (placed before deal; WoO Progressive v2A, for-one)
+//! - Optional Progressive side bet (placed before deal; WoO Progressive v2A, for-one), with an
+//!   envy bonus when the dealer (the only other seat) holds the spade mini-royal
+//! - Optional Prime side bet (placed before deal; all three player cards the same color)
 //! - Play/Fold decision (Play bet equals Ante; charged before reveal)
 //! - Dealer qualification: Q-6-4 or better (per WoO)
 //! - Ante bonus (pay table #1: SF 5, Trips 4, Straight 1), paid when player plays
 //!
 //! State blob format:
+//! v4 (40 bytes):
+//! [version:u8=4]
+//! [stage:u8]
+//! [playerCard1:u8] [playerCard2:u8] [playerCard3:u8]   (0xFF if not dealt yet)
+//! [dealerCard1:u8] [dealerCard2:u8] [dealerCard3:u8]   (0xFF if unrevealed)
+//! [pairplusBetAmount:u64 BE]
+//! [sixCardBonusBetAmount:u64 BE]
+//! [progressiveBetAmount:u64 BE]
+//! [primeBetAmount:u64 BE]
+//!
 //! v3 (32 bytes):
 //! [version:u8=3]
 //! [stage:u8]
@@ -49,7 +61,11 @@
 //! 4 = Reveal
 //! 5 = Set 6-Card Bonus bet (u64)
 //! 6 = Set Progressive bet (u64)
+//! 7 = Set Prime bet (u64)
 
+use super::poker::{card_rank, card_suit};
+use super::side_bet::{self, JackpotTier, Progressive, SideBet};
+use super::state_blob::{impl_blob_field_enum, BlobReader, BlobWriter, StateBlob};
 use super::super_mode::apply_super_multiplier_cards;
 use super::{CasinoGame, GameError, GameResult, GameRng};
 use nullspace_types::casino::{GameSession, THREE_CARD_PROGRESSIVE_BASE_JACKPOT};
@@ -57,12 +73,24 @@ use nullspace_types::casino::{GameSession, THREE_CARD_PROGRESSIVE_BASE_JACKPOT};
 const STATE_VERSION_V1: u8 = 1;
 const STATE_VERSION_V2: u8 = 2;
 const STATE_VERSION_V3: u8 = 3;
+const STATE_VERSION_V4: u8 = 4;
 const CARD_UNKNOWN: u8 = 0xFF;
-const STATE_LEN_V1: usize = 16;
-const STATE_LEN_V2: usize = 24;
-const STATE_LEN_V3: usize = 32;
 
 const PROGRESSIVE_BET_UNIT: u64 = 1;
+/// Envy bonus (for-one) paid to a progressive bet when the dealer holds A-K-Q of spades.
+const PROGRESSIVE_ENVY_BONUS: u64 = 100;
+
+/// WoO Prime side bet: three cards of one color pay 3:1, and all six cards (player and
+/// dealer) of one color pay 4:1 (house edge 3.62%).
+/// https://wizardofodds.com/games/three-card-poker/side-bets/prime/
+const PRIME_THREE_CARD_PAYOUT_TO_1: u64 = 3;
+const PRIME_SIX_CARD_PAYOUT_TO_1: u64 = 4;
+
+/// Side bet identifiers (reported by [side_bet::SideBet::id]).
+pub const SIDE_BET_PAIR_PLUS: u8 = 0;
+pub const SIDE_BET_SIX_CARD_BONUS: u8 = 1;
+pub const SIDE_BET_PROGRESSIVE: u8 = 2;
+pub const SIDE_BET_PRIME: u8 = 3;
 
 /// Three Card Poker stages.
 #[repr(u8)]
@@ -88,6 +116,8 @@ impl TryFrom<u8> for Stage {
     }
 }
 
+impl_blob_field_enum!(Stage);
+
 /// Player moves.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -99,6 +129,7 @@ pub enum Move {
     Reveal = 4,
     SetSixCardBonus = 5,
     SetProgressive = 6,
+    SetPrime = 7,
 }
 
 impl TryFrom<u8> for Move {
@@ -113,6 +144,7 @@ impl TryFrom<u8> for Move {
             4 => Ok(Move::Reveal),
             5 => Ok(Move::SetSixCardBonus),
             6 => Ok(Move::SetProgressive),
+            7 => Ok(Move::SetPrime),
             _ => Err(GameError::InvalidPayload),
         }
     }
@@ -129,21 +161,6 @@ pub enum HandRank {
     StraightFlush = 5,
 }
 
-/// Get card rank (2-14, Ace = 14 for comparison).
-fn card_rank(card: u8) -> u8 {
-    let r = (card % 13) + 1;
-    if r == 1 {
-        14
-    } else {
-        r
-    }
-}
-
-/// Get card suit.
-fn card_suit(card: u8) -> u8 {
-    card / 13
-}
-
 /// Evaluate a 3-card hand, returns (HandRank, high cards for tiebreaker).
 pub fn evaluate_hand(cards: &[u8; 3]) -> (HandRank, [u8; 3]) {
     let mut ranks: Vec<u8> = cards.iter().map(|&c| card_rank(c)).collect();
@@ -224,70 +241,35 @@ struct TcState {
     pairplus_bet: u64,
     six_card_bonus_bet: u64,
     progressive_bet: u64,
+    prime_bet: u64,
 }
 
-fn parse_state(state: &[u8]) -> Option<TcState> {
-    if state.len() == STATE_LEN_V1 && state[0] == STATE_VERSION_V1 {
-        let stage = Stage::try_from(state[1]).ok()?;
-        let player = [state[2], state[3], state[4]];
-        let dealer = [state[5], state[6], state[7]];
-        let pairplus_bet = u64::from_be_bytes(state[8..16].try_into().ok()?);
-        return Some(TcState {
-            stage,
-            player,
-            dealer,
-            pairplus_bet,
-            six_card_bonus_bet: 0,
-            progressive_bet: 0,
-        });
-    }
-
-    if state.len() == STATE_LEN_V2 && state[0] == STATE_VERSION_V2 {
-        let stage = Stage::try_from(state[1]).ok()?;
-        let player = [state[2], state[3], state[4]];
-        let dealer = [state[5], state[6], state[7]];
-        let pairplus_bet = u64::from_be_bytes(state[8..16].try_into().ok()?);
-        let six_card_bonus_bet = u64::from_be_bytes(state[16..24].try_into().ok()?);
-        return Some(TcState {
-            stage,
-            player,
-            dealer,
-            pairplus_bet,
-            six_card_bonus_bet,
-            progressive_bet: 0,
-        });
-    }
-
-    if state.len() == STATE_LEN_V3 && state[0] == STATE_VERSION_V3 {
-        let stage = Stage::try_from(state[1]).ok()?;
-        let player = [state[2], state[3], state[4]];
-        let dealer = [state[5], state[6], state[7]];
-        let pairplus_bet = u64::from_be_bytes(state[8..16].try_into().ok()?);
-        let six_card_bonus_bet = u64::from_be_bytes(state[16..24].try_into().ok()?);
-        let progressive_bet = u64::from_be_bytes(state[24..32].try_into().ok()?);
-        return Some(TcState {
-            stage,
-            player,
-            dealer,
-            pairplus_bet,
-            six_card_bonus_bet,
-            progressive_bet,
-        });
-    }
-
-    None
-}
+impl StateBlob for TcState {
+    const VERSION: u8 = STATE_VERSION_V4;
+    const MIN_VERSION: u8 = STATE_VERSION_V1;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.stage)
+            .put(&self.player)
+            .put(&self.dealer)
+            .put(&self.pairplus_bet)
+            .put(&self.six_card_bonus_bet)
+            .put(&self.progressive_bet)
+            .put(&self.prime_bet);
+    }
 
-fn serialize_state(state: &TcState) -> Vec<u8> {
-    let mut out = Vec::with_capacity(STATE_LEN_V3);
-    out.push(STATE_VERSION_V3);
-    out.push(state.stage as u8);
-    out.extend_from_slice(&state.player);
-    out.extend_from_slice(&state.dealer);
-    out.extend_from_slice(&state.pairplus_bet.to_be_bytes());
-    out.extend_from_slice(&state.six_card_bonus_bet.to_be_bytes());
-    out.extend_from_slice(&state.progressive_bet.to_be_bytes());
-    out
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        Some(Self {
+            stage: reader.get()?,
+            player: reader.get()?,
+            dealer: reader.get()?,
+            pairplus_bet: reader.get()?,
+            six_card_bonus_bet: reader.since(STATE_VERSION_V2, 0)?,
+            progressive_bet: reader.since(STATE_VERSION_V3, 0)?,
+            prime_bet: reader.since(STATE_VERSION_V4, 0)?,
+        })
+    }
 }
 
 fn parse_u64_be(payload: &[u8], offset: usize) -> Result<u64, GameError> {
@@ -335,6 +317,17 @@ fn apply_progressive_update(state: &mut TcState, new_bet: u64) -> Result<i64, Ga
     Ok(-(delta as i64))
 }
 
+fn apply_prime_update(state: &mut TcState, new_bet: u64) -> Result<i64, GameError> {
+    let old = state.prime_bet as i128;
+    let new = new_bet as i128;
+    let delta = new - old;
+    if delta > i64::MAX as i128 || delta < i64::MIN as i128 {
+        return Err(GameError::InvalidMove);
+    }
+    state.prime_bet = new_bet;
+    Ok(-(delta as i64))
+}
+
 fn is_known_card(card: u8) -> bool {
     card < 52
 }
@@ -487,12 +480,28 @@ fn resolve_six_card_bonus_return(player_cards: &[u8; 3], dealer_cards: &[u8; 3],
     }
 }
 
-fn resolve_progressive_return(player_cards: &[u8; 3], progressive_bet: u64) -> u64 {
+/// Whether the cards are A-K-Q of spades (the progressive jackpot hand).
+fn is_spade_mini_royal(cards: &[u8; 3]) -> bool {
+    evaluate_hand(cards) == (HandRank::StraightFlush, [14, 13, 12])
+        && cards.iter().all(|&c| card_suit(c) == 0)
+}
+
+fn resolve_progressive_return(
+    player_cards: &[u8; 3],
+    dealer_cards: &[u8; 3],
+    progressive_bet: u64,
+) -> u64 {
     if progressive_bet == 0 {
         return 0;
     }
+    // The envy bonus is paid on top of the player's own hand.
+    let envy = if is_spade_mini_royal(dealer_cards) {
+        progressive_bet.saturating_mul(PROGRESSIVE_ENVY_BONUS)
+    } else {
+        0
+    };
     let player_hand = evaluate_hand(player_cards);
-    match player_hand.0 {
+    let own = match player_hand.0 {
         HandRank::StraightFlush => {
             // Mini-royal is A-K-Q suited.
             if player_hand.1 == [14, 13, 12] {
@@ -509,7 +518,63 @@ fn resolve_progressive_return(player_cards: &[u8; 3], progressive_bet: u64) -> u
         HandRank::ThreeOfAKind => progressive_bet.saturating_mul(60),
         HandRank::Straight => progressive_bet.saturating_mul(6),
         _ => 0,
+    };
+    own.saturating_add(envy)
+}
+
+/// Whether a card is red (hearts or diamonds).
+fn is_red(card: u8) -> bool {
+    matches!(card_suit(card), 1 | 2)
+}
+
+fn resolve_prime_return(player_cards: &[u8; 3], dealer_cards: &[u8; 3], prime_bet: u64) -> u64 {
+    if prime_bet == 0 {
+        return 0;
     }
+    let red = is_red(player_cards[0]);
+    if player_cards.iter().any(|&c| is_red(c) != red) {
+        return 0;
+    }
+    let mult = if dealer_cards.iter().all(|&c| is_red(c) == red) {
+        PRIME_SIX_CARD_PAYOUT_TO_1
+    } else {
+        PRIME_THREE_CARD_PAYOUT_TO_1
+    };
+    prime_bet.saturating_mul(mult.saturating_add(1))
+}
+
+/// The side bets held, with their returns once the hand is complete.
+fn resolve_side_bets(state: &TcState) -> Vec<SideBet> {
+    let bets = [
+        (SIDE_BET_PAIR_PLUS, state.pairplus_bet),
+        (SIDE_BET_SIX_CARD_BONUS, state.six_card_bonus_bet),
+        (SIDE_BET_PROGRESSIVE, state.progressive_bet),
+        (SIDE_BET_PRIME, state.prime_bet),
+    ];
+    let complete = state.stage == Stage::Complete;
+    bets.into_iter()
+        .filter(|&(_, amount)| amount > 0)
+        .map(|(id, amount)| {
+            // Nothing is returned until the dealer's cards are revealed
+            let (player, dealer) = (&state.player, &state.dealer);
+            let returned = match id {
+                _ if !complete => 0,
+                SIDE_BET_PAIR_PLUS => resolve_pairplus_return(player, amount),
+                SIDE_BET_SIX_CARD_BONUS => resolve_six_card_bonus_return(player, dealer, amount),
+                SIDE_BET_PROGRESSIVE => resolve_progressive_return(player, dealer, amount),
+                _ => resolve_prime_return(player, dealer, amount),
+            };
+            if id != SIDE_BET_PROGRESSIVE {
+                return SideBet::new(id, amount, returned);
+            }
+            let tier = if complete && is_spade_mini_royal(player) {
+                JackpotTier::RoyalFlush
+            } else {
+                JackpotTier::None
+            };
+            SideBet::progressive(id, amount, returned, Progressive::ThreeCard, tier)
+        })
+        .collect()
 }
 
 pub struct ThreeCardPoker;
@@ -524,8 +589,9 @@ impl CasinoGame for ThreeCardPoker {
             pairplus_bet: 0,
             six_card_bonus_bet: 0,
             progressive_bet: 0,
+            prime_bet: 0,
         };
-        session.state_blob = serialize_state(&state);
+        session.state_blob = state.to_blob();
         GameResult::Continue
     }
 
@@ -542,14 +608,14 @@ impl CasinoGame for ThreeCardPoker {
         }
 
         let mv = Move::try_from(payload[0])?;
-        let mut state = parse_state(&session.state_blob).ok_or(GameError::InvalidPayload)?;
+        let mut state = TcState::from_blob(&session.state_blob).ok_or(GameError::InvalidPayload)?;
 
         match state.stage {
             Stage::Betting => match mv {
                 Move::SetPairPlus => {
                     let new_bet = parse_u64_be(payload, 1)?;
                     let payout = apply_pairplus_update(&mut state, new_bet)?;
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(if payout == 0 {
                         GameResult::Continue
                     } else {
@@ -575,7 +641,7 @@ impl CasinoGame for ThreeCardPoker {
                     state.player[2] = rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
                     state.stage = Stage::Decision;
 
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(if payout_update == 0 {
                         GameResult::Continue
                     } else {
@@ -587,7 +653,7 @@ impl CasinoGame for ThreeCardPoker {
                 Move::SetSixCardBonus => {
                     let new_bet = parse_u64_be(payload, 1)?;
                     let payout = apply_six_card_bonus_update(&mut state, new_bet)?;
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(if payout == 0 {
                         GameResult::Continue
                     } else {
@@ -600,7 +666,17 @@ impl CasinoGame for ThreeCardPoker {
                         return Err(GameError::InvalidMove);
                     }
                     let payout = apply_progressive_update(&mut state, new_bet)?;
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
+                    Ok(if payout == 0 {
+                        GameResult::Continue
+                    } else {
+                        GameResult::ContinueWithUpdate { payout }
+                    })
+                }
+                Move::SetPrime => {
+                    let new_bet = parse_u64_be(payload, 1)?;
+                    let payout = apply_prime_update(&mut state, new_bet)?;
+                    session.state_blob = state.to_blob();
                     Ok(if payout == 0 {
                         GameResult::Continue
                     } else {
@@ -621,18 +697,7 @@ impl CasinoGame for ThreeCardPoker {
                     state.stage = Stage::Complete;
                     session.is_complete = true;
 
-                    let pairplus_return =
-                        resolve_pairplus_return(&state.player, state.pairplus_bet);
-                    let six_card_return = resolve_six_card_bonus_return(
-                        &state.player,
-                        &state.dealer,
-                        state.six_card_bonus_bet,
-                    );
-                    let progressive_return =
-                        resolve_progressive_return(&state.player, state.progressive_bet);
-                    let mut total_return = pairplus_return
-                        .saturating_add(six_card_return)
-                        .saturating_add(progressive_return);
+                    let mut total_return = side_bet::total_returned(&resolve_side_bets(&state));
 
                     if session.super_mode.is_active && total_return > 0 {
                         total_return = apply_super_multiplier_cards(
@@ -646,9 +711,10 @@ impl CasinoGame for ThreeCardPoker {
                         .bet
                         .saturating_add(state.pairplus_bet)
                         .saturating_add(state.six_card_bonus_bet)
-                        .saturating_add(state.progressive_bet);
+                        .saturating_add(state.progressive_bet)
+                        .saturating_add(state.prime_bet);
 
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
 
                     if total_return == 0 {
                         Ok(GameResult::LossPreDeducted(total_wagered))
@@ -659,7 +725,7 @@ impl CasinoGame for ThreeCardPoker {
                 Move::Play => {
                     // Charge Play bet (equal to ante) now; resolve on Reveal.
                     state.stage = Stage::AwaitingReveal;
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(GameResult::ContinueWithUpdate {
                         payout: -(session.bet as i64),
                     })
@@ -681,15 +747,7 @@ impl CasinoGame for ThreeCardPoker {
                     let dealer_hand = evaluate_hand(&state.dealer);
                     let dealer_ok = dealer_qualifies(&dealer_hand);
 
-                    let pairplus_return =
-                        resolve_pairplus_return(&state.player, state.pairplus_bet);
-                    let six_card_return = resolve_six_card_bonus_return(
-                        &state.player,
-                        &state.dealer,
-                        state.six_card_bonus_bet,
-                    );
-                    let progressive_return =
-                        resolve_progressive_return(&state.player, state.progressive_bet);
+                    let side_bet_return = side_bet::total_returned(&resolve_side_bets(&state));
 
                     // Ante bonus is paid when the player plays, regardless of dealer qualification/outcome.
                     let ante_bonus = session
@@ -721,11 +779,9 @@ impl CasinoGame for ThreeCardPoker {
                         }
                     }
 
-                    let mut total_return = pairplus_return
+                    let mut total_return = side_bet_return
                         .saturating_add(main_return)
-                        .saturating_add(ante_bonus)
-                        .saturating_add(six_card_return)
-                        .saturating_add(progressive_return);
+                        .saturating_add(ante_bonus);
 
                     if session.super_mode.is_active && total_return > 0 {
                         total_return = apply_super_multiplier_cards(
@@ -740,9 +796,10 @@ impl CasinoGame for ThreeCardPoker {
                         .saturating_mul(2)
                         .saturating_add(state.pairplus_bet)
                         .saturating_add(state.six_card_bonus_bet)
-                        .saturating_add(state.progressive_bet);
+                        .saturating_add(state.progressive_bet)
+                        .saturating_add(state.prime_bet);
 
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
 
                     if total_return == 0 {
                         Ok(GameResult::LossPreDeducted(total_wagered))
@@ -755,6 +812,12 @@ impl CasinoGame for ThreeCardPoker {
             Stage::Complete => Err(GameError::GameAlreadyComplete),
         }
     }
+
+    fn side_bets(session: &GameSession) -> Vec<SideBet> {
+        TcState::from_blob(&session.state_blob)
+            .map(|state| resolve_side_bets(&state))
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -782,6 +845,9 @@ mod tests {
             super_mode: nullspace_types::casino::SuperModeState::default(),
             is_tournament: false,
             tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
         }
     }
 
@@ -837,6 +903,38 @@ mod tests {
         assert!(session.is_complete);
     }
 
+    #[test]
+    fn test_state_blob() {
+        let state = TcState {
+            stage: Stage::Decision,
+            player: [1, 2, 3],
+            dealer: [CARD_UNKNOWN; 3],
+            pairplus_bet: 5,
+            six_card_bonus_bet: 6,
+            progressive_bet: 7,
+            prime_bet: 8,
+        };
+
+        // v1 blobs only carried the Pairplus bet
+        let mut v1 = state.to_blob()[..16].to_vec();
+        v1[0] = STATE_VERSION_V1;
+        assert_eq!(
+            TcState::from_blob(&v1),
+            Some(TcState {
+                six_card_bonus_bet: 0,
+                progressive_bet: 0,
+                prime_bet: 0,
+                ..state
+            })
+        );
+
+        let states: Vec<TcState> = [Stage::Betting, Stage::Decision, Stage::Complete]
+            .into_iter()
+            .map(|stage| TcState { stage, ..state })
+            .collect();
+        crate::casino::state_blob::fuzz::roundtrip(&states);
+    }
+
     #[test]
     fn test_six_card_bonus_multiplier_examples() {
         // Royal flush in diamonds + junk.
@@ -864,36 +962,148 @@ mod tests {
 
     #[test]
     fn test_progressive_paytable_examples() {
+        // Dealer: 8♥ 10♦ J♣.
+        let junk = [20u8, 35u8, 49u8];
+
         // Mini-royal in spades: A♠ K♠ Q♠.
         let player = [0u8, 12u8, 11u8];
         assert_eq!(
-            resolve_progressive_return(&player, PROGRESSIVE_BET_UNIT),
+            resolve_progressive_return(&player, &junk, PROGRESSIVE_BET_UNIT),
             THREE_CARD_PROGRESSIVE_BASE_JACKPOT
         );
 
         // Mini-royal in hearts: A♥ K♥ Q♥.
         let player = [13u8, 25u8, 24u8];
         assert_eq!(
-            resolve_progressive_return(&player, PROGRESSIVE_BET_UNIT),
+            resolve_progressive_return(&player, &junk, PROGRESSIVE_BET_UNIT),
             500
         );
 
         // Straight flush: 2♠ 3♠ 4♠.
         let player = [1u8, 2u8, 3u8];
         assert_eq!(
-            resolve_progressive_return(&player, PROGRESSIVE_BET_UNIT),
+            resolve_progressive_return(&player, &junk, PROGRESSIVE_BET_UNIT),
             70
         );
 
         // Trips: 5♠ 5♥ 5♦.
         let player = [4u8, 17u8, 30u8];
         assert_eq!(
-            resolve_progressive_return(&player, PROGRESSIVE_BET_UNIT),
+            resolve_progressive_return(&player, &junk, PROGRESSIVE_BET_UNIT),
             60
         );
 
         // Straight: 2♠ 3♥ 4♦.
         let player = [1u8, 15u8, 29u8];
-        assert_eq!(resolve_progressive_return(&player, PROGRESSIVE_BET_UNIT), 6);
+        assert_eq!(
+            resolve_progressive_return(&player, &junk, PROGRESSIVE_BET_UNIT),
+            6
+        );
+    }
+
+    #[test]
+    fn test_progressive_envy_bonus() {
+        let spade_mini_royal = [0u8, 12u8, 11u8];
+        let heart_mini_royal = [13u8, 25u8, 24u8];
+        let junk = [20u8, 35u8, 49u8];
+        let straight = [1u8, 15u8, 29u8];
+
+        // Envy pays on top of the player's hand, even a losing one.
+        assert_eq!(
+            resolve_progressive_return(&junk, &spade_mini_royal, PROGRESSIVE_BET_UNIT),
+            PROGRESSIVE_ENVY_BONUS
+        );
+        assert_eq!(
+            resolve_progressive_return(&straight, &spade_mini_royal, PROGRESSIVE_BET_UNIT),
+            6 + PROGRESSIVE_ENVY_BONUS
+        );
+
+        // Only the jackpot hand triggers it, and only for progressive bettors.
+        assert_eq!(
+            resolve_progressive_return(&junk, &heart_mini_royal, PROGRESSIVE_BET_UNIT),
+            0
+        );
+        assert_eq!(resolve_progressive_return(&junk, &spade_mini_royal, 0), 0);
+    }
+
+    #[test]
+    fn test_prime_payouts() {
+        let red = [13u8, 27u8, 38u8]; // A♥ 2♦ K♦
+        let black = [0u8, 40u8, 51u8]; // A♠ A♣ K♣
+        let mixed = [0u8, 13u8, 26u8];
+
+        assert_eq!(resolve_prime_return(&red, &mixed, 10), 40);
+        assert_eq!(resolve_prime_return(&black, &mixed, 10), 40);
+        assert_eq!(resolve_prime_return(&red, &[14, 15, 30], 10), 50);
+        assert_eq!(resolve_prime_return(&black, &[1, 41, 2], 10), 50);
+        assert_eq!(resolve_prime_return(&mixed, &red, 10), 0);
+        assert_eq!(resolve_prime_return(&red, &black, 0), 0);
+    }
+
+    #[test]
+    fn test_prime_house_edge() {
+        // Enumerate every player hand; the dealer's three cards then match the player's color
+        // with probability C(23,3)/C(49,3). WoO: house edge 3.62%.
+        let choose3 = |n: f64| n * (n - 1.0) * (n - 2.0) / 6.0;
+        let dealer_match = choose3(23.0) / choose3(49.0);
+        let mut hands = 0.0;
+        let mut total_return = 0.0;
+        for a in 0..52u8 {
+            for b in (a + 1)..52 {
+                for c in (b + 1)..52 {
+                    hands += 1.0;
+                    let cards = [a, b, c];
+                    let red = is_red(a);
+                    if cards.iter().any(|&card| is_red(card) != red) {
+                        continue;
+                    }
+                    // Any dealer hand of the other color, then one of the player's color.
+                    let other = if red { [0u8, 1, 2] } else { [13u8, 14, 15] };
+                    let same = if red { [14u8, 15, 16] } else { [0u8, 1, 2] };
+                    total_return += (1.0 - dealer_match)
+                        * resolve_prime_return(&cards, &other, 1) as f64
+                        + dealer_match * resolve_prime_return(&cards, &same, 1) as f64;
+                }
+            }
+        }
+        let edge = 1.0 - total_return / hands;
+        assert!((edge - 0.0362).abs() < 0.00005, "{edge}");
+    }
+
+    #[test]
+    fn test_prime_bet_flow() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+        ThreeCardPoker::init(&mut session, &mut rng);
+
+        let mut payload = vec![Move::SetPrime as u8];
+        payload.extend_from_slice(&25u64.to_be_bytes());
+        let res = ThreeCardPoker::process_move(&mut session, &payload, &mut rng).unwrap();
+        assert!(matches!(
+            res,
+            GameResult::ContinueWithUpdate { payout: -25 }
+        ));
+        assert_eq!(session.state_blob.len(), 40);
+        assert_eq!(session.state_blob[0], STATE_VERSION_V4);
+        assert_eq!(
+            TcState::from_blob(&session.state_blob).unwrap().prime_bet,
+            25
+        );
+
+        // Fold still settles Prime against the revealed dealer hand.
+        ThreeCardPoker::process_move(&mut session, &[Move::Deal as u8], &mut rng).unwrap();
+        let res =
+            ThreeCardPoker::process_move(&mut session, &[Move::Fold as u8], &mut rng).unwrap();
+        let state = TcState::from_blob(&session.state_blob).unwrap();
+        let prime = resolve_prime_return(&state.player, &state.dealer, 25);
+        match res {
+            GameResult::Win(total) => assert_eq!(total, prime),
+            GameResult::LossPreDeducted(wagered) => {
+                assert_eq!(prime, 0);
+                assert_eq!(wagered, 125);
+            }
+            _ => panic!("unexpected result"),
+        }
     }
 }
@@ -61,6 +61,8 @@
 //! 9 = Set 6-Card Bonus bet (u64)
 //! 10 = Set Progressive bet (u64)
 
+use super::side_bet::{self, JackpotTier, Progressive, SideBet};
+use super::state_blob::{impl_blob_field_enum, BlobReader, BlobWriter, StateBlob};
 use super::super_mode::apply_super_multiplier_cards;
 use super::{CasinoGame, GameError, GameResult, GameRng};
 use nullspace_types::casino::{GameSession, UTH_PROGRESSIVE_BASE_JACKPOT};
@@ -69,12 +71,14 @@ const STATE_VERSION_V1: u8 = 1;
 const STATE_VERSION_V2: u8 = 2;
 const STATE_VERSION_V3: u8 = 3;
 const CARD_UNKNOWN: u8 = 0xFF;
-const STATE_LEN_V1: usize = 20;
-const STATE_LEN_V2: usize = 32;
-const STATE_LEN_V3: usize = 40;
 
 const PROGRESSIVE_BET_UNIT: u64 = 1;
 
+/// Side bet identifiers (reported by [side_bet::SideBet::id]).
+pub const SIDE_BET_TRIPS: u8 = 0;
+pub const SIDE_BET_SIX_CARD_BONUS: u8 = 1;
+pub const SIDE_BET_PROGRESSIVE: u8 = 2;
+
 /// Game stages.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -103,6 +107,8 @@ impl TryFrom<u8> for Stage {
     }
 }
 
+impl_blob_field_enum!(Stage);
+
 /// Player actions.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -154,91 +160,36 @@ struct UthState {
     progressive_bet: u64,
 }
 
-fn parse_state(state: &[u8]) -> Option<UthState> {
-    if state.len() == STATE_LEN_V1 && state[0] == STATE_VERSION_V1 {
-        let stage = Stage::try_from(state[1]).ok()?;
-        let player = [state[2], state[3]];
-        let community = [state[4], state[5], state[6], state[7], state[8]];
-        let dealer = [state[9], state[10]];
-        let play_mult = state[11];
-        let trips_bet = u64::from_be_bytes(state[12..20].try_into().ok()?);
-
-        return Some(UthState {
-            stage,
-            player,
-            community,
-            dealer,
-            play_mult,
-            trips_bet,
-            bonus: [CARD_UNKNOWN; 4],
-            six_card_bonus_bet: 0,
-            progressive_bet: 0,
-        });
-    }
-
-    if state.len() == STATE_LEN_V2 && state[0] == STATE_VERSION_V2 {
-        let stage = Stage::try_from(state[1]).ok()?;
-        let player = [state[2], state[3]];
-        let community = [state[4], state[5], state[6], state[7], state[8]];
-        let dealer = [state[9], state[10]];
-        let play_mult = state[11];
-        let bonus = [state[12], state[13], state[14], state[15]];
-        let trips_bet = u64::from_be_bytes(state[16..24].try_into().ok()?);
-        let six_card_bonus_bet = u64::from_be_bytes(state[24..32].try_into().ok()?);
-
-        return Some(UthState {
-            stage,
-            player,
-            community,
-            dealer,
-            play_mult,
-            trips_bet,
-            bonus,
-            six_card_bonus_bet,
-            progressive_bet: 0,
-        });
+impl StateBlob for UthState {
+    const VERSION: u8 = STATE_VERSION_V3;
+    const MIN_VERSION: u8 = STATE_VERSION_V1;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.stage)
+            .put(&self.player)
+            .put(&self.community)
+            .put(&self.dealer)
+            .put(&self.play_mult)
+            .put(&self.bonus)
+            .put(&self.trips_bet)
+            .put(&self.six_card_bonus_bet)
+            .put(&self.progressive_bet);
     }
 
-    if state.len() == STATE_LEN_V3 && state[0] == STATE_VERSION_V3 {
-        let stage = Stage::try_from(state[1]).ok()?;
-        let player = [state[2], state[3]];
-        let community = [state[4], state[5], state[6], state[7], state[8]];
-        let dealer = [state[9], state[10]];
-        let play_mult = state[11];
-        let bonus = [state[12], state[13], state[14], state[15]];
-        let trips_bet = u64::from_be_bytes(state[16..24].try_into().ok()?);
-        let six_card_bonus_bet = u64::from_be_bytes(state[24..32].try_into().ok()?);
-        let progressive_bet = u64::from_be_bytes(state[32..40].try_into().ok()?);
-
-        return Some(UthState {
-            stage,
-            player,
-            community,
-            dealer,
-            play_mult,
-            trips_bet,
-            bonus,
-            six_card_bonus_bet,
-            progressive_bet,
-        });
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        Some(Self {
+            stage: reader.get()?,
+            player: reader.get()?,
+            community: reader.get()?,
+            dealer: reader.get()?,
+            play_mult: reader.get()?,
+            bonus: reader.since(STATE_VERSION_V2, [CARD_UNKNOWN; 4])?,
+            trips_bet: reader.get()?,
+            six_card_bonus_bet: reader.since(STATE_VERSION_V2, 0)?,
+            progressive_bet: reader.since(STATE_VERSION_V3, 0)?,
+        })
     }
-
-    None
-}
-
-fn serialize_state(state: &UthState) -> Vec<u8> {
-    let mut out = Vec::with_capacity(STATE_LEN_V3);
-    out.push(STATE_VERSION_V3);
-    out.push(state.stage as u8);
-    out.extend_from_slice(&state.player);
-    out.extend_from_slice(&state.community);
-    out.extend_from_slice(&state.dealer);
-    out.push(state.play_mult);
-    out.extend_from_slice(&state.bonus);
-    out.extend_from_slice(&state.trips_bet.to_be_bytes());
-    out.extend_from_slice(&state.six_card_bonus_bet.to_be_bytes());
-    out.extend_from_slice(&state.progressive_bet.to_be_bytes());
-    out
 }
 
 fn is_known_card(card: u8) -> bool {
@@ -564,6 +515,57 @@ fn uth_progressive_return(hole: &[u8; 2], flop: &[u8; 3], progressive_bet: u64)
     }
 }
 
+/// The side bets held, with their returns once the hand reaches the showdown.
+fn resolve_side_bets(state: &UthState) -> Vec<SideBet> {
+    let bets = [
+        (SIDE_BET_TRIPS, state.trips_bet),
+        (SIDE_BET_SIX_CARD_BONUS, state.six_card_bonus_bet),
+        (SIDE_BET_PROGRESSIVE, state.progressive_bet),
+    ];
+    let complete = state.stage == Stage::Showdown;
+    let flop = [state.community[0], state.community[1], state.community[2]];
+    let pays = |amount: u64, mult: u64| {
+        if mult > 0 {
+            amount.saturating_mul(mult.saturating_add(1))
+        } else {
+            0
+        }
+    };
+    bets.into_iter()
+        .filter(|&(_, amount)| amount > 0)
+        .map(|(id, amount)| {
+            // Nothing is returned until the showdown
+            let returned = match id {
+                _ if !complete => 0,
+                SIDE_BET_TRIPS => {
+                    let [c1, c2, c3, c4, c5] = state.community;
+                    let cards = [state.player[0], state.player[1], c1, c2, c3, c4, c5];
+                    pays(amount, trips_multiplier(evaluate_best_hand(&cards).0))
+                }
+                SIDE_BET_SIX_CARD_BONUS => {
+                    let [b1, b2, b3, b4] = state.bonus;
+                    let cards = [state.player[0], state.player[1], b1, b2, b3, b4];
+                    pays(
+                        amount,
+                        six_card_bonus_multiplier(evaluate_best_6_card_bonus(&cards)),
+                    )
+                }
+                _ => uth_progressive_return(&state.player, &flop, amount),
+            };
+            if id != SIDE_BET_PROGRESSIVE {
+                return SideBet::new(id, amount, returned);
+            }
+            let [h1, h2] = state.player;
+            let tier = match evaluate_5_card_fast(&[h1, h2, flop[0], flop[1], flop[2]]).0 {
+                HandRank::RoyalFlush if complete => JackpotTier::RoyalFlush,
+                HandRank::StraightFlush if complete => JackpotTier::StraightFlush,
+                _ => JackpotTier::None,
+            };
+            SideBet::progressive(id, amount, returned, Progressive::UltimateHoldem, tier)
+        })
+        .collect()
+}
+
 fn resolve_showdown(
     session: &mut GameSession,
     state: &mut UthState,
@@ -622,44 +624,9 @@ fn resolve_showdown(
         || (player_hand.0 == dealer_hand.0 && player_hand.1 > dealer_hand.1);
     let tie = player_hand.0 == dealer_hand.0 && player_hand.1 == dealer_hand.1;
 
-    let mut total_return: u64 = 0;
-
-    // Progressive side bet (independent of dealer; based on hole + flop only).
-    if progressive_bet > 0 {
-        let flop = [state.community[0], state.community[1], state.community[2]];
-        total_return = total_return.saturating_add(uth_progressive_return(
-            &state.player,
-            &flop,
-            progressive_bet,
-        ));
-    }
-
-    // Trips side bet (independent of dealer).
-    if trips_bet > 0 {
-        let mult = trips_multiplier(player_hand.0);
-        if mult > 0 {
-            total_return =
-                total_return.saturating_add(trips_bet.saturating_mul(mult.saturating_add(1)));
-        }
-    }
-
-    // 6-card bonus side bet (independent of dealer).
-    if six_card_bonus_bet > 0 {
-        let cards = [
-            state.player[0],
-            state.player[1],
-            state.bonus[0],
-            state.bonus[1],
-            state.bonus[2],
-            state.bonus[3],
-        ];
-        let rank = evaluate_best_6_card_bonus(&cards);
-        let mult = six_card_bonus_multiplier(rank);
-        if mult > 0 {
-            total_return = total_return
-                .saturating_add(six_card_bonus_bet.saturating_mul(mult.saturating_add(1)));
-        }
-    }
+    // Side bets (Trips, 6-card bonus and Progressive) are independent of the dealer
+    state.stage = Stage::Showdown;
+    let mut total_return = side_bet::total_returned(&resolve_side_bets(state));
 
     // Main bets.
     if state.play_mult == 0 {
@@ -702,7 +669,6 @@ fn resolve_showdown(
         );
     }
 
-    state.stage = Stage::Showdown;
     session.is_complete = true;
 
     if total_return == 0 {
@@ -729,7 +695,7 @@ impl CasinoGame for UltimateHoldem {
             six_card_bonus_bet: 0,
             progressive_bet: 0,
         };
-        session.state_blob = serialize_state(&state);
+        session.state_blob = state.to_blob();
         GameResult::ContinueWithUpdate {
             payout: -(session.bet as i64),
         }
@@ -748,7 +714,8 @@ impl CasinoGame for UltimateHoldem {
         }
 
         let action = Action::try_from(payload[0])?;
-        let mut state = parse_state(&session.state_blob).ok_or(GameError::InvalidPayload)?;
+        let mut state =
+            UthState::from_blob(&session.state_blob).ok_or(GameError::InvalidPayload)?;
 
         let mut payout_update: i64 = 0;
 
@@ -757,7 +724,7 @@ impl CasinoGame for UltimateHoldem {
                 Action::SetTrips => {
                     let new_trips = parse_u64_be(payload, 1)?;
                     payout_update = apply_trips_update(&mut state, new_trips)?;
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(if payout_update == 0 {
                         GameResult::Continue
                     } else {
@@ -769,7 +736,7 @@ impl CasinoGame for UltimateHoldem {
                 Action::SetSixCardBonus => {
                     let new_bet = parse_u64_be(payload, 1)?;
                     payout_update = apply_six_card_bonus_update(&mut state, new_bet)?;
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(if payout_update == 0 {
                         GameResult::Continue
                     } else {
@@ -784,7 +751,7 @@ impl CasinoGame for UltimateHoldem {
                         return Err(GameError::InvalidMove);
                     }
                     payout_update = apply_progressive_update(&mut state, new_bet)?;
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(if payout_update == 0 {
                         GameResult::Continue
                     } else {
@@ -811,7 +778,7 @@ impl CasinoGame for UltimateHoldem {
                     state.player[1] = rng.draw_card(&mut deck).ok_or(GameError::DeckExhausted)?;
                     state.stage = Stage::Preflop;
 
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(if payout_update == 0 {
                         GameResult::Continue
                     } else {
@@ -834,7 +801,7 @@ impl CasinoGame for UltimateHoldem {
                         }
                     }
                     state.stage = Stage::Flop;
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(GameResult::Continue)
                 }
                 Action::Bet4x => {
@@ -844,7 +811,7 @@ impl CasinoGame for UltimateHoldem {
                     let play_bet = session.bet.saturating_mul(4);
                     state.play_mult = 4;
                     state.stage = Stage::AwaitingReveal;
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(GameResult::ContinueWithUpdate {
                         payout: -(play_bet as i64),
                     })
@@ -856,7 +823,7 @@ impl CasinoGame for UltimateHoldem {
                     let play_bet = session.bet.saturating_mul(3);
                     state.play_mult = 3;
                     state.stage = Stage::AwaitingReveal;
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(GameResult::ContinueWithUpdate {
                         payout: -(play_bet as i64),
                     })
@@ -875,7 +842,7 @@ impl CasinoGame for UltimateHoldem {
                         }
                     }
                     state.stage = Stage::River;
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(GameResult::Continue)
                 }
                 Action::Bet2x => {
@@ -885,7 +852,7 @@ impl CasinoGame for UltimateHoldem {
                     let play_bet = session.bet.saturating_mul(2);
                     state.play_mult = 2;
                     state.stage = Stage::AwaitingReveal;
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(GameResult::ContinueWithUpdate {
                         payout: -(play_bet as i64),
                     })
@@ -900,7 +867,7 @@ impl CasinoGame for UltimateHoldem {
                     let play_bet = session.bet;
                     state.play_mult = 1;
                     state.stage = Stage::AwaitingReveal;
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(GameResult::ContinueWithUpdate {
                         payout: -(play_bet as i64),
                     })
@@ -911,7 +878,7 @@ impl CasinoGame for UltimateHoldem {
                     state.play_mult = 0;
                     // Resolve as a showdown with fold semantics (no main-bet returns).
                     let result = resolve_showdown(session, &mut state)?;
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(result)
                 }
                 _ => Err(GameError::InvalidMove),
@@ -923,7 +890,7 @@ impl CasinoGame for UltimateHoldem {
                     }
                     draw_into_unknowns(&mut state, rng, true)?;
                     let result = resolve_showdown(session, &mut state)?;
-                    session.state_blob = serialize_state(&state);
+                    session.state_blob = state.to_blob();
                     Ok(result)
                 }
                 _ => Err(GameError::InvalidMove),
@@ -931,6 +898,12 @@ impl CasinoGame for UltimateHoldem {
             Stage::Showdown => Err(GameError::GameAlreadyComplete),
         }
     }
+
+    fn side_bets(session: &GameSession) -> Vec<SideBet> {
+        UthState::from_blob(&session.state_blob)
+            .map(|state| resolve_side_bets(&state))
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -958,9 +931,52 @@ mod tests {
             super_mode: nullspace_types::casino::SuperModeState::default(),
             is_tournament: false,
             tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
         }
     }
 
+    #[test]
+    fn test_state_blob() {
+        let state = UthState {
+            stage: Stage::Flop,
+            player: [1, 2],
+            community: [3, 4, 5, CARD_UNKNOWN, CARD_UNKNOWN],
+            dealer: [CARD_UNKNOWN; 2],
+            play_mult: 0,
+            trips_bet: 5,
+            bonus: [6, 7, 8, 9],
+            six_card_bonus_bet: 10,
+            progressive_bet: 11,
+        };
+
+        // v1 blobs had no 6-Card Bonus (or its cards) and no progressive
+        let mut v1 = vec![STATE_VERSION_V1];
+        v1.extend_from_slice(&state.to_blob()[1..12]);
+        v1.extend_from_slice(&state.trips_bet.to_be_bytes());
+        assert_eq!(
+            UthState::from_blob(&v1),
+            Some(UthState {
+                bonus: [CARD_UNKNOWN; 4],
+                six_card_bonus_bet: 0,
+                progressive_bet: 0,
+                ..state
+            })
+        );
+
+        let states: Vec<UthState> = [Stage::Betting, Stage::River, Stage::Showdown]
+            .into_iter()
+            .enumerate()
+            .map(|(play_mult, stage)| UthState {
+                stage,
+                play_mult: play_mult as u8,
+                ..state
+            })
+            .collect();
+        crate::casino::state_blob::fuzz::roundtrip(&states);
+    }
+
     #[test]
     fn test_init_starts_in_betting_and_deducts_blind() {
         let seed = create_test_seed();
@@ -973,7 +989,7 @@ mod tests {
             GameResult::ContinueWithUpdate { payout: -100 }
         ));
 
-        let state = parse_state(&session.state_blob).expect("Failed to parse state");
+        let state = UthState::from_blob(&session.state_blob).expect("Failed to parse state");
         assert_eq!(state.stage, Stage::Betting);
         assert_eq!(state.player, [CARD_UNKNOWN; 2]);
     }
@@ -1002,7 +1018,7 @@ mod tests {
             UltimateHoldem::process_move(&mut session, &[Action::Deal as u8], &mut rng).unwrap();
         assert!(matches!(res, GameResult::Continue));
 
-        let state = parse_state(&session.state_blob).expect("Failed to parse state");
+        let state = UthState::from_blob(&session.state_blob).expect("Failed to parse state");
         assert_eq!(state.stage, Stage::Preflop);
         assert!(state.player.iter().all(|&c| is_known_card(c)));
         assert_eq!(state.trips_bet, 25);
@@ -1033,7 +1049,7 @@ mod tests {
         let res = UltimateHoldem::process_move(&mut session, &payload, &mut rng).unwrap();
         assert!(matches!(res, GameResult::ContinueWithUpdate { payout: 25 }));
 
-        let state = parse_state(&session.state_blob).expect("Failed to parse state");
+        let state = UthState::from_blob(&session.state_blob).expect("Failed to parse state");
         assert_eq!(state.trips_bet, 0);
     }
 
@@ -1068,7 +1084,7 @@ mod tests {
         let mut rng = GameRng::new(&seed, session.id, 4);
         UltimateHoldem::process_move(&mut session, &[Action::Reveal as u8], &mut rng).unwrap();
 
-        let state = parse_state(&session.state_blob).expect("Failed to parse state");
+        let state = UthState::from_blob(&session.state_blob).expect("Failed to parse state");
         assert!(state.bonus.iter().all(|&c| is_known_card(c)));
         assert_eq!(state.six_card_bonus_bet, 25);
     }
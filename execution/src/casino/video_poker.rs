@@ -1,17 +1,46 @@
-//! Video Poker (Jacks or Better) implementation.
+//! Video Poker implementation (Jacks or Better, Bonus Poker, Double Bonus and Deuces Wild).
 //!
 //! State blob format:
-//! [stage:u8] [card1:u8] [card2:u8] [card3:u8] [card4:u8] [card5:u8]
+//! v3:
+//! [version:u8=3] [stage:u8] [variant:u8] [card1:u8] ... [card5:u8]
+//! [hand_count:u8] [hands:(card1:u8 ... card5:u8, hand:u8)×hand_count]
+//! [winnings:u64 BE] [gamble_count:u8] [gambles:(dealer:u8, player:u8)×gamble_count]
 //!
-//! Stage: 0 = Deal (initial), 1 = Draw (after hold selection)
+//! v2: v3 without the winnings and gambles.
+//!
+//! v1:
+//! [stage:u8] [card1:u8] [card2:u8] [card3:u8] [card4:u8] [card5:u8] [variant:u8]?
+//!
+//! Stage: 0 = Deal (initial), 1 = Draw (after hold selection), 2 = DoubleUp (winnings at stake)
+//! Variant: 0 = Jacks or Better, 1 = Bonus Poker, 2 = Double Bonus, 3 = Deuces Wild (v1 blobs
+//! without it are Jacks or Better). The variant is chosen from the game config's paytable when
+//! the session starts.
+//! The cards are the dealt hand, then the first hand's final cards after the draw. Hands (empty
+//! until the draw) hold each hand's final cards and ranking (`Hand` as u8).
+//! Winnings are what a draw played with double-up won (doubled by each gamble won since), and
+//! the gambles are the cards of each double-up taken so far.
 //!
 //! Payload format:
-//! [holdMask:u8] - bits indicate which cards to hold
+//! [holdMask:u8] [hands:u8]? [doubleUp:u8]? - bits indicate which cards to hold
 //! bit 0 = hold card 1, bit 1 = hold card 2, etc.
+//! hands = 1 (default), 3, 5 or 10: the held cards are completed once per hand, each from its
+//! own copy of the deck, and each extra hand costs another bet.
+//! doubleUp = 1 keeps a winning draw's winnings at the table (instead of paying them out) so
+//! they can be gambled:
+//!
+//! [0] - Take the winnings (ends the session)
+//! [1] - Double up: the dealer shows a card and the player draws one (Aces high). A higher card
+//!       doubles the winnings, the same rank leaves them as they are and a lower card loses them
+//!       (ending the session). After `MAX_DOUBLE_UPS` gambles the winnings are paid out.
+//!
+//! Extra hands played with double-up are charged with the draw (`ContinueWithUpdate`), since
+//! the session stays open. Super mode multipliers only apply to the hands drawn, never to a
+//! double-up.
 
+use super::state_blob::{impl_blob_field_enum, BlobReader, BlobWriter, StateBlob};
 use super::super_mode::apply_super_multiplier_cards;
 use super::{CasinoGame, GameError, GameResult, GameRng};
-use nullspace_types::casino::GameSession;
+use nullspace_types::casino::{GameConfig, GameSession};
 
 /// Video Poker stages.
 #[repr(u8)]
@@ -19,6 +48,7 @@ use nullspace_types::casino::GameSession;
 pub enum Stage {
     Deal = 0,
     Draw = 1,
+    DoubleUp = 2,
 }
 
 impl TryFrom<u8> for Stage {
@@ -28,11 +58,60 @@ impl TryFrom<u8> for Stage {
         match value {
             0 => Ok(Stage::Deal),
             1 => Ok(Stage::Draw),
+            2 => Ok(Stage::DoubleUp),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+impl_blob_field_enum!(Stage);
+
+/// Game variants (each with its own paytable).
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Variant {
+    #[default]
+    JacksOrBetter = 0,
+    BonusPoker = 1,
+    DoubleBonus = 2,
+    DeucesWild = 3,
+}
+
+impl Variant {
+    /// Every variant, in paytable order.
+    pub const ALL: [Variant; 4] = [
+        Variant::JacksOrBetter,
+        Variant::BonusPoker,
+        Variant::DoubleBonus,
+        Variant::DeucesWild,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Variant::JacksOrBetter => "Jacks or Better",
+            Variant::BonusPoker => "Bonus Poker",
+            Variant::DoubleBonus => "Double Bonus",
+            Variant::DeucesWild => "Deuces Wild",
+        }
+    }
+}
+
+impl TryFrom<u8> for Variant {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Variant::JacksOrBetter),
+            1 => Ok(Variant::BonusPoker),
+            2 => Ok(Variant::DoubleBonus),
+            3 => Ok(Variant::DeucesWild),
             _ => Err(GameError::InvalidPayload),
         }
     }
 }
 
+impl_blob_field_enum!(Variant);
+
 /// Poker hand rankings.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Hand {
@@ -43,9 +122,69 @@ pub enum Hand {
     Straight = 4,
     Flush = 5,
     FullHouse = 6,
+    /// Four of a kind (Fives through Kings in the bonus variants).
     FourOfAKind = 7,
-    StraightFlush = 8,
-    RoyalFlush = 9,
+    /// Four Twos, Threes or Fours (bonus variants).
+    FourTwosThroughFours = 8,
+    /// Four Aces (bonus variants).
+    FourAces = 9,
+    StraightFlush = 10,
+    /// Five of a kind, made with wild cards (Deuces Wild).
+    FiveOfAKind = 11,
+    /// A royal flush made with wild cards (Deuces Wild).
+    WildRoyalFlush = 12,
+    /// All four Deuces (Deuces Wild).
+    FourDeuces = 13,
+    RoyalFlush = 14,
+}
+
+impl TryFrom<u8> for Hand {
+    type Error = GameError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Hand::HighCard),
+            1 => Ok(Hand::JacksOrBetter),
+            2 => Ok(Hand::TwoPair),
+            3 => Ok(Hand::ThreeOfAKind),
+            4 => Ok(Hand::Straight),
+            5 => Ok(Hand::Flush),
+            6 => Ok(Hand::FullHouse),
+            7 => Ok(Hand::FourOfAKind),
+            8 => Ok(Hand::FourTwosThroughFours),
+            9 => Ok(Hand::FourAces),
+            10 => Ok(Hand::StraightFlush),
+            11 => Ok(Hand::FiveOfAKind),
+            12 => Ok(Hand::WildRoyalFlush),
+            13 => Ok(Hand::FourDeuces),
+            14 => Ok(Hand::RoyalFlush),
+            _ => Err(GameError::InvalidPayload),
+        }
+    }
+}
+
+impl_blob_field_enum!(Hand);
+
+impl Hand {
+    pub fn name(self) -> &'static str {
+        match self {
+            Hand::HighCard => "High Card",
+            Hand::JacksOrBetter => "Jacks or Better",
+            Hand::TwoPair => "Two Pair",
+            Hand::ThreeOfAKind => "Three of a Kind",
+            Hand::Straight => "Straight",
+            Hand::Flush => "Flush",
+            Hand::FullHouse => "Full House",
+            Hand::FourOfAKind => "Four of a Kind",
+            Hand::FourTwosThroughFours => "Four 2s-4s",
+            Hand::FourAces => "Four Aces",
+            Hand::StraightFlush => "Straight Flush",
+            Hand::FiveOfAKind => "Five of a Kind",
+            Hand::WildRoyalFlush => "Wild Royal Flush",
+            Hand::FourDeuces => "Four Deuces",
+            Hand::RoyalFlush => "Royal Flush",
+        }
+    }
 }
 
 /// Get card rank (1-13, Ace = 1).
@@ -147,40 +286,307 @@ pub fn evaluate_hand(cards: &[u8; 5]) -> Hand {
     }
 }
 
-/// Payout multiplier for each hand (Jacks or Better paytable).
-fn payout_multiplier(hand: Hand) -> u64 {
-    match hand {
-        Hand::HighCard => 0,
-        Hand::JacksOrBetter => 1,
-        Hand::TwoPair => 2,
-        Hand::ThreeOfAKind => 3,
-        Hand::Straight => 4,
-        Hand::Flush => 6,
-        Hand::FullHouse => 9,
-        Hand::FourOfAKind => 25,
-        Hand::StraightFlush => 50,
-        Hand::RoyalFlush => 800,
+/// Whether `card` is a Deuce (wild in Deuces Wild).
+fn is_deuce(card: u8) -> bool {
+    card_rank(card) == 2
+}
+
+/// Evaluate a 5-card Deuces Wild hand. Three of a kind is the lowest paying hand, so anything
+/// below it is `HighCard`.
+pub fn evaluate_deuces_wild(cards: &[u8; 5]) -> Hand {
+    let wilds = cards.iter().filter(|&&card| is_deuce(card)).count() as u8;
+    if wilds == 0 {
+        return match evaluate_hand(cards) {
+            Hand::TwoPair | Hand::JacksOrBetter => Hand::HighCard,
+            hand => hand,
+        };
     }
+    if wilds == 4 {
+        return Hand::FourDeuces;
+    }
+
+    let naturals: Vec<u8> = cards
+        .iter()
+        .copied()
+        .filter(|&card| !is_deuce(card))
+        .collect();
+    let mut counts = [0u8; 14];
+    for &card in &naturals {
+        counts[card_rank(card) as usize] += 1;
+    }
+    let most = counts.iter().copied().max().unwrap_or(0);
+    let pairs = counts.iter().filter(|&&count| count == 2).count();
+    let is_flush = naturals
+        .iter()
+        .all(|&card| card_suit(card) == card_suit(naturals[0]));
+
+    // The naturals fit in five consecutive ranks (Aces low or high)
+    let is_straight = most == 1 && {
+        let low = naturals.iter().map(|&card| card_rank(card));
+        let high = low.clone().map(|rank| if rank == 1 { 14 } else { rank });
+        low.clone().max().unwrap_or(0) - low.min().unwrap_or(0) <= 4
+            || high.clone().max().unwrap_or(0) - high.min().unwrap_or(0) <= 4
+    };
+    let is_royal = is_straight
+        && naturals
+            .iter()
+            .all(|&card| matches!(card_rank(card), 1 | 10..=13));
+
+    if is_royal && is_flush {
+        Hand::WildRoyalFlush
+    } else if most + wilds == 5 {
+        Hand::FiveOfAKind
+    } else if is_straight && is_flush {
+        Hand::StraightFlush
+    } else if most + wilds == 4 {
+        Hand::FourOfAKind
+    } else if pairs == 2 {
+        // One wild card joins one of the pairs
+        Hand::FullHouse
+    } else if is_flush {
+        Hand::Flush
+    } else if is_straight {
+        Hand::Straight
+    } else if most + wilds == 3 {
+        Hand::ThreeOfAKind
+    } else {
+        Hand::HighCard
+    }
+}
+
+/// Evaluate a 5-card hand under `variant`'s rules.
+pub fn evaluate_variant(variant: Variant, cards: &[u8; 5]) -> Hand {
+    match variant {
+        Variant::JacksOrBetter => evaluate_hand(cards),
+        Variant::BonusPoker | Variant::DoubleBonus => match evaluate_hand(cards) {
+            Hand::FourOfAKind => {
+                // The middle card of the sorted hand always belongs to the four
+                let mut ranks = cards.map(card_rank);
+                ranks.sort_unstable();
+                match ranks[2] {
+                    1 => Hand::FourAces,
+                    2..=4 => Hand::FourTwosThroughFours,
+                    _ => Hand::FourOfAKind,
+                }
+            }
+            hand => hand,
+        },
+        Variant::DeucesWild => evaluate_deuces_wild(cards),
+    }
+}
+
+/// Paytables (winning hands from the best down, and what they pay "to 1").
+const JACKS_OR_BETTER_PAYTABLE: &[(Hand, u64)] = &[
+    (Hand::RoyalFlush, 800),
+    (Hand::StraightFlush, 50),
+    (Hand::FourOfAKind, 25),
+    (Hand::FullHouse, 9),
+    (Hand::Flush, 6),
+    (Hand::Straight, 4),
+    (Hand::ThreeOfAKind, 3),
+    (Hand::TwoPair, 2),
+    (Hand::JacksOrBetter, 1),
+];
+
+const BONUS_POKER_PAYTABLE: &[(Hand, u64)] = &[
+    (Hand::RoyalFlush, 800),
+    (Hand::StraightFlush, 50),
+    (Hand::FourAces, 80),
+    (Hand::FourTwosThroughFours, 40),
+    (Hand::FourOfAKind, 25),
+    (Hand::FullHouse, 8),
+    (Hand::Flush, 5),
+    (Hand::Straight, 4),
+    (Hand::ThreeOfAKind, 3),
+    (Hand::TwoPair, 2),
+    (Hand::JacksOrBetter, 1),
+];
+
+const DOUBLE_BONUS_PAYTABLE: &[(Hand, u64)] = &[
+    (Hand::RoyalFlush, 800),
+    (Hand::StraightFlush, 50),
+    (Hand::FourAces, 160),
+    (Hand::FourTwosThroughFours, 80),
+    (Hand::FourOfAKind, 50),
+    (Hand::FullHouse, 10),
+    (Hand::Flush, 7),
+    (Hand::Straight, 5),
+    (Hand::ThreeOfAKind, 3),
+    (Hand::TwoPair, 1),
+    (Hand::JacksOrBetter, 1),
+];
+
+const DEUCES_WILD_PAYTABLE: &[(Hand, u64)] = &[
+    (Hand::RoyalFlush, 800),
+    (Hand::FourDeuces, 200),
+    (Hand::WildRoyalFlush, 25),
+    (Hand::FiveOfAKind, 15),
+    (Hand::StraightFlush, 9),
+    (Hand::FourOfAKind, 5),
+    (Hand::FullHouse, 3),
+    (Hand::Flush, 2),
+    (Hand::Straight, 2),
+    (Hand::ThreeOfAKind, 1),
+];
+
+/// The paytable of `variant`.
+pub fn paytable(variant: Variant) -> &'static [(Hand, u64)] {
+    match variant {
+        Variant::JacksOrBetter => JACKS_OR_BETTER_PAYTABLE,
+        Variant::BonusPoker => BONUS_POKER_PAYTABLE,
+        Variant::DoubleBonus => DOUBLE_BONUS_PAYTABLE,
+        Variant::DeucesWild => DEUCES_WILD_PAYTABLE,
+    }
+}
+
+/// Payout multiplier for a hand under `variant`'s paytable (0 if it doesn't pay).
+fn payout_multiplier(variant: Variant, hand: Hand) -> u64 {
+    paytable(variant)
+        .iter()
+        .find(|&&(paying, _)| paying == hand)
+        .map_or(0, |&(_, multiplier)| multiplier)
+}
+
+/// Current state blob version.
+const STATE_VERSION: u8 = 3;
+
+/// Hand counts a player can draw (one draw per copy of the deck).
+const HAND_COUNTS: [u8; 4] = [1, 3, 5, 10];
+
+/// Most double-ups a player can take on one draw's winnings.
+const MAX_DOUBLE_UPS: usize = 5;
+
+/// Game state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct VideoPokerState {
+    stage: Stage,
+    /// The dealt cards, then the first hand's final cards after the draw.
+    cards: [u8; 5],
+    variant: Variant,
+    /// Every hand's final cards and ranking (empty until the draw).
+    hands: Vec<([u8; 5], Hand)>,
+    /// Winnings kept at the table for double-up (0 unless the draw was played with it).
+    winnings: u64,
+    /// Each double-up's dealer and player cards.
+    gambles: Vec<(u8, u8)>,
 }
 
-fn parse_state(state: &[u8]) -> Option<(Stage, [u8; 5])> {
-    if state.len() < 6 {
-        return None;
+impl StateBlob for VideoPokerState {
+    const VERSION: u8 = STATE_VERSION;
+    const MIN_VERSION: u8 = 2;
+
+    fn write(&self, writer: &mut BlobWriter) {
+        writer
+            .put(&self.stage)
+            .put(&self.variant)
+            .put(&self.cards)
+            .put_list(&self.hands)
+            .put(&self.winnings)
+            .put_list(&self.gambles);
+    }
+
+    fn read(reader: &mut BlobReader<'_>) -> Option<Self> {
+        let state = Self {
+            stage: reader.get()?,
+            variant: reader.get()?,
+            cards: reader.get()?,
+            hands: reader.get_list(HAND_COUNTS[HAND_COUNTS.len() - 1] as usize)?,
+            winnings: reader.since(3, 0)?,
+            gambles: if reader.version() >= 3 {
+                reader.get_list(MAX_DOUBLE_UPS)?
+            } else {
+                Vec::new()
+            },
+        };
+        // Double-up needs winnings to gamble
+        let valid = state.stage != Stage::DoubleUp
+            || (state.winnings > 0
+                && !state.hands.is_empty()
+                && state.gambles.len() < MAX_DOUBLE_UPS);
+        valid.then_some(state)
     }
-    let stage = Stage::try_from(state[0]).ok()?;
-    let cards = [state[1], state[2], state[3], state[4], state[5]];
-    Some((stage, cards))
 }
 
-fn serialize_state(stage: Stage, cards: &[u8; 5]) -> Vec<u8> {
-    vec![
-        stage as u8,
-        cards[0],
-        cards[1],
-        cards[2],
-        cards[3],
-        cards[4],
-    ]
+fn parse_state(state: &[u8]) -> Option<VideoPokerState> {
+    if matches!(state.first(), Some(2..=STATE_VERSION)) {
+        return VideoPokerState::from_blob(state);
+    }
+
+    // v1 (unversioned): [stage] [cards×5] [variant]?
+    let mut reader = BlobReader::unversioned(state);
+    let state = VideoPokerState {
+        stage: reader.get()?,
+        cards: reader.get()?,
+        variant: reader.get_or(Variant::JacksOrBetter)?,
+        hands: Vec::new(),
+        winnings: 0,
+        gambles: Vec::new(),
+    };
+    reader.finish()?;
+    Some(state)
+}
+
+/// Rank of a double-up card, Aces high (2-14).
+fn double_up_rank(card: u8) -> u8 {
+    match card_rank(card) {
+        1 => 14,
+        rank => rank,
+    }
+}
+
+/// Take the winnings at stake, or gamble them on a double-up.
+fn double_up(
+    session: &mut GameSession,
+    state: &mut VideoPokerState,
+    payload: &[u8],
+    rng: &mut GameRng,
+) -> Result<GameResult, GameError> {
+    match payload {
+        [0] => {}
+        [1] => {
+            let mut deck = rng.create_deck();
+            let dealer = rng.draw_card(&mut deck).ok_or(GameError::InvalidMove)?;
+            let player = rng.draw_card(&mut deck).ok_or(GameError::InvalidMove)?;
+            state.gambles.push((dealer, player));
+
+            // Doubling is even money: super mode multipliers never apply to it
+            match double_up_rank(player).cmp(&double_up_rank(dealer)) {
+                std::cmp::Ordering::Greater => {
+                    state.winnings = state.winnings.saturating_mul(2);
+                }
+                std::cmp::Ordering::Equal => {}
+                std::cmp::Ordering::Less => {
+                    state.winnings = 0;
+                    state.stage = Stage::Draw;
+                    session.state_blob = state.to_blob();
+                    session.move_count += 1;
+                    session.is_complete = true;
+
+                    // Extra hands were charged with the draw
+                    let hands = state.hands.len() as u64;
+                    return Ok(if hands == 1 {
+                        GameResult::Loss
+                    } else {
+                        GameResult::LossPreDeducted(session.bet.saturating_mul(hands))
+                    });
+                }
+            }
+
+            if state.gambles.len() < MAX_DOUBLE_UPS {
+                session.state_blob = state.to_blob();
+                session.move_count += 1;
+                return Ok(GameResult::Continue);
+            }
+        }
+        _ => return Err(GameError::InvalidPayload),
+    }
+
+    // Pay out the winnings
+    state.stage = Stage::Draw;
+    session.state_blob = state.to_blob();
+    session.move_count += 1;
+    session.is_complete = true;
+    Ok(GameResult::Win(state.winnings))
 }
 
 pub struct VideoPoker;
@@ -197,10 +603,25 @@ impl CasinoGame for VideoPoker {
             rng.draw_card(&mut deck).unwrap_or(4),
         ];
 
-        session.state_blob = serialize_state(Stage::Deal, &cards);
+        session.state_blob = VideoPokerState {
+            stage: Stage::Deal,
+            cards,
+            variant: Variant::default(),
+            hands: Vec::new(),
+            winnings: 0,
+            gambles: Vec::new(),
+        }
+        .to_blob();
         GameResult::Continue
     }
 
+    fn configure(session: &mut GameSession, config: &GameConfig) {
+        if let Some(mut state) = parse_state(&session.state_blob) {
+            state.variant = Variant::try_from(config.paytable).unwrap_or_default();
+            session.state_blob = state.to_blob();
+        }
+    }
+
     fn process_move(
         session: &mut GameSession,
         payload: &[u8],
@@ -210,52 +631,101 @@ impl CasinoGame for VideoPoker {
             return Err(GameError::GameAlreadyComplete);
         }
 
-        if payload.is_empty() {
+        if payload.is_empty() || payload.len() > 3 {
             return Err(GameError::InvalidPayload);
         }
 
-        let (stage, mut cards) =
-            parse_state(&session.state_blob).ok_or(GameError::InvalidPayload)?;
+        let mut state = parse_state(&session.state_blob).ok_or(GameError::InvalidPayload)?;
 
-        if stage != Stage::Deal {
-            return Err(GameError::GameAlreadyComplete);
+        match state.stage {
+            Stage::Deal => {}
+            Stage::DoubleUp => return double_up(session, &mut state, payload, rng),
+            Stage::Draw => return Err(GameError::GameAlreadyComplete),
         }
 
         let hold_mask = payload[0];
+        let hand_count = payload.get(1).copied().unwrap_or(1);
+        if !HAND_COUNTS.contains(&hand_count) {
+            return Err(GameError::InvalidPayload);
+        }
+        let keep_for_double_up = match payload.get(2) {
+            None | Some(0) => false,
+            Some(1) => true,
+            Some(_) => return Err(GameError::InvalidPayload),
+        };
+        // Each extra hand costs another bet
+        let extra_deduction = session
+            .bet
+            .checked_mul(u64::from(hand_count) - 1)
+            .ok_or(GameError::InvalidPayload)?;
         session.move_count += 1;
 
-        // Build the draw deck from the remaining cards in the pack.
-        // All 5 originally-dealt cards are removed from the deck (even discards cannot be re-drawn).
-        let original_cards = cards;
-        let mut deck = rng.create_deck_excluding(&original_cards);
+        // Every hand draws from its own copy of the remaining pack.
+        // All 5 originally-dealt cards are removed from each deck (even discards cannot be
+        // re-drawn).
+        let original_cards = state.cards;
+        let mut total_return: u64 = 0;
+        for _ in 0..hand_count {
+            let mut deck = rng.create_deck_excluding(&original_cards);
+            let mut cards = original_cards;
 
-        // Replace non-held cards
-        for i in 0..5 {
-            if hold_mask & (1 << i) == 0 {
-                cards[i] = rng.draw_card(&mut deck).ok_or(GameError::InvalidMove)?;
+            // Replace non-held cards
+            for (i, card) in cards.iter_mut().enumerate() {
+                if hold_mask & (1 << i) == 0 {
+                    *card = rng.draw_card(&mut deck).ok_or(GameError::InvalidMove)?;
+                }
             }
-        }
 
-        session.state_blob = serialize_state(Stage::Draw, &cards);
-        session.is_complete = true;
-
-        // Evaluate final hand
-        let hand = evaluate_hand(&cards);
-        let multiplier = payout_multiplier(hand);
+            // Evaluate final hand
+            let hand = evaluate_variant(state.variant, &cards);
+            let multiplier = payout_multiplier(state.variant, hand);
+            if multiplier > 0 {
+                // Pay tables are expressed "to 1" (winnings). Our executor expects TOTAL RETURN.
+                let base_winnings = session.bet.saturating_mul(multiplier.saturating_add(1));
+                // Apply super mode multipliers if active
+                let winnings = if session.super_mode.is_active {
+                    apply_super_multiplier_cards(
+                        &cards,
+                        &session.super_mode.multipliers,
+                        base_winnings,
+                    )
+                } else {
+                    base_winnings
+                };
+                total_return = total_return.saturating_add(winnings);
+            }
+            state.hands.push((cards, hand));
+        }
 
-        if multiplier > 0 {
-            // Pay tables are expressed "to 1" (winnings). Our executor expects TOTAL RETURN.
-            let base_winnings = session.bet.saturating_mul(multiplier.saturating_add(1));
-            // Apply super mode multipliers if active
-            let final_winnings = if session.super_mode.is_active {
-                apply_super_multiplier_cards(&cards, &session.super_mode.multipliers, base_winnings)
+        state.cards = state.hands[0].0;
+        if keep_for_double_up && total_return > 0 {
+            // Keep the winnings at the table, charging for the extra hands now
+            state.stage = Stage::DoubleUp;
+            state.winnings = total_return;
+            session.state_blob = state.to_blob();
+            return Ok(if extra_deduction > 0 {
+                GameResult::ContinueWithUpdate {
+                    payout: -i64::try_from(extra_deduction)
+                        .map_err(|_| GameError::InvalidPayload)?,
+                }
             } else {
-                base_winnings
-            };
-            Ok(GameResult::Win(final_winnings))
-        } else {
-            Ok(GameResult::Loss)
+                GameResult::Continue
+            });
         }
+
+        state.stage = Stage::Draw;
+        session.state_blob = state.to_blob();
+        session.is_complete = true;
+
+        Ok(match (total_return, extra_deduction) {
+            (0, 0) => GameResult::Loss,
+            (0, extra_deduction) => GameResult::LossWithExtraDeduction(extra_deduction),
+            (payout, 0) => GameResult::Win(payout),
+            (payout, extra_deduction) => GameResult::WinWithExtraDeduction {
+                payout,
+                extra_deduction,
+            },
+        })
     }
 }
 
@@ -284,9 +754,25 @@ mod tests {
             super_mode: nullspace_types::casino::SuperModeState::default(),
             is_tournament: false,
             tournament_id: None,
+            last_move_at: 0,
+            wagered: bet,
+            entropy: None,
         }
     }
 
+    /// State blob of a hand dealt `cards`.
+    fn deal_state(cards: [u8; 5], variant: Variant) -> Vec<u8> {
+        VideoPokerState {
+            stage: Stage::Deal,
+            cards,
+            variant,
+            hands: Vec::new(),
+            winnings: 0,
+            gambles: Vec::new(),
+        }
+        .to_blob()
+    }
+
     #[test]
     fn test_card_rank() {
         assert_eq!(card_rank(0), 1); // Ace
@@ -371,10 +857,11 @@ mod tests {
 
     #[test]
     fn test_payout_multipliers() {
-        assert_eq!(payout_multiplier(Hand::HighCard), 0);
-        assert_eq!(payout_multiplier(Hand::JacksOrBetter), 1);
-        assert_eq!(payout_multiplier(Hand::TwoPair), 2);
-        assert_eq!(payout_multiplier(Hand::RoyalFlush), 800);
+        let jacks = Variant::JacksOrBetter;
+        assert_eq!(payout_multiplier(jacks, Hand::HighCard), 0);
+        assert_eq!(payout_multiplier(jacks, Hand::JacksOrBetter), 1);
+        assert_eq!(payout_multiplier(jacks, Hand::TwoPair), 2);
+        assert_eq!(payout_multiplier(jacks, Hand::RoyalFlush), 800);
     }
 
     #[test]
@@ -386,9 +873,11 @@ mod tests {
         VideoPoker::init(&mut session, &mut rng);
         assert!(!session.is_complete);
 
-        let (stage, cards) = parse_state(&session.state_blob).expect("Failed to parse state");
-        assert_eq!(stage, Stage::Deal);
-        for card in cards {
+        let state = parse_state(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.stage, Stage::Deal);
+        assert_eq!(state.variant, Variant::JacksOrBetter);
+        assert!(state.hands.is_empty());
+        for card in state.cards {
             assert!(card < 52);
         }
 
@@ -407,14 +896,18 @@ mod tests {
         let mut rng = GameRng::new(&seed, session.id, 0);
 
         VideoPoker::init(&mut session, &mut rng);
-        let (_, original_cards) = parse_state(&session.state_blob).expect("Failed to parse state");
+        let original_cards = parse_state(&session.state_blob)
+            .expect("Failed to parse state")
+            .cards;
 
         // Discard all cards (hold none)
         let mut rng = GameRng::new(&seed, session.id, 1);
         let result = VideoPoker::process_move(&mut session, &[0], &mut rng);
 
         assert!(result.is_ok());
-        let (_, new_cards) = parse_state(&session.state_blob).expect("Failed to parse state");
+        let new_cards = parse_state(&session.state_blob)
+            .expect("Failed to parse state")
+            .cards;
 
         // None of the original 5 cards may be re-drawn in the same hand.
         let same_count = original_cards
@@ -432,7 +925,7 @@ mod tests {
 
         // Force a known Jacks-or-Better hand and hold all cards so no draw occurs.
         let cards = [10, 23, 1, 2, 3]; // J-J-2-3-4
-        session.state_blob = serialize_state(Stage::Deal, &cards);
+        session.state_blob = deal_state(cards, Variant::JacksOrBetter);
 
         let result = VideoPoker::process_move(&mut session, &[0b11111], &mut rng)
             .expect("Failed to process move");
@@ -447,4 +940,343 @@ mod tests {
         let cards = [0, 1, 2, 3, 4]; // A-2-3-4-5 of spades
         assert_eq!(evaluate_hand(&cards), Hand::StraightFlush); // All same suit
     }
+
+    #[test]
+    fn test_bonus_quads() {
+        let four_aces = [0, 13, 26, 39, 5];
+        let four_threes = [2, 15, 28, 41, 0];
+        let four_kings = [12, 25, 38, 51, 0];
+
+        assert_eq!(
+            evaluate_variant(Variant::JacksOrBetter, &four_aces),
+            Hand::FourOfAKind
+        );
+        for variant in [Variant::BonusPoker, Variant::DoubleBonus] {
+            assert_eq!(evaluate_variant(variant, &four_aces), Hand::FourAces);
+            assert_eq!(
+                evaluate_variant(variant, &four_threes),
+                Hand::FourTwosThroughFours
+            );
+            assert_eq!(evaluate_variant(variant, &four_kings), Hand::FourOfAKind);
+        }
+
+        assert_eq!(payout_multiplier(Variant::BonusPoker, Hand::FourAces), 80);
+        assert_eq!(payout_multiplier(Variant::DoubleBonus, Hand::FourAces), 160);
+        assert_eq!(payout_multiplier(Variant::DoubleBonus, Hand::TwoPair), 1);
+        assert_eq!(payout_multiplier(Variant::JacksOrBetter, Hand::FourAces), 0);
+    }
+
+    #[test]
+    fn test_deuces_wild() {
+        let cases: [([u8; 5], Hand); 13] = [
+            ([9, 10, 11, 12, 0], Hand::RoyalFlush),
+            ([1, 14, 27, 40, 0], Hand::FourDeuces),
+            ([1, 9, 10, 11, 12], Hand::WildRoyalFlush),
+            ([1, 14, 0, 13, 26], Hand::FiveOfAKind),
+            ([1, 4, 5, 6, 8], Hand::StraightFlush),
+            ([1, 14, 0, 13, 3], Hand::FourOfAKind),
+            ([1, 0, 13, 3, 16], Hand::FullHouse),
+            ([1, 0, 4, 7, 11], Hand::Flush),
+            ([1, 2, 16, 30, 44], Hand::Straight),
+            ([1, 0, 15, 29, 43], Hand::Straight), // A-2-3-4-5 with a Deuce
+            ([1, 0, 13, 4, 20], Hand::ThreeOfAKind),
+            ([1, 0, 4, 20, 37], Hand::HighCard),
+            ([0, 13, 3, 16, 8], Hand::HighCard), // Two pair doesn't pay
+        ];
+        for (cards, hand) in cases {
+            assert_eq!(
+                evaluate_variant(Variant::DeucesWild, &cards),
+                hand,
+                "{cards:?}"
+            );
+        }
+
+        assert_eq!(
+            payout_multiplier(Variant::DeucesWild, Hand::FourDeuces),
+            200
+        );
+        assert_eq!(
+            payout_multiplier(Variant::DeucesWild, Hand::ThreeOfAKind),
+            1
+        );
+        assert_eq!(
+            payout_multiplier(Variant::DeucesWild, Hand::JacksOrBetter),
+            0
+        );
+    }
+
+    #[test]
+    fn test_variant_state() {
+        let seed = create_test_seed();
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 0);
+
+        // Blobs without a variant play Jacks or Better
+        let legacy = vec![0, 0, 13, 26, 39, 5];
+        let state = parse_state(&legacy).expect("Failed to parse state");
+        assert_eq!(state.variant, Variant::JacksOrBetter);
+        assert_eq!(state.cards, [0, 13, 26, 39, 5]);
+        assert!(parse_state(&[0, 0, 13, 26, 39, 5, 4]).is_none());
+
+        // The variant comes from the config's paytable
+        VideoPoker::init(&mut session, &mut rng);
+        let config = GameConfig {
+            paytable: Variant::DeucesWild as u8,
+            ..GameConfig::default()
+        };
+        VideoPoker::configure(&mut session, &config);
+        let state = parse_state(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.stage, Stage::Deal);
+        assert_eq!(state.variant, Variant::DeucesWild);
+
+        // Four Deuces pays 200 to 1
+        session.state_blob = deal_state([1, 14, 27, 40, 0], Variant::DeucesWild);
+        let result = VideoPoker::process_move(&mut session, &[0b11111], &mut rng)
+            .expect("Failed to process move");
+        assert!(matches!(result, GameResult::Win(20100)));
+        let state = parse_state(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.stage, Stage::Draw);
+        assert_eq!(state.variant, Variant::DeucesWild);
+        assert_eq!(state.hands, vec![([1, 14, 27, 40, 0], Hand::FourDeuces)]);
+    }
+
+    #[test]
+    fn test_state_versions() {
+        let state = VideoPokerState {
+            stage: Stage::Draw,
+            cards: [10, 23, 1, 2, 3],
+            variant: Variant::BonusPoker,
+            hands: vec![
+                ([10, 23, 1, 2, 3], Hand::JacksOrBetter),
+                ([10, 23, 36, 2, 3], Hand::ThreeOfAKind),
+            ],
+            winnings: 0,
+            gambles: Vec::new(),
+        };
+        // Header: version, stage, variant, cards and the hand count
+        let header_len = 9;
+        let blob = state.to_blob();
+        assert_eq!(blob.len(), header_len + 12 + 9);
+        assert_eq!(parse_state(&blob), Some(state.clone()));
+
+        // v2 blobs end with the hands
+        let mut v2 = blob[..header_len + 12].to_vec();
+        v2[0] = 2;
+        assert_eq!(parse_state(&v2), Some(state.clone()));
+
+        // Double-up needs winnings at stake
+        let doubling = VideoPokerState {
+            stage: Stage::DoubleUp,
+            winnings: 400,
+            gambles: vec![(4, 12)],
+            ..state.clone()
+        };
+        assert_eq!(parse_state(&doubling.to_blob()), Some(doubling.clone()));
+        let broke = VideoPokerState {
+            winnings: 0,
+            ..doubling.clone()
+        };
+        assert!(parse_state(&broke.to_blob()).is_none());
+        crate::casino::state_blob::fuzz::roundtrip(&[state, doubling]);
+
+        // Hand counts and rankings must be consistent
+        let mut bad_count = blob.clone();
+        bad_count[8] = 3;
+        assert!(parse_state(&bad_count).is_none());
+        let mut bad_hand = blob.clone();
+        bad_hand[header_len + 5] = 15;
+        assert!(parse_state(&bad_hand).is_none());
+        assert!(parse_state(&blob[..header_len - 1]).is_none());
+
+        // v1 blobs (with or without a variant) still parse
+        let v1 = parse_state(&[1, 10, 23, 1, 2, 3, 2]).expect("Failed to parse state");
+        assert_eq!(v1.stage, Stage::Draw);
+        assert_eq!(v1.variant, Variant::DoubleBonus);
+        assert!(v1.hands.is_empty());
+    }
+
+    #[test]
+    fn test_multi_hand() {
+        let seed = create_test_seed();
+
+        // Holding a dealt pair of Jacks wins on every hand
+        for hand_count in [3u8, 5, 10] {
+            let mut session = create_test_session(100);
+            let mut rng = GameRng::new(&seed, session.id, 1);
+            session.state_blob = deal_state([10, 23, 1, 2, 3], Variant::JacksOrBetter);
+
+            let result = VideoPoker::process_move(&mut session, &[0b00011, hand_count], &mut rng)
+                .expect("Failed to process move");
+            let state = parse_state(&session.state_blob).expect("Failed to parse state");
+            assert_eq!(state.stage, Stage::Draw);
+            assert_eq!(state.hands.len(), hand_count as usize);
+            assert_eq!(state.cards, state.hands[0].0);
+
+            let mut expected = 0;
+            for (cards, hand) in &state.hands {
+                assert_eq!(cards[..2], [10, 23]);
+                // Each hand draws from its own deck, without the dealt cards
+                for card in &cards[2..] {
+                    assert!(![10, 23, 1, 2, 3].contains(card));
+                }
+                assert_eq!(*hand, evaluate_hand(cards));
+                expected += 100 * (payout_multiplier(Variant::JacksOrBetter, *hand) + 1);
+            }
+            assert!(matches!(
+                result,
+                GameResult::WinWithExtraDeduction { payout, extra_deduction }
+                    if payout == expected && extra_deduction == 100 * (hand_count as u64 - 1)
+            ));
+        }
+
+        // Losing every hand still charges for the extra hands
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        session.state_blob = deal_state([0, 15, 30, 45, 8], Variant::DeucesWild);
+        let result = VideoPoker::process_move(&mut session, &[0b11111, 3], &mut rng)
+            .expect("Failed to process move");
+        assert!(matches!(result, GameResult::LossWithExtraDeduction(200)));
+
+        // Only the offered hand counts are allowed
+        for payload in [vec![0, 0], vec![0, 2], vec![0, 11], vec![0, 3, 0, 0]] {
+            let mut session = create_test_session(100);
+            session.state_blob = deal_state([10, 23, 1, 2, 3], Variant::JacksOrBetter);
+            assert!(matches!(
+                VideoPoker::process_move(&mut session, &payload, &mut rng),
+                Err(GameError::InvalidPayload)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_double_up() {
+        let seed = create_test_seed();
+
+        // A losing draw ends the session as usual
+        let mut session = create_test_session(100);
+        let mut rng = GameRng::new(&seed, session.id, 1);
+        session.state_blob = deal_state([0, 15, 30, 45, 8], Variant::JacksOrBetter);
+        let result = VideoPoker::process_move(&mut session, &[0b11111, 1, 1], &mut rng)
+            .expect("Failed to process move");
+        assert!(matches!(result, GameResult::Loss));
+        assert!(session.is_complete);
+
+        // A winning draw keeps its winnings at the table, and taking them ends the session
+        let mut session = create_test_session(100);
+        session.state_blob = deal_state([10, 23, 1, 2, 3], Variant::JacksOrBetter);
+        let result = VideoPoker::process_move(&mut session, &[0b11111, 1, 1], &mut rng)
+            .expect("Failed to process move");
+        assert!(matches!(result, GameResult::Continue));
+        assert!(!session.is_complete);
+        let state = parse_state(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.stage, Stage::DoubleUp);
+        assert_eq!(state.winnings, 200);
+        let result =
+            VideoPoker::process_move(&mut session, &[0], &mut rng).expect("Failed to collect");
+        assert!(matches!(result, GameResult::Win(200)));
+        assert!(session.is_complete);
+        assert_eq!(
+            parse_state(&session.state_blob).map(|state| state.stage),
+            Some(Stage::Draw)
+        );
+
+        // Each gamble doubles the winnings on a higher card, keeps them on the same rank and
+        // loses them on a lower one (super mode never multiplies them)
+        let mut outcomes = [false; 3];
+        for session_id in 1..50 {
+            let mut session = create_test_session(100);
+            session.id = session_id;
+            session.super_mode.is_active = true;
+            session.state_blob = VideoPokerState {
+                stage: Stage::DoubleUp,
+                cards: [10, 23, 1, 2, 3],
+                variant: Variant::JacksOrBetter,
+                hands: vec![([10, 23, 1, 2, 3], Hand::JacksOrBetter)],
+                winnings: 200,
+                gambles: Vec::new(),
+            }
+            .to_blob();
+
+            let mut winnings = 200;
+            let mut rounds = 0u32;
+            let result = loop {
+                let mut rng = GameRng::new(&seed, session_id, rounds);
+                let result = VideoPoker::process_move(&mut session, &[1], &mut rng)
+                    .expect("Failed to double up");
+                rounds += 1;
+                let state = parse_state(&session.state_blob).expect("Failed to parse state");
+                let &(dealer, player) = state.gambles.last().expect("No gamble recorded");
+                assert_ne!(dealer, player);
+                match double_up_rank(player).cmp(&double_up_rank(dealer)) {
+                    std::cmp::Ordering::Greater => {
+                        outcomes[0] = true;
+                        winnings *= 2;
+                    }
+                    std::cmp::Ordering::Equal => outcomes[1] = true,
+                    std::cmp::Ordering::Less => {
+                        outcomes[2] = true;
+                        winnings = 0;
+                    }
+                }
+                assert_eq!(state.winnings, winnings);
+                if session.is_complete {
+                    break result;
+                }
+                assert!(matches!(result, GameResult::Continue));
+            };
+            assert_eq!(session.move_count, rounds);
+            if winnings == 0 {
+                assert!(matches!(result, GameResult::Loss));
+            } else {
+                // Paid out once the double-ups run out
+                assert_eq!(rounds as usize, MAX_DOUBLE_UPS);
+                assert!(matches!(result, GameResult::Win(payout) if payout == winnings));
+            }
+            assert!(matches!(
+                VideoPoker::process_move(&mut session, &[1], &mut rng),
+                Err(GameError::GameAlreadyComplete)
+            ));
+        }
+        assert_eq!(outcomes, [true; 3]);
+
+        // Extra hands are charged with the draw, and losing a gamble reports every hand's stake
+        let mut session = create_test_session(100);
+        session.state_blob = deal_state([10, 23, 1, 2, 3], Variant::JacksOrBetter);
+        let result = VideoPoker::process_move(&mut session, &[0b00011, 3, 1], &mut rng)
+            .expect("Failed to process move");
+        assert!(matches!(
+            result,
+            GameResult::ContinueWithUpdate { payout: -200 }
+        ));
+        let mut state = parse_state(&session.state_blob).expect("Failed to parse state");
+        assert_eq!(state.hands.len(), 3);
+        let session_id = (1..50)
+            .find(|&id| {
+                let mut rng = GameRng::new(&seed, id, 0);
+                let mut deck = rng.create_deck();
+                let dealer = rng.draw_card(&mut deck).unwrap();
+                let player = rng.draw_card(&mut deck).unwrap();
+                double_up_rank(player) < double_up_rank(dealer)
+            })
+            .expect("No losing gamble");
+        let mut rng = GameRng::new(&seed, session_id, 0);
+        let result = double_up(&mut session, &mut state, &[1], &mut rng).expect("Gamble failed");
+        assert!(matches!(result, GameResult::LossPreDeducted(300)));
+
+        // Only the double-up moves are allowed, and only on winnings
+        let mut session = create_test_session(100);
+        session.state_blob = deal_state([10, 23, 1, 2, 3], Variant::JacksOrBetter);
+        assert!(matches!(
+            VideoPoker::process_move(&mut session, &[0b11111, 1, 2], &mut rng),
+            Err(GameError::InvalidPayload)
+        ));
+        VideoPoker::process_move(&mut session, &[0b11111, 1, 1], &mut rng)
+            .expect("Failed to process move");
+        for payload in [vec![2], vec![1, 1], vec![0b11111, 1, 1]] {
+            assert!(matches!(
+                VideoPoker::process_move(&mut session, &payload, &mut rng),
+                Err(GameError::InvalidPayload)
+            ));
+        }
+    }
 }
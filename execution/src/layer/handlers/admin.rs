@@ -0,0 +1,79 @@
+use super::super::*;
+use commonware_cryptography::bls12381::primitives::ops;
+use nullspace_types::casino::AdminRole;
+
+impl<'a, S: State> Layer<'a, S> {
+    // === Admin Handlers ===
+
+    pub(in crate::layer) async fn handle_set_admin(
+        &mut self,
+        public: &PublicKey,
+        role: AdminRole,
+        admin: Option<&PublicKey>,
+        signature: &nullspace_types::Signature,
+    ) -> Vec<Event> {
+        // The validator set must approve this change (for the next admin key set)
+        let mut house = self.get_or_init_house().await;
+        let message = nullspace_types::execution::admin_message(role, admin, house.admin_rotations);
+        if ops::verify_message::<MinSig>(
+            &self.master,
+            Some(&self.admin_namespace),
+            &message,
+            signature,
+        )
+        .is_err()
+        {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::SET_ADMIN,
+                nullspace_types::casino::CasinoErrorCode::Unauthorized,
+            );
+        }
+
+        house.admin_rotations += 1;
+        match role {
+            AdminRole::Governance => house.governance = admin.cloned(),
+            AdminRole::Kyc => house.kyc.admin = admin.cloned(),
+            AdminRole::Treasury => {
+                let mut treasury = self.get_treasury().await;
+                treasury.admin = admin.cloned();
+                self.insert(Key::Treasury, Value::Treasury(treasury));
+            }
+        }
+        self.insert(Key::House, Value::House(house));
+
+        vec![Event::AdminSet {
+            role,
+            admin: admin.cloned(),
+        }]
+    }
+
+    pub(in crate::layer) async fn handle_set_house_settings(
+        &mut self,
+        public: &PublicKey,
+        settings: &nullspace_types::casino::HouseSettings,
+    ) -> Vec<Event> {
+        let mut house = self.get_or_init_house().await;
+        if house.governance.as_ref() != Some(public) {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::SET_HOUSE_SETTINGS,
+                nullspace_types::casino::CasinoErrorCode::Unauthorized,
+            );
+        }
+        if !settings.is_valid() {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::SET_HOUSE_SETTINGS,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
+        }
+
+        house.set_settings(settings.clone());
+        self.insert(Key::House, Value::House(house));
+
+        vec![Event::HouseSettingsUpdated {
+            settings: settings.clone(),
+        }]
+    }
+}
@@ -1,4 +1,7 @@
 use super::super::*;
+use commonware_codec::ReadExt;
+use commonware_cryptography::sha256::Digest;
+use nullspace_types::casino::{Chips, PlayerEntropy};
 
 impl<'a, S: State> Layer<'a, S> {
     // === Casino Handler Methods ===
@@ -13,14 +16,15 @@ impl<'a, S: State> Layer<'a, S> {
             return vec![Event::CasinoError {
                 player: public.clone(),
                 session_id: None,
-                error_code: nullspace_types::casino::ERROR_PLAYER_ALREADY_REGISTERED,
+                error_code: nullspace_types::casino::CasinoErrorCode::PlayerAlreadyRegistered,
                 message: "Player already registered".to_string(),
             }];
         }
 
         // Create new player with initial chips and current block for rate limiting
-        let player =
+        let mut player =
             nullspace_types::casino::Player::new_with_block(name.to_string(), self.seed.view);
+        player.chips = Chips(self.economy.initial_chips);
 
         self.insert(
             Key::CasinoPlayer(public.clone()),
@@ -47,12 +51,47 @@ impl<'a, S: State> Layer<'a, S> {
                 return vec![Event::CasinoError {
                     player: public.clone(),
                     session_id: None,
-                    error_code: nullspace_types::casino::ERROR_PLAYER_NOT_FOUND,
+                    error_code: nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
                     message: "Player not found".to_string(),
                 }]
             }
         };
 
+        // Deployments can disable the faucet (leaving the daily bonus)
+        let house = self.house_settings().await;
+        if !house.daily_bonus.faucet_enabled {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::InvalidMove,
+                message: "Faucet disabled".to_string(),
+            }];
+        }
+        if amount > self.economy.faucet_amount {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+                message: format!(
+                    "Faucet deposits are limited to {} chips",
+                    self.economy.faucet_amount
+                ),
+            }];
+        }
+
+        // Large deposits require KYC
+        if amount > house.kyc.deposit_limit && !player.is_kyc_verified {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::KycRequired,
+                message: format!(
+                    "Deposits over {} require KYC verification",
+                    house.kyc.deposit_limit
+                ),
+            }];
+        }
+
         // Daily faucet rate limiting (dev/testing).
         let current_block = self.seed.view;
         let current_time_sec = current_block.saturating_mul(3);
@@ -63,13 +102,21 @@ impl<'a, S: State> Layer<'a, S> {
             return vec![Event::CasinoError {
                 player: public.clone(),
                 session_id: None,
-                error_code: nullspace_types::casino::ERROR_RATE_LIMITED,
+                error_code: nullspace_types::casino::CasinoErrorCode::RateLimited,
                 message: "Daily faucet already claimed, try again tomorrow".to_string(),
             }];
         }
 
         // Grant faucet chips
-        player.chips = player.chips.saturating_add(amount);
+        let Some(chips) = player.chips.checked_add(Chips(amount)) else {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+                message: "Deposit too large".to_string(),
+            }];
+        };
+        player.chips = chips;
         player.last_deposit_block = current_block;
 
         self.insert(
@@ -85,6 +132,703 @@ impl<'a, S: State> Layer<'a, S> {
         }]
     }
 
+    pub(in crate::layer) async fn handle_claim_daily_bonus(
+        &mut self,
+        public: &PublicKey,
+    ) -> Vec<Event> {
+        let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
+            Some(Value::CasinoPlayer(p)) => p,
+            _ => {
+                return vec![Event::CasinoError {
+                    player: public.clone(),
+                    session_id: None,
+                    error_code: nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                    message: "Player not found".to_string(),
+                }]
+            }
+        };
+
+        // Enforce the cooldown since the last claim (the first claim is always allowed)
+        let settings = self.house_settings().await.daily_bonus;
+        let current_block = self.seed.view;
+        let claimed = player.last_deposit_block != 0;
+        let next_claim = player.last_deposit_block.saturating_add(settings.cooldown);
+        if claimed && current_block < next_claim {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::RateLimited,
+                message: "Daily bonus already claimed".to_string(),
+            }];
+        }
+
+        // Claiming before the next cooldown passes extends the streak
+        let streak_expiry = next_claim.saturating_add(settings.cooldown);
+        player.daily_streak = if claimed && current_block < streak_expiry {
+            player
+                .daily_streak
+                .saturating_add(1)
+                .min(settings.max_streak.max(1))
+        } else {
+            1
+        };
+        let amount = settings.bonus(player.daily_streak);
+        let Some(chips) = player.chips.checked_add(Chips(amount)) else {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+                message: "Daily bonus too large".to_string(),
+            }];
+        };
+        player.chips = chips;
+        player.last_deposit_block = current_block;
+
+        self.insert(
+            Key::CasinoPlayer(public.clone()),
+            Value::CasinoPlayer(player.clone()),
+        );
+
+        self.update_casino_leaderboard(public, &player).await;
+
+        vec![Event::DailyBonusClaimed {
+            player: public.clone(),
+            amount,
+            streak: player.daily_streak,
+            new_chips: player.chips.into(),
+            next_claim: current_block.saturating_add(settings.cooldown),
+        }]
+    }
+
+    pub(in crate::layer) async fn handle_update_profile(
+        &mut self,
+        public: &PublicKey,
+        display_name: &str,
+        avatar_id: u32,
+        title: &str,
+    ) -> Vec<Event> {
+        let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
+            Some(Value::CasinoPlayer(p)) => p,
+            _ => {
+                return vec![Event::CasinoError {
+                    player: public.clone(),
+                    session_id: None,
+                    error_code: nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                    message: "Player not found".to_string(),
+                }]
+            }
+        };
+
+        if let Err(message) =
+            nullspace_types::casino::Profile::validate(display_name, avatar_id, title)
+        {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::InvalidMove,
+                message: message.to_string(),
+            }];
+        }
+
+        // Rate limit updates (the first update is always allowed)
+        let current_block = self.seed.view;
+        let updated_at = player.profile.updated_at;
+        if updated_at != 0
+            && current_block
+                < updated_at.saturating_add(nullspace_types::casino::PROFILE_UPDATE_COOLDOWN)
+        {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::RateLimited,
+                message: "Profile updated too recently".to_string(),
+            }];
+        }
+
+        player.profile = nullspace_types::casino::Profile {
+            display_name: display_name.to_string(),
+            avatar_id,
+            title: title.to_string(),
+            updated_at: current_block,
+        };
+        self.insert(
+            Key::CasinoPlayer(public.clone()),
+            Value::CasinoPlayer(player.clone()),
+        );
+
+        // Show the new name on leaderboards
+        self.update_casino_leaderboard(public, &player).await;
+        if let Some(tournament_id) = player.active_tournament {
+            self.update_tournament_leaderboard(tournament_id, public, &player)
+                .await;
+        }
+
+        vec![Event::ProfileUpdated {
+            player: public.clone(),
+            display_name: display_name.to_string(),
+            avatar_id,
+            title: title.to_string(),
+        }]
+    }
+
+    pub(in crate::layer) async fn handle_set_kyc_status(
+        &mut self,
+        public: &PublicKey,
+        target: &PublicKey,
+        verified: bool,
+    ) -> Vec<Event> {
+        let house = self.house_settings().await;
+        if house.kyc.admin.as_ref() != Some(public) {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::Unauthorized,
+                message: "Only the KYC admin can set KYC status".to_string(),
+            }];
+        }
+
+        let mut player = match self.get(&Key::CasinoPlayer(target.clone())).await {
+            Some(Value::CasinoPlayer(p)) => p,
+            _ => {
+                return vec![Event::CasinoError {
+                    player: public.clone(),
+                    session_id: None,
+                    error_code: nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                    message: "Player not found".to_string(),
+                }]
+            }
+        };
+        player.is_kyc_verified = verified;
+        self.insert(
+            Key::CasinoPlayer(target.clone()),
+            Value::CasinoPlayer(player),
+        );
+
+        vec![Event::KycStatusChanged {
+            player: target.clone(),
+            verified,
+        }]
+    }
+
+    pub(in crate::layer) async fn handle_set_game_config(
+        &mut self,
+        public: &PublicKey,
+        game_type: nullspace_types::casino::GameType,
+        config: &nullspace_types::casino::GameConfig,
+    ) -> Vec<Event> {
+        let house = self.house_settings().await;
+        if house.governance.as_ref() != Some(public) {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::Unauthorized,
+                message: "Only governance can set game rules".to_string(),
+            }];
+        }
+        if !config.is_valid(game_type) {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::InvalidGameConfig,
+                message: "Invalid game rules".to_string(),
+            }];
+        }
+
+        // Sessions in progress keep the rules they started with
+        self.insert(
+            Key::GameConfig(game_type),
+            Value::GameConfig(config.clone()),
+        );
+
+        vec![Event::GameConfigUpdated {
+            game_type,
+            config: config.clone(),
+        }]
+    }
+
+    pub(in crate::layer) async fn handle_grant_bonus(
+        &mut self,
+        public: &PublicKey,
+        target: &PublicKey,
+        amount: u64,
+    ) -> Vec<Event> {
+        let house = self.house_settings().await;
+        if house.governance.as_ref() != Some(public) {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::Unauthorized,
+                message: "Only governance can grant bonuses".to_string(),
+            }];
+        }
+        if amount == 0 {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+                message: "Bonus must be positive".to_string(),
+            }];
+        }
+        let Some(Value::CasinoPlayer(mut player)) =
+            self.get(&Key::CasinoPlayer(target.clone())).await
+        else {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                message: "Player not found".to_string(),
+            }];
+        };
+
+        // The requirement is fixed when granted (later settings changes don't move it)
+        if !player
+            .bonus
+            .grant(Chips(amount), house.bonus.requirement(amount))
+        {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+                message: "Bonus too large".to_string(),
+            }];
+        }
+        let event = Event::BonusGranted {
+            player: target.clone(),
+            amount,
+            bonus_chips: player.bonus.chips.into(),
+            wagering_remaining: player.bonus.wagering_remaining,
+        };
+        self.insert(
+            Key::CasinoPlayer(target.clone()),
+            Value::CasinoPlayer(player),
+        );
+        vec![event]
+    }
+
+    pub(in crate::layer) async fn handle_set_economy_params(
+        &mut self,
+        public: &PublicKey,
+        params: &nullspace_types::casino::EconomyParams,
+    ) -> Vec<Event> {
+        let house = self.house_settings().await;
+        if house.governance.as_ref() != Some(public) {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::Unauthorized,
+                message: "Only governance can set economy parameters".to_string(),
+            }];
+        }
+        if !params.is_valid() {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+                message: "Invalid economy parameters".to_string(),
+            }];
+        }
+
+        // The layer loads the parameters once per block, so they apply from the next block
+        self.insert(Key::EconomyParams, Value::EconomyParams(params.clone()));
+
+        vec![Event::EconomyParamsUpdated {
+            params: params.clone(),
+        }]
+    }
+
+    pub(in crate::layer) async fn handle_casino_abandon_session(
+        &mut self,
+        public: &PublicKey,
+        session_id: u64,
+    ) -> Vec<Event> {
+        let mut session = match self.get(&Key::CasinoSession(session_id)).await {
+            Some(Value::CasinoSession(s)) => s,
+            _ => {
+                return vec![Event::CasinoError {
+                    player: public.clone(),
+                    session_id: Some(session_id),
+                    error_code: nullspace_types::casino::CasinoErrorCode::SessionNotFound,
+                    message: "Session not found".to_string(),
+                }]
+            }
+        };
+        if session.player != *public {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: Some(session_id),
+                error_code: nullspace_types::casino::CasinoErrorCode::SessionNotOwned,
+                message: "Session does not belong to this player".to_string(),
+            }];
+        }
+        if session.is_complete {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: Some(session_id),
+                error_code: nullspace_types::casino::CasinoErrorCode::SessionComplete,
+                message: "Session already complete".to_string(),
+            }];
+        }
+
+        // Only sessions without a recent move can be abandoned
+        let expires_at = session
+            .last_move_at
+            .saturating_add(nullspace_types::casino::SESSION_EXPIRY);
+        if self.seed.view < expires_at {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: Some(session_id),
+                error_code: nullspace_types::casino::CasinoErrorCode::RateLimited,
+                message: format!("Session can't be abandoned until view {expires_at}"),
+            }];
+        }
+
+        session.is_complete = true;
+        self.insert(
+            Key::CasinoSession(session_id),
+            Value::CasinoSession(session.clone()),
+        );
+        self.queue_session_expiry(session_id).await;
+
+        let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
+            Some(Value::CasinoPlayer(p)) => p,
+            _ => return vec![],
+        };
+        let refund = crate::casino::abandon_refund(&session);
+        if session.is_tournament {
+            player.tournament_chips = player.tournament_chips.saturating_add(Chips(refund));
+        } else {
+            player.chips = player.chips.saturating_add(Chips(refund));
+            if refund > 0 {
+                self.update_house_pnl(-(refund as i128)).await;
+            }
+        }
+        player.release_session(session_id);
+        let final_chips = if session.is_tournament {
+            player.tournament_chips
+        } else {
+            player.chips
+        };
+        self.insert(
+            Key::CasinoPlayer(public.clone()),
+            Value::CasinoPlayer(player.clone()),
+        );
+        self.update_leaderboard_for_session(&session, public, &player)
+            .await;
+
+        vec![Event::CasinoSessionExpired {
+            session_id,
+            player: public.clone(),
+            game_type: session.game_type,
+            refund,
+            final_chips: final_chips.into(),
+        }]
+    }
+
+    /// Deduct `amount` plus `fee` from `stack`, returning the total deducted (or `None`, leaving
+    /// the stack untouched, if the stack is too small or the total overflows).
+    fn deduct(stack: &mut Chips, amount: u64, fee: u64) -> Option<Chips> {
+        let total = Chips(amount).checked_add(Chips(fee))?;
+        *stack = stack.checked_sub(total)?;
+        Some(total)
+    }
+
+    /// Check a bet of `amount` (in a session that has already wagered `wagered`) against the
+    /// house limits.
+    fn check_bet_limits(
+        public: &PublicKey,
+        session_id: u64,
+        limits: &nullspace_types::casino::BetLimits,
+        game_type: nullspace_types::casino::GameType,
+        amount: u64,
+        wagered: u64,
+    ) -> Result<(), Event> {
+        let max_bet = limits.max_bet(game_type);
+        if amount > max_bet {
+            return Err(Event::CasinoError {
+                player: public.clone(),
+                session_id: Some(session_id),
+                error_code: nullspace_types::casino::CasinoErrorCode::BetLimitExceeded,
+                message: format!("Bet of {amount} exceeds the maximum of {max_bet}"),
+            });
+        }
+        if wagered
+            .checked_add(amount)
+            .is_none_or(|exposure| exposure > limits.max_exposure)
+        {
+            return Err(Event::CasinoError {
+                player: public.clone(),
+                session_id: Some(session_id),
+                error_code: nullspace_types::casino::CasinoErrorCode::ExposureLimitExceeded,
+                message: format!(
+                    "Session wagers of {wagered} plus {amount} exceed the maximum of {}",
+                    limits.max_exposure
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Check a bet against the rule overrides of a tournament.
+    fn check_tournament_rules(
+        public: &PublicKey,
+        session_id: u64,
+        rules: &nullspace_types::casino::TournamentRules,
+        game_type: nullspace_types::casino::GameType,
+        amount: u64,
+    ) -> Result<(), Event> {
+        if !rules.allows(game_type) {
+            return Err(Event::CasinoError {
+                player: public.clone(),
+                session_id: Some(session_id),
+                error_code: nullspace_types::casino::CasinoErrorCode::GameDisabled,
+                message: format!("{game_type:?} is disabled in this tournament"),
+            });
+        }
+        if amount > 0 && amount < rules.min_bet {
+            return Err(Event::CasinoError {
+                player: public.clone(),
+                session_id: Some(session_id),
+                error_code: nullspace_types::casino::CasinoErrorCode::InvalidBet,
+                message: format!("Bet of {amount} is below the minimum of {}", rules.min_bet),
+            });
+        }
+        Ok(())
+    }
+
+    /// The rule overrides of the tournament a session is played in.
+    async fn tournament_rules(
+        &self,
+        tournament_id: Option<u64>,
+    ) -> Option<nullspace_types::casino::TournamentRules> {
+        match self.get(&Key::Tournament(tournament_id?)).await {
+            Some(Value::Tournament(tournament)) => Some(tournament.rules),
+            _ => None,
+        }
+    }
+
+    /// The table rules of a session (its tournament's overrides, or the house's rules).
+    async fn session_game_config(
+        &self,
+        game_type: nullspace_types::casino::GameType,
+        rules: Option<&nullspace_types::casino::TournamentRules>,
+    ) -> nullspace_types::casino::GameConfig {
+        match rules.and_then(|rules| rules.game_config(game_type)) {
+            Some(config) => config.clone(),
+            None => self.game_config(game_type).await,
+        }
+    }
+
+    /// Free the concurrent session slot held by a completed session.
+    async fn release_session(&mut self, public: &PublicKey, session_id: u64) {
+        if let Some(Value::CasinoPlayer(mut player)) =
+            self.get(&Key::CasinoPlayer(public.clone())).await
+        {
+            if player.release_session(session_id) {
+                self.insert(
+                    Key::CasinoPlayer(public.clone()),
+                    Value::CasinoPlayer(player),
+                );
+            }
+        }
+    }
+
+    /// Append the result of a session to the player's history (if it completed).
+    ///
+    /// Completed sessions are deleted from state once they are `SESSION_RETENTION` views old
+    /// (sessions of players who stop playing are reclaimed by [Self::reclaim_expired_sessions]).
+    async fn record_game_history(
+        &mut self,
+        public: &PublicKey,
+        session: &nullspace_types::casino::GameSession,
+        events: &[Event],
+    ) {
+        let Some(payout) = events.iter().find_map(|event| match event {
+            Event::CasinoGameCompleted { payout, .. } => Some(*payout),
+            _ => None,
+        }) else {
+            return;
+        };
+        let mut history = match self.get(&Key::GameHistory(public.clone())).await {
+            Some(Value::GameHistory(history)) => history,
+            _ => nullspace_types::casino::GameHistory::default(),
+        };
+        let dropped = history.push(nullspace_types::casino::GameRecord {
+            session_id: session.id,
+            game_type: session.game_type,
+            bet: session.bet,
+            payout,
+            view: self.seed.view,
+        });
+        // Results dropped from the history before their session expired are left for
+        // the expiry queue
+        let expired = self
+            .seed
+            .view
+            .saturating_sub(nullspace_types::casino::SESSION_RETENTION);
+        let mut reclaimed: Vec<u64> = dropped
+            .into_iter()
+            .filter(|record| record.view > history.reclaimed_through && record.view <= expired)
+            .map(|record| record.session_id)
+            .collect();
+        reclaimed.extend(history.reclaim(expired));
+        for session_id in reclaimed {
+            self.delete(&Key::CasinoSession(session_id)).await;
+        }
+        self.insert(
+            Key::GameHistory(public.clone()),
+            Value::GameHistory(history),
+        );
+    }
+
+    /// Add a completed (non-tournament) session to its game's statistics, reporting them every
+    /// `GAME_STATS_INTERVAL` sessions.
+    async fn record_game_stats(
+        &mut self,
+        session: &nullspace_types::casino::GameSession,
+        events: &[Event],
+    ) -> Vec<Event> {
+        if session.is_tournament {
+            return Vec::new();
+        }
+        let Some(payout) = events.iter().find_map(|event| match event {
+            Event::CasinoGameCompleted { payout, .. } => Some(*payout),
+            _ => None,
+        }) else {
+            return Vec::new();
+        };
+        let key = Key::GameStats(session.game_type);
+        let mut stats = match self.get(&key).await {
+            Some(Value::GameStats(stats)) => stats,
+            _ => nullspace_types::casino::GameStats::default(),
+        };
+        let report = stats.record(session.wagered, payout);
+        self.insert(key, Value::GameStats(stats.clone()));
+        if !report {
+            return Vec::new();
+        }
+        vec![Event::GameStatsUpdated {
+            game_type: session.game_type,
+            stats,
+        }]
+    }
+
+    /// Accrue loyalty points for a completed (non-tournament) session, paying rakeback on
+    /// losses and granting the shields of any VIP tier reached.
+    async fn accrue_loyalty(
+        &mut self,
+        public: &PublicKey,
+        session: &nullspace_types::casino::GameSession,
+        events: &[Event],
+    ) -> Vec<Event> {
+        if session.is_tournament {
+            return Vec::new();
+        }
+        let Some(payout) = events.iter().find_map(|event| match event {
+            Event::CasinoGameCompleted { payout, .. } => Some(*payout),
+            _ => None,
+        }) else {
+            return Vec::new();
+        };
+        let Some(Value::CasinoPlayer(mut player)) =
+            self.get(&Key::CasinoPlayer(public.clone())).await
+        else {
+            return Vec::new();
+        };
+        let settings = self.house_settings().await.vip;
+
+        // Rakeback is paid at the tier held when the game was played
+        let points = settings.points(session.wagered);
+        player.total_wagered = player.total_wagered.saturating_add(session.wagered);
+        player.loyalty_points = player.loyalty_points.saturating_add(points);
+        let rakeback = if payout < 0 {
+            settings.rakeback(player.vip_tier, payout.unsigned_abs())
+        } else {
+            0
+        };
+        if rakeback > 0 {
+            player.chips = player.chips.saturating_add(Chips(rakeback));
+            self.update_house_pnl(-(rakeback as i128)).await;
+        }
+        let mut events = vec![Event::LoyaltyEarned {
+            player: public.clone(),
+            points,
+            total_points: player.loyalty_points,
+            rakeback,
+        }];
+
+        // Shields are only granted for tiers reached for the first time
+        let tier = settings.tier(player.loyalty_points);
+        if tier != player.vip_tier {
+            let shields = settings
+                .tiers
+                .iter()
+                .take(tier as usize)
+                .skip(player.vip_tier as usize)
+                .fold(0u32, |total, reached| total.saturating_add(reached.shields));
+            player.shields = player.shields.saturating_add(shields);
+            player.vip_tier = tier;
+            events.push(Event::TierChanged {
+                player: public.clone(),
+                tier,
+                shields,
+            });
+        }
+        self.insert(
+            Key::CasinoPlayer(public.clone()),
+            Value::CasinoPlayer(player.clone()),
+        );
+        if rakeback > 0 {
+            self.update_casino_leaderboard(public, &player).await;
+        }
+        events
+    }
+
+    /// Count a completed game towards the player's bonus wagering requirement, releasing the
+    /// bonus chips once it is met.
+    async fn wager_bonus(
+        &mut self,
+        public: &PublicKey,
+        session: &nullspace_types::casino::GameSession,
+        events: &[Event],
+    ) -> Vec<Event> {
+        if session.is_tournament
+            || !events
+                .iter()
+                .any(|event| matches!(event, Event::CasinoGameCompleted { .. }))
+        {
+            return Vec::new();
+        }
+        let Some(Value::CasinoPlayer(mut player)) =
+            self.get(&Key::CasinoPlayer(public.clone())).await
+        else {
+            return Vec::new();
+        };
+        if player.bonus.chips.is_zero() {
+            return Vec::new();
+        }
+        let settings = self.house_settings().await.bonus;
+
+        let contribution = settings.contribution(session.game_type, session.wagered);
+        let released = player.bonus.wager(contribution);
+        if !released.is_zero() {
+            player.chips = player.chips.saturating_add(released);
+            self.update_house_pnl(-(released.0 as i128)).await;
+        }
+        self.insert(
+            Key::CasinoPlayer(public.clone()),
+            Value::CasinoPlayer(player.clone()),
+        );
+        if released.is_zero() {
+            return Vec::new();
+        }
+        self.update_casino_leaderboard(public, &player).await;
+        vec![Event::BonusReleased {
+            player: public.clone(),
+            amount: released.into(),
+            chips: player.chips.into(),
+        }]
+    }
+
     fn update_aura_meter_for_completion(
         player: &mut nullspace_types::casino::Player,
         session: &nullspace_types::casino::GameSession,
@@ -121,9 +865,11 @@ impl<'a, S: State> Layer<'a, S> {
     pub(in crate::layer) async fn handle_casino_start_game(
         &mut self,
         public: &PublicKey,
+        nonce: u64,
         game_type: nullspace_types::casino::GameType,
         bet: u64,
         session_id: u64,
+        commitment: Option<Digest>,
     ) -> Vec<Event> {
         // Get player
         let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
@@ -132,7 +878,7 @@ impl<'a, S: State> Layer<'a, S> {
                 return vec![Event::CasinoError {
                     player: public.clone(),
                     session_id: Some(session_id),
-                    error_code: nullspace_types::casino::ERROR_PLAYER_NOT_FOUND,
+                    error_code: nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
                     message: "Player not found".to_string(),
                 }]
             }
@@ -141,11 +887,13 @@ impl<'a, S: State> Layer<'a, S> {
         // Determine play mode (cash vs tournament)
         let mut is_tournament = false;
         let mut tournament_id = None;
+        let mut tournament_rules = None;
         if let Some(active_tid) = player.active_tournament {
             if let Some(Value::Tournament(t)) = self.get(&Key::Tournament(active_tid)).await {
                 if matches!(t.phase, nullspace_types::casino::TournamentPhase::Active) {
                     is_tournament = true;
                     tournament_id = Some(active_tid);
+                    tournament_rules = Some(t.rules);
                 } else {
                     player.active_tournament = None;
                 }
@@ -162,37 +910,56 @@ impl<'a, S: State> Layer<'a, S> {
                 | nullspace_types::casino::GameType::Craps
                 | nullspace_types::casino::GameType::Roulette
                 | nullspace_types::casino::GameType::SicBo
+                | nullspace_types::casino::GameType::MoneyWheel
         );
         if bet == 0 && !allows_zero_bet {
             return vec![Event::CasinoError {
                 player: public.clone(),
                 session_id: Some(session_id),
-                error_code: nullspace_types::casino::ERROR_INVALID_BET,
+                error_code: nullspace_types::casino::CasinoErrorCode::InvalidBet,
                 message: "Bet must be greater than zero".to_string(),
             }];
         }
+        if let Some(rules) = &tournament_rules {
+            if let Err(event) =
+                Self::check_tournament_rules(public, session_id, rules, game_type, bet)
+            {
+                return vec![event];
+            }
+        }
         let wants_super = player.active_super;
         let super_fee = if wants_super && bet > 0 {
-            crate::casino::get_super_mode_fee(bet)
+            self.economy.super_mode_fee(bet)
         } else {
             0
         };
-        let required_stack = bet.saturating_add(super_fee);
         let available_stack = if is_tournament {
             player.tournament_chips
         } else {
             player.chips
         };
-        if available_stack < required_stack {
+        let mut remaining_stack = available_stack;
+        let Some(required_stack) = Self::deduct(&mut remaining_stack, bet, super_fee) else {
             return vec![Event::CasinoError {
                 player: public.clone(),
                 session_id: Some(session_id),
-                error_code: nullspace_types::casino::ERROR_INSUFFICIENT_FUNDS,
+                error_code: nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
                 message: format!(
-                    "Insufficient chips: have {}, need {}",
-                    available_stack, required_stack
+                    "Insufficient chips: have {}, need {} plus {} in fees",
+                    available_stack.0, bet, super_fee
                 ),
             }];
+        };
+
+        // Session IDs are derived from the player and nonce (so they can't collide across players)
+        let expected = nullspace_types::casino::derive_session_id(public, nonce);
+        if session_id != expected {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: Some(session_id),
+                error_code: nullspace_types::casino::CasinoErrorCode::InvalidSessionId,
+                message: format!("Session ID must be {expected} (derived from the nonce)"),
+            }];
         }
 
         // Check for existing session
@@ -200,25 +967,48 @@ impl<'a, S: State> Layer<'a, S> {
             return vec![Event::CasinoError {
                 player: public.clone(),
                 session_id: Some(session_id),
-                error_code: nullspace_types::casino::ERROR_SESSION_EXISTS,
+                error_code: nullspace_types::casino::CasinoErrorCode::SessionExists,
                 message: "Session already exists".to_string(),
             }];
         }
 
+        // Check the house limits (concurrent sessions and wagers)
+        let house = self.house_settings().await;
+        if !player.can_open_session(house.max_active_sessions) {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: Some(session_id),
+                error_code: nullspace_types::casino::CasinoErrorCode::SessionLimitReached,
+                message: format!(
+                    "Too many open sessions: {} (limit {})",
+                    player.active_sessions.len(),
+                    house.max_active_sessions
+                ),
+            }];
+        }
+        let limits = match &tournament_rules {
+            Some(rules) => rules.bet_limits(&house.bet_limits),
+            None => house.bet_limits.clone(),
+        };
+        if let Err(event) = Self::check_bet_limits(public, session_id, &limits, game_type, bet, 0) {
+            return vec![event];
+        }
+
         // Deduct bet (and any upfront super fee) from player
         if is_tournament {
-            player.tournament_chips = player.tournament_chips.saturating_sub(required_stack);
+            player.tournament_chips = remaining_stack;
         } else {
-            player.chips = player.chips.saturating_sub(required_stack);
+            player.chips = remaining_stack;
         }
+        player.active_sessions.push(session_id);
         self.insert(
             Key::CasinoPlayer(public.clone()),
             Value::CasinoPlayer(player.clone()),
         );
 
         // Update House PnL (Income)
-        if !is_tournament && required_stack > 0 {
-            self.update_house_pnl(required_stack as i128).await;
+        if !is_tournament && !required_stack.is_zero() {
+            self.update_house_pnl(required_stack.0 as i128).await;
         }
 
         // Create game session and update leaderboard after bet deduction
@@ -234,6 +1024,9 @@ impl<'a, S: State> Layer<'a, S> {
             super_mode: nullspace_types::casino::SuperModeState::default(),
             is_tournament,
             tournament_id,
+            last_move_at: self.seed.view,
+            wagered: bet,
+            entropy: commitment.map(PlayerEntropy::Committed),
         };
 
         // Initialize Super/Aura mode for this session (independent RNG domain).
@@ -246,14 +1039,28 @@ impl<'a, S: State> Layer<'a, S> {
             if aura_round {
                 crate::casino::super_mode::enhance_multipliers_for_aura_round(&mut multipliers);
             }
+            if let Some(rules) = &tournament_rules {
+                crate::casino::super_mode::boost_multipliers(
+                    &mut multipliers,
+                    rules.super_boost_bps,
+                );
+            }
             session.super_mode.multipliers = multipliers;
         }
         self.update_leaderboard_for_session(&session, public, &player)
             .await;
 
-        // Initialize game
+        // Initialize game (the player's entropy is only revealed by the first move, so the
+        // initial deal relies on the seed alone)
+        let config = self
+            .session_game_config(session.game_type, tournament_rules.as_ref())
+            .await;
         let mut rng = crate::casino::GameRng::new(&self.seed, session_id, 0);
+        if config.record_rng {
+            rng.record_transcript();
+        }
         let result = crate::casino::init_game(&mut session, &mut rng);
+        crate::casino::configure_game(&mut session, &config);
 
         let initial_state = session.state_blob.clone();
         self.insert(
@@ -268,12 +1075,21 @@ impl<'a, S: State> Layer<'a, S> {
             bet,
             initial_state,
         }];
+        if let Some(draws) = rng.take_transcript().filter(|draws| !draws.is_empty()) {
+            events.push(Event::CasinoRngTranscript {
+                session_id,
+                move_number: 0,
+                draws,
+            });
+        }
 
         // Handle immediate result (e.g. Natural Blackjack)
         if !matches!(result, crate::casino::GameResult::Continue) {
             if let Some(Value::CasinoPlayer(mut player)) =
                 self.get(&Key::CasinoPlayer(public.clone())).await
             {
+                // Settled results free the session's slot (persisted by each arm below)
+                player.release_session(session_id);
                 match result {
                     crate::casino::GameResult::Win(base_payout) => {
                         let mut payout = base_payout as i64;
@@ -293,9 +1109,9 @@ impl<'a, S: State> Layer<'a, S> {
                         let addition = u64::try_from(payout).unwrap_or(0);
                         if session.is_tournament {
                             player.tournament_chips =
-                                player.tournament_chips.saturating_add(addition);
+                                player.tournament_chips.saturating_add(Chips(addition));
                         } else {
-                            player.chips = player.chips.saturating_add(addition);
+                            player.chips = player.chips.saturating_add(Chips(addition));
                         }
                         player.active_shield = false;
                         player.active_double = false;
@@ -324,7 +1140,7 @@ impl<'a, S: State> Layer<'a, S> {
                             player: public.clone(),
                             game_type: session.game_type,
                             payout,
-                            final_chips,
+                            final_chips: final_chips.into(),
                             was_shielded: false,
                             was_doubled,
                         });
@@ -332,9 +1148,9 @@ impl<'a, S: State> Layer<'a, S> {
                     crate::casino::GameResult::Push => {
                         if session.is_tournament {
                             player.tournament_chips =
-                                player.tournament_chips.saturating_add(session.bet);
+                                player.tournament_chips.saturating_add(Chips(session.bet));
                         } else {
-                            player.chips = player.chips.saturating_add(session.bet);
+                            player.chips = player.chips.saturating_add(Chips(session.bet));
                         }
                         player.active_shield = false;
                         player.active_double = false;
@@ -360,7 +1176,7 @@ impl<'a, S: State> Layer<'a, S> {
                             player: public.clone(),
                             game_type: session.game_type,
                             payout: session.bet as i64,
-                            final_chips,
+                            final_chips: final_chips.into(),
                             was_shielded: false,
                             was_doubled: false,
                         });
@@ -407,7 +1223,7 @@ impl<'a, S: State> Layer<'a, S> {
                             player: public.clone(),
                             game_type: session.game_type,
                             payout,
-                            final_chips,
+                            final_chips: final_chips.into(),
                             was_shielded,
                             was_doubled: false,
                         });
@@ -416,6 +1232,16 @@ impl<'a, S: State> Layer<'a, S> {
                 }
             }
         }
+        if session.is_complete {
+            self.queue_session_expiry(session_id).await;
+        }
+        self.record_game_history(public, &session, &events).await;
+        let stats = self.record_game_stats(&session, &events).await;
+        events.extend(stats);
+        let loyalty = self.accrue_loyalty(public, &session, &events).await;
+        events.extend(loyalty);
+        let bonus = self.wager_bonus(public, &session, &events).await;
+        events.extend(bonus);
 
         events
     }
@@ -433,7 +1259,7 @@ impl<'a, S: State> Layer<'a, S> {
                 return vec![Event::CasinoError {
                     player: public.clone(),
                     session_id: Some(session_id),
-                    error_code: nullspace_types::casino::ERROR_SESSION_NOT_FOUND,
+                    error_code: nullspace_types::casino::CasinoErrorCode::SessionNotFound,
                     message: "Session not found".to_string(),
                 }]
             }
@@ -444,7 +1270,7 @@ impl<'a, S: State> Layer<'a, S> {
             return vec![Event::CasinoError {
                 player: public.clone(),
                 session_id: Some(session_id),
-                error_code: nullspace_types::casino::ERROR_SESSION_NOT_OWNED,
+                error_code: nullspace_types::casino::CasinoErrorCode::SessionNotOwned,
                 message: "Session does not belong to this player".to_string(),
             }];
         }
@@ -452,30 +1278,104 @@ impl<'a, S: State> Layer<'a, S> {
             return vec![Event::CasinoError {
                 player: public.clone(),
                 session_id: Some(session_id),
-                error_code: nullspace_types::casino::ERROR_SESSION_COMPLETE,
+                error_code: nullspace_types::casino::CasinoErrorCode::SessionComplete,
                 message: "Session already complete".to_string(),
             }];
         }
 
+        // The first move of a committed session starts with the preimage of its commitment
+        let mut payload = payload;
+        if let Some(entropy @ PlayerEntropy::Committed(_)) = session.entropy.as_mut() {
+            let revealed =
+                Digest::read(&mut payload).is_ok_and(|preimage| entropy.reveal(preimage));
+            if !revealed {
+                return vec![Event::CasinoError {
+                    player: public.clone(),
+                    session_id: Some(session_id),
+                    error_code: nullspace_types::casino::CasinoErrorCode::InvalidReveal,
+                    message: "Payload does not reveal the committed entropy".to_string(),
+                }];
+            }
+        }
+
         // Process move
         session.move_count += 1;
-        let mut rng = crate::casino::GameRng::new(&self.seed, session_id, session.move_count);
+        session.last_move_at = self.seed.view;
+        let mut rng = match session.entropy.as_ref().and_then(PlayerEntropy::revealed) {
+            Some(entropy) => crate::casino::GameRng::with_entropy(
+                &self.seed,
+                session_id,
+                session.move_count,
+                entropy,
+            ),
+            None => crate::casino::GameRng::new(&self.seed, session_id, session.move_count),
+        };
+        let tournament_rules = self.tournament_rules(session.tournament_id).await;
+        if self
+            .session_game_config(session.game_type, tournament_rules.as_ref())
+            .await
+            .record_rng
+        {
+            rng.record_transcript();
+        }
 
+        let before = session.state_blob.clone();
         let result = match crate::casino::process_game_move(&mut session, payload, &mut rng) {
             Ok(r) => r,
             Err(_) => {
                 return vec![Event::CasinoError {
                     player: public.clone(),
                     session_id: Some(session_id),
-                    error_code: nullspace_types::casino::ERROR_INVALID_MOVE,
+                    error_code: nullspace_types::casino::CasinoErrorCode::InvalidMove,
                     message: "Invalid game move".to_string(),
                 }]
             }
         };
 
-        let result = self
-            .apply_progressive_meters_for_completion(&session, result)
-            .await;
+        // Bets placed by the move count against the house limits
+        let placed = match &result {
+            crate::casino::GameResult::ContinueWithUpdate { payout } if *payout < 0 => {
+                payout.unsigned_abs()
+            }
+            crate::casino::GameResult::WinWithExtraDeduction {
+                extra_deduction, ..
+            }
+            | crate::casino::GameResult::LossPreDeductedWithExtraDeduction {
+                extra_deduction,
+                ..
+            } => *extra_deduction,
+            crate::casino::GameResult::LossWithExtraDeduction(extra) => *extra,
+            _ => 0,
+        };
+        if placed > 0 {
+            let mut limits = self.house_settings().await.bet_limits;
+            if let Some(rules) = &tournament_rules {
+                if let Err(event) = Self::check_tournament_rules(
+                    public,
+                    session_id,
+                    rules,
+                    session.game_type,
+                    placed,
+                ) {
+                    return vec![event];
+                }
+                limits = rules.bet_limits(&limits);
+            }
+            if let Err(event) = Self::check_bet_limits(
+                public,
+                session_id,
+                &limits,
+                session.game_type,
+                placed,
+                session.wagered,
+            ) {
+                return vec![event];
+            }
+            // Can't overflow (the total was checked against the exposure limit)
+            session.wagered += placed;
+        }
+
+        let (result, side_bet_events) = self.settle_side_bets(&session, result).await;
 
         let move_number = session.move_count;
         let new_state = session.state_blob.clone();
@@ -486,12 +1386,48 @@ impl<'a, S: State> Layer<'a, S> {
             move_number,
             new_state,
         }];
+        if let Some(draws) = rng.take_transcript() {
+            // Moves that play several rounds can draw more than one event holds
+            events.extend(
+                draws
+                    .chunks(nullspace_types::casino::MAX_RNG_TRANSCRIPT_DRAWS)
+                    .map(|draws| Event::CasinoRngTranscript {
+                        session_id,
+                        move_number,
+                        draws: draws.to_vec(),
+                    }),
+            );
+        }
+        events.extend(
+            crate::casino::table_events(&before, &session, payload)
+                .into_iter()
+                .map(|event| match event {
+                    crate::casino::TableEvent::CardDealt { card, to } => Event::CasinoCardDealt {
+                        session_id,
+                        move_number,
+                        card,
+                        to,
+                    },
+                    crate::casino::TableEvent::CrapsRolled {
+                        d1,
+                        d2,
+                        resolved_bets,
+                    } => Event::CasinoCrapsRolled {
+                        session_id,
+                        move_number,
+                        d1,
+                        d2,
+                        resolved_bets,
+                    },
+                }),
+        );
+        events.extend(side_bet_events);
 
         match result {
             crate::casino::GameResult::Continue => {
                 self.insert(
                     Key::CasinoSession(session_id),
-                    Value::CasinoSession(session),
+                    Value::CasinoSession(session.clone()),
                 );
             }
             crate::casino::GameResult::ContinueWithUpdate { payout } => {
@@ -512,34 +1448,38 @@ impl<'a, S: State> Layer<'a, S> {
                             .and_then(|v| u64::try_from(v).ok())
                             .unwrap_or(0);
                         let super_fee = if session.super_mode.is_active {
-                            crate::casino::get_super_mode_fee(deduction)
+                            self.economy.super_mode_fee(deduction)
                         } else {
                             0
                         };
-                        let total_deduction = deduction.saturating_add(super_fee);
-                        if deduction == 0 || *stack < total_deduction {
+                        let total_deduction = if deduction > 0 {
+                            Self::deduct(stack, deduction, super_fee)
+                        } else {
+                            None
+                        };
+                        let Some(total_deduction) = total_deduction else {
                             // Insufficient funds or overflow - reject the move
                             return vec![Event::CasinoError {
                                 player: public.clone(),
                                 session_id: Some(session_id),
-                                error_code: nullspace_types::casino::ERROR_INSUFFICIENT_FUNDS,
+                                error_code:
+                                    nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
                                 message: format!(
-                                    "Insufficient chips for additional bet: have {}, need {}",
-                                    *stack, total_deduction
+                                    "Insufficient chips for additional bet: have {}, need {} plus {} in fees",
+                                    stack.0, deduction, super_fee
                                 ),
                             }];
-                        }
-                        *stack = stack.saturating_sub(total_deduction);
+                        };
 
                         // Update House PnL for cash games only (income from wager + super fee).
-                        if !session.is_tournament && total_deduction > 0 {
-                            self.update_house_pnl(total_deduction as i128).await;
+                        if !session.is_tournament && !total_deduction.is_zero() {
+                            self.update_house_pnl(total_deduction.0 as i128).await;
                         }
                     } else {
                         // Adding chips (intermediate win)
                         // Safe cast: positive i64 fits in u64
                         let addition = u64::try_from(payout).unwrap_or(0);
-                        *stack = stack.saturating_add(addition);
+                        *stack = stack.saturating_add(Chips(addition));
 
                         // Update House PnL for cash games only (payout outflow).
                         if !session.is_tournament && addition > 0 {
@@ -557,7 +1497,7 @@ impl<'a, S: State> Layer<'a, S> {
                 }
                 self.insert(
                     Key::CasinoSession(session_id),
-                    Value::CasinoSession(session),
+                    Value::CasinoSession(session.clone()),
                 );
             }
             crate::casino::GameResult::Win(base_payout) => {
@@ -590,7 +1530,7 @@ impl<'a, S: State> Layer<'a, S> {
                         } else {
                             &mut player.chips
                         };
-                        *stack = stack.saturating_add(addition);
+                        *stack = stack.saturating_add(Chips(addition));
                         *stack
                     };
                     player.active_shield = false;
@@ -614,7 +1554,7 @@ impl<'a, S: State> Layer<'a, S> {
                         player: public.clone(),
                         game_type: session.game_type,
                         payout,
-                        final_chips,
+                        final_chips: final_chips.into(),
                         was_shielded: false,
                         was_doubled,
                     });
@@ -631,32 +1571,32 @@ impl<'a, S: State> Layer<'a, S> {
                 {
                     if extra_deduction > 0 {
                         let super_fee = if session.super_mode.is_active {
-                            crate::casino::get_super_mode_fee(extra_deduction)
+                            self.economy.super_mode_fee(extra_deduction)
                         } else {
                             0
                         };
-                        let total_deduction = extra_deduction.saturating_add(super_fee);
                         let stack = if session.is_tournament {
                             &mut player.tournament_chips
                         } else {
                             &mut player.chips
                         };
-                        if *stack < total_deduction {
+                        let Some(total_deduction) = Self::deduct(stack, extra_deduction, super_fee)
+                        else {
                             return vec![Event::CasinoError {
                                 player: public.clone(),
                                 session_id: Some(session_id),
-                                error_code: nullspace_types::casino::ERROR_INSUFFICIENT_FUNDS,
+                                error_code:
+                                    nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
                                 message: format!(
-                                    "Insufficient chips for additional bet: have {}, need {}",
-                                    *stack, total_deduction
+                                    "Insufficient chips for additional bet: have {}, need {} plus {} in fees",
+                                    stack.0, extra_deduction, super_fee
                                 ),
                             }];
-                        }
-                        *stack = stack.saturating_sub(total_deduction);
+                        };
 
                         // Update House PnL for cash games only (income from the extra wager).
-                        if !session.is_tournament && total_deduction > 0 {
-                            self.update_house_pnl(total_deduction as i128).await;
+                        if !session.is_tournament && !total_deduction.is_zero() {
+                            self.update_house_pnl(total_deduction.0 as i128).await;
                         }
                     }
 
@@ -686,7 +1626,7 @@ impl<'a, S: State> Layer<'a, S> {
                         } else {
                             &mut player.chips
                         };
-                        *stack = stack.saturating_add(addition);
+                        *stack = stack.saturating_add(Chips(addition));
                         *stack
                     };
 
@@ -712,7 +1652,7 @@ impl<'a, S: State> Layer<'a, S> {
                         player: public.clone(),
                         game_type: session.game_type,
                         payout,
-                        final_chips,
+                        final_chips: final_chips.into(),
                         was_shielded: false,
                         was_doubled,
                     });
@@ -742,7 +1682,7 @@ impl<'a, S: State> Layer<'a, S> {
                         } else {
                             &mut player.chips
                         };
-                        *stack = stack.saturating_add(session.bet);
+                        *stack = stack.saturating_add(Chips(session.bet));
                         *stack
                     };
                     player.active_shield = false;
@@ -769,7 +1709,7 @@ impl<'a, S: State> Layer<'a, S> {
                         player: public.clone(),
                         game_type: session.game_type,
                         payout: session.bet as i64,
-                        final_chips,
+                        final_chips: final_chips.into(),
                         was_shielded: false,
                         was_doubled: false,
                     });
@@ -822,7 +1762,7 @@ impl<'a, S: State> Layer<'a, S> {
                         player: public.clone(),
                         game_type: session.game_type,
                         payout,
-                        final_chips,
+                        final_chips: final_chips.into(),
                         was_shielded,
                         was_doubled: false,
                     });
@@ -862,28 +1802,28 @@ impl<'a, S: State> Layer<'a, S> {
                         // Deduct the extra amount that wasn't charged at StartGame (plus any super fee).
                         if extra > 0 {
                             let super_fee = if session.super_mode.is_active {
-                                crate::casino::get_super_mode_fee(extra)
+                                self.economy.super_mode_fee(extra)
                             } else {
                                 0
                             };
-                            let total_deduction = extra.saturating_add(super_fee);
-                            if *stack < total_deduction {
+                            let Some(total_deduction) = Self::deduct(stack, extra, super_fee)
+                            else {
                                 return vec![Event::CasinoError {
                                     player: public.clone(),
                                     session_id: Some(session_id),
-                                    error_code: nullspace_types::casino::ERROR_INSUFFICIENT_FUNDS,
+                                    error_code:
+                                        nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
                                     message: format!(
-                                        "Insufficient chips for additional bet: have {}, need {}",
-                                        *stack, total_deduction
+                                        "Insufficient chips for additional bet: have {}, need {} plus {} in fees",
+                                        stack.0, extra, super_fee
                                     ),
                                 }];
-                            }
-                            *stack = stack.saturating_sub(total_deduction);
+                            };
 
                             // Update House PnL for cash games only (income from extra wager + super fee).
                             // Note: Shield does NOT prevent this extra deduction in current logic.
-                            if !session.is_tournament && total_deduction > 0 {
-                                self.update_house_pnl(total_deduction as i128).await;
+                            if !session.is_tournament && !total_deduction.is_zero() {
+                                self.update_house_pnl(total_deduction.0 as i128).await;
                             }
                         }
 
@@ -909,7 +1849,7 @@ impl<'a, S: State> Layer<'a, S> {
                         player: public.clone(),
                         game_type: session.game_type,
                         payout: payout - (extra as i64), // Total loss includes extra
-                        final_chips,
+                        final_chips: final_chips.into(),
                         was_shielded,
                         was_doubled: false,
                     });
@@ -943,7 +1883,7 @@ impl<'a, S: State> Layer<'a, S> {
                         let payout = if was_shielded {
                             // Shield prevents loss - refund the pre-deducted amount
                             *shields_pool = shields_pool.saturating_sub(1);
-                            *stack = stack.saturating_add(total_loss);
+                            *stack = stack.saturating_add(Chips(total_loss));
 
                             // Update House PnL (Refund)
                             if !session.is_tournament {
@@ -977,7 +1917,7 @@ impl<'a, S: State> Layer<'a, S> {
                         player: public.clone(),
                         game_type: session.game_type,
                         payout,
-                        final_chips,
+                        final_chips: final_chips.into(),
                         was_shielded,
                         was_doubled: false,
                     });
@@ -1005,27 +1945,28 @@ impl<'a, S: State> Layer<'a, S> {
 
                         if extra_deduction > 0 {
                             let super_fee = if session.super_mode.is_active {
-                                crate::casino::get_super_mode_fee(extra_deduction)
+                                self.economy.super_mode_fee(extra_deduction)
                             } else {
                                 0
                             };
-                            let total_deduction = extra_deduction.saturating_add(super_fee);
-                            if *stack < total_deduction {
+                            let Some(total_deduction) =
+                                Self::deduct(stack, extra_deduction, super_fee)
+                            else {
                                 return vec![Event::CasinoError {
                                     player: public.clone(),
                                     session_id: Some(session_id),
-                                    error_code: nullspace_types::casino::ERROR_INSUFFICIENT_FUNDS,
+                                    error_code:
+                                        nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
                                     message: format!(
-                                        "Insufficient chips for additional bet: have {}, need {}",
-                                        *stack, total_deduction
+                                        "Insufficient chips for additional bet: have {}, need {} plus {} in fees",
+                                        stack.0, extra_deduction, super_fee
                                     ),
                                 }];
-                            }
-                            *stack = stack.saturating_sub(total_deduction);
+                            };
 
                             // Update House PnL for cash games only (income from the extra wager).
-                            if !session.is_tournament && total_deduction > 0 {
-                                self.update_house_pnl(total_deduction as i128).await;
+                            if !session.is_tournament && !total_deduction.is_zero() {
+                                self.update_house_pnl(total_deduction.0 as i128).await;
                             }
                         }
 
@@ -1039,7 +1980,7 @@ impl<'a, S: State> Layer<'a, S> {
                         let payout = if was_shielded {
                             // Shield prevents loss - refund the full loss amount (including the extra deduction).
                             *shields_pool = shields_pool.saturating_sub(1);
-                            *stack = stack.saturating_add(total_loss);
+                            *stack = stack.saturating_add(Chips(total_loss));
 
                             if !session.is_tournament {
                                 self.update_house_pnl(-(total_loss as i128)).await;
@@ -1069,7 +2010,7 @@ impl<'a, S: State> Layer<'a, S> {
                         player: public.clone(),
                         game_type: session.game_type,
                         payout,
-                        final_chips,
+                        final_chips: final_chips.into(),
                         was_shielded,
                         was_doubled: false,
                     });
@@ -1083,6 +2024,18 @@ impl<'a, S: State> Layer<'a, S> {
             }
         }
 
+        if session.is_complete {
+            self.release_session(public, session_id).await;
+            self.queue_session_expiry(session_id).await;
+        }
+        self.record_game_history(public, &session, &events).await;
+        let stats = self.record_game_stats(&session, &events).await;
+        events.extend(stats);
+        let loyalty = self.accrue_loyalty(public, &session, &events).await;
+        events.extend(loyalty);
+        let bonus = self.wager_bonus(public, &session, &events).await;
+        events.extend(bonus);
+
         events
     }
 
@@ -1146,7 +2099,7 @@ impl<'a, S: State> Layer<'a, S> {
                 return vec![Event::CasinoError {
                     player: public.clone(),
                     session_id: None,
-                    error_code: nullspace_types::casino::ERROR_PLAYER_NOT_FOUND,
+                    error_code: nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
                     message: "Player not found".to_string(),
                 }]
             }
@@ -1166,7 +2119,7 @@ impl<'a, S: State> Layer<'a, S> {
             return vec![Event::CasinoError {
                 player: public.clone(),
                 session_id: None,
-                error_code: nullspace_types::casino::ERROR_TOURNAMENT_LIMIT_REACHED,
+                error_code: nullspace_types::casino::CasinoErrorCode::TournamentLimitReached,
                 message: "Daily tournament limit reached (5/5)".to_string(),
             }];
         }
@@ -1186,6 +2139,8 @@ impl<'a, S: State> Layer<'a, S> {
                 starting_shields: nullspace_types::casino::STARTING_SHIELDS,
                 starting_doubles: nullspace_types::casino::STARTING_DOUBLES,
                 leaderboard: nullspace_types::casino::CasinoLeaderboard::default(),
+                schedule: None,
+                rules: nullspace_types::casino::TournamentRules::default(),
             },
         };
 
@@ -1197,7 +2152,7 @@ impl<'a, S: State> Layer<'a, S> {
             return vec![Event::CasinoError {
                 player: public.clone(),
                 session_id: None,
-                error_code: nullspace_types::casino::ERROR_TOURNAMENT_NOT_REGISTERING,
+                error_code: nullspace_types::casino::CasinoErrorCode::TournamentNotRegistering,
                 message: "Tournament is not in registration phase".to_string(),
             }];
         }
@@ -1207,7 +2162,7 @@ impl<'a, S: State> Layer<'a, S> {
             return vec![Event::CasinoError {
                 player: public.clone(),
                 session_id: None,
-                error_code: nullspace_types::casino::ERROR_ALREADY_IN_TOURNAMENT,
+                error_code: nullspace_types::casino::CasinoErrorCode::AlreadyInTournament,
                 message: "Already joined this tournament".to_string(),
             }];
         }
@@ -1246,7 +2201,7 @@ impl<'a, S: State> Layer<'a, S> {
                     return vec![Event::CasinoError {
                         player: public.clone(),
                         session_id: None,
-                        error_code: nullspace_types::casino::ERROR_INVALID_MOVE,
+                        error_code: nullspace_types::casino::CasinoErrorCode::InvalidMove,
                         message: "Tournament already active".to_string(),
                     }];
                 }
@@ -1254,10 +2209,19 @@ impl<'a, S: State> Layer<'a, S> {
                     return vec![Event::CasinoError {
                         player: public.clone(),
                         session_id: None,
-                        error_code: nullspace_types::casino::ERROR_INVALID_MOVE,
+                        error_code: nullspace_types::casino::CasinoErrorCode::InvalidMove,
                         message: "Tournament already complete".to_string(),
                     }];
                 }
+                // Scheduled tournaments start at their start view
+                if t.schedule.is_some() {
+                    return vec![Event::CasinoError {
+                        player: public.clone(),
+                        session_id: None,
+                        error_code: nullspace_types::casino::CasinoErrorCode::InvalidMove,
+                        message: "Tournament is scheduled".to_string(),
+                    }];
+                }
                 t
             }
             None => {
@@ -1274,6 +2238,8 @@ impl<'a, S: State> Layer<'a, S> {
                     starting_shields: nullspace_types::casino::STARTING_SHIELDS,
                     starting_doubles: nullspace_types::casino::STARTING_DOUBLES,
                     leaderboard: nullspace_types::casino::CasinoLeaderboard::default(),
+                    schedule: None,
+                    rules: nullspace_types::casino::TournamentRules::default(),
                 };
                 t.add_player(public.clone());
                 t
@@ -1293,6 +2259,20 @@ impl<'a, S: State> Layer<'a, S> {
             start_time_ms.saturating_add(expected_duration_ms)
         };
 
+        self.activate_tournament(&mut tournament, start_time_ms, end_time_ms)
+            .await
+    }
+
+    /// Move `tournament` into the Active phase (minting its prize pool and resetting the
+    /// tournament stacks of its players).
+    async fn activate_tournament(
+        &mut self,
+        tournament: &mut nullspace_types::casino::Tournament,
+        start_time_ms: u64,
+        end_time_ms: u64,
+    ) -> Vec<Event> {
+        let tournament_id = tournament.id;
+
         // Calculate Prize Pool (Inflationary)
         let total_supply = nullspace_types::casino::TOTAL_SUPPLY as u128;
         let annual_bps = nullspace_types::casino::ANNUAL_EMISSION_RATE_BPS as u128;
@@ -1330,14 +2310,13 @@ impl<'a, S: State> Layer<'a, S> {
             if let Some(Value::CasinoPlayer(mut player)) =
                 self.get(&Key::CasinoPlayer(player_pk.clone())).await
             {
-                player.tournament_chips = tournament.starting_chips;
+                player.tournament_chips = Chips(tournament.starting_chips);
                 player.tournament_shields = tournament.starting_shields;
                 player.tournament_doubles = tournament.starting_doubles;
                 player.active_tournament = Some(tournament_id);
                 player.active_shield = false;
                 player.active_double = false;
                 player.active_super = false;
-                player.active_session = None;
                 player.aura_meter = 0;
 
                 self.insert(
@@ -1346,7 +2325,7 @@ impl<'a, S: State> Layer<'a, S> {
                 );
                 leaderboard.update(
                     player_pk.clone(),
-                    player.name.clone(),
+                    player.display_name().to_string(),
                     player.tournament_chips,
                 );
             }
@@ -1377,57 +2356,60 @@ impl<'a, S: State> Layer<'a, S> {
                 return vec![];
             };
 
+        // Scheduled tournaments end at their end view
         if !matches!(
             tournament.phase,
             nullspace_types::casino::TournamentPhase::Active
-        ) {
+        ) || tournament.schedule.is_some()
+        {
             return vec![];
         }
 
+        self.complete_tournament(&mut tournament).await
+    }
+
+    /// Pay out the prize pool of an Active `tournament` and mark it Complete.
+    async fn complete_tournament(
+        &mut self,
+        tournament: &mut nullspace_types::casino::Tournament,
+    ) -> Vec<Event> {
+        let tournament_id = tournament.id;
+
         // Gather player tournament chips
         let mut rankings: Vec<(PublicKey, u64)> = Vec::new();
         for player_pk in &tournament.players {
             if let Some(Value::CasinoPlayer(p)) =
                 self.get(&Key::CasinoPlayer(player_pk.clone())).await
             {
-                rankings.push((player_pk.clone(), p.tournament_chips));
+                rankings.push((player_pk.clone(), p.tournament_chips.into()));
             }
         }
 
         // Sort descending
         rankings.sort_by(|a, b| b.1.cmp(&a.1));
 
-        // Determine winners (Top 15% for MTT style)
-        let num_players = rankings.len();
-        let num_winners = (num_players as f64 * 0.15).ceil() as usize;
-        let num_winners = num_winners.max(1).min(num_players);
-
-        // Calculate payout weights (1/rank harmonic distribution)
-        let mut weights = Vec::with_capacity(num_winners);
-        let mut total_weight = 0.0;
-        for i in 1..=num_winners {
-            let w = 1.0 / (i as f64);
-            weights.push(w);
-            total_weight += w;
-        }
-
-        // Distribute Prize Pool
-        if total_weight > 0.0 && tournament.prize_pool > 0 {
-            for i in 0..num_winners {
-                let (pk, _) = &rankings[i];
-                let weight = weights[i];
-                let share = weight / total_weight;
-                let payout = (share * tournament.prize_pool as f64) as u64;
-
-                if payout > 0 {
-                    if let Some(Value::CasinoPlayer(mut p)) =
-                        self.get(&Key::CasinoPlayer(pk.clone())).await
-                    {
-                        // Tournament prizes are credited to the real bankroll
-                        p.chips = p.chips.saturating_add(payout);
-                        self.insert(Key::CasinoPlayer(pk.clone()), Value::CasinoPlayer(p));
-                    }
-                }
+        // Distribute the prize pool according to the house payout table
+        let payouts = self
+            .house_settings()
+            .await
+            .tournament_payouts
+            .split(tournament.prize_pool, rankings.len());
+        let mut events = Vec::with_capacity(payouts.len() + 1);
+        for (rank, ((pk, _), payout)) in rankings.iter().zip(payouts).enumerate() {
+            if payout == 0 {
+                continue;
+            }
+            if let Some(Value::CasinoPlayer(mut p)) = self.get(&Key::CasinoPlayer(pk.clone())).await
+            {
+                // Tournament prizes are credited to the real bankroll
+                p.chips = p.chips.saturating_add(Chips(payout));
+                self.insert(Key::CasinoPlayer(pk.clone()), Value::CasinoPlayer(p));
+                events.push(Event::TournamentPrizePaid {
+                    tournament_id,
+                    player: pk.clone(),
+                    rank: rank as u32 + 1,
+                    amount: payout,
+                });
             }
         }
 
@@ -1438,13 +2420,27 @@ impl<'a, S: State> Layer<'a, S> {
             {
                 if player.active_tournament == Some(tournament_id) {
                     player.active_tournament = None;
-                    player.tournament_chips = 0;
+                    player.tournament_chips = Chips::ZERO;
                     player.tournament_shields = 0;
                     player.tournament_doubles = 0;
                     player.active_shield = false;
                     player.active_double = false;
                     player.active_super = false;
-                    player.active_session = None;
+
+                    // Free the slots of sessions played in the tournament (cash sessions stay
+                    // open)
+                    let mut active_sessions = Vec::with_capacity(player.active_sessions.len());
+                    for id in player.active_sessions {
+                        if let Some(Value::CasinoSession(session)) =
+                            self.get(&Key::CasinoSession(id)).await
+                        {
+                            if session.tournament_id == Some(tournament_id) {
+                                continue;
+                            }
+                        }
+                        active_sessions.push(id);
+                    }
+                    player.active_sessions = active_sessions;
                     self.insert(
                         Key::CasinoPlayer(player_pk.clone()),
                         Value::CasinoPlayer(player.clone()),
@@ -1456,25 +2452,428 @@ impl<'a, S: State> Layer<'a, S> {
         tournament.phase = nullspace_types::casino::TournamentPhase::Complete;
         self.insert(
             Key::Tournament(tournament_id),
-            Value::Tournament(tournament),
+            Value::Tournament(tournament.clone()),
         );
 
-        vec![Event::TournamentEnded {
+        events.push(Event::TournamentEnded {
             id: tournament_id,
             rankings,
+        });
+        events
+    }
+
+    pub(in crate::layer) async fn handle_set_tournament_rules(
+        &mut self,
+        public: &PublicKey,
+        tournament_id: u64,
+        rules: &nullspace_types::casino::TournamentRules,
+    ) -> Vec<Event> {
+        let house = self.house_settings().await;
+        if house.governance.as_ref() != Some(public) {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::Unauthorized,
+                message: "Only governance can set tournament rules".to_string(),
+            }];
+        }
+        if !rules.is_valid() {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::InvalidGameConfig,
+                message: "Invalid tournament rules".to_string(),
+            }];
+        }
+
+        // Rules are fixed once the tournament starts
+        let mut tournament = match self.get(&Key::Tournament(tournament_id)).await {
+            Some(Value::Tournament(t)) => {
+                if !matches!(
+                    t.phase,
+                    nullspace_types::casino::TournamentPhase::Registration
+                ) {
+                    return vec![Event::CasinoError {
+                        player: public.clone(),
+                        session_id: None,
+                        error_code:
+                            nullspace_types::casino::CasinoErrorCode::TournamentNotRegistering,
+                        message: "Tournament is not in registration phase".to_string(),
+                    }];
+                }
+                t
+            }
+            _ => nullspace_types::casino::Tournament {
+                id: tournament_id,
+                starting_chips: nullspace_types::casino::STARTING_CHIPS,
+                starting_shields: nullspace_types::casino::STARTING_SHIELDS,
+                starting_doubles: nullspace_types::casino::STARTING_DOUBLES,
+                ..Default::default()
+            },
+        };
+        tournament.rules = rules.clone();
+        self.insert(
+            Key::Tournament(tournament_id),
+            Value::Tournament(tournament),
+        );
+
+        vec![Event::TournamentRulesSet {
+            tournament_id,
+            rules: rules.clone(),
+        }]
+    }
+
+    pub(in crate::layer) async fn handle_casino_schedule_tournament(
+        &mut self,
+        public: &PublicKey,
+        tournament_id: u64,
+        start_view: u64,
+        end_view: u64,
+    ) -> Vec<Event> {
+        if start_view <= self.seed.view || end_view <= start_view {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::InvalidMove,
+                message: "Invalid tournament schedule".to_string(),
+            }];
+        }
+
+        // Players may already have joined an unscheduled tournament that is still registering
+        let mut tournament = match self.get(&Key::Tournament(tournament_id)).await {
+            Some(Value::Tournament(t)) => {
+                if !matches!(
+                    t.phase,
+                    nullspace_types::casino::TournamentPhase::Registration
+                ) {
+                    return vec![Event::CasinoError {
+                        player: public.clone(),
+                        session_id: None,
+                        error_code:
+                            nullspace_types::casino::CasinoErrorCode::TournamentNotRegistering,
+                        message: "Tournament is not in registration phase".to_string(),
+                    }];
+                }
+                if t.schedule.is_some() {
+                    return vec![Event::CasinoError {
+                        player: public.clone(),
+                        session_id: None,
+                        error_code: nullspace_types::casino::CasinoErrorCode::InvalidMove,
+                        message: "Tournament already scheduled".to_string(),
+                    }];
+                }
+                t
+            }
+            _ => nullspace_types::casino::Tournament {
+                id: tournament_id,
+                phase: nullspace_types::casino::TournamentPhase::Registration,
+                start_block: 0,
+                start_time_ms: 0,
+                end_time_ms: 0,
+                players: Vec::new(),
+                prize_pool: 0,
+                starting_chips: nullspace_types::casino::STARTING_CHIPS,
+                starting_shields: nullspace_types::casino::STARTING_SHIELDS,
+                starting_doubles: nullspace_types::casino::STARTING_DOUBLES,
+                leaderboard: nullspace_types::casino::CasinoLeaderboard::default(),
+                schedule: None,
+                rules: nullspace_types::casino::TournamentRules::default(),
+            },
+        };
+
+        let mut schedules = match self.get(&Key::TournamentSchedules).await {
+            Some(Value::TournamentSchedules(schedules)) => schedules,
+            _ => nullspace_types::casino::TournamentSchedules::default(),
+        };
+        if schedules.ids.len() >= nullspace_types::casino::MAX_SCHEDULED_TOURNAMENTS {
+            return vec![Event::CasinoError {
+                player: public.clone(),
+                session_id: None,
+                error_code: nullspace_types::casino::CasinoErrorCode::InvalidMove,
+                message: "Too many scheduled tournaments".to_string(),
+            }];
+        }
+
+        tournament.schedule = Some(nullspace_types::casino::TournamentSchedule {
+            start_view,
+            end_view,
+        });
+        schedules.ids.push(tournament_id);
+        self.insert(
+            Key::Tournament(tournament_id),
+            Value::Tournament(tournament),
+        );
+        self.insert(
+            Key::TournamentSchedules,
+            Value::TournamentSchedules(schedules),
+        );
+
+        vec![Event::TournamentScheduled {
+            id: tournament_id,
+            start_view,
+            end_view,
         }]
     }
 
-    async fn update_casino_leaderboard(
+    /// Start and end the scheduled tournaments whose start or end view has been reached.
+    ///
+    /// Runs at the beginning of every block, so views skipped without a block are caught up
+    /// in the next one.
+    pub(in crate::layer) async fn process_tournament_schedules(&mut self) -> Vec<Event> {
+        let Some(Value::TournamentSchedules(mut schedules)) =
+            self.get(&Key::TournamentSchedules).await
+        else {
+            return vec![];
+        };
+
+        let view = self.seed.view;
+        let mut events = Vec::new();
+        let mut pending = Vec::with_capacity(schedules.ids.len());
+        for &tournament_id in &schedules.ids {
+            let Some(Value::Tournament(mut tournament)) =
+                self.get(&Key::Tournament(tournament_id)).await
+            else {
+                continue;
+            };
+            let Some(schedule) = tournament.schedule else {
+                continue;
+            };
+
+            if tournament.phase == nullspace_types::casino::TournamentPhase::Registration
+                && view >= schedule.start_view
+            {
+                events.push(Event::TournamentPhaseChanged {
+                    id: tournament_id,
+                    phase: nullspace_types::casino::TournamentPhase::Active,
+                });
+                // Approximate time from view (3s per block)
+                let start_time_ms = view.saturating_mul(3_000);
+                let end_time_ms = schedule.end_view.saturating_mul(3_000);
+                events.extend(
+                    self.activate_tournament(&mut tournament, start_time_ms, end_time_ms)
+                        .await,
+                );
+            }
+            if tournament.phase == nullspace_types::casino::TournamentPhase::Active
+                && view >= schedule.end_view
+            {
+                events.push(Event::TournamentPhaseChanged {
+                    id: tournament_id,
+                    phase: nullspace_types::casino::TournamentPhase::Complete,
+                });
+                events.extend(self.complete_tournament(&mut tournament).await);
+            }
+            if tournament.phase != nullspace_types::casino::TournamentPhase::Complete {
+                pending.push(tournament_id);
+            }
+        }
+
+        // Only rewrite the index when a tournament completed (most blocks don't change it)
+        if pending.len() != schedules.ids.len() {
+            schedules.ids = pending;
+            self.insert(
+                Key::TournamentSchedules,
+                Value::TournamentSchedules(schedules),
+            );
+        }
+        events
+    }
+
+    /// Queue a completed session for reclamation once it is `SESSION_RETENTION` views old
+    /// (sessions are queued by the view they completed at).
+    async fn queue_session_expiry(&mut self, session_id: u64) {
+        match &mut self.deferred {
+            Some(deferred) => deferred.expiries.push(session_id),
+            None => self.queue_session_expiry_entry(session_id).await,
+        }
+    }
+
+    pub(in crate::layer) async fn queue_session_expiry_entry(&mut self, session_id: u64) {
+        // The cursor starts at the first view with a queued session
+        if self.get(&Key::SessionReclaimCursor).await.is_none() {
+            self.insert(
+                Key::SessionReclaimCursor,
+                Value::SessionReclaimCursor(self.seed.view),
+            );
+        }
+        let key = Key::SessionExpiries(self.seed.view);
+        let mut ids = match self.get(&key).await {
+            Some(Value::SessionExpiries(ids)) => ids,
+            _ => Vec::new(),
+        };
+        if ids.len() >= nullspace_types::casino::MAX_SESSION_EXPIRIES || ids.contains(&session_id) {
+            return;
+        }
+        ids.push(session_id);
+        self.insert(key, Value::SessionExpiries(ids));
+    }
+
+    /// Delete the completed sessions that are `SESSION_RETENTION` views old, so sessions of
+    /// players who stopped playing don't stay in state forever. Their results remain in the
+    /// players' histories.
+    ///
+    /// Queued views are visited in order from a cursor (so views without a block aren't
+    /// skipped), and at most `SESSION_RECLAIM_LIMIT` views and sessions are visited per block.
+    pub(in crate::layer) async fn reclaim_expired_sessions(&mut self) {
+        let Some(Value::SessionReclaimCursor(start)) = self.get(&Key::SessionReclaimCursor).await
+        else {
+            return;
+        };
+        let expired = self
+            .seed
+            .view
+            .saturating_sub(nullspace_types::casino::SESSION_RETENTION);
+        let mut budget = nullspace_types::casino::SESSION_RECLAIM_LIMIT;
+        let mut cursor = start;
+        while cursor <= expired && budget > 0 {
+            budget -= 1;
+            let key = Key::SessionExpiries(cursor);
+            let Some(Value::SessionExpiries(mut ids)) = self.get(&key).await else {
+                cursor += 1;
+                continue;
+            };
+            let reclaimed = ids.len().min(budget);
+            budget -= reclaimed;
+            for session_id in ids.drain(..reclaimed) {
+                let session_key = Key::CasinoSession(session_id);
+                if let Some(Value::CasinoSession(session)) = self.get(&session_key).await {
+                    if session.is_complete {
+                        self.delete(&session_key).await;
+                    }
+                }
+            }
+
+            // Sessions left over are reclaimed in the next block
+            if !ids.is_empty() {
+                self.insert(key, Value::SessionExpiries(ids));
+                break;
+            }
+            self.delete(&key).await;
+            cursor += 1;
+        }
+        if cursor != start {
+            self.insert(
+                Key::SessionReclaimCursor,
+                Value::SessionReclaimCursor(cursor),
+            );
+        }
+    }
+
+    /// End the leaderboard season once its end view has been reached: archive the standings,
+    /// pay the season reward to the top players and start the next season.
+    pub(in crate::layer) async fn process_leaderboard_season(&mut self) -> Vec<Event> {
+        let Some(Value::CasinoLeaderboard(leaderboard)) = self.get(&Key::CasinoLeaderboard).await
+        else {
+            return vec![];
+        };
+        if leaderboard.season_end == 0 || self.seed.view < leaderboard.season_end {
+            return vec![];
+        }
+        let season = leaderboard.season;
+
+        // Rewards are minted from the reward pool (like tournament prizes)
+        let mut house = self.get_or_init_house().await;
+        let reward_pool_cap = (nullspace_types::casino::TOTAL_SUPPLY as u128
+            * nullspace_types::casino::REWARD_POOL_BPS as u128
+            / 10000) as u64;
+        let reward = house
+            .seasons
+            .reward
+            .min(reward_pool_cap.saturating_sub(house.total_issuance));
+        let payouts = house
+            .seasons
+            .payouts
+            .split(reward, leaderboard.entries.len());
+        let mut events = Vec::with_capacity(payouts.len() + 1);
+        let mut minted = 0u64;
+        for (rank, (entry, amount)) in leaderboard.entries.iter().zip(payouts).enumerate() {
+            if amount == 0 {
+                continue;
+            }
+            let Some(Value::CasinoPlayer(mut player)) =
+                self.get(&Key::CasinoPlayer(entry.player.clone())).await
+            else {
+                continue;
+            };
+            player.chips = player.chips.saturating_add(Chips(amount));
+            self.insert(
+                Key::CasinoPlayer(entry.player.clone()),
+                Value::CasinoPlayer(player),
+            );
+            minted = minted.saturating_add(amount);
+            events.push(Event::SeasonRewardPaid {
+                season,
+                player: entry.player.clone(),
+                rank: rank as u32 + 1,
+                amount,
+            });
+        }
+        let length = house.seasons.length;
+        if minted > 0 {
+            house.total_issuance = house.total_issuance.saturating_add(minted);
+            self.insert(Key::House, Value::House(house));
+        }
+
+        // Archive the standings and start the next season (unless seasons have been disabled)
+        self.insert(
+            Key::LeaderboardSeason(season),
+            Value::CasinoLeaderboard(leaderboard),
+        );
+        let next_season_end = if length > 0 {
+            self.seed.view.saturating_add(length)
+        } else {
+            0
+        };
+        self.insert(
+            Key::CasinoLeaderboard,
+            Value::CasinoLeaderboard(nullspace_types::casino::CasinoLeaderboard {
+                entries: Vec::new(),
+                season: season + 1,
+                season_end: next_season_end,
+            }),
+        );
+        events.push(Event::LeaderboardSeasonEnded {
+            season,
+            next_season_end,
+        });
+        events
+    }
+
+    pub(in crate::layer) async fn update_casino_leaderboard(
         &mut self,
         public: &PublicKey,
         player: &nullspace_types::casino::Player,
+    ) {
+        let name = player.display_name().to_string();
+        match &mut self.deferred {
+            Some(deferred) => deferred
+                .leaderboard
+                .push((public.clone(), name, player.chips)),
+            None => {
+                self.update_casino_leaderboard_entry(public, name, player.chips)
+                    .await
+            }
+        }
+    }
+
+    async fn update_casino_leaderboard_entry(
+        &mut self,
+        public: &PublicKey,
+        name: String,
+        chips: Chips,
     ) {
         let mut leaderboard = match self.get(&Key::CasinoLeaderboard).await {
             Some(Value::CasinoLeaderboard(lb)) => lb,
             _ => nullspace_types::casino::CasinoLeaderboard::default(),
         };
-        leaderboard.update(public.clone(), player.name.clone(), player.chips);
+        // The first season starts with the first update (if seasons are enabled)
+        if leaderboard.season_end == 0 {
+            let length = self.house_settings().await.seasons.length;
+            if length > 0 {
+                leaderboard.season_end = self.seed.view.saturating_add(length);
+            }
+        }
+        leaderboard.update(public.clone(), name, chips);
         self.insert(
             Key::CasinoLeaderboard,
             Value::CasinoLeaderboard(leaderboard),
@@ -1488,8 +2887,11 @@ impl<'a, S: State> Layer<'a, S> {
         player: &nullspace_types::casino::Player,
     ) {
         if let Some(Value::Tournament(mut t)) = self.get(&Key::Tournament(tournament_id)).await {
-            t.leaderboard
-                .update(public.clone(), player.name.clone(), player.tournament_chips);
+            t.leaderboard.update(
+                public.clone(),
+                player.display_name().to_string(),
+                player.tournament_chips,
+            );
             self.insert(Key::Tournament(tournament_id), Value::Tournament(t));
         }
     }
@@ -1510,119 +2912,65 @@ impl<'a, S: State> Layer<'a, S> {
         }
     }
 
-    async fn apply_progressive_meters_for_completion(
+    /// Settle the side bets of a completed session: feed (and pay out) any progressive meters
+    /// and report each bet.
+    async fn settle_side_bets(
         &mut self,
         session: &nullspace_types::casino::GameSession,
-        result: crate::casino::GameResult,
-    ) -> crate::casino::GameResult {
-        if session.is_tournament || !session.is_complete {
-            return result;
+        mut result: crate::casino::GameResult,
+    ) -> (crate::casino::GameResult, Vec<Event>) {
+        if !session.is_complete {
+            return (result, Vec::new());
         }
 
-        match session.game_type {
-            nullspace_types::casino::GameType::ThreeCard => {
-                self.apply_three_card_progressive_meter(session, result)
-                    .await
+        let mut events = Vec::new();
+        for bet in crate::casino::side_bets(session) {
+            if bet.amount == 0 {
+                continue;
             }
-            nullspace_types::casino::GameType::UltimateHoldem => {
-                self.apply_uth_progressive_meter(session, result).await
-            }
-            _ => result,
-        }
-    }
-
-    async fn apply_three_card_progressive_meter(
-        &mut self,
-        session: &nullspace_types::casino::GameSession,
-        result: crate::casino::GameResult,
-    ) -> crate::casino::GameResult {
-        let Some((progressive_bet, player_cards)) =
-            parse_three_card_progressive_state(&session.state_blob)
-        else {
-            return result;
-        };
-        if progressive_bet == 0 {
-            return result;
-        }
-
-        let mut house = self.get_or_init_house().await;
-        let base = nullspace_types::casino::THREE_CARD_PROGRESSIVE_BASE_JACKPOT;
-
-        let mut jackpot = house.three_card_progressive_jackpot.max(base);
-        jackpot = jackpot.saturating_add(progressive_bet);
 
-        let can_adjust = matches!(result, crate::casino::GameResult::Win(_));
-        let is_jackpot = can_adjust && is_three_card_mini_royal_spades(&player_cards);
-        let delta = if is_jackpot {
-            progressive_bet.saturating_mul(jackpot.saturating_sub(base))
-        } else {
-            0
-        };
+            let mut delta = 0;
+            if let Some((progressive, tier)) = bet.progressive {
+                if !session.is_tournament {
+                    // Jackpots are only paid on top of a winning result
+                    let tier = if matches!(result, crate::casino::GameResult::Win(_)) {
+                        tier
+                    } else {
+                        crate::casino::side_bet::JackpotTier::None
+                    };
 
-        house.three_card_progressive_jackpot = if is_jackpot { base } else { jackpot };
-        self.insert(Key::House, Value::House(house));
+                    let mut house = self.get_or_init_house().await;
+                    delta = crate::casino::side_bet::settle_progressive_meter(
+                        progressive.meter(&mut house),
+                        progressive.base_jackpot(),
+                        bet.amount,
+                        tier,
+                    );
+                    self.insert(Key::House, Value::House(house));
 
-        match result {
-            crate::casino::GameResult::Win(payout) if delta > 0 => {
-                crate::casino::GameResult::Win(payout.saturating_add(delta))
+                    if let crate::casino::GameResult::Win(payout) = &mut result {
+                        *payout = payout.saturating_add(delta);
+                    }
+                }
             }
-            other => other,
-        }
-    }
 
-    async fn apply_uth_progressive_meter(
-        &mut self,
-        session: &nullspace_types::casino::GameSession,
-        result: crate::casino::GameResult,
-    ) -> crate::casino::GameResult {
-        let Some((progressive_bet, hole, flop)) = parse_uth_progressive_state(&session.state_blob)
-        else {
-            return result;
-        };
-        if progressive_bet == 0 {
-            return result;
+            events.push(Event::CasinoSideBetSettled {
+                session_id: session.id,
+                bet_id: bet.id,
+                amount: bet.amount,
+                payout: bet.returned.saturating_add(delta),
+            });
         }
 
-        let mut house = self.get_or_init_house().await;
-        let base = nullspace_types::casino::UTH_PROGRESSIVE_BASE_JACKPOT;
-
-        let mut jackpot = house.uth_progressive_jackpot.max(base);
-        jackpot = jackpot.saturating_add(progressive_bet);
-
-        let can_adjust = matches!(result, crate::casino::GameResult::Win(_));
-        let tier = if can_adjust {
-            uth_progressive_jackpot_tier(&hole, &flop)
-        } else {
-            UthJackpotTier::None
-        };
-        let delta = match tier {
-            UthJackpotTier::RoyalFlush => {
-                progressive_bet.saturating_mul(jackpot.saturating_sub(base))
-            }
-            UthJackpotTier::StraightFlush => {
-                let desired = jackpot / 10;
-                let current = base / 10;
-                progressive_bet.saturating_mul(desired.saturating_sub(current))
-            }
-            UthJackpotTier::None => 0,
-        };
-
-        house.uth_progressive_jackpot = if matches!(tier, UthJackpotTier::RoyalFlush) {
-            base
-        } else {
-            jackpot
-        };
-        self.insert(Key::House, Value::House(house));
-
-        match result {
-            crate::casino::GameResult::Win(payout) if delta > 0 => {
-                crate::casino::GameResult::Win(payout.saturating_add(delta))
-            }
-            other => other,
-        }
+        (result, events)
     }
 
     async fn update_house_pnl(&mut self, amount: i128) {
+        if let Some(deferred) = &mut self.deferred {
+            deferred.house_updated = true;
+            deferred.pnl += amount;
+            return;
+        }
         let mut house = self.get_or_init_house().await;
         house.net_pnl += amount;
         self.insert(Key::House, Value::House(house));
@@ -1,16 +1,194 @@
 use super::super::*;
+use nullspace_types::casino::{AmmPool, Chips, LpShares, PairId, Vusdt};
+
+/// Whether `debt` is within the max LTV (50%) of `collateral` valued at `price` (scaled by
+/// `PRICE_SCALE`).
+fn within_max_ltv(collateral: Chips, debt: Vusdt, price: u128) -> bool {
+    // LTV Calculation: Max Debt = (Collateral * Price) * 50%
+    // Debt <= (Collateral * Price / PRICE_SCALE) / 2
+    // 2 * Debt * PRICE_SCALE <= Collateral * Price
+    let lhs = 2 * (debt.0 as u128) * nullspace_types::casino::PRICE_SCALE;
+    let rhs = (collateral.0 as u128).saturating_mul(price);
+    lhs <= rhs
+}
+
+/// The outcome of swapping some amount against an AMM pool.
+///
+/// Amounts are in the asset sold (vUSDT when buying RNG) except `amount_out`, which is in the
+/// asset bought.
+pub(super) struct SwapQuote {
+    /// Sell tax taken from the input (when selling RNG).
+    pub burned_amount: u64,
+    /// Input added to the pool's reserves (after the sell tax).
+    pub net_amount_in: u64,
+    pub fee_amount: u64,
+    pub amount_out: u64,
+}
+
+impl SwapQuote {
+    /// The reserves of `amm` after this swap (if they don't overflow).
+    pub fn apply(&self, amm: &AmmPool, is_buying_rng: bool) -> Option<AmmPool> {
+        let mut after = amm.clone();
+        if is_buying_rng {
+            after.reserve_vusdt = amm.reserve_vusdt.checked_add(Vusdt(self.net_amount_in))?;
+            after.reserve_rng = amm.reserve_rng.checked_sub(Chips(self.amount_out))?;
+        } else {
+            after.reserve_rng = amm.reserve_rng.checked_add(Chips(self.net_amount_in))?;
+            after.reserve_vusdt = amm.reserve_vusdt.checked_sub(Vusdt(self.amount_out))?;
+        }
+        Some(after)
+    }
+}
+
+/// Quote swapping `amount_in` against `amm` (returning `None` if the pool has no liquidity).
+pub(super) fn quote_swap(amm: &AmmPool, amount_in: u64, is_buying_rng: bool) -> Option<SwapQuote> {
+    if amm.reserve_rng.is_zero() || amm.reserve_vusdt.is_zero() {
+        return None;
+    }
+
+    // Apply Sell Tax (if Selling RNG)
+    let mut burned_amount = 0;
+    if !is_buying_rng {
+        // Sell Tax: 5% (default)
+        burned_amount = (amount_in as u128 * amm.sell_tax_basis_points as u128 / 10000) as u64;
+    }
+    let amount_in = amount_in.saturating_sub(burned_amount);
+
+    // Reserves (u128 for safety)
+    let (reserve_in, reserve_out) = if is_buying_rng {
+        (amm.reserve_vusdt.0 as u128, amm.reserve_rng.0 as u128)
+    } else {
+        (amm.reserve_rng.0 as u128, amm.reserve_vusdt.0 as u128)
+    };
+
+    // Fee (30 bps = 0.3%)
+    let fee_bps = amm.fee_basis_points as u128;
+    let fee_amount = ((amount_in as u128) * fee_bps) / 10_000;
+    let net_in = (amount_in as u128).saturating_sub(fee_amount);
+    let amount_in_with_fee = net_in * 10_000;
+    let numerator = amount_in_with_fee.saturating_mul(reserve_out);
+    let denominator = reserve_in
+        .saturating_mul(10_000)
+        .saturating_add(amount_in_with_fee);
+    if denominator == 0 {
+        return None;
+    }
+
+    Some(SwapQuote {
+        burned_amount,
+        net_amount_in: amount_in,
+        fee_amount: fee_amount as u64,
+        amount_out: (numerator / denominator) as u64,
+    })
+}
+
+/// Shares minted (for each asset) by depositing into `amm` (which must have liquidity).
+fn proportional_shares(amm: &AmmPool, rng_amount: Chips, vusdt_amount: Vusdt) -> (u128, u128) {
+    let total_shares = amm.total_shares.0 as u128;
+    let share_rng = (rng_amount.0 as u128 * total_shares) / amm.reserve_rng.0 as u128;
+    let share_vusdt = (vusdt_amount.0 as u128 * total_shares) / amm.reserve_vusdt.0 as u128;
+    (share_rng, share_vusdt)
+}
+
+/// `amm` after depositing `rng_amount` and `vusdt_amount` for `shares` (if it doesn't overflow).
+fn deposit(
+    amm: &AmmPool,
+    rng_amount: Chips,
+    vusdt_amount: Vusdt,
+    shares: LpShares,
+) -> Option<AmmPool> {
+    Some(AmmPool {
+        reserve_rng: amm.reserve_rng.checked_add(rng_amount)?,
+        reserve_vusdt: amm.reserve_vusdt.checked_add(vusdt_amount)?,
+        total_shares: amm.total_shares.checked_add(shares)?,
+        ..amm.clone()
+    })
+}
+
+/// The assets `shares` redeem from `amm` (if it has any shares).
+fn redeem(amm: &AmmPool, shares: LpShares) -> Option<(Chips, Vusdt)> {
+    let total_shares = amm.total_shares.0 as u128;
+    Some((
+        amm.reserve_rng
+            .checked_mul_div(shares.0 as u128, total_shares)?,
+        amm.reserve_vusdt
+            .checked_mul_div(shares.0 as u128, total_shares)?,
+    ))
+}
+
+/// A single-sided deposit: the swap leg and the amounts deposited after it.
+struct Zap {
+    swap_amount_in: u64,
+    quote: SwapQuote,
+    /// The pool after the swap leg.
+    pool: AmmPool,
+    rng_amount: Chips,
+    vusdt_amount: Vusdt,
+    shares: LpShares,
+}
+
+/// Plan depositing `amount_in` of a single asset into `amm` (which must have liquidity),
+/// swapping the portion that maximizes the shares minted.
+fn plan_zap(amm: &AmmPool, amount_in: u64, from_rng: bool) -> Option<Zap> {
+    let is_buying_rng = !from_rng;
+    let zap = |swap_amount_in: u64| -> Option<(Zap, u128, u128)> {
+        let quote = quote_swap(amm, swap_amount_in, is_buying_rng)?;
+        let pool = quote.apply(amm, is_buying_rng)?;
+        let kept = amount_in - swap_amount_in;
+        let (rng_amount, vusdt_amount) = if from_rng {
+            (Chips(kept), Vusdt(quote.amount_out))
+        } else {
+            (Chips(quote.amount_out), Vusdt(kept))
+        };
+        let (share_rng, share_vusdt) = proportional_shares(&pool, rng_amount, vusdt_amount);
+        let (kept_shares, swapped_shares) = if from_rng {
+            (share_rng, share_vusdt)
+        } else {
+            (share_vusdt, share_rng)
+        };
+        let planned = Zap {
+            swap_amount_in,
+            quote,
+            pool,
+            rng_amount,
+            vusdt_amount,
+            shares: LpShares(kept_shares.min(swapped_shares) as u64),
+        };
+        Some((planned, kept_shares, swapped_shares))
+    };
+
+    // Swapping more grows the shares of the swapped asset and shrinks those of the kept one,
+    // so the best split is where they cross
+    let (mut lo, mut hi) = (1, amount_in);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        let (_, kept_shares, swapped_shares) = zap(mid)?;
+        if swapped_shares >= kept_shares {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    let (best, ..) = zap(lo)?;
+    if lo > 1 {
+        let (previous, ..) = zap(lo - 1)?;
+        if previous.shares > best.shares {
+            return Some(previous);
+        }
+    }
+    Some(best)
+}
 
 impl<'a, S: State> Layer<'a, S> {
     // === Liquidity / Vault Handlers ===
 
     pub(in crate::layer) async fn handle_create_vault(&mut self, public: &PublicKey) -> Vec<Event> {
         if self.get(&Key::Vault(public.clone())).await.is_some() {
-            return vec![Event::CasinoError {
-                player: public.clone(),
-                session_id: None,
-                error_code: nullspace_types::casino::ERROR_INVALID_MOVE, // Reuse
-                message: "Vault already exists".to_string(),
-            }];
+            return Self::instruction_failed(
+                public,
+                tags::instruction::CREATE_VAULT,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
         }
 
         let vault = nullspace_types::casino::Vault::default();
@@ -27,33 +205,43 @@ impl<'a, S: State> Layer<'a, S> {
     ) -> Vec<Event> {
         let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
             Some(Value::CasinoPlayer(p)) => p,
-            _ => return vec![],
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::DEPOSIT_COLLATERAL,
+                    nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                )
+            }
         };
 
-        if player.chips < amount {
-            return vec![Event::CasinoError {
-                player: public.clone(),
-                session_id: None,
-                error_code: nullspace_types::casino::ERROR_INSUFFICIENT_FUNDS,
-                message: "Insufficient chips".to_string(),
-            }];
-        }
+        let Some(chips) = player.chips.checked_sub(Chips(amount)) else {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::DEPOSIT_COLLATERAL,
+                nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+            );
+        };
 
         let mut vault = match self.get(&Key::Vault(public.clone())).await {
             Some(Value::Vault(v)) => v,
             _ => {
-                return vec![Event::CasinoError {
-                    player: public.clone(),
-                    session_id: None,
-                    error_code: nullspace_types::casino::ERROR_INVALID_MOVE,
-                    message: "Vault not found".to_string(),
-                }]
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::DEPOSIT_COLLATERAL,
+                    nullspace_types::casino::CasinoErrorCode::NotFound,
+                )
             }
         };
 
-        player.chips -= amount;
-        vault.collateral_rng += amount;
-        let new_collateral = vault.collateral_rng;
+        let Some(new_collateral) = vault.collateral_rng.checked_add(Chips(amount)) else {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::DEPOSIT_COLLATERAL,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
+        };
+        player.chips = chips;
+        vault.collateral_rng = new_collateral;
 
         self.insert(
             Key::CasinoPlayer(public.clone()),
@@ -64,70 +252,197 @@ impl<'a, S: State> Layer<'a, S> {
         vec![Event::CollateralDeposited {
             player: public.clone(),
             amount,
-            new_collateral,
+            new_collateral: new_collateral.into(),
         }]
     }
 
-    pub(in crate::layer) async fn handle_borrow_usdt(
+    /// Apply the interest accrued on `vault` since it was last updated (crediting it to the
+    /// house).
+    ///
+    /// The vault and house are persisted immediately, so the interest is kept even if the
+    /// instruction that touched the vault fails.
+    async fn accrue_vault_interest(
+        &mut self,
+        public: &PublicKey,
+        vault: &mut nullspace_types::casino::Vault,
+    ) -> Option<Event> {
+        let mut house = self.get_or_init_house().await;
+        let last_index = vault.debt_index;
+        let amount = vault.accrue(house.debt_index);
+        if amount.is_zero() {
+            // Track the index even if nothing accrued (so interest starts from now)
+            self.insert(Key::Vault(public.clone()), Value::Vault(vault.clone()));
+            return None;
+        }
+        house.accumulated_fees = house.accumulated_fees.saturating_add(amount.into());
+        let effective_rate_bps =
+            ((house.debt_index - last_index) * 10_000 / last_index).min(u64::MAX as u128) as u64;
+        let rate_bps = house.debt_rate_bps;
+        self.insert(Key::House, Value::House(house));
+        self.insert(Key::Vault(public.clone()), Value::Vault(vault.clone()));
+
+        Some(Event::InterestAccrued {
+            player: public.clone(),
+            amount: amount.into(),
+            new_debt: vault.debt_vusdt.into(),
+            effective_rate_bps,
+            rate_bps,
+        })
+    }
+
+    pub(in crate::layer) async fn handle_withdraw_collateral(
         &mut self,
         public: &PublicKey,
         amount: u64,
     ) -> Vec<Event> {
+        let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
+            Some(Value::CasinoPlayer(p)) => p,
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::WITHDRAW_COLLATERAL,
+                    nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                )
+            }
+        };
+
         let mut vault = match self.get(&Key::Vault(public.clone())).await {
             Some(Value::Vault(v)) => v,
-            _ => return vec![],
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::WITHDRAW_COLLATERAL,
+                    nullspace_types::casino::CasinoErrorCode::NotFound,
+                )
+            }
         };
-
-        // Determine Price (RNG price in vUSDT)
-        let amm = self.get_or_init_amm().await;
-        let price_numerator = if amm.reserve_rng > 0 {
-            amm.reserve_vusdt as u128
-        } else {
-            1 // Bootstrap price: 1 RNG = 1 vUSDT
+        let mut events: Vec<Event> = self
+            .accrue_vault_interest(public, &mut vault)
+            .await
+            .into_iter()
+            .collect();
+
+        let Some(new_collateral) = vault.collateral_rng.checked_sub(Chips(amount)) else {
+            events.extend(Self::instruction_failed(
+                public,
+                tags::instruction::WITHDRAW_COLLATERAL,
+                nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+            ));
+            return events;
         };
-        let price_denominator = if amm.reserve_rng > 0 {
-            amm.reserve_rng as u128
-        } else {
-            1
+
+        // The remaining collateral must still cover the debt
+        let price = self.collateral_price().await;
+        if !within_max_ltv(new_collateral, vault.debt_vusdt, price) {
+            events.extend(Self::instruction_failed(
+                public,
+                tags::instruction::WITHDRAW_COLLATERAL,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            ));
+            return events;
+        }
+
+        let Some(chips) = player.chips.checked_add(Chips(amount)) else {
+            events.extend(Self::instruction_failed(
+                public,
+                tags::instruction::WITHDRAW_COLLATERAL,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            ));
+            return events;
         };
+        vault.collateral_rng = new_collateral;
+        player.chips = chips;
 
-        // LTV Calculation: Max Debt = (Collateral * Price) * 50%
-        // Debt <= (Collateral * P_num / P_den) / 2
-        // 2 * Debt * P_den <= Collateral * P_num
-        let new_debt = vault.debt_vusdt + amount;
+        self.insert(
+            Key::CasinoPlayer(public.clone()),
+            Value::CasinoPlayer(player),
+        );
+        self.insert(Key::Vault(public.clone()), Value::Vault(vault));
 
-        let lhs = 2 * (new_debt as u128) * price_denominator;
-        let rhs = (vault.collateral_rng as u128) * price_numerator;
+        events.push(Event::CollateralWithdrawn {
+            player: public.clone(),
+            amount,
+            new_collateral: new_collateral.into(),
+        });
+        events
+    }
 
-        if lhs > rhs {
-            return vec![Event::CasinoError {
-                player: public.clone(),
-                session_id: None,
-                error_code: nullspace_types::casino::ERROR_INVALID_MOVE,
-                message: "Insufficient collateral (Max 50% LTV)".to_string(),
-            }];
+    /// Price of RNG collateral in vUSDT (scaled by `PRICE_SCALE`), read from the oracle.
+    pub(super) async fn collateral_price(&self) -> u128 {
+        match self.get(&Key::Oracle).await {
+            Some(Value::Oracle(oracle)) => oracle.twap(self.seed.view),
+            // The AMM had no liquidity at the start of any block yet
+            _ => nullspace_types::casino::PRICE_SCALE, // Bootstrap price: 1 RNG = 1 vUSDT
         }
+    }
 
-        // Update Vault
-        vault.debt_vusdt = new_debt;
-        self.insert(Key::Vault(public.clone()), Value::Vault(vault));
+    pub(in crate::layer) async fn handle_borrow_usdt(
+        &mut self,
+        public: &PublicKey,
+        amount: u64,
+    ) -> Vec<Event> {
+        let mut vault = match self.get(&Key::Vault(public.clone())).await {
+            Some(Value::Vault(v)) => v,
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::BORROW_USDT,
+                    nullspace_types::casino::CasinoErrorCode::NotFound,
+                )
+            }
+        };
+        let mut events: Vec<Event> = self
+            .accrue_vault_interest(public, &mut vault)
+            .await
+            .into_iter()
+            .collect();
+
+        // Determine Price (RNG price in vUSDT, averaged by the oracle so it can't be moved
+        // within a block)
+        let price = self.collateral_price().await;
+
+        let Some(new_debt) = vault
+            .debt_vusdt
+            .checked_add(Vusdt(amount))
+            .filter(|debt| within_max_ltv(vault.collateral_rng, *debt, price))
+        else {
+            events.extend(Self::instruction_failed(
+                public,
+                tags::instruction::BORROW_USDT,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            ));
+            return events;
+        };
 
         // Mint vUSDT to Player
         if let Some(Value::CasinoPlayer(mut player)) =
             self.get(&Key::CasinoPlayer(public.clone())).await
         {
-            player.vusdt_balance += amount;
+            let Some(balance) = player.vusdt_balance.checked_add(Vusdt(amount)) else {
+                events.extend(Self::instruction_failed(
+                    public,
+                    tags::instruction::BORROW_USDT,
+                    nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+                ));
+                return events;
+            };
+            player.vusdt_balance = balance;
             self.insert(
                 Key::CasinoPlayer(public.clone()),
                 Value::CasinoPlayer(player),
             );
         }
 
-        vec![Event::VusdtBorrowed {
+        // Update Vault
+        vault.debt_vusdt = new_debt;
+        self.insert(Key::Vault(public.clone()), Value::Vault(vault));
+
+        events.push(Event::VusdtBorrowed {
             player: public.clone(),
             amount,
-            new_debt,
-        }]
+            new_debt: new_debt.into(),
+        });
+        events
     }
 
     pub(in crate::layer) async fn handle_repay_usdt(
@@ -137,27 +452,50 @@ impl<'a, S: State> Layer<'a, S> {
     ) -> Vec<Event> {
         let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
             Some(Value::CasinoPlayer(p)) => p,
-            _ => return vec![],
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::REPAY_USDT,
+                    nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                )
+            }
         };
 
         let mut vault = match self.get(&Key::Vault(public.clone())).await {
             Some(Value::Vault(v)) => v,
-            _ => return vec![],
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::REPAY_USDT,
+                    nullspace_types::casino::CasinoErrorCode::NotFound,
+                )
+            }
         };
-
-        if player.vusdt_balance < amount {
-            return vec![Event::CasinoError {
-                player: public.clone(),
-                session_id: None,
-                error_code: nullspace_types::casino::ERROR_INSUFFICIENT_FUNDS,
-                message: "Insufficient vUSDT".to_string(),
-            }];
+        let mut events: Vec<Event> = self
+            .accrue_vault_interest(public, &mut vault)
+            .await
+            .into_iter()
+            .collect();
+
+        if player.vusdt_balance < Vusdt(amount) {
+            events.extend(Self::instruction_failed(
+                public,
+                tags::instruction::REPAY_USDT,
+                nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+            ));
+            return events;
         }
 
-        let actual_repay = amount.min(vault.debt_vusdt);
-
-        player.vusdt_balance -= actual_repay;
-        vault.debt_vusdt -= actual_repay;
+        // Repaying more than the debt only repays the debt
+        let actual_repay = Vusdt(amount).min(vault.debt_vusdt);
+        player.vusdt_balance = player
+            .vusdt_balance
+            .checked_sub(actual_repay)
+            .expect("balance covers the amount");
+        vault.debt_vusdt = vault
+            .debt_vusdt
+            .checked_sub(actual_repay)
+            .expect("repayment is capped at the debt");
         let new_debt = vault.debt_vusdt;
 
         self.insert(
@@ -166,325 +504,582 @@ impl<'a, S: State> Layer<'a, S> {
         );
         self.insert(Key::Vault(public.clone()), Value::Vault(vault));
 
-        vec![Event::VusdtRepaid {
+        events.push(Event::VusdtRepaid {
             player: public.clone(),
-            amount: actual_repay,
-            new_debt,
-        }]
+            amount: actual_repay.into(),
+            new_debt: new_debt.into(),
+        });
+        events
     }
 
     pub(in crate::layer) async fn handle_swap(
         &mut self,
         public: &PublicKey,
-        mut amount_in: u64,
+        pair: PairId,
+        amount_in: u64,
         min_amount_out: u64,
         is_buying_rng: bool,
     ) -> Vec<Event> {
-        let original_amount_in = amount_in;
-        let mut amm = self.get_or_init_amm().await;
+        let amm = self.get_or_init_amm(pair).await;
         let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
             Some(Value::CasinoPlayer(p)) => p,
-            _ => return vec![],
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::SWAP,
+                    nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                )
+            }
         };
 
         if amount_in == 0 {
-            return vec![];
+            return Self::instruction_failed(
+                public,
+                tags::instruction::SWAP,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
         }
 
-        if amm.reserve_rng == 0 || amm.reserve_vusdt == 0 {
-            return vec![Event::CasinoError {
-                player: public.clone(),
-                session_id: None,
-                error_code: nullspace_types::casino::ERROR_INVALID_MOVE,
-                message: "AMM has zero liquidity".to_string(),
-            }];
+        if amm.reserve_rng.is_zero() || amm.reserve_vusdt.is_zero() {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::SWAP,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
         }
 
-        // Apply Sell Tax (if Selling RNG)
-        let mut burned_amount = 0;
-        if !is_buying_rng {
-            // Sell Tax: 5% (default)
-            burned_amount = (amount_in as u128 * amm.sell_tax_basis_points as u128 / 10000) as u64;
-            if burned_amount > 0 {
-                // Deduct tax from input amount
-                amount_in = amount_in.saturating_sub(burned_amount);
-
-                // Track burned amount in House
-                let mut house = self.get_or_init_house().await;
-                house.total_burned += burned_amount;
-                self.insert(Key::House, Value::House(house));
-            }
-        }
+        let Some((quote, after)) = quote_swap(&amm, amount_in, is_buying_rng).and_then(|quote| {
+            let after = quote.apply(&amm, is_buying_rng)?;
+            Some((quote, after))
+        }) else {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::SWAP,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
+        };
 
-        // Reserves (u128 for safety)
-        let (reserve_in, reserve_out) = if is_buying_rng {
-            (amm.reserve_vusdt as u128, amm.reserve_rng as u128)
-        } else {
-            (amm.reserve_rng as u128, amm.reserve_vusdt as u128)
-        };
-
-        // Fee (30 bps = 0.3%)
-        let fee_bps = amm.fee_basis_points as u128;
-        let fee_amount = ((amount_in as u128) * fee_bps) / 10_000;
-        let net_in = (amount_in as u128).saturating_sub(fee_amount);
-        let amount_in_with_fee = net_in * 10_000;
-        let numerator = amount_in_with_fee.saturating_mul(reserve_out);
-        let denominator = reserve_in
-            .saturating_mul(10_000)
-            .saturating_add(amount_in_with_fee);
-        if denominator == 0 {
-            return vec![Event::CasinoError {
-                player: public.clone(),
-                session_id: None,
-                error_code: nullspace_types::casino::ERROR_INVALID_MOVE,
-                message: "Invalid AMM state".to_string(),
-            }];
+        if quote.amount_out < min_amount_out {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::SWAP,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
         }
-        let amount_out = (numerator / denominator) as u64;
-
-        if amount_out < min_amount_out {
-            return vec![Event::CasinoError {
-                player: public.clone(),
-                session_id: None,
-                error_code: nullspace_types::casino::ERROR_INVALID_MOVE, // Slippage
-                message: "Slippage limit exceeded".to_string(),
-            }];
+
+        // Reject trades that move the price more than the pool allows
+        if amm.max_price_impact_bps > 0 {
+            let within_limit = amm
+                .price_impact_bps(&after)
+                .is_some_and(|impact| impact <= amm.max_price_impact_bps as u128);
+            if !within_limit {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::SWAP,
+                    nullspace_types::casino::CasinoErrorCode::InvalidMove,
+                );
+            }
         }
 
         // Execute Swap
         if is_buying_rng {
             // Player gives vUSDT, gets RNG
-            if player.vusdt_balance < amount_in {
-                return vec![Event::CasinoError {
-                    player: public.clone(),
-                    session_id: None,
-                    error_code: nullspace_types::casino::ERROR_INSUFFICIENT_FUNDS,
-                    message: "Insufficient vUSDT".to_string(),
-                }];
-            }
-            player.vusdt_balance -= amount_in;
-            player.chips = player.chips.saturating_add(amount_out);
-
-            amm.reserve_vusdt = amm.reserve_vusdt.saturating_add(amount_in);
-            amm.reserve_rng = amm.reserve_rng.saturating_sub(amount_out);
+            let Some(balance) = player.vusdt_balance.checked_sub(Vusdt(amount_in)) else {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::SWAP,
+                    nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+                );
+            };
+            let Some(chips) = player.chips.checked_add(Chips(quote.amount_out)) else {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::SWAP,
+                    nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+                );
+            };
+            player.vusdt_balance = balance;
+            player.chips = chips;
         } else {
             // Player gives RNG, gets vUSDT
             // Note: We deduct the FULL amount (incl tax) from player
-            let total_deduction = amount_in + burned_amount;
-            if player.chips < total_deduction {
-                return vec![Event::CasinoError {
-                    player: public.clone(),
-                    session_id: None,
-                    error_code: nullspace_types::casino::ERROR_INSUFFICIENT_FUNDS,
-                    message: "Insufficient RNG".to_string(),
-                }];
-            }
-
-            player.chips = player.chips.saturating_sub(total_deduction);
-            player.vusdt_balance = player.vusdt_balance.saturating_add(amount_out);
-
-            amm.reserve_rng = amm.reserve_rng.saturating_add(amount_in); // Add net amount (after tax) to reserves
-            amm.reserve_vusdt = amm.reserve_vusdt.saturating_sub(amount_out);
-        }
-
-        // Book fee to House
-        if fee_amount > 0 {
-            let mut house = self.get_or_init_house().await;
-            house.accumulated_fees = house.accumulated_fees.saturating_add(fee_amount as u64);
-            self.insert(Key::House, Value::House(house));
+            let Some(chips) = player.chips.checked_sub(Chips(amount_in)) else {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::SWAP,
+                    nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+                );
+            };
+            let Some(balance) = player.vusdt_balance.checked_add(Vusdt(quote.amount_out)) else {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::SWAP,
+                    nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+                );
+            };
+            player.chips = chips;
+            player.vusdt_balance = balance;
         }
+        let amm = after;
+        self.book_swap(&quote).await;
 
         let event = Event::AmmSwapped {
             player: public.clone(),
             is_buying_rng,
-            amount_in: original_amount_in,
-            amount_out,
-            fee_amount: fee_amount as u64,
-            burned_amount,
-            reserve_rng: amm.reserve_rng,
-            reserve_vusdt: amm.reserve_vusdt,
+            amount_in,
+            amount_out: quote.amount_out,
+            fee_amount: quote.fee_amount,
+            burned_amount: quote.burned_amount,
+            reserve_rng: amm.reserve_rng.into(),
+            reserve_vusdt: amm.reserve_vusdt.into(),
         };
 
         self.insert(
             Key::CasinoPlayer(public.clone()),
             Value::CasinoPlayer(player),
         );
-        self.insert(Key::AmmPool, Value::AmmPool(amm));
+        self.put_amm(pair, amm).await;
 
-        vec![event]
+        // The new price may satisfy open limit orders (which trade against the RNG/vUSDT pool)
+        let mut events = vec![event];
+        if pair == PairId::RNG_VUSDT {
+            events.extend(self.fill_limit_orders().await);
+        }
+        events
+    }
+
+    pub(in crate::layer) async fn handle_execute_buyback(
+        &mut self,
+        public: &PublicKey,
+        amount: u64,
+    ) -> Vec<Event> {
+        let kind = tags::instruction::EXECUTE_BUYBACK;
+        let mut treasury = self.get_treasury().await;
+        if treasury.admin.as_ref() != Some(public) {
+            return Self::instruction_failed(
+                public,
+                kind,
+                nullspace_types::casino::CasinoErrorCode::Unauthorized,
+            );
+        }
+        if amount == 0 {
+            return Self::instruction_failed(
+                public,
+                kind,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
+        }
+        let Some(balance) = treasury.vusdt_balance.checked_sub(Vusdt(amount)) else {
+            return Self::instruction_failed(
+                public,
+                kind,
+                nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+            );
+        };
+
+        // Buy RNG through the pool like any other trader (within the pool's price impact limit)
+        let amm = self.get_or_init_amm(PairId::RNG_VUSDT).await;
+        let Some((quote, after)) = quote_swap(&amm, amount, true).and_then(|quote| {
+            let after = quote.apply(&amm, true)?;
+            Some((quote, after))
+        }) else {
+            return Self::instruction_failed(
+                public,
+                kind,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
+        };
+        if amm.max_price_impact_bps > 0
+            && !amm
+                .price_impact_bps(&after)
+                .is_some_and(|impact| impact <= amm.max_price_impact_bps as u128)
+        {
+            return Self::instruction_failed(
+                public,
+                kind,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
+        }
+        let amm = after;
+        self.book_swap(&quote).await;
+
+        // Burn everything bought
+        treasury.vusdt_balance = balance;
+        treasury.total_bought_back = treasury
+            .total_bought_back
+            .saturating_add(Chips(quote.amount_out));
+        self.insert(Key::Treasury, Value::Treasury(treasury));
+        let mut house = self.get_or_init_house().await;
+        house.total_burned = house.total_burned.saturating_add(quote.amount_out);
+        self.insert(Key::House, Value::House(house));
+
+        let event = Event::BuybackExecuted {
+            amount,
+            burned: quote.amount_out,
+            balance: balance.into(),
+            reserve_rng: amm.reserve_rng.into(),
+            reserve_vusdt: amm.reserve_vusdt.into(),
+        };
+        self.put_amm(PairId::RNG_VUSDT, amm).await;
+
+        // The new price may satisfy open limit orders
+        let mut events = vec![event];
+        events.extend(self.fill_limit_orders().await);
+        events
+    }
+
+    /// Track the burn (sell tax) and fee of an executed swap in the house.
+    pub(super) async fn book_swap(&mut self, quote: &SwapQuote) {
+        if quote.burned_amount == 0 && quote.fee_amount == 0 {
+            return;
+        }
+        let mut house = self.get_or_init_house().await;
+        house.total_burned = house.total_burned.saturating_add(quote.burned_amount);
+        house.accumulated_fees = house.accumulated_fees.saturating_add(quote.fee_amount);
+        self.insert(Key::House, Value::House(house));
     }
 
     pub(in crate::layer) async fn handle_add_liquidity(
         &mut self,
         public: &PublicKey,
+        pair: PairId,
         rng_amount: u64,
         usdt_amount: u64,
     ) -> Vec<Event> {
-        let mut amm = self.get_or_init_amm().await;
+        let mut amm = self.get_or_init_amm(pair).await;
         let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
             Some(Value::CasinoPlayer(p)) => p,
-            _ => return vec![],
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::ADD_LIQUIDITY,
+                    nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                )
+            }
         };
 
         if rng_amount == 0 || usdt_amount == 0 {
-            return vec![Event::CasinoError {
-                player: public.clone(),
-                session_id: None,
-                error_code: nullspace_types::casino::ERROR_INVALID_MOVE,
-                message: "Zero liquidity not allowed".to_string(),
-            }];
+            return Self::instruction_failed(
+                public,
+                tags::instruction::ADD_LIQUIDITY,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
         }
 
-        if player.chips < rng_amount || player.vusdt_balance < usdt_amount {
-            return vec![Event::CasinoError {
-                player: public.clone(),
-                session_id: None,
-                error_code: nullspace_types::casino::ERROR_INSUFFICIENT_FUNDS,
-                message: "Insufficient funds".to_string(),
-            }];
+        let (rng_amount, usdt_amount) = (Chips(rng_amount), Vusdt(usdt_amount));
+        let (Some(chips), Some(vusdt_balance)) = (
+            player.chips.checked_sub(rng_amount),
+            player.vusdt_balance.checked_sub(usdt_amount),
+        ) else {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::ADD_LIQUIDITY,
+                nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+            );
+        };
+
+        // The first deposit into a pair creates its pool (if the registry has room)
+        if amm.total_shares.is_zero() && !self.amm_pool_available(pair).await {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::ADD_LIQUIDITY,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
         }
 
-        let lp_balance = self.get_lp_balance(public).await;
+        let lp_balance = self.get_lp_balance(pair, public).await;
 
         // Initial liquidity?
-        let mut shares_minted = if amm.total_shares == 0 {
+        let mut shares_minted = if amm.total_shares.is_zero() {
             // Sqrt(x*y)
-            let val = (rng_amount as u128) * (usdt_amount as u128);
-            Self::integer_sqrt(val)
+            let val = (rng_amount.0 as u128) * (usdt_amount.0 as u128);
+            LpShares(Self::integer_sqrt(val))
         } else {
             // Proportional to current reserves
-            if amm.reserve_rng == 0 || amm.reserve_vusdt == 0 {
-                return vec![Event::CasinoError {
-                    player: public.clone(),
-                    session_id: None,
-                    error_code: nullspace_types::casino::ERROR_INVALID_MOVE,
-                    message: "AMM has zero liquidity".to_string(),
-                }];
+            if amm.reserve_rng.is_zero() || amm.reserve_vusdt.is_zero() {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::ADD_LIQUIDITY,
+                    nullspace_types::casino::CasinoErrorCode::InvalidMove,
+                );
             }
-            let share_a = (rng_amount as u128 * amm.total_shares as u128) / amm.reserve_rng as u128;
-            let share_b =
-                (usdt_amount as u128 * amm.total_shares as u128) / amm.reserve_vusdt as u128;
-            share_a.min(share_b) as u64
+            let (share_a, share_b) = proportional_shares(&amm, rng_amount, usdt_amount);
+            LpShares(share_a.min(share_b) as u64)
         };
 
         // Lock a minimum amount of LP shares on first deposit so reserves can never be fully drained.
-        if amm.total_shares == 0 {
-            if shares_minted <= MINIMUM_LIQUIDITY {
-                return vec![Event::CasinoError {
-                    player: public.clone(),
-                    session_id: None,
-                    error_code: nullspace_types::casino::ERROR_INVALID_MOVE,
-                    message: "Initial liquidity too small".to_string(),
-                }];
-            }
-            amm.total_shares = amm.total_shares.saturating_add(MINIMUM_LIQUIDITY);
-            shares_minted = shares_minted.saturating_sub(MINIMUM_LIQUIDITY);
+        if amm.total_shares.is_zero() {
+            let Some(shares) = shares_minted
+                .checked_sub(self.economy.minimum_liquidity)
+                .filter(|shares| !shares.is_zero())
+            else {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::ADD_LIQUIDITY,
+                    nullspace_types::casino::CasinoErrorCode::InvalidMove,
+                );
+            };
+            amm.total_shares = self.economy.minimum_liquidity;
+            shares_minted = shares;
         }
 
-        if shares_minted == 0 {
-            return vec![Event::CasinoError {
-                player: public.clone(),
-                session_id: None,
-                error_code: nullspace_types::casino::ERROR_INVALID_MOVE,
-                message: "Deposit too small".to_string(),
-            }];
+        if shares_minted.is_zero() {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::ADD_LIQUIDITY,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
         }
 
-        player.chips = player.chips.saturating_sub(rng_amount);
-        player.vusdt_balance = player.vusdt_balance.saturating_sub(usdt_amount);
-
-        amm.reserve_rng = amm.reserve_rng.saturating_add(rng_amount);
-        amm.reserve_vusdt = amm.reserve_vusdt.saturating_add(usdt_amount);
-        amm.total_shares = amm.total_shares.saturating_add(shares_minted);
-
-        let new_lp_balance = lp_balance.saturating_add(shares_minted);
+        let (Some(amm), Some(new_lp_balance)) = (
+            deposit(&amm, rng_amount, usdt_amount, shares_minted),
+            lp_balance.checked_add(shares_minted),
+        ) else {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::ADD_LIQUIDITY,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
+        };
+        player.chips = chips;
+        player.vusdt_balance = vusdt_balance;
 
         let event = Event::LiquidityAdded {
             player: public.clone(),
-            rng_amount,
-            vusdt_amount: usdt_amount,
-            shares_minted,
-            total_shares: amm.total_shares,
-            reserve_rng: amm.reserve_rng,
-            reserve_vusdt: amm.reserve_vusdt,
-            lp_balance: new_lp_balance,
+            rng_amount: rng_amount.into(),
+            vusdt_amount: usdt_amount.into(),
+            shares_minted: shares_minted.into(),
+            total_shares: amm.total_shares.into(),
+            reserve_rng: amm.reserve_rng.into(),
+            reserve_vusdt: amm.reserve_vusdt.into(),
+            lp_balance: new_lp_balance.into(),
         };
 
         self.insert(
             Key::CasinoPlayer(public.clone()),
             Value::CasinoPlayer(player),
         );
-        self.insert(Key::AmmPool, Value::AmmPool(amm));
+        self.put_amm(pair, amm).await;
         self.insert(
-            Key::LpBalance(public.clone()),
+            Key::LpBalance(pair, public.clone()),
             Value::LpBalance(new_lp_balance),
         );
 
         vec![event]
     }
 
+    pub(in crate::layer) async fn handle_zap_liquidity(
+        &mut self,
+        public: &PublicKey,
+        pair: PairId,
+        amount_in: u64,
+        min_shares: u64,
+        from_rng: bool,
+    ) -> Vec<Event> {
+        let mut amm = self.get_or_init_amm(pair).await;
+        let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
+            Some(Value::CasinoPlayer(p)) => p,
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::ZAP_LIQUIDITY,
+                    nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                )
+            }
+        };
+
+        if amount_in == 0 {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::ZAP_LIQUIDITY,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
+        }
+
+        // Balance of the asset deposited
+        let balance: u64 = if from_rng {
+            player.chips.into()
+        } else {
+            player.vusdt_balance.into()
+        };
+        let Some(remaining) = balance.checked_sub(amount_in) else {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::ZAP_LIQUIDITY,
+                nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+            );
+        };
+
+        // The pool's ratio determines the split (so it must already have liquidity)
+        if amm.total_shares.is_zero() || amm.reserve_rng.is_zero() || amm.reserve_vusdt.is_zero() {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::ZAP_LIQUIDITY,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
+        }
+
+        let Some(zap) = plan_zap(&amm, amount_in, from_rng).filter(|zap| !zap.shares.is_zero())
+        else {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::ZAP_LIQUIDITY,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
+        };
+        if zap.shares < LpShares(min_shares) {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::ZAP_LIQUIDITY,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
+        }
+
+        // Swap leg (planned), then mint leg
+        let (Some(amm), Some(lp_balance)) = (
+            deposit(&zap.pool, zap.rng_amount, zap.vusdt_amount, zap.shares),
+            self.get_lp_balance(pair, public)
+                .await
+                .checked_add(zap.shares),
+        ) else {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::ZAP_LIQUIDITY,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
+        };
+        self.book_swap(&zap.quote).await;
+        if from_rng {
+            player.chips = Chips(remaining);
+        } else {
+            player.vusdt_balance = Vusdt(remaining);
+        }
+
+        let event = Event::LiquidityZapped {
+            player: public.clone(),
+            from_rng,
+            amount_in,
+            swap_amount_in: zap.swap_amount_in,
+            swap_amount_out: zap.quote.amount_out,
+            fee_amount: zap.quote.fee_amount,
+            burned_amount: zap.quote.burned_amount,
+            rng_amount: zap.rng_amount.into(),
+            vusdt_amount: zap.vusdt_amount.into(),
+            shares_minted: zap.shares.into(),
+            total_shares: amm.total_shares.into(),
+            reserve_rng: amm.reserve_rng.into(),
+            reserve_vusdt: amm.reserve_vusdt.into(),
+            lp_balance: lp_balance.into(),
+        };
+
+        self.insert(
+            Key::CasinoPlayer(public.clone()),
+            Value::CasinoPlayer(player),
+        );
+        self.put_amm(pair, amm).await;
+        self.insert(
+            Key::LpBalance(pair, public.clone()),
+            Value::LpBalance(lp_balance),
+        );
+
+        // The swap leg moves the price (which may satisfy open limit orders on RNG/vUSDT)
+        let mut events = vec![event];
+        if pair == PairId::RNG_VUSDT {
+            events.extend(self.fill_limit_orders().await);
+        }
+        events
+    }
+
     pub(in crate::layer) async fn handle_remove_liquidity(
         &mut self,
         public: &PublicKey,
+        pair: PairId,
         shares: u64,
     ) -> Vec<Event> {
         if shares == 0 {
-            return vec![];
+            return Self::instruction_failed(
+                public,
+                tags::instruction::REMOVE_LIQUIDITY,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
         }
 
-        let mut amm = self.get_or_init_amm().await;
-        if amm.total_shares == 0 || shares > amm.total_shares {
-            return vec![];
+        let shares = LpShares(shares);
+        let mut amm = self.get_or_init_amm(pair).await;
+        if amm.total_shares.is_zero() || shares > amm.total_shares {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::REMOVE_LIQUIDITY,
+                nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+            );
         }
 
-        let lp_balance = self.get_lp_balance(public).await;
-        if shares > lp_balance {
-            return vec![Event::CasinoError {
-                player: public.clone(),
-                session_id: None,
-                error_code: nullspace_types::casino::ERROR_INSUFFICIENT_FUNDS,
-                message: "Not enough LP shares".to_string(),
-            }];
-        }
+        let Some(new_lp_balance) = self.get_lp_balance(pair, public).await.checked_sub(shares)
+        else {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::REMOVE_LIQUIDITY,
+                nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+            );
+        };
 
         let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
             Some(Value::CasinoPlayer(p)) => p,
-            _ => return vec![],
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::REMOVE_LIQUIDITY,
+                    nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                )
+            }
         };
 
-        // Calculate amounts out proportionally
-        let amount_rng =
-            ((shares as u128 * amm.reserve_rng as u128) / amm.total_shares as u128) as u64;
-        let amount_vusd =
-            ((shares as u128 * amm.reserve_vusdt as u128) / amm.total_shares as u128) as u64;
-
-        amm.reserve_rng = amm.reserve_rng.saturating_sub(amount_rng);
-        amm.reserve_vusdt = amm.reserve_vusdt.saturating_sub(amount_vusd);
-        amm.total_shares = amm.total_shares.saturating_sub(shares);
-
-        player.chips = player.chips.saturating_add(amount_rng);
-        player.vusdt_balance = player.vusdt_balance.saturating_add(amount_vusd);
-
-        let new_lp_balance = lp_balance.saturating_sub(shares);
+        // Calculate amounts out proportionally (shares never redeem more than the reserves)
+        let (amount_rng, amount_vusd) =
+            redeem(&amm, shares).expect("shares are within the total outstanding");
+        let (Some(chips), Some(vusdt_balance)) = (
+            player.chips.checked_add(amount_rng),
+            player.vusdt_balance.checked_add(amount_vusd),
+        ) else {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::REMOVE_LIQUIDITY,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
+        };
+        amm.reserve_rng = amm
+            .reserve_rng
+            .checked_sub(amount_rng)
+            .expect("redeemed at most the reserves");
+        amm.reserve_vusdt = amm
+            .reserve_vusdt
+            .checked_sub(amount_vusd)
+            .expect("redeemed at most the reserves");
+        amm.total_shares = amm
+            .total_shares
+            .checked_sub(shares)
+            .expect("shares are within the total outstanding");
+        player.chips = chips;
+        player.vusdt_balance = vusdt_balance;
 
         let event = Event::LiquidityRemoved {
             player: public.clone(),
-            rng_amount: amount_rng,
-            vusdt_amount: amount_vusd,
-            shares_burned: shares,
-            total_shares: amm.total_shares,
-            reserve_rng: amm.reserve_rng,
-            reserve_vusdt: amm.reserve_vusdt,
-            lp_balance: new_lp_balance,
+            rng_amount: amount_rng.into(),
+            vusdt_amount: amount_vusd.into(),
+            shares_burned: shares.into(),
+            total_shares: amm.total_shares.into(),
+            reserve_rng: amm.reserve_rng.into(),
+            reserve_vusdt: amm.reserve_vusdt.into(),
+            lp_balance: new_lp_balance.into(),
         };
 
         self.insert(
             Key::CasinoPlayer(public.clone()),
             Value::CasinoPlayer(player),
         );
-        self.insert(Key::AmmPool, Value::AmmPool(amm));
+        self.put_amm(pair, amm).await;
         self.insert(
-            Key::LpBalance(public.clone()),
+            Key::LpBalance(pair, public.clone()),
             Value::LpBalance(new_lp_balance),
         );
 
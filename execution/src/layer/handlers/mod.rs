@@ -1,3 +1,7 @@
+mod admin;
 mod casino;
 mod liquidity;
+mod orders;
+mod recovery;
 mod staking;
+mod transfers;
@@ -0,0 +1,244 @@
+use super::super::*;
+use super::liquidity::quote_swap;
+use nullspace_types::casino::{Chips, LimitOrder, LimitOrderBook, PairId, Vusdt, PRICE_SCALE};
+
+/// Whether `amount_out` for the input of `order` meets its minimum price.
+fn meets_limit(order: &LimitOrder, amount_out: u64) -> bool {
+    (amount_out as u128) * PRICE_SCALE >= (order.amount_in as u128) * (order.min_price as u128)
+}
+
+impl<'a, S: State> Layer<'a, S> {
+    // === Limit Order Handlers ===
+
+    async fn get_or_init_order_book(&self) -> LimitOrderBook {
+        match self.get(&Key::LimitOrderBook).await {
+            Some(Value::LimitOrderBook(book)) => book,
+            _ => LimitOrderBook::default(),
+        }
+    }
+
+    pub(in crate::layer) async fn handle_place_limit_order(
+        &mut self,
+        public: &PublicKey,
+        amount_in: u64,
+        min_price: u64,
+        is_buying_rng: bool,
+    ) -> Vec<Event> {
+        let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
+            Some(Value::CasinoPlayer(p)) => p,
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::PLACE_LIMIT_ORDER,
+                    nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                )
+            }
+        };
+
+        if amount_in == 0 {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::PLACE_LIMIT_ORDER,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
+        }
+
+        let mut book = self.get_or_init_order_book().await;
+        if book.open.len() >= nullspace_types::casino::MAX_OPEN_LIMIT_ORDERS {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::PLACE_LIMIT_ORDER,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
+        }
+
+        // Escrow the input
+        if is_buying_rng {
+            let Some(balance) = player.vusdt_balance.checked_sub(Vusdt(amount_in)) else {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::PLACE_LIMIT_ORDER,
+                    nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+                );
+            };
+            player.vusdt_balance = balance;
+        } else {
+            let Some(chips) = player.chips.checked_sub(Chips(amount_in)) else {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::PLACE_LIMIT_ORDER,
+                    nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+                );
+            };
+            player.chips = chips;
+        }
+
+        let order_id = book.next_id;
+        book.next_id += 1;
+        book.open.push(order_id);
+        self.insert(
+            Key::CasinoPlayer(public.clone()),
+            Value::CasinoPlayer(player),
+        );
+        self.insert(
+            Key::LimitOrder(order_id),
+            Value::LimitOrder(LimitOrder {
+                owner: public.clone(),
+                amount_in,
+                min_price,
+                is_buying_rng,
+            }),
+        );
+        self.insert(Key::LimitOrderBook, Value::LimitOrderBook(book));
+
+        // The order may already be fillable at the current price
+        let mut events = vec![Event::LimitOrderPlaced {
+            order_id,
+            player: public.clone(),
+            amount_in,
+            min_price,
+            is_buying_rng,
+        }];
+        events.extend(self.fill_limit_orders().await);
+        events
+    }
+
+    pub(in crate::layer) async fn handle_cancel_limit_order(
+        &mut self,
+        public: &PublicKey,
+        order_id: u64,
+    ) -> Vec<Event> {
+        let order = match self.get(&Key::LimitOrder(order_id)).await {
+            Some(Value::LimitOrder(order)) if order.owner == *public => order,
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::CANCEL_LIMIT_ORDER,
+                    nullspace_types::casino::CasinoErrorCode::NotFound,
+                )
+            }
+        };
+        let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
+            Some(Value::CasinoPlayer(p)) => p,
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::CANCEL_LIMIT_ORDER,
+                    nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                )
+            }
+        };
+
+        // Refund the escrow
+        let refunded = if order.is_buying_rng {
+            player
+                .vusdt_balance
+                .checked_add(Vusdt(order.amount_in))
+                .map(|balance| player.vusdt_balance = balance)
+        } else {
+            player
+                .chips
+                .checked_add(Chips(order.amount_in))
+                .map(|chips| player.chips = chips)
+        };
+        if refunded.is_none() {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::CANCEL_LIMIT_ORDER,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
+        }
+        let mut book = self.get_or_init_order_book().await;
+        book.open.retain(|id| *id != order_id);
+
+        self.insert(
+            Key::CasinoPlayer(public.clone()),
+            Value::CasinoPlayer(player),
+        );
+        self.insert(Key::LimitOrderBook, Value::LimitOrderBook(book));
+        self.delete(&Key::LimitOrder(order_id)).await;
+
+        vec![Event::LimitOrderCancelled {
+            order_id,
+            player: public.clone(),
+            refunded: order.amount_in,
+        }]
+    }
+
+    /// Fill open limit orders (oldest first, in a single pass) whose minimum price is met by
+    /// the pool, swapping their escrowed input and crediting the output to their owner.
+    pub(super) async fn fill_limit_orders(&mut self) -> Vec<Event> {
+        let mut book = self.get_or_init_order_book().await;
+        if book.open.is_empty() {
+            return vec![];
+        }
+
+        let mut amm = self.get_or_init_amm(PairId::RNG_VUSDT).await;
+        let mut events = Vec::new();
+        let mut open = Vec::with_capacity(book.open.len());
+        for order_id in std::mem::take(&mut book.open) {
+            let Some(Value::LimitOrder(order)) = self.get(&Key::LimitOrder(order_id)).await else {
+                continue;
+            };
+            let Some((quote, after)) = quote_swap(&amm, order.amount_in, order.is_buying_rng)
+                .filter(|quote| quote.amount_out > 0 && meets_limit(&order, quote.amount_out))
+                .and_then(|quote| {
+                    let after = quote.apply(&amm, order.is_buying_rng)?;
+                    Some((quote, after))
+                })
+            else {
+                open.push(order_id);
+                continue;
+            };
+            let Some(Value::CasinoPlayer(mut owner)) =
+                self.get(&Key::CasinoPlayer(order.owner.clone())).await
+            else {
+                open.push(order_id);
+                continue;
+            };
+
+            // Swap the escrowed input
+            let credited = if order.is_buying_rng {
+                owner
+                    .chips
+                    .checked_add(Chips(quote.amount_out))
+                    .map(|chips| owner.chips = chips)
+            } else {
+                owner
+                    .vusdt_balance
+                    .checked_add(Vusdt(quote.amount_out))
+                    .map(|balance| owner.vusdt_balance = balance)
+            };
+            if credited.is_none() {
+                open.push(order_id);
+                continue;
+            }
+            amm = after;
+            self.book_swap(&quote).await;
+            self.insert(
+                Key::CasinoPlayer(order.owner.clone()),
+                Value::CasinoPlayer(owner),
+            );
+            self.delete(&Key::LimitOrder(order_id)).await;
+
+            events.push(Event::LimitOrderFilled {
+                order_id,
+                player: order.owner,
+                is_buying_rng: order.is_buying_rng,
+                amount_in: order.amount_in,
+                amount_out: quote.amount_out,
+                fee_amount: quote.fee_amount,
+                burned_amount: quote.burned_amount,
+                reserve_rng: amm.reserve_rng.into(),
+                reserve_vusdt: amm.reserve_vusdt.into(),
+            });
+        }
+
+        if !events.is_empty() {
+            book.open = open;
+            self.insert(Key::LimitOrderBook, Value::LimitOrderBook(book));
+            self.put_amm(PairId::RNG_VUSDT, amm).await;
+        }
+        events
+    }
+}
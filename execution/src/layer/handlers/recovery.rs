@@ -0,0 +1,245 @@
+use super::super::*;
+use nullspace_types::casino::GuardianSet;
+
+impl<'a, S: State> Layer<'a, S> {
+    // === Account Recovery Handlers ===
+
+    pub(in crate::layer) async fn handle_set_guardians(
+        &mut self,
+        public: &PublicKey,
+        guardians: &[PublicKey],
+        threshold: u8,
+    ) -> Vec<Event> {
+        // An empty set disables recovery
+        if guardians.is_empty() && threshold == 0 {
+            self.delete(&Key::Guardians(public.clone())).await;
+            return vec![Event::GuardiansSet {
+                player: public.clone(),
+                guardians: Vec::new(),
+                threshold,
+            }];
+        }
+        if !GuardianSet::is_valid(public, guardians, threshold) {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::SET_GUARDIANS,
+                nullspace_types::casino::CasinoErrorCode::InvalidGuardians,
+            );
+        }
+
+        // Replacing the set cancels any pending recovery
+        self.insert(
+            Key::Guardians(public.clone()),
+            Value::Guardians(GuardianSet {
+                guardians: guardians.to_vec(),
+                threshold,
+                recovery: None,
+            }),
+        );
+
+        vec![Event::GuardiansSet {
+            player: public.clone(),
+            guardians: guardians.to_vec(),
+            threshold,
+        }]
+    }
+
+    pub(in crate::layer) async fn handle_recover_account(
+        &mut self,
+        public: &PublicKey,
+        account: &PublicKey,
+        new_key: &PublicKey,
+    ) -> Vec<Event> {
+        let mut set = match self.get(&Key::Guardians(account.clone())).await {
+            Some(Value::Guardians(set)) => set,
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::RECOVER_ACCOUNT,
+                    nullspace_types::casino::CasinoErrorCode::NotFound,
+                )
+            }
+        };
+        if !set.guardians.contains(public) {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::RECOVER_ACCOUNT,
+                nullspace_types::casino::CasinoErrorCode::Unauthorized,
+            );
+        }
+        if new_key == account || set.guardians.contains(new_key) {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::RECOVER_ACCOUNT,
+                nullspace_types::casino::CasinoErrorCode::InvalidGuardians,
+            );
+        }
+
+        let approved = set.approve(public, new_key, self.seed.view);
+        if set
+            .recovery
+            .as_ref()
+            .is_none_or(|recovery| recovery.new_key != *new_key)
+        {
+            // A recovery to another key reached the threshold
+            return Self::instruction_failed(
+                public,
+                tags::instruction::RECOVER_ACCOUNT,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
+        }
+        if !set.is_ready(self.seed.view) {
+            if !approved {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::RECOVER_ACCOUNT,
+                    nullspace_types::casino::CasinoErrorCode::RateLimited,
+                );
+            }
+            let recovery = set.recovery.as_ref().expect("approved recovery is pending");
+            let event = Event::RecoveryApproved {
+                player: account.clone(),
+                guardian: public.clone(),
+                new_key: new_key.clone(),
+                approvals: recovery.approvals.len() as u8,
+                ready_view: recovery.ready_view,
+            };
+            self.insert(Key::Guardians(account.clone()), Value::Guardians(set));
+            return vec![event];
+        }
+
+        // The challenge window has passed, so move everything the account holds
+        if !self.is_unused(new_key).await {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::RECOVER_ACCOUNT,
+                nullspace_types::casino::CasinoErrorCode::PlayerAlreadyRegistered,
+            );
+        }
+        set.recovery = None;
+        self.delete(&Key::Guardians(account.clone())).await;
+        self.insert(Key::Guardians(new_key.clone()), Value::Guardians(set));
+        self.migrate_account(account, new_key).await;
+
+        vec![Event::AccountRecovered {
+            old_key: account.clone(),
+            new_key: new_key.clone(),
+        }]
+    }
+
+    /// Whether `public` holds no state an account could be moved onto.
+    async fn is_unused(&self, public: &PublicKey) -> bool {
+        let mut keys = vec![
+            Key::CasinoPlayer(public.clone()),
+            Key::Staker(public.clone()),
+            Key::Vault(public.clone()),
+            Key::GameHistory(public.clone()),
+            Key::Guardians(public.clone()),
+            Key::Vesting(public.clone()),
+        ];
+        for pair in self.amm_pairs().await {
+            keys.push(Key::LpBalance(pair, public.clone()));
+        }
+        for key in keys {
+            if self.get(&key).await.is_some() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Move the player, their sessions, stake, vault, liquidity, history, vesting schedules and
+    /// open limit orders from `old_key` to `new_key` (the account's nonce stays with `old_key`,
+    /// and allowances it granted lapse).
+    async fn migrate_account(&mut self, old_key: &PublicKey, new_key: &PublicKey) {
+        if let Some(Value::CasinoPlayer(player)) =
+            self.get(&Key::CasinoPlayer(old_key.clone())).await
+        {
+            for session_id in &player.active_sessions {
+                if let Some(Value::CasinoSession(mut session)) =
+                    self.get(&Key::CasinoSession(*session_id)).await
+                {
+                    session.player = new_key.clone();
+                    self.insert(
+                        Key::CasinoSession(*session_id),
+                        Value::CasinoSession(session),
+                    );
+                }
+            }
+            if let Some(tournament_id) = player.active_tournament {
+                if let Some(Value::Tournament(mut tournament)) =
+                    self.get(&Key::Tournament(tournament_id)).await
+                {
+                    for key in tournament.players.iter_mut() {
+                        if *key == *old_key {
+                            *key = new_key.clone();
+                        }
+                    }
+                    for entry in tournament.leaderboard.entries.iter_mut() {
+                        if entry.player == *old_key {
+                            entry.player = new_key.clone();
+                        }
+                    }
+                    self.insert(
+                        Key::Tournament(tournament_id),
+                        Value::Tournament(tournament),
+                    );
+                }
+            }
+            if let Some(Value::CasinoLeaderboard(mut leaderboard)) =
+                self.get(&Key::CasinoLeaderboard).await
+            {
+                if leaderboard.entries.iter().any(|e| e.player == *old_key) {
+                    leaderboard.entries.retain(|e| e.player != *old_key);
+                    self.insert(
+                        Key::CasinoLeaderboard,
+                        Value::CasinoLeaderboard(leaderboard),
+                    );
+                    self.update_casino_leaderboard(new_key, &player).await;
+                }
+            }
+            self.delete(&Key::CasinoPlayer(old_key.clone())).await;
+            self.insert(
+                Key::CasinoPlayer(new_key.clone()),
+                Value::CasinoPlayer(player),
+            );
+        }
+
+        // Per-account values move as they are
+        let mut moves = vec![
+            (Key::Staker(old_key.clone()), Key::Staker(new_key.clone())),
+            (Key::Vault(old_key.clone()), Key::Vault(new_key.clone())),
+            (
+                Key::GameHistory(old_key.clone()),
+                Key::GameHistory(new_key.clone()),
+            ),
+            (Key::Vesting(old_key.clone()), Key::Vesting(new_key.clone())),
+        ];
+        for pair in self.amm_pairs().await {
+            moves.push((
+                Key::LpBalance(pair, old_key.clone()),
+                Key::LpBalance(pair, new_key.clone()),
+            ));
+        }
+        for (from, to) in moves {
+            if let Some(value) = self.get(&from).await {
+                self.delete(&from).await;
+                self.insert(to, value);
+            }
+        }
+
+        // Open limit orders are filled to (and cancellable by) their owner
+        if let Some(Value::LimitOrderBook(book)) = self.get(&Key::LimitOrderBook).await {
+            for order_id in book.open {
+                if let Some(Value::LimitOrder(mut order)) =
+                    self.get(&Key::LimitOrder(order_id)).await
+                {
+                    if order.owner == *old_key {
+                        order.owner = new_key.clone();
+                        self.insert(Key::LimitOrder(order_id), Value::LimitOrder(order));
+                    }
+                }
+            }
+        }
+    }
+}
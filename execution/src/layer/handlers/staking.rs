@@ -1,4 +1,5 @@
 use super::super::*;
+use commonware_cryptography::bls12381::primitives::ops;
 
 impl<'a, S: State> Layer<'a, S> {
     // === Staking Handlers ===
@@ -11,97 +12,135 @@ impl<'a, S: State> Layer<'a, S> {
     ) -> Vec<Event> {
         let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
             Some(Value::CasinoPlayer(p)) => p,
-            _ => return vec![], // Error handled by checking balance
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::STAKE,
+                    nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                )
+            }
         };
 
-        if player.chips < amount {
-            return vec![Event::CasinoError {
-                player: public.clone(),
-                session_id: None,
-                error_code: nullspace_types::casino::ERROR_INSUFFICIENT_FUNDS,
-                message: "Insufficient chips to stake".to_string(),
-            }];
+        if amount == 0 {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::STAKE,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
         }
 
+        let Some(chips) = player
+            .chips
+            .checked_sub(nullspace_types::casino::Chips(amount))
+        else {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::STAKE,
+                nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+            );
+        };
+
         // Min duration 1 week (approx 201600 blocks @ 3s), Max 4 years
         const MIN_DURATION: u64 = 1; // Simplified for dev
         if duration < MIN_DURATION {
-            return vec![Event::CasinoError {
-                player: public.clone(),
-                session_id: None,
-                error_code: nullspace_types::casino::ERROR_INVALID_BET, // Reuse code
-                message: "Duration too short".to_string(),
-            }];
+            return Self::instruction_failed(
+                public,
+                tags::instruction::STAKE,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
         }
 
-        // Deduct chips
-        player.chips -= amount;
-        self.insert(
-            Key::CasinoPlayer(public.clone()),
-            Value::CasinoPlayer(player),
-        );
-
-        // Create/Update Staker
+        // Create/Update Staker (accruing the rewards earned before its voting power changes)
+        let mut house = self.get_or_init_house().await;
         let mut staker = match self.get(&Key::Staker(public.clone())).await {
             Some(Value::Staker(s)) => s,
             _ => nullspace_types::casino::Staker::default(),
         };
+        staker.accrue(house.reward_index);
 
-        // Calculate Voting Power: Amount * Duration
-        // If adding to existing stake, we weight-average or just add?
-        // Simple model: New stake resets lockup to max(old_unlock, new_unlock)
-        let current_block = self.seed.view;
-        let new_unlock = current_block + duration;
-
-        // If extending, new VP is total amount * new duration remaining
-        staker.balance += amount;
-        staker.unlock_ts = new_unlock;
-        staker.voting_power = (staker.balance as u128) * (duration as u128);
-
+        // Each stake is a separate tranche (with its own lockup) worth Amount * Duration of
+        // voting power
+        let new_unlock = self.seed.view + duration;
+        let voting_power = (amount as u128) * (duration as u128);
+        if !staker.stake(
+            nullspace_types::casino::Chips(amount),
+            new_unlock,
+            voting_power,
+        ) {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::STAKE,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
+        }
         self.insert(Key::Staker(public.clone()), Value::Staker(staker.clone()));
 
+        // Deduct chips
+        player.chips = chips;
+        self.insert(
+            Key::CasinoPlayer(public.clone()),
+            Value::CasinoPlayer(player),
+        );
+
         // Update House Total VP
-        let mut house = self.get_or_init_house().await;
         house.total_staked_amount += amount;
-        house.total_voting_power += (amount as u128) * (duration as u128); // Approximation for new stake
+        house.total_voting_power += voting_power;
         self.insert(Key::House, Value::House(house));
 
         vec![Event::Staked {
             player: public.clone(),
             amount,
             duration,
-            new_balance: staker.balance,
-            unlock_ts: staker.unlock_ts,
+            new_balance: staker.balance.into(),
+            unlock_ts: new_unlock,
             voting_power: staker.voting_power,
         }]
     }
 
-    pub(in crate::layer) async fn handle_unstake(&mut self, public: &PublicKey) -> Vec<Event> {
+    pub(in crate::layer) async fn handle_unstake(
+        &mut self,
+        public: &PublicKey,
+        amount: u64,
+    ) -> Vec<Event> {
         let mut staker = match self.get(&Key::Staker(public.clone())).await {
             Some(Value::Staker(s)) => s,
-            _ => return vec![],
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::UNSTAKE,
+                    nullspace_types::casino::CasinoErrorCode::NotFound,
+                )
+            }
         };
 
-        if self.seed.view < staker.unlock_ts {
-            return vec![Event::CasinoError {
-                player: public.clone(),
-                session_id: None,
-                error_code: nullspace_types::casino::ERROR_INVALID_MOVE,
-                message: "Stake still locked".to_string(),
-            }];
-        }
-
-        if staker.balance == 0 {
-            return vec![];
+        if amount == 0 {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::UNSTAKE,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
         }
 
-        let unstake_amount = staker.balance;
+        // Only tranches whose lockup has ended can be withdrawn
+        let mut house = self.get_or_init_house().await;
+        staker.accrue(house.reward_index);
+        let Some(voting_power_removed) =
+            staker.unstake(nullspace_types::casino::Chips(amount), self.seed.view)
+        else {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::UNSTAKE,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
+        };
 
-        // Return chips
+        // Return chips (the stake was taken from the player, so the credit can't be rejected)
         if let Some(Value::CasinoPlayer(mut player)) =
             self.get(&Key::CasinoPlayer(public.clone())).await
         {
-            player.chips += staker.balance;
+            player.chips = player
+                .chips
+                .saturating_add(nullspace_types::casino::Chips(amount));
             self.insert(
                 Key::CasinoPlayer(public.clone()),
                 Value::CasinoPlayer(player),
@@ -109,19 +148,86 @@ impl<'a, S: State> Layer<'a, S> {
         }
 
         // Update House
-        let mut house = self.get_or_init_house().await;
-        house.total_staked_amount = house.total_staked_amount.saturating_sub(staker.balance);
-        house.total_voting_power = house.total_voting_power.saturating_sub(staker.voting_power);
+        house.total_staked_amount = house.total_staked_amount.saturating_sub(amount);
+        house.total_voting_power = house
+            .total_voting_power
+            .saturating_sub(voting_power_removed);
         self.insert(Key::House, Value::House(house));
 
-        // Clear Staker
-        staker.balance = 0;
-        staker.voting_power = 0;
         self.insert(Key::Staker(public.clone()), Value::Staker(staker));
 
         vec![Event::Unstaked {
             player: public.clone(),
-            amount: unstake_amount,
+            amount,
+        }]
+    }
+
+    pub(in crate::layer) async fn handle_slash(
+        &mut self,
+        public: &PublicKey,
+        staker_key: &PublicKey,
+        bps: u16,
+        signature: &nullspace_types::Signature,
+    ) -> Vec<Event> {
+        let mut staker = match self.get(&Key::Staker(staker_key.clone())).await {
+            Some(Value::Staker(s)) => s,
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::SLASH,
+                    nullspace_types::casino::CasinoErrorCode::NotFound,
+                )
+            }
+        };
+
+        if bps == 0 || bps > 10_000 {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::SLASH,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
+        }
+
+        // Evidence must be signed by the validator set (for the staker's next slash)
+        let message =
+            nullspace_types::execution::slash_message(staker_key, bps, staker.slash_count);
+        if ops::verify_message::<MinSig>(
+            &self.master,
+            Some(&self.slash_namespace),
+            &message,
+            signature,
+        )
+        .is_err()
+        {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::SLASH,
+                nullspace_types::casino::CasinoErrorCode::InvalidMove,
+            );
+        }
+
+        // Voting power shrinks in proportion to the balance (of each stake)
+        let mut house = self.get_or_init_house().await;
+        staker.accrue(house.reward_index);
+        let (amount, voting_power_removed) = staker.slash(bps);
+        let (new_balance, voting_power) = (staker.balance, staker.voting_power);
+        staker.slash_count += 1;
+        self.insert(Key::Staker(staker_key.clone()), Value::Staker(staker));
+
+        // Burn the slashed stake
+        house.total_staked_amount = house.total_staked_amount.saturating_sub(amount.0);
+        house.total_voting_power = house
+            .total_voting_power
+            .saturating_sub(voting_power_removed);
+        house.total_burned = house.total_burned.saturating_add(amount.0);
+        self.insert(Key::House, Value::House(house));
+
+        vec![Event::Slashed {
+            player: staker_key.clone(),
+            bps,
+            amount: amount.into(),
+            new_balance: new_balance.into(),
+            voting_power,
         }]
     }
 
@@ -129,20 +235,48 @@ impl<'a, S: State> Layer<'a, S> {
         &mut self,
         public: &PublicKey,
     ) -> Vec<Event> {
-        // Placeholder for distribution logic
-        // In this MVP, rewards are auto-compounded or we just skip this for now
-        let staker = match self.get(&Key::Staker(public.clone())).await {
+        let mut staker = match self.get(&Key::Staker(public.clone())).await {
             Some(Value::Staker(s)) => s,
-            _ => return vec![],
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    tags::instruction::CLAIM_REWARDS,
+                    nullspace_types::casino::CasinoErrorCode::NotFound,
+                )
+            }
         };
 
-        if staker.balance == 0 {
-            return vec![];
+        let mut house = self.get_or_init_house().await;
+        staker.accrue(house.reward_index);
+        if staker.balance.is_zero() && staker.pending_rewards.is_zero() {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::CLAIM_REWARDS,
+                nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+            );
+        }
+
+        // Rewards are minted as they are claimed
+        let amount = std::mem::take(&mut staker.pending_rewards);
+        staker.last_claim_epoch = house.current_epoch;
+        self.insert(Key::Staker(public.clone()), Value::Staker(staker));
+        if !amount.is_zero() {
+            if let Some(Value::CasinoPlayer(mut player)) =
+                self.get(&Key::CasinoPlayer(public.clone())).await
+            {
+                player.chips = player.chips.saturating_add(amount);
+                self.insert(
+                    Key::CasinoPlayer(public.clone()),
+                    Value::CasinoPlayer(player),
+                );
+            }
+            house.total_issuance = house.total_issuance.saturating_add(amount.0);
+            self.insert(Key::House, Value::House(house));
         }
 
         vec![Event::RewardsClaimed {
             player: public.clone(),
-            amount: 0,
+            amount: amount.into(),
         }]
     }
 
@@ -152,32 +286,137 @@ impl<'a, S: State> Layer<'a, S> {
     ) -> Vec<Event> {
         let mut house = self.get_or_init_house().await;
 
-        // 1 Week Epoch (approx)
-        const EPOCH_LENGTH: u64 = 100; // Short for testing
-
-        if self.seed.view >= house.epoch_start_ts + EPOCH_LENGTH {
+        if self.seed.view >= house.epoch_start_ts + nullspace_types::casino::EPOCH_LENGTH {
             // End Epoch
+            let mut events = Vec::new();
 
-            // If Net PnL > 0, Surplus!
-            if house.net_pnl > 0 {
-                // In a real system, we'd snapshot this into a "RewardPool"
-                // For now, we just reset PnL and log it (via debug/warn or event)
-                // warn!("Epoch Surplus: {}", house.net_pnl);
-            } else {
-                // Deficit. Minting happened. Inflation.
-                // warn!("Epoch Deficit: {}", house.net_pnl);
+            // A share of any surplus is paid into the treasury (valued in vUSDT at the oracle
+            // price); a deficit was already covered by minting (and funding that would overflow
+            // the treasury is skipped)
+            let mut treasury = self.get_treasury().await;
+            let share = nullspace_types::casino::Chips(treasury.funding(house.net_pnl));
+            let funded = share
+                .to_vusdt(self.collateral_price().await)
+                .filter(|funded| !funded.is_zero());
+            if let Some((funded, balance)) = funded.and_then(|funded| {
+                let balance = treasury.vusdt_balance.checked_add(funded)?;
+                Some((funded, balance))
+            }) {
+                treasury.vusdt_balance = balance;
+                events.push(Event::TreasuryFunded {
+                    epoch: house.current_epoch,
+                    amount: funded.into(),
+                    balance: balance.into(),
+                });
+                self.insert(Key::Treasury, Value::Treasury(treasury));
             }
 
+            // Issue the epoch's staking rewards (split by voting power through the reward index)
+            let rewards_epoch = house.current_epoch;
+            let apr_bps = house.rewards.apr_bps(rewards_epoch);
+            let rewards = if house.total_voting_power > 0 {
+                house
+                    .rewards
+                    .issuance(rewards_epoch, house.total_staked_amount)
+            } else {
+                0
+            };
+            house.reward_index = house.reward_index.saturating_add(
+                rewards as u128 * nullspace_types::casino::REWARD_INDEX_SCALE
+                    / house.total_voting_power.max(1),
+            );
+
             house.current_epoch += 1;
             house.epoch_start_ts = self.seed.view;
             house.net_pnl = 0; // Reset for next week
 
+            // Accrue interest on all vUSDT debt (applied to each vault when next touched)
+            house.debt_index = house
+                .debt_index
+                .saturating_mul(10_000 + house.debt_rate_bps as u128)
+                / 10_000;
+
             let epoch = house.current_epoch;
             self.insert(Key::House, Value::House(house));
 
-            return vec![Event::EpochProcessed { epoch }];
+            events.push(Event::EpochProcessed { epoch });
+            events.push(Event::StakingRewardsIssued {
+                epoch: rewards_epoch,
+                amount: rewards,
+                apr_bps,
+            });
+            return events;
         }
 
         vec![]
     }
+
+    /// Pay a share of the fees and sell tax collected since `start` into the insurance fund,
+    /// then draw on the fund if the house has lost more than the fund's threshold.
+    ///
+    /// `bought_back` is the treasury's total buybacks at `start` (buybacks are burned in full,
+    /// so they are excluded from the sell tax).
+    pub(in crate::layer) async fn process_insurance(
+        &mut self,
+        start: &nullspace_types::casino::HouseState,
+        bought_back: nullspace_types::casino::Chips,
+    ) -> Vec<Event> {
+        let mut house = self.get_or_init_house().await;
+        let from_fees = house.insurance.funding(nullspace_types::casino::Chips(
+            house
+                .accumulated_fees
+                .saturating_sub(start.accumulated_fees),
+        ));
+        let buybacks = self
+            .get_treasury()
+            .await
+            .total_bought_back
+            .checked_sub(bought_back)
+            .map_or(0, u64::from);
+        let from_tax = house.insurance.funding(nullspace_types::casino::Chips(
+            house
+                .total_burned
+                .saturating_sub(start.total_burned)
+                .saturating_sub(buybacks),
+        ));
+
+        // Funding that would overflow the fund is skipped (and left in the fees and sell tax)
+        let mut events = Vec::new();
+        let funded = from_fees.checked_add(from_tax).and_then(|funded| {
+            let balance = house.insurance.balance.checked_add(funded)?;
+            Some((funded, balance))
+        });
+        if let Some((funded, balance)) = funded.filter(|(funded, _)| !funded.is_zero()) {
+            // The fund's share of the sell tax is held instead of burned
+            house.accumulated_fees -= from_fees.0;
+            house.total_burned -= from_tax.0;
+            house.insurance.balance = balance;
+            events.push(Event::InsuranceFunded {
+                amount: funded.into(),
+                balance: balance.into(),
+            });
+        }
+
+        // Draws are limited to the balance
+        let drawn = house.insurance.draw(house.net_pnl);
+        if let Some(balance) = house
+            .insurance
+            .balance
+            .checked_sub(drawn)
+            .filter(|_| !drawn.is_zero())
+        {
+            house.insurance.balance = balance;
+            house.net_pnl += drawn.0 as i128;
+            events.push(Event::InsuranceDrawn {
+                amount: drawn.into(),
+                balance: balance.into(),
+                net_pnl: house.net_pnl,
+            });
+        }
+
+        if !events.is_empty() {
+            self.insert(Key::House, Value::House(house));
+        }
+        events
+    }
 }
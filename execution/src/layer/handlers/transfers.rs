@@ -0,0 +1,238 @@
+use super::super::*;
+
+impl<'a, S: State> Layer<'a, S> {
+    // === Transfer Handlers ===
+
+    pub(in crate::layer) async fn handle_approve(
+        &mut self,
+        public: &PublicKey,
+        spender: &PublicKey,
+        amount: u64,
+    ) -> Vec<Event> {
+        if spender == public {
+            return Self::instruction_failed(
+                public,
+                tags::instruction::APPROVE,
+                nullspace_types::casino::CasinoErrorCode::Unauthorized,
+            );
+        }
+        let key = Key::Allowance(public.clone(), spender.clone());
+        if amount == 0 {
+            self.delete(&key).await;
+        } else {
+            self.insert(key, Value::Allowance(amount));
+        }
+
+        vec![Event::AllowanceSet {
+            owner: public.clone(),
+            spender: spender.clone(),
+            amount,
+        }]
+    }
+
+    pub(in crate::layer) async fn handle_transfer_vusdt_from(
+        &mut self,
+        public: &PublicKey,
+        owner: &PublicKey,
+        to: &PublicKey,
+        amount: u64,
+    ) -> Vec<Event> {
+        let kind = tags::instruction::TRANSFER_VUSDT_FROM;
+        if amount == 0 || owner == to {
+            return Self::instruction_failed(
+                public,
+                kind,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
+        }
+
+        // Spend the allowance
+        let key = Key::Allowance(owner.clone(), public.clone());
+        let allowance = match self.get(&key).await {
+            Some(Value::Allowance(allowance)) => allowance,
+            _ => 0,
+        };
+        let Some(remaining) = allowance.checked_sub(amount) else {
+            return Self::instruction_failed(
+                public,
+                kind,
+                nullspace_types::casino::CasinoErrorCode::AllowanceExceeded,
+            );
+        };
+
+        // Move the vUSDT
+        let (Some(Value::CasinoPlayer(mut from)), Some(Value::CasinoPlayer(mut recipient))) = (
+            self.get(&Key::CasinoPlayer(owner.clone())).await,
+            self.get(&Key::CasinoPlayer(to.clone())).await,
+        ) else {
+            return Self::instruction_failed(
+                public,
+                kind,
+                nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+            );
+        };
+        let amount = nullspace_types::casino::Vusdt(amount);
+        let Some(balance) = from.vusdt_balance.checked_sub(amount) else {
+            return Self::instruction_failed(
+                public,
+                kind,
+                nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+            );
+        };
+        let Some(recipient_balance) = recipient.vusdt_balance.checked_add(amount) else {
+            return Self::instruction_failed(
+                public,
+                kind,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
+        };
+        from.vusdt_balance = balance;
+        recipient.vusdt_balance = recipient_balance;
+
+        if remaining == 0 {
+            self.delete(&key).await;
+        } else {
+            self.insert(key, Value::Allowance(remaining));
+        }
+        self.insert(Key::CasinoPlayer(owner.clone()), Value::CasinoPlayer(from));
+        self.insert(
+            Key::CasinoPlayer(to.clone()),
+            Value::CasinoPlayer(recipient),
+        );
+
+        vec![Event::VusdtTransferred {
+            owner: owner.clone(),
+            to: to.clone(),
+            spender: public.clone(),
+            amount: amount.into(),
+            allowance: remaining,
+        }]
+    }
+
+    pub(in crate::layer) async fn handle_create_vesting(
+        &mut self,
+        public: &PublicKey,
+        to: &PublicKey,
+        amount: u64,
+        start_view: u64,
+        duration: u64,
+    ) -> Vec<Event> {
+        let kind = tags::instruction::CREATE_VESTING;
+        if amount == 0 {
+            return Self::instruction_failed(
+                public,
+                kind,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
+        }
+        let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
+            Some(Value::CasinoPlayer(p)) => p,
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    kind,
+                    nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                )
+            }
+        };
+        let Some(chips) = player.chips.checked_sub(amount.into()) else {
+            return Self::instruction_failed(
+                public,
+                kind,
+                nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+            );
+        };
+        let mut vesting = match self.get(&Key::Vesting(to.clone())).await {
+            Some(Value::Vesting(vesting)) => vesting,
+            _ => nullspace_types::casino::Vesting::default(),
+        };
+        if vesting.schedules.len() >= nullspace_types::casino::MAX_VESTING_SCHEDULES {
+            return Self::instruction_failed(
+                public,
+                kind,
+                nullspace_types::casino::CasinoErrorCode::VestingLimitReached,
+            );
+        }
+
+        // Escrow the chips until they are claimed
+        player.chips = chips;
+        vesting
+            .schedules
+            .push(nullspace_types::casino::VestingSchedule {
+                from: public.clone(),
+                amount,
+                claimed: 0,
+                start_view,
+                duration,
+            });
+        self.insert(
+            Key::CasinoPlayer(public.clone()),
+            Value::CasinoPlayer(player),
+        );
+        self.insert(Key::Vesting(to.clone()), Value::Vesting(vesting));
+
+        vec![Event::VestingCreated {
+            from: public.clone(),
+            to: to.clone(),
+            amount,
+            start_view,
+            duration,
+        }]
+    }
+
+    pub(in crate::layer) async fn handle_claim_vested(&mut self, public: &PublicKey) -> Vec<Event> {
+        let kind = tags::instruction::CLAIM_VESTED;
+        let mut vesting = match self.get(&Key::Vesting(public.clone())).await {
+            Some(Value::Vesting(vesting)) => vesting,
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    kind,
+                    nullspace_types::casino::CasinoErrorCode::NotFound,
+                )
+            }
+        };
+        let mut player = match self.get(&Key::CasinoPlayer(public.clone())).await {
+            Some(Value::CasinoPlayer(p)) => p,
+            _ => {
+                return Self::instruction_failed(
+                    public,
+                    kind,
+                    nullspace_types::casino::CasinoErrorCode::PlayerNotFound,
+                )
+            }
+        };
+        let amount = vesting.claim(self.seed.view);
+        if amount == 0 {
+            return Self::instruction_failed(
+                public,
+                kind,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
+        }
+
+        let Some(new_chips) = player.chips.checked_add(amount.into()) else {
+            return Self::instruction_failed(
+                public,
+                kind,
+                nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+            );
+        };
+        player.chips = new_chips;
+        if vesting.schedules.is_empty() {
+            self.delete(&Key::Vesting(public.clone())).await;
+        } else {
+            self.insert(Key::Vesting(public.clone()), Value::Vesting(vesting));
+        }
+        self.insert(
+            Key::CasinoPlayer(public.clone()),
+            Value::CasinoPlayer(player),
+        );
+
+        vec![Event::VestedClaimed {
+            player: public.clone(),
+            amount,
+            new_chips: new_chips.into(),
+        }]
+    }
+}
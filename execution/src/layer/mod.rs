@@ -6,162 +6,61 @@ use commonware_cryptography::{
 #[cfg(feature = "parallel")]
 use commonware_runtime::ThreadPool;
 use nullspace_types::{
-    execution::{Event, Instruction, Key, Output, Transaction, Value},
+    execution::{
+        tags, Event, Instruction, Key, Output, Transaction, Value, COMPUTE_UNITS_PER_BET,
+        MAX_TRANSACTION_COMPUTE_UNITS,
+    },
     Seed,
 };
 use std::collections::BTreeMap;
 
-use crate::state::{load_account, validate_and_increment_nonce, PrepareError, State, Status};
+use crate::state::{
+    load_account, overlay_scan, validate_and_increment_nonce, PrepareError, State, Status,
+};
 
 mod handlers;
-
-// Keep a small amount of LP tokens permanently locked so the pool can never be fully drained.
-// This mirrors the MINIMUM_LIQUIDITY pattern used by Raydium/Uniswap to avoid zero-price states.
-const MINIMUM_LIQUIDITY: u64 = 1_000;
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum UthJackpotTier {
-    None,
-    StraightFlush,
-    RoyalFlush,
-}
-
-fn parse_u64_be_at(bytes: &[u8], offset: usize) -> Option<u64> {
-    let slice = bytes.get(offset..offset + 8)?;
-    let buf: [u8; 8] = slice.try_into().ok()?;
-    Some(u64::from_be_bytes(buf))
-}
-
-fn parse_three_card_progressive_state(state_blob: &[u8]) -> Option<(u64, [u8; 3])> {
-    // v3:
-    // [version:u8=3] [stage:u8] [player:3] [dealer:3] [pairplus:u64] [six_card:u64] [progressive:u64]
-    //
-    // v1/v2 have the same leading bytes for player cards but no progressive bet field.
-    if state_blob.len() < 5 {
-        return None;
-    }
-
-    let version = state_blob[0];
-    let player = [state_blob[2], state_blob[3], state_blob[4]];
-    let progressive_bet = if version >= 3 {
-        parse_u64_be_at(state_blob, 24).unwrap_or(0)
-    } else {
-        0
-    };
-
-    Some((progressive_bet, player))
-}
-
-fn is_three_card_mini_royal_spades(cards: &[u8; 3]) -> bool {
-    if !cards.iter().all(|&c| c < 52) {
-        return false;
-    }
-    if !cards.iter().all(|&c| card_suit(c) == 0) {
-        return false;
-    }
-
-    let mut ranks = [
-        card_rank_ace_high(cards[0]),
-        card_rank_ace_high(cards[1]),
-        card_rank_ace_high(cards[2]),
-    ];
-    ranks.sort_unstable_by(|a, b| b.cmp(a));
-
-    ranks == [14, 13, 12]
-}
-
-fn parse_uth_progressive_state(state_blob: &[u8]) -> Option<(u64, [u8; 2], [u8; 3])> {
-    // v3:
-    // [version:u8=3] [stage:u8] [hole:2] [community:5] [dealer:2] [play_mult:u8] [bonus:4]
-    // [trips:u64] [six_card:u64] [progressive:u64]
-    //
-    // v1/v2 have the same leading bytes for hole+community but no progressive bet field.
-    if state_blob.len() < 7 {
-        return None;
-    }
-
-    let version = state_blob[0];
-    let hole = [state_blob[2], state_blob[3]];
-    let flop = [state_blob[4], state_blob[5], state_blob[6]];
-    let progressive_bet = if version >= 3 {
-        parse_u64_be_at(state_blob, 32).unwrap_or(0)
-    } else {
-        0
-    };
-
-    Some((progressive_bet, hole, flop))
-}
-
-fn uth_progressive_jackpot_tier(hole: &[u8; 2], flop: &[u8; 3]) -> UthJackpotTier {
-    let cards = [hole[0], hole[1], flop[0], flop[1], flop[2]];
-    if !cards.iter().all(|&c| c < 52) {
-        return UthJackpotTier::None;
-    }
-
-    let suits = [
-        card_suit(cards[0]),
-        card_suit(cards[1]),
-        card_suit(cards[2]),
-        card_suit(cards[3]),
-        card_suit(cards[4]),
-    ];
-    let is_flush = suits[0] == suits[1]
-        && suits[1] == suits[2]
-        && suits[2] == suits[3]
-        && suits[3] == suits[4];
-
-    let mut ranks = [
-        card_rank_ace_high(cards[0]),
-        card_rank_ace_high(cards[1]),
-        card_rank_ace_high(cards[2]),
-        card_rank_ace_high(cards[3]),
-        card_rank_ace_high(cards[4]),
-    ];
-    ranks.sort_unstable();
-
-    let has_duplicates = ranks[0] == ranks[1]
-        || ranks[1] == ranks[2]
-        || ranks[2] == ranks[3]
-        || ranks[3] == ranks[4];
-
-    let is_straight = if has_duplicates {
-        false
-    } else if ranks[4].saturating_sub(ranks[0]) == 4 {
-        true
-    } else {
-        // Wheel: A-2-3-4-5
-        ranks == [2, 3, 4, 5, 14]
-    };
-
-    let is_royal = ranks == [10, 11, 12, 13, 14];
-
-    if is_flush && is_royal {
-        UthJackpotTier::RoyalFlush
-    } else if is_flush && is_straight {
-        UthJackpotTier::StraightFlush
-    } else {
-        UthJackpotTier::None
-    }
-}
-
-fn card_rank_ace_high(card: u8) -> u8 {
-    let r = (card % 13) + 1;
-    if r == 1 {
-        14
-    } else {
-        r
-    }
-}
-
-fn card_suit(card: u8) -> u8 {
-    card / 13
-}
+#[cfg(feature = "parallel")]
+mod parallel;
 
 pub struct Layer<'a, S: State> {
     state: &'a S,
     pending: BTreeMap<Key, Status>,
+    /// Previous status of each key modified by the batch being applied (if any).
+    undo: Option<Vec<(Key, Option<Status>)>>,
+
+    /// Network identity (and namespaces) slashing evidence and admin keys must be signed with.
+    master: <MinSig as Variant>::Public,
+    slash_namespace: Vec<u8>,
+    admin_namespace: Vec<u8>,
 
     seed: Seed,
+    /// Economy parameters in effect for the block (loaded when the layer is created, so
+    /// parameters set during a block take effect from the next one).
+    economy: nullspace_types::casino::EconomyParams,
+
+    /// Updates recorded (instead of applied) while executing a transaction speculatively.
+    deferred: Option<Deferred>,
+}
+
+/// House counter and leaderboard updates made by a transaction.
+///
+/// Most transactions update these (fees, house PnL and chip balances) without otherwise
+/// depending on them, so recording the updates (and applying them once the transaction is
+/// merged) keeps transactions that don't share any other keys independent.
+#[derive(Clone, Default)]
+#[cfg_attr(not(feature = "parallel"), allow(dead_code))]
+struct Deferred {
+    /// Whether the house counters were updated (even if the updates cancel out).
+    house_updated: bool,
+    fees: u64,
+    pnl: i128,
+    /// Leaderboard entries (player, name and chips) in the order they were updated.
+    leaderboard: Vec<(PublicKey, String, nullspace_types::casino::Chips)>,
+    /// Completed sessions to queue for reclamation, in the order they completed.
+    expiries: Vec<u64>,
+    /// Whether the transaction read the house counters (folding in the updates recorded so
+    /// far).
+    house_read: bool,
 }
 
 impl<'a, S: State> Layer<'a, S> {
@@ -178,74 +77,254 @@ impl<'a, S: State> Layer<'a, S> {
         x as u64
     }
 
-    pub fn new(
+    pub async fn new(
         state: &'a S,
-        _master: <MinSig as Variant>::Public,
-        _namespace: &[u8],
+        master: <MinSig as Variant>::Public,
+        namespace: &[u8],
         seed: Seed,
     ) -> Self {
-        Self {
+        let mut layer = Self {
             state,
             pending: BTreeMap::new(),
+            undo: None,
+
+            master,
+            slash_namespace: nullspace_types::execution::slash_namespace(namespace),
+            admin_namespace: nullspace_types::execution::admin_namespace(namespace),
 
             seed,
-        }
+            economy: nullspace_types::casino::EconomyParams::default(),
+
+            deferred: None,
+        };
+        layer.economy = layer.economy_params().await;
+        layer
     }
 
     fn insert(&mut self, key: Key, value: Value) {
+        self.track(&key);
         self.pending.insert(key, Status::Update(value));
     }
 
+    /// Remember the status of `key` (if applying a batch) so it can be restored.
+    fn track(&mut self, key: &Key) {
+        if let Some(undo) = &mut self.undo {
+            undo.push((key.clone(), self.pending.get(key).cloned()));
+        }
+    }
+
     pub fn view(&self) -> View {
         self.seed.view
     }
 
-    async fn prepare(&mut self, transaction: &Transaction) -> Result<(), PrepareError> {
+    /// Compute units used by `transaction` (see [Instruction::compute_units]), including the
+    /// bets held by the sessions it makes moves in.
+    async fn compute_units(&self, transaction: &Transaction) -> u64 {
+        let instructions = match &transaction.instruction {
+            Instruction::Batch(instructions) => instructions.as_slice(),
+            instruction => std::slice::from_ref(instruction),
+        };
+        let mut units = transaction.instruction.compute_units();
+        for instruction in instructions {
+            let Instruction::CasinoGameMove { session_id, .. } = instruction else {
+                continue;
+            };
+            if let Some(Value::CasinoSession(session)) =
+                self.get(&Key::CasinoSession(*session_id)).await
+            {
+                let bets = crate::casino::bet_count(&session) as u64;
+                units = units.saturating_add(COMPUTE_UNITS_PER_BET.saturating_mul(bets));
+            }
+        }
+        units
+    }
+
+    /// Validate the nonce of `transaction` and charge its fee (returning a [Event::FeeCharged]
+    /// if any fee was charged).
+    ///
+    /// Fees are paid in chips and credited to the house. If the player can't cover the fee
+    /// (or the transaction uses more than [MAX_TRANSACTION_COMPUTE_UNITS]), the transaction is
+    /// rejected (and its nonce is not consumed).
+    async fn prepare(&mut self, transaction: &Transaction) -> Result<Option<Event>, PrepareError> {
+        let units = self.compute_units(transaction).await;
+        if units > MAX_TRANSACTION_COMPUTE_UNITS {
+            return Err(PrepareError::ComputeLimitExceeded {
+                units,
+                limit: MAX_TRANSACTION_COMPUTE_UNITS,
+            });
+        }
+
         let mut account = load_account(self, &transaction.public).await;
         validate_and_increment_nonce(&mut account, transaction.nonce)?;
+
+        // Charge the fee (if any)
+        let fee = self
+            .house_settings()
+            .await
+            .fee_schedule
+            .fee(&transaction.instruction);
+        let event = if fee > 0 {
+            let player_key = Key::CasinoPlayer(transaction.public.clone());
+            let mut player = match self.get(&player_key).await {
+                Some(Value::CasinoPlayer(player)) => player,
+                _ => {
+                    return Err(PrepareError::InsufficientFee {
+                        required: fee,
+                        available: 0,
+                    })
+                }
+            };
+            let Some(chips) = player
+                .chips
+                .checked_sub(nullspace_types::casino::Chips(fee))
+            else {
+                return Err(PrepareError::InsufficientFee {
+                    required: fee,
+                    available: player.chips.into(),
+                });
+            };
+            player.chips = chips;
+            let accumulated_fees = self.credit_fee(fee).await;
+            let event = Event::FeeCharged {
+                player: transaction.public.clone(),
+                amount: fee,
+                new_chips: player.chips.into(),
+                accumulated_fees,
+            };
+            self.insert(player_key, Value::CasinoPlayer(player));
+            Some(event)
+        } else {
+            None
+        };
+
         self.insert(
             Key::Account(transaction.public.clone()),
             Value::Account(account),
         );
 
-        Ok(())
+        Ok(event)
     }
 
     async fn apply(&mut self, transaction: &Transaction) -> Vec<Event> {
         match &transaction.instruction {
-            Instruction::CasinoRegister { name } => {
-                self.handle_casino_register(&transaction.public, name).await
+            Instruction::Batch(instructions) => {
+                self.apply_batch(&transaction.public, transaction.nonce, instructions)
+                    .await
             }
+            instruction => {
+                self.apply_instruction(&transaction.public, transaction.nonce, instruction)
+                    .await
+            }
+        }
+    }
+
+    /// Apply all `instructions` or none of them.
+    ///
+    /// An instruction fails if it emits a [Event::CasinoError] or [Event::InstructionFailed]. If
+    /// any does, the changes made by the batch are discarded and only that error is returned.
+    async fn apply_batch(
+        &mut self,
+        public: &PublicKey,
+        nonce: u64,
+        instructions: &[Instruction],
+    ) -> Vec<Event> {
+        self.undo = Some(Vec::new());
+        let deferred = self.deferred.clone();
+        let mut events = Vec::new();
+        for instruction in instructions {
+            let result = self.apply_instruction(public, nonce, instruction).await;
+            if result.iter().any(|event| {
+                matches!(
+                    event,
+                    Event::CasinoError { .. } | Event::InstructionFailed { .. }
+                )
+            }) {
+                // Restore in reverse (the first change to each key holds its original status)
+                let undo = self.undo.take().unwrap_or_default();
+                for (key, status) in undo.into_iter().rev() {
+                    match status {
+                        Some(status) => self.pending.insert(key, status),
+                        None => self.pending.remove(&key),
+                    };
+                }
+                self.deferred = deferred;
+                return result;
+            }
+            events.extend(result);
+        }
+        self.undo = None;
+        events
+    }
+
+    /// Apply `instruction` for `public` (as part of the transaction with `nonce`).
+    async fn apply_instruction(
+        &mut self,
+        public: &PublicKey,
+        nonce: u64,
+        instruction: &Instruction,
+    ) -> Vec<Event> {
+        match instruction {
+            Instruction::CasinoRegister { name } => self.handle_casino_register(public, name).await,
             Instruction::CasinoDeposit { amount } => {
-                self.handle_casino_deposit(&transaction.public, *amount)
+                self.handle_casino_deposit(public, *amount).await
+            }
+            Instruction::ClaimDailyBonus => self.handle_claim_daily_bonus(public).await,
+            Instruction::CasinoAbandonSession { session_id } => {
+                self.handle_casino_abandon_session(public, *session_id)
+                    .await
+            }
+            Instruction::SetKycStatus { player, verified } => {
+                self.handle_set_kyc_status(public, player, *verified).await
+            }
+            Instruction::CasinoScheduleTournament {
+                tournament_id,
+                start_view,
+                end_view,
+            } => {
+                self.handle_casino_schedule_tournament(
+                    public,
+                    *tournament_id,
+                    *start_view,
+                    *end_view,
+                )
+                .await
+            }
+            Instruction::UpdateProfile {
+                display_name,
+                avatar_id,
+                title,
+            } => {
+                self.handle_update_profile(public, display_name, *avatar_id, title)
                     .await
             }
             Instruction::CasinoStartGame {
                 game_type,
                 bet,
                 session_id,
+                commitment,
             } => {
-                self.handle_casino_start_game(&transaction.public, *game_type, *bet, *session_id)
-                    .await
+                self.handle_casino_start_game(
+                    public,
+                    nonce,
+                    *game_type,
+                    *bet,
+                    *session_id,
+                    *commitment,
+                )
+                .await
             }
             Instruction::CasinoGameMove {
                 session_id,
                 payload,
             } => {
-                self.handle_casino_game_move(&transaction.public, *session_id, payload)
+                self.handle_casino_game_move(public, *session_id, payload)
                     .await
             }
-            Instruction::CasinoToggleShield => {
-                self.handle_casino_toggle_shield(&transaction.public).await
-            }
-            Instruction::CasinoToggleDouble => {
-                self.handle_casino_toggle_double(&transaction.public).await
-            }
-            Instruction::CasinoToggleSuper => {
-                self.handle_casino_toggle_super(&transaction.public).await
-            }
+            Instruction::CasinoToggleShield => self.handle_casino_toggle_shield(public).await,
+            Instruction::CasinoToggleDouble => self.handle_casino_toggle_double(public).await,
+            Instruction::CasinoToggleSuper => self.handle_casino_toggle_super(public).await,
             Instruction::CasinoJoinTournament { tournament_id } => {
-                self.handle_casino_join_tournament(&transaction.public, *tournament_id)
+                self.handle_casino_join_tournament(public, *tournament_id)
                     .await
             }
             Instruction::CasinoStartTournament {
@@ -254,7 +333,7 @@ impl<'a, S: State> Layer<'a, S> {
                 end_time_ms,
             } => {
                 self.handle_casino_start_tournament(
-                    &transaction.public,
+                    public,
                     *tournament_id,
                     *start_time_ms,
                     *end_time_ms,
@@ -262,96 +341,371 @@ impl<'a, S: State> Layer<'a, S> {
                 .await
             }
             Instruction::CasinoEndTournament { tournament_id } => {
-                self.handle_casino_end_tournament(&transaction.public, *tournament_id)
+                self.handle_casino_end_tournament(public, *tournament_id)
                     .await
             }
             // Staking
             Instruction::Stake { amount, duration } => {
-                self.handle_stake(&transaction.public, *amount, *duration)
-                    .await
+                self.handle_stake(public, *amount, *duration).await
             }
-            Instruction::Unstake => self.handle_unstake(&transaction.public).await,
-            Instruction::ClaimRewards => self.handle_claim_rewards(&transaction.public).await,
-            Instruction::ProcessEpoch => self.handle_process_epoch(&transaction.public).await,
+            Instruction::Unstake { amount } => self.handle_unstake(public, *amount).await,
+            Instruction::ClaimRewards => self.handle_claim_rewards(public).await,
+            Instruction::ProcessEpoch => self.handle_process_epoch(public).await,
 
             // Vaults
-            Instruction::CreateVault => self.handle_create_vault(&transaction.public).await,
+            Instruction::CreateVault => self.handle_create_vault(public).await,
             Instruction::DepositCollateral { amount } => {
-                self.handle_deposit_collateral(&transaction.public, *amount)
-                    .await
-            }
-            Instruction::BorrowUSDT { amount } => {
-                self.handle_borrow_usdt(&transaction.public, *amount).await
+                self.handle_deposit_collateral(public, *amount).await
             }
-            Instruction::RepayUSDT { amount } => {
-                self.handle_repay_usdt(&transaction.public, *amount).await
+            Instruction::BorrowUSDT { amount } => self.handle_borrow_usdt(public, *amount).await,
+            Instruction::RepayUSDT { amount } => self.handle_repay_usdt(public, *amount).await,
+            Instruction::WithdrawCollateral { amount } => {
+                self.handle_withdraw_collateral(public, *amount).await
             }
 
             // AMM
             Instruction::Swap {
+                pair,
                 amount_in,
                 min_amount_out,
                 is_buying_rng,
             } => {
-                self.handle_swap(
-                    &transaction.public,
-                    *amount_in,
-                    *min_amount_out,
-                    *is_buying_rng,
-                )
-                .await
+                self.handle_swap(public, *pair, *amount_in, *min_amount_out, *is_buying_rng)
+                    .await
             }
             Instruction::AddLiquidity {
+                pair,
                 rng_amount,
                 usdt_amount,
             } => {
-                self.handle_add_liquidity(&transaction.public, *rng_amount, *usdt_amount)
+                self.handle_add_liquidity(public, *pair, *rng_amount, *usdt_amount)
+                    .await
+            }
+            Instruction::RemoveLiquidity { pair, shares } => {
+                self.handle_remove_liquidity(public, *pair, *shares).await
+            }
+            Instruction::PlaceLimitOrder {
+                amount_in,
+                min_price,
+                is_buying_rng,
+            } => {
+                self.handle_place_limit_order(public, *amount_in, *min_price, *is_buying_rng)
+                    .await
+            }
+            Instruction::CancelLimitOrder { order_id } => {
+                self.handle_cancel_limit_order(public, *order_id).await
+            }
+            Instruction::ZapLiquidity {
+                pair,
+                amount_in,
+                min_shares,
+                from_rng,
+            } => {
+                self.handle_zap_liquidity(public, *pair, *amount_in, *min_shares, *from_rng)
+                    .await
+            }
+            Instruction::Slash {
+                staker,
+                bps,
+                signature,
+            } => self.handle_slash(public, staker, *bps, signature).await,
+
+            // Account recovery
+            Instruction::SetGuardians {
+                guardians,
+                threshold,
+            } => {
+                self.handle_set_guardians(public, guardians, *threshold)
+                    .await
+            }
+            Instruction::RecoverAccount { account, new_key } => {
+                self.handle_recover_account(public, account, new_key).await
+            }
+
+            // vUSDT allowances
+            Instruction::Approve { spender, amount } => {
+                self.handle_approve(public, spender, *amount).await
+            }
+            Instruction::TransferVusdtFrom { owner, to, amount } => {
+                self.handle_transfer_vusdt_from(public, owner, to, *amount)
+                    .await
+            }
+
+            // Vesting
+            Instruction::CreateVesting {
+                to,
+                amount,
+                start_view,
+                duration,
+            } => {
+                self.handle_create_vesting(public, to, *amount, *start_view, *duration)
+                    .await
+            }
+            Instruction::ClaimVested => self.handle_claim_vested(public).await,
+            Instruction::ExecuteBuyback { amount } => {
+                self.handle_execute_buyback(public, *amount).await
+            }
+            Instruction::SetGameConfig { game_type, config } => {
+                self.handle_set_game_config(public, *game_type, config)
+                    .await
+            }
+            Instruction::GrantBonus { player, amount } => {
+                self.handle_grant_bonus(public, player, *amount).await
+            }
+            Instruction::SetTournamentRules {
+                tournament_id,
+                rules,
+            } => {
+                self.handle_set_tournament_rules(public, *tournament_id, rules)
                     .await
             }
-            Instruction::RemoveLiquidity { shares } => {
-                self.handle_remove_liquidity(&transaction.public, *shares)
+            Instruction::SetEconomyParams { params } => {
+                self.handle_set_economy_params(public, params).await
+            }
+            Instruction::SetAdmin {
+                role,
+                admin,
+                signature,
+            } => {
+                self.handle_set_admin(public, *role, admin.as_ref(), signature)
                     .await
             }
+            Instruction::SetHouseSettings { settings } => {
+                self.handle_set_house_settings(public, settings).await
+            }
+
+            // Nested batches are rejected when decoded
+            Instruction::Batch(_) => vec![],
         }
     }
 
-    async fn get_or_init_house(&mut self) -> nullspace_types::casino::HouseState {
+    /// Reject a (non-casino) instruction of `kind` (its tag) with `code`.
+    fn instruction_failed(
+        public: &PublicKey,
+        kind: u8,
+        code: nullspace_types::casino::CasinoErrorCode,
+    ) -> Vec<Event> {
+        vec![Event::InstructionFailed {
+            player: public.clone(),
+            kind,
+            code,
+        }]
+    }
+
+    /// Load the house to read its settings (which only governance and admin instructions
+    /// change).
+    ///
+    /// Use [Self::get_or_init_house] to read (or update) its counters.
+    async fn house_settings(&self) -> nullspace_types::casino::HouseState {
         match self.get(&Key::House).await {
             Some(Value::House(h)) => h,
             _ => nullspace_types::casino::HouseState::new(self.seed.view),
         }
     }
 
-    async fn get_or_init_amm(&mut self) -> nullspace_types::casino::AmmPool {
-        match self.get(&Key::AmmPool).await {
+    async fn get_or_init_house(&mut self) -> nullspace_types::casino::HouseState {
+        let mut house = self.house_settings().await;
+        if let Some(deferred) = &mut self.deferred {
+            // The counters are about to be read, so they must include the updates recorded
+            // so far
+            deferred.house_read = true;
+            if deferred.house_updated {
+                house.accumulated_fees = house.accumulated_fees.saturating_add(deferred.fees);
+                house.net_pnl += deferred.pnl;
+                deferred.house_updated = false;
+                deferred.fees = 0;
+                deferred.pnl = 0;
+                self.insert(Key::House, Value::House(house.clone()));
+            }
+        }
+        house
+    }
+
+    /// Credit `fee` to the house, returning the fees accumulated so far (or 0 if the fee was
+    /// deferred, in which case the total is only known once the transaction is merged).
+    async fn credit_fee(&mut self, fee: u64) -> u64 {
+        if let Some(deferred) = &mut self.deferred {
+            deferred.house_updated = true;
+            deferred.fees = deferred.fees.saturating_add(fee);
+            return 0;
+        }
+        let mut house = self.get_or_init_house().await;
+        house.accumulated_fees = house.accumulated_fees.saturating_add(fee);
+        let accumulated_fees = house.accumulated_fees;
+        self.insert(Key::House, Value::House(house));
+        accumulated_fees
+    }
+
+    /// Load the pool for `pair` (with its price accumulators advanced to the current view, so
+    /// they are up to date before its reserves change).
+    async fn get_or_init_amm(
+        &mut self,
+        pair: nullspace_types::casino::PairId,
+    ) -> nullspace_types::casino::AmmPool {
+        let mut pool = match self.get(&Key::AmmPool(pair)).await {
             Some(Value::AmmPool(p)) => p,
-            _ => nullspace_types::casino::AmmPool::new(30), // 0.3% fee
+            _ => nullspace_types::casino::AmmPool::new(self.economy.amm_fee_bps),
+        };
+        pool.accumulate(self.seed.view);
+        pool
+    }
+
+    /// Pairs with an AMM pool (the RNG/vUSDT pool is always included, since it predates the
+    /// registry).
+    async fn amm_pairs(&self) -> Vec<nullspace_types::casino::PairId> {
+        let mut pairs = match self.get(&Key::AmmPools).await {
+            Some(Value::AmmPools(pools)) => pools.pairs,
+            _ => Vec::new(),
+        };
+        if !pairs.contains(&nullspace_types::casino::PairId::RNG_VUSDT) {
+            pairs.insert(0, nullspace_types::casino::PairId::RNG_VUSDT);
+        }
+        pairs
+    }
+
+    /// Whether `pair` already has a pool or the pool registry has room to add one.
+    async fn amm_pool_available(&self, pair: nullspace_types::casino::PairId) -> bool {
+        match self.get(&Key::AmmPools).await {
+            Some(Value::AmmPools(pools)) => {
+                pools.pairs.contains(&pair)
+                    || pools.pairs.len() < nullspace_types::casino::MAX_AMM_POOLS
+            }
+            _ => true,
+        }
+    }
+
+    /// Store the pool for `pair` (adding it to the pool registry if it isn't listed yet).
+    async fn put_amm(
+        &mut self,
+        pair: nullspace_types::casino::PairId,
+        pool: nullspace_types::casino::AmmPool,
+    ) {
+        let mut pools = match self.get(&Key::AmmPools).await {
+            Some(Value::AmmPools(pools)) => pools,
+            _ => nullspace_types::casino::AmmPools::default(),
+        };
+        if !pools.pairs.contains(&pair) {
+            pools.pairs.push(pair);
+            self.insert(Key::AmmPools, Value::AmmPools(pools));
+        }
+        self.insert(Key::AmmPool(pair), Value::AmmPool(pool));
+    }
+
+    /// Record the AMM spot price (before any transaction in the block is applied) in the
+    /// price oracle.
+    async fn update_oracle(&mut self) {
+        let pair = nullspace_types::casino::PairId::RNG_VUSDT;
+        let Some(spot_price) = self.get_or_init_amm(pair).await.spot_price() else {
+            return;
+        };
+        let mut oracle = match self.get(&Key::Oracle).await {
+            Some(Value::Oracle(oracle)) => oracle,
+            _ => nullspace_types::casino::PriceOracle::default(),
+        };
+        oracle.observe(self.seed.view, spot_price);
+        self.insert(Key::Oracle, Value::Oracle(oracle));
+    }
+
+    /// Load the table rules of `game_type` (the standard rules unless governance set others).
+    async fn game_config(
+        &self,
+        game_type: nullspace_types::casino::GameType,
+    ) -> nullspace_types::casino::GameConfig {
+        match self.get(&Key::GameConfig(game_type)).await {
+            Some(Value::GameConfig(config)) => config,
+            _ => nullspace_types::casino::GameConfig::default(),
+        }
+    }
+
+    /// Load the economy parameters (the defaults unless governance set others).
+    async fn economy_params(&self) -> nullspace_types::casino::EconomyParams {
+        match self.get(&Key::EconomyParams).await {
+            Some(Value::EconomyParams(params)) => params,
+            _ => nullspace_types::casino::EconomyParams::default(),
+        }
+    }
+
+    async fn get_treasury(&self) -> nullspace_types::casino::Treasury {
+        match self.get(&Key::Treasury).await {
+            Some(Value::Treasury(treasury)) => treasury,
+            _ => nullspace_types::casino::Treasury::default(),
         }
     }
 
-    async fn get_lp_balance(&self, public: &PublicKey) -> u64 {
-        match self.get(&Key::LpBalance(public.clone())).await {
+    async fn get_lp_balance(
+        &self,
+        pair: nullspace_types::casino::PairId,
+        public: &PublicKey,
+    ) -> nullspace_types::casino::LpShares {
+        match self.get(&Key::LpBalance(pair, public.clone())).await {
             Some(Value::LpBalance(bal)) => bal,
-            _ => 0,
+            _ => nullspace_types::casino::LpShares::ZERO,
         }
     }
 
     pub async fn execute(
         &mut self,
-        #[cfg(feature = "parallel")] _pool: ThreadPool,
+        #[cfg(feature = "parallel")] pool: ThreadPool,
         transactions: Vec<Transaction>,
     ) -> (Vec<Output>, BTreeMap<PublicKey, u64>) {
         let mut processed_nonces = BTreeMap::new();
         let mut outputs = Vec::new();
 
+        // Reserves only change when transactions are applied, so the oracle only needs to be
+        // updated in blocks that have them
+        if !transactions.is_empty() {
+            self.update_oracle().await;
+        }
+
+        // Scheduled tournaments and leaderboard seasons advance with the view (including in
+        // blocks without transactions)
+        outputs.extend(
+            self.process_tournament_schedules()
+                .await
+                .into_iter()
+                .map(Output::Event),
+        );
+        outputs.extend(
+            self.process_leaderboard_season()
+                .await
+                .into_iter()
+                .map(Output::Event),
+        );
+        self.reclaim_expired_sessions().await;
+
+        // Fees and sell tax collected in the block partially fund the insurance fund
+        let start = self.house_settings().await;
+        let bought_back = self.get_treasury().await.total_bought_back;
+
+        #[cfg(feature = "parallel")]
+        self.execute_parallel(pool, transactions, &mut outputs, &mut processed_nonces)
+            .await;
+        #[cfg(not(feature = "parallel"))]
+        let mut compute_used = 0u64;
+        #[cfg(not(feature = "parallel"))]
         for tx in transactions {
-            if self.prepare(&tx).await.is_err() {
+            // Skip transactions that don't fit in the block's compute budget
+            let units = self.compute_units(&tx).await;
+            if compute_used.saturating_add(units)
+                > nullspace_types::execution::MAX_BLOCK_COMPUTE_UNITS
+            {
                 continue;
             }
+            let Ok(fee) = self.prepare(&tx).await else {
+                continue;
+            };
+            compute_used += units;
             processed_nonces.insert(tx.public.clone(), tx.nonce.saturating_add(1));
+            outputs.extend(fee.map(Output::Event));
             outputs.extend(self.apply(&tx).await.into_iter().map(Output::Event));
             outputs.push(Output::Transaction(tx));
         }
+        outputs.extend(
+            self.process_insurance(&start, bought_back)
+                .await
+                .into_iter()
+                .map(Output::Event),
+        );
 
         (outputs, processed_nonces)
     }
@@ -371,17 +725,26 @@ impl<'a, S: State> State for Layer<'a, S> {
     }
 
     async fn insert(&mut self, key: Key, value: Value) {
+        self.track(&key);
         self.pending.insert(key, Status::Update(value));
     }
 
     async fn delete(&mut self, key: &Key) {
+        self.track(key);
         self.pending.insert(key.clone(), Status::Delete);
     }
+
+    async fn scan(&self, prefix: &[u8]) -> Vec<(Key, Value)> {
+        overlay_scan(self.state.scan(prefix).await, &self.pending, prefix)
+    }
 }
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::mocks::{create_account_keypair, create_network_keypair, create_seed};
+    use commonware_codec::Encode;
+    use commonware_cryptography::bls12381::primitives::ops;
+    use commonware_cryptography::{sha256::Sha256, Hasher};
     use commonware_runtime::deterministic::Runner;
     use commonware_runtime::Runner as _;
 
@@ -411,6 +774,20 @@ mod tests {
         async fn delete(&mut self, key: &Key) {
             self.data.remove(key);
         }
+
+        async fn scan(&self, prefix: &[u8]) -> Vec<(Key, Value)> {
+            if !Key::scannable(prefix) {
+                return Vec::new();
+            }
+            let mut entries: Vec<_> = self
+                .data
+                .iter()
+                .filter(|(key, _)| key.encode().starts_with(prefix))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect();
+            entries.sort_by_cached_key(|(key, _)| key.encode());
+            entries
+        }
     }
 
     #[test]
@@ -420,7 +797,7 @@ mod tests {
             let state = MockState::new();
             let (network_secret, master_public) = create_network_keypair();
             let seed = create_seed(&network_secret, 1);
-            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
 
             let (signer, _) = create_account_keypair(1);
 
@@ -455,7 +832,7 @@ mod tests {
             let state = MockState::new();
             let (network_secret, master_public) = create_network_keypair();
             let seed = create_seed(&network_secret, 1);
-            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
 
             let (signer, public) = create_account_keypair(1);
 
@@ -481,7 +858,7 @@ mod tests {
             // Verify player was created
             if let Some(Value::CasinoPlayer(player)) = layer.get(&Key::CasinoPlayer(public)).await {
                 assert_eq!(player.name, "Alice");
-                assert_eq!(player.chips, 1000); // Initial chips
+                assert_eq!(player.chips.0, 1000); // Initial chips
             } else {
                 panic!("Player not found");
             }
@@ -489,4 +866,4127 @@ mod tests {
             let _ = layer.commit();
         });
     }
+
+    #[test]
+    fn test_transaction_fee() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+
+            let (signer, public) = create_account_keypair(1);
+
+            // Unregistered accounts can't pay fees
+            let tx = Transaction::sign(&signer, 0, Instruction::CasinoToggleShield);
+            assert_eq!(
+                layer.prepare(&tx).await,
+                Err(PrepareError::InsufficientFee {
+                    required: nullspace_types::casino::TRANSACTION_FEE,
+                    available: 0,
+                })
+            );
+
+            // Registration is free
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+            );
+            assert_eq!(layer.prepare(&tx).await, Ok(None));
+            layer.apply(&tx).await;
+
+            // Other instructions pay the fee to the house
+            let tx = Transaction::sign(&signer, 1, Instruction::CasinoToggleShield);
+            let fee = nullspace_types::casino::TRANSACTION_FEE;
+            assert_eq!(
+                layer.prepare(&tx).await,
+                Ok(Some(Event::FeeCharged {
+                    player: public.clone(),
+                    amount: fee,
+                    new_chips: 1000 - fee,
+                    accumulated_fees: fee,
+                }))
+            );
+            let house = layer.get_or_init_house().await;
+            assert_eq!(house.accumulated_fees, fee);
+
+            // Players that can't cover the fee are rejected (without consuming the nonce)
+            let mut player = match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                Some(Value::CasinoPlayer(player)) => player,
+                _ => panic!("Player not found"),
+            };
+            player.chips = nullspace_types::casino::Chips::ZERO;
+            layer.insert(
+                Key::CasinoPlayer(public.clone()),
+                Value::CasinoPlayer(player),
+            );
+            let tx = Transaction::sign(&signer, 2, Instruction::CasinoToggleShield);
+            assert_eq!(
+                layer.prepare(&tx).await,
+                Err(PrepareError::InsufficientFee {
+                    required: fee,
+                    available: 0,
+                })
+            );
+            let tx = Transaction::sign(&signer, 2, Instruction::CasinoDeposit { amount: 0 });
+            assert_eq!(layer.prepare(&tx).await, Ok(None));
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_debt_interest() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let mut state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+
+            // Borrow against collateral
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let instructions = vec![
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+                Instruction::CreateVault,
+                Instruction::DepositCollateral { amount: 500 },
+                Instruction::BorrowUSDT { amount: 100 },
+            ];
+            for (nonce, instruction) in instructions.into_iter().enumerate() {
+                let tx = Transaction::sign(&signer, nonce as u64, instruction);
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // Interest accrues once an epoch is processed (and is applied when the vault is
+            // next touched)
+            let seed = create_seed(&network_secret, 200);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(&signer, 4, Instruction::ProcessEpoch);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(
+                events[..],
+                [
+                    Event::EpochProcessed { epoch: 1 },
+                    Event::StakingRewardsIssued { amount: 0, .. }
+                ]
+            ));
+            let fees = layer.get_or_init_house().await.accumulated_fees;
+
+            let tx = Transaction::sign(&signer, 5, Instruction::RepayUSDT { amount: 0 });
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            let rate = nullspace_types::casino::DEBT_INTEREST_RATE_BPS;
+            assert_eq!(
+                events[0],
+                Event::InterestAccrued {
+                    player: public.clone(),
+                    amount: 1,
+                    new_debt: 101,
+                    effective_rate_bps: rate,
+                    rate_bps: rate,
+                }
+            );
+            let house = layer.get_or_init_house().await;
+            assert_eq!(
+                house.accumulated_fees,
+                fees + nullspace_types::casino::TRANSACTION_FEE + 1
+            );
+
+            // Interest is only applied once
+            let tx = Transaction::sign(&signer, 6, Instruction::RepayUSDT { amount: 0 });
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(
+                events[..],
+                [Event::VusdtRepaid { new_debt: 101, .. }]
+            ));
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_withdraw_collateral() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+
+            let (signer, public) = create_account_keypair(1);
+            let instructions = vec![
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+                Instruction::CreateVault,
+                Instruction::DepositCollateral { amount: 500 },
+                Instruction::BorrowUSDT { amount: 100 },
+            ];
+            for (nonce, instruction) in instructions.into_iter().enumerate() {
+                let tx = Transaction::sign(&signer, nonce as u64, instruction);
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+
+            // Can't withdraw below the max LTV (100 vUSDT of debt needs 200 RNG of collateral)
+            let tx = Transaction::sign(&signer, 4, Instruction::WithdrawCollateral { amount: 301 });
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::InstructionFailed { .. }]));
+
+            // Can withdraw down to the max LTV
+            let tx = Transaction::sign(&signer, 5, Instruction::WithdrawCollateral { amount: 300 });
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert_eq!(
+                events,
+                vec![Event::CollateralWithdrawn {
+                    player: public.clone(),
+                    amount: 300,
+                    new_collateral: 200,
+                }]
+            );
+            let chips = match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                Some(Value::CasinoPlayer(player)) => player.chips.0,
+                _ => panic!("Player not found"),
+            };
+            let fees = 5 * nullspace_types::casino::TRANSACTION_FEE;
+            assert_eq!(chips, 1000 - 500 + 300 - fees);
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_limit_orders() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let player = |value: Option<Value>| match value {
+                Some(Value::CasinoPlayer(player)) => player,
+                _ => panic!("Player not found"),
+            };
+
+            let (alice_signer, alice) = create_account_keypair(1);
+            let (bob_signer, bob) = create_account_keypair(2);
+            for signer in [&alice_signer, &bob_signer] {
+                let tx = Transaction::sign(
+                    signer,
+                    0,
+                    Instruction::CasinoRegister {
+                        name: "Player".to_string(),
+                    },
+                );
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+
+            // Seed the pool (1:1) and give Bob some vUSDT
+            layer.insert(
+                Key::AmmPool(nullspace_types::casino::PairId::RNG_VUSDT),
+                Value::AmmPool(nullspace_types::casino::AmmPool {
+                    reserve_rng: nullspace_types::casino::Chips(10_000),
+                    reserve_vusdt: nullspace_types::casino::Vusdt(10_000),
+                    ..nullspace_types::casino::AmmPool::new(30)
+                }),
+            );
+            let mut bob_player = player(layer.get(&Key::CasinoPlayer(bob.clone())).await);
+            bob_player.vusdt_balance = nullspace_types::casino::Vusdt(1_000);
+            layer.insert(
+                Key::CasinoPlayer(bob.clone()),
+                Value::CasinoPlayer(bob_player),
+            );
+
+            // Buying at ≥ 1 RNG per vUSDT isn't possible at the current price
+            let min_price = nullspace_types::casino::PRICE_SCALE as u64;
+            let tx = Transaction::sign(
+                &bob_signer,
+                1,
+                Instruction::PlaceLimitOrder {
+                    amount_in: 100,
+                    min_price,
+                    is_buying_rng: true,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert_eq!(
+                events,
+                vec![Event::LimitOrderPlaced {
+                    order_id: 0,
+                    player: bob.clone(),
+                    amount_in: 100,
+                    min_price,
+                    is_buying_rng: true,
+                }]
+            );
+            let bob_player = player(layer.get(&Key::CasinoPlayer(bob.clone())).await);
+            assert_eq!(
+                bob_player.vusdt_balance,
+                nullspace_types::casino::Vusdt(900)
+            );
+            let bob_chips = bob_player.chips.0;
+
+            // Selling RNG moves the price past the limit and fills the order
+            let tx = Transaction::sign(
+                &alice_signer,
+                1,
+                Instruction::Swap {
+                    pair: nullspace_types::casino::PairId::RNG_VUSDT,
+                    amount_in: 900,
+                    min_amount_out: 0,
+                    is_buying_rng: false,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert_eq!(events.len(), 2);
+            assert!(matches!(events[0], Event::AmmSwapped { .. }));
+            let Event::LimitOrderFilled {
+                order_id: 0,
+                amount_in: 100,
+                amount_out,
+                ..
+            } = events[1]
+            else {
+                panic!("Expected LimitOrderFilled");
+            };
+            assert!(amount_out >= 100);
+            let bob_player = player(layer.get(&Key::CasinoPlayer(bob.clone())).await);
+            assert_eq!(bob_player.chips.0, bob_chips + amount_out);
+            assert!(layer.get(&Key::LimitOrder(0)).await.is_none());
+
+            // Only the owner can cancel an open order (refunding its escrow)
+            let tx = Transaction::sign(
+                &bob_signer,
+                2,
+                Instruction::PlaceLimitOrder {
+                    amount_in: 100,
+                    min_price: u64::MAX,
+                    is_buying_rng: false,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let tx = Transaction::sign(
+                &alice_signer,
+                2,
+                Instruction::CancelLimitOrder { order_id: 1 },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::InstructionFailed { .. }]));
+
+            let tx = Transaction::sign(
+                &bob_signer,
+                3,
+                Instruction::CancelLimitOrder { order_id: 1 },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert_eq!(
+                events,
+                vec![Event::LimitOrderCancelled {
+                    order_id: 1,
+                    player: bob.clone(),
+                    refunded: 100,
+                }]
+            );
+            let fees = 2 * nullspace_types::casino::TRANSACTION_FEE;
+            let bob_player = player(layer.get(&Key::CasinoPlayer(bob.clone())).await);
+            assert_eq!(bob_player.chips.0, bob_chips + amount_out - fees);
+            assert!(layer.get(&Key::LimitOrder(1)).await.is_none());
+            match layer.get(&Key::LimitOrderBook).await {
+                Some(Value::LimitOrderBook(book)) => {
+                    assert_eq!(book.next_id, 2);
+                    assert!(book.open.is_empty());
+                }
+                _ => panic!("Order book not found"),
+            }
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_swap_price_impact() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            layer.insert(
+                Key::AmmPool(nullspace_types::casino::PairId::RNG_VUSDT),
+                Value::AmmPool(nullspace_types::casino::AmmPool {
+                    reserve_rng: nullspace_types::casino::Chips(10_000),
+                    reserve_vusdt: nullspace_types::casino::Vusdt(10_000),
+                    max_price_impact_bps: 1_000,
+                    ..nullspace_types::casino::AmmPool::new(30)
+                }),
+            );
+            let swap = |nonce, amount_in| {
+                Transaction::sign(
+                    &signer,
+                    nonce,
+                    Instruction::Swap {
+                        pair: nullspace_types::casino::PairId::RNG_VUSDT,
+                        amount_in,
+                        min_amount_out: 0,
+                        is_buying_rng: false,
+                    },
+                )
+            };
+
+            // Trades moving the price more than 10% are rejected
+            let tx = swap(1, 900);
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await.as_slice(),
+                [Event::InstructionFailed { player, kind, code }]
+                    if *player == public
+                        && *kind == tags::instruction::SWAP
+                        && *code == nullspace_types::casino::CasinoErrorCode::InvalidMove
+            ));
+
+            // Smaller trades aren't
+            let tx = swap(2, 300);
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await.as_slice(),
+                [Event::AmmSwapped { amount_in: 300, .. }]
+            ));
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_winnings_enter_defi() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            layer.insert(
+                Key::AmmPool(nullspace_types::casino::PairId::RNG_VUSDT),
+                Value::AmmPool(nullspace_types::casino::AmmPool {
+                    reserve_rng: nullspace_types::casino::Chips(1_000_000),
+                    reserve_vusdt: nullspace_types::casino::Vusdt(1_000_000),
+                    ..nullspace_types::casino::AmmPool::new(30)
+                }),
+            );
+
+            // Play (and settle) a game
+            let instructions = vec![
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::HiLo,
+                    bet: 10,
+                    session_id: nullspace_types::casino::derive_session_id(&public, 1),
+                    commitment: None,
+                },
+                Instruction::CasinoGameMove {
+                    session_id: nullspace_types::casino::derive_session_id(&public, 1),
+                    payload: vec![2],
+                },
+            ];
+            for (nonce, instruction) in instructions.into_iter().enumerate() {
+                let tx = Transaction::sign(&signer, nonce as u64, instruction);
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+            let chips = match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                Some(Value::CasinoPlayer(player)) => player.chips.0,
+                _ => panic!("player not found"),
+            };
+            assert!(chips > 0);
+
+            // Chips are the RNG balance, so the settled balance can be swapped and staked
+            // directly (without converting it first)
+            let swapped = chips / 2;
+            let tx = Transaction::sign(
+                &signer,
+                3,
+                Instruction::Swap {
+                    pair: nullspace_types::casino::PairId::RNG_VUSDT,
+                    amount_in: swapped,
+                    min_amount_out: 0,
+                    is_buying_rng: false,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await.as_slice(),
+                [Event::AmmSwapped { amount_in, .. }] if *amount_in == swapped
+            ));
+            let tx = Transaction::sign(
+                &signer,
+                4,
+                Instruction::Stake {
+                    amount: chips - swapped,
+                    duration: 10,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await.as_slice(),
+                [Event::Staked { amount, .. }] if *amount == chips - swapped
+            ));
+            match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                Some(Value::CasinoPlayer(player)) => {
+                    assert_eq!(player.chips.0, 0);
+                    assert!(player.vusdt_balance > nullspace_types::casino::Vusdt(0));
+                }
+                _ => panic!("player not found"),
+            }
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_treasury_buyback() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (admin, admin_public) = create_account_keypair(1);
+            let (other, other_public) = create_account_keypair(2);
+
+            let seed = create_seed(&network_secret, 100);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            for (signer, name) in [(&admin, "Admin"), (&other, "Other")] {
+                let tx = Transaction::sign(
+                    signer,
+                    0,
+                    Instruction::CasinoRegister {
+                        name: name.to_string(),
+                    },
+                );
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+            layer.insert(
+                Key::AmmPool(nullspace_types::casino::PairId::RNG_VUSDT),
+                Value::AmmPool(nullspace_types::casino::AmmPool {
+                    reserve_rng: nullspace_types::casino::Chips(10_000),
+                    reserve_vusdt: nullspace_types::casino::Vusdt(10_000),
+                    max_price_impact_bps: 1_000,
+                    ..nullspace_types::casino::AmmPool::new(30)
+                }),
+            );
+            layer.insert(
+                Key::Treasury,
+                Value::Treasury(nullspace_types::casino::Treasury {
+                    admin: Some(admin_public.clone()),
+                    ..Default::default()
+                }),
+            );
+
+            // A 10% share of the epoch's profit is paid into the treasury
+            let mut house = layer.get_or_init_house().await;
+            house.epoch_start_ts = 0;
+            house.net_pnl = 10_000;
+            layer.insert(Key::House, Value::House(house));
+            let tx = Transaction::sign(&admin, 1, Instruction::ProcessEpoch);
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await.as_slice(),
+                [
+                    Event::TreasuryFunded {
+                        epoch: 0,
+                        amount: 1_000,
+                        balance: 1_000,
+                    },
+                    Event::EpochProcessed { epoch: 1 },
+                    Event::StakingRewardsIssued { .. },
+                ]
+            ));
+
+            // Only the admin can spend the treasury, and only what it holds
+            let buyback = |signer, nonce, amount| {
+                Transaction::sign(signer, nonce, Instruction::ExecuteBuyback { amount })
+            };
+            let attempts = [
+                (
+                    &other,
+                    &other_public,
+                    1,
+                    100,
+                    nullspace_types::casino::CasinoErrorCode::Unauthorized,
+                ),
+                (
+                    &admin,
+                    &admin_public,
+                    2,
+                    2_000,
+                    nullspace_types::casino::CasinoErrorCode::InsufficientFunds,
+                ),
+                // Buybacks are subject to the pool's price impact limit
+                (
+                    &admin,
+                    &admin_public,
+                    3,
+                    900,
+                    nullspace_types::casino::CasinoErrorCode::InvalidMove,
+                ),
+            ];
+            for (signer, public, nonce, amount, code) in attempts {
+                let tx = buyback(signer, nonce, amount);
+                assert!(layer.prepare(&tx).await.is_ok());
+                assert_eq!(
+                    layer.apply(&tx).await,
+                    vec![Event::InstructionFailed {
+                        player: public.clone(),
+                        kind: tags::instruction::EXECUTE_BUYBACK,
+                        code,
+                    }]
+                );
+            }
+
+            // The RNG bought is burned
+            let tx = buyback(&admin, 4, 400);
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert_eq!(
+                layer.apply(&tx).await,
+                vec![Event::BuybackExecuted {
+                    amount: 400,
+                    burned: 383,
+                    balance: 600,
+                    reserve_rng: 10_000 - 383,
+                    reserve_vusdt: 10_400,
+                }]
+            );
+            let Some(Value::Treasury(treasury)) = layer.get(&Key::Treasury).await else {
+                panic!("Treasury not found");
+            };
+            assert_eq!(treasury.vusdt_balance, nullspace_types::casino::Vusdt(600));
+            assert_eq!(treasury.total_bought_back.0, 383);
+            assert_eq!(layer.get_or_init_house().await.total_burned, 383);
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_game_config() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Governor".to_string(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let set_config = |nonce, paytable| {
+                Transaction::sign(
+                    &signer,
+                    nonce,
+                    Instruction::SetGameConfig {
+                        game_type: nullspace_types::casino::GameType::Craps,
+                        config: nullspace_types::casino::GameConfig {
+                            paytable,
+                            commission_on_win: true,
+                            ..Default::default()
+                        },
+                    },
+                )
+            };
+
+            // Only governance can change the rules
+            let tx = set_config(1, 1);
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await.as_slice(),
+                [Event::CasinoError { error_code, .. }]
+                    if *error_code == nullspace_types::casino::CasinoErrorCode::Unauthorized
+            ));
+            let mut house = layer.get_or_init_house().await;
+            house.governance = Some(public.clone());
+            layer.insert(Key::House, Value::House(house));
+
+            // The rules must be offered by the game
+            let tx = set_config(2, 2);
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await.as_slice(),
+                [Event::CasinoError { error_code, .. }]
+                    if *error_code == nullspace_types::casino::CasinoErrorCode::InvalidGameConfig
+            ));
+            let tx = set_config(3, 1);
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await.as_slice(),
+                [Event::GameConfigUpdated {
+                    game_type: nullspace_types::casino::GameType::Craps,
+                    config,
+                }] if config.paytable == 1 && config.commission_on_win
+            ));
+
+            // New sessions record the rules (field paytable and buy commission timing)
+            let session_id = nullspace_types::casino::derive_session_id(&public, 4);
+            let tx = Transaction::sign(
+                &signer,
+                4,
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::Craps,
+                    bet: 0,
+                    session_id,
+                    commitment: None,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let Some(Value::CasinoSession(session)) =
+                layer.get(&Key::CasinoSession(session_id)).await
+            else {
+                panic!("Session not found");
+            };
+            assert!(session.state_blob.ends_with(&[1, 1]));
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_economy_params() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let mut state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let register = |signer, name: &str| {
+                Transaction::sign(
+                    signer,
+                    0,
+                    Instruction::CasinoRegister {
+                        name: name.to_string(),
+                    },
+                )
+            };
+            let player_key = |player: &PublicKey| Key::CasinoPlayer(player.clone());
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = register(&signer, "Governor");
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let params = nullspace_types::casino::EconomyParams {
+                initial_chips: 5_000,
+                faucet_amount: 250,
+                super_mode_fee_bps: 1_000,
+                amm_fee_bps: 50,
+                minimum_liquidity: nullspace_types::casino::LpShares(10),
+                ..Default::default()
+            };
+            let set_params = |nonce, params| {
+                Transaction::sign(&signer, nonce, Instruction::SetEconomyParams { params })
+            };
+
+            // Only governance can change the parameters
+            let tx = set_params(1, params.clone());
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await.as_slice(),
+                [Event::CasinoError { error_code, .. }]
+                    if *error_code == nullspace_types::casino::CasinoErrorCode::Unauthorized
+            ));
+            let mut house = layer.get_or_init_house().await;
+            house.governance = Some(public.clone());
+            layer.insert(Key::House, Value::House(house));
+
+            // Swap fees must be below 100%
+            let tx = set_params(
+                2,
+                nullspace_types::casino::EconomyParams {
+                    amm_fee_bps: 10_000,
+                    ..params.clone()
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await.as_slice(),
+                [Event::CasinoError { error_code, .. }]
+                    if *error_code == nullspace_types::casino::CasinoErrorCode::InvalidAmount
+            ));
+            let tx = set_params(3, params.clone());
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert_eq!(
+                layer.apply(&tx).await,
+                vec![Event::EconomyParamsUpdated {
+                    params: params.clone()
+                }]
+            );
+
+            // Players registering in the same block still get the previous parameters
+            let (signer2, public2) = create_account_keypair(2);
+            let tx = register(&signer2, "Early");
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let Some(Value::CasinoPlayer(player)) = layer.get(&player_key(&public2)).await else {
+                panic!("Player not found");
+            };
+            assert_eq!(player.chips.0, nullspace_types::casino::INITIAL_CHIPS);
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // The next block's layer loads them
+            let seed = create_seed(&network_secret, 2);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            assert_eq!(layer.economy, params);
+            let (signer3, public3) = create_account_keypair(3);
+            let tx = register(&signer3, "Late");
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let Some(Value::CasinoPlayer(player)) = layer.get(&player_key(&public3)).await else {
+                panic!("Player not found");
+            };
+            assert_eq!(player.chips.0, 5_000);
+
+            // Faucet deposits are limited to the faucet amount
+            let tx = Transaction::sign(&signer3, 1, Instruction::CasinoDeposit { amount: 251 });
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await.as_slice(),
+                [Event::CasinoError { error_code, .. }]
+                    if *error_code == nullspace_types::casino::CasinoErrorCode::InvalidAmount
+            ));
+            let tx = Transaction::sign(&signer3, 2, Instruction::CasinoDeposit { amount: 250 });
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let Some(Value::CasinoPlayer(player)) = layer.get(&player_key(&public3)).await else {
+                panic!("Player not found");
+            };
+            assert_eq!(player.chips.0, 5_250);
+            let pool = layer
+                .get_or_init_amm(nullspace_types::casino::PairId::RNG_VUSDT)
+                .await;
+            assert_eq!(pool.fee_basis_points, 50);
+        });
+    }
+
+    #[test]
+    fn test_rng_transcript() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let session_id = nullspace_types::casino::derive_session_id(&public, 2);
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Governor".to_string(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let mut house = layer.get_or_init_house().await;
+            house.governance = Some(public.clone());
+            layer.insert(Key::House, Value::House(house));
+            let tx = Transaction::sign(
+                &signer,
+                1,
+                Instruction::SetGameConfig {
+                    game_type: nullspace_types::casino::GameType::HiLo,
+                    config: nullspace_types::casino::GameConfig {
+                        record_rng: true,
+                        ..Default::default()
+                    },
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+
+            // The card dealt as the session starts is reported as move 0
+            let tx = Transaction::sign(
+                &signer,
+                2,
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::HiLo,
+                    bet: 10,
+                    session_id,
+                    commitment: None,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            let Event::CasinoGameStarted { initial_state, .. } = &events[0] else {
+                panic!("Game not started");
+            };
+            let card = initial_state[0];
+            assert_eq!(
+                events[1],
+                Event::CasinoRngTranscript {
+                    session_id,
+                    move_number: 0,
+                    draws: vec![nullspace_types::casino::RngDraw::Card(card)],
+                }
+            );
+
+            // And each card drawn by a move follows the move
+            let tx = Transaction::sign(
+                &signer,
+                3,
+                Instruction::CasinoGameMove {
+                    session_id,
+                    // Higher (or lower from a King)
+                    payload: vec![u8::from(card % 13 == 12)],
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            let Event::CasinoGameMoved { new_state, .. } = &events[0] else {
+                panic!("Move not processed");
+            };
+            assert_eq!(
+                events[1],
+                Event::CasinoRngTranscript {
+                    session_id,
+                    move_number: 1,
+                    draws: vec![nullspace_types::casino::RngDraw::Card(new_state[0])],
+                }
+            );
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_table_events() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let session_id = nullspace_types::casino::derive_session_id(&public, 1);
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Shooter".to_string(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let tx = Transaction::sign(
+                &signer,
+                1,
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::Craps,
+                    bet: 0,
+                    session_id,
+                    commitment: None,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+
+            // Place a field bet, then roll
+            let mut payload = vec![0, 4, 0];
+            payload.extend_from_slice(&100u64.to_be_bytes());
+            for (nonce, payload) in [(2, payload), (3, vec![2])] {
+                let tx = Transaction::sign(
+                    &signer,
+                    nonce,
+                    Instruction::CasinoGameMove {
+                        session_id,
+                        payload,
+                    },
+                );
+                assert!(layer.prepare(&tx).await.is_ok());
+                let events = layer.apply(&tx).await;
+                let rolled = events
+                    .iter()
+                    .find(|event| matches!(event, Event::CasinoCrapsRolled { .. }));
+                if nonce == 2 {
+                    assert!(rolled.is_none());
+                    continue;
+                }
+
+                // The roll settles the field bet
+                let Some(Event::CasinoCrapsRolled {
+                    session_id: rolled,
+                    move_number,
+                    resolved_bets,
+                    ..
+                }) = rolled
+                else {
+                    panic!("Roll not reported");
+                };
+                assert_eq!((*rolled, *move_number), (session_id, 2));
+                assert_eq!(resolved_bets.len(), 1);
+                assert_eq!(resolved_bets[0].bet_type, 4);
+                assert_eq!(resolved_bets[0].amount, 100);
+            }
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_zap_liquidity() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+
+            let (signer, public) = create_account_keypair(1);
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+
+            // Zaps need an existing pool
+            let zap = |nonce, min_shares| {
+                Transaction::sign(
+                    &signer,
+                    nonce,
+                    Instruction::ZapLiquidity {
+                        pair: nullspace_types::casino::PairId::RNG_VUSDT,
+                        amount_in: 500,
+                        min_shares,
+                        from_rng: true,
+                    },
+                )
+            };
+            let tx = zap(1, 0);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::InstructionFailed { .. }]));
+
+            layer.insert(
+                Key::AmmPool(nullspace_types::casino::PairId::RNG_VUSDT),
+                Value::AmmPool(nullspace_types::casino::AmmPool {
+                    reserve_rng: nullspace_types::casino::Chips(10_000),
+                    reserve_vusdt: nullspace_types::casino::Vusdt(10_000),
+                    total_shares: nullspace_types::casino::LpShares(10_000),
+                    ..nullspace_types::casino::AmmPool::new(30)
+                }),
+            );
+
+            // Minting less than the minimum is rejected
+            let tx = zap(2, 1_000);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::InstructionFailed { .. }]));
+
+            // About half of the RNG is swapped (a bit more to cover the fee and sell tax)
+            let tx = zap(3, 200);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            let [Event::LiquidityZapped {
+                swap_amount_in,
+                swap_amount_out,
+                burned_amount,
+                rng_amount,
+                vusdt_amount,
+                shares_minted,
+                total_shares,
+                lp_balance,
+                ..
+            }] = events[..]
+            else {
+                panic!("Expected LiquidityZapped");
+            };
+            assert_eq!(swap_amount_in + rng_amount, 500);
+            assert!(swap_amount_in > 250 && swap_amount_in < 275);
+            assert_eq!(vusdt_amount, swap_amount_out);
+            assert!(burned_amount > 0);
+            assert!(shares_minted >= 230);
+            assert_eq!(total_shares, 10_000 + shares_minted);
+            assert_eq!(lp_balance, shares_minted);
+            assert_eq!(
+                layer
+                    .get(&Key::LpBalance(
+                        nullspace_types::casino::PairId::RNG_VUSDT,
+                        public.clone()
+                    ))
+                    .await,
+                Some(Value::LpBalance(nullspace_types::casino::LpShares(
+                    shares_minted
+                )))
+            );
+            let chips = match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                Some(Value::CasinoPlayer(player)) => player.chips.0,
+                _ => panic!("Player not found"),
+            };
+            let fees = 3 * nullspace_types::casino::TRANSACTION_FEE;
+            assert_eq!(chips, 1000 - 500 - fees);
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_second_amm_pool() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let pair = nullspace_types::casino::PairId(1);
+
+            let (signer, public) = create_account_keypair(1);
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let mut player = match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                Some(Value::CasinoPlayer(player)) => player,
+                _ => panic!("Player not found"),
+            };
+            player.chips = nullspace_types::casino::Chips(100_000);
+            player.vusdt_balance = nullspace_types::casino::Vusdt(100_000);
+            layer.insert(
+                Key::CasinoPlayer(public.clone()),
+                Value::CasinoPlayer(player),
+            );
+
+            // The first deposit into the pair creates its pool
+            let tx = Transaction::sign(
+                &signer,
+                1,
+                Instruction::AddLiquidity {
+                    pair,
+                    rng_amount: 10_000,
+                    usdt_amount: 10_000,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(
+                events[..],
+                [Event::LiquidityAdded {
+                    shares_minted: 9_000,
+                    lp_balance: 9_000,
+                    ..
+                }]
+            ));
+            assert_eq!(
+                layer.get(&Key::AmmPools).await,
+                Some(Value::AmmPools(nullspace_types::casino::AmmPools {
+                    pairs: vec![pair],
+                }))
+            );
+            assert_eq!(
+                layer.get(&Key::LpBalance(pair, public.clone())).await,
+                Some(Value::LpBalance(nullspace_types::casino::LpShares(9_000)))
+            );
+
+            // Trades on the pair leave the RNG/vUSDT pool untouched
+            let tx = Transaction::sign(
+                &signer,
+                2,
+                Instruction::Swap {
+                    pair,
+                    amount_in: 100,
+                    min_amount_out: 1,
+                    is_buying_rng: true,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(
+                events[..],
+                [Event::AmmSwapped {
+                    reserve_vusdt: 10_100,
+                    ..
+                }]
+            ));
+            assert!(layer
+                .get(&Key::AmmPool(nullspace_types::casino::PairId::RNG_VUSDT))
+                .await
+                .is_none());
+
+            // Shares are only redeemable from the pool they were minted in
+            let remove = |nonce, pair| {
+                Transaction::sign(
+                    &signer,
+                    nonce,
+                    Instruction::RemoveLiquidity {
+                        pair,
+                        shares: 1_000,
+                    },
+                )
+            };
+            let tx = remove(3, nullspace_types::casino::PairId::RNG_VUSDT);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::InstructionFailed { .. }]));
+            let tx = remove(4, pair);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(
+                events[..],
+                [Event::LiquidityRemoved {
+                    lp_balance: 8_000,
+                    ..
+                }]
+            ));
+            assert!(layer
+                .get(&Key::LpBalance(
+                    nullspace_types::casino::PairId::RNG_VUSDT,
+                    public.clone()
+                ))
+                .await
+                .is_none());
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_slash() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+
+            let (alice_signer, _) = create_account_keypair(1);
+            let (bob_signer, bob) = create_account_keypair(2);
+            let register = |name: &str| Instruction::CasinoRegister {
+                name: name.to_string(),
+            };
+            let txs = vec![
+                Transaction::sign(&alice_signer, 0, register("Alice")),
+                Transaction::sign(&bob_signer, 0, register("Bob")),
+                Transaction::sign(
+                    &bob_signer,
+                    1,
+                    Instruction::Stake {
+                        amount: 500,
+                        duration: 10,
+                    },
+                ),
+            ];
+            for tx in txs {
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+
+            // Evidence must be signed by the network for the staker's next slash
+            let namespace = nullspace_types::execution::slash_namespace(TEST_NAMESPACE);
+            let evidence = |bps, index| {
+                let message = nullspace_types::execution::slash_message(&bob, bps, index);
+                ops::sign_message::<MinSig>(&network_secret, Some(&namespace), &message)
+            };
+            let slash = |nonce, bps, signature| {
+                Transaction::sign(
+                    &alice_signer,
+                    nonce,
+                    Instruction::Slash {
+                        staker: bob.clone(),
+                        bps,
+                        signature,
+                    },
+                )
+            };
+            let tx = slash(1, 2_000, evidence(1_000, 0));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::InstructionFailed { .. }]));
+
+            let signature = evidence(1_000, 0);
+            let tx = slash(2, 1_000, signature);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert_eq!(
+                events,
+                vec![Event::Slashed {
+                    player: bob.clone(),
+                    bps: 1_000,
+                    amount: 50,
+                    new_balance: 450,
+                    voting_power: 4_500,
+                }]
+            );
+            match layer.get(&Key::House).await {
+                Some(Value::House(house)) => {
+                    assert_eq!(house.total_staked_amount, 450);
+                    assert_eq!(house.total_voting_power, 4_500);
+                    assert_eq!(house.total_burned, 50);
+                }
+                _ => panic!("House not found"),
+            }
+
+            // Evidence can't be replayed
+            let tx = slash(3, 1_000, signature);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::InstructionFailed { .. }]));
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_set_admin() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+
+            let (alice_signer, alice) = create_account_keypair(1);
+            let (bob_signer, bob) = create_account_keypair(2);
+            for signer in [&alice_signer, &bob_signer] {
+                let tx = Transaction::sign(
+                    signer,
+                    0,
+                    Instruction::CasinoRegister {
+                        name: "Player".to_string(),
+                    },
+                );
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+
+            // Admin keys are set by the validator set (for the next rotation)
+            let namespace = nullspace_types::execution::admin_namespace(TEST_NAMESPACE);
+            let approval = |role, admin: &PublicKey, index| {
+                let message = nullspace_types::execution::admin_message(role, Some(admin), index);
+                ops::sign_message::<MinSig>(&network_secret, Some(&namespace), &message)
+            };
+            let set_admin = |nonce, role, admin: &PublicKey, signature| {
+                Transaction::sign(
+                    &bob_signer,
+                    nonce,
+                    Instruction::SetAdmin {
+                        role,
+                        admin: Some(admin.clone()),
+                        signature,
+                    },
+                )
+            };
+            let governance = nullspace_types::casino::AdminRole::Governance;
+            let tx = set_admin(1, governance, &alice, approval(governance, &alice, 1));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::InstructionFailed { .. }]));
+
+            let signature = approval(governance, &alice, 0);
+            let tx = set_admin(2, governance, &alice, signature);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert_eq!(
+                events,
+                vec![Event::AdminSet {
+                    role: governance,
+                    admin: Some(alice.clone()),
+                }]
+            );
+
+            // Approvals can't be replayed
+            let tx = set_admin(3, governance, &alice, signature);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::InstructionFailed { .. }]));
+
+            // Governance can then change the house settings (here, disabling the faucet)
+            let mut settings = layer.house_settings().await.settings();
+            settings.daily_bonus.faucet_enabled = false;
+            let set_settings = |signer, nonce| {
+                Transaction::sign(
+                    signer,
+                    nonce,
+                    Instruction::SetHouseSettings {
+                        settings: settings.clone(),
+                    },
+                )
+            };
+            let tx = set_settings(&bob_signer, 4);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::InstructionFailed { .. }]));
+            let tx = set_settings(&alice_signer, 1);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::HouseSettingsUpdated { .. }]));
+            let tx = Transaction::sign(&bob_signer, 5, Instruction::CasinoDeposit { amount: 10 });
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::CasinoError { .. }]));
+
+            // The KYC and treasury admins are set the same way
+            let kyc = nullspace_types::casino::AdminRole::Kyc;
+            let tx = set_admin(6, kyc, &bob, approval(kyc, &bob, 1));
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let tx = Transaction::sign(
+                &bob_signer,
+                7,
+                Instruction::SetKycStatus {
+                    player: alice.clone(),
+                    verified: true,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::KycStatusChanged { .. }]));
+
+            let treasury = nullspace_types::casino::AdminRole::Treasury;
+            let tx = set_admin(8, treasury, &bob, approval(treasury, &bob, 2));
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            assert_eq!(layer.get_treasury().await.admin, Some(bob.clone()));
+            match layer.get(&Key::House).await {
+                Some(Value::House(house)) => {
+                    assert_eq!(house.governance, Some(alice.clone()));
+                    assert_eq!(house.kyc.admin, Some(bob.clone()));
+                    assert!(!house.daily_bonus.faucet_enabled);
+                    assert_eq!(house.admin_rotations, 3);
+                }
+                _ => panic!("House not found"),
+            }
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_partial_unstake() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let mut state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+
+            // Stake twice with different lockups
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let instructions = vec![
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+                Instruction::Stake {
+                    amount: 100,
+                    duration: 10,
+                },
+                Instruction::Stake {
+                    amount: 200,
+                    duration: 100,
+                },
+            ];
+            for (nonce, instruction) in instructions.into_iter().enumerate() {
+                let tx = Transaction::sign(&signer, nonce as u64, instruction);
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // Only the first stake has unlocked
+            let seed = create_seed(&network_secret, 20);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(&signer, 3, Instruction::Unstake { amount: 150 });
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::InstructionFailed { .. }]));
+
+            let tx = Transaction::sign(&signer, 4, Instruction::Unstake { amount: 40 });
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert_eq!(
+                events,
+                vec![Event::Unstaked {
+                    player: public.clone(),
+                    amount: 40,
+                }]
+            );
+            match layer.get(&Key::Staker(public.clone())).await {
+                Some(Value::Staker(staker)) => {
+                    assert_eq!(staker.balance, nullspace_types::casino::Chips(260));
+                    assert_eq!(staker.voting_power, 600 + 20_000);
+                    assert_eq!(staker.tranches.len(), 2);
+                }
+                _ => panic!("Staker not found"),
+            }
+            let house = layer.get_or_init_house().await;
+            assert_eq!(house.total_staked_amount, 260);
+            assert_eq!(house.total_voting_power, 600 + 20_000);
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_staking_rewards() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let mut state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (alice, alice_public) = create_account_keypair(1);
+            let (bob, bob_public) = create_account_keypair(2);
+            let per_epoch =
+                nullspace_types::casino::VIEWS_PER_YEAR / nullspace_types::casino::EPOCH_LENGTH;
+
+            // A 10% rate per epoch (halving every epoch), with Bob locking three times longer
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let mut house = layer.get_or_init_house().await;
+            house.rewards = nullspace_types::casino::RewardCurve {
+                base_apr_bps: 1_000 * per_epoch,
+                decay_bps: 5_000,
+                floor_apr_bps: 0,
+            };
+            layer.insert(Key::House, Value::House(house));
+            for (signer, name, duration) in [(&alice, "Alice", 10), (&bob, "Bob", 30)] {
+                let instructions = vec![
+                    Instruction::CasinoRegister {
+                        name: name.to_string(),
+                    },
+                    Instruction::Stake {
+                        amount: 500,
+                        duration,
+                    },
+                ];
+                for (nonce, instruction) in instructions.into_iter().enumerate() {
+                    let tx = Transaction::sign(signer, nonce as u64, instruction);
+                    assert!(layer.prepare(&tx).await.is_ok());
+                    layer.apply(&tx).await;
+                }
+            }
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // Rewards are issued at the end of the epoch and split by voting power
+            let seed = create_seed(&network_secret, 200);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(&alice, 2, Instruction::ProcessEpoch);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert_eq!(
+                events,
+                vec![
+                    Event::EpochProcessed { epoch: 1 },
+                    Event::StakingRewardsIssued {
+                        epoch: 0,
+                        amount: 100,
+                        apr_bps: 1_000 * per_epoch,
+                    },
+                ]
+            );
+            let tx = Transaction::sign(&alice, 3, Instruction::ClaimRewards);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert_eq!(
+                events,
+                vec![Event::RewardsClaimed {
+                    player: alice_public.clone(),
+                    amount: 25,
+                }]
+            );
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // The rate decays, and unclaimed rewards keep accruing
+            let seed = create_seed(&network_secret, 400);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(&alice, 4, Instruction::ProcessEpoch);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(
+                events[..],
+                [
+                    Event::EpochProcessed { epoch: 2 },
+                    Event::StakingRewardsIssued {
+                        epoch: 1,
+                        amount: 50,
+                        ..
+                    }
+                ]
+            ));
+            let chips = match layer.get(&Key::CasinoPlayer(bob_public.clone())).await {
+                Some(Value::CasinoPlayer(player)) => player.chips.0,
+                _ => panic!("Player not found"),
+            };
+            let tx = Transaction::sign(&bob, 2, Instruction::ClaimRewards);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert_eq!(
+                events,
+                vec![Event::RewardsClaimed {
+                    player: bob_public.clone(),
+                    amount: 75 + 37,
+                }]
+            );
+            match layer.get(&Key::CasinoPlayer(bob_public.clone())).await {
+                Some(Value::CasinoPlayer(player)) => assert_eq!(
+                    player.chips.0,
+                    chips - nullspace_types::casino::TRANSACTION_FEE + 112
+                ),
+                _ => panic!("Player not found"),
+            }
+            match layer.get(&Key::Staker(bob_public.clone())).await {
+                Some(Value::Staker(staker)) => {
+                    assert!(staker.pending_rewards.is_zero());
+                    assert_eq!(staker.last_claim_epoch, 2);
+                }
+                _ => panic!("Staker not found"),
+            }
+            let house = layer.get_or_init_house().await;
+            assert_eq!(house.total_issuance, 25 + 112);
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_daily_bonus() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let mut state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let cooldown = nullspace_types::casino::DAILY_BONUS_COOLDOWN;
+            let amount = nullspace_types::casino::DAILY_BONUS_AMOUNT;
+
+            // The first claim is allowed immediately (but only once per cooldown)
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let register = Instruction::CasinoRegister {
+                name: "Alice".to_string(),
+            };
+            let tx = Transaction::sign(&signer, 0, register);
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let tx = Transaction::sign(&signer, 1, Instruction::ClaimDailyBonus);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert_eq!(
+                events,
+                vec![Event::DailyBonusClaimed {
+                    player: public.clone(),
+                    amount,
+                    streak: 1,
+                    new_chips: 1000 + amount,
+                    next_claim: 1 + cooldown,
+                }]
+            );
+            let tx = Transaction::sign(&signer, 2, Instruction::ClaimDailyBonus);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::CasinoError { .. }]));
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // Claiming on consecutive days grows the bonus
+            let seed = create_seed(&network_secret, 1 + cooldown);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(&signer, 3, Instruction::ClaimDailyBonus);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            let bonus = amount + amount * nullspace_types::casino::DAILY_BONUS_STREAK_BPS / 10_000;
+            assert!(matches!(
+                events[..],
+                [Event::DailyBonusClaimed { amount: a, streak: 2, .. }] if a == bonus
+            ));
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // Missing a day resets the streak
+            let seed = create_seed(&network_secret, 1 + 4 * cooldown);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(&signer, 4, Instruction::ClaimDailyBonus);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(
+                events[..],
+                [Event::DailyBonusClaimed { amount: a, streak: 1, .. }] if a == amount
+            ));
+
+            // The faucet can be disabled
+            let mut house = layer.get_or_init_house().await;
+            house.daily_bonus.faucet_enabled = false;
+            layer.insert(Key::House, Value::House(house));
+            let tx = Transaction::sign(&signer, 5, Instruction::CasinoDeposit { amount: 100 });
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::CasinoError { .. }]));
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_update_profile() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let mut state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let update = |display_name: &str, title: &str| Instruction::UpdateProfile {
+                display_name: display_name.to_string(),
+                avatar_id: 3,
+                title: title.to_string(),
+            };
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let register = Instruction::CasinoRegister {
+                name: "Alice".to_string(),
+            };
+            let tx = Transaction::sign(&signer, 0, register);
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+
+            // Invalid profiles are rejected
+            let tx = Transaction::sign(&signer, 1, update("Ace!", ""));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::CasinoError { .. }]));
+
+            // The display name is shown on the leaderboard
+            let tx = Transaction::sign(&signer, 2, update("Ace", "High Roller"));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert_eq!(
+                events,
+                vec![Event::ProfileUpdated {
+                    player: public.clone(),
+                    display_name: "Ace".to_string(),
+                    avatar_id: 3,
+                    title: "High Roller".to_string(),
+                }]
+            );
+            match layer.get(&Key::CasinoLeaderboard).await {
+                Some(Value::CasinoLeaderboard(leaderboard)) => {
+                    assert_eq!(leaderboard.entries[0].name, "Ace");
+                }
+                _ => panic!("Leaderboard not found"),
+            }
+
+            // Updates are rate limited
+            let tx = Transaction::sign(&signer, 3, update("Bob", ""));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::CasinoError { .. }]));
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            let seed = create_seed(
+                &network_secret,
+                1 + nullspace_types::casino::PROFILE_UPDATE_COOLDOWN,
+            );
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(&signer, 4, update("Bob", ""));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::ProfileUpdated { .. }]));
+            match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                Some(Value::CasinoPlayer(player)) => {
+                    assert_eq!(player.name, "Alice");
+                    assert_eq!(player.display_name(), "Bob");
+                    assert!(player.profile.title.is_empty());
+                }
+                _ => panic!("Player not found"),
+            }
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_abandon_session() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let mut state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let (other_signer, _) = create_account_keypair(2);
+            let session_id = |nonce| nullspace_types::casino::derive_session_id(&public, nonce);
+            let abandon = |nonce| Instruction::CasinoAbandonSession {
+                session_id: session_id(nonce),
+            };
+
+            // Start a table game and a card game (without moving)
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let instructions = vec![
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::Roulette,
+                    bet: 100,
+                    session_id: session_id(1),
+                    commitment: None,
+                },
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::HiLo,
+                    bet: 100,
+                    session_id: session_id(2),
+                    commitment: None,
+                },
+            ];
+            for (nonce, instruction) in instructions.into_iter().enumerate() {
+                let tx = Transaction::sign(&signer, nonce as u64, instruction);
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+
+            // Sessions can't be abandoned before they expire
+            let tx = Transaction::sign(&signer, 3, abandon(1));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::CasinoError { .. }]));
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            let seed = create_seed(&network_secret, 1 + nullspace_types::casino::SESSION_EXPIRY);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+
+            // Only the owner can abandon a session
+            let tx = Transaction::sign(
+                &other_signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Bob".to_string(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let tx = Transaction::sign(&other_signer, 1, abandon(1));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(
+                events[..],
+                [Event::CasinoError {
+                    error_code: nullspace_types::casino::CasinoErrorCode::SessionNotOwned,
+                    ..
+                }]
+            ));
+
+            // An untouched table game is refunded
+            let chips = match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                Some(Value::CasinoPlayer(player)) => player.chips.0,
+                _ => panic!("Player not found"),
+            };
+            let fee = nullspace_types::casino::TRANSACTION_FEE;
+            let tx = Transaction::sign(&signer, 4, abandon(1));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert_eq!(
+                events,
+                vec![Event::CasinoSessionExpired {
+                    session_id: session_id(1),
+                    player: public.clone(),
+                    game_type: nullspace_types::casino::GameType::Roulette,
+                    refund: 100,
+                    final_chips: chips - fee + 100,
+                }]
+            );
+
+            // A dealt card game is forfeited
+            let tx = Transaction::sign(&signer, 5, abandon(2));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(
+                events[..],
+                [Event::CasinoSessionExpired { refund: 0, .. }]
+            ));
+
+            // Abandoned sessions are complete
+            let tx = Transaction::sign(&signer, 6, abandon(2));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(
+                events[..],
+                [Event::CasinoError {
+                    error_code: nullspace_types::casino::CasinoErrorCode::SessionComplete,
+                    ..
+                }]
+            ));
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_session_ids() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let (_, other) = create_account_keypair(2);
+            let start = |nonce, session_id| {
+                Transaction::sign(
+                    &signer,
+                    nonce,
+                    Instruction::CasinoStartGame {
+                        game_type: nullspace_types::casino::GameType::HiLo,
+                        bet: 10,
+                        session_id,
+                        commitment: None,
+                    },
+                )
+            };
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+
+            // Sessions must use the ID derived from the player and (transaction) nonce
+            for (nonce, session_id) in [
+                (1, 1),
+                (2, nullspace_types::casino::derive_session_id(&public, 1)),
+                (3, nullspace_types::casino::derive_session_id(&other, 3)),
+            ] {
+                let tx = start(nonce, session_id);
+                assert!(layer.prepare(&tx).await.is_ok());
+                assert!(matches!(
+                    layer.apply(&tx).await[..],
+                    [Event::CasinoError {
+                        error_code: nullspace_types::casino::CasinoErrorCode::InvalidSessionId,
+                        ..
+                    }]
+                ));
+            }
+            assert!(layer.get(&Key::CasinoSession(1)).await.is_none());
+            let tx = start(4, nullspace_types::casino::derive_session_id(&public, 4));
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await[..],
+                [Event::CasinoGameStarted { session_id, .. }]
+                    if session_id == nullspace_types::casino::derive_session_id(&public, 4)
+            ));
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_concurrent_sessions() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let session_id = |nonce| nullspace_types::casino::derive_session_id(&public, nonce);
+            let start = |nonce| Instruction::CasinoStartGame {
+                game_type: nullspace_types::casino::GameType::HiLo,
+                bet: 10,
+                session_id: session_id(nonce),
+                commitment: None,
+            };
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+
+            // Sessions can be opened up to the house limit
+            let limit = nullspace_types::casino::DEFAULT_MAX_ACTIVE_SESSIONS as u64;
+            for nonce in 1..=limit {
+                let tx = Transaction::sign(&signer, nonce, start(nonce));
+                assert!(layer.prepare(&tx).await.is_ok());
+                let events = layer.apply(&tx).await;
+                assert!(matches!(events[..], [Event::CasinoGameStarted { .. }]));
+            }
+            let mut nonce = limit + 1;
+            let tx = Transaction::sign(&signer, nonce, start(nonce));
+            nonce += 1;
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(
+                events[..],
+                [Event::CasinoError {
+                    error_code: nullspace_types::casino::CasinoErrorCode::SessionLimitReached,
+                    ..
+                }]
+            ));
+            let active_sessions = |player: Option<Value>| match player {
+                Some(Value::CasinoPlayer(player)) => player.active_sessions,
+                _ => panic!("Player not found"),
+            };
+            assert_eq!(
+                active_sessions(layer.get(&Key::CasinoPlayer(public.clone())).await),
+                (1..=limit).map(session_id).collect::<Vec<_>>()
+            );
+
+            // Completing a session frees its slot
+            let tx = Transaction::sign(
+                &signer,
+                nonce,
+                Instruction::CasinoGameMove {
+                    session_id: session_id(2),
+                    payload: vec![2], // Cashout
+                },
+            );
+            nonce += 1;
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(
+                events[..],
+                [
+                    Event::CasinoGameMoved { .. },
+                    Event::CasinoGameCompleted { .. }
+                ]
+            ));
+            let sessions = active_sessions(layer.get(&Key::CasinoPlayer(public.clone())).await);
+            assert!(!sessions.contains(&session_id(2)));
+            let tx = Transaction::sign(&signer, nonce, start(nonce));
+            nonce += 1;
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::CasinoGameStarted { .. }]));
+
+            // The limit is a house parameter
+            let mut house = layer.get_or_init_house().await;
+            house.max_active_sessions = 1;
+            layer.insert(Key::House, Value::House(house));
+            let tx = Transaction::sign(&signer, nonce, start(nonce));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(
+                events[..],
+                [Event::CasinoError {
+                    error_code: nullspace_types::casino::CasinoErrorCode::SessionLimitReached,
+                    ..
+                }]
+            ));
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_scheduled_tournament() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let mut state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let schedule = |start_view, end_view| Instruction::CasinoScheduleTournament {
+                tournament_id: 1,
+                start_view,
+                end_view,
+            };
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+
+            // Schedules must start in the future
+            let tx = Transaction::sign(&signer, 1, schedule(1, 20));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::CasinoError { .. }]));
+
+            let tx = Transaction::sign(&signer, 2, schedule(10, 20));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert_eq!(
+                events,
+                vec![Event::TournamentScheduled {
+                    id: 1,
+                    start_view: 10,
+                    end_view: 20,
+                }]
+            );
+            let tx = Transaction::sign(
+                &signer,
+                3,
+                Instruction::CasinoJoinTournament { tournament_id: 1 },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::PlayerJoined { .. }]));
+            assert!(layer.process_tournament_schedules().await.is_empty());
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // The tournament starts in the first block at (or after) its start view
+            let seed = create_seed(&network_secret, 12);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let events = layer.process_tournament_schedules().await;
+            assert!(matches!(
+                events[..],
+                [
+                    Event::TournamentPhaseChanged {
+                        id: 1,
+                        phase: nullspace_types::casino::TournamentPhase::Active,
+                    },
+                    Event::TournamentStarted { id: 1, .. },
+                ]
+            ));
+            match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                Some(Value::CasinoPlayer(player)) => {
+                    assert_eq!(player.active_tournament, Some(1));
+                    assert_eq!(
+                        player.tournament_chips.0,
+                        nullspace_types::casino::STARTING_CHIPS
+                    );
+                }
+                _ => panic!("player not found"),
+            }
+
+            // Scheduled tournaments can't be ended early
+            let tx = Transaction::sign(
+                &signer,
+                4,
+                Instruction::CasinoEndTournament { tournament_id: 1 },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(layer.apply(&tx).await.is_empty());
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // ...and end in the first block at (or after) their end view
+            let seed = create_seed(&network_secret, 25);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let events = layer.process_tournament_schedules().await;
+            assert!(matches!(
+                events[..],
+                [
+                    Event::TournamentPhaseChanged {
+                        id: 1,
+                        phase: nullspace_types::casino::TournamentPhase::Complete,
+                    },
+                    Event::TournamentPrizePaid {
+                        tournament_id: 1,
+                        rank: 1,
+                        ..
+                    },
+                    Event::TournamentEnded { id: 1, .. },
+                ]
+            ));
+            match layer.get(&Key::Tournament(1)).await {
+                Some(Value::Tournament(tournament)) => assert_eq!(
+                    tournament.phase,
+                    nullspace_types::casino::TournamentPhase::Complete
+                ),
+                _ => panic!("tournament not found"),
+            }
+            match layer.get(&Key::TournamentSchedules).await {
+                Some(Value::TournamentSchedules(schedules)) => assert!(schedules.ids.is_empty()),
+                _ => panic!("schedules not found"),
+            }
+            assert!(layer.process_tournament_schedules().await.is_empty());
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_tournament_payouts() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let players: Vec<_> = (1..=4).map(create_account_keypair).collect();
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            for (i, (signer, _)) in players.iter().enumerate() {
+                let instructions = [
+                    Instruction::CasinoRegister {
+                        name: format!("Player{i}"),
+                    },
+                    Instruction::CasinoJoinTournament { tournament_id: 1 },
+                ];
+                for (nonce, instruction) in instructions.into_iter().enumerate() {
+                    let tx = Transaction::sign(signer, nonce as u64, instruction);
+                    assert!(layer.prepare(&tx).await.is_ok());
+                    layer.apply(&tx).await;
+                }
+            }
+            let (signer, _) = &players[0];
+            let tx = Transaction::sign(
+                signer,
+                2,
+                Instruction::CasinoStartTournament {
+                    tournament_id: 1,
+                    start_time_ms: 0,
+                    end_time_ms: 0,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let prize_pool = match layer.get(&Key::Tournament(1)).await {
+                Some(Value::Tournament(tournament)) => tournament.prize_pool,
+                _ => panic!("tournament not found"),
+            };
+            assert!(prize_pool > 0);
+
+            // Give the players distinct results (the fourth finishes outside the paid places)
+            for (i, (_, public)) in players.iter().enumerate() {
+                let Some(Value::CasinoPlayer(mut player)) =
+                    layer.get(&Key::CasinoPlayer(public.clone())).await
+                else {
+                    panic!("player not found");
+                };
+                player.tournament_chips = nullspace_types::casino::Chips(100 * (i as u64 + 1));
+                layer.insert(
+                    Key::CasinoPlayer(public.clone()),
+                    Value::CasinoPlayer(player),
+                );
+            }
+            let chips_before = {
+                let mut chips = Vec::new();
+                for (_, public) in &players {
+                    match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                        Some(Value::CasinoPlayer(player)) => chips.push(player.chips.0),
+                        _ => panic!("player not found"),
+                    }
+                }
+                chips
+            };
+
+            let tx = Transaction::sign(
+                signer,
+                3,
+                Instruction::CasinoEndTournament { tournament_id: 1 },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            let expected =
+                nullspace_types::casino::TournamentPayouts::default().split(prize_pool, 4);
+            let paid: Vec<_> = events
+                .iter()
+                .filter_map(|event| match event {
+                    Event::TournamentPrizePaid {
+                        player,
+                        rank,
+                        amount,
+                        ..
+                    } => Some((player.clone(), *rank, *amount)),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(
+                paid,
+                vec![
+                    (players[3].1.clone(), 1, expected[0]),
+                    (players[2].1.clone(), 2, expected[1]),
+                    (players[1].1.clone(), 3, expected[2]),
+                ]
+            );
+            assert_eq!(expected.iter().sum::<u64>(), prize_pool);
+            assert!(matches!(events.last(), Some(Event::TournamentEnded { .. })));
+
+            // Prizes are credited to the real bankroll (the first player also paid the end fee)
+            for (i, (_, public)) in players.iter().enumerate() {
+                let prize = paid
+                    .iter()
+                    .find(|(player, _, _)| player == public)
+                    .map_or(0, |(_, _, amount)| *amount);
+                let fee = if i == 0 {
+                    nullspace_types::casino::TRANSACTION_FEE
+                } else {
+                    0
+                };
+                match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                    Some(Value::CasinoPlayer(player)) => {
+                        assert_eq!(player.chips.0, chips_before[i] + prize - fee)
+                    }
+                    _ => panic!("player not found"),
+                }
+            }
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_tournament_rules() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Organizer".to_string(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let mut house = layer.get_or_init_house().await;
+            house.governance = Some(public.clone());
+            layer.insert(Key::House, Value::House(house));
+
+            // Rules are set while the tournament is registering
+            let rules = nullspace_types::casino::TournamentRules {
+                min_bet: 10,
+                max_bet: 50,
+                disabled_games: vec![nullspace_types::casino::GameType::Roulette],
+                game_configs: vec![(
+                    nullspace_types::casino::GameType::Craps,
+                    nullspace_types::casino::GameConfig {
+                        paytable: 1,
+                        commission_on_win: true,
+                        ..Default::default()
+                    },
+                )],
+                super_boost_bps: 5_000,
+            };
+            let tx = Transaction::sign(
+                &signer,
+                1,
+                Instruction::SetTournamentRules {
+                    tournament_id: 1,
+                    rules: rules.clone(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert_eq!(
+                layer.apply(&tx).await,
+                vec![Event::TournamentRulesSet {
+                    tournament_id: 1,
+                    rules: rules.clone(),
+                }]
+            );
+            let instructions = [
+                Instruction::CasinoJoinTournament { tournament_id: 1 },
+                Instruction::CasinoStartTournament {
+                    tournament_id: 1,
+                    start_time_ms: 0,
+                    end_time_ms: 0,
+                },
+            ];
+            for (nonce, instruction) in instructions.into_iter().enumerate() {
+                let tx = Transaction::sign(&signer, 2 + nonce as u64, instruction);
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+            let tx = Transaction::sign(
+                &signer,
+                4,
+                Instruction::SetTournamentRules {
+                    tournament_id: 1,
+                    rules: Default::default(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await.as_slice(),
+                [Event::CasinoError { error_code, .. }]
+                    if *error_code == nullspace_types::casino::CasinoErrorCode::TournamentNotRegistering
+            ));
+
+            // Tournament sessions are held to the tournament's games and bets
+            let start = |nonce, game_type, bet| {
+                Transaction::sign(
+                    &signer,
+                    nonce,
+                    Instruction::CasinoStartGame {
+                        game_type,
+                        bet,
+                        session_id: nullspace_types::casino::derive_session_id(&public, nonce),
+                        commitment: None,
+                    },
+                )
+            };
+            for (nonce, game_type, bet, expected) in [
+                (
+                    5,
+                    nullspace_types::casino::GameType::Roulette,
+                    0,
+                    nullspace_types::casino::CasinoErrorCode::GameDisabled,
+                ),
+                (
+                    6,
+                    nullspace_types::casino::GameType::HiLo,
+                    5,
+                    nullspace_types::casino::CasinoErrorCode::InvalidBet,
+                ),
+                (
+                    7,
+                    nullspace_types::casino::GameType::HiLo,
+                    100,
+                    nullspace_types::casino::CasinoErrorCode::BetLimitExceeded,
+                ),
+            ] {
+                let tx = start(nonce, game_type, bet);
+                assert!(layer.prepare(&tx).await.is_ok());
+                assert!(matches!(
+                    layer.apply(&tx).await.as_slice(),
+                    [Event::CasinoError { error_code, .. }] if *error_code == expected
+                ));
+            }
+
+            // And use the tournament's table rules
+            let tx = start(8, nullspace_types::casino::GameType::Craps, 0);
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let session_id = nullspace_types::casino::derive_session_id(&public, 8);
+            let Some(Value::CasinoSession(session)) =
+                layer.get(&Key::CasinoSession(session_id)).await
+            else {
+                panic!("Session not found");
+            };
+            assert!(session.is_tournament);
+            assert!(session.state_blob.ends_with(&[1, 1]));
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_leaderboard_seasons() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let mut state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let players: Vec<_> = (1..=2).map(create_account_keypair).collect();
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let mut house = layer.get_or_init_house().await;
+            house.seasons.length = 10;
+            house.seasons.reward = 1_000;
+            layer.insert(Key::House, Value::House(house));
+            for (i, (signer, _)) in players.iter().enumerate() {
+                let instructions = [
+                    Instruction::CasinoRegister {
+                        name: format!("Player{i}"),
+                    },
+                    Instruction::CasinoDeposit {
+                        amount: 100 * (i as u64 + 1),
+                    },
+                ];
+                for (nonce, instruction) in instructions.into_iter().enumerate() {
+                    let tx = Transaction::sign(signer, nonce as u64, instruction);
+                    assert!(layer.prepare(&tx).await.is_ok());
+                    layer.apply(&tx).await;
+                }
+            }
+
+            // The first season started with the first leaderboard update
+            match layer.get(&Key::CasinoLeaderboard).await {
+                Some(Value::CasinoLeaderboard(leaderboard)) => {
+                    assert_eq!(leaderboard.season, 0);
+                    assert_eq!(leaderboard.season_end, 11);
+                    assert_eq!(leaderboard.entries.len(), 2);
+                }
+                _ => panic!("leaderboard not found"),
+            }
+            assert!(layer.process_leaderboard_season().await.is_empty());
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // The season ends in the first block at (or after) its end view
+            let seed = create_seed(&network_secret, 15);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let chips_before = {
+                let mut chips = Vec::new();
+                for (_, public) in &players {
+                    match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                        Some(Value::CasinoPlayer(player)) => chips.push(player.chips.0),
+                        _ => panic!("player not found"),
+                    }
+                }
+                chips
+            };
+            let events = layer.process_leaderboard_season().await;
+            let expected = nullspace_types::casino::TournamentPayouts::default().split(1_000, 2);
+            assert_eq!(
+                events,
+                vec![
+                    Event::SeasonRewardPaid {
+                        season: 0,
+                        player: players[1].1.clone(),
+                        rank: 1,
+                        amount: expected[0],
+                    },
+                    Event::SeasonRewardPaid {
+                        season: 0,
+                        player: players[0].1.clone(),
+                        rank: 2,
+                        amount: expected[1],
+                    },
+                    Event::LeaderboardSeasonEnded {
+                        season: 0,
+                        next_season_end: 25,
+                    },
+                ]
+            );
+            for (i, (_, public)) in players.iter().enumerate() {
+                match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                    Some(Value::CasinoPlayer(player)) => {
+                        assert_eq!(player.chips.0, chips_before[i] + expected[1 - i])
+                    }
+                    _ => panic!("player not found"),
+                }
+            }
+
+            // The standings are archived and the next season starts empty
+            match layer.get(&Key::LeaderboardSeason(0)).await {
+                Some(Value::CasinoLeaderboard(leaderboard)) => {
+                    assert_eq!(leaderboard.entries.len(), 2);
+                    assert_eq!(leaderboard.entries[0].player, players[1].1);
+                }
+                _ => panic!("season not archived"),
+            }
+            match layer.get(&Key::CasinoLeaderboard).await {
+                Some(Value::CasinoLeaderboard(leaderboard)) => {
+                    assert!(leaderboard.entries.is_empty());
+                    assert_eq!(leaderboard.season, 1);
+                    assert_eq!(leaderboard.season_end, 25);
+                }
+                _ => panic!("leaderboard not found"),
+            }
+            assert!(layer.process_leaderboard_season().await.is_empty());
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_execution() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let mut state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let signers: Vec<_> = (1..=4).map(create_account_keypair).collect();
+
+            // Register players (with a session in progress each)
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            for (i, (signer, public)) in signers.iter().enumerate() {
+                let instructions = vec![
+                    Instruction::CasinoRegister {
+                        name: format!("Player{i}"),
+                    },
+                    Instruction::CasinoStartGame {
+                        game_type: nullspace_types::casino::GameType::HiLo,
+                        bet: 10,
+                        session_id: nullspace_types::casino::derive_session_id(public, 1),
+                        commitment: None,
+                    },
+                ];
+                for (nonce, instruction) in instructions.into_iter().enumerate() {
+                    let tx = Transaction::sign(signer, nonce as u64, instruction);
+                    assert!(layer.prepare(&tx).await.is_ok());
+                    layer.apply(&tx).await;
+                }
+            }
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // Independent moves, dependent transactions (from the same player) and an
+            // invalid nonce
+            let cashout = |player: usize| Instruction::CasinoGameMove {
+                session_id: nullspace_types::casino::derive_session_id(&signers[player].1, 1),
+                payload: vec![2],
+            };
+            let transactions = vec![
+                Transaction::sign(&signers[0].0, 2, cashout(0)),
+                Transaction::sign(&signers[1].0, 2, cashout(1)),
+                Transaction::sign(
+                    &signers[0].0,
+                    3,
+                    Instruction::CasinoStartGame {
+                        game_type: nullspace_types::casino::GameType::HiLo,
+                        bet: 20,
+                        session_id: nullspace_types::casino::derive_session_id(&signers[0].1, 3),
+                        commitment: None,
+                    },
+                ),
+                Transaction::sign(&signers[2].0, 2, cashout(2)),
+                Transaction::sign(&signers[3].0, 5, cashout(3)),
+                Transaction::sign(&signers[3].0, 2, Instruction::CasinoDeposit { amount: 100 }),
+            ];
+
+            // Parallel execution matches applying the transactions one after another
+            let seed = create_seed(&network_secret, 2);
+            let pool = ThreadPool::new(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(4)
+                    .build()
+                    .unwrap(),
+            );
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed.clone()).await;
+            let (outputs, nonces) = layer.execute(pool, transactions.clone()).await;
+            let changes = layer.commit();
+
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            layer.update_oracle().await;
+            assert!(layer.process_tournament_schedules().await.is_empty());
+            assert!(layer.process_leaderboard_season().await.is_empty());
+            layer.reclaim_expired_sessions().await;
+            let mut expected_outputs = Vec::new();
+            let mut expected_nonces = BTreeMap::new();
+            for tx in transactions {
+                let Ok(fee) = layer.prepare(&tx).await else {
+                    continue;
+                };
+                expected_nonces.insert(tx.public.clone(), tx.nonce + 1);
+                expected_outputs.extend(fee.map(Output::Event));
+                expected_outputs.extend(layer.apply(&tx).await.into_iter().map(Output::Event));
+                expected_outputs.push(Output::Transaction(tx));
+            }
+            let expected_changes = layer.commit();
+
+            assert_eq!(outputs, expected_outputs);
+            assert_eq!(nonces, expected_nonces);
+            assert_eq!(nonces.len(), 4);
+            let encode = |changes: Vec<(Key, Status)>| -> Vec<_> {
+                changes
+                    .into_iter()
+                    .map(|(key, status)| (key, status.encode()))
+                    .collect()
+            };
+            assert_eq!(encode(changes), encode(expected_changes));
+        });
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_house_counters() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let mut state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let signers: Vec<_> = (1..=4).map(create_account_keypair).collect();
+
+            // Register players (with a session in progress each)
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            for (i, (signer, public)) in signers.iter().enumerate() {
+                let instructions = vec![
+                    Instruction::CasinoRegister {
+                        name: format!("Player{i}"),
+                    },
+                    Instruction::CasinoStartGame {
+                        game_type: nullspace_types::casino::GameType::HiLo,
+                        bet: 10,
+                        session_id: nullspace_types::casino::derive_session_id(public, 1),
+                        commitment: None,
+                    },
+                ];
+                for (nonce, instruction) in instructions.into_iter().enumerate() {
+                    let tx = Transaction::sign(signer, nonce as u64, instruction);
+                    assert!(layer.prepare(&tx).await.is_ok());
+                    layer.apply(&tx).await;
+                }
+            }
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // A stake (updating the house counters) followed by independent moves
+            let mut transactions = vec![Transaction::sign(
+                &signers[0].0,
+                2,
+                Instruction::Stake {
+                    amount: 50,
+                    duration: 10,
+                },
+            )];
+            for (signer, public) in &signers[1..] {
+                transactions.push(Transaction::sign(
+                    signer,
+                    2,
+                    Instruction::CasinoGameMove {
+                        session_id: nullspace_types::casino::derive_session_id(public, 1),
+                        payload: vec![2],
+                    },
+                ));
+            }
+
+            // The moves only read the house settings, so none of them is re-executed
+            let seed = create_seed(&network_secret, 2);
+            let pool = ThreadPool::new(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(4)
+                    .build()
+                    .unwrap(),
+            );
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed.clone()).await;
+            let mut outputs = Vec::new();
+            let mut nonces = BTreeMap::new();
+            let reexecuted = layer
+                .execute_parallel(pool, transactions.clone(), &mut outputs, &mut nonces)
+                .await;
+            assert_eq!(reexecuted, 0);
+            assert_eq!(nonces.len(), 4);
+            let changes = layer.commit();
+
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let mut expected_outputs = Vec::new();
+            for tx in transactions {
+                let fee = layer.prepare(&tx).await.unwrap();
+                expected_outputs.extend(fee.map(Output::Event));
+                expected_outputs.extend(layer.apply(&tx).await.into_iter().map(Output::Event));
+                expected_outputs.push(Output::Transaction(tx));
+            }
+            let expected_changes = layer.commit();
+
+            assert_eq!(outputs, expected_outputs);
+            let encode = |changes: Vec<(Key, Status)>| -> Vec<_> {
+                changes
+                    .into_iter()
+                    .map(|(key, status)| (key, status.encode()))
+                    .collect()
+            };
+            assert_eq!(encode(changes), encode(expected_changes));
+        });
+    }
+
+    #[test]
+    fn test_compute_limits() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let session_id = nullspace_types::casino::derive_session_id(&public, 1);
+            let place_red = |amount: u64| {
+                let mut payload = vec![0, 1, 0];
+                payload.extend_from_slice(&amount.to_be_bytes());
+                Instruction::CasinoGameMove {
+                    session_id,
+                    payload,
+                }
+            };
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let instructions = vec![
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::Roulette,
+                    bet: 0,
+                    session_id,
+                    commitment: None,
+                },
+                place_red(10),
+                place_red(10),
+            ];
+            for (nonce, instruction) in instructions.into_iter().enumerate() {
+                let tx = Transaction::sign(&signer, nonce as u64, instruction);
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+
+            // Moves are charged for the bets held by their session
+            let spin = Transaction::sign(
+                &signer,
+                4,
+                Instruction::CasinoGameMove {
+                    session_id,
+                    payload: vec![1],
+                },
+            );
+            assert_eq!(
+                layer.compute_units(&spin).await,
+                spin.instruction.compute_units() + 2 * COMPUTE_UNITS_PER_BET
+            );
+
+            // Transactions over the limit are rejected (without consuming their nonce)
+            let batch = Instruction::Batch(vec![
+                Instruction::CasinoGameMove {
+                    session_id,
+                    payload: vec![0; nullspace_types::casino::MAX_PAYLOAD_LENGTH],
+                };
+                nullspace_types::execution::MAX_BATCH_INSTRUCTIONS
+            ]);
+            let tx = Transaction::sign(&signer, 4, batch);
+            assert!(matches!(
+                layer.prepare(&tx).await,
+                Err(PrepareError::ComputeLimitExceeded { units, limit })
+                    if units > limit && limit == MAX_TRANSACTION_COMPUTE_UNITS
+            ));
+            assert!(layer.prepare(&spin).await.is_ok());
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_loyalty_points() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let session_id = |nonce| nullspace_types::casino::derive_session_id(&public, nonce);
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let mut house = layer.get_or_init_house().await;
+            house.vip = nullspace_types::casino::VipSettings {
+                points_bps: 10_000,
+                tiers: vec![nullspace_types::casino::VipTier {
+                    points: 10,
+                    rakeback_bps: 5_000,
+                    shields: 2,
+                }],
+            };
+            layer.insert(Key::House, Value::House(house));
+
+            let instructions = vec![
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::HiLo,
+                    bet: 10,
+                    session_id: session_id(1),
+                    commitment: None,
+                },
+            ];
+            for (nonce, instruction) in instructions.into_iter().enumerate() {
+                let tx = Transaction::sign(&signer, nonce as u64, instruction);
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+
+            // Completing a session earns points (reaching the first tier grants its shields)
+            let tx = Transaction::sign(
+                &signer,
+                2,
+                Instruction::CasinoGameMove {
+                    session_id: session_id(1),
+                    payload: vec![2], // Cashout
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(events.contains(&Event::LoyaltyEarned {
+                player: public.clone(),
+                points: 10,
+                total_points: 10,
+                rakeback: 0,
+            }));
+            assert!(events.contains(&Event::TierChanged {
+                player: public.clone(),
+                tier: 1,
+                shields: 2,
+            }));
+            let chips = match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                Some(Value::CasinoPlayer(player)) => {
+                    assert_eq!(player.total_wagered, 10);
+                    assert_eq!(player.vip_tier, 1);
+                    assert_eq!(
+                        player.shields,
+                        nullspace_types::casino::STARTING_SHIELDS + 2
+                    );
+                    player.chips.0
+                }
+                _ => panic!("Player not found"),
+            };
+
+            // Losses earn rakeback at the player's tier
+            let mut payload = vec![0, 0, 0]; // Straight up on zero
+            payload.extend_from_slice(&100u64.to_be_bytes());
+            let instructions = vec![
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::Roulette,
+                    bet: 0,
+                    session_id: session_id(3),
+                    commitment: None,
+                },
+                Instruction::CasinoGameMove {
+                    session_id: session_id(3),
+                    payload,
+                },
+                Instruction::CasinoGameMove {
+                    session_id: session_id(3),
+                    payload: vec![1], // Spin
+                },
+            ];
+            let mut results = Vec::new();
+            for (nonce, instruction) in instructions.into_iter().enumerate() {
+                let tx = Transaction::sign(&signer, 3 + nonce as u64, instruction);
+                assert!(layer.prepare(&tx).await.is_ok());
+                results.push(layer.apply(&tx).await);
+            }
+            let events = results.pop().unwrap();
+            let payout = events
+                .iter()
+                .find_map(|event| match event {
+                    Event::CasinoGameCompleted { payout, .. } => Some(*payout),
+                    _ => None,
+                })
+                .expect("Session not completed");
+            let rakeback = if payout < 0 {
+                payout.unsigned_abs() / 2
+            } else {
+                0
+            };
+            assert!(events.contains(&Event::LoyaltyEarned {
+                player: public.clone(),
+                points: 100,
+                total_points: 110,
+                rakeback,
+            }));
+            match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                Some(Value::CasinoPlayer(player)) => {
+                    let returned = u64::try_from(payout).unwrap_or(0);
+                    assert_eq!(
+                        player.chips.0,
+                        chips - 100 - 3 * nullspace_types::casino::TRANSACTION_FEE
+                            + returned
+                            + rakeback
+                    );
+                    assert_eq!(player.total_wagered, 110);
+                }
+                _ => panic!("Player not found"),
+            }
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_insurance_fund() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let mut house = layer.get_or_init_house().await;
+            house.insurance = nullspace_types::casino::InsuranceFund {
+                balance: nullspace_types::casino::Chips(100),
+                funding_bps: 5_000,
+                threshold: 50,
+            };
+            layer.insert(Key::House, Value::House(house));
+            let start = layer.house_settings().await;
+
+            // Nothing happens while the house is within the threshold
+            assert!(layer
+                .process_insurance(&start, nullspace_types::casino::Chips::ZERO)
+                .await
+                .is_empty());
+
+            // Half of the fees and sell tax collected are paid into the fund and the fund
+            // covers losses beyond the threshold
+            let mut house = layer.get_or_init_house().await;
+            house.accumulated_fees += 40;
+            house.total_burned += 20;
+            house.net_pnl = -500;
+            layer.insert(Key::House, Value::House(house));
+            assert_eq!(
+                layer
+                    .process_insurance(&start, nullspace_types::casino::Chips::ZERO)
+                    .await,
+                vec![
+                    Event::InsuranceFunded {
+                        amount: 30,
+                        balance: 130,
+                    },
+                    Event::InsuranceDrawn {
+                        amount: 130,
+                        balance: 0,
+                        net_pnl: -370,
+                    },
+                ]
+            );
+            let house = layer.get_or_init_house().await;
+            assert_eq!(house.accumulated_fees, 20);
+            assert_eq!(house.total_burned, 10);
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_instruction_failed() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let swap = Instruction::Swap {
+                pair: nullspace_types::casino::PairId::RNG_VUSDT,
+                amount_in: 0,
+                min_amount_out: 0,
+                is_buying_rng: false,
+            };
+
+            // Rejected instructions explain why
+            let failed = |kind, code| {
+                vec![Event::InstructionFailed {
+                    player: public.clone(),
+                    kind,
+                    code,
+                }]
+            };
+            let instructions = vec![
+                (
+                    swap.clone(),
+                    failed(
+                        tags::instruction::SWAP,
+                        nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+                    ),
+                ),
+                (
+                    Instruction::Unstake { amount: 10 },
+                    failed(
+                        tags::instruction::UNSTAKE,
+                        nullspace_types::casino::CasinoErrorCode::NotFound,
+                    ),
+                ),
+            ];
+            for (nonce, (instruction, expected)) in instructions.into_iter().enumerate() {
+                let tx = Transaction::sign(&signer, 1 + nonce as u64, instruction);
+                assert!(layer.prepare(&tx).await.is_ok());
+                assert_eq!(layer.apply(&tx).await, expected);
+            }
+
+            // And abort the batch they're in
+            let tx = Transaction::sign(
+                &signer,
+                3,
+                Instruction::Batch(vec![Instruction::CasinoToggleShield, swap]),
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert_eq!(
+                layer.apply(&tx).await,
+                failed(
+                    tags::instruction::SWAP,
+                    nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+                )
+            );
+            match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                Some(Value::CasinoPlayer(player)) => assert!(!player.active_shield),
+                _ => panic!("Player not found"),
+            }
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_account_recovery() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let mut state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let session_id = nullspace_types::casino::derive_session_id(&public, 1);
+            let (guardian_a, public_a) = create_account_keypair(2);
+            let (guardian_b, public_b) = create_account_keypair(3);
+            let (new_signer, new_key) = create_account_keypair(4);
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let set_guardians = Instruction::SetGuardians {
+                guardians: vec![public_a.clone(), public_b.clone()],
+                threshold: 2,
+            };
+            let instructions = vec![
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::HiLo,
+                    bet: 10,
+                    session_id,
+                    commitment: None,
+                },
+                set_guardians.clone(),
+            ];
+            for (nonce, instruction) in instructions.into_iter().enumerate() {
+                let tx = Transaction::sign(&signer, nonce as u64, instruction);
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+            let recover = Instruction::RecoverAccount {
+                account: public.clone(),
+                new_key: new_key.clone(),
+            };
+            let approved = |guardian: &PublicKey, approvals, ready_view| {
+                vec![Event::RecoveryApproved {
+                    player: public.clone(),
+                    guardian: guardian.clone(),
+                    new_key: new_key.clone(),
+                    approvals,
+                    ready_view,
+                }]
+            };
+
+            // Only guardians can approve a recovery
+            let tx = Transaction::sign(&new_signer, 0, recover.clone());
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await[..],
+                [Event::InstructionFailed { code, .. }]
+                    if code == nullspace_types::casino::CasinoErrorCode::Unauthorized
+            ));
+
+            // The owner cancels a recovery by setting their guardians again
+            let tx = Transaction::sign(&guardian_a, 0, recover.clone());
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert_eq!(layer.apply(&tx).await, approved(&public_a, 1, 0));
+            let tx = Transaction::sign(&signer, 3, set_guardians);
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            match layer.get(&Key::Guardians(public.clone())).await {
+                Some(Value::Guardians(set)) => assert_eq!(set.recovery, None),
+                _ => panic!("Guardians not found"),
+            }
+
+            // Once enough guardians approve, the owner has a challenge window
+            let ready_view = 1 + nullspace_types::casino::RECOVERY_DELAY;
+            let tx = Transaction::sign(&guardian_a, 1, recover.clone());
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert_eq!(layer.apply(&tx).await, approved(&public_a, 1, 0));
+            let tx = Transaction::sign(&guardian_b, 0, recover.clone());
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert_eq!(layer.apply(&tx).await, approved(&public_b, 2, ready_view));
+            let tx = Transaction::sign(&guardian_a, 2, recover.clone());
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await[..],
+                [Event::InstructionFailed { code, .. }]
+                    if code == nullspace_types::casino::CasinoErrorCode::RateLimited
+            ));
+
+            // A single guardian can't replace it with another key
+            let tx = Transaction::sign(
+                &guardian_b,
+                1,
+                Instruction::RecoverAccount {
+                    account: public.clone(),
+                    new_key: create_account_keypair(5).1,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await[..],
+                [Event::InstructionFailed { code, .. }]
+                    if code == nullspace_types::casino::CasinoErrorCode::InvalidMove
+            ));
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // After which the account moves to the new key
+            let seed = create_seed(&network_secret, ready_view);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(&guardian_a, 3, recover);
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert_eq!(
+                layer.apply(&tx).await,
+                vec![Event::AccountRecovered {
+                    old_key: public.clone(),
+                    new_key: new_key.clone(),
+                }]
+            );
+            assert!(layer
+                .get(&Key::CasinoPlayer(public.clone()))
+                .await
+                .is_none());
+            assert!(layer.get(&Key::Guardians(public.clone())).await.is_none());
+            match layer.get(&Key::CasinoPlayer(new_key.clone())).await {
+                Some(Value::CasinoPlayer(player)) => {
+                    assert_eq!(player.active_sessions, vec![session_id])
+                }
+                _ => panic!("Player not found"),
+            }
+            match layer.get(&Key::Guardians(new_key.clone())).await {
+                Some(Value::Guardians(set)) => assert_eq!(set.recovery, None),
+                _ => panic!("Guardians not found"),
+            }
+
+            // Including the sessions it had in progress
+            let tx = Transaction::sign(
+                &new_signer,
+                1,
+                Instruction::CasinoGameMove {
+                    session_id,
+                    payload: vec![2], // Cashout
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(events.iter().any(|event| matches!(
+                event,
+                Event::CasinoGameCompleted { player, .. } if *player == new_key
+            )));
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_vusdt_allowance() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+
+            let (alice_signer, alice) = create_account_keypair(1);
+            let (bob_signer, bob) = create_account_keypair(2);
+            let (spender_signer, spender) = create_account_keypair(3);
+            for (signer, name) in [(&alice_signer, "Alice"), (&bob_signer, "Bob")] {
+                let tx = Transaction::sign(
+                    signer,
+                    0,
+                    Instruction::CasinoRegister {
+                        name: name.to_string(),
+                    },
+                );
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+            let mut player = match layer.get(&Key::CasinoPlayer(alice.clone())).await {
+                Some(Value::CasinoPlayer(player)) => player,
+                _ => panic!("Player not found"),
+            };
+            player.vusdt_balance = nullspace_types::casino::Vusdt(1_000);
+            layer.insert(
+                Key::CasinoPlayer(alice.clone()),
+                Value::CasinoPlayer(player),
+            );
+            let transfer = |amount| Instruction::TransferVusdtFrom {
+                owner: alice.clone(),
+                to: bob.clone(),
+                amount,
+            };
+            let failed = |code| {
+                vec![Event::InstructionFailed {
+                    player: spender.clone(),
+                    kind: tags::instruction::TRANSFER_VUSDT_FROM,
+                    code,
+                }]
+            };
+
+            // Nothing can be pulled without an allowance
+            let tx = Transaction::sign(&spender_signer, 0, transfer(100));
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert_eq!(
+                layer.apply(&tx).await,
+                failed(nullspace_types::casino::CasinoErrorCode::AllowanceExceeded)
+            );
+
+            // Transfers spend the allowance
+            let tx = Transaction::sign(
+                &alice_signer,
+                1,
+                Instruction::Approve {
+                    spender: spender.clone(),
+                    amount: 150,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let tx = Transaction::sign(&spender_signer, 1, transfer(100));
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert_eq!(
+                layer.apply(&tx).await,
+                vec![Event::VusdtTransferred {
+                    owner: alice.clone(),
+                    to: bob.clone(),
+                    spender: spender.clone(),
+                    amount: 100,
+                    allowance: 50,
+                }]
+            );
+            let tx = Transaction::sign(&spender_signer, 2, transfer(100));
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert_eq!(
+                layer.apply(&tx).await,
+                failed(nullspace_types::casino::CasinoErrorCode::AllowanceExceeded)
+            );
+            let tx = Transaction::sign(&spender_signer, 3, transfer(50));
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            assert!(layer
+                .get(&Key::Allowance(alice.clone(), spender.clone()))
+                .await
+                .is_none());
+
+            let balance = |value: Option<Value>| match value {
+                Some(Value::CasinoPlayer(player)) => u64::from(player.vusdt_balance),
+                _ => panic!("Player not found"),
+            };
+            assert_eq!(
+                balance(layer.get(&Key::CasinoPlayer(alice.clone())).await),
+                850
+            );
+            assert_eq!(
+                balance(layer.get(&Key::CasinoPlayer(bob.clone())).await),
+                150
+            );
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_vesting() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let mut state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let (beneficiary_signer, beneficiary) = create_account_keypair(2);
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            for (signer, name) in [(&signer, "Alice"), (&beneficiary_signer, "Bob")] {
+                let tx = Transaction::sign(
+                    signer,
+                    0,
+                    Instruction::CasinoRegister {
+                        name: name.to_string(),
+                    },
+                );
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+            let chips = |value: Option<Value>| match value {
+                Some(Value::CasinoPlayer(player)) => player.chips.0,
+                _ => panic!("Player not found"),
+            };
+            let initial = chips(layer.get(&Key::CasinoPlayer(public.clone())).await);
+            let fee = nullspace_types::casino::TRANSACTION_FEE;
+
+            // The chips are escrowed when the schedule is created
+            let tx = Transaction::sign(
+                &signer,
+                1,
+                Instruction::CreateVesting {
+                    to: beneficiary.clone(),
+                    amount: 400,
+                    start_view: 10,
+                    duration: 100,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            assert_eq!(
+                chips(layer.get(&Key::CasinoPlayer(public.clone())).await),
+                initial - 400 - fee
+            );
+
+            // Nothing can be claimed before the schedule starts
+            let tx = Transaction::sign(&beneficiary_signer, 1, Instruction::ClaimVested);
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert_eq!(
+                layer.apply(&tx).await,
+                vec![Event::InstructionFailed {
+                    player: beneficiary.clone(),
+                    kind: tags::instruction::CLAIM_VESTED,
+                    code: nullspace_types::casino::CasinoErrorCode::InvalidAmount,
+                }]
+            );
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // Chips unlock over the duration
+            for (view, nonce, amount) in [(60, 2, 200), (500, 3, 200)] {
+                let seed = create_seed(&network_secret, view);
+                let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+                let tx = Transaction::sign(&beneficiary_signer, nonce, Instruction::ClaimVested);
+                assert!(layer.prepare(&tx).await.is_ok());
+                assert!(matches!(
+                    layer.apply(&tx).await[..],
+                    [Event::VestedClaimed { amount: a, .. }] if a == amount
+                ));
+                let changes = layer.commit();
+                state.apply(changes).await;
+            }
+            assert!(state
+                .get(&Key::Vesting(beneficiary.clone()))
+                .await
+                .is_none());
+            assert_eq!(
+                chips(state.get(&Key::CasinoPlayer(beneficiary.clone())).await),
+                initial + 400 - 3 * fee
+            );
+        });
+    }
+
+    #[test]
+    fn test_committed_entropy() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let session_id = nullspace_types::casino::derive_session_id(&public, 1);
+            let preimage = Sha256::hash(b"player entropy");
+            let commitment = Sha256::hash(preimage.as_ref());
+            let cashout = |prefix: &[u8]| Instruction::CasinoGameMove {
+                session_id,
+                payload: [prefix, &[2u8][..]].concat(),
+            };
+
+            // Start a game committing to the preimage
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let instructions = vec![
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::HiLo,
+                    bet: 100,
+                    session_id,
+                    commitment: Some(commitment),
+                },
+            ];
+            for (nonce, instruction) in instructions.into_iter().enumerate() {
+                let tx = Transaction::sign(&signer, nonce as u64, instruction);
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+
+            // The first move must reveal the preimage
+            let prefixes: [&[u8]; 2] = [&[], commitment.as_ref()];
+            for (nonce, prefix) in (2..).zip(prefixes) {
+                let tx = Transaction::sign(&signer, nonce, cashout(prefix));
+                assert!(layer.prepare(&tx).await.is_ok());
+                let events = layer.apply(&tx).await;
+                assert!(matches!(
+                    events[..],
+                    [Event::CasinoError {
+                        error_code: nullspace_types::casino::CasinoErrorCode::InvalidReveal,
+                        ..
+                    }]
+                ));
+            }
+            match layer.get(&Key::CasinoSession(session_id)).await {
+                Some(Value::CasinoSession(session)) => {
+                    assert_eq!(session.move_count, 0);
+                    assert_eq!(
+                        session.entropy,
+                        Some(nullspace_types::casino::PlayerEntropy::Committed(
+                            commitment
+                        ))
+                    );
+                }
+                _ => panic!("Session not found"),
+            }
+
+            // Revealing the preimage plays the rest of the payload
+            let tx = Transaction::sign(&signer, 4, cashout(preimage.as_ref()));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(events.iter().any(|event| matches!(
+                event,
+                Event::CasinoGameCompleted { session_id: id, .. } if *id == session_id
+            )));
+            match layer.get(&Key::CasinoSession(session_id)).await {
+                Some(Value::CasinoSession(session)) => {
+                    assert!(session.is_complete);
+                    assert_eq!(
+                        session.entropy,
+                        Some(nullspace_types::casino::PlayerEntropy::Revealed(preimage))
+                    );
+                }
+                _ => panic!("Session not found"),
+            }
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_set_kyc_status() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let (admin_signer, admin) = create_account_keypair(2);
+            let set_kyc = |verified| Instruction::SetKycStatus {
+                player: public.clone(),
+                verified,
+            };
+            let deposit = Instruction::CasinoDeposit { amount: 600 };
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let mut house = layer.get_or_init_house().await;
+            house.kyc = nullspace_types::casino::KycSettings {
+                admin: Some(admin.clone()),
+                deposit_limit: 500,
+            };
+            layer.insert(Key::House, Value::House(house));
+            for (signer, name) in [(&signer, "Alice"), (&admin_signer, "Admin")] {
+                let tx = Transaction::sign(
+                    signer,
+                    0,
+                    Instruction::CasinoRegister {
+                        name: name.to_string(),
+                    },
+                );
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+
+            // Large deposits require KYC
+            let tx = Transaction::sign(&signer, 1, deposit.clone());
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(
+                events[..],
+                [Event::CasinoError {
+                    error_code: nullspace_types::casino::CasinoErrorCode::KycRequired,
+                    ..
+                }]
+            ));
+
+            // Only the admin can set KYC status
+            let tx = Transaction::sign(&signer, 2, set_kyc(true));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(
+                events[..],
+                [Event::CasinoError {
+                    error_code: nullspace_types::casino::CasinoErrorCode::Unauthorized,
+                    ..
+                }]
+            ));
+            let tx = Transaction::sign(&admin_signer, 1, set_kyc(true));
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert_eq!(
+                events,
+                vec![Event::KycStatusChanged {
+                    player: public.clone(),
+                    verified: true,
+                }]
+            );
+
+            // Verified players can make large deposits
+            let tx = Transaction::sign(&signer, 3, deposit);
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::CasinoPlayerRegistered { .. }]));
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_bet_limits() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let session_id = |nonce| nullspace_types::casino::derive_session_id(&public, nonce);
+            let place_red = |amount: u64| {
+                let mut payload = vec![0, 1, 0];
+                payload.extend_from_slice(&amount.to_be_bytes());
+                Instruction::CasinoGameMove {
+                    session_id: session_id(2),
+                    payload,
+                }
+            };
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let mut house = layer.get_or_init_house().await;
+            house.bet_limits = nullspace_types::casino::BetLimits {
+                default_max_bet: 100,
+                max_bets: vec![(nullspace_types::casino::GameType::Roulette, 50)],
+                max_exposure: 120,
+            };
+            layer.insert(Key::House, Value::House(house));
+
+            let instructions = vec![
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+                // Over the default maximum bet
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::HiLo,
+                    bet: 101,
+                    session_id: session_id(1),
+                    commitment: None,
+                },
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::Roulette,
+                    bet: 0,
+                    session_id: session_id(2),
+                    commitment: None,
+                },
+                // Over the roulette maximum bet
+                place_red(60),
+                place_red(50),
+                place_red(50),
+                // Over the session exposure
+                place_red(50),
+            ];
+            let mut results = Vec::new();
+            for (nonce, instruction) in instructions.into_iter().enumerate() {
+                let tx = Transaction::sign(&signer, nonce as u64, instruction);
+                assert!(layer.prepare(&tx).await.is_ok());
+                results.push(match layer.apply(&tx).await.as_slice() {
+                    [Event::CasinoError { error_code, .. }] => Some(*error_code),
+                    _ => None,
+                });
+            }
+            assert_eq!(
+                results,
+                vec![
+                    None,
+                    Some(nullspace_types::casino::CasinoErrorCode::BetLimitExceeded),
+                    None,
+                    Some(nullspace_types::casino::CasinoErrorCode::BetLimitExceeded),
+                    None,
+                    None,
+                    Some(nullspace_types::casino::CasinoErrorCode::ExposureLimitExceeded),
+                ]
+            );
+
+            // Rejected bets aren't charged
+            match layer.get(&Key::CasinoSession(session_id(2))).await {
+                Some(Value::CasinoSession(session)) => assert_eq!(session.wagered, 100),
+                _ => panic!("Session not found"),
+            }
+            match layer.get(&Key::CasinoPlayer(public.clone())).await {
+                Some(Value::CasinoPlayer(player)) => assert_eq!(
+                    player.chips.0,
+                    nullspace_types::casino::INITIAL_CHIPS
+                        - 100
+                        - 6 * nullspace_types::casino::TRANSACTION_FEE
+                ),
+                _ => panic!("Player not found"),
+            }
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_game_history() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let mut state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let session_id = |nonce| nullspace_types::casino::derive_session_id(&public, nonce);
+
+            let seed = create_seed(&network_secret, 3);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let instructions = vec![
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::HiLo,
+                    bet: 10,
+                    session_id: session_id(1),
+                    commitment: None,
+                },
+            ];
+            for (nonce, instruction) in instructions.into_iter().enumerate() {
+                let tx = Transaction::sign(&signer, nonce as u64, instruction);
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+
+            // Sessions in progress aren't recorded
+            assert!(layer.get(&Key::GameHistory(public.clone())).await.is_none());
+
+            // Completed sessions are
+            let tx = Transaction::sign(
+                &signer,
+                2,
+                Instruction::CasinoGameMove {
+                    session_id: session_id(1),
+                    payload: vec![2], // Cashout
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            match layer.get(&Key::GameHistory(public.clone())).await {
+                Some(Value::GameHistory(history)) => assert_eq!(
+                    history.records,
+                    vec![nullspace_types::casino::GameRecord {
+                        session_id: session_id(1),
+                        game_type: nullspace_types::casino::GameType::HiLo,
+                        bet: 10,
+                        payout: 10,
+                        view: 3,
+                    }]
+                ),
+                _ => panic!("History not found"),
+            }
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // Completed sessions are deleted once they are old enough
+            let view = 3 + nullspace_types::casino::SESSION_RETENTION;
+            let seed = create_seed(&network_secret, view);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            assert!(layer
+                .get(&Key::CasinoSession(session_id(1)))
+                .await
+                .is_some());
+            let instructions = vec![
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::HiLo,
+                    bet: 10,
+                    session_id: session_id(3),
+                    commitment: None,
+                },
+                Instruction::CasinoGameMove {
+                    session_id: session_id(3),
+                    payload: vec![2], // Cashout
+                },
+            ];
+            for (nonce, instruction) in instructions.into_iter().enumerate() {
+                let tx = Transaction::sign(&signer, 3 + nonce as u64, instruction);
+                assert!(layer.prepare(&tx).await.is_ok());
+                layer.apply(&tx).await;
+            }
+            assert!(layer
+                .get(&Key::CasinoSession(session_id(1)))
+                .await
+                .is_none());
+            assert!(layer
+                .get(&Key::CasinoSession(session_id(3)))
+                .await
+                .is_some());
+            match layer.get(&Key::GameHistory(public.clone())).await {
+                Some(Value::GameHistory(history)) => {
+                    assert_eq!(history.records.len(), 2);
+                    assert_eq!(history.reclaimed_through, 3);
+                }
+                _ => panic!("History not found"),
+            }
+
+            // Leave a session in progress
+            let tx = Transaction::sign(
+                &signer,
+                5,
+                Instruction::CasinoStartGame {
+                    game_type: nullspace_types::casino::GameType::HiLo,
+                    bet: 10,
+                    session_id: session_id(5),
+                    commitment: None,
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let changes = layer.commit();
+            state.apply(changes).await;
+
+            // Completed sessions of idle players are reclaimed from the expiry queue once they
+            // are old enough, walking the queued views from a cursor (starting at the first
+            // completed session) a bounded number at a time
+            let limit = nullspace_types::casino::SESSION_RECLAIM_LIMIT as u64;
+            let expiry = view + nullspace_types::casino::SESSION_RETENTION;
+            let mut cursor = 3;
+            for reclaim_view in expiry - 1.. {
+                let seed = create_seed(&network_secret, reclaim_view);
+                let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+                layer.reclaim_expired_sessions().await;
+                let Some(Value::SessionReclaimCursor(next)) =
+                    layer.get(&Key::SessionReclaimCursor).await
+                else {
+                    panic!("Cursor not found");
+                };
+                assert!(next > cursor && next - cursor <= limit);
+                cursor = next;
+                let reclaimed = layer
+                    .get(&Key::CasinoSession(session_id(3)))
+                    .await
+                    .is_none();
+                assert!(!reclaimed || reclaim_view >= expiry);
+                let changes = layer.commit();
+                state.apply(changes).await;
+                if reclaimed {
+                    break;
+                }
+            }
+            assert!(cursor > view);
+            assert!(state.get(&Key::SessionExpiries(view)).await.is_none());
+            assert!(state
+                .get(&Key::CasinoSession(session_id(5)))
+                .await
+                .is_some());
+        });
+    }
+
+    #[test]
+    fn test_game_stats() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+            let key = Key::GameStats(nullspace_types::casino::GameType::HiLo);
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+
+            // Each completed session is added to its game's totals
+            let play = |round: u64| {
+                let nonce = 2 * round - 1;
+                let session_id = nullspace_types::casino::derive_session_id(&public, nonce);
+                [
+                    Instruction::CasinoStartGame {
+                        game_type: nullspace_types::casino::GameType::HiLo,
+                        bet: 10,
+                        session_id,
+                        commitment: None,
+                    },
+                    Instruction::CasinoGameMove {
+                        session_id,
+                        payload: vec![2], // Cashout
+                    },
+                ]
+                .into_iter()
+                .enumerate()
+                .map(|(i, instruction)| Transaction::sign(&signer, nonce + i as u64, instruction))
+                .collect::<Vec<_>>()
+            };
+            for tx in play(1) {
+                assert!(layer.prepare(&tx).await.is_ok());
+                let events = layer.apply(&tx).await;
+                assert!(!events
+                    .iter()
+                    .any(|event| matches!(event, Event::GameStatsUpdated { .. })));
+            }
+            let expected = nullspace_types::casino::GameStats {
+                hands_played: 1,
+                total_wagered: 10,
+                total_paid_out: 10,
+                biggest_win: 10,
+            };
+            assert_eq!(
+                layer.get(&key).await,
+                Some(Value::GameStats(expected.clone()))
+            );
+
+            // And the totals are reported every `GAME_STATS_INTERVAL` sessions
+            layer.insert(
+                key.clone(),
+                Value::GameStats(nullspace_types::casino::GameStats {
+                    hands_played: nullspace_types::casino::GAME_STATS_INTERVAL - 1,
+                    ..expected
+                }),
+            );
+            let mut events = Vec::new();
+            for tx in play(2) {
+                assert!(layer.prepare(&tx).await.is_ok());
+                events.extend(layer.apply(&tx).await);
+            }
+            assert!(events.contains(&Event::GameStatsUpdated {
+                game_type: nullspace_types::casino::GameType::HiLo,
+                stats: nullspace_types::casino::GameStats {
+                    hands_played: nullspace_types::casino::GAME_STATS_INTERVAL,
+                    total_wagered: 20,
+                    total_paid_out: 20,
+                    biggest_win: 10,
+                },
+            }));
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_bonus() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let (signer, public) = create_account_keypair(1);
+
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let grant = |nonce| {
+                Transaction::sign(
+                    &signer,
+                    nonce,
+                    Instruction::GrantBonus {
+                        player: public.clone(),
+                        amount: 10,
+                    },
+                )
+            };
+            let player = |value: Option<Value>| match value {
+                Some(Value::CasinoPlayer(player)) => player,
+                _ => panic!("Player not found"),
+            };
+
+            // Only governance can grant bonuses
+            let tx = grant(1);
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert!(matches!(
+                layer.apply(&tx).await.as_slice(),
+                [Event::CasinoError { error_code, .. }]
+                    if *error_code == nullspace_types::casino::CasinoErrorCode::Unauthorized
+            ));
+            let mut house = layer.get_or_init_house().await;
+            house.governance = Some(public.clone());
+            house.bonus.wagering_multiplier = 2;
+            layer.insert(Key::House, Value::House(house));
+
+            // The bonus is held apart from the player's chips
+            let chips = player(layer.get(&Key::CasinoPlayer(public.clone())).await)
+                .chips
+                .0;
+            let tx = grant(2);
+            assert!(layer.prepare(&tx).await.is_ok());
+            assert_eq!(
+                layer.apply(&tx).await,
+                vec![Event::BonusGranted {
+                    player: public.clone(),
+                    amount: 10,
+                    bonus_chips: 10,
+                    wagering_remaining: 20,
+                }]
+            );
+            let granted = player(layer.get(&Key::CasinoPlayer(public.clone())).await);
+            assert_eq!(granted.chips.0, chips);
+
+            // Completed games count towards the requirement until the bonus is released
+            let play = |round: u64| {
+                let nonce = 2 * round + 1;
+                let session_id = nullspace_types::casino::derive_session_id(&public, nonce);
+                [
+                    Instruction::CasinoStartGame {
+                        game_type: nullspace_types::casino::GameType::HiLo,
+                        bet: 10,
+                        session_id,
+                        commitment: None,
+                    },
+                    Instruction::CasinoGameMove {
+                        session_id,
+                        payload: vec![2], // Cashout
+                    },
+                ]
+                .into_iter()
+                .enumerate()
+                .map(|(i, instruction)| Transaction::sign(&signer, nonce + i as u64, instruction))
+                .collect::<Vec<_>>()
+            };
+            let mut events = Vec::new();
+            for tx in play(1) {
+                assert!(layer.prepare(&tx).await.is_ok());
+                events.extend(layer.apply(&tx).await);
+            }
+            assert!(!events
+                .iter()
+                .any(|event| matches!(event, Event::BonusReleased { .. })));
+            let wagering = player(layer.get(&Key::CasinoPlayer(public.clone())).await);
+            assert_eq!(wagering.bonus.wagering_remaining, 10);
+
+            let mut events = Vec::new();
+            for tx in play(2) {
+                assert!(layer.prepare(&tx).await.is_ok());
+                events.extend(layer.apply(&tx).await);
+            }
+            let released = player(layer.get(&Key::CasinoPlayer(public.clone())).await);
+            assert!(events.contains(&Event::BonusReleased {
+                player: public.clone(),
+                amount: 10,
+                chips: released.chips.0,
+            }));
+            assert_eq!(released.bonus, Default::default());
+
+            let _ = layer.commit();
+        });
+    }
+
+    #[test]
+    fn test_batch_atomic() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = MockState::new();
+            let (network_secret, master_public) = create_network_keypair();
+            let seed = create_seed(&network_secret, 1);
+            let mut layer = Layer::new(&state, master_public, TEST_NAMESPACE, seed).await;
+
+            let (signer, public) = create_account_keypair(1);
+            let active_double = |value: Option<Value>| match value {
+                Some(Value::CasinoPlayer(player)) => player.active_double,
+                _ => panic!("Player not found"),
+            };
+
+            // A new player can't pay for the toggle in the same transaction as registering
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::Batch(vec![
+                    Instruction::CasinoRegister {
+                        name: "Alice".to_string(),
+                    },
+                    Instruction::CasinoToggleShield,
+                ]),
+            );
+            assert!(matches!(
+                layer.prepare(&tx).await,
+                Err(PrepareError::InsufficientFee { .. })
+            ));
+            let tx = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            layer.apply(&tx).await;
+            let tx = Transaction::sign(
+                &signer,
+                1,
+                Instruction::Batch(vec![
+                    Instruction::CasinoToggleShield,
+                    Instruction::CasinoToggleDouble,
+                ]),
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(events.is_empty());
+            let player = layer.get(&Key::CasinoPlayer(public.clone())).await;
+            assert!(active_double(player));
+
+            // If any instruction fails, none are applied
+            let tx = Transaction::sign(
+                &signer,
+                2,
+                Instruction::Batch(vec![
+                    Instruction::CasinoToggleDouble,
+                    Instruction::CasinoRegister {
+                        name: "Alice".to_string(),
+                    },
+                ]),
+            );
+            assert!(layer.prepare(&tx).await.is_ok());
+            let events = layer.apply(&tx).await;
+            assert!(matches!(events[..], [Event::CasinoError { .. }]));
+            let player = layer.get(&Key::CasinoPlayer(public.clone())).await;
+            assert!(active_double(player));
+
+            // The nonce is still consumed
+            let tx = Transaction::sign(&signer, 2, Instruction::CasinoToggleDouble);
+            assert!(layer.prepare(&tx).await.is_err());
+
+            let _ = layer.commit();
+        });
+    }
 }
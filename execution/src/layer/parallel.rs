@@ -0,0 +1,301 @@
+//! Speculative execution of a block's transactions across the execution pool.
+//!
+//! Every transaction is first executed concurrently against a snapshot of the state at the
+//! start of the block (covering the keys it is expected to touch, see
+//! [crate::state_transition::prefetch_keys]), recording the keys it reads. Results are then
+//! merged in block order: a transaction that read a key written by an earlier transaction
+//! in the block (or that touched a key missing from the snapshot) is re-executed against the
+//! merged state instead. The outputs (and state) are identical to executing the block
+//! sequentially.
+//!
+//! Fees, house PnL, leaderboard updates and session expiries are deferred (see [Deferred]) and applied as each
+//! transaction is merged, so moves in independent game sessions never conflict. Every
+//! transaction reads the house settings (the fee schedule, at least), so the house only
+//! counts as written when its settings change; a write to its counters alone re-executes
+//! just the transactions that read the counters.
+
+use super::{Deferred, Layer};
+use crate::{
+    state::{State, Status},
+    state_transition::prefetch_keys,
+};
+use commonware_cryptography::{
+    bls12381::primitives::variant::{MinSig, Variant},
+    ed25519::PublicKey,
+};
+use commonware_runtime::ThreadPool;
+use futures::executor::block_on;
+use nullspace_types::{
+    casino::EconomyParams,
+    execution::{Event, Key, Output, Transaction, Value, MAX_BLOCK_COMPUTE_UNITS},
+    Seed,
+};
+use rayon::prelude::*;
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, BTreeSet},
+};
+
+/// State at the start of the block (as seen by speculative executions).
+struct Snapshot<'a> {
+    pending: &'a BTreeMap<Key, Status>,
+    cache: &'a BTreeMap<Key, Option<Value>>,
+
+    reads: RefCell<BTreeSet<Key>>,
+    /// Whether every key read was available.
+    complete: Cell<bool>,
+}
+
+impl<'a> Snapshot<'a> {
+    fn new(pending: &'a BTreeMap<Key, Status>, cache: &'a BTreeMap<Key, Option<Value>>) -> Self {
+        Self {
+            pending,
+            cache,
+            reads: RefCell::new(BTreeSet::new()),
+            complete: Cell::new(true),
+        }
+    }
+}
+
+impl State for Snapshot<'_> {
+    async fn get(&self, key: &Key) -> Option<Value> {
+        self.reads.borrow_mut().insert(key.clone());
+        match self.pending.get(key) {
+            Some(Status::Update(value)) => return Some(value.clone()),
+            Some(Status::Delete) => return None,
+            None => {}
+        }
+        match self.cache.get(key) {
+            Some(value) => value.clone(),
+            None => {
+                self.complete.set(false);
+                None
+            }
+        }
+    }
+
+    async fn scan(&self, _: &[u8]) -> Vec<(Key, Value)> {
+        // The snapshot only covers the keys prefetched for the block
+        self.complete.set(false);
+        Vec::new()
+    }
+
+    async fn insert(&mut self, _: Key, _: Value) {
+        unreachable!("speculative changes are merged into the parent layer");
+    }
+
+    async fn delete(&mut self, _: &Key) {
+        unreachable!("speculative changes are merged into the parent layer");
+    }
+}
+
+/// The changes made by executing a transaction (on top of some state).
+struct Execution {
+    units: u64,
+    pending: BTreeMap<Key, Status>,
+    deferred: Deferred,
+    /// The fee charged and events emitted (if the transaction was valid).
+    result: Option<(Option<Event>, Vec<Event>)>,
+}
+
+impl<'a, S: State> Layer<'a, S> {
+    /// Create a layer over `state` that defers house counter and leaderboard updates.
+    fn speculative(
+        state: &'a S,
+        master: <MinSig as Variant>::Public,
+        slash_namespace: Vec<u8>,
+        admin_namespace: Vec<u8>,
+        seed: Seed,
+        economy: EconomyParams,
+    ) -> Self {
+        Self {
+            state,
+            pending: BTreeMap::new(),
+            undo: None,
+
+            master,
+            slash_namespace,
+            admin_namespace,
+
+            seed,
+            economy,
+
+            deferred: Some(Deferred::default()),
+        }
+    }
+
+    async fn run(mut self, transaction: &Transaction) -> Execution {
+        let units = self.compute_units(transaction).await;
+        let result = match self.prepare(transaction).await {
+            Ok(fee) => Some((fee, self.apply(transaction).await)),
+            Err(_) => None,
+        };
+        Execution {
+            units,
+            pending: self.pending,
+            deferred: self.deferred.unwrap_or_default(),
+            result,
+        }
+    }
+
+    /// Execute `transactions` (equivalent to applying them one after another), returning
+    /// how many had to be re-executed.
+    pub(super) async fn execute_parallel(
+        &mut self,
+        pool: ThreadPool,
+        transactions: Vec<Transaction>,
+        outputs: &mut Vec<Output>,
+        processed_nonces: &mut BTreeMap<PublicKey, u64>,
+    ) -> usize {
+        // Load the keys each transaction is expected to touch (and the house, which all of
+        // them read)
+        let mut cache = BTreeMap::new();
+        let keys = std::iter::once(Key::House).chain(transactions.iter().flat_map(prefetch_keys));
+        for key in keys {
+            if self.pending.contains_key(&key) || cache.contains_key(&key) {
+                continue;
+            }
+            let value = self.state.get(&key).await;
+            cache.insert(key, value);
+        }
+
+        // Execute all transactions against the snapshot
+        let (pending, master, slash_namespace, admin_namespace, seed, economy) = (
+            &self.pending,
+            &self.master,
+            &self.slash_namespace,
+            &self.admin_namespace,
+            &self.seed,
+            &self.economy,
+        );
+        let speculations: Vec<_> = pool.install(|| {
+            transactions
+                .par_iter()
+                .map(|transaction| {
+                    let snapshot = Snapshot::new(pending, &cache);
+                    let layer = Layer::speculative(
+                        &snapshot,
+                        *master,
+                        slash_namespace.clone(),
+                        admin_namespace.clone(),
+                        seed.clone(),
+                        economy.clone(),
+                    );
+                    let execution = block_on(layer.run(transaction));
+                    let Snapshot {
+                        reads, complete, ..
+                    } = snapshot;
+                    complete.get().then(|| (reads.into_inner(), execution))
+                })
+                .collect()
+        });
+
+        // Merge in order (re-executing transactions that observed stale state)
+        let mut written = BTreeSet::new();
+        let mut counters_updated = false;
+        let mut reexecuted = 0;
+        let mut compute_used = 0u64;
+        for (transaction, speculation) in transactions.into_iter().zip(speculations) {
+            let valid = speculation.filter(|(reads, execution)| {
+                !(execution.deferred.house_read && counters_updated)
+                    && reads.iter().all(|key| !written.contains(key))
+            });
+            let execution = match valid {
+                Some((_, execution)) => execution,
+                None => {
+                    reexecuted += 1;
+                    Layer::speculative(
+                        &*self,
+                        self.master,
+                        self.slash_namespace.clone(),
+                        self.admin_namespace.clone(),
+                        self.seed.clone(),
+                        self.economy.clone(),
+                    )
+                    .run(&transaction)
+                    .await
+                }
+            };
+            let Some((mut fee, events)) = execution.result else {
+                continue;
+            };
+
+            // Skip transactions that don't fit in the block's compute budget
+            if compute_used.saturating_add(execution.units) > MAX_BLOCK_COMPUTE_UNITS {
+                continue;
+            }
+            compute_used += execution.units;
+
+            // The fee is credited before anything else the transaction does
+            let accumulated_fees = self.house_settings().await.accumulated_fees;
+            if let Some(Event::FeeCharged {
+                amount,
+                accumulated_fees: total,
+                ..
+            }) = &mut fee
+            {
+                *total = accumulated_fees.saturating_add(*amount);
+            }
+            // A transaction that only moved the house counters conflicts with later
+            // transactions that read them (tracked via `house_read`), not with the ones that
+            // only looked at the house settings
+            let mut house_written = false;
+            for key in execution.pending.keys() {
+                if *key == Key::House {
+                    house_written = true;
+                    continue;
+                }
+                written.insert(key.clone());
+            }
+            if house_written {
+                let before = self.house_settings().await;
+                match execution.pending.get(&Key::House) {
+                    Some(Status::Update(Value::House(after))) if after.same_settings(&before) => {
+                        counters_updated = true;
+                    }
+                    _ => {
+                        written.insert(Key::House);
+                    }
+                }
+            }
+            if !execution.deferred.leaderboard.is_empty() {
+                written.insert(Key::CasinoLeaderboard);
+            }
+            if !execution.deferred.expiries.is_empty() {
+                written.insert(Key::SessionExpiries(self.seed.view));
+                written.insert(Key::SessionReclaimCursor);
+            }
+            self.pending.extend(execution.pending);
+            counters_updated |= self.settle(execution.deferred).await;
+
+            processed_nonces.insert(
+                transaction.public.clone(),
+                transaction.nonce.saturating_add(1),
+            );
+            outputs.extend(fee.map(Output::Event));
+            outputs.extend(events.into_iter().map(Output::Event));
+            outputs.push(Output::Transaction(transaction));
+        }
+        reexecuted
+    }
+
+    /// Apply the updates deferred by a transaction, returning whether the house counters
+    /// changed.
+    async fn settle(&mut self, deferred: Deferred) -> bool {
+        for (public, name, chips) in deferred.leaderboard {
+            self.update_casino_leaderboard_entry(&public, name, chips)
+                .await;
+        }
+        for session_id in deferred.expiries {
+            self.queue_session_expiry_entry(session_id).await;
+        }
+        if !deferred.house_updated {
+            return false;
+        }
+        let mut house = self.get_or_init_house().await;
+        house.accumulated_fees = house.accumulated_fees.saturating_add(deferred.fees);
+        house.net_pnl += deferred.pnl;
+        self.insert(Key::House, Value::House(house));
+        true
+    }
+}
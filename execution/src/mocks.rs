@@ -141,7 +141,7 @@ pub async fn execute_block_result<E: Spawner + Metrics + Storage + Clock>(
         .get_metadata()
         .await
         .context("failed to read state metadata")?
-        .and_then(|(_, v)| match v {
+        .and_then(|(_, v)| match v.map(Value::into_inner) {
             Some(Value::Commit { height, start: _ }) => Some(height),
             _ => None,
         })
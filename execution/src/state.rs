@@ -7,7 +7,9 @@ use commonware_cryptography::{
 };
 use commonware_runtime::{Clock, Metrics, Spawner, Storage};
 use commonware_storage::{adb::any::variable::Any, translator::Translator};
-use nullspace_types::execution::{Account, Key, Transaction, Value};
+use nullspace_types::execution::{
+    protocol_version, Account, Key, Transaction, Value, MAX_KEY_INDEX_BUCKET,
+};
 use std::{
     collections::{BTreeMap, HashMap},
     future::Future,
@@ -19,6 +21,8 @@ pub type Adb<E, T> = Any<E, Digest, Value, Sha256, T>;
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PrepareError {
     NonceMismatch { expected: u64, got: u64 },
+    InsufficientFee { required: u64, available: u64 },
+    ComputeLimitExceeded { units: u64, limit: u64 },
 }
 
 pub trait State {
@@ -26,6 +30,12 @@ pub trait State {
     fn insert(&mut self, key: Key, value: Value) -> impl Future<Output = ()>;
     fn delete(&mut self, key: &Key) -> impl Future<Output = ()>;
 
+    /// The entries whose key encoding starts with `prefix`, in order of their encoding.
+    ///
+    /// Only indexed domains can be scanned (see [Key::scannable]): scans of any other prefix
+    /// return nothing.
+    fn scan(&self, prefix: &[u8]) -> impl Future<Output = Vec<(Key, Value)>>;
+
     fn apply(&mut self, changes: Vec<(Key, Status)>) -> impl Future<Output = ()> {
         async {
             for (key, status) in changes {
@@ -38,11 +48,31 @@ pub trait State {
     }
 }
 
+/// The height of the last block committed to `state` (0 before the first block).
+async fn committed_height<E: Spawner + Metrics + Clock + Storage, T: Translator>(
+    state: &Adb<E, T>,
+) -> u64 {
+    match state.get_metadata().await {
+        Ok(Some((_, Some(value)))) => match value.into_inner() {
+            Value::Commit { height, .. } => height,
+            _ => 0,
+        },
+        _ => 0,
+    }
+}
+
+/// Keys are stored by the hash of their encoding, so the keys of each indexed domain are also
+/// listed by key index buckets (see [Key::index]), which [State::scan] reads. Keys written
+/// before their domain was indexed are only listed once they are written again.
+///
+/// Values are written in the encoding of the protocol version active at the height being
+/// executed (the one after the last committed height, see [protocol_version]), so blocks
+/// below an activation height produce the same state root as releases without it.
 impl<E: Spawner + Metrics + Clock + Storage, T: Translator> State for Adb<E, T> {
     async fn get(&self, key: &Key) -> Option<Value> {
         let key = Sha256::hash(&key.encode());
         match self.get(&key).await {
-            Ok(value) => value,
+            Ok(value) => value.map(Value::into_inner),
             Err(e) => {
                 warn!("Database error during get operation: {:?}", e);
                 None
@@ -51,16 +81,94 @@ impl<E: Spawner + Metrics + Clock + Storage, T: Translator> State for Adb<E, T>
     }
 
     async fn insert(&mut self, key: Key, value: Value) {
-        let key = Sha256::hash(&key.encode());
-        if let Err(e) = self.update(key, value).await {
-            warn!("Database error during insert operation: {:?}", e);
-        }
+        State::apply(self, vec![(key, Status::Update(value))]).await;
     }
 
     async fn delete(&mut self, key: &Key) {
-        let key = Sha256::hash(&key.encode());
-        if let Err(e) = self.delete(key).await {
-            warn!("Database error during delete operation: {:?}", e);
+        State::apply(self, vec![(key.clone(), Status::Delete)]).await;
+    }
+
+    async fn scan(&self, prefix: &[u8]) -> Vec<(Key, Value)> {
+        let mut entries = Vec::new();
+        for bucket in Key::index_buckets(prefix) {
+            let Some(Value::KeyIndex(keys)) = State::get(self, &bucket).await else {
+                continue;
+            };
+            for key in keys {
+                if !key.encode().starts_with(prefix) {
+                    continue;
+                }
+                if let Some(value) = State::get(self, &key).await {
+                    entries.push((key, value));
+                }
+            }
+        }
+        entries.sort_by_cached_key(|(key, _)| key.encode());
+        entries
+    }
+
+    async fn apply(&mut self, changes: Vec<(Key, Status)>) {
+        let version = protocol_version(committed_height(self).await + 1);
+
+        // Keys added to (or removed from) each key index bucket
+        let mut indexed: BTreeMap<Key, Vec<(Key, bool)>> = BTreeMap::new();
+        for (key, status) in changes {
+            if let Some(bucket) = key.index() {
+                let present = matches!(status, Status::Update(_));
+                indexed
+                    .entry(bucket)
+                    .or_default()
+                    .push((key.clone(), present));
+            }
+            let digest = Sha256::hash(&key.encode());
+            let result = match status {
+                Status::Update(value) => self.update(digest, value.versioned(version)).await,
+                Status::Delete => self.delete(digest).await,
+            };
+            if let Err(e) = result {
+                warn!("Database error during apply operation: {:?}", e);
+            }
+        }
+
+        // Update each bucket once
+        for (bucket, changes) in indexed {
+            let mut keys = match State::get(self, &bucket).await {
+                Some(Value::KeyIndex(keys)) => keys,
+                _ => Vec::new(),
+            };
+            let mut changed = false;
+            for (key, present) in changes {
+                let encoded = key.encode();
+                let position = keys.binary_search_by(|listed| listed.encode().cmp(&encoded));
+                match (position, present) {
+                    (Err(i), true) => {
+                        assert!(
+                            keys.len() < MAX_KEY_INDEX_BUCKET,
+                            "key index bucket is full: can't index {key:?} in {bucket:?}"
+                        );
+                        keys.insert(i, key);
+                        changed = true;
+                    }
+                    (Ok(i), false) => {
+                        keys.remove(i);
+                        changed = true;
+                    }
+                    _ => {}
+                }
+            }
+            if !changed {
+                continue;
+            }
+            let digest = Sha256::hash(&bucket.encode());
+            let result = if keys.is_empty() {
+                self.delete(digest).await
+            } else {
+                self.update(digest, Value::KeyIndex(keys).versioned(version))
+                    .await
+            };
+            if let Err(e) = result {
+                warn!("Database error during key index update: {:?}", e);
+            }
         }
     }
 }
@@ -82,6 +190,20 @@ impl State for Memory {
     async fn delete(&mut self, key: &Key) {
         self.state.remove(key);
     }
+
+    async fn scan(&self, prefix: &[u8]) -> Vec<(Key, Value)> {
+        if !Key::scannable(prefix) {
+            return Vec::new();
+        }
+        let mut entries: Vec<_> = self
+            .state
+            .iter()
+            .filter(|(key, _)| key.encode().starts_with(prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        entries.sort_by_cached_key(|(key, _)| key.encode());
+        entries
+    }
 }
 
 #[derive(Clone)]
@@ -125,6 +247,36 @@ impl EncodeSize for Status {
     }
 }
 
+/// Overlay `pending` changes on the entries scanned for `prefix` from the underlying state.
+pub(crate) fn overlay_scan(
+    entries: Vec<(Key, Value)>,
+    pending: &BTreeMap<Key, Status>,
+    prefix: &[u8],
+) -> Vec<(Key, Value)> {
+    if !Key::scannable(prefix) {
+        return entries;
+    }
+    let mut entries: BTreeMap<Vec<u8>, (Key, Value)> = entries
+        .into_iter()
+        .map(|(key, value)| (key.encode().to_vec(), (key, value)))
+        .collect();
+    for (key, status) in pending {
+        let encoded = key.encode().to_vec();
+        if !encoded.starts_with(prefix) {
+            continue;
+        }
+        match status {
+            Status::Update(value) => {
+                entries.insert(encoded, (key.clone(), value.clone()));
+            }
+            Status::Delete => {
+                entries.remove(&encoded);
+            }
+        }
+    }
+    entries.into_values().collect()
+}
+
 pub async fn nonce<S: State>(state: &S, public: &PublicKey) -> u64 {
     load_account(state, public).await.nonce
 }
@@ -192,4 +344,235 @@ impl<'a, S: State> State for Noncer<'a, S> {
     async fn delete(&mut self, key: &Key) {
         self.pending.insert(key.clone(), Status::Delete);
     }
+
+    async fn scan(&self, prefix: &[u8]) -> Vec<(Key, Value)> {
+        overlay_scan(self.state.scan(prefix).await, &self.pending, prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::{
+        create_account_keypair, create_adbs, create_network_keypair, execute_block,
+    };
+    use commonware_runtime::deterministic::Runner;
+    use commonware_runtime::Runner as _;
+    use commonware_storage::{
+        mmr::hasher::Standard,
+        store::operation::{Keyless, Variable},
+    };
+    use nullspace_types::{
+        casino::Vault,
+        execution::{tags, Instruction, Output, PROTOCOL_VERSION},
+    };
+
+    // Scans don't look at values, so any value will do
+    fn session(id: u64) -> Value {
+        Value::Account(Account { nonce: id })
+    }
+
+    fn ids(entries: &[(Key, Value)]) -> Vec<u64> {
+        entries
+            .iter()
+            .map(|(key, _)| match key {
+                Key::CasinoSession(id) => *id,
+                _ => panic!("unexpected key"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_adb_scan() {
+        let executor = Runner::default();
+        executor.start(|context| async move {
+            let (mut state, _) = create_adbs(&context).await;
+            let (_, public) = create_account_keypair(1);
+            let ids_written = [0x0300, 2, 0x0102, 0x0202, 1 << 56];
+            let mut changes: Vec<_> = ids_written
+                .iter()
+                .map(|&id| (Key::CasinoSession(id), Status::Update(session(id))))
+                .collect();
+            changes.push((
+                Key::Vault(public.clone()),
+                Status::Update(Value::Vault(Vault::default())),
+            ));
+            changes.push((Key::House, Status::Update(session(0))));
+            State::apply(&mut state, changes).await;
+
+            // Sessions are scanned in order of their encoding, across buckets
+            let sessions = state.scan(&[tags::key::CASINO_SESSION]).await;
+            assert_eq!(ids(&sessions), vec![2, 0x0102, 0x0202, 0x0300, 1 << 56]);
+            assert_eq!(sessions[0].1, session(2));
+            let prefix = Key::CasinoSession(0x0102).encode();
+            assert_eq!(ids(&state.scan(&prefix).await), vec![0x0102]);
+            assert_eq!(state.scan(&[tags::key::VAULT]).await.len(), 1);
+
+            // Unindexed domains can't be scanned
+            assert!(state.scan(&[tags::key::HOUSE]).await.is_empty());
+            assert!(state.scan(&[]).await.is_empty());
+
+            // Deleted keys are removed from the index (and empty buckets are deleted)
+            State::delete(&mut state, &Key::CasinoSession(0x0102)).await;
+            State::delete(&mut state, &Key::CasinoSession(0x0202)).await;
+            State::insert(&mut state, Key::CasinoSession(2), session(3)).await;
+            let sessions = state.scan(&[tags::key::CASINO_SESSION]).await;
+            assert_eq!(ids(&sessions), vec![2, 0x0300, 1 << 56]);
+            assert_eq!(sessions[0].1, session(3));
+            let bucket = Key::KeyIndex {
+                tag: tags::key::CASINO_SESSION,
+                bucket: 2,
+            };
+            assert_eq!(
+                State::get(&state, &bucket).await,
+                Some(Value::KeyIndex(vec![Key::CasinoSession(2)]))
+            );
+            State::delete(&mut state, &Key::CasinoSession(2)).await;
+            assert_eq!(State::get(&state, &bucket).await, None);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "key index bucket is full")]
+    fn test_adb_full_bucket() {
+        let executor = Runner::default();
+        executor.start(|context| async move {
+            let (mut state, _) = create_adbs(&context).await;
+
+            // Fill a bucket (sessions with the same last byte share one)
+            let changes = (0..MAX_KEY_INDEX_BUCKET as u64)
+                .map(|i| (Key::CasinoSession(i << 8), Status::Update(session(i))))
+                .collect();
+            State::apply(&mut state, changes).await;
+            let bucket = Key::CasinoSession(0).index().unwrap();
+            match State::get(&state, &bucket).await {
+                Some(Value::KeyIndex(keys)) => assert_eq!(keys.len(), MAX_KEY_INDEX_BUCKET),
+                _ => panic!("bucket not found"),
+            }
+
+            // Keys already listed can still be updated (and removed)...
+            let changes = vec![
+                (Key::CasinoSession(0), Status::Update(session(1))),
+                (Key::CasinoSession(1 << 8), Status::Delete),
+            ];
+            State::apply(&mut state, changes).await;
+
+            // ...but a new key can't be indexed once it is full again
+            let changes = vec![
+                (Key::CasinoSession(1 << 8), Status::Update(session(1))),
+                (
+                    Key::CasinoSession(u64::MAX << 8),
+                    Status::Update(session(2)),
+                ),
+            ];
+            State::apply(&mut state, changes).await;
+        });
+    }
+
+    /// The state root after committing some values at height 1, written through
+    /// [State::apply] (or directly in the encoding of `version`).
+    fn committed_root(version: Option<u8>) -> Digest {
+        let executor = Runner::default();
+        executor.start(|context| async move {
+            let (mut state, _) = create_adbs(&context).await;
+            let (_, public) = create_account_keypair(1);
+            let changes = vec![
+                (Key::Account(public), Status::Update(session(1))),
+                (Key::House, Status::Update(session(2))),
+            ];
+            match version {
+                None => State::apply(&mut state, changes).await,
+                Some(version) => {
+                    for (key, status) in changes {
+                        let Status::Update(value) = status else {
+                            unreachable!();
+                        };
+                        let digest = Sha256::hash(&key.encode());
+                        state
+                            .update(digest, value.versioned(version))
+                            .await
+                            .unwrap();
+                    }
+                }
+            }
+            let commit = Value::Commit {
+                height: 1,
+                start: 0,
+            };
+            state.commit(Some(commit)).await.unwrap();
+            state.root(&mut Standard::<Sha256>::new())
+        })
+    }
+
+    #[test]
+    fn test_adb_legacy_encoding() {
+        // Blocks below the activation height write values in the legacy encoding, so they
+        // produce the state root of a release without versioned values
+        assert_eq!(committed_root(None), committed_root(Some(0)));
+        assert_ne!(committed_root(None), committed_root(Some(PROTOCOL_VERSION)));
+
+        // Including every value a block writes
+        let executor = Runner::default();
+        executor.start(|context| async move {
+            let (mut state, mut events) = create_adbs(&context).await;
+            let (network_secret, network_identity) = create_network_keypair();
+            let (signer, _) = create_account_keypair(1);
+            let register = Transaction::sign(
+                &signer,
+                0,
+                Instruction::CasinoRegister {
+                    name: "Alice".to_string(),
+                },
+            );
+            let (_, summary) = execute_block(
+                &network_secret,
+                network_identity,
+                &mut state,
+                &mut events,
+                1,
+                vec![register],
+            )
+            .await;
+            summary.verify(&network_identity).unwrap();
+            let mut updates = 0;
+            for op in &summary.state_proof_ops {
+                if let Variable::Update(_, value) = op {
+                    assert!(!matches!(value, Value::Versioned { .. }));
+                    updates += 1;
+                }
+            }
+            assert!(updates > 0);
+
+            // And every event it emits
+            let mut emitted = 0;
+            for op in &summary.events_proof_ops {
+                if let Keyless::Append(output) = op {
+                    assert!(!matches!(output, Output::VersionedEvent { .. }));
+                    emitted += output.event().is_some() as usize;
+                }
+            }
+            assert!(emitted > 0);
+        });
+    }
+
+    #[test]
+    fn test_noncer_scan() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let mut state = Memory::default();
+            for id in [1, 2, 3] {
+                state.insert(Key::CasinoSession(id), session(id)).await;
+            }
+
+            // Pending changes are overlaid on the underlying state
+            let mut noncer = Noncer::new(&state);
+            noncer.delete(&Key::CasinoSession(2)).await;
+            noncer.insert(Key::CasinoSession(4), session(4)).await;
+            noncer.insert(Key::CasinoSession(1), session(5)).await;
+            let sessions = noncer.scan(&[tags::key::CASINO_SESSION]).await;
+            assert_eq!(ids(&sessions), vec![1, 3, 4]);
+            assert_eq!(sessions[0].1, session(5));
+            assert_eq!(state.scan(&[tags::key::CASINO_SESSION]).await.len(), 3);
+        });
+    }
 }
@@ -6,10 +6,13 @@ use commonware_runtime::ThreadPool;
 use commonware_runtime::{Clock, Metrics, Spawner, Storage};
 use commonware_storage::{adb::keyless, mmr::hasher::Standard, translator::Translator};
 use nullspace_types::{
-    execution::{Output, Seed, Transaction, Value},
+    casino::PairId,
+    execution::{
+        event_version, protocol_version, Instruction, Key, Output, Seed, Transaction, Value,
+    },
     Identity, NAMESPACE,
 };
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 /// Result of executing a block's state transition
 pub struct StateTransitionResult {
@@ -45,7 +48,7 @@ pub async fn execute_state_transition<S: Spawner + Storage + Clock + Metrics, T:
         .get_metadata()
         .await
         .unwrap_or(None)
-        .and_then(|(_, v)| match v {
+        .and_then(|(_, v)| match v.map(Value::into_inner) {
             Some(Value::Commit { height, start }) => Some((height, start)),
             _ => None,
         })
@@ -87,7 +90,14 @@ pub async fn execute_state_transition<S: Spawner + Storage + Clock + Metrics, T:
 
         state_start_op = state.op_count();
         events_start_op = events.op_count();
-        let mut layer = Layer::new(state, identity, NAMESPACE, seed);
+        // Transactions can't use a version before it activates
+        let version = protocol_version(height);
+        let transactions = transactions
+            .into_iter()
+            .filter(|tx| tx.version <= version)
+            .collect();
+
+        let mut layer = Layer::new(state, identity, NAMESPACE, seed).await;
         let (outputs, nonces) = layer
             .execute(
                 #[cfg(feature = "parallel")]
@@ -98,9 +108,11 @@ pub async fn execute_state_transition<S: Spawner + Storage + Clock + Metrics, T:
         processed_nonces.extend(nonces);
 
         // Events must be committed before state, otherwise we risk wedging on restart.
+        // Events are wrapped in an envelope once it activates
+        let event_version = event_version(height);
         for output in outputs.into_iter() {
             events
-                .append(output)
+                .append(output.versioned(event_version))
                 .await
                 .with_context(|| format!("append event output (height={height})"))?;
         }
@@ -115,10 +127,13 @@ pub async fn execute_state_transition<S: Spawner + Storage + Clock + Metrics, T:
         // Apply state once we've committed events (can't regenerate after state updated)
         state.apply(layer.commit()).await;
         state
-            .commit(Some(Value::Commit {
-                height,
-                start: state_start_op,
-            }))
+            .commit(Some(
+                Value::Commit {
+                    height,
+                    start: state_start_op,
+                }
+                .versioned(version),
+            ))
             .await
             .with_context(|| format!("commit state (height={height})"))?;
     }
@@ -140,3 +155,219 @@ pub async fn execute_state_transition<S: Spawner + Storage + Clock + Metrics, T:
         processed_nonces,
     })
 }
+
+/// Keys that executing a transaction is expected to read.
+///
+/// This is a best-effort hint (execution may touch additional keys) used to warm
+/// storage before a block is executed and to decide which keys transactions can read when
+/// executed in parallel (transactions that touch others are re-executed sequentially).
+pub fn prefetch_keys(transaction: &Transaction) -> Vec<Key> {
+    let public = &transaction.public;
+    let mut keys = vec![
+        Key::Account(public.clone()),
+        Key::CasinoPlayer(public.clone()),
+    ];
+    match &transaction.instruction {
+        Instruction::Batch(instructions) => {
+            for instruction in instructions {
+                instruction_keys(public, instruction, &mut keys);
+            }
+        }
+        instruction => instruction_keys(public, instruction, &mut keys),
+    }
+    keys
+}
+
+/// Keys (beyond the account and player) that executing `instruction` is expected to read.
+fn instruction_keys(public: &PublicKey, instruction: &Instruction, keys: &mut Vec<Key>) {
+    match instruction {
+        Instruction::CasinoStartGame {
+            session_id,
+            game_type,
+            ..
+        } => {
+            keys.push(Key::CasinoSession(*session_id));
+            keys.push(Key::GameHistory(public.clone()));
+            keys.push(Key::GameConfig(*game_type));
+            keys.push(Key::GameStats(*game_type));
+        }
+        Instruction::CasinoGameMove { session_id, .. } => {
+            keys.push(Key::CasinoSession(*session_id));
+            keys.push(Key::GameHistory(public.clone()));
+        }
+        Instruction::CasinoAbandonSession { session_id } => {
+            keys.push(Key::CasinoSession(*session_id));
+        }
+        Instruction::CasinoJoinTournament { tournament_id }
+        | Instruction::CasinoStartTournament { tournament_id, .. }
+        | Instruction::CasinoEndTournament { tournament_id } => {
+            keys.push(Key::Tournament(*tournament_id));
+        }
+        Instruction::CasinoScheduleTournament { tournament_id, .. } => {
+            keys.push(Key::Tournament(*tournament_id));
+            keys.push(Key::TournamentSchedules);
+        }
+        Instruction::Stake { .. } | Instruction::Unstake { .. } | Instruction::ClaimRewards => {
+            keys.push(Key::Staker(public.clone()));
+            keys.push(Key::House);
+        }
+        Instruction::Slash { staker, .. } => {
+            keys.push(Key::Staker(staker.clone()));
+            keys.push(Key::House);
+        }
+        Instruction::CasinoDeposit { .. } | Instruction::ClaimDailyBonus => {
+            keys.push(Key::House);
+        }
+        Instruction::SetKycStatus { player, .. } => {
+            keys.push(Key::House);
+            keys.push(Key::CasinoPlayer(player.clone()));
+        }
+        Instruction::CreateVault
+        | Instruction::DepositCollateral { .. }
+        | Instruction::RepayUSDT { .. } => {
+            keys.push(Key::Vault(public.clone()));
+        }
+        Instruction::BorrowUSDT { .. } | Instruction::WithdrawCollateral { .. } => {
+            keys.push(Key::Vault(public.clone()));
+            keys.push(Key::Oracle);
+        }
+        Instruction::Swap { pair, .. } => {
+            keys.push(Key::AmmPool(*pair));
+            keys.push(Key::AmmPools);
+            keys.push(Key::LimitOrderBook);
+        }
+        Instruction::PlaceLimitOrder { .. } => {
+            keys.push(Key::AmmPool(PairId::RNG_VUSDT));
+            keys.push(Key::AmmPools);
+            keys.push(Key::LimitOrderBook);
+        }
+        Instruction::CancelLimitOrder { order_id } => {
+            keys.push(Key::LimitOrder(*order_id));
+            keys.push(Key::LimitOrderBook);
+        }
+        Instruction::AddLiquidity { pair, .. } | Instruction::RemoveLiquidity { pair, .. } => {
+            keys.push(Key::AmmPool(*pair));
+            keys.push(Key::AmmPools);
+            keys.push(Key::LpBalance(*pair, public.clone()));
+        }
+        Instruction::ZapLiquidity { pair, .. } => {
+            keys.push(Key::AmmPool(*pair));
+            keys.push(Key::AmmPools);
+            keys.push(Key::LpBalance(*pair, public.clone()));
+            keys.push(Key::LimitOrderBook);
+        }
+        Instruction::SetGuardians { .. } => {
+            keys.push(Key::Guardians(public.clone()));
+        }
+        Instruction::RecoverAccount { account, new_key } => {
+            keys.push(Key::Guardians(account.clone()));
+            keys.push(Key::CasinoPlayer(account.clone()));
+            keys.push(Key::CasinoPlayer(new_key.clone()));
+        }
+        Instruction::Approve { spender, .. } => {
+            keys.push(Key::Allowance(public.clone(), spender.clone()));
+        }
+        Instruction::TransferVusdtFrom { owner, to, .. } => {
+            keys.push(Key::Allowance(owner.clone(), public.clone()));
+            keys.push(Key::CasinoPlayer(owner.clone()));
+            keys.push(Key::CasinoPlayer(to.clone()));
+        }
+        Instruction::CreateVesting { to, .. } => {
+            keys.push(Key::Vesting(to.clone()));
+        }
+        Instruction::ClaimVested => {
+            keys.push(Key::Vesting(public.clone()));
+        }
+        Instruction::ProcessEpoch => {
+            keys.push(Key::House);
+            keys.push(Key::Treasury);
+            keys.push(Key::Oracle);
+        }
+        Instruction::SetGameConfig { game_type, .. } => {
+            keys.push(Key::House);
+            keys.push(Key::GameConfig(*game_type));
+        }
+        Instruction::GrantBonus { player, .. } => {
+            keys.push(Key::House);
+            keys.push(Key::CasinoPlayer(player.clone()));
+        }
+        Instruction::SetTournamentRules { tournament_id, .. } => {
+            keys.push(Key::House);
+            keys.push(Key::Tournament(*tournament_id));
+        }
+        Instruction::SetEconomyParams { .. } => {
+            keys.push(Key::House);
+            keys.push(Key::EconomyParams);
+        }
+        Instruction::SetAdmin { .. } => {
+            keys.push(Key::House);
+            keys.push(Key::Treasury);
+        }
+        Instruction::SetHouseSettings { .. } => {
+            keys.push(Key::House);
+        }
+        Instruction::ExecuteBuyback { .. } => {
+            keys.push(Key::Treasury);
+            keys.push(Key::House);
+            keys.push(Key::AmmPool(PairId::RNG_VUSDT));
+            keys.push(Key::AmmPools);
+            keys.push(Key::LimitOrderBook);
+        }
+        _ => {}
+    }
+}
+
+/// Read the keys touched by `transactions` so that they are cached when the block
+/// containing them is executed.
+///
+/// Returns the number of distinct keys read.
+pub async fn prefetch<S: State>(state: &S, transactions: &[Transaction]) -> usize {
+    let keys: BTreeSet<Key> = transactions.iter().flat_map(prefetch_keys).collect();
+    for key in &keys {
+        let _ = state.get(key).await;
+    }
+    keys.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mocks::create_account_keypair, Memory};
+    use commonware_runtime::{deterministic::Runner, Runner as _};
+
+    #[test]
+    fn test_prefetch_keys() {
+        let (signer, public) = create_account_keypair(1);
+        let tx = Transaction::sign(
+            &signer,
+            0,
+            Instruction::CasinoGameMove {
+                session_id: 7,
+                payload: vec![],
+            },
+        );
+        // Key doesn't implement Debug, so we can't use assert_eq
+        assert!(
+            prefetch_keys(&tx)
+                == vec![
+                    Key::Account(public.clone()),
+                    Key::CasinoPlayer(public.clone()),
+                    Key::CasinoSession(7),
+                    Key::GameHistory(public),
+                ]
+        );
+    }
+
+    #[test]
+    fn test_prefetch_deduplicates() {
+        let executor = Runner::default();
+        executor.start(|_| async move {
+            let state = Memory::default();
+            let (signer, _) = create_account_keypair(1);
+            let transactions: Vec<_> = (0..3)
+                .map(|nonce| Transaction::sign(&signer, nonce, Instruction::CasinoToggleShield))
+                .collect();
+            assert_eq!(prefetch(&state, &transactions).await, 2);
+        });
+    }
+}
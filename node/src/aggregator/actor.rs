@@ -1,6 +1,7 @@
 use crate::{
-    aggregator::{ingress::Mailbox, Config, Message},
+    aggregator::{ingress::Mailbox, Backfill, Config, Message, UploadStatus},
     indexer::Indexer,
+    scorer::{Offense, PeerReporter},
 };
 use bytes::{Buf, BufMut};
 use commonware_codec::{
@@ -13,12 +14,13 @@ use commonware_cryptography::{
     sha256::Digest,
     Digestible,
 };
-use commonware_p2p::{Receiver, Sender};
+use commonware_p2p::{Blocker, Receiver, Sender};
 use commonware_resolver::{p2p, Resolver};
 use commonware_runtime::{Clock, Handle, Metrics, Spawner, Storage};
 use commonware_storage::{
     cache,
     journal::fixed,
+    metadata::{self, Metadata},
     mmr::verification::Proof,
     ordinal::{self, Ordinal},
     rmap::RMap,
@@ -31,7 +33,7 @@ use futures::{
 };
 use governor::clock::Clock as GClock;
 use nullspace_types::{
-    api::Summary,
+    api::{Summary, MAX_SUBMISSION_SUMMARIES},
     execution::{Output, Progress, Value},
     genesis_digest,
 };
@@ -45,6 +47,14 @@ use tracing::{debug, info, warn};
 
 const BATCH_ENQUEUE: usize = 20;
 const RETRY_DELAY: Duration = Duration::from_secs(10);
+const CHECKPOINT_KEY: u64 = 0;
+const MAX_CHECKPOINT_PENDING: usize = 4096;
+
+/// Number of heights waiting to be uploaded above which summaries are uploaded in batches.
+const BATCH_UPLOAD_THRESHOLD: u64 = 8;
+
+/// Maximum encoded size of a batch of summaries (a batch always holds at least one).
+const MAX_BATCH_BYTES: usize = 1024 * 1024;
 
 pub struct Proofs {
     pub state_proof: Proof<Digest>,
@@ -91,6 +101,106 @@ impl EncodeSize for Proofs {
     }
 }
 
+/// Upload progress persisted so that restarts only revisit pending work.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// End of the contiguous range of stored certificates (starting at height 1).
+    pub certified: u64,
+    /// All heights below this have been uploaded.
+    pub boundary: u64,
+    /// The next height to upload.
+    pub cursor: u64,
+    /// Heights in `[boundary, cursor)` that were still being uploaded.
+    pub pending: Vec<u64>,
+}
+
+impl Checkpoint {
+    /// Create a checkpoint of upload progress.
+    ///
+    /// If more than [MAX_CHECKPOINT_PENDING] heights are pending, only the lowest are kept and
+    /// the cursor is moved back to the first height dropped (so it is uploaded again after a
+    /// restart instead of being treated as uploaded).
+    fn new(
+        certified: u64,
+        boundary: u64,
+        mut cursor: u64,
+        pending: impl IntoIterator<Item = u64>,
+    ) -> Self {
+        let mut pending: Vec<u64> = pending.into_iter().collect();
+        pending.sort_unstable();
+        pending.dedup();
+        if pending.len() > MAX_CHECKPOINT_PENDING {
+            cursor = pending[MAX_CHECKPOINT_PENDING];
+            pending.truncate(MAX_CHECKPOINT_PENDING);
+        }
+        Self {
+            certified,
+            boundary,
+            cursor,
+            pending,
+        }
+    }
+
+    /// Restore upload tracking from the checkpoint, given the first height still in the cache.
+    ///
+    /// Returns the boundary, the cursor, the heights to retry, and the heights known to be
+    /// uploaded.
+    fn restore(&self, first: u64) -> (u64, u64, BTreeSet<u64>, RMap) {
+        let mut uploaded = RMap::new();
+        if self.cursor <= first {
+            return (first, first, BTreeSet::new(), uploaded);
+        }
+        let boundary = self.boundary.max(first);
+        let retries: BTreeSet<u64> = self
+            .pending
+            .iter()
+            .copied()
+            .filter(|index| *index >= boundary)
+            .collect();
+        for index in boundary..self.cursor {
+            if !retries.contains(&index) {
+                uploaded.insert(index);
+            }
+        }
+        (boundary, self.cursor, retries, uploaded)
+    }
+}
+
+impl Write for Checkpoint {
+    fn write(&self, buf: &mut impl BufMut) {
+        self.certified.write(buf);
+        self.boundary.write(buf);
+        self.cursor.write(buf);
+        self.pending.write(buf);
+    }
+}
+
+impl Read for Checkpoint {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &()) -> Result<Self, commonware_codec::Error> {
+        let certified = u64::read(reader)?;
+        let boundary = u64::read(reader)?;
+        let cursor = u64::read(reader)?;
+        let pending = Vec::read_range(reader, 0..=MAX_CHECKPOINT_PENDING)?;
+        Ok(Self {
+            certified,
+            boundary,
+            cursor,
+            pending,
+        })
+    }
+}
+
+impl EncodeSize for Checkpoint {
+    fn encode_size(&self) -> usize {
+        self.certified.encode_size()
+            + self.boundary.encode_size()
+            + self.cursor.encode_size()
+            + self.pending.encode_size()
+    }
+}
+
 /// A fixed-size certificate that can be used to store in an ordinal.
 pub struct FixedCertificate {
     pub index: Index,
@@ -184,23 +294,40 @@ impl<R: Storage + Metrics + Clock + Spawner + GClock + RngCore, I: Indexer> Acto
         )
     }
 
-    pub fn start(
+    pub fn start<B: Blocker<PublicKey = PublicKey>>(
         mut self,
         backfill: (
             impl Sender<PublicKey = PublicKey>,
             impl Receiver<PublicKey = PublicKey>,
         ),
+        reporter: PeerReporter<R, B>,
     ) -> Handle<()> {
-        self.context.spawn_ref()(self.run(backfill))
+        self.context.spawn_ref()(self.run(backfill, reporter))
     }
 
-    async fn run(
+    async fn run<B: Blocker<PublicKey = PublicKey>>(
         mut self,
         backfill: (
             impl Sender<PublicKey = PublicKey>,
             impl Receiver<PublicKey = PublicKey>,
         ),
+        mut reporter: PeerReporter<R, B>,
     ) {
+        // Create metadata
+        let mut metadata = Metadata::<_, U64, Checkpoint>::init(
+            self.context.with_label("metadata"),
+            metadata::Config {
+                partition: format!("{}-metadata", self.config.partition),
+                codec_config: (),
+            },
+        )
+        .await
+        .expect("failed to initialize metadata");
+        let checkpoint = metadata
+            .get(&CHECKPOINT_KEY.into())
+            .cloned()
+            .unwrap_or_default();
+
         // Create storage
         let mut cache = cache::Cache::<_, Proofs>::init(
             self.context.with_label("cache"),
@@ -260,8 +387,14 @@ impl<R: Storage + Metrics + Clock + Spawner + GClock + RngCore, I: Indexer> Acto
         );
         resolver_engine.start(backfill);
 
-        // Start by fetching the first missing certificates
-        let missing = certificates.missing_items(1, BATCH_ENQUEUE);
+        // Start by fetching the first missing certificates (everything below the
+        // checkpoint is known to be stored)
+        let mut certified = checkpoint.certified.max(1);
+        if let Some(current_end) = certificates.next_gap(certified).0 {
+            certified = current_end;
+            self.certificates_processed.set(current_end as i64);
+        }
+        let missing = certificates.missing_items(certified, BATCH_ENQUEUE);
         for next in missing {
             self.waiting.insert(next);
             resolver.fetch(next.into()).await;
@@ -271,11 +404,20 @@ impl<R: Storage + Metrics + Clock + Spawner + GClock + RngCore, I: Indexer> Acto
         let genesis_digest = genesis_digest();
 
         // Track uploads
-        let mut uploads_outstanding = 0;
-        let mut cursor = cache.first().unwrap_or(1); // start at height 1
-        let mut boundary = cursor;
-        let mut tracked_uploads = RMap::new();
-        info!(cursor, "initial summary cursor");
+        //
+        // If we have a checkpoint, we only need to retry the uploads that were pending
+        // when it was written (instead of everything still in the cache).
+        let mut inflight = BTreeSet::new();
+        let mut submissions = 0;
+        let (mut boundary, mut cursor, mut retries, mut tracked_uploads) =
+            checkpoint.restore(cache.first().unwrap_or(1)); // start at height 1
+        info!(
+            cursor,
+            boundary,
+            pending = retries.len(),
+            certified,
+            "initial summary cursor"
+        );
 
         // Track pending aggregation work
         let mut proposal_requests: BTreeMap<u64, oneshot::Sender<Digest>> = BTreeMap::new();
@@ -286,12 +428,15 @@ impl<R: Storage + Metrics + Clock + Spawner + GClock + RngCore, I: Indexer> Acto
                 break;
             };
             match message {
-                Message::Uploaded { index } => {
+                Message::Uploaded { indices } => {
                     // Decrement uploads outstanding
-                    uploads_outstanding -= 1;
+                    submissions -= 1;
+                    for index in indices {
+                        inflight.remove(&index);
 
-                    // Track uploaded index
-                    tracked_uploads.insert(index);
+                        // Track uploaded index
+                        tracked_uploads.insert(index);
+                    }
 
                     // Prune proofs up to the uploaded height (contiguous with the boundary)
                     let Some(end_region) = tracked_uploads.next_gap(boundary).0 else {
@@ -303,9 +448,86 @@ impl<R: Storage + Metrics + Clock + Spawner + GClock + RngCore, I: Indexer> Acto
                             .await
                             .expect("failed to prune cache");
                         boundary = end_region;
+
+                        // Checkpoint progress
+                        metadata.put(
+                            CHECKPOINT_KEY.into(),
+                            Checkpoint::new(
+                                certified,
+                                boundary,
+                                cursor,
+                                inflight.iter().chain(retries.iter()).copied(),
+                            ),
+                        );
+                        metadata.sync().await.expect("failed to sync metadata");
                         info!(boundary, "updated summary upload marker");
                     }
                 }
+                Message::Backfill { index, response } => {
+                    // There is no summary for genesis
+                    let outcome = if index == 0 {
+                        Backfill::Unavailable
+                    } else if !certificates.has(index) {
+                        self.waiting.insert(index);
+                        resolver.fetch(index.into()).await;
+                        Backfill::Fetching
+                    } else if index >= cursor
+                        || inflight.contains(&index)
+                        || retries.contains(&index)
+                    {
+                        Backfill::Pending
+                    } else if cache.has(index) {
+                        retries.insert(index);
+                        Backfill::Reuploading
+                    } else {
+                        Backfill::Unavailable
+                    };
+                    info!(index, ?outcome, "forced backfill");
+                    let _ = response.send(outcome);
+                }
+                Message::UploadStatus { response } => {
+                    let _ = response.send(UploadStatus {
+                        certified,
+                        boundary,
+                        cursor,
+                        inflight: inflight.len(),
+                        retries: retries.len(),
+                    });
+                }
+                Message::Latest { response } => {
+                    // Find the latest executed height with a stored certificate
+                    let executed = results.size().await.expect("failed to get results size");
+                    let mut height = executed.min(certified);
+                    while height > 0 && !certificates.has(height) {
+                        height -= 1;
+                    }
+                    if height == 0 {
+                        let _ = response.send(None);
+                        continue;
+                    }
+                    let progress = results
+                        .read(height - 1)
+                        .await
+                        .expect("failed to fetch result"); // offset by 1 because stored by 0th offset
+                    let certificate = certificates
+                        .get(height)
+                        .await
+                        .expect("failed to fetch certificate")
+                        .expect("missing certificate");
+                    let _ = response.send(Some((progress, certificate.into())));
+                }
+                Message::Progress { height, response } => {
+                    let executed = results.size().await.expect("failed to get results size");
+                    if height == 0 || height > executed {
+                        let _ = response.send(None);
+                        continue;
+                    }
+                    let progress = results
+                        .read(height - 1)
+                        .await
+                        .expect("failed to fetch result"); // offset by 1 because stored by 0th offset
+                    let _ = response.send(Some(progress));
+                }
                 Message::Executed {
                     view,
                     height,
@@ -445,14 +667,15 @@ impl<R: Storage + Metrics + Clock + Spawner + GClock + RngCore, I: Indexer> Acto
                     certificates.sync().await.unwrap();
 
                     // Cancel resolver
-                    if let Some(current_end) = certificates.next_gap(1).0 {
+                    if let Some(current_end) = certificates.next_gap(certified).0 {
+                        certified = current_end;
                         self.certificates_processed.set(current_end as i64);
                         let current_end = U64::from(current_end);
                         resolver.retain(move |x| x > &current_end).await;
                     }
 
                     // Enqueue missing seeds
-                    let missing = certificates.missing_items(1, BATCH_ENQUEUE);
+                    let missing = certificates.missing_items(certified, BATCH_ENQUEUE);
                     for next in missing {
                         if !self.waiting.insert(next) {
                             continue;
@@ -473,16 +696,19 @@ impl<R: Storage + Metrics + Clock + Spawner + GClock + RngCore, I: Indexer> Acto
                         Certificate::<MinSig, Digest>::decode(&mut certificate.as_ref())
                     else {
                         response.send(false).expect("failed to send false");
+                        reporter.report(Offense::DecodeFailure).await;
                         continue;
                     };
                     if certificate.item.index != index {
                         response.send(false).expect("failed to send false");
+                        reporter.report(Offense::InvalidCertificate).await;
                         continue;
                     }
 
                     // Verify certificate
                     if !certificate.verify(&self.config.namespace, &self.config.identity) {
                         response.send(false).expect("failed to send false");
+                        reporter.report(Offense::InvalidCertificate).await;
                         continue;
                     }
 
@@ -493,9 +719,13 @@ impl<R: Storage + Metrics + Clock + Spawner + GClock + RngCore, I: Indexer> Acto
                         .await
                         .unwrap();
                     certificates.sync().await.unwrap();
+                    if let Some(current_end) = certificates.next_gap(certified).0 {
+                        certified = current_end;
+                        self.certificates_processed.set(current_end as i64);
+                    }
 
                     // Enqueue missing seeds
-                    let missing = certificates.missing_items(1, BATCH_ENQUEUE);
+                    let missing = certificates.missing_items(certified, BATCH_ENQUEUE);
                     for next in missing {
                         if !self.waiting.insert(next) {
                             continue;
@@ -519,65 +749,159 @@ impl<R: Storage + Metrics + Clock + Spawner + GClock + RngCore, I: Indexer> Acto
             //
             // We only delete entires in the cache when they cross the section boundary,
             // so we may re-upload the same height again on restart.
-            while uploads_outstanding < self.config.max_uploads_outstanding {
-                // Get next certificate
-                if !cache.has(cursor) || !certificates.has(cursor) {
-                    break;
-                }
+            //
+            // When far behind (e.g. catching up after downtime), consecutive summaries are
+            // uploaded together to avoid paying for a request per height.
+            while submissions < self.config.tunables.max_uploads_outstanding() {
+                let batch = if certified.saturating_sub(cursor) > BATCH_UPLOAD_THRESHOLD {
+                    MAX_SUBMISSION_SUMMARIES
+                } else {
+                    1
+                };
+                let mut indices = Vec::new();
+                let mut summaries = Vec::new();
+                let mut bytes = 0;
+                while summaries.len() < batch {
+                    // Get next certificate (retrying anything pending at the last checkpoint first)
+                    let index = retries.first().copied().unwrap_or(cursor);
+                    if !cache.has(index) || !certificates.has(index) {
+                        break;
+                    }
 
-                // Increment uploads outstanding
-                uploads_outstanding += 1;
+                    // Get certificate
+                    let certificate = certificates
+                        .get(index)
+                        .await
+                        .unwrap()
+                        .expect("failed to fetch certificate");
 
-                // Get certificate
-                let certificate = certificates
-                    .get(cursor)
-                    .await
-                    .unwrap()
-                    .expect("failed to fetch certificate");
+                    // Get result
+                    let result = results
+                        .read(index - 1)
+                        .await
+                        .expect("failed to fetch result"); // offset by 1 because stored by 0th offset
 
-                // Get result
-                let result = results
-                    .read(cursor - 1)
-                    .await
-                    .expect("failed to fetch result"); // offset by 1 because stored by 0th offset
+                    // Get proofs
+                    let proofs = cache
+                        .get(index)
+                        .await
+                        .unwrap()
+                        .expect("failed to fetch proofs");
+
+                    // Add the summary to the batch (if it fits)
+                    let summary = Summary {
+                        progress: result,
+                        certificate: certificate.into(),
+                        state_proof: proofs.state_proof,
+                        state_proof_ops: proofs.state_proof_ops,
+                        events_proof: proofs.events_proof,
+                        events_proof_ops: proofs.events_proof_ops,
+                    };
+                    bytes += summary.encode_size();
+                    if !summaries.is_empty() && bytes > MAX_BATCH_BYTES {
+                        break;
+                    }
+                    if index == cursor {
+                        cursor += 1;
+                    } else {
+                        retries.remove(&index);
+                    }
 
-                // Get proofs
-                let proofs = cache
-                    .get(cursor)
-                    .await
-                    .unwrap()
-                    .expect("failed to fetch proofs");
-
-                // Upload the summary to the indexer
-                let summary = Summary {
-                    progress: result,
-                    certificate: certificate.into(),
-                    state_proof: proofs.state_proof,
-                    state_proof_ops: proofs.state_proof_ops,
-                    events_proof: proofs.events_proof,
-                    events_proof_ops: proofs.events_proof_ops,
-                };
+                    // Increment uploads outstanding
+                    inflight.insert(index);
+                    indices.push(index);
+                    summaries.push(summary);
+                }
+                if summaries.is_empty() {
+                    break;
+                }
+                submissions += 1;
+
+                // Upload the summaries to the indexer
                 self.context.with_label("summary_submit").spawn({
                     let indexer = self.config.indexer.clone();
                     let mut channel = self.inbound.clone();
                     move |context| async move {
                         let mut attempts = 1;
                         loop {
-                            let Err(e) = indexer.submit_summary(summary.clone()).await else {
+                            let result = if summaries.len() == 1 {
+                                indexer.submit_summary(summaries[0].clone()).await
+                            } else {
+                                indexer.submit_summaries(summaries.clone()).await
+                            };
+                            let Err(e) = result else {
                                 break;
                             };
-                            warn!(?e, attempts, "failed to upload summary");
+                            warn!(
+                                ?e,
+                                attempts,
+                                count = summaries.len(),
+                                "failed to upload summaries"
+                            );
                             context.sleep(RETRY_DELAY).await;
                             attempts += 1;
                         }
-                        debug!(cursor, attempts, "summary uploaded to indexer");
-                        channel.uploaded(cursor).await;
+                        debug!(?indices, attempts, "summaries uploaded to indexer");
+                        channel.uploaded(indices).await;
                     }
                 });
-
-                // Increment cursor
-                cursor += 1;
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commonware_runtime::{deterministic, Runner};
+
+    #[test]
+    fn test_checkpoint_restart() {
+        let executor = deterministic::Runner::default();
+        executor.start(|context| async move {
+            // More heights are pending than can be checkpointed
+            let boundary = 100;
+            let cursor = boundary + 3 * MAX_CHECKPOINT_PENDING as u64;
+            let pending: Vec<u64> = (boundary..cursor).step_by(2).collect();
+            let checkpoint = Checkpoint::new(10, boundary, cursor, pending.iter().rev().copied());
+            assert_eq!(checkpoint.pending.len(), MAX_CHECKPOINT_PENDING);
+            assert_eq!(checkpoint.cursor, pending[MAX_CHECKPOINT_PENDING]);
+
+            // Persist the checkpoint and read it back (as on restart)
+            let config = metadata::Config {
+                partition: "metadata".to_string(),
+                codec_config: (),
+            };
+            let mut metadata =
+                Metadata::<_, U64, Checkpoint>::init(context.with_label("first"), config.clone())
+                    .await
+                    .unwrap();
+            metadata.put(CHECKPOINT_KEY.into(), checkpoint.clone());
+            metadata.sync().await.unwrap();
+            drop(metadata);
+            let metadata =
+                Metadata::<_, U64, Checkpoint>::init(context.with_label("second"), config)
+                    .await
+                    .unwrap();
+            let restored = metadata.get(&CHECKPOINT_KEY.into()).cloned().unwrap();
+            assert_eq!(restored, checkpoint);
+
+            // No pending height is treated as uploaded (it is either retried or uploaded again
+            // once the cursor reaches it)
+            let (restored_boundary, restored_cursor, retries, uploaded) =
+                restored.restore(boundary);
+            assert_eq!(restored_boundary, boundary);
+            assert_eq!(restored_cursor, checkpoint.cursor);
+            for index in pending {
+                assert!(retries.contains(&index) || index >= restored_cursor);
+                assert!(uploaded.next_gap(index).0.is_none());
+            }
+            assert_eq!(uploaded.next_gap(boundary + 1).0, Some(boundary + 1));
+
+            // A checkpoint behind the cache is ignored
+            let (restored_boundary, restored_cursor, retries, _) = restored.restore(cursor);
+            assert_eq!((restored_boundary, restored_cursor), (cursor, cursor));
+            assert!(retries.is_empty());
+        });
+    }
+}
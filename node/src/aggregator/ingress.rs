@@ -16,7 +16,57 @@ use futures::{
     SinkExt,
 };
 use nullspace_execution::state_transition::StateTransitionResult;
-use nullspace_types::execution::{Output, Value};
+use nullspace_types::execution::{Output, Progress, Value};
+use std::fmt;
+
+/// Progress of summary uploads to the indexer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UploadStatus {
+    /// Height up to which (exclusive) all certificates are stored.
+    pub certified: u64,
+    /// Height up to which (exclusive) all summaries have been uploaded.
+    pub boundary: u64,
+    /// Next height to upload.
+    pub cursor: u64,
+    /// Number of heights being uploaded.
+    pub inflight: usize,
+    /// Number of uploads waiting to be retried.
+    pub retries: usize,
+}
+
+impl fmt::Display for UploadStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "certified={} boundary={} cursor={} inflight={} retries={}",
+            self.certified, self.boundary, self.cursor, self.inflight, self.retries
+        )
+    }
+}
+
+/// The outcome of forcing a backfill at some height.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backfill {
+    /// The certificate is missing and was requested from peers.
+    Fetching,
+    /// The summary was already uploaded and has been queued for upload again.
+    Reuploading,
+    /// The summary has not been uploaded yet (or is being uploaded).
+    Pending,
+    /// The proofs required to upload the summary have been pruned.
+    Unavailable,
+}
+
+impl fmt::Display for Backfill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Backfill::Fetching => write!(f, "fetching certificate"),
+            Backfill::Reuploading => write!(f, "reuploading summary"),
+            Backfill::Pending => write!(f, "summary upload pending"),
+            Backfill::Unavailable => write!(f, "proofs unavailable"),
+        }
+    }
+}
 
 pub enum Message {
     Executed {
@@ -58,7 +108,21 @@ pub enum Message {
         response: oneshot::Sender<Bytes>,
     },
     Uploaded {
+        indices: Vec<Index>,
+    },
+    Backfill {
         index: Index,
+        response: oneshot::Sender<Backfill>,
+    },
+    UploadStatus {
+        response: oneshot::Sender<UploadStatus>,
+    },
+    Latest {
+        response: oneshot::Sender<Option<(Progress, Certificate<MinSig, Digest>)>>,
+    },
+    Progress {
+        height: u64,
+        response: oneshot::Sender<Option<Progress>>,
     },
 }
 
@@ -72,13 +136,54 @@ impl Mailbox {
         Self { sender }
     }
 
-    pub(super) async fn uploaded(&mut self, index: Index) {
+    pub(super) async fn uploaded(&mut self, indices: Vec<Index>) {
         self.sender
-            .send(Message::Uploaded { index })
+            .send(Message::Uploaded { indices })
             .await
             .expect("failed to send uploaded");
     }
 
+    /// Fetch the certificate at `index` (if missing) or upload its summary again.
+    pub async fn backfill(&mut self, index: Index) -> Backfill {
+        let (response, receiver) = oneshot::channel();
+        self.sender
+            .send(Message::Backfill { index, response })
+            .await
+            .expect("failed to send backfill");
+        receiver.await.unwrap_or(Backfill::Unavailable)
+    }
+
+    /// Get the progress of summary uploads.
+    pub async fn upload_status(&mut self) -> UploadStatus {
+        let (response, receiver) = oneshot::channel();
+        self.sender
+            .send(Message::UploadStatus { response })
+            .await
+            .expect("failed to send upload status");
+        receiver.await.unwrap_or_default()
+    }
+
+    /// Get the progress (and certificate) of the latest height that has been both executed
+    /// and certified.
+    pub async fn latest(&mut self) -> Option<(Progress, Certificate<MinSig, Digest>)> {
+        let (response, receiver) = oneshot::channel();
+        self.sender
+            .send(Message::Latest { response })
+            .await
+            .expect("failed to send latest");
+        receiver.await.ok().flatten()
+    }
+
+    /// Get the progress of an executed height (whether or not it has been certified).
+    pub async fn progress(&mut self, height: u64) -> Option<Progress> {
+        let (response, receiver) = oneshot::channel();
+        self.sender
+            .send(Message::Progress { height, response })
+            .await
+            .expect("failed to send progress");
+        receiver.await.ok().flatten()
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn executed(
         &mut self,
@@ -1,12 +1,12 @@
 mod actor;
 mod ingress;
 
-use crate::{indexer::Indexer, supervisor::ViewSupervisor};
+use crate::{indexer::Indexer, reload::Tunables, supervisor::ViewSupervisor};
 pub use actor::Actor;
 use commonware_cryptography::ed25519::PublicKey;
 use commonware_runtime::buffer::PoolRef;
 use governor::Quota;
-pub use ingress::{Mailbox, Message};
+pub use ingress::{Backfill, Mailbox, Message, UploadStatus};
 use nullspace_types::Identity;
 use std::num::NonZero;
 
@@ -24,5 +24,5 @@ pub struct Config<I: Indexer> {
     pub replay_buffer: NonZero<usize>,
     pub buffer_pool: PoolRef,
     pub indexer: I,
-    pub max_uploads_outstanding: usize,
+    pub tunables: Tunables,
 }
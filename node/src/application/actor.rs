@@ -4,8 +4,14 @@ use super::{
 };
 use crate::{
     aggregator,
-    application::mempool::Mempool,
-    indexer::Indexer,
+    application::{
+        admission::{self, AdmissionLog, Admissions, Decision, Reason, Record},
+        budget::Budget,
+        mempool::{Admission, Mempool},
+    },
+    export,
+    indexer::{spawn_report, Indexer},
+    reload::Tunables,
     seeder,
     supervisor::{EpochSupervisor, Supervisor, ViewSupervisor},
 };
@@ -23,22 +29,35 @@ use commonware_runtime::{
 };
 use commonware_storage::{
     adb::{self, keyless},
+    store::operation::Variable,
     translator::EightCap,
 };
 use commonware_utils::{futures::ClosedExt, NZU64};
 use futures::StreamExt;
-use futures::{channel::mpsc, future::try_join};
+use futures::{
+    channel::mpsc,
+    future::{join, try_join},
+};
 use futures::{future, future::Either};
-use nullspace_execution::{nonce, state_transition, Adb, Noncer};
+use nullspace_execution::{
+    nonce,
+    state_transition::{self, prefetch},
+    Adb, Noncer,
+};
 use nullspace_types::{
-    execution::{Output, Value, MAX_BLOCK_TRANSACTIONS},
+    api::{Rejection, RejectionReason},
+    execution::{
+        protocol_version, Output, Transaction, Value, MAX_TRANSACTION_COMPUTE_UNITS,
+    },
     genesis_block, genesis_digest, Block, Identity,
 };
 use prometheus_client::metrics::{counter::Counter, histogram::Histogram};
 use rand::{CryptoRng, Rng};
 use std::{
+    collections::{BTreeMap, HashSet},
     num::NonZero,
     sync::{atomic::AtomicU64, Arc, Mutex},
+    time::Duration,
 };
 use tracing::{debug, info, warn};
 
@@ -51,6 +70,25 @@ const LATENCY: [f64; 20] = [
 /// Attempt to prune the state every 10000 blocks (randomly).
 const PRUNE_INTERVAL: u64 = 10_000;
 
+/// Number of mempool decisions to retain in the admission log.
+const ADMISSION_LOG_CAPACITY: u64 = 100_000;
+
+/// Number of admission records per blob (the log is pruned a blob at a time).
+const ADMISSION_LOG_ITEMS_PER_BLOB: NonZero<u64> = NZU64!(10_000);
+
+/// How often to persist the admission log.
+const ADMISSION_LOG_SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often to evict transactions that have outlived the mempool TTL.
+const MEMPOOL_SWEEP_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Number of state operations read at a time when looking up a historical value.
+const STATE_AT_BATCH: u64 = 1_024;
+
+/// Maximum number of state operations to walk back when looking up a historical value (the
+/// lookup blocks the application, so it can't scan an unbounded history).
+const MAX_STATE_AT_SCAN: u64 = 65_536;
+
 // OPTIMIZATION: Consider caching ancestry computation results.
 // Currently recomputes ancestry on each call. A LRU cache keyed by (start, end)
 // could significantly reduce computation for repeated queries.
@@ -87,6 +125,7 @@ pub struct Actor<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: In
     context: R,
     inbound: Mailbox<R>,
     mailbox: mpsc::Receiver<Message<R>>,
+    admissions: Admissions,
     identity: Identity,
     partition_prefix: String,
     mmr_items_per_blob: NonZero<u64>,
@@ -97,8 +136,12 @@ pub struct Actor<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: In
     buffer_pool: PoolRef,
     indexer: I,
     execution_concurrency: usize,
-    mempool_max_backlog: usize,
-    mempool_max_transactions: usize,
+    tunables: Tunables,
+    max_block_txs: usize,
+    max_block_bytes: usize,
+    mempool_tx_ttl: Duration,
+    archive: bool,
+    exporter: Option<export::Mailbox>,
 }
 
 impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor<R, I> {
@@ -109,11 +152,17 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
     ) -> (Self, ViewSupervisor, EpochSupervisor, Mailbox<R>) {
         // Create actor
         let (sender, mailbox) = mpsc::channel(config.mailbox_size);
-        let inbound = Mailbox::new(sender);
+        let admissions = Admissions::default();
+        let inbound = Mailbox::new(sender, admissions.clone());
 
         // Create supervisors
         let identity = *public::<MinSig>(&config.polynomial);
-        let supervisor = Supervisor::new(config.polynomial, config.participants, config.share);
+        let supervisor = Supervisor::new(
+            config.polynomial,
+            config.participants,
+            config.share,
+            &config.rotations,
+        );
         let view_supervisor = ViewSupervisor::new(supervisor.clone());
         let epoch_supervisor = EpochSupervisor::new(supervisor);
 
@@ -122,6 +171,7 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
                 context,
                 mailbox,
                 inbound: inbound.clone(),
+                admissions,
                 identity,
                 partition_prefix: config.partition_prefix,
                 mmr_items_per_blob: config.mmr_items_per_blob,
@@ -132,8 +182,12 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
                 buffer_pool: config.buffer_pool,
                 indexer: config.indexer,
                 execution_concurrency: config.execution_concurrency,
-                mempool_max_backlog: config.mempool_max_backlog,
-                mempool_max_transactions: config.mempool_max_transactions,
+                tunables: config.tunables,
+                max_block_txs: config.max_block_txs,
+                max_block_bytes: config.max_block_bytes,
+                mempool_tx_ttl: config.mempool_tx_ttl,
+                archive: config.archive,
+                exporter: config.exporter,
             },
             view_supervisor,
             epoch_supervisor,
@@ -159,7 +213,10 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
     ) {
         // Initialize metrics
         let txs_considered: Counter<u64, AtomicU64> = Counter::default();
+        let txs_deferred: Counter<u64, AtomicU64> = Counter::default();
         let txs_executed: Counter<u64, AtomicU64> = Counter::default();
+        let txs_expired: Counter<u64, AtomicU64> = Counter::default();
+        let keys_prefetched: Counter<u64, AtomicU64> = Counter::default();
         let ancestry_latency = Histogram::new(LATENCY.into_iter());
         let propose_latency = Histogram::new(LATENCY.into_iter());
         let verify_latency = Histogram::new(LATENCY.into_iter());
@@ -172,11 +229,26 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
             "Number of transactions considered during propose",
             txs_considered.clone(),
         );
+        self.context.register(
+            "txs_deferred",
+            "Number of transactions deferred to a later block during propose",
+            txs_deferred.clone(),
+        );
         self.context.register(
             "txs_executed",
             "Number of transactions executed after finalization",
             txs_executed.clone(),
         );
+        self.context.register(
+            "txs_expired",
+            "Number of transactions evicted from the mempool after outliving their TTL",
+            txs_expired.clone(),
+        );
+        self.context.register(
+            "keys_prefetched",
+            "Number of state keys prefetched for upcoming blocks",
+            keys_prefetched.clone(),
+        );
         self.context.register(
             "ancestry_latency",
             "Latency of ancestry requests",
@@ -290,6 +362,18 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
         )
         .await
         .unwrap();
+        let mut admissions = AdmissionLog::init(
+            self.context.with_label("admissions"),
+            admission::Config {
+                partition: format!("{}-admissions", self.partition_prefix),
+                capacity: ADMISSION_LOG_CAPACITY,
+                items_per_blob: ADMISSION_LOG_ITEMS_PER_BLOB,
+                write_buffer: self.log_write_buffer,
+                buffer_pool: self.buffer_pool.clone(),
+            },
+            self.admissions.clone(),
+        )
+        .await;
 
         // Create the execution pool
         //
@@ -309,11 +393,15 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
         let built: Option<(View, Block)> = None;
         let built = Arc::new(Mutex::new(built));
 
+        // Track finalized blocks awaiting execution (so we can warm their state while
+        // generating proofs for the previous block)
+        let mut upcoming: BTreeMap<u64, Vec<Transaction>> = BTreeMap::new();
+
         // Initialize mempool
         let mut mempool = Mempool::new_with_limits(
             self.context.with_label("mempool"),
-            self.mempool_max_backlog,
-            self.mempool_max_transactions,
+            self.tunables.mempool_max_backlog(),
+            self.tunables.mempool_max_transactions(),
         );
 
         // Use reconnecting indexer wrapper
@@ -324,6 +412,8 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
 
         // This will never fail and handles reconnection internally
         let mut next_prune = self.context.gen_range(1..=PRUNE_INTERVAL);
+        let mut next_sweep = self.context.current() + MEMPOOL_SWEEP_INTERVAL;
+        let mut next_admissions_sync = self.context.current() + ADMISSION_LOG_SYNC_INTERVAL;
         let mut tx_stream = Box::pin(reconnecting_indexer.listen_mempool().await.unwrap());
         loop {
             select! {
@@ -337,6 +427,65 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
                                 // payload.
                                 let _ = response.send(genesis_digest);
                             }
+                            Message::Mempool { response } => {
+                                let _ = response.send(mempool.transactions());
+                            }
+                            Message::Prove { key, size, response } => {
+                                // Only a value set before `size` can be proven against it (if the key was
+                                // updated since, the caller must wait for a later `size`)
+                                let Ok(Some((_, loc))) = state.get_with_loc(&key).await else {
+                                    let _ = response.send(None);
+                                    continue;
+                                };
+                                if loc >= size {
+                                    let _ = response.send(None);
+                                    continue;
+                                }
+                                let proof = match state.historical_proof(size, loc, 1).await {
+                                    Ok((proof, mut ops)) => ops.pop().map(|op| (proof, loc, op)),
+                                    Err(err) => {
+                                        warn!(?err, loc, size, "failed to generate lookup proof");
+                                        None
+                                    }
+                                };
+                                let _ = response.send(proof);
+                            }
+                            Message::StateAt { key, size, response } => {
+                                // If the current value was set before `size`, it was also the value at `size`
+                                let size = size.min(state.op_count());
+                                if let Ok(Some((value, loc))) = state.get_with_loc(&key).await {
+                                    if loc < size {
+                                        let _ = response.send(Some((loc, Variable::Update(key, value))));
+                                        continue;
+                                    }
+                                }
+
+                                // Otherwise, walk back from `size` to the last operation on the key (only archive
+                                // nodes retain the operations that were overwritten)
+                                if !self.archive {
+                                    let _ = response.send(None);
+                                    continue;
+                                }
+                                let floor = size.saturating_sub(MAX_STATE_AT_SCAN);
+                                let mut end = size;
+                                let mut found = None;
+                                while found.is_none() && end > floor {
+                                    let start = end.saturating_sub(STATE_AT_BATCH).max(floor);
+                                    let ops = match state.historical_proof(size, start, end - start).await {
+                                        Ok((_, ops)) => ops,
+                                        Err(err) => {
+                                            warn!(?err, start, size, "failed to read historical state");
+                                            break;
+                                        }
+                                    };
+                                    found = ops.into_iter().enumerate().rev().find_map(|(i, op)| {
+                                        let matched = matches!(&op, Variable::Update(k, _) | Variable::Delete(k) if *k == key);
+                                        matched.then(|| (start + i as u64, op))
+                                    });
+                                    end = start;
+                                }
+                                let _ = response.send(found.filter(|(_, op)| matches!(op, Variable::Update(..))));
+                            }
                             Message::Propose {
                                 view,
                                 parent,
@@ -354,7 +503,7 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
                                 }
 
                                 // Get the ancestry
-                                let ancestry = ancestry(marshal.clone(), (Some(parent.0), parent.1), state.get_metadata().await.unwrap().and_then(|(_, v)| match v {
+                                let ancestry = ancestry(marshal.clone(), (Some(parent.0), parent.1), state.get_metadata().await.unwrap().and_then(|(_, v)| match v.map(Value::into_inner) {
                                     Some(Value::Commit { height, start: _ }) => Some(height),
                                     _ => None,
                                 }).unwrap_or(0));
@@ -396,7 +545,7 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
                                 let parent = blocks.last().unwrap();
 
                                 // Find first block on top of finalized state (may have increased since we started)
-                                let height = state.get_metadata().await.unwrap().and_then(|(_, v)| match v {
+                                let height = state.get_metadata().await.unwrap().and_then(|(_, v)| match v.map(Value::into_inner) {
                                     Some(Value::Commit { height, start: _ }) => Some(height),
                                     _ => None,
                                 }).unwrap_or(0);
@@ -415,28 +564,55 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
                                     }
                                 }
 
-                                // Select up to max transactions
+                                // Select transactions until the block is full
                                 let mut considered = 0;
                                 let mut transactions = Vec::new();
-                                while transactions.len() < MAX_BLOCK_TRANSACTIONS {
+                                let mut budget = Budget::new(self.max_block_txs, self.max_block_bytes);
+                                let mut deferred = Vec::new();
+                                let mut deferred_accounts = HashSet::new();
+                                while !budget.is_full() && deferred.len() < self.max_block_txs {
                                     // Get next transaction
-                                    let Some(tx) = mempool.next() else {
+                                    let Some((tx, admitted)) = mempool.next() else {
                                         break;
                                     };
                                     considered += 1;
 
+                                    // Drop transactions using a version that isn't active yet
+                                    let now = self.context.current();
+                                    if tx.version > protocol_version(parent.height + 1) {
+                                        admissions.record(Record::new(now, &tx, Decision::Evicted(Reason::InactiveVersion))).await;
+                                        continue;
+                                    }
+
+                                    // Defer transactions that don't fit (and any later transactions from
+                                    // the same account, to avoid a nonce gap) to a future block
+                                    if deferred_accounts.contains(&tx.public) || !budget.try_add(&tx) {
+                                        deferred_accounts.insert(tx.public.clone());
+                                        deferred.push((tx, admitted));
+                                        continue;
+                                    }
+
                                     // Attempt to apply
                                     if noncer.prepare(&tx).await.is_err() {
+                                        admissions.record(Record::new(now, &tx, Decision::Evicted(Reason::InvalidNonce))).await;
                                         continue;
                                     }
 
                                     // Add to transactions
+                                    admissions.record(Record::new(now, &tx, Decision::Selected(view))).await;
                                     transactions.push(tx);
                                 }
                                 let txs = transactions.len();
+                                let postponed = deferred.len();
+                                for (tx, admitted) in deferred {
+                                    if let Admission::Admitted { evicted: Some(evicted) } = mempool.defer(tx, admitted) {
+                                        admissions.record(Record::new(self.context.current(), &evicted, Decision::Evicted(Reason::BacklogExceeded))).await;
+                                    }
+                                }
 
                                 // Update metrics
                                 txs_considered.inc_by(considered as u64);
+                                txs_deferred.inc_by(postponed as u64);
 
                                 // When ancestry for propose is provided, we can attempt to pack a block
                                 let block = Block::new(parent.digest(), view, parent.height+1, transactions);
@@ -549,6 +725,18 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
                                 let seeded_timer = seeded_latency.timer();
                                 let finalize_timer = finalize_latency.timer();
 
+                                // Evict transactions invalidated by the block (without waiting for it to be
+                                // executed, so they aren't proposed again in the meantime)
+                                let now = self.context.current();
+                                for tx in &block.transactions {
+                                    if let Some(evicted) = mempool.remove_nonce(&tx.public, tx.nonce) {
+                                        admissions.record(Record::new(now, &evicted, Decision::Evicted(Reason::Finalized))).await;
+                                    }
+                                }
+
+                                // Remember the transactions to prefetch their state
+                                upcoming.insert(block.height, block.transactions.clone());
+
                                 // While waiting for the seed required for processing, we should spawn a task
                                 // to handle resolution to avoid blocking the application.
                                 self.context.with_label("seeded").spawn({
@@ -599,18 +787,36 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
                                 txs_executed.inc_by(tx_count as u64);
 
                                 // Update mempool based on processed transactions
+                                let now = self.context.current();
                                 for (public, next_nonce) in &result.processed_nonces {
-                                    mempool.retain(public, *next_nonce);
+                                    for tx in mempool.retain(public, *next_nonce) {
+                                        admissions.record(Record::new(now, &tx, Decision::Evicted(Reason::Executed))).await;
+                                    }
                                 }
 
                                 // Generate range proof for changes
+                                //
+                                // While proofs are generated, we load the state touched by the next block (if already
+                                // finalized) so that its execution doesn't wait on storage.
+                                upcoming.retain(|pending, _| *pending > height);
+                                let next = upcoming.remove(&(height + 1)).unwrap_or_default();
                                 let state_proof_ops = result.state_end_op - result.state_start_op;
                                 let events_start_op = result.events_start_op;
                                 let events_proof_ops = result.events_end_op - events_start_op;
-                                let ((state_proof, state_proof_ops), (events_proof, events_proof_ops)) = try_join(
-                                    state.historical_proof(result.state_end_op, result.state_start_op, state_proof_ops),
-                                    events.historical_proof(result.events_end_op, events_start_op, NZU64!(events_proof_ops)),
-                                ).await.expect("failed to generate proofs");
+                                let (proofs, prefetched) = join(
+                                    try_join(
+                                        state.historical_proof(result.state_end_op, result.state_start_op, state_proof_ops),
+                                        events.historical_proof(result.events_end_op, events_start_op, NZU64!(events_proof_ops)),
+                                    ),
+                                    prefetch(&state, &next),
+                                ).await;
+                                let ((state_proof, state_proof_ops), (events_proof, events_proof_ops)) = proofs.expect("failed to generate proofs");
+                                keys_prefetched.inc_by(prefetched as u64);
+
+                                // Export events
+                                if let Some(exporter) = &mut self.exporter {
+                                    exporter.export(height, events_start_op, &events_proof_ops);
+                                }
 
                                 // Send to aggregator
                                 aggregator.executed(block.view, block.height, commitment, result, state_proof, state_proof_ops, events_proof, events_proof_ops, response).await;
@@ -619,22 +825,48 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
                                 drop(timer);
 
                                 // Attempt to prune (this syncs data prior to prune, so we don't need to call separately)
-                                next_prune -= 1;
-                                if next_prune == 0 {
-                                    // Prune storage
-                                    let timer = prune_latency.timer();
-                                    try_join(
-                                        state.prune(state.inactivity_floor_loc()),
-                                        events.prune(events_start_op),
-                                    ).await.expect("failed to prune storage");
-                                    drop(timer);
-
-                                    // Reset next prune
-                                    next_prune = self.context.gen_range(1..=PRUNE_INTERVAL);
+                                //
+                                // In archive mode, we retain every operation so historical proofs (and values) can be
+                                // served for any height.
+                                if !self.archive {
+                                    next_prune -= 1;
+                                    if next_prune == 0 {
+                                        // Prune storage
+                                        let timer = prune_latency.timer();
+                                        try_join(
+                                            state.prune(state.inactivity_floor_loc()),
+                                            events.prune(events_start_op),
+                                        ).await.expect("failed to prune storage");
+                                        drop(timer);
+
+                                        // Reset next prune
+                                        next_prune = self.context.gen_range(1..=PRUNE_INTERVAL);
+                                    }
                                 }
                             },
                         }
                 },
+                _ = self.context.sleep_until(next_sweep) => {
+                    next_sweep = self.context.current() + MEMPOOL_SWEEP_INTERVAL;
+
+                    // Evict transactions that have waited too long
+                    let expired = mempool.expire(self.mempool_tx_ttl);
+                    if expired.is_empty() {
+                        continue;
+                    }
+                    debug!(expired = expired.len(), "expired mempool transactions");
+                    txs_expired.inc_by(expired.len() as u64);
+                    let now = self.context.current();
+                    for tx in expired {
+                        admissions.record(Record::new(now, &tx, Decision::Evicted(Reason::Expired))).await;
+                    }
+                },
+                _ = self.context.sleep_until(next_admissions_sync) => {
+                    next_admissions_sync = self.context.current() + ADMISSION_LOG_SYNC_INTERVAL;
+
+                    // Persist admission records periodically (rather than an fsync per batch)
+                    admissions.sync().await;
+                },
                 pending = tx_stream.next() => {
                     // The reconnecting wrapper handles all connection issues internally
                     // We only get Some(Ok(tx)) for valid transactions
@@ -644,19 +876,61 @@ impl<R: Rng + CryptoRng + Spawner + Metrics + Clock + Storage, I: Indexer> Actor
                         continue;
                     };
 
+                    // Pick up any limits changed at runtime
+                    mempool.set_limits(
+                        self.tunables.mempool_max_backlog(),
+                        self.tunables.mempool_max_transactions(),
+                    );
+
                     // Process transactions (already verified in indexer client)
+                    let now = self.context.current();
+                    let max_backlog = self.tunables.mempool_max_backlog() as u64;
+                    let mut rejections = Vec::new();
                     for tx in pending.transactions {
+                        // Check if the transaction can ever be executed (moves may use more
+                        // units once executed, which is checked then)
+                        let units = tx.instruction.compute_units();
+                        if units > MAX_TRANSACTION_COMPUTE_UNITS {
+                            debug!(units, "dropping transaction over compute limit");
+                            admissions.record(Record::new(now, &tx, Decision::Rejected(Reason::ComputeLimitExceeded))).await;
+                            rejections.push(Rejection::new(&tx, RejectionReason::ComputeLimitExceeded { units }));
+                            continue;
+                        }
+
                         // Check if below next
                         let next = nonce(&state, &tx.public).await;
                         if tx.nonce < next {
                             // If below next, we drop the incoming transaction
                             debug!(tx = tx.nonce, state = next, "dropping incoming transaction");
+                            admissions.record(Record::new(now, &tx, Decision::Rejected(Reason::StaleNonce))).await;
+                            rejections.push(Rejection::new(&tx, RejectionReason::StaleNonce { next }));
+                            continue;
+                        }
+
+                        // Check if too far ahead of next (it can't be included until the
+                        // account's entire backlog is executed)
+                        if tx.nonce - next >= max_backlog {
+                            debug!(tx = tx.nonce, state = next, "dropping future transaction");
+                            admissions.record(Record::new(now, &tx, Decision::Rejected(Reason::FutureNonce))).await;
+                            rejections.push(Rejection::new(&tx, RejectionReason::FutureNonce { next }));
                             continue;
                         }
 
                         // Add to mempool
-                        mempool.add(tx);
+                        let record = Record::new(now, &tx, Decision::Admitted);
+                        match mempool.add(tx) {
+                            Admission::Admitted { evicted } => {
+                                admissions.record(record).await;
+                                if let Some(evicted) = evicted {
+                                    admissions.record(Record::new(now, &evicted, Decision::Evicted(Reason::BacklogExceeded))).await;
+                                }
+                            }
+                            Admission::Rejected(reason) => {
+                                admissions.record(Record { decision: Decision::Rejected(reason), ..record }).await;
+                            }
+                        }
                     }
+                    spawn_report(&self.context, &reconnecting_indexer, rejections);
                 }
             }
         }
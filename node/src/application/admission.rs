@@ -0,0 +1,500 @@
+use bytes::{Buf, BufMut};
+use commonware_codec::{Error, FixedSize, Read, ReadExt, Write};
+use commonware_consensus::threshold_simplex::types::View;
+use commonware_cryptography::{ed25519::PublicKey, sha256::Digest, Digestible};
+use commonware_runtime::{buffer::PoolRef, Clock, Metrics, Storage};
+use commonware_storage::journal::fixed;
+use nullspace_types::execution::Transaction;
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    hash::Hash,
+    num::{NonZero, NonZeroU64},
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Why a transaction was rejected by (or removed from) the mempool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reason {
+    /// The mempool already holds the maximum number of transactions.
+    MempoolFull,
+    /// The transaction is already in the mempool.
+    Duplicate,
+    /// Another transaction from the same account is tracked at this nonce.
+    NonceTaken,
+    /// The account already has the maximum number of transactions in the mempool.
+    BacklogExceeded,
+    /// The nonce was already used when the transaction arrived.
+    StaleNonce,
+    /// The nonce was invalid when the transaction was considered for a block.
+    InvalidNonce,
+    /// The account's nonce advanced past the transaction (it, or a conflicting
+    /// transaction, was executed).
+    Executed,
+    /// The transaction waited in the mempool for longer than its time-to-live.
+    Expired,
+    /// A transaction from the same account at the same nonce was included in a finalized
+    /// block.
+    Finalized,
+    /// The nonce was too far ahead of the account's next nonce when the transaction
+    /// arrived.
+    FutureNonce,
+    /// The transaction uses more compute units than a transaction may.
+    ComputeLimitExceeded,
+    /// The transaction's version wasn't active when it was considered for a block.
+    InactiveVersion,
+}
+
+impl Reason {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Reason::MempoolFull => "mempool_full",
+            Reason::Duplicate => "duplicate",
+            Reason::NonceTaken => "nonce_taken",
+            Reason::BacklogExceeded => "backlog_exceeded",
+            Reason::StaleNonce => "stale_nonce",
+            Reason::InvalidNonce => "invalid_nonce",
+            Reason::Executed => "executed",
+            Reason::Expired => "expired",
+            Reason::Finalized => "finalized",
+            Reason::FutureNonce => "future_nonce",
+            Reason::ComputeLimitExceeded => "compute_limit_exceeded",
+            Reason::InactiveVersion => "inactive_version",
+        }
+    }
+
+    fn code(&self) -> u64 {
+        match self {
+            Reason::MempoolFull => 0,
+            Reason::Duplicate => 1,
+            Reason::NonceTaken => 2,
+            Reason::BacklogExceeded => 3,
+            Reason::StaleNonce => 4,
+            Reason::InvalidNonce => 5,
+            Reason::Executed => 6,
+            Reason::Expired => 7,
+            Reason::Finalized => 8,
+            Reason::FutureNonce => 9,
+            Reason::ComputeLimitExceeded => 10,
+            Reason::InactiveVersion => 11,
+        }
+    }
+
+    fn from_code(code: u64) -> Result<Self, Error> {
+        match code {
+            0 => Ok(Reason::MempoolFull),
+            1 => Ok(Reason::Duplicate),
+            2 => Ok(Reason::NonceTaken),
+            3 => Ok(Reason::BacklogExceeded),
+            4 => Ok(Reason::StaleNonce),
+            5 => Ok(Reason::InvalidNonce),
+            6 => Ok(Reason::Executed),
+            7 => Ok(Reason::Expired),
+            8 => Ok(Reason::Finalized),
+            9 => Ok(Reason::FutureNonce),
+            10 => Ok(Reason::ComputeLimitExceeded),
+            11 => Ok(Reason::InactiveVersion),
+            _ => Err(Error::Invalid("Reason", "unknown reason")),
+        }
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A decision made by the mempool about a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decision {
+    /// Added to the mempool.
+    Admitted,
+    /// Not added to the mempool.
+    Rejected(Reason),
+    /// Included in a block proposed at the given view.
+    Selected(View),
+    /// Removed from the mempool.
+    Evicted(Reason),
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Decision::Admitted => write!(f, "admitted"),
+            Decision::Rejected(reason) => write!(f, "rejected({reason})"),
+            Decision::Selected(view) => write!(f, "selected(view={view})"),
+            Decision::Evicted(reason) => write!(f, "evicted({reason})"),
+        }
+    }
+}
+
+/// An entry in the [AdmissionLog].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    /// Milliseconds since the UNIX epoch.
+    pub time: u64,
+    pub digest: Digest,
+    pub public: PublicKey,
+    pub nonce: u64,
+    pub decision: Decision,
+}
+
+impl Record {
+    pub fn new(now: SystemTime, tx: &Transaction, decision: Decision) -> Self {
+        Self {
+            time: now
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+            digest: tx.digest(),
+            public: tx.public.clone(),
+            nonce: tx.nonce,
+            decision,
+        }
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} nonce={} {}",
+            self.time, self.digest, self.public, self.nonce, self.decision
+        )
+    }
+}
+
+impl Write for Record {
+    fn write(&self, buf: &mut impl BufMut) {
+        self.time.write(buf);
+        self.digest.write(buf);
+        self.public.write(buf);
+        self.nonce.write(buf);
+        let (tag, detail) = match self.decision {
+            Decision::Admitted => (0u8, 0),
+            Decision::Rejected(reason) => (1, reason.code()),
+            Decision::Selected(view) => (2, view),
+            Decision::Evicted(reason) => (3, reason.code()),
+        };
+        tag.write(buf);
+        detail.write(buf);
+    }
+}
+
+impl Read for Record {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &()) -> Result<Self, Error> {
+        let time = u64::read(reader)?;
+        let digest = Digest::read(reader)?;
+        let public = PublicKey::read(reader)?;
+        let nonce = u64::read(reader)?;
+        let tag = u8::read(reader)?;
+        let detail = u64::read(reader)?;
+        let decision = match tag {
+            0 => Decision::Admitted,
+            1 => Decision::Rejected(Reason::from_code(detail)?),
+            2 => Decision::Selected(detail),
+            3 => Decision::Evicted(Reason::from_code(detail)?),
+            i => return Err(Error::InvalidEnum(i)),
+        };
+        Ok(Self {
+            time,
+            digest,
+            public,
+            nonce,
+            decision,
+        })
+    }
+}
+
+impl FixedSize for Record {
+    const SIZE: usize =
+        u64::SIZE + Digest::SIZE + PublicKey::SIZE + u64::SIZE + u8::SIZE + u64::SIZE;
+}
+
+/// Selects which records to return from the [AdmissionLog].
+#[derive(Clone, Debug)]
+pub enum Filter {
+    Transaction(Digest),
+    Account(PublicKey),
+}
+
+/// Positions of the retained records, by transaction and by account.
+#[derive(Default)]
+struct Index {
+    /// Position of the oldest record in `records`.
+    oldest: u64,
+    records: VecDeque<Record>,
+    transactions: HashMap<Digest, VecDeque<u64>>,
+    accounts: HashMap<PublicKey, VecDeque<u64>>,
+}
+
+impl Index {
+    fn push(&mut self, record: Record, capacity: u64) {
+        let pos = self.oldest + self.records.len() as u64;
+        self.transactions
+            .entry(record.digest)
+            .or_default()
+            .push_back(pos);
+        self.accounts
+            .entry(record.public.clone())
+            .or_default()
+            .push_back(pos);
+        self.records.push_back(record);
+
+        // Records are evicted oldest first, so their positions are at the front of each list
+        while self.records.len() as u64 > capacity {
+            let record = self.records.pop_front().expect("index is not empty");
+            Self::evict(&mut self.transactions, &record.digest);
+            Self::evict(&mut self.accounts, &record.public);
+            self.oldest += 1;
+        }
+    }
+
+    fn evict<K: Eq + Hash>(positions: &mut HashMap<K, VecDeque<u64>>, key: &K) {
+        let Some(list) = positions.get_mut(key) else {
+            return;
+        };
+        list.pop_front();
+        if list.is_empty() {
+            positions.remove(key);
+        }
+    }
+
+    fn query(&self, filter: &Filter, limit: usize) -> Vec<Record> {
+        let positions = match filter {
+            Filter::Transaction(digest) => self.transactions.get(digest),
+            Filter::Account(public) => self.accounts.get(public),
+        };
+        let Some(positions) = positions else {
+            return Vec::new();
+        };
+        let skip = positions.len().saturating_sub(limit);
+        positions
+            .iter()
+            .skip(skip)
+            .map(|pos| self.records[(pos - self.oldest) as usize].clone())
+            .collect()
+    }
+}
+
+/// A handle for querying the [AdmissionLog] from outside the application (queries are
+/// served from memory and never wait on the log's storage).
+#[derive(Clone, Default)]
+pub struct Admissions {
+    index: Arc<Mutex<Index>>,
+}
+
+impl Admissions {
+    /// Return up to `limit` of the most recent records matching `filter` (oldest first).
+    pub fn query(&self, filter: &Filter, limit: usize) -> Vec<Record> {
+        self.index.lock().unwrap().query(filter, limit)
+    }
+}
+
+/// Configuration for the [AdmissionLog].
+pub struct Config {
+    pub partition: String,
+    /// Number of records to retain (older records are pruned a blob at a time).
+    pub capacity: u64,
+    pub items_per_blob: NonZeroU64,
+    pub write_buffer: NonZero<usize>,
+    pub buffer_pool: PoolRef,
+}
+
+/// A bounded, on-disk log of every decision the mempool makes about a transaction.
+///
+/// Records are buffered until [AdmissionLog::sync] is called. The retained records are also
+/// indexed in memory (shared with [Admissions]), so queries don't read the journal.
+pub struct AdmissionLog<E: Storage + Metrics + Clock> {
+    journal: fixed::Journal<E, Record>,
+    capacity: u64,
+    items_per_blob: u64,
+    admissions: Admissions,
+    /// Whether records were appended since the last sync.
+    dirty: bool,
+}
+
+impl<E: Storage + Metrics + Clock> AdmissionLog<E> {
+    /// Initialize the log (indexing any existing records in `admissions`).
+    pub async fn init(context: E, cfg: Config, admissions: Admissions) -> Self {
+        let journal = fixed::Journal::init(
+            context,
+            fixed::Config {
+                partition: cfg.partition,
+                items_per_blob: cfg.items_per_blob,
+                write_buffer: cfg.write_buffer,
+                buffer_pool: cfg.buffer_pool,
+            },
+        )
+        .await
+        .expect("failed to initialize admission log");
+
+        // Index the records retained from a previous run
+        let size = journal.size().await.expect("failed to get size");
+        let oldest = journal
+            .oldest_retained_pos()
+            .await
+            .expect("failed to get oldest position")
+            .unwrap_or(size);
+        let mut index = Index::default();
+        for pos in oldest..size {
+            let Ok(record) = journal.read(pos).await else {
+                continue;
+            };
+            index.push(record, cfg.capacity);
+        }
+        *admissions.index.lock().unwrap() = index;
+        Self {
+            journal,
+            capacity: cfg.capacity,
+            items_per_blob: cfg.items_per_blob.get(),
+            admissions,
+            dirty: false,
+        }
+    }
+
+    /// Append a record (pruning the oldest records once over capacity).
+    pub async fn record(&mut self, record: Record) {
+        self.admissions
+            .index
+            .lock()
+            .unwrap()
+            .push(record.clone(), self.capacity);
+        self.dirty = true;
+        let pos = self
+            .journal
+            .append(record)
+            .await
+            .expect("failed to append admission record");
+        let size = pos + 1;
+        if size > self.capacity && size % self.items_per_blob == 0 {
+            self.journal
+                .prune(size - self.capacity)
+                .await
+                .expect("failed to prune admission log");
+        }
+    }
+
+    /// Persist all appended records (if any).
+    pub async fn sync(&mut self) {
+        if !std::mem::take(&mut self.dirty) {
+            return;
+        }
+        self.journal
+            .sync()
+            .await
+            .expect("failed to sync admission log");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commonware_codec::{DecodeExt, Encode};
+    use commonware_cryptography::{ed25519::PrivateKey, PrivateKeyExt, Signer};
+    use commonware_runtime::{deterministic, Runner};
+    use commonware_utils::{NZUsize, NZU64};
+    use nullspace_types::execution::Instruction;
+
+    fn tx(seed: u64, nonce: u64) -> Transaction {
+        Transaction::sign(
+            &PrivateKey::from_seed(seed),
+            nonce,
+            Instruction::CasinoDeposit { amount: 1 },
+        )
+    }
+
+    fn config(capacity: u64) -> Config {
+        Config {
+            partition: "admissions".to_string(),
+            capacity,
+            items_per_blob: NZU64!(4),
+            write_buffer: NZUsize!(1024),
+            buffer_pool: PoolRef::new(NZUsize!(1024), NZUsize!(10)),
+        }
+    }
+
+    #[test]
+    fn test_record_codec() {
+        let now = UNIX_EPOCH;
+        for decision in [
+            Decision::Admitted,
+            Decision::Rejected(Reason::BacklogExceeded),
+            Decision::Selected(42),
+            Decision::Evicted(Reason::Executed),
+        ] {
+            let record = Record::new(now, &tx(0, 1), decision);
+            let encoded = record.encode();
+            assert_eq!(encoded.len(), Record::SIZE);
+            assert_eq!(Record::decode(encoded).unwrap(), record);
+        }
+    }
+
+    #[test]
+    fn test_query() {
+        let executor = deterministic::Runner::default();
+        executor.start(|context| async move {
+            let admissions = Admissions::default();
+            let mut log =
+                AdmissionLog::init(context.clone(), config(100), admissions.clone()).await;
+            let (a, b) = (tx(0, 0), tx(1, 0));
+            let now = context.current();
+            log.record(Record::new(now, &a, Decision::Admitted)).await;
+            log.record(Record::new(now, &b, Decision::Rejected(Reason::StaleNonce)))
+                .await;
+            log.record(Record::new(now, &a, Decision::Selected(7)))
+                .await;
+            log.sync().await;
+
+            let records = admissions.query(&Filter::Transaction(a.digest()), 10);
+            let decisions: Vec<_> = records.iter().map(|r| r.decision).collect();
+            assert_eq!(decisions, vec![Decision::Admitted, Decision::Selected(7)]);
+
+            let records = admissions.query(&Filter::Account(b.public.clone()), 10);
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0].decision, Decision::Rejected(Reason::StaleNonce));
+
+            // Only the most recent records are returned when limited
+            let records = admissions.query(&Filter::Transaction(a.digest()), 1);
+            assert_eq!(records[0].decision, Decision::Selected(7));
+        });
+    }
+
+    #[test]
+    fn test_bounded() {
+        let executor = deterministic::Runner::default();
+        executor.start(|context| async move {
+            let admissions = Admissions::default();
+            let mut log = AdmissionLog::init(context.clone(), config(8), admissions.clone()).await;
+            let a = tx(0, 0);
+            for view in 0..32 {
+                log.record(Record::new(context.current(), &a, Decision::Selected(view)))
+                    .await;
+            }
+            log.sync().await;
+
+            // The index only holds the most recent records
+            let records = admissions.query(&Filter::Transaction(a.digest()), 100);
+            let decisions: Vec<_> = records.iter().map(|r| r.decision).collect();
+            assert_eq!(
+                decisions,
+                (24..32).map(Decision::Selected).collect::<Vec<_>>()
+            );
+            assert!(admissions
+                .query(&Filter::Account(a.public.clone()), 100)
+                .iter()
+                .eq(records.iter()));
+
+            // And is rebuilt from the journal on restart
+            drop(log);
+            let admissions = Admissions::default();
+            let _log = AdmissionLog::init(context.clone(), config(8), admissions.clone()).await;
+            let restored = admissions.query(&Filter::Transaction(a.digest()), 100);
+            assert_eq!(restored, records);
+        });
+    }
+}
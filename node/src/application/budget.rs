@@ -0,0 +1,185 @@
+use commonware_codec::EncodeSize;
+use nullspace_types::execution::{
+    Instruction, Transaction, COMPUTE_UNITS_PER_BET, MAX_BLOCK_COMPUTE_UNITS,
+};
+
+/// Payload length (in bytes) above which a [Instruction::CasinoGameMove] is considered large
+/// (e.g. a craps move placing many bets).
+const LARGE_MOVE_PAYLOAD: usize = 64;
+
+/// Fraction of a block's bytes that large moves may occupy (once at least one has been
+/// included).
+const LARGE_MOVE_SHARE: usize = 4;
+
+/// Returns true if `instruction` is (or is a batch containing) a [Instruction::CasinoGameMove]
+/// with a large payload.
+fn is_large_move(instruction: &Instruction) -> bool {
+    match instruction {
+        Instruction::CasinoGameMove { payload, .. } => payload.len() > LARGE_MOVE_PAYLOAD,
+        Instruction::Batch(instructions) => instructions.iter().any(is_large_move),
+        _ => false,
+    }
+}
+
+/// Most bets a session can hold (craps, roulette and sic bo tables).
+const MAX_SESSION_BETS: u64 = 20;
+
+/// Compute units `instruction` may use when executed (assuming every move is made in a
+/// session holding the most bets possible, so packed blocks never exceed their compute
+/// budget).
+fn max_compute_units(instruction: &Instruction) -> u64 {
+    let moves = match instruction {
+        Instruction::CasinoGameMove { .. } => 1,
+        Instruction::Batch(instructions) => instructions
+            .iter()
+            .filter(|i| matches!(i, Instruction::CasinoGameMove { .. }))
+            .count() as u64,
+        _ => 0,
+    };
+    instruction
+        .compute_units()
+        .saturating_add(moves * MAX_SESSION_BETS * COMPUTE_UNITS_PER_BET)
+}
+
+/// Tracks the space remaining in a block being packed.
+///
+/// Large moves are accepted while they fit in their share of the block (so a few of them
+/// can't crowd out everything else), but the first large move is always accepted if it fits
+/// in the block at all (so they are never starved by a small `max_block_bytes`).
+pub struct Budget {
+    max_txs: usize,
+    max_bytes: usize,
+    max_large_bytes: usize,
+
+    txs: usize,
+    bytes: usize,
+    large_bytes: usize,
+    compute_units: u64,
+}
+
+impl Budget {
+    pub fn new(max_txs: usize, max_bytes: usize) -> Self {
+        Self {
+            max_txs,
+            max_bytes,
+            max_large_bytes: max_bytes / LARGE_MOVE_SHARE,
+            txs: 0,
+            bytes: 0,
+            large_bytes: 0,
+            compute_units: 0,
+        }
+    }
+
+    /// Returns true if no more transactions can be added to the block.
+    pub fn is_full(&self) -> bool {
+        self.txs >= self.max_txs
+            || self.bytes >= self.max_bytes
+            || self.compute_units >= MAX_BLOCK_COMPUTE_UNITS
+    }
+
+    /// Add `tx` to the block if it fits, returning whether it was added.
+    pub fn try_add(&mut self, tx: &Transaction) -> bool {
+        if self.txs >= self.max_txs {
+            return false;
+        }
+        let size = tx.encode_size();
+        if self.bytes + size > self.max_bytes {
+            return false;
+        }
+        let units = max_compute_units(&tx.instruction);
+        if self.compute_units + units > MAX_BLOCK_COMPUTE_UNITS {
+            return false;
+        }
+        if is_large_move(&tx.instruction) {
+            if self.large_bytes > 0 && self.large_bytes + size > self.max_large_bytes {
+                return false;
+            }
+            self.large_bytes += size;
+        }
+        self.txs += 1;
+        self.bytes += size;
+        self.compute_units += units;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commonware_cryptography::{ed25519::PrivateKey, PrivateKeyExt};
+
+    fn deposit(seed: u64) -> Transaction {
+        Transaction::sign(
+            &PrivateKey::from_seed(seed),
+            0,
+            Instruction::CasinoDeposit { amount: 1 },
+        )
+    }
+
+    fn large_move(seed: u64) -> Transaction {
+        Transaction::sign(
+            &PrivateKey::from_seed(seed),
+            0,
+            Instruction::CasinoGameMove {
+                session_id: 0,
+                payload: vec![0; 200],
+            },
+        )
+    }
+
+    #[test]
+    fn test_max_txs() {
+        let mut budget = Budget::new(2, usize::MAX);
+        assert!(budget.try_add(&deposit(0)));
+        assert!(!budget.is_full());
+        assert!(budget.try_add(&deposit(1)));
+        assert!(budget.is_full());
+        assert!(!budget.try_add(&deposit(2)));
+    }
+
+    #[test]
+    fn test_max_bytes() {
+        let size = deposit(0).encode_size();
+        let mut budget = Budget::new(100, size * 2 + 1);
+        assert!(budget.try_add(&deposit(0)));
+        assert!(budget.try_add(&deposit(1)));
+        assert!(!budget.try_add(&deposit(2)));
+    }
+
+    #[test]
+    fn test_max_compute_units() {
+        // Blocks of moves are limited by their compute budget
+        let mut budget = Budget::new(usize::MAX, usize::MAX);
+        let units = max_compute_units(&large_move(0).instruction);
+        let fit = MAX_BLOCK_COMPUTE_UNITS / units;
+        for seed in 0..fit {
+            assert!(budget.try_add(&large_move(seed)));
+        }
+        assert!(!budget.try_add(&large_move(fit)));
+    }
+
+    #[test]
+    fn test_large_moves_limited() {
+        let size = large_move(0).encode_size();
+        let mut budget = Budget::new(100, size * 8);
+
+        // Large moves may use up to a quarter of the block
+        assert!(budget.try_add(&large_move(0)));
+        assert!(budget.try_add(&large_move(1)));
+        assert!(!budget.try_add(&large_move(2)));
+
+        // Other transactions can still use the rest
+        assert!(budget.try_add(&deposit(0)));
+    }
+
+    #[test]
+    fn test_large_moves_not_starved() {
+        let size = large_move(0).encode_size();
+        let mut budget = Budget::new(100, size * 2);
+
+        // The first large move is always accepted (even if larger than its share)
+        assert!(budget.try_add(&large_move(0)));
+        assert!(!budget.try_add(&large_move(1)));
+        assert!(budget.try_add(&deposit(0)));
+    }
+}
@@ -1,12 +1,17 @@
+use super::admission::{Admissions, Filter, Record};
 use commonware_consensus::threshold_simplex::types::{Context, View};
 use commonware_consensus::{Automaton, Relay, Reporter};
 use commonware_cryptography::sha256::Digest;
 use commonware_runtime::{telemetry::metrics::histogram, Clock};
+use commonware_storage::{mmr::verification::Proof, store::operation::Variable};
 use futures::{
     channel::{mpsc, oneshot},
     SinkExt,
 };
-use nullspace_types::{Block, Seed};
+use nullspace_types::{
+    execution::{Transaction, Value},
+    Block, Seed,
+};
 
 /// Messages sent to the application.
 pub enum Message<E: Clock> {
@@ -43,17 +48,34 @@ pub enum Message<E: Clock> {
         timer: histogram::Timer<E>,
         response: oneshot::Sender<()>,
     },
+    Mempool {
+        response: oneshot::Sender<Vec<Transaction>>,
+    },
+    Prove {
+        key: Digest,
+        size: u64,
+        response: oneshot::Sender<Option<(Proof<Digest>, u64, Variable<Digest, Value>)>>,
+    },
+    StateAt {
+        key: Digest,
+        size: u64,
+        response: oneshot::Sender<Option<(u64, Variable<Digest, Value>)>>,
+    },
 }
 
+/// Maximum number of records returned by a single admission log query.
+const ADMISSION_LOG_QUERY_LIMIT: usize = 1_000;
+
 /// Mailbox for the application.
 #[derive(Clone)]
 pub struct Mailbox<E: Clock> {
     sender: mpsc::Sender<Message<E>>,
+    admissions: Admissions,
 }
 
 impl<E: Clock> Mailbox<E> {
-    pub(super) fn new(sender: mpsc::Sender<Message<E>>) -> Self {
-        Self { sender }
+    pub(super) fn new(sender: mpsc::Sender<Message<E>>, admissions: Admissions) -> Self {
+        Self { sender, admissions }
     }
 
     pub(super) async fn ancestry(
@@ -93,6 +115,69 @@ impl<E: Clock> Mailbox<E> {
     }
 }
 
+impl<E: Clock> Mailbox<E> {
+    /// Query the mempool admission log for the most recent decisions matching `filter`
+    /// (oldest first).
+    ///
+    /// Served from the log's in-memory index, so this doesn't wait on the application.
+    pub fn admissions(&self, filter: Filter) -> Vec<Record> {
+        self.admissions.query(&filter, ADMISSION_LOG_QUERY_LIMIT)
+    }
+
+    /// Get all transactions in the mempool.
+    pub async fn mempool(&mut self) -> Vec<Transaction> {
+        let (response, receiver) = oneshot::channel();
+        self.sender
+            .send(Message::Mempool { response })
+            .await
+            .expect("Failed to send mempool");
+        receiver.await.unwrap_or_default()
+    }
+
+    /// Prove the current value of `key` against the state as of `size` operations, returning
+    /// the proof, the location of the value, and the operation that set it.
+    ///
+    /// Returns `None` if the key doesn't exist or was updated at or after `size`.
+    pub async fn prove(
+        &mut self,
+        key: Digest,
+        size: u64,
+    ) -> Option<(Proof<Digest>, u64, Variable<Digest, Value>)> {
+        let (response, receiver) = oneshot::channel();
+        self.sender
+            .send(Message::Prove {
+                key,
+                size,
+                response,
+            })
+            .await
+            .expect("Failed to send prove");
+        receiver.await.ok().flatten()
+    }
+
+    /// Get the value of `key` as of `size` operations (the last update before `size`), returning
+    /// the location of the value and the operation that set it.
+    ///
+    /// Returns `None` if the key didn't exist at `size`. Values that have since been overwritten
+    /// are only retained by archive nodes (and only found within a bounded number of operations).
+    pub async fn state_at(
+        &mut self,
+        key: Digest,
+        size: u64,
+    ) -> Option<(u64, Variable<Digest, Value>)> {
+        let (response, receiver) = oneshot::channel();
+        self.sender
+            .send(Message::StateAt {
+                key,
+                size,
+                response,
+            })
+            .await
+            .expect("Failed to send state at");
+        receiver.await.ok().flatten()
+    }
+}
+
 impl<E: Clock> Automaton for Mailbox<E> {
     type Digest = Digest;
     type Context = Context<Self::Digest>;
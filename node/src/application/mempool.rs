@@ -1,8 +1,12 @@
+use super::admission::Reason;
 use commonware_cryptography::{ed25519::PublicKey, sha256::Digest, Digestible};
-use commonware_runtime::Metrics;
+use commonware_runtime::{Clock, Metrics};
 use nullspace_types::execution::Transaction;
 use prometheus_client::metrics::gauge::Gauge;
-use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    time::{Duration, SystemTime},
+};
 
 /// The maximum number of transactions a single account can have in the mempool.
 // Increased for higher transaction throughput per account
@@ -12,11 +16,31 @@ const DEFAULT_MAX_BACKLOG: usize = 64;
 // Scaled for 1000+ concurrent players
 const DEFAULT_MAX_TRANSACTIONS: usize = 100_000;
 
+/// The outcome of adding a transaction to the [Mempool].
+#[derive(Debug, PartialEq, Eq)]
+pub enum Admission {
+    /// The transaction was added (possibly evicting a transaction with a higher nonce
+    /// from the same account).
+    Admitted { evicted: Option<Transaction> },
+    /// The transaction was not added.
+    Rejected(Reason),
+}
+
+/// A transaction in the [Mempool] (with the time it was admitted).
+struct Entry {
+    tx: Transaction,
+    admitted: SystemTime,
+}
+
 /// A mempool for transactions.
-pub struct Mempool {
+pub struct Mempool<E: Clock> {
+    context: E,
     max_backlog: usize,
     max_transactions: usize,
-    transactions: HashMap<Digest, Transaction>,
+    transactions: HashMap<Digest, Entry>,
+    /// Transactions ordered by admission time (so expired transactions can be found without
+    /// scanning the whole mempool).
+    admitted: BTreeSet<(SystemTime, Digest)>,
     tracked: HashMap<PublicKey, BTreeMap<u64, Digest>>,
     /// We store the public keys of the transactions to be processed next (rather than transactions
     /// received by digest) because we may receive transactions out-of-order (and/or some may have
@@ -29,17 +53,13 @@ pub struct Mempool {
     accounts: Gauge,
 }
 
-impl Mempool {
+impl<E: Clock + Metrics> Mempool<E> {
     /// Create a new mempool.
-    pub fn new(context: impl Metrics) -> Self {
+    pub fn new(context: E) -> Self {
         Self::new_with_limits(context, DEFAULT_MAX_BACKLOG, DEFAULT_MAX_TRANSACTIONS)
     }
 
-    pub fn new_with_limits(
-        context: impl Metrics,
-        max_backlog: usize,
-        max_transactions: usize,
-    ) -> Self {
+    pub fn new_with_limits(context: E, max_backlog: usize, max_transactions: usize) -> Self {
         // Initialize metrics
         let unique = Gauge::default();
         let accounts = Gauge::default();
@@ -56,9 +76,11 @@ impl Mempool {
 
         // Initialize mempool
         Self {
+            context,
             max_backlog,
             max_transactions,
             transactions: HashMap::new(),
+            admitted: BTreeSet::new(),
             tracked: HashMap::new(),
             queue: VecDeque::new(),
             queued: HashSet::new(),
@@ -68,18 +90,59 @@ impl Mempool {
         }
     }
 
-    /// Add a transaction to the mempool.
-    pub fn add(&mut self, tx: Transaction) {
+    /// Update the mempool limits.
+    ///
+    /// Lowering a limit does not evict transactions already tracked (they drain as blocks
+    /// are built), it only applies to new transactions.
+    pub fn set_limits(&mut self, max_backlog: usize, max_transactions: usize) {
+        self.max_backlog = max_backlog;
+        self.max_transactions = max_transactions;
+    }
+
+    /// Remove a transaction (that is no longer tracked by any account) from the mempool.
+    fn remove(&mut self, digest: &Digest) -> Option<Transaction> {
+        let entry = self.transactions.remove(digest)?;
+        self.admitted.remove(&(entry.admitted, *digest));
+        Some(entry.tx)
+    }
+
+    /// Stop tracking the transaction at `nonce` for `public`, returning its digest.
+    fn untrack(&mut self, public: &PublicKey, nonce: u64) -> Option<Digest> {
+        let tracked = self.tracked.get_mut(public)?;
+        let digest = tracked.remove(&nonce)?;
+        if tracked.is_empty() {
+            self.tracked.remove(public);
+            self.queued.remove(public);
+        }
+        Some(digest)
+    }
+
+    /// Add a transaction to the mempool (timestamped with the current time).
+    pub fn add(&mut self, tx: Transaction) -> Admission {
+        let admitted = self.context.current();
+        self.insert(tx, admitted)
+    }
+
+    /// Re-add a transaction returned by [Mempool::next] that was deferred to a later block.
+    ///
+    /// The transaction keeps the time it was originally admitted (so a transaction that is
+    /// deferred repeatedly still expires).
+    pub fn defer(&mut self, tx: Transaction, admitted: SystemTime) -> Admission {
+        self.insert(tx, admitted)
+    }
+
+    /// Insert a transaction admitted at `admitted`.
+    fn insert(&mut self, tx: Transaction, admitted: SystemTime) -> Admission {
         // If there are too many transactions, ignore
         if self.transactions.len() >= self.max_transactions {
-            return;
+            return Admission::Rejected(Reason::MempoolFull);
         }
 
         // Determine if duplicate
         let digest = tx.digest();
         if self.transactions.contains_key(&digest) {
             // If we already have a transaction with this digest, we don't need to track it
-            return;
+            return Admission::Rejected(Reason::Duplicate);
         }
 
         // Track the transaction
@@ -88,7 +151,7 @@ impl Mempool {
 
         // If there already exists a transaction at some nonce, return
         if entry.contains_key(&tx.nonce) {
-            return;
+            return Admission::Rejected(Reason::NonceTaken);
         }
 
         // Insert the transaction into the mempool
@@ -97,13 +160,20 @@ impl Mempool {
             replaced.is_none(),
             "duplicate nonce per account should have been filtered"
         );
-        self.transactions.insert(digest, tx);
+        self.transactions.insert(digest, Entry { tx, admitted });
+        self.admitted.insert((admitted, digest));
 
         // If there are too many transactions, remove the furthest in the future
         let entries = entry.len();
+        let mut evicted = None;
         if entries > self.max_backlog {
             let (_, future) = entry.pop_last().unwrap();
-            self.transactions.remove(&future);
+            let removed = self.remove(&future);
+            if future == digest {
+                // The new transaction was the furthest in the future
+                return Admission::Rejected(Reason::BacklogExceeded);
+            }
+            evicted = removed;
         }
 
         // Add to queue if this is the first entry (otherwise the public key will already be
@@ -115,14 +185,17 @@ impl Mempool {
         // Update metrics
         self.unique.set(self.transactions.len() as i64);
         self.accounts.set(self.tracked.len() as i64);
+        Admission::Admitted { evicted }
     }
 
-    /// Retain transactions for a given account with a minimum nonce.
-    pub fn retain(&mut self, public: &PublicKey, min: u64) {
+    /// Retain transactions for a given account with a minimum nonce, returning
+    /// any removed transactions.
+    pub fn retain(&mut self, public: &PublicKey, min: u64) -> Vec<Transaction> {
         // Remove any items no longer present
         let Some(tracked) = self.tracked.get_mut(public) else {
-            return;
+            return Vec::new();
         };
+        let mut removed = Vec::new();
         let remove = loop {
             let Some((nonce, digest)) = tracked.first_key_value() else {
                 break true;
@@ -130,8 +203,12 @@ impl Mempool {
             if nonce >= &min {
                 break false;
             }
-            self.transactions.remove(digest);
+            let digest = *digest;
             tracked.pop_first();
+            if let Some(entry) = self.transactions.remove(&digest) {
+                self.admitted.remove(&(entry.admitted, digest));
+                removed.push(entry.tx);
+            }
         };
 
         // If we removed a transaction, remove the address from the tracked map
@@ -143,10 +220,61 @@ impl Mempool {
         // Update metrics
         self.unique.set(self.transactions.len() as i64);
         self.accounts.set(self.tracked.len() as i64);
+        removed
+    }
+
+    /// Remove the transaction tracked at `nonce` for `public` (if any).
+    ///
+    /// Used when a finalized block includes a transaction at this nonce (so any transaction
+    /// we hold for it is either the same transaction or will be invalidated once executed).
+    pub fn remove_nonce(&mut self, public: &PublicKey, nonce: u64) -> Option<Transaction> {
+        let digest = self.untrack(public, nonce)?;
+        let removed = self.remove(&digest);
+
+        // Update metrics
+        self.unique.set(self.transactions.len() as i64);
+        self.accounts.set(self.tracked.len() as i64);
+        removed
+    }
+
+    /// Remove all transactions admitted more than `ttl` ago, returning them.
+    pub fn expire(&mut self, ttl: Duration) -> Vec<Transaction> {
+        let Some(cutoff) = self.context.current().checked_sub(ttl) else {
+            return Vec::new();
+        };
+        let mut expired = Vec::new();
+        while let Some((admitted, digest)) = self.admitted.first().copied() {
+            if admitted > cutoff {
+                break;
+            }
+            self.admitted.pop_first();
+            let Some(entry) = self.transactions.remove(&digest) else {
+                continue;
+            };
+            self.untrack(&entry.tx.public, entry.tx.nonce);
+            expired.push(entry.tx);
+        }
+
+        // Update metrics
+        self.unique.set(self.transactions.len() as i64);
+        self.accounts.set(self.tracked.len() as i64);
+        expired
+    }
+
+    /// Get all transactions in the mempool (ordered by account and then nonce).
+    pub fn transactions(&self) -> Vec<Transaction> {
+        let mut accounts: Vec<_> = self.tracked.iter().collect();
+        accounts.sort_by(|a, b| a.0.cmp(b.0));
+        accounts
+            .into_iter()
+            .flat_map(|(_, tracked)| tracked.values())
+            .filter_map(|digest| self.transactions.get(digest))
+            .map(|entry| entry.tx.clone())
+            .collect()
     }
 
-    /// Get the next transaction to process from the mempool.
-    pub fn next(&mut self) -> Option<Transaction> {
+    /// Get the next transaction to process from the mempool (with the time it was admitted).
+    pub fn next(&mut self) -> Option<(Transaction, SystemTime)> {
         const COMPACT_AFTER_STALE_SKIPS: usize = 1024;
 
         let mut stale_skips = 0;
@@ -197,11 +325,12 @@ impl Mempool {
             }
 
             // Remove the transaction from the mempool
-            let tx = self
+            let entry = self
                 .transactions
                 .remove(&digest)
                 .expect("tracked digest must exist in transactions map");
-            break Some(tx);
+            self.admitted.remove(&(entry.admitted, digest));
+            break Some((entry.tx, entry.admitted));
         };
 
         // Update metrics
@@ -249,8 +378,11 @@ mod tests {
             let private = PrivateKey::from_seed(1);
             let tx = Transaction::sign(&private, 0, Instruction::CasinoDeposit { amount: 100 });
 
-            mempool.add(tx.clone());
-            mempool.add(tx);
+            assert_eq!(
+                mempool.add(tx.clone()),
+                Admission::Admitted { evicted: None }
+            );
+            assert_eq!(mempool.add(tx), Admission::Rejected(Reason::Duplicate));
 
             assert_eq!(mempool.transactions.len(), 1);
             assert_eq!(mempool.tracked.len(), 1);
@@ -273,7 +405,7 @@ mod tests {
             mempool.add(tx1);
             assert!(mempool.transactions.contains_key(&digest1));
 
-            mempool.add(tx2);
+            assert_eq!(mempool.add(tx2), Admission::Rejected(Reason::NonceTaken));
             assert!(mempool.transactions.contains_key(&digest1));
             assert!(!mempool.transactions.contains_key(&digest2));
             assert_eq!(mempool.transactions.len(), 1);
@@ -327,6 +459,36 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_add_evicts_furthest_nonce() {
+        let runner = deterministic::Runner::default();
+        runner.start(|ctx| async move {
+            let mut mempool = Mempool::new_with_limits(ctx, 2, 100);
+
+            let private = PrivateKey::from_seed(1);
+            let tx = |nonce| {
+                Transaction::sign(&private, nonce, Instruction::CasinoDeposit { amount: 100 })
+            };
+            mempool.add(tx(1));
+            mempool.add(tx(2));
+
+            // A higher nonce is rejected
+            assert_eq!(
+                mempool.add(tx(3)),
+                Admission::Rejected(Reason::BacklogExceeded)
+            );
+
+            // A lower nonce evicts the highest tracked nonce
+            assert_eq!(
+                mempool.add(tx(0)),
+                Admission::Admitted {
+                    evicted: Some(tx(2))
+                }
+            );
+            assert_eq!(mempool.transactions.len(), 2);
+        });
+    }
+
     #[test]
     fn test_add_multiple_accounts() {
         let runner = deterministic::Runner::default();
@@ -345,6 +507,32 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_transactions_ordered() {
+        let runner = deterministic::Runner::default();
+        runner.start(|ctx| async move {
+            let mut mempool = Mempool::new(ctx);
+
+            let mut expected = Vec::new();
+            for seed in 0..3 {
+                let private = PrivateKey::from_seed(seed);
+                for nonce in (0..3).rev() {
+                    let tx = Transaction::sign(
+                        &private,
+                        nonce,
+                        Instruction::CasinoDeposit { amount: 100 },
+                    );
+                    expected.push(tx.clone());
+                    mempool.add(tx);
+                }
+            }
+            expected.sort_by(|a, b| (&a.public, a.nonce).cmp(&(&b.public, b.nonce)));
+
+            let transactions = mempool.transactions();
+            assert_eq!(transactions, expected);
+        });
+    }
+
     #[test]
     fn test_retain_removes_old_transactions() {
         let runner = deterministic::Runner::default();
@@ -360,7 +548,9 @@ mod tests {
                 mempool.add(tx);
             }
 
-            mempool.retain(&public, 3);
+            let removed = mempool.retain(&public, 3);
+            let nonces: Vec<_> = removed.iter().map(|tx| tx.nonce).collect();
+            assert_eq!(nonces, vec![0, 1, 2]);
 
             assert_eq!(mempool.transactions.len(), 2);
             let tracked = mempool.tracked.get(&public).unwrap();
@@ -410,6 +600,96 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_remove_nonce() {
+        let runner = deterministic::Runner::default();
+        runner.start(|ctx| async move {
+            let mut mempool = Mempool::new(ctx);
+
+            let private = PrivateKey::from_seed(1);
+            let public = private.public_key();
+            let tx = |nonce| {
+                Transaction::sign(&private, nonce, Instruction::CasinoDeposit { amount: 100 })
+            };
+            mempool.add(tx(0));
+            mempool.add(tx(1));
+
+            assert_eq!(mempool.remove_nonce(&public, 0), Some(tx(0)));
+            assert_eq!(mempool.remove_nonce(&public, 0), None);
+            assert_eq!(mempool.transactions(), vec![tx(1)]);
+
+            // Removing the last transaction stops tracking the account
+            assert_eq!(mempool.remove_nonce(&public, 1), Some(tx(1)));
+            assert!(mempool.tracked.is_empty());
+            assert!(mempool.admitted.is_empty());
+            assert!(mempool.next().is_none());
+        });
+    }
+
+    #[test]
+    fn test_expire() {
+        let runner = deterministic::Runner::default();
+        runner.start(|ctx| async move {
+            let mut mempool = Mempool::new(ctx.clone());
+            let ttl = Duration::from_secs(60);
+
+            let old = PrivateKey::from_seed(1);
+            let old_tx = Transaction::sign(&old, 0, Instruction::CasinoDeposit { amount: 100 });
+            mempool.add(old_tx.clone());
+            ctx.sleep(Duration::from_secs(30)).await;
+
+            let new = PrivateKey::from_seed(2);
+            let new_tx = Transaction::sign(&new, 0, Instruction::CasinoDeposit { amount: 100 });
+            mempool.add(new_tx.clone());
+
+            // Nothing has outlived the TTL yet
+            assert!(mempool.expire(ttl).is_empty());
+
+            // Only the older transaction expires
+            ctx.sleep(Duration::from_secs(31)).await;
+            assert_eq!(mempool.expire(ttl), vec![old_tx]);
+            assert!(!mempool.tracked.contains_key(&old.public_key()));
+            assert_eq!(mempool.next().map(|(tx, _)| tx), Some(new_tx));
+            assert!(mempool.next().is_none());
+
+            // Transactions removed by other means don't expire later
+            ctx.sleep(ttl).await;
+            assert!(mempool.expire(ttl).is_empty());
+        });
+    }
+
+    #[test]
+    fn test_deferred_transactions_expire() {
+        let runner = deterministic::Runner::default();
+        runner.start(|ctx| async move {
+            let mut mempool = Mempool::new(ctx.clone());
+            let ttl = Duration::from_secs(60);
+
+            let private = PrivateKey::from_seed(1);
+            let tx = Transaction::sign(&private, 0, Instruction::CasinoDeposit { amount: 100 });
+            mempool.add(tx.clone());
+            let start = ctx.current();
+
+            // Defer the transaction to a later block (repeatedly)
+            for _ in 0..3 {
+                ctx.sleep(Duration::from_secs(15)).await;
+                assert!(mempool.expire(ttl).is_empty());
+                let (next, admitted) = mempool.next().unwrap();
+                assert_eq!(next, tx);
+                assert_eq!(admitted, start);
+                assert!(matches!(
+                    mempool.defer(next, admitted),
+                    Admission::Admitted { evicted: None }
+                ));
+            }
+
+            // The transaction expires relative to when it was first admitted
+            ctx.sleep(Duration::from_secs(16)).await;
+            assert_eq!(mempool.expire(ttl), vec![tx]);
+            assert!(mempool.next().is_none());
+        });
+    }
+
     #[test]
     fn test_next_single_transaction() {
         let runner = deterministic::Runner::default();
@@ -424,7 +704,7 @@ mod tests {
 
             let next = mempool.next();
             assert!(next.is_some());
-            assert_eq!(next.unwrap().nonce, expected_nonce);
+            assert_eq!(next.unwrap().0.nonce, expected_nonce);
 
             assert_eq!(mempool.transactions.len(), 0);
             assert_eq!(mempool.tracked.len(), 0);
@@ -449,7 +729,7 @@ mod tests {
             for expected_nonce in 0..3 {
                 let next = mempool.next();
                 assert!(next.is_some());
-                assert_eq!(next.unwrap().nonce, expected_nonce);
+                assert_eq!(next.unwrap().0.nonce, expected_nonce);
             }
 
             assert_eq!(mempool.transactions.len(), 0);
@@ -481,7 +761,7 @@ mod tests {
 
             let mut account_counts = std::collections::HashMap::new();
             for _ in 0..6 {
-                let next = mempool.next().unwrap();
+                let (next, _) = mempool.next().unwrap();
                 *account_counts.entry(next.public.clone()).or_insert(0) += 1;
             }
 
@@ -523,7 +803,7 @@ mod tests {
 
             let next = mempool.next();
             assert!(next.is_some());
-            assert_eq!(next.unwrap().public, private2.public_key());
+            assert_eq!(next.unwrap().0.public, private2.public_key());
         });
     }
 
@@ -543,6 +823,32 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_set_limits() {
+        let runner = deterministic::Runner::default();
+        runner.start(|ctx| async move {
+            let mut mempool = Mempool::new(ctx);
+            mempool.set_limits(2, 3);
+
+            // Per-account backlog is lowered
+            let private = PrivateKey::from_seed(1);
+            for nonce in 0..3 {
+                let tx =
+                    Transaction::sign(&private, nonce, Instruction::CasinoDeposit { amount: 100 });
+                mempool.add(tx);
+            }
+            assert_eq!(mempool.transactions.len(), 2);
+
+            // Overall limit is lowered
+            for seed in 2..5 {
+                let private = PrivateKey::from_seed(seed);
+                let tx = Transaction::sign(&private, 0, Instruction::CasinoDeposit { amount: 100 });
+                mempool.add(tx);
+            }
+            assert_eq!(mempool.transactions.len(), 3);
+        });
+    }
+
     #[test]
     fn test_metrics_updates() {
         let runner = deterministic::Runner::default();
@@ -1,6 +1,6 @@
-use std::num::NonZero;
+use std::{num::NonZero, time::Duration};
 
-use crate::indexer::Indexer;
+use crate::{export, indexer::Indexer, reload::Tunables, rotation::Rotation};
 use commonware_cryptography::{
     bls12381::primitives::{group, poly::Poly},
     ed25519::PublicKey,
@@ -9,6 +9,9 @@ use nullspace_types::Evaluation;
 
 mod actor;
 pub use actor::Actor;
+mod admission;
+pub use admission::{Decision, Filter, Reason, Record};
+mod budget;
 mod ingress;
 use commonware_runtime::buffer::PoolRef;
 pub use ingress::Mailbox;
@@ -19,6 +22,9 @@ pub struct Config<I: Indexer> {
     /// Participants active in consensus.
     pub participants: Vec<PublicKey>,
 
+    /// Key rotations to apply to the participants.
+    pub rotations: Vec<Rotation>,
+
     /// The unevaluated group polynomial associated with the current dealing.
     pub polynomial: Poly<Evaluation>,
 
@@ -56,9 +62,23 @@ pub struct Config<I: Indexer> {
     /// The number of threads to use for execution.
     pub execution_concurrency: usize,
 
-    /// The maximum number of transactions a single account can have in the mempool.
-    pub mempool_max_backlog: usize,
+    /// Runtime-adjustable limits (including the maximum number of transactions in the
+    /// mempool, overall and per account).
+    pub tunables: Tunables,
+
+    /// The maximum number of transactions to include in a proposed block.
+    pub max_block_txs: usize,
+
+    /// The maximum encoded size (in bytes) of the transactions in a proposed block.
+    pub max_block_bytes: usize,
+
+    /// How long a transaction may wait in the mempool before it is evicted.
+    pub mempool_tx_ttl: Duration,
+
+    /// Whether to retain every historical state version (disables pruning of
+    /// state and events).
+    pub archive: bool,
 
-    /// The maximum number of transactions in the mempool.
-    pub mempool_max_transactions: usize,
+    /// Where to send the events of each executed block (if anywhere).
+    pub exporter: Option<export::Mailbox>,
 }
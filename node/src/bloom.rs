@@ -0,0 +1,151 @@
+use commonware_cryptography::sha256::Digest;
+use std::time::{Duration, SystemTime};
+
+/// Number of bits allocated per expected item (~1% false positive rate with [HASHES]).
+const BITS_PER_ITEM: usize = 10;
+
+/// Number of bit positions set per item.
+const HASHES: u64 = 7;
+
+/// A rolling bloom filter of recently seen digests.
+///
+/// The filter is split into two generations. Items are inserted into the current
+/// generation and looked up in both. Once the current generation reaches its capacity
+/// (or has been active for longer than the configured window), it becomes the previous
+/// generation and a fresh one is started. This bounds memory while guaranteeing an item
+/// is remembered for at least one full generation.
+///
+/// Because inputs are already uniformly distributed digests, bit positions are derived
+/// directly from the digest bytes (via double hashing) rather than re-hashing.
+pub struct RollingBloom {
+    capacity: usize,
+    window: Duration,
+    bits: u64,
+
+    current: Vec<u64>,
+    previous: Vec<u64>,
+    inserted: usize,
+    rotated_at: SystemTime,
+}
+
+impl RollingBloom {
+    /// Create a new filter that remembers at least `capacity` items (or `window` of time).
+    pub fn new(capacity: usize, window: Duration, now: SystemTime) -> Self {
+        assert!(capacity > 0, "capacity must be non-zero");
+        let bits = (capacity * BITS_PER_ITEM).next_multiple_of(64);
+        Self {
+            capacity,
+            window,
+            bits: bits as u64,
+            current: vec![0; bits / 64],
+            previous: vec![0; bits / 64],
+            inserted: 0,
+            rotated_at: now,
+        }
+    }
+
+    fn positions(&self, digest: &Digest) -> impl Iterator<Item = u64> {
+        let bytes = digest.as_ref();
+        let h1 = u64::from_be_bytes(bytes[0..8].try_into().unwrap());
+        let h2 = u64::from_be_bytes(bytes[8..16].try_into().unwrap()) | 1;
+        let bits = self.bits;
+        (0..HASHES).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % bits)
+    }
+
+    fn test(generation: &[u64], mut positions: impl Iterator<Item = u64>) -> bool {
+        positions.all(|pos| generation[(pos / 64) as usize] & (1 << (pos % 64)) != 0)
+    }
+
+    fn maybe_rotate(&mut self, now: SystemTime) {
+        let expired = now
+            .duration_since(self.rotated_at)
+            .map(|elapsed| elapsed >= self.window)
+            .unwrap_or(false);
+        if self.inserted < self.capacity && !expired {
+            return;
+        }
+        self.previous = std::mem::replace(&mut self.current, vec![0; self.previous.len()]);
+        self.inserted = 0;
+        self.rotated_at = now;
+    }
+
+    /// Returns true if the digest has (probably) been seen recently.
+    pub fn contains(&mut self, digest: &Digest, now: SystemTime) -> bool {
+        self.maybe_rotate(now);
+        Self::test(&self.current, self.positions(digest))
+            || Self::test(&self.previous, self.positions(digest))
+    }
+
+    /// Record a digest as seen, returning true if it was not already present.
+    pub fn insert(&mut self, digest: &Digest, now: SystemTime) -> bool {
+        if self.contains(digest, now) {
+            return false;
+        }
+        for pos in self.positions(digest) {
+            self.current[(pos / 64) as usize] |= 1 << (pos % 64);
+        }
+        self.inserted += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commonware_cryptography::{Hasher, Sha256};
+
+    fn digest(i: u64) -> Digest {
+        Sha256::hash(&i.to_be_bytes())
+    }
+
+    #[test]
+    fn test_insert_and_contains() {
+        let now = SystemTime::UNIX_EPOCH;
+        let mut filter = RollingBloom::new(128, Duration::from_secs(60), now);
+
+        assert!(!filter.contains(&digest(1), now));
+        assert!(filter.insert(&digest(1), now));
+        assert!(filter.contains(&digest(1), now));
+        assert!(!filter.insert(&digest(1), now));
+        assert!(!filter.contains(&digest(2), now));
+    }
+
+    #[test]
+    fn test_rotates_on_capacity() {
+        let now = SystemTime::UNIX_EPOCH;
+        let mut filter = RollingBloom::new(16, Duration::from_secs(60), now);
+
+        // Fill two generations (the second is remembered as the previous generation)
+        for i in 0..32 {
+            filter.insert(&digest(i), now);
+        }
+        assert!(filter.contains(&digest(20), now));
+
+        // After two rotations, the first generation is forgotten (modulo false positives)
+        for i in 32..48 {
+            filter.insert(&digest(i), now);
+        }
+        filter.insert(&digest(48), now);
+        let remembered = (0..16)
+            .filter(|i| filter.contains(&digest(*i), now))
+            .count();
+        assert!(remembered < 4);
+    }
+
+    #[test]
+    fn test_rotates_on_window() {
+        let start = SystemTime::UNIX_EPOCH;
+        let window = Duration::from_secs(10);
+        let mut filter = RollingBloom::new(1024, window, start);
+        assert!(filter.insert(&digest(1), start));
+
+        // Still remembered after one window (in the previous generation)
+        let later = start + window;
+        assert!(filter.contains(&digest(1), later));
+
+        // Forgotten after two windows
+        let much_later = later + window;
+        assert!(!filter.contains(&digest(1), much_later));
+        assert!(filter.insert(&digest(1), much_later));
+    }
+}
@@ -0,0 +1,272 @@
+//! Publishing of seeds and summaries to a message bus (instead of the HTTP indexer).
+//!
+//! Each message is published to `<prefix>.<kind>.<partition>`, where the partition is derived
+//! from the view (seeds) or height (summaries). Consumers can subscribe to a subset of
+//! partitions (or to `<prefix>.<kind>.*`) and scale independently of the simulator, while
+//! messages for the same key always land in the same partition.
+//!
+//! Publishes only succeed once the broker has persisted the message and the seeder and
+//! aggregator retry failed uploads, so delivery is at-least-once: consumers must tolerate
+//! duplicates (e.g. by keying on the height).
+//!
+//! Pending transactions are read from `<prefix>.transactions` (as encoded [Pending]) and
+//! those refused by the mempool are reported to `<prefix>.rejections` (as encoded [Rejection]).
+
+use crate::indexer::Indexer;
+use bytes::Bytes;
+use commonware_codec::{DecodeExt, Encode};
+use commonware_consensus::Viewable;
+use futures::{future, Stream, StreamExt};
+use nullspace_types::{
+    api::{Pending, Rejection, Summary},
+    Seed,
+};
+use std::{future::Future, sync::Arc};
+use thiserror::Error;
+use tracing::debug;
+
+/// A message bus that can persist published messages.
+pub trait Bus: Clone + Send + Sync + 'static {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Publish `payload` to `subject`, resolving once the broker has acknowledged it.
+    fn publish(
+        &self,
+        subject: String,
+        payload: Bytes,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Subscribe to all messages published to `subject`.
+    fn subscribe(
+        &self,
+        subject: String,
+    ) -> impl Future<Output = Result<impl Stream<Item = Bytes> + Send, Self::Error>> + Send;
+}
+
+/// Subjects messages are published to.
+#[derive(Clone, Debug)]
+pub struct Topics {
+    prefix: String,
+    partitions: u64,
+}
+
+impl Topics {
+    /// Create topics under `prefix`, partitioning keyed messages into `partitions`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `partitions` is zero.
+    pub fn new(prefix: impl Into<String>, partitions: u64) -> Self {
+        assert!(partitions > 0, "partitions must be > 0");
+        Self {
+            prefix: prefix.into(),
+            partitions,
+        }
+    }
+
+    fn keyed(&self, kind: &str, key: u64) -> String {
+        format!("{}.{}.{}", self.prefix, kind, key % self.partitions)
+    }
+
+    /// Subject for the seed of `view`.
+    pub fn seed(&self, view: u64) -> String {
+        self.keyed("seeds", view)
+    }
+
+    /// Subject for the summary of `height`.
+    pub fn summary(&self, height: u64) -> String {
+        self.keyed("summaries", height)
+    }
+
+    /// Subject rejected transactions are reported to.
+    pub fn rejections(&self) -> String {
+        format!("{}.rejections", self.prefix)
+    }
+
+    /// Subject pending transactions are read from.
+    pub fn transactions(&self) -> String {
+        format!("{}.transactions", self.prefix)
+    }
+}
+
+/// An [Indexer] that publishes to (and reads pending transactions from) a [Bus].
+#[derive(Clone)]
+pub struct BusIndexer<B: Bus> {
+    bus: B,
+    topics: Arc<Topics>,
+}
+
+impl<B: Bus> BusIndexer<B> {
+    pub fn new(bus: B, topics: Topics) -> Self {
+        Self {
+            bus,
+            topics: Arc::new(topics),
+        }
+    }
+}
+
+impl<B: Bus> Indexer for BusIndexer<B> {
+    type Error = B::Error;
+
+    async fn submit_seed(&self, seed: Seed) -> Result<(), Self::Error> {
+        let subject = self.topics.seed(seed.view());
+        self.bus.publish(subject, seed.encode().freeze()).await
+    }
+
+    async fn listen_mempool(
+        &self,
+    ) -> Result<impl Stream<Item = Result<Pending, Self::Error>> + Send, Self::Error> {
+        let stream = self.bus.subscribe(self.topics.transactions()).await?;
+        Ok(stream.filter_map(|payload| {
+            let pending = match Pending::decode(payload) {
+                Ok(pending) => Some(Ok(pending)),
+                Err(e) => {
+                    debug!(%e, "failed to decode pending transactions from bus");
+                    None
+                }
+            };
+            future::ready(pending)
+        }))
+    }
+
+    async fn submit_summary(&self, summary: Summary) -> Result<(), Self::Error> {
+        let subject = self.topics.summary(summary.progress.height);
+        self.bus.publish(subject, summary.encode().freeze()).await
+    }
+
+    async fn submit_summaries(&self, summaries: Vec<Summary>) -> Result<(), Self::Error> {
+        // Published in order (so a failure leaves a prefix of the batch published, which is
+        // republished on retry)
+        for summary in summaries {
+            self.submit_summary(summary).await?;
+        }
+        Ok(())
+    }
+
+    async fn report_rejections(&self, rejections: Vec<Rejection>) -> Result<(), Self::Error> {
+        for rejection in rejections {
+            let subject = self.topics.rejections();
+            self.bus
+                .publish(subject, rejection.encode().freeze())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum NatsError {
+    #[error("failed to connect: {0}")]
+    Connect(#[from] async_nats::ConnectError),
+    #[error("failed to publish: {0}")]
+    Publish(#[from] async_nats::jetstream::context::PublishError),
+    #[error("failed to subscribe: {0}")]
+    Subscribe(#[from] async_nats::SubscribeError),
+}
+
+/// A [Bus] backed by NATS (with JetStream persisting published messages).
+///
+/// A stream covering the published subjects (e.g. `<prefix>.>`) must already exist.
+#[derive(Clone)]
+pub struct Nats {
+    client: async_nats::Client,
+    jetstream: async_nats::jetstream::Context,
+}
+
+impl Nats {
+    /// Connect to the NATS server at `url` (e.g. `nats://127.0.0.1:4222`).
+    pub async fn connect(url: &str) -> Result<Self, NatsError> {
+        let client = async_nats::connect(url).await?;
+        let jetstream = async_nats::jetstream::new(client.clone());
+        Ok(Self { client, jetstream })
+    }
+}
+
+impl Bus for Nats {
+    type Error = NatsError;
+
+    async fn publish(&self, subject: String, payload: Bytes) -> Result<(), Self::Error> {
+        // Wait for the stream to acknowledge the message
+        self.jetstream.publish(subject, payload).await?.await?;
+        Ok(())
+    }
+
+    async fn subscribe(
+        &self,
+        subject: String,
+    ) -> Result<impl Stream<Item = Bytes> + Send, Self::Error> {
+        let subscriber = self.client.subscribe(subject).await?;
+        Ok(subscriber.map(|message| message.payload))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commonware_runtime::{deterministic, Runner};
+    use futures::channel::mpsc;
+    use std::{collections::HashMap, convert::Infallible, sync::Mutex};
+
+    #[derive(Clone, Default)]
+    struct Memory {
+        published: Arc<Mutex<Vec<(String, Bytes)>>>,
+        subscribers: Arc<Mutex<HashMap<String, Vec<mpsc::UnboundedSender<Bytes>>>>>,
+    }
+
+    impl Bus for Memory {
+        type Error = Infallible;
+
+        async fn publish(&self, subject: String, payload: Bytes) -> Result<(), Self::Error> {
+            if let Some(subscribers) = self.subscribers.lock().unwrap().get_mut(&subject) {
+                subscribers.retain(|sender| sender.unbounded_send(payload.clone()).is_ok());
+            }
+            self.published.lock().unwrap().push((subject, payload));
+            Ok(())
+        }
+
+        async fn subscribe(
+            &self,
+            subject: String,
+        ) -> Result<impl Stream<Item = Bytes> + Send, Self::Error> {
+            let (sender, receiver) = mpsc::unbounded();
+            self.subscribers
+                .lock()
+                .unwrap()
+                .entry(subject)
+                .or_default()
+                .push(sender);
+            Ok(receiver)
+        }
+    }
+
+    #[test]
+    fn test_topics() {
+        let topics = Topics::new("nullspace", 4);
+        assert_eq!(topics.seed(5), "nullspace.seeds.1");
+        assert_eq!(topics.summary(8), "nullspace.summaries.0");
+        assert_eq!(topics.summary(11), "nullspace.summaries.3");
+        assert_eq!(topics.transactions(), "nullspace.transactions");
+    }
+
+    #[test]
+    fn test_listen_mempool() {
+        deterministic::Runner::default().start(|_| async move {
+            let bus = Memory::default();
+            let indexer = BusIndexer::new(bus.clone(), Topics::new("test", 2));
+            let mut stream = Box::pin(indexer.listen_mempool().await.unwrap());
+
+            // Invalid payloads are skipped
+            bus.publish("test.transactions".to_string(), Bytes::from_static(&[0xff]))
+                .await
+                .unwrap();
+            let pending = Pending {
+                transactions: Vec::new(),
+            };
+            bus.publish("test.transactions".to_string(), pending.encode().freeze())
+                .await
+                .unwrap();
+            let received = stream.next().await.unwrap().unwrap();
+            assert!(received.transactions.is_empty());
+        });
+    }
+}
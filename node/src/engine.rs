@@ -1,6 +1,9 @@
 use crate::{
-    aggregator, application,
+    aggregator, application, export,
     indexer::Indexer,
+    reload::Tunables,
+    rotation::Rotation,
+    scorer::{ScoredReceiver, Scorer},
     seeder,
     supervisor::{EpochSupervisor, ViewSupervisor},
 };
@@ -30,6 +33,7 @@ use nullspace_types::{Activity, Block, Evaluation, NAMESPACE};
 use rand::{CryptoRng, Rng};
 use std::{
     num::{NonZero, NonZeroUsize},
+    sync::{Arc, Mutex},
     time::Duration,
 };
 use tracing::{error, warn};
@@ -55,6 +59,10 @@ const REPLAY_BUFFER: NonZero<usize> = NZUsize!(8 * 1024 * 1024); // 8MB
 const WRITE_BUFFER: NonZero<usize> = NZUsize!(1024 * 1024); // 1MB
 const MAX_REPAIR: u64 = 20;
 
+/// Peers sending more than this multiple of the backfill quota in a single second
+/// are penalized.
+const EXCESSIVE_BACKFILL_MULTIPLIER: u32 = 4;
+
 /// Configuration for the [Engine].
 pub struct Config<B: Blocker<PublicKey = PublicKey>, I: Indexer> {
     pub blocker: B,
@@ -67,6 +75,8 @@ pub struct Config<B: Blocker<PublicKey = PublicKey>, I: Indexer> {
     pub polynomial: Poly<Evaluation>,
     pub share: group::Share,
     pub participants: Vec<PublicKey>,
+    /// Key rotations to apply to `participants`.
+    pub rotations: Vec<Rotation>,
     pub mailbox_size: usize,
     pub backfill_quota: Quota,
     pub deque_size: usize,
@@ -87,6 +97,12 @@ pub struct Config<B: Blocker<PublicKey = PublicKey>, I: Indexer> {
     pub max_uploads_outstanding: usize,
     pub mempool_max_backlog: usize,
     pub mempool_max_transactions: usize,
+    pub max_block_txs: usize,
+    pub max_block_bytes: usize,
+    pub mempool_tx_ttl: Duration,
+    pub archive: bool,
+    /// Where to send the events of each executed block (if anywhere).
+    pub exporter: Option<export::Mailbox>,
 }
 
 /// The engine that drives the [application].
@@ -96,6 +112,11 @@ pub struct Engine<
     I: Indexer,
 > {
     context: E,
+    blocker: B,
+    scorer: Arc<Mutex<Scorer>>,
+    max_backfill_per_second: u32,
+    tunables: Tunables,
+    supervisor: ViewSupervisor,
 
     application: application::Actor<E, I>,
     application_mailbox: application::Mailbox<E>,
@@ -144,6 +165,13 @@ impl<
         // Create the buffer pool
         let buffer_pool = PoolRef::new(cfg.buffer_pool_page_size, cfg.buffer_pool_capacity);
 
+        // Create the runtime-adjustable settings
+        let tunables = Tunables::new(
+            cfg.mempool_max_backlog,
+            cfg.mempool_max_transactions,
+            cfg.max_uploads_outstanding,
+        );
+
         // Create the application
         let identity = *public::<MinSig>(&cfg.polynomial);
         let (application, view_supervisor, epoch_supervisor, application_mailbox) =
@@ -151,6 +179,7 @@ impl<
                 context.with_label("application"),
                 application::Config {
                     participants: cfg.participants.clone(),
+                    rotations: cfg.rotations.clone(),
                     polynomial: cfg.polynomial.clone(),
                     share: cfg.share.clone(),
                     mailbox_size: cfg.mailbox_size,
@@ -163,8 +192,12 @@ impl<
                     buffer_pool: buffer_pool.clone(),
                     indexer: cfg.indexer.clone(),
                     execution_concurrency: cfg.execution_concurrency,
-                    mempool_max_backlog: cfg.mempool_max_backlog,
-                    mempool_max_transactions: cfg.mempool_max_transactions,
+                    tunables: tunables.clone(),
+                    max_block_txs: cfg.max_block_txs,
+                    max_block_bytes: cfg.max_block_bytes,
+                    mempool_tx_ttl: cfg.mempool_tx_ttl,
+                    archive: cfg.archive,
+                    exporter: cfg.exporter,
                 },
             );
 
@@ -183,7 +216,7 @@ impl<
                 items_per_blob: MMR_ITEMS_PER_BLOB,
                 write_buffer: WRITE_BUFFER,
                 replay_buffer: REPLAY_BUFFER,
-                max_uploads_outstanding: cfg.max_uploads_outstanding,
+                tunables: tunables.clone(),
             },
         );
 
@@ -204,7 +237,7 @@ impl<
                 write_buffer: WRITE_BUFFER,
                 replay_buffer: REPLAY_BUFFER,
                 indexer: cfg.indexer.clone(),
-                max_uploads_outstanding: cfg.max_uploads_outstanding,
+                tunables: tunables.clone(),
             },
         );
 
@@ -263,7 +296,7 @@ impl<
                 automaton: application_mailbox.clone(),
                 relay: application_mailbox.clone(),
                 reporter,
-                supervisor: view_supervisor,
+                supervisor: view_supervisor.clone(),
                 partition: format!("{}-consensus", cfg.partition_prefix),
                 mailbox_size: cfg.mailbox_size,
                 leader_timeout: cfg.leader_timeout,
@@ -282,6 +315,14 @@ impl<
             },
         );
 
+        // Track misbehaving peers
+        let blocker = cfg.blocker.clone();
+        let max_backfill_per_second = cfg
+            .backfill_quota
+            .burst_size()
+            .get()
+            .saturating_mul(EXCESSIVE_BACKFILL_MULTIPLIER);
+
         // Create the aggregator
         let aggregation = aggregation::Engine::new(
             context.with_label("aggregation"),
@@ -309,6 +350,11 @@ impl<
         // Return the engine
         Self {
             context,
+            blocker,
+            scorer: Arc::new(Mutex::new(Scorer::new())),
+            max_backfill_per_second,
+            tunables,
+            supervisor: view_supervisor,
 
             application,
             application_mailbox,
@@ -325,6 +371,36 @@ impl<
         }
     }
 
+    /// Get a mailbox for querying the application.
+    pub fn application(&self) -> application::Mailbox<E> {
+        self.application_mailbox.clone()
+    }
+
+    /// Get a mailbox for querying the aggregator.
+    pub fn aggregator(&self) -> aggregator::Mailbox {
+        self.aggregator_mailbox.clone()
+    }
+
+    /// Get a mailbox for querying the seeder.
+    pub fn seeder(&self) -> seeder::Mailbox {
+        self.seeder_mailbox.clone()
+    }
+
+    /// Get the scores of peers serving backfill requests.
+    pub fn scorer(&self) -> Arc<Mutex<Scorer>> {
+        self.scorer.clone()
+    }
+
+    /// Get the supervisor of the participant set.
+    pub fn supervisor(&self) -> ViewSupervisor {
+        self.supervisor.clone()
+    }
+
+    /// Get a handle to the settings that can be changed while the engine is running.
+    pub fn tunables(&self) -> Tunables {
+        self.tunables.clone()
+    }
+
     /// Start the [threshold_simplex::Engine].
     #[allow(clippy::too_many_arguments)]
     pub fn start(
@@ -376,6 +452,21 @@ impl<
         })
     }
 
+    /// Wrap a [Receiver] with peer scoring (rate limiting backfill requests, if `backfill`).
+    fn scored<R: Receiver<PublicKey = PublicKey>>(
+        &self,
+        receiver: R,
+        backfill: bool,
+    ) -> ScoredReceiver<E, R, B> {
+        ScoredReceiver::new(
+            self.context.clone(),
+            receiver,
+            self.blocker.clone(),
+            self.scorer.clone(),
+            backfill.then_some(self.max_backfill_per_second),
+        )
+    }
+
     #[allow(clippy::too_many_arguments)]
     async fn run(
         self,
@@ -416,14 +507,35 @@ impl<
         // that restart could block (as the upstream actor may fill the downstream actor's mailbox with items during initialization,
         // potentially blocking if not read).
 
-        // Start the seeder
-        let seeder_handle = self.seeder.start(seeder_network);
+        // Score peers on every channel (dropping messages from banned peers), rate limiting
+        // requests on the backfill channels
+        let pending_network = (pending_network.0, self.scored(pending_network.1, false));
+        let recovered_network = (recovered_network.0, self.scored(recovered_network.1, false));
+        let resolver_network = (resolver_network.0, self.scored(resolver_network.1, false));
+        let broadcast_network = (broadcast_network.0, self.scored(broadcast_network.1, false));
+        let aggregation_network = (
+            aggregation_network.0,
+            self.scored(aggregation_network.1, false),
+        );
+        let seeder_network = (seeder_network.0, self.scored(seeder_network.1, true));
+        let aggregator_network = (
+            aggregator_network.0,
+            self.scored(aggregator_network.1, true),
+        );
+        let backfill_network = (backfill_network.0, self.scored(backfill_network.1, true));
+
+        // Start the seeder (reporting peers that send invalid seeds)
+        let seeder_reporter = seeder_network.1.reporter();
+        let seeder_handle = self.seeder.start(seeder_network, seeder_reporter);
 
         // Start aggregation
         let aggregation_handle = self.aggregation.start(aggregation_network);
 
-        // Start the aggregator
-        let aggregator_handle = self.aggregator.start(aggregator_network);
+        // Start the aggregator (reporting peers that send invalid certificates)
+        let aggregator_reporter = aggregator_network.1.reporter();
+        let aggregator_handle = self
+            .aggregator
+            .start(aggregator_network, aggregator_reporter);
 
         // Start the buffer
         let buffer_handle = self.buffer.start(broadcast_network);
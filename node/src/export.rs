@@ -0,0 +1,272 @@
+//! Export of finalized events (as JSONL) independent of the indexer.
+//!
+//! After each block is executed, its events are handed to the [Exporter], which writes one
+//! JSON object per line to a file or a TCP socket. If the exporter falls too far behind
+//! (e.g. the socket is unreachable for a long time), events are dropped rather than
+//! stalling execution (see the `events_export_dropped` metric).
+
+use commonware_runtime::{Clock, Handle, Metrics, Network, Sink as _, Spawner};
+use commonware_storage::store::operation::Keyless;
+use futures::{channel::mpsc, StreamExt};
+use nullspace_types::execution::{Event, Output};
+use prometheus_client::metrics::counter::Counter;
+use serde_json::{json, Value};
+use std::{
+    fmt, fs::OpenOptions, io::Write as _, net::SocketAddr, path::PathBuf, sync::atomic::AtomicU64,
+    time::Duration,
+};
+use tracing::{debug, warn};
+
+/// Number of blocks of events to buffer before dropping.
+const BUFFER: usize = 1_024;
+
+/// Delay before retrying a failed write (or connection).
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Where to export events.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Target {
+    /// Append to a file.
+    File(PathBuf),
+    /// Stream to a TCP socket (reconnecting on failure).
+    Tcp(SocketAddr),
+}
+
+impl Target {
+    /// Parse a target (`tcp://<addr>` or a file path).
+    pub fn parse(value: &str) -> Option<Self> {
+        if let Some(addr) = value.strip_prefix("tcp://") {
+            return addr.parse().ok().map(Target::Tcp);
+        }
+        if value.is_empty() {
+            return None;
+        }
+        Some(Target::File(PathBuf::from(value)))
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::File(path) => write!(f, "{}", path.display()),
+            Target::Tcp(addr) => write!(f, "tcp://{addr}"),
+        }
+    }
+}
+
+/// Events from a single finalized block.
+struct Batch {
+    height: u64,
+    events: Vec<(u64, Event)>,
+}
+
+impl Batch {
+    fn lines(&self) -> String {
+        let mut lines = String::new();
+        for (location, event) in &self.events {
+            let mut value = encode(event);
+            value["height"] = json!(self.height);
+            value["location"] = json!(location);
+            lines.push_str(&value.to_string());
+            lines.push('\n');
+        }
+        lines
+    }
+}
+
+/// Encode an event as a JSON object (with its variant name under `type`).
+pub fn encode(event: &Event) -> Value {
+    // Events serialize as `{"<variant>": {<fields>}}`, so move the variant name next to its fields
+    let Value::Object(tagged) = serde_json::to_value(event).expect("event serializes to JSON")
+    else {
+        unreachable!("events are struct variants");
+    };
+    let (kind, fields) = tagged.into_iter().next().expect("event has a variant name");
+    let Value::Object(mut fields) = fields else {
+        unreachable!("events are struct variants");
+    };
+    fields.insert("type".to_string(), Value::String(kind));
+    Value::Object(fields)
+}
+
+/// Mailbox for the [Exporter].
+#[derive(Clone)]
+pub struct Mailbox {
+    sender: mpsc::Sender<Batch>,
+    dropped: Counter<u64, AtomicU64>,
+}
+
+impl Mailbox {
+    /// Export the events in the operations of a finalized block (starting at `start`).
+    ///
+    /// Never blocks: if the exporter is too far behind, the events are dropped.
+    pub fn export(&mut self, height: u64, start: u64, ops: &[Keyless<Output>]) {
+        let events: Vec<_> = ops
+            .iter()
+            .enumerate()
+            .filter_map(|(i, op)| match op {
+                Keyless::Append(output) => output
+                    .event()
+                    .map(|event| (start + i as u64, event.clone())),
+                _ => None,
+            })
+            .collect();
+        if events.is_empty() {
+            return;
+        }
+        let count = events.len();
+        if self.sender.try_send(Batch { height, events }).is_err() {
+            warn!(height, count, "event exporter is behind, dropping events");
+            self.dropped.inc_by(count as u64);
+        }
+    }
+}
+
+/// Writes finalized events to a [Target].
+pub struct Exporter<E: Clock + Spawner + Network + Metrics> {
+    context: E,
+    target: Target,
+    receiver: mpsc::Receiver<Batch>,
+    exported: Counter<u64, AtomicU64>,
+}
+
+impl<E: Clock + Spawner + Network + Metrics> Exporter<E> {
+    pub fn new(context: E, target: Target) -> (Self, Mailbox) {
+        let (sender, receiver) = mpsc::channel(BUFFER);
+        let exported = Counter::default();
+        context.register(
+            "events_exported",
+            "Number of events exported",
+            exported.clone(),
+        );
+        let dropped = Counter::default();
+        context.register(
+            "events_export_dropped",
+            "Number of events dropped because the exporter was behind",
+            dropped.clone(),
+        );
+        (
+            Self {
+                context,
+                target,
+                receiver,
+                exported,
+            },
+            Mailbox { sender, dropped },
+        )
+    }
+
+    pub fn start(mut self) -> Handle<()> {
+        self.context.spawn_ref()(self.run())
+    }
+
+    async fn run(mut self) {
+        let mut connection = None;
+        while let Some(batch) = self.receiver.next().await {
+            let lines = batch.lines();
+
+            // Retry until the batch is written (blocks are exported in order)
+            loop {
+                let result = match &self.target {
+                    Target::File(path) => OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(path)
+                        .and_then(|mut file| file.write_all(lines.as_bytes()))
+                        .map_err(|e| e.to_string()),
+                    Target::Tcp(addr) => {
+                        if connection.is_none() {
+                            match self.context.dial(*addr).await {
+                                Ok((sink, _)) => connection = Some(sink),
+                                Err(e) => {
+                                    warn!(?e, %addr, "failed to connect to event sink");
+                                    self.context.sleep(RETRY_DELAY).await;
+                                    continue;
+                                }
+                            }
+                        }
+                        let sink = connection.as_mut().unwrap();
+                        sink.send(lines.as_bytes().to_vec())
+                            .await
+                            .map_err(|e| e.to_string())
+                    }
+                };
+                match result {
+                    Ok(()) => break,
+                    Err(e) => {
+                        warn!(error = %e, target = %self.target, "failed to export events");
+                        connection = None;
+                        self.context.sleep(RETRY_DELAY).await;
+                    }
+                }
+            }
+            debug!(
+                height = batch.height,
+                events = batch.events.len(),
+                "exported events"
+            );
+            self.exported.inc_by(batch.events.len() as u64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commonware_cryptography::{ed25519::PrivateKey, PrivateKeyExt, Signer};
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(
+            Target::parse("tcp://127.0.0.1:9000"),
+            Some(Target::Tcp("127.0.0.1:9000".parse().unwrap()))
+        );
+        assert_eq!(
+            Target::parse("/var/log/events.jsonl"),
+            Some(Target::File(PathBuf::from("/var/log/events.jsonl")))
+        );
+        assert_eq!(Target::parse("tcp://localhost"), None);
+        assert_eq!(Target::parse(""), None);
+    }
+
+    #[test]
+    fn test_lines() {
+        let player = PrivateKey::from_seed(0).public_key();
+        let batch = Batch {
+            height: 7,
+            events: vec![
+                (
+                    3,
+                    Event::CasinoPlayerRegistered {
+                        player: player.clone(),
+                        name: "alice".to_string(),
+                    },
+                ),
+                (
+                    5,
+                    Event::Staked {
+                        player,
+                        amount: 10,
+                        duration: 100,
+                        new_balance: 10,
+                        unlock_ts: 200,
+                        voting_power: u128::MAX,
+                    },
+                ),
+            ],
+        };
+        let lines: Vec<Value> = batch
+            .lines()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["type"], "CasinoPlayerRegistered");
+        assert_eq!(lines[0]["name"], "alice");
+        assert_eq!(lines[0]["height"], 7);
+        assert_eq!(lines[0]["location"], 3);
+        assert_eq!(lines[1]["type"], "Staked");
+        assert_eq!(lines[1]["voting_power"], u128::MAX.to_string());
+        assert_eq!(lines[1]["location"], 5);
+    }
+}
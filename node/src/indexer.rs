@@ -1,14 +1,17 @@
+use crate::bloom::RollingBloom;
+use crate::bus::{BusIndexer, Nats, NatsError};
 #[cfg(test)]
 use commonware_consensus::{threshold_simplex::types::View, Viewable};
 use commonware_cryptography::ed25519::Batch;
-use commonware_cryptography::BatchVerifier;
+use commonware_cryptography::{BatchVerifier, Digestible};
 #[cfg(test)]
 use commonware_runtime::RwLock;
 use commonware_runtime::Spawner;
 use commonware_runtime::{Clock, Handle};
 use futures::channel::mpsc;
+use futures::future::Either;
 use futures::{SinkExt, Stream, StreamExt};
-use nullspace_types::api::Pending;
+use nullspace_types::api::{Pending, Rejection, RejectionReason};
 #[cfg(test)]
 use nullspace_types::execution::Transaction;
 use nullspace_types::{api::Summary, Seed};
@@ -17,12 +20,10 @@ use nullspace_types::{Identity, NAMESPACE};
 use rand::{CryptoRng, Rng};
 use std::future::Future;
 #[cfg(test)]
-use std::{
-    collections::HashMap,
-    sync::{Arc, Mutex},
-};
+use std::{collections::HashMap, sync::Mutex};
 use std::{
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
@@ -34,6 +35,13 @@ const TX_STREAM_RECONNECT_DELAY: Duration = Duration::from_secs(10);
 /// Buffer size for the tx_stream channel
 const TX_STREAM_BUFFER_SIZE: usize = 1_024;
 
+/// Number of recently seen transactions to remember (per generation) when deduplicating.
+const SEEN_TRANSACTIONS_CAPACITY: usize = 65_536;
+
+/// Maximum time a transaction digest is remembered per generation (users may need to
+/// rebroadcast a transaction that was dropped from an unfinalized block).
+const SEEN_TRANSACTIONS_WINDOW: Duration = Duration::from_secs(10);
+
 /// Trait for interacting with an indexer.
 pub trait Indexer: Clone + Send + Sync + 'static {
     type Error: std::error::Error + Send + Sync + 'static;
@@ -53,6 +61,18 @@ pub trait Indexer: Clone + Send + Sync + 'static {
         &self,
         summary: Summary,
     ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Upload the results of multiple heights at once
+    fn submit_summaries(
+        &self,
+        summaries: Vec<Summary>,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Report pending transactions that were refused by the mempool
+    fn report_rejections(
+        &self,
+        rejections: Vec<Rejection>,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
 }
 
 /// A mock indexer implementation for testing.
@@ -65,6 +85,7 @@ pub struct Mock {
     pub summaries: Arc<RwLock<Vec<(u64, Summary)>>>,
     #[allow(clippy::type_complexity)]
     pub tx_sender: Arc<Mutex<Vec<mpsc::UnboundedSender<Result<Pending, std::io::Error>>>>>,
+    pub rejections: Arc<Mutex<Vec<Rejection>>>,
 }
 
 #[cfg(test)]
@@ -75,6 +96,7 @@ impl Mock {
             seeds: Arc::new(Mutex::new(HashMap::new())),
             summaries: Arc::new(RwLock::new(Vec::new())),
             tx_sender: Arc::new(Mutex::new(Vec::new())),
+            rejections: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -122,6 +144,18 @@ impl Indexer for Mock {
 
         Ok(())
     }
+
+    async fn submit_summaries(&self, summaries: Vec<Summary>) -> Result<(), Self::Error> {
+        for summary in summaries {
+            self.submit_summary(summary).await?;
+        }
+        Ok(())
+    }
+
+    async fn report_rejections(&self, rejections: Vec<Rejection>) -> Result<(), Self::Error> {
+        self.rejections.lock().unwrap().extend(rejections);
+        Ok(())
+    }
 }
 
 impl Indexer for nullspace_client::Client {
@@ -144,6 +178,151 @@ impl Indexer for nullspace_client::Client {
     async fn submit_summary(&self, summary: Summary) -> Result<(), Self::Error> {
         self.submit_summary(summary).await
     }
+
+    async fn submit_summaries(&self, summaries: Vec<Summary>) -> Result<(), Self::Error> {
+        self.submit_summaries(summaries).await
+    }
+
+    async fn report_rejections(&self, rejections: Vec<Rejection>) -> Result<(), Self::Error> {
+        self.submit_rejections(rejections).await
+    }
+}
+
+/// An indexer client whose endpoint can be replaced while the node is running.
+///
+/// Requests issued after [ReloadableClient::replace] use the new endpoint (an already
+/// connected mempool stream continues until it reconnects).
+#[derive(Clone)]
+pub struct ReloadableClient {
+    inner: Arc<std::sync::RwLock<nullspace_client::Client>>,
+}
+
+impl ReloadableClient {
+    pub fn new(client: nullspace_client::Client) -> Self {
+        Self {
+            inner: Arc::new(std::sync::RwLock::new(client)),
+        }
+    }
+
+    /// Replace the underlying client.
+    pub fn replace(&self, client: nullspace_client::Client) {
+        *self.inner.write().unwrap_or_else(|p| p.into_inner()) = client;
+    }
+
+    fn current(&self) -> nullspace_client::Client {
+        self.inner.read().unwrap_or_else(|p| p.into_inner()).clone()
+    }
+}
+
+impl Indexer for ReloadableClient {
+    type Error = nullspace_client::Error;
+
+    async fn submit_seed(&self, seed: Seed) -> Result<(), Self::Error> {
+        self.current().submit_seed(seed).await
+    }
+
+    async fn listen_mempool(
+        &self,
+    ) -> Result<impl Stream<Item = Result<Pending, Self::Error>>, Self::Error> {
+        match self.current().connect_mempool().await {
+            Ok(stream) => Ok(stream
+                .map(|result| result.map_err(|_| nullspace_client::Error::UnexpectedResponse))),
+            Err(_) => Err(nullspace_client::Error::UnexpectedResponse),
+        }
+    }
+
+    async fn submit_summary(&self, summary: Summary) -> Result<(), Self::Error> {
+        self.current().submit_summary(summary).await
+    }
+
+    async fn submit_summaries(&self, summaries: Vec<Summary>) -> Result<(), Self::Error> {
+        self.current().submit_summaries(summaries).await
+    }
+
+    async fn report_rejections(&self, rejections: Vec<Rejection>) -> Result<(), Self::Error> {
+        self.current().submit_rejections(rejections).await
+    }
+}
+
+/// Error returned by a [Backend].
+#[derive(Debug, thiserror::Error)]
+pub enum BackendError {
+    #[error(transparent)]
+    Http(#[from] nullspace_client::Error),
+    #[error(transparent)]
+    Nats(#[from] NatsError),
+}
+
+/// The indexer selected at startup: the HTTP indexer or a message bus.
+#[derive(Clone)]
+pub enum Backend {
+    Http(ReloadableClient),
+    Nats(BusIndexer<Nats>),
+}
+
+impl Indexer for Backend {
+    type Error = BackendError;
+
+    async fn submit_seed(&self, seed: Seed) -> Result<(), Self::Error> {
+        match self {
+            Backend::Http(client) => Ok(client.submit_seed(seed).await?),
+            Backend::Nats(bus) => Ok(bus.submit_seed(seed).await?),
+        }
+    }
+
+    async fn listen_mempool(
+        &self,
+    ) -> Result<impl Stream<Item = Result<Pending, Self::Error>> + Send, Self::Error> {
+        match self {
+            Backend::Http(client) => {
+                let stream = client.listen_mempool().await?;
+                Ok(Either::Left(
+                    stream.map(|result| result.map_err(Into::into)),
+                ))
+            }
+            Backend::Nats(bus) => {
+                let stream = bus.listen_mempool().await?;
+                Ok(Either::Right(
+                    stream.map(|result| result.map_err(Into::into)),
+                ))
+            }
+        }
+    }
+
+    async fn submit_summary(&self, summary: Summary) -> Result<(), Self::Error> {
+        match self {
+            Backend::Http(client) => Ok(client.submit_summary(summary).await?),
+            Backend::Nats(bus) => Ok(bus.submit_summary(summary).await?),
+        }
+    }
+
+    async fn submit_summaries(&self, summaries: Vec<Summary>) -> Result<(), Self::Error> {
+        match self {
+            Backend::Http(client) => Ok(client.submit_summaries(summaries).await?),
+            Backend::Nats(bus) => Ok(bus.submit_summaries(summaries).await?),
+        }
+    }
+
+    async fn report_rejections(&self, rejections: Vec<Rejection>) -> Result<(), Self::Error> {
+        match self {
+            Backend::Http(client) => Ok(client.report_rejections(rejections).await?),
+            Backend::Nats(bus) => Ok(bus.report_rejections(rejections).await?),
+        }
+    }
+}
+
+/// Report `rejections` to `indexer` in the background (so the mempool is never blocked on
+/// the indexer).
+pub fn spawn_report<E: Spawner, I: Indexer>(context: &E, indexer: &I, rejections: Vec<Rejection>) {
+    if rejections.is_empty() {
+        return;
+    }
+    let indexer = indexer.clone();
+    context.clone().spawn(move |_| async move {
+        if let Err(e) = indexer.report_rejections(rejections).await {
+            warn!(?e, "failed to report rejected transactions");
+        }
+    });
 }
 
 /// A stream that wraps the indexer's listen_mempool with automatic reconnection
@@ -167,6 +346,13 @@ where
         let (mut tx, rx) = mpsc::channel(TX_STREAM_BUFFER_SIZE);
         let handle = context.spawn({
             move |mut context| async move {
+                // Track recently seen transactions (across reconnections) to avoid
+                // re-verifying and re-admitting duplicates
+                let mut seen = RollingBloom::new(
+                    SEEN_TRANSACTIONS_CAPACITY,
+                    SEEN_TRANSACTIONS_WINDOW,
+                    context.current(),
+                );
                 loop {
                     // Try to connect
                     match indexer.listen_mempool().await {
@@ -177,15 +363,51 @@ where
                             // Forward transactions until stream fails
                             while let Some(result) = stream.next().await {
                                 match result {
-                                    Ok(pending) => {
-                                        // Batch verify transactions
+                                    Ok(mut pending) => {
+                                        // Drop recently seen transactions
+                                        let now = context.current();
+                                        pending
+                                            .transactions
+                                            .retain(|tx| !seen.contains(&tx.digest(), now));
+                                        if pending.transactions.is_empty() {
+                                            continue;
+                                        }
+
+                                        // Batch verify transactions (falling back to verifying
+                                        // each one to find the invalid ones)
                                         let mut batcher = Batch::new();
                                         for tx in &pending.transactions {
                                             tx.verify_batch(&mut batcher);
                                         }
                                         if !batcher.verify(&mut context) {
-                                            warn!("received invalid transaction from indexer");
-                                            return;
+                                            let (valid, invalid): (Vec<_>, Vec<_>) = pending
+                                                .transactions
+                                                .into_iter()
+                                                .partition(|tx| tx.verify());
+                                            warn!(
+                                                invalid = invalid.len(),
+                                                "received transactions with invalid signatures from indexer"
+                                            );
+                                            let rejections = invalid
+                                                .iter()
+                                                .map(|tx| {
+                                                    Rejection::new(
+                                                        tx,
+                                                        RejectionReason::InvalidSignature,
+                                                    )
+                                                })
+                                                .collect();
+                                            spawn_report(&context, &indexer, rejections);
+                                            pending.transactions = valid;
+                                            if pending.transactions.is_empty() {
+                                                continue;
+                                            }
+                                        }
+
+                                        // Only remember transactions once verified (the digest does not
+                                        // cover the signature)
+                                        for tx in &pending.transactions {
+                                            seen.insert(&tx.digest(), now);
                                         }
 
                                         // Pass to receiver
@@ -276,4 +498,12 @@ where
     async fn submit_summary(&self, summary: Summary) -> Result<(), Self::Error> {
         self.inner.submit_summary(summary).await
     }
+
+    async fn submit_summaries(&self, summaries: Vec<Summary>) -> Result<(), Self::Error> {
+        self.inner.submit_summaries(summaries).await
+    }
+
+    async fn report_rejections(&self, rejections: Vec<Rejection>) -> Result<(), Self::Error> {
+        self.inner.report_rejections(rejections).await
+    }
 }
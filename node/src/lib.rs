@@ -6,23 +6,36 @@ use commonware_cryptography::{
 };
 use commonware_utils::{from_hex_formatted, quorum};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, net::SocketAddr, path::PathBuf, str::FromStr};
+use std::{collections::HashMap, net::SocketAddr, path::PathBuf, str::FromStr, time::Duration};
 use thiserror::Error;
 use tracing::Level;
 
-use nullspace_types::{Evaluation, Identity};
+use nullspace_types::{execution::MAX_BLOCK_TRANSACTIONS, Evaluation, Identity};
 
 pub mod aggregator;
 pub mod application;
+pub mod bloom;
+pub mod bus;
 pub mod engine;
+pub mod export;
 pub mod indexer;
+pub mod light;
+pub mod reload;
+pub mod rotation;
+pub mod rpc;
+pub mod scorer;
 pub mod seeder;
 pub mod supervisor;
 
 /// Configuration for the [engine::Engine].
-#[derive(Deserialize, Serialize)]
+///
+/// A subset of fields can be changed at runtime (see [reload::Changes]).
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Config {
     pub private_key: String,
+    /// Key to rotate to (announced to peers while still running with `private_key`).
+    #[serde(default)]
+    pub next_private_key: Option<String>,
     pub share: String,
     pub polynomial: String,
 
@@ -42,9 +55,50 @@ pub struct Config {
     pub mempool_max_backlog: usize,
     #[serde(default = "default_mempool_max_transactions")]
     pub mempool_max_transactions: usize,
+    /// Seconds a transaction may wait in the mempool before it is evicted.
+    #[serde(default = "default_mempool_tx_ttl_secs")]
+    pub mempool_tx_ttl_secs: u64,
 
     pub indexer: String,
     pub execution_concurrency: usize,
+    #[serde(default = "default_max_uploads_outstanding")]
+    pub max_uploads_outstanding: usize,
+
+    /// Maximum number of transactions to include in a proposed block.
+    #[serde(default = "default_max_block_txs")]
+    pub max_block_txs: usize,
+    /// Maximum encoded size (in bytes) of the transactions in a proposed block.
+    #[serde(default = "default_max_block_bytes")]
+    pub max_block_bytes: usize,
+
+    /// Retain every historical state version instead of pruning.
+    #[serde(default)]
+    pub archive: bool,
+
+    /// Port for the local RPC (bound to localhost). Disabled if not set.
+    #[serde(default)]
+    pub rpc_port: Option<u16>,
+
+    /// Where to export finalized events as JSONL (a file path or `tcp://<addr>`).
+    /// Disabled if not set.
+    #[serde(default)]
+    pub event_export: Option<String>,
+
+    /// Public keys of light peers (e.g. wallets) allowed to connect and request state
+    /// proofs and seeds (see [light]).
+    #[serde(default)]
+    pub light_peers: Vec<String>,
+
+    /// NATS server to publish seeds and summaries to (and read pending transactions from)
+    /// instead of `indexer`, e.g. `nats://127.0.0.1:4222` (see [bus]). Disabled if not set.
+    #[serde(default)]
+    pub message_bus: Option<String>,
+    /// Prefix of the subjects published to the message bus.
+    #[serde(default = "default_message_bus_prefix")]
+    pub message_bus_prefix: String,
+    /// Number of partitions seeds and summaries are spread across on the message bus.
+    #[serde(default = "default_message_bus_partitions")]
+    pub message_bus_partitions: usize,
 }
 
 #[derive(Debug, Error)]
@@ -62,6 +116,16 @@ pub enum ConfigError {
     InvalidLogLevel { value: String },
     #[error("{field} must be > 0 (got {value})")]
     InvalidNonZero { field: &'static str, value: usize },
+    #[error("invalid event export target: {value}")]
+    InvalidExportTarget { value: String },
+    #[error("next_private_key must differ from private_key")]
+    UnchangedKey,
+    #[error("{field} must be <= {max} (got {value})")]
+    OutOfRange {
+        field: &'static str,
+        value: usize,
+        max: usize,
+    },
 }
 
 pub struct ValidatedConfig {
@@ -85,9 +149,20 @@ pub struct ValidatedConfig {
     pub deque_size: usize,
     pub mempool_max_backlog: usize,
     pub mempool_max_transactions: usize,
+    pub mempool_tx_ttl: Duration,
 
     pub indexer: String,
     pub execution_concurrency: usize,
+    pub max_uploads_outstanding: usize,
+    pub max_block_txs: usize,
+    pub max_block_bytes: usize,
+    pub archive: bool,
+    pub rpc_port: Option<u16>,
+    pub event_export: Option<export::Target>,
+    pub light_peers: Vec<PublicKey>,
+    pub message_bus: Option<String>,
+    pub message_bus_prefix: String,
+    pub message_bus_partitions: usize,
 }
 
 fn default_mempool_max_backlog() -> usize {
@@ -98,6 +173,30 @@ fn default_mempool_max_transactions() -> usize {
     100_000
 }
 
+fn default_mempool_tx_ttl_secs() -> u64 {
+    600
+}
+
+fn default_message_bus_prefix() -> String {
+    "nullspace".to_string()
+}
+
+fn default_message_bus_partitions() -> usize {
+    16
+}
+
+fn default_max_uploads_outstanding() -> usize {
+    4
+}
+
+fn default_max_block_txs() -> usize {
+    MAX_BLOCK_TRANSACTIONS
+}
+
+fn default_max_block_bytes() -> usize {
+    1024 * 1024
+}
+
 fn parse_hex(field: &'static str, value: &str) -> Result<Vec<u8>, ConfigError> {
     from_hex_formatted(value).ok_or(ConfigError::InvalidHex {
         field,
@@ -123,6 +222,13 @@ impl Config {
         decode_hex("private_key", &self.private_key)
     }
 
+    pub fn parse_next_signer(&self) -> Result<Option<PrivateKey>, ConfigError> {
+        self.next_private_key
+            .as_deref()
+            .map(|key| decode_hex("next_private_key", key))
+            .transpose()
+    }
+
     pub fn validate(self, peer_count: u32) -> Result<ValidatedConfig, ConfigError> {
         let signer = self.parse_signer()?;
         self.validate_with_signer(signer, peer_count)
@@ -145,8 +251,51 @@ impl Config {
                 value: self.mempool_max_transactions,
             });
         }
+        if self.mempool_tx_ttl_secs == 0 {
+            return Err(ConfigError::InvalidNonZero {
+                field: "mempool_tx_ttl_secs",
+                value: 0,
+            });
+        }
+        if self.max_uploads_outstanding == 0 {
+            return Err(ConfigError::InvalidNonZero {
+                field: "max_uploads_outstanding",
+                value: self.max_uploads_outstanding,
+            });
+        }
+        if self.max_block_txs == 0 {
+            return Err(ConfigError::InvalidNonZero {
+                field: "max_block_txs",
+                value: self.max_block_txs,
+            });
+        }
+        if self.max_block_txs > MAX_BLOCK_TRANSACTIONS {
+            return Err(ConfigError::OutOfRange {
+                field: "max_block_txs",
+                value: self.max_block_txs,
+                max: MAX_BLOCK_TRANSACTIONS,
+            });
+        }
+        if self.max_block_bytes == 0 {
+            return Err(ConfigError::InvalidNonZero {
+                field: "max_block_bytes",
+                value: self.max_block_bytes,
+            });
+        }
+
+        if self.message_bus_partitions == 0 {
+            return Err(ConfigError::InvalidNonZero {
+                field: "message_bus_partitions",
+                value: self.message_bus_partitions,
+            });
+        }
 
         let public_key = signer.public_key();
+        if let Some(next) = self.parse_next_signer()? {
+            if next.public_key() == public_key {
+                return Err(ConfigError::UnchangedKey);
+            }
+        }
 
         let share = decode_hex("share", &self.share)?;
 
@@ -161,6 +310,22 @@ impl Config {
                 })?;
         let identity = *poly::public::<MinSig>(&polynomial);
 
+        let event_export = self
+            .event_export
+            .as_deref()
+            .map(|value| {
+                export::Target::parse(value).ok_or_else(|| ConfigError::InvalidExportTarget {
+                    value: value.to_string(),
+                })
+            })
+            .transpose()?;
+
+        let light_peers = self
+            .light_peers
+            .iter()
+            .map(|peer| decode_hex("light_peers", peer))
+            .collect::<Result<Vec<_>, _>>()?;
+
         let log_level =
             Level::from_str(&self.log_level).map_err(|_| ConfigError::InvalidLogLevel {
                 value: self.log_level.clone(),
@@ -184,8 +349,19 @@ impl Config {
             deque_size: self.deque_size,
             mempool_max_backlog: self.mempool_max_backlog,
             mempool_max_transactions: self.mempool_max_transactions,
+            mempool_tx_ttl: Duration::from_secs(self.mempool_tx_ttl_secs),
             indexer: self.indexer,
             execution_concurrency: self.execution_concurrency,
+            max_uploads_outstanding: self.max_uploads_outstanding,
+            max_block_txs: self.max_block_txs,
+            max_block_bytes: self.max_block_bytes,
+            archive: self.archive,
+            rpc_port: self.rpc_port,
+            event_export,
+            light_peers,
+            message_bus: self.message_bus,
+            message_bus_prefix: self.message_bus_prefix,
+            message_bus_partitions: self.message_bus_partitions,
         })
     }
 }
@@ -0,0 +1,232 @@
+//! Serving of state proofs and seeds to light peers.
+//!
+//! Light peers (e.g. wallets) are allowed to connect over p2p (see `light_peers` in
+//! [crate::Config]) without participating in consensus. They send [LightRequest]s on a
+//! dedicated channel and receive [LightResponse]s they can verify against the network
+//! identity, so balances can be checked against any validator instead of a single indexer.
+//!
+//! Lookups are proven as of the latest height that is both executed and certified. If the
+//! key was updated after that height, the request is answered with
+//! [LightResponse::Unavailable] (and can be retried once the update is certified).
+//!
+//! Light peers share the validators' peer set, so every other channel is wrapped in a
+//! [Restricted] receiver that drops their messages.
+
+use crate::{aggregator, application, seeder};
+use commonware_codec::{DecodeExt, Encode};
+use commonware_cryptography::ed25519::PublicKey;
+use commonware_p2p::{Message, Receiver, Recipients, Sender};
+use commonware_runtime::{Clock, Handle, Metrics, Spawner};
+use nullspace_types::api::{LightRequest, LightResponse, Lookup};
+use prometheus_client::metrics::counter::Counter;
+use std::{
+    collections::HashSet,
+    sync::{atomic::AtomicU64, Arc},
+};
+use tracing::{debug, warn};
+
+/// A [Receiver] that drops messages sent by light peers (which may only use the light channel).
+#[derive(Debug)]
+pub struct Restricted<R: Receiver<PublicKey = PublicKey>> {
+    inner: R,
+    light_peers: Arc<HashSet<PublicKey>>,
+}
+
+impl<R: Receiver<PublicKey = PublicKey>> Restricted<R> {
+    pub fn new(inner: R, light_peers: Arc<HashSet<PublicKey>>) -> Self {
+        Self { inner, light_peers }
+    }
+}
+
+impl<R: Receiver<PublicKey = PublicKey>> Receiver for Restricted<R> {
+    type Error = R::Error;
+    type PublicKey = PublicKey;
+
+    async fn recv(&mut self) -> Result<Message<Self::PublicKey>, Self::Error> {
+        loop {
+            let (peer, message) = self.inner.recv().await?;
+            if self.light_peers.contains(&peer) {
+                debug!(?peer, "dropping message from light peer");
+                continue;
+            }
+            return Ok((peer, message));
+        }
+    }
+}
+
+/// Answers [LightRequest]s from light peers.
+pub struct Server<E: Clock + Spawner + Metrics> {
+    context: E,
+    application: application::Mailbox<E>,
+    aggregator: aggregator::Mailbox,
+    seeder: seeder::Mailbox,
+
+    served: Counter<u64, AtomicU64>,
+    unavailable: Counter<u64, AtomicU64>,
+}
+
+impl<E: Clock + Spawner + Metrics> Server<E> {
+    pub fn new(
+        context: E,
+        application: application::Mailbox<E>,
+        aggregator: aggregator::Mailbox,
+        seeder: seeder::Mailbox,
+    ) -> Self {
+        let served = Counter::default();
+        context.register(
+            "light_served",
+            "Number of light requests answered with a proof or seed",
+            served.clone(),
+        );
+        let unavailable = Counter::default();
+        context.register(
+            "light_unavailable",
+            "Number of light requests that could not be served",
+            unavailable.clone(),
+        );
+        Self {
+            context,
+            application,
+            aggregator,
+            seeder,
+            served,
+            unavailable,
+        }
+    }
+
+    pub fn start(
+        mut self,
+        network: (
+            impl Sender<PublicKey = PublicKey>,
+            impl Receiver<PublicKey = PublicKey>,
+        ),
+    ) -> Handle<()> {
+        self.context.spawn_ref()(self.run(network))
+    }
+
+    async fn run(
+        mut self,
+        (mut sender, mut receiver): (
+            impl Sender<PublicKey = PublicKey>,
+            impl Receiver<PublicKey = PublicKey>,
+        ),
+    ) {
+        loop {
+            let Ok((peer, message)) = receiver.recv().await else {
+                warn!("light receiver closed");
+                return;
+            };
+            let Ok(request) = LightRequest::decode(message) else {
+                debug!(?peer, "failed to decode light request");
+                continue;
+            };
+            let response = self.serve(request).await;
+            match response {
+                LightResponse::Unavailable { .. } => self.unavailable.inc(),
+                _ => self.served.inc(),
+            };
+            let _ = sender
+                .send(Recipients::One(peer), response.encode().into(), false)
+                .await;
+        }
+    }
+
+    async fn serve(&mut self, request: LightRequest) -> LightResponse {
+        match request {
+            LightRequest::Lookup { id, key } => {
+                let Some((progress, certificate)) = self.aggregator.latest().await else {
+                    return LightResponse::Unavailable { id };
+                };
+                let Some((proof, location, operation)) =
+                    self.application.prove(key, progress.state_end_op).await
+                else {
+                    return LightResponse::Unavailable { id };
+                };
+                LightResponse::Lookup {
+                    id,
+                    lookup: Lookup {
+                        progress,
+                        certificate,
+                        proof,
+                        location,
+                        operation,
+                    },
+                }
+            }
+            LightRequest::Seed { id, view } => match self.seeder.stored(view).await {
+                Ok(Some(seed)) => LightResponse::Seed { id, seed },
+                _ => LightResponse::Unavailable { id },
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use commonware_cryptography::{ed25519::PrivateKey, PrivateKeyExt, Signer};
+    use commonware_macros::select;
+    use commonware_p2p::simulated::{self, Link, Network};
+    use commonware_runtime::{deterministic, Runner};
+    use std::time::Duration;
+
+    #[test]
+    fn test_restricted_drops_light_peers() {
+        let executor = deterministic::Runner::default();
+        executor.start(|context| async move {
+            let (network, mut oracle) = Network::new(
+                context.with_label("network"),
+                simulated::Config {
+                    max_size: 1024 * 1024,
+                },
+            );
+            network.start();
+            let me = PrivateKey::from_seed(0).public_key();
+            let validator = PrivateKey::from_seed(1).public_key();
+            let light = PrivateKey::from_seed(2).public_key();
+            let (_, receiver) = oracle.register(me.clone(), 0).await.unwrap();
+            let (mut validator_sender, _) = oracle.register(validator.clone(), 0).await.unwrap();
+            let (mut light_sender, _) = oracle.register(light.clone(), 0).await.unwrap();
+            let link = Link {
+                latency: Duration::from_millis(10),
+                jitter: Duration::from_millis(1),
+                success_rate: 1.0,
+            };
+            for peer in [&validator, &light] {
+                oracle
+                    .add_link(peer.clone(), me.clone(), link.clone())
+                    .await
+                    .unwrap();
+            }
+            let mut receiver = Restricted::new(receiver, Arc::new(HashSet::from([light.clone()])));
+
+            // Messages from light peers are dropped...
+            light_sender
+                .send(
+                    Recipients::One(me.clone()),
+                    Bytes::from_static(b"light"),
+                    false,
+                )
+                .await
+                .unwrap();
+            select! {
+                _ = receiver.recv() => panic!("received message from light peer"),
+                _ = context.sleep(Duration::from_secs(1)) => {},
+            }
+
+            // ...while messages from validators are delivered
+            validator_sender
+                .send(
+                    Recipients::One(me.clone()),
+                    Bytes::from_static(b"validator"),
+                    false,
+                )
+                .await
+                .unwrap();
+            let (peer, message) = receiver.recv().await.unwrap();
+            assert_eq!(peer, validator);
+            assert_eq!(message, Bytes::from_static(b"validator"));
+        });
+    }
+}
@@ -1,25 +1,38 @@
 use anyhow::{Context, Result};
+use axum::{extract::State, http::header, response::IntoResponse, routing::get, Router};
 use clap::{Arg, ArgAction, Command};
 use commonware_codec::DecodeExt;
 use commonware_cryptography::{ed25519::PublicKey, Signer};
 use commonware_deployer::ec2::Hosts;
 use commonware_p2p::authenticated::discovery as authenticated;
-use commonware_runtime::{tokio, Metrics, Runner};
+use commonware_runtime::{tokio, Clock, Metrics, Runner, Spawner};
 use commonware_utils::{from_hex_formatted, union_unique, NZUsize};
 use futures::future::try_join_all;
 use governor::Quota;
 use nullspace_client::Client;
-use nullspace_node::{engine, parse_peer_public_key, Config, Peers};
-use nullspace_types::NAMESPACE;
+use nullspace_node::{
+    bus, engine, export,
+    indexer::{Backend, ReloadableClient},
+    light, parse_peer_public_key,
+    reload::Changes,
+    reload::Tunables,
+    rotation::{self, Registry, Rotation},
+    rpc, Config, Peers,
+};
+use nullspace_types::{Identity, NAMESPACE};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     net::{IpAddr, Ipv4Addr, SocketAddr},
     num::{NonZeroU32, NonZeroUsize},
     path::PathBuf,
     str::FromStr,
+    sync::Arc,
     time::Duration,
 };
-use tracing::{error, info, Level};
+use tracing::{error, info, warn, Level};
+use tracing_subscriber::{
+    filter::LevelFilter, fmt, layer::SubscriberExt, reload, util::SubscriberInitExt, Registry,
+};
 
 const PENDING_CHANNEL: u32 = 0;
 const RECOVERED_CHANNEL: u32 = 1;
@@ -29,6 +42,8 @@ const BACKFILL_BY_DIGEST_CHANNEL: u32 = 4;
 const SEEDER_CHANNEL: u32 = 5;
 const AGGREGATOR_CHANNEL: u32 = 6;
 const AGGREGATION_CHANNEL: u32 = 7;
+const ROTATION_CHANNEL: u32 = 8;
+const LIGHT_CHANNEL: u32 = 9;
 
 const LEADER_TIMEOUT: Duration = Duration::from_secs(1);
 const NOTARIZATION_TIMEOUT: Duration = Duration::from_secs(2);
@@ -44,11 +59,131 @@ const BLOCKS_FREEZER_TABLE_INITIAL_SIZE: u32 = 2u32.pow(21); // 100MB
 const FINALIZED_FREEZER_TABLE_INITIAL_SIZE: u32 = 2u32.pow(21); // 100MB
 const BUFFER_POOL_PAGE_SIZE: NonZeroUsize = NZUsize!(4_096); // 4KB
 const BUFFER_POOL_CAPACITY: NonZeroUsize = NZUsize!(32_768); // 128MB
-const MAX_UPLOADS_OUTSTANDING: usize = 4;
+const CONFIG_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
 
 type PeerList = Vec<PublicKey>;
 type BootstrapList = Vec<(PublicKey, SocketAddr)>;
 type PeerConfig = (IpAddr, PeerList, BootstrapList);
+type LogHandle = reload::Handle<LevelFilter, Registry>;
+
+/// Allows the keys of rotated peers to connect.
+#[derive(Clone)]
+struct PeerRegistry {
+    oracle: authenticated::Oracle<tokio::Context, PublicKey>,
+    peers: Vec<PublicKey>,
+    index: u64,
+}
+
+impl Registry for PeerRegistry {
+    async fn register(&mut self, peer: PublicKey) {
+        if self.peers.contains(&peer) {
+            return;
+        }
+        self.peers.push(peer);
+        self.index += 1;
+        self.oracle.register(self.index, self.peers.clone()).await;
+    }
+}
+
+/// Install a global subscriber whose level can be changed at runtime.
+fn init_logging(level: Level, json: bool) -> LogHandle {
+    let (filter, handle) = reload::Layer::new(LevelFilter::from_level(level));
+    let registry = tracing_subscriber::registry().with(filter);
+    if json {
+        registry.with(fmt::layer().json()).init();
+    } else {
+        registry.with(fmt::layer()).init();
+    }
+    handle
+}
+
+/// Serve the runtime's metrics (in the Prometheus text format) at `/metrics`.
+async fn serve_metrics(context: tokio::Context, addr: SocketAddr) {
+    let listener = match ::tokio::net::TcpListener::bind(addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            error!(?e, ?addr, "failed to bind metrics server");
+            return;
+        }
+    };
+    let app = Router::new()
+        .route("/metrics", get(metrics))
+        .with_state(context);
+    if let Err(e) = axum::serve(listener, app).await {
+        error!(?e, "metrics server failed");
+    }
+}
+
+async fn metrics(State(context): State<tokio::Context>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        context.encode(),
+    )
+}
+
+/// Poll the config file and apply any safe-to-change fields.
+async fn watch_config(
+    context: tokio::Context,
+    path: String,
+    mut current: Config,
+    log_handle: LogHandle,
+    tunables: Tunables,
+    indexer: ReloadableClient,
+    identity: Identity,
+) {
+    let modified = |path: &str| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let mut last_modified = modified(&path);
+    loop {
+        context.sleep(CONFIG_RELOAD_INTERVAL).await;
+        let latest = modified(&path);
+        if latest == last_modified {
+            continue;
+        }
+        last_modified = latest;
+
+        // Parse the updated config
+        let next = std::fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|contents| serde_yaml::from_str::<Config>(&contents).map_err(Into::into));
+        let mut next = match next {
+            Ok(next) => next,
+            Err(e) => {
+                warn!(?e, "failed to reload config");
+                continue;
+            }
+        };
+        let changes = match Changes::between(&current, &next) {
+            Ok(changes) => changes,
+            Err(e) => {
+                warn!(?e, "ignoring invalid config reload");
+                continue;
+            }
+        };
+        if changes.is_empty() {
+            continue;
+        }
+
+        // Apply changes
+        if let Some(level) = changes.log_level {
+            if let Err(e) = log_handle.modify(|filter| *filter = LevelFilter::from_level(level)) {
+                warn!(?e, "failed to update log level");
+            }
+        }
+        changes.apply(&tunables);
+        if let Some(endpoint) = &changes.indexer {
+            match Client::new(endpoint, identity) {
+                Ok(client) => indexer.replace(client),
+                Err(e) => {
+                    // Retry on the next change
+                    warn!(?e, "failed to create indexer client");
+                    next.indexer = current.indexer.clone();
+                }
+            }
+        }
+        info!(?changes, "reloaded config");
+        current = next;
+    }
+}
 
 fn load_peers(
     hosts_file: Option<String>,
@@ -56,7 +191,10 @@ fn load_peers(
     bootstrappers: &[String],
     port: u16,
     public_key: &PublicKey,
+    rotations: &[Rotation],
 ) -> Result<PeerConfig> {
+    // Peers are listed by their original keys (which may have since been rotated)
+    let public_key = &rotation::original(rotations, public_key);
     if let Some(hosts_file) = hosts_file {
         let hosts_file = std::fs::read_to_string(&hosts_file)
             .with_context(|| format!("Could not read hosts file {hosts_file}"))?;
@@ -82,9 +220,12 @@ fn load_peers(
             let key = PublicKey::decode(key.as_ref())
                 .with_context(|| format!("Bootstrapper key is invalid: {bootstrapper}"))?;
             let ip = peers
-                .get(&key)
+                .get(&rotation::original(rotations, &key))
                 .with_context(|| format!("Could not find bootstrapper {bootstrapper} in hosts"))?;
-            bootstrap_sockets.push((key, SocketAddr::new(*ip, port)));
+            bootstrap_sockets.push((
+                rotation::current(rotations, &key),
+                SocketAddr::new(*ip, port),
+            ));
         }
         let ip = peers
             .get(public_key)
@@ -116,9 +257,9 @@ fn load_peers(
         let key = PublicKey::decode(key.as_ref())
             .with_context(|| format!("Bootstrapper key is invalid: {bootstrapper}"))?;
         let socket = peers
-            .get(&key)
+            .get(&rotation::original(rotations, &key))
             .with_context(|| format!("Could not find bootstrapper {bootstrapper} in peers"))?;
-        bootstrap_sockets.push((key, *socket));
+        bootstrap_sockets.push((rotation::current(rotations, &key), *socket));
     }
     let ip = peers
         .get(public_key)
@@ -158,11 +299,12 @@ fn main_result() -> Result<()> {
     }
 
     // Load config
-    let config_file = matches
+    let config_path = matches
         .get_one::<String>("config")
-        .context("missing --config")?;
-    let config_file = std::fs::read_to_string(config_file)
-        .with_context(|| format!("Could not read config file {config_file}"))?;
+        .context("missing --config")?
+        .clone();
+    let config_file = std::fs::read_to_string(&config_path)
+        .with_context(|| format!("Could not read config file {config_path}"))?;
     let config: Config =
         serde_yaml::from_str(&config_file).context("Could not parse config file")?;
 
@@ -176,6 +318,7 @@ fn main_result() -> Result<()> {
             &config.bootstrappers,
             config.port,
             &public_key,
+            &[],
         )?;
         let peers_u32 = peers.len() as u32;
 
@@ -201,24 +344,34 @@ fn main_result() -> Result<()> {
             let use_json_logs = hosts_file.is_some();
 
             // Configure telemetry
+            //
+            // If we are using `commonware-deployer`, we should use structured logging.
             let log_level = Level::from_str(&config.log_level).context("Invalid log level")?;
-            tokio::telemetry::init(
-                context.with_label("telemetry"),
-                tokio::telemetry::Logging {
-                    level: log_level,
-                    // If we are using `commonware-deployer`, we should use structured logging.
-                    json: use_json_logs,
-                },
-                Some(SocketAddr::new(
-                    IpAddr::V4(Ipv4Addr::UNSPECIFIED),
-                    config.metrics_port,
-                )),
-                None,
-            );
+            let log_handle = init_logging(log_level, use_json_logs);
+            context.with_label("metrics").spawn({
+                let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), config.metrics_port);
+                move |context| serve_metrics(context, addr)
+            });
+            let reloadable = config.clone();
 
             let signer = config.parse_signer().context("Private key is invalid")?;
             let public_key = signer.public_key();
 
+            // Load key rotations
+            let next_signer = config
+                .parse_next_signer()
+                .context("Next private key is invalid")?;
+            let rotator = rotation::Actor::init(
+                context.with_label("rotation"),
+                rotation::Config {
+                    partition: "rotation".to_string(),
+                    signer: signer.clone(),
+                    next: next_signer,
+                },
+            )
+            .await;
+            let rotations = rotator.rotations();
+
             // Load peers
             let (ip, peers, bootstrappers) = load_peers(
                 hosts_file,
@@ -226,6 +379,7 @@ fn main_result() -> Result<()> {
                 &config.bootstrappers,
                 config.port,
                 &public_key,
+                &rotations,
             )?;
             info!(peers = peers.len(), "loaded peers");
             let peers_u32 = peers.len() as u32;
@@ -256,53 +410,94 @@ fn main_result() -> Result<()> {
             let (mut network, mut oracle) =
                 authenticated::Network::new(context.with_label("network"), p2p_cfg);
 
-            // Provide authorized peers
-            oracle.register(0, peers.clone()).await;
+            // Provide authorized peers (light peers may connect but only use the light channel)
+            let current_peers = peers
+                .iter()
+                .map(|peer| rotation::current(&rotations, peer))
+                .chain(config.light_peers.iter().cloned())
+                .collect::<Vec<_>>();
+            oracle.register(0, current_peers.clone()).await;
+            let registry = PeerRegistry {
+                oracle: oracle.clone(),
+                peers: current_peers,
+                index: 0,
+            };
+
+            // Drop messages from light peers on all channels but the light channel
+            let light_peers = Arc::new(config.light_peers.iter().cloned().collect::<HashSet<_>>());
+            let restrict = |(sender, receiver)| {
+                (
+                    sender,
+                    light::Restricted::new(receiver, light_peers.clone()),
+                )
+            };
 
             // Register pending channel
             let pending_limit = Quota::per_second(NonZeroU32::new(128).unwrap());
-            let pending = network.register(PENDING_CHANNEL, pending_limit, config.message_backlog);
+            let pending =
+                restrict(network.register(PENDING_CHANNEL, pending_limit, config.message_backlog));
 
             // Register recovered channel
             let recovered_limit = Quota::per_second(NonZeroU32::new(128).unwrap());
-            let recovered =
-                network.register(RECOVERED_CHANNEL, recovered_limit, config.message_backlog);
+            let recovered = restrict(network.register(
+                RECOVERED_CHANNEL,
+                recovered_limit,
+                config.message_backlog,
+            ));
 
             // Register resolver channel
             let resolver_limit = Quota::per_second(NonZeroU32::new(128).unwrap());
-            let resolver =
-                network.register(RESOLVER_CHANNEL, resolver_limit, config.message_backlog);
+            let resolver = restrict(network.register(
+                RESOLVER_CHANNEL,
+                resolver_limit,
+                config.message_backlog,
+            ));
 
             // Register broadcast channel
             let broadcaster_limit = Quota::per_second(NonZeroU32::new(32).unwrap()); // Increased for faster block propagation
-            let broadcaster = network.register(
+            let broadcaster = restrict(network.register(
                 BROADCASTER_CHANNEL,
                 broadcaster_limit,
                 config.message_backlog,
-            );
+            ));
 
             // Register backfill channel
             let backfill_quota = Quota::per_second(NonZeroU32::new(8).unwrap());
-            let backfill = network.register(
+            let backfill = restrict(network.register(
                 BACKFILL_BY_DIGEST_CHANNEL,
                 backfill_quota,
                 config.message_backlog,
-            );
+            ));
 
             // Register seeder channel
-            let seeder = network.register(SEEDER_CHANNEL, backfill_quota, config.message_backlog);
+            let seeder =
+                restrict(network.register(SEEDER_CHANNEL, backfill_quota, config.message_backlog));
 
             // Register aggregator channel
-            let aggregator =
-                network.register(AGGREGATOR_CHANNEL, backfill_quota, config.message_backlog);
+            let aggregator = restrict(network.register(
+                AGGREGATOR_CHANNEL,
+                backfill_quota,
+                config.message_backlog,
+            ));
 
             // Register aggregation channel
             let aggregation_quota = Quota::per_second(NonZeroU32::new(128).unwrap());
-            let aggregation = network.register(
+            let aggregation = restrict(network.register(
                 AGGREGATION_CHANNEL,
                 aggregation_quota,
                 config.message_backlog,
-            );
+            ));
+
+            // Register rotation channel
+            let rotation_network = restrict(network.register(
+                ROTATION_CHANNEL,
+                backfill_quota,
+                config.message_backlog,
+            ));
+
+            // Register light channel
+            let light_network =
+                network.register(LIGHT_CHANNEL, backfill_quota, config.message_backlog);
 
             // Create network
             let p2p = network.start();
@@ -310,8 +505,34 @@ fn main_result() -> Result<()> {
             // Create indexer
             let indexer = Client::new(&config.indexer, identity)
                 .context("Failed to create indexer client")?;
+            let indexer = ReloadableClient::new(indexer);
+            let backend = match &config.message_bus {
+                Some(url) => {
+                    let nats = bus::Nats::connect(url)
+                        .await
+                        .context("Failed to connect to message bus")?;
+                    info!(%url, prefix = %config.message_bus_prefix, "publishing to message bus");
+                    let topics = bus::Topics::new(
+                        config.message_bus_prefix.clone(),
+                        config.message_bus_partitions as u64,
+                    );
+                    Backend::Nats(bus::BusIndexer::new(nats, topics))
+                }
+                None => Backend::Http(indexer.clone()),
+            };
+
+            // Create event exporter
+            let exporter = config.event_export.clone().map(|target| {
+                info!(%target, "exporting events");
+                let (exporter, mailbox) =
+                    export::Exporter::new(context.with_label("export"), target);
+                exporter.start();
+                mailbox
+            });
 
             // Create engine
+            let rpc_port = config.rpc_port;
+            let participants = peers.clone();
             let config = engine::Config {
                 blocker: oracle,
                 partition_prefix: "engine".to_string(),
@@ -321,6 +542,7 @@ fn main_result() -> Result<()> {
                 polynomial: config.polynomial,
                 share: config.share,
                 participants: peers,
+                rotations,
                 mailbox_size: config.mailbox_size,
                 deque_size: config.deque_size,
                 backfill_quota,
@@ -336,14 +558,69 @@ fn main_result() -> Result<()> {
                 fetch_rate_per_peer: resolver_limit,
                 buffer_pool_page_size: BUFFER_POOL_PAGE_SIZE,
                 buffer_pool_capacity: BUFFER_POOL_CAPACITY,
-                indexer,
+                indexer: backend,
                 execution_concurrency: config.execution_concurrency,
-                max_uploads_outstanding: MAX_UPLOADS_OUTSTANDING,
+                max_uploads_outstanding: config.max_uploads_outstanding,
+                max_block_txs: config.max_block_txs,
+                max_block_bytes: config.max_block_bytes,
+                mempool_tx_ttl: config.mempool_tx_ttl,
                 mempool_max_backlog: config.mempool_max_backlog,
                 mempool_max_transactions: config.mempool_max_transactions,
+                archive: config.archive,
+                exporter,
             };
             let engine = engine::Engine::new(context.with_label("engine"), config).await;
 
+            // Watch for config changes
+            context.with_label("config").spawn({
+                let tunables = engine.tunables();
+                let log_handle = log_handle.clone();
+                move |context| {
+                    watch_config(
+                        context,
+                        config_path,
+                        reloadable,
+                        log_handle,
+                        tunables,
+                        indexer,
+                        identity,
+                    )
+                }
+            });
+
+            // Serve the local RPC
+            if let Some(port) = rpc_port {
+                context.with_label("rpc").spawn({
+                    let set_log_level: rpc::SetLogLevel = Arc::new(move |level| {
+                        log_handle
+                            .modify(|filter| *filter = LevelFilter::from_level(level))
+                            .map_err(|e| e.to_string())
+                    });
+                    let handler = rpc::Handler::new(
+                        context.with_label("rpc_handler"),
+                        engine.application(),
+                        engine.aggregator(),
+                        engine.scorer(),
+                        participants,
+                        set_log_level,
+                    );
+                    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
+                    move |context| rpc::serve(context, addr, handler)
+                });
+            }
+
+            // Start rotation
+            let rotator = rotator.start(engine.supervisor(), registry, rotation_network);
+
+            // Serve light peers
+            let light = light::Server::new(
+                context.with_label("light"),
+                engine.application(),
+                engine.aggregator(),
+                engine.seeder(),
+            )
+            .start(light_network);
+
             // Start engine
             let engine = engine.start(
                 pending,
@@ -357,7 +634,7 @@ fn main_result() -> Result<()> {
             );
 
             // Wait for any task to error
-            if let Err(e) = try_join_all(vec![p2p, engine]).await {
+            if let Err(e) = try_join_all(vec![p2p, engine, rotator, light]).await {
                 error!(?e, "task failed");
             }
             Ok(())
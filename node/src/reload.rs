@@ -0,0 +1,222 @@
+use crate::{Config, ConfigError};
+use std::{
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use tracing::Level;
+
+/// Settings that can be adjusted while the node is running.
+///
+/// Actors read these on each use (rather than copying them at startup) so that
+/// reloaded values take effect without a restart.
+#[derive(Clone)]
+pub struct Tunables {
+    mempool_max_backlog: Arc<AtomicUsize>,
+    mempool_max_transactions: Arc<AtomicUsize>,
+    max_uploads_outstanding: Arc<AtomicUsize>,
+}
+
+impl Tunables {
+    pub fn new(
+        mempool_max_backlog: usize,
+        mempool_max_transactions: usize,
+        max_uploads_outstanding: usize,
+    ) -> Self {
+        Self {
+            mempool_max_backlog: Arc::new(AtomicUsize::new(mempool_max_backlog)),
+            mempool_max_transactions: Arc::new(AtomicUsize::new(mempool_max_transactions)),
+            max_uploads_outstanding: Arc::new(AtomicUsize::new(max_uploads_outstanding)),
+        }
+    }
+
+    pub fn mempool_max_backlog(&self) -> usize {
+        self.mempool_max_backlog.load(Ordering::Relaxed)
+    }
+
+    pub fn set_mempool_max_backlog(&self, value: usize) {
+        self.mempool_max_backlog.store(value, Ordering::Relaxed);
+    }
+
+    pub fn mempool_max_transactions(&self) -> usize {
+        self.mempool_max_transactions.load(Ordering::Relaxed)
+    }
+
+    pub fn set_mempool_max_transactions(&self, value: usize) {
+        self.mempool_max_transactions
+            .store(value, Ordering::Relaxed);
+    }
+
+    pub fn max_uploads_outstanding(&self) -> usize {
+        self.max_uploads_outstanding.load(Ordering::Relaxed)
+    }
+
+    pub fn set_max_uploads_outstanding(&self, value: usize) {
+        self.max_uploads_outstanding.store(value, Ordering::Relaxed);
+    }
+}
+
+/// Safe-to-change fields that differ between two configurations.
+///
+/// All other fields (keys, ports, peers, storage) require a restart and are ignored.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Changes {
+    pub log_level: Option<Level>,
+    pub mempool_max_backlog: Option<usize>,
+    pub mempool_max_transactions: Option<usize>,
+    pub max_uploads_outstanding: Option<usize>,
+    pub indexer: Option<String>,
+}
+
+impl Changes {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Compute (and validate) the reloadable changes from `current` to `next`.
+    pub fn between(current: &Config, next: &Config) -> Result<Self, ConfigError> {
+        let mut changes = Self::default();
+        if next.log_level != current.log_level {
+            let level =
+                Level::from_str(&next.log_level).map_err(|_| ConfigError::InvalidLogLevel {
+                    value: next.log_level.clone(),
+                })?;
+            changes.log_level = Some(level);
+        }
+        if next.mempool_max_backlog != current.mempool_max_backlog {
+            changes.mempool_max_backlog =
+                Some(non_zero("mempool_max_backlog", next.mempool_max_backlog)?);
+        }
+        if next.mempool_max_transactions != current.mempool_max_transactions {
+            changes.mempool_max_transactions = Some(non_zero(
+                "mempool_max_transactions",
+                next.mempool_max_transactions,
+            )?);
+        }
+        if next.max_uploads_outstanding != current.max_uploads_outstanding {
+            changes.max_uploads_outstanding = Some(non_zero(
+                "max_uploads_outstanding",
+                next.max_uploads_outstanding,
+            )?);
+        }
+        if next.indexer != current.indexer {
+            changes.indexer = Some(next.indexer.clone());
+        }
+        Ok(changes)
+    }
+
+    /// Apply the changed limits to the running node.
+    pub fn apply(&self, tunables: &Tunables) {
+        if let Some(value) = self.mempool_max_backlog {
+            tunables.set_mempool_max_backlog(value);
+        }
+        if let Some(value) = self.mempool_max_transactions {
+            tunables.set_mempool_max_transactions(value);
+        }
+        if let Some(value) = self.max_uploads_outstanding {
+            tunables.set_max_uploads_outstanding(value);
+        }
+    }
+}
+
+fn non_zero(field: &'static str, value: usize) -> Result<usize, ConfigError> {
+    if value == 0 {
+        return Err(ConfigError::InvalidNonZero { field, value });
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        serde_yaml::from_str(
+            r#"
+private_key: "00"
+share: "00"
+polynomial: "00"
+port: 3000
+metrics_port: 9090
+directory: "/tmp/node"
+worker_threads: 2
+log_level: "info"
+allowed_peers: []
+bootstrappers: []
+message_backlog: 128
+mailbox_size: 128
+deque_size: 10
+indexer: "http://localhost:8080"
+execution_concurrency: 2
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_no_changes() {
+        let changes = Changes::between(&config(), &config()).unwrap();
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_reloadable_changes() {
+        let current = config();
+        let mut next = config();
+        next.log_level = "debug".to_string();
+        next.mempool_max_backlog = 8;
+        next.max_uploads_outstanding = 16;
+        next.indexer = "http://localhost:9090".to_string();
+
+        // Fields that require a restart are ignored
+        next.port = 4000;
+
+        let changes = Changes::between(&current, &next).unwrap();
+        assert_eq!(
+            changes,
+            Changes {
+                log_level: Some(Level::DEBUG),
+                mempool_max_backlog: Some(8),
+                mempool_max_transactions: None,
+                max_uploads_outstanding: Some(16),
+                indexer: Some("http://localhost:9090".to_string()),
+            }
+        );
+
+        let tunables = Tunables::new(
+            current.mempool_max_backlog,
+            current.mempool_max_transactions,
+            current.max_uploads_outstanding,
+        );
+        changes.apply(&tunables);
+        assert_eq!(tunables.mempool_max_backlog(), 8);
+        assert_eq!(
+            tunables.mempool_max_transactions(),
+            current.mempool_max_transactions
+        );
+        assert_eq!(tunables.max_uploads_outstanding(), 16);
+    }
+
+    #[test]
+    fn test_invalid_changes() {
+        let current = config();
+        let mut next = config();
+        next.mempool_max_transactions = 0;
+        assert!(matches!(
+            Changes::between(&current, &next),
+            Err(ConfigError::InvalidNonZero {
+                field: "mempool_max_transactions",
+                ..
+            })
+        ));
+
+        let mut next = config();
+        next.log_level = "loud".to_string();
+        assert!(matches!(
+            Changes::between(&current, &next),
+            Err(ConfigError::InvalidLogLevel { .. })
+        ));
+    }
+}
@@ -0,0 +1,233 @@
+use super::{Registry, Rotation, Wire, MAX_ROTATIONS};
+use crate::supervisor::ViewSupervisor;
+use commonware_codec::{DecodeExt, Encode, RangeCfg};
+use commonware_consensus::Supervisor as _;
+use commonware_cryptography::{
+    ed25519::{PrivateKey, PublicKey},
+    Signer,
+};
+use commonware_macros::select;
+use commonware_p2p::{Receiver, Recipients, Sender};
+use commonware_resolver::p2p::Coordinator;
+use commonware_runtime::{Clock, Handle, Metrics, Spawner, Storage};
+use commonware_storage::metadata::{self, Metadata};
+use commonware_utils::sequence::U64;
+use prometheus_client::metrics::{counter::Counter, gauge::Gauge};
+use std::{collections::HashSet, sync::atomic::AtomicU64, time::Duration};
+use tracing::{debug, info, warn};
+
+/// How often to announce a pending rotation to peers that have not acknowledged it.
+const ANNOUNCE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Key under which all rotations are persisted.
+const ROTATIONS_KEY: u64 = 0;
+
+/// Configuration for the [Actor].
+pub struct Config {
+    /// The partition to persist rotations in.
+    pub partition: String,
+
+    /// The key the node is currently running with.
+    pub signer: PrivateKey,
+
+    /// The key to rotate to (if any).
+    pub next: Option<PrivateKey>,
+}
+
+/// Announces this node's key rotation and applies the rotations announced by peers.
+pub struct Actor<E: Clock + Spawner + Storage + Metrics> {
+    context: E,
+    public_key: PublicKey,
+    pending: Option<Rotation>,
+    store: Metadata<E, U64, Vec<Rotation>>,
+    rotations: Vec<Rotation>,
+
+    rotations_applied: Counter<u64, AtomicU64>,
+    rotation_acknowledged: Gauge,
+}
+
+impl<E: Clock + Spawner + Storage + Metrics> Actor<E> {
+    /// Initialize the actor (loading all persisted rotations).
+    pub async fn init(context: E, config: Config) -> Self {
+        let mut store = Metadata::init(
+            context.with_label("store"),
+            metadata::Config {
+                partition: config.partition,
+                codec_config: (RangeCfg::from(0..=MAX_ROTATIONS), ()),
+            },
+        )
+        .await
+        .expect("failed to initialize rotation store");
+        let mut rotations: Vec<Rotation> = store
+            .get(&ROTATIONS_KEY.into())
+            .cloned()
+            .unwrap_or_default();
+
+        // Persist our own rotation (so we can apply it once restarted with the new key)
+        let public_key = config.signer.public_key();
+        let pending = config
+            .next
+            .map(|next| Rotation::sign(&config.signer, &next));
+        if let Some(rotation) = &pending {
+            if !rotations.contains(rotation) {
+                rotations.retain(|r| r.old != rotation.old);
+                rotations.push(rotation.clone());
+                store.put(ROTATIONS_KEY.into(), rotations.clone());
+                store.sync().await.expect("failed to sync rotation store");
+            }
+            info!(old = ?rotation.old, new = ?rotation.new, "rotating key");
+        }
+
+        // Create metrics
+        let rotations_applied = Counter::default();
+        context.register(
+            "rotations_applied",
+            "Number of peer key rotations applied",
+            rotations_applied.clone(),
+        );
+        let rotation_acknowledged = Gauge::default();
+        context.register(
+            "rotation_acknowledged",
+            "Whether all peers have acknowledged our pending key rotation",
+            rotation_acknowledged.clone(),
+        );
+
+        Self {
+            context,
+            public_key,
+            pending,
+            store,
+            rotations,
+            rotations_applied,
+            rotation_acknowledged,
+        }
+    }
+
+    /// Rotations to apply to the participant set.
+    ///
+    /// If we are still running with a key we are rotating away from, our own rotation is
+    /// excluded (it is only applied once we restart with the new key).
+    pub fn rotations(&self) -> Vec<Rotation> {
+        self.rotations
+            .iter()
+            .filter(|rotation| rotation.old != self.public_key)
+            .cloned()
+            .collect()
+    }
+
+    pub fn start<R: Registry>(
+        mut self,
+        supervisor: ViewSupervisor,
+        registry: R,
+        network: (
+            impl Sender<PublicKey = PublicKey>,
+            impl Receiver<PublicKey = PublicKey>,
+        ),
+    ) -> Handle<()> {
+        self.context.spawn_ref()(self.run(supervisor, registry, network))
+    }
+
+    async fn run<R: Registry>(
+        mut self,
+        supervisor: ViewSupervisor,
+        mut registry: R,
+        (mut sender, mut receiver): (
+            impl Sender<PublicKey = PublicKey>,
+            impl Receiver<PublicKey = PublicKey>,
+        ),
+    ) {
+        let mut acknowledged = HashSet::new();
+        let mut next_announce = self.context.current();
+        loop {
+            select! {
+                _ = self.context.sleep_until(next_announce) => {
+                    next_announce = self.context.current() + ANNOUNCE_INTERVAL;
+                    let Some(rotation) = &self.pending else {
+                        continue;
+                    };
+
+                    // Announce to all peers that haven't acknowledged the rotation
+                    let remaining: Vec<_> = supervisor
+                        .peers()
+                        .iter()
+                        .filter(|peer| **peer != rotation.old && !acknowledged.contains(*peer))
+                        .cloned()
+                        .collect();
+                    if remaining.is_empty() {
+                        continue;
+                    }
+                    debug!(remaining = remaining.len(), "announcing key rotation");
+                    let message = Wire::Announce(rotation.clone()).encode().into();
+                    let _ = sender.send(Recipients::Some(remaining), message, false).await;
+                },
+                message = receiver.recv() => {
+                    let Ok((peer, message)) = message else {
+                        warn!("rotation receiver closed");
+                        return;
+                    };
+                    let Ok(message) = Wire::decode(message) else {
+                        debug!(?peer, "failed to decode rotation message");
+                        continue;
+                    };
+                    match message {
+                        Wire::Announce(rotation) => {
+                            // Only the old key can announce a rotation
+                            if rotation.old != peer || !rotation.verify() {
+                                debug!(?peer, "invalid key rotation");
+                                continue;
+                            }
+
+                            // Apply the rotation (if we haven't already)
+                            match self.rotations.iter().find(|r| r.old == rotation.old) {
+                                Some(existing) if *existing != rotation => {
+                                    warn!(?peer, "ignoring conflicting key rotation");
+                                    continue;
+                                }
+                                Some(_) => {}
+                                None => {
+                                    if supervisor.is_participant(0, &rotation.old).is_none() {
+                                        debug!(?peer, "ignoring key rotation from non-participant");
+                                        continue;
+                                    }
+                                    if self.rotations.len() >= MAX_ROTATIONS {
+                                        warn!(?peer, "too many key rotations");
+                                        continue;
+                                    }
+                                    self.rotations.push(rotation.clone());
+                                    self.store.put(ROTATIONS_KEY.into(), self.rotations.clone());
+                                    self.store.sync().await.expect("failed to sync rotation store");
+                                    supervisor.rotate(&rotation.old, rotation.new.clone());
+                                    registry.register(rotation.new.clone()).await;
+                                    self.rotations_applied.inc();
+                                    info!(old = ?rotation.old, new = ?rotation.new, "applied key rotation");
+                                }
+                            }
+
+                            // Acknowledge the rotation
+                            let message = Wire::Ack(rotation.new).encode().into();
+                            let _ = sender.send(Recipients::One(peer), message, false).await;
+                        }
+                        Wire::Ack(new) => {
+                            let Some(rotation) = &self.pending else {
+                                continue;
+                            };
+                            if rotation.new != new || !acknowledged.insert(peer.clone()) {
+                                continue;
+                            }
+                            let remaining = supervisor
+                                .peers()
+                                .iter()
+                                .filter(|peer| **peer != rotation.old && !acknowledged.contains(*peer))
+                                .count();
+                            debug!(?peer, remaining, "key rotation acknowledged");
+                            if remaining == 0 {
+                                self.rotation_acknowledged.set(1);
+                                info!(new = ?rotation.new, "key rotation acknowledged by all peers (restart with the new key)");
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Rotation of a validator's network (ed25519) key.
+//!
+//! A validator rotating its key sets `next_private_key` in its config and keeps running
+//! with its current key. The [Actor] announces a [Rotation] (signed by both keys) to all
+//! peers, which persist it, accept the new key as an alias for the old one, and acknowledge
+//! it. Once every peer has acknowledged, the validator can be restarted with the new key.
+//!
+//! On restart, persisted rotations replace the old key in the participant set (so the old
+//! key is no longer accepted). The position of each participant (and so its share of the
+//! group polynomial) is always derived from its original key.
+
+use bytes::{Buf, BufMut};
+use commonware_codec::{EncodeSize, Error, FixedSize, Read, ReadExt, Write};
+use commonware_cryptography::{
+    ed25519::{PrivateKey, PublicKey, Signature},
+    Signer, Verifier,
+};
+use std::future::Future;
+
+mod actor;
+pub use actor::{Actor, Config};
+
+/// Namespace for signatures over a [Rotation].
+const ROTATION_NAMESPACE: &[u8] = b"_SUPERSOCIETY_ROTATION";
+
+/// The maximum number of rotations persisted by a node.
+const MAX_ROTATIONS: usize = 1024;
+
+/// Allows additional keys to connect over p2p.
+pub trait Registry: Clone + Send + 'static {
+    /// Allow `peer` to connect (in addition to all existing peers).
+    fn register(&mut self, peer: PublicKey) -> impl Future<Output = ()> + Send;
+}
+
+/// A validator's announcement that it is replacing its key.
+///
+/// Signed by both the old and the new key (proving possession of both).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rotation {
+    pub old: PublicKey,
+    pub new: PublicKey,
+    old_signature: Signature,
+    new_signature: Signature,
+}
+
+impl Rotation {
+    fn payload(old: &PublicKey, new: &PublicKey) -> Vec<u8> {
+        let mut payload = Vec::with_capacity(PublicKey::SIZE * 2);
+        old.write(&mut payload);
+        new.write(&mut payload);
+        payload
+    }
+
+    pub fn sign(old: &PrivateKey, new: &PrivateKey) -> Self {
+        let (old_public, new_public) = (old.public_key(), new.public_key());
+        let payload = Self::payload(&old_public, &new_public);
+        Self {
+            old_signature: old.sign(Some(ROTATION_NAMESPACE), &payload),
+            new_signature: new.sign(Some(ROTATION_NAMESPACE), &payload),
+            old: old_public,
+            new: new_public,
+        }
+    }
+
+    pub fn verify(&self) -> bool {
+        if self.old == self.new {
+            return false;
+        }
+        let payload = Self::payload(&self.old, &self.new);
+        self.old
+            .verify(Some(ROTATION_NAMESPACE), &payload, &self.old_signature)
+            && self
+                .new
+                .verify(Some(ROTATION_NAMESPACE), &payload, &self.new_signature)
+    }
+}
+
+impl Write for Rotation {
+    fn write(&self, buf: &mut impl BufMut) {
+        self.old.write(buf);
+        self.new.write(buf);
+        self.old_signature.write(buf);
+        self.new_signature.write(buf);
+    }
+}
+
+impl Read for Rotation {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &()) -> Result<Self, Error> {
+        Ok(Self {
+            old: PublicKey::read(reader)?,
+            new: PublicKey::read(reader)?,
+            old_signature: Signature::read(reader)?,
+            new_signature: Signature::read(reader)?,
+        })
+    }
+}
+
+impl FixedSize for Rotation {
+    const SIZE: usize = PublicKey::SIZE * 2 + Signature::SIZE * 2;
+}
+
+/// Returns the current key of the participant originally identified by `key`.
+pub fn current(rotations: &[Rotation], key: &PublicKey) -> PublicKey {
+    let mut key = key.clone();
+    for _ in 0..rotations.len() {
+        let Some(rotation) = rotations.iter().find(|r| r.old == key) else {
+            break;
+        };
+        key = rotation.new.clone();
+    }
+    key
+}
+
+/// Returns the original key of the participant currently identified by `key`.
+pub fn original(rotations: &[Rotation], key: &PublicKey) -> PublicKey {
+    let mut key = key.clone();
+    for _ in 0..rotations.len() {
+        let Some(rotation) = rotations.iter().find(|r| r.new == key) else {
+            break;
+        };
+        key = rotation.old.clone();
+    }
+    key
+}
+
+/// Messages exchanged by the [Actor].
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Wire {
+    /// Announce a rotation (sent by the old key).
+    Announce(Rotation),
+    /// Acknowledge the rotation to the given key.
+    Ack(PublicKey),
+}
+
+impl Write for Wire {
+    fn write(&self, buf: &mut impl BufMut) {
+        match self {
+            Wire::Announce(rotation) => {
+                0u8.write(buf);
+                rotation.write(buf);
+            }
+            Wire::Ack(new) => {
+                1u8.write(buf);
+                new.write(buf);
+            }
+        }
+    }
+}
+
+impl Read for Wire {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &()) -> Result<Self, Error> {
+        match u8::read(reader)? {
+            0 => Ok(Wire::Announce(Rotation::read(reader)?)),
+            1 => Ok(Wire::Ack(PublicKey::read(reader)?)),
+            i => Err(Error::InvalidEnum(i)),
+        }
+    }
+}
+
+impl EncodeSize for Wire {
+    fn encode_size(&self) -> usize {
+        u8::SIZE
+            + match self {
+                Wire::Announce(_) => Rotation::SIZE,
+                Wire::Ack(_) => PublicKey::SIZE,
+            }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commonware_codec::{DecodeExt, Encode};
+    use commonware_cryptography::PrivateKeyExt;
+
+    #[test]
+    fn test_rotation_verify() {
+        let (old, new) = (PrivateKey::from_seed(0), PrivateKey::from_seed(1));
+        let rotation = Rotation::sign(&old, &new);
+        assert!(rotation.verify());
+        assert_eq!(Rotation::decode(rotation.encode()).unwrap(), rotation);
+
+        // Both keys must sign
+        let other = PrivateKey::from_seed(2);
+        let mut forged = Rotation::sign(&old, &other);
+        forged.new = new.public_key();
+        assert!(!forged.verify());
+
+        // Rotating to the same key is not allowed
+        assert!(!Rotation::sign(&old, &old).verify());
+    }
+
+    #[test]
+    fn test_wire_codec() {
+        let rotation = Rotation::sign(&PrivateKey::from_seed(0), &PrivateKey::from_seed(1));
+        for wire in [Wire::Announce(rotation.clone()), Wire::Ack(rotation.new)] {
+            assert_eq!(Wire::decode(wire.encode()).unwrap(), wire);
+        }
+    }
+
+    #[test]
+    fn test_current_and_original() {
+        let keys: Vec<_> = (0..3).map(PrivateKey::from_seed).collect();
+        let rotations = vec![
+            Rotation::sign(&keys[1], &keys[2]),
+            Rotation::sign(&keys[0], &keys[1]),
+        ];
+        let public: Vec<_> = keys.iter().map(|k| k.public_key()).collect();
+        assert_eq!(current(&rotations, &public[0]), public[2]);
+        assert_eq!(original(&rotations, &public[2]), public[0]);
+
+        let unrelated = PrivateKey::from_seed(3).public_key();
+        assert_eq!(current(&rotations, &unrelated), unrelated);
+        assert_eq!(original(&rotations, &unrelated), unrelated);
+    }
+}
@@ -0,0 +1,322 @@
+//! Local RPC for inspecting (and debugging) a running node.
+//!
+//! Each connection sends a single newline-terminated command and receives a
+//! newline-separated response, after which the connection is closed. The server
+//! should only be bound to a loopback address.
+//!
+//! Supported commands:
+//! - `mempool-log tx <digest>` / `mempool-log account <public key>`
+//! - `dump-mempool`
+//! - `dump-peers`
+//! - `force-backfill <height>`
+//! - `set-log-level <level>`
+//! - `state-at <key digest> <height>`
+//! - `upload-status`
+
+use crate::{
+    aggregator,
+    application::{self, Filter},
+    scorer::Scorer,
+};
+use commonware_codec::DecodeExt;
+use commonware_cryptography::{ed25519::PublicKey, sha256::Digest, Digestible};
+use commonware_runtime::{Clock, Listener, Network, Sink, Spawner, Stream};
+use commonware_storage::store::operation::Variable;
+use commonware_utils::from_hex_formatted;
+use nullspace_types::execution::Value;
+use std::{
+    net::SocketAddr,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::UNIX_EPOCH,
+};
+use tracing::{debug, error, info, Level};
+
+/// Maximum length of a single command.
+const MAX_COMMAND_LENGTH: usize = 1024;
+
+/// Changes the log level of the running node.
+pub type SetLogLevel = Arc<dyn Fn(Level) -> Result<(), String> + Send + Sync>;
+
+/// A request made over the local RPC.
+#[derive(Clone, Debug)]
+pub enum Command {
+    /// Return the mempool admission log for a transaction (by digest) or account.
+    MempoolLog(Filter),
+    /// Return all transactions in the mempool.
+    DumpMempool,
+    /// Return the standing of all participants.
+    DumpPeers,
+    /// Fetch the certificate at some height (if missing) or upload its summary again.
+    ForceBackfill(u64),
+    /// Change the log level.
+    SetLogLevel(Level),
+    /// Return the value of a key (by the digest of its encoding) as of some height.
+    StateAt(Digest, u64),
+    /// Return the progress of summary uploads.
+    UploadStatus,
+}
+
+impl Command {
+    /// Parse a command from a single line.
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        match parts.as_slice() {
+            ["mempool-log", "tx", digest] => {
+                let digest = decode_hex::<Digest>(digest)?;
+                Ok(Command::MempoolLog(Filter::Transaction(digest)))
+            }
+            ["mempool-log", "account", public] => {
+                let public = decode_hex::<PublicKey>(public)?;
+                Ok(Command::MempoolLog(Filter::Account(public)))
+            }
+            ["dump-mempool"] => Ok(Command::DumpMempool),
+            ["dump-peers"] => Ok(Command::DumpPeers),
+            ["force-backfill", height] => {
+                let height = height
+                    .parse()
+                    .map_err(|_| format!("invalid height: {height}"))?;
+                Ok(Command::ForceBackfill(height))
+            }
+            ["set-log-level", level] => {
+                let level =
+                    Level::from_str(level).map_err(|_| format!("invalid log level: {level}"))?;
+                Ok(Command::SetLogLevel(level))
+            }
+            ["state-at", key, height] => {
+                let key = decode_hex::<Digest>(key)?;
+                let height = height
+                    .parse()
+                    .map_err(|_| format!("invalid height: {height}"))?;
+                Ok(Command::StateAt(key, height))
+            }
+            ["upload-status"] => Ok(Command::UploadStatus),
+            _ => Err(format!("unknown command: {line}")),
+        }
+    }
+}
+
+fn decode_hex<T: DecodeExt<()>>(value: &str) -> Result<T, String> {
+    let bytes = from_hex_formatted(value).ok_or_else(|| format!("invalid hex: {value}"))?;
+    T::decode(bytes.as_ref()).map_err(|e| format!("invalid value {value}: {e}"))
+}
+
+/// Handles commands received over the local RPC.
+#[derive(Clone)]
+pub struct Handler<A: Clock> {
+    context: A,
+    application: application::Mailbox<A>,
+    aggregator: aggregator::Mailbox,
+    scorer: Arc<Mutex<Scorer>>,
+    participants: Vec<PublicKey>,
+    set_log_level: SetLogLevel,
+}
+
+impl<A: Clock> Handler<A> {
+    pub fn new(
+        context: A,
+        application: application::Mailbox<A>,
+        aggregator: aggregator::Mailbox,
+        scorer: Arc<Mutex<Scorer>>,
+        participants: Vec<PublicKey>,
+        set_log_level: SetLogLevel,
+    ) -> Self {
+        Self {
+            context,
+            application,
+            aggregator,
+            scorer,
+            participants,
+            set_log_level,
+        }
+    }
+
+    /// Execute a command, returning the response lines.
+    pub async fn execute(&mut self, command: Command) -> Vec<String> {
+        match command {
+            Command::MempoolLog(filter) => self
+                .application
+                .admissions(filter)
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            Command::DumpMempool => self
+                .application
+                .mempool()
+                .await
+                .iter()
+                .map(|tx| format!("{} {} nonce={}", tx.digest(), tx.public, tx.nonce))
+                .collect(),
+            Command::DumpPeers => {
+                let now = self.context.current();
+                let scorer = self.scorer.lock().unwrap();
+                self.participants
+                    .iter()
+                    .map(|peer| {
+                        let standing = scorer.standing(peer, now);
+                        let banned_until = standing.banned_until.map_or_else(
+                            || "-".to_string(),
+                            |until| {
+                                let millis = until.duration_since(UNIX_EPOCH).unwrap_or_default();
+                                millis.as_millis().to_string()
+                            },
+                        );
+                        format!(
+                            "{peer} score={} bans={} banned_until={banned_until}",
+                            standing.score, standing.bans
+                        )
+                    })
+                    .collect()
+            }
+            Command::ForceBackfill(height) => {
+                vec![self.aggregator.backfill(height).await.to_string()]
+            }
+            Command::SetLogLevel(level) => match (self.set_log_level)(level) {
+                Ok(()) => {
+                    info!(%level, "updated log level");
+                    vec!["ok".to_string()]
+                }
+                Err(e) => vec![format!("error: {e}")],
+            },
+            Command::StateAt(key, height) => match self.state_at(key, height).await {
+                Ok(Some((location, value))) => vec![format!("location={location} {value:?}")],
+                Ok(None) => vec!["not found".to_string()],
+                Err(e) => vec![format!("error: {e}")],
+            },
+            Command::UploadStatus => vec![self.aggregator.upload_status().await.to_string()],
+        }
+    }
+
+    /// Get the value of `key` (by the digest of its encoding) as of `height`, returning the
+    /// location of the operation that set it.
+    ///
+    /// Values that were overwritten after `height` are only available on archive nodes.
+    pub async fn state_at(
+        &mut self,
+        key: Digest,
+        height: u64,
+    ) -> Result<Option<(u64, Value)>, String> {
+        let progress = self
+            .aggregator
+            .progress(height)
+            .await
+            .ok_or_else(|| format!("height {height} has not been executed"))?;
+        match self.application.state_at(key, progress.state_end_op).await {
+            Some((location, Variable::Update(_, value))) => Ok(Some((location, value))),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Read a single newline-terminated line from `stream`.
+async fn read_line(stream: &mut impl Stream) -> Result<String, String> {
+    let mut line = Vec::new();
+    loop {
+        let buf = stream
+            .recv(vec![0u8; 1])
+            .await
+            .map_err(|e| format!("failed to read command: {e}"))?;
+        match buf.as_ref()[0] {
+            b'\n' => break,
+            byte => line.push(byte),
+        }
+        if line.len() > MAX_COMMAND_LENGTH {
+            return Err("command too long".to_string());
+        }
+    }
+    String::from_utf8(line).map_err(|_| "command is not UTF-8".to_string())
+}
+
+/// Serve the local RPC on `addr` until the listener fails.
+pub async fn serve<E: Network + Spawner, A: Clock>(
+    context: E,
+    addr: SocketAddr,
+    handler: Handler<A>,
+) {
+    let mut listener = match context.bind(addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            error!(?e, ?addr, "failed to bind rpc");
+            return;
+        }
+    };
+    loop {
+        let (peer, mut sink, mut stream) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                error!(?e, "failed to accept rpc connection");
+                return;
+            }
+        };
+        context.with_label("connection").spawn({
+            let mut handler = handler.clone();
+            move |_| async move {
+                let response = match read_line(&mut stream)
+                    .await
+                    .and_then(|line| Command::parse(&line))
+                {
+                    Ok(command) => {
+                        debug!(?peer, ?command, "rpc command");
+                        handler.execute(command).await
+                    }
+                    Err(e) => vec![format!("error: {e}")],
+                };
+                let mut body = response.join("\n");
+                body.push('\n');
+                let _ = sink.send(body.into_bytes()).await;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commonware_codec::Encode;
+    use commonware_cryptography::{ed25519::PrivateKey, Hasher, PrivateKeyExt, Sha256, Signer};
+    use commonware_utils::hex;
+
+    #[test]
+    fn test_parse() {
+        let digest = Sha256::hash(b"tx");
+        let command = Command::parse(&format!("mempool-log tx {}", hex(&digest.encode()))).unwrap();
+        assert!(matches!(command, Command::MempoolLog(Filter::Transaction(d)) if d == digest));
+
+        let public = PrivateKey::from_seed(0).public_key();
+        let command =
+            Command::parse(&format!("mempool-log account {}", hex(&public.encode()))).unwrap();
+        assert!(matches!(command, Command::MempoolLog(Filter::Account(p)) if p == public));
+
+        assert!(matches!(
+            Command::parse("dump-mempool"),
+            Ok(Command::DumpMempool)
+        ));
+        assert!(matches!(
+            Command::parse("dump-peers"),
+            Ok(Command::DumpPeers)
+        ));
+        assert!(matches!(
+            Command::parse("force-backfill 42"),
+            Ok(Command::ForceBackfill(42))
+        ));
+        assert!(matches!(
+            Command::parse("set-log-level debug"),
+            Ok(Command::SetLogLevel(Level::DEBUG))
+        ));
+        assert!(matches!(
+            Command::parse(&format!("state-at {} 7", hex(&digest.encode()))),
+            Ok(Command::StateAt(d, 7)) if d == digest
+        ));
+        assert!(matches!(
+            Command::parse("upload-status"),
+            Ok(Command::UploadStatus)
+        ));
+
+        assert!(Command::parse("mempool-log tx zz").is_err());
+        assert!(Command::parse("mempool-log").is_err());
+        assert!(Command::parse("force-backfill tip").is_err());
+        assert!(Command::parse("set-log-level loud").is_err());
+        assert!(Command::parse("state-at zz 7").is_err());
+        assert!(Command::parse("unknown").is_err());
+    }
+}
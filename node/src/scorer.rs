@@ -0,0 +1,494 @@
+use commonware_cryptography::ed25519::PublicKey;
+use commonware_p2p::{Blocker, Message, Receiver};
+use commonware_runtime::Clock;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, warn};
+
+/// Score at which a peer is temporarily banned.
+const BAN_THRESHOLD: u64 = 100;
+
+/// Points forgiven per second of good behavior.
+const DECAY_PER_SECOND: u64 = 1;
+
+/// Duration of the first temporary ban (doubled for each subsequent ban).
+const BASE_BAN_DURATION: Duration = Duration::from_secs(60);
+
+/// Number of temporary bans after which a peer is blocked outright.
+const MAX_TEMPORARY_BANS: u32 = 3;
+
+/// Misbehavior that can be attributed to a peer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Offense {
+    /// The peer sent a message that could not be decoded.
+    DecodeFailure,
+    /// The peer sent a certificate (or seed) that failed verification.
+    InvalidCertificate,
+    /// The peer sent more backfill requests than allowed in a single window.
+    ExcessiveBackfill,
+}
+
+impl Offense {
+    fn penalty(self) -> u64 {
+        match self {
+            Offense::DecodeFailure => 20,
+            Offense::InvalidCertificate => 50,
+            Offense::ExcessiveBackfill => 10,
+        }
+    }
+}
+
+/// The outcome of reporting an [Offense].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The peer remains in good standing.
+    Allowed,
+    /// The peer is banned until the given time.
+    Banned(SystemTime),
+    /// The peer has been banned too many times and should be blocked.
+    Blocked,
+}
+
+/// A snapshot of a peer's standing with the [Scorer].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Standing {
+    /// Current (decayed) misbehavior score.
+    pub score: u64,
+    /// Number of temporary bans served.
+    pub bans: u32,
+    /// End of the current temporary ban (if any).
+    pub banned_until: Option<SystemTime>,
+}
+
+#[derive(Debug)]
+struct PeerScore {
+    score: u64,
+    updated: SystemTime,
+    bans: u32,
+    banned_until: Option<SystemTime>,
+    blocked: bool,
+}
+
+/// Tracks a decaying misbehavior score for each peer.
+#[derive(Debug, Default)]
+pub struct Scorer {
+    peers: HashMap<PublicKey, PeerScore>,
+}
+
+impl Scorer {
+    /// Create a new (empty) scorer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an offense committed by `peer`.
+    pub fn report(&mut self, peer: &PublicKey, offense: Offense, now: SystemTime) -> Verdict {
+        let entry = self.peers.entry(peer.clone()).or_insert(PeerScore {
+            score: 0,
+            updated: now,
+            bans: 0,
+            banned_until: None,
+            blocked: false,
+        });
+
+        // Apply decay since the last update
+        let elapsed = now
+            .duration_since(entry.updated)
+            .unwrap_or_default()
+            .as_secs();
+        entry.score = entry
+            .score
+            .saturating_sub(elapsed.saturating_mul(DECAY_PER_SECOND));
+        entry.updated = now;
+
+        // Apply penalty
+        entry.score = entry.score.saturating_add(offense.penalty());
+        if entry.score < BAN_THRESHOLD {
+            return Verdict::Allowed;
+        }
+
+        // Ban the peer (escalating the duration with each ban)
+        entry.score = 0;
+        entry.bans += 1;
+        if entry.bans > MAX_TEMPORARY_BANS {
+            entry.blocked = true;
+            warn!(?peer, ?offense, bans = entry.bans, "blocking peer");
+            return Verdict::Blocked;
+        }
+        let until = now + BASE_BAN_DURATION * 2u32.pow(entry.bans - 1);
+        entry.banned_until = Some(until);
+        warn!(
+            ?peer,
+            ?offense,
+            bans = entry.bans,
+            "temporarily banning peer"
+        );
+        Verdict::Banned(until)
+    }
+
+    /// Returns the current standing of `peer` (without applying any decay to the stored score).
+    pub fn standing(&self, peer: &PublicKey, now: SystemTime) -> Standing {
+        let Some(entry) = self.peers.get(peer) else {
+            return Standing::default();
+        };
+        let elapsed = now
+            .duration_since(entry.updated)
+            .unwrap_or_default()
+            .as_secs();
+        Standing {
+            score: entry
+                .score
+                .saturating_sub(elapsed.saturating_mul(DECAY_PER_SECOND)),
+            bans: entry.bans,
+            banned_until: entry.banned_until.filter(|until| now < *until),
+        }
+    }
+
+    /// Returns true if `peer` is currently serving a temporary ban (or has been blocked).
+    pub fn is_banned(&self, peer: &PublicKey, now: SystemTime) -> bool {
+        self.peers.get(peer).is_some_and(|entry| {
+            entry.blocked || entry.banned_until.is_some_and(|until| now < until)
+        })
+    }
+}
+
+/// Report an offense committed by `peer`, blocking it via `blocker` once it has been
+/// banned too many times.
+async fn report<B: Blocker<PublicKey = PublicKey>>(
+    scorer: &Mutex<Scorer>,
+    blocker: &mut B,
+    peer: PublicKey,
+    offense: Offense,
+    now: SystemTime,
+) -> Verdict {
+    let verdict = scorer.lock().unwrap().report(&peer, offense, now);
+    if verdict == Verdict::Blocked {
+        blocker.block(peer).await;
+    }
+    verdict
+}
+
+/// Reports offenses found in messages received through a [ScoredReceiver] (like a backfill
+/// response that fails to decode or verify).
+///
+/// Resolvers don't tell their consumer which peer sent a response, but they deliver each
+/// response before receiving the next message, so an offense is attributed to the sender of
+/// the last message received.
+#[derive(Clone)]
+pub struct PeerReporter<E: Clock, B: Blocker<PublicKey = PublicKey>> {
+    context: E,
+    blocker: B,
+    scorer: Arc<Mutex<Scorer>>,
+    last: Arc<Mutex<Option<PublicKey>>>,
+}
+
+impl<E: Clock, B: Blocker<PublicKey = PublicKey>> PeerReporter<E, B> {
+    /// Report an offense committed by the sender of the last message received.
+    pub async fn report(&mut self, offense: Offense) -> Verdict {
+        let Some(peer) = self.last.lock().unwrap().clone() else {
+            return Verdict::Allowed;
+        };
+        let now = self.context.current();
+        report(&self.scorer, &mut self.blocker, peer, offense, now).await
+    }
+}
+
+/// A [Receiver] that drops messages from banned peers and (if rate limited) reports peers
+/// that exceed the allowed request rate.
+///
+/// Peers that are banned too many times are blocked via the provided [Blocker].
+pub struct ScoredReceiver<E, R, B>
+where
+    E: Clock,
+    R: Receiver<PublicKey = PublicKey>,
+    B: Blocker<PublicKey = PublicKey>,
+{
+    context: E,
+    inner: R,
+    blocker: B,
+    scorer: Arc<Mutex<Scorer>>,
+    max_per_second: Option<u32>,
+    windows: HashMap<PublicKey, (u64, u32)>,
+    last: Arc<Mutex<Option<PublicKey>>>,
+}
+
+impl<E, R, B> ScoredReceiver<E, R, B>
+where
+    E: Clock,
+    R: Receiver<PublicKey = PublicKey>,
+    B: Blocker<PublicKey = PublicKey>,
+{
+    pub fn new(
+        context: E,
+        inner: R,
+        blocker: B,
+        scorer: Arc<Mutex<Scorer>>,
+        max_per_second: Option<u32>,
+    ) -> Self {
+        Self {
+            context,
+            inner,
+            blocker,
+            scorer,
+            max_per_second,
+            windows: HashMap::new(),
+            last: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Get a [PeerReporter] for offenses found in the messages received.
+    pub fn reporter(&self) -> PeerReporter<E, B> {
+        PeerReporter {
+            context: self.context.clone(),
+            blocker: self.blocker.clone(),
+            scorer: self.scorer.clone(),
+            last: self.last.clone(),
+        }
+    }
+}
+
+impl<E, R, B> fmt::Debug for ScoredReceiver<E, R, B>
+where
+    E: Clock,
+    R: Receiver<PublicKey = PublicKey>,
+    B: Blocker<PublicKey = PublicKey>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScoredReceiver")
+            .field("inner", &self.inner)
+            .field("max_per_second", &self.max_per_second)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E, R, B> Receiver for ScoredReceiver<E, R, B>
+where
+    E: Clock,
+    R: Receiver<PublicKey = PublicKey>,
+    B: Blocker<PublicKey = PublicKey>,
+{
+    type Error = R::Error;
+    type PublicKey = PublicKey;
+
+    async fn recv(&mut self) -> Result<Message<Self::PublicKey>, Self::Error> {
+        loop {
+            let (peer, message) = self.inner.recv().await?;
+            let now = self.context.current();
+
+            // Report the peer once per (one second) window if it exceeds the limit
+            if let Some(max_per_second) = self.max_per_second {
+                let second = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+                let window = self.windows.entry(peer.clone()).or_insert((second, 0));
+                if window.0 != second {
+                    *window = (second, 0);
+                }
+                window.1 += 1;
+                if window.1 == max_per_second + 1 {
+                    let offense = Offense::ExcessiveBackfill;
+                    report(&self.scorer, &mut self.blocker, peer.clone(), offense, now).await;
+                }
+            }
+            if self.scorer.lock().unwrap().is_banned(&peer, now) {
+                debug!(?peer, "dropping message from banned peer");
+                continue;
+            }
+            *self.last.lock().unwrap() = Some(peer.clone());
+            return Ok((peer, message));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use commonware_cryptography::{ed25519::PrivateKey, PrivateKeyExt, Signer};
+    use commonware_macros::select;
+    use commonware_p2p::{
+        simulated::{self, Link, Network},
+        Recipients, Sender,
+    };
+    use commonware_runtime::{deterministic, Metrics, Runner};
+
+    #[test]
+    fn test_ban_after_threshold() {
+        let peer = PrivateKey::from_seed(0).public_key();
+        let mut scorer = Scorer::new();
+        let now = UNIX_EPOCH;
+
+        assert_eq!(
+            scorer.report(&peer, Offense::InvalidCertificate, now),
+            Verdict::Allowed
+        );
+        assert!(!scorer.is_banned(&peer, now));
+        assert_eq!(
+            scorer.report(&peer, Offense::InvalidCertificate, now),
+            Verdict::Banned(now + BASE_BAN_DURATION)
+        );
+        assert!(scorer.is_banned(&peer, now));
+        assert!(!scorer.is_banned(&peer, now + BASE_BAN_DURATION));
+    }
+
+    #[test]
+    fn test_standing() {
+        let peer = PrivateKey::from_seed(0).public_key();
+        let mut scorer = Scorer::new();
+        let now = UNIX_EPOCH;
+        assert_eq!(scorer.standing(&peer, now), Standing::default());
+
+        scorer.report(&peer, Offense::InvalidCertificate, now);
+        let later = now + Duration::from_secs(10);
+        assert_eq!(
+            scorer.standing(&peer, later),
+            Standing {
+                score: 40,
+                bans: 0,
+                banned_until: None,
+            }
+        );
+
+        scorer.report(&peer, Offense::InvalidCertificate, now);
+        let standing = scorer.standing(&peer, later);
+        assert_eq!(standing.bans, 1);
+        assert_eq!(standing.banned_until, Some(now + BASE_BAN_DURATION));
+        assert_eq!(
+            scorer.standing(&peer, now + BASE_BAN_DURATION).banned_until,
+            None
+        );
+    }
+
+    #[test]
+    fn test_score_decays() {
+        let peer = PrivateKey::from_seed(0).public_key();
+        let mut scorer = Scorer::new();
+        let now = UNIX_EPOCH;
+
+        assert_eq!(
+            scorer.report(&peer, Offense::InvalidCertificate, now),
+            Verdict::Allowed
+        );
+
+        // After enough time has passed, the previous offense is forgiven
+        let later = now + Duration::from_secs(50);
+        assert_eq!(
+            scorer.report(&peer, Offense::InvalidCertificate, later),
+            Verdict::Allowed
+        );
+    }
+
+    #[test]
+    fn test_escalates_to_block() {
+        let peer = PrivateKey::from_seed(0).public_key();
+        let mut scorer = Scorer::new();
+        let now = UNIX_EPOCH;
+
+        let mut durations = Vec::new();
+        for _ in 0..MAX_TEMPORARY_BANS {
+            scorer.report(&peer, Offense::InvalidCertificate, now);
+            let Verdict::Banned(until) = scorer.report(&peer, Offense::InvalidCertificate, now)
+            else {
+                panic!("expected ban");
+            };
+            durations.push(until.duration_since(now).unwrap());
+        }
+        assert_eq!(
+            durations,
+            vec![
+                BASE_BAN_DURATION,
+                BASE_BAN_DURATION * 2,
+                BASE_BAN_DURATION * 4
+            ]
+        );
+
+        scorer.report(&peer, Offense::InvalidCertificate, now);
+        assert_eq!(
+            scorer.report(&peer, Offense::InvalidCertificate, now),
+            Verdict::Blocked
+        );
+    }
+
+    #[test]
+    fn test_bad_certificates_ban_peer() {
+        let executor = deterministic::Runner::default();
+        executor.start(|context| async move {
+            let (network, mut oracle) = Network::new(
+                context.with_label("network"),
+                simulated::Config {
+                    max_size: 1024 * 1024,
+                },
+            );
+            network.start();
+            let me = PrivateKey::from_seed(0).public_key();
+            let peer = PrivateKey::from_seed(1).public_key();
+            let (_, receiver) = oracle.register(me.clone(), 0).await.unwrap();
+            let (mut sender, _) = oracle.register(peer.clone(), 0).await.unwrap();
+            let link = Link {
+                latency: Duration::from_millis(10),
+                jitter: Duration::from_millis(1),
+                success_rate: 1.0,
+            };
+            oracle
+                .add_link(peer.clone(), me.clone(), link)
+                .await
+                .unwrap();
+
+            let scorer = Arc::new(Mutex::new(Scorer::new()));
+            let mut receiver = ScoredReceiver::new(
+                context.clone(),
+                receiver,
+                oracle.control(me.clone()),
+                scorer.clone(),
+                None,
+            );
+            let mut reporter = receiver.reporter();
+
+            // Each response that fails to verify is attributed to the peer that sent it...
+            for verdict in [
+                Verdict::Allowed,
+                Verdict::Banned(context.current() + BASE_BAN_DURATION),
+            ] {
+                sender
+                    .send(
+                        Recipients::One(me.clone()),
+                        Bytes::from_static(b"bad"),
+                        false,
+                    )
+                    .await
+                    .unwrap();
+                let (from, _) = receiver.recv().await.unwrap();
+                assert_eq!(from, peer);
+                assert_eq!(reporter.report(Offense::InvalidCertificate).await, verdict);
+            }
+            assert!(scorer.lock().unwrap().is_banned(&peer, context.current()));
+
+            // ...and its messages are dropped while it is banned
+            sender
+                .send(
+                    Recipients::One(me.clone()),
+                    Bytes::from_static(b"bad"),
+                    false,
+                )
+                .await
+                .unwrap();
+            select! {
+                _ = receiver.recv() => panic!("received message from banned peer"),
+                _ = context.sleep(Duration::from_secs(1)) => {},
+            }
+
+            // Peers banned too many times are blocked
+            for _ in 0..2 * MAX_TEMPORARY_BANS {
+                reporter.report(Offense::InvalidCertificate).await;
+            }
+            assert!(scorer
+                .lock()
+                .unwrap()
+                .is_banned(&peer, context.current() + Duration::from_secs(3600)));
+            let blocked = oracle.blocked().await.unwrap();
+            assert!(blocked.contains(&(me, peer)));
+        });
+    }
+}
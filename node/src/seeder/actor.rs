@@ -5,15 +5,16 @@ use std::{
 
 use crate::{
     indexer::Indexer,
+    scorer::{Offense, PeerReporter},
     seeder::{ingress::Mailbox, Config, Message},
 };
-use commonware_codec::{DecodeExt, Encode};
+use commonware_codec::{Decode, Encode, RangeCfg};
 use commonware_consensus::{threshold_simplex::types::View, Viewable};
 use commonware_cryptography::{
     bls12381::primitives::variant::{MinSig, Variant},
     ed25519::PublicKey,
 };
-use commonware_p2p::{Receiver, Sender};
+use commonware_p2p::{Blocker, Receiver, Sender};
 use commonware_resolver::{p2p, Resolver};
 use commonware_runtime::{Clock, Handle, Metrics, Spawner, Storage};
 use commonware_storage::{
@@ -31,10 +32,70 @@ use nullspace_types::Seed;
 use rand::RngCore;
 use tracing::{debug, info, warn};
 
+type Signature = <MinSig as Variant>::Signature;
+
 const BATCH_ENQUEUE: usize = 20;
+/// Maximum number of consecutive seeds served in response to a single backfill request.
+const SEEDS_PER_RANGE: u64 = 256;
 const LAST_UPLOADED_KEY: u64 = 0;
 const RETRY_DELAY: Duration = Duration::from_secs(10);
 
+/// Find the first missing view of each range that is not already covered by an outstanding
+/// request (marking it as requested).
+///
+/// Each request is answered with up to [SEEDS_PER_RANGE] consecutive seeds, so we only
+/// request the first missing view of each range.
+fn missing_ranges<E: Storage + Metrics + Clock>(
+    storage: &Ordinal<E, Signature>,
+    waiting: &mut BTreeSet<View>,
+) -> Vec<View> {
+    let mut missing = Vec::new();
+    let mut from = 1;
+    for _ in 0..BATCH_ENQUEUE {
+        let Some(&next) = storage.missing_items(from, 1).first() else {
+            break;
+        };
+        from = next + SEEDS_PER_RANGE;
+        let covered = waiting
+            .range(..=next)
+            .next_back()
+            .is_some_and(|start| next < start + SEEDS_PER_RANGE);
+        if covered || !waiting.insert(next) {
+            continue;
+        }
+        missing.push(next);
+    }
+    missing
+}
+
+/// Read the seed at `view` and any consecutive seeds that follow it (up to [SEEDS_PER_RANGE]).
+async fn produce_range<E: Storage + Metrics + Clock>(
+    storage: &Ordinal<E, Signature>,
+    view: View,
+) -> Vec<Signature> {
+    let mut signatures = Vec::new();
+    for view in view..view + SEEDS_PER_RANGE {
+        let Some(signature) = storage.get(view).await.expect("failed to get seed") else {
+            break;
+        };
+        signatures.push(signature);
+    }
+    signatures
+}
+
+/// Decode a range of consecutive seeds starting at `view`.
+fn decode_range(view: View, range: &[u8]) -> Option<Vec<Seed>> {
+    let signatures =
+        Vec::<Signature>::decode_cfg(range, &(RangeCfg::from(1..=SEEDS_PER_RANGE as usize), ()))
+            .ok()?;
+    Some(
+        (view..)
+            .zip(signatures)
+            .map(|(view, signature)| Seed::new(view, signature))
+            .collect(),
+    )
+}
+
 pub struct Actor<R: Storage + Metrics + Clock + Spawner + GClock + RngCore, I: Indexer> {
     context: R,
     config: Config<I>,
@@ -61,22 +122,35 @@ impl<R: Storage + Metrics + Clock + Spawner + GClock + RngCore, I: Indexer> Acto
         )
     }
 
-    pub fn start(
+    /// Request any missing seeds that are not already covered by an outstanding request.
+    async fn enqueue_missing(
+        &mut self,
+        storage: &Ordinal<R, Signature>,
+        resolver: &mut impl Resolver<Key = U64>,
+    ) {
+        for view in missing_ranges(storage, &mut self.waiting) {
+            resolver.fetch(view.into()).await;
+        }
+    }
+
+    pub fn start<B: Blocker<PublicKey = PublicKey>>(
         mut self,
         backfill: (
             impl Sender<PublicKey = PublicKey>,
             impl Receiver<PublicKey = PublicKey>,
         ),
+        reporter: PeerReporter<R, B>,
     ) -> Handle<()> {
-        self.context.spawn_ref()(self.run(backfill))
+        self.context.spawn_ref()(self.run(backfill, reporter))
     }
 
-    async fn run(
+    async fn run<B: Blocker<PublicKey = PublicKey>>(
         mut self,
         backfill: (
             impl Sender<PublicKey = PublicKey>,
             impl Receiver<PublicKey = PublicKey>,
         ),
+        mut reporter: PeerReporter<R, B>,
     ) {
         // Create metadata
         let mut metadata = Metadata::<_, U64, u64>::init(
@@ -127,11 +201,7 @@ impl<R: Storage + Metrics + Clock + Spawner + GClock + RngCore, I: Indexer> Acto
         let mut listeners: HashMap<View, Vec<oneshot::Sender<Seed>>> = HashMap::new();
 
         // Start by fetching the first missing seeds
-        let missing = storage.missing_items(1, BATCH_ENQUEUE);
-        for next in missing {
-            resolver.fetch(next.into()).await;
-            self.waiting.insert(next);
-        }
+        self.enqueue_missing(&storage, &mut resolver).await;
 
         // Track uploads
         let mut uploads_outstanding = 0;
@@ -194,16 +264,7 @@ impl<R: Storage + Metrics + Clock + Spawner + GClock + RngCore, I: Indexer> Acto
                     }
 
                     // Enqueue missing seeds
-                    let missing = storage.missing_items(1, BATCH_ENQUEUE);
-                    if missing.is_empty() {
-                        continue;
-                    }
-                    for next in missing {
-                        if !self.waiting.insert(next) {
-                            continue;
-                        }
-                        resolver.fetch(next.into()).await;
-                    }
+                    self.enqueue_missing(&storage, &mut resolver).await;
                 }
                 Message::Get { view, response } => {
                     let Some(signature) = storage.get(view).await.expect("failed to get seed")
@@ -218,73 +279,91 @@ impl<R: Storage + Metrics + Clock + Spawner + GClock + RngCore, I: Indexer> Acto
                         .send(Seed { view, signature })
                         .expect("failed to send seed");
                 }
+                Message::Stored { view, response } => {
+                    let seed = storage
+                        .get(view)
+                        .await
+                        .expect("failed to get seed")
+                        .map(|signature| Seed { view, signature });
+                    let _ = response.send(seed);
+                }
                 Message::Deliver {
                     view,
                     signature,
                     response,
                 } => {
-                    // Verify signature
-                    let Ok(signature) =
-                        <<MinSig as Variant>::Signature>::decode(&mut signature.as_ref())
-                    else {
-                        response.send(false).expect("failed to send none");
+                    // Decode the range of consecutive seeds starting at view
+                    let Some(seeds) = decode_range(view, signature.as_ref()) else {
+                        response.send(false).expect("failed to send false");
+                        reporter.report(Offense::DecodeFailure).await;
                         continue;
                     };
-                    let seed = Seed::new(view, signature);
-                    if !seed.verify(&self.config.namespace, &self.config.identity) {
+
+                    // Verify signatures (rejecting the entire range if any are invalid)
+                    if !seeds
+                        .iter()
+                        .all(|seed| seed.verify(&self.config.namespace, &self.config.identity))
+                    {
                         response.send(false).expect("failed to send false");
+                        reporter.report(Offense::InvalidCertificate).await;
                         continue;
                     }
-
-                    self.waiting.remove(&view);
+                    let end = view + seeds.len() as u64;
+                    self.waiting
+                        .retain(|waiting| !(view..end).contains(waiting));
 
                     // Notify resolver
                     response.send(true).expect("failed to send true");
 
-                    // Store seed
-                    if !storage.has(view) {
+                    // Store seeds
+                    let mut stored = false;
+                    for seed in &seeds {
+                        if storage.has(seed.view) {
+                            continue;
+                        }
                         storage
-                            .put(view, signature)
+                            .put(seed.view, seed.signature)
                             .await
                             .expect("failed to put seed");
+                        stored = true;
+                    }
+                    if stored {
                         storage.sync().await.expect("failed to sync seed");
                     }
 
                     // Notify listeners
-                    if let Some(listeners) = listeners.remove(&view) {
-                        for listener in listeners {
-                            listener.send(seed.clone()).expect("failed to send seed");
+                    for seed in seeds {
+                        if let Some(listeners) = listeners.remove(&seed.view) {
+                            for listener in listeners {
+                                listener.send(seed.clone()).expect("failed to send seed");
+                            }
                         }
                     }
 
-                    // Cancel resolver
-                    if let Some(current_end) = storage.next_gap(1).0 {
-                        let current_end = U64::from(current_end);
-                        resolver.retain(move |x| x > &current_end).await;
-                    }
+                    // Cancel resolver (including any requests covered by this range)
+                    let current_end = U64::from(storage.next_gap(1).0.unwrap_or(0));
+                    let (start, end) = (U64::from(view), U64::from(end));
+                    resolver
+                        .retain(move |x| x > &current_end && (x < &start || x >= &end))
+                        .await;
 
                     // Enqueue missing seeds
-                    let missing = storage.missing_items(1, BATCH_ENQUEUE);
-                    for next in missing {
-                        if !self.waiting.insert(next) {
-                            continue;
-                        }
-                        resolver.fetch(next.into()).await;
-                    }
+                    self.enqueue_missing(&storage, &mut resolver).await;
                 }
                 Message::Produce { view, response } => {
-                    // Serve seed from storage
-                    let Some(encoded) = storage.get(view).await.expect("failed to get seed") else {
+                    // Serve the requested seed and any consecutive seeds that follow it
+                    let signatures = produce_range(&storage, view).await;
+                    if signatures.is_empty() {
                         continue;
-                    };
+                    }
                     response
-                        .send(encoded.encode().into())
-                        .expect("failed to send seed");
+                        .send(signatures.encode().into())
+                        .expect("failed to send seeds");
                 }
             }
 
             // Attempt to upload any seeds
-            while uploads_outstanding < self.config.max_uploads_outstanding {
+            while uploads_outstanding < self.config.tunables.max_uploads_outstanding() {
                 // Get next seed
                 let Some(seed) = storage.get(cursor).await.expect("failed to get seed") else {
                     break;
@@ -320,3 +399,86 @@ impl<R: Storage + Metrics + Clock + Spawner + GClock + RngCore, I: Indexer> Acto
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use commonware_consensus::{
+        simplex::types::view_message, threshold_simplex::types::seed_namespace,
+    };
+    use commonware_cryptography::bls12381::primitives::ops;
+    use commonware_runtime::{deterministic, Runner};
+    use commonware_utils::{NZUsize, NZU64};
+    use nullspace_types::NAMESPACE;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_seed_ranges() {
+        let executor = deterministic::Runner::default();
+        executor.start(|context| async move {
+            let (secret, identity) = ops::keypair::<_, MinSig>(&mut StdRng::seed_from_u64(0));
+            let namespace = seed_namespace(NAMESPACE);
+            let mut storage = Ordinal::init(
+                context.with_label("storage"),
+                ordinal::Config {
+                    partition: "storage".to_string(),
+                    items_per_blob: NZU64!(64),
+                    write_buffer: NZUsize!(1024),
+                    replay_buffer: NZUsize!(1024),
+                },
+            )
+            .await
+            .unwrap();
+
+            // Store seeds 1..=1000 except 5, 6, 100, and 600
+            for view in 1..=1000 {
+                if [5, 6, 100, 600].contains(&view) {
+                    continue;
+                }
+                let signature =
+                    ops::sign_message::<MinSig>(&secret, Some(&namespace), &view_message(view));
+                storage.put(view, signature).await.unwrap();
+            }
+            storage.sync().await.unwrap();
+
+            // Only the first missing view of each range is requested
+            let mut waiting = BTreeSet::new();
+            assert_eq!(missing_ranges(&storage, &mut waiting), vec![5, 600]);
+            assert_eq!(missing_ranges(&storage, &mut waiting), Vec::<View>::new());
+
+            // A full range is served from the first stored view
+            let range = produce_range(&storage, 7).await;
+            assert_eq!(range.len(), SEEDS_PER_RANGE as usize);
+            let seeds = decode_range(7, &range.encode()).unwrap();
+            assert_eq!(seeds.first().unwrap().view, 7);
+            assert_eq!(seeds.last().unwrap().view, 7 + SEEDS_PER_RANGE - 1);
+            assert!(seeds.iter().all(|seed| seed.verify(NAMESPACE, &identity)));
+
+            // A partial range stops at the first missing view
+            let range = produce_range(&storage, 500).await;
+            assert_eq!(range.len(), 100);
+            let seeds = decode_range(500, &range.encode()).unwrap();
+            assert_eq!(seeds.last().unwrap().view, 599);
+            assert!(seeds.iter().all(|seed| seed.verify(NAMESPACE, &identity)));
+
+            // A partial range stops at the last stored view
+            let range = produce_range(&storage, 900).await;
+            assert_eq!(range.len(), 101);
+            assert_eq!(decode_range(900, &range.encode()).unwrap().len(), 101);
+
+            // Nothing is served for a missing view
+            assert!(produce_range(&storage, 5).await.is_empty());
+
+            // Seeds attributed to the wrong views do not verify
+            let seeds = decode_range(8, &produce_range(&storage, 7).await.encode()).unwrap();
+            assert!(!seeds.iter().any(|seed| seed.verify(NAMESPACE, &identity)));
+
+            // Ranges that are empty or longer than SEEDS_PER_RANGE are rejected
+            assert!(decode_range(1, &Vec::<Signature>::new().encode()).is_none());
+            let mut range = produce_range(&storage, 1).await;
+            range.extend(produce_range(&storage, 7).await);
+            assert!(range.len() > SEEDS_PER_RANGE as usize);
+            assert!(decode_range(1, &range.encode()).is_none());
+        });
+    }
+}
@@ -21,6 +21,10 @@ pub enum Message {
         view: View,
         response: oneshot::Sender<Seed>,
     },
+    Stored {
+        view: View,
+        response: oneshot::Sender<Option<Seed>>,
+    },
     Deliver {
         view: View,
         signature: Bytes,
@@ -96,6 +100,24 @@ impl Mailbox {
         }
     }
 
+    /// Get the seed for `view` if it is stored (without fetching it from peers).
+    pub async fn stored(&mut self, view: View) -> Result<Option<Seed>, MailboxError> {
+        let (sender, receiver) = oneshot::channel();
+        {
+            let mut mailbox_sender = self.sender.clone();
+            let mut stopped = self.stopped.clone();
+            select! {
+                result = mailbox_sender.send(Message::Stored { view, response: sender }) => {
+                    result.map_err(|_| MailboxError::Closed)?;
+                },
+                _ = &mut stopped => {
+                    return Err(MailboxError::ShuttingDown);
+                },
+            }
+        }
+        receiver.await.map_err(|_| MailboxError::Canceled)
+    }
+
     pub async fn uploaded(&mut self, view: View) -> Result<(), MailboxError> {
         let mut sender = self.sender.clone();
         let mut stopped = self.stopped.clone();
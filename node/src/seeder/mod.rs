@@ -1,7 +1,7 @@
 mod actor;
 mod ingress;
 
-use crate::{indexer::Indexer, supervisor::ViewSupervisor};
+use crate::{indexer::Indexer, reload::Tunables, supervisor::ViewSupervisor};
 pub use actor::Actor;
 use commonware_cryptography::ed25519::PublicKey;
 use governor::Quota;
@@ -21,5 +21,5 @@ pub struct Config<I: Indexer> {
     pub items_per_blob: NonZero<u64>,
     pub write_buffer: NonZero<usize>,
     pub replay_buffer: NonZero<usize>,
-    pub max_uploads_outstanding: usize,
+    pub tunables: Tunables,
 }
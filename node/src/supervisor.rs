@@ -1,3 +1,4 @@
+use crate::rotation::{self, Rotation};
 use commonware_codec::Encode;
 use commonware_consensus::{
     aggregation::types::Epoch, threshold_simplex::types::View, Monitor, Supervisor as Su,
@@ -18,7 +19,10 @@ use commonware_resolver::p2p;
 use commonware_runtime::RwLock;
 use futures::{channel::mpsc, SinkExt};
 use nullspace_types::{leader_index, Evaluation, Identity, Signature};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{self, Arc},
+};
 
 /// Manages epoch state and subscribers.
 struct EpochManager {
@@ -66,20 +70,28 @@ pub struct Supervisor {
     identity: Identity,
     polynomial: Vec<Evaluation>,
     participants: Vec<ed25519::PublicKey>,
-    participants_map: HashMap<ed25519::PublicKey, u32>,
+    participants_map: sync::RwLock<HashMap<ed25519::PublicKey, u32>>,
     share: group::Share,
     epoch_manager: RwLock<EpochManager>,
 }
 
 impl Supervisor {
     /// Create a new supervisor.
+    ///
+    /// `participants` are the keys the polynomial was dealt to (their order determines
+    /// each participant's share), with `rotations` applied afterwards.
     pub fn new(
         polynomial: Poly<Evaluation>,
         mut participants: Vec<ed25519::PublicKey>,
         share: group::Share,
+        rotations: &[Rotation],
     ) -> Arc<Self> {
         // Setup participants
         participants.sort();
+        let participants: Vec<_> = participants
+            .iter()
+            .map(|participant| rotation::current(rotations, participant))
+            .collect();
         let mut participants_map = HashMap::new();
         for (index, validator) in participants.iter().enumerate() {
             participants_map.insert(validator.clone(), index as u32);
@@ -92,13 +104,23 @@ impl Supervisor {
             identity,
             polynomial,
             participants,
-            participants_map,
+            participants_map: sync::RwLock::new(participants_map),
             share,
             epoch_manager: RwLock::new(EpochManager::new()),
         })
     }
 }
 
+impl Supervisor {
+    fn is_participant(&self, candidate: &ed25519::PublicKey) -> Option<u32> {
+        self.participants_map
+            .read()
+            .unwrap()
+            .get(candidate)
+            .cloned()
+    }
+}
+
 /// View-based [Supervisor] for [commonware_consensus::threshold_simplex].
 #[derive(Clone)]
 pub struct ViewSupervisor {
@@ -109,6 +131,17 @@ impl ViewSupervisor {
     pub fn new(supervisor: Arc<Supervisor>) -> Self {
         Self { inner: supervisor }
     }
+
+    /// Accept `new` in place of the participant identified by `old`.
+    ///
+    /// `old` remains a participant (and is still used for leader election) until the
+    /// supervisor is recreated with the rotation applied.
+    pub fn rotate(&self, old: &ed25519::PublicKey, new: ed25519::PublicKey) {
+        let mut participants_map = self.inner.participants_map.write().unwrap();
+        if let Some(index) = participants_map.get(old).cloned() {
+            participants_map.insert(new, index);
+        }
+    }
 }
 
 impl p2p::Coordinator for ViewSupervisor {
@@ -137,7 +170,7 @@ impl Su for ViewSupervisor {
     }
 
     fn is_participant(&self, _: Self::Index, candidate: &Self::PublicKey) -> Option<u32> {
-        self.inner.participants_map.get(candidate).cloned()
+        self.inner.is_participant(candidate)
     }
 }
 
@@ -195,7 +228,7 @@ impl Su for EpochSupervisor {
     }
 
     fn is_participant(&self, _: Self::Index, candidate: &Self::PublicKey) -> Option<u32> {
-        self.inner.participants_map.get(candidate).cloned()
+        self.inner.is_participant(candidate)
     }
 }
 
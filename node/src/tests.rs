@@ -1,11 +1,16 @@
 use super::*;
+use commonware_codec::Encode;
 use commonware_cryptography::{
     bls12381::{
         dkg::ops,
-        primitives::{poly::public, variant::MinSig},
+        primitives::{
+            group::Share,
+            poly::{public, Poly},
+            variant::MinSig,
+        },
     },
     ed25519::{PrivateKey, PublicKey},
-    PrivateKeyExt, Signer,
+    Hasher, PrivateKeyExt, Sha256, Signer,
 };
 use commonware_macros::{select, test_traced};
 use commonware_p2p::simulated::{self, Link, Network, Oracle, Receiver, Sender};
@@ -17,11 +22,16 @@ use commonware_utils::{quorum, NZUsize};
 use engine::{Config, Engine};
 use governor::Quota;
 use indexer::Mock;
-use nullspace_types::execution::{Instruction, Transaction};
+use nullspace_types::{
+    execution::{Instruction, Key, Progress, Transaction, Value},
+    Evaluation,
+};
 use rand::{rngs::StdRng, Rng, SeedableRng};
+use rotation::Registry;
 use std::{
     collections::{hash_map::Entry, BTreeMap, HashMap, HashSet},
     num::{NonZeroU32, NonZeroUsize},
+    sync::Arc,
     time::Duration,
 };
 use tracing::info;
@@ -121,6 +131,35 @@ async fn link_validators(
     }
 }
 
+/// Unlinks validators using the oracle.
+///
+/// The `restrict_to` function can be used to restrict the unlinking to certain connections,
+/// otherwise all validators will be unlinked from all other validators.
+async fn unlink_validators(
+    oracle: &mut Oracle<PublicKey>,
+    validators: &[PublicKey],
+    restrict_to: Option<fn(usize, usize, usize) -> bool>,
+) {
+    for (i1, v1) in validators.iter().enumerate() {
+        for (i2, v2) in validators.iter().enumerate() {
+            // Ignore self
+            if v2 == v1 {
+                continue;
+            }
+
+            // Restrict to certain connections
+            if let Some(f) = restrict_to {
+                if !f(validators.len(), i1, i2) {
+                    continue;
+                }
+            }
+
+            // Remove link
+            oracle.remove_link(v1.clone(), v2.clone()).await.unwrap();
+        }
+    }
+}
+
 fn all_online(n: u32, seed: u64, link: Link, required: u64) -> String {
     // Create context
     let threshold = quorum(n);
@@ -180,6 +219,7 @@ fn all_online(n: u32, seed: u64, link: Link, required: u64) -> String {
                 polynomial: polynomial.clone(),
                 share: shares[idx].clone(),
                 participants: validators.clone(),
+                rotations: Vec::new(),
                 mailbox_size: 1024,
                 deque_size: 10,
                 backfill_quota: Quota::per_second(NonZeroU32::new(10).unwrap()),
@@ -200,6 +240,11 @@ fn all_online(n: u32, seed: u64, link: Link, required: u64) -> String {
                 max_uploads_outstanding: 4,
                 mempool_max_backlog: 64,
                 mempool_max_transactions: 100_000,
+                max_block_txs: MAX_BLOCK_TRANSACTIONS,
+                max_block_bytes: 1024 * 1024,
+                mempool_tx_ttl: Duration::from_secs(600),
+                archive: false,
+                exporter: None,
             };
             let engine = Engine::new(context.with_label(&uid), config).await;
 
@@ -416,6 +461,7 @@ fn test_backfill() {
                 polynomial: polynomial.clone(),
                 share: shares[idx].clone(),
                 participants: validators.clone(),
+                rotations: Vec::new(),
                 mailbox_size: 1024,
                 deque_size: 10,
                 backfill_quota: Quota::per_second(NonZeroU32::new(10).unwrap()),
@@ -436,6 +482,11 @@ fn test_backfill() {
                 max_uploads_outstanding: 4,
                 mempool_max_backlog: 64,
                 mempool_max_transactions: 100_000,
+                max_block_txs: MAX_BLOCK_TRANSACTIONS,
+                max_block_bytes: 1024 * 1024,
+                mempool_tx_ttl: Duration::from_secs(600),
+                archive: false,
+                exporter: None,
             };
             let engine = Engine::new(context.with_label(&uid), config).await;
 
@@ -526,6 +577,7 @@ fn test_backfill() {
             polynomial: polynomial.clone(),
             share,
             participants: validators.clone(),
+            rotations: Vec::new(),
             mailbox_size: 1024,
             deque_size: 10,
             backfill_quota: Quota::per_second(NonZeroU32::new(10).unwrap()),
@@ -546,6 +598,11 @@ fn test_backfill() {
             max_uploads_outstanding: 4,
             mempool_max_backlog: 64,
             mempool_max_transactions: 100_000,
+            max_block_txs: MAX_BLOCK_TRANSACTIONS,
+            max_block_bytes: 1024 * 1024,
+            mempool_tx_ttl: Duration::from_secs(600),
+            archive: false,
+            exporter: None,
         };
         let engine = Engine::new(context.with_label(&uid), config).await;
 
@@ -681,6 +738,7 @@ fn test_unclean_shutdown() {
                     polynomial: polynomial.clone(),
                     share: shares[idx].clone(),
                     participants: validators.clone(),
+                    rotations: Vec::new(),
                     mailbox_size: 1024,
                     deque_size: 10,
                     backfill_quota: Quota::per_second(NonZeroU32::new(10).unwrap()),
@@ -701,6 +759,11 @@ fn test_unclean_shutdown() {
                     max_uploads_outstanding: 4,
                     mempool_max_backlog: 64,
                     mempool_max_transactions: 100_000,
+                    max_block_txs: MAX_BLOCK_TRANSACTIONS,
+                    max_block_bytes: 1024 * 1024,
+                    mempool_tx_ttl: Duration::from_secs(600),
+                    archive: false,
+                    exporter: None,
                 };
                 let engine = Engine::new(context.with_label(&uid), config).await;
 
@@ -906,6 +969,7 @@ fn test_execution(seed: u64, link: Link) -> String {
                 polynomial: polynomial.clone(),
                 share: shares[idx].clone(),
                 participants: validators.clone(),
+                rotations: Vec::new(),
                 mailbox_size: 1024,
                 deque_size: 10,
                 backfill_quota: Quota::per_second(NonZeroU32::new(10).unwrap()),
@@ -926,6 +990,11 @@ fn test_execution(seed: u64, link: Link) -> String {
                 max_uploads_outstanding: 4,
                 mempool_max_backlog: 64,
                 mempool_max_transactions: 100_000,
+                max_block_txs: MAX_BLOCK_TRANSACTIONS,
+                max_block_bytes: 1024 * 1024,
+                mempool_tx_ttl: Duration::from_secs(600),
+                archive: false,
+                exporter: None,
             };
             let engine = Engine::new(context.with_label(&uid), config).await;
 
@@ -1090,3 +1159,544 @@ fn test_execution_bad_links() {
         assert_eq!(state1, state2);
     }
 }
+
+/// Allows rotated keys to connect (a no-op, as the simulated network only delivers messages
+/// between registered and linked keys).
+#[derive(Clone)]
+struct NoopRegistry;
+
+impl Registry for NoopRegistry {
+    async fn register(&mut self, _: PublicKey) {}
+}
+
+/// Creates a simulated network and starts an engine (and rotator) for each validator (reusing
+/// any state persisted by a previous run), returning an RPC handler for each validator.
+///
+/// `signers` are the keys each validator currently runs with (in the order of the original
+/// `validators` keys). If `rotate` is set, the validator at that index rotates to the given key.
+#[allow(clippy::too_many_arguments)]
+async fn start_validators(
+    context: &deterministic::Context,
+    signers: &[PrivateKey],
+    validators: &[PublicKey],
+    polynomial: &Poly<Evaluation>,
+    shares: &[Share],
+    indexer: &Mock,
+    archive: bool,
+    rotate: Option<(usize, PrivateKey)>,
+) -> (Oracle<PublicKey>, Vec<rpc::Handler<deterministic::Context>>) {
+    // Create simulated network
+    let (network, mut oracle) = Network::new(
+        context.with_label("network"),
+        simulated::Config {
+            max_size: 1024 * 1024,
+        },
+    );
+
+    // Start network
+    network.start();
+
+    // Register participants (with their current keys)
+    let keys: Vec<_> = signers.iter().map(|signer| signer.public_key()).collect();
+    let mut registrations = register_validators(&mut oracle, &keys).await;
+
+    // Create instances
+    let mut handlers = Vec::new();
+    for (idx, signer) in signers.iter().enumerate() {
+        // Load rotations (persisted under the original key)
+        let public_key = signer.public_key();
+        let uid = format!("validator-{}", validators[idx]);
+        let rotator = rotation::Actor::init(
+            context.with_label(&format!("{uid}-rotation")),
+            rotation::Config {
+                partition: format!("{uid}-rotation"),
+                signer: signer.clone(),
+                next: rotate
+                    .as_ref()
+                    .filter(|(rotating, _)| *rotating == idx)
+                    .map(|(_, next)| next.clone()),
+            },
+        )
+        .await;
+
+        // Configure engine
+        let config: Config<_, Mock> = engine::Config {
+            blocker: oracle.control(public_key.clone()),
+            partition_prefix: uid.clone(),
+            blocks_freezer_table_initial_size: FREEZER_TABLE_INITIAL_SIZE,
+            finalized_freezer_table_initial_size: FREEZER_TABLE_INITIAL_SIZE,
+            signer: signer.clone(),
+            polynomial: polynomial.clone(),
+            share: shares[idx].clone(),
+            participants: validators.to_vec(),
+            rotations: rotator.rotations(),
+            mailbox_size: 1024,
+            deque_size: 10,
+            backfill_quota: Quota::per_second(NonZeroU32::new(10).unwrap()),
+            leader_timeout: Duration::from_secs(1),
+            notarization_timeout: Duration::from_secs(2),
+            nullify_retry: Duration::from_secs(10),
+            fetch_timeout: Duration::from_secs(1),
+            activity_timeout: 10,
+            skip_timeout: 5,
+            max_fetch_count: 10,
+            max_fetch_size: 1024 * 512,
+            fetch_concurrent: 10,
+            fetch_rate_per_peer: Quota::per_second(NonZeroU32::new(10).unwrap()),
+            buffer_pool_page_size: BUFFER_POOL_PAGE_SIZE,
+            buffer_pool_capacity: BUFFER_POOL_CAPACITY,
+            indexer: indexer.clone(),
+            execution_concurrency: 2,
+            max_uploads_outstanding: 4,
+            mempool_max_backlog: 64,
+            mempool_max_transactions: 100_000,
+            max_block_txs: MAX_BLOCK_TRANSACTIONS,
+            max_block_bytes: 1024 * 1024,
+            mempool_tx_ttl: Duration::from_secs(600),
+            archive,
+            exporter: None,
+        };
+        let engine = Engine::new(context.with_label(&uid), config).await;
+        handlers.push(rpc::Handler::new(
+            context.clone(),
+            engine.application(),
+            engine.aggregator(),
+            engine.scorer(),
+            validators.to_vec(),
+            Arc::new(|_| Ok(())),
+        ));
+
+        // Get networking
+        let (pending, recovered, resolver, broadcast, backfill, seeder, aggregator, aggregation) =
+            registrations.remove(&public_key).unwrap();
+
+        // Start rotator
+        let rotation_network = oracle.register(public_key.clone(), 8).await.unwrap();
+        rotator.start(engine.supervisor(), NoopRegistry, rotation_network);
+
+        // Start engine
+        engine.start(
+            pending,
+            recovered,
+            resolver,
+            broadcast,
+            backfill,
+            seeder,
+            aggregator,
+            aggregation,
+        );
+    }
+    (oracle, handlers)
+}
+
+/// Returns the number of contiguous certificates processed by each validator.
+fn certificates_processed(context: &deterministic::Context, validators: &[PublicKey]) -> Vec<u64> {
+    let prefixes: Vec<_> = validators
+        .iter()
+        .map(|public_key| format!("validator-{public_key}_"))
+        .collect();
+    let mut processed = vec![0; validators.len()];
+    let metrics = context.encode();
+    for line in metrics.lines() {
+        // Ensure it is a metrics line
+        if !line.starts_with("validator-") {
+            continue;
+        }
+
+        // Split metric and value
+        let mut parts = line.split_whitespace();
+        let metric = parts.next().unwrap();
+        let value = parts.next().unwrap();
+
+        // If ends with peers_blocked, ensure it is zero
+        if metric.ends_with("_peers_blocked") {
+            let value = value.parse::<u64>().unwrap();
+            assert_eq!(value, 0);
+        }
+
+        // Record certificates processed
+        if metric.ends_with("_certificates_processed") {
+            let idx = prefixes
+                .iter()
+                .position(|prefix| metric.starts_with(prefix))
+                .unwrap();
+            processed[idx] = value.parse::<u64>().unwrap();
+        }
+    }
+    processed
+}
+
+/// Waits for all validators selected by `include` to process at least `required` certificates.
+async fn wait_for_progress(
+    context: &deterministic::Context,
+    validators: &[PublicKey],
+    include: impl Fn(usize) -> bool,
+    required: u64,
+) {
+    loop {
+        let processed = certificates_processed(context, validators);
+        if processed
+            .iter()
+            .enumerate()
+            .all(|(idx, processed)| !include(idx) || *processed >= required)
+        {
+            return;
+        }
+        context.sleep(Duration::from_secs(1)).await;
+    }
+}
+
+/// Ensures no two validators uploaded conflicting results for the same height.
+async fn check_safety(indexer: &Mock, finalized: &mut BTreeMap<u64, Progress>) {
+    let summaries = indexer
+        .summaries
+        .write()
+        .await
+        .drain(..)
+        .collect::<Vec<_>>();
+    for (height, summary) in summaries {
+        match finalized.entry(height) {
+            std::collections::btree_map::Entry::Vacant(entry) => {
+                entry.insert(summary.progress);
+            }
+            std::collections::btree_map::Entry::Occupied(entry) => {
+                assert_eq!(
+                    entry.get(),
+                    &summary.progress,
+                    "conflicting finalization at height {height}"
+                );
+            }
+        }
+    }
+}
+
+/// Runs 5 validators through delays, partitions, and a crash, ensuring they never
+/// finalize conflicting blocks and continue to make progress.
+fn simulate(seed: u64) -> String {
+    // Create context
+    let n = 5;
+    let threshold = quorum(n);
+    let link = Link {
+        latency: Duration::from_millis(50),
+        jitter: Duration::from_millis(40),
+        success_rate: 0.9,
+    };
+
+    // Register participants
+    let mut signers = Vec::new();
+    let mut validators = Vec::new();
+    for i in 0..n {
+        let signer = PrivateKey::from_seed(i as u64);
+        let pk = signer.public_key();
+        signers.push(signer);
+        validators.push(pk);
+    }
+    validators.sort();
+    signers.sort_by_key(|s| s.public_key());
+
+    // Derive threshold
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (polynomial, shares) = ops::generate_shares::<_, MinSig>(&mut rng, None, n, threshold);
+    let identity = *public::<MinSig>(&polynomial);
+
+    // Define mock indexer (must live outside of the runs because it stores
+    // summaries from before the crash)
+    let indexer = Mock::new(identity);
+
+    // Run until the network is split, then crash every validator
+    let cfg = deterministic::Config::default()
+        .with_seed(seed)
+        .with_timeout(Some(Duration::from_secs(1200)));
+    let (context, mut finalized) = Runner::from(cfg).start({
+        let (signers, validators) = (signers.clone(), validators.clone());
+        let (polynomial, shares) = (polynomial.clone(), shares.clone());
+        let (indexer, link) = (indexer.clone(), link.clone());
+        move |context| async move {
+            let mut finalized = BTreeMap::new();
+            let (mut oracle, _) = start_validators(
+                &context,
+                &signers,
+                &validators,
+                &polynomial,
+                &shares,
+                &indexer,
+                false,
+                None,
+            )
+            .await;
+            link_validators(&mut oracle, &validators, link.clone(), None).await;
+
+            // Wait for all validators to make progress
+            wait_for_progress(&context, &validators, |_| true, 10).await;
+            check_safety(&indexer, &mut finalized).await;
+
+            // Isolate the first validator (the rest still have a quorum)
+            unlink_validators(
+                &mut oracle,
+                &validators,
+                Some(|_, i, j| [i, j].contains(&0usize)),
+            )
+            .await;
+            let isolated = certificates_processed(&context, &validators)[0];
+            wait_for_progress(&context, &validators, |idx| idx != 0, isolated + 20).await;
+            check_safety(&indexer, &mut finalized).await;
+
+            // Reconnect the first validator and wait for it to catch up
+            link_validators(
+                &mut oracle,
+                &validators,
+                link,
+                Some(|_, i, j| [i, j].contains(&0usize)),
+            )
+            .await;
+            let tip = certificates_processed(&context, &validators)
+                .into_iter()
+                .max()
+                .unwrap();
+            wait_for_progress(&context, &validators, |_| true, tip).await;
+            check_safety(&indexer, &mut finalized).await;
+
+            // Split the network so that neither side has a quorum
+            unlink_validators(
+                &mut oracle,
+                &validators,
+                Some(|n, i, j| (i < n / 2) != (j < n / 2)),
+            )
+            .await;
+            context.sleep(Duration::from_secs(10)).await;
+            check_safety(&indexer, &mut finalized).await;
+            (context, finalized)
+        }
+    });
+    let tip = *finalized.keys().next_back().unwrap();
+    info!(tip, "crashing all validators");
+
+    // Restart all validators (with the network healed) and ensure they resume
+    Runner::from(context.recover()).start(|context| async move {
+        let (mut oracle, _) = start_validators(
+            &context,
+            &signers,
+            &validators,
+            &polynomial,
+            &shares,
+            &indexer,
+            false,
+            None,
+        )
+        .await;
+        link_validators(&mut oracle, &validators, link, None).await;
+        wait_for_progress(&context, &validators, |_| true, tip + 20).await;
+        check_safety(&indexer, &mut finalized).await;
+        context.auditor().state()
+    })
+}
+
+#[test_traced("INFO")]
+fn test_simulation() {
+    for seed in 0..3 {
+        let state = simulate(seed);
+        assert_eq!(state, simulate(seed));
+    }
+}
+
+#[test_traced("INFO")]
+fn test_key_rotation() {
+    // Create context
+    let n = 4;
+    let threshold = quorum(n);
+    let link = Link {
+        latency: Duration::from_millis(10),
+        jitter: Duration::from_millis(1),
+        success_rate: 1.0,
+    };
+
+    // Register participants
+    let mut signers = Vec::new();
+    let mut validators = Vec::new();
+    for i in 0..n {
+        let signer = PrivateKey::from_seed(i as u64);
+        validators.push(signer.public_key());
+        signers.push(signer);
+    }
+    validators.sort();
+    signers.sort_by_key(|s| s.public_key());
+
+    // Derive threshold
+    let mut rng = StdRng::seed_from_u64(0);
+    let (polynomial, shares) = ops::generate_shares::<_, MinSig>(&mut rng, None, n, threshold);
+    let identity = *public::<MinSig>(&polynomial);
+    let indexer = Mock::new(identity);
+
+    // Rotate the key of the first validator while the network is running
+    let next = PrivateKey::from_seed(100);
+    let (context, mut finalized, tip) = Runner::timed(Duration::from_secs(600)).start({
+        let (signers, validators) = (signers.clone(), validators.clone());
+        let (polynomial, shares) = (polynomial.clone(), shares.clone());
+        let (indexer, link, next) = (indexer.clone(), link.clone(), next.clone());
+        move |context| async move {
+            let mut finalized = BTreeMap::new();
+            let (mut oracle, _) = start_validators(
+                &context,
+                &signers,
+                &validators,
+                &polynomial,
+                &shares,
+                &indexer,
+                false,
+                Some((0, next)),
+            )
+            .await;
+            link_validators(&mut oracle, &validators, link, None).await;
+            wait_for_progress(&context, &validators, |_| true, 5).await;
+
+            // Wait for all peers to acknowledge the rotation
+            let acknowledged = format!(
+                "validator-{}-rotation_rotation_acknowledged 1",
+                validators[0]
+            );
+            while !context.encode().contains(&acknowledged) {
+                context.sleep(Duration::from_secs(1)).await;
+            }
+
+            // Ensure finalization continues after the rotation is applied
+            let tip = certificates_processed(&context, &validators)
+                .into_iter()
+                .max()
+                .unwrap();
+            wait_for_progress(&context, &validators, |_| true, tip + 5).await;
+            check_safety(&indexer, &mut finalized).await;
+            let tip = certificates_processed(&context, &validators)
+                .into_iter()
+                .max()
+                .unwrap();
+            (context, finalized, tip)
+        }
+    });
+    info!(tip, "restarting with rotated key");
+
+    // Restart all validators with the first one running with its new key (the old key is
+    // no longer registered)
+    let mut rotated = signers.clone();
+    rotated[0] = next;
+    let keys: Vec<_> = rotated.iter().map(|signer| signer.public_key()).collect();
+    Runner::from(context.recover()).start(|context| async move {
+        let (mut oracle, _) = start_validators(
+            &context,
+            &rotated,
+            &validators,
+            &polynomial,
+            &shares,
+            &indexer,
+            false,
+            None,
+        )
+        .await;
+        link_validators(&mut oracle, &keys, link, None).await;
+        wait_for_progress(&context, &validators, |_| true, tip + 20).await;
+        check_safety(&indexer, &mut finalized).await;
+    });
+}
+
+#[test_traced("INFO")]
+fn test_archive_state_at() {
+    // Create context
+    let n = 4;
+    let threshold = quorum(n);
+    let link = Link {
+        latency: Duration::from_millis(10),
+        jitter: Duration::from_millis(1),
+        success_rate: 1.0,
+    };
+    let executor = Runner::timed(Duration::from_secs(300));
+    executor.start(|mut context| async move {
+        // Register participants
+        let mut signers = Vec::new();
+        let mut validators = Vec::new();
+        for i in 0..n {
+            let signer = PrivateKey::from_seed(i as u64);
+            validators.push(signer.public_key());
+            signers.push(signer);
+        }
+        validators.sort();
+        signers.sort_by_key(|s| s.public_key());
+
+        // Derive threshold
+        let (polynomial, shares) =
+            ops::generate_shares::<_, MinSig>(&mut context, None, n, threshold);
+        let identity = *public::<MinSig>(&polynomial);
+        let indexer = Mock::new(identity);
+
+        // Start archive validators
+        let (mut oracle, mut handlers) = start_validators(
+            &context,
+            &signers,
+            &validators,
+            &polynomial,
+            &shares,
+            &indexer,
+            true,
+            None,
+        )
+        .await;
+        link_validators(&mut oracle, &validators, link, None).await;
+
+        // Register a player and then deposit (each transaction updates the account nonce),
+        // recording a height at which each update is visible
+        let player = PrivateKey::from_seed(100);
+        let key = Sha256::hash(&Key::Account(player.public_key()).encode());
+        let handler = &mut handlers[0];
+        let mut heights = Vec::new();
+        for (nonce, instruction) in [
+            Instruction::CasinoRegister {
+                name: "archive".to_string(),
+            },
+            Instruction::CasinoDeposit { amount: 100 },
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let tx = Transaction::sign(&player, nonce as u64, instruction);
+            let height = loop {
+                indexer.submit_tx(tx.clone());
+                context.sleep(Duration::from_secs(1)).await;
+                let tip = indexer
+                    .summaries
+                    .read()
+                    .await
+                    .iter()
+                    .map(|(height, _)| *height)
+                    .max();
+                let Some(tip) = tip else {
+                    continue;
+                };
+                if let Ok(Some((_, Value::Account(account)))) = handler.state_at(key, tip).await {
+                    if account.nonce == nonce as u64 + 1 {
+                        break tip;
+                    }
+                }
+            };
+            heights.push(height);
+        }
+
+        // Wait for later blocks to be executed
+        wait_for_progress(&context, &validators, |_| true, heights[1] + 10).await;
+
+        // The registration can still be read at its height (after the deposit overwrote it)
+        let Ok(Some((registered, Value::Account(account)))) =
+            handler.state_at(key, heights[0]).await
+        else {
+            panic!("missing account at height {}", heights[0]);
+        };
+        assert_eq!(account.nonce, 1);
+        let Ok(Some((deposited, Value::Account(account)))) =
+            handler.state_at(key, heights[1]).await
+        else {
+            panic!("missing account at height {}", heights[1]);
+        };
+        assert_eq!(account.nonce, 2);
+        assert!(registered < deposited);
+
+        // Heights that haven't been executed can't be read
+        assert!(handler.state_at(key, u64::MAX).await.is_err());
+    });
+}
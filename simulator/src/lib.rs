@@ -29,7 +29,10 @@ use commonware_storage::{
 use commonware_utils::{from_hex, hex};
 use futures::{SinkExt, StreamExt};
 use nullspace_types::{
-    api::{Events, FilteredEvents, Lookup, Pending, Submission, Summary, Update, UpdatesFilter},
+    api::{
+        Events, FilteredEvents, Lookup, PageRequest, Pending, Rejection, RejectionReason,
+        Submission, Summary, Update, UpdatesFilter,
+    },
     execution::{Event, Output, Progress, Seed, Transaction, Value},
     Identity, Query as ChainQuery, NAMESPACE,
 };
@@ -38,7 +41,7 @@ use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::{
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
 };
@@ -50,6 +53,9 @@ use tower_http::cors::{Any, CorsLayer};
 #[cfg(feature = "passkeys")]
 use uuid::Uuid;
 
+/// Number of reported mempool rejections to remember (the oldest are forgotten first).
+const MAX_REJECTIONS: usize = 65_536;
+
 #[derive(Clone)]
 #[allow(clippy::large_enum_variant)]
 pub enum InternalUpdate {
@@ -76,6 +82,8 @@ pub struct ExplorerTransaction {
     position: u32,
     public_key: String,
     nonce: u64,
+    /// Name of the instruction's kind (from the instruction catalog).
+    kind: String,
     description: String,
     instruction: String,
 }
@@ -147,6 +155,9 @@ pub struct State {
     explorer: ExplorerState,
     #[cfg(feature = "passkeys")]
     passkeys: PasskeyStore,
+
+    rejections: HashMap<Digest, Rejection>,
+    rejection_order: VecDeque<Digest>,
 }
 
 #[derive(Clone)]
@@ -189,24 +200,68 @@ impl Simulator {
             Event::CasinoPlayerRegistered { .. } => "CasinoPlayerRegistered",
             Event::CasinoGameStarted { .. } => "CasinoGameStarted",
             Event::CasinoGameMoved { .. } => "CasinoGameMoved",
+            Event::CasinoRngTranscript { .. } => "CasinoRngTranscript",
+            Event::CasinoSideBetSettled { .. } => "CasinoSideBetSettled",
+            Event::CasinoCardDealt { .. } => "CasinoCardDealt",
+            Event::CasinoCrapsRolled { .. } => "CasinoCrapsRolled",
             Event::CasinoGameCompleted { .. } => "CasinoGameCompleted",
+            Event::CasinoSessionExpired { .. } => "CasinoSessionExpired",
             Event::CasinoLeaderboardUpdated { .. } => "CasinoLeaderboardUpdated",
             Event::CasinoError { .. } => "CasinoError",
+            Event::InstructionFailed { .. } => "InstructionFailed",
+            Event::GuardiansSet { .. } => "GuardiansSet",
+            Event::RecoveryApproved { .. } => "RecoveryApproved",
+            Event::AccountRecovered { .. } => "AccountRecovered",
+            Event::AllowanceSet { .. } => "AllowanceSet",
+            Event::VusdtTransferred { .. } => "VusdtTransferred",
+            Event::VestingCreated { .. } => "VestingCreated",
+            Event::VestedClaimed { .. } => "VestedClaimed",
+            Event::StakingRewardsIssued { .. } => "StakingRewardsIssued",
+            Event::TreasuryFunded { .. } => "TreasuryFunded",
+            Event::BuybackExecuted { .. } => "BuybackExecuted",
+            Event::GameConfigUpdated { .. } => "GameConfigUpdated",
+            Event::GameStatsUpdated { .. } => "GameStatsUpdated",
+            Event::EconomyParamsUpdated { .. } => "EconomyParamsUpdated",
+            Event::AdminSet { .. } => "AdminSet",
+            Event::HouseSettingsUpdated { .. } => "HouseSettingsUpdated",
+            Event::BonusGranted { .. } => "BonusGranted",
+            Event::BonusReleased { .. } => "BonusReleased",
             Event::TournamentStarted { .. } => "TournamentStarted",
             Event::PlayerJoined { .. } => "PlayerJoined",
             Event::TournamentPhaseChanged { .. } => "TournamentPhaseChanged",
             Event::TournamentEnded { .. } => "TournamentEnded",
+            Event::TournamentScheduled { .. } => "TournamentScheduled",
+            Event::TournamentRulesSet { .. } => "TournamentRulesSet",
+            Event::TournamentPrizePaid { .. } => "TournamentPrizePaid",
+            Event::LeaderboardSeasonEnded { .. } => "LeaderboardSeasonEnded",
+            Event::SeasonRewardPaid { .. } => "SeasonRewardPaid",
+            Event::LoyaltyEarned { .. } => "LoyaltyEarned",
+            Event::TierChanged { .. } => "TierChanged",
             Event::VaultCreated { .. } => "VaultCreated",
             Event::CollateralDeposited { .. } => "CollateralDeposited",
+            Event::CollateralWithdrawn { .. } => "CollateralWithdrawn",
             Event::VusdtBorrowed { .. } => "VusdtBorrowed",
             Event::VusdtRepaid { .. } => "VusdtRepaid",
             Event::AmmSwapped { .. } => "AmmSwapped",
             Event::LiquidityAdded { .. } => "LiquidityAdded",
             Event::LiquidityRemoved { .. } => "LiquidityRemoved",
+            Event::LimitOrderPlaced { .. } => "LimitOrderPlaced",
+            Event::LimitOrderFilled { .. } => "LimitOrderFilled",
+            Event::LimitOrderCancelled { .. } => "LimitOrderCancelled",
+            Event::LiquidityZapped { .. } => "LiquidityZapped",
+            Event::Slashed { .. } => "Slashed",
+            Event::DailyBonusClaimed { .. } => "DailyBonusClaimed",
+            Event::ProfileUpdated { .. } => "ProfileUpdated",
+            Event::KycStatusChanged { .. } => "KycStatusChanged",
             Event::Staked { .. } => "Staked",
             Event::Unstaked { .. } => "Unstaked",
             Event::EpochProcessed { .. } => "EpochProcessed",
+            Event::InsuranceFunded { .. } => "InsuranceFunded",
+            Event::InsuranceDrawn { .. } => "InsuranceDrawn",
             Event::RewardsClaimed { .. } => "RewardsClaimed",
+            Event::FeeCharged { .. } => "FeeCharged",
+            Event::InterestAccrued { .. } => "InterestAccrued",
+            Event::Unknown { .. } => "Unknown",
         };
 
         let mut touch_account = |pk: &PublicKey| {
@@ -224,12 +279,60 @@ impl Simulator {
             Event::CasinoPlayerRegistered { player, .. } => touch_account(player),
             Event::CasinoGameStarted { player, .. } => touch_account(player),
             Event::CasinoGameMoved { .. } => {} // broadcasted; not account-specific
+            Event::CasinoRngTranscript { .. } => {}
+            Event::CasinoSideBetSettled { .. } => {}
+            Event::CasinoCardDealt { .. } => {}
+            Event::CasinoCrapsRolled { .. } => {}
             Event::CasinoGameCompleted { player, .. } => touch_account(player),
+            Event::CasinoSessionExpired { player, .. } => touch_account(player),
             Event::CasinoLeaderboardUpdated { .. } => {}
             Event::CasinoError { player, .. } => touch_account(player),
+            Event::InstructionFailed { player, .. } => touch_account(player),
+            Event::GuardiansSet { player, .. } => touch_account(player),
+            Event::RecoveryApproved {
+                player, guardian, ..
+            } => {
+                touch_account(player);
+                touch_account(guardian);
+            }
+            Event::AccountRecovered { old_key, new_key } => {
+                touch_account(old_key);
+                touch_account(new_key);
+            }
+            Event::AllowanceSet { owner, spender, .. } => {
+                touch_account(owner);
+                touch_account(spender);
+            }
+            Event::VusdtTransferred {
+                owner, to, spender, ..
+            } => {
+                touch_account(owner);
+                touch_account(to);
+                touch_account(spender);
+            }
+            Event::VestingCreated { from, to, .. } => {
+                touch_account(from);
+                touch_account(to);
+            }
+            Event::VestedClaimed { player, .. } => touch_account(player),
+            Event::StakingRewardsIssued { .. } => {}
+            Event::TreasuryFunded { .. } | Event::BuybackExecuted { .. } => {}
+            Event::GameConfigUpdated { .. } => {}
+            Event::GameStatsUpdated { .. } => {}
+            Event::EconomyParamsUpdated { .. } => {}
+            Event::AdminSet { .. } | Event::HouseSettingsUpdated { .. } => {}
+            Event::BonusGranted { player, .. } => touch_account(player),
+            Event::BonusReleased { player, .. } => touch_account(player),
             Event::TournamentStarted { .. } => {}
             Event::PlayerJoined { player, .. } => touch_account(player),
             Event::TournamentPhaseChanged { .. } => {}
+            Event::TournamentScheduled { .. } => {}
+            Event::TournamentRulesSet { .. } => {}
+            Event::TournamentPrizePaid { player, .. } => touch_account(player),
+            Event::LeaderboardSeasonEnded { .. } => {}
+            Event::SeasonRewardPaid { player, .. } => touch_account(player),
+            Event::LoyaltyEarned { player, .. } => touch_account(player),
+            Event::TierChanged { player, .. } => touch_account(player),
             Event::TournamentEnded { rankings, .. } => {
                 for (pk, _) in rankings {
                     touch_account(pk);
@@ -237,15 +340,29 @@ impl Simulator {
             }
             Event::VaultCreated { player } => touch_account(player),
             Event::CollateralDeposited { player, .. } => touch_account(player),
+            Event::CollateralWithdrawn { player, .. } => touch_account(player),
             Event::VusdtBorrowed { player, .. } => touch_account(player),
             Event::VusdtRepaid { player, .. } => touch_account(player),
             Event::AmmSwapped { player, .. } => touch_account(player),
             Event::LiquidityAdded { player, .. } => touch_account(player),
             Event::LiquidityRemoved { player, .. } => touch_account(player),
+            Event::LimitOrderPlaced { player, .. } => touch_account(player),
+            Event::LimitOrderFilled { player, .. } => touch_account(player),
+            Event::LimitOrderCancelled { player, .. } => touch_account(player),
+            Event::LiquidityZapped { player, .. } => touch_account(player),
+            Event::Slashed { player, .. } => touch_account(player),
+            Event::DailyBonusClaimed { player, .. } => touch_account(player),
+            Event::ProfileUpdated { player, .. } => touch_account(player),
+            Event::KycStatusChanged { player, .. } => touch_account(player),
             Event::Staked { player, .. } => touch_account(player),
             Event::Unstaked { player, .. } => touch_account(player),
             Event::RewardsClaimed { player, .. } => touch_account(player),
+            Event::FeeCharged { player, .. } => touch_account(player),
+            Event::InterestAccrued { player, .. } => touch_account(player),
             Event::EpochProcessed { .. } => {}
+            Event::InsuranceFunded { .. } => {}
+            Event::InsuranceDrawn { .. } => {}
+            Event::Unknown { .. } => {}
         }
     }
 
@@ -263,6 +380,13 @@ impl Simulator {
             GameType::SicBo => "Sic Bo",
             GameType::ThreeCard => "Three Card",
             GameType::UltimateHoldem => "Ultimate Hold'em",
+            GameType::Slots => "Slots",
+            GameType::Mines => "Mines",
+            GameType::PaiGow => "Pai Gow",
+            GameType::CaribbeanStud => "Caribbean Stud",
+            GameType::MississippiStud => "Mississippi Stud",
+            GameType::MoneyWheel => "Money Wheel",
+            GameType::FourCard => "Four Card",
         }
     }
 
@@ -272,14 +396,34 @@ impl Simulator {
         match instruction {
             Instruction::CasinoRegister { name } => format!("Register casino player \"{name}\""),
             Instruction::CasinoDeposit { amount } => format!("Deposit {amount} RNG (faucet)"),
+            Instruction::CasinoToggleShield
+            | Instruction::CasinoToggleDouble
+            | Instruction::CasinoToggleSuper
+            | Instruction::ClaimDailyBonus
+            | Instruction::ClaimRewards
+            | Instruction::ProcessEpoch
+            | Instruction::CreateVault
+            | Instruction::ClaimVested => instruction.info().label.to_string(),
+            Instruction::UpdateProfile { display_name, .. } => {
+                format!("Update profile (display name \"{display_name}\")")
+            }
             Instruction::CasinoStartGame {
                 game_type,
                 bet,
                 session_id,
+                commitment,
             } => format!(
-                "Start {} game (bet {bet} RNG, session {session_id})",
-                Self::describe_game_type(game_type)
+                "Start {} game (bet {bet} RNG, session {session_id}{})",
+                Self::describe_game_type(game_type),
+                if commitment.is_some() {
+                    ", committed entropy"
+                } else {
+                    ""
+                }
             ),
+            Instruction::CasinoAbandonSession { session_id } => {
+                format!("Abandon casino session {session_id}")
+            }
             Instruction::CasinoGameMove {
                 session_id,
                 payload,
@@ -291,9 +435,6 @@ impl Simulator {
                     format!("Casino game move (session {session_id}, {bytes} bytes)")
                 }
             }
-            Instruction::CasinoToggleShield => "Toggle shield modifier".to_string(),
-            Instruction::CasinoToggleDouble => "Toggle double modifier".to_string(),
-            Instruction::CasinoToggleSuper => "Toggle super mode".to_string(),
             Instruction::CasinoJoinTournament { tournament_id } => {
                 format!("Join tournament {tournament_id}")
             }
@@ -307,25 +448,34 @@ impl Simulator {
             Instruction::CasinoEndTournament { tournament_id } => {
                 format!("End tournament {tournament_id}")
             }
+            Instruction::CasinoScheduleTournament {
+                tournament_id,
+                start_view,
+                end_view,
+            } => format!("Schedule tournament {tournament_id} (views {start_view} to {end_view})"),
+            Instruction::SetTournamentRules { tournament_id, .. } => {
+                format!("Set tournament {tournament_id} rules")
+            }
 
             Instruction::Stake { amount, duration } => {
                 format!("Stake {amount} RNG for {duration} blocks")
             }
-            Instruction::Unstake => "Unstake".to_string(),
-            Instruction::ClaimRewards => "Claim staking rewards".to_string(),
-            Instruction::ProcessEpoch => "Process epoch".to_string(),
+            Instruction::Unstake { amount } => format!("Unstake {amount} RNG"),
 
-            Instruction::CreateVault => "Create vault".to_string(),
             Instruction::DepositCollateral { amount } => {
                 format!("Deposit {amount} RNG as collateral")
             }
             Instruction::BorrowUSDT { amount } => format!("Borrow {amount} vUSDT"),
             Instruction::RepayUSDT { amount } => format!("Repay {amount} vUSDT"),
+            Instruction::WithdrawCollateral { amount } => {
+                format!("Withdraw {amount} RNG of collateral")
+            }
 
             Instruction::Swap {
                 amount_in,
                 min_amount_out,
                 is_buying_rng,
+                ..
             } => {
                 if *is_buying_rng {
                     format!("Swap {amount_in} vUSDT for ≥ {min_amount_out} RNG")
@@ -336,10 +486,108 @@ impl Simulator {
             Instruction::AddLiquidity {
                 rng_amount,
                 usdt_amount,
+                ..
             } => format!("Add liquidity ({rng_amount} RNG + {usdt_amount} vUSDT)"),
-            Instruction::RemoveLiquidity { shares } => {
+            Instruction::RemoveLiquidity { shares, .. } => {
                 format!("Remove liquidity ({shares} LP shares)")
             }
+            Instruction::PlaceLimitOrder {
+                amount_in,
+                min_price,
+                is_buying_rng,
+            } => {
+                if *is_buying_rng {
+                    format!("Limit order: {amount_in} vUSDT for RNG at ≥ {min_price} (scaled)")
+                } else {
+                    format!("Limit order: {amount_in} RNG for vUSDT at ≥ {min_price} (scaled)")
+                }
+            }
+            Instruction::CancelLimitOrder { order_id } => {
+                format!("Cancel limit order {order_id}")
+            }
+            Instruction::ZapLiquidity {
+                amount_in,
+                min_shares,
+                from_rng,
+                ..
+            } => {
+                let asset = if *from_rng { "RNG" } else { "vUSDT" };
+                format!("Add liquidity from {amount_in} {asset} (≥ {min_shares} LP shares)")
+            }
+            Instruction::Slash { staker, bps, .. } => {
+                format!("Slash {bps} bps of the stake of {}", hex(staker.as_ref()))
+            }
+            Instruction::SetKycStatus { player, verified } => format!(
+                "Set KYC status of {} to {}",
+                hex(player.as_ref()),
+                if *verified { "verified" } else { "unverified" }
+            ),
+            Instruction::SetGuardians {
+                guardians,
+                threshold,
+            } => {
+                if guardians.is_empty() {
+                    "Disable account recovery".to_string()
+                } else {
+                    format!("Set {} guardians (threshold {threshold})", guardians.len())
+                }
+            }
+            Instruction::RecoverAccount { account, new_key } => format!(
+                "Approve recovery of {} to {}",
+                hex(account.as_ref()),
+                hex(new_key.as_ref())
+            ),
+            Instruction::Approve { spender, amount } => {
+                format!("Approve {} to spend {amount} vUSDT", hex(spender.as_ref()))
+            }
+            Instruction::TransferVusdtFrom { owner, to, amount } => format!(
+                "Transfer {amount} vUSDT from {} to {}",
+                hex(owner.as_ref()),
+                hex(to.as_ref())
+            ),
+            Instruction::CreateVesting {
+                to,
+                amount,
+                start_view,
+                duration,
+            } => format!(
+                "Vest {amount} RNG to {} over {duration} views from view {start_view}",
+                hex(to.as_ref())
+            ),
+            Instruction::ExecuteBuyback { amount } => {
+                format!("Buy back and burn RNG with {amount} treasury vUSDT")
+            }
+            Instruction::SetGameConfig { game_type, .. } => {
+                format!("Set {} rules", Self::describe_game_type(game_type))
+            }
+            Instruction::GrantBonus { amount, .. } => format!("Grant a {amount} chip bonus"),
+            Instruction::SetEconomyParams { params } => format!(
+                "Set economy parameters ({} initial chips, {} bps super mode fee, {} bps AMM fee)",
+                params.initial_chips, params.super_mode_fee_bps, params.amm_fee_bps
+            ),
+            Instruction::SetAdmin { role, admin, .. } => match admin {
+                Some(admin) => format!("Set the {role:?} admin to {}", hex(admin.as_ref())),
+                None => format!("Clear the {role:?} admin"),
+            },
+            Instruction::SetHouseSettings { settings } => format!(
+                "Set house settings ({} chip fee, {} max bet, faucet {})",
+                settings.fee_schedule.flat,
+                settings.bet_limits.default_max_bet,
+                if settings.daily_bonus.faucet_enabled {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            ),
+
+            Instruction::Batch(instructions) => format!(
+                "Batch: {}",
+                instructions
+                    .iter()
+                    .map(Self::describe_instruction)
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ),
         }
     }
 
@@ -372,6 +620,7 @@ impl Simulator {
                         position: idx as u32,
                         public_key: hex(tx.public.as_ref()),
                         nonce: tx.nonce,
+                        kind: tx.instruction.info().name.to_string(),
                         description: Self::describe_instruction(&tx.instruction),
                         instruction: format!("{:?}", tx.instruction),
                     };
@@ -389,7 +638,7 @@ impl Simulator {
                     activity.last_nonce = Some(tx.nonce);
                     activity.last_updated_height = Some(progress.height);
                 }
-                Keyless::Append(Output::Event(evt)) => {
+                Keyless::Append(Output::Event(evt) | Output::VersionedEvent { event: evt, .. }) => {
                     Self::record_event_for_accounts(
                         &mut state.explorer.accounts,
                         evt,
@@ -436,6 +685,29 @@ impl Simulator {
         }
     }
 
+    /// Remember why validators refused pending transactions (so submitters can look it up).
+    ///
+    /// Reports are not authenticated, so they are only a hint.
+    pub async fn submit_rejections(&self, rejections: Vec<Rejection>) {
+        let mut state = self.state.write().await;
+        for rejection in rejections {
+            let digest = rejection.digest;
+            if state.rejections.insert(digest, rejection).is_none() {
+                state.rejection_order.push_back(digest);
+            }
+        }
+        while state.rejection_order.len() > MAX_REJECTIONS {
+            if let Some(oldest) = state.rejection_order.pop_front() {
+                state.rejections.remove(&oldest);
+            }
+        }
+    }
+
+    pub async fn query_rejection(&self, digest: &Digest) -> Option<Rejection> {
+        let state = self.state.read().await;
+        state.rejections.get(digest).cloned()
+    }
+
     pub async fn submit_state(&self, summary: Summary, inner: Vec<(u64, Digest)>) {
         let mut state = self.state.write().await;
         if !state.submitted_state.insert(summary.progress.height) {
@@ -516,12 +788,29 @@ impl Simulator {
 
     async fn try_query_state(&self, key: &Digest) -> Option<Lookup> {
         let state = self.state.read().await;
+        Self::lookup(&state, key, None)
+    }
+
+    /// Query the value of a key as of the given height (i.e. the last update at or
+    /// before `height`).
+    ///
+    /// Because every uploaded key version is retained, this can serve "value at
+    /// block N" queries for any height that has been submitted.
+    pub async fn query_state_at(&self, key: &Digest, height: u64) -> Option<Lookup> {
+        let state = self.state.read().await;
+        Self::lookup(&state, key, Some(height))
+    }
 
+    fn lookup(state: &State, key: &Digest, at: Option<u64>) -> Option<Lookup> {
         let key_history = match state.keys.get(key) {
             Some(key_history) => key_history,
             None => return None,
         };
-        let (height, operation) = match key_history.last_key_value() {
+        let latest = match at {
+            Some(at) => key_history.range(..=at).next_back(),
+            None => key_history.last_key_value(),
+        };
+        let (height, operation) = match latest {
             Some((height, operation)) => (height, operation),
             None => return None,
         };
@@ -617,12 +906,18 @@ impl Api {
             .route("/submit", post(submit))
             .route("/seed/:query", get(query_seed))
             .route("/state/:query", get(query_state))
+            .route("/state/:query/:height", get(query_state_at))
             .route("/updates/:filter", get(updates_ws))
             .route("/mempool", get(mempool_ws))
+            .route("/rejection/:hash", get(query_rejection))
             .route("/explorer/blocks", get(list_blocks))
             .route("/explorer/blocks/:id", get(get_block))
             .route("/explorer/tx/:hash", get(get_transaction))
             .route("/explorer/account/:pubkey", get(get_account_activity))
+            .route(
+                "/explorer/account/:pubkey/txs",
+                get(list_account_transactions),
+            )
             .route("/explorer/search", get(search_explorer));
 
         #[cfg(feature = "passkeys")]
@@ -790,29 +1085,63 @@ async fn submit(AxumState(simulator): AxumState<Arc<Simulator>>, body: Bytes) ->
             StatusCode::OK
         }
         Submission::Summary(summary) => {
-            let (state_digests, events_digests) = match summary.verify(&simulator.identity) {
-                Ok(digests) => digests,
-                Err(err) => {
-                    tracing::warn!(
-                        ?err,
-                        view = summary.progress.view,
-                        height = summary.progress.height,
-                        state_ops = summary.state_proof_ops.len(),
-                        events_ops = summary.events_proof_ops.len(),
-                        "Summary verification failed"
-                    );
-                    return StatusCode::BAD_REQUEST;
-                }
+            let Some(digests) = verify_summary(&simulator, &summary) else {
+                return StatusCode::BAD_REQUEST;
             };
-            simulator
-                .submit_events(summary.clone(), events_digests)
-                .await;
-            simulator.submit_state(summary, state_digests).await;
+            submit_summary(&simulator, summary, digests).await;
+            StatusCode::OK
+        }
+        Submission::Rejections(rejections) => {
+            simulator.submit_rejections(rejections).await;
+            StatusCode::OK
+        }
+        Submission::Summaries(summaries) => {
+            // Reject the whole batch if any summary is invalid
+            let mut verified = Vec::with_capacity(summaries.len());
+            for summary in summaries {
+                let Some(digests) = verify_summary(&simulator, &summary) else {
+                    return StatusCode::BAD_REQUEST;
+                };
+                verified.push((summary, digests));
+            }
+            for (summary, digests) in verified {
+                submit_summary(&simulator, summary, digests).await;
+            }
             StatusCode::OK
         }
     }
 }
 
+type SummaryDigests = (Vec<(u64, Digest)>, Vec<(u64, Digest)>);
+
+fn verify_summary(simulator: &Simulator, summary: &Summary) -> Option<SummaryDigests> {
+    match summary.verify(&simulator.identity) {
+        Ok(digests) => Some(digests),
+        Err(err) => {
+            tracing::warn!(
+                ?err,
+                view = summary.progress.view,
+                height = summary.progress.height,
+                state_ops = summary.state_proof_ops.len(),
+                events_ops = summary.events_proof_ops.len(),
+                "Summary verification failed"
+            );
+            None
+        }
+    }
+}
+
+async fn submit_summary(
+    simulator: &Simulator,
+    summary: Summary,
+    (state_digests, events_digests): SummaryDigests,
+) {
+    simulator
+        .submit_events(summary.clone(), events_digests)
+        .await;
+    simulator.submit_state(summary, state_digests).await;
+}
+
 async fn query_state(
     AxumState(simulator): AxumState<Arc<Simulator>>,
     axum::extract::Path(query): axum::extract::Path<String>,
@@ -831,6 +1160,24 @@ async fn query_state(
     }
 }
 
+async fn query_state_at(
+    AxumState(simulator): AxumState<Arc<Simulator>>,
+    axum::extract::Path((query, height)): axum::extract::Path<(String, u64)>,
+) -> impl IntoResponse {
+    let raw = match from_hex(&query) {
+        Some(raw) => raw,
+        None => return StatusCode::BAD_REQUEST.into_response(),
+    };
+    let key = match Digest::decode(&mut raw.as_slice()) {
+        Ok(key) => key,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+    match simulator.query_state_at(&key, height).await {
+        Some(value) => (StatusCode::OK, value.encode().to_vec()).into_response(),
+        None => (StatusCode::NOT_FOUND, vec![]).into_response(),
+    }
+}
+
 async fn query_seed(
     AxumState(simulator): AxumState<Arc<Simulator>>,
     axum::extract::Path(query): axum::extract::Path<String>,
@@ -1039,39 +1386,24 @@ async fn handle_mempool_ws(socket: axum::extract::ws::WebSocket, simulator: Arc<
     let _ = sender.close().await;
 }
 
-#[derive(Deserialize)]
-struct Pagination {
-    offset: Option<usize>,
-    limit: Option<usize>,
-}
-
+/// Blocks, newest first (the cursor is the height of the first block in the page).
 async fn list_blocks(
     AxumState(simulator): AxumState<Arc<Simulator>>,
-    Query(pagination): Query<Pagination>,
+    Query(request): Query<PageRequest>,
 ) -> impl IntoResponse {
-    let offset = pagination.offset.unwrap_or(0);
-    let limit = pagination.limit.unwrap_or(20).min(200);
-
     let state = simulator.state.read().await;
 
-    let total = state.explorer.indexed_blocks.len();
-    let blocks: Vec<_> = state
-        .explorer
-        .indexed_blocks
-        .iter()
-        .rev()
-        .skip(offset)
-        .take(limit)
-        .map(|(_, b)| b.clone())
-        .collect();
-
-    let next_offset = if offset + blocks.len() < total {
-        Some(offset + blocks.len())
-    } else {
-        None
-    };
+    let blocks = &state.explorer.indexed_blocks;
+    let start = request.cursor.map_or(u64::MAX, |cursor| cursor.position());
+    let page = request.page(
+        blocks
+            .range(..=start)
+            .rev()
+            .map(|(height, block)| (*height, block.clone())),
+        blocks.len(),
+    );
 
-    Json(json!({ "blocks": blocks, "next_offset": next_offset, "total": total })).into_response()
+    Json(page).into_response()
 }
 
 async fn get_block(
@@ -1117,6 +1449,38 @@ async fn get_transaction(
     }
 }
 
+async fn query_rejection(
+    AxumState(simulator): AxumState<Arc<Simulator>>,
+    Path(hash): Path<String>,
+) -> impl IntoResponse {
+    let raw = match from_hex(&hash) {
+        Some(raw) => raw,
+        None => return StatusCode::BAD_REQUEST.into_response(),
+    };
+    let digest = match Digest::decode(&mut raw.as_slice()) {
+        Ok(d) => d,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+
+    let Some(rejection) = simulator.query_rejection(&digest).await else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let (reason, next) = match rejection.reason {
+        RejectionReason::InvalidSignature => ("invalid_signature", None),
+        RejectionReason::StaleNonce { next } => ("stale_nonce", Some(next)),
+        RejectionReason::FutureNonce { next } => ("future_nonce", Some(next)),
+        RejectionReason::ComputeLimitExceeded { .. } => ("compute_limit_exceeded", None),
+    };
+    Json(json!({
+        "hash": hash,
+        "public_key": hex(rejection.public.as_ref()),
+        "nonce": rejection.nonce,
+        "reason": reason,
+        "next_nonce": next,
+    }))
+    .into_response()
+}
+
 async fn get_account_activity(
     AxumState(simulator): AxumState<Arc<Simulator>>,
     Path(pubkey): Path<String>,
@@ -1138,6 +1502,48 @@ async fn get_account_activity(
     }
 }
 
+/// An account's transactions, newest first (the cursor is the index of the first transaction
+/// in the page).
+async fn list_account_transactions(
+    AxumState(simulator): AxumState<Arc<Simulator>>,
+    Path(pubkey): Path<String>,
+    Query(request): Query<PageRequest>,
+) -> impl IntoResponse {
+    let raw = match from_hex(&pubkey) {
+        Some(raw) => raw,
+        None => return StatusCode::BAD_REQUEST.into_response(),
+    };
+    let public_key = match ed25519::PublicKey::read(&mut raw.as_slice()) {
+        Ok(pk) => pk,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+
+    let state = simulator.state.read().await;
+
+    let Some(account) = state.explorer.accounts.get(&public_key) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let end = request.cursor.map_or(account.txs.len(), |cursor| {
+        (cursor.position() as usize)
+            .saturating_add(1)
+            .min(account.txs.len())
+    });
+    let page = request.page(
+        account.txs[..end]
+            .iter()
+            .enumerate()
+            .rev()
+            .filter_map(|(index, hash)| {
+                let digest = Digest::decode(from_hex(hash)?.as_slice()).ok()?;
+                let tx = state.explorer.txs_by_hash.get(&digest)?;
+                Some((index as u64, tx.clone()))
+            }),
+        account.txs.len(),
+    );
+
+    Json(page).into_response()
+}
+
 #[derive(Deserialize)]
 struct SearchQuery {
     q: String,
@@ -1381,7 +1787,9 @@ async fn filter_updates_for_account(
     for (i, op) in events.events_proof_ops.into_iter().enumerate() {
         let should_include = match &op {
             Keyless::Append(output) => match output {
-                Output::Event(event) => is_event_relevant_to_account(event, account),
+                Output::Event(event) | Output::VersionedEvent { event, .. } => {
+                    is_event_relevant_to_account(event, account)
+                }
                 Output::Transaction(tx) => tx.public == *account,
                 _ => false,
             },
@@ -1425,13 +1833,56 @@ fn is_event_relevant_to_account(event: &Event, account: &PublicKey) -> bool {
         Event::CasinoPlayerRegistered { player, .. } => player == account,
         Event::CasinoGameStarted { player, .. } => player == account,
         Event::CasinoGameMoved { .. } => true, // Broadcast all moves - clients filter by session_id
+        Event::CasinoRngTranscript { .. } => true, // Broadcast with the moves they belong to
+        Event::CasinoSideBetSettled { .. } => true, // Broadcast with the moves they belong to
+        Event::CasinoCardDealt { .. } => true, // Broadcast with the moves they belong to
+        Event::CasinoCrapsRolled { .. } => true, // Broadcast with the moves they belong to
         Event::CasinoGameCompleted { player, .. } => player == account,
+        Event::CasinoSessionExpired { player, .. } => player == account,
         Event::CasinoLeaderboardUpdated { .. } => true, // Leaderboard updates are public
         Event::CasinoError { player, .. } => player == account,
+        Event::InstructionFailed { player, .. } => player == account,
+        // Account recovery events
+        Event::GuardiansSet { player, .. } => player == account,
+        Event::RecoveryApproved {
+            player, guardian, ..
+        } => player == account || guardian == account,
+        Event::AccountRecovered { old_key, new_key } => old_key == account || new_key == account,
+        // Allowance events
+        Event::AllowanceSet { owner, spender, .. } => owner == account || spender == account,
+        Event::VusdtTransferred {
+            owner, to, spender, ..
+        } => owner == account || to == account || spender == account,
+        // Vesting events
+        Event::VestingCreated { from, to, .. } => from == account || to == account,
+        Event::VestedClaimed { player, .. } => player == account,
+        // Staking reward events
+        Event::StakingRewardsIssued { .. } => true,
+        // Treasury events
+        Event::TreasuryFunded { .. } | Event::BuybackExecuted { .. } => true,
+        // Game rule changes are public
+        Event::GameConfigUpdated { .. } => true,
+        // Game statistics are public
+        Event::GameStatsUpdated { .. } => true,
+        // Economy parameter changes are public
+        Event::EconomyParamsUpdated { .. } => true,
+        // Admin keys and house settings are public
+        Event::AdminSet { .. } | Event::HouseSettingsUpdated { .. } => true,
+        // Bonus events
+        Event::BonusGranted { player, .. } | Event::BonusReleased { player, .. } => {
+            player == account
+        }
         // Tournament events
         Event::TournamentStarted { .. } => true, // Tournament start is public
         Event::PlayerJoined { player, .. } => player == account,
         Event::TournamentPhaseChanged { .. } => true, // Phase changes are public
+        Event::TournamentScheduled { .. } => true,    // Schedules are public
+        Event::TournamentRulesSet { .. } => true,     // Tournament rules are public
+        Event::TournamentPrizePaid { player, .. } => player == account,
+        Event::LeaderboardSeasonEnded { .. } => true, // Season rollovers are public
+        Event::SeasonRewardPaid { player, .. } => player == account,
+        Event::LoyaltyEarned { player, .. } => player == account,
+        Event::TierChanged { player, .. } => player == account,
         Event::TournamentEnded { rankings, .. } => {
             // Check if account is in the rankings
             rankings.iter().any(|(player, _)| player == account)
@@ -1439,16 +1890,32 @@ fn is_event_relevant_to_account(event: &Event, account: &PublicKey) -> bool {
         // Liquidity / Vault events
         Event::VaultCreated { player } => player == account,
         Event::CollateralDeposited { player, .. } => player == account,
+        Event::CollateralWithdrawn { player, .. } => player == account,
         Event::VusdtBorrowed { player, .. } => player == account,
         Event::VusdtRepaid { player, .. } => player == account,
         Event::AmmSwapped { player, .. } => player == account,
         Event::LiquidityAdded { player, .. } => player == account,
         Event::LiquidityRemoved { player, .. } => player == account,
+        Event::LimitOrderPlaced { player, .. } => player == account,
+        Event::LimitOrderFilled { player, .. } => player == account,
+        Event::LimitOrderCancelled { player, .. } => player == account,
+        Event::LiquidityZapped { player, .. } => player == account,
+        Event::Slashed { player, .. } => player == account,
+        Event::DailyBonusClaimed { player, .. } => player == account,
+        Event::ProfileUpdated { player, .. } => player == account,
+        Event::KycStatusChanged { player, .. } => player == account,
         // Staking events
         Event::Staked { player, .. } => player == account,
         Event::Unstaked { player, .. } => player == account,
         Event::RewardsClaimed { player, .. } => player == account,
         Event::EpochProcessed { .. } => true,
+        Event::InsuranceFunded { .. } => true, // House accounting is public
+        Event::InsuranceDrawn { .. } => true,
+        // Fee events
+        Event::FeeCharged { player, .. } => player == account,
+        Event::InterestAccrued { player, .. } => player == account,
+        // Events this release can't decode
+        Event::Unknown { .. } => false,
     }
 }
 
@@ -1529,6 +1996,35 @@ mod tests {
         assert_eq!(received_tx.nonce, tx.nonce);
     }
 
+    #[tokio::test]
+    async fn test_submit_rejections() {
+        let (_, network_identity) = create_network_keypair();
+        let simulator = Simulator::new(network_identity);
+
+        let (private, _) = create_account_keypair(1);
+        let tx = Transaction::sign(&private, 5, Instruction::CasinoDeposit { amount: 1 });
+        let rejection = Rejection::new(&tx, RejectionReason::StaleNonce { next: 7 });
+        simulator.submit_rejections(vec![rejection.clone()]).await;
+        assert_eq!(
+            simulator.query_rejection(&tx.digest()).await,
+            Some(rejection)
+        );
+
+        // Only the most recent rejections are remembered
+        let rejections: Vec<_> = (0..MAX_REJECTIONS as u64)
+            .map(|i| Rejection {
+                digest: Sha256::hash(&i.to_be_bytes()),
+                public: tx.public.clone(),
+                nonce: i,
+                reason: RejectionReason::InvalidSignature,
+            })
+            .collect();
+        for chunk in rejections.chunks(1024) {
+            simulator.submit_rejections(chunk.to_vec()).await;
+        }
+        assert_eq!(simulator.query_rejection(&tx.digest()).await, None);
+    }
+
     #[test]
     fn test_submit_summary() {
         let executor = Runner::default();
@@ -1819,6 +2315,28 @@ mod tests {
                 let expected_nonce = if i < 3 { 2 } else { 1 };
                 assert_eq!(account.nonce, expected_nonce);
             }
+
+            // Historical queries return the version as of the requested height
+            for (i, (_, public)) in accounts.iter().enumerate() {
+                let account_key = Sha256::hash(&Key::Account(public.clone()).encode());
+                assert!(simulator.query_state_at(&account_key, 0).await.is_none());
+
+                let lookup = simulator.query_state_at(&account_key, 1).await.unwrap();
+                lookup.verify(&network_identity).unwrap();
+                assert_eq!(lookup.progress.height, 1);
+                let Variable::Update(_, Value::Account(account)) = lookup.operation else {
+                    panic!("Account not found for {public:?}");
+                };
+                assert_eq!(account.nonce, 1);
+
+                let lookup = simulator.query_state_at(&account_key, 2).await.unwrap();
+                lookup.verify(&network_identity).unwrap();
+                let Variable::Update(_, Value::Account(account)) = lookup.operation else {
+                    panic!("Account not found for {public:?}");
+                };
+                let expected_nonce = if i < 3 { 2 } else { 1 };
+                assert_eq!(account.nonce, expected_nonce);
+            }
         });
     }
 }
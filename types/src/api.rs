@@ -14,9 +14,18 @@ use commonware_storage::{
 /// Maximum number of transactions that can be submitted in a single submission
 pub const MAX_SUBMISSION_TRANSACTIONS: usize = 128;
 
+/// Maximum number of summaries that can be submitted in a single submission
+pub const MAX_SUBMISSION_SUMMARIES: usize = 32;
+
 const MAX_PROOF_NODES: usize = 500;
 const MAX_PROOF_OPS: usize = 500;
 
+/// Number of items in a page when the request doesn't ask for a limit.
+pub const DEFAULT_PAGE_LIMIT: usize = 20;
+
+/// Maximum number of items in a page.
+pub const MAX_PAGE_LIMIT: usize = 200;
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum VerifyError {
@@ -514,6 +523,10 @@ pub enum Submission {
     Seed(Seed),
     Transactions(Vec<Transaction>),
     Summary(Summary),
+    /// Summaries of consecutive heights (uploaded together when catching up).
+    Summaries(Vec<Summary>),
+    /// Pending transactions a validator refused to admit to its mempool.
+    Rejections(Vec<Rejection>),
 }
 
 impl Write for Submission {
@@ -531,6 +544,14 @@ impl Write for Submission {
                 2u8.write(writer);
                 summary.write(writer);
             }
+            Submission::Summaries(summaries) => {
+                3u8.write(writer);
+                summaries.write(writer);
+            }
+            Submission::Rejections(rejections) => {
+                4u8.write(writer);
+                rejections.write(writer);
+            }
         }
     }
 }
@@ -547,6 +568,14 @@ impl Read for Submission {
                 1..=MAX_SUBMISSION_TRANSACTIONS,
             )?)),
             2 => Ok(Submission::Summary(Summary::read(reader)?)),
+            3 => Ok(Submission::Summaries(Vec::read_range(
+                reader,
+                1..=MAX_SUBMISSION_SUMMARIES,
+            )?)),
+            4 => Ok(Submission::Rejections(Vec::read_range(
+                reader,
+                1..=MAX_SUBMISSION_TRANSACTIONS,
+            )?)),
             _ => Err(Error::InvalidEnum(kind)),
         }
     }
@@ -558,6 +587,8 @@ impl EncodeSize for Submission {
             Submission::Seed(seed) => seed.encode_size(),
             Submission::Transactions(txs) => txs.encode_size(),
             Submission::Summary(summary) => summary.encode_size(),
+            Submission::Summaries(summaries) => summaries.encode_size(),
+            Submission::Rejections(rejections) => rejections.encode_size(),
         }
     }
 }
@@ -631,3 +662,381 @@ impl EncodeSize for Pending {
         self.transactions.encode_size()
     }
 }
+
+/// Why a validator refused to admit a pending transaction to its mempool.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RejectionReason {
+    /// The signature is invalid.
+    InvalidSignature,
+    /// The nonce was already used (`next` is the account's next nonce).
+    StaleNonce { next: u64 },
+    /// The nonce is too far ahead of the account's next nonce (`next`) to be included.
+    FutureNonce { next: u64 },
+    /// The transaction uses more compute units (`units`) than a transaction may.
+    ComputeLimitExceeded { units: u64 },
+}
+
+impl Write for RejectionReason {
+    fn write(&self, writer: &mut impl BufMut) {
+        match self {
+            RejectionReason::InvalidSignature => 0u8.write(writer),
+            RejectionReason::StaleNonce { next } => {
+                1u8.write(writer);
+                next.write(writer);
+            }
+            RejectionReason::FutureNonce { next } => {
+                2u8.write(writer);
+                next.write(writer);
+            }
+            RejectionReason::ComputeLimitExceeded { units } => {
+                3u8.write(writer);
+                units.write(writer);
+            }
+        }
+    }
+}
+
+impl Read for RejectionReason {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        let kind = u8::read(reader)?;
+        match kind {
+            0 => Ok(RejectionReason::InvalidSignature),
+            1 => Ok(RejectionReason::StaleNonce {
+                next: u64::read(reader)?,
+            }),
+            2 => Ok(RejectionReason::FutureNonce {
+                next: u64::read(reader)?,
+            }),
+            3 => Ok(RejectionReason::ComputeLimitExceeded {
+                units: u64::read(reader)?,
+            }),
+            _ => Err(Error::InvalidEnum(kind)),
+        }
+    }
+}
+
+impl EncodeSize for RejectionReason {
+    fn encode_size(&self) -> usize {
+        1 + match self {
+            RejectionReason::InvalidSignature => 0,
+            RejectionReason::StaleNonce { next } | RejectionReason::FutureNonce { next } => {
+                next.encode_size()
+            }
+            RejectionReason::ComputeLimitExceeded { units } => units.encode_size(),
+        }
+    }
+}
+
+/// A pending transaction a validator refused to admit to its mempool.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rejection {
+    pub digest: Digest,
+    pub public: PublicKey,
+    pub nonce: u64,
+    pub reason: RejectionReason,
+}
+
+impl Rejection {
+    pub fn new(tx: &Transaction, reason: RejectionReason) -> Self {
+        Self {
+            digest: tx.digest(),
+            public: tx.public.clone(),
+            nonce: tx.nonce,
+            reason,
+        }
+    }
+}
+
+impl Write for Rejection {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.digest.write(writer);
+        self.public.write(writer);
+        self.nonce.write(writer);
+        self.reason.write(writer);
+    }
+}
+
+impl Read for Rejection {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            digest: Digest::read(reader)?,
+            public: PublicKey::read(reader)?,
+            nonce: u64::read(reader)?,
+            reason: RejectionReason::read(reader)?,
+        })
+    }
+}
+
+impl EncodeSize for Rejection {
+    fn encode_size(&self) -> usize {
+        self.digest.encode_size()
+            + self.public.encode_size()
+            + self.nonce.encode_size()
+            + self.reason.encode_size()
+    }
+}
+
+/// A request sent by a light peer to a validator over p2p.
+///
+/// `id` is echoed in the [LightResponse] (so a peer can have multiple requests outstanding).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LightRequest {
+    /// Prove the value of a state key (the hash of an encoded [crate::execution::Key]).
+    Lookup { id: u64, key: Digest },
+    /// Fetch the seed for a view.
+    Seed { id: u64, view: u64 },
+}
+
+impl LightRequest {
+    pub fn id(&self) -> u64 {
+        match self {
+            LightRequest::Lookup { id, .. } | LightRequest::Seed { id, .. } => *id,
+        }
+    }
+}
+
+impl Write for LightRequest {
+    fn write(&self, writer: &mut impl BufMut) {
+        match self {
+            LightRequest::Lookup { id, key } => {
+                0u8.write(writer);
+                id.write(writer);
+                key.write(writer);
+            }
+            LightRequest::Seed { id, view } => {
+                1u8.write(writer);
+                id.write(writer);
+                view.write(writer);
+            }
+        }
+    }
+}
+
+impl Read for LightRequest {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        let kind = u8::read(reader)?;
+        match kind {
+            0 => Ok(LightRequest::Lookup {
+                id: u64::read(reader)?,
+                key: Digest::read(reader)?,
+            }),
+            1 => Ok(LightRequest::Seed {
+                id: u64::read(reader)?,
+                view: u64::read(reader)?,
+            }),
+            _ => Err(Error::InvalidEnum(kind)),
+        }
+    }
+}
+
+impl EncodeSize for LightRequest {
+    fn encode_size(&self) -> usize {
+        1 + match self {
+            LightRequest::Lookup { id, key } => id.encode_size() + key.encode_size(),
+            LightRequest::Seed { id, view } => id.encode_size() + view.encode_size(),
+        }
+    }
+}
+
+/// A validator's response to a [LightRequest].
+///
+/// Responses are verifiable against the network [Identity] (via [Lookup::verify] and
+/// [Seed::verify]), so a light peer need not trust the validator serving them.
+#[allow(clippy::large_enum_variant)]
+pub enum LightResponse {
+    Lookup {
+        id: u64,
+        lookup: Lookup,
+    },
+    Seed {
+        id: u64,
+        seed: Seed,
+    },
+    /// The validator can't serve the request (the key doesn't exist, its latest value is
+    /// not yet certified, or the seed is not stored).
+    Unavailable {
+        id: u64,
+    },
+}
+
+impl LightResponse {
+    pub fn id(&self) -> u64 {
+        match self {
+            LightResponse::Lookup { id, .. }
+            | LightResponse::Seed { id, .. }
+            | LightResponse::Unavailable { id } => *id,
+        }
+    }
+}
+
+impl Write for LightResponse {
+    fn write(&self, writer: &mut impl BufMut) {
+        match self {
+            LightResponse::Lookup { id, lookup } => {
+                0u8.write(writer);
+                id.write(writer);
+                lookup.write(writer);
+            }
+            LightResponse::Seed { id, seed } => {
+                1u8.write(writer);
+                id.write(writer);
+                seed.write(writer);
+            }
+            LightResponse::Unavailable { id } => {
+                2u8.write(writer);
+                id.write(writer);
+            }
+        }
+    }
+}
+
+impl Read for LightResponse {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        let kind = u8::read(reader)?;
+        match kind {
+            0 => Ok(LightResponse::Lookup {
+                id: u64::read(reader)?,
+                lookup: Lookup::read(reader)?,
+            }),
+            1 => Ok(LightResponse::Seed {
+                id: u64::read(reader)?,
+                seed: Seed::read(reader)?,
+            }),
+            2 => Ok(LightResponse::Unavailable {
+                id: u64::read(reader)?,
+            }),
+            _ => Err(Error::InvalidEnum(kind)),
+        }
+    }
+}
+
+impl EncodeSize for LightResponse {
+    fn encode_size(&self) -> usize {
+        1 + match self {
+            LightResponse::Lookup { id, lookup } => id.encode_size() + lookup.encode_size(),
+            LightResponse::Seed { id, seed } => id.encode_size() + seed.encode_size(),
+            LightResponse::Unavailable { id } => id.encode_size(),
+        }
+    }
+}
+
+/// An opaque position in a paginated listing.
+///
+/// Each page carries the cursor of the next one, which clients pass back unchanged (as the hex
+/// string it is displayed as). What the position means is up to the listing: the height of a
+/// block, the index of a transaction, and so on.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Cursor(u64);
+
+impl Cursor {
+    pub fn new(position: u64) -> Self {
+        Self(position)
+    }
+
+    pub fn position(&self) -> u64 {
+        self.0
+    }
+
+    /// Parse a cursor from the string it is displayed as.
+    pub fn parse(cursor: &str) -> Option<Self> {
+        let raw: [u8; 8] = commonware_utils::from_hex(cursor)?.try_into().ok()?;
+        Some(Self(u64::from_be_bytes(raw)))
+    }
+}
+
+impl std::fmt::Display for Cursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&commonware_utils::hex(&self.0.to_be_bytes()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Cursor {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Cursor {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let cursor = String::deserialize(deserializer)?;
+        Self::parse(&cursor).ok_or_else(|| serde::de::Error::custom("invalid cursor"))
+    }
+}
+
+/// A request for a page of a listing (the query string of paginated endpoints).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageRequest {
+    /// Where the page starts (the start of the listing if absent).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub cursor: Option<Cursor>,
+    /// Items requested (see [PageRequest::limit]).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub limit: Option<usize>,
+}
+
+impl PageRequest {
+    /// Items in the page: the limit requested ([DEFAULT_PAGE_LIMIT] if none), at least 1 and
+    /// at most [MAX_PAGE_LIMIT].
+    pub fn limit(&self) -> usize {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_LIMIT)
+            .clamp(1, MAX_PAGE_LIMIT)
+    }
+
+    /// The request as a URL query string (empty for the first page with the default limit).
+    pub fn to_query(&self) -> String {
+        let mut params = Vec::new();
+        if let Some(cursor) = self.cursor {
+            params.push(format!("cursor={cursor}"));
+        }
+        if let Some(limit) = self.limit {
+            params.push(format!("limit={limit}"));
+        }
+        params.join("&")
+    }
+
+    /// The page of a listing of `total` items, given the items from the cursor onwards (each
+    /// with its position).
+    pub fn page<T>(
+        &self,
+        items: impl IntoIterator<Item = (u64, T)>,
+        total: usize,
+    ) -> PageResponse<T> {
+        let limit = self.limit();
+        let mut items = items.into_iter();
+        let page = items.by_ref().take(limit).map(|(_, item)| item).collect();
+        PageResponse {
+            items: page,
+            next_cursor: items.next().map(|(position, _)| Cursor::new(position)),
+            total,
+        }
+    }
+}
+
+/// A page of a listing.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageResponse<T> {
+    pub items: Vec<T>,
+    /// Where the next page starts (none if this is the last page).
+    pub next_cursor: Option<Cursor>,
+    /// Items in the whole listing.
+    pub total: usize,
+}
@@ -1,6 +1,15 @@
 /// Maximum name length for player registration
 pub const MAX_NAME_LENGTH: usize = 32;
 
+/// Maximum profile title length
+pub const MAX_TITLE_LENGTH: usize = 32;
+
+/// Maximum profile avatar id
+pub const MAX_AVATAR_ID: u32 = 1_023;
+
+/// Minimum number of views between profile updates (1,200 views ≈ 1 hour at 3s/view)
+pub const PROFILE_UPDATE_COOLDOWN: u64 = 1_200;
+
 /// Maximum payload length for game moves
 pub const MAX_PAYLOAD_LENGTH: usize = 256;
 
@@ -13,18 +22,179 @@ pub const STARTING_SHIELDS: u32 = 3;
 /// Starting doubles per tournament
 pub const STARTING_DOUBLES: u32 = 3;
 
-/// Game session expiry in blocks
+/// Views without a move after which a game session can be abandoned
 pub const SESSION_EXPIRY: u64 = 100;
 
-/// Faucet deposit amount (dev mode only)
+/// Domain separator for session IDs (see [super::derive_session_id])
+pub const SESSION_ID_NAMESPACE: &[u8] = b"_SUPERSOCIETY_SESSION";
+
+/// Default faucet claim amount (dev mode only, see [super::EconomyParams])
 pub const FAUCET_AMOUNT: u64 = 1_000;
 
 /// Faucet rate limit in blocks (100 blocks ≈ 5 minutes at 3s/block)
 pub const FAUCET_RATE_LIMIT: u64 = 100;
 
-/// Initial chips granted on registration
+/// Default daily bonus (in chips, before the streak multiplier)
+pub const DAILY_BONUS_AMOUNT: u64 = 1_000;
+
+/// Default number of views between daily bonus claims (28,800 views ≈ 1 day at 3s/view)
+pub const DAILY_BONUS_COOLDOWN: u64 = 28_800;
+
+/// Default bonus added per consecutive daily claim (basis points of the base amount)
+pub const DAILY_BONUS_STREAK_BPS: u64 = 1_000;
+
+/// Default streak at which the daily bonus stops growing
+pub const DAILY_BONUS_MAX_STREAK: u32 = 7;
+
+/// Default chips granted on registration (see [super::EconomyParams])
 pub const INITIAL_CHIPS: u64 = 1_000;
 
+/// Default fee (in chips) charged per instruction (credited to the house)
+pub const TRANSACTION_FEE: u64 = 1;
+
+/// Maximum number of per-instruction overrides in a fee schedule
+pub const MAX_FEE_OVERRIDES: usize = 32;
+
+/// Default interest (stability fee) charged on vUSDT debt per epoch (basis points)
+pub const DEBT_INTEREST_RATE_BPS: u64 = 10;
+
+/// Initial value of the debt index (interest accrued is tracked as growth from this value)
+pub const DEBT_INDEX_SCALE: u128 = 1_000_000_000_000;
+
+/// Views per epoch (short for testing; ~1 week would be 201_600 at 3s/view)
+pub const EPOCH_LENGTH: u64 = 100;
+
+/// Views per year (at 3s/view), used to convert annual staking rates to epochs
+pub const VIEWS_PER_YEAR: u64 = 365 * 24 * 60 * 20;
+
+/// Default annual staking reward rate at the first epoch (basis points of the total staked)
+pub const DEFAULT_REWARD_APR_BPS: u64 = 1_000;
+
+/// Default reduction of the staking reward rate per epoch (basis points of the previous rate)
+pub const DEFAULT_REWARD_DECAY_BPS: u16 = 0;
+
+/// Default annual staking reward rate the decay stops at (basis points)
+pub const DEFAULT_REWARD_FLOOR_APR_BPS: u64 = 0;
+
+/// Scale of the staking reward index (rewards earned per unit of voting power)
+pub const REWARD_INDEX_SCALE: u128 = 1_000_000_000_000_000_000;
+
+/// Scale of oracle prices (a price of 1 vUSDT per RNG is `PRICE_SCALE`)
+pub const PRICE_SCALE: u128 = 1_000_000_000_000;
+
+/// Maximum number of AMM pools (tracked in the pool registry)
+pub const MAX_AMM_POOLS: usize = 256;
+
+/// Maximum number of open limit orders
+pub const MAX_OPEN_LIMIT_ORDERS: usize = 256;
+
+/// Maximum number of stake tranches per staker
+pub const MAX_STAKE_TRANCHES: usize = 32;
+
+/// Maximum number of unclaimed vesting schedules per beneficiary
+pub const MAX_VESTING_SCHEDULES: usize = 16;
+
+/// Maximum number of game sessions a player can have open at once (the house limit is capped
+/// at this)
+pub const MAX_ACTIVE_SESSIONS: usize = 16;
+
+/// Default house limit on concurrent game sessions per player
+pub const DEFAULT_MAX_ACTIVE_SESSIONS: u32 = 4;
+
+/// Maximum number of per-game bet limits
+pub const MAX_BET_LIMITS: usize = 16;
+
+/// Default maximum bet (for games without a specific limit)
+pub const DEFAULT_MAX_BET: u64 = 100_000;
+
+/// Default maximum total wagered in a single game session
+pub const DEFAULT_MAX_EXPOSURE: u64 = 1_000_000;
+
+/// Number of completed games kept in each player's history
+pub const GAME_HISTORY_LENGTH: usize = 32;
+
+/// Views a completed game session is kept in state before it is deleted (its result remains in
+/// the player's history)
+pub const SESSION_RETENTION: u64 = 1_000;
+
+/// Maximum number of views and sessions the reclamation of completed sessions older than
+/// `SESSION_RETENTION` visits per block (any left over are reclaimed in later blocks)
+pub const SESSION_RECLAIM_LIMIT: usize = 256;
+
+/// Maximum number of sessions completed at a single view (each transaction completes at most
+/// one session)
+pub const MAX_SESSION_EXPIRIES: usize = crate::execution::MAX_BLOCK_TRANSACTIONS;
+
+/// Completed sessions of a game between reports of its statistics
+pub const GAME_STATS_INTERVAL: u64 = 100;
+
+/// Maximum number of guardians that can recover an account
+pub const MAX_GUARDIANS: usize = 8;
+
+/// Views between guardians approving a recovery and it being executable (~2 days at 3s/view),
+/// giving the owner time to cancel it
+pub const RECOVERY_DELAY: u64 = 2 * 24 * 60 * 20;
+
+/// Default length of a leaderboard season in views (~1 week at 3s/view)
+pub const DEFAULT_SEASON_LENGTH: u64 = 7 * 24 * 60 * 20;
+
+/// Default chips minted (from the reward pool) for the top players of each season
+pub const DEFAULT_SEASON_REWARD: u64 = 10_000;
+
+/// Default maximum change in a pool's price from a single swap (basis points): 20%
+pub const DEFAULT_MAX_PRICE_IMPACT_BPS: u16 = 2_000;
+
+/// Default swap fee of new AMM pools (basis points): 0.3%
+pub const DEFAULT_AMM_FEE_BPS: u16 = 30;
+
+/// Default LP shares locked on a pool's first deposit (so it can never be fully drained)
+pub const MINIMUM_LIQUIDITY: u64 = 1_000;
+
+/// Default super mode fee (basis points of the amount wagered): 20%
+pub const SUPER_MODE_FEE_BPS: u16 = 2_000;
+
+/// Default share of fees and sell tax paid into the insurance fund (basis points): 10%
+pub const DEFAULT_INSURANCE_FUNDING_BPS: u16 = 1_000;
+
+/// Default house loss absorbed before the insurance fund is drawn on
+pub const DEFAULT_INSURANCE_THRESHOLD: u64 = 100_000;
+
+/// Default share of each epoch's profit paid into the treasury (basis points): 10%
+pub const DEFAULT_TREASURY_SHARE_BPS: u16 = 1_000;
+
+/// Maximum number of VIP tiers
+pub const MAX_VIP_TIERS: usize = 8;
+
+/// Default loyalty points earned per chip wagered (basis points): 1 point per 100 chips
+pub const DEFAULT_LOYALTY_POINTS_BPS: u16 = 100;
+
+/// Default VIP tiers: (loyalty points required, rakeback in basis points, shields granted)
+pub const DEFAULT_VIP_TIERS: [(u64, u16, u32); 4] = [
+    (100, 50, 1),
+    (1_000, 100, 2),
+    (10_000, 200, 3),
+    (100_000, 300, 5),
+];
+
+/// Default wagering requirement of a bonus, as a multiple of the bonus
+pub const DEFAULT_BONUS_WAGERING_MULTIPLIER: u16 = 20;
+
+/// Default share of a wager counted towards bonus requirements (basis points): 100%
+pub const DEFAULT_BONUS_CONTRIBUTION_BPS: u16 = 10_000;
+
+/// Default share counted for low edge games (blackjack, baccarat, video poker): 10%
+pub const LOW_EDGE_BONUS_CONTRIBUTION_BPS: u16 = 1_000;
+
+/// Maximum number of per-game bonus contributions
+pub const MAX_BONUS_CONTRIBUTIONS: usize = 16;
+
+/// Minimum number of views between oracle checkpoints
+pub const ORACLE_CHECKPOINT_INTERVAL: u64 = 10;
+
+/// Number of oracle checkpoints retained (the TWAP covers roughly
+/// `ORACLE_CHECKPOINTS * ORACLE_CHECKPOINT_INTERVAL` views)
+pub const ORACLE_CHECKPOINTS: usize = 10;
+
 /// Tokenomics Constants
 pub const TOTAL_SUPPLY: u64 = 1_000_000_000;
 /// Annual emission rate (basis points) used for freeroll tournament prizes.
@@ -39,8 +209,51 @@ pub const TOURNAMENTS_PER_DAY: u64 = 240;
 // Progressive base jackpots (chip-denominated; meters, if enabled, reset to these values).
 pub const THREE_CARD_PROGRESSIVE_BASE_JACKPOT: u64 = 10_000;
 pub const UTH_PROGRESSIVE_BASE_JACKPOT: u64 = 10_000;
+pub const CARIBBEAN_STUD_PROGRESSIVE_BASE_JACKPOT: u64 = 10_000;
+
+/// Most hands a blackjack player can hold by splitting (tables can be configured to fewer).
+pub const BLACKJACK_MAX_HANDS: u8 = 4;
+
+/// Decks in a persistent blackjack or baccarat shoe (tables can keep one across rounds).
+pub const MIN_SHOE_DECKS: u8 = 6;
+pub const MAX_SHOE_DECKS: u8 = 8;
+
+/// Percent of a persistent shoe dealt before the cut card comes out and it is reshuffled.
+pub const MIN_SHOE_PENETRATION: u8 = 50;
+pub const MAX_SHOE_PENETRATION: u8 = 90;
+pub const DEFAULT_SHOE_PENETRATION: u8 = 75;
+
+/// Most draws reported by a single RNG transcript event.
+pub const MAX_RNG_TRANSCRIPT_DRAWS: usize = 256;
 
-/// Error codes for CasinoError events
+/// Most bets a craps session holds (and so a single roll can resolve).
+pub const MAX_CRAPS_BETS: usize = 20;
+
+/// Most rounds a single auto-play move resolves.
+pub const MAX_AUTO_PLAY_ROUNDS: u8 = 100;
+
+// Four Card Poker pay tables (to 1), indexed by hand rank: high card, pair, two pair, straight,
+// flush, three of a kind, straight flush, four of a kind.
+/// Ante bonus, paid whenever the player plays (whether or not they beat the dealer).
+pub const FOUR_CARD_ANTE_BONUS_PAYTABLE: [u64; 8] = [0, 0, 0, 0, 0, 2, 40, 25];
+/// Aces Up side bet (a pair only pays when it is a pair of Aces).
+pub const FOUR_CARD_ACES_UP_PAYTABLE: [u64; 8] = [0, 1, 2, 4, 6, 9, 40, 50];
+/// Largest Play bet, as a multiple of the ante.
+pub const FOUR_CARD_MAX_PLAY_MULTIPLIER: u8 = 3;
+
+// Baccarat side bet pay tables (to 1).
+/// Player Pair / Banker Pair: the hand's first two cards share a rank.
+pub const BACCARAT_PAIR_PAYOUT: u64 = 11;
+/// Perfect Pair: either hand's first two cards are the same card (rank and suit).
+pub const BACCARAT_PERFECT_PAIR_PAYOUT: u64 = 25;
+/// Perfect Pair when both hands hold one.
+pub const BACCARAT_PERFECT_PAIR_BOTH_PAYOUT: u64 = 200;
+/// Dragon Bonus on a natural win (a natural tie pushes).
+pub const BACCARAT_DRAGON_NATURAL_PAYOUT: u64 = 1;
+/// Dragon Bonus on a non-natural win, indexed by the winning margin (wins by 3 or less lose).
+pub const BACCARAT_DRAGON_BONUS_PAYTABLE: [u64; 10] = [0, 0, 0, 0, 1, 2, 4, 6, 10, 30];
+
+/// Error codes for CasinoError and InstructionFailed events (see [super::CasinoErrorCode])
 pub const ERROR_PLAYER_ALREADY_REGISTERED: u8 = 1;
 pub const ERROR_PLAYER_NOT_FOUND: u8 = 2;
 pub const ERROR_INSUFFICIENT_FUNDS: u8 = 3;
@@ -54,6 +267,32 @@ pub const ERROR_RATE_LIMITED: u8 = 10;
 pub const ERROR_TOURNAMENT_NOT_REGISTERING: u8 = 11;
 pub const ERROR_ALREADY_IN_TOURNAMENT: u8 = 12;
 pub const ERROR_TOURNAMENT_LIMIT_REACHED: u8 = 13;
+pub const ERROR_SESSION_LIMIT_REACHED: u8 = 14;
+pub const ERROR_BET_LIMIT_EXCEEDED: u8 = 15;
+pub const ERROR_EXPOSURE_LIMIT_EXCEEDED: u8 = 16;
+pub const ERROR_UNAUTHORIZED: u8 = 17;
+pub const ERROR_KYC_REQUIRED: u8 = 18;
+pub const ERROR_INVALID_AMOUNT: u8 = 19;
+pub const ERROR_NOT_FOUND: u8 = 20;
+pub const ERROR_INVALID_GUARDIANS: u8 = 21;
+pub const ERROR_ALLOWANCE_EXCEEDED: u8 = 22;
+pub const ERROR_VESTING_LIMIT_REACHED: u8 = 23;
+pub const ERROR_INVALID_REVEAL: u8 = 24;
+pub const ERROR_INVALID_GAME_CONFIG: u8 = 25;
+pub const ERROR_GAME_DISABLED: u8 = 26;
+pub const ERROR_INVALID_SESSION_ID: u8 = 27;
 
 /// Tournament duration in seconds (5 minutes)
 pub const TOURNAMENT_DURATION_SECS: u64 = 5 * 60;
+
+/// Maximum number of scheduled tournaments that haven't completed yet
+pub const MAX_SCHEDULED_TOURNAMENTS: usize = 64;
+
+/// Maximum number of paid places in a tournament payout table
+pub const MAX_PAYOUT_PLACES: usize = 16;
+
+/// Maximum number of games listed by a tournament's rules (disabled games or rule overrides)
+pub const MAX_TOURNAMENT_RULE_GAMES: usize = 32;
+
+/// Default share of the prize pool paid to each place (basis points): 50/30/20
+pub const DEFAULT_TOURNAMENT_PAYOUTS_BPS: [u16; 3] = [5000, 3000, 2000];
@@ -1,10 +1,934 @@
 use bytes::{Buf, BufMut};
-use commonware_codec::{EncodeSize, Error, FixedSize, Read, ReadExt, Write};
+use commonware_codec::{EncodeSize, Error, FixedSize, Read, ReadExt, ReadRangeExt, Write};
+use commonware_cryptography::ed25519::PublicKey;
 
-use super::{THREE_CARD_PROGRESSIVE_BASE_JACKPOT, UTH_PROGRESSIVE_BASE_JACKPOT};
+use super::{
+    GameType, TournamentPayouts, CARIBBEAN_STUD_PROGRESSIVE_BASE_JACKPOT, DAILY_BONUS_AMOUNT,
+    DAILY_BONUS_COOLDOWN, DAILY_BONUS_MAX_STREAK, DAILY_BONUS_STREAK_BPS, DEBT_INDEX_SCALE,
+    DEBT_INTEREST_RATE_BPS, DEFAULT_AMM_FEE_BPS, DEFAULT_BONUS_CONTRIBUTION_BPS,
+    DEFAULT_BONUS_WAGERING_MULTIPLIER, DEFAULT_INSURANCE_FUNDING_BPS, DEFAULT_INSURANCE_THRESHOLD,
+    DEFAULT_LOYALTY_POINTS_BPS, DEFAULT_MAX_ACTIVE_SESSIONS, DEFAULT_MAX_BET, DEFAULT_MAX_EXPOSURE,
+    DEFAULT_MAX_PRICE_IMPACT_BPS, DEFAULT_REWARD_APR_BPS, DEFAULT_REWARD_DECAY_BPS,
+    DEFAULT_REWARD_FLOOR_APR_BPS, DEFAULT_SEASON_LENGTH, DEFAULT_SEASON_REWARD,
+    DEFAULT_TREASURY_SHARE_BPS, DEFAULT_VIP_TIERS, EPOCH_LENGTH, FAUCET_AMOUNT, INITIAL_CHIPS,
+    LOW_EDGE_BONUS_CONTRIBUTION_BPS, MAX_AMM_POOLS, MAX_BET_LIMITS, MAX_BONUS_CONTRIBUTIONS,
+    MAX_FEE_OVERRIDES, MAX_STAKE_TRANCHES, MAX_VIP_TIERS, MINIMUM_LIQUIDITY, ORACLE_CHECKPOINTS,
+    ORACLE_CHECKPOINT_INTERVAL, PRICE_SCALE, REWARD_INDEX_SCALE, SUPER_MODE_FEE_BPS,
+    THREE_CARD_PROGRESSIVE_BASE_JACKPOT, TRANSACTION_FEE, UTH_PROGRESSIVE_BASE_JACKPOT,
+    VIEWS_PER_YEAR,
+};
+use crate::execution::{tags, Instruction};
+use std::fmt;
+
+macro_rules! amount {
+    ($(#[$doc:meta])* $name:ident, $unit:literal) => {
+        $(#[$doc])*
+        ///
+        /// Arithmetic is checked (returning `None` on overflow or underflow) so mistakes surface
+        /// instead of being clamped away. Encoded as a `u64`.
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(transparent)
+        )]
+        pub struct $name(pub u64);
+
+        impl $name {
+            pub const ZERO: Self = Self(0);
+
+            pub fn is_zero(self) -> bool {
+                self.0 == 0
+            }
+
+            pub fn checked_add(self, other: Self) -> Option<Self> {
+                self.0.checked_add(other.0).map(Self)
+            }
+
+            pub fn checked_sub(self, other: Self) -> Option<Self> {
+                self.0.checked_sub(other.0).map(Self)
+            }
+
+            /// `self * numerator / denominator` (rounded down), if `denominator` isn't zero and
+            /// the result fits.
+            pub fn checked_mul_div(self, numerator: u128, denominator: u128) -> Option<Self> {
+                let product = (self.0 as u128).checked_mul(numerator)?;
+                product
+                    .checked_div(denominator)
+                    .and_then(|amount| u64::try_from(amount).ok())
+                    .map(Self)
+            }
+
+            /// Add `other`, clamping at the maximum. Only for credits that can't be rejected
+            /// (such as the payout of a game that already settled); prefer
+            /// [Self::checked_add].
+            pub fn saturating_add(self, other: Self) -> Self {
+                Self(self.0.saturating_add(other.0))
+            }
+
+            /// The portion of this amount given by `bps` basis points (rounded down), if it
+            /// fits (portions above 100% can overflow).
+            pub fn bps(self, bps: u16) -> Option<Self> {
+                self.checked_mul_div(bps as u128, 10_000)
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(amount: u64) -> Self {
+                Self(amount)
+            }
+        }
+
+        impl From<$name> for u64 {
+            fn from(amount: $name) -> Self {
+                amount.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} {}", self.0, $unit)
+            }
+        }
+
+        impl Write for $name {
+            fn write(&self, writer: &mut impl BufMut) {
+                self.0.write(writer);
+            }
+        }
+
+        impl Read for $name {
+            type Cfg = ();
+
+            fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+                Ok(Self(u64::read(reader)?))
+            }
+        }
+
+        impl FixedSize for $name {
+            const SIZE: usize = u64::SIZE;
+        }
+    };
+}
+
+amount!(
+    /// An amount of RNG (the chips players bet with).
+    Chips,
+    "RNG"
+);
+
+amount!(
+    /// An amount of vUSDT (the stablecoin borrowed against vaults and traded against RNG).
+    Vusdt,
+    "vUSDT"
+);
+
+amount!(
+    /// An amount of AMM liquidity shares.
+    LpShares,
+    "LP"
+);
+
+impl Chips {
+    /// The value of these chips in vUSDT at `price` (RNG in vUSDT, scaled by [PRICE_SCALE]),
+    /// rounded down (if it fits).
+    pub fn to_vusdt(self, price: u128) -> Option<Vusdt> {
+        self.checked_mul_div(price, PRICE_SCALE)
+            .map(|value| Vusdt(value.0))
+    }
+}
+
+impl Vusdt {
+    /// The chips this vUSDT buys at `price` (RNG in vUSDT, scaled by [PRICE_SCALE]), rounded
+    /// down (if the price isn't zero and the result fits).
+    pub fn to_chips(self, price: u128) -> Option<Chips> {
+        self.checked_mul_div(PRICE_SCALE, price)
+            .map(|chips| Chips(chips.0))
+    }
+}
+
+/// Transaction fees (in chips) charged before an instruction is applied.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeSchedule {
+    /// Fee for instructions without an override.
+    pub flat: u64,
+    /// Fees for specific kinds of instructions, keyed by instruction tag.
+    pub overrides: Vec<(u8, u64)>,
+}
+
+impl FeeSchedule {
+    /// The fee for `instruction` (a batch pays the sum of the fees of its instructions).
+    pub fn fee(&self, instruction: &Instruction) -> u64 {
+        if let Instruction::Batch(instructions) = instruction {
+            return instructions
+                .iter()
+                .fold(0u64, |total, i| total.saturating_add(self.fee(i)));
+        }
+        let tag = instruction.tag();
+        self.overrides
+            .iter()
+            .find(|(kind, _)| *kind == tag)
+            .map_or(self.flat, |(_, fee)| *fee)
+    }
+}
+
+impl Default for FeeSchedule {
+    fn default() -> Self {
+        Self {
+            flat: TRANSACTION_FEE,
+            // Registration, the faucet and the daily bonus are free (new or broke players have
+            // no chips to pay with) and epoch processing is a public service
+            overrides: vec![
+                (tags::instruction::CASINO_REGISTER, 0),
+                (tags::instruction::CASINO_DEPOSIT, 0),
+                (tags::instruction::CLAIM_DAILY_BONUS, 0),
+                (tags::instruction::PROCESS_EPOCH, 0),
+            ],
+        }
+    }
+}
+
+impl Write for FeeSchedule {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.flat.write(writer);
+        self.overrides.write(writer);
+    }
+}
+
+impl Read for FeeSchedule {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            flat: u64::read(reader)?,
+            overrides: Vec::<(u8, u64)>::read_range(reader, 0..=MAX_FEE_OVERRIDES)?,
+        })
+    }
+}
+
+impl EncodeSize for FeeSchedule {
+    fn encode_size(&self) -> usize {
+        self.flat.encode_size() + self.overrides.encode_size()
+    }
+}
+
+/// Daily bonus (and faucet) settings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DailyBonus {
+    /// Bonus (in chips) for a claim without a streak.
+    pub amount: u64,
+    /// Views between claims (a claim within twice the cooldown extends the streak).
+    pub cooldown: u64,
+    /// Bonus added per consecutive claim (basis points of `amount`).
+    pub streak_bps: u64,
+    /// Streak at which the bonus stops growing.
+    pub max_streak: u32,
+    /// Whether `CasinoDeposit` (the faucet) is allowed.
+    pub faucet_enabled: bool,
+}
+
+impl DailyBonus {
+    /// The bonus for a claim extending the streak to `streak`.
+    pub fn bonus(&self, streak: u32) -> u64 {
+        let extra = streak.clamp(1, self.max_streak.max(1)) as u128 - 1;
+        let bps = 10_000 + extra * self.streak_bps as u128;
+        (self.amount as u128 * bps / 10_000).min(u64::MAX as u128) as u64
+    }
+}
+
+impl Default for DailyBonus {
+    fn default() -> Self {
+        Self {
+            amount: DAILY_BONUS_AMOUNT,
+            cooldown: DAILY_BONUS_COOLDOWN,
+            streak_bps: DAILY_BONUS_STREAK_BPS,
+            max_streak: DAILY_BONUS_MAX_STREAK,
+            faucet_enabled: true,
+        }
+    }
+}
+
+impl Write for DailyBonus {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.amount.write(writer);
+        self.cooldown.write(writer);
+        self.streak_bps.write(writer);
+        self.max_streak.write(writer);
+        self.faucet_enabled.write(writer);
+    }
+}
+
+impl Read for DailyBonus {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            amount: u64::read(reader)?,
+            cooldown: u64::read(reader)?,
+            streak_bps: u64::read(reader)?,
+            max_streak: u32::read(reader)?,
+            faucet_enabled: bool::read(reader)?,
+        })
+    }
+}
+
+impl FixedSize for DailyBonus {
+    const SIZE: usize = u64::SIZE + u64::SIZE + u64::SIZE + u32::SIZE + bool::SIZE;
+}
+
+/// Limits on wagers (so a single game can't win more than the house can cover).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BetLimits {
+    /// Maximum bet for games without a specific limit.
+    pub default_max_bet: u64,
+    /// Maximum bet for specific games.
+    pub max_bets: Vec<(GameType, u64)>,
+    /// Maximum total wagered in a single session.
+    pub max_exposure: u64,
+}
+
+impl BetLimits {
+    /// The maximum bet (at start or placed by a move) for `game_type`.
+    pub fn max_bet(&self, game_type: GameType) -> u64 {
+        self.max_bets
+            .iter()
+            .find(|(kind, _)| *kind == game_type)
+            .map_or(self.default_max_bet, |(_, max)| *max)
+    }
+}
+
+impl Default for BetLimits {
+    fn default() -> Self {
+        Self {
+            default_max_bet: DEFAULT_MAX_BET,
+            max_bets: Vec::new(),
+            max_exposure: DEFAULT_MAX_EXPOSURE,
+        }
+    }
+}
+
+impl Write for BetLimits {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.default_max_bet.write(writer);
+        self.max_bets.write(writer);
+        self.max_exposure.write(writer);
+    }
+}
+
+impl Read for BetLimits {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            default_max_bet: u64::read(reader)?,
+            max_bets: Vec::<(GameType, u64)>::read_range(reader, 0..=MAX_BET_LIMITS)?,
+            max_exposure: u64::read(reader)?,
+        })
+    }
+}
+
+impl EncodeSize for BetLimits {
+    fn encode_size(&self) -> usize {
+        self.default_max_bet.encode_size()
+            + self.max_bets.encode_size()
+            + self.max_exposure.encode_size()
+    }
+}
+
+/// KYC settings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KycSettings {
+    /// Key allowed to set the KYC status of players (if any).
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::option"))]
+    pub admin: Option<PublicKey>,
+    /// Largest faucet deposit allowed without KYC.
+    pub deposit_limit: u64,
+}
+
+impl Default for KycSettings {
+    fn default() -> Self {
+        Self {
+            admin: None,
+            deposit_limit: u64::MAX,
+        }
+    }
+}
+
+impl Write for KycSettings {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.admin.write(writer);
+        self.deposit_limit.write(writer);
+    }
+}
+
+impl Read for KycSettings {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            admin: Option::<PublicKey>::read(reader)?,
+            deposit_limit: u64::read(reader)?,
+        })
+    }
+}
+
+impl EncodeSize for KycSettings {
+    fn encode_size(&self) -> usize {
+        self.admin.encode_size() + self.deposit_limit.encode_size()
+    }
+}
+
+/// Leaderboard season settings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeasonSettings {
+    /// Length of a season in views (0 disables seasons).
+    pub length: u64,
+    /// Chips minted (from the reward pool) for the top players of each season.
+    pub reward: u64,
+    /// How the reward is split between the top players.
+    pub payouts: TournamentPayouts,
+}
+
+impl Default for SeasonSettings {
+    fn default() -> Self {
+        Self {
+            length: DEFAULT_SEASON_LENGTH,
+            reward: DEFAULT_SEASON_REWARD,
+            payouts: TournamentPayouts::default(),
+        }
+    }
+}
+
+impl Write for SeasonSettings {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.length.write(writer);
+        self.reward.write(writer);
+        self.payouts.write(writer);
+    }
+}
+
+impl Read for SeasonSettings {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            length: u64::read(reader)?,
+            reward: u64::read(reader)?,
+            payouts: TournamentPayouts::read(reader)?,
+        })
+    }
+}
+
+impl EncodeSize for SeasonSettings {
+    fn encode_size(&self) -> usize {
+        self.length.encode_size() + self.reward.encode_size() + self.payouts.encode_size()
+    }
+}
+
+/// A VIP tier (reached once a player has earned enough loyalty points).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VipTier {
+    /// Loyalty points required to reach the tier.
+    pub points: u64,
+    /// Share of lost bets (in basis points) returned to players in the tier.
+    pub rakeback_bps: u16,
+    /// Shields granted when the tier is reached.
+    pub shields: u32,
+}
+
+impl Write for VipTier {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.points.write(writer);
+        self.rakeback_bps.write(writer);
+        self.shields.write(writer);
+    }
+}
+
+impl Read for VipTier {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            points: u64::read(reader)?,
+            rakeback_bps: u16::read(reader)?,
+            shields: u32::read(reader)?,
+        })
+    }
+}
+
+impl FixedSize for VipTier {
+    const SIZE: usize = u64::SIZE + u16::SIZE + u32::SIZE;
+}
+
+/// VIP program settings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VipSettings {
+    /// Loyalty points earned per chip wagered (in basis points).
+    pub points_bps: u16,
+    /// Tiers, in increasing order of points required (tier `n` is `tiers[n - 1]`, tier 0 has
+    /// no perks).
+    pub tiers: Vec<VipTier>,
+}
+
+impl VipSettings {
+    /// Loyalty points earned by wagering `wagered` chips.
+    pub fn points(&self, wagered: u64) -> u64 {
+        (wagered as u128 * self.points_bps as u128 / 10_000) as u64
+    }
+
+    /// The tier reached with `points` loyalty points.
+    pub fn tier(&self, points: u64) -> u8 {
+        self.tiers
+            .iter()
+            .take_while(|tier| tier.points <= points)
+            .count() as u8
+    }
+
+    /// The perks of `tier` (if it isn't tier 0).
+    pub fn perks(&self, tier: u8) -> Option<&VipTier> {
+        (tier as usize)
+            .checked_sub(1)
+            .and_then(|index| self.tiers.get(index))
+    }
+
+    /// Chips returned to a player in `tier` after losing `loss`.
+    pub fn rakeback(&self, tier: u8, loss: u64) -> u64 {
+        self.perks(tier).map_or(0, |perks| {
+            (loss as u128 * perks.rakeback_bps as u128 / 10_000) as u64
+        })
+    }
+}
+
+impl Default for VipSettings {
+    fn default() -> Self {
+        Self {
+            points_bps: DEFAULT_LOYALTY_POINTS_BPS,
+            tiers: DEFAULT_VIP_TIERS
+                .iter()
+                .map(|(points, rakeback_bps, shields)| VipTier {
+                    points: *points,
+                    rakeback_bps: *rakeback_bps,
+                    shields: *shields,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Write for VipSettings {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.points_bps.write(writer);
+        self.tiers.write(writer);
+    }
+}
+
+impl Read for VipSettings {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            points_bps: u16::read(reader)?,
+            tiers: Vec::<VipTier>::read_range(reader, 0..=MAX_VIP_TIERS)?,
+        })
+    }
+}
+
+impl EncodeSize for VipSettings {
+    fn encode_size(&self) -> usize {
+        self.points_bps.encode_size() + self.tiers.encode_size()
+    }
+}
+
+/// Wagering requirements of bonus chips (see [super::BonusBalance]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BonusSettings {
+    /// Chips to wager before a bonus is released, as a multiple of the bonus.
+    pub wagering_multiplier: u16,
+    /// Share of a wager counted towards the requirement (in basis points) for games without
+    /// a specific contribution.
+    pub default_contribution_bps: u16,
+    /// Share counted for specific games (in basis points).
+    pub contributions: Vec<(GameType, u16)>,
+}
+
+impl BonusSettings {
+    /// The wagering requirement of a bonus of `amount` chips.
+    pub fn requirement(&self, amount: u64) -> u64 {
+        amount.saturating_mul(self.wagering_multiplier as u64)
+    }
+
+    /// The part of `wagered` (on `game_type`) counted towards the requirement.
+    pub fn contribution(&self, game_type: GameType, wagered: u64) -> u64 {
+        let bps = self
+            .contributions
+            .iter()
+            .find(|(kind, _)| *kind == game_type)
+            .map_or(self.default_contribution_bps, |(_, bps)| *bps);
+        (wagered as u128 * bps as u128 / 10_000) as u64
+    }
+}
+
+impl Default for BonusSettings {
+    fn default() -> Self {
+        Self {
+            wagering_multiplier: DEFAULT_BONUS_WAGERING_MULTIPLIER,
+            default_contribution_bps: DEFAULT_BONUS_CONTRIBUTION_BPS,
+            // Games where optimal play has little edge would clear requirements at little risk
+            contributions: vec![
+                (GameType::Blackjack, LOW_EDGE_BONUS_CONTRIBUTION_BPS),
+                (GameType::Baccarat, LOW_EDGE_BONUS_CONTRIBUTION_BPS),
+                (GameType::VideoPoker, LOW_EDGE_BONUS_CONTRIBUTION_BPS),
+            ],
+        }
+    }
+}
+
+impl Write for BonusSettings {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.wagering_multiplier.write(writer);
+        self.default_contribution_bps.write(writer);
+        self.contributions.write(writer);
+    }
+}
+
+impl Read for BonusSettings {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            wagering_multiplier: u16::read(reader)?,
+            default_contribution_bps: u16::read(reader)?,
+            contributions: Vec::<(GameType, u16)>::read_range(reader, 0..=MAX_BONUS_CONTRIBUTIONS)?,
+        })
+    }
+}
+
+impl EncodeSize for BonusSettings {
+    fn encode_size(&self) -> usize {
+        self.wagering_multiplier.encode_size()
+            + self.default_contribution_bps.encode_size()
+            + self.contributions.encode_size()
+    }
+}
+
+/// Insurance fund covering house losses (so stakers aren't exposed to jackpot-sized variance).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InsuranceFund {
+    /// Chips available to cover losses.
+    pub balance: Chips,
+    /// Share of the fees and sell tax collected (in basis points) paid into the fund.
+    pub funding_bps: u16,
+    /// Loss (negative net PnL) the house absorbs before the fund is drawn on.
+    pub threshold: u64,
+}
+
+impl InsuranceFund {
+    /// The amount paid into the fund from `amount` collected (rounded down, and never more
+    /// than `amount`).
+    pub fn funding(&self, amount: Chips) -> Chips {
+        amount
+            .bps(self.funding_bps)
+            .map_or(amount, |funding| funding.min(amount))
+    }
+
+    /// The amount drawn from the fund to cover a house with `net_pnl` (never more than the
+    /// balance).
+    pub fn draw(&self, net_pnl: i128) -> Chips {
+        let excess = net_pnl
+            .saturating_neg()
+            .saturating_sub(self.threshold as i128);
+        Chips(excess.clamp(0, self.balance.0 as i128) as u64)
+    }
+}
+
+impl Default for InsuranceFund {
+    fn default() -> Self {
+        Self {
+            balance: Chips::ZERO,
+            funding_bps: DEFAULT_INSURANCE_FUNDING_BPS,
+            threshold: DEFAULT_INSURANCE_THRESHOLD,
+        }
+    }
+}
+
+impl Write for InsuranceFund {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.balance.write(writer);
+        self.funding_bps.write(writer);
+        self.threshold.write(writer);
+    }
+}
+
+impl Read for InsuranceFund {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            balance: Chips::read(reader)?,
+            funding_bps: u16::read(reader)?,
+            threshold: u64::read(reader)?,
+        })
+    }
+}
+
+impl FixedSize for InsuranceFund {
+    const SIZE: usize = Chips::SIZE + u16::SIZE + u64::SIZE;
+}
+
+/// Curve of the staking rewards minted each epoch (split between stakers by voting power, so
+/// longer lockups earn proportionally more).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RewardCurve {
+    /// Annual rate (in basis points of the total staked) at the first epoch.
+    pub base_apr_bps: u64,
+    /// Reduction of the rate every epoch (in basis points of the previous epoch's rate).
+    pub decay_bps: u16,
+    /// Annual rate (in basis points) the decay stops at.
+    pub floor_apr_bps: u64,
+}
+
+impl RewardCurve {
+    /// Scale of the fixed-point decay factor.
+    const SCALE: u128 = 1_000_000_000_000;
+
+    /// The annual rate of `epoch` (in basis points scaled by `SCALE`).
+    fn scaled_apr(&self, epoch: u64) -> u128 {
+        // Compound the decay by squaring
+        let mut factor = Self::SCALE;
+        let mut base = Self::SCALE * 10_000u128.saturating_sub(self.decay_bps as u128) / 10_000;
+        let mut exponent = epoch;
+        while exponent > 0 && factor > 0 {
+            if exponent & 1 == 1 {
+                factor = factor * base / Self::SCALE;
+            }
+            base = base * base / Self::SCALE;
+            exponent >>= 1;
+        }
+        (self.base_apr_bps as u128 * factor).max(self.floor_apr_bps as u128 * Self::SCALE)
+    }
+
+    /// The annual rate of `epoch` (in basis points).
+    pub fn apr_bps(&self, epoch: u64) -> u64 {
+        (self.scaled_apr(epoch) / Self::SCALE) as u64
+    }
+
+    /// The rewards minted at the end of `epoch` for `total_staked`.
+    pub fn issuance(&self, epoch: u64, total_staked: u64) -> u64 {
+        let rewards = (total_staked as u128)
+            .saturating_mul(self.scaled_apr(epoch))
+            .saturating_mul(EPOCH_LENGTH as u128)
+            / (10_000 * Self::SCALE * VIEWS_PER_YEAR as u128);
+        rewards.min(u64::MAX as u128) as u64
+    }
+}
+
+impl Default for RewardCurve {
+    fn default() -> Self {
+        Self {
+            base_apr_bps: DEFAULT_REWARD_APR_BPS,
+            decay_bps: DEFAULT_REWARD_DECAY_BPS,
+            floor_apr_bps: DEFAULT_REWARD_FLOOR_APR_BPS,
+        }
+    }
+}
+
+impl Write for RewardCurve {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.base_apr_bps.write(writer);
+        self.decay_bps.write(writer);
+        self.floor_apr_bps.write(writer);
+    }
+}
+
+impl Read for RewardCurve {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            base_apr_bps: u64::read(reader)?,
+            decay_bps: u16::read(reader)?,
+            floor_apr_bps: u64::read(reader)?,
+        })
+    }
+}
+
+impl FixedSize for RewardCurve {
+    const SIZE: usize = u64::SIZE + u16::SIZE + u64::SIZE;
+}
+
+/// Economic parameters set by governance (stored under `Key::EconomyParams`, the defaults
+/// until set), loaded by the execution layer at the start of every block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EconomyParams {
+    /// Chips granted on registration.
+    pub initial_chips: u64,
+    /// Most chips a faucet deposit can claim (larger deposits also need KYC, see
+    /// [KycSettings::deposit_limit]).
+    pub faucet_amount: u64,
+    /// Super mode fee (in basis points of the amount wagered).
+    pub super_mode_fee_bps: u16,
+    /// Swap fee of new AMM pools (in basis points).
+    pub amm_fee_bps: u16,
+    /// LP shares locked on a pool's first deposit.
+    pub minimum_liquidity: LpShares,
+}
+
+impl EconomyParams {
+    /// Whether the parameters can be used (a super mode fee of at most 100%, a swap fee below
+    /// 100% and some liquidity locked).
+    pub fn is_valid(&self) -> bool {
+        self.super_mode_fee_bps <= 10_000
+            && self.amm_fee_bps < 10_000
+            && !self.minimum_liquidity.is_zero()
+    }
+
+    /// The super mode fee charged on `amount` wagered.
+    pub fn super_mode_fee(&self, amount: u64) -> u64 {
+        (amount as u128 * self.super_mode_fee_bps as u128 / 10_000) as u64
+    }
+}
+
+impl Default for EconomyParams {
+    fn default() -> Self {
+        Self {
+            initial_chips: INITIAL_CHIPS,
+            faucet_amount: FAUCET_AMOUNT,
+            super_mode_fee_bps: SUPER_MODE_FEE_BPS,
+            amm_fee_bps: DEFAULT_AMM_FEE_BPS,
+            minimum_liquidity: LpShares(MINIMUM_LIQUIDITY),
+        }
+    }
+}
+
+impl Write for EconomyParams {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.initial_chips.write(writer);
+        self.faucet_amount.write(writer);
+        self.super_mode_fee_bps.write(writer);
+        self.amm_fee_bps.write(writer);
+        self.minimum_liquidity.write(writer);
+    }
+}
+
+impl Read for EconomyParams {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            initial_chips: u64::read(reader)?,
+            faucet_amount: u64::read(reader)?,
+            super_mode_fee_bps: u16::read(reader)?,
+            amm_fee_bps: u16::read(reader)?,
+            minimum_liquidity: LpShares::read(reader)?,
+        })
+    }
+}
+
+impl FixedSize for EconomyParams {
+    const SIZE: usize = u64::SIZE + u64::SIZE + u16::SIZE + u16::SIZE + LpShares::SIZE;
+}
+
+/// A privileged key, set by the validator set (see `Instruction::SetAdmin`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum AdminRole {
+    /// [HouseState::governance]: game rules, bonuses, economy parameters and house settings.
+    Governance = 0,
+    /// [KycSettings::admin]: the KYC status of players.
+    Kyc = 1,
+    /// [Treasury::admin]: buybacks.
+    Treasury = 2,
+}
+
+impl Write for AdminRole {
+    fn write(&self, writer: &mut impl BufMut) {
+        (*self as u8).write(writer);
+    }
+}
+
+impl Read for AdminRole {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        match u8::read(reader)? {
+            0 => Ok(Self::Governance),
+            1 => Ok(Self::Kyc),
+            2 => Ok(Self::Treasury),
+            i => Err(Error::InvalidEnum(i)),
+        }
+    }
+}
+
+impl FixedSize for AdminRole {
+    const SIZE: usize = 1;
+}
+
+/// House settings governance can change (see `Instruction::SetHouseSettings`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HouseSettings {
+    pub fee_schedule: FeeSchedule,
+    pub daily_bonus: DailyBonus,
+    pub bet_limits: BetLimits,
+    /// [KycSettings::deposit_limit].
+    pub kyc_deposit_limit: u64,
+}
+
+impl HouseSettings {
+    /// Whether the settings can be used (a daily bonus cooldown and bets allowed).
+    pub fn is_valid(&self) -> bool {
+        self.daily_bonus.cooldown > 0
+            && self.bet_limits.default_max_bet > 0
+            && self.bet_limits.max_exposure > 0
+    }
+}
+
+impl Write for HouseSettings {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.fee_schedule.write(writer);
+        self.daily_bonus.write(writer);
+        self.bet_limits.write(writer);
+        self.kyc_deposit_limit.write(writer);
+    }
+}
+
+impl Read for HouseSettings {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            fee_schedule: FeeSchedule::read(reader)?,
+            daily_bonus: DailyBonus::read(reader)?,
+            bet_limits: BetLimits::read(reader)?,
+            kyc_deposit_limit: u64::read(reader)?,
+        })
+    }
+}
+
+impl EncodeSize for HouseSettings {
+    fn encode_size(&self) -> usize {
+        self.fee_schedule.encode_size()
+            + self.daily_bonus.encode_size()
+            + self.bet_limits.encode_size()
+            + self.kyc_deposit_limit.encode_size()
+    }
+}
 
 /// House state for the "Central Bank" model
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HouseState {
     pub current_epoch: u64,
     pub epoch_start_ts: u64,
@@ -12,10 +936,28 @@ pub struct HouseState {
     pub total_staked_amount: u64,
     pub total_voting_power: u128,
     pub accumulated_fees: u64, // Fees from AMM or other sources
-    pub total_burned: u64,     // Total RNG burned via Sell Tax
+    pub total_burned: u64,     // Total RNG burned (sell tax and treasury buybacks)
     pub total_issuance: u64,   // Total RNG minted (Inflation)
     pub three_card_progressive_jackpot: u64,
     pub uth_progressive_jackpot: u64,
+    pub fee_schedule: FeeSchedule,
+    pub debt_rate_bps: u64, // Interest charged on vUSDT debt per epoch
+    pub debt_index: u128,   // Cumulative interest multiplier (starts at DEBT_INDEX_SCALE)
+    pub daily_bonus: DailyBonus,
+    pub max_active_sessions: u32, // Concurrent game sessions allowed per player
+    pub bet_limits: BetLimits,
+    pub kyc: KycSettings,
+    pub tournament_payouts: TournamentPayouts,
+    pub seasons: SeasonSettings,
+    pub vip: VipSettings,
+    pub insurance: InsuranceFund,
+    pub rewards: RewardCurve,
+    pub reward_index: u128, // Cumulative staking rewards per unit of voting power (REWARD_INDEX_SCALE)
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::option"))]
+    pub governance: Option<PublicKey>, // Key allowed to change governed settings (game rules)
+    pub caribbean_stud_progressive_jackpot: u64,
+    pub bonus: BonusSettings,
+    pub admin_rotations: u64, // Admin keys set so far (so the validator set's approvals can't be replayed)
 }
 
 impl HouseState {
@@ -31,8 +973,60 @@ impl HouseState {
             total_issuance: 0,
             three_card_progressive_jackpot: THREE_CARD_PROGRESSIVE_BASE_JACKPOT,
             uth_progressive_jackpot: UTH_PROGRESSIVE_BASE_JACKPOT,
+            fee_schedule: FeeSchedule::default(),
+            debt_rate_bps: DEBT_INTEREST_RATE_BPS,
+            debt_index: DEBT_INDEX_SCALE,
+            daily_bonus: DailyBonus::default(),
+            max_active_sessions: DEFAULT_MAX_ACTIVE_SESSIONS,
+            bet_limits: BetLimits::default(),
+            kyc: KycSettings::default(),
+            tournament_payouts: TournamentPayouts::default(),
+            seasons: SeasonSettings::default(),
+            vip: VipSettings::default(),
+            insurance: InsuranceFund::default(),
+            rewards: RewardCurve::default(),
+            reward_index: 0,
+            governance: None,
+            caribbean_stud_progressive_jackpot: CARIBBEAN_STUD_PROGRESSIVE_BASE_JACKPOT,
+            bonus: BonusSettings::default(),
+            admin_rotations: 0,
         }
     }
+
+    /// The settings governance can change.
+    pub fn settings(&self) -> HouseSettings {
+        HouseSettings {
+            fee_schedule: self.fee_schedule.clone(),
+            daily_bonus: self.daily_bonus.clone(),
+            bet_limits: self.bet_limits.clone(),
+            kyc_deposit_limit: self.kyc.deposit_limit,
+        }
+    }
+
+    /// Replace the settings governance can change.
+    pub fn set_settings(&mut self, settings: HouseSettings) {
+        self.fee_schedule = settings.fee_schedule;
+        self.daily_bonus = settings.daily_bonus;
+        self.bet_limits = settings.bet_limits;
+        self.kyc.deposit_limit = settings.kyc_deposit_limit;
+    }
+
+    /// Whether `other` has the same rules and configuration (ignoring counters like fees,
+    /// PnL, jackpots and indices, which change as transactions execute).
+    pub fn same_settings(&self, other: &Self) -> bool {
+        self.fee_schedule == other.fee_schedule
+            && self.debt_rate_bps == other.debt_rate_bps
+            && self.daily_bonus == other.daily_bonus
+            && self.max_active_sessions == other.max_active_sessions
+            && self.bet_limits == other.bet_limits
+            && self.kyc == other.kyc
+            && self.tournament_payouts == other.tournament_payouts
+            && self.seasons == other.seasons
+            && self.vip == other.vip
+            && self.rewards == other.rewards
+            && self.governance == other.governance
+            && self.bonus == other.bonus
+    }
 }
 
 impl Write for HouseState {
@@ -47,6 +1041,23 @@ impl Write for HouseState {
         self.total_issuance.write(writer);
         self.three_card_progressive_jackpot.write(writer);
         self.uth_progressive_jackpot.write(writer);
+        self.fee_schedule.write(writer);
+        self.debt_rate_bps.write(writer);
+        self.debt_index.write(writer);
+        self.daily_bonus.write(writer);
+        self.max_active_sessions.write(writer);
+        self.bet_limits.write(writer);
+        self.kyc.write(writer);
+        self.tournament_payouts.write(writer);
+        self.seasons.write(writer);
+        self.vip.write(writer);
+        self.insurance.write(writer);
+        self.rewards.write(writer);
+        self.reward_index.write(writer);
+        self.governance.write(writer);
+        self.caribbean_stud_progressive_jackpot.write(writer);
+        self.bonus.write(writer);
+        self.admin_rotations.write(writer);
     }
 }
 
@@ -74,6 +1085,91 @@ impl Read for HouseState {
         } else {
             UTH_PROGRESSIVE_BASE_JACKPOT
         };
+        let fee_schedule = if reader.has_remaining() {
+            FeeSchedule::read(reader)?
+        } else {
+            FeeSchedule::default()
+        };
+        let debt_rate_bps = if reader.remaining() >= u64::SIZE {
+            u64::read(reader)?
+        } else {
+            DEBT_INTEREST_RATE_BPS
+        };
+        let debt_index = if reader.remaining() >= u128::SIZE {
+            u128::read(reader)?
+        } else {
+            DEBT_INDEX_SCALE
+        };
+        let daily_bonus = if reader.remaining() >= DailyBonus::SIZE {
+            DailyBonus::read(reader)?
+        } else {
+            DailyBonus::default()
+        };
+        let max_active_sessions = if reader.remaining() >= u32::SIZE {
+            u32::read(reader)?
+        } else {
+            DEFAULT_MAX_ACTIVE_SESSIONS
+        };
+        let bet_limits = if reader.has_remaining() {
+            BetLimits::read(reader)?
+        } else {
+            BetLimits::default()
+        };
+        let kyc = if reader.has_remaining() {
+            KycSettings::read(reader)?
+        } else {
+            KycSettings::default()
+        };
+        let tournament_payouts = if reader.has_remaining() {
+            TournamentPayouts::read(reader)?
+        } else {
+            TournamentPayouts::default()
+        };
+        let seasons = if reader.has_remaining() {
+            SeasonSettings::read(reader)?
+        } else {
+            SeasonSettings::default()
+        };
+        let vip = if reader.has_remaining() {
+            VipSettings::read(reader)?
+        } else {
+            VipSettings::default()
+        };
+        let insurance = if reader.remaining() >= InsuranceFund::SIZE {
+            InsuranceFund::read(reader)?
+        } else {
+            InsuranceFund::default()
+        };
+        let rewards = if reader.remaining() >= RewardCurve::SIZE {
+            RewardCurve::read(reader)?
+        } else {
+            RewardCurve::default()
+        };
+        let reward_index = if reader.remaining() >= u128::SIZE {
+            u128::read(reader)?
+        } else {
+            0
+        };
+        let governance = if reader.has_remaining() {
+            Option::<PublicKey>::read(reader)?
+        } else {
+            None
+        };
+        let caribbean_stud_progressive_jackpot = if reader.remaining() >= u64::SIZE {
+            u64::read(reader)?
+        } else {
+            CARIBBEAN_STUD_PROGRESSIVE_BASE_JACKPOT
+        };
+        let bonus = if reader.has_remaining() {
+            BonusSettings::read(reader)?
+        } else {
+            BonusSettings::default()
+        };
+        let admin_rotations = if reader.remaining() >= u64::SIZE {
+            u64::read(reader)?
+        } else {
+            0
+        };
 
         Ok(Self {
             current_epoch,
@@ -86,6 +1182,23 @@ impl Read for HouseState {
             total_issuance,
             three_card_progressive_jackpot,
             uth_progressive_jackpot,
+            fee_schedule,
+            debt_rate_bps,
+            debt_index,
+            daily_bonus,
+            max_active_sessions,
+            bet_limits,
+            kyc,
+            tournament_payouts,
+            seasons,
+            vip,
+            insurance,
+            rewards,
+            reward_index,
+            governance,
+            caribbean_stud_progressive_jackpot,
+            bonus,
+            admin_rotations,
         })
     }
 }
@@ -102,16 +1215,185 @@ impl EncodeSize for HouseState {
             + self.total_issuance.encode_size()
             + self.three_card_progressive_jackpot.encode_size()
             + self.uth_progressive_jackpot.encode_size()
+            + self.fee_schedule.encode_size()
+            + self.debt_rate_bps.encode_size()
+            + self.debt_index.encode_size()
+            + self.daily_bonus.encode_size()
+            + self.max_active_sessions.encode_size()
+            + self.bet_limits.encode_size()
+            + self.kyc.encode_size()
+            + self.tournament_payouts.encode_size()
+            + self.seasons.encode_size()
+            + self.vip.encode_size()
+            + self.insurance.encode_size()
+            + self.rewards.encode_size()
+            + self.reward_index.encode_size()
+            + self.governance.encode_size()
+            + self.caribbean_stud_progressive_jackpot.encode_size()
+            + self.bonus.encode_size()
+            + self.admin_rotations.encode_size()
+    }
+}
+
+/// A single stake (locked until `unlock_ts`)
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StakeTranche {
+    pub amount: Chips,
+    pub unlock_ts: u64,
+    pub voting_power: u128,
+}
+
+impl Write for StakeTranche {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.amount.write(writer);
+        self.unlock_ts.write(writer);
+        self.voting_power.write(writer);
+    }
+}
+
+impl Read for StakeTranche {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            amount: Chips::read(reader)?,
+            unlock_ts: u64::read(reader)?,
+            voting_power: u128::read(reader)?,
+        })
     }
 }
 
+impl FixedSize for StakeTranche {
+    const SIZE: usize = Chips::SIZE + u64::SIZE + u128::SIZE;
+}
+
 /// Staker state
+///
+/// `balance`, `unlock_ts` (the latest unlock) and `voting_power` summarize `tranches`.
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Staker {
-    pub balance: u64,
+    pub balance: Chips,
     pub unlock_ts: u64,
     pub last_claim_epoch: u64,
     pub voting_power: u128,
+    pub slash_count: u64, // Times slashed (so slashing evidence can't be replayed)
+    pub tranches: Vec<StakeTranche>,
+    pub reward_index: u128, // HouseState::reward_index when rewards were last accrued
+    pub pending_rewards: Chips, // Rewards accrued but not yet claimed
+}
+
+impl Staker {
+    /// Recompute the summary fields from the tranches.
+    ///
+    /// The balance can't overflow (tranches are only added by [Self::stake], which checks it).
+    fn summarize(&mut self) {
+        self.balance = self
+            .tranches
+            .iter()
+            .fold(Chips::ZERO, |total, t| total.saturating_add(t.amount));
+        self.unlock_ts = self.tranches.iter().map(|t| t.unlock_ts).max().unwrap_or(0);
+        self.voting_power = self.tranches.iter().map(|t| t.voting_power).sum();
+    }
+
+    /// Accrue the rewards earned by the current voting power since the last accrual (given the
+    /// current `reward_index`).
+    ///
+    /// Must be called before the voting power changes.
+    pub fn accrue(&mut self, reward_index: u128) {
+        let earned = self
+            .voting_power
+            .saturating_mul(reward_index.saturating_sub(self.reward_index))
+            / REWARD_INDEX_SCALE;
+        self.pending_rewards = self
+            .pending_rewards
+            .saturating_add(Chips(earned.min(u64::MAX as u128) as u64));
+        self.reward_index = reward_index;
+    }
+
+    /// Add a tranche of `amount` locked until `unlock_ts` (returning false if the staker
+    /// already has [MAX_STAKE_TRANCHES] or the balance would overflow).
+    pub fn stake(&mut self, amount: Chips, unlock_ts: u64, voting_power: u128) -> bool {
+        if self.tranches.len() >= MAX_STAKE_TRANCHES || self.balance.checked_add(amount).is_none() {
+            return false;
+        }
+        self.tranches.push(StakeTranche {
+            amount,
+            unlock_ts,
+            voting_power,
+        });
+        self.summarize();
+        true
+    }
+
+    /// The amount that can be unstaked at `now`.
+    pub fn unlocked(&self, now: u64) -> Chips {
+        self.tranches
+            .iter()
+            .filter(|t| t.unlock_ts <= now)
+            .fold(Chips::ZERO, |total, t| total.saturating_add(t.amount))
+    }
+
+    /// Withdraw `amount` from the tranches unlocked at `now` (earliest unlock first), returning
+    /// the voting power removed (or `None` if less than `amount` is unlocked).
+    ///
+    /// A partially withdrawn tranche keeps voting power in proportion to what remains.
+    pub fn unstake(&mut self, amount: Chips, now: u64) -> Option<u128> {
+        if self.unlocked(now) < amount {
+            return None;
+        }
+        self.tranches.sort_by_key(|t| t.unlock_ts);
+        let mut remaining = amount;
+        let mut voting_power_removed = 0u128;
+        for tranche in self.tranches.iter_mut() {
+            if remaining.is_zero() || tranche.unlock_ts > now {
+                break;
+            }
+            let withdrawn = remaining.min(tranche.amount);
+            let kept = tranche
+                .amount
+                .checked_sub(withdrawn)
+                .expect("withdrawn is at most the tranche");
+            let voting_power =
+                tranche.voting_power.saturating_mul(kept.0 as u128) / tranche.amount.0 as u128;
+            voting_power_removed += tranche.voting_power - voting_power;
+            tranche.amount = kept;
+            tranche.voting_power = voting_power;
+            remaining = remaining
+                .checked_sub(withdrawn)
+                .expect("withdrawn is at most the remainder");
+        }
+        self.tranches.retain(|t| !t.amount.is_zero());
+        self.summarize();
+        Some(voting_power_removed)
+    }
+
+    /// Remove `bps` of every tranche, returning the amount and voting power removed.
+    ///
+    /// At most the whole stake is removed (even if `bps` is above 10,000).
+    pub fn slash(&mut self, bps: u16) -> (Chips, u128) {
+        let (mut amount, mut voting_power_removed) = (Chips::ZERO, 0u128);
+        for tranche in self.tranches.iter_mut() {
+            let slashed = tranche
+                .amount
+                .bps(bps)
+                .map_or(tranche.amount, |slashed| slashed.min(tranche.amount));
+            let kept = tranche
+                .amount
+                .checked_sub(slashed)
+                .expect("slashed is at most the tranche");
+            let voting_power =
+                tranche.voting_power.saturating_mul(kept.0 as u128) / tranche.amount.0 as u128;
+            amount = amount.saturating_add(slashed);
+            voting_power_removed += tranche.voting_power - voting_power;
+            tranche.amount = kept;
+            tranche.voting_power = voting_power;
+        }
+        self.tranches.retain(|t| !t.amount.is_zero());
+        self.summarize();
+        (amount, voting_power_removed)
+    }
 }
 
 impl Write for Staker {
@@ -120,6 +1402,10 @@ impl Write for Staker {
         self.unlock_ts.write(writer);
         self.last_claim_epoch.write(writer);
         self.voting_power.write(writer);
+        self.slash_count.write(writer);
+        self.tranches.write(writer);
+        self.reward_index.write(writer);
+        self.pending_rewards.write(writer);
     }
 }
 
@@ -127,11 +1413,52 @@ impl Read for Staker {
     type Cfg = ();
 
     fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        let balance = Chips::read(reader)?;
+        let unlock_ts = u64::read(reader)?;
+        let last_claim_epoch = u64::read(reader)?;
+        let voting_power = u128::read(reader)?;
+
+        // Optional extensions (backwards compatible with stakers stored before slashing and
+        // tranches).
+        let slash_count = if reader.remaining() >= u64::SIZE {
+            u64::read(reader)?
+        } else {
+            0
+        };
+        let tranches = if reader.has_remaining() {
+            Vec::<StakeTranche>::read_range(reader, 0..=MAX_STAKE_TRANCHES)?
+        } else if !balance.is_zero() {
+            // Older stakers hold a single stake
+            vec![StakeTranche {
+                amount: balance,
+                unlock_ts,
+                voting_power,
+            }]
+        } else {
+            Vec::new()
+        };
+
+        // Stakers stored before rewards were issued have accrued nothing
+        let reward_index = if reader.remaining() >= u128::SIZE {
+            u128::read(reader)?
+        } else {
+            0
+        };
+        let pending_rewards = if reader.remaining() >= Chips::SIZE {
+            Chips::read(reader)?
+        } else {
+            Chips::ZERO
+        };
+
         Ok(Self {
-            balance: u64::read(reader)?,
-            unlock_ts: u64::read(reader)?,
-            last_claim_epoch: u64::read(reader)?,
-            voting_power: u128::read(reader)?,
+            balance,
+            unlock_ts,
+            last_claim_epoch,
+            voting_power,
+            slash_count,
+            tranches,
+            reward_index,
+            pending_rewards,
         })
     }
 }
@@ -142,20 +1469,49 @@ impl EncodeSize for Staker {
             + self.unlock_ts.encode_size()
             + self.last_claim_epoch.encode_size()
             + self.voting_power.encode_size()
+            + self.slash_count.encode_size()
+            + self.tranches.encode_size()
+            + self.reward_index.encode_size()
+            + self.pending_rewards.encode_size()
     }
 }
 
 /// Vault state for CDP (Collateralized Debt Position)
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vault {
-    pub collateral_rng: u64,
-    pub debt_vusdt: u64,
+    pub collateral_rng: Chips,
+    pub debt_vusdt: Vusdt,
+    pub debt_index: u128, // HouseState::debt_index when interest was last applied (0 if never)
+}
+
+impl Vault {
+    /// Apply the interest accrued since the debt was last touched (given the current
+    /// `debt_index`), returning the interest added to the debt.
+    ///
+    /// Interest is rounded up (in favor of the house).
+    pub fn accrue(&mut self, debt_index: u128) -> Vusdt {
+        let last = std::mem::replace(&mut self.debt_index, debt_index);
+        if last == 0 || debt_index <= last || self.debt_vusdt.is_zero() {
+            return Vusdt::ZERO;
+        }
+        let debt = Vusdt(
+            (self.debt_vusdt.0 as u128)
+                .saturating_mul(debt_index)
+                .div_ceil(last)
+                .min(u64::MAX as u128) as u64,
+        );
+        let interest = Vusdt(debt.0 - self.debt_vusdt.0);
+        self.debt_vusdt = debt;
+        interest
+    }
 }
 
 impl Write for Vault {
     fn write(&self, writer: &mut impl BufMut) {
         self.collateral_rng.write(writer);
         self.debt_vusdt.write(writer);
+        self.debt_index.write(writer);
     }
 }
 
@@ -163,38 +1519,242 @@ impl Read for Vault {
     type Cfg = ();
 
     fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        let collateral_rng = Chips::read(reader)?;
+        let debt_vusdt = Vusdt::read(reader)?;
+
+        // Optional extension (vaults stored before interest accrual only accrue from their
+        // next update)
+        let debt_index = if reader.remaining() >= u128::SIZE {
+            u128::read(reader)?
+        } else {
+            0
+        };
+
         Ok(Self {
-            collateral_rng: u64::read(reader)?,
-            debt_vusdt: u64::read(reader)?,
+            collateral_rng,
+            debt_vusdt,
+            debt_index,
         })
     }
 }
 
 impl EncodeSize for Vault {
     fn encode_size(&self) -> usize {
-        self.collateral_rng.encode_size() + self.debt_vusdt.encode_size()
+        self.collateral_rng.encode_size()
+            + self.debt_vusdt.encode_size()
+            + self.debt_index.encode_size()
+    }
+}
+
+/// Treasury funded by a share of the house's profit, spent buying back (and burning) RNG.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Treasury {
+    /// Key allowed to execute buybacks (if any).
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::option"))]
+    pub admin: Option<PublicKey>,
+    /// Share of each epoch's profit (in basis points) paid into the treasury.
+    pub profit_share_bps: u16,
+    pub vusdt_balance: Vusdt,
+    /// Total RNG bought back and burned.
+    pub total_bought_back: Chips,
+}
+
+impl Treasury {
+    /// The share of `net_pnl` (in chips) paid into the treasury.
+    pub fn funding(&self, net_pnl: i128) -> u64 {
+        (net_pnl.max(0) as u128 * self.profit_share_bps as u128 / 10_000).min(u64::MAX as u128)
+            as u64
+    }
+}
+
+impl Default for Treasury {
+    fn default() -> Self {
+        Self {
+            admin: None,
+            profit_share_bps: DEFAULT_TREASURY_SHARE_BPS,
+            vusdt_balance: Vusdt::ZERO,
+            total_bought_back: Chips::ZERO,
+        }
+    }
+}
+
+impl Write for Treasury {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.admin.write(writer);
+        self.profit_share_bps.write(writer);
+        self.vusdt_balance.write(writer);
+        self.total_bought_back.write(writer);
+    }
+}
+
+impl Read for Treasury {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            admin: Option::<PublicKey>::read(reader)?,
+            profit_share_bps: u16::read(reader)?,
+            vusdt_balance: Vusdt::read(reader)?,
+            total_bought_back: Chips::read(reader)?,
+        })
+    }
+}
+
+impl EncodeSize for Treasury {
+    fn encode_size(&self) -> usize {
+        self.admin.encode_size()
+            + self.profit_share_bps.encode_size()
+            + self.vusdt_balance.encode_size()
+            + self.total_bought_back.encode_size()
+    }
+}
+
+/// Identifies an AMM pool (by the pair of assets it trades).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairId(pub u16);
+
+impl PairId {
+    /// The RNG/vUSDT pool.
+    pub const RNG_VUSDT: Self = Self(0);
+}
+
+impl Write for PairId {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.0.write(writer);
+    }
+}
+
+impl Read for PairId {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self(u16::read(reader)?))
+    }
+}
+
+impl FixedSize for PairId {
+    const SIZE: usize = u16::SIZE;
+}
+
+/// Registry of all pairs with an AMM pool.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AmmPools {
+    pub pairs: Vec<PairId>,
+}
+
+impl Write for AmmPools {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.pairs.write(writer);
+    }
+}
+
+impl Read for AmmPools {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            pairs: Vec::<PairId>::read_range(reader, 0..=MAX_AMM_POOLS)?,
+        })
+    }
+}
+
+impl EncodeSize for AmmPools {
+    fn encode_size(&self) -> usize {
+        self.pairs.encode_size()
     }
 }
 
 /// AMM Pool state (Constant Product Market Maker)
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AmmPool {
-    pub reserve_rng: u64,
-    pub reserve_vusdt: u64,
-    pub total_shares: u64,
+    pub reserve_rng: Chips,
+    pub reserve_vusdt: Vusdt,
+    pub total_shares: LpShares,
     pub fee_basis_points: u16,      // e.g., 30 = 0.3%
     pub sell_tax_basis_points: u16, // e.g., 500 = 5%
+
+    // Sums of the price (scaled by PRICE_SCALE) over every view up to `last_update_view`
+    // (the average price between two snapshots is the change in the sum over the views elapsed)
+    pub price_rng_cumulative: u128,   // RNG priced in vUSDT
+    pub price_vusdt_cumulative: u128, // vUSDT priced in RNG
+    pub last_update_view: u64,
+
+    /// Maximum change in the spot price (in basis points) a single swap may cause (0 for no
+    /// limit).
+    pub max_price_impact_bps: u16,
 }
 
 impl AmmPool {
     pub fn new(fee_bps: u16) -> Self {
         Self {
-            reserve_rng: 0,
-            reserve_vusdt: 0,
-            total_shares: 0,
+            reserve_rng: Chips::ZERO,
+            reserve_vusdt: Vusdt::ZERO,
+            total_shares: LpShares::ZERO,
             fee_basis_points: fee_bps,
             sell_tax_basis_points: 500, // 5% default
+            price_rng_cumulative: 0,
+            price_vusdt_cumulative: 0,
+            last_update_view: 0,
+            max_price_impact_bps: DEFAULT_MAX_PRICE_IMPACT_BPS,
+        }
+    }
+
+    /// The cumulative prices (RNG in vUSDT, vUSDT in RNG) as of `view`, assuming the reserves
+    /// don't change before then.
+    pub fn cumulative_prices(&self, view: u64) -> (u128, u128) {
+        let elapsed = view.saturating_sub(self.last_update_view) as u128;
+        // Pools stored before accumulation was added have no last update (view 0 is genesis)
+        if self.last_update_view == 0
+            || self.reserve_rng.is_zero()
+            || self.reserve_vusdt.is_zero()
+            || elapsed == 0
+        {
+            return (self.price_rng_cumulative, self.price_vusdt_cumulative);
+        }
+        let rng_price = self.reserve_vusdt.0 as u128 * PRICE_SCALE / self.reserve_rng.0 as u128;
+        let vusdt_price = self.reserve_rng.0 as u128 * PRICE_SCALE / self.reserve_vusdt.0 as u128;
+        (
+            self.price_rng_cumulative
+                .wrapping_add(rng_price.wrapping_mul(elapsed)),
+            self.price_vusdt_cumulative
+                .wrapping_add(vusdt_price.wrapping_mul(elapsed)),
+        )
+    }
+
+    /// Accumulate the current prices until `view` (call before the reserves change).
+    pub fn accumulate(&mut self, view: u64) {
+        if view <= self.last_update_view {
+            return;
+        }
+        (self.price_rng_cumulative, self.price_vusdt_cumulative) = self.cumulative_prices(view);
+        self.last_update_view = view;
+    }
+
+    /// The average price of RNG in vUSDT (scaled by [PRICE_SCALE]) between two
+    /// `(view, price_rng_cumulative)` snapshots (if any views elapsed between them).
+    pub fn average_price(start: (u64, u128), end: (u64, u128)) -> Option<u128> {
+        let elapsed = end.0.checked_sub(start.0).filter(|elapsed| *elapsed > 0)?;
+        Some(end.1.wrapping_sub(start.1) / elapsed as u128)
+    }
+
+    /// Spot price of RNG in vUSDT (scaled by [PRICE_SCALE]), if the pool has liquidity.
+    pub fn spot_price(&self) -> Option<u128> {
+        if self.reserve_rng.is_zero() || self.reserve_vusdt.is_zero() {
+            return None;
         }
+        Some(self.reserve_vusdt.0 as u128 * PRICE_SCALE / self.reserve_rng.0 as u128)
+    }
+
+    /// Change in the spot price (in basis points) between this pool and `after`, if both have
+    /// liquidity.
+    pub fn price_impact_bps(&self, after: &AmmPool) -> Option<u128> {
+        let before = self.spot_price().filter(|price| *price > 0)?;
+        let after = after.spot_price()?;
+        Some(before.abs_diff(after).saturating_mul(10_000) / before)
     }
 }
 
@@ -205,6 +1765,10 @@ impl Write for AmmPool {
         self.total_shares.write(writer);
         self.fee_basis_points.write(writer);
         self.sell_tax_basis_points.write(writer);
+        self.price_rng_cumulative.write(writer);
+        self.price_vusdt_cumulative.write(writer);
+        self.last_update_view.write(writer);
+        self.max_price_impact_bps.write(writer);
     }
 }
 
@@ -212,12 +1776,36 @@ impl Read for AmmPool {
     type Cfg = ();
 
     fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        let reserve_rng = Chips::read(reader)?;
+        let reserve_vusdt = Vusdt::read(reader)?;
+        let total_shares = LpShares::read(reader)?;
+        let fee_basis_points = u16::read(reader)?;
+        let sell_tax_basis_points = u16::read(reader)?;
+
+        // Optional extension (pools stored before price accumulators start accumulating from
+        // their next update)
+        let (price_rng_cumulative, price_vusdt_cumulative, last_update_view) =
+            if reader.remaining() >= u128::SIZE * 2 + u64::SIZE {
+                (u128::read(reader)?, u128::read(reader)?, u64::read(reader)?)
+            } else {
+                (0, 0, 0)
+            };
+        let max_price_impact_bps = if reader.remaining() >= u16::SIZE {
+            u16::read(reader)?
+        } else {
+            DEFAULT_MAX_PRICE_IMPACT_BPS
+        };
+
         Ok(Self {
-            reserve_rng: u64::read(reader)?,
-            reserve_vusdt: u64::read(reader)?,
-            total_shares: u64::read(reader)?,
-            fee_basis_points: u16::read(reader)?,
-            sell_tax_basis_points: u16::read(reader)?,
+            reserve_rng,
+            reserve_vusdt,
+            total_shares,
+            fee_basis_points,
+            sell_tax_basis_points,
+            price_rng_cumulative,
+            price_vusdt_cumulative,
+            last_update_view,
+            max_price_impact_bps,
         })
     }
 }
@@ -229,5 +1817,96 @@ impl EncodeSize for AmmPool {
             + self.total_shares.encode_size()
             + self.fee_basis_points.encode_size()
             + self.sell_tax_basis_points.encode_size()
+            + self.price_rng_cumulative.encode_size()
+            + self.price_vusdt_cumulative.encode_size()
+            + self.last_update_view.encode_size()
+            + self.max_price_impact_bps.encode_size()
+    }
+}
+
+/// Time-weighted average price (TWAP) of RNG in vUSDT, tracked from AMM reserves.
+///
+/// The spot price is observed before any transaction in a block is applied, so a price moved
+/// within a block only contributes once later views have elapsed (and then only in proportion
+/// to how long it persists).
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PriceOracle {
+    /// Spot price (scaled by [PRICE_SCALE]) as of `last_view`.
+    pub spot_price: u128,
+    /// Sum of the spot price over all views up to `last_view`.
+    pub cumulative: u128,
+    pub last_view: u64,
+    /// Recent `(view, cumulative)` checkpoints (oldest first) the average is measured from.
+    pub checkpoints: Vec<(u64, u128)>,
+}
+
+impl PriceOracle {
+    fn cumulative_at(&self, view: u64) -> u128 {
+        let elapsed = view.saturating_sub(self.last_view) as u128;
+        self.cumulative
+            .saturating_add(self.spot_price.saturating_mul(elapsed))
+    }
+
+    /// Record the spot price at the start of `view`.
+    pub fn observe(&mut self, view: u64, spot_price: u128) {
+        if view < self.last_view {
+            return;
+        }
+        self.cumulative = self.cumulative_at(view);
+        self.last_view = view;
+        self.spot_price = spot_price;
+
+        let due = self
+            .checkpoints
+            .last()
+            .is_none_or(|(last, _)| view.saturating_sub(*last) >= ORACLE_CHECKPOINT_INTERVAL);
+        if due {
+            self.checkpoints.push((view, self.cumulative));
+            if self.checkpoints.len() > ORACLE_CHECKPOINTS {
+                self.checkpoints.remove(0);
+            }
+        }
+    }
+
+    /// The average price (scaled by [PRICE_SCALE]) from the oldest checkpoint until `view`.
+    pub fn twap(&self, view: u64) -> u128 {
+        match self.checkpoints.first() {
+            Some((start, cumulative)) if view > *start => {
+                (self.cumulative_at(view) - cumulative) / (view - start) as u128
+            }
+            _ => self.spot_price,
+        }
+    }
+}
+
+impl Write for PriceOracle {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.spot_price.write(writer);
+        self.cumulative.write(writer);
+        self.last_view.write(writer);
+        self.checkpoints.write(writer);
+    }
+}
+
+impl Read for PriceOracle {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            spot_price: u128::read(reader)?,
+            cumulative: u128::read(reader)?,
+            last_view: u64::read(reader)?,
+            checkpoints: Vec::<(u64, u128)>::read_range(reader, 0..=ORACLE_CHECKPOINTS)?,
+        })
+    }
+}
+
+impl EncodeSize for PriceOracle {
+    fn encode_size(&self) -> usize {
+        self.spot_price.encode_size()
+            + self.cumulative.encode_size()
+            + self.last_view.encode_size()
+            + self.checkpoints.encode_size()
     }
 }
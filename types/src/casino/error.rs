@@ -0,0 +1,93 @@
+use bytes::{Buf, BufMut};
+use commonware_codec::{Error, FixedSize, Read, ReadExt, Write};
+
+use super::constants::*;
+
+macro_rules! casino_error_codes {
+    ($($(#[$doc:meta])* $variant:ident = $code:ident,)*) => {
+        /// Why an instruction was rejected (reported by `CasinoError` and `InstructionFailed`
+        /// events, alongside any supplemental message).
+        ///
+        /// Encoded as its one-byte code (the `ERROR_*` constants). Codes added by later releases
+        /// decode as [CasinoErrorCode::Unknown], so readers don't reject the events carrying them.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum CasinoErrorCode {
+            $($(#[$doc])* $variant,)*
+            /// A code this release doesn't know.
+            Unknown(u8),
+        }
+
+        impl From<u8> for CasinoErrorCode {
+            fn from(code: u8) -> Self {
+                match code {
+                    $($code => Self::$variant,)*
+                    code => Self::Unknown(code),
+                }
+            }
+        }
+
+        impl From<CasinoErrorCode> for u8 {
+            fn from(code: CasinoErrorCode) -> Self {
+                match code {
+                    $(CasinoErrorCode::$variant => $code,)*
+                    CasinoErrorCode::Unknown(code) => code,
+                }
+            }
+        }
+    };
+}
+
+casino_error_codes! {
+    PlayerAlreadyRegistered = ERROR_PLAYER_ALREADY_REGISTERED,
+    PlayerNotFound = ERROR_PLAYER_NOT_FOUND,
+    /// Not enough chips (or vUSDT, shares or collateral) for the instruction.
+    InsufficientFunds = ERROR_INSUFFICIENT_FUNDS,
+    InvalidBet = ERROR_INVALID_BET,
+    SessionExists = ERROR_SESSION_EXISTS,
+    SessionNotFound = ERROR_SESSION_NOT_FOUND,
+    SessionNotOwned = ERROR_SESSION_NOT_OWNED,
+    SessionComplete = ERROR_SESSION_COMPLETE,
+    /// A move the game rejects (also used by the DeFi handlers for invalid parameters, such as
+    /// exceeded slippage).
+    InvalidMove = ERROR_INVALID_MOVE,
+    RateLimited = ERROR_RATE_LIMITED,
+    TournamentNotRegistering = ERROR_TOURNAMENT_NOT_REGISTERING,
+    AlreadyInTournament = ERROR_ALREADY_IN_TOURNAMENT,
+    TournamentLimitReached = ERROR_TOURNAMENT_LIMIT_REACHED,
+    SessionLimitReached = ERROR_SESSION_LIMIT_REACHED,
+    /// A bet above the game's (or tournament's) limit.
+    BetLimitExceeded = ERROR_BET_LIMIT_EXCEEDED,
+    /// A bet that would take the session's exposure above its limit.
+    ExposureLimitExceeded = ERROR_EXPOSURE_LIMIT_EXCEEDED,
+    Unauthorized = ERROR_UNAUTHORIZED,
+    KycRequired = ERROR_KYC_REQUIRED,
+    InvalidAmount = ERROR_INVALID_AMOUNT,
+    NotFound = ERROR_NOT_FOUND,
+    InvalidGuardians = ERROR_INVALID_GUARDIANS,
+    AllowanceExceeded = ERROR_ALLOWANCE_EXCEEDED,
+    VestingLimitReached = ERROR_VESTING_LIMIT_REACHED,
+    InvalidReveal = ERROR_INVALID_REVEAL,
+    InvalidGameConfig = ERROR_INVALID_GAME_CONFIG,
+    GameDisabled = ERROR_GAME_DISABLED,
+    /// A session ID other than the one derived from the player and transaction nonce.
+    InvalidSessionId = ERROR_INVALID_SESSION_ID,
+}
+
+impl Write for CasinoErrorCode {
+    fn write(&self, writer: &mut impl BufMut) {
+        u8::from(*self).write(writer);
+    }
+}
+
+impl Read for CasinoErrorCode {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(u8::read(reader)?.into())
+    }
+}
+
+impl FixedSize for CasinoErrorCode {
+    const SIZE: usize = u8::SIZE;
+}
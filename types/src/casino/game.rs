@@ -1,8 +1,14 @@
 use bytes::{Buf, BufMut};
 use commonware_codec::{EncodeSize, Error, FixedSize, Read, ReadExt, ReadRangeExt, Write};
 
+use super::{
+    BLACKJACK_MAX_HANDS, DEFAULT_SHOE_PENETRATION, MAX_SHOE_DECKS, MAX_SHOE_PENETRATION,
+    MIN_SHOE_DECKS, MIN_SHOE_PENETRATION,
+};
+
 /// Casino game types matching frontend GameType enum
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum GameType {
     Baccarat = 0,
@@ -15,6 +21,13 @@ pub enum GameType {
     SicBo = 7,
     ThreeCard = 8,
     UltimateHoldem = 9,
+    Slots = 10,
+    Mines = 11,
+    PaiGow = 12,
+    CaribbeanStud = 13,
+    MississippiStud = 14,
+    MoneyWheel = 15,
+    FourCard = 16,
 }
 
 impl Write for GameType {
@@ -39,6 +52,13 @@ impl Read for GameType {
             7 => Ok(Self::SicBo),
             8 => Ok(Self::ThreeCard),
             9 => Ok(Self::UltimateHoldem),
+            10 => Ok(Self::Slots),
+            11 => Ok(Self::Mines),
+            12 => Ok(Self::PaiGow),
+            13 => Ok(Self::CaribbeanStud),
+            14 => Ok(Self::MississippiStud),
+            15 => Ok(Self::MoneyWheel),
+            16 => Ok(Self::FourCard),
             i => Err(Error::InvalidEnum(i)),
         }
     }
@@ -48,8 +68,251 @@ impl FixedSize for GameType {
     const SIZE: usize = 1;
 }
 
+/// Table rules of a game (snapshotted into each session as it starts, so changing them never
+/// affects sessions in progress).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameConfig {
+    /// Paytable variant (0 is the game's standard paytable; casino war: 1 drops the bonus for a
+    /// tie in the war; craps: 1 pays triple on a field 12; roulette: 1 is the American
+    /// double-zero wheel; sic bo: 1 is the Atlantic City table; video poker: 1 is Bonus Poker,
+    /// 2 Double Bonus and 3 Deuces Wild).
+    pub paytable: u8,
+    /// Commissions are charged when a bet wins instead of when it is placed (craps buy bets).
+    pub commission_on_win: bool,
+    /// The dealer hits soft 17 (blackjack H17, otherwise S17).
+    pub dealer_hits_soft_17: bool,
+    /// Most hands a blackjack player can split into, re-splitting up to the limit (1 disables
+    /// splitting).
+    pub max_split_hands: u8,
+    /// Every outcome drawn by the game's sessions is reported (in a `CasinoRngTranscript` event
+    /// per move) so clients can verify it without replaying the game. Unlike the table rules,
+    /// this applies to sessions in progress from their next move.
+    pub record_rng: bool,
+    /// Decks in a shoe kept across the rounds of a blackjack or baccarat session (0 deals each
+    /// round from a freshly shuffled shoe).
+    pub shoe_decks: u8,
+    /// Percent of a persistent shoe dealt before the cut card triggers a reshuffle.
+    pub shoe_penetration: u8,
+    /// The blackjack dealer peeks at the hole card when showing an Ace or ten-value card, and a
+    /// dealer blackjack ends the round at once (otherwise the hole card is drawn at the reveal).
+    pub dealer_peeks: bool,
+    /// A blackjack player can take even money (a 1:1 payout at once) when the dealer shows an
+    /// Ace.
+    pub even_money: bool,
+}
+
+impl GameConfig {
+    /// Number of paytable variants offered by `game_type`.
+    pub fn paytables(game_type: GameType) -> u8 {
+        match game_type {
+            GameType::CasinoWar | GameType::Craps | GameType::Roulette | GameType::SicBo => 2,
+            GameType::VideoPoker => 4,
+            _ => 1,
+        }
+    }
+
+    /// Whether the rules can be used for `game_type`.
+    pub fn is_valid(&self, game_type: GameType) -> bool {
+        self.paytable < Self::paytables(game_type)
+            && (1..=BLACKJACK_MAX_HANDS).contains(&self.max_split_hands)
+            && (self.shoe_decks == 0
+                || (matches!(game_type, GameType::Blackjack | GameType::Baccarat)
+                    && (MIN_SHOE_DECKS..=MAX_SHOE_DECKS).contains(&self.shoe_decks)
+                    && (MIN_SHOE_PENETRATION..=MAX_SHOE_PENETRATION)
+                        .contains(&self.shoe_penetration)))
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            paytable: 0,
+            commission_on_win: false,
+            dealer_hits_soft_17: true,
+            max_split_hands: BLACKJACK_MAX_HANDS,
+            record_rng: false,
+            shoe_decks: 0,
+            shoe_penetration: DEFAULT_SHOE_PENETRATION,
+            dealer_peeks: false,
+            even_money: false,
+        }
+    }
+}
+
+impl Write for GameConfig {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.paytable.write(writer);
+        self.commission_on_win.write(writer);
+        self.dealer_hits_soft_17.write(writer);
+        self.max_split_hands.write(writer);
+        self.record_rng.write(writer);
+        self.shoe_decks.write(writer);
+        self.shoe_penetration.write(writer);
+        self.dealer_peeks.write(writer);
+        self.even_money.write(writer);
+    }
+}
+
+impl Read for GameConfig {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            paytable: u8::read(reader)?,
+            commission_on_win: bool::read(reader)?,
+            dealer_hits_soft_17: bool::read(reader)?,
+            max_split_hands: u8::read(reader)?,
+            record_rng: bool::read(reader)?,
+            shoe_decks: u8::read(reader)?,
+            shoe_penetration: u8::read(reader)?,
+            dealer_peeks: bool::read(reader)?,
+            even_money: bool::read(reader)?,
+        })
+    }
+}
+
+impl FixedSize for GameConfig {
+    const SIZE: usize = u8::SIZE
+        + bool::SIZE
+        + bool::SIZE
+        + u8::SIZE
+        + bool::SIZE
+        + u8::SIZE
+        + u8::SIZE
+        + bool::SIZE
+        + bool::SIZE;
+}
+
+/// An outcome drawn by a game's RNG (recorded in the order it was drawn).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RngDraw {
+    /// A card dealt (0-51, or 52 for the joker).
+    Card(u8),
+    /// A die rolled (1-6).
+    Die(u8),
+    /// A roulette spin (0-36, with 37 standing for 00).
+    Wheel(u8),
+    /// Any other value drawn (a money wheel segment, slot reel stop or mine cell).
+    Value(u8),
+}
+
+impl Write for RngDraw {
+    fn write(&self, writer: &mut impl BufMut) {
+        let (kind, value) = match *self {
+            Self::Card(card) => (0u8, card),
+            Self::Die(die) => (1, die),
+            Self::Wheel(pocket) => (2, pocket),
+            Self::Value(value) => (3, value),
+        };
+        kind.write(writer);
+        value.write(writer);
+    }
+}
+
+impl Read for RngDraw {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        let kind = u8::read(reader)?;
+        let value = u8::read(reader)?;
+        match kind {
+            0 => Ok(Self::Card(value)),
+            1 => Ok(Self::Die(value)),
+            2 => Ok(Self::Wheel(value)),
+            3 => Ok(Self::Value(value)),
+            i => Err(Error::InvalidEnum(i)),
+        }
+    }
+}
+
+impl FixedSize for RngDraw {
+    const SIZE: usize = u8::SIZE + u8::SIZE;
+}
+
+/// The hand a card was dealt to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Seat {
+    /// One of the player's hands (blackjack numbers split hands in table order; the baccarat
+    /// player hand is 0).
+    Player(u8),
+    /// The dealer's hand (the banker hand in baccarat).
+    Dealer,
+}
+
+impl Write for Seat {
+    fn write(&self, writer: &mut impl BufMut) {
+        let (kind, hand) = match *self {
+            Self::Player(hand) => (0u8, hand),
+            Self::Dealer => (1, 0),
+        };
+        kind.write(writer);
+        hand.write(writer);
+    }
+}
+
+impl Read for Seat {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        let kind = u8::read(reader)?;
+        let hand = u8::read(reader)?;
+        match (kind, hand) {
+            (0, hand) => Ok(Self::Player(hand)),
+            (1, 0) => Ok(Self::Dealer),
+            (1, _) => Err(Error::Invalid("Seat", "dealer has a single hand")),
+            (i, _) => Err(Error::InvalidEnum(i)),
+        }
+    }
+}
+
+impl FixedSize for Seat {
+    const SIZE: usize = u8::SIZE + u8::SIZE;
+}
+
+/// A craps bet resolved by a roll.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResolvedBet {
+    pub bet_type: u8,
+    pub target: u8,
+    /// Amount wagered (odds included).
+    pub amount: u64,
+    /// Stake plus winnings returned (0 if the bet lost), before any super mode multiplier.
+    pub payout: u64,
+}
+
+impl Write for ResolvedBet {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.bet_type.write(writer);
+        self.target.write(writer);
+        self.amount.write(writer);
+        self.payout.write(writer);
+    }
+}
+
+impl Read for ResolvedBet {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            bet_type: u8::read(reader)?,
+            target: u8::read(reader)?,
+            amount: u64::read(reader)?,
+            payout: u64::read(reader)?,
+        })
+    }
+}
+
+impl FixedSize for ResolvedBet {
+    const SIZE: usize = u8::SIZE + u8::SIZE + u64::SIZE + u64::SIZE;
+}
+
 /// Super mode multiplier type
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum SuperType {
     Card = 0,   // Specific card (rank+suit)
@@ -87,6 +350,7 @@ impl FixedSize for SuperType {
 
 /// Super mode multiplier entry
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SuperMultiplier {
     pub id: u8,          // Card (0-51), number (0-36), or total (4-17)
     pub multiplier: u16, // 2-500x
@@ -121,6 +385,7 @@ impl EncodeSize for SuperMultiplier {
 
 /// Super mode state
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SuperModeState {
     pub is_active: bool,
     pub multipliers: Vec<SuperMultiplier>,
@@ -0,0 +1,177 @@
+use bytes::{Buf, BufMut};
+use commonware_codec::{EncodeSize, Error, FixedSize, Read, ReadExt, ReadRangeExt, Write};
+
+use super::{GameType, GAME_HISTORY_LENGTH, GAME_STATS_INTERVAL};
+
+/// The result of a completed game session.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRecord {
+    pub session_id: u64,
+    pub game_type: GameType,
+    pub bet: u64,
+    /// Payout reported when the session completed (negative for losses).
+    pub payout: i64,
+    /// View the session completed in.
+    pub view: u64,
+}
+
+impl Write for GameRecord {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.session_id.write(writer);
+        self.game_type.write(writer);
+        self.bet.write(writer);
+        self.payout.write(writer);
+        self.view.write(writer);
+    }
+}
+
+impl Read for GameRecord {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            session_id: u64::read(reader)?,
+            game_type: GameType::read(reader)?,
+            bet: u64::read(reader)?,
+            payout: i64::read(reader)?,
+            view: u64::read(reader)?,
+        })
+    }
+}
+
+impl FixedSize for GameRecord {
+    const SIZE: usize = u64::SIZE + GameType::SIZE + u64::SIZE + i64::SIZE + u64::SIZE;
+}
+
+/// The most recent results of a player (oldest first, at most `GAME_HISTORY_LENGTH`).
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameHistory {
+    pub records: Vec<GameRecord>,
+    /// Sessions completed in (or before) this view have been deleted from state.
+    pub reclaimed_through: u64,
+}
+
+impl GameHistory {
+    /// Append a result (dropping the oldest once the history is full), returning the results
+    /// dropped.
+    pub fn push(&mut self, record: GameRecord) -> Vec<GameRecord> {
+        let mut dropped = Vec::new();
+        if self.records.len() >= GAME_HISTORY_LENGTH {
+            let excess = self.records.len() + 1 - GAME_HISTORY_LENGTH;
+            dropped.extend(self.records.drain(..excess));
+        }
+        self.records.push(record);
+        dropped
+    }
+
+    /// Mark the sessions completed in (or before) `view` as reclaimed, returning those that
+    /// weren't already.
+    pub fn reclaim(&mut self, view: u64) -> Vec<u64> {
+        if view <= self.reclaimed_through {
+            return Vec::new();
+        }
+        let sessions = self
+            .records
+            .iter()
+            .filter(|record| record.view > self.reclaimed_through && record.view <= view)
+            .map(|record| record.session_id)
+            .collect();
+        self.reclaimed_through = view;
+        sessions
+    }
+}
+
+impl Write for GameHistory {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.records.write(writer);
+        self.reclaimed_through.write(writer);
+    }
+}
+
+impl Read for GameHistory {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        let records = Vec::<GameRecord>::read_range(reader, 0..=GAME_HISTORY_LENGTH)?;
+
+        // Optional extension (histories stored before sessions were reclaimed have reclaimed
+        // nothing)
+        let reclaimed_through = if reader.remaining() >= u64::SIZE {
+            u64::read(reader)?
+        } else {
+            0
+        };
+        Ok(Self {
+            records,
+            reclaimed_through,
+        })
+    }
+}
+
+impl EncodeSize for GameHistory {
+    fn encode_size(&self) -> usize {
+        self.records.encode_size() + self.reclaimed_through.encode_size()
+    }
+}
+
+/// Running totals of a game's completed (non-tournament) sessions.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameStats {
+    pub hands_played: u64,
+    pub total_wagered: u64,
+    /// Returned to players, stakes included (so the game's RTP is `total_paid_out` over
+    /// `total_wagered`).
+    pub total_paid_out: u64,
+    /// Largest return from a single session.
+    pub biggest_win: u64,
+}
+
+impl GameStats {
+    /// Add a completed session (its total wager and the payout reported when it completed,
+    /// negative for losses), returning whether the totals are due to be reported.
+    pub fn record(&mut self, wagered: u64, payout: i64) -> bool {
+        let returned = u64::try_from(payout).unwrap_or(0);
+        self.hands_played = self.hands_played.saturating_add(1);
+        self.total_wagered = self.total_wagered.saturating_add(wagered);
+        self.total_paid_out = self.total_paid_out.saturating_add(returned);
+        self.biggest_win = self.biggest_win.max(returned);
+        self.hands_played % GAME_STATS_INTERVAL == 0
+    }
+
+    /// Return to player in basis points (0 before anything has been wagered).
+    pub fn rtp_bps(&self) -> u64 {
+        if self.total_wagered == 0 {
+            return 0;
+        }
+        (self.total_paid_out as u128 * 10_000 / self.total_wagered as u128) as u64
+    }
+}
+
+impl Write for GameStats {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.hands_played.write(writer);
+        self.total_wagered.write(writer);
+        self.total_paid_out.write(writer);
+        self.biggest_win.write(writer);
+    }
+}
+
+impl Read for GameStats {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            hands_played: u64::read(reader)?,
+            total_wagered: u64::read(reader)?,
+            total_paid_out: u64::read(reader)?,
+            biggest_win: u64::read(reader)?,
+        })
+    }
+}
+
+impl FixedSize for GameStats {
+    const SIZE: usize = 4 * u64::SIZE;
+}
@@ -1,15 +1,17 @@
 use bytes::{Buf, BufMut};
-use commonware_codec::{EncodeSize, Error, Read, ReadExt, ReadRangeExt, Write};
+use commonware_codec::{EncodeSize, Error, FixedSize, Read, ReadExt, ReadRangeExt, Write};
 use commonware_cryptography::ed25519::PublicKey;
 
-use super::{read_string, string_encode_size, write_string, MAX_NAME_LENGTH};
+use super::{read_string, string_encode_size, write_string, Chips, MAX_NAME_LENGTH};
 
 /// Casino leaderboard entry
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LeaderboardEntry {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
     pub player: PublicKey,
     pub name: String,
-    pub chips: u64,
+    pub chips: Chips,
     pub rank: u32,
 }
 
@@ -29,7 +31,7 @@ impl Read for LeaderboardEntry {
         Ok(Self {
             player: PublicKey::read(reader)?,
             name: read_string(reader, MAX_NAME_LENGTH)?,
-            chips: u64::read(reader)?,
+            chips: Chips::read(reader)?,
             rank: u32::read(reader)?,
         })
     }
@@ -46,12 +48,17 @@ impl EncodeSize for LeaderboardEntry {
 
 /// Casino leaderboard
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CasinoLeaderboard {
     pub entries: Vec<LeaderboardEntry>,
+    /// Season the entries belong to (only used by the global leaderboard).
+    pub season: u64,
+    /// View the season ends at (0 until the season has started).
+    pub season_end: u64,
 }
 
 impl CasinoLeaderboard {
-    pub fn update(&mut self, player: PublicKey, name: String, chips: u64) {
+    pub fn update(&mut self, player: PublicKey, name: String, chips: Chips) {
         // Find and remove existing entry for this player
         if let Some(idx) = self.entries.iter().position(|e| e.player == player) {
             self.entries.remove(idx);
@@ -96,6 +103,8 @@ impl CasinoLeaderboard {
 impl Write for CasinoLeaderboard {
     fn write(&self, writer: &mut impl BufMut) {
         self.entries.write(writer);
+        self.season.write(writer);
+        self.season_end.write(writer);
     }
 }
 
@@ -103,16 +112,27 @@ impl Read for CasinoLeaderboard {
     type Cfg = ();
 
     fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        // Read up to 10 entries (matches truncate(10) in update())
+        // FIXED: Use inclusive range 0..=10 to allow exactly 10 entries
+        let entries = Vec::<LeaderboardEntry>::read_range(reader, 0..=10)?;
+
+        // Leaderboards stored before seasons existed are in the first (not yet started) season
+        let (season, season_end) = if reader.remaining() >= u64::SIZE * 2 {
+            (u64::read(reader)?, u64::read(reader)?)
+        } else {
+            (0, 0)
+        };
+
         Ok(Self {
-            // Read up to 10 entries (matches truncate(10) in update())
-            // FIXED: Use inclusive range 0..=10 to allow exactly 10 entries
-            entries: Vec::<LeaderboardEntry>::read_range(reader, 0..=10)?,
+            entries,
+            season,
+            season_end,
         })
     }
 }
 
 impl EncodeSize for CasinoLeaderboard {
     fn encode_size(&self) -> usize {
-        self.entries.encode_size()
+        self.entries.encode_size() + self.season.encode_size() + self.season_end.encode_size()
     }
 }
@@ -1,18 +1,28 @@
 mod codec;
 mod constants;
 mod economy;
+mod error;
 mod game;
+mod history;
 mod leaderboard;
+mod orders;
 mod player;
+mod recovery;
 mod tournament;
+mod vesting;
 
 pub use codec::{read_string, string_encode_size, write_string};
 pub use constants::*;
 pub use economy::*;
+pub use error::*;
 pub use game::*;
+pub use history::*;
 pub use leaderboard::*;
+pub use orders::*;
 pub use player::*;
+pub use recovery::*;
 pub use tournament::*;
+pub use vesting::*;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,77 @@
+use bytes::{Buf, BufMut};
+use commonware_codec::{EncodeSize, Error, FixedSize, Read, ReadExt, ReadRangeExt, Write};
+use commonware_cryptography::ed25519::PublicKey;
+
+use super::MAX_OPEN_LIMIT_ORDERS;
+
+/// An order to swap against the AMM once the price reaches `min_price`.
+///
+/// The input (vUSDT when buying RNG, RNG otherwise) is escrowed when the order is placed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LimitOrder {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub owner: PublicKey,
+    pub amount_in: u64,
+    /// Minimum output per unit of input (scaled by PRICE_SCALE).
+    pub min_price: u64,
+    pub is_buying_rng: bool,
+}
+
+impl Write for LimitOrder {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.owner.write(writer);
+        self.amount_in.write(writer);
+        self.min_price.write(writer);
+        self.is_buying_rng.write(writer);
+    }
+}
+
+impl Read for LimitOrder {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            owner: PublicKey::read(reader)?,
+            amount_in: u64::read(reader)?,
+            min_price: u64::read(reader)?,
+            is_buying_rng: bool::read(reader)?,
+        })
+    }
+}
+
+impl FixedSize for LimitOrder {
+    const SIZE: usize = PublicKey::SIZE + u64::SIZE + u64::SIZE + bool::SIZE;
+}
+
+/// Open limit orders (in the order they are matched).
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LimitOrderBook {
+    pub next_id: u64,
+    pub open: Vec<u64>,
+}
+
+impl Write for LimitOrderBook {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.next_id.write(writer);
+        self.open.write(writer);
+    }
+}
+
+impl Read for LimitOrderBook {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            next_id: u64::read(reader)?,
+            open: Vec::<u64>::read_range(reader, 0..=MAX_OPEN_LIMIT_ORDERS)?,
+        })
+    }
+}
+
+impl EncodeSize for LimitOrderBook {
+    fn encode_size(&self) -> usize {
+        self.next_id.encode_size() + self.open.encode_size()
+    }
+}
@@ -1,22 +1,159 @@
 use bytes::{Buf, BufMut};
-use commonware_codec::{EncodeSize, Error, Read, ReadExt, ReadRangeExt, Write};
-use commonware_cryptography::ed25519::PublicKey;
+use commonware_codec::{EncodeSize, Error, FixedSize, Read, ReadExt, ReadRangeExt, Write};
+use commonware_cryptography::{
+    ed25519::PublicKey,
+    sha256::{Digest, Sha256},
+    Hasher,
+};
 
 use super::{
-    read_string, string_encode_size, write_string, GameType, SuperModeState, INITIAL_CHIPS,
-    MAX_NAME_LENGTH, STARTING_DOUBLES, STARTING_SHIELDS,
+    read_string, string_encode_size, write_string, Chips, GameType, SuperModeState, Vusdt,
+    INITIAL_CHIPS, MAX_ACTIVE_SESSIONS, MAX_AVATAR_ID, MAX_NAME_LENGTH, MAX_TITLE_LENGTH,
+    SESSION_ID_NAMESPACE, STARTING_DOUBLES, STARTING_SHIELDS,
 };
 
+/// Customizable profile (shown instead of the registration name once set)
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Profile {
+    pub display_name: String,
+    pub avatar_id: u32,
+    pub title: String,
+    pub updated_at: u64, // View of the last update (0 if never updated)
+}
+
+impl Profile {
+    /// Whether `text` only contains characters allowed in profiles (ASCII letters, digits,
+    /// spaces, `_`, `-` and `.`, without leading or trailing spaces).
+    pub fn is_valid_text(text: &str) -> bool {
+        text.trim() == text
+            && text
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '_' | '-' | '.'))
+    }
+
+    /// Check the fields of a profile update (returning the reason it is invalid).
+    pub fn validate(display_name: &str, avatar_id: u32, title: &str) -> Result<(), &'static str> {
+        if display_name.is_empty() || display_name.len() > MAX_NAME_LENGTH {
+            return Err("Invalid display name length");
+        }
+        if title.len() > MAX_TITLE_LENGTH {
+            return Err("Title too long");
+        }
+        if !Self::is_valid_text(display_name) || !Self::is_valid_text(title) {
+            return Err("Invalid characters in profile");
+        }
+        if avatar_id > MAX_AVATAR_ID {
+            return Err("Invalid avatar");
+        }
+        Ok(())
+    }
+}
+
+impl Write for Profile {
+    fn write(&self, writer: &mut impl BufMut) {
+        write_string(&self.display_name, writer);
+        self.avatar_id.write(writer);
+        write_string(&self.title, writer);
+        self.updated_at.write(writer);
+    }
+}
+
+impl Read for Profile {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            display_name: read_string(reader, MAX_NAME_LENGTH)?,
+            avatar_id: u32::read(reader)?,
+            title: read_string(reader, MAX_TITLE_LENGTH)?,
+            updated_at: u64::read(reader)?,
+        })
+    }
+}
+
+impl EncodeSize for Profile {
+    fn encode_size(&self) -> usize {
+        string_encode_size(&self.display_name)
+            + self.avatar_id.encode_size()
+            + string_encode_size(&self.title)
+            + self.updated_at.encode_size()
+    }
+}
+
+/// Bonus chips granted by promotions, held apart from `chips` until their wagering requirement
+/// is met (so they can't be withdrawn, swapped or staked before then).
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BonusBalance {
+    /// Bonus chips awaiting release.
+    pub chips: Chips,
+    /// Chips still to be wagered (weighted by each game's contribution, see
+    /// [super::BonusSettings]) before `chips` are released.
+    pub wagering_remaining: u64,
+}
+
+impl BonusBalance {
+    /// Add a bonus of `amount` chips that requires `requirement` more chips to be wagered,
+    /// returning whether it fits (the balance is unchanged otherwise).
+    pub fn grant(&mut self, amount: Chips, requirement: u64) -> bool {
+        let Some(chips) = self.chips.checked_add(amount) else {
+            return false;
+        };
+        self.chips = chips;
+        self.wagering_remaining = self.wagering_remaining.saturating_add(requirement);
+        true
+    }
+
+    /// Count `contribution` chips towards the requirement, returning the chips released once
+    /// it is met.
+    pub fn wager(&mut self, contribution: u64) -> Chips {
+        if self.chips.is_zero() {
+            return Chips::ZERO;
+        }
+        self.wagering_remaining = self.wagering_remaining.saturating_sub(contribution);
+        if self.wagering_remaining > 0 {
+            return Chips::ZERO;
+        }
+        std::mem::take(&mut self.chips)
+    }
+}
+
+impl Write for BonusBalance {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.chips.write(writer);
+        self.wagering_remaining.write(writer);
+    }
+}
+
+impl Read for BonusBalance {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            chips: Chips::read(reader)?,
+            wagering_remaining: u64::read(reader)?,
+        })
+    }
+}
+
+impl FixedSize for BonusBalance {
+    const SIZE: usize = Chips::SIZE + u64::SIZE;
+}
+
 /// Player state for casino games
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Player {
     pub nonce: u64,
     pub name: String,
-    pub chips: u64,
-    pub vusdt_balance: u64, // Virtual USDT balance
+    /// RNG balance (chips are RNG: games, staking, vaults and the AMM all use this balance,
+    /// so no conversion is needed to move winnings into DeFi).
+    pub chips: Chips,
+    pub vusdt_balance: Vusdt, // Virtual USDT balance
     pub shields: u32,
     pub doubles: u32,
-    pub tournament_chips: u64,
+    pub tournament_chips: Chips,
     pub tournament_shields: u32,
     pub tournament_doubles: u32,
     pub active_tournament: Option<u64>,
@@ -24,7 +161,8 @@ pub struct Player {
     pub active_shield: bool,
     pub active_double: bool,
     pub active_super: bool,
-    pub active_session: Option<u64>,
+    /// Open (incomplete) game sessions, oldest first.
+    pub active_sessions: Vec<u64>,
     pub last_deposit_block: u64,
     /// Aura Meter for Super Mode (0-5 segments).
     /// Increments on near-misses, triggers Super Aura Round at 5.
@@ -33,6 +171,17 @@ pub struct Player {
     pub tournaments_played_today: u8,
     pub last_tournament_ts: u64,
     pub is_kyc_verified: bool,
+    /// Consecutive daily bonus claims (see [super::DailyBonus]).
+    pub daily_streak: u32,
+    pub profile: Profile,
+    /// Chips wagered in completed (non-tournament) games.
+    pub total_wagered: u64,
+    /// Loyalty points earned by wagering (see [super::VipSettings]).
+    pub loyalty_points: u64,
+    /// VIP tier reached (0 until the first tier is reached).
+    pub vip_tier: u8,
+    /// Bonus chips awaiting their wagering requirement.
+    pub bonus: BonusBalance,
 }
 
 impl Player {
@@ -40,11 +189,11 @@ impl Player {
         Self {
             nonce: 0,
             name,
-            chips: INITIAL_CHIPS,
-            vusdt_balance: 0,
+            chips: Chips(INITIAL_CHIPS),
+            vusdt_balance: Vusdt::ZERO,
             shields: STARTING_SHIELDS,
             doubles: STARTING_DOUBLES,
-            tournament_chips: 0,
+            tournament_chips: Chips::ZERO,
             tournament_shields: 0,
             tournament_doubles: 0,
             active_tournament: None,
@@ -52,13 +201,19 @@ impl Player {
             active_shield: false,
             active_double: false,
             active_super: false,
-            active_session: None,
+            active_sessions: Vec::new(),
             // Allow an immediate first faucet deposit
             last_deposit_block: 0,
             aura_meter: 0,
             tournaments_played_today: 0,
             last_tournament_ts: 0,
             is_kyc_verified: false,
+            daily_streak: 0,
+            profile: Profile::default(),
+            total_wagered: 0,
+            loyalty_points: 0,
+            vip_tier: 0,
+            bonus: BonusBalance::default(),
         }
     }
 
@@ -66,11 +221,11 @@ impl Player {
         Self {
             nonce: 0,
             name,
-            chips: INITIAL_CHIPS,
-            vusdt_balance: 0,
+            chips: Chips(INITIAL_CHIPS),
+            vusdt_balance: Vusdt::ZERO,
             shields: STARTING_SHIELDS,
             doubles: STARTING_DOUBLES,
-            tournament_chips: 0,
+            tournament_chips: Chips::ZERO,
             tournament_shields: 0,
             tournament_doubles: 0,
             active_tournament: None,
@@ -78,13 +233,41 @@ impl Player {
             active_shield: false,
             active_double: false,
             active_super: false,
-            active_session: None,
+            active_sessions: Vec::new(),
             // Allow an immediate first faucet claim (daily limit is enforced by the executor).
             last_deposit_block: 0,
             aura_meter: 0,
             tournaments_played_today: 0,
             last_tournament_ts: 0,
             is_kyc_verified: false,
+            daily_streak: 0,
+            profile: Profile::default(),
+            total_wagered: 0,
+            loyalty_points: 0,
+            vip_tier: 0,
+            bonus: BonusBalance::default(),
+        }
+    }
+
+    /// Whether the player can open another game session under the house limit.
+    pub fn can_open_session(&self, max_active_sessions: u32) -> bool {
+        let limit = (max_active_sessions as usize).min(MAX_ACTIVE_SESSIONS);
+        self.active_sessions.len() < limit
+    }
+
+    /// Stop tracking `session_id` as open (returns whether it was tracked).
+    pub fn release_session(&mut self, session_id: u64) -> bool {
+        let before = self.active_sessions.len();
+        self.active_sessions.retain(|id| *id != session_id);
+        self.active_sessions.len() != before
+    }
+
+    /// The name to show for the player (the profile's display name, if set).
+    pub fn display_name(&self) -> &str {
+        if self.profile.display_name.is_empty() {
+            &self.name
+        } else {
+            &self.profile.display_name
         }
     }
 }
@@ -105,12 +288,20 @@ impl Write for Player {
         self.active_shield.write(writer);
         self.active_double.write(writer);
         self.active_super.write(writer);
-        self.active_session.write(writer);
+        // Legacy single-session slot (the full set is appended after the profile)
+        self.active_sessions.first().copied().write(writer);
         self.last_deposit_block.write(writer);
         self.aura_meter.write(writer);
         self.tournaments_played_today.write(writer);
         self.last_tournament_ts.write(writer);
         self.is_kyc_verified.write(writer);
+        self.daily_streak.write(writer);
+        self.profile.write(writer);
+        self.active_sessions.write(writer);
+        self.total_wagered.write(writer);
+        self.loyalty_points.write(writer);
+        self.vip_tier.write(writer);
+        self.bonus.write(writer);
     }
 }
 
@@ -118,14 +309,14 @@ impl Read for Player {
     type Cfg = ();
 
     fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
-        Ok(Self {
+        let mut player = Self {
             nonce: u64::read(reader)?,
             name: read_string(reader, MAX_NAME_LENGTH)?,
-            chips: u64::read(reader)?,
-            vusdt_balance: u64::read(reader)?,
+            chips: Chips::read(reader)?,
+            vusdt_balance: Vusdt::read(reader)?,
             shields: u32::read(reader)?,
             doubles: u32::read(reader)?,
-            tournament_chips: u64::read(reader)?,
+            tournament_chips: Chips::read(reader)?,
             tournament_shields: u32::read(reader)?,
             tournament_doubles: u32::read(reader)?,
             active_tournament: Option::<u64>::read(reader)?,
@@ -133,13 +324,40 @@ impl Read for Player {
             active_shield: bool::read(reader)?,
             active_double: bool::read(reader)?,
             active_super: bool::read(reader)?,
-            active_session: Option::<u64>::read(reader)?,
+            active_sessions: Option::<u64>::read(reader)?.into_iter().collect(),
             last_deposit_block: u64::read(reader)?,
             aura_meter: u8::read(reader)?,
             tournaments_played_today: u8::read(reader)?,
             last_tournament_ts: u64::read(reader)?,
             is_kyc_verified: bool::read(reader)?,
-        })
+            daily_streak: 0,
+            profile: Profile::default(),
+            total_wagered: 0,
+            loyalty_points: 0,
+            vip_tier: 0,
+            bonus: BonusBalance::default(),
+        };
+
+        // Optional extensions (backwards compatible with players stored before daily bonuses,
+        // profiles, concurrent sessions, loyalty points and bonuses).
+        if reader.remaining() >= u32::SIZE {
+            player.daily_streak = u32::read(reader)?;
+        }
+        if reader.has_remaining() {
+            player.profile = Profile::read(reader)?;
+        }
+        if reader.has_remaining() {
+            player.active_sessions = Vec::<u64>::read_range(reader, 0..=MAX_ACTIVE_SESSIONS)?;
+        }
+        if reader.remaining() >= u64::SIZE + u64::SIZE + u8::SIZE {
+            player.total_wagered = u64::read(reader)?;
+            player.loyalty_points = u64::read(reader)?;
+            player.vip_tier = u8::read(reader)?;
+        }
+        if reader.remaining() >= BonusBalance::SIZE {
+            player.bonus = BonusBalance::read(reader)?;
+        }
+        Ok(player)
     }
 }
 
@@ -159,22 +377,48 @@ impl EncodeSize for Player {
             + self.active_shield.encode_size()
             + self.active_double.encode_size()
             + self.active_super.encode_size()
-            + self.active_session.encode_size()
+            + self.active_sessions.first().copied().encode_size()
             + self.last_deposit_block.encode_size()
             + self.aura_meter.encode_size()
             + self.tournaments_played_today.encode_size()
             + self.last_tournament_ts.encode_size()
             + self.is_kyc_verified.encode_size()
+            + self.daily_streak.encode_size()
+            + self.profile.encode_size()
+            + self.active_sessions.encode_size()
+            + self.total_wagered.encode_size()
+            + self.loyalty_points.encode_size()
+            + self.vip_tier.encode_size()
+            + self.bonus.encode_size()
     }
 }
 
+/// The ID of the session `player` starts with the transaction of `nonce`: the first 8 bytes
+/// (big-endian) of `sha256(SESSION_ID_NAMESPACE || player || nonce)`.
+///
+/// `CasinoStartGame` must use this ID, so sessions can't collide across players (and a player
+/// can't reuse one). A batch can therefore start at most one session.
+pub fn derive_session_id(player: &PublicKey, nonce: u64) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(SESSION_ID_NAMESPACE);
+    hasher.update(player.as_ref());
+    hasher.update(&nonce.to_be_bytes());
+    let digest = hasher.finalize();
+    let mut id = [0u8; 8];
+    id.copy_from_slice(&digest.as_ref()[..8]);
+    u64::from_be_bytes(id)
+}
+
 /// Game session state
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameSession {
     pub id: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
     pub player: PublicKey,
     pub game_type: GameType,
     pub bet: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::bytes"))]
     pub state_blob: Vec<u8>,
     pub move_count: u32,
     pub created_at: u64,
@@ -182,6 +426,9 @@ pub struct GameSession {
     pub super_mode: SuperModeState,
     pub is_tournament: bool,
     pub tournament_id: Option<u64>,
+    pub last_move_at: u64, // View of the last move (or creation)
+    pub wagered: u64,      // Total wagered (the initial bet plus bets placed by moves)
+    pub entropy: Option<PlayerEntropy>,
 }
 
 impl Write for GameSession {
@@ -197,6 +444,9 @@ impl Write for GameSession {
         self.super_mode.write(writer);
         self.is_tournament.write(writer);
         self.tournament_id.write(writer);
+        self.last_move_at.write(writer);
+        self.wagered.write(writer);
+        self.entropy.write(writer);
     }
 }
 
@@ -204,7 +454,7 @@ impl Read for GameSession {
     type Cfg = ();
 
     fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
-        Ok(Self {
+        let mut session = Self {
             id: u64::read(reader)?,
             player: PublicKey::read(reader)?,
             game_type: GameType::read(reader)?,
@@ -216,7 +466,28 @@ impl Read for GameSession {
             super_mode: SuperModeState::read(reader)?,
             is_tournament: bool::read(reader)?,
             tournament_id: Option::<u64>::read(reader)?,
-        })
+            last_move_at: 0,
+            wagered: 0,
+            entropy: None,
+        };
+
+        // Optional extensions (sessions stored before moves were tracked count from creation,
+        // those stored before wagers were tracked only count the initial bet, and those stored
+        // before player entropy have none).
+        session.last_move_at = if reader.remaining() >= u64::SIZE {
+            u64::read(reader)?
+        } else {
+            session.created_at
+        };
+        session.wagered = if reader.remaining() >= u64::SIZE {
+            u64::read(reader)?
+        } else {
+            session.bet
+        };
+        if reader.has_remaining() {
+            session.entropy = Option::<PlayerEntropy>::read(reader)?;
+        }
+        Ok(session)
     }
 }
 
@@ -233,5 +504,71 @@ impl EncodeSize for GameSession {
             + self.super_mode.encode_size()
             + self.is_tournament.encode_size()
             + self.tournament_id.encode_size()
+            + self.last_move_at.encode_size()
+            + self.wagered.encode_size()
+            + self.entropy.encode_size()
     }
 }
+
+/// Entropy a player contributes to a session's moves (committed when the game starts and
+/// revealed by the first move).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlayerEntropy {
+    /// Hash of the preimage the first move must reveal.
+    Committed(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Digest),
+    /// Preimage mixed into the RNG of every move after it is revealed.
+    Revealed(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Digest),
+}
+
+impl PlayerEntropy {
+    /// Reveal the preimage of a commitment (returning whether it matches).
+    pub fn reveal(&mut self, preimage: Digest) -> bool {
+        match self {
+            Self::Committed(commitment) if Sha256::hash(preimage.as_ref()) == *commitment => {
+                *self = Self::Revealed(preimage);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The revealed preimage (if any).
+    pub fn revealed(&self) -> Option<&Digest> {
+        match self {
+            Self::Committed(_) => None,
+            Self::Revealed(preimage) => Some(preimage),
+        }
+    }
+}
+
+impl Write for PlayerEntropy {
+    fn write(&self, writer: &mut impl BufMut) {
+        match self {
+            Self::Committed(commitment) => {
+                0u8.write(writer);
+                commitment.write(writer);
+            }
+            Self::Revealed(preimage) => {
+                1u8.write(writer);
+                preimage.write(writer);
+            }
+        }
+    }
+}
+
+impl Read for PlayerEntropy {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        match u8::read(reader)? {
+            0 => Ok(Self::Committed(Digest::read(reader)?)),
+            1 => Ok(Self::Revealed(Digest::read(reader)?)),
+            i => Err(Error::InvalidEnum(i)),
+        }
+    }
+}
+
+impl FixedSize for PlayerEntropy {
+    const SIZE: usize = 1 + Digest::SIZE;
+}
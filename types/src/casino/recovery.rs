@@ -0,0 +1,138 @@
+use bytes::{Buf, BufMut};
+use commonware_codec::{EncodeSize, Error, Read, ReadExt, ReadRangeExt, Write};
+use commonware_cryptography::ed25519::PublicKey;
+
+use super::{MAX_GUARDIANS, RECOVERY_DELAY};
+
+/// A pending move of an account to a new key, approved by its guardians.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Recovery {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub new_key: PublicKey,
+    /// Guardians that approved the move.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::vec"))]
+    pub approvals: Vec<PublicKey>,
+    /// View the move can be executed from (0 until enough guardians approve it).
+    pub ready_view: u64,
+}
+
+impl Write for Recovery {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.new_key.write(writer);
+        self.approvals.write(writer);
+        self.ready_view.write(writer);
+    }
+}
+
+impl Read for Recovery {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            new_key: PublicKey::read(reader)?,
+            approvals: Vec::<PublicKey>::read_range(reader, 0..=MAX_GUARDIANS)?,
+            ready_view: u64::read(reader)?,
+        })
+    }
+}
+
+impl EncodeSize for Recovery {
+    fn encode_size(&self) -> usize {
+        self.new_key.encode_size() + self.approvals.encode_size() + self.ready_view.encode_size()
+    }
+}
+
+/// Keys that can jointly move an account to a new key (if its owner loses theirs).
+///
+/// Once `threshold` guardians approve a new key, the owner has `RECOVERY_DELAY` views to
+/// cancel the recovery (by setting their guardians again) before any guardian can execute it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GuardianSet {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::vec"))]
+    pub guardians: Vec<PublicKey>,
+    pub threshold: u8,
+    pub recovery: Option<Recovery>,
+}
+
+impl GuardianSet {
+    /// Whether `threshold` of `guardians` (distinct keys, excluding `owner`) is a valid set.
+    pub fn is_valid(owner: &PublicKey, guardians: &[PublicKey], threshold: u8) -> bool {
+        if guardians.len() > MAX_GUARDIANS || guardians.contains(owner) {
+            return false;
+        }
+        if threshold == 0 || threshold as usize > guardians.len() {
+            return false;
+        }
+        guardians
+            .iter()
+            .enumerate()
+            .all(|(i, guardian)| !guardians[..i].contains(guardian))
+    }
+
+    /// Record `guardian`'s approval of moving the account to `new_key` in `view` (replacing
+    /// any pending recovery to a different key that hasn't reached the threshold), returning
+    /// whether the approval is new.
+    ///
+    /// A recovery that reached the threshold is kept until the owner cancels it (by setting
+    /// the guardians), so a single guardian can't discard it.
+    ///
+    /// The caller must check that `guardian` is one of the guardians.
+    pub fn approve(&mut self, guardian: &PublicKey, new_key: &PublicKey, view: u64) -> bool {
+        if let Some(recovery) = &self.recovery {
+            if recovery.new_key != *new_key {
+                if recovery.ready_view != 0 {
+                    return false;
+                }
+                self.recovery = None;
+            }
+        }
+        let recovery = self.recovery.get_or_insert_with(|| Recovery {
+            new_key: new_key.clone(),
+            approvals: Vec::new(),
+            ready_view: 0,
+        });
+        if recovery.approvals.contains(guardian) {
+            return false;
+        }
+        recovery.approvals.push(guardian.clone());
+        if recovery.ready_view == 0 && recovery.approvals.len() >= self.threshold as usize {
+            recovery.ready_view = view.saturating_add(RECOVERY_DELAY);
+        }
+        true
+    }
+
+    /// Whether the pending recovery can be executed in `view`.
+    pub fn is_ready(&self, view: u64) -> bool {
+        self.recovery
+            .as_ref()
+            .is_some_and(|recovery| recovery.ready_view != 0 && view >= recovery.ready_view)
+    }
+}
+
+impl Write for GuardianSet {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.guardians.write(writer);
+        self.threshold.write(writer);
+        self.recovery.write(writer);
+    }
+}
+
+impl Read for GuardianSet {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            guardians: Vec::<PublicKey>::read_range(reader, 0..=MAX_GUARDIANS)?,
+            threshold: u8::read(reader)?,
+            recovery: Option::<Recovery>::read(reader)?,
+        })
+    }
+}
+
+impl EncodeSize for GuardianSet {
+    fn encode_size(&self) -> usize {
+        self.guardians.encode_size() + self.threshold.encode_size() + self.recovery.encode_size()
+    }
+}
@@ -1,7 +1,10 @@
 use super::*;
+use crate::execution::Instruction;
 use commonware_codec::Encode;
+use commonware_codec::EncodeSize;
+use commonware_codec::FixedSize;
 use commonware_codec::ReadExt;
-use commonware_cryptography::{ed25519::PrivateKey, PrivateKeyExt, Signer};
+use commonware_cryptography::{ed25519::PrivateKey, sha256::Sha256, Hasher, PrivateKeyExt, Signer};
 use rand::{rngs::StdRng, SeedableRng};
 
 #[test]
@@ -17,6 +20,13 @@ fn test_game_type_roundtrip() {
         GameType::SicBo,
         GameType::ThreeCard,
         GameType::UltimateHoldem,
+        GameType::Slots,
+        GameType::Mines,
+        GameType::PaiGow,
+        GameType::CaribbeanStud,
+        GameType::MississippiStud,
+        GameType::MoneyWheel,
+        GameType::FourCard,
     ] {
         let encoded = game_type.encode();
         let decoded = GameType::read(&mut &encoded[..]).unwrap();
@@ -24,12 +34,412 @@ fn test_game_type_roundtrip() {
     }
 }
 
+#[test]
+fn test_error_code_roundtrip() {
+    for code in ERROR_PLAYER_ALREADY_REGISTERED..=ERROR_INVALID_SESSION_ID {
+        let error_code = CasinoErrorCode::from(code);
+        assert!(!matches!(error_code, CasinoErrorCode::Unknown(_)));
+        assert_eq!(u8::from(error_code), code);
+        assert_eq!(error_code.encode().as_ref(), &[code]);
+        assert_eq!(CasinoErrorCode::read(&mut &[code][..]).unwrap(), error_code);
+    }
+    assert_eq!(
+        CasinoErrorCode::from(ERROR_INSUFFICIENT_FUNDS),
+        CasinoErrorCode::InsufficientFunds
+    );
+
+    // Codes from newer releases are kept as they are
+    for code in [0, ERROR_INVALID_SESSION_ID + 1, u8::MAX] {
+        let error_code = CasinoErrorCode::read(&mut &[code][..]).unwrap();
+        assert_eq!(error_code, CasinoErrorCode::Unknown(code));
+        assert_eq!(error_code.encode().as_ref(), &[code]);
+    }
+}
+
+#[test]
+fn test_derive_session_id() {
+    let player = PrivateKey::from_seed(0).public_key();
+    let other = PrivateKey::from_seed(1).public_key();
+
+    // The first 8 bytes of the namespaced hash of the player and nonce
+    let mut preimage = SESSION_ID_NAMESPACE.to_vec();
+    preimage.extend_from_slice(player.as_ref());
+    preimage.extend_from_slice(&7u64.to_be_bytes());
+    let digest = Sha256::hash(&preimage);
+    assert_eq!(
+        derive_session_id(&player, 7).to_be_bytes(),
+        digest.as_ref()[..8]
+    );
+
+    // Distinct across nonces and players
+    assert_ne!(derive_session_id(&player, 7), derive_session_id(&player, 8));
+    assert_ne!(derive_session_id(&player, 7), derive_session_id(&other, 7));
+}
+
 #[test]
 fn test_player_roundtrip() {
     let player = Player::new("TestPlayer".to_string());
     let encoded = player.encode();
     let decoded = Player::read(&mut &encoded[..]).unwrap();
     assert_eq!(player, decoded);
+
+    // Players stored before profiles have none (and those stored before daily bonuses have no
+    // streak)
+    let mut player = player;
+    player.daily_streak = 3;
+    player.profile = Profile {
+        display_name: "Ace".to_string(),
+        avatar_id: 7,
+        title: "High Roller".to_string(),
+        updated_at: 10,
+    };
+    player.active_sessions = vec![4, 9];
+    player.total_wagered = 500;
+    player.loyalty_points = 5;
+    player.vip_tier = 1;
+    let encoded = player.encode();
+    assert_eq!(Player::read(&mut &encoded[..]).unwrap(), player);
+
+    // Players stored before loyalty points have none
+    let legacy = &encoded[..encoded.len() - u64::SIZE - u64::SIZE - u8::SIZE];
+    let decoded = Player::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.active_sessions, vec![4, 9]);
+    assert_eq!(
+        (
+            decoded.total_wagered,
+            decoded.loyalty_points,
+            decoded.vip_tier
+        ),
+        (0, 0, 0)
+    );
+
+    // Players stored before concurrent sessions only keep the legacy single session
+    let legacy = &legacy[..legacy.len() - player.active_sessions.encode_size()];
+    let decoded = Player::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.active_sessions, vec![4]);
+    let legacy = &legacy[..legacy.len() - player.profile.encode_size()];
+    let decoded = Player::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.daily_streak, 3);
+    assert_eq!(decoded.profile, Profile::default());
+    assert_eq!(decoded.display_name(), "TestPlayer");
+    let legacy = &legacy[..legacy.len() - u32::SIZE];
+    let decoded = Player::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.daily_streak, 0);
+    assert_eq!(player.display_name(), "Ace");
+}
+
+#[test]
+fn test_game_history() {
+    let record = |session_id| GameRecord {
+        session_id,
+        game_type: GameType::Roulette,
+        bet: 10,
+        payout: -10,
+        view: session_id,
+    };
+    let mut history = GameHistory::default();
+    let mut dropped = Vec::new();
+    for session_id in 0..GAME_HISTORY_LENGTH as u64 + 3 {
+        dropped.extend(history.push(record(session_id)));
+    }
+    assert_eq!(dropped, vec![record(0), record(1), record(2)]);
+
+    // Only the most recent results are kept (oldest first)
+    assert_eq!(history.records.len(), GAME_HISTORY_LENGTH);
+    assert_eq!(history.records[0], record(3));
+    assert_eq!(
+        history.records.last(),
+        Some(&record(GAME_HISTORY_LENGTH as u64 + 2))
+    );
+
+    // Sessions are only reclaimed once
+    assert_eq!(history.reclaim(4), vec![3, 4]);
+    assert_eq!(history.reclaim(4), Vec::<u64>::new());
+    assert_eq!(history.reclaim(5), vec![5]);
+    assert_eq!(history.reclaimed_through, 5);
+
+    let encoded = history.encode();
+    assert_eq!(encoded.len(), history.encode_size());
+    assert_eq!(GameHistory::read(&mut &encoded[..]).unwrap(), history);
+
+    // Histories stored before sessions were reclaimed have reclaimed nothing
+    let legacy = &encoded[..encoded.len() - u64::SIZE];
+    let decoded = GameHistory::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.records, history.records);
+    assert_eq!(decoded.reclaimed_through, 0);
+}
+
+#[test]
+fn test_game_stats() {
+    let mut stats = GameStats::default();
+    assert_eq!(stats.rtp_bps(), 0);
+
+    // Losses return nothing, wins and pushes return the payout
+    assert!(!stats.record(100, -100));
+    assert!(!stats.record(100, 250));
+    assert!(!stats.record(50, 50));
+    assert_eq!(
+        stats,
+        GameStats {
+            hands_played: 3,
+            total_wagered: 250,
+            total_paid_out: 300,
+            biggest_win: 250,
+        }
+    );
+    assert_eq!(stats.rtp_bps(), 12_000);
+
+    // The totals are reported every `GAME_STATS_INTERVAL` sessions
+    let reported = (3..GAME_STATS_INTERVAL * 2)
+        .filter(|_| stats.record(10, -10))
+        .count();
+    assert_eq!(reported, 2);
+
+    let encoded = stats.encode();
+    assert_eq!(encoded.len(), GameStats::SIZE);
+    assert_eq!(GameStats::read(&mut &encoded[..]).unwrap(), stats);
+}
+
+#[test]
+fn test_tournament_schedule() {
+    let tournament = Tournament {
+        id: 7,
+        players: vec![PrivateKey::from_seed(0).public_key()],
+        schedule: Some(TournamentSchedule {
+            start_view: 100,
+            end_view: 200,
+        }),
+        ..Default::default()
+    };
+    let encoded = tournament.encode();
+    assert_eq!(encoded.len(), tournament.encode_size());
+    assert_eq!(Tournament::read(&mut &encoded[..]).unwrap(), tournament);
+
+    // Tournaments stored before scheduling existed decode as unscheduled
+    let legacy =
+        &encoded[..encoded.len() - tournament.rules.encode_size() - 1 - TournamentSchedule::SIZE];
+    let decoded = Tournament::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.schedule, None);
+    assert_eq!(decoded.players, tournament.players);
+}
+
+#[test]
+fn test_tournament_rules() {
+    let rules = TournamentRules {
+        min_bet: 10,
+        max_bet: 500,
+        disabled_games: vec![GameType::Slots],
+        game_configs: vec![(
+            GameType::Blackjack,
+            GameConfig {
+                max_split_hands: 1,
+                ..Default::default()
+            },
+        )],
+        super_boost_bps: 5_000,
+    };
+    assert!(rules.is_valid());
+    assert!(!rules.allows(GameType::Slots));
+    assert!(rules.allows(GameType::Blackjack));
+    assert_eq!(
+        rules
+            .game_config(GameType::Blackjack)
+            .map(|c| c.max_split_hands),
+        Some(1)
+    );
+    assert_eq!(rules.game_config(GameType::Craps), None);
+
+    // The maximum replaces the house's per-game limits
+    let house = BetLimits {
+        max_bets: vec![(GameType::Blackjack, 100)],
+        ..Default::default()
+    };
+    let limits = rules.bet_limits(&house);
+    assert_eq!(limits.max_bet(GameType::Blackjack), 500);
+    assert_eq!(limits.max_exposure, house.max_exposure);
+    assert_eq!(TournamentRules::default().bet_limits(&house), house);
+
+    // Empty bet ranges and invalid table rules are rejected
+    assert!(!TournamentRules {
+        max_bet: 5,
+        ..rules.clone()
+    }
+    .is_valid());
+    assert!(!TournamentRules {
+        game_configs: vec![(
+            GameType::Slots,
+            GameConfig {
+                paytable: 1,
+                ..Default::default()
+            }
+        )],
+        ..rules.clone()
+    }
+    .is_valid());
+
+    // Tournaments stored before rule overrides existed use the house's rules
+    let tournament = Tournament {
+        id: 7,
+        rules,
+        ..Default::default()
+    };
+    let encoded = tournament.encode();
+    assert_eq!(encoded.len(), tournament.encode_size());
+    assert_eq!(Tournament::read(&mut &encoded[..]).unwrap(), tournament);
+    let legacy = &encoded[..encoded.len() - tournament.rules.encode_size()];
+    assert_eq!(
+        Tournament::read(&mut &legacy[..]).unwrap().rules,
+        TournamentRules::default()
+    );
+}
+
+#[test]
+fn test_tournament_payouts() {
+    let payouts = TournamentPayouts::default();
+    assert_eq!(payouts.split(1000, 10), vec![500, 300, 200]);
+
+    // Rounding dust goes to first place
+    assert_eq!(payouts.split(1001, 10), vec![501, 300, 200]);
+
+    // With fewer players than paid places, the paid shares are scaled up
+    assert_eq!(payouts.split(1000, 2), vec![625, 375]);
+    assert!(payouts.split(1000, 0).is_empty());
+
+    let encoded = payouts.encode();
+    assert_eq!(TournamentPayouts::read(&mut &encoded[..]).unwrap(), payouts);
+}
+
+#[test]
+fn test_game_session_last_move() {
+    let session = GameSession {
+        id: 1,
+        player: PrivateKey::from_seed(0).public_key(),
+        game_type: GameType::HiLo,
+        bet: 100,
+        state_blob: vec![1, 2, 3],
+        move_count: 2,
+        created_at: 5,
+        is_complete: false,
+        super_mode: SuperModeState::default(),
+        is_tournament: false,
+        tournament_id: None,
+        last_move_at: 9,
+        wagered: 150,
+        entropy: None,
+    };
+    let encoded = session.encode();
+    assert_eq!(GameSession::read(&mut &encoded[..]).unwrap(), session);
+
+    // Sessions stored before wagers were tracked only count the initial bet
+    let legacy = &encoded[..encoded.len() - u64::SIZE];
+    let decoded = GameSession::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.last_move_at, 9);
+    assert_eq!(decoded.wagered, 100);
+
+    // Sessions stored before moves were tracked count from creation
+    let legacy = &legacy[..legacy.len() - u64::SIZE];
+    let decoded = GameSession::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.last_move_at, 5);
+}
+
+#[test]
+fn test_player_entropy() {
+    let preimage = Sha256::hash(b"preimage");
+    let commitment = Sha256::hash(preimage.as_ref());
+
+    // Only the preimage of the commitment is revealed (once)
+    let mut entropy = PlayerEntropy::Committed(commitment);
+    assert_eq!(entropy.revealed(), None);
+    assert!(!entropy.reveal(commitment));
+    assert!(entropy.reveal(preimage));
+    assert_eq!(entropy.revealed(), Some(&preimage));
+    assert!(!entropy.reveal(preimage));
+
+    let mut session = GameSession {
+        id: 1,
+        player: PrivateKey::from_seed(0).public_key(),
+        game_type: GameType::Blackjack,
+        bet: 100,
+        state_blob: vec![],
+        move_count: 0,
+        created_at: 5,
+        is_complete: false,
+        super_mode: SuperModeState::default(),
+        is_tournament: false,
+        tournament_id: None,
+        last_move_at: 5,
+        wagered: 100,
+        entropy: Some(PlayerEntropy::Committed(commitment)),
+    };
+    let encoded = session.encode();
+    assert_eq!(encoded.len(), session.encode_size());
+    assert_eq!(GameSession::read(&mut &encoded[..]).unwrap(), session);
+    session.entropy = Some(entropy);
+    let encoded = session.encode();
+    assert_eq!(GameSession::read(&mut &encoded[..]).unwrap(), session);
+
+    // Sessions stored before player entropy have none
+    let legacy = &encoded[..encoded.len() - 1 - PlayerEntropy::SIZE];
+    let decoded = GameSession::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.entropy, None);
+    assert_eq!(decoded.wagered, 100);
+
+    // Starts without a commitment keep the original encoding
+    let start = Instruction::CasinoStartGame {
+        game_type: GameType::Blackjack,
+        bet: 100,
+        session_id: 1,
+        commitment: None,
+    };
+    let encoded = start.encode();
+    assert_eq!(
+        encoded[0],
+        crate::execution::tags::instruction::CASINO_START_GAME
+    );
+    assert_eq!(encoded.len(), 1 + 1 + 8 + 8);
+    assert_eq!(Instruction::read(&mut &encoded[..]).unwrap(), start);
+    let start = Instruction::CasinoStartGame {
+        game_type: GameType::Blackjack,
+        bet: 100,
+        session_id: 1,
+        commitment: Some(commitment),
+    };
+    let encoded = start.encode();
+    assert_eq!(
+        encoded[0],
+        crate::execution::tags::instruction::CASINO_START_GAME_COMMITTED
+    );
+    assert_eq!(encoded.len(), start.encode_size());
+    assert_eq!(Instruction::read(&mut &encoded[..]).unwrap(), start);
+}
+
+#[test]
+fn test_profile_validate() {
+    assert!(Profile::validate("Ace_1", 0, "").is_ok());
+    assert!(Profile::validate("Lady Luck", MAX_AVATAR_ID, "High-Roller.").is_ok());
+    assert!(Profile::validate("", 0, "").is_err());
+    assert!(Profile::validate(&"a".repeat(MAX_NAME_LENGTH + 1), 0, "").is_err());
+    assert!(Profile::validate("Ace", 0, &"a".repeat(MAX_TITLE_LENGTH + 1)).is_err());
+    assert!(Profile::validate("Ace", MAX_AVATAR_ID + 1, "").is_err());
+    assert!(Profile::validate(" Ace", 0, "").is_err());
+    assert!(Profile::validate("Ace\n", 0, "").is_err());
+    assert!(Profile::validate("Ace", 0, "<script>").is_err());
+}
+
+#[test]
+fn test_daily_bonus() {
+    let bonus = DailyBonus::default();
+    assert_eq!(bonus.bonus(1), DAILY_BONUS_AMOUNT);
+    assert_eq!(
+        bonus.bonus(2),
+        DAILY_BONUS_AMOUNT + DAILY_BONUS_AMOUNT * DAILY_BONUS_STREAK_BPS / 10_000
+    );
+
+    // The bonus stops growing at the max streak
+    assert_eq!(
+        bonus.bonus(DAILY_BONUS_MAX_STREAK),
+        bonus.bonus(DAILY_BONUS_MAX_STREAK + 10)
+    );
 }
 
 #[test]
@@ -40,7 +450,7 @@ fn test_leaderboard_update() {
     // Add some players
     for i in 0..15 {
         let pk = PrivateKey::from_rng(&mut rng).public_key();
-        leaderboard.update(pk, format!("Player{}", i), (i as u64 + 1) * 1000);
+        leaderboard.update(pk, format!("Player{}", i), Chips((i as u64 + 1) * 1000));
     }
 
     // Should only keep top 10
@@ -55,4 +465,2237 @@ fn test_leaderboard_update() {
     for (i, entry) in leaderboard.entries.iter().enumerate() {
         assert_eq!(entry.rank, (i + 1) as u32);
     }
+
+    leaderboard.season = 3;
+    leaderboard.season_end = 500;
+    let encoded = leaderboard.encode();
+    assert_eq!(
+        CasinoLeaderboard::read(&mut &encoded[..]).unwrap(),
+        leaderboard
+    );
+
+    // Leaderboards stored before seasons existed are in the first (not yet started) season
+    let legacy = &encoded[..encoded.len() - u64::SIZE * 2];
+    let decoded = CasinoLeaderboard::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.entries, leaderboard.entries);
+    assert_eq!((decoded.season, decoded.season_end), (0, 0));
+}
+
+#[test]
+fn test_staker_slash_count() {
+    let mut staker = Staker::default();
+    assert!(staker.stake(Chips(500), 10, 5_000));
+    staker.slash_count = 2;
+    let encoded = staker.encode();
+    assert_eq!(Staker::read(&mut &encoded[..]).unwrap(), staker);
+
+    // Stakers stored before rewards have accrued nothing
+    let legacy = &encoded[..encoded.len() - u128::SIZE - u64::SIZE];
+    let decoded = Staker::read(&mut &legacy[..]).unwrap();
+    assert_eq!(
+        (decoded.reward_index, decoded.pending_rewards),
+        (0, Chips::ZERO)
+    );
+
+    // Stakers stored before slashing have never been slashed (and hold a single stake)
+    let legacy = &legacy[..legacy.len() - u64::SIZE - staker.tranches.encode_size()];
+    let decoded = Staker::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.slash_count, 0);
+    assert_eq!(decoded.balance, Chips(500));
+    assert_eq!(decoded.tranches, staker.tranches);
+}
+
+#[test]
+fn test_reward_curve() {
+    // A 10% rate per epoch, halving every epoch down to a 2.5% floor
+    let per_epoch = VIEWS_PER_YEAR / EPOCH_LENGTH;
+    let curve = RewardCurve {
+        base_apr_bps: 1_000 * per_epoch,
+        decay_bps: 5_000,
+        floor_apr_bps: 250 * per_epoch,
+    };
+    assert_eq!(curve.apr_bps(0), 1_000 * per_epoch);
+    assert_eq!(curve.apr_bps(1), 500 * per_epoch);
+    assert_eq!(curve.apr_bps(2), 250 * per_epoch);
+    assert_eq!(curve.apr_bps(3), 250 * per_epoch);
+    assert_eq!(curve.apr_bps(u64::MAX), 250 * per_epoch);
+    assert_eq!(curve.issuance(0, 1_000), 100);
+    assert_eq!(curve.issuance(1, 1_000), 50);
+    assert_eq!(curve.issuance(10, 1_000), 25);
+    assert_eq!(curve.issuance(0, 0), 0);
+
+    // Without decay the rate stays at the base rate
+    let curve = RewardCurve::default();
+    assert_eq!(curve.apr_bps(1_000), DEFAULT_REWARD_APR_BPS);
+
+    // Stakers accrue rewards in proportion to their voting power
+    let mut staker = Staker::default();
+    staker.accrue(REWARD_INDEX_SCALE);
+    assert_eq!(staker.pending_rewards, Chips::ZERO);
+    assert!(staker.stake(Chips(100), 10, 1_000));
+    staker.accrue(REWARD_INDEX_SCALE + REWARD_INDEX_SCALE / 100);
+    assert_eq!(staker.pending_rewards, Chips(10));
+    staker.accrue(REWARD_INDEX_SCALE + REWARD_INDEX_SCALE / 100);
+    assert_eq!(staker.pending_rewards, Chips(10));
+}
+
+#[test]
+fn test_economy_params() {
+    use crate::execution::{Key, Value};
+
+    // The defaults are the compiled-in constants
+    let params = EconomyParams::default();
+    assert!(params.is_valid());
+    assert_eq!(params.initial_chips, INITIAL_CHIPS);
+    assert_eq!(params.amm_fee_bps, 30);
+    assert_eq!(params.minimum_liquidity, LpShares(MINIMUM_LIQUIDITY));
+    assert_eq!(params.super_mode_fee(1_000), 200);
+    assert_eq!(params.super_mode_fee(u64::MAX), u64::MAX / 5);
+
+    // Fees are capped (swaps must leave some output) and some liquidity must be locked
+    for (params, valid) in [
+        (
+            EconomyParams {
+                super_mode_fee_bps: 10_000,
+                ..EconomyParams::default()
+            },
+            true,
+        ),
+        (
+            EconomyParams {
+                super_mode_fee_bps: 10_001,
+                ..EconomyParams::default()
+            },
+            false,
+        ),
+        (
+            EconomyParams {
+                amm_fee_bps: 10_000,
+                ..EconomyParams::default()
+            },
+            false,
+        ),
+        (
+            EconomyParams {
+                minimum_liquidity: LpShares::ZERO,
+                ..EconomyParams::default()
+            },
+            false,
+        ),
+    ] {
+        assert_eq!(params.is_valid(), valid);
+    }
+
+    // Parameters roundtrip as an instruction and as a stored value
+    let params = EconomyParams {
+        initial_chips: 5_000,
+        faucet_amount: 250,
+        super_mode_fee_bps: 1_000,
+        amm_fee_bps: 50,
+        minimum_liquidity: LpShares(10),
+    };
+    let encoded = params.encode();
+    assert_eq!(encoded.len(), EconomyParams::SIZE);
+    assert_eq!(EconomyParams::read(&mut &encoded[..]).unwrap(), params);
+    let instruction = Instruction::SetEconomyParams {
+        params: params.clone(),
+    };
+    let encoded = instruction.encode();
+    assert_eq!(encoded.len(), instruction.encode_size());
+    assert_eq!(Instruction::read(&mut &encoded[..]).unwrap(), instruction);
+    assert_eq!(instruction.info().name, "SetEconomyParams");
+    let value = Value::EconomyParams(params);
+    assert_eq!(Value::read(&mut &value.encode()[..]).unwrap(), value);
+    let key = Key::EconomyParams;
+    assert_eq!(Key::read(&mut &key.encode()[..]).unwrap(), key);
+}
+
+#[test]
+fn test_house_settings() {
+    use crate::execution::admin_message;
+
+    // Settings governance changes are a view of the house
+    let mut house = HouseState::new(0);
+    let mut settings = house.settings();
+    assert!(settings.is_valid());
+    settings.daily_bonus.faucet_enabled = false;
+    settings.kyc_deposit_limit = 100;
+    house.set_settings(settings.clone());
+    assert!(!house.daily_bonus.faucet_enabled);
+    assert_eq!(house.kyc.deposit_limit, 100);
+    assert_eq!(house.settings(), settings);
+    let encoded = settings.encode();
+    assert_eq!(encoded.len(), settings.encode_size());
+    assert_eq!(HouseSettings::read(&mut &encoded[..]).unwrap(), settings);
+
+    // Without a daily bonus cooldown the bonus could be claimed every view
+    settings.daily_bonus.cooldown = 0;
+    assert!(!settings.is_valid());
+
+    // Admin rotations are stored with the house (and default to 0 for older values)
+    house.admin_rotations = 3;
+    let encoded = house.encode();
+    assert_eq!(HouseState::read(&mut &encoded[..]).unwrap(), house);
+    let legacy = &encoded[..encoded.len() - u64::SIZE];
+    assert_eq!(
+        HouseState::read(&mut &legacy[..]).unwrap().admin_rotations,
+        0
+    );
+
+    // Approvals are bound to the role, the key and the rotation
+    let admin = PrivateKey::from_seed(0).public_key();
+    let message = admin_message(AdminRole::Kyc, Some(&admin), 3);
+    assert_ne!(message, admin_message(AdminRole::Treasury, Some(&admin), 3));
+    assert_ne!(message, admin_message(AdminRole::Kyc, None, 3));
+    assert_ne!(message, admin_message(AdminRole::Kyc, Some(&admin), 4));
+    assert!(AdminRole::read(&mut &[3u8][..]).is_err());
+}
+
+#[test]
+fn test_game_config() {
+    // The standard rules are valid for every game
+    let config = GameConfig::default();
+    assert!(config.dealer_hits_soft_17);
+    assert!(config.is_valid(GameType::Blackjack));
+    assert!(config.is_valid(GameType::Craps));
+
+    // Only games with paytable variants accept them
+    let config = GameConfig {
+        paytable: 1,
+        commission_on_win: true,
+        dealer_hits_soft_17: false,
+        max_split_hands: 2,
+        record_rng: true,
+        shoe_decks: 0,
+        shoe_penetration: DEFAULT_SHOE_PENETRATION,
+        dealer_peeks: true,
+        even_money: true,
+    };
+    assert!(config.is_valid(GameType::Craps));
+    assert!(config.is_valid(GameType::Roulette));
+    assert!(config.is_valid(GameType::SicBo));
+    assert!(config.is_valid(GameType::CasinoWar));
+    assert!(!config.is_valid(GameType::Blackjack));
+    assert!(!GameConfig {
+        paytable: 2,
+        ..config.clone()
+    }
+    .is_valid(GameType::Craps));
+    let config = GameConfig {
+        paytable: 3,
+        ..config
+    };
+    assert!(config.is_valid(GameType::VideoPoker));
+    assert!(!config.is_valid(GameType::Roulette));
+    assert!(!GameConfig {
+        paytable: 4,
+        ..config
+    }
+    .is_valid(GameType::VideoPoker));
+
+    // Split limits must leave at least one hand and stay within the table maximum
+    for (max_split_hands, valid) in [(0, false), (1, true), (BLACKJACK_MAX_HANDS, true)] {
+        let config = GameConfig {
+            max_split_hands,
+            ..GameConfig::default()
+        };
+        assert_eq!(config.is_valid(GameType::Blackjack), valid);
+    }
+    assert!(!GameConfig {
+        max_split_hands: BLACKJACK_MAX_HANDS + 1,
+        ..GameConfig::default()
+    }
+    .is_valid(GameType::Blackjack));
+
+    // Persistent shoes hold 6-8 decks and are only kept by blackjack and baccarat tables
+    let shoe = GameConfig {
+        shoe_decks: MIN_SHOE_DECKS,
+        ..GameConfig::default()
+    };
+    assert!(shoe.is_valid(GameType::Blackjack));
+    assert!(shoe.is_valid(GameType::Baccarat));
+    assert!(!shoe.is_valid(GameType::CasinoWar));
+    for (shoe_decks, shoe_penetration) in [
+        (MIN_SHOE_DECKS - 1, DEFAULT_SHOE_PENETRATION),
+        (MAX_SHOE_DECKS + 1, DEFAULT_SHOE_PENETRATION),
+        (MAX_SHOE_DECKS, MIN_SHOE_PENETRATION - 1),
+        (MAX_SHOE_DECKS, MAX_SHOE_PENETRATION + 1),
+    ] {
+        assert!(!GameConfig {
+            shoe_decks,
+            shoe_penetration,
+            ..GameConfig::default()
+        }
+        .is_valid(GameType::Baccarat));
+    }
+    let config = GameConfig {
+        shoe_decks: MAX_SHOE_DECKS,
+        shoe_penetration: MAX_SHOE_PENETRATION,
+        ..config
+    };
+
+    let encoded = config.encode();
+    assert_eq!(encoded.len(), GameConfig::SIZE);
+    assert_eq!(GameConfig::read(&mut &encoded[..]).unwrap(), config);
+}
+
+#[test]
+fn test_rng_draw() {
+    for draw in [
+        RngDraw::Card(52),
+        RngDraw::Die(6),
+        RngDraw::Wheel(37),
+        RngDraw::Value(24),
+    ] {
+        let encoded = draw.encode();
+        assert_eq!(encoded.len(), RngDraw::SIZE);
+        assert_eq!(RngDraw::read(&mut &encoded[..]).unwrap(), draw);
+    }
+    assert!(RngDraw::read(&mut &[4u8, 0][..]).is_err());
+}
+
+#[test]
+fn test_table_events() {
+    for seat in [Seat::Player(0), Seat::Player(3), Seat::Dealer] {
+        let encoded = seat.encode();
+        assert_eq!(encoded.len(), Seat::SIZE);
+        assert_eq!(Seat::read(&mut &encoded[..]).unwrap(), seat);
+    }
+    assert!(Seat::read(&mut &[1u8, 1][..]).is_err());
+    assert!(Seat::read(&mut &[2u8, 0][..]).is_err());
+
+    let bet = ResolvedBet {
+        bet_type: 2,
+        target: 0,
+        amount: 10,
+        payout: 20,
+    };
+    let encoded = bet.encode();
+    assert_eq!(encoded.len(), ResolvedBet::SIZE);
+    assert_eq!(ResolvedBet::read(&mut &encoded[..]).unwrap(), bet);
+}
+
+#[test]
+fn test_treasury() {
+    // The treasury's share is only taken from profit
+    let treasury = Treasury::default();
+    assert_eq!(treasury.funding(10_000), 1_000);
+    assert_eq!(treasury.funding(-10_000), 0);
+    assert_eq!(treasury.funding(i128::MAX), u64::MAX);
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let treasury = Treasury {
+        admin: Some(PrivateKey::from_rng(&mut rng).public_key()),
+        profit_share_bps: 2_500,
+        vusdt_balance: Vusdt(1_000),
+        total_bought_back: Chips(300),
+    };
+    let encoded = treasury.encode();
+    assert_eq!(encoded.len(), treasury.encode_size());
+    assert_eq!(Treasury::read(&mut &encoded[..]).unwrap(), treasury);
+}
+
+#[test]
+fn test_staker_tranches() {
+    let mut staker = Staker::default();
+    assert!(staker.stake(Chips(100), 10, 1_000));
+    assert!(staker.stake(Chips(200), 20, 4_000));
+    assert_eq!(staker.balance, Chips(300));
+    assert_eq!(staker.unlock_ts, 20);
+    assert_eq!(staker.voting_power, 5_000);
+
+    // Only unlocked tranches can be withdrawn
+    assert_eq!(staker.unlocked(9), Chips::ZERO);
+    assert_eq!(staker.unlocked(10), Chips(100));
+    assert_eq!(staker.unstake(Chips(150), 10), None);
+    assert_eq!(staker.balance, Chips(300));
+
+    // Partial withdrawals keep voting power in proportion
+    assert_eq!(staker.unstake(Chips(40), 10), Some(400));
+    assert_eq!(staker.balance, Chips(260));
+    assert_eq!(staker.voting_power, 4_600);
+    assert_eq!(staker.unstake(Chips(160), 20), Some(2_600));
+    assert_eq!(staker.voting_power, 2_000);
+    assert_eq!(staker.balance, Chips(100));
+    assert_eq!(staker.tranches.len(), 1);
+
+    // Slashing applies to every tranche (and never removes more than the stake)
+    assert!(staker.stake(Chips(100), 30, 1_000));
+    assert_eq!(staker.slash(1_000), (Chips(20), 300));
+    assert_eq!(staker.balance, Chips(180));
+    assert_eq!(staker.voting_power, 2_700);
+    let mut slashed = staker.clone();
+    assert_eq!(slashed.slash(u16::MAX), (Chips(180), 2_700));
+    assert_eq!(slashed.balance, Chips::ZERO);
+    assert!(slashed.tranches.is_empty());
+
+    // Stakes that would overflow the balance are rejected
+    assert!(!staker.stake(Chips(u64::MAX), 40, 1));
+    assert_eq!(staker.balance, Chips(180));
+
+    // Stakers hold a bounded number of tranches
+    let mut staker = Staker::default();
+    for _ in 0..MAX_STAKE_TRANCHES {
+        assert!(staker.stake(Chips(1), 1, 1));
+    }
+    assert!(!staker.stake(Chips(1), 1, 1));
+}
+
+#[test]
+fn test_house_state_fee_schedule() {
+    let mut house = HouseState::new(0);
+    house
+        .fee_schedule
+        .overrides
+        .push((crate::execution::tags::instruction::CASINO_START_GAME, 5));
+    house.kyc.admin = Some(PrivateKey::from_seed(0).public_key());
+    house.tournament_payouts.shares_bps = vec![6000, 4000];
+    house.seasons.length = 100;
+    house.vip.tiers.truncate(2);
+    house.insurance.balance = Chips(1_000);
+    house.rewards.decay_bps = 100;
+    house.reward_index = 5;
+    house.governance = Some(PrivateKey::from_seed(1).public_key());
+    house.caribbean_stud_progressive_jackpot = 12_345;
+    let encoded = house.encode();
+    let decoded = HouseState::read(&mut &encoded[..]).unwrap();
+    assert_eq!(house, decoded);
+
+    // Older values (without admin rotations or bonus settings) use the defaults
+    let legacy = &encoded[..encoded.len() - u64::SIZE];
+    let decoded = HouseState::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.admin_rotations, 0);
+    let legacy = &legacy[..legacy.len() - house.bonus.encode_size()];
+    let decoded = HouseState::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.bonus, BonusSettings::default());
+
+    // Older values (without a Caribbean Stud meter) start at the base jackpot
+    let legacy = &legacy[..legacy.len() - u64::SIZE];
+    let decoded = HouseState::read(&mut &legacy[..]).unwrap();
+    assert_eq!(
+        decoded.caribbean_stud_progressive_jackpot,
+        CARIBBEAN_STUD_PROGRESSIVE_BASE_JACKPOT
+    );
+
+    // Older values (without a governance key) have no governance
+    let legacy = &legacy[..legacy.len() - house.governance.encode_size()];
+    let decoded = HouseState::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.governance, None);
+
+    // Older values (without a reward curve) use the default curve
+    let legacy = &legacy[..legacy.len() - RewardCurve::SIZE - u128::SIZE];
+    let decoded = HouseState::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.rewards, RewardCurve::default());
+    assert_eq!(decoded.reward_index, 0);
+
+    // Older values (without an insurance fund) start with an empty fund
+    let legacy = &legacy[..legacy.len() - InsuranceFund::SIZE];
+    let decoded = HouseState::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.insurance, InsuranceFund::default());
+
+    // Older values (without VIP settings) use the default settings
+    let legacy = &legacy[..legacy.len() - house.vip.encode_size()];
+    let decoded = HouseState::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.vip, VipSettings::default());
+
+    // Older values (without season settings) use the default settings
+    let legacy = &legacy[..legacy.len() - house.seasons.encode_size()];
+    let decoded = HouseState::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.seasons, SeasonSettings::default());
+
+    // Older values (without a payout table) use the default table
+    let legacy = &legacy[..legacy.len() - house.tournament_payouts.encode_size()];
+    let decoded = HouseState::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.tournament_payouts, TournamentPayouts::default());
+
+    // Older values (without KYC settings) have no KYC admin
+    let legacy = &legacy[..legacy.len() - house.kyc.encode_size()];
+    let decoded = HouseState::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.kyc, KycSettings::default());
+
+    // Older values (without bet limits) use the default limits
+    let legacy = &legacy[..legacy.len() - house.bet_limits.encode_size()];
+    let decoded = HouseState::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.bet_limits, BetLimits::default());
+
+    // Older values (without a session limit) use the default limit
+    let legacy = &legacy[..legacy.len() - u32::SIZE];
+    let decoded = HouseState::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.max_active_sessions, DEFAULT_MAX_ACTIVE_SESSIONS);
+
+    // Older values (without a daily bonus) use the default settings
+    let legacy = &legacy[..legacy.len() - DailyBonus::SIZE];
+    let decoded = HouseState::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.fee_schedule, house.fee_schedule);
+    assert_eq!(decoded.daily_bonus, DailyBonus::default());
+
+    // Older values (without a fee schedule) use the default schedule
+    let legacy = &legacy[..legacy.len() - u128::SIZE - u64::SIZE];
+    let legacy = &legacy[..legacy.len() - house.fee_schedule.encode_size()];
+    let decoded = HouseState::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.fee_schedule, FeeSchedule::default());
+
+    // Batches pay the fees of their instructions
+    let batch = Instruction::Batch(vec![
+        Instruction::CasinoRegister {
+            name: "Alice".to_string(),
+        },
+        Instruction::CasinoToggleShield,
+        Instruction::CasinoStartGame {
+            game_type: GameType::Blackjack,
+            bet: 10,
+            session_id: 1,
+            commitment: None,
+        },
+    ]);
+    assert_eq!(house.fee_schedule.fee(&batch), TRANSACTION_FEE + 5);
+}
+
+#[test]
+fn test_vip_settings() {
+    let vip = VipSettings::default();
+    assert_eq!(vip.points(10_000), 100);
+    assert_eq!(vip.points(99), 0);
+
+    // Tiers are reached once enough points have been earned
+    assert_eq!(vip.tier(0), 0);
+    assert_eq!(vip.tier(99), 0);
+    assert_eq!(vip.tier(100), 1);
+    assert_eq!(vip.tier(5_000), 2);
+    assert_eq!(vip.tier(u64::MAX), vip.tiers.len() as u8);
+
+    // Tier 0 has no rakeback
+    assert_eq!(vip.rakeback(0, 1_000), 0);
+    assert_eq!(vip.rakeback(1, 1_000), 5);
+    assert_eq!(vip.rakeback(2, 1_000), 10);
+    assert!(vip.perks(vip.tiers.len() as u8 + 1).is_none());
+}
+
+#[test]
+fn test_bonus() {
+    let settings = BonusSettings::default();
+    assert_eq!(settings.requirement(50), 1_000);
+
+    // Low edge games only count part of each wager
+    assert_eq!(settings.contribution(GameType::Roulette, 100), 100);
+    assert_eq!(settings.contribution(GameType::Blackjack, 100), 10);
+
+    // Chips are released once the requirement is met
+    let mut bonus = BonusBalance::default();
+    assert_eq!(bonus.wager(100), Chips::ZERO);
+    assert!(bonus.grant(Chips(50), settings.requirement(50)));
+    assert_eq!(bonus.wager(999), Chips::ZERO);
+    assert_eq!(bonus.wagering_remaining, 1);
+    assert_eq!(bonus.wager(100), Chips(50));
+    assert_eq!(bonus, BonusBalance::default());
+
+    // Bonuses that don't fit are rejected
+    assert!(bonus.grant(Chips(u64::MAX), 0));
+    assert!(!bonus.grant(Chips(1), 1_000));
+    assert_eq!(bonus.chips, Chips(u64::MAX));
+    assert_eq!(bonus.wagering_remaining, 0);
+
+    let mut player = Player::new("Alice".to_string());
+    assert!(player.bonus.grant(Chips(50), 1_000));
+    let encoded = player.encode();
+    assert_eq!(encoded.len(), player.encode_size());
+    assert_eq!(Player::read(&mut &encoded[..]).unwrap(), player);
+
+    // Players stored before bonuses decode without one
+    let legacy = &encoded[..encoded.len() - BonusBalance::SIZE];
+    assert_eq!(
+        Player::read(&mut &legacy[..]).unwrap().bonus,
+        BonusBalance::default()
+    );
+}
+
+#[test]
+fn test_insurance_fund() {
+    let mut fund = InsuranceFund {
+        balance: Chips(500),
+        funding_bps: 1_000,
+        threshold: 100,
+    };
+    assert_eq!(fund.funding(Chips(1_005)), Chips(100));
+
+    // Losses up to the threshold are absorbed by the house
+    assert_eq!(fund.draw(50), Chips::ZERO);
+    assert_eq!(fund.draw(-100), Chips::ZERO);
+    assert_eq!(fund.draw(-150), Chips(50));
+
+    // Draws are limited to the balance
+    assert_eq!(fund.draw(-1_000), Chips(500));
+    assert_eq!(fund.draw(i128::MIN), Chips(500));
+
+    // Funding is limited to the amount collected
+    fund.funding_bps = u16::MAX;
+    assert_eq!(fund.funding(Chips(1_000)), Chips(1_000));
+    assert_eq!(fund.funding(Chips(u64::MAX)), Chips(u64::MAX));
+}
+
+#[test]
+fn test_guardian_set() {
+    let key = |seed| PrivateKey::from_seed(seed).public_key();
+    let (owner, new_key) = (key(0), key(10));
+    let guardians = vec![key(1), key(2), key(3)];
+
+    // Guardians must be distinct, exclude the owner and meet the threshold
+    assert!(GuardianSet::is_valid(&owner, &guardians, 2));
+    assert!(!GuardianSet::is_valid(&owner, &guardians, 0));
+    assert!(!GuardianSet::is_valid(&owner, &guardians, 4));
+    assert!(!GuardianSet::is_valid(&owner, &[key(1), key(1)], 1));
+    assert!(!GuardianSet::is_valid(&owner, &[owner.clone()], 1));
+
+    // The challenge window starts once enough guardians approve
+    let mut set = GuardianSet {
+        guardians: guardians.clone(),
+        threshold: 2,
+        recovery: None,
+    };
+    assert!(set.approve(&guardians[0], &new_key, 100));
+    assert!(!set.approve(&guardians[0], &new_key, 101));
+    assert_eq!(set.recovery.as_ref().unwrap().ready_view, 0);
+    assert!(set.approve(&guardians[1], &new_key, 102));
+    assert_eq!(
+        set.recovery.as_ref().unwrap().ready_view,
+        102 + RECOVERY_DELAY
+    );
+    assert!(!set.is_ready(101 + RECOVERY_DELAY));
+    assert!(set.is_ready(102 + RECOVERY_DELAY));
+
+    let encoded = set.encode();
+    assert_eq!(encoded.len(), set.encode_size());
+    assert_eq!(GuardianSet::read(&mut &encoded[..]).unwrap(), set);
+
+    // Approving a different key can't discard a recovery that reached the threshold
+    let ready = set.clone();
+    assert!(!set.approve(&guardians[2], &key(11), 103));
+    assert_eq!(set, ready);
+
+    // But starts over if it hasn't
+    let mut set = GuardianSet {
+        guardians: guardians.clone(),
+        threshold: 2,
+        recovery: None,
+    };
+    assert!(set.approve(&guardians[0], &new_key, 100));
+    assert!(set.approve(&guardians[2], &key(11), 103));
+    let recovery = set.recovery.as_ref().unwrap();
+    assert_eq!(recovery.new_key, key(11));
+    assert_eq!(recovery.approvals, vec![guardians[2].clone()]);
+    assert_eq!(recovery.ready_view, 0);
+}
+
+#[test]
+fn test_vesting() {
+    let schedule = |amount, start_view, duration| VestingSchedule {
+        from: PrivateKey::from_seed(0).public_key(),
+        amount,
+        claimed: 0,
+        start_view,
+        duration,
+    };
+
+    // Chips unlock linearly
+    let linear = schedule(1_000, 100, 50);
+    assert_eq!(linear.vested(0), 0);
+    assert_eq!(linear.vested(100), 0);
+    assert_eq!(linear.vested(125), 500);
+    assert_eq!(linear.vested(150), 1_000);
+    assert_eq!(linear.vested(u64::MAX), 1_000);
+
+    // Or all at once without a duration
+    assert_eq!(schedule(1_000, 100, 0).vested(99), 0);
+    assert_eq!(schedule(1_000, 100, 0).vested(100), 1_000);
+
+    // Claims skip what was already claimed and drop completed schedules
+    let mut vesting = Vesting {
+        schedules: vec![linear, schedule(10, 0, 0)],
+    };
+    assert_eq!(vesting.claim(110), 210);
+    assert_eq!(vesting.schedules.len(), 1);
+    assert_eq!(vesting.claim(110), 0);
+    assert_eq!(vesting.claim(200), 800);
+    assert!(vesting.schedules.is_empty());
+
+    let vesting = Vesting {
+        schedules: vec![schedule(1_000, 100, 50)],
+    };
+    let encoded = vesting.encode();
+    assert_eq!(encoded.len(), vesting.encode_size());
+    assert_eq!(Vesting::read(&mut &encoded[..]).unwrap(), vesting);
+}
+
+#[test]
+fn test_instruction_catalog() {
+    use crate::execution::{instruction_catalog, tags};
+    use std::collections::HashSet;
+
+    // Kinds are the catalog's indices, and names and tags are unique
+    let catalog = instruction_catalog();
+    let mut names = HashSet::new();
+    let mut tags_seen = HashSet::new();
+    for (index, info) in catalog.iter().enumerate() {
+        assert_eq!(info.kind as usize, index);
+        assert!(names.insert(info.name));
+        for tag in info.tags {
+            assert!(tags_seen.insert(*tag));
+        }
+    }
+
+    // Instructions find their entry, which lists the tags they are encoded with
+    let player = PrivateKey::from_seed(0).public_key();
+    let instructions = [
+        Instruction::CasinoRegister {
+            name: "player".to_string(),
+        },
+        Instruction::CasinoStartGame {
+            game_type: GameType::Blackjack,
+            bet: 10,
+            session_id: 1,
+            commitment: Some(Sha256::hash(b"preimage")),
+        },
+        Instruction::Swap {
+            pair: PairId::RNG_VUSDT,
+            amount_in: 10,
+            min_amount_out: 5,
+            is_buying_rng: true,
+        },
+        Instruction::Swap {
+            pair: PairId(3),
+            amount_in: 10,
+            min_amount_out: 5,
+            is_buying_rng: true,
+        },
+        Instruction::Approve {
+            spender: player,
+            amount: 10,
+        },
+        Instruction::ClaimVested,
+        Instruction::Batch(vec![Instruction::ClaimRewards]),
+    ];
+    for instruction in instructions {
+        let info = instruction.info();
+        assert!(info.tags.contains(&instruction.tag()));
+        assert_eq!(instruction.encode()[0], instruction.tag());
+    }
+
+    let info = catalog[2];
+    assert_eq!(info.name, "CasinoStartGame");
+    assert_eq!(
+        info.tags,
+        &[
+            tags::instruction::CASINO_START_GAME,
+            tags::instruction::CASINO_START_GAME_COMMITTED
+        ]
+    );
+    assert_eq!(info.params.len(), 4);
+    assert_eq!(info.params[1].name, "bet");
+    assert!(info.mutates_casino && !info.mutates_defi && !info.mutates_staking);
+    assert!(Instruction::Unstake { amount: 1 }.info().mutates_staking);
+    assert!(Instruction::CreateVault.info().mutates_defi);
+}
+
+#[test]
+fn test_instruction_compute_units() {
+    use crate::execution::{COMPUTE_UNITS_PER_BYTE, COMPUTE_UNITS_PER_INSTRUCTION};
+
+    assert_eq!(
+        Instruction::CasinoToggleShield.compute_units(),
+        COMPUTE_UNITS_PER_INSTRUCTION
+    );
+
+    // Payload bytes are charged
+    let mv = Instruction::CasinoGameMove {
+        session_id: 1,
+        payload: vec![0; 11],
+    };
+    assert_eq!(
+        mv.compute_units(),
+        COMPUTE_UNITS_PER_INSTRUCTION + 11 * COMPUTE_UNITS_PER_BYTE
+    );
+
+    // Batches are charged for each of their instructions
+    let batch = Instruction::Batch(vec![mv.clone(), Instruction::CasinoToggleShield]);
+    assert_eq!(
+        batch.compute_units(),
+        mv.compute_units() + COMPUTE_UNITS_PER_INSTRUCTION
+    );
+}
+
+#[test]
+fn test_batch_starts_one_game() {
+    let start = Instruction::CasinoStartGame {
+        game_type: GameType::Blackjack,
+        bet: 10,
+        session_id: 1,
+        commitment: None,
+    };
+    let batch = Instruction::Batch(vec![Instruction::CasinoToggleShield, start.clone()]);
+    let encoded = batch.encode();
+    assert_eq!(Instruction::read(&mut &encoded[..]).unwrap(), batch);
+
+    // Both games would derive the same session ID from the transaction's nonce
+    let encoded = Instruction::Batch(vec![start.clone(), start]).encode();
+    assert!(Instruction::read(&mut &encoded[..]).is_err());
+}
+
+#[test]
+fn test_price_oracle_twap() {
+    let mut oracle = PriceOracle::default();
+    oracle.observe(10, 100);
+    assert_eq!(oracle.twap(10), 100);
+
+    // A price that only persists briefly barely moves the average
+    oracle.observe(100, 1_000);
+    oracle.observe(101, 100);
+    assert_eq!(oracle.twap(110), (90 * 100 + 1_000 + 9 * 100) / 100);
+
+    // Old checkpoints are dropped (so the average follows sustained moves)
+    for view in (120..=300).step_by(ORACLE_CHECKPOINT_INTERVAL as usize) {
+        oracle.observe(view, 500);
+    }
+    assert_eq!(oracle.checkpoints.len(), ORACLE_CHECKPOINTS);
+    assert_eq!(oracle.twap(300), 500);
+
+    let encoded = oracle.encode();
+    assert_eq!(PriceOracle::read(&mut &encoded[..]).unwrap(), oracle);
+}
+
+#[test]
+fn test_amm_pool_key() {
+    use crate::execution::Key;
+
+    // The RNG/vUSDT pool keeps its original encoding
+    let key = Key::AmmPool(PairId::RNG_VUSDT);
+    assert_eq!(key.encode().as_ref(), &[17]);
+    assert!(Key::read(&mut &[17u8][..]).unwrap() == key);
+
+    // Other pools include the pair
+    let key = Key::AmmPool(PairId(3));
+    let encoded = key.encode();
+    assert_eq!(encoded.len(), key.encode_size());
+    assert!(Key::read(&mut &encoded[..]).unwrap() == key);
+
+    // The RNG/vUSDT pool can't be encoded with a pair
+    assert!(Key::read(&mut &[20u8, 0, 0][..]).is_err());
+
+    // LP balances are keyed by pair the same way
+    let player = PrivateKey::from_seed(0).public_key();
+    let key = Key::LpBalance(PairId::RNG_VUSDT, player.clone());
+    assert_eq!(key.encode()[0], 18);
+    assert_eq!(key.encode_size(), 1 + 32);
+    let key = Key::LpBalance(PairId(3), player.clone());
+    let encoded = key.encode();
+    assert_eq!(&encoded[..3], &[35, 0, 3]);
+    assert_eq!(encoded.len(), key.encode_size());
+    assert!(Key::read(&mut &encoded[..]).unwrap() == key);
+    let mut encoded = encoded.to_vec();
+    encoded[2] = 0;
+    assert!(Key::read(&mut &encoded[..]).is_err());
+}
+
+#[test]
+fn test_amm_instruction_pair() {
+    use crate::execution::tags;
+
+    // Instructions on the RNG/vUSDT pool keep their original encoding
+    let instruction = Instruction::RemoveLiquidity {
+        pair: PairId::RNG_VUSDT,
+        shares: 7,
+    };
+    let encoded = instruction.encode();
+    assert_eq!(encoded[0], tags::instruction::REMOVE_LIQUIDITY);
+    assert_eq!(encoded.len(), 1 + 8);
+    assert_eq!(Instruction::read(&mut &encoded[..]).unwrap(), instruction);
+
+    // Other pools carry the pair after their own tag
+    for instruction in [
+        Instruction::Swap {
+            pair: PairId(3),
+            amount_in: 10,
+            min_amount_out: 5,
+            is_buying_rng: false,
+        },
+        Instruction::AddLiquidity {
+            pair: PairId(3),
+            rng_amount: 10,
+            usdt_amount: 20,
+        },
+        Instruction::RemoveLiquidity {
+            pair: PairId(3),
+            shares: 7,
+        },
+        Instruction::ZapLiquidity {
+            pair: PairId(3),
+            amount_in: 10,
+            min_shares: 1,
+            from_rng: true,
+        },
+    ] {
+        let encoded = instruction.encode();
+        assert_eq!(encoded.len(), instruction.encode_size());
+        assert_eq!(&encoded[1..3], &[0, 3]);
+        assert_eq!(Instruction::read(&mut &encoded[..]).unwrap(), instruction);
+
+        // The RNG/vUSDT pool can't be encoded with a pair
+        let mut encoded = encoded.to_vec();
+        encoded[2] = 0;
+        assert!(Instruction::read(&mut &encoded[..]).is_err());
+    }
+}
+
+#[test]
+fn test_key_index() {
+    use crate::execution::{tags, Key, Value};
+
+    // Keys of indexed domains are bucketed by the last byte of their encoding
+    let key = Key::CasinoSession(0x0102);
+    let bucket = Key::KeyIndex {
+        tag: tags::key::CASINO_SESSION,
+        bucket: 0x02,
+    };
+    assert!(key.index() == Some(bucket.clone()));
+    assert_eq!(bucket.encode().as_ref(), &[tags::key::KEY_INDEX, 11, 0x02]);
+    assert!(Key::read(&mut &bucket.encode()[..]).unwrap() == bucket);
+    assert!(Key::House.index().is_none());
+    assert!(bucket.index().is_none());
+
+    // Scans read every bucket of an indexed domain
+    assert!(Key::scannable(&[tags::key::VAULT]));
+    assert!(!Key::scannable(&[tags::key::ACCOUNT]));
+    assert!(!Key::scannable(&[]));
+    assert_eq!(Key::index_buckets(&key.encode()).count(), 256);
+    assert_eq!(Key::index_buckets(&[tags::key::HOUSE]).count(), 0);
+
+    let value = Value::KeyIndex(vec![Key::CasinoSession(2), key]);
+    let encoded = value.encode();
+    assert_eq!(encoded.len(), value.encode_size());
+    assert_eq!(Value::read(&mut &encoded[..]).unwrap(), value);
+}
+
+#[test]
+fn test_session_expiries() {
+    use crate::execution::{Key, Value};
+
+    for key in [Key::SessionExpiries(7), Key::SessionReclaimCursor] {
+        let encoded = key.encode();
+        assert_eq!(encoded.len(), key.encode_size());
+        assert!(Key::read(&mut &encoded[..]).unwrap() == key);
+    }
+    for value in [
+        Value::SessionExpiries(vec![1, 2, 3]),
+        Value::SessionReclaimCursor(7),
+    ] {
+        let encoded = value.encode();
+        assert_eq!(encoded.len(), value.encode_size());
+        assert_eq!(Value::read(&mut &encoded[..]).unwrap(), value);
+    }
+
+    // A view lists at most one session per transaction
+    let value = Value::SessionExpiries(vec![0; MAX_SESSION_EXPIRIES + 1]);
+    assert!(Value::read(&mut &value.encode()[..]).is_err());
+}
+
+#[test]
+fn test_amounts() {
+    // Arithmetic is checked
+    assert_eq!(Chips(5).checked_add(Chips(7)), Some(Chips(12)));
+    assert_eq!(Chips(u64::MAX).checked_add(Chips(1)), None);
+    assert_eq!(Vusdt(7).checked_sub(Vusdt(5)), Some(Vusdt(2)));
+    assert_eq!(Vusdt(5).checked_sub(Vusdt(7)), None);
+    assert_eq!(LpShares(10).checked_mul_div(3, 4), Some(LpShares(7)));
+    assert_eq!(LpShares(10).checked_mul_div(3, 0), None);
+    assert_eq!(LpShares(u64::MAX).checked_mul_div(2, 1), None);
+    assert_eq!(Chips(1_000).bps(250), Some(Chips(25)));
+    assert_eq!(Chips(1_000).bps(20_000), Some(Chips(2_000)));
+    assert_eq!(Chips(u64::MAX).bps(20_000), None);
+    assert_eq!(Chips(u64::MAX).saturating_add(Chips(1)), Chips(u64::MAX));
+    assert!(Chips::ZERO.is_zero() && Chips::default().is_zero());
+
+    // Converting between chips and vUSDT uses the price of RNG in vUSDT
+    let price = 2 * PRICE_SCALE;
+    assert_eq!(Chips(150).to_vusdt(price), Some(Vusdt(300)));
+    assert_eq!(Vusdt(301).to_chips(price), Some(Chips(150)));
+    assert_eq!(Vusdt(1).to_chips(0), None);
+    assert_eq!(Chips(u64::MAX).to_vusdt(price), None);
+
+    assert_eq!(Chips(1_250).to_string(), "1250 RNG");
+    assert_eq!(Vusdt(3).to_string(), "3 vUSDT");
+    assert_eq!(LpShares(0).to_string(), "0 LP");
+
+    // Amounts are encoded as plain integers
+    assert_eq!(Chips(9).encode(), 9u64.encode());
+    assert_eq!(Vusdt::read(&mut &9u64.encode()[..]).unwrap(), Vusdt(9));
+    assert_eq!(u64::from(LpShares::from(9)), 9);
+}
+
+#[test]
+fn test_amm_pool_price_accumulators() {
+    let mut pool = AmmPool::new(30);
+    pool.accumulate(10);
+    pool.reserve_rng = Chips(1_000);
+    pool.reserve_vusdt = Vusdt(2_000);
+
+    // Prices accumulate over the views the reserves were held
+    pool.accumulate(20);
+    assert_eq!(pool.price_rng_cumulative, 20 * PRICE_SCALE);
+    assert_eq!(pool.price_vusdt_cumulative, 5 * PRICE_SCALE);
+    let start = (20, pool.price_rng_cumulative);
+
+    pool.reserve_vusdt = Vusdt(4_000);
+    let (rng_cumulative, _) = pool.cumulative_prices(30);
+    assert_eq!(
+        AmmPool::average_price(start, (30, rng_cumulative)),
+        Some(4 * PRICE_SCALE)
+    );
+    assert_eq!(AmmPool::average_price(start, start), None);
+
+    let encoded = pool.encode();
+    assert_eq!(AmmPool::read(&mut &encoded[..]).unwrap(), pool);
+
+    // Pools stored without a price impact limit use the default limit
+    let legacy = &encoded[..encoded.len() - u16::SIZE];
+    let decoded = AmmPool::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.max_price_impact_bps, DEFAULT_MAX_PRICE_IMPACT_BPS);
+
+    // Pools stored without accumulators start accumulating from their next update
+    let legacy = &legacy[..legacy.len() - (u128::SIZE * 2 + u64::SIZE)];
+    let mut decoded = AmmPool::read(&mut &legacy[..]).unwrap();
+    assert_eq!(decoded.last_update_view, 0);
+    decoded.accumulate(40);
+    assert_eq!(decoded.price_rng_cumulative, 0);
+    assert_eq!(decoded.last_update_view, 40);
+}
+
+#[test]
+fn test_amm_pool_price_impact() {
+    let pool = AmmPool {
+        reserve_rng: Chips(1_000),
+        reserve_vusdt: Vusdt(1_000),
+        ..AmmPool::new(30)
+    };
+    let after = AmmPool {
+        reserve_rng: Chips(800),
+        reserve_vusdt: Vusdt(1_250),
+        ..pool.clone()
+    };
+    assert_eq!(pool.price_impact_bps(&after), Some(5_625));
+    assert_eq!(after.price_impact_bps(&pool), Some(3_600));
+    assert_eq!(pool.price_impact_bps(&pool), Some(0));
+
+    // Impact is undefined without liquidity
+    assert_eq!(pool.price_impact_bps(&AmmPool::default()), None);
+    assert_eq!(AmmPool::default().price_impact_bps(&pool), None);
+}
+
+#[test]
+fn test_protocol_versions() {
+    use crate::execution::{
+        protocol_version, Transaction, Value, PROTOCOL_ACTIVATION_HEIGHTS, PROTOCOL_VERSION,
+    };
+    use commonware_cryptography::Digestible;
+
+    // Versions only apply from their activation height
+    let activation = PROTOCOL_ACTIVATION_HEIGHTS[0];
+    assert!(activation > 0);
+    assert_eq!(protocol_version(0), 0);
+    assert_eq!(protocol_version(activation - 1), 0);
+    assert_eq!(protocol_version(activation), PROTOCOL_VERSION);
+    assert_eq!(protocol_version(u64::MAX), PROTOCOL_VERSION);
+    let private = PrivateKey::from_seed(0);
+
+    // Versioned transactions are encoded (and signed) with their version
+    let tx = Transaction::sign_version(
+        &private,
+        PROTOCOL_VERSION,
+        1,
+        Instruction::CasinoToggleShield,
+    );
+    let encoded = tx.encode();
+    assert_eq!(encoded[0], PROTOCOL_VERSION);
+    let decoded = Transaction::read(&mut &encoded[..]).unwrap();
+    assert_eq!(decoded, tx);
+    assert!(decoded.verify());
+
+    // Legacy transactions (accepted at every height) can still be decoded (and verified)
+    let legacy = Transaction::sign(&private, 1, Instruction::CasinoToggleShield);
+    assert_eq!(legacy.version, 0);
+    let encoded = legacy.encode();
+    assert_eq!(encoded.len(), tx.encode_size() - 1);
+    let decoded = Transaction::read(&mut &encoded[..]).unwrap();
+    assert_eq!(decoded.version, 0);
+    assert!(decoded.verify());
+    assert_ne!(decoded.digest(), tx.digest());
+
+    // Future versions are rejected
+    let mut encoded = tx.encode().to_vec();
+    encoded[0] = PROTOCOL_VERSION + 1;
+    assert!(Transaction::read(&mut &encoded[..]).is_err());
+
+    // Values are written in the legacy encoding (their tag, then their fields) until a version
+    // activates
+    let value = Value::LpBalance(LpShares(5));
+    let legacy = value.encode();
+    assert_eq!(legacy[0], crate::execution::tags::value::LP_BALANCE);
+    assert_eq!(value.clone().versioned(0), value);
+    assert_eq!(Value::read(&mut &legacy[..]).unwrap(), value);
+
+    // Versioned values keep their version when decoded (so they re-encode byte for byte)
+    let versioned = value.clone().versioned(PROTOCOL_VERSION);
+    let encoded = versioned.encode();
+    assert_eq!(encoded.len(), versioned.encode_size());
+    assert_eq!(encoded[0], 0x80 | PROTOCOL_VERSION);
+    assert_eq!(&encoded[1..], &legacy[..]);
+    let decoded = Value::read(&mut &encoded[..]).unwrap();
+    assert_eq!(decoded, versioned);
+    assert_eq!(decoded.encode(), encoded);
+    assert_eq!(decoded.into_inner(), value);
+    assert_eq!(versioned.clone().versioned(PROTOCOL_VERSION), versioned);
+
+    // Future (and nested) versions are rejected
+    let mut future = encoded.to_vec();
+    future[0] += 1;
+    assert!(Value::read(&mut &future[..]).is_err());
+    let nested = [&[0x80 | PROTOCOL_VERSION][..], &encoded[..]].concat();
+    assert!(Value::read(&mut &nested[..]).is_err());
+}
+
+/// An event's legacy encoding (and the body of its envelope): its tag, then its fields.
+fn event_body(tag: u8, fields: &[&[u8]]) -> Vec<u8> {
+    let mut body = vec![tag];
+    body.extend_from_slice(&fields.concat());
+    body
+}
+
+/// A versioned event: its version, the length of its body, then the body.
+fn event_envelope(version: u8, body: &[u8]) -> Vec<u8> {
+    use commonware_codec::{varint::UInt, Write};
+
+    let mut encoded = vec![0x80 | version];
+    UInt(body.len() as u32).write(&mut encoded);
+    encoded.extend_from_slice(body);
+    encoded
+}
+
+#[test]
+fn test_event_encodings() {
+    use crate::execution::{Event, Output, EVENT_VERSION};
+    use std::collections::BTreeSet;
+
+    let player = PrivateKey::from_seed(0).public_key();
+    let other = PrivateKey::from_seed(1).public_key();
+    let third = PrivateKey::from_seed(2).public_key();
+    let (p, o, t): (&[u8], &[u8], &[u8]) = (player.as_ref(), other.as_ref(), third.as_ref());
+    let blackjack = [GameType::Blackjack as u8];
+    let leaderboard = CasinoLeaderboard {
+        entries: Vec::new(),
+        season: 4,
+        season_end: 9,
+    };
+    let config = GameConfig {
+        dealer_hits_soft_17: false,
+        ..GameConfig::default()
+    };
+    let stats = GameStats {
+        hands_played: 1,
+        total_wagered: 2,
+        total_paid_out: 3,
+        biggest_win: 4,
+    };
+    let rules = TournamentRules {
+        min_bet: 5,
+        ..TournamentRules::default()
+    };
+    let resolved = ResolvedBet {
+        bet_type: 1,
+        target: 6,
+        amount: 10,
+        payout: 20,
+    };
+
+    // Every current event, with the layout it must keep
+    let cases = vec![
+        (
+            Event::CasinoPlayerRegistered {
+                player: player.clone(),
+                name: "Al".to_string(),
+            },
+            event_body(20, &[p, &2u32.to_be_bytes(), b"Al"]),
+        ),
+        (
+            Event::CasinoGameStarted {
+                session_id: 1,
+                player: player.clone(),
+                game_type: GameType::Blackjack,
+                bet: 2,
+                initial_state: vec![7, 8],
+            },
+            event_body(
+                21,
+                &[
+                    &1u64.to_be_bytes(),
+                    p,
+                    &blackjack,
+                    &2u64.to_be_bytes(),
+                    &[2, 7, 8],
+                ],
+            ),
+        ),
+        (
+            Event::CasinoGameMoved {
+                session_id: 1,
+                move_number: 2,
+                new_state: vec![9],
+            },
+            event_body(22, &[&1u64.to_be_bytes(), &2u32.to_be_bytes(), &[1, 9]]),
+        ),
+        (
+            Event::CasinoGameCompleted {
+                session_id: 1,
+                player: player.clone(),
+                game_type: GameType::Blackjack,
+                payout: -2,
+                final_chips: 3,
+                was_shielded: true,
+                was_doubled: false,
+            },
+            event_body(
+                23,
+                &[
+                    &1u64.to_be_bytes(),
+                    p,
+                    &blackjack,
+                    &(-2i64).to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                    &[1, 0],
+                ],
+            ),
+        ),
+        (
+            Event::CasinoLeaderboardUpdated {
+                leaderboard: leaderboard.clone(),
+            },
+            event_body(24, &[&leaderboard.encode()[..]]),
+        ),
+        (
+            Event::TournamentStarted {
+                id: 1,
+                start_block: 2,
+            },
+            event_body(25, &[&1u64.to_be_bytes(), &2u64.to_be_bytes()]),
+        ),
+        (
+            Event::PlayerJoined {
+                tournament_id: 1,
+                player: player.clone(),
+            },
+            event_body(26, &[&1u64.to_be_bytes(), p]),
+        ),
+        (
+            Event::TournamentPhaseChanged {
+                id: 1,
+                phase: TournamentPhase::Active,
+            },
+            event_body(27, &[&1u64.to_be_bytes(), &[1]]),
+        ),
+        (
+            Event::TournamentEnded {
+                id: 1,
+                rankings: vec![(player.clone(), 2)],
+            },
+            event_body(28, &[&1u64.to_be_bytes(), &[1], p, &2u64.to_be_bytes()]),
+        ),
+        (
+            Event::CasinoError {
+                player: player.clone(),
+                session_id: Some(1),
+                error_code: CasinoErrorCode::PlayerNotFound,
+                message: "no".to_string(),
+            },
+            event_body(
+                29,
+                &[
+                    p,
+                    &[1],
+                    &1u64.to_be_bytes(),
+                    &[2],
+                    &2u32.to_be_bytes(),
+                    b"no",
+                ],
+            ),
+        ),
+        (
+            Event::VaultCreated {
+                player: player.clone(),
+            },
+            event_body(30, &[p]),
+        ),
+        (
+            Event::CollateralDeposited {
+                player: player.clone(),
+                amount: 1,
+                new_collateral: 2,
+            },
+            event_body(31, &[p, &1u64.to_be_bytes(), &2u64.to_be_bytes()]),
+        ),
+        (
+            Event::VusdtBorrowed {
+                player: player.clone(),
+                amount: 1,
+                new_debt: 2,
+            },
+            event_body(32, &[p, &1u64.to_be_bytes(), &2u64.to_be_bytes()]),
+        ),
+        (
+            Event::VusdtRepaid {
+                player: player.clone(),
+                amount: 1,
+                new_debt: 2,
+            },
+            event_body(33, &[p, &1u64.to_be_bytes(), &2u64.to_be_bytes()]),
+        ),
+        (
+            Event::AmmSwapped {
+                player: player.clone(),
+                is_buying_rng: true,
+                amount_in: 1,
+                amount_out: 2,
+                fee_amount: 3,
+                burned_amount: 4,
+                reserve_rng: 5,
+                reserve_vusdt: 6,
+            },
+            event_body(
+                34,
+                &[
+                    p,
+                    &[1],
+                    &1u64.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                    &4u64.to_be_bytes(),
+                    &5u64.to_be_bytes(),
+                    &6u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::LiquidityAdded {
+                player: player.clone(),
+                rng_amount: 1,
+                vusdt_amount: 2,
+                shares_minted: 3,
+                total_shares: 4,
+                reserve_rng: 5,
+                reserve_vusdt: 6,
+                lp_balance: 7,
+            },
+            event_body(
+                35,
+                &[
+                    p,
+                    &1u64.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                    &4u64.to_be_bytes(),
+                    &5u64.to_be_bytes(),
+                    &6u64.to_be_bytes(),
+                    &7u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::LiquidityRemoved {
+                player: player.clone(),
+                rng_amount: 1,
+                vusdt_amount: 2,
+                shares_burned: 3,
+                total_shares: 4,
+                reserve_rng: 5,
+                reserve_vusdt: 6,
+                lp_balance: 7,
+            },
+            event_body(
+                36,
+                &[
+                    p,
+                    &1u64.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                    &4u64.to_be_bytes(),
+                    &5u64.to_be_bytes(),
+                    &6u64.to_be_bytes(),
+                    &7u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::Staked {
+                player: player.clone(),
+                amount: 1,
+                duration: 2,
+                new_balance: 3,
+                unlock_ts: 4,
+                voting_power: 5,
+            },
+            event_body(
+                37,
+                &[
+                    p,
+                    &1u64.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                    &4u64.to_be_bytes(),
+                    &5u128.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::Unstaked {
+                player: player.clone(),
+                amount: 1,
+            },
+            event_body(38, &[p, &1u64.to_be_bytes()]),
+        ),
+        (
+            Event::EpochProcessed { epoch: 1 },
+            event_body(39, &[&1u64.to_be_bytes()]),
+        ),
+        (
+            Event::RewardsClaimed {
+                player: player.clone(),
+                amount: 1,
+            },
+            event_body(40, &[p, &1u64.to_be_bytes()]),
+        ),
+        (
+            Event::FeeCharged {
+                player: player.clone(),
+                amount: 1,
+                new_chips: 2,
+                accumulated_fees: 3,
+            },
+            event_body(
+                41,
+                &[
+                    p,
+                    &1u64.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::InterestAccrued {
+                player: player.clone(),
+                amount: 1,
+                new_debt: 2,
+                effective_rate_bps: 3,
+                rate_bps: 4,
+            },
+            event_body(
+                42,
+                &[
+                    p,
+                    &1u64.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                    &4u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::CollateralWithdrawn {
+                player: player.clone(),
+                amount: 1,
+                new_collateral: 2,
+            },
+            event_body(43, &[p, &1u64.to_be_bytes(), &2u64.to_be_bytes()]),
+        ),
+        (
+            Event::LimitOrderPlaced {
+                order_id: 1,
+                player: player.clone(),
+                amount_in: 2,
+                min_price: 3,
+                is_buying_rng: true,
+            },
+            event_body(
+                44,
+                &[
+                    &1u64.to_be_bytes(),
+                    p,
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                    &[1],
+                ],
+            ),
+        ),
+        (
+            Event::LimitOrderFilled {
+                order_id: 1,
+                player: player.clone(),
+                is_buying_rng: false,
+                amount_in: 2,
+                amount_out: 3,
+                fee_amount: 4,
+                burned_amount: 5,
+                reserve_rng: 6,
+                reserve_vusdt: 7,
+            },
+            event_body(
+                45,
+                &[
+                    &1u64.to_be_bytes(),
+                    p,
+                    &[0],
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                    &4u64.to_be_bytes(),
+                    &5u64.to_be_bytes(),
+                    &6u64.to_be_bytes(),
+                    &7u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::LimitOrderCancelled {
+                order_id: 1,
+                player: player.clone(),
+                refunded: 2,
+            },
+            event_body(46, &[&1u64.to_be_bytes(), p, &2u64.to_be_bytes()]),
+        ),
+        (
+            Event::LiquidityZapped {
+                player: player.clone(),
+                from_rng: true,
+                amount_in: 1,
+                swap_amount_in: 2,
+                swap_amount_out: 3,
+                fee_amount: 4,
+                burned_amount: 5,
+                rng_amount: 6,
+                vusdt_amount: 7,
+                shares_minted: 8,
+                total_shares: 9,
+                reserve_rng: 10,
+                reserve_vusdt: 11,
+                lp_balance: 12,
+            },
+            event_body(
+                47,
+                &[
+                    p,
+                    &[1],
+                    &1u64.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                    &4u64.to_be_bytes(),
+                    &5u64.to_be_bytes(),
+                    &6u64.to_be_bytes(),
+                    &7u64.to_be_bytes(),
+                    &8u64.to_be_bytes(),
+                    &9u64.to_be_bytes(),
+                    &10u64.to_be_bytes(),
+                    &11u64.to_be_bytes(),
+                    &12u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::Slashed {
+                player: player.clone(),
+                bps: 1,
+                amount: 2,
+                new_balance: 3,
+                voting_power: 4,
+            },
+            event_body(
+                48,
+                &[
+                    p,
+                    &1u16.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                    &4u128.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::DailyBonusClaimed {
+                player: player.clone(),
+                amount: 1,
+                streak: 2,
+                new_chips: 3,
+                next_claim: 4,
+            },
+            event_body(
+                49,
+                &[
+                    p,
+                    &1u64.to_be_bytes(),
+                    &2u32.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                    &4u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::ProfileUpdated {
+                player: player.clone(),
+                display_name: "Al".to_string(),
+                avatar_id: 1,
+                title: "Ace".to_string(),
+            },
+            event_body(
+                50,
+                &[
+                    p,
+                    &2u32.to_be_bytes(),
+                    b"Al",
+                    &1u32.to_be_bytes(),
+                    &3u32.to_be_bytes(),
+                    b"Ace",
+                ],
+            ),
+        ),
+        (
+            Event::CasinoSessionExpired {
+                session_id: 1,
+                player: player.clone(),
+                game_type: GameType::Blackjack,
+                refund: 2,
+                final_chips: 3,
+            },
+            event_body(
+                51,
+                &[
+                    &1u64.to_be_bytes(),
+                    p,
+                    &blackjack,
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::KycStatusChanged {
+                player: player.clone(),
+                verified: true,
+            },
+            event_body(52, &[p, &[1]]),
+        ),
+        (
+            Event::TournamentScheduled {
+                id: 1,
+                start_view: 2,
+                end_view: 3,
+            },
+            event_body(
+                53,
+                &[
+                    &1u64.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::TournamentPrizePaid {
+                tournament_id: 1,
+                player: player.clone(),
+                rank: 2,
+                amount: 3,
+            },
+            event_body(
+                54,
+                &[
+                    &1u64.to_be_bytes(),
+                    p,
+                    &2u32.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::LeaderboardSeasonEnded {
+                season: 1,
+                next_season_end: 2,
+            },
+            event_body(55, &[&1u64.to_be_bytes(), &2u64.to_be_bytes()]),
+        ),
+        (
+            Event::SeasonRewardPaid {
+                season: 1,
+                player: player.clone(),
+                rank: 2,
+                amount: 3,
+            },
+            event_body(
+                56,
+                &[
+                    &1u64.to_be_bytes(),
+                    p,
+                    &2u32.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::LoyaltyEarned {
+                player: player.clone(),
+                points: 1,
+                total_points: 2,
+                rakeback: 3,
+            },
+            event_body(
+                57,
+                &[
+                    p,
+                    &1u64.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::TierChanged {
+                player: player.clone(),
+                tier: 1,
+                shields: 2,
+            },
+            event_body(58, &[p, &[1], &2u32.to_be_bytes()]),
+        ),
+        (
+            Event::InsuranceFunded {
+                amount: 1,
+                balance: 2,
+            },
+            event_body(59, &[&1u64.to_be_bytes(), &2u64.to_be_bytes()]),
+        ),
+        (
+            Event::InsuranceDrawn {
+                amount: 1,
+                balance: 2,
+                net_pnl: -3,
+            },
+            event_body(
+                60,
+                &[
+                    &1u64.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &(-3i128).to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::InstructionFailed {
+                player: player.clone(),
+                kind: 1,
+                code: CasinoErrorCode::PlayerNotFound,
+            },
+            event_body(61, &[p, &[1, 2]]),
+        ),
+        (
+            Event::GuardiansSet {
+                player: player.clone(),
+                guardians: vec![other.clone()],
+                threshold: 1,
+            },
+            event_body(62, &[p, &[1], o, &[1]]),
+        ),
+        (
+            Event::RecoveryApproved {
+                player: player.clone(),
+                guardian: other.clone(),
+                new_key: third.clone(),
+                approvals: 1,
+                ready_view: 2,
+            },
+            event_body(63, &[p, o, t, &[1], &2u64.to_be_bytes()]),
+        ),
+        (
+            Event::AccountRecovered {
+                old_key: player.clone(),
+                new_key: other.clone(),
+            },
+            event_body(64, &[p, o]),
+        ),
+        (
+            Event::AllowanceSet {
+                owner: player.clone(),
+                spender: other.clone(),
+                amount: 1,
+            },
+            event_body(65, &[p, o, &1u64.to_be_bytes()]),
+        ),
+        (
+            Event::VusdtTransferred {
+                owner: player.clone(),
+                to: other.clone(),
+                spender: third.clone(),
+                amount: 1,
+                allowance: 2,
+            },
+            event_body(66, &[p, o, t, &1u64.to_be_bytes(), &2u64.to_be_bytes()]),
+        ),
+        (
+            Event::VestingCreated {
+                from: player.clone(),
+                to: other.clone(),
+                amount: 1,
+                start_view: 2,
+                duration: 3,
+            },
+            event_body(
+                67,
+                &[
+                    p,
+                    o,
+                    &1u64.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::VestedClaimed {
+                player: player.clone(),
+                amount: 1,
+                new_chips: 2,
+            },
+            event_body(68, &[p, &1u64.to_be_bytes(), &2u64.to_be_bytes()]),
+        ),
+        (
+            Event::StakingRewardsIssued {
+                epoch: 1,
+                amount: 2,
+                apr_bps: 3,
+            },
+            event_body(
+                69,
+                &[
+                    &1u64.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::TreasuryFunded {
+                epoch: 1,
+                amount: 2,
+                balance: 3,
+            },
+            event_body(
+                70,
+                &[
+                    &1u64.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::BuybackExecuted {
+                amount: 1,
+                burned: 2,
+                balance: 3,
+                reserve_rng: 4,
+                reserve_vusdt: 5,
+            },
+            event_body(
+                71,
+                &[
+                    &1u64.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                    &4u64.to_be_bytes(),
+                    &5u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::GameConfigUpdated {
+                game_type: GameType::Blackjack,
+                config: config.clone(),
+            },
+            event_body(72, &[&blackjack, &config.encode()[..]]),
+        ),
+        (
+            Event::CasinoRngTranscript {
+                session_id: 1,
+                move_number: 2,
+                draws: vec![RngDraw::Card(7)],
+            },
+            event_body(
+                73,
+                &[
+                    &1u64.to_be_bytes(),
+                    &2u32.to_be_bytes(),
+                    &[1],
+                    &RngDraw::Card(7).encode()[..],
+                ],
+            ),
+        ),
+        (
+            Event::CasinoSideBetSettled {
+                session_id: 1,
+                bet_id: 2,
+                amount: 3,
+                payout: 4,
+            },
+            event_body(
+                74,
+                &[
+                    &1u64.to_be_bytes(),
+                    &[2],
+                    &3u64.to_be_bytes(),
+                    &4u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::GameStatsUpdated {
+                game_type: GameType::Blackjack,
+                stats: stats.clone(),
+            },
+            event_body(75, &[&blackjack, &stats.encode()[..]]),
+        ),
+        (
+            Event::BonusGranted {
+                player: player.clone(),
+                amount: 1,
+                bonus_chips: 2,
+                wagering_remaining: 3,
+            },
+            event_body(
+                76,
+                &[
+                    p,
+                    &1u64.to_be_bytes(),
+                    &2u64.to_be_bytes(),
+                    &3u64.to_be_bytes(),
+                ],
+            ),
+        ),
+        (
+            Event::BonusReleased {
+                player: player.clone(),
+                amount: 1,
+                chips: 2,
+            },
+            event_body(77, &[p, &1u64.to_be_bytes(), &2u64.to_be_bytes()]),
+        ),
+        (
+            Event::TournamentRulesSet {
+                tournament_id: 1,
+                rules: rules.clone(),
+            },
+            event_body(78, &[&1u64.to_be_bytes(), &rules.encode()[..]]),
+        ),
+        (
+            Event::CasinoCardDealt {
+                session_id: 1,
+                move_number: 2,
+                card: 3,
+                to: Seat::Dealer,
+            },
+            event_body(
+                79,
+                &[
+                    &1u64.to_be_bytes(),
+                    &2u32.to_be_bytes(),
+                    &[3],
+                    &Seat::Dealer.encode()[..],
+                ],
+            ),
+        ),
+        (
+            Event::CasinoCrapsRolled {
+                session_id: 1,
+                move_number: 2,
+                d1: 3,
+                d2: 4,
+                resolved_bets: vec![resolved.clone()],
+            },
+            event_body(
+                80,
+                &[
+                    &1u64.to_be_bytes(),
+                    &2u32.to_be_bytes(),
+                    &[3, 4, 1],
+                    &resolved.encode()[..],
+                ],
+            ),
+        ),
+    ];
+
+    let mut tags = BTreeSet::new();
+    for (event, body) in cases {
+        assert!(tags.insert(body[0]), "{event:?}");
+
+        // Events are written in the legacy encoding (the body alone)...
+        let encoded = event.encode();
+        assert_eq!(encoded.to_vec(), body, "{event:?}");
+        assert_eq!(event.encode_size(), body.len());
+        assert_eq!(Event::read(&mut &encoded[..]).unwrap(), event);
+
+        // ...until the envelope activates
+        let expected = event_envelope(EVENT_VERSION, &body);
+        assert_eq!(Event::read(&mut &expected[..]).unwrap(), event);
+        let output = Output::Event(event.clone()).versioned(EVENT_VERSION);
+        let encoded = output.encode();
+        assert_eq!(encoded.to_vec(), [&[0u8][..], &expected[..]].concat());
+        assert_eq!(output.encode_size(), encoded.len());
+        assert_eq!(Output::read(&mut &encoded[..]).unwrap(), output);
+        assert_eq!(output.event(), Some(&event));
+    }
+    assert_eq!(tags, (20..=80).collect::<BTreeSet<u8>>());
+}
+
+#[test]
+fn test_event_envelope() {
+    use crate::execution::{
+        event_version, Event, Output, EVENT_ACTIVATION_HEIGHTS, EVENT_VERSION, MAX_EVENT_SIZE,
+    };
+    use commonware_codec::Write;
+
+    let known = event_body(39, &[&1u64.to_be_bytes()]);
+
+    // Events with a tag this release doesn't know are kept as they were encoded
+    let encoded = event_envelope(EVENT_VERSION, &[200, 1, 2, 3]);
+    let event = Event::read(&mut &encoded[..]).unwrap();
+    assert_eq!(
+        event,
+        Event::Unknown {
+            version: EVENT_VERSION,
+            tag: 200,
+            data: vec![1, 2, 3],
+        }
+    );
+    assert_eq!(event.encode().to_vec(), encoded);
+    assert_eq!(event.encode_size(), encoded.len());
+
+    // As are events from a newer version (e.g. with a field appended), even with a known tag
+    let newer = event_envelope(EVENT_VERSION + 1, &[&known[..], &[9u8][..]].concat());
+    let event = Event::read(&mut &newer[..]).unwrap();
+    assert_eq!(
+        event,
+        Event::Unknown {
+            version: EVENT_VERSION + 1,
+            tag: 39,
+            data: [&1u64.to_be_bytes()[..], &[9u8][..]].concat(),
+        }
+    );
+    assert_eq!(event.encode().to_vec(), newer);
+
+    // ...so a reader can carry on past them
+    let mut outputs = Vec::new();
+    for event in [&newer, &event_envelope(EVENT_VERSION, &known)] {
+        0u8.write(&mut outputs);
+        outputs.extend_from_slice(event);
+    }
+    let mut reader = &outputs[..];
+    assert!(matches!(
+        Output::read(&mut reader).unwrap(),
+        Output::VersionedEvent {
+            version,
+            event: Event::Unknown { .. },
+        } if version == EVENT_VERSION + 1
+    ));
+    assert_eq!(
+        Output::read(&mut reader).unwrap(),
+        Output::VersionedEvent {
+            version: EVENT_VERSION,
+            event: Event::EpochProcessed { epoch: 1 },
+        }
+    );
+    assert!(reader.is_empty());
+
+    // Known events of a current version must decode exactly
+    let trailing = event_envelope(EVENT_VERSION, &[&known[..], &[9u8][..]].concat());
+    assert!(Event::read(&mut &trailing[..]).is_err());
+    let short = event_envelope(EVENT_VERSION, &known[..known.len() - 1]);
+    assert!(Event::read(&mut &short[..]).is_err());
+
+    // Malformed envelopes are rejected
+    assert!(Event::read(&mut &event_envelope(0, &known)[..]).is_err());
+    assert!(Event::read(&mut &event_envelope(EVENT_VERSION, &[])[..]).is_err());
+    let oversized = event_envelope(EVENT_VERSION, &vec![200; MAX_EVENT_SIZE + 1][..]);
+    assert!(Event::read(&mut &oversized[..]).is_err());
+    let truncated = event_envelope(EVENT_VERSION, &known);
+    assert!(Event::read(&mut &truncated[..truncated.len() - 1]).is_err());
+    assert!(Event::read(&mut &[0u8; 0][..]).is_err());
+
+    // Legacy events have no length, so an unknown legacy tag can't be skipped
+    assert!(Event::read(&mut &[200, 1, 2, 3][..]).is_err());
+
+    // The envelope is only written once it activates
+    let activation = EVENT_ACTIVATION_HEIGHTS[0];
+    assert_eq!(event_version(0), 0);
+    assert_eq!(event_version(activation - 1), 0);
+    assert_eq!(event_version(activation), EVENT_VERSION);
+    let output = Output::Event(Event::EpochProcessed { epoch: 1 });
+    assert_eq!(output.clone().versioned(0), output);
+    assert_eq!(output.encode().to_vec(), [&[0u8][..], &known[..]].concat());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    use crate::execution::Event;
+    use commonware_cryptography::sha256::Digest;
+    use serde_json::{json, Value};
+
+    let key = PrivateKey::from_seed(0).public_key();
+    let other = PrivateKey::from_seed(1).public_key();
+    let key_hex = commonware_utils::hex(key.as_ref());
+    let other_hex = commonware_utils::hex(other.as_ref());
+
+    // Keys, digests and byte strings are hex
+    let guardians = GuardianSet {
+        guardians: vec![key.clone(), other.clone()],
+        threshold: 2,
+        recovery: Some(Recovery {
+            new_key: other.clone(),
+            approvals: vec![key.clone()],
+            ready_view: 5,
+        }),
+    };
+    let value = serde_json::to_value(&guardians).unwrap();
+    assert_eq!(value["guardians"], json!([key_hex, other_hex]));
+    assert_eq!(value["recovery"]["new_key"], json!(other_hex));
+    assert_eq!(
+        serde_json::from_value::<GuardianSet>(value).unwrap(),
+        guardians
+    );
+
+    let entropy = PlayerEntropy::Committed(Sha256::hash(b"preimage"));
+    let value = serde_json::to_value(entropy.clone()).unwrap();
+    let Value::String(digest) = &value["Committed"] else {
+        panic!("digest is not a string: {value}");
+    };
+    assert_eq!(digest.len(), 2 * Digest::SIZE);
+    assert_eq!(
+        serde_json::from_value::<PlayerEntropy>(value).unwrap(),
+        entropy
+    );
+
+    // Events (and the values they carry) round trip
+    let events = [
+        Event::CasinoGameStarted {
+            session_id: 1,
+            player: key.clone(),
+            game_type: GameType::Blackjack,
+            bet: 100,
+            initial_state: vec![0xab, 0x01],
+        },
+        Event::TournamentEnded {
+            id: 2,
+            rankings: vec![(key.clone(), 500), (other.clone(), 300)],
+        },
+        Event::GameConfigUpdated {
+            game_type: GameType::Baccarat,
+            config: GameConfig::default(),
+        },
+        Event::Unknown {
+            version: 2,
+            tag: 200,
+            data: vec![1, 2],
+        },
+    ];
+    for event in events {
+        let encoded = serde_json::to_string(&event).unwrap();
+        assert_eq!(serde_json::from_str::<Event>(&encoded).unwrap(), event);
+    }
+    let value = serde_json::to_value(Event::TournamentEnded {
+        id: 2,
+        rankings: vec![(key.clone(), 500)],
+    })
+    .unwrap();
+    assert_eq!(
+        value,
+        json!({ "TournamentEnded": { "id": 2, "rankings": [[key_hex, 500]] } })
+    );
+
+    // Malformed hex (or a value that isn't a key) is rejected
+    let mut value = serde_json::to_value(&guardians).unwrap();
+    value["guardians"][0] = json!("zz");
+    assert!(serde_json::from_value::<GuardianSet>(value.clone()).is_err());
+    value["guardians"][0] = json!("00");
+    assert!(serde_json::from_value::<GuardianSet>(value).is_err());
+}
+
+#[test]
+fn test_pagination() {
+    use crate::api::{Cursor, PageRequest, PageResponse, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
+
+    // Cursors are opaque strings
+    let cursor = Cursor::new(0x0102);
+    assert_eq!(cursor.to_string(), "0000000000000102");
+    assert_eq!(Cursor::parse(&cursor.to_string()), Some(cursor));
+    assert_eq!(Cursor::parse("0102"), None);
+    assert_eq!(Cursor::parse("zz"), None);
+
+    // Limits are clamped
+    assert_eq!(PageRequest::default().limit(), DEFAULT_PAGE_LIMIT);
+    let request = |limit| PageRequest {
+        cursor: None,
+        limit: Some(limit),
+    };
+    assert_eq!(request(0).limit(), 1);
+    assert_eq!(request(1_000).limit(), MAX_PAGE_LIMIT);
+
+    // A page ends with the cursor of the next (if there is one)
+    let items = |from: u64| (from..10).map(|position| (position, position * 10));
+    let page = request(4).page(items(0), 10);
+    assert_eq!(page.items, vec![0, 10, 20, 30]);
+    assert_eq!(page.next_cursor, Some(Cursor::new(4)));
+    let page = request(4).page(items(8), 10);
+    assert_eq!(page.items, vec![80, 90]);
+    assert_eq!(page.next_cursor, None);
+    assert_eq!(page.total, 10);
+
+    // Requests are query strings
+    let request = PageRequest {
+        cursor: Some(cursor),
+        limit: Some(5),
+    };
+    assert_eq!(request.to_query(), "cursor=0000000000000102&limit=5");
+    assert_eq!(PageRequest::default().to_query(), "");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_pagination_serde() {
+    use crate::api::{Cursor, PageRequest, PageResponse};
+    use serde_json::json;
+
+    let request = PageRequest {
+        cursor: Some(Cursor::new(0x0102)),
+        limit: Some(5),
+    };
+    let json = serde_json::to_value(request).unwrap();
+    assert_eq!(json, json!({ "cursor": "0000000000000102", "limit": 5 }));
+    assert_eq!(
+        serde_json::from_value::<PageRequest>(json).unwrap(),
+        request
+    );
+    assert_eq!(
+        serde_json::from_value::<PageRequest>(json!({})).unwrap(),
+        PageRequest::default()
+    );
+    let page = request.page((7..10).map(|position| (position, position * 10)), 10);
+    let json = serde_json::to_value(&page).unwrap();
+    assert_eq!(
+        json,
+        json!({ "items": [70, 80, 90], "next_cursor": null, "total": 10 })
+    );
+    assert_eq!(
+        serde_json::from_value::<PageResponse<u64>>(json).unwrap(),
+        page
+    );
 }
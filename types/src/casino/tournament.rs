@@ -2,10 +2,14 @@ use bytes::{Buf, BufMut};
 use commonware_codec::{EncodeSize, Error, FixedSize, Read, ReadExt, ReadRangeExt, Write};
 use commonware_cryptography::ed25519::PublicKey;
 
-use super::CasinoLeaderboard;
+use super::{
+    BetLimits, CasinoLeaderboard, GameConfig, GameType, DEFAULT_TOURNAMENT_PAYOUTS_BPS,
+    MAX_PAYOUT_PLACES, MAX_SCHEDULED_TOURNAMENTS, MAX_TOURNAMENT_RULE_GAMES,
+};
 
 /// Tournament phases
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum TournamentPhase {
     #[default]
@@ -37,8 +41,133 @@ impl FixedSize for TournamentPhase {
     const SIZE: usize = 1;
 }
 
+/// Views at which a scheduled tournament starts and ends.
+///
+/// Scheduled tournaments don't need `CasinoStartTournament`/`CasinoEndTournament`: their
+/// phase advances in the first block executed at (or after) each view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TournamentSchedule {
+    pub start_view: u64,
+    pub end_view: u64,
+}
+
+impl Write for TournamentSchedule {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.start_view.write(writer);
+        self.end_view.write(writer);
+    }
+}
+
+impl Read for TournamentSchedule {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            start_view: u64::read(reader)?,
+            end_view: u64::read(reader)?,
+        })
+    }
+}
+
+impl FixedSize for TournamentSchedule {
+    const SIZE: usize = u64::SIZE + u64::SIZE;
+}
+
+/// Rule overrides of a tournament, applied to its sessions as they start (so organizers can run
+/// different formats without changing the house's rules).
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TournamentRules {
+    /// Smallest bet a session can start with, or a move can place (0 for no minimum).
+    pub min_bet: u64,
+    /// Largest bet, replacing the house's per-game limits (0 to keep them).
+    pub max_bet: u64,
+    /// Games that can't be played in the tournament.
+    pub disabled_games: Vec<GameType>,
+    /// Table rules used instead of the house's (e.g. turbo blackjack without splits).
+    pub game_configs: Vec<(GameType, GameConfig)>,
+    /// Boost of super mode multipliers (in basis points, e.g. 5_000 pays 1.5x the multipliers).
+    pub super_boost_bps: u16,
+}
+
+impl TournamentRules {
+    /// Whether the rules can be used (the bet range isn't empty and each override is valid
+    /// for its game).
+    pub fn is_valid(&self) -> bool {
+        (self.max_bet == 0 || self.min_bet <= self.max_bet)
+            && self
+                .game_configs
+                .iter()
+                .all(|(game_type, config)| config.is_valid(*game_type))
+    }
+
+    /// Whether `game_type` can be played.
+    pub fn allows(&self, game_type: GameType) -> bool {
+        !self.disabled_games.contains(&game_type)
+    }
+
+    /// The table rules overriding the house's for `game_type`, if any.
+    pub fn game_config(&self, game_type: GameType) -> Option<&GameConfig> {
+        self.game_configs
+            .iter()
+            .find(|(kind, _)| *kind == game_type)
+            .map(|(_, config)| config)
+    }
+
+    /// The bet limits of the tournament's sessions (`house` with `max_bet` applied).
+    pub fn bet_limits(&self, house: &BetLimits) -> BetLimits {
+        if self.max_bet == 0 {
+            return house.clone();
+        }
+        BetLimits {
+            default_max_bet: self.max_bet,
+            max_bets: Vec::new(),
+            max_exposure: house.max_exposure,
+        }
+    }
+}
+
+impl Write for TournamentRules {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.min_bet.write(writer);
+        self.max_bet.write(writer);
+        self.disabled_games.write(writer);
+        self.game_configs.write(writer);
+        self.super_boost_bps.write(writer);
+    }
+}
+
+impl Read for TournamentRules {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            min_bet: u64::read(reader)?,
+            max_bet: u64::read(reader)?,
+            disabled_games: Vec::<GameType>::read_range(reader, 0..=MAX_TOURNAMENT_RULE_GAMES)?,
+            game_configs: Vec::<(GameType, GameConfig)>::read_range(
+                reader,
+                0..=MAX_TOURNAMENT_RULE_GAMES,
+            )?,
+            super_boost_bps: u16::read(reader)?,
+        })
+    }
+}
+
+impl EncodeSize for TournamentRules {
+    fn encode_size(&self) -> usize {
+        self.min_bet.encode_size()
+            + self.max_bet.encode_size()
+            + self.disabled_games.encode_size()
+            + self.game_configs.encode_size()
+            + self.super_boost_bps.encode_size()
+    }
+}
+
 /// Tournament state
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tournament {
     pub id: u64,
     pub phase: TournamentPhase,
@@ -47,12 +176,17 @@ pub struct Tournament {
     pub start_time_ms: u64,
     /// Unix timestamp (milliseconds) when the tournament ends
     pub end_time_ms: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::vec"))]
     pub players: Vec<PublicKey>,
     pub prize_pool: u64,
     pub starting_chips: u64,   // 1000
     pub starting_shields: u32, // 3
     pub starting_doubles: u32, // 3
     pub leaderboard: CasinoLeaderboard,
+    /// Set for tournaments whose phases are driven by the view (instead of instructions).
+    pub schedule: Option<TournamentSchedule>,
+    /// Rule overrides for the tournament's sessions.
+    pub rules: TournamentRules,
 }
 
 impl Write for Tournament {
@@ -68,6 +202,8 @@ impl Write for Tournament {
         self.starting_shields.write(writer);
         self.starting_doubles.write(writer);
         self.leaderboard.write(writer);
+        self.schedule.write(writer);
+        self.rules.write(writer);
     }
 }
 
@@ -87,6 +223,18 @@ impl Read for Tournament {
             starting_shields: u32::read(reader)?,
             starting_doubles: u32::read(reader)?,
             leaderboard: CasinoLeaderboard::read(reader)?,
+            // Tournaments stored before scheduling existed are unscheduled
+            schedule: if reader.has_remaining() {
+                Option::<TournamentSchedule>::read(reader)?
+            } else {
+                None
+            },
+            // Tournaments stored before rule overrides existed use the house's rules
+            rules: if reader.has_remaining() {
+                TournamentRules::read(reader)?
+            } else {
+                TournamentRules::default()
+            },
         })
     }
 }
@@ -104,6 +252,8 @@ impl EncodeSize for Tournament {
             + self.starting_shields.encode_size()
             + self.starting_doubles.encode_size()
             + self.leaderboard.encode_size()
+            + self.schedule.encode_size()
+            + self.rules.encode_size()
     }
 }
 
@@ -124,3 +274,92 @@ impl Tournament {
         true
     }
 }
+
+/// How the prize pool of a tournament is split between its top finishers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TournamentPayouts {
+    /// Share of the prize pool (in basis points) paid to each place, starting with first.
+    pub shares_bps: Vec<u16>,
+}
+
+impl TournamentPayouts {
+    /// Split `prize_pool` between the top `players` finishers (in rank order).
+    ///
+    /// If there are fewer players than paid places (or the shares don't add up to 100%), the
+    /// shares of the paid places are scaled so the whole pool is paid out. Rounding dust goes
+    /// to first place.
+    pub fn split(&self, prize_pool: u64, players: usize) -> Vec<u64> {
+        let shares = &self.shares_bps[..self.shares_bps.len().min(players)];
+        let total: u128 = shares.iter().map(|share| *share as u128).sum();
+        if total == 0 {
+            return Vec::new();
+        }
+        let mut amounts: Vec<u64> = shares
+            .iter()
+            .map(|share| (prize_pool as u128 * *share as u128 / total) as u64)
+            .collect();
+        let paid: u64 = amounts.iter().sum();
+        amounts[0] += prize_pool - paid;
+        amounts
+    }
+}
+
+impl Default for TournamentPayouts {
+    fn default() -> Self {
+        Self {
+            shares_bps: DEFAULT_TOURNAMENT_PAYOUTS_BPS.to_vec(),
+        }
+    }
+}
+
+impl Write for TournamentPayouts {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.shares_bps.write(writer);
+    }
+}
+
+impl Read for TournamentPayouts {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            shares_bps: Vec::<u16>::read_range(reader, 0..=MAX_PAYOUT_PLACES)?,
+        })
+    }
+}
+
+impl EncodeSize for TournamentPayouts {
+    fn encode_size(&self) -> usize {
+        self.shares_bps.encode_size()
+    }
+}
+
+/// Ids of the scheduled tournaments that haven't completed yet.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TournamentSchedules {
+    pub ids: Vec<u64>,
+}
+
+impl Write for TournamentSchedules {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.ids.write(writer);
+    }
+}
+
+impl Read for TournamentSchedules {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            ids: Vec::<u64>::read_range(reader, 0..=MAX_SCHEDULED_TOURNAMENTS)?,
+        })
+    }
+}
+
+impl EncodeSize for TournamentSchedules {
+    fn encode_size(&self) -> usize {
+        self.ids.encode_size()
+    }
+}
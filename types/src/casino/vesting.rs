@@ -0,0 +1,110 @@
+use bytes::{Buf, BufMut};
+use commonware_codec::{EncodeSize, Error, FixedSize, Read, ReadExt, ReadRangeExt, Write};
+use commonware_cryptography::ed25519::PublicKey;
+
+use super::MAX_VESTING_SCHEDULES;
+
+/// Chips unlocking linearly over `duration` views from `start_view`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VestingSchedule {
+    /// Player that escrowed the chips.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub from: PublicKey,
+    pub amount: u64,
+    pub claimed: u64,
+    pub start_view: u64,
+    pub duration: u64,
+}
+
+impl VestingSchedule {
+    /// Chips unlocked by `view` (all of them once `duration` views have passed).
+    pub fn vested(&self, view: u64) -> u64 {
+        if view < self.start_view {
+            return 0;
+        }
+        let elapsed = view - self.start_view;
+        if elapsed >= self.duration {
+            return self.amount;
+        }
+        (self.amount as u128 * elapsed as u128 / self.duration as u128) as u64
+    }
+
+    /// Chips unlocked by `view` that haven't been claimed.
+    pub fn claimable(&self, view: u64) -> u64 {
+        self.vested(view).saturating_sub(self.claimed)
+    }
+}
+
+impl Write for VestingSchedule {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.from.write(writer);
+        self.amount.write(writer);
+        self.claimed.write(writer);
+        self.start_view.write(writer);
+        self.duration.write(writer);
+    }
+}
+
+impl Read for VestingSchedule {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            from: PublicKey::read(reader)?,
+            amount: u64::read(reader)?,
+            claimed: u64::read(reader)?,
+            start_view: u64::read(reader)?,
+            duration: u64::read(reader)?,
+        })
+    }
+}
+
+impl FixedSize for VestingSchedule {
+    const SIZE: usize = PublicKey::SIZE + u64::SIZE + u64::SIZE + u64::SIZE + u64::SIZE;
+}
+
+/// The vesting schedules a player is the beneficiary of (oldest first).
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vesting {
+    pub schedules: Vec<VestingSchedule>,
+}
+
+impl Vesting {
+    /// Claim everything unlocked by `view` (dropping fully claimed schedules), returning the
+    /// chips claimed.
+    pub fn claim(&mut self, view: u64) -> u64 {
+        let mut total = 0u64;
+        for schedule in &mut self.schedules {
+            let claimable = schedule.claimable(view);
+            schedule.claimed += claimable;
+            total = total.saturating_add(claimable);
+        }
+        self.schedules
+            .retain(|schedule| schedule.claimed < schedule.amount);
+        total
+    }
+}
+
+impl Write for Vesting {
+    fn write(&self, writer: &mut impl BufMut) {
+        self.schedules.write(writer);
+    }
+}
+
+impl Read for Vesting {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        Ok(Self {
+            schedules: Vec::<VestingSchedule>::read_range(reader, 0..=MAX_VESTING_SCHEDULES)?,
+        })
+    }
+}
+
+impl EncodeSize for Vesting {
+    fn encode_size(&self) -> usize {
+        self.schedules.encode_size()
+    }
+}
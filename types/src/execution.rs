@@ -19,10 +19,88 @@ use std::{fmt::Debug, hash::Hash};
 pub const NAMESPACE: &[u8] = b"_SUPERSOCIETY";
 pub const TRANSACTION_SUFFIX: &[u8] = b"_TX";
 pub const TRANSACTION_NAMESPACE: &[u8] = b"_SUPERSOCIETY_TX";
+pub const SLASH_SUFFIX: &[u8] = b"_SLASH";
+pub const ADMIN_SUFFIX: &[u8] = b"_ADMIN";
 // Phase 1 scaling: Increased from 100 to 500 for higher throughput
 pub const MAX_BLOCK_TRANSACTIONS: usize = 500;
 
-mod tags {
+/// Compute units charged for every instruction.
+pub const COMPUTE_UNITS_PER_INSTRUCTION: u64 = 1_000;
+/// Compute units charged per byte of variable-length instruction data (names and move
+/// payloads).
+pub const COMPUTE_UNITS_PER_BYTE: u64 = 10;
+/// Compute units charged per bet held by a session when a move is made in it.
+pub const COMPUTE_UNITS_PER_BET: u64 = 100;
+/// Maximum compute units a transaction may use (larger transactions are rejected).
+pub const MAX_TRANSACTION_COMPUTE_UNITS: u64 = 25_000;
+/// Maximum compute units the transactions in a block may use (transactions that don't fit
+/// are skipped).
+pub const MAX_BLOCK_COMPUTE_UNITS: u64 = 2_500_000;
+
+/// Newest version of the [Transaction] and [Value] encodings this release supports (used once
+/// it activates, see [PROTOCOL_ACTIVATION_HEIGHTS]).
+///
+/// Version 0 is the legacy (unversioned) encoding, which remains readable. Payloads encoded
+/// with a newer version than this are rejected.
+pub const PROTOCOL_VERSION: u8 = 1;
+
+/// Height from which each version (after the legacy version 0) is used (index `v - 1` holds
+/// the activation height of version `v`): transactions may use it, and values are written in
+/// it. Before a version activates, blocks produce the same state as a release without it.
+///
+/// Validators must agree on this schedule, so new versions are appended (activating far
+/// enough in the future for validators to upgrade) and existing entries never change.
+pub const PROTOCOL_ACTIVATION_HEIGHTS: [u64; PROTOCOL_VERSION as usize] = [10_000_000];
+
+/// Flag set on the first byte of versioned [Value] encodings (legacy encodings start with
+/// the value tag, which is always below it).
+const VALUE_VERSION_FLAG: u8 = 0x80;
+
+/// Version of the [Event] encoding written by this release.
+///
+/// Version 0 is the legacy encoding (the event's tag followed by its fields), which remains
+/// readable and is written until the envelope activates (see [EVENT_ACTIVATION_HEIGHTS]).
+/// Versioned events are wrapped in an envelope: the version (with
+/// [EVENT_VERSION_FLAG] set), the varint length of the event, then its tag and fields.
+///
+/// Readers keep events they can't decode (a tag they don't know, or a newer version) as
+/// [Event::Unknown] instead of failing, so adding an event only needs a new tag. Changing the
+/// fields of an existing event needs a new version (and readers of the old layout for it).
+pub const EVENT_VERSION: u8 = 1;
+
+/// Flag set on the first byte of versioned [Event] encodings (legacy encodings start with
+/// the event tag, which is always below it).
+const EVENT_VERSION_FLAG: u8 = 0x80;
+
+/// Maximum encoded size of a versioned [Event] (bounds what an unknown event may allocate).
+pub const MAX_EVENT_SIZE: usize = 64 * 1024;
+
+/// Height from which each [Event] version (after the legacy version 0) is written (index
+/// `v - 1` holds the activation height of version `v`). Before a version activates, blocks
+/// produce the same events root as a release without it.
+///
+/// The envelope activates with version 1 of the protocol (see [PROTOCOL_ACTIVATION_HEIGHTS]).
+pub const EVENT_ACTIVATION_HEIGHTS: [u64; EVENT_VERSION as usize] =
+    [PROTOCOL_ACTIVATION_HEIGHTS[0]];
+
+/// The newest version transactions may use at `height` (and the version values written at
+/// `height` are encoded in).
+pub fn protocol_version(height: u64) -> u8 {
+    PROTOCOL_ACTIVATION_HEIGHTS
+        .iter()
+        .take_while(|activation| **activation <= height)
+        .count() as u8
+}
+
+/// The version events emitted at `height` are encoded in.
+pub fn event_version(height: u64) -> u8 {
+    EVENT_ACTIVATION_HEIGHTS
+        .iter()
+        .take_while(|activation| **activation <= height)
+        .count() as u8
+}
+
+pub mod tags {
     pub mod instruction {
         // Casino instructions (10-17)
         pub const CASINO_REGISTER: u8 = 10;
@@ -56,6 +134,77 @@ mod tags {
 
         // Super/Aura mode (30)
         pub const CASINO_TOGGLE_SUPER: u8 = 30;
+
+        // Batches (31)
+        pub const BATCH: u8 = 31;
+
+        // Vaults (32)
+        pub const WITHDRAW_COLLATERAL: u8 = 32;
+
+        // Limit orders (33-34)
+        pub const PLACE_LIMIT_ORDER: u8 = 33;
+        pub const CANCEL_LIMIT_ORDER: u8 = 34;
+
+        // Single-sided liquidity (35)
+        pub const ZAP_LIQUIDITY: u8 = 35;
+
+        // Slashing (36)
+        pub const SLASH: u8 = 36;
+
+        // Daily bonus (37)
+        pub const CLAIM_DAILY_BONUS: u8 = 37;
+
+        // Profiles (38)
+        pub const UPDATE_PROFILE: u8 = 38;
+
+        // Session expiry (39)
+        pub const CASINO_ABANDON_SESSION: u8 = 39;
+
+        // KYC (40)
+        pub const SET_KYC_STATUS: u8 = 40;
+
+        // Scheduled tournaments (41)
+        pub const CASINO_SCHEDULE_TOURNAMENT: u8 = 41;
+
+        // Account recovery (42-43)
+        pub const SET_GUARDIANS: u8 = 42;
+        pub const RECOVER_ACCOUNT: u8 = 43;
+
+        // vUSDT allowances (44-45)
+        pub const APPROVE: u8 = 44;
+        pub const TRANSFER_VUSDT_FROM: u8 = 45;
+
+        // Vesting (46-47)
+        pub const CREATE_VESTING: u8 = 46;
+        pub const CLAIM_VESTED: u8 = 47;
+
+        // Committed game starts (48)
+        pub const CASINO_START_GAME_COMMITTED: u8 = 48;
+
+        // Treasury (49)
+        pub const EXECUTE_BUYBACK: u8 = 49;
+
+        // Game rules (50)
+        pub const SET_GAME_CONFIG: u8 = 50;
+
+        // Bonuses (51)
+        pub const GRANT_BONUS: u8 = 51;
+
+        // Tournament rules (52)
+        pub const SET_TOURNAMENT_RULES: u8 = 52;
+
+        // Economy parameters (53)
+        pub const SET_ECONOMY_PARAMS: u8 = 53;
+
+        // AMM instructions for pools other than RNG/vUSDT (54-57)
+        pub const SWAP_PAIR: u8 = 54;
+        pub const ADD_LIQUIDITY_PAIR: u8 = 55;
+        pub const REMOVE_LIQUIDITY_PAIR: u8 = 56;
+        pub const ZAP_LIQUIDITY_PAIR: u8 = 57;
+
+        // Admin keys and house settings (58-59)
+        pub const SET_ADMIN: u8 = 58;
+        pub const SET_HOUSE_SETTINGS: u8 = 59;
     }
 
     pub mod key {
@@ -77,6 +226,57 @@ mod tags {
 
         // LP balance (18)
         pub const LP_BALANCE: u8 = 18;
+
+        // Price oracle (19)
+        pub const ORACLE: u8 = 19;
+
+        // AMM pools beyond RNG/vUSDT (20) and the pool registry (21)
+        pub const AMM_PAIR_POOL: u8 = 20;
+        pub const AMM_POOLS: u8 = 21;
+
+        // Limit orders (22-23)
+        pub const LIMIT_ORDER: u8 = 22;
+        pub const LIMIT_ORDER_BOOK: u8 = 23;
+
+        // Game history (24)
+        pub const GAME_HISTORY: u8 = 24;
+
+        // Scheduled tournaments (25)
+        pub const TOURNAMENT_SCHEDULES: u8 = 25;
+
+        // Archived leaderboard seasons (26)
+        pub const LEADERBOARD_SEASON: u8 = 26;
+
+        // Account recovery guardians (27)
+        pub const GUARDIANS: u8 = 27;
+
+        // vUSDT allowances (28)
+        pub const ALLOWANCE: u8 = 28;
+
+        // Vesting schedules (29)
+        pub const VESTING: u8 = 29;
+
+        // Treasury (30)
+        pub const TREASURY: u8 = 30;
+
+        // Game rules (31)
+        pub const GAME_CONFIG: u8 = 31;
+
+        // Game statistics (32)
+        pub const GAME_STATS: u8 = 32;
+
+        // Key index buckets (33)
+        pub const KEY_INDEX: u8 = 33;
+
+        // Economy parameters (34)
+        pub const ECONOMY_PARAMS: u8 = 34;
+
+        // LP balances in pools other than RNG/vUSDT (35)
+        pub const LP_PAIR_BALANCE: u8 = 35;
+
+        // Completed sessions awaiting reclamation (36-37)
+        pub const SESSION_EXPIRIES: u8 = 36;
+        pub const SESSION_RECLAIM_CURSOR: u8 = 37;
     }
 
     pub mod value {
@@ -99,6 +299,50 @@ mod tags {
 
         // LP balance (18)
         pub const LP_BALANCE: u8 = 18;
+
+        // Price oracle (19)
+        pub const ORACLE: u8 = 19;
+
+        // AMM pool registry (20)
+        pub const AMM_POOLS: u8 = 20;
+
+        // Limit orders (21-22)
+        pub const LIMIT_ORDER: u8 = 21;
+        pub const LIMIT_ORDER_BOOK: u8 = 22;
+
+        // Game history (23)
+        pub const GAME_HISTORY: u8 = 23;
+
+        // Scheduled tournaments (24)
+        pub const TOURNAMENT_SCHEDULES: u8 = 24;
+
+        // Account recovery guardians (25)
+        pub const GUARDIANS: u8 = 25;
+
+        // vUSDT allowances (26)
+        pub const ALLOWANCE: u8 = 26;
+
+        // Vesting schedules (27)
+        pub const VESTING: u8 = 27;
+
+        // Treasury (28)
+        pub const TREASURY: u8 = 28;
+
+        // Game rules (29)
+        pub const GAME_CONFIG: u8 = 29;
+
+        // Game statistics (30)
+        pub const GAME_STATS: u8 = 30;
+
+        // Key index buckets (31)
+        pub const KEY_INDEX: u8 = 31;
+
+        // Economy parameters (32)
+        pub const ECONOMY_PARAMS: u8 = 32;
+
+        // Completed sessions awaiting reclamation (33-34)
+        pub const SESSION_EXPIRIES: u8 = 33;
+        pub const SESSION_RECLAIM_CURSOR: u8 = 34;
     }
 
     pub mod event {
@@ -130,6 +374,107 @@ mod tags {
         pub const UNSTAKED: u8 = 38;
         pub const EPOCH_PROCESSED: u8 = 39;
         pub const REWARDS_CLAIMED: u8 = 40;
+
+        // Fee events (41-42)
+        pub const FEE_CHARGED: u8 = 41;
+        pub const INTEREST_ACCRUED: u8 = 42;
+
+        // Vault events (43)
+        pub const COLLATERAL_WITHDRAWN: u8 = 43;
+
+        // Limit order events (44-46)
+        pub const LIMIT_ORDER_PLACED: u8 = 44;
+        pub const LIMIT_ORDER_FILLED: u8 = 45;
+        pub const LIMIT_ORDER_CANCELLED: u8 = 46;
+
+        // Single-sided liquidity events (47)
+        pub const LIQUIDITY_ZAPPED: u8 = 47;
+
+        // Slashing events (48)
+        pub const SLASHED: u8 = 48;
+
+        // Daily bonus events (49)
+        pub const DAILY_BONUS_CLAIMED: u8 = 49;
+
+        // Profile events (50)
+        pub const PROFILE_UPDATED: u8 = 50;
+
+        // Session expiry events (51)
+        pub const CASINO_SESSION_EXPIRED: u8 = 51;
+
+        // KYC events (52)
+        pub const KYC_STATUS_CHANGED: u8 = 52;
+
+        // Scheduled tournament events (53)
+        pub const TOURNAMENT_SCHEDULED: u8 = 53;
+
+        // Tournament payouts (54)
+        pub const TOURNAMENT_PRIZE_PAID: u8 = 54;
+
+        // Leaderboard seasons (55-56)
+        pub const LEADERBOARD_SEASON_ENDED: u8 = 55;
+        pub const SEASON_REWARD_PAID: u8 = 56;
+
+        // VIP program (57-58)
+        pub const LOYALTY_EARNED: u8 = 57;
+        pub const TIER_CHANGED: u8 = 58;
+
+        // Insurance fund (59-60)
+        pub const INSURANCE_FUNDED: u8 = 59;
+        pub const INSURANCE_DRAWN: u8 = 60;
+
+        // Rejected instructions (61)
+        pub const INSTRUCTION_FAILED: u8 = 61;
+
+        // Account recovery (62-64)
+        pub const GUARDIANS_SET: u8 = 62;
+        pub const RECOVERY_APPROVED: u8 = 63;
+        pub const ACCOUNT_RECOVERED: u8 = 64;
+
+        // vUSDT allowances (65-66)
+        pub const ALLOWANCE_SET: u8 = 65;
+        pub const VUSDT_TRANSFERRED: u8 = 66;
+
+        // Vesting events (67-68)
+        pub const VESTING_CREATED: u8 = 67;
+        pub const VESTED_CLAIMED: u8 = 68;
+
+        // Staking rewards (69)
+        pub const STAKING_REWARDS_ISSUED: u8 = 69;
+
+        // Treasury events (70-71)
+        pub const TREASURY_FUNDED: u8 = 70;
+        pub const BUYBACK_EXECUTED: u8 = 71;
+
+        // Game rules (72)
+        pub const GAME_CONFIG_UPDATED: u8 = 72;
+
+        // RNG transcripts (73)
+        pub const CASINO_RNG_TRANSCRIPT: u8 = 73;
+
+        // Side bets (74)
+        pub const CASINO_SIDE_BET_SETTLED: u8 = 74;
+
+        // Game statistics (75)
+        pub const GAME_STATS_UPDATED: u8 = 75;
+
+        // Bonuses (76-77)
+        pub const BONUS_GRANTED: u8 = 76;
+        pub const BONUS_RELEASED: u8 = 77;
+
+        // Tournament rules (78)
+        pub const TOURNAMENT_RULES_SET: u8 = 78;
+
+        // Table events (79-80)
+        pub const CASINO_CARD_DEALT: u8 = 79;
+        pub const CASINO_CRAPS_ROLLED: u8 = 80;
+
+        // Economy parameters (81)
+        pub const ECONOMY_PARAMS_UPDATED: u8 = 81;
+
+        // Admin key and house settings events (82-83)
+        pub const ADMIN_SET: u8 = 82;
+        pub const HOUSE_SETTINGS_UPDATED: u8 = 83;
     }
 }
 
@@ -147,8 +492,54 @@ pub fn transaction_namespace(namespace: &[u8]) -> Vec<u8> {
     union(namespace, TRANSACTION_SUFFIX)
 }
 
+#[inline]
+pub fn slash_namespace(namespace: &[u8]) -> Vec<u8> {
+    union(namespace, SLASH_SUFFIX)
+}
+
+/// The message the validator set signs (with the network identity) to slash `bps` of the stake
+/// of `staker`.
+///
+/// `index` is the number of times the staker has already been slashed (so evidence can't be
+/// replayed).
+pub fn slash_message(staker: &PublicKey, bps: u16, index: u64) -> Vec<u8> {
+    let mut message = Vec::with_capacity(PublicKey::SIZE + u16::SIZE + u64::SIZE);
+    staker.write(&mut message);
+    bps.write(&mut message);
+    index.write(&mut message);
+    message
+}
+
+#[inline]
+pub fn admin_namespace(namespace: &[u8]) -> Vec<u8> {
+    union(namespace, ADMIN_SUFFIX)
+}
+
+/// The message the validator set signs (with the network identity) to set the key holding
+/// `role` to `admin` (or clear it).
+///
+/// `index` is the number of admin keys already set (so approvals can't be replayed).
+pub fn admin_message(
+    role: crate::casino::AdminRole,
+    admin: Option<&PublicKey>,
+    index: u64,
+) -> Vec<u8> {
+    let admin = admin.cloned();
+    let mut message = Vec::with_capacity(role.encode_size() + admin.encode_size() + u64::SIZE);
+    role.write(&mut message);
+    admin.write(&mut message);
+    index.write(&mut message);
+    message
+}
+
+/// A signed instruction.
+///
+/// Versioned transactions are encoded (and signed) with their version first. Legacy (version
+/// 0) transactions start with their nonce instead, which is told apart by its first byte
+/// being 0 (so they can't use nonces of 2^56 or more).
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Transaction {
+    pub version: u8,
     pub nonce: u64,
     pub instruction: Instruction,
 
@@ -157,21 +548,38 @@ pub struct Transaction {
 }
 
 impl Transaction {
-    fn payload(nonce: &u64, instruction: &Instruction) -> Vec<u8> {
-        let mut payload = Vec::with_capacity(nonce.encode_size() + instruction.encode_size());
+    fn payload(version: u8, nonce: &u64, instruction: &Instruction) -> Vec<u8> {
+        let mut payload =
+            Vec::with_capacity(u8::SIZE + nonce.encode_size() + instruction.encode_size());
+        if version > 0 {
+            version.write(&mut payload);
+        }
         nonce.write(&mut payload);
         instruction.write(&mut payload);
 
         payload
     }
 
+    /// Sign a legacy (version 0) transaction, which blocks accept at every height.
     pub fn sign(private: &ed25519::PrivateKey, nonce: u64, instruction: Instruction) -> Self {
+        Self::sign_version(private, 0, nonce, instruction)
+    }
+
+    /// Sign a transaction with `version` (blocks only include it once the version has
+    /// activated, see [protocol_version]).
+    pub fn sign_version(
+        private: &ed25519::PrivateKey,
+        version: u8,
+        nonce: u64,
+        instruction: Instruction,
+    ) -> Self {
         let signature = private.sign(
             Some(TRANSACTION_NAMESPACE),
-            &Self::payload(&nonce, &instruction),
+            &Self::payload(version, &nonce, &instruction),
         );
 
         Self {
+            version,
             nonce,
             instruction,
             public: private.public_key(),
@@ -182,7 +590,7 @@ impl Transaction {
     pub fn verify(&self) -> bool {
         self.public.verify(
             Some(TRANSACTION_NAMESPACE),
-            &Self::payload(&self.nonce, &self.instruction),
+            &Self::payload(self.version, &self.nonce, &self.instruction),
             &self.signature,
         )
     }
@@ -190,7 +598,7 @@ impl Transaction {
     pub fn verify_batch(&self, batch: &mut Batch) {
         batch.add(
             Some(TRANSACTION_NAMESPACE),
-            &Self::payload(&self.nonce, &self.instruction),
+            &Self::payload(self.version, &self.nonce, &self.instruction),
             &self.public,
             &self.signature,
         );
@@ -199,6 +607,9 @@ impl Transaction {
 
 impl Write for Transaction {
     fn write(&self, writer: &mut impl BufMut) {
+        if self.version > 0 {
+            self.version.write(writer);
+        }
         self.nonce.write(writer);
         self.instruction.write(writer);
         self.public.write(writer);
@@ -210,12 +621,21 @@ impl Read for Transaction {
     type Cfg = ();
 
     fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        // Legacy transactions start with the (zero) first byte of their nonce
+        let version = match reader.chunk().first() {
+            Some(0) | None => 0,
+            Some(_) => u8::read(reader)?,
+        };
+        if version > PROTOCOL_VERSION {
+            return Err(Error::Invalid("Transaction", "unsupported version"));
+        }
         let nonce = u64::read(reader)?;
         let instruction = Instruction::read(reader)?;
         let public = ed25519::PublicKey::read(reader)?;
         let signature = ed25519::Signature::read(reader)?;
 
         Ok(Self {
+            version,
             nonce,
             instruction,
             public,
@@ -226,7 +646,8 @@ impl Read for Transaction {
 
 impl EncodeSize for Transaction {
     fn encode_size(&self) -> usize {
-        self.nonce.encode_size()
+        usize::from(self.version > 0) * u8::SIZE
+            + self.nonce.encode_size()
             + self.instruction.encode_size()
             + self.public.encode_size()
             + self.signature.encode_size()
@@ -238,6 +659,10 @@ impl Digestible for Transaction {
 
     fn digest(&self) -> Digest {
         let mut hasher = Sha256::new();
+        // Legacy transactions keep their original digest
+        if self.version > 0 {
+            hasher.update(&[self.version]);
+        }
         hasher.update(self.nonce.to_be_bytes().as_ref());
         hasher.update(self.instruction.encode().as_ref());
         hasher.update(self.public.as_ref());
@@ -255,16 +680,25 @@ pub enum Instruction {
     /// Binary: [10] [nameLen:u32 BE] [nameBytes...]
     CasinoRegister { name: String },
 
-    /// Deposit chips (for testing/faucet).
+    /// Deposit chips (for testing/faucet), at most [crate::casino::EconomyParams::faucet_amount].
     /// Binary: [11] [amount:u64 BE]
     CasinoDeposit { amount: u64 },
 
     /// Start a new casino game session.
     /// Binary: [12] [gameType:u8] [bet:u64 BE] [sessionId:u64 BE]
+    /// With a commitment: [48] [gameType:u8] [bet:u64 BE] [sessionId:u64 BE] [commitment:32]
+    ///
+    /// A commitment is the sha256 of a 32-byte preimage the first move must prefix its payload
+    /// with; the preimage is then mixed into the RNG of every later move (the initial deal only
+    /// uses the seed).
+    ///
+    /// The session ID must be [crate::casino::derive_session_id] of the signer and the
+    /// transaction's nonce.
     CasinoStartGame {
         game_type: crate::casino::GameType,
         bet: u64,
         session_id: u64,
+        commitment: Option<Digest>,
     },
 
     /// Make a move in an active casino game.
@@ -301,9 +735,9 @@ pub enum Instruction {
     /// Binary: [18] [amount:u64 BE] [duration:u64 BE]
     Stake { amount: u64, duration: u64 },
 
-    /// Unstake chips after lockup period.
-    /// Binary: [19]
-    Unstake,
+    /// Unstake `amount` chips from stakes whose lockup has ended.
+    /// Binary: [19] [amount:u64 BE]
+    Unstake { amount: u64 },
 
     /// Claim staking rewards.
     /// Binary: [20]
@@ -313,7 +747,7 @@ pub enum Instruction {
     /// Binary: [21]
     ProcessEpoch,
 
-    // Virtual Liquidity / Vault Instructions (tags 22-25)
+    // Virtual Liquidity / Vault Instructions (tags 22-25, 32)
     /// Create a new Vault (CDP).
     /// Binary: [22]
     CreateVault,
@@ -330,26 +764,964 @@ pub enum Instruction {
     /// Binary: [25] [amount:u64 BE]
     RepayUSDT { amount: u64 },
 
+    /// Withdraw RNG collateral from vault (only while the vault stays within the max LTV).
+    /// Binary: [32] [amount:u64 BE]
+    WithdrawCollateral { amount: u64 },
+
     // AMM Instructions (tags 26-28)
+    //
+    // Instructions on the RNG/vUSDT pool keep their original encoding. Instructions on other
+    // pools use a separate tag (54-57) followed by the pair.
     /// Swap tokens on the AMM.
     /// Binary: [26] [amountIn:u64 BE] [minAmountOut:u64 BE] [isBuyingRng:u8]
+    /// or [54] [pair:u16 BE] [amountIn:u64 BE] [minAmountOut:u64 BE] [isBuyingRng:u8]
     Swap {
+        pair: crate::casino::PairId,
         amount_in: u64,
         min_amount_out: u64,
         is_buying_rng: bool,
     },
 
-    /// Add liquidity to AMM.
+    /// Add liquidity to AMM (creating the pool if the pair has none yet).
     /// Binary: [27] [rngAmount:u64 BE] [usdtAmount:u64 BE]
-    AddLiquidity { rng_amount: u64, usdt_amount: u64 },
+    /// or [55] [pair:u16 BE] [rngAmount:u64 BE] [usdtAmount:u64 BE]
+    AddLiquidity {
+        pair: crate::casino::PairId,
+        rng_amount: u64,
+        usdt_amount: u64,
+    },
 
     /// Remove liquidity from AMM.
-    /// Binary: [28] [shares:u64 BE]
-    RemoveLiquidity { shares: u64 },
+    /// Binary: [28] [shares:u64 BE] or [56] [pair:u16 BE] [shares:u64 BE]
+    RemoveLiquidity {
+        pair: crate::casino::PairId,
+        shares: u64,
+    },
+
+    /// Escrow `amount_in` and swap it on the AMM once the output per unit of input (scaled by
+    /// PRICE_SCALE) reaches `min_price`.
+    /// Binary: [33] [amountIn:u64 BE] [minPrice:u64 BE] [isBuyingRng:u8]
+    PlaceLimitOrder {
+        amount_in: u64,
+        min_price: u64,
+        is_buying_rng: bool,
+    },
+
+    /// Cancel an open limit order (refunding its escrow).
+    /// Binary: [34] [orderId:u64 BE]
+    CancelLimitOrder { order_id: u64 },
+
+    /// Add liquidity from a single asset (RNG if `from_rng`, vUSDT otherwise), swapping the
+    /// portion needed to match the pool's ratio.
+    /// Binary: [35] [amountIn:u64 BE] [minShares:u64 BE] [fromRng:u8]
+    /// or [57] [pair:u16 BE] [amountIn:u64 BE] [minShares:u64 BE] [fromRng:u8]
+    ZapLiquidity {
+        pair: crate::casino::PairId,
+        amount_in: u64,
+        min_shares: u64,
+        from_rng: bool,
+    },
+
+    /// Slash `bps` of a staker's stake (burning it), authorized by the validator set signing
+    /// [slash_message] with the network identity (any account can submit the evidence).
+    /// Binary: [36] [staker:PublicKey] [bps:u16 BE] [signature:Signature]
+    Slash {
+        staker: PublicKey,
+        bps: u16,
+        signature: Signature,
+    },
+
+    /// Claim the daily bonus (once per cooldown, growing with consecutive daily claims).
+    /// Binary: [37]
+    ClaimDailyBonus,
+
+    /// Update the player's profile (rate limited to once per `PROFILE_UPDATE_COOLDOWN` views).
+    /// Binary: [38] [displayNameLen:u32 BE] [displayNameBytes...] [avatarId:u32 BE]
+    ///         [titleLen:u32 BE] [titleBytes...]
+    UpdateProfile {
+        display_name: String,
+        avatar_id: u32,
+        title: String,
+    },
+
+    /// Abandon a game session without a move for `SESSION_EXPIRY` views (resolving it as a
+    /// refund or loss depending on the game).
+    /// Binary: [39] [sessionId:u64 BE]
+    CasinoAbandonSession { session_id: u64 },
+
+    /// Set whether a player has passed KYC (only the house's KYC admin can submit this).
+    /// Binary: [40] [player:PublicKey] [verified:u8]
+    SetKycStatus { player: PublicKey, verified: bool },
+
+    /// Schedule a tournament to start at `start_view` and end at `end_view` (without any
+    /// further instructions).
+    /// Binary: [41] [tournamentId:u64 BE] [startView:u64 BE] [endView:u64 BE]
+    CasinoScheduleTournament {
+        tournament_id: u64,
+        start_view: u64,
+        end_view: u64,
+    },
 
     /// End a tournament and distribute prizes.
     /// Binary: [29] [tournamentId:u64 BE]
     CasinoEndTournament { tournament_id: u64 },
+
+    /// Set the guardians that can recover the account (cancelling any pending recovery). An
+    /// empty set (with a zero threshold) disables recovery.
+    /// Binary: [42] [count:varint] [guardian:PublicKey...] [threshold:u8]
+    SetGuardians {
+        guardians: Vec<PublicKey>,
+        threshold: u8,
+    },
+
+    /// Approve moving `account` to `new_key` (only its guardians can submit this). Once enough
+    /// guardians approve and `RECOVERY_DELAY` views pass, the next approval executes the move.
+    /// Approving a different key replaces a pending recovery only until it reaches the
+    /// threshold (after which only the owner can cancel it).
+    /// Binary: [43] [account:PublicKey] [newKey:PublicKey]
+    RecoverAccount {
+        account: PublicKey,
+        new_key: PublicKey,
+    },
+
+    /// Allow `spender` to transfer up to `amount` of the player's vUSDT (replacing any previous
+    /// allowance, zero revokes it).
+    /// Binary: [44] [spender:PublicKey] [amount:u64 BE]
+    Approve { spender: PublicKey, amount: u64 },
+
+    /// Transfer `amount` of `owner`'s vUSDT to `to`, spending the allowance `owner` granted the
+    /// signer.
+    /// Binary: [45] [owner:PublicKey] [to:PublicKey] [amount:u64 BE]
+    TransferVusdtFrom {
+        owner: PublicKey,
+        to: PublicKey,
+        amount: u64,
+    },
+
+    /// Escrow `amount` chips that unlock to `to` linearly over `duration` views from
+    /// `start_view`.
+    /// Binary: [46] [to:PublicKey] [amount:u64 BE] [startView:u64 BE] [duration:u64 BE]
+    CreateVesting {
+        to: PublicKey,
+        amount: u64,
+        start_view: u64,
+        duration: u64,
+    },
+
+    /// Claim the chips unlocked by all of the player's vesting schedules.
+    /// Binary: [47]
+    ClaimVested,
+
+    /// Spend `amount` vUSDT from the treasury buying RNG through the AMM, and burn the RNG
+    /// bought (treasury admin only).
+    /// Binary: [49] [amount:u64 BE]
+    ExecuteBuyback { amount: u64 },
+
+    /// Set the table rules of a game (governance only), applied to sessions started afterwards.
+    /// Binary: [50] [gameType:u8] [paytable:u8] [commissionOnWin:bool] [dealerHitsSoft17:bool]
+    /// [maxSplitHands:u8] [recordRng:bool] [shoeDecks:u8] [shoePenetration:u8]
+    /// [dealerPeeks:bool] [evenMoney:bool]
+    SetGameConfig {
+        game_type: crate::casino::GameType,
+        config: crate::casino::GameConfig,
+    },
+
+    /// Grant bonus chips to a player (governance only), released once the house's wagering
+    /// requirement is met.
+    /// Binary: [51] [player:PublicKey] [amount:u64 BE]
+    GrantBonus { player: PublicKey, amount: u64 },
+
+    /// Set the rule overrides of a tournament that hasn't started (governance only).
+    /// Binary: [52] [tournamentId:u64 BE] [minBet:u64 BE] [maxBet:u64 BE]
+    /// [disabledGames:varint count] [gameType:u8...] [gameConfigs:varint count]
+    /// [gameType:u8 config...] [superBoostBps:u16 BE]
+    SetTournamentRules {
+        tournament_id: u64,
+        rules: crate::casino::TournamentRules,
+    },
+
+    /// Set the economy parameters (governance only), taking effect from the next block.
+    /// Binary: [53] [initialChips:u64 BE] [faucetAmount:u64 BE] [superModeFeeBps:u16 BE]
+    /// [ammFeeBps:u16 BE] [minimumLiquidity:u64 BE]
+    SetEconomyParams {
+        params: crate::casino::EconomyParams,
+    },
+
+    /// Set (or clear) the key holding an admin role, authorized by the validator set signing
+    /// [admin_message] with the network identity (any account can submit the approval).
+    /// Binary: [58] [role:u8] [admin:Option<PublicKey>] [signature:Signature]
+    SetAdmin {
+        role: crate::casino::AdminRole,
+        admin: Option<PublicKey>,
+        signature: Signature,
+    },
+
+    /// Set the house settings (governance only).
+    /// Binary: [59] [flatFee:u64 BE] [feeOverrides:varint count] [tag:u8 fee:u64 BE...]
+    /// [dailyBonus...] [defaultMaxBet:u64 BE] [maxBets:varint count] [gameType:u8 max:u64 BE...]
+    /// [maxExposure:u64 BE] [kycDepositLimit:u64 BE]
+    SetHouseSettings {
+        settings: crate::casino::HouseSettings,
+    },
+
+    /// Execute multiple instructions atomically (if any fails, none are applied).
+    /// Batches can't be nested and can start at most one game (session IDs are derived from
+    /// the transaction's nonce).
+    /// Binary: [31] [count:varint] [instruction...]
+    Batch(Vec<Instruction>),
+}
+
+/// A parameter of an instruction (in encoding order).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InstructionParam {
+    pub name: &'static str,
+    /// The parameter's type, as written in the instruction's binary layout (e.g. `u64`).
+    pub kind: &'static str,
+}
+
+const fn param(name: &'static str, kind: &'static str) -> InstructionParam {
+    InstructionParam { name, kind }
+}
+
+/// Machine-readable description of an [Instruction] variant (see [instruction_catalog]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InstructionInfo {
+    /// Stable discriminant of the variant (its index in the catalog, and the
+    /// `InstructionKind` of clients).
+    pub kind: u8,
+    /// Name of the variant.
+    pub name: &'static str,
+    /// Human-readable name.
+    pub label: &'static str,
+    /// Tags the variant is encoded with.
+    pub tags: &'static [u8],
+    pub params: &'static [InstructionParam],
+    /// Whether the instruction can change casino state (players, sessions, tournaments).
+    pub mutates_casino: bool,
+    /// Whether the instruction can change DeFi state (vaults, the AMM, vUSDT).
+    pub mutates_defi: bool,
+    /// Whether the instruction can change staking state.
+    pub mutates_staking: bool,
+}
+
+static INSTRUCTION_CATALOG: [InstructionInfo; 45] = [
+    InstructionInfo {
+        kind: 0,
+        name: "CasinoRegister",
+        label: "Register casino player",
+        tags: &[tags::instruction::CASINO_REGISTER],
+        params: &[param("name", "string")],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 1,
+        name: "CasinoDeposit",
+        label: "Deposit chips (faucet)",
+        tags: &[tags::instruction::CASINO_DEPOSIT],
+        params: &[param("amount", "u64")],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 2,
+        name: "CasinoStartGame",
+        label: "Start game",
+        tags: &[
+            tags::instruction::CASINO_START_GAME,
+            tags::instruction::CASINO_START_GAME_COMMITTED,
+        ],
+        params: &[
+            param("game_type", "GameType"),
+            param("bet", "u64"),
+            param("session_id", "u64"),
+            param("commitment", "Option<Digest>"),
+        ],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 3,
+        name: "CasinoGameMove",
+        label: "Casino game move",
+        tags: &[tags::instruction::CASINO_GAME_MOVE],
+        params: &[param("session_id", "u64"), param("payload", "bytes")],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 4,
+        name: "CasinoToggleShield",
+        label: "Toggle shield modifier",
+        tags: &[tags::instruction::CASINO_TOGGLE_SHIELD],
+        params: &[],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 5,
+        name: "CasinoToggleDouble",
+        label: "Toggle double modifier",
+        tags: &[tags::instruction::CASINO_TOGGLE_DOUBLE],
+        params: &[],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 6,
+        name: "CasinoToggleSuper",
+        label: "Toggle super mode",
+        tags: &[tags::instruction::CASINO_TOGGLE_SUPER],
+        params: &[],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 7,
+        name: "CasinoJoinTournament",
+        label: "Join tournament",
+        tags: &[tags::instruction::CASINO_JOIN_TOURNAMENT],
+        params: &[param("tournament_id", "u64")],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 8,
+        name: "CasinoStartTournament",
+        label: "Start tournament",
+        tags: &[tags::instruction::CASINO_START_TOURNAMENT],
+        params: &[
+            param("tournament_id", "u64"),
+            param("start_time_ms", "u64"),
+            param("end_time_ms", "u64"),
+        ],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 9,
+        name: "CasinoEndTournament",
+        label: "End tournament",
+        tags: &[tags::instruction::CASINO_END_TOURNAMENT],
+        params: &[param("tournament_id", "u64")],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 10,
+        name: "Stake",
+        label: "Stake",
+        tags: &[tags::instruction::STAKE],
+        params: &[param("amount", "u64"), param("duration", "u64")],
+        mutates_casino: false,
+        mutates_defi: false,
+        mutates_staking: true,
+    },
+    InstructionInfo {
+        kind: 11,
+        name: "Unstake",
+        label: "Unstake",
+        tags: &[tags::instruction::UNSTAKE],
+        params: &[param("amount", "u64")],
+        mutates_casino: false,
+        mutates_defi: false,
+        mutates_staking: true,
+    },
+    InstructionInfo {
+        kind: 12,
+        name: "ClaimRewards",
+        label: "Claim staking rewards",
+        tags: &[tags::instruction::CLAIM_REWARDS],
+        params: &[],
+        mutates_casino: false,
+        mutates_defi: false,
+        mutates_staking: true,
+    },
+    InstructionInfo {
+        kind: 13,
+        name: "ProcessEpoch",
+        label: "Process epoch",
+        tags: &[tags::instruction::PROCESS_EPOCH],
+        params: &[],
+        mutates_casino: false,
+        mutates_defi: false,
+        mutates_staking: true,
+    },
+    InstructionInfo {
+        kind: 14,
+        name: "CreateVault",
+        label: "Create vault",
+        tags: &[tags::instruction::CREATE_VAULT],
+        params: &[],
+        mutates_casino: false,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 15,
+        name: "DepositCollateral",
+        label: "Deposit collateral",
+        tags: &[tags::instruction::DEPOSIT_COLLATERAL],
+        params: &[param("amount", "u64")],
+        mutates_casino: false,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 16,
+        name: "BorrowUSDT",
+        label: "Borrow vUSDT",
+        tags: &[tags::instruction::BORROW_USDT],
+        params: &[param("amount", "u64")],
+        mutates_casino: false,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 17,
+        name: "RepayUSDT",
+        label: "Repay vUSDT",
+        tags: &[tags::instruction::REPAY_USDT],
+        params: &[param("amount", "u64")],
+        mutates_casino: false,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 18,
+        name: "Swap",
+        label: "Swap",
+        tags: &[tags::instruction::SWAP, tags::instruction::SWAP_PAIR],
+        params: &[
+            param("pair", "PairId"),
+            param("amount_in", "u64"),
+            param("min_amount_out", "u64"),
+            param("is_buying_rng", "bool"),
+        ],
+        mutates_casino: false,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 19,
+        name: "AddLiquidity",
+        label: "Add liquidity",
+        tags: &[
+            tags::instruction::ADD_LIQUIDITY,
+            tags::instruction::ADD_LIQUIDITY_PAIR,
+        ],
+        params: &[
+            param("pair", "PairId"),
+            param("rng_amount", "u64"),
+            param("usdt_amount", "u64"),
+        ],
+        mutates_casino: false,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 20,
+        name: "RemoveLiquidity",
+        label: "Remove liquidity",
+        tags: &[
+            tags::instruction::REMOVE_LIQUIDITY,
+            tags::instruction::REMOVE_LIQUIDITY_PAIR,
+        ],
+        params: &[param("pair", "PairId"), param("shares", "u64")],
+        mutates_casino: false,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 21,
+        name: "Batch",
+        label: "Batch",
+        tags: &[tags::instruction::BATCH],
+        params: &[param("instructions", "Vec<Instruction>")],
+        mutates_casino: true,
+        mutates_defi: true,
+        mutates_staking: true,
+    },
+    InstructionInfo {
+        kind: 22,
+        name: "WithdrawCollateral",
+        label: "Withdraw collateral",
+        tags: &[tags::instruction::WITHDRAW_COLLATERAL],
+        params: &[param("amount", "u64")],
+        mutates_casino: false,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 23,
+        name: "PlaceLimitOrder",
+        label: "Place limit order",
+        tags: &[tags::instruction::PLACE_LIMIT_ORDER],
+        params: &[
+            param("amount_in", "u64"),
+            param("min_price", "u64"),
+            param("is_buying_rng", "bool"),
+        ],
+        mutates_casino: false,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 24,
+        name: "CancelLimitOrder",
+        label: "Cancel limit order",
+        tags: &[tags::instruction::CANCEL_LIMIT_ORDER],
+        params: &[param("order_id", "u64")],
+        mutates_casino: false,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 25,
+        name: "ZapLiquidity",
+        label: "Add single-sided liquidity",
+        tags: &[
+            tags::instruction::ZAP_LIQUIDITY,
+            tags::instruction::ZAP_LIQUIDITY_PAIR,
+        ],
+        params: &[
+            param("pair", "PairId"),
+            param("amount_in", "u64"),
+            param("min_shares", "u64"),
+            param("from_rng", "bool"),
+        ],
+        mutates_casino: false,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 26,
+        name: "Slash",
+        label: "Slash staker",
+        tags: &[tags::instruction::SLASH],
+        params: &[
+            param("staker", "PublicKey"),
+            param("bps", "u16"),
+            param("signature", "Signature"),
+        ],
+        mutates_casino: false,
+        mutates_defi: false,
+        mutates_staking: true,
+    },
+    InstructionInfo {
+        kind: 27,
+        name: "ClaimDailyBonus",
+        label: "Claim daily bonus",
+        tags: &[tags::instruction::CLAIM_DAILY_BONUS],
+        params: &[],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 28,
+        name: "UpdateProfile",
+        label: "Update profile",
+        tags: &[tags::instruction::UPDATE_PROFILE],
+        params: &[
+            param("display_name", "string"),
+            param("avatar_id", "u32"),
+            param("title", "string"),
+        ],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 29,
+        name: "CasinoAbandonSession",
+        label: "Abandon casino session",
+        tags: &[tags::instruction::CASINO_ABANDON_SESSION],
+        params: &[param("session_id", "u64")],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 30,
+        name: "SetKycStatus",
+        label: "Set KYC status",
+        tags: &[tags::instruction::SET_KYC_STATUS],
+        params: &[param("player", "PublicKey"), param("verified", "bool")],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 31,
+        name: "CasinoScheduleTournament",
+        label: "Schedule tournament",
+        tags: &[tags::instruction::CASINO_SCHEDULE_TOURNAMENT],
+        params: &[
+            param("tournament_id", "u64"),
+            param("start_view", "u64"),
+            param("end_view", "u64"),
+        ],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 32,
+        name: "SetGuardians",
+        label: "Set guardians",
+        tags: &[tags::instruction::SET_GUARDIANS],
+        params: &[
+            param("guardians", "Vec<PublicKey>"),
+            param("threshold", "u8"),
+        ],
+        mutates_casino: false,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 33,
+        name: "RecoverAccount",
+        label: "Approve account recovery",
+        tags: &[tags::instruction::RECOVER_ACCOUNT],
+        params: &[param("account", "PublicKey"), param("new_key", "PublicKey")],
+        mutates_casino: true,
+        mutates_defi: true,
+        mutates_staking: true,
+    },
+    InstructionInfo {
+        kind: 34,
+        name: "Approve",
+        label: "Approve vUSDT spender",
+        tags: &[tags::instruction::APPROVE],
+        params: &[param("spender", "PublicKey"), param("amount", "u64")],
+        mutates_casino: false,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 35,
+        name: "TransferVusdtFrom",
+        label: "Transfer approved vUSDT",
+        tags: &[tags::instruction::TRANSFER_VUSDT_FROM],
+        params: &[
+            param("owner", "PublicKey"),
+            param("to", "PublicKey"),
+            param("amount", "u64"),
+        ],
+        mutates_casino: false,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 36,
+        name: "CreateVesting",
+        label: "Create vesting schedule",
+        tags: &[tags::instruction::CREATE_VESTING],
+        params: &[
+            param("to", "PublicKey"),
+            param("amount", "u64"),
+            param("start_view", "u64"),
+            param("duration", "u64"),
+        ],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 37,
+        name: "ClaimVested",
+        label: "Claim vested RNG",
+        tags: &[tags::instruction::CLAIM_VESTED],
+        params: &[],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 38,
+        name: "ExecuteBuyback",
+        label: "Execute buyback",
+        tags: &[tags::instruction::EXECUTE_BUYBACK],
+        params: &[param("amount", "u64")],
+        mutates_casino: false,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 39,
+        name: "SetGameConfig",
+        label: "Set game rules",
+        tags: &[tags::instruction::SET_GAME_CONFIG],
+        params: &[
+            param("game_type", "GameType"),
+            param("config", "GameConfig"),
+        ],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 40,
+        name: "GrantBonus",
+        label: "Grant bonus",
+        tags: &[tags::instruction::GRANT_BONUS],
+        params: &[param("player", "PublicKey"), param("amount", "u64")],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 41,
+        name: "SetTournamentRules",
+        label: "Set tournament rules",
+        tags: &[tags::instruction::SET_TOURNAMENT_RULES],
+        params: &[
+            param("tournament_id", "u64"),
+            param("rules", "TournamentRules"),
+        ],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 42,
+        name: "SetEconomyParams",
+        label: "Set economy parameters",
+        tags: &[tags::instruction::SET_ECONOMY_PARAMS],
+        params: &[param("params", "EconomyParams")],
+        mutates_casino: true,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 43,
+        name: "SetAdmin",
+        label: "Set admin key",
+        tags: &[tags::instruction::SET_ADMIN],
+        params: &[
+            param("role", "AdminRole"),
+            param("admin", "Option<PublicKey>"),
+            param("signature", "Signature"),
+        ],
+        mutates_casino: true,
+        mutates_defi: true,
+        mutates_staking: false,
+    },
+    InstructionInfo {
+        kind: 44,
+        name: "SetHouseSettings",
+        label: "Set house settings",
+        tags: &[tags::instruction::SET_HOUSE_SETTINGS],
+        params: &[param("settings", "HouseSettings")],
+        mutates_casino: true,
+        mutates_defi: false,
+        mutates_staking: false,
+    },
+];
+
+/// Every [Instruction] variant, ordered by [InstructionInfo::kind].
+///
+/// New variants are appended (so kinds never change).
+pub fn instruction_catalog() -> &'static [InstructionInfo] {
+    &INSTRUCTION_CATALOG
+}
+
+impl Instruction {
+    /// The tag identifying this kind of instruction (its first encoded byte).
+    pub fn tag(&self) -> u8 {
+        match self {
+            Self::CasinoRegister { .. } => tags::instruction::CASINO_REGISTER,
+            Self::CasinoDeposit { .. } => tags::instruction::CASINO_DEPOSIT,
+            Self::CasinoStartGame {
+                commitment: None, ..
+            } => tags::instruction::CASINO_START_GAME,
+            Self::CasinoStartGame {
+                commitment: Some(_),
+                ..
+            } => tags::instruction::CASINO_START_GAME_COMMITTED,
+            Self::CasinoGameMove { .. } => tags::instruction::CASINO_GAME_MOVE,
+            Self::CasinoToggleShield => tags::instruction::CASINO_TOGGLE_SHIELD,
+            Self::CasinoToggleDouble => tags::instruction::CASINO_TOGGLE_DOUBLE,
+            Self::CasinoToggleSuper => tags::instruction::CASINO_TOGGLE_SUPER,
+            Self::CasinoJoinTournament { .. } => tags::instruction::CASINO_JOIN_TOURNAMENT,
+            Self::CasinoStartTournament { .. } => tags::instruction::CASINO_START_TOURNAMENT,
+            Self::CasinoEndTournament { .. } => tags::instruction::CASINO_END_TOURNAMENT,
+            Self::Stake { .. } => tags::instruction::STAKE,
+            Self::Unstake { .. } => tags::instruction::UNSTAKE,
+            Self::ClaimRewards => tags::instruction::CLAIM_REWARDS,
+            Self::ProcessEpoch => tags::instruction::PROCESS_EPOCH,
+            Self::CreateVault => tags::instruction::CREATE_VAULT,
+            Self::DepositCollateral { .. } => tags::instruction::DEPOSIT_COLLATERAL,
+            Self::BorrowUSDT { .. } => tags::instruction::BORROW_USDT,
+            Self::RepayUSDT { .. } => tags::instruction::REPAY_USDT,
+            Self::WithdrawCollateral { .. } => tags::instruction::WITHDRAW_COLLATERAL,
+            Self::Swap { pair, .. } => {
+                pair_tag(*pair, tags::instruction::SWAP, tags::instruction::SWAP_PAIR)
+            }
+            Self::AddLiquidity { pair, .. } => pair_tag(
+                *pair,
+                tags::instruction::ADD_LIQUIDITY,
+                tags::instruction::ADD_LIQUIDITY_PAIR,
+            ),
+            Self::RemoveLiquidity { pair, .. } => pair_tag(
+                *pair,
+                tags::instruction::REMOVE_LIQUIDITY,
+                tags::instruction::REMOVE_LIQUIDITY_PAIR,
+            ),
+            Self::PlaceLimitOrder { .. } => tags::instruction::PLACE_LIMIT_ORDER,
+            Self::CancelLimitOrder { .. } => tags::instruction::CANCEL_LIMIT_ORDER,
+            Self::ZapLiquidity { pair, .. } => pair_tag(
+                *pair,
+                tags::instruction::ZAP_LIQUIDITY,
+                tags::instruction::ZAP_LIQUIDITY_PAIR,
+            ),
+            Self::Slash { .. } => tags::instruction::SLASH,
+            Self::ClaimDailyBonus => tags::instruction::CLAIM_DAILY_BONUS,
+            Self::UpdateProfile { .. } => tags::instruction::UPDATE_PROFILE,
+            Self::CasinoAbandonSession { .. } => tags::instruction::CASINO_ABANDON_SESSION,
+            Self::SetKycStatus { .. } => tags::instruction::SET_KYC_STATUS,
+            Self::CasinoScheduleTournament { .. } => tags::instruction::CASINO_SCHEDULE_TOURNAMENT,
+            Self::SetGuardians { .. } => tags::instruction::SET_GUARDIANS,
+            Self::RecoverAccount { .. } => tags::instruction::RECOVER_ACCOUNT,
+            Self::Approve { .. } => tags::instruction::APPROVE,
+            Self::TransferVusdtFrom { .. } => tags::instruction::TRANSFER_VUSDT_FROM,
+            Self::CreateVesting { .. } => tags::instruction::CREATE_VESTING,
+            Self::ClaimVested => tags::instruction::CLAIM_VESTED,
+            Self::ExecuteBuyback { .. } => tags::instruction::EXECUTE_BUYBACK,
+            Self::SetGameConfig { .. } => tags::instruction::SET_GAME_CONFIG,
+            Self::GrantBonus { .. } => tags::instruction::GRANT_BONUS,
+            Self::SetTournamentRules { .. } => tags::instruction::SET_TOURNAMENT_RULES,
+            Self::SetEconomyParams { .. } => tags::instruction::SET_ECONOMY_PARAMS,
+            Self::SetAdmin { .. } => tags::instruction::SET_ADMIN,
+            Self::SetHouseSettings { .. } => tags::instruction::SET_HOUSE_SETTINGS,
+            Self::Batch(_) => tags::instruction::BATCH,
+        }
+    }
+
+    /// The catalog entry describing this kind of instruction.
+    pub fn info(&self) -> &'static InstructionInfo {
+        let kind = match self {
+            Self::CasinoRegister { .. } => 0,
+            Self::CasinoDeposit { .. } => 1,
+            Self::CasinoStartGame { .. } => 2,
+            Self::CasinoGameMove { .. } => 3,
+            Self::CasinoToggleShield => 4,
+            Self::CasinoToggleDouble => 5,
+            Self::CasinoToggleSuper => 6,
+            Self::CasinoJoinTournament { .. } => 7,
+            Self::CasinoStartTournament { .. } => 8,
+            Self::CasinoEndTournament { .. } => 9,
+            Self::Stake { .. } => 10,
+            Self::Unstake { .. } => 11,
+            Self::ClaimRewards => 12,
+            Self::ProcessEpoch => 13,
+            Self::CreateVault => 14,
+            Self::DepositCollateral { .. } => 15,
+            Self::BorrowUSDT { .. } => 16,
+            Self::RepayUSDT { .. } => 17,
+            Self::Swap { .. } => 18,
+            Self::AddLiquidity { .. } => 19,
+            Self::RemoveLiquidity { .. } => 20,
+            Self::Batch(_) => 21,
+            Self::WithdrawCollateral { .. } => 22,
+            Self::PlaceLimitOrder { .. } => 23,
+            Self::CancelLimitOrder { .. } => 24,
+            Self::ZapLiquidity { .. } => 25,
+            Self::Slash { .. } => 26,
+            Self::ClaimDailyBonus => 27,
+            Self::UpdateProfile { .. } => 28,
+            Self::CasinoAbandonSession { .. } => 29,
+            Self::SetKycStatus { .. } => 30,
+            Self::CasinoScheduleTournament { .. } => 31,
+            Self::SetGuardians { .. } => 32,
+            Self::RecoverAccount { .. } => 33,
+            Self::Approve { .. } => 34,
+            Self::TransferVusdtFrom { .. } => 35,
+            Self::CreateVesting { .. } => 36,
+            Self::ClaimVested => 37,
+            Self::ExecuteBuyback { .. } => 38,
+            Self::SetGameConfig { .. } => 39,
+            Self::GrantBonus { .. } => 40,
+            Self::SetTournamentRules { .. } => 41,
+            Self::SetEconomyParams { .. } => 42,
+            Self::SetAdmin { .. } => 43,
+            Self::SetHouseSettings { .. } => 44,
+        };
+        &INSTRUCTION_CATALOG[kind]
+    }
+
+    /// Compute units charged for the instruction itself (a batch is charged for each of its
+    /// instructions).
+    ///
+    /// Moves are also charged [COMPUTE_UNITS_PER_BET] for every bet their session holds when
+    /// executed, which depends on state.
+    pub fn compute_units(&self) -> u64 {
+        let bytes = match self {
+            Self::Batch(instructions) => {
+                return instructions
+                    .iter()
+                    .fold(0u64, |total, i| total.saturating_add(i.compute_units()));
+            }
+            Self::CasinoRegister { name } => name.len(),
+            Self::CasinoGameMove { payload, .. } => payload.len(),
+            Self::UpdateProfile {
+                display_name,
+                title,
+                ..
+            } => display_name.len() + title.len(),
+            _ => 0,
+        };
+        COMPUTE_UNITS_PER_INSTRUCTION.saturating_add(COMPUTE_UNITS_PER_BYTE * bytes as u64)
+    }
+}
+
+/// The tag of an AMM instruction on `pair` (`legacy` for the RNG/vUSDT pool).
+fn pair_tag(pair: crate::casino::PairId, legacy: u8, tagged: u8) -> u8 {
+    if pair == crate::casino::PairId::RNG_VUSDT {
+        legacy
+    } else {
+        tagged
+    }
+}
+
+/// Write the tag of an AMM instruction (and its pair, unless it is the RNG/vUSDT pool).
+fn write_pair(writer: &mut impl BufMut, pair: crate::casino::PairId, tag: u8) {
+    tag.write(writer);
+    if pair != crate::casino::PairId::RNG_VUSDT {
+        pair.write(writer);
+    }
+}
+
+/// Read the pair of an AMM instruction (written only after the tags of other pools).
+fn read_pair(reader: &mut impl Buf, tagged: bool) -> Result<crate::casino::PairId, Error> {
+    if !tagged {
+        return Ok(crate::casino::PairId::RNG_VUSDT);
+    }
+    let pair = crate::casino::PairId::read(reader)?;
+    if pair == crate::casino::PairId::RNG_VUSDT {
+        return Err(Error::Invalid("Instruction", "non-canonical AMM pair"));
+    }
+    Ok(pair)
+}
+
+/// Encoded size of the pair of an AMM instruction.
+fn pair_size(pair: crate::casino::PairId) -> usize {
+    if pair == crate::casino::PairId::RNG_VUSDT {
+        0
+    } else {
+        crate::casino::PairId::SIZE
+    }
 }
 
 impl Write for Instruction {
@@ -369,11 +1741,15 @@ impl Write for Instruction {
                 game_type,
                 bet,
                 session_id,
+                commitment,
             } => {
-                tags::instruction::CASINO_START_GAME.write(writer);
+                self.tag().write(writer);
                 game_type.write(writer);
                 bet.write(writer);
                 session_id.write(writer);
+                if let Some(commitment) = commitment {
+                    commitment.write(writer);
+                }
             }
             Self::CasinoGameMove {
                 session_id,
@@ -408,11 +1784,14 @@ impl Write for Instruction {
                 amount.write(writer);
                 duration.write(writer);
             }
-            Self::Unstake => tags::instruction::UNSTAKE.write(writer),
+            Self::Unstake { amount } => {
+                tags::instruction::UNSTAKE.write(writer);
+                amount.write(writer);
+            }
             Self::ClaimRewards => tags::instruction::CLAIM_REWARDS.write(writer),
             Self::ProcessEpoch => tags::instruction::PROCESS_EPOCH.write(writer),
 
-            // Vaults (22-25)
+            // Vaults (22-25, 32)
             Self::CreateVault => tags::instruction::CREATE_VAULT.write(writer),
             Self::DepositCollateral { amount } => {
                 tags::instruction::DEPOSIT_COLLATERAL.write(writer);
@@ -426,44 +1805,201 @@ impl Write for Instruction {
                 tags::instruction::REPAY_USDT.write(writer);
                 amount.write(writer);
             }
+            Self::WithdrawCollateral { amount } => {
+                tags::instruction::WITHDRAW_COLLATERAL.write(writer);
+                amount.write(writer);
+            }
 
             // AMM (26-28)
             Self::Swap {
+                pair,
                 amount_in,
                 min_amount_out,
                 is_buying_rng,
             } => {
-                tags::instruction::SWAP.write(writer);
+                write_pair(writer, *pair, self.tag());
                 amount_in.write(writer);
                 min_amount_out.write(writer);
                 is_buying_rng.write(writer);
             }
             Self::AddLiquidity {
+                pair,
                 rng_amount,
                 usdt_amount,
             } => {
-                tags::instruction::ADD_LIQUIDITY.write(writer);
+                write_pair(writer, *pair, self.tag());
                 rng_amount.write(writer);
                 usdt_amount.write(writer);
             }
-            Self::RemoveLiquidity { shares } => {
-                tags::instruction::REMOVE_LIQUIDITY.write(writer);
+            Self::RemoveLiquidity { pair, shares } => {
+                write_pair(writer, *pair, self.tag());
                 shares.write(writer);
             }
-            Self::CasinoEndTournament { tournament_id } => {
-                tags::instruction::CASINO_END_TOURNAMENT.write(writer);
-                tournament_id.write(writer);
+            Self::PlaceLimitOrder {
+                amount_in,
+                min_price,
+                is_buying_rng,
+            } => {
+                tags::instruction::PLACE_LIMIT_ORDER.write(writer);
+                amount_in.write(writer);
+                min_price.write(writer);
+                is_buying_rng.write(writer);
             }
-        }
-    }
-}
-
-/// Maximum name length for casino player registration
-pub const CASINO_MAX_NAME_LENGTH: usize = crate::casino::MAX_NAME_LENGTH;
-
-/// Maximum payload length for casino game moves
+            Self::CancelLimitOrder { order_id } => {
+                tags::instruction::CANCEL_LIMIT_ORDER.write(writer);
+                order_id.write(writer);
+            }
+            Self::ZapLiquidity {
+                pair,
+                amount_in,
+                min_shares,
+                from_rng,
+            } => {
+                write_pair(writer, *pair, self.tag());
+                amount_in.write(writer);
+                min_shares.write(writer);
+                from_rng.write(writer);
+            }
+            Self::Slash {
+                staker,
+                bps,
+                signature,
+            } => {
+                tags::instruction::SLASH.write(writer);
+                staker.write(writer);
+                bps.write(writer);
+                signature.write(writer);
+            }
+            Self::ClaimDailyBonus => tags::instruction::CLAIM_DAILY_BONUS.write(writer),
+            Self::UpdateProfile {
+                display_name,
+                avatar_id,
+                title,
+            } => {
+                tags::instruction::UPDATE_PROFILE.write(writer);
+                crate::casino::write_string(display_name, writer);
+                avatar_id.write(writer);
+                crate::casino::write_string(title, writer);
+            }
+            Self::CasinoAbandonSession { session_id } => {
+                tags::instruction::CASINO_ABANDON_SESSION.write(writer);
+                session_id.write(writer);
+            }
+            Self::SetKycStatus { player, verified } => {
+                tags::instruction::SET_KYC_STATUS.write(writer);
+                player.write(writer);
+                verified.write(writer);
+            }
+            Self::CasinoScheduleTournament {
+                tournament_id,
+                start_view,
+                end_view,
+            } => {
+                tags::instruction::CASINO_SCHEDULE_TOURNAMENT.write(writer);
+                tournament_id.write(writer);
+                start_view.write(writer);
+                end_view.write(writer);
+            }
+            Self::CasinoEndTournament { tournament_id } => {
+                tags::instruction::CASINO_END_TOURNAMENT.write(writer);
+                tournament_id.write(writer);
+            }
+            Self::SetGuardians {
+                guardians,
+                threshold,
+            } => {
+                tags::instruction::SET_GUARDIANS.write(writer);
+                guardians.write(writer);
+                threshold.write(writer);
+            }
+            Self::RecoverAccount { account, new_key } => {
+                tags::instruction::RECOVER_ACCOUNT.write(writer);
+                account.write(writer);
+                new_key.write(writer);
+            }
+            Self::Approve { spender, amount } => {
+                tags::instruction::APPROVE.write(writer);
+                spender.write(writer);
+                amount.write(writer);
+            }
+            Self::TransferVusdtFrom { owner, to, amount } => {
+                tags::instruction::TRANSFER_VUSDT_FROM.write(writer);
+                owner.write(writer);
+                to.write(writer);
+                amount.write(writer);
+            }
+            Self::CreateVesting {
+                to,
+                amount,
+                start_view,
+                duration,
+            } => {
+                tags::instruction::CREATE_VESTING.write(writer);
+                to.write(writer);
+                amount.write(writer);
+                start_view.write(writer);
+                duration.write(writer);
+            }
+            Self::ClaimVested => tags::instruction::CLAIM_VESTED.write(writer),
+            Self::ExecuteBuyback { amount } => {
+                tags::instruction::EXECUTE_BUYBACK.write(writer);
+                amount.write(writer);
+            }
+            Self::SetGameConfig { game_type, config } => {
+                tags::instruction::SET_GAME_CONFIG.write(writer);
+                game_type.write(writer);
+                config.write(writer);
+            }
+            Self::GrantBonus { player, amount } => {
+                tags::instruction::GRANT_BONUS.write(writer);
+                player.write(writer);
+                amount.write(writer);
+            }
+            Self::SetTournamentRules {
+                tournament_id,
+                rules,
+            } => {
+                tags::instruction::SET_TOURNAMENT_RULES.write(writer);
+                tournament_id.write(writer);
+                rules.write(writer);
+            }
+            Self::SetEconomyParams { params } => {
+                tags::instruction::SET_ECONOMY_PARAMS.write(writer);
+                params.write(writer);
+            }
+            Self::SetAdmin {
+                role,
+                admin,
+                signature,
+            } => {
+                tags::instruction::SET_ADMIN.write(writer);
+                role.write(writer);
+                admin.write(writer);
+                signature.write(writer);
+            }
+            Self::SetHouseSettings { settings } => {
+                tags::instruction::SET_HOUSE_SETTINGS.write(writer);
+                settings.write(writer);
+            }
+
+            // Batches (31)
+            Self::Batch(instructions) => {
+                tags::instruction::BATCH.write(writer);
+                instructions.write(writer);
+            }
+        }
+    }
+}
+
+/// Maximum name length for casino player registration
+pub const CASINO_MAX_NAME_LENGTH: usize = crate::casino::MAX_NAME_LENGTH;
+
+/// Maximum payload length for casino game moves
 pub const CASINO_MAX_PAYLOAD_LENGTH: usize = crate::casino::MAX_PAYLOAD_LENGTH;
 
+/// Maximum number of instructions in a [Instruction::Batch]
+pub const MAX_BATCH_INSTRUCTIONS: usize = 8;
+
 impl Read for Instruction {
     type Cfg = ();
 
@@ -492,6 +2028,13 @@ impl Read for Instruction {
                 game_type: crate::casino::GameType::read(reader)?,
                 bet: u64::read(reader)?,
                 session_id: u64::read(reader)?,
+                commitment: None,
+            },
+            tags::instruction::CASINO_START_GAME_COMMITTED => Self::CasinoStartGame {
+                game_type: crate::casino::GameType::read(reader)?,
+                bet: u64::read(reader)?,
+                session_id: u64::read(reader)?,
+                commitment: Some(Digest::read(reader)?),
             },
             tags::instruction::CASINO_GAME_MOVE => {
                 let session_id = u64::read(reader)?;
@@ -526,11 +2069,13 @@ impl Read for Instruction {
                 amount: u64::read(reader)?,
                 duration: u64::read(reader)?,
             },
-            tags::instruction::UNSTAKE => Self::Unstake,
+            tags::instruction::UNSTAKE => Self::Unstake {
+                amount: u64::read(reader)?,
+            },
             tags::instruction::CLAIM_REWARDS => Self::ClaimRewards,
             tags::instruction::PROCESS_EPOCH => Self::ProcessEpoch,
 
-            // Vaults (22-25)
+            // Vaults (22-25, 32)
             tags::instruction::CREATE_VAULT => Self::CreateVault,
             tags::instruction::DEPOSIT_COLLATERAL => Self::DepositCollateral {
                 amount: u64::read(reader)?,
@@ -541,23 +2086,143 @@ impl Read for Instruction {
             tags::instruction::REPAY_USDT => Self::RepayUSDT {
                 amount: u64::read(reader)?,
             },
+            tags::instruction::WITHDRAW_COLLATERAL => Self::WithdrawCollateral {
+                amount: u64::read(reader)?,
+            },
 
             // AMM (26-28)
-            tags::instruction::SWAP => Self::Swap {
+            tags::instruction::SWAP | tags::instruction::SWAP_PAIR => Self::Swap {
+                pair: read_pair(reader, kind == tags::instruction::SWAP_PAIR)?,
                 amount_in: u64::read(reader)?,
                 min_amount_out: u64::read(reader)?,
                 is_buying_rng: bool::read(reader)?,
             },
-            tags::instruction::ADD_LIQUIDITY => Self::AddLiquidity {
-                rng_amount: u64::read(reader)?,
-                usdt_amount: u64::read(reader)?,
+            tags::instruction::ADD_LIQUIDITY | tags::instruction::ADD_LIQUIDITY_PAIR => {
+                Self::AddLiquidity {
+                    pair: read_pair(reader, kind == tags::instruction::ADD_LIQUIDITY_PAIR)?,
+                    rng_amount: u64::read(reader)?,
+                    usdt_amount: u64::read(reader)?,
+                }
+            }
+            tags::instruction::REMOVE_LIQUIDITY | tags::instruction::REMOVE_LIQUIDITY_PAIR => {
+                Self::RemoveLiquidity {
+                    pair: read_pair(reader, kind == tags::instruction::REMOVE_LIQUIDITY_PAIR)?,
+                    shares: u64::read(reader)?,
+                }
+            }
+            tags::instruction::PLACE_LIMIT_ORDER => Self::PlaceLimitOrder {
+                amount_in: u64::read(reader)?,
+                min_price: u64::read(reader)?,
+                is_buying_rng: bool::read(reader)?,
+            },
+            tags::instruction::CANCEL_LIMIT_ORDER => Self::CancelLimitOrder {
+                order_id: u64::read(reader)?,
+            },
+            tags::instruction::ZAP_LIQUIDITY | tags::instruction::ZAP_LIQUIDITY_PAIR => {
+                Self::ZapLiquidity {
+                    pair: read_pair(reader, kind == tags::instruction::ZAP_LIQUIDITY_PAIR)?,
+                    amount_in: u64::read(reader)?,
+                    min_shares: u64::read(reader)?,
+                    from_rng: bool::read(reader)?,
+                }
+            }
+            tags::instruction::SLASH => Self::Slash {
+                staker: PublicKey::read(reader)?,
+                bps: u16::read(reader)?,
+                signature: Signature::read(reader)?,
+            },
+            tags::instruction::CLAIM_DAILY_BONUS => Self::ClaimDailyBonus,
+            tags::instruction::UPDATE_PROFILE => Self::UpdateProfile {
+                display_name: crate::casino::read_string(reader, CASINO_MAX_NAME_LENGTH)?,
+                avatar_id: u32::read(reader)?,
+                title: crate::casino::read_string(reader, crate::casino::MAX_TITLE_LENGTH)?,
+            },
+            tags::instruction::CASINO_ABANDON_SESSION => Self::CasinoAbandonSession {
+                session_id: u64::read(reader)?,
+            },
+            tags::instruction::SET_KYC_STATUS => Self::SetKycStatus {
+                player: PublicKey::read(reader)?,
+                verified: bool::read(reader)?,
             },
-            tags::instruction::REMOVE_LIQUIDITY => Self::RemoveLiquidity {
-                shares: u64::read(reader)?,
+            tags::instruction::CASINO_SCHEDULE_TOURNAMENT => Self::CasinoScheduleTournament {
+                tournament_id: u64::read(reader)?,
+                start_view: u64::read(reader)?,
+                end_view: u64::read(reader)?,
             },
             tags::instruction::CASINO_END_TOURNAMENT => Self::CasinoEndTournament {
                 tournament_id: u64::read(reader)?,
             },
+            tags::instruction::SET_GUARDIANS => Self::SetGuardians {
+                guardians: Vec::<PublicKey>::read_range(reader, 0..=crate::casino::MAX_GUARDIANS)?,
+                threshold: u8::read(reader)?,
+            },
+            tags::instruction::RECOVER_ACCOUNT => Self::RecoverAccount {
+                account: PublicKey::read(reader)?,
+                new_key: PublicKey::read(reader)?,
+            },
+            tags::instruction::APPROVE => Self::Approve {
+                spender: PublicKey::read(reader)?,
+                amount: u64::read(reader)?,
+            },
+            tags::instruction::TRANSFER_VUSDT_FROM => Self::TransferVusdtFrom {
+                owner: PublicKey::read(reader)?,
+                to: PublicKey::read(reader)?,
+                amount: u64::read(reader)?,
+            },
+            tags::instruction::CREATE_VESTING => Self::CreateVesting {
+                to: PublicKey::read(reader)?,
+                amount: u64::read(reader)?,
+                start_view: u64::read(reader)?,
+                duration: u64::read(reader)?,
+            },
+            tags::instruction::CLAIM_VESTED => Self::ClaimVested,
+            tags::instruction::EXECUTE_BUYBACK => Self::ExecuteBuyback {
+                amount: u64::read(reader)?,
+            },
+            tags::instruction::SET_GAME_CONFIG => Self::SetGameConfig {
+                game_type: crate::casino::GameType::read(reader)?,
+                config: crate::casino::GameConfig::read(reader)?,
+            },
+            tags::instruction::GRANT_BONUS => Self::GrantBonus {
+                player: PublicKey::read(reader)?,
+                amount: u64::read(reader)?,
+            },
+            tags::instruction::SET_TOURNAMENT_RULES => Self::SetTournamentRules {
+                tournament_id: u64::read(reader)?,
+                rules: crate::casino::TournamentRules::read(reader)?,
+            },
+            tags::instruction::SET_ECONOMY_PARAMS => Self::SetEconomyParams {
+                params: crate::casino::EconomyParams::read(reader)?,
+            },
+            tags::instruction::SET_ADMIN => Self::SetAdmin {
+                role: crate::casino::AdminRole::read(reader)?,
+                admin: Option::<PublicKey>::read(reader)?,
+                signature: Signature::read(reader)?,
+            },
+            tags::instruction::SET_HOUSE_SETTINGS => Self::SetHouseSettings {
+                settings: crate::casino::HouseSettings::read(reader)?,
+            },
+
+            // Batches (31)
+            tags::instruction::BATCH => {
+                let len = usize::read_cfg(reader, &RangeCfg::from(1..=MAX_BATCH_INSTRUCTIONS))?;
+                let mut instructions = Vec::with_capacity(len);
+                for _ in 0..len {
+                    // Reject nested batches before decoding them (bounding recursion)
+                    if reader.has_remaining() && reader.chunk()[0] == tags::instruction::BATCH {
+                        return Err(Error::Invalid("Instruction", "nested batch"));
+                    }
+                    instructions.push(Self::read(reader)?);
+                }
+                let starts = instructions
+                    .iter()
+                    .filter(|i| matches!(i, Self::CasinoStartGame { .. }))
+                    .count();
+                if starts > 1 {
+                    return Err(Error::Invalid("Instruction", "batch starts multiple games"));
+                }
+                Self::Batch(instructions)
+            }
 
             i => return Err(Error::InvalidEnum(i)),
         };
@@ -573,7 +2238,9 @@ impl EncodeSize for Instruction {
                 // Casino
                 Self::CasinoRegister { name } => 4 + name.len(),
                 Self::CasinoDeposit { .. } => 8,
-                Self::CasinoStartGame { .. } => 1 + 8 + 8,
+                Self::CasinoStartGame { commitment, .. } => {
+                    1 + 8 + 8 + commitment.as_ref().map_or(0, |_| Digest::SIZE)
+                }
                 Self::CasinoGameMove { payload, .. } => 8 + 4 + payload.len(),
                 Self::CasinoToggleShield | Self::CasinoToggleDouble | Self::CasinoToggleSuper => 0,
                 Self::CasinoJoinTournament { .. } => 8,
@@ -581,30 +2248,94 @@ impl EncodeSize for Instruction {
 
                 // Staking
                 Self::Stake { amount, duration } => amount.encode_size() + duration.encode_size(),
-                Self::Unstake | Self::ClaimRewards | Self::ProcessEpoch => 0,
+                Self::Unstake { amount } => amount.encode_size(),
+                Self::ClaimRewards | Self::ProcessEpoch => 0,
 
                 // Vaults
                 Self::CreateVault => 0,
                 Self::DepositCollateral { amount }
                 | Self::BorrowUSDT { amount }
-                | Self::RepayUSDT { amount } => amount.encode_size(),
+                | Self::RepayUSDT { amount }
+                | Self::WithdrawCollateral { amount } => amount.encode_size(),
 
                 // AMM
                 Self::Swap {
+                    pair,
                     amount_in,
                     min_amount_out,
                     is_buying_rng,
                 } => {
-                    amount_in.encode_size()
+                    pair_size(*pair)
+                        + amount_in.encode_size()
                         + min_amount_out.encode_size()
                         + is_buying_rng.encode_size()
                 }
                 Self::AddLiquidity {
+                    pair,
                     rng_amount,
                     usdt_amount,
-                } => rng_amount.encode_size() + usdt_amount.encode_size(),
-                Self::RemoveLiquidity { shares } => shares.encode_size(),
+                } => pair_size(*pair) + rng_amount.encode_size() + usdt_amount.encode_size(),
+                Self::RemoveLiquidity { pair, shares } => pair_size(*pair) + shares.encode_size(),
+                Self::PlaceLimitOrder {
+                    amount_in,
+                    min_price,
+                    is_buying_rng,
+                } => {
+                    amount_in.encode_size() + min_price.encode_size() + is_buying_rng.encode_size()
+                }
+                Self::CancelLimitOrder { order_id } => order_id.encode_size(),
+                Self::ZapLiquidity {
+                    pair,
+                    amount_in,
+                    min_shares,
+                    from_rng,
+                } => {
+                    pair_size(*pair)
+                        + amount_in.encode_size()
+                        + min_shares.encode_size()
+                        + from_rng.encode_size()
+                }
+                Self::Slash { .. } => PublicKey::SIZE + u16::SIZE + Signature::SIZE,
+                Self::ClaimDailyBonus => 0,
+                Self::UpdateProfile {
+                    display_name,
+                    avatar_id,
+                    title,
+                } => {
+                    crate::casino::string_encode_size(display_name)
+                        + avatar_id.encode_size()
+                        + crate::casino::string_encode_size(title)
+                }
+                Self::CasinoAbandonSession { session_id } => session_id.encode_size(),
+                Self::SetKycStatus { .. } => PublicKey::SIZE + bool::SIZE,
+                Self::CasinoScheduleTournament { .. } => 8 + 8 + 8,
                 Self::CasinoEndTournament { tournament_id } => tournament_id.encode_size(),
+                Self::SetGuardians {
+                    guardians,
+                    threshold,
+                } => guardians.encode_size() + threshold.encode_size(),
+                Self::RecoverAccount { .. } => PublicKey::SIZE + PublicKey::SIZE,
+                Self::Approve { .. } => PublicKey::SIZE + u64::SIZE,
+                Self::TransferVusdtFrom { .. } => PublicKey::SIZE + PublicKey::SIZE + u64::SIZE,
+                Self::CreateVesting { .. } => PublicKey::SIZE + 8 + 8 + 8,
+                Self::ClaimVested => 0,
+                Self::ExecuteBuyback { .. } => u64::SIZE,
+                Self::SetGameConfig { .. } => {
+                    crate::casino::GameType::SIZE + crate::casino::GameConfig::SIZE
+                }
+                Self::GrantBonus { .. } => PublicKey::SIZE + u64::SIZE,
+                Self::SetTournamentRules {
+                    tournament_id,
+                    rules,
+                } => tournament_id.encode_size() + rules.encode_size(),
+                Self::SetEconomyParams { .. } => crate::casino::EconomyParams::SIZE,
+                Self::SetAdmin { admin, .. } => {
+                    crate::casino::AdminRole::SIZE + admin.encode_size() + Signature::SIZE
+                }
+                Self::SetHouseSettings { settings } => settings.encode_size(),
+
+                // Batches
+                Self::Batch(instructions) => instructions.encode_size(),
             }
     }
 }
@@ -834,6 +2565,7 @@ pub fn leader_index(seed: &[u8], participants: usize) -> usize {
 /// Minimal account structure for transaction nonce tracking.
 /// Used for replay protection across all transaction types.
 #[derive(Clone, Default, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Account {
     pub nonce: u64,
 }
@@ -860,7 +2592,22 @@ impl EncodeSize for Account {
     }
 }
 
-#[derive(Hash, Eq, PartialEq, Ord, PartialOrd, Clone)]
+/// Domains (key tags) whose keys are indexed, so they can be scanned by prefix.
+pub const INDEXED_KEY_TAGS: &[u8] = &[
+    tags::key::CASINO_PLAYER,
+    tags::key::CASINO_SESSION,
+    tags::key::TOURNAMENT,
+    tags::key::VAULT,
+    tags::key::LIMIT_ORDER,
+    tags::key::VESTING,
+];
+
+/// Maximum number of keys listed by a key index bucket (about four million keys per domain,
+/// as keys are spread over the buckets by their last byte). Writing a key to a full bucket
+/// is fatal, as scans of its domain would silently miss it.
+pub const MAX_KEY_INDEX_BUCKET: usize = 16_384;
+
+#[derive(Hash, Eq, PartialEq, Ord, PartialOrd, Clone, Debug)]
 pub enum Key {
     /// Account for nonce tracking (tag 0)
     Account(PublicKey),
@@ -875,12 +2622,66 @@ pub enum Key {
     House,
     Staker(PublicKey),
 
-    // Virtual Liquidity keys (tags 16-17)
+    // Virtual Liquidity keys (tags 16-17, 20-21)
     Vault(PublicKey),
-    AmmPool,
+    /// The pool for a pair (the RNG/vUSDT pool keeps its original encoding, tag 17 without
+    /// a pair, so existing state remains addressable).
+    AmmPool(crate::casino::PairId),
+    AmmPools,
 
-    // LP Balance (Tag 18)
-    LpBalance(PublicKey),
+    // LP Balance (Tags 18, 35)
+    /// A player's shares in the pool for a pair (the RNG/vUSDT pool keeps its original
+    /// encoding, tag 18 without a pair).
+    LpBalance(crate::casino::PairId, PublicKey),
+
+    // Price oracle (Tag 19)
+    Oracle,
+
+    // Limit orders (tags 22-23)
+    LimitOrder(u64),
+    LimitOrderBook,
+
+    // Game history (tag 24)
+    GameHistory(PublicKey),
+
+    // Scheduled tournaments (tag 25)
+    TournamentSchedules,
+
+    // Archived leaderboard seasons (tag 26)
+    LeaderboardSeason(u64),
+
+    // Account recovery guardians (tag 27)
+    Guardians(PublicKey),
+
+    // vUSDT allowances, by owner and spender (tag 28)
+    Allowance(PublicKey, PublicKey),
+
+    // Vesting schedules, by beneficiary (tag 29)
+    Vesting(PublicKey),
+
+    // Treasury (tag 30)
+    Treasury,
+
+    // Game rules, by game (tag 31)
+    GameConfig(crate::casino::GameType),
+
+    // Game statistics, by game (tag 32)
+    GameStats(crate::casino::GameType),
+
+    /// The keys of an indexed domain, bucketed by the last byte of their encoding (tag 33).
+    KeyIndex {
+        tag: u8,
+        bucket: u8,
+    },
+
+    // Economy parameters (tag 34)
+    EconomyParams,
+
+    // Sessions completed at a view, awaiting reclamation (tag 36)
+    SessionExpiries(u64),
+
+    // Next view whose completed sessions are reclaimed (tag 37)
+    SessionReclaimCursor,
 }
 
 impl Write for Key {
@@ -919,11 +2720,99 @@ impl Write for Key {
                 tags::key::VAULT.write(writer);
                 pk.write(writer);
             }
-            Self::AmmPool => tags::key::AMM_POOL.write(writer),
-            Self::LpBalance(pk) => {
+            Self::AmmPool(pair) if *pair == crate::casino::PairId::RNG_VUSDT => {
+                tags::key::AMM_POOL.write(writer)
+            }
+            Self::AmmPool(pair) => {
+                tags::key::AMM_PAIR_POOL.write(writer);
+                pair.write(writer);
+            }
+            Self::AmmPools => tags::key::AMM_POOLS.write(writer),
+            Self::LpBalance(pair, pk) if *pair == crate::casino::PairId::RNG_VUSDT => {
                 tags::key::LP_BALANCE.write(writer);
                 pk.write(writer);
             }
+            Self::LpBalance(pair, pk) => {
+                tags::key::LP_PAIR_BALANCE.write(writer);
+                pair.write(writer);
+                pk.write(writer);
+            }
+
+            // Price oracle
+            Self::Oracle => tags::key::ORACLE.write(writer),
+
+            // Limit orders
+            Self::LimitOrder(id) => {
+                tags::key::LIMIT_ORDER.write(writer);
+                id.write(writer);
+            }
+            Self::LimitOrderBook => tags::key::LIMIT_ORDER_BOOK.write(writer),
+
+            // Game history
+            Self::GameHistory(pk) => {
+                tags::key::GAME_HISTORY.write(writer);
+                pk.write(writer);
+            }
+
+            // Scheduled tournaments
+            Self::TournamentSchedules => tags::key::TOURNAMENT_SCHEDULES.write(writer),
+
+            // Archived leaderboard seasons
+            Self::LeaderboardSeason(season) => {
+                tags::key::LEADERBOARD_SEASON.write(writer);
+                season.write(writer);
+            }
+
+            // Account recovery guardians
+            Self::Guardians(pk) => {
+                tags::key::GUARDIANS.write(writer);
+                pk.write(writer);
+            }
+
+            // vUSDT allowances
+            Self::Allowance(owner, spender) => {
+                tags::key::ALLOWANCE.write(writer);
+                owner.write(writer);
+                spender.write(writer);
+            }
+
+            // Vesting schedules
+            Self::Vesting(pk) => {
+                tags::key::VESTING.write(writer);
+                pk.write(writer);
+            }
+
+            // Treasury
+            Self::Treasury => tags::key::TREASURY.write(writer),
+
+            // Game rules
+            Self::GameConfig(game_type) => {
+                tags::key::GAME_CONFIG.write(writer);
+                game_type.write(writer);
+            }
+
+            // Game statistics
+            Self::GameStats(game_type) => {
+                tags::key::GAME_STATS.write(writer);
+                game_type.write(writer);
+            }
+
+            // Key index buckets
+            Self::KeyIndex { tag, bucket } => {
+                tags::key::KEY_INDEX.write(writer);
+                tag.write(writer);
+                bucket.write(writer);
+            }
+
+            // Economy parameters
+            Self::EconomyParams => tags::key::ECONOMY_PARAMS.write(writer),
+
+            // Session reclamation
+            Self::SessionExpiries(view) => {
+                tags::key::SESSION_EXPIRIES.write(writer);
+                view.write(writer);
+            }
+            Self::SessionReclaimCursor => tags::key::SESSION_RECLAIM_CURSOR.write(writer),
         }
     }
 }
@@ -949,8 +2838,74 @@ impl Read for Key {
 
             // Virtual Liquidity
             tags::key::VAULT => Self::Vault(PublicKey::read(reader)?),
-            tags::key::AMM_POOL => Self::AmmPool,
-            tags::key::LP_BALANCE => Self::LpBalance(PublicKey::read(reader)?),
+            tags::key::AMM_POOL => Self::AmmPool(crate::casino::PairId::RNG_VUSDT),
+            tags::key::AMM_PAIR_POOL => {
+                let pair = crate::casino::PairId::read(reader)?;
+                if pair == crate::casino::PairId::RNG_VUSDT {
+                    return Err(Error::Invalid("Key", "non-canonical AMM pool pair"));
+                }
+                Self::AmmPool(pair)
+            }
+            tags::key::AMM_POOLS => Self::AmmPools,
+            tags::key::LP_BALANCE => {
+                Self::LpBalance(crate::casino::PairId::RNG_VUSDT, PublicKey::read(reader)?)
+            }
+            tags::key::LP_PAIR_BALANCE => {
+                let pair = crate::casino::PairId::read(reader)?;
+                if pair == crate::casino::PairId::RNG_VUSDT {
+                    return Err(Error::Invalid("Key", "non-canonical LP balance pair"));
+                }
+                Self::LpBalance(pair, PublicKey::read(reader)?)
+            }
+
+            // Price oracle
+            tags::key::ORACLE => Self::Oracle,
+
+            // Limit orders
+            tags::key::LIMIT_ORDER => Self::LimitOrder(u64::read(reader)?),
+            tags::key::LIMIT_ORDER_BOOK => Self::LimitOrderBook,
+
+            // Game history
+            tags::key::GAME_HISTORY => Self::GameHistory(PublicKey::read(reader)?),
+
+            // Scheduled tournaments
+            tags::key::TOURNAMENT_SCHEDULES => Self::TournamentSchedules,
+
+            // Archived leaderboard seasons
+            tags::key::LEADERBOARD_SEASON => Self::LeaderboardSeason(u64::read(reader)?),
+
+            // Account recovery guardians
+            tags::key::GUARDIANS => Self::Guardians(PublicKey::read(reader)?),
+
+            // vUSDT allowances
+            tags::key::ALLOWANCE => {
+                Self::Allowance(PublicKey::read(reader)?, PublicKey::read(reader)?)
+            }
+
+            // Vesting schedules
+            tags::key::VESTING => Self::Vesting(PublicKey::read(reader)?),
+
+            // Treasury
+            tags::key::TREASURY => Self::Treasury,
+
+            // Game rules
+            tags::key::GAME_CONFIG => Self::GameConfig(crate::casino::GameType::read(reader)?),
+
+            // Game statistics
+            tags::key::GAME_STATS => Self::GameStats(crate::casino::GameType::read(reader)?),
+
+            // Key index buckets
+            tags::key::KEY_INDEX => Self::KeyIndex {
+                tag: u8::read(reader)?,
+                bucket: u8::read(reader)?,
+            },
+
+            // Economy parameters
+            tags::key::ECONOMY_PARAMS => Self::EconomyParams,
+
+            // Session reclamation
+            tags::key::SESSION_EXPIRIES => Self::SessionExpiries(u64::read(reader)?),
+            tags::key::SESSION_RECLAIM_CURSOR => Self::SessionReclaimCursor,
 
             i => return Err(Error::InvalidEnum(i)),
         };
@@ -978,12 +2933,88 @@ impl EncodeSize for Key {
 
                 // Virtual Liquidity
                 Self::Vault(_) => PublicKey::SIZE,
-                Self::AmmPool => 0,
-                Self::LpBalance(_) => PublicKey::SIZE,
+                Self::AmmPool(pair) if *pair == crate::casino::PairId::RNG_VUSDT => 0,
+                Self::AmmPool(_) => crate::casino::PairId::SIZE,
+                Self::AmmPools => 0,
+                Self::LpBalance(pair, _) if *pair == crate::casino::PairId::RNG_VUSDT => {
+                    PublicKey::SIZE
+                }
+                Self::LpBalance(_, _) => crate::casino::PairId::SIZE + PublicKey::SIZE,
+
+                // Price oracle
+                Self::Oracle => 0,
+
+                // Limit orders
+                Self::LimitOrder(_) => u64::SIZE,
+                Self::LimitOrderBook => 0,
+
+                // Game history
+                Self::GameHistory(_) => PublicKey::SIZE,
+
+                // Scheduled tournaments
+                Self::TournamentSchedules => 0,
+
+                // Archived leaderboard seasons
+                Self::LeaderboardSeason(_) => u64::SIZE,
+
+                // Account recovery guardians
+                Self::Guardians(_) => PublicKey::SIZE,
+
+                // vUSDT allowances
+                Self::Allowance(_, _) => PublicKey::SIZE + PublicKey::SIZE,
+
+                // Vesting schedules
+                Self::Vesting(_) => PublicKey::SIZE,
+
+                // Treasury
+                Self::Treasury => 0,
+
+                // Game rules
+                Self::GameConfig(_) => crate::casino::GameType::SIZE,
+
+                // Game statistics
+                Self::GameStats(_) => crate::casino::GameType::SIZE,
+
+                // Key index buckets
+                Self::KeyIndex { .. } => u8::SIZE + u8::SIZE,
+
+                // Economy parameters
+                Self::EconomyParams => 0,
+
+                // Session reclamation
+                Self::SessionExpiries(_) => u64::SIZE,
+                Self::SessionReclaimCursor => 0,
             }
     }
 }
 
+impl Key {
+    /// Whether keys starting with `prefix` can be scanned (the prefix starts with the tag of
+    /// an indexed domain, see [INDEXED_KEY_TAGS]).
+    pub fn scannable(prefix: &[u8]) -> bool {
+        prefix
+            .first()
+            .is_some_and(|tag| INDEXED_KEY_TAGS.contains(tag))
+    }
+
+    /// The key index bucket listing this key, if its domain is indexed.
+    pub fn index(&self) -> Option<Key> {
+        let encoded = self.encode();
+        Self::scannable(&encoded).then(|| Key::KeyIndex {
+            tag: encoded[0],
+            bucket: encoded[encoded.len() - 1],
+        })
+    }
+
+    /// The key index buckets listing the keys that start with `prefix` (none if the prefix
+    /// can't be scanned).
+    pub fn index_buckets(prefix: &[u8]) -> impl Iterator<Item = Key> + '_ {
+        let tag = prefix.first().copied().filter(|_| Self::scannable(prefix));
+        tag.into_iter()
+            .flat_map(|tag| (0..=u8::MAX).map(move |bucket| Key::KeyIndex { tag, bucket }))
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Debug)]
 #[allow(clippy::large_enum_variant)]
 pub enum Value {
@@ -1011,12 +3042,93 @@ pub enum Value {
     AmmPool(crate::casino::AmmPool),
 
     // LP Balance (Tag 18)
-    LpBalance(u64),
+    LpBalance(crate::casino::LpShares),
+
+    // Price oracle (Tag 19)
+    Oracle(crate::casino::PriceOracle),
+
+    // AMM pool registry (Tag 20)
+    AmmPools(crate::casino::AmmPools),
+
+    // Limit orders (Tags 21-22)
+    LimitOrder(crate::casino::LimitOrder),
+    LimitOrderBook(crate::casino::LimitOrderBook),
+
+    // Game history (Tag 23)
+    GameHistory(crate::casino::GameHistory),
+
+    // Scheduled tournaments (Tag 24)
+    TournamentSchedules(crate::casino::TournamentSchedules),
+
+    // Account recovery guardians (Tag 25)
+    Guardians(crate::casino::GuardianSet),
+
+    // vUSDT allowance (Tag 26)
+    Allowance(u64),
+
+    // Vesting schedules (Tag 27)
+    Vesting(crate::casino::Vesting),
+
+    // Treasury (Tag 28)
+    Treasury(crate::casino::Treasury),
+
+    // Game rules (Tag 29)
+    GameConfig(crate::casino::GameConfig),
+
+    // Game statistics (Tag 30)
+    GameStats(crate::casino::GameStats),
+
+    /// The keys listed by a key index bucket, in order of their encoding (Tag 31).
+    KeyIndex(Vec<Key>),
+
+    // Economy parameters (Tag 32)
+    EconomyParams(crate::casino::EconomyParams),
+
+    // Sessions completed at a view, in order of completion (Tag 33)
+    SessionExpiries(Vec<u64>),
+
+    // Next view whose completed sessions are reclaimed (Tag 34)
+    SessionReclaimCursor(u64),
+
+    /// A value in the encoding of `version` (values are written in it once it activates, see
+    /// [protocol_version]). Decoded values keep it, so they re-encode byte for byte (and the
+    /// proofs they are part of still verify).
+    Versioned {
+        version: u8,
+        value: Box<Value>,
+    },
+}
+
+impl Value {
+    /// The value in the encoding of `version` (the legacy encoding for version 0).
+    pub fn versioned(self, version: u8) -> Self {
+        match (self.into_inner(), version) {
+            (value, 0) => value,
+            (value, version) => Self::Versioned {
+                version,
+                value: Box::new(value),
+            },
+        }
+    }
+
+    /// The value without its encoding version.
+    pub fn into_inner(self) -> Self {
+        match self {
+            Self::Versioned { value, .. } => *value,
+            value => value,
+        }
+    }
 }
 
 impl Write for Value {
     fn write(&self, writer: &mut impl BufMut) {
         match self {
+            // Versioned values (the version, then the value's legacy encoding)
+            Self::Versioned { version, value } => {
+                (VALUE_VERSION_FLAG | version).write(writer);
+                value.write(writer);
+            }
+
             // Account value (tag 0)
             Self::Account(account) => {
                 tags::value::ACCOUNT.write(writer);
@@ -1071,15 +3183,126 @@ impl Write for Value {
                 tags::value::LP_BALANCE.write(writer);
                 bal.write(writer);
             }
-        }
-    }
-}
 
-impl Read for Value {
+            // Price oracle
+            Self::Oracle(oracle) => {
+                tags::value::ORACLE.write(writer);
+                oracle.write(writer);
+            }
+
+            // AMM pool registry
+            Self::AmmPools(pools) => {
+                tags::value::AMM_POOLS.write(writer);
+                pools.write(writer);
+            }
+
+            // Limit orders
+            Self::LimitOrder(order) => {
+                tags::value::LIMIT_ORDER.write(writer);
+                order.write(writer);
+            }
+            Self::LimitOrderBook(book) => {
+                tags::value::LIMIT_ORDER_BOOK.write(writer);
+                book.write(writer);
+            }
+
+            // Game history
+            Self::GameHistory(history) => {
+                tags::value::GAME_HISTORY.write(writer);
+                history.write(writer);
+            }
+
+            // Scheduled tournaments
+            Self::TournamentSchedules(schedules) => {
+                tags::value::TOURNAMENT_SCHEDULES.write(writer);
+                schedules.write(writer);
+            }
+
+            // Account recovery guardians
+            Self::Guardians(guardians) => {
+                tags::value::GUARDIANS.write(writer);
+                guardians.write(writer);
+            }
+
+            // vUSDT allowances
+            Self::Allowance(amount) => {
+                tags::value::ALLOWANCE.write(writer);
+                amount.write(writer);
+            }
+
+            // Vesting schedules
+            Self::Vesting(vesting) => {
+                tags::value::VESTING.write(writer);
+                vesting.write(writer);
+            }
+
+            // Treasury
+            Self::Treasury(treasury) => {
+                tags::value::TREASURY.write(writer);
+                treasury.write(writer);
+            }
+
+            // Game rules
+            Self::GameConfig(config) => {
+                tags::value::GAME_CONFIG.write(writer);
+                config.write(writer);
+            }
+
+            // Game statistics
+            Self::GameStats(stats) => {
+                tags::value::GAME_STATS.write(writer);
+                stats.write(writer);
+            }
+
+            // Key index buckets
+            Self::KeyIndex(keys) => {
+                tags::value::KEY_INDEX.write(writer);
+                keys.write(writer);
+            }
+
+            // Economy parameters
+            Self::EconomyParams(params) => {
+                tags::value::ECONOMY_PARAMS.write(writer);
+                params.write(writer);
+            }
+
+            // Session reclamation
+            Self::SessionExpiries(ids) => {
+                tags::value::SESSION_EXPIRIES.write(writer);
+                ids.write(writer);
+            }
+            Self::SessionReclaimCursor(view) => {
+                tags::value::SESSION_RECLAIM_CURSOR.write(writer);
+                view.write(writer);
+            }
+        }
+    }
+}
+
+impl Read for Value {
     type Cfg = ();
 
     fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        // Legacy values start with their tag
+        let kind = u8::read(reader)?;
+        if kind & VALUE_VERSION_FLAG == 0 {
+            return Self::read_tagged(kind, reader);
+        }
+        let version = kind & !VALUE_VERSION_FLAG;
+        if version == 0 || version > PROTOCOL_VERSION {
+            return Err(Error::Invalid("Value", "unsupported version"));
+        }
         let kind = u8::read(reader)?;
+        Ok(Self::Versioned {
+            version,
+            value: Box::new(Self::read_tagged(kind, reader)?),
+        })
+    }
+}
+
+impl Value {
+    /// Read the value following its (legacy) `kind` tag.
+    fn read_tagged(kind: u8, reader: &mut impl Buf) -> Result<Self, Error> {
         let value = match kind {
             // Account value (tag 0)
             tags::value::ACCOUNT => Self::Account(Account::read(reader)?),
@@ -1107,7 +3330,64 @@ impl Read for Value {
             // Virtual Liquidity
             tags::value::VAULT => Self::Vault(crate::casino::Vault::read(reader)?),
             tags::value::AMM_POOL => Self::AmmPool(crate::casino::AmmPool::read(reader)?),
-            tags::value::LP_BALANCE => Self::LpBalance(u64::read(reader)?),
+            tags::value::LP_BALANCE => Self::LpBalance(crate::casino::LpShares::read(reader)?),
+
+            // Price oracle
+            tags::value::ORACLE => Self::Oracle(crate::casino::PriceOracle::read(reader)?),
+
+            // AMM pool registry
+            tags::value::AMM_POOLS => Self::AmmPools(crate::casino::AmmPools::read(reader)?),
+
+            // Limit orders
+            tags::value::LIMIT_ORDER => Self::LimitOrder(crate::casino::LimitOrder::read(reader)?),
+            tags::value::LIMIT_ORDER_BOOK => {
+                Self::LimitOrderBook(crate::casino::LimitOrderBook::read(reader)?)
+            }
+
+            // Game history
+            tags::value::GAME_HISTORY => {
+                Self::GameHistory(crate::casino::GameHistory::read(reader)?)
+            }
+
+            // Scheduled tournaments
+            tags::value::TOURNAMENT_SCHEDULES => {
+                Self::TournamentSchedules(crate::casino::TournamentSchedules::read(reader)?)
+            }
+
+            // Account recovery guardians
+            tags::value::GUARDIANS => Self::Guardians(crate::casino::GuardianSet::read(reader)?),
+
+            // vUSDT allowances
+            tags::value::ALLOWANCE => Self::Allowance(u64::read(reader)?),
+
+            // Vesting schedules
+            tags::value::VESTING => Self::Vesting(crate::casino::Vesting::read(reader)?),
+
+            // Treasury
+            tags::value::TREASURY => Self::Treasury(crate::casino::Treasury::read(reader)?),
+
+            // Game rules
+            tags::value::GAME_CONFIG => Self::GameConfig(crate::casino::GameConfig::read(reader)?),
+
+            // Game statistics
+            tags::value::GAME_STATS => Self::GameStats(crate::casino::GameStats::read(reader)?),
+
+            // Key index buckets
+            tags::value::KEY_INDEX => {
+                Self::KeyIndex(Vec::<Key>::read_range(reader, 0..=MAX_KEY_INDEX_BUCKET)?)
+            }
+
+            // Economy parameters
+            tags::value::ECONOMY_PARAMS => {
+                Self::EconomyParams(crate::casino::EconomyParams::read(reader)?)
+            }
+
+            // Session reclamation
+            tags::value::SESSION_EXPIRIES => Self::SessionExpiries(Vec::<u64>::read_range(
+                reader,
+                0..=crate::casino::MAX_SESSION_EXPIRIES,
+            )?),
+            tags::value::SESSION_RECLAIM_CURSOR => Self::SessionReclaimCursor(u64::read(reader)?),
 
             i => return Err(Error::InvalidEnum(i)),
         };
@@ -1118,8 +3398,12 @@ impl Read for Value {
 
 impl EncodeSize for Value {
     fn encode_size(&self) -> usize {
+        // Version (of versioned values) or tag
         u8::SIZE
             + match self {
+                // Versioned values
+                Self::Versioned { value, .. } => value.encode_size(),
+
                 // Account value
                 Self::Account(account) => account.encode_size(),
 
@@ -1140,32 +3424,82 @@ impl EncodeSize for Value {
                 Self::Vault(vault) => vault.encode_size(),
                 Self::AmmPool(pool) => pool.encode_size(),
                 Self::LpBalance(bal) => bal.encode_size(),
+
+                // Price oracle
+                Self::Oracle(oracle) => oracle.encode_size(),
+
+                // AMM pool registry
+                Self::AmmPools(pools) => pools.encode_size(),
+
+                // Limit orders
+                Self::LimitOrder(_) => crate::casino::LimitOrder::SIZE,
+                Self::LimitOrderBook(book) => book.encode_size(),
+
+                // Game history
+                Self::GameHistory(history) => history.encode_size(),
+
+                // Scheduled tournaments
+                Self::TournamentSchedules(schedules) => schedules.encode_size(),
+
+                // Account recovery guardians
+                Self::Guardians(guardians) => guardians.encode_size(),
+
+                // vUSDT allowances
+                Self::Allowance(amount) => amount.encode_size(),
+
+                // Vesting schedules
+                Self::Vesting(vesting) => vesting.encode_size(),
+
+                // Treasury
+                Self::Treasury(treasury) => treasury.encode_size(),
+
+                // Game rules
+                Self::GameConfig(config) => config.encode_size(),
+
+                // Game statistics
+                Self::GameStats(_) => crate::casino::GameStats::SIZE,
+
+                // Key index buckets
+                Self::KeyIndex(keys) => keys.encode_size(),
+
+                // Economy parameters
+                Self::EconomyParams(_) => crate::casino::EconomyParams::SIZE,
+
+                // Session reclamation
+                Self::SessionExpiries(ids) => ids.encode_size(),
+                Self::SessionReclaimCursor(view) => view.encode_size(),
             }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(clippy::large_enum_variant)]
 pub enum Event {
     // Casino events (tags 20-24)
     CasinoPlayerRegistered {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
         player: PublicKey,
         name: String,
     },
     CasinoGameStarted {
         session_id: u64,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
         player: PublicKey,
         game_type: crate::casino::GameType,
         bet: u64,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::bytes"))]
         initial_state: Vec<u8>,
     },
     CasinoGameMoved {
         session_id: u64,
         move_number: u32,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::bytes"))]
         new_state: Vec<u8>,
     },
     CasinoGameCompleted {
         session_id: u64,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
         player: PublicKey,
         game_type: crate::casino::GameType,
         payout: i64,
@@ -1179,9 +3513,11 @@ pub enum Event {
 
     // Error event (tag 29)
     CasinoError {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
         player: PublicKey,
         session_id: Option<u64>,
-        error_code: u8,
+        error_code: crate::casino::CasinoErrorCode,
+        /// Supplemental detail for logs (clients should branch on `error_code`).
         message: String,
     },
 
@@ -1192,6 +3528,7 @@ pub enum Event {
     },
     PlayerJoined {
         tournament_id: u64,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
         player: PublicKey,
     },
     TournamentPhaseChanged {
@@ -1200,29 +3537,41 @@ pub enum Event {
     },
     TournamentEnded {
         id: u64,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::pairs"))]
         rankings: Vec<(PublicKey, u64)>,
     },
 
-    // Vault & AMM events (tags 30-36)
+    // Vault & AMM events (tags 30-36, 43)
     VaultCreated {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
         player: PublicKey,
     },
     CollateralDeposited {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        amount: u64,
+        new_collateral: u64,
+    },
+    CollateralWithdrawn {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
         player: PublicKey,
         amount: u64,
         new_collateral: u64,
     },
     VusdtBorrowed {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
         player: PublicKey,
         amount: u64,
         new_debt: u64,
     },
     VusdtRepaid {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
         player: PublicKey,
         amount: u64,
         new_debt: u64,
     },
     AmmSwapped {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
         player: PublicKey,
         is_buying_rng: bool,
         amount_in: u64,
@@ -1233,6 +3582,7 @@ pub enum Event {
         reserve_vusdt: u64,
     },
     LiquidityAdded {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
         player: PublicKey,
         rng_amount: u64,
         vusdt_amount: u64,
@@ -1243,6 +3593,7 @@ pub enum Event {
         lp_balance: u64,
     },
     LiquidityRemoved {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
         player: PublicKey,
         rng_amount: u64,
         vusdt_amount: u64,
@@ -1253,16 +3604,358 @@ pub enum Event {
         lp_balance: u64,
     },
 
+    // Limit order events (tags 44-46)
+    LimitOrderPlaced {
+        order_id: u64,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        amount_in: u64,
+        min_price: u64,
+        is_buying_rng: bool,
+    },
+    LimitOrderFilled {
+        order_id: u64,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        is_buying_rng: bool,
+        amount_in: u64,
+        amount_out: u64,
+        fee_amount: u64,
+        burned_amount: u64,
+        reserve_rng: u64,
+        reserve_vusdt: u64,
+    },
+    LimitOrderCancelled {
+        order_id: u64,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        refunded: u64,
+    },
+
+    // Single-sided liquidity events (tag 47)
+    LiquidityZapped {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        from_rng: bool,
+        amount_in: u64,
+        swap_amount_in: u64,
+        swap_amount_out: u64,
+        fee_amount: u64,
+        burned_amount: u64,
+        rng_amount: u64,
+        vusdt_amount: u64,
+        shares_minted: u64,
+        total_shares: u64,
+        reserve_rng: u64,
+        reserve_vusdt: u64,
+        lp_balance: u64,
+    },
+
+    // Slashing events (tag 48)
+    Slashed {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        bps: u16,
+        amount: u64,
+        new_balance: u64,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_decimal"))]
+        voting_power: u128,
+    },
+
+    // Daily bonus events (tag 49)
+    DailyBonusClaimed {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        amount: u64,
+        streak: u32,
+        new_chips: u64,
+        next_claim: u64,
+    },
+
+    // Profile events (tag 50)
+    ProfileUpdated {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        display_name: String,
+        avatar_id: u32,
+        title: String,
+    },
+
+    // Session expiry events (tag 51)
+    CasinoSessionExpired {
+        session_id: u64,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        game_type: crate::casino::GameType,
+        refund: u64,
+        final_chips: u64,
+    },
+
+    // KYC events (tag 52)
+    KycStatusChanged {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        verified: bool,
+    },
+
+    // Scheduled tournament events (tag 53)
+    TournamentScheduled {
+        id: u64,
+        start_view: u64,
+        end_view: u64,
+    },
+
+    // Tournament payouts (tag 54)
+    TournamentPrizePaid {
+        tournament_id: u64,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        rank: u32,
+        amount: u64,
+    },
+
+    // Leaderboard seasons (tags 55-56)
+    LeaderboardSeasonEnded {
+        season: u64,
+        next_season_end: u64,
+    },
+    SeasonRewardPaid {
+        season: u64,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        rank: u32,
+        amount: u64,
+    },
+
+    // VIP program (tags 57-58)
+    LoyaltyEarned {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        points: u64,
+        total_points: u64,
+        rakeback: u64,
+    },
+    TierChanged {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        tier: u8,
+        shields: u32,
+    },
+
+    // Insurance fund (tags 59-60)
+    InsuranceFunded {
+        amount: u64,
+        balance: u64,
+    },
+    InsuranceDrawn {
+        amount: u64,
+        balance: u64,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_decimal"))]
+        net_pnl: i128,
+    },
+
+    // Rejected (non-casino) instructions (tag 61)
+    InstructionFailed {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        /// Tag of the rejected instruction.
+        kind: u8,
+        /// Reason for the rejection.
+        code: crate::casino::CasinoErrorCode,
+    },
+
+    // Account recovery events (tags 62-64)
+    GuardiansSet {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::vec"))]
+        guardians: Vec<PublicKey>,
+        threshold: u8,
+    },
+    RecoveryApproved {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        guardian: PublicKey,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        new_key: PublicKey,
+        approvals: u8,
+        /// View the recovery can be executed from (0 until enough guardians approve it).
+        ready_view: u64,
+    },
+    AccountRecovered {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        old_key: PublicKey,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        new_key: PublicKey,
+    },
+
+    // vUSDT allowance events (tags 65-66)
+    AllowanceSet {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        owner: PublicKey,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        spender: PublicKey,
+        amount: u64,
+    },
+    VusdtTransferred {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        owner: PublicKey,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        to: PublicKey,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        spender: PublicKey,
+        amount: u64,
+        /// Allowance left after the transfer.
+        allowance: u64,
+    },
+
+    // Vesting events (tags 67-68)
+    VestingCreated {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        from: PublicKey,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        to: PublicKey,
+        amount: u64,
+        start_view: u64,
+        duration: u64,
+    },
+    VestedClaimed {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        amount: u64,
+        new_chips: u64,
+    },
+
+    // Staking reward events (tag 69)
+    StakingRewardsIssued {
+        /// Epoch the rewards were earned in.
+        epoch: u64,
+        /// Rewards issued to stakers (minted as they are claimed).
+        amount: u64,
+        /// Annual rate of the reward curve for the epoch (basis points of the total staked).
+        apr_bps: u64,
+    },
+
+    // Treasury events (tags 70-71)
+    TreasuryFunded {
+        epoch: u64,
+        /// vUSDT paid into the treasury (the value of its share of the epoch's profit).
+        amount: u64,
+        balance: u64,
+    },
+    BuybackExecuted {
+        /// vUSDT spent.
+        amount: u64,
+        /// RNG bought and burned.
+        burned: u64,
+        balance: u64,
+        reserve_rng: u64,
+        reserve_vusdt: u64,
+    },
+
+    // Game rule events (tag 72)
+    GameConfigUpdated {
+        game_type: crate::casino::GameType,
+        config: crate::casino::GameConfig,
+    },
+
+    // RNG transcript events (tag 73)
+    /// Outcomes drawn while starting a session (move 0) or processing one of its moves, when
+    /// the game's rules record them.
+    CasinoRngTranscript {
+        session_id: u64,
+        move_number: u32,
+        draws: Vec<crate::casino::RngDraw>,
+    },
+
+    // Side bet events (tag 74)
+    /// A side bet settled with its session (`payout` includes the stake and any progressive
+    /// jackpot, before any super mode multiplier).
+    CasinoSideBetSettled {
+        session_id: u64,
+        bet_id: u8,
+        amount: u64,
+        payout: u64,
+    },
+
+    // Game statistics events (tag 75)
+    /// A game's running totals, reported every `GAME_STATS_INTERVAL` completed sessions.
+    GameStatsUpdated {
+        game_type: crate::casino::GameType,
+        stats: crate::casino::GameStats,
+    },
+
+    // Bonus events (tags 76-77)
+    BonusGranted {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        amount: u64,
+        bonus_chips: u64,
+        wagering_remaining: u64,
+    },
+    /// A bonus whose wagering requirement was met, converted to chips.
+    BonusReleased {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        amount: u64,
+        chips: u64,
+    },
+
+    // Tournament rules (tag 78)
+    TournamentRulesSet {
+        tournament_id: u64,
+        rules: crate::casino::TournamentRules,
+    },
+
+    // Table events (tags 79-80)
+    /// A card dealt by a session's move (alongside `CasinoGameMoved`, in the order dealt).
+    CasinoCardDealt {
+        session_id: u64,
+        move_number: u32,
+        card: u8,
+        to: crate::casino::Seat,
+    },
+    /// The dice rolled by a craps session's move, and the bets the roll resolved.
+    CasinoCrapsRolled {
+        session_id: u64,
+        move_number: u32,
+        d1: u8,
+        d2: u8,
+        resolved_bets: Vec<crate::casino::ResolvedBet>,
+    },
+
+    // Economy parameter events (tag 81)
+    /// New economy parameters, applied from the next block.
+    EconomyParamsUpdated {
+        params: crate::casino::EconomyParams,
+    },
+
+    // Admin key and house settings events (tags 82-83)
+    /// The key holding an admin role was set (or cleared).
+    AdminSet {
+        role: crate::casino::AdminRole,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::option"))]
+        admin: Option<PublicKey>,
+    },
+    /// New house settings.
+    HouseSettingsUpdated {
+        settings: crate::casino::HouseSettings,
+    },
+
     // Staking events (tags 37-40)
     Staked {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
         player: PublicKey,
         amount: u64,
         duration: u64,
         new_balance: u64,
         unlock_ts: u64,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_decimal"))]
         voting_power: u128,
     },
     Unstaked {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
         player: PublicKey,
         amount: u64,
     },
@@ -1270,13 +3963,42 @@ pub enum Event {
         epoch: u64,
     },
     RewardsClaimed {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        amount: u64,
+    },
+
+    // Fee events (tags 41-42)
+    FeeCharged {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        player: PublicKey,
+        amount: u64,
+        new_chips: u64,
+        accumulated_fees: u64,
+    },
+    InterestAccrued {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
         player: PublicKey,
         amount: u64,
+        new_debt: u64,
+        effective_rate_bps: u64,
+        rate_bps: u64,
+    },
+
+    /// An event this release can't decode: one with a tag it doesn't know, or encoded by a
+    /// newer [EVENT_VERSION]. It is kept as encoded, so it re-encodes byte for byte (and the
+    /// proofs it is part of still verify).
+    Unknown {
+        version: u8,
+        tag: u8,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::bytes"))]
+        data: Vec<u8>,
     },
 }
 
-impl Write for Event {
-    fn write(&self, writer: &mut impl BufMut) {
+impl Event {
+    /// Write the event's tag and fields (the legacy encoding, and the body of the envelope).
+    fn write_body(&self, writer: &mut impl BufMut) {
         match self {
             // Casino events (tags 20-24)
             Self::CasinoPlayerRegistered { player, name } => {
@@ -1370,7 +4092,7 @@ impl Write for Event {
                 rankings.write(writer);
             }
 
-            // Vault & AMM events (tags 30-36)
+            // Vault & AMM events (tags 30-36, 43)
             Self::VaultCreated { player } => {
                 tags::event::VAULT_CREATED.write(writer);
                 player.write(writer);
@@ -1385,6 +4107,16 @@ impl Write for Event {
                 amount.write(writer);
                 new_collateral.write(writer);
             }
+            Self::CollateralWithdrawn {
+                player,
+                amount,
+                new_collateral,
+            } => {
+                tags::event::COLLATERAL_WITHDRAWN.write(writer);
+                player.write(writer);
+                amount.write(writer);
+                new_collateral.write(writer);
+            }
             Self::VusdtBorrowed {
                 player,
                 amount,
@@ -1466,20 +4198,502 @@ impl Write for Event {
                 lp_balance.write(writer);
             }
 
-            // Staking events (tags 37-40)
-            Self::Staked {
+            // Limit order events (tags 44-46)
+            Self::LimitOrderPlaced {
+                order_id,
                 player,
-                amount,
-                duration,
-                new_balance,
-                unlock_ts,
-                voting_power,
+                amount_in,
+                min_price,
+                is_buying_rng,
             } => {
-                tags::event::STAKED.write(writer);
+                tags::event::LIMIT_ORDER_PLACED.write(writer);
+                order_id.write(writer);
                 player.write(writer);
-                amount.write(writer);
-                duration.write(writer);
-                new_balance.write(writer);
+                amount_in.write(writer);
+                min_price.write(writer);
+                is_buying_rng.write(writer);
+            }
+            Self::LimitOrderFilled {
+                order_id,
+                player,
+                is_buying_rng,
+                amount_in,
+                amount_out,
+                fee_amount,
+                burned_amount,
+                reserve_rng,
+                reserve_vusdt,
+            } => {
+                tags::event::LIMIT_ORDER_FILLED.write(writer);
+                order_id.write(writer);
+                player.write(writer);
+                is_buying_rng.write(writer);
+                amount_in.write(writer);
+                amount_out.write(writer);
+                fee_amount.write(writer);
+                burned_amount.write(writer);
+                reserve_rng.write(writer);
+                reserve_vusdt.write(writer);
+            }
+            Self::LimitOrderCancelled {
+                order_id,
+                player,
+                refunded,
+            } => {
+                tags::event::LIMIT_ORDER_CANCELLED.write(writer);
+                order_id.write(writer);
+                player.write(writer);
+                refunded.write(writer);
+            }
+
+            // Single-sided liquidity events (tag 47)
+            Self::LiquidityZapped {
+                player,
+                from_rng,
+                amount_in,
+                swap_amount_in,
+                swap_amount_out,
+                fee_amount,
+                burned_amount,
+                rng_amount,
+                vusdt_amount,
+                shares_minted,
+                total_shares,
+                reserve_rng,
+                reserve_vusdt,
+                lp_balance,
+            } => {
+                tags::event::LIQUIDITY_ZAPPED.write(writer);
+                player.write(writer);
+                from_rng.write(writer);
+                amount_in.write(writer);
+                swap_amount_in.write(writer);
+                swap_amount_out.write(writer);
+                fee_amount.write(writer);
+                burned_amount.write(writer);
+                rng_amount.write(writer);
+                vusdt_amount.write(writer);
+                shares_minted.write(writer);
+                total_shares.write(writer);
+                reserve_rng.write(writer);
+                reserve_vusdt.write(writer);
+                lp_balance.write(writer);
+            }
+
+            // Slashing events (tag 48)
+            Self::Slashed {
+                player,
+                bps,
+                amount,
+                new_balance,
+                voting_power,
+            } => {
+                tags::event::SLASHED.write(writer);
+                player.write(writer);
+                bps.write(writer);
+                amount.write(writer);
+                new_balance.write(writer);
+                voting_power.write(writer);
+            }
+
+            // Daily bonus events (tag 49)
+            Self::DailyBonusClaimed {
+                player,
+                amount,
+                streak,
+                new_chips,
+                next_claim,
+            } => {
+                tags::event::DAILY_BONUS_CLAIMED.write(writer);
+                player.write(writer);
+                amount.write(writer);
+                streak.write(writer);
+                new_chips.write(writer);
+                next_claim.write(writer);
+            }
+
+            // Profile events (tag 50)
+            Self::ProfileUpdated {
+                player,
+                display_name,
+                avatar_id,
+                title,
+            } => {
+                tags::event::PROFILE_UPDATED.write(writer);
+                player.write(writer);
+                crate::casino::write_string(display_name, writer);
+                avatar_id.write(writer);
+                crate::casino::write_string(title, writer);
+            }
+
+            // Session expiry events (tag 51)
+            Self::CasinoSessionExpired {
+                session_id,
+                player,
+                game_type,
+                refund,
+                final_chips,
+            } => {
+                tags::event::CASINO_SESSION_EXPIRED.write(writer);
+                session_id.write(writer);
+                player.write(writer);
+                game_type.write(writer);
+                refund.write(writer);
+                final_chips.write(writer);
+            }
+
+            // KYC events (tag 52)
+            Self::KycStatusChanged { player, verified } => {
+                tags::event::KYC_STATUS_CHANGED.write(writer);
+                player.write(writer);
+                verified.write(writer);
+            }
+
+            // Scheduled tournament events (tag 53)
+            Self::TournamentScheduled {
+                id,
+                start_view,
+                end_view,
+            } => {
+                tags::event::TOURNAMENT_SCHEDULED.write(writer);
+                id.write(writer);
+                start_view.write(writer);
+                end_view.write(writer);
+            }
+
+            // Tournament payouts (tag 54)
+            Self::TournamentPrizePaid {
+                tournament_id,
+                player,
+                rank,
+                amount,
+            } => {
+                tags::event::TOURNAMENT_PRIZE_PAID.write(writer);
+                tournament_id.write(writer);
+                player.write(writer);
+                rank.write(writer);
+                amount.write(writer);
+            }
+
+            // Leaderboard seasons (tags 55-56)
+            Self::LeaderboardSeasonEnded {
+                season,
+                next_season_end,
+            } => {
+                tags::event::LEADERBOARD_SEASON_ENDED.write(writer);
+                season.write(writer);
+                next_season_end.write(writer);
+            }
+            Self::SeasonRewardPaid {
+                season,
+                player,
+                rank,
+                amount,
+            } => {
+                tags::event::SEASON_REWARD_PAID.write(writer);
+                season.write(writer);
+                player.write(writer);
+                rank.write(writer);
+                amount.write(writer);
+            }
+
+            // VIP program (tags 57-58)
+            Self::LoyaltyEarned {
+                player,
+                points,
+                total_points,
+                rakeback,
+            } => {
+                tags::event::LOYALTY_EARNED.write(writer);
+                player.write(writer);
+                points.write(writer);
+                total_points.write(writer);
+                rakeback.write(writer);
+            }
+            Self::TierChanged {
+                player,
+                tier,
+                shields,
+            } => {
+                tags::event::TIER_CHANGED.write(writer);
+                player.write(writer);
+                tier.write(writer);
+                shields.write(writer);
+            }
+
+            // Insurance fund (tags 59-60)
+            Self::InsuranceFunded { amount, balance } => {
+                tags::event::INSURANCE_FUNDED.write(writer);
+                amount.write(writer);
+                balance.write(writer);
+            }
+            Self::InsuranceDrawn {
+                amount,
+                balance,
+                net_pnl,
+            } => {
+                tags::event::INSURANCE_DRAWN.write(writer);
+                amount.write(writer);
+                balance.write(writer);
+                net_pnl.write(writer);
+            }
+
+            // Rejected (non-casino) instructions (tag 61)
+            Self::InstructionFailed { player, kind, code } => {
+                tags::event::INSTRUCTION_FAILED.write(writer);
+                player.write(writer);
+                kind.write(writer);
+                code.write(writer);
+            }
+
+            // Account recovery events (tags 62-64)
+            Self::GuardiansSet {
+                player,
+                guardians,
+                threshold,
+            } => {
+                tags::event::GUARDIANS_SET.write(writer);
+                player.write(writer);
+                guardians.write(writer);
+                threshold.write(writer);
+            }
+            Self::RecoveryApproved {
+                player,
+                guardian,
+                new_key,
+                approvals,
+                ready_view,
+            } => {
+                tags::event::RECOVERY_APPROVED.write(writer);
+                player.write(writer);
+                guardian.write(writer);
+                new_key.write(writer);
+                approvals.write(writer);
+                ready_view.write(writer);
+            }
+            Self::AccountRecovered { old_key, new_key } => {
+                tags::event::ACCOUNT_RECOVERED.write(writer);
+                old_key.write(writer);
+                new_key.write(writer);
+            }
+
+            // vUSDT allowance events (tags 65-66)
+            Self::AllowanceSet {
+                owner,
+                spender,
+                amount,
+            } => {
+                tags::event::ALLOWANCE_SET.write(writer);
+                owner.write(writer);
+                spender.write(writer);
+                amount.write(writer);
+            }
+            Self::VusdtTransferred {
+                owner,
+                to,
+                spender,
+                amount,
+                allowance,
+            } => {
+                tags::event::VUSDT_TRANSFERRED.write(writer);
+                owner.write(writer);
+                to.write(writer);
+                spender.write(writer);
+                amount.write(writer);
+                allowance.write(writer);
+            }
+
+            // Vesting events (tags 67-68)
+            Self::VestingCreated {
+                from,
+                to,
+                amount,
+                start_view,
+                duration,
+            } => {
+                tags::event::VESTING_CREATED.write(writer);
+                from.write(writer);
+                to.write(writer);
+                amount.write(writer);
+                start_view.write(writer);
+                duration.write(writer);
+            }
+            Self::VestedClaimed {
+                player,
+                amount,
+                new_chips,
+            } => {
+                tags::event::VESTED_CLAIMED.write(writer);
+                player.write(writer);
+                amount.write(writer);
+                new_chips.write(writer);
+            }
+
+            Self::StakingRewardsIssued {
+                epoch,
+                amount,
+                apr_bps,
+            } => {
+                tags::event::STAKING_REWARDS_ISSUED.write(writer);
+                epoch.write(writer);
+                amount.write(writer);
+                apr_bps.write(writer);
+            }
+
+            Self::TreasuryFunded {
+                epoch,
+                amount,
+                balance,
+            } => {
+                tags::event::TREASURY_FUNDED.write(writer);
+                epoch.write(writer);
+                amount.write(writer);
+                balance.write(writer);
+            }
+            Self::BuybackExecuted {
+                amount,
+                burned,
+                balance,
+                reserve_rng,
+                reserve_vusdt,
+            } => {
+                tags::event::BUYBACK_EXECUTED.write(writer);
+                amount.write(writer);
+                burned.write(writer);
+                balance.write(writer);
+                reserve_rng.write(writer);
+                reserve_vusdt.write(writer);
+            }
+
+            Self::GameConfigUpdated { game_type, config } => {
+                tags::event::GAME_CONFIG_UPDATED.write(writer);
+                game_type.write(writer);
+                config.write(writer);
+            }
+
+            Self::CasinoRngTranscript {
+                session_id,
+                move_number,
+                draws,
+            } => {
+                tags::event::CASINO_RNG_TRANSCRIPT.write(writer);
+                session_id.write(writer);
+                move_number.write(writer);
+                draws.write(writer);
+            }
+
+            Self::CasinoSideBetSettled {
+                session_id,
+                bet_id,
+                amount,
+                payout,
+            } => {
+                tags::event::CASINO_SIDE_BET_SETTLED.write(writer);
+                session_id.write(writer);
+                bet_id.write(writer);
+                amount.write(writer);
+                payout.write(writer);
+            }
+
+            Self::GameStatsUpdated { game_type, stats } => {
+                tags::event::GAME_STATS_UPDATED.write(writer);
+                game_type.write(writer);
+                stats.write(writer);
+            }
+
+            // Bonus events (tags 76-77)
+            Self::BonusGranted {
+                player,
+                amount,
+                bonus_chips,
+                wagering_remaining,
+            } => {
+                tags::event::BONUS_GRANTED.write(writer);
+                player.write(writer);
+                amount.write(writer);
+                bonus_chips.write(writer);
+                wagering_remaining.write(writer);
+            }
+            Self::BonusReleased {
+                player,
+                amount,
+                chips,
+            } => {
+                tags::event::BONUS_RELEASED.write(writer);
+                player.write(writer);
+                amount.write(writer);
+                chips.write(writer);
+            }
+
+            // Tournament rules (tag 78)
+            Self::TournamentRulesSet {
+                tournament_id,
+                rules,
+            } => {
+                tags::event::TOURNAMENT_RULES_SET.write(writer);
+                tournament_id.write(writer);
+                rules.write(writer);
+            }
+
+            // Table events (tags 79-80)
+            Self::CasinoCardDealt {
+                session_id,
+                move_number,
+                card,
+                to,
+            } => {
+                tags::event::CASINO_CARD_DEALT.write(writer);
+                session_id.write(writer);
+                move_number.write(writer);
+                card.write(writer);
+                to.write(writer);
+            }
+            Self::CasinoCrapsRolled {
+                session_id,
+                move_number,
+                d1,
+                d2,
+                resolved_bets,
+            } => {
+                tags::event::CASINO_CRAPS_ROLLED.write(writer);
+                session_id.write(writer);
+                move_number.write(writer);
+                d1.write(writer);
+                d2.write(writer);
+                resolved_bets.write(writer);
+            }
+
+            // Economy parameters (tag 81)
+            Self::EconomyParamsUpdated { params } => {
+                tags::event::ECONOMY_PARAMS_UPDATED.write(writer);
+                params.write(writer);
+            }
+
+            // Admin keys and house settings (tags 82-83)
+            Self::AdminSet { role, admin } => {
+                tags::event::ADMIN_SET.write(writer);
+                role.write(writer);
+                admin.write(writer);
+            }
+            Self::HouseSettingsUpdated { settings } => {
+                tags::event::HOUSE_SETTINGS_UPDATED.write(writer);
+                settings.write(writer);
+            }
+
+            // Staking events (tags 37-40)
+            Self::Staked {
+                player,
+                amount,
+                duration,
+                new_balance,
+                unlock_ts,
+                voting_power,
+            } => {
+                tags::event::STAKED.write(writer);
+                player.write(writer);
+                amount.write(writer);
+                duration.write(writer);
+                new_balance.write(writer);
                 unlock_ts.write(writer);
                 voting_power.write(writer);
             }
@@ -1497,14 +4711,44 @@ impl Write for Event {
                 player.write(writer);
                 amount.write(writer);
             }
+
+            // Fee events (tags 41-42)
+            Self::FeeCharged {
+                player,
+                amount,
+                new_chips,
+                accumulated_fees,
+            } => {
+                tags::event::FEE_CHARGED.write(writer);
+                player.write(writer);
+                amount.write(writer);
+                new_chips.write(writer);
+                accumulated_fees.write(writer);
+            }
+            Self::InterestAccrued {
+                player,
+                amount,
+                new_debt,
+                effective_rate_bps,
+                rate_bps,
+            } => {
+                tags::event::INTEREST_ACCRUED.write(writer);
+                player.write(writer);
+                amount.write(writer);
+                new_debt.write(writer);
+                effective_rate_bps.write(writer);
+                rate_bps.write(writer);
+            }
+
+            Self::Unknown { tag, data, .. } => {
+                tag.write(writer);
+                writer.put_slice(data);
+            }
         }
     }
-}
-
-impl Read for Event {
-    type Cfg = ();
 
-    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+    /// Read an event's tag and fields, or `None` if the tag isn't known.
+    fn read_body(reader: &mut impl Buf) -> Result<Option<Self>, Error> {
         let kind = u8::read(reader)?;
         let event = match kind {
             // Casino events (tags 20-24)
@@ -1550,7 +4794,7 @@ impl Read for Event {
             tags::event::CASINO_ERROR => {
                 let player = PublicKey::read(reader)?;
                 let session_id = Option::<u64>::read(reader)?;
-                let error_code = u8::read(reader)?;
+                let error_code = crate::casino::CasinoErrorCode::read(reader)?;
                 let message_len = u32::read(reader)? as usize;
                 const MAX_ERROR_MESSAGE_LENGTH: usize = 256;
                 if message_len > MAX_ERROR_MESSAGE_LENGTH {
@@ -1589,7 +4833,7 @@ impl Read for Event {
                 rankings: Vec::<(PublicKey, u64)>::read_range(reader, 0..=1000)?,
             },
 
-            // Vault & AMM events (tags 30-36)
+            // Vault & AMM events (tags 30-36, 43)
             tags::event::VAULT_CREATED => Self::VaultCreated {
                 player: PublicKey::read(reader)?,
             },
@@ -1598,6 +4842,11 @@ impl Read for Event {
                 amount: u64::read(reader)?,
                 new_collateral: u64::read(reader)?,
             },
+            tags::event::COLLATERAL_WITHDRAWN => Self::CollateralWithdrawn {
+                player: PublicKey::read(reader)?,
+                amount: u64::read(reader)?,
+                new_collateral: u64::read(reader)?,
+            },
             tags::event::VUSDT_BORROWED => Self::VusdtBorrowed {
                 player: PublicKey::read(reader)?,
                 amount: u64::read(reader)?,
@@ -1638,6 +4887,285 @@ impl Read for Event {
                 reserve_vusdt: u64::read(reader)?,
                 lp_balance: u64::read(reader)?,
             },
+
+            // Limit order events (tags 44-46)
+            tags::event::LIMIT_ORDER_PLACED => Self::LimitOrderPlaced {
+                order_id: u64::read(reader)?,
+                player: PublicKey::read(reader)?,
+                amount_in: u64::read(reader)?,
+                min_price: u64::read(reader)?,
+                is_buying_rng: bool::read(reader)?,
+            },
+            tags::event::LIMIT_ORDER_FILLED => Self::LimitOrderFilled {
+                order_id: u64::read(reader)?,
+                player: PublicKey::read(reader)?,
+                is_buying_rng: bool::read(reader)?,
+                amount_in: u64::read(reader)?,
+                amount_out: u64::read(reader)?,
+                fee_amount: u64::read(reader)?,
+                burned_amount: u64::read(reader)?,
+                reserve_rng: u64::read(reader)?,
+                reserve_vusdt: u64::read(reader)?,
+            },
+            tags::event::LIMIT_ORDER_CANCELLED => Self::LimitOrderCancelled {
+                order_id: u64::read(reader)?,
+                player: PublicKey::read(reader)?,
+                refunded: u64::read(reader)?,
+            },
+
+            // Single-sided liquidity events (tag 47)
+            tags::event::LIQUIDITY_ZAPPED => Self::LiquidityZapped {
+                player: PublicKey::read(reader)?,
+                from_rng: bool::read(reader)?,
+                amount_in: u64::read(reader)?,
+                swap_amount_in: u64::read(reader)?,
+                swap_amount_out: u64::read(reader)?,
+                fee_amount: u64::read(reader)?,
+                burned_amount: u64::read(reader)?,
+                rng_amount: u64::read(reader)?,
+                vusdt_amount: u64::read(reader)?,
+                shares_minted: u64::read(reader)?,
+                total_shares: u64::read(reader)?,
+                reserve_rng: u64::read(reader)?,
+                reserve_vusdt: u64::read(reader)?,
+                lp_balance: u64::read(reader)?,
+            },
+
+            // Slashing events (tag 48)
+            tags::event::SLASHED => Self::Slashed {
+                player: PublicKey::read(reader)?,
+                bps: u16::read(reader)?,
+                amount: u64::read(reader)?,
+                new_balance: u64::read(reader)?,
+                voting_power: u128::read(reader)?,
+            },
+
+            // Daily bonus events (tag 49)
+            tags::event::DAILY_BONUS_CLAIMED => Self::DailyBonusClaimed {
+                player: PublicKey::read(reader)?,
+                amount: u64::read(reader)?,
+                streak: u32::read(reader)?,
+                new_chips: u64::read(reader)?,
+                next_claim: u64::read(reader)?,
+            },
+
+            // Profile events (tag 50)
+            tags::event::PROFILE_UPDATED => Self::ProfileUpdated {
+                player: PublicKey::read(reader)?,
+                display_name: crate::casino::read_string(reader, CASINO_MAX_NAME_LENGTH)?,
+                avatar_id: u32::read(reader)?,
+                title: crate::casino::read_string(reader, crate::casino::MAX_TITLE_LENGTH)?,
+            },
+
+            // Session expiry events (tag 51)
+            tags::event::CASINO_SESSION_EXPIRED => Self::CasinoSessionExpired {
+                session_id: u64::read(reader)?,
+                player: PublicKey::read(reader)?,
+                game_type: crate::casino::GameType::read(reader)?,
+                refund: u64::read(reader)?,
+                final_chips: u64::read(reader)?,
+            },
+
+            // KYC events (tag 52)
+            tags::event::KYC_STATUS_CHANGED => Self::KycStatusChanged {
+                player: PublicKey::read(reader)?,
+                verified: bool::read(reader)?,
+            },
+
+            // Scheduled tournament events (tag 53)
+            tags::event::TOURNAMENT_SCHEDULED => Self::TournamentScheduled {
+                id: u64::read(reader)?,
+                start_view: u64::read(reader)?,
+                end_view: u64::read(reader)?,
+            },
+
+            // Tournament payouts (tag 54)
+            tags::event::TOURNAMENT_PRIZE_PAID => Self::TournamentPrizePaid {
+                tournament_id: u64::read(reader)?,
+                player: PublicKey::read(reader)?,
+                rank: u32::read(reader)?,
+                amount: u64::read(reader)?,
+            },
+
+            // Leaderboard seasons (tags 55-56)
+            tags::event::LEADERBOARD_SEASON_ENDED => Self::LeaderboardSeasonEnded {
+                season: u64::read(reader)?,
+                next_season_end: u64::read(reader)?,
+            },
+            tags::event::SEASON_REWARD_PAID => Self::SeasonRewardPaid {
+                season: u64::read(reader)?,
+                player: PublicKey::read(reader)?,
+                rank: u32::read(reader)?,
+                amount: u64::read(reader)?,
+            },
+
+            // VIP program (tags 57-58)
+            tags::event::LOYALTY_EARNED => Self::LoyaltyEarned {
+                player: PublicKey::read(reader)?,
+                points: u64::read(reader)?,
+                total_points: u64::read(reader)?,
+                rakeback: u64::read(reader)?,
+            },
+            tags::event::TIER_CHANGED => Self::TierChanged {
+                player: PublicKey::read(reader)?,
+                tier: u8::read(reader)?,
+                shields: u32::read(reader)?,
+            },
+
+            // Insurance fund (tags 59-60)
+            tags::event::INSURANCE_FUNDED => Self::InsuranceFunded {
+                amount: u64::read(reader)?,
+                balance: u64::read(reader)?,
+            },
+            tags::event::INSURANCE_DRAWN => Self::InsuranceDrawn {
+                amount: u64::read(reader)?,
+                balance: u64::read(reader)?,
+                net_pnl: i128::read(reader)?,
+            },
+
+            // Rejected (non-casino) instructions (tag 61)
+            tags::event::INSTRUCTION_FAILED => Self::InstructionFailed {
+                player: PublicKey::read(reader)?,
+                kind: u8::read(reader)?,
+                code: crate::casino::CasinoErrorCode::read(reader)?,
+            },
+
+            // Account recovery events (tags 62-64)
+            tags::event::GUARDIANS_SET => Self::GuardiansSet {
+                player: PublicKey::read(reader)?,
+                guardians: Vec::<PublicKey>::read_range(reader, 0..=crate::casino::MAX_GUARDIANS)?,
+                threshold: u8::read(reader)?,
+            },
+            tags::event::RECOVERY_APPROVED => Self::RecoveryApproved {
+                player: PublicKey::read(reader)?,
+                guardian: PublicKey::read(reader)?,
+                new_key: PublicKey::read(reader)?,
+                approvals: u8::read(reader)?,
+                ready_view: u64::read(reader)?,
+            },
+            tags::event::ACCOUNT_RECOVERED => Self::AccountRecovered {
+                old_key: PublicKey::read(reader)?,
+                new_key: PublicKey::read(reader)?,
+            },
+
+            // vUSDT allowance events (tags 65-66)
+            tags::event::ALLOWANCE_SET => Self::AllowanceSet {
+                owner: PublicKey::read(reader)?,
+                spender: PublicKey::read(reader)?,
+                amount: u64::read(reader)?,
+            },
+            tags::event::VUSDT_TRANSFERRED => Self::VusdtTransferred {
+                owner: PublicKey::read(reader)?,
+                to: PublicKey::read(reader)?,
+                spender: PublicKey::read(reader)?,
+                amount: u64::read(reader)?,
+                allowance: u64::read(reader)?,
+            },
+
+            // Vesting events (tags 67-68)
+            tags::event::VESTING_CREATED => Self::VestingCreated {
+                from: PublicKey::read(reader)?,
+                to: PublicKey::read(reader)?,
+                amount: u64::read(reader)?,
+                start_view: u64::read(reader)?,
+                duration: u64::read(reader)?,
+            },
+            tags::event::VESTED_CLAIMED => Self::VestedClaimed {
+                player: PublicKey::read(reader)?,
+                amount: u64::read(reader)?,
+                new_chips: u64::read(reader)?,
+            },
+
+            tags::event::STAKING_REWARDS_ISSUED => Self::StakingRewardsIssued {
+                epoch: u64::read(reader)?,
+                amount: u64::read(reader)?,
+                apr_bps: u64::read(reader)?,
+            },
+
+            tags::event::TREASURY_FUNDED => Self::TreasuryFunded {
+                epoch: u64::read(reader)?,
+                amount: u64::read(reader)?,
+                balance: u64::read(reader)?,
+            },
+            tags::event::BUYBACK_EXECUTED => Self::BuybackExecuted {
+                amount: u64::read(reader)?,
+                burned: u64::read(reader)?,
+                balance: u64::read(reader)?,
+                reserve_rng: u64::read(reader)?,
+                reserve_vusdt: u64::read(reader)?,
+            },
+
+            tags::event::GAME_CONFIG_UPDATED => Self::GameConfigUpdated {
+                game_type: crate::casino::GameType::read(reader)?,
+                config: crate::casino::GameConfig::read(reader)?,
+            },
+
+            tags::event::CASINO_RNG_TRANSCRIPT => Self::CasinoRngTranscript {
+                session_id: u64::read(reader)?,
+                move_number: u32::read(reader)?,
+                draws: Vec::<crate::casino::RngDraw>::read_range(
+                    reader,
+                    0..=crate::casino::MAX_RNG_TRANSCRIPT_DRAWS,
+                )?,
+            },
+
+            tags::event::CASINO_SIDE_BET_SETTLED => Self::CasinoSideBetSettled {
+                session_id: u64::read(reader)?,
+                bet_id: u8::read(reader)?,
+                amount: u64::read(reader)?,
+                payout: u64::read(reader)?,
+            },
+
+            tags::event::GAME_STATS_UPDATED => Self::GameStatsUpdated {
+                game_type: crate::casino::GameType::read(reader)?,
+                stats: crate::casino::GameStats::read(reader)?,
+            },
+
+            tags::event::BONUS_GRANTED => Self::BonusGranted {
+                player: PublicKey::read(reader)?,
+                amount: u64::read(reader)?,
+                bonus_chips: u64::read(reader)?,
+                wagering_remaining: u64::read(reader)?,
+            },
+            tags::event::BONUS_RELEASED => Self::BonusReleased {
+                player: PublicKey::read(reader)?,
+                amount: u64::read(reader)?,
+                chips: u64::read(reader)?,
+            },
+
+            tags::event::TOURNAMENT_RULES_SET => Self::TournamentRulesSet {
+                tournament_id: u64::read(reader)?,
+                rules: crate::casino::TournamentRules::read(reader)?,
+            },
+
+            tags::event::CASINO_CARD_DEALT => Self::CasinoCardDealt {
+                session_id: u64::read(reader)?,
+                move_number: u32::read(reader)?,
+                card: u8::read(reader)?,
+                to: crate::casino::Seat::read(reader)?,
+            },
+            tags::event::CASINO_CRAPS_ROLLED => Self::CasinoCrapsRolled {
+                session_id: u64::read(reader)?,
+                move_number: u32::read(reader)?,
+                d1: u8::read(reader)?,
+                d2: u8::read(reader)?,
+                resolved_bets: Vec::<crate::casino::ResolvedBet>::read_range(
+                    reader,
+                    0..=crate::casino::MAX_CRAPS_BETS,
+                )?,
+            },
+
+            tags::event::ECONOMY_PARAMS_UPDATED => Self::EconomyParamsUpdated {
+                params: crate::casino::EconomyParams::read(reader)?,
+            },
+            tags::event::ADMIN_SET => Self::AdminSet {
+                role: crate::casino::AdminRole::read(reader)?,
+                admin: Option::<PublicKey>::read(reader)?,
+            },
+            tags::event::HOUSE_SETTINGS_UPDATED => Self::HouseSettingsUpdated {
+                settings: crate::casino::HouseSettings::read(reader)?,
+            },
+
             tags::event::STAKED => Self::Staked {
                 player: PublicKey::read(reader)?,
                 amount: u64::read(reader)?,
@@ -1658,15 +5186,29 @@ impl Read for Event {
                 amount: u64::read(reader)?,
             },
 
-            i => return Err(Error::InvalidEnum(i)),
+            // Fee events (tags 41-42)
+            tags::event::FEE_CHARGED => Self::FeeCharged {
+                player: PublicKey::read(reader)?,
+                amount: u64::read(reader)?,
+                new_chips: u64::read(reader)?,
+                accumulated_fees: u64::read(reader)?,
+            },
+            tags::event::INTEREST_ACCRUED => Self::InterestAccrued {
+                player: PublicKey::read(reader)?,
+                amount: u64::read(reader)?,
+                new_debt: u64::read(reader)?,
+                effective_rate_bps: u64::read(reader)?,
+                rate_bps: u64::read(reader)?,
+            },
+
+            _ => return Ok(None),
         };
 
-        Ok(event)
+        Ok(Some(event))
     }
-}
 
-impl EncodeSize for Event {
-    fn encode_size(&self) -> usize {
+    /// Size of the event's tag and fields.
+    fn body_size(&self) -> usize {
         u8::SIZE
             + match self {
                 // Casino events (tags 20-24)
@@ -1722,37 +5264,115 @@ impl EncodeSize for Event {
                         + message.len()
                 }
 
-                // Tournament events (tags 25-28)
-                Self::TournamentStarted { id, start_block } => {
-                    id.encode_size() + start_block.encode_size()
-                }
-                Self::PlayerJoined {
-                    tournament_id,
+                // Tournament events (tags 25-28)
+                Self::TournamentStarted { id, start_block } => {
+                    id.encode_size() + start_block.encode_size()
+                }
+                Self::PlayerJoined {
+                    tournament_id,
+                    player,
+                } => tournament_id.encode_size() + player.encode_size(),
+                Self::TournamentPhaseChanged { id, phase } => {
+                    id.encode_size() + phase.encode_size()
+                }
+                Self::TournamentEnded { id, rankings } => id.encode_size() + rankings.encode_size(),
+
+                // Vault & AMM events (tags 30-36, 43)
+                Self::VaultCreated { player } => player.encode_size(),
+                Self::CollateralDeposited {
+                    player,
+                    amount,
+                    new_collateral,
+                }
+                | Self::CollateralWithdrawn {
+                    player,
+                    amount,
+                    new_collateral,
+                } => player.encode_size() + amount.encode_size() + new_collateral.encode_size(),
+                Self::VusdtBorrowed {
+                    player,
+                    amount,
+                    new_debt,
+                } => player.encode_size() + amount.encode_size() + new_debt.encode_size(),
+                Self::VusdtRepaid {
+                    player,
+                    amount,
+                    new_debt,
+                } => player.encode_size() + amount.encode_size() + new_debt.encode_size(),
+                Self::AmmSwapped {
+                    player,
+                    is_buying_rng,
+                    amount_in,
+                    amount_out,
+                    fee_amount,
+                    burned_amount,
+                    reserve_rng,
+                    reserve_vusdt,
+                } => {
+                    player.encode_size()
+                        + is_buying_rng.encode_size()
+                        + amount_in.encode_size()
+                        + amount_out.encode_size()
+                        + fee_amount.encode_size()
+                        + burned_amount.encode_size()
+                        + reserve_rng.encode_size()
+                        + reserve_vusdt.encode_size()
+                }
+                Self::LiquidityAdded {
+                    player,
+                    rng_amount,
+                    vusdt_amount,
+                    shares_minted,
+                    total_shares,
+                    reserve_rng,
+                    reserve_vusdt,
+                    lp_balance,
+                } => {
+                    player.encode_size()
+                        + rng_amount.encode_size()
+                        + vusdt_amount.encode_size()
+                        + shares_minted.encode_size()
+                        + total_shares.encode_size()
+                        + reserve_rng.encode_size()
+                        + reserve_vusdt.encode_size()
+                        + lp_balance.encode_size()
+                }
+                Self::LiquidityRemoved {
+                    player,
+                    rng_amount,
+                    vusdt_amount,
+                    shares_burned,
+                    total_shares,
+                    reserve_rng,
+                    reserve_vusdt,
+                    lp_balance,
+                } => {
+                    player.encode_size()
+                        + rng_amount.encode_size()
+                        + vusdt_amount.encode_size()
+                        + shares_burned.encode_size()
+                        + total_shares.encode_size()
+                        + reserve_rng.encode_size()
+                        + reserve_vusdt.encode_size()
+                        + lp_balance.encode_size()
+                }
+
+                // Limit order events (tags 44-46)
+                Self::LimitOrderPlaced {
+                    order_id,
                     player,
-                } => tournament_id.encode_size() + player.encode_size(),
-                Self::TournamentPhaseChanged { id, phase } => {
-                    id.encode_size() + phase.encode_size()
+                    amount_in,
+                    min_price,
+                    is_buying_rng,
+                } => {
+                    order_id.encode_size()
+                        + player.encode_size()
+                        + amount_in.encode_size()
+                        + min_price.encode_size()
+                        + is_buying_rng.encode_size()
                 }
-                Self::TournamentEnded { id, rankings } => id.encode_size() + rankings.encode_size(),
-
-                // Vault & AMM events (tags 30-36)
-                Self::VaultCreated { player } => player.encode_size(),
-                Self::CollateralDeposited {
-                    player,
-                    amount,
-                    new_collateral,
-                } => player.encode_size() + amount.encode_size() + new_collateral.encode_size(),
-                Self::VusdtBorrowed {
-                    player,
-                    amount,
-                    new_debt,
-                } => player.encode_size() + amount.encode_size() + new_debt.encode_size(),
-                Self::VusdtRepaid {
-                    player,
-                    amount,
-                    new_debt,
-                } => player.encode_size() + amount.encode_size() + new_debt.encode_size(),
-                Self::AmmSwapped {
+                Self::LimitOrderFilled {
+                    order_id,
                     player,
                     is_buying_rng,
                     amount_in,
@@ -1762,7 +5382,8 @@ impl EncodeSize for Event {
                     reserve_rng,
                     reserve_vusdt,
                 } => {
-                    player.encode_size()
+                    order_id.encode_size()
+                        + player.encode_size()
                         + is_buying_rng.encode_size()
                         + amount_in.encode_size()
                         + amount_out.encode_size()
@@ -1771,8 +5392,21 @@ impl EncodeSize for Event {
                         + reserve_rng.encode_size()
                         + reserve_vusdt.encode_size()
                 }
-                Self::LiquidityAdded {
+                Self::LimitOrderCancelled {
+                    order_id,
+                    player,
+                    refunded,
+                } => order_id.encode_size() + player.encode_size() + refunded.encode_size(),
+
+                // Single-sided liquidity events (tag 47)
+                Self::LiquidityZapped {
                     player,
+                    from_rng,
+                    amount_in,
+                    swap_amount_in,
+                    swap_amount_out,
+                    fee_amount,
+                    burned_amount,
                     rng_amount,
                     vusdt_amount,
                     shares_minted,
@@ -1782,6 +5416,12 @@ impl EncodeSize for Event {
                     lp_balance,
                 } => {
                     player.encode_size()
+                        + from_rng.encode_size()
+                        + amount_in.encode_size()
+                        + swap_amount_in.encode_size()
+                        + swap_amount_out.encode_size()
+                        + fee_amount.encode_size()
+                        + burned_amount.encode_size()
                         + rng_amount.encode_size()
                         + vusdt_amount.encode_size()
                         + shares_minted.encode_size()
@@ -1790,25 +5430,295 @@ impl EncodeSize for Event {
                         + reserve_vusdt.encode_size()
                         + lp_balance.encode_size()
                 }
-                Self::LiquidityRemoved {
+
+                // Slashing events (tag 48)
+                Self::Slashed {
                     player,
-                    rng_amount,
-                    vusdt_amount,
-                    shares_burned,
-                    total_shares,
+                    bps,
+                    amount,
+                    new_balance,
+                    voting_power,
+                } => {
+                    player.encode_size()
+                        + bps.encode_size()
+                        + amount.encode_size()
+                        + new_balance.encode_size()
+                        + voting_power.encode_size()
+                }
+
+                // Daily bonus events (tag 49)
+                Self::DailyBonusClaimed {
+                    player,
+                    amount,
+                    streak,
+                    new_chips,
+                    next_claim,
+                } => {
+                    player.encode_size()
+                        + amount.encode_size()
+                        + streak.encode_size()
+                        + new_chips.encode_size()
+                        + next_claim.encode_size()
+                }
+
+                // Profile events (tag 50)
+                Self::ProfileUpdated {
+                    player,
+                    display_name,
+                    avatar_id,
+                    title,
+                } => {
+                    player.encode_size()
+                        + crate::casino::string_encode_size(display_name)
+                        + avatar_id.encode_size()
+                        + crate::casino::string_encode_size(title)
+                }
+
+                // Session expiry events (tag 51)
+                Self::CasinoSessionExpired {
+                    session_id,
+                    player,
+                    game_type,
+                    refund,
+                    final_chips,
+                } => {
+                    session_id.encode_size()
+                        + player.encode_size()
+                        + game_type.encode_size()
+                        + refund.encode_size()
+                        + final_chips.encode_size()
+                }
+
+                // KYC events (tag 52)
+                Self::KycStatusChanged { player, verified } => {
+                    player.encode_size() + verified.encode_size()
+                }
+
+                // Scheduled tournament events (tag 53)
+                Self::TournamentScheduled {
+                    id,
+                    start_view,
+                    end_view,
+                } => id.encode_size() + start_view.encode_size() + end_view.encode_size(),
+
+                // Tournament payouts (tag 54)
+                Self::TournamentPrizePaid {
+                    tournament_id,
+                    player,
+                    rank,
+                    amount,
+                } => {
+                    tournament_id.encode_size()
+                        + player.encode_size()
+                        + rank.encode_size()
+                        + amount.encode_size()
+                }
+
+                // Leaderboard seasons (tags 55-56)
+                Self::LeaderboardSeasonEnded {
+                    season,
+                    next_season_end,
+                } => season.encode_size() + next_season_end.encode_size(),
+                Self::SeasonRewardPaid {
+                    season,
+                    player,
+                    rank,
+                    amount,
+                } => {
+                    season.encode_size()
+                        + player.encode_size()
+                        + rank.encode_size()
+                        + amount.encode_size()
+                }
+                Self::LoyaltyEarned {
+                    player,
+                    points,
+                    total_points,
+                    rakeback,
+                } => {
+                    player.encode_size()
+                        + points.encode_size()
+                        + total_points.encode_size()
+                        + rakeback.encode_size()
+                }
+                Self::TierChanged {
+                    player,
+                    tier,
+                    shields,
+                } => player.encode_size() + tier.encode_size() + shields.encode_size(),
+                Self::InsuranceFunded { amount, balance } => {
+                    amount.encode_size() + balance.encode_size()
+                }
+                Self::InsuranceDrawn {
+                    amount,
+                    balance,
+                    net_pnl,
+                } => amount.encode_size() + balance.encode_size() + net_pnl.encode_size(),
+                Self::InstructionFailed { player, kind, code } => {
+                    player.encode_size() + kind.encode_size() + code.encode_size()
+                }
+                Self::GuardiansSet {
+                    player,
+                    guardians,
+                    threshold,
+                } => player.encode_size() + guardians.encode_size() + threshold.encode_size(),
+                Self::RecoveryApproved {
+                    player,
+                    guardian,
+                    new_key,
+                    approvals,
+                    ready_view,
+                } => {
+                    player.encode_size()
+                        + guardian.encode_size()
+                        + new_key.encode_size()
+                        + approvals.encode_size()
+                        + ready_view.encode_size()
+                }
+                Self::AccountRecovered { old_key, new_key } => {
+                    old_key.encode_size() + new_key.encode_size()
+                }
+                Self::AllowanceSet {
+                    owner,
+                    spender,
+                    amount,
+                } => owner.encode_size() + spender.encode_size() + amount.encode_size(),
+                Self::VusdtTransferred {
+                    owner,
+                    to,
+                    spender,
+                    amount,
+                    allowance,
+                } => {
+                    owner.encode_size()
+                        + to.encode_size()
+                        + spender.encode_size()
+                        + amount.encode_size()
+                        + allowance.encode_size()
+                }
+                Self::VestingCreated {
+                    from,
+                    to,
+                    amount,
+                    start_view,
+                    duration,
+                } => {
+                    from.encode_size()
+                        + to.encode_size()
+                        + amount.encode_size()
+                        + start_view.encode_size()
+                        + duration.encode_size()
+                }
+                Self::VestedClaimed {
+                    player,
+                    amount,
+                    new_chips,
+                } => player.encode_size() + amount.encode_size() + new_chips.encode_size(),
+
+                Self::StakingRewardsIssued {
+                    epoch,
+                    amount,
+                    apr_bps,
+                } => epoch.encode_size() + amount.encode_size() + apr_bps.encode_size(),
+
+                Self::TreasuryFunded {
+                    epoch,
+                    amount,
+                    balance,
+                } => epoch.encode_size() + amount.encode_size() + balance.encode_size(),
+                Self::BuybackExecuted {
+                    amount,
+                    burned,
+                    balance,
                     reserve_rng,
                     reserve_vusdt,
-                    lp_balance,
                 } => {
-                    player.encode_size()
-                        + rng_amount.encode_size()
-                        + vusdt_amount.encode_size()
-                        + shares_burned.encode_size()
-                        + total_shares.encode_size()
+                    amount.encode_size()
+                        + burned.encode_size()
+                        + balance.encode_size()
                         + reserve_rng.encode_size()
                         + reserve_vusdt.encode_size()
-                        + lp_balance.encode_size()
                 }
+
+                Self::GameConfigUpdated { game_type, config } => {
+                    game_type.encode_size() + config.encode_size()
+                }
+
+                Self::CasinoRngTranscript {
+                    session_id,
+                    move_number,
+                    draws,
+                } => session_id.encode_size() + move_number.encode_size() + draws.encode_size(),
+
+                Self::CasinoSideBetSettled {
+                    session_id,
+                    bet_id,
+                    amount,
+                    payout,
+                } => {
+                    session_id.encode_size()
+                        + bet_id.encode_size()
+                        + amount.encode_size()
+                        + payout.encode_size()
+                }
+
+                Self::GameStatsUpdated { game_type, stats } => {
+                    game_type.encode_size() + stats.encode_size()
+                }
+
+                Self::BonusGranted {
+                    player,
+                    amount,
+                    bonus_chips,
+                    wagering_remaining,
+                } => {
+                    player.encode_size()
+                        + amount.encode_size()
+                        + bonus_chips.encode_size()
+                        + wagering_remaining.encode_size()
+                }
+                Self::BonusReleased {
+                    player,
+                    amount,
+                    chips,
+                } => player.encode_size() + amount.encode_size() + chips.encode_size(),
+
+                Self::TournamentRulesSet {
+                    tournament_id,
+                    rules,
+                } => tournament_id.encode_size() + rules.encode_size(),
+
+                Self::CasinoCardDealt {
+                    session_id,
+                    move_number,
+                    card,
+                    to,
+                } => {
+                    session_id.encode_size()
+                        + move_number.encode_size()
+                        + card.encode_size()
+                        + to.encode_size()
+                }
+                Self::CasinoCrapsRolled {
+                    session_id,
+                    move_number,
+                    d1,
+                    d2,
+                    resolved_bets,
+                } => {
+                    session_id.encode_size()
+                        + move_number.encode_size()
+                        + d1.encode_size()
+                        + d2.encode_size()
+                        + resolved_bets.encode_size()
+                }
+
+                Self::EconomyParamsUpdated { .. } => crate::casino::EconomyParams::SIZE,
+                Self::AdminSet { admin, .. } => {
+                    crate::casino::AdminRole::SIZE + admin.encode_size()
+                }
+                Self::HouseSettingsUpdated { settings } => settings.encode_size(),
+
                 Self::Staked {
                     player,
                     amount,
@@ -1829,7 +5739,134 @@ impl EncodeSize for Event {
                 Self::RewardsClaimed { player, amount } => {
                     player.encode_size() + amount.encode_size()
                 }
+
+                // Fee events (tags 41-42)
+                Self::FeeCharged {
+                    player,
+                    amount,
+                    new_chips,
+                    accumulated_fees,
+                } => {
+                    player.encode_size()
+                        + amount.encode_size()
+                        + new_chips.encode_size()
+                        + accumulated_fees.encode_size()
+                }
+                Self::InterestAccrued {
+                    player,
+                    amount,
+                    new_debt,
+                    effective_rate_bps,
+                    rate_bps,
+                } => {
+                    player.encode_size()
+                        + amount.encode_size()
+                        + new_debt.encode_size()
+                        + effective_rate_bps.encode_size()
+                        + rate_bps.encode_size()
+                }
+
+                Self::Unknown { data, .. } => data.len(),
+            }
+    }
+}
+
+impl Event {
+    /// Write the event in the envelope of `version` (see [EVENT_VERSION]).
+    pub fn write_envelope(&self, version: u8, writer: &mut impl BufMut) {
+        (EVENT_VERSION_FLAG | version).write(writer);
+        UInt(self.body_size() as u32).write(writer);
+        self.write_body(writer);
+    }
+
+    /// Size of the event in an envelope.
+    pub fn envelope_size(&self) -> usize {
+        let body_size = self.body_size();
+        u8::SIZE + UInt(body_size as u32).encode_size() + body_size
+    }
+
+    /// Read an event in an envelope, returning the envelope version with it.
+    fn read_envelope(reader: &mut impl Buf) -> Result<(u8, Self), Error> {
+        let version = u8::read(reader)?;
+        if version & EVENT_VERSION_FLAG == 0 {
+            return Err(Error::Invalid("Event", "missing envelope"));
+        }
+        let version = version & !EVENT_VERSION_FLAG;
+        if version == 0 {
+            return Err(Error::Invalid("Event", "invalid version"));
+        }
+        let len: u32 = UInt::read(reader)?.into();
+        let len = len as usize;
+        if len == 0 || len > MAX_EVENT_SIZE {
+            return Err(Error::Invalid("Event", "invalid length"));
+        }
+        if reader.remaining() < len {
+            return Err(Error::EndOfBuffer);
+        }
+        let mut body = vec![0u8; len];
+        reader.copy_to_slice(&mut body);
+
+        // Events of a version this release reads must decode exactly
+        if version <= EVENT_VERSION {
+            let mut fields = &body[..];
+            if let Some(event) = Self::read_body(&mut fields)? {
+                if fields.has_remaining() {
+                    return Err(Error::Invalid("Event", "trailing bytes"));
+                }
+                return Ok((version, event));
             }
+        }
+        Ok((
+            version,
+            Self::Unknown {
+                version,
+                tag: body[0],
+                data: body[1..].to_vec(),
+            },
+        ))
+    }
+
+    /// Whether the next encoded event in `reader` is in an envelope.
+    fn is_envelope(reader: &impl Buf) -> Result<bool, Error> {
+        let Some(&first) = reader.chunk().first() else {
+            return Err(Error::EndOfBuffer);
+        };
+        Ok(first & EVENT_VERSION_FLAG != 0)
+    }
+}
+
+impl Write for Event {
+    /// Writes the legacy encoding (events are only wrapped in an envelope once it activates,
+    /// see [Output::versioned]). Unknown events keep the envelope they were read in.
+    fn write(&self, writer: &mut impl BufMut) {
+        match self {
+            Self::Unknown { version, .. } => self.write_envelope(*version, writer),
+            _ => self.write_body(writer),
+        }
+    }
+}
+
+impl Read for Event {
+    type Cfg = ();
+
+    fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
+        // Legacy events start with their tag
+        let Some(&first) = reader.chunk().first() else {
+            return Err(Error::EndOfBuffer);
+        };
+        if first & EVENT_VERSION_FLAG != 0 {
+            return Ok(Self::read_envelope(reader)?.1);
+        }
+        Self::read_body(reader)?.ok_or(Error::InvalidEnum(first))
+    }
+}
+
+impl EncodeSize for Event {
+    fn encode_size(&self) -> usize {
+        match self {
+            Self::Unknown { .. } => self.envelope_size(),
+            _ => self.body_size(),
+        }
     }
 }
 
@@ -1837,7 +5874,35 @@ impl EncodeSize for Event {
 pub enum Output {
     Event(Event),
     Transaction(Transaction),
-    Commit { height: u64, start: u64 },
+    Commit {
+        height: u64,
+        start: u64,
+    },
+    /// An event in the envelope of `version` (written once the version activates, see
+    /// [event_version]).
+    VersionedEvent {
+        version: u8,
+        event: Event,
+    },
+}
+
+impl Output {
+    /// The event in the output, in either encoding.
+    pub fn event(&self) -> Option<&Event> {
+        match self {
+            Self::Event(event) | Self::VersionedEvent { event, .. } => Some(event),
+            _ => None,
+        }
+    }
+
+    /// Encode an event output in the envelope of `version` (version 0 keeps the legacy
+    /// encoding, and other outputs are unchanged).
+    pub fn versioned(self, version: u8) -> Self {
+        match self {
+            Self::Event(event) if version > 0 => Self::VersionedEvent { version, event },
+            output => output,
+        }
+    }
 }
 
 impl Write for Output {
@@ -1856,6 +5921,10 @@ impl Write for Output {
                 height.write(writer);
                 start.write(writer);
             }
+            Self::VersionedEvent { version, event } => {
+                0u8.write(writer);
+                event.write_envelope(*version, writer);
+            }
         }
     }
 }
@@ -1866,7 +5935,13 @@ impl Read for Output {
     fn read_cfg(reader: &mut impl Buf, _: &Self::Cfg) -> Result<Self, Error> {
         let kind = u8::read(reader)?;
         match kind {
-            0 => Ok(Self::Event(Event::read(reader)?)),
+            0 => {
+                if !Event::is_envelope(reader)? {
+                    return Ok(Self::Event(Event::read(reader)?));
+                }
+                let (version, event) = Event::read_envelope(reader)?;
+                Ok(Self::VersionedEvent { version, event })
+            }
             1 => Ok(Self::Transaction(Transaction::read(reader)?)),
             2 => Ok(Self::Commit {
                 height: u64::read(reader)?,
@@ -1883,6 +5958,7 @@ impl EncodeSize for Output {
             Self::Event(event) => event.encode_size(),
             Self::Transaction(transaction) => transaction.encode_size(),
             Self::Commit { height, start } => height.encode_size() + start.encode_size(),
+            Self::VersionedEvent { event, .. } => event.envelope_size(),
         }
     }
 }
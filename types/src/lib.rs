@@ -1,6 +1,10 @@
 pub mod api;
 pub mod casino;
 pub mod execution;
+#[cfg(feature = "serde")]
+pub mod serde_decimal;
+#[cfg(feature = "serde")]
+pub mod serde_hex;
 pub mod token;
 
 pub use api::*;
@@ -0,0 +1,22 @@
+//! Serde representation of wide integers as decimal strings (with the `serde` feature).
+//!
+//! JSON numbers can't carry a `u128` or `i128` losslessly (and `serde_json::Value` rejects
+//! them outright), so fields of these types use `#[serde(with = "crate::serde_decimal")]`.
+
+use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+use std::{fmt::Display, str::FromStr};
+
+pub fn serialize<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromStr,
+    T::Err: Display,
+    D: Deserializer<'de>,
+{
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(D::Error::custom)
+}
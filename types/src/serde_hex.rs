@@ -0,0 +1,112 @@
+//! Serde representations of keys, digests and byte strings as hex (with the `serde` feature).
+//!
+//! Fields use these through `#[serde(with = "...")]`: the module itself handles a single value
+//! (anything with a codec, such as a [commonware_cryptography::ed25519::PublicKey] or a
+//! [commonware_cryptography::sha256::Digest]), and the submodules handle optional values, lists,
+//! lists of pairs and raw bytes.
+
+use commonware_codec::{DecodeExt, Encode};
+use commonware_utils::{from_hex, hex};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+/// A value represented by the hex of its encoding.
+struct Hex<T>(T);
+
+impl<T: Encode> Serialize for Hex<&T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex(&self.0.encode()))
+    }
+}
+
+impl<'de, T: DecodeExt<()>> Deserialize<'de> for Hex<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = bytes::deserialize(deserializer)?;
+        T::decode(bytes.as_slice())
+            .map(Hex)
+            .map_err(D::Error::custom)
+    }
+}
+
+pub fn serialize<T: Encode, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    Hex(value).serialize(serializer)
+}
+
+pub fn deserialize<'de, T: DecodeExt<()>, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    Ok(Hex::deserialize(deserializer)?.0)
+}
+
+/// An optional value (`null` if absent).
+pub mod option {
+    use super::*;
+
+    pub fn serialize<T: Encode, S: Serializer>(
+        value: &Option<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.as_ref().map(Hex).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: DecodeExt<()>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<T>, D::Error> {
+        Ok(Option::<Hex<T>>::deserialize(deserializer)?.map(|value| value.0))
+    }
+}
+
+/// A list of values.
+pub mod vec {
+    use super::*;
+
+    pub fn serialize<T: Encode, S: Serializer>(
+        values: &[T],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(Hex))
+    }
+
+    pub fn deserialize<'de, T: DecodeExt<()>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<T>, D::Error> {
+        Ok(Vec::<Hex<T>>::deserialize(deserializer)?
+            .into_iter()
+            .map(|value| value.0)
+            .collect())
+    }
+}
+
+/// A list of pairs whose first element is hex (e.g. rankings of players and their chips).
+pub mod pairs {
+    use super::*;
+
+    pub fn serialize<T: Encode, V: Serialize, S: Serializer>(
+        pairs: &[(T, V)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(pairs.iter().map(|(key, value)| (Hex(key), value)))
+    }
+
+    pub fn deserialize<'de, T: DecodeExt<()>, V: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(T, V)>, D::Error> {
+        Ok(Vec::<(Hex<T>, V)>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(key, value)| (key.0, value))
+            .collect())
+    }
+}
+
+/// Raw bytes (such as a game's state blob).
+pub mod bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        from_hex(&encoded).ok_or_else(|| D::Error::custom("invalid hex"))
+    }
+}
@@ -215,7 +215,6 @@ export default function CasinoApp() {
                 currentChips={stats.chips}
                 prizePool={playMode === 'FREEROLL' ? (freerollActivePrizePool ?? undefined) : undefined}
                 totalPlayers={playMode === 'FREEROLL' ? (freerollActivePlayerCount ?? undefined) : undefined}
-                winnersPct={0.15}
              />
           )}
        </div>
@@ -116,6 +116,10 @@ export default function LiquidityApp() {
           if (e?.player?.toLowerCase?.() !== pkHexLower) return;
           pushActivity(`ERROR: ${e.message ?? 'Unknown error'}`);
         });
+        client.onEvent('InstructionFailed', (e: any) => {
+          if (e?.player?.toLowerCase?.() !== pkHexLower) return;
+          pushActivity(`ERROR: instruction ${e.kind} rejected (code ${e.code})`);
+        });
         client.onEvent('VaultCreated', (e: any) => {
           if (e?.player?.toLowerCase?.() !== pkHexLower) return;
           pushActivity('Vault created');
@@ -87,6 +87,10 @@ export default function StakingApp() {
           if (e?.player?.toLowerCase?.() !== pkHexLower) return;
           pushActivity(`ERROR: ${e.message ?? 'Unknown error'}`);
         });
+        client.onEvent('InstructionFailed', (e: any) => {
+          if (e?.player?.toLowerCase?.() !== pkHexLower) return;
+          pushActivity(`ERROR: instruction ${e.kind} rejected (code ${e.code})`);
+        });
         client.onEvent('Staked', (e: any) => {
           if (e?.player?.toLowerCase?.() !== pkHexLower) return;
           pushActivity(`Staked: +${e.amount} (unlock @ ${e.unlockTs ?? e.unlock_ts ?? '—'})`);
@@ -160,8 +164,16 @@ export default function StakingApp() {
     const totalStaked = BigInt(house?.totalStakedAmount ?? 0);
 
     const view = currentView ?? 0;
-    const locked = unlockTs > 0 && view < unlockTs;
-    const remainingBlocks = locked ? unlockTs - view : 0;
+    const tranches: any[] = staker?.tranches ?? [];
+    const unlocked = tranches
+      .filter((t) => Number(t.unlockTs ?? 0) <= view)
+      .reduce((sum, t) => sum + BigInt(t.amount ?? 0), 0n);
+    const locked = staked > 0n && unlocked === 0n;
+    const nextUnlock = tranches
+      .map((t) => Number(t.unlockTs ?? 0))
+      .filter((ts) => ts > view)
+      .reduce((min, ts) => Math.min(min, ts), unlockTs);
+    const remainingBlocks = locked && nextUnlock > view ? nextUnlock - view : 0;
 
     const shareBps = totalVp > 0n ? Number((vp * 10_000n) / totalVp) : 0;
     const stakedShareBps = totalStaked > 0n ? Number((staked * 10_000n) / totalStaked) : 0;
@@ -172,6 +184,7 @@ export default function StakingApp() {
       vp,
       totalVp,
       totalStaked,
+      unlocked,
       locked,
       remainingBlocks,
       shareBps,
@@ -218,9 +231,10 @@ export default function StakingApp() {
     const client = clientRef.current as any;
     if (!client?.nonceManager) throw new Error('Client not ready');
     await ensureRegistered();
-    const result = await client.nonceManager.submitUnstake();
+    const amount = derived.unlocked;
+    const result = await client.nonceManager.submitUnstake(amount.toString());
     if (result?.txHash) setLastTxSig(result.txHash);
-    pushActivity('Submitted unstake');
+    pushActivity(`Submitted unstake (amount=${amount})`);
   };
 
   const claimRewards = async () => {
@@ -624,6 +624,25 @@ export class CasinoClient {
     return null;
   }
 
+  /**
+   * Get the final standings of a past leaderboard season.
+   * @param {bigint|number} season - Season number
+   * @returns {Promise<Object|null>} CasinoLeaderboard data or null if the season hasn't ended
+   */
+  async getLeaderboardSeason(season) {
+    const keyBytes = this.wasm.encodeLeaderboardSeasonKey(season);
+    const result = await this.queryState(keyBytes);
+
+    if (result.found && result.value) {
+      if (result.value.type === 'CasinoLeaderboard') {
+        return result.value;
+      }
+      return null;
+    }
+
+    return null;
+  }
+
   /**
    * Get casino tournament information by tournament ID.
    * @param {bigint|number} tournamentId - Tournament ID
@@ -646,6 +665,25 @@ export class CasinoClient {
     return null;
   }
 
+  /**
+   * Get the recent game results of an account.
+   * @param {Uint8Array} publicKeyBytes - Account public key
+   * @returns {Promise<Object|null>} GameHistory data (oldest record first) or null if not found
+   */
+  async getGameHistory(publicKeyBytes) {
+    const keyBytes = this.wasm.encodeGameHistoryKey(publicKeyBytes);
+    const result = await this.queryState(keyBytes);
+
+    if (result.found && result.value) {
+      if (result.value.type === 'GameHistory') {
+        return snakeToCamel(result.value);
+      }
+      return null;
+    }
+
+    return null;
+  }
+
   /**
    * Get vault state for an account.
    * @param {Uint8Array} publicKeyBytes - Account public key
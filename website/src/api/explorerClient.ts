@@ -1,3 +1,5 @@
+import { initWasm } from './wasm.js';
+
 export interface ExplorerBlock {
   height: number;
   view: number;
@@ -15,6 +17,7 @@ export interface ExplorerTransaction {
   position: number;
   public_key: string;
   nonce: number;
+  kind: string;
   description?: string | null;
   instruction: string;
 }
@@ -27,6 +30,12 @@ export interface AccountActivity {
   last_updated_height?: number | null;
 }
 
+export interface Page<T> {
+  items: T[];
+  next_cursor?: string | null;
+  total: number;
+}
+
 const API_BASE = '/api';
 
 async function getJson<T>(path: string): Promise<T> {
@@ -37,12 +46,15 @@ async function getJson<T>(path: string): Promise<T> {
   return res.json();
 }
 
-export async function fetchBlocks(offset = 0, limit = 20): Promise<{
-  blocks: ExplorerBlock[];
-  next_offset?: number | null;
-  total: number;
-}> {
-  return getJson(`/explorer/blocks?offset=${offset}&limit=${limit}`);
+// Query string for a page (starting at the `next_cursor` of the previous page, if any)
+async function pageQuery(cursor: string | null, limit: number): Promise<string> {
+  const wasm = await initWasm();
+  const query = wasm.encode_page_request(cursor ?? undefined, limit);
+  return query ? `?${query}` : '';
+}
+
+export async function fetchBlocks(cursor: string | null = null, limit = 20): Promise<Page<ExplorerBlock>> {
+  return getJson(`/explorer/blocks${await pageQuery(cursor, limit)}`);
 }
 
 export async function fetchBlock(id: string | number): Promise<ExplorerBlock> {
@@ -57,6 +69,14 @@ export async function fetchAccount(pubkey: string): Promise<AccountActivity> {
   return getJson(`/explorer/account/${pubkey}`);
 }
 
+export async function fetchAccountTransactions(
+  pubkey: string,
+  cursor: string | null = null,
+  limit = 20
+): Promise<Page<ExplorerTransaction>> {
+  return getJson(`/explorer/account/${pubkey}/txs${await pageQuery(cursor, limit)}`);
+}
+
 export async function searchExplorer(
   query: string
 ): Promise<
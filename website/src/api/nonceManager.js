@@ -468,11 +468,33 @@ export class NonceManager {
     );
   }
 
+  /**
+   * Submit a profile update transaction.
+   * @param {string} displayName - Name shown instead of the registration name
+   * @param {number} avatarId - Avatar to show
+   * @param {string} title - Title shown with the name (may be empty)
+   * @returns {Promise<{status: string, txHash?: string}>} Transaction result
+   */
+  async submitUpdateProfile(displayName, avatarId, title) {
+    return this.submitTransaction(
+      (nonce) => this.wasm.createUpdateProfileTransaction(nonce, displayName, avatarId, title),
+      'updateProfile'
+    );
+  }
+
+  /**
+   * Session ID of a game started by the next transaction (derived from its nonce).
+   * @returns {bigint} The session ID
+   */
+  nextSessionId() {
+    return this.wasm.deriveSessionId(this.getNextNonce());
+  }
+
   /**
    * Submit a casino start game transaction.
    * @param {number} gameType - The game type (0-9)
    * @param {bigint} bet - The bet amount
-   * @param {bigint} sessionId - The session ID
+   * @param {bigint} sessionId - The session ID (see nextSessionId)
    * @returns {Promise<{status: string}>} Transaction result
    */
   async submitCasinoStartGame(gameType, bet, sessionId) {
@@ -495,6 +517,18 @@ export class NonceManager {
     );
   }
 
+  /**
+   * Submit a casino abandon session transaction (for a session without a recent move).
+   * @param {bigint} sessionId - The session ID
+   * @returns {Promise<{status: string, txHash?: string}>} Transaction result
+   */
+  async submitCasinoAbandonSession(sessionId) {
+    return this.submitTransaction(
+      (nonce) => this.wasm.createCasinoAbandonSessionTransaction(nonce, sessionId),
+      'casinoAbandonSession'
+    );
+  }
+
   /**
    * Submit a casino toggle shield transaction.
    * @returns {Promise<{status: string}>} Transaction result
@@ -540,6 +574,17 @@ export class NonceManager {
     );
   }
 
+  /**
+   * Submit a daily bonus claim transaction.
+   * @returns {Promise<{status: string, txHash?: string}>} Transaction result
+   */
+  async submitClaimDailyBonus() {
+    return this.submitTransaction(
+      (nonce) => this.wasm.createClaimDailyBonusTransaction(nonce),
+      'claimDailyBonus'
+    );
+  }
+
   /**
    * Submit a casino join tournament transaction.
    * @param {bigint|number} tournamentId - Tournament ID
@@ -566,6 +611,20 @@ export class NonceManager {
     );
   }
 
+  /**
+   * Submit a casino schedule tournament transaction.
+   * @param {bigint|number} tournamentId - Tournament ID
+   * @param {bigint|number} startView - View the tournament starts at
+   * @param {bigint|number} endView - View the tournament ends at
+   * @returns {Promise<{status: string, txHash?: string}>} Transaction result
+   */
+  async submitCasinoScheduleTournament(tournamentId, startView, endView) {
+    return this.submitTransaction(
+      (nonce) => this.wasm.createCasinoScheduleTournamentTransaction(nonce, tournamentId, startView, endView),
+      'casinoScheduleTournament'
+    );
+  }
+
   /**
    * Submit a casino end tournament transaction.
    * @param {bigint|number} tournamentId - Tournament ID
@@ -601,6 +660,18 @@ export class NonceManager {
     );
   }
 
+  /**
+   * Submit a withdraw collateral transaction.
+   * @param {bigint|number} amount - Amount of RNG to unlock from the vault
+   * @returns {Promise<{status: string, txHash?: string}>} Transaction result
+   */
+  async submitWithdrawCollateral(amount) {
+    return this.submitTransaction(
+      (nonce) => this.wasm.createWithdrawCollateralTransaction(nonce, amount),
+      'withdrawCollateral'
+    );
+  }
+
   /**
    * Submit a borrow vUSDT transaction.
    * @param {bigint|number} amount - Amount of vUSDT to borrow
@@ -664,6 +735,46 @@ export class NonceManager {
     );
   }
 
+  /**
+   * Submit a limit order (filled against the AMM once its price is reached).
+   * @param {bigint|number} amountIn - Amount of input token to escrow
+   * @param {bigint|number} minPrice - Minimum output per unit of input (scaled by 1e12)
+   * @param {boolean} isBuyingRng - True to swap vUSDT->RNG, false to swap RNG->vUSDT
+   * @returns {Promise<{status: string, txHash?: string}>} Transaction result
+   */
+  async submitPlaceLimitOrder(amountIn, minPrice, isBuyingRng) {
+    return this.submitTransaction(
+      (nonce) => this.wasm.createPlaceLimitOrderTransaction(nonce, amountIn, minPrice, isBuyingRng),
+      'placeLimitOrder'
+    );
+  }
+
+  /**
+   * Submit a cancel limit order transaction.
+   * @param {bigint|number} orderId - Order to cancel (refunding its escrow)
+   * @returns {Promise<{status: string, txHash?: string}>} Transaction result
+   */
+  async submitCancelLimitOrder(orderId) {
+    return this.submitTransaction(
+      (nonce) => this.wasm.createCancelLimitOrderTransaction(nonce, orderId),
+      'cancelLimitOrder'
+    );
+  }
+
+  /**
+   * Submit a single-sided add liquidity transaction (swapping part of the input).
+   * @param {bigint|number} amountIn - Amount of the input token
+   * @param {bigint|number} minShares - Minimum LP shares to mint (slippage protection)
+   * @param {boolean} fromRng - True to provide RNG, false to provide vUSDT
+   * @returns {Promise<{status: string, txHash?: string}>} Transaction result
+   */
+  async submitZapLiquidity(amountIn, minShares, fromRng) {
+    return this.submitTransaction(
+      (nonce) => this.wasm.createZapLiquidityTransaction(nonce, amountIn, minShares, fromRng),
+      'zapLiquidity'
+    );
+  }
+
   /**
    * Submit a stake transaction.
    * @param {bigint|number} amount - Amount of RNG to stake
@@ -679,11 +790,12 @@ export class NonceManager {
 
   /**
    * Submit an unstake transaction.
+   * @param {bigint|number} amount - Amount of unlocked RNG to unstake
    * @returns {Promise<{status: string, txHash?: string}>} Transaction result
    */
-  async submitUnstake() {
+  async submitUnstake(amount) {
     return this.submitTransaction(
-      (nonce) => this.wasm.createUnstakeTransaction(nonce),
+      (nonce) => this.wasm.createUnstakeTransaction(nonce, amount),
       'unstake'
     );
   }
@@ -80,16 +80,36 @@ export class WasmWrapper {
     return this.wasm.encode_casino_session_key(BigInt(sessionId));
   }
 
+  // Derive the session ID a start game transaction with this nonce must use
+  deriveSessionId(nonce) {
+    return this.wasm.derive_session_id(this.getPublicKeyBytes(), BigInt(nonce));
+  }
+
+  // Describe every instruction kind (name, label, parameters and the state it can change)
+  getInstructionCatalog() {
+    return this.wasm.get_instruction_catalog();
+  }
+
   // Encode casino leaderboard key
   encodeCasinoLeaderboardKey() {
     return this.wasm.encode_casino_leaderboard_key();
   }
 
+  // Encode archived leaderboard season key
+  encodeLeaderboardSeasonKey(season) {
+    return this.wasm.encode_leaderboard_season_key(BigInt(season));
+  }
+
   // Encode casino tournament key
   encodeCasinoTournamentKey(tournamentId) {
     return this.wasm.encode_casino_tournament_key(BigInt(tournamentId));
   }
 
+  // Encode game history key
+  encodeGameHistoryKey(publicKeyBytes) {
+    return this.wasm.encode_game_history_key(publicKeyBytes);
+  }
+
   // Encode vault key
   encodeVaultKey(publicKeyBytes) {
     return this.wasm.encode_vault_key(publicKeyBytes);
@@ -282,6 +302,19 @@ export class WasmWrapper {
     return tx.encode();
   }
 
+  // Create a casino abandon session transaction
+  createCasinoAbandonSessionTransaction(nonce, sessionId) {
+    if (!this.keypair) {
+      throw new Error('Keypair not initialized');
+    }
+    const tx = this.wasm.Transaction.casino_abandon_session(
+      this.keypair,
+      BigInt(nonce),
+      BigInt(sessionId)
+    );
+    return tx.encode();
+  }
+
   // Create a casino toggle shield transaction
   createCasinoToggleShieldTransaction(nonce) {
     if (!this.keypair) {
@@ -331,6 +364,21 @@ export class WasmWrapper {
     return tx.encode();
   }
 
+  // Create a profile update transaction
+  createUpdateProfileTransaction(nonce, displayName, avatarId, title) {
+    if (!this.keypair) {
+      throw new Error('Keypair not initialized');
+    }
+    const tx = this.wasm.Transaction.update_profile(
+      this.keypair,
+      BigInt(nonce),
+      displayName,
+      avatarId,
+      title
+    );
+    return tx.encode();
+  }
+
   // Create a casino join tournament transaction
   createCasinoJoinTournamentTransaction(nonce, tournamentId) {
     if (!this.keypair) {
@@ -359,6 +407,21 @@ export class WasmWrapper {
     return tx.encode();
   }
 
+  // Create a casino schedule tournament transaction
+  createCasinoScheduleTournamentTransaction(nonce, tournamentId, startView, endView) {
+    if (!this.keypair) {
+      throw new Error('Keypair not initialized');
+    }
+    const tx = this.wasm.Transaction.casino_schedule_tournament(
+      this.keypair,
+      BigInt(nonce),
+      BigInt(tournamentId),
+      BigInt(startView),
+      BigInt(endView)
+    );
+    return tx.encode();
+  }
+
   // Create a casino deposit transaction (dev faucet / testing)
   createCasinoDepositTransaction(nonce, amount) {
     if (!this.keypair) {
@@ -372,6 +435,18 @@ export class WasmWrapper {
     return tx.encode();
   }
 
+  // Create a daily bonus claim transaction
+  createClaimDailyBonusTransaction(nonce) {
+    if (!this.keypair) {
+      throw new Error('Keypair not initialized');
+    }
+    const tx = this.wasm.Transaction.claim_daily_bonus(
+      this.keypair,
+      BigInt(nonce)
+    );
+    return tx.encode();
+  }
+
   // Create a casino end tournament transaction
   createCasinoEndTournamentTransaction(nonce, tournamentId) {
     if (!this.keypair) {
@@ -400,13 +475,14 @@ export class WasmWrapper {
   }
 
   // Create an unstake transaction
-  createUnstakeTransaction(nonce) {
+  createUnstakeTransaction(nonce, amount) {
     if (!this.keypair) {
       throw new Error('Keypair not initialized');
     }
     const tx = this.wasm.Transaction.unstake(
       this.keypair,
-      BigInt(nonce)
+      BigInt(nonce),
+      BigInt(amount)
     );
     return tx.encode();
   }
@@ -460,6 +536,19 @@ export class WasmWrapper {
     return tx.encode();
   }
 
+  // Create a withdraw collateral transaction
+  createWithdrawCollateralTransaction(nonce, amount) {
+    if (!this.keypair) {
+      throw new Error('Keypair not initialized');
+    }
+    const tx = this.wasm.Transaction.withdraw_collateral(
+      this.keypair,
+      BigInt(nonce),
+      BigInt(amount)
+    );
+    return tx.encode();
+  }
+
   // Create a borrow vUSDT transaction
   createBorrowUsdtTransaction(nonce, amount) {
     if (!this.keypair) {
@@ -527,4 +616,47 @@ export class WasmWrapper {
     );
     return tx.encode();
   }
+
+  // Create a place limit order transaction
+  createPlaceLimitOrderTransaction(nonce, amountIn, minPrice, isBuyingRng) {
+    if (!this.keypair) {
+      throw new Error('Keypair not initialized');
+    }
+    const tx = this.wasm.Transaction.place_limit_order(
+      this.keypair,
+      BigInt(nonce),
+      BigInt(amountIn),
+      BigInt(minPrice),
+      !!isBuyingRng
+    );
+    return tx.encode();
+  }
+
+  // Create a cancel limit order transaction
+  createCancelLimitOrderTransaction(nonce, orderId) {
+    if (!this.keypair) {
+      throw new Error('Keypair not initialized');
+    }
+    const tx = this.wasm.Transaction.cancel_limit_order(
+      this.keypair,
+      BigInt(nonce),
+      BigInt(orderId)
+    );
+    return tx.encode();
+  }
+
+  // Create a single-sided (zap) add liquidity transaction
+  createZapLiquidityTransaction(nonce, amountIn, minShares, fromRng) {
+    if (!this.keypair) {
+      throw new Error('Keypair not initialized');
+    }
+    const tx = this.wasm.Transaction.zap_liquidity(
+      this.keypair,
+      BigInt(nonce),
+      BigInt(amountIn),
+      BigInt(minShares),
+      !!fromRng
+    );
+    return tx.encode();
+  }
 }
@@ -138,23 +138,25 @@ interface SidebarProps {
     currentChips?: number;
     prizePool?: number;
     totalPlayers?: number;
-    winnersPct?: number;
+    payoutSharesBps?: number[];
 }
 
-export const Sidebar: React.FC<SidebarProps> = ({ leaderboard, history, viewMode = 'RANK', currentChips, prizePool, totalPlayers, winnersPct = 0.15 }) => {
+export const Sidebar: React.FC<SidebarProps> = ({ leaderboard, history, viewMode = 'RANK', currentChips, prizePool, totalPlayers, payoutSharesBps = [5000, 3000, 2000] }) => {
     const effectivePlayerCount = totalPlayers ?? leaderboard.length;
-    const bubbleIndex = Math.max(1, Math.min(effectivePlayerCount, Math.ceil(effectivePlayerCount * winnersPct))); // Top 15% by default
+    const bubbleIndex = Math.max(1, Math.min(effectivePlayerCount, payoutSharesBps.length)); // House payout table (50/30/20 by default)
     const userEntry = leaderboard.find(e => e.name === 'YOU' || e.name.includes('(YOU)'));
 
     const getPayout = (rank: number) => {
         if (!prizePool || effectivePlayerCount <= 0) return "$0";
         if (rank > bubbleIndex) return "$0";
 
+        // Mirrors the chain: shares of the paid places are scaled to pay out the whole pool
         let totalWeight = 0;
-        for (let i = 1; i <= bubbleIndex; i++) {
-            totalWeight += 1 / i;
+        for (let i = 0; i < bubbleIndex; i++) {
+            totalWeight += payoutSharesBps[i];
         }
-        const payout = Math.floor(((1 / rank) / totalWeight) * prizePool);
+        if (totalWeight <= 0) return "$0";
+        const payout = Math.floor((payoutSharesBps[rank - 1] / totalWeight) * prizePool);
         return `$${payout.toLocaleString()}`;
     };
 
@@ -47,10 +47,10 @@ export const ModeSelectView: React.FC<ModeSelectViewProps> = ({ onSelect }) => {
             <div className="text-terminal-accent font-bold tracking-widest mb-2">FREEROLL</div>
             <div className="text-xs text-gray-400 leading-relaxed">
               Join 5-minute tournaments (up to 5 entries/day). Start with 1,000 chips.
-              Top 15% share the RNG daily emission.
+              The top 3 share the RNG daily emission (50/30/20).
             </div>
             <div className="text-[10px] text-gray-600 mt-3 tracking-widest">
-              TOURNAMENTS • TOP 15% PAID
+              TOURNAMENTS • TOP 3 PAID
             </div>
           </button>
         </div>
@@ -3,6 +3,7 @@ import React, { useCallback } from 'react';
 import { GameState, Card } from '../../../types';
 import { Hand } from '../GameComponents';
 import { getHiLoRank } from '../../../utils/gameUtils';
+import { HILO_MULTIPLIERS } from '../../../utils/gameStateParser';
 
 interface HiLoViewProps {
     gameState: GameState;
@@ -19,13 +20,11 @@ export const HiLoView = React.memo<HiLoViewProps>(({ gameState, deck }) => {
             const currentCard = gameState.playerCards[gameState.playerCards.length - 1];
             if (!currentCard) return '0.00x';
 
-            // Match on-chain hilo.rs `calculate_multiplier`:
-            // multiplier_bps = floor(13 * 10000 / wins)
+            // Match the on-chain ladder steps (hilo.rs `MULTIPLIERS`)
             const rank = getHiLoRank(currentCard); // 1..13
-            const wins = guess === 'HIGHER' ? (13 - rank) : (rank - 1);
-            if (wins <= 0) return '—';
+            const bps = HILO_MULTIPLIERS[rank - 1]?.[guess === 'HIGHER' ? 0 : 1] ?? 0;
+            if (bps <= 0) return '—';
 
-            const bps = Math.floor((13 * 10_000) / wins);
             return (bps / 10_000).toFixed(2) + 'x';
         },
         [gameState.playerCards]
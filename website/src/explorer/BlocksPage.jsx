@@ -4,22 +4,22 @@ import { fetchBlocks } from '../api/explorerClient';
 
 export default function BlocksPage() {
   const [blocks, setBlocks] = useState([]);
-  const [nextOffset, setNextOffset] = useState(null);
+  const [nextCursor, setNextCursor] = useState(null);
   const [loading, setLoading] = useState(true);
 
-  const load = async (offset = 0) => {
+  const load = async (cursor = null) => {
     setLoading(true);
     try {
-      const data = await fetchBlocks(offset, 20);
-      setBlocks(data.blocks);
-      setNextOffset(data.next_offset ?? null);
+      const data = await fetchBlocks(cursor, 20);
+      setBlocks(data.items);
+      setNextCursor(data.next_cursor ?? null);
     } finally {
       setLoading(false);
     }
   };
 
   useEffect(() => {
-    load(0);
+    load();
   }, []);
 
   if (loading) {
@@ -32,7 +32,7 @@ export default function BlocksPage() {
         <h1 className="text-xl font-semibold">Recent Blocks</h1>
         <button
           className="text-terminal-green text-sm hover:underline"
-          onClick={() => load(0)}
+          onClick={() => load()}
         >
           Refresh
         </button>
@@ -65,10 +65,10 @@ export default function BlocksPage() {
           </tbody>
         </table>
       </div>
-      {nextOffset !== null && (
+      {nextCursor !== null && (
         <div className="flex justify-end">
           <button
-            onClick={() => load(nextOffset)}
+            onClick={() => load(nextCursor)}
             className="px-3 py-2 bg-gray-900 border border-gray-800 rounded text-sm"
           >
             Next
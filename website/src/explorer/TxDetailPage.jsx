@@ -10,7 +10,6 @@ function getInstructionDescriptionFallback(instruction) {
   if (trimmed === 'CasinoToggleShield') return 'Toggle shield modifier';
   if (trimmed === 'CasinoToggleDouble') return 'Toggle double modifier';
   if (trimmed === 'CasinoToggleSuper') return 'Toggle super mode';
-  if (trimmed === 'Unstake') return 'Unstake';
   if (trimmed === 'ClaimRewards') return 'Claim staking rewards';
   if (trimmed === 'ProcessEpoch') return 'Process epoch';
   if (trimmed === 'CreateVault') return 'Create vault';
@@ -50,6 +49,9 @@ function getInstructionDescriptionFallback(instruction) {
   match = trimmed.match(/^Stake\s*\{\s*amount:\s*(\d+)\s*,\s*duration:\s*(\d+)\s*\}$/);
   if (match) return `Stake ${match[1]} RNG for ${match[2]} blocks`;
 
+  match = trimmed.match(/^Unstake\s*\{\s*amount:\s*(\d+)\s*\}$/);
+  if (match) return `Unstake ${match[1]} RNG`;
+
   match = trimmed.match(/^DepositCollateral\s*\{\s*amount:\s*(\d+)\s*\}$/);
   if (match) return `Deposit ${match[1]} RNG as collateral`;
 
@@ -2,7 +2,7 @@
 import { useState, useEffect, useRef } from 'react';
 import { GameType, PlayerStats, GameState, Card, LeaderboardEntry, TournamentPhase, CompletedHand, CrapsBet, RouletteBet, SicBoBet, BaccaratBet } from '../types';
 import { GameType as ChainGameType, CasinoGameStartedEvent, CasinoGameMovedEvent, CasinoGameCompletedEvent } from '../types/casino';
-import { createDeck, rollDie, getHandValue, getBaccaratValue, getHiLoRank, WAYS, getRouletteColor, evaluateVideoPokerHand, calculateCrapsExposure, calculateSicBoOutcomeExposure, getSicBoCombinations, resolveCrapsBets, resolveRouletteBets, resolveSicBoBets, evaluateThreeCardHand } from '../utils/gameUtils';
+import { createDeck, rollDie, getHandValue, getBaccaratValue, getHiLoRank, WAYS, getRouletteColor, isRouletteZero, formatRouletteNumber, evaluateVideoPokerHand, calculateCrapsExposure, calculateSicBoOutcomeExposure, getSicBoCombinations, resolveCrapsBets, resolveRouletteBets, resolveSicBoBets, evaluateThreeCardHand } from '../utils/gameUtils';
 import { getStrategicAdvice } from '../services/geminiService';
 import { CasinoChainService } from '../services/CasinoChainService';
 import { CasinoClient } from '../api/client.js';
@@ -56,7 +56,8 @@ const CHAIN_TO_FRONTEND_GAME_TYPE: Record<ChainGameType, GameType> = {
   [ChainGameType.UltimateHoldem]: GameType.ULTIMATE_HOLDEM,
 };
 
-// Baccarat bet type mapping for on-chain: 0=Player, 1=Banker, 2=Tie, 3=P_PAIR, 4=B_PAIR, 5=Lucky6
+// Baccarat bet type mapping for on-chain: 0=Player, 1=Banker, 2=Tie, 3=P_PAIR, 4=B_PAIR, 5=Lucky6,
+// 6=PERFECT_PAIR, 7=P_DRAGON, 8=B_DRAGON
 // Payload format: [action:u8] [betType:u8] [amount:u64 BE] - 10 bytes total
 // Action 0 = Place bet, Action 1 = Deal cards, Action 2 = Clear bets
 const serializeBaccaratBet = (betType: number, amount: number): Uint8Array => {
@@ -85,6 +86,9 @@ const getBaccaratBetsToPlace = (selection: 'PLAYER' | 'BANKER', sideBets: Baccar
       case 'P_PAIR': betType = 3; break;
       case 'B_PAIR': betType = 4; break;
       case 'LUCKY6': betType = 5; break;
+      case 'PERFECT_PAIR': betType = 6; break;
+      case 'P_DRAGON': betType = 7; break;
+      case 'B_DRAGON': betType = 8; break;
       default: continue;
     }
     bets.push({ betType, amount: sideBet.amount });
@@ -170,6 +174,7 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
     threeCardPairPlusBet: 0,
     threeCardSixCardBonusBet: 0,
     threeCardProgressiveBet: 0,
+    threeCardPrimeBet: 0,
     threeCardProgressiveJackpot: 10000,
     uthTripsBet: 0,
     uthSixCardBonusBet: 0,
@@ -450,9 +455,10 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
             setIsRegistered(true);
             hasRegisteredRef.current = true;
 
-            // Check for active session and restore game state
-            if (playerState.activeSession) {
-              const sessionId = BigInt(playerState.activeSession);
+            // Check for active sessions and restore the most recent one
+            const activeSessions = playerState.activeSessions ?? [];
+            if (activeSessions.length > 0) {
+              const sessionId = BigInt(activeSessions[activeSessions.length - 1]);
               console.log('[useTerminalGame] Found active session:', sessionId.toString());
               try {
                 const sessionState = await client.getCasinoSession(sessionId);
@@ -1373,7 +1379,7 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
         const isBlackjackAwaitingReveal =
           gameTypeRef.current === GameType.BLACKJACK &&
           stateBlob.length >= 2 &&
-          stateBlob[0] === 2 && // state version
+          stateBlob[0] >= 2 && stateBlob[0] <= 4 && // state version
           stateBlob[1] === 2; // Stage::AwaitingReveal
 
         if (isBlackjackAwaitingReveal && !isPendingRef.current && currentSessionIdRef.current) {
@@ -1648,12 +1654,13 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
         }
 
         const version = stateBlob[0];
-        if (version !== 2) {
+        // v3 and v4 only append bytes (rules, then insurance) after the dealer cards.
+        if (version < 2 || version > 4) {
           console.error('[parseGameState] Unsupported blackjack state version:', version);
           return;
         }
         if (stateBlob.length < 14) {
-          console.error('[parseGameState] Blackjack v2 state blob too short:', stateBlob.length);
+          console.error('[parseGameState] Blackjack state blob too short:', stateBlob.length);
           return;
         }
 
@@ -1678,7 +1685,7 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
 
         for (let h = 0; h < handCount; h++) {
           const betMult = stateBlob[offset++];
-          const status = stateBlob[offset++]; // 0=Play, 1=Stand, 2=Bust, 3=BJ
+          const status = stateBlob[offset++]; // 0=Play, 1=Stand, 2=Bust, 3=BJ, 4=Surrender
           offset++; // was_split (unused for display)
           const cLen = stateBlob[offset++];
 
@@ -1702,6 +1709,7 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
             if (status === 2) msg = 'BUST';
             else if (status === 3) msg = 'BLACKJACK';
             else if (status === 1) msg = 'STAND';
+            else if (status === 4) msg = 'SURRENDER';
             finishedHands.push({ cards: handCards, bet: handBet, isDoubled, message: msg });
           }
         }
@@ -1745,7 +1753,7 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
         gameStateRef.current = newState;
         setGameState(newState);
       } else if (currentType === GameType.HILO) {
-        // [currentCard:u8] [accumulator:i64 BE]
+        // [currentCard:u8] [accumulator:i64 BE] [rung:u8]?
         // Accumulator is in basis points (10000 = 1x multiplier)
         if (stateBlob.length < 9) {
           console.error('[parseGameState] HiLo state blob too short:', stateBlob.length);
@@ -1793,6 +1801,7 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
         });
       } else if (currentType === GameType.BACCARAT) {
         // State format: [bet_count:u8] [bets:9bytes×count] [playerHandLen:u8] [playerCards:u8×n] [bankerHandLen:u8] [bankerCards:u8×n]
+        // [historyLen:u8] [history:2bytes×n] [betReturns:u64 BE×count] (see parseBaccaratState for the scoreboard)
         // Each BaccaratBet is 9 bytes: [bet_type:u8] [amount:u64 BE]
         if (stateBlob.length < 1) {
           console.error('[parseGameState] Baccarat state blob too short:', stateBlob.length);
@@ -1884,15 +1893,19 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
           message: 'BACCARAT DEALT',
         }));
       } else if (currentType === GameType.VIDEO_POKER) {
-        // [stage:u8] [c1:u8] [c2:u8] [c3:u8] [c4:u8] [c5:u8]
-        // Stage: 0 = Deal (waiting for hold selection), 1 = Draw (game complete)
+        // v2/v3: [version:u8] [stage:u8] [variant:u8] [c1..c5:u8] [handCount:u8] [hands...] ...
+        // v1: [stage:u8] [c1:u8] [c2:u8] [c3:u8] [c4:u8] [c5:u8] [variant:u8]?
+        // Stage: 0 = Deal (waiting for hold selection), 1 = Draw (game complete),
+        // 2 = Double up (winnings at stake)
         if (stateBlob.length < 6) {
           console.error('[parseGameState] Video Poker state blob too short:', stateBlob.length);
           return;
         }
-        const stage = stateBlob[0];
+        const isV2 = (stateBlob[0] === 2 || stateBlob[0] === 3) && stateBlob.length >= 9;
+        const stage = stateBlob[isV2 ? 1 : 0];
+        const cardsOffset = isV2 ? 3 : 1;
         const cards: Card[] = [];
-        for (let i = 1; i <= 5 && i < stateBlob.length; i++) {
+        for (let i = cardsOffset; i < cardsOffset + 5 && i < stateBlob.length; i++) {
           cards.push(decodeCard(stateBlob[i]));
         }
 
@@ -1918,7 +1931,12 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
             type: currentType,
             playerCards: cardsWithHolds,
             stage: (stage === 1 ? 'RESULT' : 'PLAYING') as 'RESULT' | 'PLAYING',
-            message: stage === 0 ? 'HOLD (1-5), DRAW (D)' : 'GAME COMPLETE',
+            message:
+              stage === 0
+                ? 'HOLD (1-5), DRAW (D)'
+                : stage === 2
+                  ? 'DOUBLE UP OR COLLECT'
+                  : 'GAME COMPLETE',
           };
           // Also update ref inside for consistency
           gameStateRef.current = newState;
@@ -1991,13 +2009,16 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
         // Each bet entry is 19 bytes:
         // [bet_type:u8] [target:u8] [status:u8] [amount:u64 BE] [odds_amount:u64 BE]
         //
+        // v2/v3 add [epoch_point_established:u8] before bet_count; v3 bet entries are 20 bytes
+        // with a trailing [working:u8] (0 = bet/odds turned off).
+        //
         // Legacy (pre-version) state is also supported as a fallback:
         // [phase:u8] [main_point:u8] [d1:u8] [d2:u8] [bet_count:u8] [bets...]
         if (stateBlob.length < 5) {
           console.error('[parseGameState] Craps state blob too short:', stateBlob.length);
           return;
         }
-        const looksLikeV2 = stateBlob[0] === 2 && stateBlob.length >= 8 && (stateBlob[1] === 0 || stateBlob[1] === 1);
+        const looksLikeV2 = (stateBlob[0] === 2 || stateBlob[0] === 3) && stateBlob.length >= 8 && (stateBlob[1] === 0 || stateBlob[1] === 1);
         const looksLikeV1 = stateBlob[0] === 1 && stateBlob.length >= 7 && (stateBlob[1] === 0 || stateBlob[1] === 1);
 
         let d1: number;
@@ -2006,6 +2027,7 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
         let epochPointEstablished: boolean;
         let betCount: number;
         let betsOffset: number;
+        const betEntryLen = looksLikeV2 && stateBlob[0] === 3 ? 20 : 19;
 
         if (looksLikeV2) {
           mainPoint = stateBlob[2];
@@ -2046,10 +2068,11 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
           15: 'ATS_SMALL',
           16: 'ATS_TALL',
           17: 'ATS_ALL',
+          18: 'HOP',
         };
         const parsedBets: CrapsBet[] = [];
         let offset = betsOffset;
-        for (let i = 0; i < betCount && offset + 19 <= stateBlob.length; i++) {
+        for (let i = 0; i < betCount && offset + betEntryLen <= stateBlob.length; i++) {
           const betTypeVal = stateBlob[offset];
           const target = stateBlob[offset + 1];
           const statusVal = stateBlob[offset + 2];
@@ -2073,8 +2096,9 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
             amount,
             oddsAmount: (!isHardway && !isAts && oddsAmount > 0) ? oddsAmount : undefined,
             progressMask,
+            working: betEntryLen === 20 ? stateBlob[offset + 19] !== 0 : true,
           });
-          offset += 19;
+          offset += betEntryLen;
         }
 
         // Update ref BEFORE setGameState to ensure CasinoGameCompleted handler has access to dice
@@ -2137,6 +2161,8 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
           return newState;
         });
       } else if (currentType === GameType.ROULETTE) {
+        // v3 (result 0xFF before the first spin; wheel 0 = European, 1 = American with 37 = 00):
+        // [bet_count:u8] [zero_rule:u8] [phase:u8] [wheel:u8] [totalWagered:u64 BE] [pendingReturn:u64 BE] [bets:10bytes×count] [result:u8]
         // v2:
         // [bet_count:u8] [zero_rule:u8] [phase:u8] [totalWagered:u64 BE] [pendingReturn:u64 BE] [bets:10bytes×count] [result:u8]?
         // legacy:
@@ -2152,12 +2178,17 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
         const v2HeaderLen = 19;
         const v2ResultOffset = v2HeaderLen + betsSize;
 
+        const v3HeaderLen = 20;
+        const v3ResultOffset = v3HeaderLen + betsSize;
+
+        const looksLikeV3 = stateBlob.length === v3ResultOffset + 1;
         const looksLikeV2 =
           stateBlob.length === v2HeaderLen + betsSize || stateBlob.length === v2HeaderLen + betsSize + 1;
 
-        const zeroRuleByte = looksLikeV2 ? stateBlob[1] : 0;
-        const phaseByte = looksLikeV2 ? stateBlob[2] : 0;
-        const resultOffset = looksLikeV2 ? v2ResultOffset : legacyResultOffset;
+        const zeroRuleByte = looksLikeV3 || looksLikeV2 ? stateBlob[1] : 0;
+        const phaseByte = looksLikeV3 || looksLikeV2 ? stateBlob[2] : 0;
+        const resultOffset = looksLikeV3 ? v3ResultOffset : looksLikeV2 ? v2ResultOffset : legacyResultOffset;
+        const hasResult = looksLikeV3 ? stateBlob[resultOffset] !== 0xff : stateBlob.length > resultOffset;
 
         const zeroRule =
           zeroRuleByte === 1
@@ -2171,8 +2202,8 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
 
         console.log('[parseGameState] Roulette: betCount=' + betCount + ', betsSize=' + betsSize + ', resultOffset=' + resultOffset + ', totalLen=' + stateBlob.length);
 
-        // Check if we have a result (state length > bet section)
-        if (stateBlob.length > resultOffset) {
+        // Check if we have a result
+        if (hasResult) {
           const result = stateBlob[resultOffset];
           console.log('[parseGameState] Roulette: result=' + result);
           
@@ -2190,8 +2221,10 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
             rouletteZeroRule: zeroRule,
             rouletteIsPrison,
             rouletteHistory: [...prev.rouletteHistory, result].slice(-MAX_GRAPH_POINTS),
-            stage: rouletteIsPrison && result === 0 ? 'PLAYING' : 'RESULT',
-            message: rouletteIsPrison && result === 0 ? 'EN PRISON - SPACE TO SPIN' : `LANDED ON ${result}`,
+            stage: rouletteIsPrison && isRouletteZero(result) ? 'PLAYING' : 'RESULT',
+            message: rouletteIsPrison && isRouletteZero(result)
+              ? 'EN PRISON - SPACE TO SPIN'
+              : `LANDED ON ${formatRouletteNumber(result)}`,
           }));
         } else {
           // Betting stage - no result yet
@@ -2254,18 +2287,19 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
         }
       } else if (currentType === GameType.THREE_CARD) {
         const version = stateBlob[0];
-        if (version !== 1 && version !== 2 && version !== 3) {
+        if (version !== 1 && version !== 2 && version !== 3 && version !== 4) {
           console.error('[parseGameState] Unsupported Three Card state version:', version);
           return;
         }
 
+        // v4 (40 bytes): v3 followed by [prime:u64 BE]
         // v3 (32 bytes):
         // [version:u8=3] [stage:u8] [p1..p3:u8] [d1..d3:u8] [pairplus:u64 BE] [sixcard:u64 BE] [progressive:u64 BE]
         // v2 (24 bytes):
         // [version:u8=2] [stage:u8] [p1..p3:u8] [d1..d3:u8] [pairplus:u64 BE] [sixcard:u64 BE]
         // v1 (16 bytes):
         // [version:u8=1] [stage:u8] [p1..p3:u8] [d1..d3:u8] [pairplus:u64 BE]
-        const requiredLen = version === 3 ? 32 : version === 2 ? 24 : 16;
+        const requiredLen = version === 4 ? 40 : version === 3 ? 32 : version === 2 ? 24 : 16;
         if (stateBlob.length < requiredLen) {
           console.error('[parseGameState] Three Card state blob too short:', stateBlob.length);
           return;
@@ -2274,7 +2308,8 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
         const stageVal = stateBlob[1]; // 0=Betting, 1=Decision, 2=AwaitingReveal, 3=Complete
         const pairplusBet = Number(view.getBigUint64(8, false));
         const sixCardBonusBet = version >= 2 ? Number(view.getBigUint64(16, false)) : 0;
-        const progressiveBet = version === 3 ? Number(view.getBigUint64(24, false)) : 0;
+        const progressiveBet = version >= 3 ? Number(view.getBigUint64(24, false)) : 0;
+        const primeBet = version === 4 ? Number(view.getBigUint64(32, false)) : 0;
 
         const pBytes = [stateBlob[2], stateBlob[3], stateBlob[4]];
         const dBytes = [stateBlob[5], stateBlob[6], stateBlob[7]];
@@ -2306,6 +2341,7 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
             threeCardPairPlusBet: pairplusBet,
             threeCardSixCardBonusBet: sixCardBonusBet,
             threeCardProgressiveBet: progressiveBet,
+            threeCardPrimeBet: primeBet,
             stage: uiStage,
             message,
           };
@@ -2513,6 +2549,7 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
       threeCardPairPlusBet: 0,
       threeCardSixCardBonusBet: 0,
       threeCardProgressiveBet: 0,
+      threeCardPrimeBet: 0,
       threeCardProgressiveJackpot: prev.threeCardProgressiveJackpot,
       uthTripsBet: 0,
       uthSixCardBonusBet: 0,
@@ -4254,6 +4291,7 @@ export const useTerminalGame = (playMode: 'CASH' | 'FREEROLL' | null = null) =>
       'ATS_SMALL': 15,
       'ATS_TALL': 16,
       'ATS_ALL': 17,
+      'HOP': 18, // target = (low die << 4) | high die
     };
 
     let betTypeValue = BET_TYPE_MAP[bet.type];
@@ -45,7 +45,6 @@ export class VaultBetBot {
   private privateKeyBytes: Uint8Array;
   private wasm: WasmWrapper | null = null;
   private nonce = 0;
-  private sessionCounter = Date.now();
   private isRunning = false;
   private timer: number | null = null;
   private config: VaultBetBotConfig = DEFAULT_CONFIG;
@@ -192,10 +191,10 @@ export class VaultBetBot {
     if (!this.wasm) throw new Error('bot-not-initialized');
 
     const gameType = ALL_GAMES[Math.floor(Math.random() * ALL_GAMES.length)];
-    const sessionId = BigInt(++this.sessionCounter);
     const bet = this.config.betAmount;
 
     const startNonce = this.nonce;
+    const sessionId = this.wasm.deriveSessionId(startNonce);
     const startTx = this.wasm.createCasinoStartGameTransaction(startNonce, gameType, bet, sessionId);
     try {
       await this.submitTransaction(startTx);
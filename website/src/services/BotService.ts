@@ -26,7 +26,6 @@ interface BotState {
   name: string;
   wasm: WasmWrapper;
   nonce: number;
-  sessionCounter: number;
   isActive: boolean;
 }
 
@@ -207,7 +206,6 @@ export class BotService {
       name,
       wasm,
       nonce: currentNonce,
-      sessionCounter: id * 1_000_000,
       isActive: true,
     };
   }
@@ -314,11 +312,11 @@ export class BotService {
 
   private async playRandomGame(bot: BotState): Promise<void> {
     const gameType = ALL_GAMES[Math.floor(Math.random() * ALL_GAMES.length)];
-    const sessionId = BigInt(++bot.sessionCounter);
     const bet = 10; // Small consistent bet
 
     // Start game - use current nonce, only increment on success
     const startNonce = bot.nonce;
+    const sessionId = bot.wasm.deriveSessionId(startNonce);
     const startTx = bot.wasm.createCasinoStartGameTransaction(
       startNonce,
       gameType,
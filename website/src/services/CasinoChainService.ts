@@ -11,6 +11,7 @@ import { snakeToCamel } from '../utils/caseNormalizer';
 interface CasinoClientWithNonceManager extends CasinoClient {
   nonceManager: {
     submitCasinoRegister: (name: string) => Promise<{ txHash?: string }>;
+    nextSessionId: () => bigint;
     submitCasinoStartGame: (gameType: GameType, bet: bigint, sessionId: bigint) => Promise<{ txHash?: string }>;
     submitCasinoGameMove: (sessionId: bigint, payload: Uint8Array) => Promise<{ txHash?: string }>;
     submitCasinoToggleShield: () => Promise<{ txHash?: string }>;
@@ -42,9 +43,6 @@ interface RawCasinoGameCompletedEvent {
   was_doubled: boolean;
 }
 
-// Session ID counter for generating unique session IDs
-let sessionIdCounter = BigInt(Date.now());
-
 /**
  * Read a varint from a buffer (commonware-codec style)
  */
@@ -346,9 +344,11 @@ export class CasinoChainService {
    * Generate the next session ID without submitting anything.
    * Call this to get the session ID before submitting, so you can store it
    * in a ref before the WebSocket event arrives.
+   * Session IDs are derived from the player and the nonce of the transaction starting the
+   * game, so submit the game before any other transaction.
    */
   generateNextSessionId(): bigint {
-    const sessionId = sessionIdCounter++;
+    const sessionId = this.client.nonceManager.nextSessionId();
     console.log('[CasinoChainService] generateNextSessionId:', sessionId.toString());
     return sessionId;
   }
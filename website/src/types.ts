@@ -61,17 +61,19 @@ export interface CrapsBet {
     | 'BUY'
     | 'ATS_SMALL'
     | 'ATS_TALL'
-    | 'ATS_ALL';
+    | 'ATS_ALL'
+    | 'HOP';
   amount: number;
   target?: number; // The number (e.g., 4 for a Place 4, or the Point for a Come bet)
   oddsAmount?: number; // Attached odds amount
   progressMask?: number; // ATS progress (bitmask), if applicable
   status?: 'PENDING' | 'ON'; // PENDING means Come bet waiting to travel
+  working?: boolean; // false = bet (or its odds) turned off: neither wins nor loses
   local?: boolean; // true = locally staged bet not yet sent to chain, undefined/false = on-chain bet
 }
 
 export interface BaccaratBet {
-    type: 'TIE' | 'P_PAIR' | 'B_PAIR' | 'LUCKY6';
+    type: 'TIE' | 'P_PAIR' | 'B_PAIR' | 'LUCKY6' | 'PERFECT_PAIR' | 'P_DRAGON' | 'B_DRAGON';
     amount: number;
 }
 
@@ -186,6 +188,7 @@ export interface GameState {
   threeCardPairPlusBet: number;
   threeCardSixCardBonusBet: number;
   threeCardProgressiveBet: number;
+  threeCardPrimeBet: number;
   threeCardProgressiveJackpot: number;
 
   // Ultimate Texas Hold'em
@@ -17,6 +17,13 @@ export enum GameType {
   SicBo = 7,
   ThreeCard = 8,
   UltimateHoldem = 9,
+  Slots = 10,
+  Mines = 11,
+  PaiGow = 12,
+  CaribbeanStud = 13,
+  MississippiStud = 14,
+  MoneyWheel = 15,
+  FourCard = 16,
 }
 
 /**
@@ -32,7 +39,7 @@ export interface Player {
   activeShield: boolean;
   activeDouble: boolean;
   activeSuper?: boolean;
-  activeSession: bigint | null;
+  activeSessions: bigint[];
   lastDepositBlock: bigint;
   auraMeter?: number;
 }
@@ -88,13 +88,13 @@ describe('caseNormalizer', () => {
         doubles: 2,
         active_shield: true,
         active_double: false,
-        active_session: 789n,
+        active_sessions: [789n],
       };
 
       const result = snakeToCamel(input);
       expect(result.activeShield).toBe(true);
       expect(result.activeDouble).toBe(false);
-      expect(result.activeSession).toBe(789n);
+      expect(result.activeSessions).toEqual([789n]);
     });
 
     test('handles CasinoGameCompleted event example', () => {
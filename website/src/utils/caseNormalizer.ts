@@ -12,7 +12,7 @@
  * - move_number -> moveNumber
  * - active_shield -> activeShield
  * - active_double -> activeDouble
- * - active_session -> activeSession
+ * - active_sessions -> activeSessions
  * - was_shielded -> wasShielded
  * - was_doubled -> wasDoubled
  * - final_chips -> finalChips
@@ -119,57 +119,90 @@ export function parseBlackjackState(state: Uint8Array): BlackjackState {
 // ============================================================================
 
 export interface RouletteState {
+  /** Pocket number; 37 is the American wheel's 00. */
   result: number | null;
+  wheel: 'EUROPEAN' | 'AMERICAN';
 }
 
 /**
  * Roulette State Format:
- * Empty before spin, [result:u8] after spin
+ * v3: [betCount:u8] [zeroRule:u8] [phase:u8] [wheel:u8] [totalWagered:u64] [pendingReturn:u64] [bets:10 bytes×count] [result:u8]
+ *     (result 0xFF before the first spin; wheel 0 = European, 1 = American)
+ * v2: [betCount:u8] [zeroRule:u8] [phase:u8] [totalWagered:u64] [pendingReturn:u64] [bets:10 bytes×count] [result:u8]?
+ * legacy: [betCount:u8] [bets:10 bytes×count] [result:u8]?
+ * v2 and legacy sessions are always on the European wheel.
  */
 export function parseRouletteState(state: Uint8Array): RouletteState {
   if (!state || state.length === 0) {
-    return { result: null };
+    return { result: null, wheel: 'EUROPEAN' };
   }
 
-  return { result: state[0] };
+  const betsSize = state[0] * 10;
+  if (state.length === 20 + betsSize + 1) {
+    const result = state[20 + betsSize];
+    return { result: result === 0xff ? null : result, wheel: state[3] === 1 ? 'AMERICAN' : 'EUROPEAN' };
+  }
+
+  const resultOffset = state.length >= 19 + betsSize ? 19 + betsSize : 1 + betsSize;
+  return { result: state.length > resultOffset ? state[resultOffset] : null, wheel: 'EUROPEAN' };
 }
 
 // ============================================================================
 // Baccarat State Parser
 // ============================================================================
 
+export interface BaccaratRound {
+  playerTotal: number;
+  bankerTotal: number;
+  winner: 'PLAYER' | 'BANKER' | 'TIE';
+  playerPair: boolean;
+  bankerPair: boolean;
+  natural: boolean;
+}
+
 export interface BaccaratState {
   playerHand: Card[];
   bankerHand: Card[];
+  /** Rounds dealt this session, oldest first (the Bead Plate / Big Road source). */
+  history: BaccaratRound[];
+  /** What each bet returned once the cards are dealt (0 = loss, the bet amount = push). */
+  betReturns: number[];
 }
 
 /**
  * Baccarat State Format:
- * [playerHandLen:u8] [playerCards:u8×n] [bankerHandLen:u8] [bankerCards:u8×n]
+ * [betCount:u8] [bets:9 bytes×count] [playerHandLen:u8] [playerCards:u8×n] [bankerHandLen:u8] [bankerCards:u8×n]
+ * [historyLen:u8] [history:2 bytes×n] [betReturns:u64 BE×count]
+ *
+ * Each history entry is [flags:u8] [playerTotal << 4 | bankerTotal]
+ * (flags: bit 0 = player pair, bit 1 = banker pair, bit 2 = natural).
+ * Older blobs end after the banker cards.
  */
 export function parseBaccaratState(state: Uint8Array): BaccaratState {
+  const empty: BaccaratState = { playerHand: [], bankerHand: [], history: [], betReturns: [] };
   // Default safe state for malformed input
-  if (!state || state.length < 2) {
-    return { playerHand: [], bankerHand: [] };
+  if (!state || state.length < 1) {
+    return empty;
   }
 
-  let offset = 0;
+  const betCount = state[0];
+  let offset = 1 + betCount * 9;
+  if (offset >= state.length) {
+    return empty;
+  }
 
   // Read player hand
   const playerLen = state[offset++];
   if (offset + playerLen >= state.length) {
-    return { playerHand: [], bankerHand: [] };
+    return empty;
   }
 
   const playerHand: Card[] = [];
-  for (let i = 0; i < playerLen && offset < state.length; i++) {
+  for (let i = 0; i < playerLen; i++) {
     playerHand.push(parseCard(state[offset++]));
   }
 
   // Read banker hand
-  if (offset >= state.length) {
-    return { playerHand, bankerHand: [] };
-  }
   const bankerLen = state[offset++];
 
   const bankerHand: Card[] = [];
@@ -177,7 +210,35 @@ export function parseBaccaratState(state: Uint8Array): BaccaratState {
     bankerHand.push(parseCard(state[offset++]));
   }
 
-  return { playerHand, bankerHand };
+  // Read scoreboard
+  const history: BaccaratRound[] = [];
+  const historyLen = offset < state.length ? state[offset++] : 0;
+  for (let i = 0; i < historyLen && offset + 1 < state.length; i++) {
+    const flags = state[offset++];
+    const totals = state[offset++];
+    const playerTotal = totals >> 4;
+    const bankerTotal = totals & 0x0f;
+    history.push({
+      playerTotal,
+      bankerTotal,
+      winner: playerTotal > bankerTotal ? 'PLAYER' : bankerTotal > playerTotal ? 'BANKER' : 'TIE',
+      playerPair: (flags & 1) !== 0,
+      bankerPair: (flags & 2) !== 0,
+      natural: (flags & 4) !== 0,
+    });
+  }
+
+  // Read bet returns
+  const betReturns: number[] = [];
+  if (state.length - offset === betCount * 8) {
+    const view = new DataView(state.buffer, state.byteOffset, state.byteLength);
+    for (let i = 0; i < betCount; i++) {
+      betReturns.push(Number(view.getBigUint64(offset, false)));
+      offset += 8;
+    }
+  }
+
+  return { playerHand, bankerHand, history, betReturns };
 }
 
 // ============================================================================
@@ -206,36 +267,103 @@ export function parseSicBoState(state: Uint8Array): SicBoState {
 // Video Poker State Parser
 // ============================================================================
 
+export type VideoPokerVariant = 'JACKS_OR_BETTER' | 'BONUS_POKER' | 'DOUBLE_BONUS' | 'DEUCES_WILD';
+
+const VIDEO_POKER_VARIANTS: VideoPokerVariant[] = ['JACKS_OR_BETTER', 'BONUS_POKER', 'DOUBLE_BONUS', 'DEUCES_WILD'];
+
+/** Hand rankings, indexed by the on-chain hand byte. */
+export const VIDEO_POKER_HANDS = [
+  'HIGH CARD', 'JACKS OR BETTER', 'TWO PAIR', 'THREE OF A KIND', 'STRAIGHT', 'FLUSH', 'FULL HOUSE',
+  'FOUR OF A KIND', 'FOUR 2S-4S', 'FOUR ACES', 'STRAIGHT FLUSH', 'FIVE OF A KIND', 'WILD ROYAL FLUSH',
+  'FOUR DEUCES', 'ROYAL FLUSH'
+];
+
+export interface VideoPokerHandResult {
+  cards: Card[];
+  hand: string;
+}
+
+/** A double-up: the dealer's card and the player's. */
+export interface VideoPokerGamble {
+  dealer: Card;
+  player: Card;
+}
+
 export interface VideoPokerState {
   cards: [Card, Card, Card, Card, Card];
-  stage: 'DEAL' | 'DRAW';
+  stage: 'DEAL' | 'DRAW' | 'DOUBLE_UP';
+  variant: VideoPokerVariant;
+  /** Every hand's final cards and ranking (empty until the draw). */
+  hands: VideoPokerHandResult[];
+  /** Winnings kept at the table for double-up. */
+  winnings: number;
+  gambles: VideoPokerGamble[];
 }
 
+const VIDEO_POKER_STAGES: VideoPokerState['stage'][] = ['DEAL', 'DRAW', 'DOUBLE_UP'];
+
 /**
  * Video Poker State Format:
- * [stage:u8] [card1:u8] [card2:u8] [card3:u8] [card4:u8] [card5:u8]
+ * v3: v2 followed by [winnings:u64 BE] [gambleCount:u8] [gambles: (dealer:u8, player:u8) x gambleCount]
+ * v2: [version:u8=2] [stage:u8] [variant:u8] [card1..card5:u8] [handCount:u8]
+ *     [hands: (card1..card5:u8, hand:u8) x handCount]
+ * v1: [stage:u8] [card1:u8] [card2:u8] [card3:u8] [card4:u8] [card5:u8] [variant:u8]?
+ *     (without the variant byte the game is Jacks or Better)
  */
 export function parseVideoPokerState(state: Uint8Array): VideoPokerState {
   // Default safe state for malformed input
   if (!state || state.length < 6) {
     return {
       cards: [DEFAULT_CARD, DEFAULT_CARD, DEFAULT_CARD, DEFAULT_CARD, DEFAULT_CARD],
-      stage: 'DEAL'
+      stage: 'DEAL',
+      variant: 'JACKS_OR_BETTER',
+      hands: [],
+      winnings: 0,
+      gambles: []
     };
   }
 
-  const stageValue = state[0];
-  const stage = stageValue === 0 ? 'DEAL' : 'DRAW';
+  const isV2 = (state[0] === 2 || state[0] === 3) && state.length >= 9;
+  const cardsOffset = isV2 ? 3 : 1;
+  const stage = VIDEO_POKER_STAGES[state[isV2 ? 1 : 0]] ?? 'DRAW';
+  const variantByte = isV2 ? state[2] : state.length > 6 ? state[6] : 0;
+  const variant = VIDEO_POKER_VARIANTS[variantByte] || 'JACKS_OR_BETTER';
 
   const cards: [Card, Card, Card, Card, Card] = [
-    parseCard(state[1]),
-    parseCard(state[2]),
-    parseCard(state[3]),
-    parseCard(state[4]),
-    parseCard(state[5])
+    parseCard(state[cardsOffset]),
+    parseCard(state[cardsOffset + 1]),
+    parseCard(state[cardsOffset + 2]),
+    parseCard(state[cardsOffset + 3]),
+    parseCard(state[cardsOffset + 4])
   ];
 
-  return { cards, stage };
+  const hands: VideoPokerHandResult[] = [];
+  if (isV2) {
+    const handCount = state[8];
+    for (let i = 0; i < handCount; i++) {
+      const offset = 9 + i * 6;
+      if (offset + 6 > state.length) break;
+      hands.push({
+        cards: Array.from(state.slice(offset, offset + 5), card => parseCard(card)),
+        hand: VIDEO_POKER_HANDS[state[offset + 5]] ?? 'HIGH CARD'
+      });
+    }
+  }
+
+  let winnings = 0;
+  const gambles: VideoPokerGamble[] = [];
+  const winningsOffset = 9 + hands.length * 6;
+  if (state[0] === 3 && winningsOffset + 9 <= state.length) {
+    winnings = readBigEndianU64(state, winningsOffset);
+    const gambleCount = state[winningsOffset + 8];
+    for (let i = 0; i < gambleCount; i++) {
+      const offset = winningsOffset + 9 + i * 2;
+      if (offset + 2 > state.length) break;
+      gambles.push({ dealer: parseCard(state[offset]), player: parseCard(state[offset + 1]) });
+    }
+  }
+
+  return { cards, stage, variant, hands, winnings, gambles };
 }
 
 // ============================================================================
@@ -385,21 +513,37 @@ export function parseCasinoWarState(state: Uint8Array): CasinoWarState {
 // HiLo State Parser
 // ============================================================================
 
+export type HiLoSideBetType = 'EXACT' | 'COLOR' | 'SUIT';
+
+const HILO_SIDE_BETS: HiLoSideBetType[] = ['EXACT', 'COLOR', 'SUIT'];
+
+export interface HiLoSideBet {
+  type: HiLoSideBetType;
+  target: number; // Rank 1-13 (EXACT), 0 red / 1 black (COLOR), or suit 0-3 (SUIT)
+  amount: number;
+  returned: number; // Stake + winnings (0 on a loss)
+}
+
 export interface HiLoState {
   currentCard: Card;
   accumulator: number; // Multiplier in basis points (10000 = 1.0x)
+  rung: number; // Correct calls made so far
+  sideBet: HiLoSideBet | null; // The last call's side bet
 }
 
 /**
  * HiLo State Format:
- * [currentCard:u8] [accumulator:i64 BE]
+ * [currentCard:u8] [accumulator:i64 BE] [rung:u8]?
+ * [sideBet:u8] [sideTarget:u8] [sideAmount:u64 BE] [sideReturn:u64 BE] (after a call with a side bet)
  */
 export function parseHiLoState(state: Uint8Array): HiLoState {
   // Default safe state for malformed input
   if (!state || state.length < 9) {
     return {
       currentCard: DEFAULT_CARD,
-      accumulator: 10000 // 1.0x multiplier
+      accumulator: 10000, // 1.0x multiplier
+      rung: 0,
+      sideBet: null
     };
   }
 
@@ -408,8 +552,20 @@ export function parseHiLoState(state: Uint8Array): HiLoState {
   // Read accumulator as i64 Big Endian
   const view = new DataView(state.buffer, state.byteOffset + 1, 8);
   const accumulator = Number(view.getBigInt64(0, false)); // false = Big Endian
+  const rung = state.length > 9 ? state[9] : 0;
+
+  let sideBet: HiLoSideBet | null = null;
+  if (state.length >= 28 && HILO_SIDE_BETS[state[10]]) {
+    const sideView = new DataView(state.buffer, state.byteOffset + 12, 16);
+    sideBet = {
+      type: HILO_SIDE_BETS[state[10]],
+      target: state[11],
+      amount: Number(sideView.getBigUint64(0, false)),
+      returned: Number(sideView.getBigUint64(8, false))
+    };
+  }
 
-  return { currentCard, accumulator };
+  return { currentCard, accumulator, rung, sideBet };
 }
 
 // ============================================================================
@@ -628,6 +784,21 @@ export function getHiLoRank(card: Card): number {
   return parseInt(card.rank);
 }
 
+/**
+ * HiLo ladder steps in basis points, as [higher, lower] for current ranks Ace (1) through King (13).
+ * Mirrors hilo.rs `MULTIPLIERS` (published by the wasm `hilo_multipliers`): the fair multiplier
+ * on the 51 cards left, rounded down. 0 means the call is impossible.
+ */
+export const HILO_MULTIPLIERS: [number, number][] = Array.from({ length: 13 }, (_, i) => {
+  const rank = i + 1;
+  const higher = 4 * (13 - rank);
+  const lower = 4 * (rank - 1);
+  return [
+    higher > 0 ? Math.floor((51 * 10000) / higher) : 0,
+    lower > 0 ? Math.floor((51 * 10000) / lower) : 0,
+  ];
+});
+
 /**
  * Convert HiLo accumulator from basis points to multiplier
  * @param accumulator Value in basis points (10000 = 1.0x)
@@ -1,24 +1,31 @@
-use commonware_codec::{Encode, ReadExt};
+use commonware_codec::{Encode, FixedSize, ReadExt};
 #[cfg(feature = "testing")]
 use commonware_consensus::threshold_simplex::types::{seed_namespace, view_message};
 #[cfg(feature = "testing")]
 use commonware_cryptography::bls12381::primitives::ops;
 #[cfg(feature = "testing")]
 use commonware_cryptography::bls12381::primitives::variant::MinSig;
-use commonware_cryptography::{ed25519, Hasher, PrivateKeyExt, Sha256, Signer as _};
+use commonware_cryptography::{
+    ed25519, sha256::Digest, Hasher, PrivateKeyExt, Sha256, Signer as _,
+};
 #[cfg(feature = "testing")]
 use commonware_runtime::{deterministic::Runner, Runner as _};
 use commonware_storage::store::operation::{Keyless, Variable};
 use commonware_utils::hex;
+use nullspace_execution::casino::{hilo, slots, state_blob::StateBlob, video_poker};
 #[cfg(feature = "testing")]
 use nullspace_execution::mocks;
 #[cfg(feature = "testing")]
 use nullspace_types::api::Summary;
 use nullspace_types::{
     api::{Lookup, Submission, Update, UpdatesFilter},
+    casino::{
+        AdminRole, EconomyParams, GameConfig, GameType, HouseSettings, LpShares, PlayerEntropy,
+        RngDraw, Seat, TournamentRules,
+    },
     execution::{
-        Event, Instruction, Key, Output, Seed, Transaction as ExecutionTransaction, Value,
-        NAMESPACE, TRANSACTION_NAMESPACE,
+        instruction_catalog, Event, Instruction, Key, Output, Seed,
+        Transaction as ExecutionTransaction, Value, NAMESPACE, TRANSACTION_NAMESPACE,
     },
     Identity, Query,
 };
@@ -60,72 +67,231 @@ pub enum InstructionKind {
     Swap = 18,
     AddLiquidity = 19,
     RemoveLiquidity = 20,
+
+    // Batches
+    Batch = 21,
+
+    // Vault instructions (continued)
+    WithdrawCollateral = 22,
+
+    // Limit order instructions
+    PlaceLimitOrder = 23,
+    CancelLimitOrder = 24,
+
+    // Single-sided liquidity
+    ZapLiquidity = 25,
+
+    // Slashing
+    Slash = 26,
+
+    // Daily bonus
+    ClaimDailyBonus = 27,
+
+    // Profiles
+    UpdateProfile = 28,
+
+    // Session expiry
+    CasinoAbandonSession = 29,
+
+    // KYC
+    SetKycStatus = 30,
+
+    // Scheduled tournaments
+    CasinoScheduleTournament = 31,
+
+    // Account recovery
+    SetGuardians = 32,
+    RecoverAccount = 33,
+
+    // vUSDT allowances
+    Approve = 34,
+    TransferVusdtFrom = 35,
+
+    // Vesting
+    CreateVesting = 36,
+    ClaimVested = 37,
+
+    // Treasury
+    ExecuteBuyback = 38,
+
+    // Game rules
+    SetGameConfig = 39,
+    GrantBonus = 40,
+    SetTournamentRules = 41,
+
+    // Economy parameters
+    SetEconomyParams = 42,
+
+    // Admin keys and house settings
+    SetAdmin = 43,
+    SetHouseSettings = 44,
 }
 
 impl InstructionKind {
+    /// Every kind, indexed by its discriminant (the instruction's catalog kind).
+    const ALL: [Self; 45] = [
+        Self::CasinoRegister,
+        Self::CasinoDeposit,
+        Self::CasinoStartGame,
+        Self::CasinoGameMove,
+        Self::CasinoToggleShield,
+        Self::CasinoToggleDouble,
+        Self::CasinoToggleSuper,
+        Self::CasinoJoinTournament,
+        Self::CasinoStartTournament,
+        Self::CasinoEndTournament,
+        Self::Stake,
+        Self::Unstake,
+        Self::ClaimRewards,
+        Self::ProcessEpoch,
+        Self::CreateVault,
+        Self::DepositCollateral,
+        Self::BorrowUSDT,
+        Self::RepayUSDT,
+        Self::Swap,
+        Self::AddLiquidity,
+        Self::RemoveLiquidity,
+        Self::Batch,
+        Self::WithdrawCollateral,
+        Self::PlaceLimitOrder,
+        Self::CancelLimitOrder,
+        Self::ZapLiquidity,
+        Self::Slash,
+        Self::ClaimDailyBonus,
+        Self::UpdateProfile,
+        Self::CasinoAbandonSession,
+        Self::SetKycStatus,
+        Self::CasinoScheduleTournament,
+        Self::SetGuardians,
+        Self::RecoverAccount,
+        Self::Approve,
+        Self::TransferVusdtFrom,
+        Self::CreateVesting,
+        Self::ClaimVested,
+        Self::ExecuteBuyback,
+        Self::SetGameConfig,
+        Self::GrantBonus,
+        Self::SetTournamentRules,
+        Self::SetEconomyParams,
+        Self::SetAdmin,
+        Self::SetHouseSettings,
+    ];
+
     fn from_instruction(instruction: &Instruction) -> Self {
-        match instruction {
-            // Casino instructions
-            Instruction::CasinoRegister { .. } => Self::CasinoRegister,
-            Instruction::CasinoDeposit { .. } => Self::CasinoDeposit,
-            Instruction::CasinoStartGame { .. } => Self::CasinoStartGame,
-            Instruction::CasinoGameMove { .. } => Self::CasinoGameMove,
-            Instruction::CasinoToggleShield => Self::CasinoToggleShield,
-            Instruction::CasinoToggleDouble => Self::CasinoToggleDouble,
-            Instruction::CasinoToggleSuper => Self::CasinoToggleSuper,
-            Instruction::CasinoJoinTournament { .. } => Self::CasinoJoinTournament,
-            Instruction::CasinoStartTournament { .. } => Self::CasinoStartTournament,
-            Instruction::CasinoEndTournament { .. } => Self::CasinoEndTournament,
-
-            // Staking instructions
-            Instruction::Stake { .. } => Self::Stake,
-            Instruction::Unstake => Self::Unstake,
-            Instruction::ClaimRewards => Self::ClaimRewards,
-            Instruction::ProcessEpoch => Self::ProcessEpoch,
-
-            // Vault / AMM instructions
-            Instruction::CreateVault => Self::CreateVault,
-            Instruction::DepositCollateral { .. } => Self::DepositCollateral,
-            Instruction::BorrowUSDT { .. } => Self::BorrowUSDT,
-            Instruction::RepayUSDT { .. } => Self::RepayUSDT,
-            Instruction::Swap { .. } => Self::Swap,
-            Instruction::AddLiquidity { .. } => Self::AddLiquidity,
-            Instruction::RemoveLiquidity { .. } => Self::RemoveLiquidity,
-        }
+        Self::ALL[instruction.info().kind as usize]
     }
 
     fn as_str(self) -> &'static str {
-        match self {
-            // Casino instructions
-            Self::CasinoRegister => "CasinoRegister",
-            Self::CasinoDeposit => "CasinoDeposit",
-            Self::CasinoStartGame => "CasinoStartGame",
-            Self::CasinoGameMove => "CasinoGameMove",
-            Self::CasinoToggleShield => "CasinoToggleShield",
-            Self::CasinoToggleDouble => "CasinoToggleDouble",
-            Self::CasinoToggleSuper => "CasinoToggleSuper",
-            Self::CasinoJoinTournament => "CasinoJoinTournament",
-            Self::CasinoStartTournament => "CasinoStartTournament",
-            Self::CasinoEndTournament => "CasinoEndTournament",
-
-            // Staking instructions
-            Self::Stake => "Stake",
-            Self::Unstake => "Unstake",
-            Self::ClaimRewards => "ClaimRewards",
-            Self::ProcessEpoch => "ProcessEpoch",
-
-            // Vault / AMM instructions
-            Self::CreateVault => "CreateVault",
-            Self::DepositCollateral => "DepositCollateral",
-            Self::BorrowUSDT => "BorrowUSDT",
-            Self::RepayUSDT => "RepayUSDT",
-            Self::Swap => "Swap",
-            Self::AddLiquidity => "AddLiquidity",
-            Self::RemoveLiquidity => "RemoveLiquidity",
-        }
+        instruction_catalog()[self as usize].name
     }
 }
 
+/// Describe every instruction (its kind, name, label, tags, parameters and the state it can
+/// change), ordered by kind.
+#[wasm_bindgen]
+pub fn get_instruction_catalog() -> Result<JsValue, JsValue> {
+    let catalog: Vec<_> = instruction_catalog()
+        .iter()
+        .map(|info| {
+            serde_json::json!({
+                "kind": info.kind,
+                "name": info.name,
+                "label": info.label,
+                "tags": info.tags,
+                "params": info
+                    .params
+                    .iter()
+                    .map(|param| serde_json::json!({ "name": param.name, "type": param.kind }))
+                    .collect::<Vec<_>>(),
+                "mutates_casino": info.mutates_casino,
+                "mutates_defi": info.mutates_defi,
+                "mutates_staking": info.mutates_staking
+            })
+        })
+        .collect();
+    to_object(&serde_json::Value::Array(catalog))
+}
+
+/// Helper to parse a game type from its numeric value
+fn parse_game_type(game_type: u8) -> Result<GameType, JsValue> {
+    Ok(match game_type {
+        0 => GameType::Baccarat,
+        1 => GameType::Blackjack,
+        2 => GameType::CasinoWar,
+        3 => GameType::Craps,
+        4 => GameType::VideoPoker,
+        5 => GameType::HiLo,
+        6 => GameType::Roulette,
+        7 => GameType::SicBo,
+        8 => GameType::ThreeCard,
+        9 => GameType::UltimateHoldem,
+        10 => GameType::Slots,
+        11 => GameType::Mines,
+        12 => GameType::PaiGow,
+        13 => GameType::CaribbeanStud,
+        14 => GameType::MississippiStud,
+        15 => GameType::MoneyWheel,
+        16 => GameType::FourCard,
+        _ => {
+            return Err(JsValue::from_str(&format!(
+                "Invalid game type: {}",
+                game_type
+            )))
+        }
+    })
+}
+
+/// Helper to pick the AMM pool an instruction trades on (RNG/vUSDT unless given)
+fn amm_pair(pair: Option<u16>) -> nullspace_types::casino::PairId {
+    pair.map_or(
+        nullspace_types::casino::PairId::RNG_VUSDT,
+        nullspace_types::casino::PairId,
+    )
+}
+
+/// Tournament rule overrides as JSON (game types as their ids).
+fn tournament_rules_json(rules: &TournamentRules) -> serde_json::Value {
+    serde_json::json!({
+        "min_bet": rules.min_bet,
+        "max_bet": rules.max_bet,
+        "disabled_games": rules
+            .disabled_games
+            .iter()
+            .map(|game_type| *game_type as u8)
+            .collect::<Vec<_>>(),
+        "game_configs": rules
+            .game_configs
+            .iter()
+            .map(|(game_type, config)| {
+                serde_json::json!({
+                    "game_type": *game_type as u8,
+                    "paytable": config.paytable,
+                    "commission_on_win": config.commission_on_win,
+                    "dealer_hits_soft_17": config.dealer_hits_soft_17,
+                    "max_split_hands": config.max_split_hands,
+                    "record_rng": config.record_rng,
+                    "shoe_decks": config.shoe_decks,
+                    "shoe_penetration": config.shoe_penetration,
+                    "dealer_peeks": config.dealer_peeks,
+                    "even_money": config.even_money
+                })
+            })
+            .collect::<Vec<_>>(),
+        "super_boost_bps": rules.super_boost_bps
+    })
+}
+
+/// Economy parameters as JSON.
+fn economy_params_json(params: &EconomyParams) -> serde_json::Value {
+    serde_json::json!({
+        "initial_chips": params.initial_chips,
+        "faucet_amount": params.faucet_amount,
+        "super_mode_fee_bps": params.super_mode_fee_bps,
+        "amm_fee_bps": params.amm_fee_bps,
+        "minimum_liquidity": params.minimum_liquidity.0
+    })
+}
+
 /// Helper to convert serde_json::Value to a plain JavaScript object
 fn to_object(value: &serde_json::Value) -> Result<JsValue, JsValue> {
     value
@@ -238,29 +404,35 @@ impl Transaction {
         bet: u64,
         session_id: u64,
     ) -> Result<Transaction, JsValue> {
-        use nullspace_types::casino::GameType;
-        let game_type = match game_type {
-            0 => GameType::Baccarat,
-            1 => GameType::Blackjack,
-            2 => GameType::CasinoWar,
-            3 => GameType::Craps,
-            4 => GameType::VideoPoker,
-            5 => GameType::HiLo,
-            6 => GameType::Roulette,
-            7 => GameType::SicBo,
-            8 => GameType::ThreeCard,
-            9 => GameType::UltimateHoldem,
-            _ => {
-                return Err(JsValue::from_str(&format!(
-                    "Invalid game type: {}",
-                    game_type
-                )))
-            }
+        let instruction = Instruction::CasinoStartGame {
+            game_type: parse_game_type(game_type)?,
+            bet,
+            session_id,
+            commitment: None,
         };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
+
+    /// Sign a new casino start game transaction committing to player entropy (the sha256 of
+    /// the 32-byte preimage the first move's payload must start with).
+    #[wasm_bindgen]
+    pub fn casino_start_game_committed(
+        signer: &Signer,
+        nonce: u64,
+        game_type: u8,
+        bet: u64,
+        session_id: u64,
+        commitment: &[u8],
+    ) -> Result<Transaction, JsValue> {
+        let mut buf = commitment;
+        let commitment = Digest::read(&mut buf)
+            .map_err(|e| JsValue::from_str(&format!("Invalid commitment: {e:?}")))?;
         let instruction = Instruction::CasinoStartGame {
-            game_type,
+            game_type: parse_game_type(game_type)?,
             bet,
             session_id,
+            commitment: Some(commitment),
         };
         let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
         Ok(Transaction { inner: tx })
@@ -282,6 +454,18 @@ impl Transaction {
         Ok(Transaction { inner: tx })
     }
 
+    /// Sign a new casino abandon session transaction.
+    #[wasm_bindgen]
+    pub fn casino_abandon_session(
+        signer: &Signer,
+        nonce: u64,
+        session_id: u64,
+    ) -> Result<Transaction, JsValue> {
+        let instruction = Instruction::CasinoAbandonSession { session_id };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
+
     /// Sign a new casino toggle shield transaction.
     #[wasm_bindgen]
     pub fn casino_toggle_shield(signer: &Signer, nonce: u64) -> Result<Transaction, JsValue> {
@@ -350,6 +534,71 @@ impl Transaction {
         Ok(Transaction { inner: tx })
     }
 
+    /// Sign a new casino schedule tournament transaction.
+    #[wasm_bindgen]
+    pub fn casino_schedule_tournament(
+        signer: &Signer,
+        nonce: u64,
+        tournament_id: u64,
+        start_view: u64,
+        end_view: u64,
+    ) -> Result<Transaction, JsValue> {
+        let instruction = Instruction::CasinoScheduleTournament {
+            tournament_id,
+            start_view,
+            end_view,
+        };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
+
+    /// Sign a new tournament rules transaction (only accepted from the house's governance key).
+    ///
+    /// `disabled_games` lists game type ids and `game_configs` packs each override as
+    /// [gameType, paytable, commissionOnWin, dealerHitsSoft17, maxSplitHands, recordRng,
+    /// shoeDecks, shoePenetration, dealerPeeks, evenMoney].
+    #[wasm_bindgen]
+    pub fn set_tournament_rules(
+        signer: &Signer,
+        nonce: u64,
+        tournament_id: u64,
+        min_bet: u64,
+        max_bet: u64,
+        disabled_games: &[u8],
+        game_configs: &[u8],
+        super_boost_bps: u16,
+    ) -> Result<Transaction, JsValue> {
+        let disabled_games = disabled_games
+            .iter()
+            .map(|game_type| parse_game_type(*game_type))
+            .collect::<Result<Vec<_>, _>>()?;
+        let entry_size = 1 + GameConfig::SIZE;
+        if game_configs.len() % entry_size != 0 {
+            return Err(JsValue::from_str("Invalid game configs length"));
+        }
+        let game_configs = game_configs
+            .chunks_exact(entry_size)
+            .map(|entry| {
+                let mut buf = &entry[1..];
+                let config = GameConfig::read(&mut buf)
+                    .map_err(|e| JsValue::from_str(&format!("Invalid game config: {e:?}")))?;
+                Ok((parse_game_type(entry[0])?, config))
+            })
+            .collect::<Result<Vec<_>, JsValue>>()?;
+        let instruction = Instruction::SetTournamentRules {
+            tournament_id,
+            rules: TournamentRules {
+                min_bet,
+                max_bet,
+                disabled_games,
+                game_configs,
+                super_boost_bps,
+            },
+        };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
+
     /// Sign a new casino deposit transaction (dev faucet / testing).
     #[wasm_bindgen]
     pub fn casino_deposit(
@@ -389,8 +638,8 @@ impl Transaction {
 
     /// Sign a new unstake transaction.
     #[wasm_bindgen]
-    pub fn unstake(signer: &Signer, nonce: u64) -> Result<Transaction, JsValue> {
-        let instruction = Instruction::Unstake;
+    pub fn unstake(signer: &Signer, nonce: u64, amount: u64) -> Result<Transaction, JsValue> {
+        let instruction = Instruction::Unstake { amount };
         let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
         Ok(Transaction { inner: tx })
     }
@@ -431,6 +680,18 @@ impl Transaction {
         Ok(Transaction { inner: tx })
     }
 
+    /// Sign a new withdraw collateral transaction.
+    #[wasm_bindgen]
+    pub fn withdraw_collateral(
+        signer: &Signer,
+        nonce: u64,
+        amount: u64,
+    ) -> Result<Transaction, JsValue> {
+        let instruction = Instruction::WithdrawCollateral { amount };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
+
     /// Sign a new borrow vUSDT transaction.
     #[wasm_bindgen]
     pub fn borrow_usdt(signer: &Signer, nonce: u64, amount: u64) -> Result<Transaction, JsValue> {
@@ -447,7 +708,7 @@ impl Transaction {
         Ok(Transaction { inner: tx })
     }
 
-    /// Sign a new AMM swap transaction.
+    /// Sign a new AMM swap transaction (on the RNG/vUSDT pool unless `pair` is given).
     #[wasm_bindgen]
     pub fn swap(
         signer: &Signer,
@@ -455,8 +716,10 @@ impl Transaction {
         amount_in: u64,
         min_amount_out: u64,
         is_buying_rng: bool,
+        pair: Option<u16>,
     ) -> Result<Transaction, JsValue> {
         let instruction = Instruction::Swap {
+            pair: amm_pair(pair),
             amount_in,
             min_amount_out,
             is_buying_rng,
@@ -465,15 +728,17 @@ impl Transaction {
         Ok(Transaction { inner: tx })
     }
 
-    /// Sign a new add liquidity transaction.
+    /// Sign a new add liquidity transaction (on the RNG/vUSDT pool unless `pair` is given).
     #[wasm_bindgen]
     pub fn add_liquidity(
         signer: &Signer,
         nonce: u64,
         rng_amount: u64,
         usdt_amount: u64,
+        pair: Option<u16>,
     ) -> Result<Transaction, JsValue> {
         let instruction = Instruction::AddLiquidity {
+            pair: amm_pair(pair),
             rng_amount,
             usdt_amount,
         };
@@ -481,105 +746,724 @@ impl Transaction {
         Ok(Transaction { inner: tx })
     }
 
-    /// Sign a new remove liquidity transaction.
+    /// Sign a new remove liquidity transaction (on the RNG/vUSDT pool unless `pair` is given).
     #[wasm_bindgen]
     pub fn remove_liquidity(
         signer: &Signer,
         nonce: u64,
         shares: u64,
+        pair: Option<u16>,
     ) -> Result<Transaction, JsValue> {
-        let instruction = Instruction::RemoveLiquidity { shares };
+        let instruction = Instruction::RemoveLiquidity {
+            pair: amm_pair(pair),
+            shares,
+        };
         let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
         Ok(Transaction { inner: tx })
     }
-}
 
-/// Encode an account key.
-#[wasm_bindgen]
-pub fn encode_account_key(public_key: &[u8]) -> Result<Vec<u8>, JsValue> {
-    let mut buf = public_key;
-    let pk = ed25519::PublicKey::read(&mut buf)
-        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
-    let key = Key::Account(pk);
-    Ok(key.encode().to_vec())
-}
+    /// Sign a new place limit order transaction.
+    #[wasm_bindgen]
+    pub fn place_limit_order(
+        signer: &Signer,
+        nonce: u64,
+        amount_in: u64,
+        min_price: u64,
+        is_buying_rng: bool,
+    ) -> Result<Transaction, JsValue> {
+        let instruction = Instruction::PlaceLimitOrder {
+            amount_in,
+            min_price,
+            is_buying_rng,
+        };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
 
-/// Encode a casino player key.
-#[wasm_bindgen]
-pub fn encode_casino_player_key(public_key: &[u8]) -> Result<Vec<u8>, JsValue> {
-    let mut buf = public_key;
-    let pk = ed25519::PublicKey::read(&mut buf)
-        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
-    let key = Key::CasinoPlayer(pk);
-    Ok(key.encode().to_vec())
-}
+    /// Sign a new cancel limit order transaction.
+    #[wasm_bindgen]
+    pub fn cancel_limit_order(
+        signer: &Signer,
+        nonce: u64,
+        order_id: u64,
+    ) -> Result<Transaction, JsValue> {
+        let instruction = Instruction::CancelLimitOrder { order_id };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
 
-/// Encode a casino session key.
-#[wasm_bindgen]
-pub fn encode_casino_session_key(session_id: u64) -> Vec<u8> {
-    let key = Key::CasinoSession(session_id);
-    key.encode().to_vec()
-}
+    /// Sign a new single-sided (zap) add liquidity transaction (on the RNG/vUSDT pool unless
+    /// `pair` is given).
+    #[wasm_bindgen]
+    pub fn zap_liquidity(
+        signer: &Signer,
+        nonce: u64,
+        amount_in: u64,
+        min_shares: u64,
+        from_rng: bool,
+        pair: Option<u16>,
+    ) -> Result<Transaction, JsValue> {
+        let instruction = Instruction::ZapLiquidity {
+            pair: amm_pair(pair),
+            amount_in,
+            min_shares,
+            from_rng,
+        };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
 
-/// Encode the casino leaderboard key.
-#[wasm_bindgen]
-pub fn encode_casino_leaderboard_key() -> Vec<u8> {
-    let key = Key::CasinoLeaderboard;
-    key.encode().to_vec()
-}
+    /// Sign a new transaction submitting slashing evidence (signed by the validator set).
+    #[wasm_bindgen]
+    pub fn slash(
+        signer: &Signer,
+        nonce: u64,
+        staker: &[u8],
+        bps: u16,
+        signature: &[u8],
+    ) -> Result<Transaction, JsValue> {
+        let mut buf = staker;
+        let staker = ed25519::PublicKey::read(&mut buf)
+            .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+        let mut buf = signature;
+        let signature = nullspace_types::Signature::read(&mut buf)
+            .map_err(|e| JsValue::from_str(&format!("Invalid signature: {e:?}")))?;
+        let instruction = Instruction::Slash {
+            staker,
+            bps,
+            signature,
+        };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
 
-/// Encode a casino tournament key.
-#[wasm_bindgen]
-pub fn encode_casino_tournament_key(tournament_id: u64) -> Vec<u8> {
-    let key = Key::Tournament(tournament_id);
-    key.encode().to_vec()
-}
+    /// Sign a new transaction setting (or, without `admin`, clearing) the key holding an admin
+    /// role (signed by the validator set).
+    ///
+    /// `role` is 0 for governance, 1 for the KYC admin and 2 for the treasury admin.
+    #[wasm_bindgen]
+    pub fn set_admin(
+        signer: &Signer,
+        nonce: u64,
+        role: u8,
+        admin: Option<Vec<u8>>,
+        signature: &[u8],
+    ) -> Result<Transaction, JsValue> {
+        let role = AdminRole::read(&mut &[role][..])
+            .map_err(|e| JsValue::from_str(&format!("Invalid admin role: {e:?}")))?;
+        let admin = admin
+            .map(|admin| {
+                ed25519::PublicKey::read(&mut admin.as_slice())
+                    .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))
+            })
+            .transpose()?;
+        let mut buf = signature;
+        let signature = nullspace_types::Signature::read(&mut buf)
+            .map_err(|e| JsValue::from_str(&format!("Invalid signature: {e:?}")))?;
+        let instruction = Instruction::SetAdmin {
+            role,
+            admin,
+            signature,
+        };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
 
-/// Encode a vault key.
-#[wasm_bindgen]
-pub fn encode_vault_key(public_key: &[u8]) -> Result<Vec<u8>, JsValue> {
-    let mut buf = public_key;
-    let pk = ed25519::PublicKey::read(&mut buf)
-        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
-    let key = Key::Vault(pk);
-    Ok(key.encode().to_vec())
-}
+    /// Sign a new house settings transaction (only accepted from governance).
+    ///
+    /// `settings` is the encoded [HouseSettings].
+    #[wasm_bindgen]
+    pub fn set_house_settings(
+        signer: &Signer,
+        nonce: u64,
+        settings: &[u8],
+    ) -> Result<Transaction, JsValue> {
+        let mut buf = settings;
+        let settings = HouseSettings::read(&mut buf)
+            .map_err(|e| JsValue::from_str(&format!("Invalid house settings: {e:?}")))?;
+        let instruction = Instruction::SetHouseSettings { settings };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
 
-/// Encode the AMM pool key.
-#[wasm_bindgen]
-pub fn encode_amm_pool_key() -> Vec<u8> {
-    let key = Key::AmmPool;
-    key.encode().to_vec()
-}
+    /// Sign a new KYC status transaction (only accepted from the house's KYC admin).
+    #[wasm_bindgen]
+    pub fn set_kyc_status(
+        signer: &Signer,
+        nonce: u64,
+        player: &[u8],
+        verified: bool,
+    ) -> Result<Transaction, JsValue> {
+        let mut buf = player;
+        let player = ed25519::PublicKey::read(&mut buf)
+            .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+        let instruction = Instruction::SetKycStatus { player, verified };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
 
-/// Encode an LP balance key.
-#[wasm_bindgen]
-pub fn encode_lp_balance_key(public_key: &[u8]) -> Result<Vec<u8>, JsValue> {
-    let mut buf = public_key;
-    let pk = ed25519::PublicKey::read(&mut buf)
-        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
-    let key = Key::LpBalance(pk);
-    Ok(key.encode().to_vec())
-}
+    /// Sign a new guardians transaction (`guardians` is the concatenation of their public
+    /// keys).
+    #[wasm_bindgen]
+    pub fn set_guardians(
+        signer: &Signer,
+        nonce: u64,
+        guardians: &[u8],
+        threshold: u8,
+    ) -> Result<Transaction, JsValue> {
+        if guardians.len() % ed25519::PublicKey::SIZE != 0 {
+            return Err(JsValue::from_str("Invalid guardian keys length"));
+        }
+        let guardians = guardians
+            .chunks(ed25519::PublicKey::SIZE)
+            .map(|mut buf| ed25519::PublicKey::read(&mut buf))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+        let instruction = Instruction::SetGuardians {
+            guardians,
+            threshold,
+        };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
 
-/// Encode the house key.
-#[wasm_bindgen]
-pub fn encode_house_key() -> Vec<u8> {
-    let key = Key::House;
-    key.encode().to_vec()
-}
+    /// Sign a new account recovery approval (only accepted from the account's guardians).
+    #[wasm_bindgen]
+    pub fn recover_account(
+        signer: &Signer,
+        nonce: u64,
+        account: &[u8],
+        new_key: &[u8],
+    ) -> Result<Transaction, JsValue> {
+        let mut buf = account;
+        let account = ed25519::PublicKey::read(&mut buf)
+            .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+        let mut buf = new_key;
+        let new_key = ed25519::PublicKey::read(&mut buf)
+            .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+        let instruction = Instruction::RecoverAccount { account, new_key };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
 
-/// Encode a staker key.
-#[wasm_bindgen]
-pub fn encode_staker_key(public_key: &[u8]) -> Result<Vec<u8>, JsValue> {
-    let mut buf = public_key;
-    let pk = ed25519::PublicKey::read(&mut buf)
-        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
-    let key = Key::Staker(pk);
-    Ok(key.encode().to_vec())
-}
+    /// Sign a new vUSDT allowance transaction (zero revokes the allowance).
+    #[wasm_bindgen]
+    pub fn approve(
+        signer: &Signer,
+        nonce: u64,
+        spender: &[u8],
+        amount: u64,
+    ) -> Result<Transaction, JsValue> {
+        let mut buf = spender;
+        let spender = ed25519::PublicKey::read(&mut buf)
+            .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+        let instruction = Instruction::Approve { spender, amount };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
 
-/// Encode UpdatesFilter::All
+    /// Sign a new transaction spending an allowance `owner` granted the signer.
+    #[wasm_bindgen]
+    pub fn transfer_vusdt_from(
+        signer: &Signer,
+        nonce: u64,
+        owner: &[u8],
+        to: &[u8],
+        amount: u64,
+    ) -> Result<Transaction, JsValue> {
+        let mut buf = owner;
+        let owner = ed25519::PublicKey::read(&mut buf)
+            .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+        let mut buf = to;
+        let to = ed25519::PublicKey::read(&mut buf)
+            .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+        let instruction = Instruction::TransferVusdtFrom { owner, to, amount };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
+
+    /// Sign a new vesting transaction (escrowing `amount` chips for `to`).
+    #[wasm_bindgen]
+    pub fn create_vesting(
+        signer: &Signer,
+        nonce: u64,
+        to: &[u8],
+        amount: u64,
+        start_view: u64,
+        duration: u64,
+    ) -> Result<Transaction, JsValue> {
+        let mut buf = to;
+        let to = ed25519::PublicKey::read(&mut buf)
+            .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+        let instruction = Instruction::CreateVesting {
+            to,
+            amount,
+            start_view,
+            duration,
+        };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
+
+    /// Sign a new vested chips claim transaction.
+    #[wasm_bindgen]
+    pub fn claim_vested(signer: &Signer, nonce: u64) -> Result<Transaction, JsValue> {
+        let instruction = Instruction::ClaimVested;
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
+
+    /// Sign a new treasury buyback transaction (only accepted from the treasury admin).
+    #[wasm_bindgen]
+    pub fn execute_buyback(
+        signer: &Signer,
+        nonce: u64,
+        amount: u64,
+    ) -> Result<Transaction, JsValue> {
+        let instruction = Instruction::ExecuteBuyback { amount };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
+
+    /// Sign a new game rules transaction (only accepted from the house's governance key).
+    #[wasm_bindgen]
+    pub fn set_game_config(
+        signer: &Signer,
+        nonce: u64,
+        game_type: u8,
+        paytable: u8,
+        commission_on_win: bool,
+        dealer_hits_soft_17: bool,
+        max_split_hands: u8,
+        record_rng: bool,
+        shoe_decks: u8,
+        shoe_penetration: u8,
+        dealer_peeks: bool,
+        even_money: bool,
+    ) -> Result<Transaction, JsValue> {
+        let instruction = Instruction::SetGameConfig {
+            game_type: parse_game_type(game_type)?,
+            config: GameConfig {
+                paytable,
+                commission_on_win,
+                dealer_hits_soft_17,
+                max_split_hands,
+                record_rng,
+                shoe_decks,
+                shoe_penetration,
+                dealer_peeks,
+                even_money,
+            },
+        };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
+
+    /// Sign a new bonus grant transaction (only accepted from the house's governance key).
+    #[wasm_bindgen]
+    pub fn grant_bonus(
+        signer: &Signer,
+        nonce: u64,
+        player: &[u8],
+        amount: u64,
+    ) -> Result<Transaction, JsValue> {
+        let mut buf = player;
+        let player = ed25519::PublicKey::read(&mut buf)
+            .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+        let instruction = Instruction::GrantBonus { player, amount };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
+
+    /// Sign a new economy parameters transaction (only accepted from the house's governance
+    /// key).
+    #[wasm_bindgen]
+    pub fn set_economy_params(
+        signer: &Signer,
+        nonce: u64,
+        initial_chips: u64,
+        faucet_amount: u64,
+        super_mode_fee_bps: u16,
+        amm_fee_bps: u16,
+        minimum_liquidity: u64,
+    ) -> Result<Transaction, JsValue> {
+        let instruction = Instruction::SetEconomyParams {
+            params: EconomyParams {
+                initial_chips,
+                faucet_amount,
+                super_mode_fee_bps,
+                amm_fee_bps,
+                minimum_liquidity: LpShares(minimum_liquidity),
+            },
+        };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
+
+    /// Sign a new daily bonus claim transaction.
+    #[wasm_bindgen]
+    pub fn claim_daily_bonus(signer: &Signer, nonce: u64) -> Result<Transaction, JsValue> {
+        let instruction = Instruction::ClaimDailyBonus;
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
+
+    /// Sign a new profile update transaction.
+    #[wasm_bindgen]
+    pub fn update_profile(
+        signer: &Signer,
+        nonce: u64,
+        display_name: &str,
+        avatar_id: u32,
+        title: &str,
+    ) -> Result<Transaction, JsValue> {
+        let instruction = Instruction::UpdateProfile {
+            display_name: display_name.to_string(),
+            avatar_id,
+            title: title.to_string(),
+        };
+        let tx = ExecutionTransaction::sign(&signer.private_key, nonce, instruction);
+        Ok(Transaction { inner: tx })
+    }
+}
+
+/// Encode an account key.
+#[wasm_bindgen]
+pub fn encode_account_key(public_key: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let mut buf = public_key;
+    let pk = ed25519::PublicKey::read(&mut buf)
+        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+    let key = Key::Account(pk);
+    Ok(key.encode().to_vec())
+}
+
+/// Encode a casino player key.
+#[wasm_bindgen]
+pub fn encode_casino_player_key(public_key: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let mut buf = public_key;
+    let pk = ed25519::PublicKey::read(&mut buf)
+        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+    let key = Key::CasinoPlayer(pk);
+    Ok(key.encode().to_vec())
+}
+
+/// Encode a casino session key.
+#[wasm_bindgen]
+pub fn encode_casino_session_key(session_id: u64) -> Vec<u8> {
+    let key = Key::CasinoSession(session_id);
+    key.encode().to_vec()
+}
+
+/// The session ID a `CasinoStartGame` signed by `public_key` with `nonce` must use.
+#[wasm_bindgen]
+pub fn derive_session_id(public_key: &[u8], nonce: u64) -> Result<u64, JsValue> {
+    let mut buf = public_key;
+    let pk = ed25519::PublicKey::read(&mut buf)
+        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+    Ok(nullspace_types::casino::derive_session_id(&pk, nonce))
+}
+
+/// Encode the casino leaderboard key.
+#[wasm_bindgen]
+pub fn encode_casino_leaderboard_key() -> Vec<u8> {
+    let key = Key::CasinoLeaderboard;
+    key.encode().to_vec()
+}
+
+/// Encode the key of an archived leaderboard season.
+#[wasm_bindgen]
+pub fn encode_leaderboard_season_key(season: u64) -> Vec<u8> {
+    let key = Key::LeaderboardSeason(season);
+    key.encode().to_vec()
+}
+
+/// Encode a casino tournament key.
+#[wasm_bindgen]
+pub fn encode_casino_tournament_key(tournament_id: u64) -> Vec<u8> {
+    let key = Key::Tournament(tournament_id);
+    key.encode().to_vec()
+}
+
+/// Encode the scheduled tournaments key.
+#[wasm_bindgen]
+pub fn encode_tournament_schedules_key() -> Vec<u8> {
+    let key = Key::TournamentSchedules;
+    key.encode().to_vec()
+}
+
+/// Encode a vault key.
+#[wasm_bindgen]
+pub fn encode_vault_key(public_key: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let mut buf = public_key;
+    let pk = ed25519::PublicKey::read(&mut buf)
+        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+    let key = Key::Vault(pk);
+    Ok(key.encode().to_vec())
+}
+
+/// Encode the RNG/vUSDT AMM pool key.
+#[wasm_bindgen]
+pub fn encode_amm_pool_key() -> Vec<u8> {
+    let key = Key::AmmPool(nullspace_types::casino::PairId::RNG_VUSDT);
+    key.encode().to_vec()
+}
+
+/// Encode the AMM pool key for a pair.
+#[wasm_bindgen]
+pub fn encode_amm_pair_pool_key(pair: u16) -> Vec<u8> {
+    let key = Key::AmmPool(nullspace_types::casino::PairId(pair));
+    key.encode().to_vec()
+}
+
+/// Encode the AMM pool registry key.
+#[wasm_bindgen]
+pub fn encode_amm_pools_key() -> Vec<u8> {
+    let key = Key::AmmPools;
+    key.encode().to_vec()
+}
+
+/// Encode the price oracle key.
+#[wasm_bindgen]
+pub fn encode_oracle_key() -> Vec<u8> {
+    let key = Key::Oracle;
+    key.encode().to_vec()
+}
+
+/// Encode the treasury key.
+#[wasm_bindgen]
+pub fn encode_treasury_key() -> Vec<u8> {
+    let key = Key::Treasury;
+    key.encode().to_vec()
+}
+
+/// Encode the key of a game's table rules.
+#[wasm_bindgen]
+pub fn encode_game_config_key(game_type: u8) -> Result<Vec<u8>, JsValue> {
+    let key = Key::GameConfig(parse_game_type(game_type)?);
+    Ok(key.encode().to_vec())
+}
+
+/// Encode the economy parameters key.
+#[wasm_bindgen]
+pub fn encode_economy_params_key() -> Vec<u8> {
+    let key = Key::EconomyParams;
+    key.encode().to_vec()
+}
+
+/// Encode the key of a game's statistics.
+#[wasm_bindgen]
+pub fn encode_game_stats_key(game_type: u8) -> Result<Vec<u8>, JsValue> {
+    let key = Key::GameStats(parse_game_type(game_type)?);
+    Ok(key.encode().to_vec())
+}
+
+/// Encode a limit order key.
+#[wasm_bindgen]
+pub fn encode_limit_order_key(order_id: u64) -> Vec<u8> {
+    let key = Key::LimitOrder(order_id);
+    key.encode().to_vec()
+}
+
+/// Encode the limit order book key.
+#[wasm_bindgen]
+pub fn encode_limit_order_book_key() -> Vec<u8> {
+    let key = Key::LimitOrderBook;
+    key.encode().to_vec()
+}
+
+/// Encode a game history key.
+#[wasm_bindgen]
+pub fn encode_game_history_key(public_key: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let mut buf = public_key;
+    let pk = ed25519::PublicKey::read(&mut buf)
+        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+    let key = Key::GameHistory(pk);
+    Ok(key.encode().to_vec())
+}
+
+/// Encode an account's guardians key.
+#[wasm_bindgen]
+pub fn encode_guardians_key(public_key: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let mut buf = public_key;
+    let pk = ed25519::PublicKey::read(&mut buf)
+        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+    let key = Key::Guardians(pk);
+    Ok(key.encode().to_vec())
+}
+
+/// Encode the key of the vUSDT allowance `owner` granted `spender`.
+#[wasm_bindgen]
+pub fn encode_allowance_key(owner: &[u8], spender: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let mut buf = owner;
+    let owner = ed25519::PublicKey::read(&mut buf)
+        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+    let mut buf = spender;
+    let spender = ed25519::PublicKey::read(&mut buf)
+        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+    let key = Key::Allowance(owner, spender);
+    Ok(key.encode().to_vec())
+}
+
+/// Encode the key of a player's vesting schedules.
+#[wasm_bindgen]
+pub fn encode_vesting_key(public_key: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let mut buf = public_key;
+    let pk = ed25519::PublicKey::read(&mut buf)
+        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+    let key = Key::Vesting(pk);
+    Ok(key.encode().to_vec())
+}
+
+/// Encode an LP balance key.
+#[wasm_bindgen]
+pub fn encode_lp_balance_key(public_key: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let mut buf = public_key;
+    let pk = ed25519::PublicKey::read(&mut buf)
+        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+    let key = Key::LpBalance(nullspace_types::casino::PairId::RNG_VUSDT, pk);
+    Ok(key.encode().to_vec())
+}
+
+/// Encode the LP balance key for a pair.
+#[wasm_bindgen]
+pub fn encode_lp_pair_balance_key(public_key: &[u8], pair: u16) -> Result<Vec<u8>, JsValue> {
+    let mut buf = public_key;
+    let pk = ed25519::PublicKey::read(&mut buf)
+        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+    let key = Key::LpBalance(nullspace_types::casino::PairId(pair), pk);
+    Ok(key.encode().to_vec())
+}
+
+/// Encode the house key.
+#[wasm_bindgen]
+pub fn encode_house_key() -> Vec<u8> {
+    let key = Key::House;
+    key.encode().to_vec()
+}
+
+/// Encode a staker key.
+#[wasm_bindgen]
+pub fn encode_staker_key(public_key: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let mut buf = public_key;
+    let pk = ed25519::PublicKey::read(&mut buf)
+        .map_err(|e| JsValue::from_str(&format!("Invalid public key: {e:?}")))?;
+    let key = Key::Staker(pk);
+    Ok(key.encode().to_vec())
+}
+
+/// Encode a slots move that spins the reels with the first `lines` paylines active.
+#[wasm_bindgen]
+pub fn encode_slots_spin(lines: u8) -> Vec<u8> {
+    vec![slots::Move::Spin as u8, lines]
+}
+
+/// Encode a slots move that plays the next free spin.
+#[wasm_bindgen]
+pub fn encode_slots_free_spin() -> Vec<u8> {
+    vec![slots::Move::FreeSpin as u8]
+}
+
+/// Encode a slots move that spins `rounds` times with the first `lines` paylines active.
+#[wasm_bindgen]
+pub fn encode_slots_auto_spin(lines: u8, rounds: u8) -> Vec<u8> {
+    vec![slots::Move::AutoSpin as u8, lines, rounds]
+}
+
+/// Decode the state blob of a slots session.
+#[wasm_bindgen]
+pub fn decode_slots_state(state: &[u8]) -> Result<JsValue, JsValue> {
+    let state = slots::SlotsState::from_blob(state)
+        .ok_or_else(|| JsValue::from_str("Invalid slots state"))?;
+    let stage = match state.stage {
+        slots::Stage::Ready => "Ready",
+        slots::Stage::FreeSpins => "FreeSpins",
+        slots::Stage::Complete => "Complete",
+    };
+    let json = serde_json::json!({
+        "stage": stage,
+        "lines": state.lines,
+        "free_spins": state.free_spins,
+        "last_win": state.last_win,
+        "total_won": state.total_won,
+        "reels": state.grid
+    });
+    to_object(&json)
+}
+
+/// Get the slots paytable (line pays are for 3, 4 and 5 of a kind, in multiples of the line
+/// bet, and free spins are for 3, 4 and 5 Scatters).
+#[wasm_bindgen]
+pub fn slots_paytable() -> Result<JsValue, JsValue> {
+    let pays: Vec<_> = slots::PAYTABLE
+        .iter()
+        .enumerate()
+        .map(|(symbol, pays)| {
+            serde_json::json!({
+                "symbol": symbol,
+                "name": slots::SYMBOLS[symbol],
+                "pays": pays
+            })
+        })
+        .collect();
+    let json = serde_json::json!({
+        "symbols": slots::SYMBOLS,
+        "wild": slots::WILD,
+        "scatter": slots::SCATTER,
+        "pays": pays,
+        "paylines": slots::PAYLINES,
+        "reel_strips": slots::REEL_STRIPS,
+        "free_spins": slots::FREE_SPINS,
+        "max_free_spins": slots::MAX_FREE_SPINS
+    });
+    to_object(&json)
+}
+
+/// Get the HiLo ladder steps (multipliers in basis points for a correct Higher and Lower call
+/// from each current rank, Ace through King; 0 means the call is impossible).
+#[wasm_bindgen]
+pub fn hilo_multipliers() -> Result<JsValue, JsValue> {
+    let steps: Vec<_> = hilo::MULTIPLIERS
+        .iter()
+        .enumerate()
+        .map(|(i, &[higher, lower])| {
+            serde_json::json!({
+                "rank": i + 1,
+                "higher": higher,
+                "lower": lower
+            })
+        })
+        .collect();
+    let json = serde_json::json!({
+        "base": 10_000,
+        "steps": steps
+    });
+    to_object(&json)
+}
+
+/// Get a video poker variant's paytable (winning hands from the best down, with what they pay
+/// "to 1"). Variants are numbered as the game config's paytable.
+#[wasm_bindgen]
+pub fn video_poker_paytable(variant: u8) -> Result<JsValue, JsValue> {
+    let variant = video_poker::Variant::try_from(variant)
+        .map_err(|_| JsValue::from_str(&format!("Unknown video poker variant: {variant}")))?;
+    let pays: Vec<_> = video_poker::paytable(variant)
+        .iter()
+        .map(|&(hand, pays)| {
+            serde_json::json!({
+                "hand": hand.name(),
+                "pays": pays
+            })
+        })
+        .collect();
+    let json = serde_json::json!({
+        "variant": variant as u8,
+        "name": variant.name(),
+        "pays": pays
+    });
+    to_object(&json)
+}
+
+/// Encode UpdatesFilter::All
 #[wasm_bindgen]
 pub fn encode_updates_filter_all() -> Vec<u8> {
     UpdatesFilter::All.encode().to_vec()
@@ -615,10 +1499,28 @@ pub fn encode_query_index(index: u64) -> Vec<u8> {
     query.encode().to_vec()
 }
 
+/// Encode the query string of a paginated request (`cursor` being the `next_cursor` of the
+/// previous page, if any).
+#[wasm_bindgen]
+pub fn encode_page_request(cursor: Option<String>, limit: Option<u32>) -> Result<String, JsValue> {
+    let cursor = cursor
+        .map(|cursor| {
+            nullspace_types::api::Cursor::parse(&cursor)
+                .ok_or_else(|| JsValue::from_str("Invalid cursor"))
+        })
+        .transpose()?;
+    let request = nullspace_types::api::PageRequest {
+        cursor,
+        limit: limit.map(|limit| limit as usize),
+    };
+    Ok(request.to_query())
+}
+
 // Helper function to convert Value to JSON
 fn decode_value(value: Value) -> Result<JsValue, JsValue> {
     // Convert to JSON
     let json = match value {
+        Value::Versioned { value, .. } => return decode_value(*value),
         Value::Account(account) => {
             serde_json::json!({
                 "type": "Account",
@@ -637,11 +1539,11 @@ fn decode_value(value: Value) -> Result<JsValue, JsValue> {
                 "type": "CasinoPlayer",
                 "nonce": player.nonce,
                 "name": player.name,
-                "chips": player.chips,
-                "vusdt_balance": player.vusdt_balance,
+                "chips": player.chips.0,
+                "vusdt_balance": player.vusdt_balance.0,
                 "shields": player.shields,
                 "doubles": player.doubles,
-                "tournament_chips": player.tournament_chips,
+                "tournament_chips": player.tournament_chips.0,
                 "tournament_shields": player.tournament_shields,
                 "tournament_doubles": player.tournament_doubles,
                 "active_tournament": player.active_tournament,
@@ -649,12 +1551,26 @@ fn decode_value(value: Value) -> Result<JsValue, JsValue> {
                 "active_shield": player.active_shield,
                 "active_double": player.active_double,
                 "active_super": player.active_super,
-                "active_session": player.active_session,
+                "active_sessions": player.active_sessions,
                 "last_deposit_block": player.last_deposit_block,
                 "aura_meter": player.aura_meter,
                 "tournaments_played_today": player.tournaments_played_today,
                 "last_tournament_ts": player.last_tournament_ts,
-                "is_kyc_verified": player.is_kyc_verified
+                "is_kyc_verified": player.is_kyc_verified,
+                "daily_streak": player.daily_streak,
+                "total_wagered": player.total_wagered,
+                "loyalty_points": player.loyalty_points,
+                "vip_tier": player.vip_tier,
+                "bonus": {
+                    "chips": player.bonus.chips.0,
+                    "wagering_remaining": player.bonus.wagering_remaining
+                },
+                "profile": {
+                    "display_name": player.profile.display_name,
+                    "avatar_id": player.profile.avatar_id,
+                    "title": player.profile.title,
+                    "updated_at": player.profile.updated_at
+                }
             })
         }
         Value::CasinoSession(session) => {
@@ -686,7 +1602,17 @@ fn decode_value(value: Value) -> Result<JsValue, JsValue> {
                     "multipliers": multipliers
                 },
                 "is_tournament": session.is_tournament,
-                "tournament_id": session.tournament_id
+                "tournament_id": session.tournament_id,
+                "last_move_at": session.last_move_at,
+                "wagered": session.wagered,
+                "entropy": session.entropy.as_ref().map(|entropy| match entropy {
+                    PlayerEntropy::Committed(commitment) => serde_json::json!({
+                        "commitment": hex(&commitment.encode())
+                    }),
+                    PlayerEntropy::Revealed(preimage) => serde_json::json!({
+                        "preimage": hex(&preimage.encode())
+                    }),
+                })
             })
         }
         Value::CasinoLeaderboard(leaderboard) => {
@@ -697,13 +1623,15 @@ fn decode_value(value: Value) -> Result<JsValue, JsValue> {
                     serde_json::json!({
                         "player": hex(&e.player.encode()),
                         "name": e.name,
-                        "chips": e.chips
+                        "chips": e.chips.0
                     })
                 })
                 .collect();
             serde_json::json!({
                 "type": "CasinoLeaderboard",
-                "entries": entries
+                "entries": entries,
+                "season": leaderboard.season,
+                "season_end": leaderboard.season_end
             })
         }
         Value::Tournament(tournament) => {
@@ -721,7 +1649,7 @@ fn decode_value(value: Value) -> Result<JsValue, JsValue> {
                     serde_json::json!({
                         "player": hex(&e.player.encode()),
                         "name": e.name,
-                        "chips": e.chips,
+                        "chips": e.chips.0,
                         "rank": e.rank
                     })
                 })
@@ -741,7 +1669,12 @@ fn decode_value(value: Value) -> Result<JsValue, JsValue> {
                 "starting_doubles": tournament.starting_doubles,
                 "leaderboard": {
                     "entries": leaderboard_entries
-                }
+                },
+                "schedule": tournament.schedule.map(|schedule| serde_json::json!({
+                    "start_view": schedule.start_view,
+                    "end_view": schedule.end_view
+                })),
+                "rules": tournament_rules_json(&tournament.rules)
             })
         }
         // Staking & House values
@@ -757,40 +1690,287 @@ fn decode_value(value: Value) -> Result<JsValue, JsValue> {
                 "total_burned": house.total_burned,
                 "total_issuance": house.total_issuance,
                 "three_card_progressive_jackpot": house.three_card_progressive_jackpot,
-                "uth_progressive_jackpot": house.uth_progressive_jackpot
+                "uth_progressive_jackpot": house.uth_progressive_jackpot,
+                "caribbean_stud_progressive_jackpot": house.caribbean_stud_progressive_jackpot,
+                "fee_schedule": {
+                    "flat": house.fee_schedule.flat,
+                    "overrides": house.fee_schedule.overrides
+                },
+                "debt_rate_bps": house.debt_rate_bps,
+                "debt_index": house.debt_index.to_string(),
+                "daily_bonus": {
+                    "amount": house.daily_bonus.amount,
+                    "cooldown": house.daily_bonus.cooldown,
+                    "streak_bps": house.daily_bonus.streak_bps,
+                    "max_streak": house.daily_bonus.max_streak,
+                    "faucet_enabled": house.daily_bonus.faucet_enabled
+                },
+                "max_active_sessions": house.max_active_sessions,
+                "bet_limits": {
+                    "default_max_bet": house.bet_limits.default_max_bet,
+                    "max_bets": house
+                        .bet_limits
+                        .max_bets
+                        .iter()
+                        .map(|(game_type, max_bet)| (*game_type as u8, *max_bet))
+                        .collect::<Vec<_>>(),
+                    "max_exposure": house.bet_limits.max_exposure
+                },
+                "kyc": {
+                    "admin": house.kyc.admin.as_ref().map(|admin| hex(&admin.encode())),
+                    "deposit_limit": house.kyc.deposit_limit
+                },
+                "tournament_payouts": house.tournament_payouts.shares_bps,
+                "seasons": {
+                    "length": house.seasons.length,
+                    "reward": house.seasons.reward,
+                    "payouts": house.seasons.payouts.shares_bps
+                },
+                "vip": {
+                    "points_bps": house.vip.points_bps,
+                    "tiers": house
+                        .vip
+                        .tiers
+                        .iter()
+                        .map(|tier| {
+                            serde_json::json!({
+                                "points": tier.points,
+                                "rakeback_bps": tier.rakeback_bps,
+                                "shields": tier.shields
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                },
+                "insurance": {
+                    "balance": house.insurance.balance.0,
+                    "funding_bps": house.insurance.funding_bps,
+                    "threshold": house.insurance.threshold
+                },
+                "rewards": {
+                    "base_apr_bps": house.rewards.base_apr_bps,
+                    "decay_bps": house.rewards.decay_bps,
+                    "floor_apr_bps": house.rewards.floor_apr_bps
+                },
+                "reward_index": house.reward_index.to_string(),
+                "bonus": {
+                    "wagering_multiplier": house.bonus.wagering_multiplier,
+                    "default_contribution_bps": house.bonus.default_contribution_bps,
+                    "contributions": house
+                        .bonus
+                        .contributions
+                        .iter()
+                        .map(|(game_type, bps)| (*game_type as u8, *bps))
+                        .collect::<Vec<_>>()
+                },
+                "governance": house.governance.as_ref().map(|governance| hex(&governance.encode())),
+                "admin_rotations": house.admin_rotations
             })
         }
         Value::Staker(staker) => {
             serde_json::json!({
                 "type": "Staker",
-                "balance": staker.balance,
+                "balance": staker.balance.0,
                 "unlock_ts": staker.unlock_ts,
                 "last_claim_epoch": staker.last_claim_epoch,
-                "voting_power": staker.voting_power.to_string()
+                "voting_power": staker.voting_power.to_string(),
+                "slash_count": staker.slash_count,
+                "tranches": staker.tranches.iter().map(|tranche| serde_json::json!({
+                    "amount": tranche.amount.0,
+                    "unlock_ts": tranche.unlock_ts,
+                    "voting_power": tranche.voting_power.to_string()
+                })).collect::<Vec<_>>(),
+                "reward_index": staker.reward_index.to_string(),
+                "pending_rewards": staker.pending_rewards.0
             })
         }
         // Virtual Liquidity values
         Value::Vault(vault) => {
             serde_json::json!({
                 "type": "Vault",
-                "collateral_rng": vault.collateral_rng,
-                "debt_vusdt": vault.debt_vusdt
+                "collateral_rng": vault.collateral_rng.0,
+                "debt_vusdt": vault.debt_vusdt.0,
+                "debt_index": vault.debt_index.to_string()
             })
         }
         Value::AmmPool(pool) => {
             serde_json::json!({
                 "type": "AmmPool",
-                "reserve_rng": pool.reserve_rng,
-                "reserve_vusdt": pool.reserve_vusdt,
-                "total_shares": pool.total_shares,
+                "reserve_rng": pool.reserve_rng.0,
+                "reserve_vusdt": pool.reserve_vusdt.0,
+                "total_shares": pool.total_shares.0,
                 "fee_basis_points": pool.fee_basis_points,
-                "sell_tax_basis_points": pool.sell_tax_basis_points
+                "sell_tax_basis_points": pool.sell_tax_basis_points,
+                "price_rng_cumulative": pool.price_rng_cumulative.to_string(),
+                "price_vusdt_cumulative": pool.price_vusdt_cumulative.to_string(),
+                "last_update_view": pool.last_update_view,
+                "max_price_impact_bps": pool.max_price_impact_bps
             })
         }
         Value::LpBalance(bal) => {
             serde_json::json!({
                 "type": "LpBalance",
-                "balance": bal
+                "balance": bal.0
+            })
+        }
+        Value::Allowance(amount) => {
+            serde_json::json!({
+                "type": "Allowance",
+                "amount": amount
+            })
+        }
+        Value::Vesting(vesting) => {
+            let schedules: Vec<_> = vesting
+                .schedules
+                .iter()
+                .map(|schedule| {
+                    serde_json::json!({
+                        "from": hex(&schedule.from.encode()),
+                        "amount": schedule.amount,
+                        "claimed": schedule.claimed,
+                        "start_view": schedule.start_view,
+                        "duration": schedule.duration
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "type": "Vesting",
+                "schedules": schedules
+            })
+        }
+        Value::Treasury(treasury) => {
+            serde_json::json!({
+                "type": "Treasury",
+                "admin": treasury.admin.as_ref().map(|admin| hex(&admin.encode())),
+                "profit_share_bps": treasury.profit_share_bps,
+                "vusdt_balance": treasury.vusdt_balance.0,
+                "total_bought_back": treasury.total_bought_back.0
+            })
+        }
+        Value::GameConfig(config) => {
+            serde_json::json!({
+                "type": "GameConfig",
+                "paytable": config.paytable,
+                "commission_on_win": config.commission_on_win,
+                "dealer_hits_soft_17": config.dealer_hits_soft_17,
+                "max_split_hands": config.max_split_hands,
+                "record_rng": config.record_rng,
+                "shoe_decks": config.shoe_decks,
+                "shoe_penetration": config.shoe_penetration,
+                "dealer_peeks": config.dealer_peeks,
+                "even_money": config.even_money
+            })
+        }
+        Value::GameStats(stats) => {
+            serde_json::json!({
+                "type": "GameStats",
+                "hands_played": stats.hands_played,
+                "total_wagered": stats.total_wagered,
+                "total_paid_out": stats.total_paid_out,
+                "biggest_win": stats.biggest_win,
+                "rtp_bps": stats.rtp_bps()
+            })
+        }
+        Value::AmmPools(pools) => {
+            serde_json::json!({
+                "type": "AmmPools",
+                "pairs": pools.pairs.iter().map(|pair| pair.0).collect::<Vec<_>>()
+            })
+        }
+        Value::Oracle(oracle) => {
+            serde_json::json!({
+                "type": "Oracle",
+                "spot_price": oracle.spot_price.to_string(),
+                "cumulative": oracle.cumulative.to_string(),
+                "last_view": oracle.last_view,
+                "price_scale": nullspace_types::casino::PRICE_SCALE.to_string()
+            })
+        }
+        Value::LimitOrder(order) => {
+            serde_json::json!({
+                "type": "LimitOrder",
+                "owner": hex(&order.owner.encode()),
+                "amount_in": order.amount_in,
+                "min_price": order.min_price,
+                "is_buying_rng": order.is_buying_rng
+            })
+        }
+        Value::LimitOrderBook(book) => {
+            serde_json::json!({
+                "type": "LimitOrderBook",
+                "next_id": book.next_id,
+                "open": book.open
+            })
+        }
+        Value::GameHistory(history) => {
+            let records: Vec<_> = history
+                .records
+                .iter()
+                .map(|record| {
+                    serde_json::json!({
+                        "session_id": record.session_id,
+                        "game_type": record.game_type as u8,
+                        "bet": record.bet,
+                        "payout": record.payout,
+                        "view": record.view
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "type": "GameHistory",
+                "records": records
+            })
+        }
+        Value::TournamentSchedules(schedules) => {
+            serde_json::json!({
+                "type": "TournamentSchedules",
+                "ids": schedules.ids
+            })
+        }
+        Value::Guardians(set) => {
+            let guardians: Vec<String> = set
+                .guardians
+                .iter()
+                .map(|guardian| hex(&guardian.encode()))
+                .collect();
+            let recovery = set.recovery.as_ref().map(|recovery| {
+                serde_json::json!({
+                    "new_key": hex(&recovery.new_key.encode()),
+                    "approvals": recovery
+                        .approvals
+                        .iter()
+                        .map(|guardian| hex(&guardian.encode()))
+                        .collect::<Vec<_>>(),
+                    "ready_view": recovery.ready_view
+                })
+            });
+            serde_json::json!({
+                "type": "Guardians",
+                "guardians": guardians,
+                "threshold": set.threshold,
+                "recovery": recovery
+            })
+        }
+        Value::KeyIndex(keys) => {
+            serde_json::json!({
+                "type": "KeyIndex",
+                "keys": keys.iter().map(|key| hex(&key.encode())).collect::<Vec<_>>()
+            })
+        }
+        Value::EconomyParams(params) => {
+            let mut json = economy_params_json(params);
+            json["type"] = "EconomyParams".into();
+            json
+        }
+        Value::SessionExpiries(session_ids) => {
+            serde_json::json!({
+                "type": "SessionExpiries",
+                "session_ids": session_ids
+            })
+        }
+        Value::SessionReclaimCursor(view) => {
+            serde_json::json!({
+                "type": "SessionReclaimCursor",
+                "view": view
             })
         }
     };
@@ -868,88 +2048,390 @@ fn decode_event(event: &Event) -> Result<serde_json::Value, JsValue> {
         // Casino events
         Event::CasinoPlayerRegistered { player, name } => {
             serde_json::json!({
-                "type": "CasinoPlayerRegistered",
+                "type": "CasinoPlayerRegistered",
+                "player": hex(&player.encode()),
+                "name": name
+            })
+        }
+        Event::CasinoGameStarted {
+            session_id,
+            player,
+            game_type,
+            bet,
+            initial_state,
+        } => {
+            serde_json::json!({
+                "type": "CasinoGameStarted",
+                "session_id": session_id,
+                "player": hex(&player.encode()),
+                "game_type": format!("{:?}", game_type),
+                "bet": bet,
+                "initial_state": hex(initial_state)
+            })
+        }
+        Event::CasinoGameMoved {
+            session_id,
+            move_number,
+            new_state,
+        } => {
+            serde_json::json!({
+                "type": "CasinoGameMoved",
+                "session_id": session_id,
+                "move_number": move_number,
+                "new_state": hex(new_state)
+            })
+        }
+        Event::CasinoGameCompleted {
+            session_id,
+            player,
+            game_type,
+            payout,
+            final_chips,
+            was_shielded,
+            was_doubled,
+        } => {
+            serde_json::json!({
+                "type": "CasinoGameCompleted",
+                "session_id": session_id,
+                "player": hex(&player.encode()),
+                "game_type": format!("{:?}", game_type),
+                "payout": payout,
+                "final_chips": final_chips,
+                "was_shielded": was_shielded,
+                "was_doubled": was_doubled
+            })
+        }
+        Event::CasinoSessionExpired {
+            session_id,
+            player,
+            game_type,
+            refund,
+            final_chips,
+        } => {
+            serde_json::json!({
+                "type": "CasinoSessionExpired",
+                "session_id": session_id,
+                "player": hex(&player.encode()),
+                "game_type": format!("{:?}", game_type),
+                "refund": refund,
+                "final_chips": final_chips
+            })
+        }
+        Event::CasinoLeaderboardUpdated { leaderboard } => {
+            let entries: Vec<_> = leaderboard
+                .entries
+                .iter()
+                .map(|e| {
+                    serde_json::json!({
+                        "player": hex(&e.player.encode()),
+                        "name": e.name,
+                        "chips": e.chips.0
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "type": "CasinoLeaderboardUpdated",
+                "entries": entries
+            })
+        }
+        Event::CasinoError {
+            player,
+            session_id,
+            error_code,
+            message,
+        } => {
+            serde_json::json!({
+                "type": "CasinoError",
+                "player": hex(&player.encode()),
+                "session_id": session_id,
+                "error_code": u8::from(*error_code),
+                "error": format!("{error_code:?}"),
+                "message": message
+            })
+        }
+        Event::InstructionFailed { player, kind, code } => {
+            serde_json::json!({
+                "type": "InstructionFailed",
+                "player": hex(&player.encode()),
+                "kind": kind,
+                "code": u8::from(*code),
+                "error": format!("{code:?}")
+            })
+        }
+        // Account recovery events
+        Event::GuardiansSet {
+            player,
+            guardians,
+            threshold,
+        } => {
+            let guardians: Vec<String> = guardians
+                .iter()
+                .map(|guardian| hex(&guardian.encode()))
+                .collect();
+            serde_json::json!({
+                "type": "GuardiansSet",
+                "player": hex(&player.encode()),
+                "guardians": guardians,
+                "threshold": threshold
+            })
+        }
+        Event::RecoveryApproved {
+            player,
+            guardian,
+            new_key,
+            approvals,
+            ready_view,
+        } => {
+            serde_json::json!({
+                "type": "RecoveryApproved",
+                "player": hex(&player.encode()),
+                "guardian": hex(&guardian.encode()),
+                "new_key": hex(&new_key.encode()),
+                "approvals": approvals,
+                "ready_view": ready_view
+            })
+        }
+        Event::AccountRecovered { old_key, new_key } => {
+            serde_json::json!({
+                "type": "AccountRecovered",
+                "old_key": hex(&old_key.encode()),
+                "new_key": hex(&new_key.encode())
+            })
+        }
+        // vUSDT allowance events
+        Event::AllowanceSet {
+            owner,
+            spender,
+            amount,
+        } => {
+            serde_json::json!({
+                "type": "AllowanceSet",
+                "owner": hex(&owner.encode()),
+                "spender": hex(&spender.encode()),
+                "amount": amount
+            })
+        }
+        Event::VusdtTransferred {
+            owner,
+            to,
+            spender,
+            amount,
+            allowance,
+        } => {
+            serde_json::json!({
+                "type": "VusdtTransferred",
+                "owner": hex(&owner.encode()),
+                "to": hex(&to.encode()),
+                "spender": hex(&spender.encode()),
+                "amount": amount,
+                "allowance": allowance
+            })
+        }
+        // Vesting events
+        Event::VestingCreated {
+            from,
+            to,
+            amount,
+            start_view,
+            duration,
+        } => {
+            serde_json::json!({
+                "type": "VestingCreated",
+                "from": hex(&from.encode()),
+                "to": hex(&to.encode()),
+                "amount": amount,
+                "start_view": start_view,
+                "duration": duration
+            })
+        }
+        Event::VestedClaimed {
+            player,
+            amount,
+            new_chips,
+        } => {
+            serde_json::json!({
+                "type": "VestedClaimed",
+                "player": hex(&player.encode()),
+                "amount": amount,
+                "new_chips": new_chips
+            })
+        }
+        Event::StakingRewardsIssued {
+            epoch,
+            amount,
+            apr_bps,
+        } => {
+            serde_json::json!({
+                "type": "StakingRewardsIssued",
+                "epoch": epoch,
+                "amount": amount,
+                "apr_bps": apr_bps
+            })
+        }
+        // Treasury events
+        Event::TreasuryFunded {
+            epoch,
+            amount,
+            balance,
+        } => {
+            serde_json::json!({
+                "type": "TreasuryFunded",
+                "epoch": epoch,
+                "amount": amount,
+                "balance": balance
+            })
+        }
+        Event::BuybackExecuted {
+            amount,
+            burned,
+            balance,
+            reserve_rng,
+            reserve_vusdt,
+        } => {
+            serde_json::json!({
+                "type": "BuybackExecuted",
+                "amount": amount,
+                "burned": burned,
+                "balance": balance,
+                "reserve_rng": reserve_rng,
+                "reserve_vusdt": reserve_vusdt
+            })
+        }
+        // Game rule events
+        Event::GameConfigUpdated { game_type, config } => {
+            serde_json::json!({
+                "type": "GameConfigUpdated",
+                "game_type": *game_type as u8,
+                "paytable": config.paytable,
+                "commission_on_win": config.commission_on_win,
+                "dealer_hits_soft_17": config.dealer_hits_soft_17,
+                "max_split_hands": config.max_split_hands,
+                "record_rng": config.record_rng,
+                "shoe_decks": config.shoe_decks,
+                "shoe_penetration": config.shoe_penetration,
+                "dealer_peeks": config.dealer_peeks,
+                "even_money": config.even_money
+            })
+        }
+        Event::GameStatsUpdated { game_type, stats } => {
+            serde_json::json!({
+                "type": "GameStatsUpdated",
+                "game_type": *game_type as u8,
+                "hands_played": stats.hands_played,
+                "total_wagered": stats.total_wagered,
+                "total_paid_out": stats.total_paid_out,
+                "biggest_win": stats.biggest_win,
+                "rtp_bps": stats.rtp_bps()
+            })
+        }
+        Event::BonusGranted {
+            player,
+            amount,
+            bonus_chips,
+            wagering_remaining,
+        } => {
+            serde_json::json!({
+                "type": "BonusGranted",
                 "player": hex(&player.encode()),
-                "name": name
+                "amount": amount,
+                "bonus_chips": bonus_chips,
+                "wagering_remaining": wagering_remaining
             })
         }
-        Event::CasinoGameStarted {
-            session_id,
+        Event::BonusReleased {
             player,
-            game_type,
-            bet,
-            initial_state,
+            amount,
+            chips,
         } => {
             serde_json::json!({
-                "type": "CasinoGameStarted",
-                "session_id": session_id,
+                "type": "BonusReleased",
                 "player": hex(&player.encode()),
-                "game_type": format!("{:?}", game_type),
-                "bet": bet,
-                "initial_state": hex(initial_state)
+                "amount": amount,
+                "chips": chips
             })
         }
-        Event::CasinoGameMoved {
+        Event::CasinoRngTranscript {
             session_id,
             move_number,
-            new_state,
+            draws,
         } => {
+            let draws: Vec<_> = draws
+                .iter()
+                .map(|draw| match draw {
+                    RngDraw::Card(card) => serde_json::json!({ "kind": "card", "value": card }),
+                    RngDraw::Die(die) => serde_json::json!({ "kind": "die", "value": die }),
+                    RngDraw::Wheel(pocket) => {
+                        serde_json::json!({ "kind": "wheel", "value": pocket })
+                    }
+                    RngDraw::Value(value) => {
+                        serde_json::json!({ "kind": "value", "value": value })
+                    }
+                })
+                .collect();
             serde_json::json!({
-                "type": "CasinoGameMoved",
+                "type": "CasinoRngTranscript",
                 "session_id": session_id,
                 "move_number": move_number,
-                "new_state": hex(new_state)
+                "draws": draws
             })
         }
-        Event::CasinoGameCompleted {
+        Event::CasinoSideBetSettled {
             session_id,
-            player,
-            game_type,
+            bet_id,
+            amount,
             payout,
-            final_chips,
-            was_shielded,
-            was_doubled,
         } => {
             serde_json::json!({
-                "type": "CasinoGameCompleted",
+                "type": "CasinoSideBetSettled",
                 "session_id": session_id,
-                "player": hex(&player.encode()),
-                "game_type": format!("{:?}", game_type),
-                "payout": payout,
-                "final_chips": final_chips,
-                "was_shielded": was_shielded,
-                "was_doubled": was_doubled
+                "bet_id": bet_id,
+                "amount": amount,
+                "payout": payout
             })
         }
-        Event::CasinoLeaderboardUpdated { leaderboard } => {
-            let entries: Vec<_> = leaderboard
-                .entries
-                .iter()
-                .map(|e| {
-                    serde_json::json!({
-                        "player": hex(&e.player.encode()),
-                        "name": e.name,
-                        "chips": e.chips
-                    })
-                })
-                .collect();
+        Event::CasinoCardDealt {
+            session_id,
+            move_number,
+            card,
+            to,
+        } => {
+            let to = match to {
+                Seat::Player(hand) => serde_json::json!({ "seat": "player", "hand": hand }),
+                Seat::Dealer => serde_json::json!({ "seat": "dealer" }),
+            };
             serde_json::json!({
-                "type": "CasinoLeaderboardUpdated",
-                "entries": entries
+                "type": "CasinoCardDealt",
+                "session_id": session_id,
+                "move_number": move_number,
+                "card": card,
+                "to": to
             })
         }
-        Event::CasinoError {
-            player,
+        Event::CasinoCrapsRolled {
             session_id,
-            error_code,
-            message,
+            move_number,
+            d1,
+            d2,
+            resolved_bets,
         } => {
+            let resolved_bets: Vec<_> = resolved_bets
+                .iter()
+                .map(|bet| {
+                    serde_json::json!({
+                        "bet_type": bet.bet_type,
+                        "target": bet.target,
+                        "amount": bet.amount,
+                        "payout": bet.payout
+                    })
+                })
+                .collect();
             serde_json::json!({
-                "type": "CasinoError",
-                "player": hex(&player.encode()),
+                "type": "CasinoCrapsRolled",
                 "session_id": session_id,
-                "error_code": error_code,
-                "message": message
+                "move_number": move_number,
+                "d1": d1,
+                "d2": d2,
+                "resolved_bets": resolved_bets
             })
         }
         // Tournament events
@@ -982,6 +2464,130 @@ fn decode_event(event: &Event) -> Result<serde_json::Value, JsValue> {
                 "phase": phase_str
             })
         }
+        Event::TournamentScheduled {
+            id,
+            start_view,
+            end_view,
+        } => {
+            serde_json::json!({
+                "type": "TournamentScheduled",
+                "id": id,
+                "start_view": start_view,
+                "end_view": end_view
+            })
+        }
+        Event::TournamentRulesSet {
+            tournament_id,
+            rules,
+        } => {
+            let mut json = tournament_rules_json(rules);
+            json["type"] = "TournamentRulesSet".into();
+            json["tournament_id"] = (*tournament_id).into();
+            json
+        }
+        Event::EconomyParamsUpdated { params } => {
+            let mut json = economy_params_json(params);
+            json["type"] = "EconomyParamsUpdated".into();
+            json
+        }
+        Event::AdminSet { role, admin } => {
+            serde_json::json!({
+                "type": "AdminSet",
+                "role": *role as u8,
+                "admin": admin.as_ref().map(|admin| hex(&admin.encode()))
+            })
+        }
+        Event::HouseSettingsUpdated { settings } => {
+            serde_json::json!({
+                "type": "HouseSettingsUpdated",
+                "fee_schedule": {
+                    "flat": settings.fee_schedule.flat,
+                    "overrides": settings.fee_schedule.overrides
+                },
+                "daily_bonus": {
+                    "amount": settings.daily_bonus.amount,
+                    "cooldown": settings.daily_bonus.cooldown,
+                    "streak_bps": settings.daily_bonus.streak_bps,
+                    "max_streak": settings.daily_bonus.max_streak,
+                    "faucet_enabled": settings.daily_bonus.faucet_enabled
+                },
+                "bet_limits": {
+                    "default_max_bet": settings.bet_limits.default_max_bet,
+                    "max_bets": settings
+                        .bet_limits
+                        .max_bets
+                        .iter()
+                        .map(|(game_type, max_bet)| (*game_type as u8, *max_bet))
+                        .collect::<Vec<_>>(),
+                    "max_exposure": settings.bet_limits.max_exposure
+                },
+                "kyc_deposit_limit": settings.kyc_deposit_limit
+            })
+        }
+        Event::TournamentPrizePaid {
+            tournament_id,
+            player,
+            rank,
+            amount,
+        } => {
+            serde_json::json!({
+                "type": "TournamentPrizePaid",
+                "tournament_id": tournament_id,
+                "player": hex(&player.encode()),
+                "rank": rank,
+                "amount": amount
+            })
+        }
+        Event::LeaderboardSeasonEnded {
+            season,
+            next_season_end,
+        } => {
+            serde_json::json!({
+                "type": "LeaderboardSeasonEnded",
+                "season": season,
+                "next_season_end": next_season_end
+            })
+        }
+        Event::SeasonRewardPaid {
+            season,
+            player,
+            rank,
+            amount,
+        } => {
+            serde_json::json!({
+                "type": "SeasonRewardPaid",
+                "season": season,
+                "player": hex(&player.encode()),
+                "rank": rank,
+                "amount": amount
+            })
+        }
+        Event::LoyaltyEarned {
+            player,
+            points,
+            total_points,
+            rakeback,
+        } => {
+            serde_json::json!({
+                "type": "LoyaltyEarned",
+                "player": hex(&player.encode()),
+                "points": points,
+                "total_points": total_points,
+                "rakeback": rakeback
+            })
+        }
+        Event::TierChanged {
+            player,
+            tier,
+            shields,
+        } => {
+            serde_json::json!({
+                "type": "TierChanged",
+                "player": hex(&player.encode()),
+                "tier": tier,
+                "shields": shields
+            })
+        }
         Event::TournamentEnded { id, rankings } => {
             let rankings_json: Vec<_> = rankings
                 .iter()
@@ -1018,6 +2624,18 @@ fn decode_event(event: &Event) -> Result<serde_json::Value, JsValue> {
                 "new_collateral": new_collateral
             })
         }
+        Event::CollateralWithdrawn {
+            player,
+            amount,
+            new_collateral,
+        } => {
+            serde_json::json!({
+                "type": "CollateralWithdrawn",
+                "player": hex(&player.encode()),
+                "amount": amount,
+                "new_collateral": new_collateral
+            })
+        }
         Event::VusdtBorrowed {
             player,
             amount,
@@ -1109,6 +2727,96 @@ fn decode_event(event: &Event) -> Result<serde_json::Value, JsValue> {
             })
         }
 
+        // Limit order events
+        Event::LimitOrderPlaced {
+            order_id,
+            player,
+            amount_in,
+            min_price,
+            is_buying_rng,
+        } => {
+            serde_json::json!({
+                "type": "LimitOrderPlaced",
+                "order_id": order_id,
+                "player": hex(&player.encode()),
+                "amount_in": amount_in,
+                "min_price": min_price,
+                "is_buying_rng": is_buying_rng
+            })
+        }
+        Event::LimitOrderFilled {
+            order_id,
+            player,
+            is_buying_rng,
+            amount_in,
+            amount_out,
+            fee_amount,
+            burned_amount,
+            reserve_rng,
+            reserve_vusdt,
+        } => {
+            serde_json::json!({
+                "type": "LimitOrderFilled",
+                "order_id": order_id,
+                "player": hex(&player.encode()),
+                "is_buying_rng": is_buying_rng,
+                "amount_in": amount_in,
+                "amount_out": amount_out,
+                "fee_amount": fee_amount,
+                "burned_amount": burned_amount,
+                "reserve_rng": reserve_rng,
+                "reserve_vusdt": reserve_vusdt
+            })
+        }
+        Event::LimitOrderCancelled {
+            order_id,
+            player,
+            refunded,
+        } => {
+            serde_json::json!({
+                "type": "LimitOrderCancelled",
+                "order_id": order_id,
+                "player": hex(&player.encode()),
+                "refunded": refunded
+            })
+        }
+
+        // Single-sided liquidity events
+        Event::LiquidityZapped {
+            player,
+            from_rng,
+            amount_in,
+            swap_amount_in,
+            swap_amount_out,
+            fee_amount,
+            burned_amount,
+            rng_amount,
+            vusdt_amount,
+            shares_minted,
+            total_shares,
+            reserve_rng,
+            reserve_vusdt,
+            lp_balance,
+        } => {
+            serde_json::json!({
+                "type": "LiquidityZapped",
+                "player": hex(&player.encode()),
+                "from_rng": from_rng,
+                "amount_in": amount_in,
+                "swap_amount_in": swap_amount_in,
+                "swap_amount_out": swap_amount_out,
+                "fee_amount": fee_amount,
+                "burned_amount": burned_amount,
+                "rng_amount": rng_amount,
+                "vusdt_amount": vusdt_amount,
+                "shares_minted": shares_minted,
+                "total_shares": total_shares,
+                "reserve_rng": reserve_rng,
+                "reserve_vusdt": reserve_vusdt,
+                "lp_balance": lp_balance
+            })
+        }
+
         // Staking events
         Event::Staked {
             player,
@@ -1135,12 +2843,84 @@ fn decode_event(event: &Event) -> Result<serde_json::Value, JsValue> {
                 "amount": amount
             })
         }
+        Event::Slashed {
+            player,
+            bps,
+            amount,
+            new_balance,
+            voting_power,
+        } => {
+            serde_json::json!({
+                "type": "Slashed",
+                "player": hex(&player.encode()),
+                "bps": bps,
+                "amount": amount,
+                "new_balance": new_balance,
+                "voting_power": voting_power.to_string()
+            })
+        }
+        Event::DailyBonusClaimed {
+            player,
+            amount,
+            streak,
+            new_chips,
+            next_claim,
+        } => {
+            serde_json::json!({
+                "type": "DailyBonusClaimed",
+                "player": hex(&player.encode()),
+                "amount": amount,
+                "streak": streak,
+                "new_chips": new_chips,
+                "next_claim": next_claim
+            })
+        }
+        Event::ProfileUpdated {
+            player,
+            display_name,
+            avatar_id,
+            title,
+        } => {
+            serde_json::json!({
+                "type": "ProfileUpdated",
+                "player": hex(&player.encode()),
+                "display_name": display_name,
+                "avatar_id": avatar_id,
+                "title": title
+            })
+        }
+        Event::KycStatusChanged { player, verified } => {
+            serde_json::json!({
+                "type": "KycStatusChanged",
+                "player": hex(&player.encode()),
+                "verified": verified
+            })
+        }
         Event::EpochProcessed { epoch } => {
             serde_json::json!({
                 "type": "EpochProcessed",
                 "epoch": epoch
             })
         }
+        Event::InsuranceFunded { amount, balance } => {
+            serde_json::json!({
+                "type": "InsuranceFunded",
+                "amount": amount,
+                "balance": balance
+            })
+        }
+        Event::InsuranceDrawn {
+            amount,
+            balance,
+            net_pnl,
+        } => {
+            serde_json::json!({
+                "type": "InsuranceDrawn",
+                "amount": amount,
+                "balance": balance,
+                "net_pnl": net_pnl.to_string()
+            })
+        }
         Event::RewardsClaimed { player, amount } => {
             serde_json::json!({
                 "type": "RewardsClaimed",
@@ -1148,6 +2928,46 @@ fn decode_event(event: &Event) -> Result<serde_json::Value, JsValue> {
                 "amount": amount
             })
         }
+
+        // Fee events
+        Event::FeeCharged {
+            player,
+            amount,
+            new_chips,
+            accumulated_fees,
+        } => {
+            serde_json::json!({
+                "type": "FeeCharged",
+                "player": hex(&player.encode()),
+                "amount": amount,
+                "new_chips": new_chips,
+                "accumulated_fees": accumulated_fees
+            })
+        }
+        Event::InterestAccrued {
+            player,
+            amount,
+            new_debt,
+            effective_rate_bps,
+            rate_bps,
+        } => {
+            serde_json::json!({
+                "type": "InterestAccrued",
+                "player": hex(&player.encode()),
+                "amount": amount,
+                "new_debt": new_debt,
+                "effective_rate_bps": effective_rate_bps,
+                "rate_bps": rate_bps
+            })
+        }
+        Event::Unknown { version, tag, data } => {
+            serde_json::json!({
+                "type": "Unknown",
+                "version": version,
+                "tag": tag,
+                "data": hex(data)
+            })
+        }
     };
     Ok(json)
 }
@@ -1231,7 +3051,7 @@ fn process_output(output: &Output) -> Result<serde_json::Value, JsValue> {
                 "instruction": instruction
             }))
         }
-        Output::Event(event) => decode_event(event),
+        Output::Event(event) | Output::VersionedEvent { event, .. } => decode_event(event),
         _ => Ok(serde_json::Value::Null),
     }
 }